@@ -150,6 +150,7 @@ impl pallet_message_queue::Config for Test {
 	type MaxStale = MaxStale;
 	type ServiceWeight = ServiceWeight;
 	type IdleMaxServiceWeight = ();
+	type QueueServicePolicy = ();
 	type QueuePausedQuery = ();
 }
 
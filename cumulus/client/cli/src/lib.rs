@@ -316,6 +316,14 @@ pub struct RunCmd {
 	/// It will be removed once <https://github.com/paritytech/polkadot-sdk/issues/6020> is fixed.
 	#[arg(long)]
 	pub experimental_max_pov_percentage: Option<u32>,
+
+	/// EXPERIMENTAL: Have every collation prechecked by a validator of the backing group before
+	/// advertising it.
+	///
+	/// Saves the backing group the fetch of collations it would reject anyway, at the cost of a
+	/// roundtrip before each advertisement.
+	#[arg(long)]
+	pub experimental_precheck_collations: bool,
 }
 
 impl RunCmd {
@@ -338,7 +346,10 @@ impl RunCmd {
 				_ => RelayChainMode::Embedded,
 			};
 
-		CollatorOptions { relay_chain_mode }
+		CollatorOptions {
+			relay_chain_mode,
+			precheck_collations: self.experimental_precheck_collations,
+		}
 	}
 }
 
@@ -358,6 +369,9 @@ pub enum RelayChainMode {
 pub struct CollatorOptions {
 	/// How this collator retrieves relay chain information
 	pub relay_chain_mode: RelayChainMode,
+	/// Whether collations are prechecked by a validator of the backing group before they are
+	/// advertised.
+	pub precheck_collations: bool,
 }
 
 /// A non-redundant version of the `RunCmd` that sets the `validator` field when the
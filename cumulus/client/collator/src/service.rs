@@ -29,7 +29,7 @@ use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT, Zero};
 
 use cumulus_client_consensus_common::ParachainCandidate;
 use polkadot_node_primitives::{
-	BlockData, Collation, CollationSecondedSignal, MaybeCompressedPoV, PoV,
+	BlockData, Collation, CollationSecondedSignal, MaybeCompressedPoV, PoV, PoVCompressionConfig,
 };
 
 use codec::Encode;
@@ -83,6 +83,7 @@ pub struct CollatorService<Block: BlockT, BS, RA> {
 	wait_to_announce: Arc<Mutex<WaitToAnnounce<Block>>>,
 	announce_block: Arc<dyn Fn(Block::Hash, Option<Vec<u8>>) + Send + Sync>,
 	runtime_api: Arc<RA>,
+	pov_compression: PoVCompressionConfig,
 }
 
 impl<Block: BlockT, BS, RA> Clone for CollatorService<Block, BS, RA> {
@@ -92,6 +93,7 @@ impl<Block: BlockT, BS, RA> Clone for CollatorService<Block, BS, RA> {
 			wait_to_announce: self.wait_to_announce.clone(),
 			announce_block: self.announce_block.clone(),
 			runtime_api: self.runtime_api.clone(),
+			pov_compression: self.pov_compression,
 		}
 	}
 }
@@ -113,7 +115,19 @@ where
 		let wait_to_announce =
 			Arc::new(Mutex::new(WaitToAnnounce::new(spawner, announce_block.clone())));
 
-		Self { block_status, wait_to_announce, announce_block, runtime_api }
+		Self {
+			block_status,
+			wait_to_announce,
+			announce_block,
+			runtime_api,
+			pov_compression: Default::default(),
+		}
+	}
+
+	/// Compress the PoVs of built collations as configured, instead of the default zstd level.
+	pub fn with_pov_compression(mut self, pov_compression: PoVCompressionConfig) -> Self {
+		self.pov_compression = pov_compression;
+		self
 	}
 
 	/// Checks the status of the given block hash in the Parachain.
@@ -249,7 +263,7 @@ where
 
 		let block_data = ParachainBlockData::<Block>::new(vec![block], compact_proof);
 
-		let pov = polkadot_node_primitives::maybe_compress_pov(PoV {
+		let pov = self.pov_compression.compress(PoV {
 			block_data: BlockData(if api_version >= 3 {
 				block_data.encode()
 			} else {
@@ -354,6 +354,7 @@ fn build_polkadot_full_node(
 	parachain_config: &Configuration,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	precheck_collations: bool,
 ) -> Result<(NewFull, Option<CollatorPair>), polkadot_service::Error> {
 	let (is_parachain_node, maybe_collator_key) = if parachain_config.role.is_authority() {
 		let collator_key = CollatorPair::generate().0;
@@ -384,8 +385,21 @@ fn build_polkadot_full_node(
 			execute_workers_max_num: None,
 			prepare_workers_hard_max_num: None,
 			prepare_workers_soft_max_num: None,
+			pvf_cross_check_percent: None,
+			pvf_local_precheck_percent: None,
 			enable_approval_voting_parallel: false,
 			keep_finalized_for: None,
+			dispute_window: None,
+			session_history_depth: None,
+			network_profile: polkadot_service::NetworkProfile::Datacenter,
+			collation_push_collators: Default::default(),
+			precheck_collations,
+			evict_inactive_validation_peers: false,
+			reserved_authority_slots: 0,
+			grandpa_vote_history: 0,
+			bitfield_signing_config: Default::default(),
+			chunk_serving_budget: Default::default(),
+			babe_secondary_slot_claims: true,
 		},
 	)?;
 
@@ -399,6 +413,7 @@ pub fn build_inprocess_relay_chain(
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
 	task_manager: &mut TaskManager,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	precheck_collations: bool,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
 	// This is essentially a hack, but we want to ensure that we send the correct node version
 	// to the telemetry.
@@ -410,6 +425,7 @@ pub fn build_inprocess_relay_chain(
 		parachain_config,
 		telemetry_worker_handle,
 		hwbench,
+		precheck_collations,
 	)
 	.map_err(|e| RelayChainError::Application(Box::new(e) as Box<_>))?;
 
@@ -67,6 +67,11 @@ pub enum RelayChainError {
 	JsonRpcError(#[from] JsonRpcError),
 	#[error("Unable to communicate with RPC worker: {0}")]
 	WorkerCommunicationError(String),
+	#[error(
+		"RPC endpoint saturated, rejected call to '{0}': {1} requests already in flight. \
+		The endpoint is too slow for the load of the collator, consider using a faster one."
+	)]
+	RpcOverloaded(String, usize),
 	#[error("Scale codec deserialization error: {0}")]
 	DeserializationError(CodecError),
 	#[error(transparent)]
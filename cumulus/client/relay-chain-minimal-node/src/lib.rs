@@ -95,6 +95,7 @@ async fn build_interface(
 	polkadot_config: Configuration,
 	task_manager: &mut TaskManager,
 	client: RelayChainRpcClient,
+	precheck_collations: bool,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
 	let collator_pair = CollatorPair::generate().0;
 	let blockchain_rpc_client = Arc::new(BlockChainRpcClient::new(client.clone()));
@@ -109,6 +110,7 @@ async fn build_interface(
 				polkadot_config,
 				collator_pair.clone(),
 				blockchain_rpc_client,
+				precheck_collations,
 			)
 			.await?,
 		sc_network::config::NetworkBackendType::Litep2p =>
@@ -116,6 +118,7 @@ async fn build_interface(
 				polkadot_config,
 				collator_pair.clone(),
 				blockchain_rpc_client,
+				precheck_collations,
 			)
 			.await?,
 	};
@@ -131,6 +134,7 @@ pub async fn build_minimal_relay_chain_node_with_rpc(
 	parachain_prometheus_registry: Option<&Registry>,
 	task_manager: &mut TaskManager,
 	relay_chain_url: Vec<Url>,
+	precheck_collations: bool,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
 	let client = cumulus_relay_chain_rpc_interface::create_client_and_start_worker(
 		relay_chain_url,
@@ -139,12 +143,13 @@ pub async fn build_minimal_relay_chain_node_with_rpc(
 	)
 	.await?;
 
-	build_interface(relay_chain_config, task_manager, client).await
+	build_interface(relay_chain_config, task_manager, client, precheck_collations).await
 }
 
 pub async fn build_minimal_relay_chain_node_light_client(
 	polkadot_config: Configuration,
 	task_manager: &mut TaskManager,
+	precheck_collations: bool,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
 	tracing::info!(
 		target: LOG_TARGET,
@@ -164,7 +169,7 @@ pub async fn build_minimal_relay_chain_node_light_client(
 	)
 	.await?;
 
-	build_interface(polkadot_config, task_manager, client).await
+	build_interface(polkadot_config, task_manager, client, precheck_collations).await
 }
 
 /// Builds a minimal relay chain node. Chain data is fetched
@@ -183,6 +188,7 @@ async fn new_minimal_relay_chain<Block: BlockT, Network: NetworkBackend<RelayBlo
 	config: Configuration,
 	collator_pair: CollatorPair,
 	relay_chain_rpc_client: Arc<BlockChainRpcClient>,
+	precheck_collations: bool,
 ) -> Result<NewMinimalNode, RelayChainError> {
 	let role = config.role;
 	let mut net_config = sc_network::config::FullNetworkConfiguration::<_, _, Network>::new(
@@ -214,6 +220,7 @@ async fn new_minimal_relay_chain<Block: BlockT, Network: NetworkBackend<RelayBlo
 		&peerset_protocol_names,
 		metrics.clone(),
 		Arc::clone(&peer_store_handle),
+		None,
 	)
 	.into_iter()
 	.map(|(config, (peerset, service))| {
@@ -263,6 +270,7 @@ async fn new_minimal_relay_chain<Block: BlockT, Network: NetworkBackend<RelayBlo
 		req_protocol_names: request_protocol_names,
 		peerset_protocol_names,
 		notification_services,
+		precheck_collations,
 	};
 
 	let overseer_handle =
@@ -297,5 +305,11 @@ fn build_request_response_protocol_receivers<
 	let cfg =
 		Protocol::ChunkFetchingV2.get_outbound_only_config::<_, Network>(request_protocol_names);
 	config.add_request_response_protocol(cfg);
+	let cfg = Protocol::CollationPrecheckV1
+		.get_outbound_only_config::<_, Network>(request_protocol_names);
+	config.add_request_response_protocol(cfg);
+	let cfg =
+		Protocol::CollationPushV1.get_outbound_only_config::<_, Network>(request_protocol_names);
+	config.add_request_response_protocol(cfg);
 	(collation_req_v1_receiver, collation_req_v2_receiver, available_data_req_receiver)
 }
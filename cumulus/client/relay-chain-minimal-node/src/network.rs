@@ -155,6 +155,7 @@ fn get_block_announce_proto_config<Network: NetworkBackend<Block, Hash>>(
 			out_peers: 0,
 			reserved_nodes: Vec::new(),
 			non_reserved_mode: NonReservedPeerMode::Deny,
+			inactivity_eviction: None,
 		},
 		metrics,
 		peer_store_handle,
@@ -43,6 +43,7 @@ pub use url::Url;
 mod light_client_worker;
 mod metrics;
 mod reconnecting_ws_client;
+mod request_scheduler;
 mod rpc_client;
 mod tokio_platform;
 
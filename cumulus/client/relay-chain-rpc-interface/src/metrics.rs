@@ -16,12 +16,14 @@
 // along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
 
 use prometheus::{Error as PrometheusError, HistogramTimer, Registry};
-use prometheus_endpoint::{HistogramOpts, HistogramVec, Opts};
+use prometheus_endpoint::{CounterVec, HistogramOpts, HistogramVec, Opts, U64};
 
 /// Gathers metrics about the blockchain RPC client.
 #[derive(Clone)]
 pub(crate) struct RelaychainRpcMetrics {
 	rpc_request: HistogramVec,
+	coalesced_requests: CounterVec<U64>,
+	shed_requests: CounterVec<U64>,
 }
 
 impl RelaychainRpcMetrics {
@@ -41,10 +43,38 @@ impl RelaychainRpcMetrics {
 				)?,
 				registry,
 			)?,
+			coalesced_requests: prometheus_endpoint::register(
+				CounterVec::new(
+					Opts::new(
+						"relay_chain_rpc_interface_coalesced_requests_total",
+						"Number of requests served by an identical request already in flight",
+					),
+					&["method"],
+				)?,
+				registry,
+			)?,
+			shed_requests: prometheus_endpoint::register(
+				CounterVec::new(
+					Opts::new(
+						"relay_chain_rpc_interface_shed_requests_total",
+						"Number of requests rejected because too many requests were in flight",
+					),
+					&["method"],
+				)?,
+				registry,
+			)?,
 		})
 	}
 
 	pub(crate) fn start_request_timer(&self, method: &str) -> HistogramTimer {
 		self.rpc_request.with_label_values(&[method]).start_timer()
 	}
+
+	pub(crate) fn on_request_coalesced(&self, method: &str) {
+		self.coalesced_requests.with_label_values(&[method]).inc();
+	}
+
+	pub(crate) fn on_request_shed(&self, method: &str) {
+		self.shed_requests.with_label_values(&[method]).inc();
+	}
 }
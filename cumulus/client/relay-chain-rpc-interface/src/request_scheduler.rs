@@ -0,0 +1,294 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! Coalescing and load shedding of the requests sent to the RPC worker.
+//!
+//! All the subsystems of a collator share the same [`RelayChainRpcClient`], and often ask for the
+//! same runtime API calls at the same relay parent. Identical `state_call` requests are coalesced,
+//! so that only one of them reaches the remote endpoint while the others wait for its response.
+//!
+//! When the endpoint is slow, requests pile up. Instead of queuing them without bound until they
+//! time out, the number of requests in flight is limited and additional requests are rejected
+//! right away with [`RelayChainError::RpcOverloaded`].
+//!
+//! A request only holds its permit and its coalescing entry while someone waits for its response,
+//! both are released as soon as the request completes or all the waiters are dropped.
+//!
+//! [`RelayChainRpcClient`]: crate::RelayChainRpcClient
+
+use cumulus_relay_chain_interface::RelayChainError;
+use futures::{
+	future::{BoxFuture, Shared, WeakShared},
+	Future, FutureExt,
+};
+use jsonrpsee::core::{params::ArrayParams, traits::ToRpcParams};
+use serde_json::Value as JsonValue;
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+};
+use tokio::sync::Semaphore;
+
+use crate::metrics::RelaychainRpcMetrics;
+
+const LOG_TARGET: &str = "relay-chain-rpc-client";
+
+/// Default maximum number of requests in flight to the RPC worker.
+pub(crate) const DEFAULT_MAX_REQUESTS_IN_FLIGHT: usize = 256;
+
+type ResponseFuture = BoxFuture<'static, Result<JsonValue, Arc<RelayChainError>>>;
+
+/// Coalescable requests in flight by [`coalescing_key`], with the id of the request.
+///
+/// Only weak references are kept, so that a request is dropped together with its last waiter.
+type InFlight = Arc<Mutex<HashMap<String, (u64, WeakShared<ResponseFuture>)>>>;
+
+/// Returns the key identifying the request for coalescing, if it can be coalesced.
+///
+/// Only runtime API calls are coalesced, they are read-only and their result only depends on
+/// their parameters, which include the block they are executed at.
+pub(crate) fn coalescing_key(method: &str, params: &ArrayParams) -> Option<String> {
+	if method != "state_call" {
+		return None
+	}
+
+	let params = params.clone().to_rpc_params().ok()?;
+	Some(params.map(|params| params.get().to_string()).unwrap_or_default())
+}
+
+/// Coalesces the identical requests and sheds the requests exceeding the limit of requests in
+/// flight.
+#[derive(Clone)]
+pub(crate) struct RequestScheduler {
+	max_in_flight: usize,
+	permits: Arc<Semaphore>,
+	/// Responses of the coalescable requests in flight.
+	in_flight: InFlight,
+	/// The id of the next coalescable request.
+	next_id: Arc<AtomicU64>,
+	metrics: Option<RelaychainRpcMetrics>,
+}
+
+/// Removes a coalescable request from the requests in flight once it completes, or once it is
+/// dropped because nobody waits for its response anymore.
+struct InFlightGuard {
+	in_flight: InFlight,
+	key: String,
+	id: u64,
+}
+
+impl Drop for InFlightGuard {
+	fn drop(&mut self) {
+		let mut in_flight = self.in_flight.lock().expect("Lock is never poisoned; qed");
+		// The entry may belong to a newer request already.
+		if in_flight.get(&self.key).map_or(false, |(id, _)| *id == self.id) {
+			in_flight.remove(&self.key);
+		}
+	}
+}
+
+impl RequestScheduler {
+	pub(crate) fn new(max_in_flight: usize, metrics: Option<RelaychainRpcMetrics>) -> Self {
+		Self {
+			max_in_flight,
+			permits: Arc::new(Semaphore::new(max_in_flight)),
+			in_flight: Default::default(),
+			next_id: Default::default(),
+			metrics,
+		}
+	}
+
+	/// Sends the `request`, unless an identical request is in flight, in which case its response
+	/// is awaited instead.
+	///
+	/// `key` is the [`coalescing_key`] of the request.
+	pub(crate) async fn schedule(
+		&self,
+		method: &str,
+		key: Option<String>,
+		request: impl Future<Output = Result<JsonValue, RelayChainError>> + Send + 'static,
+	) -> Result<JsonValue, RelayChainError> {
+		let Some(key) = key else { return self.send(method, request).await };
+
+		let response = {
+			let mut in_flight = self.in_flight.lock().expect("Lock is never poisoned; qed");
+			match in_flight.get(&key).and_then(|(_, response)| response.upgrade()) {
+				Some(response) => {
+					tracing::trace!(target: LOG_TARGET, %method, "Coalescing identical request.");
+					if let Some(metrics) = &self.metrics {
+						metrics.on_request_coalesced(method);
+					}
+					response
+				},
+				None => {
+					let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+					let guard =
+						InFlightGuard { in_flight: self.in_flight.clone(), key: key.clone(), id };
+					let scheduler = self.clone();
+					let method = method.to_string();
+					let response = async move {
+						let _guard = guard;
+						scheduler.send(&method, request).await.map_err(Arc::new)
+					}
+					.boxed()
+					.shared();
+					let weak = response.downgrade().expect("The response is not polled yet; qed");
+					in_flight.insert(key, (id, weak));
+					response
+				},
+			}
+		};
+
+		response.await.map_err(|error| {
+			Arc::try_unwrap(error)
+				.unwrap_or_else(|error| RelayChainError::GenericError(error.to_string()))
+		})
+	}
+
+	/// Sends the `request` if the limit of requests in flight is not reached.
+	async fn send(
+		&self,
+		method: &str,
+		request: impl Future<Output = Result<JsonValue, RelayChainError>>,
+	) -> Result<JsonValue, RelayChainError> {
+		let Ok(_permit) = self.permits.try_acquire() else {
+			tracing::debug!(
+				target: LOG_TARGET,
+				%method,
+				max_in_flight = self.max_in_flight,
+				"RPC endpoint saturated, rejecting request."
+			);
+			if let Some(metrics) = &self.metrics {
+				metrics.on_request_shed(method);
+			}
+			return Err(RelayChainError::RpcOverloaded(method.to_string(), self.max_in_flight))
+		};
+
+		request.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{channel::oneshot, executor::block_on, future::join_all};
+	use jsonrpsee::rpc_params;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	#[test]
+	fn only_runtime_api_calls_are_coalesced() {
+		assert!(coalescing_key("state_call", &rpc_params!("Core_version", "0x")).is_some());
+		assert_ne!(
+			coalescing_key("state_call", &rpc_params!("Core_version", "0x")),
+			coalescing_key("state_call", &rpc_params!("Core_version", "0x00")),
+		);
+		assert_eq!(coalescing_key("chain_getHeader", &rpc_params!()), None);
+	}
+
+	#[test]
+	fn identical_requests_are_coalesced() {
+		let scheduler = RequestScheduler::new(DEFAULT_MAX_REQUESTS_IN_FLIGHT, None);
+		let sent = Arc::new(AtomicUsize::new(0));
+		let (tx, rx) = oneshot::channel::<()>();
+		let rx = rx.shared();
+
+		let request = || {
+			let sent = sent.clone();
+			let rx = rx.clone();
+			async move {
+				sent.fetch_add(1, Ordering::SeqCst);
+				rx.await.unwrap();
+				Ok::<_, RelayChainError>(JsonValue::from(1))
+			}
+		};
+
+		let responses = block_on(async {
+			let responses = join_all(
+				(0..3).map(|_| scheduler.schedule("state_call", Some("key".into()), request())),
+			);
+			// The requests are all started before the response arrives.
+			let (responses, _) = futures::join!(responses, async move { tx.send(()).unwrap() });
+			responses
+		});
+
+		assert_eq!(sent.load(Ordering::SeqCst), 1);
+		assert!(responses.into_iter().all(|response| response.unwrap() == JsonValue::from(1)));
+		assert!(scheduler.in_flight.lock().unwrap().is_empty());
+
+		// Without a key, requests are sent separately.
+		block_on(scheduler.schedule("state_call", None, request())).unwrap();
+		assert_eq!(sent.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn dropped_requests_release_permit_and_entry() {
+		let scheduler = RequestScheduler::new(1, None);
+		let (_tx, rx) = oneshot::channel::<()>();
+
+		block_on(async {
+			let mut pending = scheduler
+				.schedule("state_call", Some("key".into()), async move {
+					let _ = rx.await;
+					Ok::<_, RelayChainError>(JsonValue::Null)
+				})
+				.boxed();
+			// Start the request, so that it holds the only permit.
+			assert!(futures::poll!(&mut pending).is_pending());
+			assert_eq!(scheduler.permits.available_permits(), 0);
+			assert_eq!(scheduler.in_flight.lock().unwrap().len(), 1);
+			drop(pending);
+		});
+
+		assert_eq!(scheduler.permits.available_permits(), 1);
+		assert!(scheduler.in_flight.lock().unwrap().is_empty());
+		let response = block_on(scheduler.schedule("state_call", Some("key".into()), async {
+			Ok::<_, RelayChainError>(JsonValue::from(2))
+		}));
+		assert_eq!(response.unwrap(), JsonValue::from(2));
+	}
+
+	#[test]
+	fn requests_over_the_limit_are_shed() {
+		let scheduler = RequestScheduler::new(1, None);
+		let (tx, rx) = oneshot::channel::<()>();
+
+		let pending = scheduler.schedule("chain_getHeader", None, async move {
+			rx.await.unwrap();
+			Ok::<_, RelayChainError>(JsonValue::Null)
+		});
+		let shed = scheduler.schedule("chain_getBlockHash", None, async {
+			Ok::<_, RelayChainError>(JsonValue::Null)
+		});
+
+		let (pending, shed) = block_on(async move {
+			let mut pending = pending.boxed();
+			// Start the first request, so that it holds the only permit.
+			assert!(futures::poll!(&mut pending).is_pending());
+			let shed = shed.await;
+			tx.send(()).unwrap();
+			(pending.await, shed)
+		});
+
+		assert!(pending.is_ok());
+		assert!(
+			matches!(shed, Err(RelayChainError::RpcOverloaded(method, 1)) if method == "chain_getBlockHash")
+		);
+	}
+}
@@ -61,6 +61,7 @@ use crate::{
 	light_client_worker::{build_smoldot_client, LightClientRpcWorker},
 	metrics::RelaychainRpcMetrics,
 	reconnecting_ws_client::ReconnectingWebsocketWorker,
+	request_scheduler::{coalescing_key, RequestScheduler, DEFAULT_MAX_REQUESTS_IN_FLIGHT},
 };
 pub use url::Url;
 
@@ -132,10 +133,15 @@ pub async fn create_client_and_start_light_client_worker(
 struct PayloadToHex<'a>(#[serde(with = "sp_core::bytes")] &'a [u8]);
 
 /// Client that maps RPC methods and deserializes results
+///
+/// Clones of the client share the same worker, which keeps a single upstream subscription per
+/// header stream for all the registered listeners, and the same [`RequestScheduler`], which
+/// coalesces identical runtime API calls and rejects requests once the endpoint is saturated.
 #[derive(Clone)]
 pub struct RelayChainRpcClient {
 	/// Sender to send messages to the worker.
 	worker_channel: TokioSender<RpcDispatcherMessage>,
+	scheduler: RequestScheduler,
 	metrics: Option<RelaychainRpcMetrics>,
 }
 
@@ -148,12 +154,15 @@ impl RelayChainRpcClient {
 		worker_channel: TokioSender<RpcDispatcherMessage>,
 		prometheus_registry: Option<&Registry>,
 	) -> Self {
+		let metrics = prometheus_registry
+			.and_then(|inner| RelaychainRpcMetrics::register(inner).map_err(|err| {
+				tracing::warn!(target: LOG_TARGET, error = %err, "Unable to instantiate the RPC client metrics, continuing w/o metrics setup.");
+			}).ok());
+
 		RelayChainRpcClient {
 			worker_channel,
-			metrics: prometheus_registry
-				.and_then(|inner| RelaychainRpcMetrics::register(inner).map_err(|err| {
-					tracing::warn!(target: LOG_TARGET, error = %err, "Unable to instantiate the RPC client metrics, continuing w/o metrics setup.");
-				}).ok()),
+			scheduler: RequestScheduler::new(DEFAULT_MAX_REQUESTS_IN_FLIGHT, metrics.clone()),
+			metrics,
 		}
 	}
 
@@ -229,22 +238,29 @@ impl RelayChainRpcClient {
 	{
 		let _timer = self.metrics.as_ref().map(|inner| inner.start_request_timer(method));
 
-		let (tx, rx) = futures::channel::oneshot::channel();
-
-		let message = RpcDispatcherMessage::Request(method.into(), params, tx);
-		self.worker_channel.send(message).await.map_err(|err| {
-			RelayChainError::WorkerCommunicationError(format!(
-				"Unable to send message to RPC worker: {}",
-				err
-			))
-		})?;
-
-		let value = rx.await.map_err(|err| {
-			RelayChainError::WorkerCommunicationError(format!(
-				"RPC worker channel closed. This can hint and connectivity issues with the supplied RPC endpoints. Message: {}",
-				err
-			))
-		})??;
+		let key = coalescing_key(method, &params);
+		let worker_channel = self.worker_channel.clone();
+		let message_method = method.to_string();
+		let request = async move {
+			let (tx, rx) = futures::channel::oneshot::channel();
+
+			let message = RpcDispatcherMessage::Request(message_method, params, tx);
+			worker_channel.send(message).await.map_err(|err| {
+				RelayChainError::WorkerCommunicationError(format!(
+					"Unable to send message to RPC worker: {}",
+					err
+				))
+			})?;
+
+			Ok::<_, RelayChainError>(rx.await.map_err(|err| {
+				RelayChainError::WorkerCommunicationError(format!(
+					"RPC worker channel closed. This can hint and connectivity issues with the supplied RPC endpoints. Message: {}",
+					err
+				))
+			})??)
+		};
+
+		let value = self.scheduler.schedule(method, key, request).await?;
 
 		serde_json::from_value(value).map_err(|_| {
 			trace_error(&RelayChainError::GenericError("Unable to deserialize value".to_string()));
@@ -39,7 +39,7 @@ use sc_consensus::{
 	BlockImport,
 };
 use sc_network::{config::SyncMode, service::traits::NetworkService, NetworkBackend};
-use sc_network_sync::SyncingService;
+use sc_network_sync::{block_relay_protocol::BlockRelayParams, SyncingService};
 use sc_network_transactions::TransactionsHandlerController;
 use sc_service::{Configuration, SpawnTaskHandle, TaskManager, WarpSyncConfig};
 use sc_telemetry::{log, TelemetryWorkerHandle};
@@ -372,6 +372,7 @@ pub async fn build_relay_chain_interface(
 			telemetry_worker_handle,
 			task_manager,
 			hwbench,
+			collator_options.precheck_collations,
 		),
 		cumulus_client_cli::RelayChainMode::ExternalRpc(rpc_target_urls) =>
 			build_minimal_relay_chain_node_with_rpc(
@@ -379,10 +380,16 @@ pub async fn build_relay_chain_interface(
 				parachain_config.prometheus_registry(),
 				task_manager,
 				rpc_target_urls,
+				collator_options.precheck_collations,
 			)
 			.await,
 		cumulus_client_cli::RelayChainMode::LightClient =>
-			build_minimal_relay_chain_node_light_client(relay_chain_config, task_manager).await,
+			build_minimal_relay_chain_node_light_client(
+				relay_chain_config,
+				task_manager,
+				collator_options.precheck_collations,
+			)
+			.await,
 	}
 }
 
@@ -426,6 +433,9 @@ pub struct BuildNetworkParams<
 	pub spawn_handle: SpawnTaskHandle,
 	pub import_queue: IQ,
 	pub sybil_resistance_level: CollatorSybilResistance,
+	/// Custom block relay protocol, e.g. to serve block bodies in a format optimized for the
+	/// parachain. If not specified, the default block request handler is used.
+	pub block_relay: Option<BlockRelayParams<Block, Network>>,
 }
 
 /// Build the network service, the network status sinks and an RPC sender.
@@ -440,6 +450,7 @@ pub async fn build_network<'a, Block, Client, RCInterface, IQ, Network>(
 		relay_chain_interface,
 		import_queue,
 		sybil_resistance_level,
+		block_relay,
 	}: BuildNetworkParams<'a, Block, Client, Network, RCInterface, IQ>,
 ) -> sc_service::error::Result<(
 	Arc<dyn NetworkService>,
@@ -511,7 +522,7 @@ where
 		import_queue,
 		block_announce_validator_builder: Some(Box::new(move |_| block_announce_validator)),
 		warp_sync_config,
-		block_relay: None,
+		block_relay,
 		metrics,
 	})
 }
@@ -99,6 +99,12 @@ impl<T: frame_system::Config> pallet_utility::WeightInfo for WeightInfo<T> {
 			// Standard Error: 18_524
 			.saturating_add(Weight::from_parts(5_336_933, 0).saturating_mul(c.into()))
 	}
+	/// Not benchmarked yet. Bounded by `force_batch`, which dispatches the calls in the same
+	/// loop.
+	/// The range of component `c` is `[0, 1000]`.
+	fn batch_with_budget(c: u32, ) -> Weight {
+		<Self as pallet_utility::WeightInfo>::force_batch(c)
+	}
 	fn dispatch_as_fallible() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
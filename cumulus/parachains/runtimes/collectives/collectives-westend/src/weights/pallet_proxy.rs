@@ -148,6 +148,31 @@ impl<T: frame_system::Config> pallet_proxy::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	/// Storage: `Proxy::Proxies` (r:1 w:0)
+	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Announcements` (r:1 w:1)
+	/// Proof: `Proxy::Announcements` (`max_values`: None, `max_size`: Some(2233), added: 4708, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `a` is `[0, 31]`.
+	/// The range of component `p` is `[1, 31]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn announce_call(a: u32, p: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `420 + a * (68 ±0) + p * (37 ±0)`
+		//  Estimated: `5698`
+		// Minimum execution time: 37_999_000 picoseconds.
+		Weight::from_parts(37_895_047, 0)
+			.saturating_add(Weight::from_parts(0, 5698))
+			// Standard Error: 2_480
+			.saturating_add(Weight::from_parts(165_564, 0).saturating_mul(a.into()))
+			// Standard Error: 2_562
+			.saturating_add(Weight::from_parts(66_557, 0).saturating_mul(p.into()))
+			// Standard Error: 9
+			.saturating_add(Weight::from_parts(1_431, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 	/// Storage: `Proxy::Proxies` (r:1 w:1)
 	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
 	/// The range of component `p` is `[1, 31]`.
@@ -19,12 +19,13 @@
 use crate::{
 	chain_spec::DiskChainSpecLoader,
 	common::{
-		chain_spec::{Extensions, LoadSpec},
+		chain_spec::{ChainSpecExtensions, Extensions, LoadSpec},
 		NodeExtraArgs,
 	},
 };
 use chain_spec_builder::ChainSpecBuilder;
 use clap::{Command, CommandFactory, FromArgMatches, ValueEnum};
+use polkadot_node_primitives::{PoVCompression, PoVCompressionConfig, POV_BOMB_LIMIT};
 use sc_chain_spec::ChainSpec;
 use sc_cli::{
 	CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams, NetworkParams,
@@ -145,6 +146,9 @@ pub struct Cli<Config: CliConfig> {
 	#[arg(skip)]
 	pub(crate) chain_spec_loader: Option<Box<dyn LoadSpec>>,
 
+	#[arg(skip)]
+	pub(crate) chain_spec_extensions: ChainSpecExtensions,
+
 	/// Possible subcommands. See [`Subcommand`].
 	#[command(subcommand)]
 	pub subcommand: Option<Subcommand>,
@@ -194,6 +198,19 @@ pub struct Cli<Config: CliConfig> {
 	#[arg(long)]
 	pub export_pov_to_path: Option<PathBuf>,
 
+	/// How the PoVs built by this collator are compressed: `none`, `zstd` or `zstd:<level>`.
+	///
+	/// Higher zstd levels save bandwidth at the cost of CPU time.
+	#[arg(long, default_value_t = PoVCompression::default())]
+	pub pov_compression: PoVCompression,
+
+	/// Maximum size in bytes of a PoV after decompression.
+	///
+	/// Bigger PoVs are sent uncompressed. Can't exceed the limit used by the relay chain
+	/// validators.
+	#[arg(long)]
+	pub pov_bomb_limit: Option<usize>,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relay_chain_args: Vec<String>,
@@ -232,6 +249,10 @@ impl<Config: CliConfig> Cli<Config> {
 				.unwrap_or(self.authoring),
 			export_pov: self.export_pov_to_path.clone(),
 			max_pov_percentage: self.run.experimental_max_pov_percentage,
+			pov_compression: PoVCompressionConfig {
+				compression: self.pov_compression,
+				bomb_limit: self.pov_bomb_limit.unwrap_or(POV_BOMB_LIMIT),
+			},
 		}
 	}
 }
@@ -262,10 +283,40 @@ impl<Config: CliConfig> SubstrateCli for Cli<Config> {
 	}
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn ChainSpec>, String> {
-		match &self.chain_spec_loader {
+		let mut chain_spec = match &self.chain_spec_loader {
 			Some(chain_spec_loader) => chain_spec_loader.load_spec(id),
 			None => DiskChainSpecLoader.load_spec(id),
-		}
+		}?;
+		self.chain_spec_extensions.apply(chain_spec.as_mut())?;
+		Ok(chain_spec)
+	}
+}
+
+impl<Config: CliConfig> Cli<Config> {
+	/// Parse the command line arguments, including the CLI flags of the given chain spec
+	/// extensions.
+	///
+	/// This is the equivalent of [`SubstrateCli::from_args`]. Print the error message and quit the
+	/// program in case of failure.
+	pub(crate) fn from_args_with_extensions(mut extensions: ChainSpecExtensions) -> Self {
+		let mut full_version = Self::impl_version();
+		full_version.push('\n');
+
+		let command = extensions
+			.augment_command(<Self as CommandFactory>::command())
+			.name(Self::executable_name())
+			.author(Self::author())
+			.about(Self::description())
+			.version(full_version)
+			.propagate_version(true)
+			.args_conflicts_with_subcommands(true)
+			.subcommand_negates_reqs(true);
+
+		let matches = command.get_matches();
+		let mut cli = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+		extensions.set_overrides(&matches);
+		cli.chain_spec_extensions = extensions;
+		cli
 	}
 }
 
@@ -17,7 +17,7 @@
 use crate::{
 	cli::{Cli, RelayChainCli, Subcommand},
 	common::{
-		chain_spec::{Extensions, LoadSpec},
+		chain_spec::{ChainSpecExtensions, Extensions, LoadSpec},
 		runtime::{
 			AuraConsensusId, Consensus, Runtime, RuntimeResolver as RuntimeResolverT,
 			RuntimeResolver,
@@ -48,6 +48,8 @@ pub struct RunConfig {
 	pub chain_spec_loader: Box<dyn LoadSpec>,
 	/// A custom runtime resolver.
 	pub runtime_resolver: Box<dyn RuntimeResolver>,
+	/// The custom chain spec extensions supported by the node.
+	pub chain_spec_extensions: ChainSpecExtensions,
 }
 
 impl RunConfig {
@@ -56,7 +58,20 @@ impl RunConfig {
 		runtime_resolver: Box<dyn RuntimeResolver>,
 		chain_spec_loader: Box<dyn LoadSpec>,
 	) -> Self {
-		RunConfig { chain_spec_loader, runtime_resolver }
+		RunConfig {
+			chain_spec_loader,
+			runtime_resolver,
+			chain_spec_extensions: ChainSpecExtensions::default(),
+		}
+	}
+
+	/// Set the custom chain spec extensions supported by the node, see [`ChainSpecExtensions`].
+	pub fn with_chain_spec_extensions(
+		mut self,
+		chain_spec_extensions: ChainSpecExtensions,
+	) -> Self {
+		self.chain_spec_extensions = chain_spec_extensions;
+		self
 	}
 }
 
@@ -100,7 +115,7 @@ fn new_node_spec(
 
 /// Parse command line arguments into service configuration.
 pub fn run<CliConfig: crate::cli::CliConfig>(cmd_config: RunConfig) -> Result<()> {
-	let mut cli = Cli::<CliConfig>::from_args();
+	let mut cli = Cli::<CliConfig>::from_args_with_extensions(cmd_config.chain_spec_extensions);
 	cli.chain_spec_loader = Some(cmd_config.chain_spec_loader);
 
 	#[allow(deprecated)]
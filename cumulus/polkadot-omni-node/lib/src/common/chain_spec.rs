@@ -16,9 +16,11 @@
 
 //! Chain spec primitives.
 
+use clap::{Arg, ArgMatches, Command};
 pub use sc_chain_spec::ChainSpec;
 use sc_chain_spec::ChainSpecExtension;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::fmt::Debug;
 
 /// Helper trait used for loading/building a chain spec starting from the chain ID.
@@ -37,7 +39,7 @@ impl LoadSpec for DiskChainSpecLoader {
 }
 
 /// Generic extensions for Parachain ChainSpecs.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ChainSpecExtension)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ChainSpecExtension)]
 pub struct Extensions {
 	/// The relay chain of the Parachain.
 	#[serde(alias = "relayChain", alias = "RelayChain")]
@@ -45,6 +47,9 @@ pub struct Extensions {
 	/// The id of the Parachain.
 	#[serde(alias = "paraId", alias = "ParaId")]
 	pub para_id: u32,
+	/// All other extensions of the chain spec, see [`CustomExtension`].
+	#[serde(flatten)]
+	pub custom: CustomExtensions,
 }
 
 impl Extensions {
@@ -57,6 +62,187 @@ impl Extensions {
 /// Generic chain spec for all polkadot-parachain runtimes
 pub type GenericChainSpec = sc_service::GenericChainSpec<Extensions>;
 
+/// A typed chain spec extension that is not known to the omni-node, e.g. the bridge endpoints of a
+/// parachain.
+///
+/// The extension is stored as JSON under [`Self::KEY`], next to the [`Extensions`] of the
+/// omni-node, and can be read with [`CustomExtensions::get`].
+pub trait CustomExtension: Serialize + DeserializeOwned {
+	/// The name of the extension in the chain spec.
+	const KEY: &'static str;
+}
+
+/// The chain spec extensions that are not known to the omni-node, by name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CustomExtensions(Map<String, Value>);
+
+impl CustomExtensions {
+	/// Decode the extension `E`. Returns `None` if the chain spec doesn't contain it.
+	pub fn get<E: CustomExtension>(&self) -> Result<Option<E>, String> {
+		self.0
+			.get(E::KEY)
+			.map(|value| E::deserialize(value))
+			.transpose()
+			.map_err(|e| format!("Invalid chain spec extension `{}`: {}", E::KEY, e))
+	}
+
+	/// Set the extension `E`, replacing any previous value.
+	pub fn set<E: CustomExtension>(&mut self, extension: &E) -> Result<(), String> {
+		let value = serde_json::to_value(extension)
+			.map_err(|e| format!("Could not encode chain spec extension `{}`: {}", E::KEY, e))?;
+		self.0.insert(E::KEY.into(), value);
+		Ok(())
+	}
+
+	/// The raw JSON value of the extension named `key`.
+	pub fn get_raw(&self, key: &str) -> Option<&Value> {
+		self.0.get(key)
+	}
+}
+
+/// A [`CustomExtension`] registered with [`ChainSpecExtensions`].
+struct RegisteredExtension {
+	key: &'static str,
+	/// The CLI flag overriding the extension, with its help text.
+	flag: Option<(&'static str, &'static str)>,
+	/// Checks that a JSON value decodes as the extension.
+	check: fn(&Value) -> Result<(), String>,
+}
+
+/// The [`CustomExtension`]s supported by a node built with this library.
+///
+/// Every chain spec the node loads is checked to contain valid values for the registered
+/// extensions. Extensions registered with a CLI flag can also be set from the command line, which
+/// overrides the value of the chain spec:
+///
+/// ```ignore
+/// let extensions = ChainSpecExtensions::new()
+/// 	.register::<ConsensusKnobs>()
+/// 	.register_with_flag::<BridgeEndpoints>("bridge-endpoints", "The bridge endpoints, as JSON.");
+/// let config = RunConfig::new(runtime_resolver, chain_spec_loader)
+/// 	.with_chain_spec_extensions(extensions);
+/// ```
+///
+/// Registries of several crates can be composed with [`Self::merge`].
+#[derive(Default)]
+pub struct ChainSpecExtensions {
+	registered: Vec<RegisteredExtension>,
+	/// The values passed to the CLI flags of the registered extensions.
+	overrides: Vec<(&'static str, Value)>,
+}
+
+impl ChainSpecExtensions {
+	/// Create an empty registry.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register the extension `E`.
+	///
+	/// Panics if an extension with the same key is already registered.
+	pub fn register<E: CustomExtension>(self) -> Self {
+		self.push(RegisteredExtension::of::<E>(None))
+	}
+
+	/// Register the extension `E`, together with a `--{flag}` CLI flag taking its JSON value.
+	///
+	/// Panics if an extension with the same key or flag is already registered. The flag must not
+	/// collide with any other flag of the node either.
+	pub fn register_with_flag<E: CustomExtension>(
+		self,
+		flag: &'static str,
+		help: &'static str,
+	) -> Self {
+		self.push(RegisteredExtension::of::<E>(Some((flag, help))))
+	}
+
+	/// Register all extensions of `other` as well.
+	///
+	/// Panics if both registries contain an extension with the same key or flag.
+	pub fn merge(self, other: Self) -> Self {
+		other.registered.into_iter().fold(self, Self::push)
+	}
+
+	fn push(mut self, extension: RegisteredExtension) -> Self {
+		for registered in &self.registered {
+			assert!(
+				registered.key != extension.key,
+				"Chain spec extension `{}` is registered twice",
+				extension.key
+			);
+			if let (Some((flag, _)), Some((other, _))) = (registered.flag, extension.flag) {
+				assert!(flag != other, "CLI flag `--{}` is registered twice", flag);
+			}
+		}
+		self.registered.push(extension);
+		self
+	}
+
+	/// Add the CLI flags of the registered extensions to `command`.
+	pub(crate) fn augment_command(&self, command: Command) -> Command {
+		self.registered.iter().fold(command, |command, extension| {
+			let Some((flag, help)) = extension.flag else { return command };
+			let check = extension.check;
+			command.arg(Arg::new(flag).long(flag).value_name("JSON").help(help).value_parser(
+				move |value: &str| -> Result<Value, String> {
+					let value = serde_json::from_str(value).map_err(|e| e.to_string())?;
+					check(&value)?;
+					Ok(value)
+				},
+			))
+		})
+	}
+
+	/// Remember the values passed to the CLI flags of the registered extensions.
+	pub(crate) fn set_overrides(&mut self, matches: &ArgMatches) {
+		self.overrides = self
+			.registered
+			.iter()
+			.filter_map(|extension| {
+				let (flag, _) = extension.flag?;
+				let value = matches.try_get_one::<Value>(flag).ok().flatten()?;
+				Some((extension.key, value.clone()))
+			})
+			.collect();
+	}
+
+	/// Apply the CLI overrides to `chain_spec` and check its registered extensions.
+	pub(crate) fn apply(&self, chain_spec: &mut dyn ChainSpec) -> Result<(), String> {
+		if self.registered.is_empty() {
+			return Ok(())
+		}
+
+		let Some(extensions) =
+			sc_chain_spec::get_extension_mut::<Extensions>(chain_spec.extensions_mut())
+		else {
+			return Err("The chain spec doesn't support custom extensions".into())
+		};
+
+		for (key, value) in &self.overrides {
+			extensions.custom.0.insert((*key).into(), value.clone());
+		}
+		for extension in &self.registered {
+			if let Some(value) = extensions.custom.get_raw(extension.key) {
+				(extension.check)(value).map_err(|e| {
+					format!("Invalid chain spec extension `{}`: {}", extension.key, e)
+				})?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl RegisteredExtension {
+	fn of<E: CustomExtension>(flag: Option<(&'static str, &'static str)>) -> Self {
+		Self {
+			key: E::KEY,
+			flag,
+			check: |value| E::deserialize(value).map(|_| ()).map_err(|e| e.to_string()),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -74,4 +260,87 @@ mod tests {
 		assert_eq!(camel_case_extension, snake_case_extension);
 		assert_eq!(snake_case_extension, pascal_case_extension);
 	}
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct BridgeEndpoints {
+		endpoints: Vec<String>,
+	}
+
+	impl CustomExtension for BridgeEndpoints {
+		const KEY: &'static str = "bridgeEndpoints";
+	}
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct ConsensusKnobs {
+		slot_duration: u64,
+	}
+
+	impl CustomExtension for ConsensusKnobs {
+		const KEY: &'static str = "consensusKnobs";
+	}
+
+	#[test]
+	fn custom_extensions_are_decoded_and_preserved() {
+		let json =
+			r#"{"relayChain":"relay","paraId":1,"bridgeEndpoints":{"endpoints":["ws://a"]}}"#;
+		let mut extension: Extensions = serde_json::from_str(json).unwrap();
+
+		assert_eq!(extension.para_id, 1);
+		assert_eq!(
+			extension.custom.get::<BridgeEndpoints>().unwrap(),
+			Some(BridgeEndpoints { endpoints: vec!["ws://a".into()] })
+		);
+		assert_eq!(extension.custom.get::<ConsensusKnobs>().unwrap(), None);
+
+		extension.custom.set(&ConsensusKnobs { slot_duration: 6000 }).unwrap();
+		let encoded = serde_json::to_value(&extension).unwrap();
+		assert_eq!(encoded["bridgeEndpoints"]["endpoints"][0], "ws://a");
+		assert_eq!(encoded["consensusKnobs"]["slot_duration"], 6000);
+		assert_eq!(serde_json::from_value::<Extensions>(encoded).unwrap(), extension);
+	}
+
+	fn chain_spec(extensions: Extensions) -> GenericChainSpec {
+		GenericChainSpec::builder(&[0u8], extensions)
+			.with_genesis_config_patch(Default::default())
+			.build()
+	}
+
+	#[test]
+	fn registered_extensions_are_checked_and_overridden() {
+		let mut registry = ChainSpecExtensions::new()
+			.register::<ConsensusKnobs>()
+			.register_with_flag::<BridgeEndpoints>("bridge-endpoints", "The bridge endpoints.");
+		let command = registry.augment_command(Command::new("node"));
+
+		// values that don't decode are rejected by the CLI.
+		assert!(command
+			.clone()
+			.try_get_matches_from(["node", "--bridge-endpoints", r#"{"endpoints":1}"#])
+			.is_err());
+
+		let matches = command
+			.try_get_matches_from(["node", "--bridge-endpoints", r#"{"endpoints":["ws://b"]}"#])
+			.unwrap();
+		registry.set_overrides(&matches);
+
+		let mut spec = chain_spec(Extensions::default());
+		registry.apply(&mut spec).unwrap();
+		assert_eq!(
+			spec.extensions().custom.get::<BridgeEndpoints>().unwrap(),
+			Some(BridgeEndpoints { endpoints: vec!["ws://b".into()] })
+		);
+
+		let mut custom = CustomExtensions::default();
+		custom.0.insert(ConsensusKnobs::KEY.into(), Value::from("fast"));
+		let mut spec = chain_spec(Extensions { custom, ..Default::default() });
+		assert!(registry.apply(&mut spec).is_err());
+	}
+
+	#[test]
+	#[should_panic(expected = "registered twice")]
+	fn extensions_cannot_be_registered_twice() {
+		let _ = ChainSpecExtensions::new()
+			.register::<BridgeEndpoints>()
+			.merge(ChainSpecExtensions::new().register::<BridgeEndpoints>());
+	}
 }
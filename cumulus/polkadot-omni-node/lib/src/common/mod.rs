@@ -28,6 +28,7 @@ pub mod types;
 
 use crate::cli::AuthoringPolicy;
 use cumulus_primitives_core::{CollectCollationInfo, GetCoreSelectorApi};
+use polkadot_node_primitives::PoVCompressionConfig;
 use sc_client_db::DbHash;
 use sc_offchain::OffchainWorkerApi;
 use serde::de::DeserializeOwned;
@@ -116,4 +117,7 @@ pub struct NodeExtraArgs {
 	/// The maximum percentage of the maximum PoV size that the collator can use.
 	/// It will be removed once <https://github.com/paritytech/polkadot-sdk/issues/6020> is fixed.
 	pub max_pov_percentage: Option<u32>,
+
+	/// How the PoVs built by the node are compressed.
+	pub pov_compression: PoVCompressionConfig,
 }
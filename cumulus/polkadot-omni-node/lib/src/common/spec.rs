@@ -304,6 +304,7 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 					relay_chain_interface: relay_chain_interface.clone(),
 					import_queue: params.import_queue,
 					sybil_resistance_level: Self::SYBIL_RESISTANCE,
+					block_relay: None,
 				})
 				.await?;
 
@@ -319,6 +320,16 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 						network_provider: Arc::new(network.clone()),
 						is_validator: parachain_config.role.is_authority(),
 						enable_http_requests: true,
+						http_client_config: sc_offchain::HttpClientConfig {
+							proxy: parachain_config.offchain_worker.http_proxy.clone(),
+							ca_certificates: parachain_config
+								.offchain_worker
+								.http_ca_certificates
+								.clone(),
+							max_request_timeout: parachain_config
+								.offchain_worker
+								.http_request_timeout,
+						},
 						custom_extensions: move |_| vec![],
 					})?;
 				task_manager.spawn_handle().spawn(
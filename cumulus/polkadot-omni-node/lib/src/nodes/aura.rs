@@ -339,7 +339,8 @@ where
 			Arc::new(task_manager.spawn_handle()),
 			announce_block,
 			client.clone(),
-		);
+		)
+		.with_pov_compression(node_extra_args.pov_compression);
 
 		let client_for_aura = client.clone();
 		let params = SlotBasedParams {
@@ -463,7 +464,8 @@ where
 			Arc::new(task_manager.spawn_handle()),
 			announce_block,
 			client.clone(),
-		);
+		)
+		.with_pov_compression(node_extra_args.pov_compression);
 
 		let params = aura::ParamsWithExport {
 			export_pov: node_extra_args.export_pov,
@@ -133,6 +133,11 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 					network_provider: Arc::new(network.clone()),
 					is_validator: config.role.is_authority(),
 					enable_http_requests: true,
+					http_client_config: sc_offchain::HttpClientConfig {
+						proxy: config.offchain_worker.http_proxy.clone(),
+						ca_certificates: config.offchain_worker.http_ca_certificates.clone(),
+						max_request_timeout: config.offchain_worker.http_request_timeout,
+					},
 					custom_extensions: move |_| vec![],
 				})?;
 			task_manager.spawn_handle().spawn(
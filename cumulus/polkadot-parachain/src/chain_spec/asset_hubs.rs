@@ -25,7 +25,7 @@ pub fn asset_hub_westend_development_config() -> GenericChainSpec {
 	GenericChainSpec::builder(
 		asset_hub_westend_runtime::WASM_BINARY
 			.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "westend".into(), para_id: 1000 },
+		Extensions { relay_chain: "westend".into(), para_id: 1000, ..Default::default() },
 	)
 	.with_name("Westend Asset Hub Development")
 	.with_id("asset-hub-westend-dev")
@@ -43,7 +43,7 @@ pub fn asset_hub_westend_local_config() -> GenericChainSpec {
 	GenericChainSpec::builder(
 		asset_hub_westend_runtime::WASM_BINARY
 			.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "westend-local".into(), para_id: 1000 },
+		Extensions { relay_chain: "westend-local".into(), para_id: 1000, ..Default::default() },
 	)
 	.with_name("Westend Asset Hub Local")
 	.with_id("asset-hub-westend-local")
@@ -61,7 +61,7 @@ pub fn asset_hub_westend_config() -> GenericChainSpec {
 	GenericChainSpec::builder(
 		asset_hub_westend_runtime::WASM_BINARY
 			.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "westend".into(), para_id: 1000 },
+		Extensions { relay_chain: "westend".into(), para_id: 1000, ..Default::default() },
 	)
 	.with_name("Westend Asset Hub")
 	.with_id("asset-hub-westend")
@@ -92,7 +92,7 @@ fn asset_hub_rococo_like_development_config(
 ) -> GenericChainSpec {
 	GenericChainSpec::builder(
 		asset_hub_rococo_runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "rococo-dev".into(), para_id },
+		Extensions { relay_chain: "rococo-dev".into(), para_id, ..Default::default() },
 	)
 	.with_name(name)
 	.with_id(chain_id)
@@ -123,7 +123,7 @@ fn asset_hub_rococo_like_local_config(
 ) -> GenericChainSpec {
 	GenericChainSpec::builder(
 		asset_hub_rococo_runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "rococo-local".into(), para_id },
+		Extensions { relay_chain: "rococo-local".into(), para_id, ..Default::default() },
 	)
 	.with_name(name)
 	.with_id(chain_id)
@@ -140,7 +140,7 @@ pub fn asset_hub_rococo_genesis_config() -> GenericChainSpec {
 	let para_id = 1000;
 	GenericChainSpec::builder(
 		asset_hub_rococo_runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "rococo".into(), para_id },
+		Extensions { relay_chain: "rococo".into(), para_id, ..Default::default() },
 	)
 	.with_name("Rococo Asset Hub")
 	.with_id("asset-hub-rococo")
@@ -27,7 +27,7 @@ pub fn collectives_westend_development_config() -> GenericChainSpec {
 	GenericChainSpec::builder(
 		collectives_westend_runtime::WASM_BINARY
 			.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "westend-dev".into(), para_id: 1001 },
+		Extensions { relay_chain: "westend-dev".into(), para_id: 1001, ..Default::default() },
 	)
 	.with_name("Westend Collectives Development")
 	.with_id("collectives_westend_dev")
@@ -48,7 +48,7 @@ pub fn collectives_westend_local_config() -> GenericChainSpec {
 	GenericChainSpec::builder(
 		collectives_westend_runtime::WASM_BINARY
 			.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "westend-local".into(), para_id: 1001 },
+		Extensions { relay_chain: "westend-local".into(), para_id: 1001, ..Default::default() },
 	)
 	.with_name("Westend Collectives Local")
 	.with_id("collectives_westend_local")
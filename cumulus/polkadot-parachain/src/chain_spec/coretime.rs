@@ -174,7 +174,11 @@ pub mod rococo {
 
 		GenericChainSpec::builder(
 			wasm_binary,
-			Extensions { relay_chain: relay_chain.to_string(), para_id: para_id.into() },
+			Extensions {
+				relay_chain: relay_chain.to_string(),
+				para_id: para_id.into(),
+				..Default::default()
+			},
 		)
 		.with_name(&chain_name)
 		.with_id(runtime_type.into())
@@ -213,7 +217,11 @@ pub mod westend {
 		GenericChainSpec::builder(
 			coretime_westend_runtime::WASM_BINARY
 				.expect("WASM binary was not built, please build it!"),
-			Extensions { relay_chain: relay_chain.to_string(), para_id: para_id.into() },
+			Extensions {
+				relay_chain: relay_chain.to_string(),
+				para_id: para_id.into(),
+				..Default::default()
+			},
 		)
 		.with_name(&chain_name)
 		.with_id(runtime_type.into())
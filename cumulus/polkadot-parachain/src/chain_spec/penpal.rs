@@ -33,6 +33,7 @@ pub fn get_penpal_chain_spec(id: ParaId, relay_chain: &str) -> GenericChainSpec
 		Extensions {
 			relay_chain: relay_chain.into(), // You MUST set this to the correct network!
 			para_id: id.into(),
+			..Default::default()
 		},
 	)
 	.with_name("Penpal Parachain")
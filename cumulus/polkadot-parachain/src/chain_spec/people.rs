@@ -145,7 +145,11 @@ pub mod rococo {
 		GenericChainSpec::builder(
 			people_rococo_runtime::WASM_BINARY
 				.expect("WASM binary was not built, please build it!"),
-			Extensions { relay_chain: relay_chain.to_string(), para_id: para_id.into() },
+			Extensions {
+				relay_chain: relay_chain.to_string(),
+				para_id: para_id.into(),
+				..Default::default()
+			},
 		)
 		.with_name(chain_name)
 		.with_id(super::ensure_id(id).expect("invalid id"))
@@ -185,7 +189,11 @@ pub mod westend {
 		GenericChainSpec::builder(
 			people_westend_runtime::WASM_BINARY
 				.expect("WASM binary was not built, please build it!"),
-			Extensions { relay_chain: relay_chain.to_string(), para_id: para_id.into() },
+			Extensions {
+				relay_chain: relay_chain.to_string(),
+				para_id: para_id.into(),
+				..Default::default()
+			},
 		)
 		.with_name(chain_name)
 		.with_id(super::ensure_id(id).expect("invalid id"))
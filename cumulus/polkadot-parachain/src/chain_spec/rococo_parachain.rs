@@ -29,7 +29,7 @@ use sp_keyring::Sr25519Keyring;
 pub fn rococo_parachain_local_config() -> GenericChainSpec {
 	GenericChainSpec::builder(
 		rococo_parachain_runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "rococo-local".into(), para_id: 1000 },
+		Extensions { relay_chain: "rococo-local".into(), para_id: 1000, ..Default::default() },
 	)
 	.with_name("Rococo Parachain Local")
 	.with_id("local_testnet")
@@ -50,7 +50,7 @@ pub fn staging_rococo_parachain_local_config() -> GenericChainSpec {
 	#[allow(deprecated)]
 	GenericChainSpec::builder(
 		rococo_parachain_runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		Extensions { relay_chain: "rococo-local".into(), para_id: 1000 },
+		Extensions { relay_chain: "rococo-local".into(), para_id: 1000, ..Default::default() },
 	)
 	.with_name("Staging Rococo Parachain Local")
 	.with_id("staging_testnet")
@@ -297,13 +297,18 @@ async fn build_relay_chain_interface(
 				parachain_prometheus_registry,
 				task_manager,
 				rpc_target_urls,
+				collator_options.precheck_collations,
 			)
 			.await
 			.map(|r| r.0),
 		cumulus_client_cli::RelayChainMode::LightClient =>
-			return build_minimal_relay_chain_node_light_client(relay_chain_config, task_manager)
-				.await
-				.map(|r| r.0),
+			return build_minimal_relay_chain_node_light_client(
+				relay_chain_config,
+				task_manager,
+				collator_options.precheck_collations,
+			)
+			.await
+			.map(|r| r.0),
 	};
 
 	task_manager.add_child(relay_chain_node.task_manager);
@@ -388,6 +393,7 @@ where
 			                                                             * resistant or null that
 			                                                             * is not producing any
 			                                                             * blocks at all. */
+			block_relay: None,
 		})
 		.await?;
 
@@ -755,7 +761,8 @@ impl TestNodeBuilder {
 			false,
 		);
 
-		let collator_options = CollatorOptions { relay_chain_mode: self.relay_chain_mode };
+		let collator_options =
+			CollatorOptions { relay_chain_mode: self.relay_chain_mode, precheck_collations: false };
 
 		relay_chain_config.network.node_name =
 			format!("{} (relay chain)", relay_chain_config.network.node_name);
@@ -899,7 +906,11 @@ pub fn node_config(
 		},
 		prometheus_config: None,
 		telemetry_endpoints: None,
-		offchain_worker: OffchainWorkerConfig { enabled: true, indexing_enabled: false },
+		offchain_worker: OffchainWorkerConfig {
+			enabled: true,
+			indexing_enabled: false,
+			..Default::default()
+		},
 		force_authoring: false,
 		disable_grandpa: false,
 		dev_key_seed: Some(key_seed),
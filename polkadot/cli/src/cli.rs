@@ -18,8 +18,9 @@
 
 pub use polkadot_node_primitives::NODE_VERSION;
 
-use clap::{ArgAction, Parser};
-use std::path::PathBuf;
+use clap::{ArgAction, Parser, ValueEnum};
+use sp_core::{crypto::Ss58Codec, sr25519};
+use std::{path::PathBuf, time::Duration};
 
 #[allow(missing_docs)]
 #[derive(Debug, Parser)]
@@ -142,6 +143,23 @@ pub struct RunCmd {
 	///  **Dangerous!** Do not touch unless explicitly advised to.
 	#[arg(long)]
 	pub prepare_workers_hard_max_num: Option<usize>,
+
+	/// Execute the given percentage of the approval PVF executions a second time, with a
+	/// different executor configuration, and report the executions giving a different result.
+	///
+	/// This is an early warning of non-determinism in the PVF executor, at the cost of additional
+	/// compilations and executions. Disabled by default.
+	#[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub pvf_cross_check_percent: Option<u8>,
+
+	/// Pre-check the given percentage of the PVFs put up for a code upgrade locally right away.
+	///
+	/// The judgement about the other PVFs is adopted from the signed statements of more than a
+	/// third of the validators, they are only pre-checked locally if those are not available.
+	/// Defaults to 50, `100` pre-checks every PVF locally.
+	#[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub pvf_local_precheck_percent: Option<u8>,
+
 	/// TESTING ONLY: disable the version check between nodes and workers.
 	#[arg(long, hide = true)]
 	pub disable_worker_version_check: bool,
@@ -158,6 +176,157 @@ pub struct RunCmd {
 	/// networks.
 	#[arg(long)]
 	pub keep_finalized_for: Option<u32>,
+
+	/// For how many sessions the dispute coordinator keeps dispute data, `0` keeps it forever.
+	///
+	/// Windows shorter than the dispute window are extended to it. Only meant for forensic nodes,
+	/// as larger windows make the database grow accordingly.
+	#[arg(long, value_name = "SESSIONS")]
+	pub unsafe_dispute_window: Option<u32>,
+
+	/// For how many sessions the validator sets are kept for the `sessionHistory` RPCs.
+	///
+	/// Defaults to 32 sessions. The validator sets are kept in memory: after a restart, only the
+	/// sessions still known to the runtime and the ones seen since then are served.
+	#[arg(long, value_name = "SESSIONS")]
+	pub session_history_depth: Option<u32>,
+
+	/// Accept collations pushed by the given collator of the para, as `<PARA_ID>=<COLLATOR_ID>`.
+	///
+	/// Pushed collations are only accepted on chains that enabled the `CollationPush` node
+	/// feature, and only from the collators given here. Can be passed several times.
+	#[arg(long, value_name = "PARA_ID=COLLATOR_ID", value_parser = parse_push_collator)]
+	pub collation_push_collator: Vec<(u32, sr25519::Public)>,
+
+	/// Disconnect non-reserved peers of the validation peer set which stop sending notifications.
+	///
+	/// Frees the slots taken by idle peers for active ones. Nobody is disconnected while most of
+	/// the peers are idle at once, as happens when the chain stalls. Disabled by default.
+	#[arg(long)]
+	pub evict_inactive_validation_peers: bool,
+
+	/// The number of non-reserved slots of the validation peer set kept for authorities.
+	///
+	/// Peers that authority discovery doesn't know as current authorities can't take these slots,
+	/// and are disconnected when they would. Defaults to `0`, i.e. no slots are reserved.
+	#[arg(long, value_name = "SLOTS", default_value_t = 0)]
+	pub reserved_authority_slots: usize,
+
+	/// The number of GRANDPA rounds whose observed votes are kept in memory.
+	///
+	/// The votes are served by the `grandpa_roundVotes` RPC, e.g. to analyse a finality incident.
+	/// Defaults to `0`, i.e. no votes are recorded.
+	#[arg(long, value_name = "ROUNDS", default_value_t = 0)]
+	pub grandpa_vote_history: usize,
+
+	/// The sustained rate at which erasure chunks are served to other validators.
+	///
+	/// Requests exceeding the rate are deferred, so that serving chunks doesn't starve block
+	/// import on small machines. Defaults to 64 MiB per second.
+	#[arg(long, value_name = "BYTES_PER_SECOND")]
+	pub chunk_serving_rate: Option<u64>,
+
+	/// The number of bytes of erasure chunks that can be served in a burst, after being idle.
+	///
+	/// Defaults to 16 MiB.
+	#[arg(long, value_name = "BYTES")]
+	pub chunk_serving_burst: Option<u64>,
+
+	/// How long to wait after a block got imported before signing its availability bitfield.
+	///
+	/// Defaults to 1500 milliseconds.
+	#[arg(long, value_name = "MILLISECONDS")]
+	pub bitfield_signing_delay: Option<u64>,
+
+	/// Sign the availability bitfield as soon as the chunks of all occupied cores are available,
+	/// instead of always waiting for `--bitfield-signing-delay`.
+	#[arg(long)]
+	pub early_bitfield_signing: bool,
+
+	/// Only author blocks in primary BABE slots, never in secondary ones.
+	///
+	/// Secondary slots are only claimed if the chain allows them, this lets a validator opt out.
+	#[arg(long)]
+	pub no_babe_secondary_slots: bool,
+
+	/// The network conditions the node operates in.
+	///
+	/// Scales the request-response queue sizes, parallel request limits and timeouts to the
+	/// bandwidth of the profile. Validators should keep the default `datacenter` profile, which
+	/// assumes the recommended 500Mbit/s.
+	#[arg(
+		long,
+		value_enum,
+		value_name = "PROFILE",
+		default_value = "datacenter",
+		ignore_case = true
+	)]
+	pub network_profile: NetworkProfile,
+}
+
+impl RunCmd {
+	/// The collators trusted to push collations, see `--collation-push-collator`.
+	pub fn collation_push_collators(&self) -> polkadot_service::overseer::PushCollators {
+		let mut collators = polkadot_service::overseer::PushCollators::new();
+		for (para_id, collator) in &self.collation_push_collator {
+			collators.entry((*para_id).into()).or_default().insert((*collator).into());
+		}
+		collators
+	}
+
+	/// The rate at which erasure chunks are served, see `--chunk-serving-rate`.
+	pub fn chunk_serving_budget(&self) -> polkadot_service::overseer::ServingBudget {
+		let default = polkadot_service::overseer::ServingBudget::default();
+		polkadot_service::overseer::ServingBudget {
+			bytes_per_second: self.chunk_serving_rate.unwrap_or(default.bytes_per_second),
+			burst_bytes: self.chunk_serving_burst.unwrap_or(default.burst_bytes),
+		}
+	}
+
+	/// The configuration of bitfield signing, see `--bitfield-signing-delay`.
+	pub fn bitfield_signing_config(&self) -> polkadot_service::overseer::BitfieldSigningConfig {
+		let default = polkadot_service::overseer::BitfieldSigningConfig::default();
+		polkadot_service::overseer::BitfieldSigningConfig {
+			signing_delay: self
+				.bitfield_signing_delay
+				.map_or(default.signing_delay, Duration::from_millis),
+			early_signing: self.early_bitfield_signing,
+		}
+	}
+}
+
+/// Parse a collator trusted to push collations, given as `<PARA_ID>=<COLLATOR_ID>` with the
+/// collator id in SS58 format.
+fn parse_push_collator(s: &str) -> Result<(u32, sr25519::Public), String> {
+	let (para_id, collator) = s
+		.split_once('=')
+		.ok_or_else(|| "expected `<PARA_ID>=<COLLATOR_ID>`".to_string())?;
+	let para_id = para_id.parse().map_err(|err| format!("invalid para id: {err}"))?;
+	let collator = sr25519::Public::from_ss58check(collator)
+		.map_err(|err| format!("invalid collator id: {err:?}"))?;
+	Ok((para_id, collator))
+}
+
+/// The network conditions a node operates in, see `--network-profile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum NetworkProfile {
+	/// About 500Mbit/s, the recommended bandwidth of validators.
+	Datacenter,
+	/// About 100Mbit/s.
+	Home,
+	/// About 40Mbit/s.
+	Constrained,
+}
+
+impl From<NetworkProfile> for polkadot_service::NetworkProfile {
+	fn from(profile: NetworkProfile) -> Self {
+		match profile {
+			NetworkProfile::Datacenter => polkadot_service::NetworkProfile::Datacenter,
+			NetworkProfile::Home => polkadot_service::NetworkProfile::Home,
+			NetworkProfile::Constrained => polkadot_service::NetworkProfile::Constrained,
+		}
+	}
 }
 
 #[allow(missing_docs)]
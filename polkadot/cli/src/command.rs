@@ -234,8 +234,21 @@ where
 				execute_workers_max_num: cli.run.execute_workers_max_num,
 				prepare_workers_hard_max_num: cli.run.prepare_workers_hard_max_num,
 				prepare_workers_soft_max_num: cli.run.prepare_workers_soft_max_num,
+				pvf_cross_check_percent: cli.run.pvf_cross_check_percent,
+				pvf_local_precheck_percent: cli.run.pvf_local_precheck_percent,
 				enable_approval_voting_parallel: cli.run.enable_approval_voting_parallel,
 				keep_finalized_for: cli.run.keep_finalized_for,
+				dispute_window: cli.run.unsafe_dispute_window,
+				session_history_depth: cli.run.session_history_depth,
+				network_profile: cli.run.network_profile.into(),
+				collation_push_collators: cli.run.collation_push_collators(),
+				precheck_collations: false,
+				evict_inactive_validation_peers: cli.run.evict_inactive_validation_peers,
+				reserved_authority_slots: cli.run.reserved_authority_slots,
+				grandpa_vote_history: cli.run.grandpa_vote_history,
+				chunk_serving_budget: cli.run.chunk_serving_budget(),
+				bitfield_signing_config: cli.run.bitfield_signing_config(),
+				babe_secondary_slot_claims: !cli.run.no_babe_secondary_slots,
 			},
 		)
 		.map(|full| full.task_manager)?;
@@ -44,9 +44,9 @@ use polkadot_node_subsystem::{
 	SubsystemContext, SubsystemError, SubsystemResult, SubsystemSender,
 };
 use polkadot_node_subsystem_util::{
-	request_claim_queue, request_node_features, request_persisted_validation_data,
-	request_session_index_for_child, request_validation_code_hash, request_validators,
-	runtime::ClaimQueueSnapshot,
+	request_claim_queue, request_persisted_validation_data, request_session_index_for_child,
+	request_validation_code_hash, request_validators,
+	runtime::{ClaimQueueSnapshot, RuntimeInfo},
 };
 use polkadot_primitives::{
 	collator_signature_payload,
@@ -491,11 +491,14 @@ struct PerSessionInfo {
 	n_validators: usize,
 }
 
-struct SessionInfoCache(LruMap<SessionIndex, PerSessionInfo>);
+struct SessionInfoCache {
+	cache: LruMap<SessionIndex, PerSessionInfo>,
+	runtime_info: RuntimeInfo,
+}
 
 impl SessionInfoCache {
 	fn new() -> Self {
-		Self(LruMap::new(ByLength::new(2)))
+		Self { cache: LruMap::new(ByLength::new(2)), runtime_info: RuntimeInfo::new(None) }
 	}
 
 	async fn get<Sender: SubsystemSender<RuntimeApiMessage>>(
@@ -504,7 +507,7 @@ impl SessionInfoCache {
 		session_index: SessionIndex,
 		sender: &mut Sender,
 	) -> Result<PerSessionInfo> {
-		if let Some(info) = self.0.get(&session_index) {
+		if let Some(info) = self.cache.get(&session_index) {
 			return Ok(info.clone())
 		}
 
@@ -512,7 +515,7 @@ impl SessionInfoCache {
 			request_validators(relay_parent, &mut sender.clone()).await.await??.len();
 
 		let node_features =
-			request_node_features(relay_parent, session_index, sender).await.await??;
+			self.runtime_info.get_node_features(sender, relay_parent, session_index).await?;
 
 		let info = PerSessionInfo {
 			v2_receipts: node_features
@@ -521,8 +524,8 @@ impl SessionInfoCache {
 				.unwrap_or(false),
 			n_validators,
 		};
-		self.0.insert(session_index, info);
-		Ok(self.0.get(&session_index).expect("Just inserted").clone())
+		self.cache.insert(session_index, info);
+		Ok(self.cache.get(&session_index).expect("Just inserted").clone())
 	}
 }
 
@@ -29,7 +29,10 @@ use polkadot_node_metrics::metered::{
 };
 
 use polkadot_node_primitives::{
-	approval::time::{Clock, SystemClock},
+	approval::{
+		stats::NoShowStatsHandle,
+		time::{Clock, SystemClock},
+	},
 	DISPUTE_WINDOW,
 };
 use polkadot_node_subsystem::{
@@ -95,6 +98,7 @@ pub struct ApprovalVotingParallelSubsystem {
 	spawner: Arc<dyn overseer::gen::Spawner + 'static>,
 	clock: Arc<dyn Clock + Send + Sync>,
 	overseer_message_channel_capacity_override: Option<usize>,
+	no_show_stats: NoShowStatsHandle,
 }
 
 impl ApprovalVotingParallelSubsystem {
@@ -141,9 +145,16 @@ impl ApprovalVotingParallelSubsystem {
 			spawner: Arc::new(spawner),
 			clock,
 			overseer_message_channel_capacity_override,
+			no_show_stats: NoShowStatsHandle::default(),
 		}
 	}
 
+	/// Record the no-show statistics of the validators in `no_show_stats`.
+	pub fn with_no_show_stats(mut self, no_show_stats: NoShowStatsHandle) -> Self {
+		self.no_show_stats = no_show_stats;
+		self
+	}
+
 	/// The size of the channel used for the workers.
 	fn workers_channel_size(&self) -> usize {
 		self.overseer_message_channel_capacity_override
@@ -275,6 +286,7 @@ where
 		"approval-voting-parallel-db",
 		"approval-voting-parallel",
 		subsystem.clock.clone(),
+		subsystem.no_show_stats.clone(),
 	)
 	.await?;
 
@@ -411,6 +411,31 @@ fn count_no_shows(
 	(no_shows, next_no_show, no_show_validators)
 }
 
+/// Whether an approval of `validator`, received at `tick_now`, arrives after the validator became a
+/// no-show, i.e. more than `no_show_duration` ticks after its assignment.
+///
+/// The clock drift allowed when counting no-shows is not taken into account. Approvals of
+/// validators that already approved the candidate, or that have no assignment, are never late.
+pub fn is_late_approval(
+	approval_entry: &ApprovalEntry,
+	approvals: &BitSlice<u8, BitOrderLsb0>,
+	validator: ValidatorIndex,
+	block_tick: Tick,
+	no_show_duration: Tick,
+	tick_now: Tick,
+) -> bool {
+	if approvals.get(validator.0 as usize).is_some_and(|approved| *approved) {
+		return false
+	}
+
+	approval_entry
+		.tranches()
+		.iter()
+		.flat_map(|tranche| tranche.assignments())
+		.find(|(v, _)| *v == validator)
+		.map_or(false, |(_, tick)| tick_now >= (*tick).max(block_tick) + no_show_duration)
+}
+
 /// Determine the amount of tranches of assignments needed to determine approval of a candidate.
 pub fn tranches_to_approve(
 	approval_entry: &ApprovalEntry,
@@ -546,6 +571,53 @@ mod tests {
 		.is_approved(Tick::max_value()));
 	}
 
+	#[test]
+	fn late_approvals_are_detected() {
+		let block_tick = 20;
+		let no_show_duration = 10;
+		let mut approvals = bitvec![u8, BitOrderLsb0; 0; 4];
+		approvals.set(2, true);
+
+		let approval_entry: ApprovalEntry = approval_db::v3::ApprovalEntry {
+			tranches: vec![
+				approval_db::v3::TrancheEntry {
+					tranche: 0,
+					assignments: vec![(ValidatorIndex(0), 5.into()), (ValidatorIndex(2), 5.into())],
+				},
+				approval_db::v3::TrancheEntry {
+					tranche: 2,
+					assignments: vec![(ValidatorIndex(1), 25.into())],
+				},
+			],
+			assigned_validators: bitvec![u8, BitOrderLsb0; 1; 4],
+			our_assignment: None,
+			our_approval_sig: None,
+			backing_group: GroupIndex(0),
+			approved: false,
+		}
+		.into();
+
+		let is_late = |validator, tick_now| {
+			is_late_approval(
+				&approval_entry,
+				&approvals,
+				ValidatorIndex(validator),
+				block_tick,
+				no_show_duration,
+				tick_now,
+			)
+		};
+
+		// assignments received before the block are counted from the block tick.
+		assert!(!is_late(0, 29));
+		assert!(is_late(0, 30));
+		assert!(!is_late(1, 34));
+		assert!(is_late(1, 35));
+		// validator 2 already approved, validator 3 has no assignment.
+		assert!(!is_late(2, 100));
+		assert!(!is_late(3, 100));
+	}
+
 	#[test]
 	fn exact_takes_only_assignments_up_to() {
 		let mut candidate: CandidateEntry = CandidateEntry::from_v1(
@@ -20,7 +20,7 @@ use std::sync::Arc;
 use codec::{Decode, Encode};
 use polkadot_node_subsystem::{SubsystemError, SubsystemResult};
 use polkadot_node_subsystem_util::database::{DBTransaction, Database};
-use polkadot_primitives::{BlockNumber, CandidateHash, CandidateIndex, Hash};
+use polkadot_primitives::{BlockNumber, CandidateHash, CandidateIndex, Hash, SessionIndex};
 
 use crate::{
 	backend::{Backend, BackendWriteOp, V1ReadBackend, V2ReadBackend},
@@ -36,9 +36,15 @@ pub mod migration_helpers;
 
 const STORED_BLOCKS_KEY: &[u8] = b"Approvals_StoredBlocks";
 
+const STORED_SESSIONS_KEY: &[u8] = b"Approvals_StoredSessions";
+
 /// A range from earliest..last block number stored within the DB.
 #[derive(Encode, Decode, Debug, Clone, PartialEq)]
 pub struct StoredBlockRange(pub BlockNumber, pub BlockNumber);
+
+/// A range from earliest..last session with a shard of blocks stored within the DB.
+#[derive(Encode, Decode, Debug, Clone, PartialEq)]
+pub struct StoredSessionRange(pub SessionIndex, pub SessionIndex);
 /// The database config.
 #[derive(Debug, Clone, Copy)]
 pub struct Config {
@@ -100,6 +106,22 @@ impl Backend for DbBackend {
 		load_stored_blocks(&*self.inner, &self.config)
 	}
 
+	fn load_shard_len(&self, session: &SessionIndex) -> SubsystemResult<u32> {
+		load_shard_len(&*self.inner, &self.config, session)
+	}
+
+	fn load_shard_block(
+		&self,
+		session: &SessionIndex,
+		index: u32,
+	) -> SubsystemResult<Option<Hash>> {
+		load_shard_block(&*self.inner, &self.config, session, index)
+	}
+
+	fn load_stored_sessions(&self) -> SubsystemResult<Option<StoredSessionRange>> {
+		load_stored_sessions(&*self.inner, &self.config)
+	}
+
 	/// Atomically write the list of operations, with later operations taking precedence over prior.
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
@@ -128,6 +150,33 @@ impl Backend for DbBackend {
 				BackendWriteOp::DeleteBlocksAtHeight(h) => {
 					tx.delete(self.config.col_approval_data, &blocks_at_height_key(h));
 				},
+				BackendWriteOp::WriteStoredSessionRange(stored_session_range) => {
+					tx.put_vec(
+						self.config.col_approval_data,
+						&STORED_SESSIONS_KEY,
+						stored_session_range.encode(),
+					);
+				},
+				BackendWriteOp::WriteShardLen(session, len) => {
+					tx.put_vec(
+						self.config.col_approval_data,
+						&shard_len_key(session),
+						len.encode(),
+					);
+				},
+				BackendWriteOp::DeleteShardLen(session) => {
+					tx.delete(self.config.col_approval_data, &shard_len_key(session));
+				},
+				BackendWriteOp::WriteShardBlock(session, index, hash) => {
+					tx.put_vec(
+						self.config.col_approval_data,
+						&shard_block_key(session, index),
+						hash.encode(),
+					);
+				},
+				BackendWriteOp::DeleteShardBlock(session, index) => {
+					tx.delete(self.config.col_approval_data, &shard_block_key(session, index));
+				},
 				BackendWriteOp::WriteBlockEntry(block_entry) => {
 					let block_entry: BlockEntry = block_entry.into();
 					tx.put_vec(
@@ -237,6 +286,29 @@ pub(crate) fn blocks_at_height_key(block_number: BlockNumber) -> [u8; 16] {
 	key
 }
 
+/// The key the number of blocks in the shard of a given session is stored under.
+pub(crate) fn shard_len_key(session: SessionIndex) -> [u8; 18] {
+	const SHARD_LEN_PREFIX: [u8; 14] = *b"Approvals_slen";
+
+	let mut key = [0u8; 14 + 4];
+	key[0..14].copy_from_slice(&SHARD_LEN_PREFIX);
+	session.using_encoded(|s| key[14..18].copy_from_slice(s));
+
+	key
+}
+
+/// The key the block at a given index of the shard of a given session is stored under.
+pub(crate) fn shard_block_key(session: SessionIndex, index: u32) -> [u8; 22] {
+	const SHARD_BLOCK_PREFIX: [u8; 14] = *b"Approvals_sblk";
+
+	let mut key = [0u8; 14 + 4 + 4];
+	key[0..14].copy_from_slice(&SHARD_BLOCK_PREFIX);
+	session.using_encoded(|s| key[14..18].copy_from_slice(s));
+	index.using_encoded(|s| key[18..22].copy_from_slice(s));
+
+	key
+}
+
 /// Return all blocks which have entries in the DB, ascending, by height.
 pub fn load_all_blocks(store: &dyn Database, config: &Config) -> SubsystemResult<Vec<Hash>> {
 	let mut hashes = Vec::new();
@@ -259,6 +331,51 @@ pub fn load_stored_blocks(
 		.map_err(|e| SubsystemError::with_origin("approval-voting", e))
 }
 
+/// Load the stored-sessions key from the state.
+pub fn load_stored_sessions(
+	store: &dyn Database,
+	config: &Config,
+) -> SubsystemResult<Option<StoredSessionRange>> {
+	load_decode(store, config.col_approval_data, STORED_SESSIONS_KEY)
+		.map_err(|e| SubsystemError::with_origin("approval-voting", e))
+}
+
+/// Load the number of blocks in the shard of a given session.
+pub fn load_shard_len(
+	store: &dyn Database,
+	config: &Config,
+	session: &SessionIndex,
+) -> SubsystemResult<u32> {
+	load_decode(store, config.col_approval_data, &shard_len_key(*session))
+		.map(|x| x.unwrap_or_default())
+		.map_err(|e| SubsystemError::with_origin("approval-voting", e))
+}
+
+/// Load the block at a given index of the shard of a given session.
+pub fn load_shard_block(
+	store: &dyn Database,
+	config: &Config,
+	session: &SessionIndex,
+	index: u32,
+) -> SubsystemResult<Option<Hash>> {
+	load_decode(store, config.col_approval_data, &shard_block_key(*session, index))
+		.map_err(|e| SubsystemError::with_origin("approval-voting", e))
+}
+
+/// Load all the blocks in the shard of a given session.
+pub fn load_blocks_in_session(
+	store: &dyn Database,
+	config: &Config,
+	session: &SessionIndex,
+) -> SubsystemResult<Vec<Hash>> {
+	let mut hashes = Vec::new();
+	for index in 0..load_shard_len(store, config, session)? {
+		hashes.extend(load_shard_block(store, config, session, index)?);
+	}
+
+	Ok(hashes)
+}
+
 /// Load a blocks-at-height entry for a given block number.
 pub fn load_blocks_at_height(
 	store: &dyn Database,
@@ -18,7 +18,9 @@
 
 use crate::{
 	approval_db::{
-		common::{migration_helpers::make_bitvec, DbBackend, StoredBlockRange, *},
+		common::{
+			migration_helpers::make_bitvec, DbBackend, StoredBlockRange, StoredSessionRange, *,
+		},
 		v3::*,
 	},
 	backend::{Backend, OverlayedBackend},
@@ -506,6 +508,81 @@ fn canonicalize_works() {
 	]);
 }
 
+#[test]
+fn canonicalize_prunes_old_sessions() {
+	let (mut db, store) = make_db();
+
+	// A -> B -> C, with B starting session 2.
+	// X is a leftover of session 1 above the canonical height, not linked to any other block.
+	//
+	// We'll canonicalize C, the shard of session 1 should be dropped with X.
+
+	let n_validators = 10;
+
+	let genesis = Hash::repeat_byte(0);
+	let block_hash_a = Hash::repeat_byte(1);
+	let block_hash_b = Hash::repeat_byte(2);
+	let block_hash_c = Hash::repeat_byte(3);
+	let block_hash_x = Hash::repeat_byte(4);
+
+	let candidate_receipt_x = make_candidate(ParaId::from(1_u32), block_hash_x);
+	let cand_hash_x = candidate_receipt_x.hash();
+
+	let blocks = vec![
+		make_block_entry(block_hash_a, genesis, 1, Vec::new()),
+		BlockEntry { session: 2, ..make_block_entry(block_hash_b, block_hash_a, 2, Vec::new()) },
+		BlockEntry { session: 2, ..make_block_entry(block_hash_c, block_hash_b, 3, Vec::new()) },
+		make_block_entry(
+			block_hash_x,
+			Hash::repeat_byte(0xff),
+			4,
+			vec![(CoreIndex(0), cand_hash_x)],
+		),
+	];
+
+	let mut overlay_db = OverlayedBackend::new(&db);
+	for block_entry in blocks {
+		add_block_entry(&mut overlay_db, block_entry.into(), n_validators, |_| {
+			Some(NewCandidateInfo::new(candidate_receipt_x.clone(), GroupIndex(1), None))
+		})
+		.unwrap();
+	}
+	let write_ops = overlay_db.into_write_ops();
+	db.write(write_ops).unwrap();
+
+	assert_eq!(
+		load_stored_sessions(store.as_ref(), &TEST_CONFIG).unwrap().unwrap(),
+		StoredSessionRange(1, 3)
+	);
+	assert_eq!(
+		load_blocks_in_session(store.as_ref(), &TEST_CONFIG, &1).unwrap(),
+		vec![block_hash_a, block_hash_x]
+	);
+	assert_eq!(
+		load_blocks_in_session(store.as_ref(), &TEST_CONFIG, &2).unwrap(),
+		vec![block_hash_b, block_hash_c]
+	);
+	assert_eq!(load_shard_len(store.as_ref(), &TEST_CONFIG, &1).unwrap(), 2);
+	assert_eq!(load_shard_block(store.as_ref(), &TEST_CONFIG, &1, 1).unwrap(), Some(block_hash_x));
+
+	let mut overlay_db = OverlayedBackend::new(&db);
+	canonicalize(&mut overlay_db, 3, block_hash_c).unwrap();
+	let write_ops = overlay_db.into_write_ops();
+	db.write(write_ops).unwrap();
+
+	assert_eq!(
+		load_stored_sessions(store.as_ref(), &TEST_CONFIG).unwrap().unwrap(),
+		StoredSessionRange(2, 3)
+	);
+	assert!(load_blocks_in_session(store.as_ref(), &TEST_CONFIG, &1).unwrap().is_empty());
+	assert_eq!(load_shard_len(store.as_ref(), &TEST_CONFIG, &1).unwrap(), 0);
+	assert!(load_shard_block(store.as_ref(), &TEST_CONFIG, &1, 0).unwrap().is_none());
+	assert!(load_block_entry(store.as_ref(), &TEST_CONFIG, &block_hash_x).unwrap().is_none());
+	assert!(load_candidate_entry(store.as_ref(), &TEST_CONFIG, &cand_hash_x)
+		.unwrap()
+		.is_none());
+}
+
 #[test]
 fn force_approve_works() {
 	let (mut db, store) = make_db();
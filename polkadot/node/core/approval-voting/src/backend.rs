@@ -22,12 +22,12 @@
 //! before any commit to the underlying storage is made.
 
 use polkadot_node_subsystem::SubsystemResult;
-use polkadot_primitives::{BlockNumber, CandidateHash, CandidateIndex, Hash};
+use polkadot_primitives::{BlockNumber, CandidateHash, CandidateIndex, Hash, SessionIndex};
 
 use std::collections::HashMap;
 
 use super::{
-	approval_db::common::StoredBlockRange,
+	approval_db::common::{StoredBlockRange, StoredSessionRange},
 	persisted_entries::{BlockEntry, CandidateEntry},
 };
 
@@ -37,8 +37,13 @@ pub enum BackendWriteOp {
 	WriteBlocksAtHeight(BlockNumber, Vec<Hash>),
 	WriteBlockEntry(BlockEntry),
 	WriteCandidateEntry(CandidateEntry),
+	WriteStoredSessionRange(StoredSessionRange),
+	WriteShardLen(SessionIndex, u32),
+	WriteShardBlock(SessionIndex, u32, Hash),
 	DeleteStoredBlockRange,
 	DeleteBlocksAtHeight(BlockNumber),
+	DeleteShardLen(SessionIndex),
+	DeleteShardBlock(SessionIndex, u32),
 	DeleteBlockEntry(Hash),
 	DeleteCandidateEntry(CandidateHash),
 }
@@ -60,6 +65,13 @@ pub trait Backend {
 	fn load_all_blocks(&self) -> SubsystemResult<Vec<Hash>>;
 	/// Load stored block range form the DB.
 	fn load_stored_blocks(&self) -> SubsystemResult<Option<StoredBlockRange>>;
+	/// Load the number of blocks in the shard of a specific session.
+	fn load_shard_len(&self, session: &SessionIndex) -> SubsystemResult<u32>;
+	/// Load the block at a specific index of the shard of a specific session.
+	fn load_shard_block(&self, session: &SessionIndex, index: u32)
+		-> SubsystemResult<Option<Hash>>;
+	/// Load the range of the sessions with a shard stored in the DB.
+	fn load_stored_sessions(&self) -> SubsystemResult<Option<StoredSessionRange>>;
 	/// Atomically write the list of operations, with later operations taking precedence over prior.
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
@@ -118,6 +130,12 @@ pub struct OverlayedBackend<'a, B: 'a> {
 	block_entries: HashMap<Hash, Option<BlockEntry>>,
 	// `None` means 'deleted', missing means query inner.
 	candidate_entries: HashMap<CandidateHash, Option<CandidateEntry>>,
+	// `None` means query inner.
+	stored_session_range: Option<StoredSessionRange>,
+	// `None` means 'deleted', missing means query inner.
+	shard_lens: HashMap<SessionIndex, Option<u32>>,
+	// `None` means 'deleted', missing means query inner.
+	shard_blocks: HashMap<(SessionIndex, u32), Option<Hash>>,
 }
 
 impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
@@ -128,6 +146,9 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			blocks_at_height: HashMap::new(),
 			block_entries: HashMap::new(),
 			candidate_entries: HashMap::new(),
+			stored_session_range: None,
+			shard_lens: HashMap::new(),
+			shard_blocks: HashMap::new(),
 		}
 	}
 
@@ -135,6 +156,9 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.block_entries.is_empty() &&
 			self.candidate_entries.is_empty() &&
 			self.blocks_at_height.is_empty() &&
+			self.shard_lens.is_empty() &&
+			self.shard_blocks.is_empty() &&
+			self.stored_session_range.is_none() &&
 			self.stored_block_range == BlockRangeStatus::NotModified
 	}
 
@@ -165,6 +189,33 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.inner.load_blocks_at_height(height)
 	}
 
+	pub fn load_stored_sessions(&self) -> SubsystemResult<Option<StoredSessionRange>> {
+		match self.stored_session_range {
+			Some(ref value) => Ok(Some(value.clone())),
+			None => self.inner.load_stored_sessions(),
+		}
+	}
+
+	pub fn load_shard_len(&self, session: &SessionIndex) -> SubsystemResult<u32> {
+		if let Some(val) = self.shard_lens.get(session) {
+			return Ok(val.unwrap_or_default())
+		}
+
+		self.inner.load_shard_len(session)
+	}
+
+	pub fn load_shard_block(
+		&self,
+		session: &SessionIndex,
+		index: u32,
+	) -> SubsystemResult<Option<Hash>> {
+		if let Some(val) = self.shard_blocks.get(&(*session, index)) {
+			return Ok(*val)
+		}
+
+		self.inner.load_shard_block(session, index)
+	}
+
 	pub fn load_block_entry(&self, hash: &Hash) -> SubsystemResult<Option<BlockEntry>> {
 		if let Some(val) = self.block_entries.get(&hash) {
 			return Ok(val.clone())
@@ -200,6 +251,26 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.blocks_at_height.insert(height, None);
 	}
 
+	pub fn write_stored_session_range(&mut self, range: StoredSessionRange) {
+		self.stored_session_range = Some(range);
+	}
+
+	pub fn write_shard_len(&mut self, session: SessionIndex, len: u32) {
+		self.shard_lens.insert(session, Some(len));
+	}
+
+	pub fn delete_shard_len(&mut self, session: SessionIndex) {
+		self.shard_lens.insert(session, None);
+	}
+
+	pub fn write_shard_block(&mut self, session: SessionIndex, index: u32, hash: Hash) {
+		self.shard_blocks.insert((session, index), Some(hash));
+	}
+
+	pub fn delete_shard_block(&mut self, session: SessionIndex, index: u32) {
+		self.shard_blocks.insert((session, index), None);
+	}
+
 	pub fn write_block_entry(&mut self, entry: BlockEntry) {
 		self.block_entries.insert(entry.block_hash(), Some(entry));
 	}
@@ -234,6 +305,19 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			None => BackendWriteOp::DeleteCandidateEntry(h),
 		});
 
+		let shard_len_ops = self.shard_lens.into_iter().map(|(s, v)| match v {
+			Some(v) => BackendWriteOp::WriteShardLen(s, v),
+			None => BackendWriteOp::DeleteShardLen(s),
+		});
+
+		let shard_block_ops = self.shard_blocks.into_iter().map(|((s, i), v)| match v {
+			Some(v) => BackendWriteOp::WriteShardBlock(s, i, v),
+			None => BackendWriteOp::DeleteShardBlock(s, i),
+		});
+
+		let stored_session_range_ops =
+			self.stored_session_range.map(BackendWriteOp::WriteStoredSessionRange);
+
 		let stored_block_range_ops = match self.stored_block_range {
 			BlockRangeStatus::Inserted(val) => Some(BackendWriteOp::WriteStoredBlockRange(val)),
 			BlockRangeStatus::Deleted => Some(BackendWriteOp::DeleteStoredBlockRange),
@@ -242,7 +326,10 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 
 		stored_block_range_ops
 			.into_iter()
+			.chain(stored_session_range_ops)
 			.chain(blocks_at_height_ops)
+			.chain(shard_len_ops)
+			.chain(shard_block_ops)
 			.chain(block_entry_ops)
 			.chain(candidate_entry_ops)
 	}
@@ -718,7 +718,9 @@ impl From<crate::approval_db::v1::OurAssignment> for OurAssignment {
 mod tests {
 	use super::*;
 	use crate::import::tests::garbage_vrf_signature;
-	use polkadot_primitives::{AssignmentId, Hash, ASSIGNMENT_KEY_TYPE_ID};
+	use polkadot_primitives::{
+		AssignmentId, ExecutorParam, ExecutorParams, Hash, SessionInfo, ASSIGNMENT_KEY_TYPE_ID,
+	};
 	use sp_application_crypto::sr25519;
 	use sp_core::crypto::Pair as PairT;
 	use sp_keyring::sr25519::Keyring as Sr25519Keyring;
@@ -868,6 +870,34 @@ mod tests {
 		assert!(assignments.is_empty());
 	}
 
+	#[test]
+	fn config_uses_executor_params_overrides() {
+		let session_info = SessionInfo {
+			validators: Default::default(),
+			discovery_keys: Vec::new(),
+			assignment_keys: assignment_keys(&[Sr25519Keyring::Alice]),
+			validator_groups: Default::default(),
+			n_cores: 1,
+			zeroth_delay_tranche_width: 10,
+			relay_vrf_modulo_samples: 10,
+			n_delay_tranches: 40,
+			no_show_slots: 2,
+			needed_approvals: 2,
+			active_validator_indices: Vec::new(),
+			dispute_period: 6,
+			random_seed: [0u8; 32],
+		};
+
+		let config = Config::new(&session_info, &ExecutorParams::default());
+		assert_eq!((config.n_delay_tranches, config.zeroth_delay_tranche_width), (40, 10));
+
+		let executor_params =
+			ExecutorParams::from(&[ExecutorParam::ApprovalRelayVrfDelay(89, 0)][..]);
+		let config = Config::new(&session_info, &executor_params);
+		assert_eq!((config.n_delay_tranches, config.zeroth_delay_tranche_width), (89, 0));
+		assert_eq!(config.relay_vrf_modulo_samples, session_info.relay_vrf_modulo_samples);
+	}
+
 	#[derive(Debug)]
 	struct MutatedAssignment {
 		cores: CoreBitfield,
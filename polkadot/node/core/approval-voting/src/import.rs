@@ -229,9 +229,11 @@ async fn imported_block_info<Sender: SubsystemSender<RuntimeApiMessage>>(
 			.unwrap_or(&false)
 	});
 
-	let session_info = get_session_info(env.runtime_info, sender, block_hash, session_index)
-		.await
-		.ok_or(ImportedBlockInfoError::SessionInfoUnavailable)?;
+	let extended_session_info =
+		get_extended_session_info(env.runtime_info, sender, block_hash, session_index)
+			.await
+			.ok_or(ImportedBlockInfoError::SessionInfoUnavailable)?;
+	let session_info = &extended_session_info.session_info;
 
 	gum::debug!(target: LOG_TARGET, ?enable_v2_assignments, "V2 assignments");
 	let (assignments, slot, relay_vrf_story) = {
@@ -250,7 +252,10 @@ async fn imported_block_info<Sender: SubsystemSender<RuntimeApiMessage>>(
 						let assignments = env.assignment_criteria.compute_assignments(
 							&env.keystore,
 							relay_vrf.clone(),
-							&crate::criteria::Config::from(session_info),
+							&crate::criteria::Config::new(
+								session_info,
+								&extended_session_info.executor_params,
+							),
 							included_candidates
 								.iter()
 								.map(|(c_hash, _, core, group)| (*c_hash, *core, *group))
@@ -24,6 +24,7 @@
 use futures_timer::Delay;
 use polkadot_node_primitives::{
 	approval::{
+		stats::{NoShowStatsHandle, ValidatorNoShowStats},
 		v1::{BlockApprovalMeta, DelayTranche},
 		v2::{
 			AssignmentCertKindV2, BitfieldError, CandidateBitfield, CoreBitfield,
@@ -183,6 +184,8 @@ pub struct ApprovalVotingSubsystem {
 	max_approval_retries: u32,
 	/// The backoff before we retry the approval.
 	retry_backoff: Duration,
+	/// The no-show statistics of the validators, shared with RPCs.
+	no_show_stats: NoShowStatsHandle,
 }
 
 #[derive(Clone)]
@@ -195,6 +198,9 @@ struct MetricsInner {
 	// moment in time. While `no_shows_total` catches that the no-shows at the moment the candidate
 	// is approved, approvals might arrive late and `no_shows_total` wouldn't catch that number.
 	observed_no_shows: prometheus::Counter<prometheus::U64>,
+	late_approvals_total: prometheus::Counter<prometheus::U64>,
+	// The no-shows and late approvals of each validator in the latest session.
+	validator_no_show_stats: prometheus::GaugeVec<prometheus::U64>,
 	approved_by_one_third: prometheus::Counter<prometheus::U64>,
 	wakeups_triggered_total: prometheus::Counter<prometheus::U64>,
 	coalesced_approvals_buckets: prometheus::Histogram,
@@ -290,6 +296,34 @@ impl Metrics {
 		}
 	}
 
+	fn on_late_approval(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.late_approvals_total.inc();
+		}
+	}
+
+	fn on_validator_no_show_stats(
+		&self,
+		new_session: bool,
+		validator_index: ValidatorIndex,
+		stats: ValidatorNoShowStats,
+	) {
+		if let Some(metrics) = &self.0 {
+			if new_session {
+				metrics.validator_no_show_stats.reset();
+			}
+			let validator_index = validator_index.0.to_string();
+			metrics
+				.validator_no_show_stats
+				.with_label_values(&[validator_index.as_str(), "no_show"])
+				.set(stats.no_shows);
+			metrics
+				.validator_no_show_stats
+				.with_label_values(&[validator_index.as_str(), "late_approval"])
+				.set(stats.late_approvals);
+		}
+	}
+
 	fn on_approved_by_one_third(&self) {
 		if let Some(metrics) = &self.0 {
 			metrics.approved_by_one_third.inc();
@@ -394,6 +428,23 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			late_approvals_total: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_approvals_late_approvals_total",
+					"Number of approvals received after the validator became a no-show",
+				)?,
+				registry,
+			)?,
+			validator_no_show_stats: prometheus::register(
+				prometheus::GaugeVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_approvals_validator_no_shows",
+						"Number of no-shows and late approvals of each validator in the latest session",
+					),
+					&["validator_index", "kind"],
+				)?,
+				registry,
+			)?,
 			wakeups_triggered_total: prometheus::register(
 				prometheus::Counter::new(
 					"polkadot_parachain_approvals_wakeups_total",
@@ -539,9 +590,16 @@ impl ApprovalVotingSubsystem {
 			spawner,
 			max_approval_retries,
 			retry_backoff,
+			no_show_stats: NoShowStatsHandle::default(),
 		}
 	}
 
+	/// Record the no-show statistics of the validators in `no_show_stats`.
+	pub fn with_no_show_stats(mut self, no_show_stats: NoShowStatsHandle) -> Self {
+		self.no_show_stats = no_show_stats;
+		self
+	}
+
 	/// Revert to the block corresponding to the specified `hash`.
 	/// The operation is not allowed for blocks older than the last finalized one.
 	pub fn revert_to(&self, hash: Hash) -> Result<(), SubsystemError> {
@@ -887,6 +945,16 @@ where
 		.map(|extended_info| &extended_info.session_info)
 }
 
+// The number of ticks after which an assigned validator which didn't approve is a no-show.
+fn no_show_duration(
+	slot_duration_millis: u64,
+	session_info: &SessionInfo,
+	executor_params: &ExecutorParams,
+) -> Tick {
+	slot_number_to_tick(slot_duration_millis, Slot::from(u64::from(session_info.no_show_slots))) +
+		executor_params.approval_no_show_slack().map_or(0, Tick::from)
+}
+
 struct State {
 	keystore: Arc<LocalKeystore>,
 	slot_duration_millis: u64,
@@ -898,6 +966,11 @@ struct State {
 	per_block_assignments_gathering_times:
 		LruMap<BlockNumber, HashMap<(Hash, CandidateHash), AssignmentGatheringRecord>>,
 	no_show_stats: NoShowStats,
+	// The per session no-show statistics of the validators, which unlike `no_show_stats` are
+	// exposed to RPCs and metrics.
+	validator_no_show_stats: NoShowStatsHandle,
+	// The session the no-show statistics metrics were last observed for.
+	last_no_show_stats_session: Option<SessionIndex>,
 }
 
 // Regularly dump the no-show stats at this block number frequency.
@@ -976,25 +1049,24 @@ impl State {
 	where
 		Sender: SubsystemSender<RuntimeApiMessage>,
 	{
-		let session_info = match get_session_info(
-			session_info_provider,
-			sender,
-			block_entry.parent_hash(),
-			block_entry.session(),
-		)
-		.await
-		{
-			Some(s) => s,
-			None => return None,
-		};
+		let ExtendedSessionInfo { ref session_info, ref executor_params, .. } =
+			match get_extended_session_info(
+				session_info_provider,
+				sender,
+				block_entry.parent_hash(),
+				block_entry.session(),
+			)
+			.await
+			{
+				Some(s) => s,
+				None => return None,
+			};
 		let block_hash = block_entry.block_hash();
 
 		let tranche_now = self.clock.tranche_now(self.slot_duration_millis, block_entry.slot());
 		let block_tick = slot_number_to_tick(self.slot_duration_millis, block_entry.slot());
-		let no_show_duration = slot_number_to_tick(
-			self.slot_duration_millis,
-			Slot::from(u64::from(session_info.no_show_slots)),
-		);
+		let no_show_duration =
+			no_show_duration(self.slot_duration_millis, session_info, executor_params);
 
 		if let Some(approval_entry) = candidate_entry.approval_entry(&block_hash) {
 			let TranchesToApproveResult {
@@ -1024,6 +1096,44 @@ impl State {
 		}
 	}
 
+	// Whether an approval of `validator` for this block and candidate combo, received now, arrives
+	// after the validator became a no-show.
+	async fn is_late_approval<Sender>(
+		&self,
+		sender: &mut Sender,
+		session_info_provider: &mut RuntimeInfo,
+		block_entry: &BlockEntry,
+		candidate_entry: &CandidateEntry,
+		validator: ValidatorIndex,
+	) -> bool
+	where
+		Sender: SubsystemSender<RuntimeApiMessage>,
+	{
+		let Some(approval_entry) = candidate_entry.approval_entry(&block_entry.block_hash()) else {
+			return false
+		};
+		let Some(ExtendedSessionInfo { session_info, executor_params, .. }) =
+			get_extended_session_info(
+				session_info_provider,
+				sender,
+				block_entry.parent_hash(),
+				block_entry.session(),
+			)
+			.await
+		else {
+			return false
+		};
+
+		approval_checking::is_late_approval(
+			approval_entry,
+			candidate_entry.approvals(),
+			validator,
+			slot_number_to_tick(self.slot_duration_millis, block_entry.slot()),
+			no_show_duration(self.slot_duration_millis, session_info, executor_params),
+			self.clock.tick_now(),
+		)
+	}
+
 	// Returns the approval voting params from the RuntimeApi.
 	async fn get_approval_voting_params_or_default<Sender: SubsystemSender<RuntimeApiMessage>>(
 		&self,
@@ -1161,6 +1271,7 @@ impl State {
 
 	fn record_no_shows(
 		&mut self,
+		metrics: &Metrics,
 		session_index: SessionIndex,
 		para_id: u32,
 		no_show_validators: &Vec<ValidatorIndex>,
@@ -1176,6 +1287,39 @@ impl State {
 				.or_default()
 				.entry(*validator_index)
 				.or_default() += 1;
+			self.validator_no_show_stats.record_no_show(session_index, *validator_index);
+			self.observe_validator_no_show_stats(metrics, session_index, *validator_index);
+		}
+	}
+
+	fn record_late_approval(
+		&mut self,
+		metrics: &Metrics,
+		session_index: SessionIndex,
+		validator_index: ValidatorIndex,
+	) {
+		metrics.on_late_approval();
+		self.validator_no_show_stats
+			.record_late_approval(session_index, validator_index);
+		self.observe_validator_no_show_stats(metrics, session_index, validator_index);
+	}
+
+	// Only the statistics of the latest session are exported as metrics.
+	fn observe_validator_no_show_stats(
+		&mut self,
+		metrics: &Metrics,
+		session_index: SessionIndex,
+		validator_index: ValidatorIndex,
+	) {
+		let latest_session = self.validator_no_show_stats.latest_session();
+		if latest_session != Some(session_index) {
+			return
+		}
+		let new_session = self.last_no_show_stats_session != latest_session;
+		self.last_no_show_stats_session = latest_session;
+		if let Some(stats) = self.validator_no_show_stats.validator(session_index, validator_index)
+		{
+			metrics.on_validator_no_show_stats(new_session, validator_index, stats);
 		}
 	}
 }
@@ -1257,6 +1401,8 @@ where
 			MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 		)),
 		no_show_stats: NoShowStats::default(),
+		validator_no_show_stats: subsystem.no_show_stats.clone(),
+		last_no_show_stats_session: None,
 	};
 
 	let mut last_finalized_height: Option<BlockNumber> = {
@@ -1480,6 +1626,7 @@ pub async fn start_approval_worker<
 	task_name: &'static str,
 	group_name: &'static str,
 	clock: Arc<dyn Clock + Send + Sync>,
+	no_show_stats: NoShowStatsHandle,
 ) -> SubsystemResult<()> {
 	let approval_voting = ApprovalVotingSubsystem::with_config_and_clock(
 		config,
@@ -1491,7 +1638,8 @@ pub async fn start_approval_worker<
 		spawner,
 		MAX_APPROVAL_RETRIES,
 		APPROVAL_CHECKING_TIMEOUT / 2,
-	);
+	)
+	.with_no_show_stats(no_show_stats);
 	let backend = DbBackend::new(db.clone(), approval_voting.db_config);
 	let spawner = approval_voting.spawner.clone();
 	spawner.spawn_blocking(
@@ -2962,6 +3110,19 @@ where
 			_ => {},
 		}
 
+		if state
+			.is_late_approval(
+				sender,
+				session_info_provider,
+				&block_entry,
+				&candidate_entry,
+				approval.validator,
+			)
+			.await
+		{
+			state.record_late_approval(metrics, block_entry.session(), approval.validator);
+		}
+
 		gum::trace!(
 			target: LOG_TARGET,
 			validator_index = approval.validator.0,
@@ -3162,7 +3323,12 @@ where
 			approval_entry.mark_approved();
 		}
 		if newly_approved {
-			state.record_no_shows(session_index, para_id.into(), &status.no_show_validators);
+			state.record_no_shows(
+				metrics,
+				session_index,
+				para_id.into(),
+				&status.no_show_validators,
+			);
 		}
 		actions.extend(schedule_wakeup_action(
 			&approval_entry,
@@ -3296,10 +3462,8 @@ async fn process_wakeup<Sender: SubsystemSender<RuntimeApiMessage>>(
 		};
 
 	let block_tick = slot_number_to_tick(state.slot_duration_millis, block_entry.slot());
-	let no_show_duration = slot_number_to_tick(
-		state.slot_duration_millis,
-		Slot::from(u64::from(session_info.no_show_slots)),
-	);
+	let no_show_duration =
+		no_show_duration(state.slot_duration_millis, session_info, executor_params);
 	let tranche_now = state.clock.tranche_now(state.slot_duration_millis, block_entry.slot());
 
 	gum::trace!(
@@ -3742,17 +3906,18 @@ async fn issue_approval<
 		},
 	};
 
-	let session_info = match get_session_info(
-		session_info_provider,
-		sender,
-		block_entry.parent_hash(),
-		block_entry.session(),
-	)
-	.await
-	{
-		Some(s) => s,
-		None => return Ok(Vec::new()),
-	};
+	let ExtendedSessionInfo { ref session_info, ref executor_params, .. } =
+		match get_extended_session_info(
+			session_info_provider,
+			sender,
+			block_entry.parent_hash(),
+			block_entry.session(),
+		)
+		.await
+		{
+			Some(s) => s,
+			None => return Ok(Vec::new()),
+		};
 
 	if block_entry
 		.defer_candidate_signature(
@@ -3763,6 +3928,7 @@ async fn issue_approval<
 				&block_entry,
 				&candidate_entry,
 				session_info,
+				executor_params,
 				&metrics,
 			),
 		)
@@ -4017,6 +4183,7 @@ fn compute_delayed_approval_sending_tick(
 	block_entry: &BlockEntry,
 	candidate_entry: &CandidateEntry,
 	session_info: &SessionInfo,
+	executor_params: &ExecutorParams,
 	metrics: &Metrics,
 ) -> Tick {
 	let current_block_tick = slot_number_to_tick(state.slot_duration_millis, block_entry.slot());
@@ -4028,10 +4195,8 @@ fn compute_delayed_approval_sending_tick(
 
 	let assignment_triggered_tick = current_block_tick + assignment_tranche as Tick;
 
-	let no_show_duration_ticks = slot_number_to_tick(
-		state.slot_duration_millis,
-		Slot::from(u64::from(session_info.no_show_slots)),
-	);
+	let no_show_duration_ticks =
+		no_show_duration(state.slot_duration_millis, session_info, executor_params);
 	let tick_now = state.clock.tick_now();
 
 	let sign_no_later_than = min(
@@ -22,12 +22,16 @@ use polkadot_node_subsystem::{SubsystemError, SubsystemResult};
 use bitvec::order::Lsb0 as BitOrderLsb0;
 use polkadot_primitives::{
 	vstaging::CandidateReceiptV2 as CandidateReceipt, BlockNumber, CandidateHash, GroupIndex, Hash,
+	SessionIndex,
 };
 
 use std::collections::{hash_map::Entry, BTreeMap, HashMap};
 
 use super::{
-	approval_db::{common::StoredBlockRange, v2::OurAssignment},
+	approval_db::{
+		common::{StoredBlockRange, StoredSessionRange},
+		v2::OurAssignment,
+	},
 	backend::{Backend, OverlayedBackend},
 	persisted_entries::{ApprovalEntry, BlockEntry, CandidateEntry},
 	LOG_TARGET,
@@ -81,8 +85,40 @@ fn visit_and_remove_block_entry(
 	Ok(block_entry.children)
 }
 
+/// Prune the shards of all the sessions before the given one, along with the blocks they hold.
+///
+/// None of these blocks can be a descendant of a block in the given session, so the whole shard
+/// can be dropped, including the blocks of competing branches above the canonical height.
+fn prune_sessions_before(
+	overlay_db: &mut OverlayedBackend<'_, impl Backend>,
+	session: SessionIndex,
+	visited_candidates: &mut HashMap<CandidateHash, CandidateEntry>,
+) -> SubsystemResult<()> {
+	let range = match overlay_db.load_stored_sessions()? {
+		Some(range) if range.0 < session => range,
+		_ => return Ok(()),
+	};
+
+	for s in range.0..std::cmp::min(session, range.1) {
+		for index in 0..overlay_db.load_shard_len(&s)? {
+			if let Some(b) = overlay_db.load_shard_block(&s, index)? {
+				visit_and_remove_block_entry(b, overlay_db, visited_candidates)?;
+			}
+			overlay_db.delete_shard_block(s, index);
+		}
+		overlay_db.delete_shard_len(s);
+	}
+
+	overlay_db
+		.write_stored_session_range(StoredSessionRange(session, std::cmp::max(range.1, session)));
+
+	Ok(())
+}
+
 /// Canonicalize some particular block, pruning everything before it and
 /// pruning any competing branches at the same height.
+///
+/// The shards of the sessions before the session of the canonical block are dropped as a whole.
 pub fn canonicalize(
 	overlay_db: &mut OverlayedBackend<'_, impl Backend>,
 	canon_number: BlockNumber,
@@ -94,6 +130,8 @@ pub fn canonicalize(
 		Some(range) => range,
 	};
 
+	let canon_session = overlay_db.load_block_entry(&canon_hash)?.map(|entry| entry.session());
+
 	// Storing all candidates in memory is potentially heavy, but should be fine
 	// as long as finality doesn't stall for a long while. We could optimize this
 	// by keeping only the metadata about which blocks reference each candidate.
@@ -154,6 +192,10 @@ pub fn canonicalize(
 		}
 	}
 
+	if let Some(canon_session) = canon_session {
+		prune_sessions_before(overlay_db, canon_session, &mut visited_candidates)?;
+	}
+
 	// Update all `CandidateEntry`s, deleting all those which now have empty `block_assignments`.
 	for (candidate_hash, candidate) in visited_candidates.into_iter() {
 		if candidate.block_assignments.is_empty() {
@@ -226,6 +268,22 @@ pub fn add_block_entry(
 		store.write_blocks_at_height(number, blocks_at_height)
 	};
 
+	// Append the block to the shard of its session.
+	{
+		let shard_len = store.load_shard_len(&session)?;
+		store.write_shard_block(session, shard_len, entry.block_hash());
+		store.write_shard_len(session, shard_len + 1);
+
+		let new_range = match store.load_stored_sessions()? {
+			None => Some(StoredSessionRange(session, session + 1)),
+			Some(range) if range.1 <= session => Some(StoredSessionRange(range.0, session + 1)),
+			Some(range) if session < range.0 => Some(StoredSessionRange(session, range.1)),
+			Some(_) => None,
+		};
+
+		new_range.map(|n| store.write_stored_session_range(n));
+	};
+
 	let mut candidate_entries = Vec::with_capacity(entry.candidates().len());
 
 	// read and write all updated entries.
@@ -394,6 +452,8 @@ pub fn revert_to(
 		overlay.write_blocks_at_height(number, blocks_at_height);
 
 		if let Some(entry) = overlay.load_block_entry(&hash)? {
+			// The block stays in the shard of its session, which skips blocks without an entry
+			// when it is pruned.
 			overlay.delete_block_entry(&hash);
 
 			// Cleanup the candidate entries by removing any reference to the
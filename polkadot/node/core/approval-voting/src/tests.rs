@@ -62,7 +62,7 @@ use std::{
 };
 
 use super::{
-	approval_db::common::StoredBlockRange,
+	approval_db::common::{StoredBlockRange, StoredSessionRange},
 	backend::BackendWriteOp,
 	import::tests::{
 		garbage_vrf_signature, AllowedSlots, BabeEpoch, BabeEpochConfiguration,
@@ -280,6 +280,9 @@ struct TestStoreInner {
 	blocks_at_height: HashMap<BlockNumber, Vec<Hash>>,
 	block_entries: HashMap<Hash, BlockEntry>,
 	candidate_entries: HashMap<CandidateHash, CandidateEntry>,
+	stored_session_range: Option<StoredSessionRange>,
+	shard_lens: HashMap<SessionIndex, u32>,
+	shard_blocks: HashMap<(SessionIndex, u32), Hash>,
 }
 
 impl V1ReadBackend for TestStoreInner {
@@ -323,6 +326,22 @@ impl Backend for TestStoreInner {
 		Ok(self.stored_block_range.clone())
 	}
 
+	fn load_shard_len(&self, session: &SessionIndex) -> SubsystemResult<u32> {
+		Ok(self.shard_lens.get(session).copied().unwrap_or_default())
+	}
+
+	fn load_shard_block(
+		&self,
+		session: &SessionIndex,
+		index: u32,
+	) -> SubsystemResult<Option<Hash>> {
+		Ok(self.shard_blocks.get(&(*session, index)).copied())
+	}
+
+	fn load_stored_sessions(&self) -> SubsystemResult<Option<StoredSessionRange>> {
+		Ok(self.stored_session_range.clone())
+	}
+
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
 		I: IntoIterator<Item = BackendWriteOp>,
@@ -341,6 +360,21 @@ impl Backend for TestStoreInner {
 				BackendWriteOp::DeleteBlocksAtHeight(h) => {
 					let _ = self.blocks_at_height.remove(&h);
 				},
+				BackendWriteOp::WriteStoredSessionRange(stored_session_range) => {
+					self.stored_session_range = Some(stored_session_range);
+				},
+				BackendWriteOp::WriteShardLen(session, len) => {
+					self.shard_lens.insert(session, len);
+				},
+				BackendWriteOp::DeleteShardLen(session) => {
+					let _ = self.shard_lens.remove(&session);
+				},
+				BackendWriteOp::WriteShardBlock(session, index, hash) => {
+					self.shard_blocks.insert((session, index), hash);
+				},
+				BackendWriteOp::DeleteShardBlock(session, index) => {
+					let _ = self.shard_blocks.remove(&(session, index));
+				},
 				BackendWriteOp::WriteBlockEntry(block_entry) => {
 					self.block_entries.insert(block_entry.block_hash(), block_entry);
 				},
@@ -408,6 +442,25 @@ impl Backend for TestStore {
 		store.load_stored_blocks()
 	}
 
+	fn load_shard_len(&self, session: &SessionIndex) -> SubsystemResult<u32> {
+		let store = self.store.lock();
+		store.load_shard_len(session)
+	}
+
+	fn load_shard_block(
+		&self,
+		session: &SessionIndex,
+		index: u32,
+	) -> SubsystemResult<Option<Hash>> {
+		let store = self.store.lock();
+		store.load_shard_block(session, index)
+	}
+
+	fn load_stored_sessions(&self) -> SubsystemResult<Option<StoredSessionRange>> {
+		let store = self.store.lock();
+		store.load_stored_sessions()
+	}
+
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
 		I: IntoIterator<Item = BackendWriteOp>,
@@ -5638,6 +5691,8 @@ fn test_gathering_assignments_statements() {
 			MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 		)),
 		no_show_stats: NoShowStats::default(),
+		validator_no_show_stats: NoShowStatsHandle::default(),
+		last_no_show_stats_session: None,
 	};
 
 	for i in 0..200i32 {
@@ -5732,6 +5787,8 @@ fn test_observe_assignment_gathering_status() {
 			MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 		)),
 		no_show_stats: NoShowStats::default(),
+		validator_no_show_stats: NoShowStatsHandle::default(),
+		last_no_show_stats_session: None,
 	};
 
 	let metrics_inner = MetricsInner {
@@ -5741,6 +5798,12 @@ fn test_observe_assignment_gathering_status() {
 			.unwrap(),
 		no_shows_total: IntCounter::new("dummy", "dummy").unwrap(),
 		observed_no_shows: IntCounter::new("dummy", "dummy").unwrap(),
+		late_approvals_total: IntCounter::new("dummy", "dummy").unwrap(),
+		validator_no_show_stats: prometheus::GaugeVec::<prometheus::U64>::new(
+			Opts::new("dummy", "dummy"),
+			&["validator_index", "kind"],
+		)
+		.unwrap(),
 		approved_by_one_third: IntCounter::new("dummy", "dummy").unwrap(),
 		wakeups_triggered_total: IntCounter::new("dummy", "dummy").unwrap(),
 		coalesced_approvals_buckets: Histogram::with_opts(HistogramOpts::new("dummy", "dummy"))
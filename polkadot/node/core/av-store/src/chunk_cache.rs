@@ -0,0 +1,159 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! In-memory cache of the erasure chunks, along with their merkle proofs, served by the store.
+//!
+//! Popular candidates get their chunks requested by many validators, loading and decoding them
+//! from the database on every `QueryChunk` is wasted work.
+
+use codec::Encode;
+use polkadot_node_primitives::ErasureChunk;
+use polkadot_primitives::{CandidateHash, ValidatorIndex};
+use schnellru::{Limiter, LruMap};
+
+/// The maximum size of the chunks and proofs held by the cache, in bytes.
+pub(crate) const CHUNK_CACHE_MAX_HEAP_SIZE: usize = 64 * 1024 * 1024;
+
+type Key = (CandidateHash, ValidatorIndex);
+
+/// A LRU cache of erasure chunks bounded by the size of the chunks.
+pub(crate) struct ChunkCache {
+	chunks: LruMap<Key, ErasureChunk, ByHeapSize>,
+}
+
+impl ChunkCache {
+	/// Create a new cache holding up to `max_heap_size` bytes of chunks.
+	pub(crate) fn new(max_heap_size: usize) -> Self {
+		Self { chunks: LruMap::new(ByHeapSize { max_heap_size, current_heap_size: 0 }) }
+	}
+
+	/// Get the chunk of `validator_index` for the candidate, if cached.
+	pub(crate) fn get(
+		&mut self,
+		candidate_hash: CandidateHash,
+		validator_index: ValidatorIndex,
+	) -> Option<ErasureChunk> {
+		self.chunks.get(&(candidate_hash, validator_index)).cloned()
+	}
+
+	/// Cache the chunk of `validator_index` for the candidate.
+	pub(crate) fn insert(
+		&mut self,
+		candidate_hash: CandidateHash,
+		validator_index: ValidatorIndex,
+		chunk: ErasureChunk,
+	) {
+		self.chunks.insert((candidate_hash, validator_index), chunk);
+	}
+
+	/// Remove the given chunks, which got pruned from the database.
+	pub(crate) fn remove_pruned(&mut self, pruned: impl IntoIterator<Item = Key>) {
+		for key in pruned {
+			self.chunks.remove(&key);
+		}
+	}
+}
+
+/// Limits the cache by the total size of the cached chunks and their proofs.
+struct ByHeapSize {
+	max_heap_size: usize,
+	current_heap_size: usize,
+}
+
+fn heap_size(chunk: &ErasureChunk) -> usize {
+	chunk.chunk.len() + chunk.proof.encoded_size()
+}
+
+impl Limiter<Key, ErasureChunk> for ByHeapSize {
+	type KeyToInsert<'a> = Key;
+	type LinkType = u32;
+
+	fn is_over_the_limit(&self, length: usize) -> bool {
+		// Always keep the most recent chunk, even if it is above the limit by itself.
+		length > 1 && self.current_heap_size > self.max_heap_size
+	}
+
+	fn on_insert(
+		&mut self,
+		_length: usize,
+		key: Key,
+		chunk: ErasureChunk,
+	) -> Option<(Key, ErasureChunk)> {
+		self.current_heap_size += heap_size(&chunk);
+		Some((key, chunk))
+	}
+
+	fn on_replace(
+		&mut self,
+		_length: usize,
+		_old_key: &mut Key,
+		_new_key: Key,
+		old_chunk: &mut ErasureChunk,
+		new_chunk: &mut ErasureChunk,
+	) -> bool {
+		self.current_heap_size =
+			self.current_heap_size + heap_size(new_chunk) - heap_size(old_chunk);
+		true
+	}
+
+	fn on_removed(&mut self, _key: &mut Key, chunk: &mut ErasureChunk) {
+		self.current_heap_size -= heap_size(chunk);
+	}
+
+	fn on_cleared(&mut self) {
+		self.current_heap_size = 0;
+	}
+
+	fn on_grow(&mut self, _new_memory_usage: usize) -> bool {
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_primitives::Proof;
+	use polkadot_primitives::{ChunkIndex, Hash};
+
+	fn chunk(size: usize) -> ErasureChunk {
+		ErasureChunk {
+			chunk: vec![0; size],
+			index: ChunkIndex(0),
+			proof: Proof::try_from(vec![vec![1; 32]]).unwrap(),
+		}
+	}
+
+	#[test]
+	fn cache_is_bounded_by_chunk_size() {
+		let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+		let chunk_size = heap_size(&chunk(1000));
+		let mut cache = ChunkCache::new(3 * chunk_size);
+
+		for i in 0..4 {
+			cache.insert(candidate_hash, ValidatorIndex(i), chunk(1000));
+		}
+
+		// The least recently used chunk got evicted.
+		assert!(cache.get(candidate_hash, ValidatorIndex(0)).is_none());
+		for i in 1..4 {
+			assert_eq!(cache.get(candidate_hash, ValidatorIndex(i)), Some(chunk(1000)));
+		}
+
+		cache.remove_pruned([(candidate_hash, ValidatorIndex(1))]);
+		assert!(cache.get(candidate_hash, ValidatorIndex(1)).is_none());
+		assert_eq!(cache.chunks.limiter().current_heap_size, 2 * chunk_size);
+	}
+}
@@ -52,6 +52,9 @@ use polkadot_primitives::{
 };
 use util::availability_chunks::availability_chunk_indices;
 
+use chunk_cache::{ChunkCache, CHUNK_CACHE_MAX_HEAP_SIZE};
+
+mod chunk_cache;
 mod metrics;
 pub use self::metrics::*;
 
@@ -454,6 +457,7 @@ pub struct AvailabilityStoreSubsystem {
 	metrics: Metrics,
 	clock: Box<dyn Clock>,
 	sync_oracle: Box<dyn SyncOracle + Send + Sync>,
+	chunk_cache: ChunkCache,
 }
 
 impl AvailabilityStoreSubsystem {
@@ -498,6 +502,7 @@ impl AvailabilityStoreSubsystem {
 			known_blocks: KnownUnfinalizedBlocks::default(),
 			sync_oracle,
 			finalized_number: None,
+			chunk_cache: ChunkCache::new(CHUNK_CACHE_MAX_HEAP_SIZE),
 		}
 	}
 }
@@ -580,8 +585,8 @@ async fn run_iteration<Context>(
 	subsystem: &mut AvailabilityStoreSubsystem,
 	mut next_pruning: &mut future::Fuse<Delay>,
 	(pruning_result_tx, pruning_result_rx): (
-		&mut MpscSender<Result<(), Error>>,
-		&mut MpscReceiver<Result<(), Error>>,
+		&mut MpscSender<Result<PrunedChunks, Error>>,
+		&mut MpscReceiver<Result<PrunedChunks, Error>>,
 	),
 ) -> Result<bool, Error> {
 	select! {
@@ -637,7 +642,7 @@ async fn run_iteration<Context>(
 		// the main loop of the subsystem can exit graciously.
 		result = pruning_result_rx.next() => {
 			if let Some(result) = result {
-				result?;
+				subsystem.chunk_cache.remove_pruned(result?);
 			}
 		},
 	}
@@ -652,7 +657,7 @@ async fn run_iteration<Context>(
 async fn start_prune_all<Context>(
 	ctx: &mut Context,
 	subsystem: &mut AvailabilityStoreSubsystem,
-	mut pruning_result_tx: MpscSender<Result<(), Error>>,
+	mut pruning_result_tx: MpscSender<Result<PrunedChunks, Error>>,
 ) -> Result<(), Error> {
 	let metrics = subsystem.metrics.clone();
 	let db = subsystem.db.clone();
@@ -1105,8 +1110,18 @@ fn process_message(
 		},
 		AvailabilityStoreMessage::QueryChunk(candidate, validator_index, tx) => {
 			let _timer = subsystem.metrics.time_get_chunk();
-			let _ =
-				tx.send(load_chunk(&subsystem.db, &subsystem.config, &candidate, validator_index)?);
+			if let Some(chunk) = subsystem.chunk_cache.get(candidate, validator_index) {
+				subsystem.metrics.on_chunk_cache_hit();
+				let _ = tx.send(Some(chunk));
+				return Ok(())
+			}
+
+			subsystem.metrics.on_chunk_cache_miss();
+			let chunk = load_chunk(&subsystem.db, &subsystem.config, &candidate, validator_index)?;
+			if let Some(chunk) = &chunk {
+				subsystem.chunk_cache.insert(candidate, validator_index, chunk.clone());
+			}
+			let _ = tx.send(chunk);
 		},
 		AvailabilityStoreMessage::QueryChunkSize(candidate, tx) => {
 			let meta = load_meta(&subsystem.db, &subsystem.config, &candidate)?;
@@ -1353,10 +1368,18 @@ fn store_available_data(
 	Ok(())
 }
 
-fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(), Error> {
+/// The chunks deleted by `prune_all`, to be removed from the chunk cache.
+type PrunedChunks = Vec<(CandidateHash, ValidatorIndex)>;
+
+fn prune_all(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	now: Duration,
+) -> Result<PrunedChunks, Error> {
 	let (range_start, range_end) = pruning_range(now);
 
 	let mut tx = DBTransaction::new();
+	let mut pruned_chunks = Vec::new();
 	let iter = db
 		.iter_with_prefix(config.col_meta, &range_start[..])
 		.take_while(|r| r.as_ref().map_or(true, |(k, _v)| &k[..] < &range_end[..]));
@@ -1383,6 +1406,7 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 			for (i, b) in meta.chunks_stored.iter().enumerate() {
 				if *b {
 					delete_chunk(&mut tx, config, &candidate_hash, ValidatorIndex(i as _));
+					pruned_chunks.push((candidate_hash, ValidatorIndex(i as _)));
 				}
 			}
 
@@ -1403,5 +1427,5 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 	}
 
 	db.write(tx)?;
-	Ok(())
+	Ok(pruned_chunks)
 }
@@ -26,6 +26,7 @@ pub(crate) struct MetricsInner {
 	store_available_data: prometheus::Histogram,
 	store_chunk: prometheus::Histogram,
 	get_chunk: prometheus::Histogram,
+	chunk_cache_requests: prometheus::CounterVec<prometheus::U64>,
 }
 
 /// Availability metrics.
@@ -85,6 +86,20 @@ impl Metrics {
 	pub(crate) fn time_get_chunk(&self) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
 		self.0.as_ref().map(|metrics| metrics.get_chunk.start_timer())
 	}
+
+	/// A requested chunk was served from the chunk cache.
+	pub(crate) fn on_chunk_cache_hit(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.chunk_cache_requests.with_label_values(&["hit"]).inc();
+		}
+	}
+
+	/// A requested chunk had to be loaded from the database.
+	pub(crate) fn on_chunk_cache_miss(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.chunk_cache_requests.with_label_values(&["miss"]).inc();
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -152,6 +167,16 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			chunk_cache_requests: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_av_store_chunk_cache_requests_total",
+						"Number of requested chunks, by whether they were cached.",
+					),
+					&["result"],
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Throughput of candidate validations dispatched by candidate-backing.
+//!
+//! Simulates a backer of several cores receiving candidates for all of them and compares
+//! sequential validation with validating up to one candidate per core in parallel. Validating a
+//! candidate is stood in for by hashing a PoV of `POV_SIZE` bytes `HASH_ROUNDS` times, which
+//! keeps a worker thread busy like a PVF execution does.
+
+use futures::{executor::ThreadPool, future, task::SpawnExt};
+use polkadot_node_core_backing::ValidationLimiter;
+use polkadot_primitives::CoreIndex;
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+const N_CORES: u32 = 4;
+const CANDIDATES_PER_CORE: u32 = 8;
+const POV_SIZE: usize = 5 * 1024 * 1024;
+const HASH_ROUNDS: usize = 4;
+
+/// Hash the PoV `HASH_ROUNDS` times, chaining the digests.
+fn validate(pov: &[u8]) -> [u8; 32] {
+	(0..HASH_ROUNDS)
+		.fold([0u8; 32], |digest, _| sp_core::hashing::blake2_256(&[&digest[..], pov].concat()))
+}
+
+/// Validate all candidates with at most `max_parallel` validations at a time.
+fn validate_all(pool: &ThreadPool, max_parallel: usize, pov: &Arc<Vec<u8>>) -> Duration {
+	let limiter = ValidationLimiter::new(max_parallel);
+	let start = Instant::now();
+	let validations = (0..CANDIDATES_PER_CORE)
+		.flat_map(|_| (0..N_CORES).map(CoreIndex))
+		.map(|core| {
+			let limiter = limiter.clone();
+			let pov = pov.clone();
+			pool.spawn_with_handle(async move {
+				let _permit = limiter.acquire(core).await;
+				validate(&pov)
+			})
+			.expect("spawning works")
+		})
+		.collect::<Vec<_>>();
+	futures::executor::block_on(future::join_all(validations));
+	start.elapsed()
+}
+
+fn main() -> Result<(), String> {
+	let pool = ThreadPool::builder()
+		.pool_size(N_CORES as usize)
+		.create()
+		.map_err(|e| e.to_string())?;
+	let n_candidates = N_CORES * CANDIDATES_PER_CORE;
+	let pov = Arc::new((0..POV_SIZE).map(|i| i as u8).collect::<Vec<_>>());
+
+	let sequential = validate_all(&pool, 1, &pov);
+	let parallel = validate_all(&pool, N_CORES as usize, &pov);
+
+	for (name, elapsed) in [("sequential", sequential), ("parallel", parallel)] {
+		println!(
+			"{name}: {n_candidates} candidates in {elapsed:?}, {:.1} candidates/s",
+			n_candidates as f64 / elapsed.as_secs_f64(),
+		);
+	}
+
+	// The gain depends on the hardware, only check it when every core can get a thread.
+	let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+	if threads >= N_CORES as usize && parallel * 2 > sequential {
+		return Err("Parallel validation is not faster than sequential validation".to_string())
+	}
+	Ok(())
+}
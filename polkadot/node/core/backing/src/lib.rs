@@ -74,6 +74,7 @@ use bitvec::vec::BitVec;
 use futures::{
 	channel::{mpsc, oneshot},
 	future::BoxFuture,
+	lock::Mutex as AsyncMutex,
 	stream::FuturesOrdered,
 	FutureExt, SinkExt, StreamExt, TryFutureExt,
 };
@@ -100,9 +101,9 @@ use polkadot_node_subsystem_util::{
 	self as util,
 	backing_implicit_view::View as ImplicitView,
 	request_claim_queue, request_disabled_validators, request_min_backing_votes,
-	request_node_features, request_session_executor_params, request_session_index_for_child,
-	request_validator_groups, request_validators,
-	runtime::{self, ClaimQueueSnapshot},
+	request_session_executor_params, request_session_index_for_child, request_validator_groups,
+	request_validators,
+	runtime::{self, ClaimQueueSnapshot, RuntimeInfo},
 	Validator,
 };
 use polkadot_parachain_primitives::primitives::IsSystem;
@@ -131,11 +132,20 @@ mod error;
 mod metrics;
 use self::metrics::Metrics;
 
+mod validation_limiter;
+pub use self::validation_limiter::{ValidationLimiter, ValidationPermit};
+
 #[cfg(test)]
 mod tests;
 
 const LOG_TARGET: &str = "parachain::candidate-backing";
 
+/// The default maximum number of candidate validations running in parallel.
+///
+/// Twice the default number of PVF execute workers, so fetching PoVs and making them available
+/// overlaps with execution.
+pub const DEFAULT_MAX_PARALLEL_VALIDATIONS: usize = 8;
+
 /// PoV data to validate.
 enum PoVData {
 	/// Already available (from candidate selection).
@@ -184,12 +194,21 @@ impl ValidatedCandidateCommand {
 pub struct CandidateBackingSubsystem {
 	keystore: KeystorePtr,
 	metrics: Metrics,
+	max_parallel_validations: usize,
 }
 
 impl CandidateBackingSubsystem {
 	/// Create a new instance of the `CandidateBackingSubsystem`.
 	pub fn new(keystore: KeystorePtr, metrics: Metrics) -> Self {
-		Self { keystore, metrics }
+		Self { keystore, metrics, max_parallel_validations: DEFAULT_MAX_PARALLEL_VALIDATIONS }
+	}
+
+	/// Set the maximum number of candidate validations running in parallel.
+	///
+	/// Validations beyond that wait, with the cores we are backing served round-robin.
+	pub fn with_max_parallel_validations(mut self, max_parallel_validations: usize) -> Self {
+		self.max_parallel_validations = max_parallel_validations;
+		self
 	}
 }
 
@@ -200,7 +219,7 @@ where
 {
 	fn start(self, ctx: Context) -> SpawnedSubsystem {
 		let future = async move {
-			run(ctx, self.keystore, self.metrics)
+			run(ctx, self.keystore, self.metrics, self.max_parallel_validations)
 				.await
 				.map_err(|e| SubsystemError::with_origin("candidate-backing", e))
 		}
@@ -231,6 +250,11 @@ struct PerRelayParentState {
 	awaiting_validation: HashSet<CandidateHash>,
 	/// Data needed for retrying in case of `ValidatedCandidateCommand::AttestNoPoV`.
 	fallbacks: HashMap<CandidateHash, AttestingData>,
+	/// Limit on validations in flight, shared by all relay parents.
+	validation_limiter: ValidationLimiter,
+	/// Locks serializing the validations of conflicting candidates, i.e. candidates of the same
+	/// para building on the same parent head, keyed by para and parent head hash.
+	conflicting_validations: HashMap<(ParaId, Hash), Arc<AsyncMutex<()>>>,
 	/// The minimum backing votes threshold.
 	minimum_backing_votes: u32,
 	/// The number of cores.
@@ -256,7 +280,7 @@ struct PerSessionCache {
 	/// Cache for storing validators list, retrieved from the runtime.
 	validators_cache: LruMap<SessionIndex, Arc<Vec<ValidatorId>>>,
 	/// Cache for storing node features, retrieved from the runtime.
-	node_features_cache: LruMap<SessionIndex, NodeFeatures>,
+	runtime_info: RuntimeInfo,
 	/// Cache for storing executor parameters, retrieved from the runtime.
 	executor_params_cache: LruMap<SessionIndex, Arc<ExecutorParams>>,
 	/// Cache for storing the minimum backing votes threshold, retrieved from the runtime.
@@ -278,7 +302,10 @@ impl PerSessionCache {
 	fn new(capacity: u32) -> Self {
 		PerSessionCache {
 			validators_cache: LruMap::new(ByLength::new(capacity)),
-			node_features_cache: LruMap::new(ByLength::new(capacity)),
+			runtime_info: RuntimeInfo::new_with_config(runtime::Config {
+				keystore: None,
+				session_cache_lru_size: capacity,
+			}),
 			executor_params_cache: LruMap::new(ByLength::new(capacity)),
 			minimum_backing_votes_cache: LruMap::new(ByLength::new(capacity)),
 			validator_to_group_cache: LruMap::new(ByLength::new(capacity)),
@@ -319,24 +346,16 @@ impl PerSessionCache {
 		parent: Hash,
 		sender: &mut impl overseer::SubsystemSender<RuntimeApiMessage>,
 	) -> Result<NodeFeatures, RuntimeApiError> {
-		// Try to get the node features from the cache.
-		if let Some(node_features) = self.node_features_cache.get(&session_index) {
-			return Ok(node_features.clone());
-		}
-
-		// Fetch the node features from the runtime since it was not in the cache.
-		let node_features = request_node_features(parent, session_index, sender)
+		self.runtime_info
+			.get_node_features(sender, parent, session_index)
 			.await
-			.await
-			.map_err(|err| RuntimeApiError::Execution {
-				runtime_api_name: "NodeFeatures",
-				source: Arc::new(err),
-			})??;
-
-		// Cache the fetched node features for future use.
-		self.node_features_cache.insert(session_index, node_features.clone());
-
-		Ok(node_features)
+			.map_err(|err| match err {
+				runtime::Error::RuntimeRequest(err) => err,
+				err => RuntimeApiError::Execution {
+					runtime_api_name: "NodeFeatures",
+					source: Arc::new(err),
+				},
+			})
 	}
 
 	/// Gets the executor parameters from the cache or
@@ -446,6 +465,8 @@ struct State {
 	/// A clonable sender which is dispatched to background candidate validation tasks to inform
 	/// the main task of the result.
 	background_validation_tx: mpsc::Sender<(Hash, ValidatedCandidateCommand)>,
+	/// Limit on the number of background validations running in parallel.
+	validation_limiter: ValidationLimiter,
 	/// The handle to the keystore used for signing.
 	keystore: KeystorePtr,
 }
@@ -454,6 +475,7 @@ impl State {
 	fn new(
 		background_validation_tx: mpsc::Sender<(Hash, ValidatedCandidateCommand)>,
 		keystore: KeystorePtr,
+		max_parallel_validations: usize,
 	) -> Self {
 		State {
 			implicit_view: ImplicitView::default(),
@@ -461,6 +483,7 @@ impl State {
 			per_candidate: HashMap::new(),
 			per_session_cache: PerSessionCache::default(),
 			background_validation_tx,
+			validation_limiter: ValidationLimiter::new(max_parallel_validations),
 			keystore,
 		}
 	}
@@ -471,9 +494,10 @@ async fn run<Context>(
 	mut ctx: Context,
 	keystore: KeystorePtr,
 	metrics: Metrics,
+	max_parallel_validations: usize,
 ) -> FatalResult<()> {
 	let (background_validation_tx, mut background_validation_rx) = mpsc::channel(16);
-	let mut state = State::new(background_validation_tx, keystore);
+	let mut state = State::new(background_validation_tx, keystore, max_parallel_validations);
 
 	loop {
 		let res =
@@ -800,6 +824,8 @@ async fn validate_and_make_available(
 		impl Fn(BackgroundValidationResult) -> ValidatedCandidateCommand + Sync,
 	>,
 	core_index: CoreIndex,
+	validation_limiter: ValidationLimiter,
+	conflict_lock: Arc<AsyncMutex<()>>,
 ) -> Result<(), Error> {
 	let BackgroundValidationParams {
 		mut sender,
@@ -860,6 +886,12 @@ async fn validate_and_make_available(
 			},
 	};
 
+	// Only wait for our turn once the PoV is there, so fetching it does not count against the
+	// validation limit. Conflicting candidates are validated one after the other, without holding
+	// a permit while waiting for each other.
+	let _conflict_guard = conflict_lock.lock().await;
+	let _permit = validation_limiter.acquire(core_index).await;
+
 	let v = {
 		request_candidate_validation(
 			&mut sender,
@@ -1038,6 +1070,7 @@ async fn handle_active_leaves_update<Context>(
 			maybe_new,
 			&state.keystore,
 			&mut state.per_session_cache,
+			&state.validation_limiter,
 		)
 		.await?;
 
@@ -1137,6 +1170,7 @@ async fn construct_per_relay_parent_state<Context>(
 	relay_parent: Hash,
 	keystore: &KeystorePtr,
 	per_session_cache: &mut PerSessionCache,
+	validation_limiter: &ValidationLimiter,
 ) -> Result<Option<PerRelayParentState>, Error> {
 	let parent = relay_parent;
 
@@ -1230,6 +1264,8 @@ async fn construct_per_relay_parent_state<Context>(
 		issued_statements: HashSet::new(),
 		awaiting_validation: HashSet::new(),
 		fallbacks: HashMap::new(),
+		validation_limiter: validation_limiter.clone(),
+		conflicting_validations: HashMap::new(),
 		minimum_backing_votes,
 		n_cores: validator_groups.len() as u32,
 		claim_queue: ClaimQueueSnapshot::from(claim_queue),
@@ -1756,10 +1792,24 @@ async fn background_validate_and_make_available<Context>(
 ) -> Result<(), Error> {
 	let candidate_hash = params.candidate.hash();
 	let Some(core_index) = rp_state.assigned_core else { return Ok(()) };
+	// Only one validation per candidate at a time, validations of distinct candidates run in
+	// parallel up to the limit.
 	if rp_state.awaiting_validation.insert(candidate_hash) {
+		let validation_limiter = rp_state.validation_limiter.clone();
+		let conflict_lock = rp_state
+			.conflicting_validations
+			.entry((
+				params.candidate.descriptor.para_id(),
+				params.persisted_validation_data.parent_head.hash(),
+			))
+			.or_default()
+			.clone();
 		// spawn background task.
 		let bg = async move {
-			if let Err(error) = validate_and_make_available(params, core_index).await {
+			if let Err(error) =
+				validate_and_make_available(params, core_index, validation_limiter, conflict_lock)
+					.await
+			{
 				if let Error::BackgroundValidationMpsc(error) = error {
 					gum::debug!(
 						target: LOG_TARGET,
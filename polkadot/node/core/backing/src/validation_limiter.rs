@@ -0,0 +1,168 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bounding the number of candidate validations running in parallel.
+//!
+//! Every validation runs in its own background task, but only [`ValidationLimiter::new`]'s
+//! `max_parallel` of them get past [`ValidationLimiter::acquire`] at a time. The others wait in a
+//! queue per core, which are served round-robin, so a core receiving a lot of candidates does not
+//! delay validation of candidates for the other cores we back.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::{Arc, Mutex},
+};
+
+use futures::channel::oneshot;
+use polkadot_primitives::CoreIndex;
+
+/// Shared limit on the number of validations in flight.
+#[derive(Clone)]
+pub struct ValidationLimiter(Arc<Mutex<Inner>>);
+
+struct Inner {
+	max_parallel: usize,
+	in_flight: usize,
+	/// Validations waiting for a permit, per core.
+	waiting: HashMap<CoreIndex, VecDeque<oneshot::Sender<ValidationPermit>>>,
+	/// Cores with waiting validations, in the order they are served.
+	cores: VecDeque<CoreIndex>,
+}
+
+/// Permission to run a validation. The slot is released on drop.
+pub struct ValidationPermit(Option<ValidationLimiter>);
+
+impl ValidationLimiter {
+	/// Create a limiter allowing `max_parallel` validations to run at the same time.
+	///
+	/// `max_parallel` is at least one.
+	pub fn new(max_parallel: usize) -> Self {
+		Self(Arc::new(Mutex::new(Inner {
+			max_parallel: max_parallel.max(1),
+			in_flight: 0,
+			waiting: HashMap::new(),
+			cores: VecDeque::new(),
+		})))
+	}
+
+	/// Wait until a validation for a candidate on `core` may run.
+	pub async fn acquire(&self, core: CoreIndex) -> ValidationPermit {
+		let rx = {
+			let mut inner = self.0.lock().expect("lock is never poisoned; qed");
+			if inner.in_flight < inner.max_parallel {
+				inner.in_flight += 1;
+				return ValidationPermit(Some(self.clone()))
+			}
+			let (tx, rx) = oneshot::channel();
+			let queue = inner.waiting.entry(core).or_default();
+			if queue.is_empty() {
+				inner.cores.push_back(core);
+			}
+			queue.push_back(tx);
+			rx
+		};
+
+		// The sender is only dropped without sending once the limiter is gone.
+		rx.await.unwrap_or(ValidationPermit(None))
+	}
+
+	/// The number of validations currently running.
+	pub fn in_flight(&self) -> usize {
+		self.0.lock().expect("lock is never poisoned; qed").in_flight
+	}
+
+	/// Hand the slot of a finished validation to the next waiting one, round-robin across cores.
+	fn release(&self) {
+		let mut inner = self.0.lock().expect("lock is never poisoned; qed");
+		while let Some(core) = inner.cores.pop_front() {
+			let Some(queue) = inner.waiting.get_mut(&core) else { continue };
+			let next = queue.pop_front();
+			if queue.is_empty() {
+				inner.waiting.remove(&core);
+			} else {
+				inner.cores.push_back(core);
+			}
+
+			let Some(tx) = next else { continue };
+			match tx.send(ValidationPermit(Some(self.clone()))) {
+				Ok(()) => return,
+				// The waiting validation was dropped, try the next one without releasing again.
+				Err(mut permit) => permit.0 = None,
+			}
+		}
+		inner.in_flight -= 1;
+	}
+}
+
+impl Drop for ValidationPermit {
+	fn drop(&mut self) {
+		if let Some(limiter) = self.0.take() {
+			limiter.release();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{executor::block_on, FutureExt};
+
+	#[test]
+	fn permits_are_bounded_and_served_round_robin() {
+		let limiter = ValidationLimiter::new(1);
+		let running = block_on(limiter.acquire(CoreIndex(0)));
+
+		let mut first_a = limiter.acquire(CoreIndex(0)).boxed();
+		let mut second_a = limiter.acquire(CoreIndex(0)).boxed();
+		let mut first_b = limiter.acquire(CoreIndex(1)).boxed();
+		assert!((&mut first_a).now_or_never().is_none());
+		assert!((&mut second_a).now_or_never().is_none());
+		assert!((&mut first_b).now_or_never().is_none());
+		assert_eq!(limiter.in_flight(), 1);
+
+		drop(running);
+		let permit = (&mut first_a).now_or_never().expect("first waiting gets the slot");
+		assert!((&mut second_a).now_or_never().is_none());
+
+		// core 1 is served before the second candidate of core 0.
+		drop(permit);
+		let permit = (&mut first_b).now_or_never().expect("other core is next");
+		assert!((&mut second_a).now_or_never().is_none());
+
+		drop(permit);
+		let permit = (&mut second_a).now_or_never().expect("last one");
+		assert_eq!(limiter.in_flight(), 1);
+		drop(permit);
+		assert_eq!(limiter.in_flight(), 0);
+	}
+
+	#[test]
+	fn dropped_waiters_are_skipped() {
+		let limiter = ValidationLimiter::new(1);
+		let running = block_on(limiter.acquire(CoreIndex(0)));
+
+		let mut dropped = limiter.acquire(CoreIndex(0)).boxed();
+		let mut waiting = limiter.acquire(CoreIndex(1)).boxed();
+		assert!((&mut dropped).now_or_never().is_none());
+		assert!((&mut waiting).now_or_never().is_none());
+		drop(dropped);
+
+		drop(running);
+		let permit = (&mut waiting).now_or_never().expect("dropped waiter is skipped");
+		drop(permit);
+		assert_eq!(limiter.in_flight(), 0);
+	}
+}
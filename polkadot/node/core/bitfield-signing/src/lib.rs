@@ -45,8 +45,10 @@ use self::metrics::Metrics;
 #[cfg(test)]
 mod tests;
 
-/// Delay between starting a bitfield signing job and its attempting to create a bitfield.
-const SPAWNED_TASK_DELAY: Duration = Duration::from_millis(1500);
+/// Default delay between starting a bitfield signing job and its attempting to create a bitfield.
+pub const SPAWNED_TASK_DELAY: Duration = Duration::from_millis(1500);
+/// How often the availability store is queried while waiting for all chunks, with early signing.
+const EARLY_SIGNING_POLL_INTERVAL: Duration = Duration::from_millis(100);
 const LOG_TARGET: &str = "parachain::bitfield-signing";
 
 // TODO: use `fatality` (https://github.com/paritytech/polkadot/issues/5540).
@@ -121,6 +123,48 @@ async fn construct_availability_bitfield(
 	let availability_cores =
 		{ recv_runtime(request_availability_cores(relay_parent, sender).await).await? };
 
+	query_availability_bitfield(relay_parent, &availability_cores, validator_idx, sender).await
+}
+
+/// Like `construct_availability_bitfield`, but waiting until `wait_until` for chunks to arrive.
+///
+/// The availability store is queried every `EARLY_SIGNING_POLL_INTERVAL` and the bitfield is
+/// returned as soon as we have our chunks of all occupied cores, or once `wait_until` passed.
+async fn construct_availability_bitfield_early(
+	relay_parent: Hash,
+	validator_idx: ValidatorIndex,
+	sender: &mut impl overseer::BitfieldSigningSenderTrait,
+	wait_until: Instant,
+) -> Result<AvailabilityBitfield, Error> {
+	let availability_cores =
+		{ recv_runtime(request_availability_cores(relay_parent, sender).await).await? };
+
+	loop {
+		let bitfield =
+			query_availability_bitfield(relay_parent, &availability_cores, validator_idx, sender)
+				.await?;
+
+		let all_available = availability_cores
+			.iter()
+			.zip(bitfield.0.iter())
+			.all(|(core, available)| !matches!(core, CoreState::Occupied(_)) || *available);
+		let now = Instant::now();
+		if all_available || now >= wait_until {
+			gum::trace!(target: LOG_TARGET, ?relay_parent, all_available, "Availability bitfield ready");
+			return Ok(bitfield)
+		}
+
+		Delay::new(EARLY_SIGNING_POLL_INTERVAL.min(wait_until - now)).await?;
+	}
+}
+
+/// Determine chunk availability of all `availability_cores` concurrently.
+async fn query_availability_bitfield(
+	relay_parent: Hash,
+	availability_cores: &[CoreState],
+	validator_idx: ValidatorIndex,
+	sender: &mut impl overseer::BitfieldSigningSenderTrait,
+) -> Result<AvailabilityBitfield, Error> {
 	// Wrap the sender in a Mutex to share it between the futures.
 	//
 	// We use a `Mutex` here to not `clone` the sender inside the future, because
@@ -149,16 +193,39 @@ async fn construct_availability_bitfield(
 	Ok(AvailabilityBitfield(core_bits))
 }
 
+/// Configuration for the bitfield signing subsystem.
+#[derive(Debug, Clone, Copy)]
+pub struct BitfieldSigningConfig {
+	/// How long to wait after a leaf got activated before signing the bitfield for it.
+	pub signing_delay: Duration,
+	/// Sign as soon as we have our chunks of all occupied cores, instead of always waiting for
+	/// `signing_delay`.
+	pub early_signing: bool,
+}
+
+impl Default for BitfieldSigningConfig {
+	fn default() -> Self {
+		Self { signing_delay: SPAWNED_TASK_DELAY, early_signing: false }
+	}
+}
+
 /// The bitfield signing subsystem.
 pub struct BitfieldSigningSubsystem {
 	keystore: KeystorePtr,
 	metrics: Metrics,
+	config: BitfieldSigningConfig,
 }
 
 impl BitfieldSigningSubsystem {
 	/// Create a new instance of the `BitfieldSigningSubsystem`.
 	pub fn new(keystore: KeystorePtr, metrics: Metrics) -> Self {
-		Self { keystore, metrics }
+		Self { keystore, metrics, config: BitfieldSigningConfig::default() }
+	}
+
+	/// Use the given configuration instead of the default one.
+	pub fn with_config(mut self, config: BitfieldSigningConfig) -> Self {
+		self.config = config;
+		self
 	}
 }
 
@@ -166,7 +233,7 @@ impl BitfieldSigningSubsystem {
 impl<Context> BitfieldSigningSubsystem {
 	fn start(self, ctx: Context) -> SpawnedSubsystem {
 		let future = async move {
-			run(ctx, self.keystore, self.metrics)
+			run(ctx, self.keystore, self.metrics, self.config)
 				.await
 				.map_err(|e| SubsystemError::with_origin("bitfield-signing", e))
 		}
@@ -181,6 +248,7 @@ async fn run<Context>(
 	mut ctx: Context,
 	keystore: KeystorePtr,
 	metrics: Metrics,
+	config: BitfieldSigningConfig,
 ) -> SubsystemResult<()> {
 	// Track spawned jobs per active leaf.
 	let mut running = HashMap::<Hash, future::AbortHandle>::new();
@@ -204,6 +272,7 @@ async fn run<Context>(
 						leaf,
 						keystore.clone(),
 						metrics.clone(),
+						config,
 					));
 
 					running.insert(leaf_hash, handle);
@@ -223,11 +292,12 @@ async fn handle_active_leaves_update<Sender>(
 	leaf: ActivatedLeaf,
 	keystore: KeystorePtr,
 	metrics: Metrics,
+	config: BitfieldSigningConfig,
 ) -> Result<(), Error>
 where
 	Sender: overseer::BitfieldSigningSenderTrait,
 {
-	let wait_until = Instant::now() + SPAWNED_TASK_DELAY;
+	let wait_until = Instant::now() + config.signing_delay;
 
 	// now do all the work we can before we need to wait for the availability store
 	// if we're not a validator, we can just succeed effortlessly
@@ -237,23 +307,31 @@ where
 		Err(err) => return Err(Error::Util(err)),
 	};
 
-	// wait a bit before doing anything else
-	Delay::new_at(wait_until).await?;
+	// wait a bit before doing anything else, unless we sign as soon as all chunks are there
+	if !config.early_signing {
+		Delay::new_at(wait_until).await?;
+	}
 
 	// this timer does not appear at the head of the function because we don't want to include
-	// SPAWNED_TASK_DELAY each time.
+	// the signing delay each time.
 	let _timer = metrics.time_run();
 
-	let bitfield =
-		match construct_availability_bitfield(leaf.hash, validator.index(), &mut sender).await {
-			Err(Error::Runtime(runtime_err)) => {
-				// Don't take down the node on runtime API errors.
-				gum::warn!(target: LOG_TARGET, err = ?runtime_err, "Encountered a runtime API error");
-				return Ok(())
-			},
-			Err(err) => return Err(err),
-			Ok(bitfield) => bitfield,
-		};
+	let bitfield = if config.early_signing {
+		construct_availability_bitfield_early(leaf.hash, validator.index(), &mut sender, wait_until)
+			.await
+	} else {
+		construct_availability_bitfield(leaf.hash, validator.index(), &mut sender).await
+	};
+
+	let bitfield = match bitfield {
+		Err(Error::Runtime(runtime_err)) => {
+			// Don't take down the node on runtime API errors.
+			gum::warn!(target: LOG_TARGET, err = ?runtime_err, "Encountered a runtime API error");
+			return Ok(())
+		},
+		Err(err) => return Err(err),
+		Ok(bitfield) => bitfield,
+	};
 
 	let signed_bitfield =
 		match validator.sign(keystore, bitfield).map_err(|e| Error::Keystore(e))? {
@@ -78,3 +78,61 @@ fn construct_availability_bitfield_works() {
 		}
 	});
 }
+
+#[test]
+fn early_signing_waits_for_all_occupied_cores() {
+	block_on(async move {
+		let relay_parent = Hash::default();
+		let validator_index = ValidatorIndex(1u32);
+		let start = Instant::now();
+		let wait_until = start + Duration::from_secs(60);
+
+		let (mut sender, mut receiver) = polkadot_node_subsystem_test_helpers::sender_receiver();
+		let future = construct_availability_bitfield_early(
+			relay_parent,
+			validator_index,
+			&mut sender,
+			wait_until,
+		)
+		.fuse();
+		pin_mut!(future);
+
+		let hash_a = CandidateHash(Hash::repeat_byte(1));
+		let hash_b = CandidateHash(Hash::repeat_byte(2));
+		let mut queries_b = 0;
+
+		loop {
+			futures::select! {
+				m = receiver.next() => match m.unwrap() {
+					AllMessages::RuntimeApi(
+						RuntimeApiMessage::Request(rp, RuntimeApiRequest::AvailabilityCores(tx)),
+					) => {
+						assert_eq!(relay_parent, rp);
+						tx.send(Ok(vec![CoreState::Free, occupied_core(1, hash_a), occupied_core(2, hash_b)])).unwrap();
+					}
+					AllMessages::AvailabilityStore(
+						AvailabilityStoreMessage::QueryChunkAvailability(c_hash, _, tx),
+					) => {
+						// the chunk of `hash_b` only arrives on the second poll.
+						if c_hash == hash_b {
+							queries_b += 1;
+						}
+						tx.send(c_hash == hash_a || queries_b > 1).unwrap();
+					},
+					o => panic!("Unknown message: {:?}", o),
+				},
+				r = future => match r {
+					Ok(r) => {
+						assert!(!r.0.get(0).unwrap());
+						assert!(r.0.get(1).unwrap());
+						assert!(r.0.get(2).unwrap());
+						assert_eq!(queries_b, 2);
+						assert!(Instant::now() < wait_until);
+						break
+					},
+					Err(e) => panic!("Failed: {:?}", e),
+				},
+			}
+		}
+	});
+}
@@ -0,0 +1,109 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Determinism cross-checks of the PVF executions.
+//!
+//! A configurable share of the approval executions is run a second time with a different
+//! executor configuration, once the approval result was sent back. The native stack limit of the
+//! second run is doubled: the PVF is compiled again into a separate artifact and executed with a
+//! different stack layout, while the deterministic logical stack limit must keep the outcome
+//! unchanged. Differing outcomes are logged and counted, as an early warning of non-determinism
+//! before it leads to disputes.
+
+use polkadot_node_primitives::{InvalidCandidate, ValidationResult};
+use polkadot_node_subsystem::messages::ValidationFailed;
+use polkadot_primitives::{
+	executor_params::DEFAULT_NATIVE_STACK_MAX, CandidateHash, ExecutorParam, ExecutorParams,
+};
+
+/// Whether the approval execution of the candidate is cross-checked, given the percentage of the
+/// executions to cross-check.
+///
+/// The selection only depends on the candidate hash, so that it is reproducible.
+pub(crate) fn is_selected(candidate_hash: &CandidateHash, percent: u8) -> bool {
+	let bytes = candidate_hash.0.to_fixed_bytes();
+	let sample = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) % 100;
+	sample < percent as u32
+}
+
+/// The executor parameters of the cross-check run, derived from the ones of the session.
+pub(crate) fn cross_check_executor_params(executor_params: &ExecutorParams) -> ExecutorParams {
+	let native_stack_max = executor_params
+		.iter()
+		.find_map(|param| match param {
+			ExecutorParam::StackNativeMax(max) => Some(*max),
+			_ => None,
+		})
+		.unwrap_or(DEFAULT_NATIVE_STACK_MAX);
+
+	let params = executor_params
+		.iter()
+		.filter(|param| !matches!(param, ExecutorParam::StackNativeMax(_)))
+		.cloned()
+		.chain(std::iter::once(ExecutorParam::StackNativeMax(native_stack_max.saturating_mul(2))))
+		.collect::<Vec<_>>();
+
+	ExecutorParams::from(&params[..])
+}
+
+/// The outcome of a validation, as far as the cross-checks are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Verdict {
+	Valid,
+	Invalid,
+	/// The execution failed for reasons that are not deterministic, e.g. a timeout.
+	Inconclusive,
+}
+
+impl From<&Result<ValidationResult, ValidationFailed>> for Verdict {
+	fn from(result: &Result<ValidationResult, ValidationFailed>) -> Self {
+		match result {
+			Ok(ValidationResult::Valid(..)) => Verdict::Valid,
+			Ok(ValidationResult::Invalid(InvalidCandidate::Timeout)) | Err(_) =>
+				Verdict::Inconclusive,
+			Ok(ValidationResult::Invalid(_)) => Verdict::Invalid,
+		}
+	}
+}
+
+/// The result of a cross-check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+	/// Both executions agree on the validity of the candidate.
+	Agreed,
+	/// The executions disagree on the validity of the candidate.
+	Discrepancy,
+	/// One of the executions failed for non-deterministic reasons.
+	Inconclusive,
+}
+
+impl Outcome {
+	pub(crate) fn compare(original: Verdict, cross_check: Verdict) -> Self {
+		match (original, cross_check) {
+			(Verdict::Inconclusive, _) | (_, Verdict::Inconclusive) => Outcome::Inconclusive,
+			(original, cross_check) if original == cross_check => Outcome::Agreed,
+			_ => Outcome::Discrepancy,
+		}
+	}
+
+	pub(crate) fn as_label(&self) -> &'static str {
+		match self {
+			Outcome::Agreed => "agreed",
+			Outcome::Discrepancy => "discrepancy",
+			Outcome::Inconclusive => "inconclusive",
+		}
+	}
+}
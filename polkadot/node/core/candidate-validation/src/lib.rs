@@ -74,6 +74,7 @@ use std::{
 
 use async_trait::async_trait;
 
+mod cross_check;
 mod metrics;
 use self::metrics::Metrics;
 
@@ -114,6 +115,9 @@ pub struct Config {
 	pub pvf_prepare_workers_soft_max_num: usize,
 	/// The absolute number of pvf workers that can be spawned in the pvf prepare pool.
 	pub pvf_prepare_workers_hard_max_num: usize,
+	/// The percentage of the approval executions that are executed again with a different
+	/// executor configuration to cross-check their determinism. `0` disables the cross-checks.
+	pub pvf_cross_check_percent: u8,
 }
 
 /// The candidate validation subsystem.
@@ -175,6 +179,7 @@ fn handle_validation_message<S>(
 	mut sender: S,
 	validation_host: ValidationHost,
 	metrics: Metrics,
+	cross_check_percent: u8,
 	msg: CandidateValidationMessage,
 ) -> Pin<Box<dyn Future<Output = ()> + Send>>
 where
@@ -230,6 +235,19 @@ where
 				return
 			};
 
+			let cross_check = (exec_kind == PvfExecKind::Approval &&
+				cross_check::is_selected(&candidate_receipt.hash(), cross_check_percent))
+			.then(|| {
+				(
+					validation_host.clone(),
+					validation_data.clone(),
+					validation_code.clone(),
+					candidate_receipt.clone(),
+					pov.clone(),
+					cross_check::cross_check_executor_params(&executor_params),
+				)
+			});
+
 			let res = validate_candidate_exhaustive(
 				session_index,
 				validation_host,
@@ -246,7 +264,61 @@ where
 			.await;
 
 			metrics.on_validation_event(&res);
+			let verdict = cross_check::Verdict::from(&res);
 			let _ = response_sender.send(res);
+
+			// The cross-check only runs once the approval result was sent back, so that it
+			// doesn't delay the approval.
+			let Some((
+				validation_host,
+				validation_data,
+				validation_code,
+				candidate_receipt,
+				pov,
+				executor_params,
+			)) = cross_check
+			else {
+				return
+			};
+			let candidate_hash = candidate_receipt.hash();
+			let para_id = candidate_receipt.descriptor.para_id();
+
+			let cross_check_res = validate_candidate_exhaustive(
+				session_index,
+				validation_host,
+				validation_data,
+				validation_code,
+				candidate_receipt,
+				pov,
+				executor_params,
+				exec_kind,
+				&metrics,
+				None,
+				validation_code_bomb_limit,
+			)
+			.await;
+			let cross_check_verdict = cross_check::Verdict::from(&cross_check_res);
+
+			let outcome = cross_check::Outcome::compare(verdict, cross_check_verdict);
+			match outcome {
+				cross_check::Outcome::Discrepancy => gum::warn!(
+					target: LOG_TARGET,
+					?para_id,
+					?candidate_hash,
+					?verdict,
+					cross_check_result = ?cross_check_res,
+					"PVF execution is not deterministic: the cross-check with a different executor \
+					configuration gave a different result",
+				),
+				_ => gum::debug!(
+					target: LOG_TARGET,
+					?para_id,
+					?candidate_hash,
+					?outcome,
+					"PVF execution cross-checked",
+				),
+			}
+			metrics.on_cross_check(outcome);
 		}
 		.boxed(),
 		CandidateValidationMessage::PreCheck {
@@ -317,6 +389,7 @@ async fn run<Context>(
 		pvf_execute_workers_max_num,
 		pvf_prepare_workers_soft_max_num,
 		pvf_prepare_workers_hard_max_num,
+		pvf_cross_check_percent,
 	}: Config,
 ) -> SubsystemResult<()> {
 	let (mut validation_host, task) = polkadot_node_core_pvf::start(
@@ -355,7 +428,13 @@ async fn run<Context>(
 						Ok(FromOrchestra::Signal(OverseerSignal::BlockFinalized(..))) => {},
 						Ok(FromOrchestra::Signal(OverseerSignal::Conclude)) => return Ok(()),
 						Ok(FromOrchestra::Communication { msg }) => {
-							let task = handle_validation_message(ctx.sender().clone(), validation_host.clone(), metrics.clone(), msg);
+							let task = handle_validation_message(
+								ctx.sender().clone(),
+								validation_host.clone(),
+								metrics.clone(),
+								pvf_cross_check_percent,
+								msg,
+							);
 							tasks.push(task);
 							if tasks.len() >= TASK_LIMIT {
 								break
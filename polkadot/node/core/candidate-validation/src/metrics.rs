@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::{ValidationFailed, ValidationResult};
+use super::{cross_check::Outcome, ValidationFailed, ValidationResult};
 use polkadot_node_metrics::metrics::{self, prometheus};
 
 #[derive(Clone)]
@@ -22,6 +22,7 @@ pub(crate) struct MetricsInner {
 	pub(crate) validation_requests: prometheus::CounterVec<prometheus::U64>,
 	pub(crate) validate_from_exhaustive: prometheus::Histogram,
 	pub(crate) validate_candidate_exhaustive: prometheus::Histogram,
+	pub(crate) pvf_cross_checks: prometheus::CounterVec<prometheus::U64>,
 }
 
 /// Candidate validation metrics.
@@ -45,6 +46,13 @@ impl Metrics {
 		}
 	}
 
+	/// Record the outcome of a PVF execution cross-check.
+	pub(crate) fn on_cross_check(&self, outcome: Outcome) {
+		if let Some(metrics) = &self.0 {
+			metrics.pvf_cross_checks.with_label_values(&[outcome.as_label()]).inc();
+		}
+	}
+
 	/// Provide a timer for `validate_from_exhaustive` which observes on drop.
 	pub fn time_validate_from_exhaustive(
 		&self,
@@ -89,6 +97,16 @@ impl metrics::Metrics for Metrics {
 				))?,
 				registry,
 			)?,
+			pvf_cross_checks: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_candidate_validation_pvf_cross_checks_total",
+						"Number of PVF execution cross-checks, by outcome.",
+					),
+					&["outcome"],
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -2220,3 +2220,62 @@ fn maybe_prepare_validation_does_not_prepare_already_prepared_pvfs() {
 	assert!(state.is_next_session_authority);
 	assert_eq!(state.already_prepared_code_hashes.len(), 3);
 }
+
+#[test]
+fn cross_check_executor_params_double_the_native_stack() {
+	use polkadot_primitives::{executor_params::DEFAULT_NATIVE_STACK_MAX, ExecutorParam};
+
+	let params = ExecutorParams::from(&[ExecutorParam::WasmExtBulkMemory][..]);
+	let cross_check_params = cross_check::cross_check_executor_params(&params);
+	assert_eq!(
+		*cross_check_params,
+		vec![
+			ExecutorParam::WasmExtBulkMemory,
+			ExecutorParam::StackNativeMax(2 * DEFAULT_NATIVE_STACK_MAX)
+		]
+	);
+	assert!(cross_check_params.check_consistency().is_ok());
+	// The cross-check runs on a separately compiled artifact.
+	assert_ne!(cross_check_params.prep_hash(), params.prep_hash());
+
+	let params = ExecutorParams::from(&[ExecutorParam::StackNativeMax(1024 * 1024 * 1024)][..]);
+	assert_eq!(
+		*cross_check::cross_check_executor_params(&params),
+		vec![ExecutorParam::StackNativeMax(2 * 1024 * 1024 * 1024)]
+	);
+}
+
+#[test]
+fn cross_check_outcomes() {
+	use cross_check::{Outcome, Verdict};
+
+	let verdict = |result: Result<ValidationResult, ValidationFailed>| Verdict::from(&result);
+	assert_eq!(
+		verdict(Ok(ValidationResult::Invalid(InvalidCandidate::Timeout))),
+		Verdict::Inconclusive
+	);
+	assert_eq!(verdict(Err(ValidationFailed("internal".into()))), Verdict::Inconclusive);
+	assert_eq!(
+		verdict(Ok(ValidationResult::Invalid(InvalidCandidate::ParaHeadHashMismatch))),
+		Verdict::Invalid
+	);
+
+	assert_eq!(Outcome::compare(Verdict::Valid, Verdict::Valid), Outcome::Agreed);
+	assert_eq!(Outcome::compare(Verdict::Invalid, Verdict::Invalid), Outcome::Agreed);
+	assert_eq!(Outcome::compare(Verdict::Valid, Verdict::Invalid), Outcome::Discrepancy);
+	assert_eq!(Outcome::compare(Verdict::Invalid, Verdict::Valid), Outcome::Discrepancy);
+	assert_eq!(Outcome::compare(Verdict::Valid, Verdict::Inconclusive), Outcome::Inconclusive);
+	assert_eq!(Outcome::compare(Verdict::Inconclusive, Verdict::Invalid), Outcome::Inconclusive);
+}
+
+#[test]
+fn cross_check_selection() {
+	use polkadot_primitives::CandidateHash;
+
+	let hashes = (0..100u64).map(|i| CandidateHash(Hash::from_low_u64_be(i)));
+
+	assert!(hashes.clone().all(|hash| !cross_check::is_selected(&hash, 0)));
+	assert!(hashes.clone().all(|hash| cross_check::is_selected(&hash, 100)));
+	assert!(hashes.clone().any(|hash| cross_check::is_selected(&hash, 50)));
+	assert!(!hashes.clone().all(|hash| cross_check::is_selected(&hash, 50)));
+}
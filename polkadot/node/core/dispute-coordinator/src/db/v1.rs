@@ -360,6 +360,22 @@ pub(crate) fn note_earliest_session(
 	Ok(())
 }
 
+/// Resume the cleanup of the votes of pruned sessions.
+///
+/// Votes are deleted in batches of `MAX_CLEAN_BATCH_SIZE` sessions whenever the earliest session is
+/// written, which otherwise only happens on session changes. Rewriting it in between works off
+/// large backlogs, e.g. after the retention window got shortened, without waiting for many
+/// sessions.
+pub(crate) fn resume_vote_cleanup(
+	overlay_db: &mut OverlayedBackend<'_, impl Backend>,
+) -> FatalResult<()> {
+	if let Some(earliest_session) = overlay_db.load_earliest_session()? {
+		overlay_db.write_earliest_session(earliest_session);
+	}
+
+	Ok(())
+}
+
 /// Until what session votes have been cleaned up already.
 ///
 /// That is the db has already been purged of votes for sessions older than the returned
@@ -473,6 +489,59 @@ mod tests {
 			.is_some());
 	}
 
+	#[test]
+	fn vote_cleanup_can_be_resumed() {
+		let mut backend = make_db();
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		let earliest_session = 2 * MAX_CLEAN_BATCH_SIZE + 1;
+
+		overlay_db.write_earliest_session(0);
+		let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+
+		for session in 0..=earliest_session {
+			overlay_db.write_candidate_votes(
+				session,
+				candidate_hash,
+				CandidateVotes {
+					candidate_receipt: dummy_candidate_receipt_v2(dummy_hash()),
+					valid: Vec::new(),
+					invalid: Vec::new(),
+				},
+			);
+		}
+		let write_ops = overlay_db.into_write_ops();
+		backend.write(write_ops).unwrap();
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		note_earliest_session(&mut overlay_db, earliest_session).unwrap();
+		let write_ops = overlay_db.into_write_ops();
+		backend.write(write_ops).unwrap();
+
+		// Only the first batch got cleaned up:
+		assert!(backend
+			.load_candidate_votes(MAX_CLEAN_BATCH_SIZE, &candidate_hash)
+			.unwrap()
+			.is_some());
+
+		for _ in 0..2 {
+			let mut overlay_db = OverlayedBackend::new(&backend);
+			resume_vote_cleanup(&mut overlay_db).unwrap();
+			let write_ops = overlay_db.into_write_ops();
+			backend.write(write_ops).unwrap();
+		}
+
+		assert_eq!(backend.load_earliest_session().unwrap(), Some(earliest_session));
+		assert!(backend
+			.load_candidate_votes(earliest_session - 1, &candidate_hash)
+			.unwrap()
+			.is_none());
+		assert!(backend
+			.load_candidate_votes(earliest_session, &candidate_hash)
+			.unwrap()
+			.is_some());
+	}
+
 	#[test]
 	fn overlay_pre_and_post_commit_consistency() {
 		let mut backend = make_db();
@@ -19,12 +19,15 @@
 use std::{
 	collections::{BTreeMap, VecDeque},
 	sync::Arc,
+	time::Duration,
 };
 
 use futures::{
 	channel::{mpsc, oneshot},
+	future::Fuse,
 	FutureExt, StreamExt,
 };
+use futures_timer::Delay;
 
 use sc_keystore::LocalKeystore;
 
@@ -59,7 +62,7 @@ use crate::{
 	metrics::Metrics,
 	scraping::ScrapedUpdates,
 	status::{get_active_with_status, Clock},
-	DisputeCoordinatorSubsystem, LOG_TARGET,
+	DisputeCoordinatorSubsystem, DisputeRetention, LOG_TARGET,
 };
 
 use super::{
@@ -80,6 +83,9 @@ use super::{
 /// updates (and especially on startup) so the dispute coordinator won't be considered stalling.
 const CHAIN_IMPORT_MAX_BATCH_SIZE: usize = 8;
 
+/// How often the cleanup of pruned sessions is resumed in between session changes.
+const PRUNING_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 // Initial data for `dispute-coordinator`. It is provided only at first start.
 pub struct InitialData {
 	pub participations: Vec<(ParticipationPriority, ParticipationRequest)>,
@@ -118,6 +124,10 @@ pub(crate) struct Initialized {
 	/// To resolve this, we limit the amount of votes imported at once to
 	/// `CHAIN_IMPORT_MAX_BATCH_SIZE` and put the rest here for later processing.
 	chain_import_backlog: VecDeque<ScrapedOnChainVotes>,
+	/// How long dispute data is kept in the database.
+	retention: DisputeRetention,
+	/// Fires whenever the votes of pruned sessions should be cleaned up some more.
+	pruning_timer: Fuse<Delay>,
 	metrics: Metrics,
 	approval_voting_parallel_enabled: bool,
 }
@@ -134,7 +144,7 @@ impl Initialized {
 		gaps_in_cache: bool,
 	) -> Self {
 		let DisputeCoordinatorSubsystem {
-			config: _,
+			config,
 			store: _,
 			keystore,
 			metrics,
@@ -156,6 +166,8 @@ impl Initialized {
 			participation,
 			participation_receiver,
 			chain_import_backlog: VecDeque::new(),
+			retention: config.retention,
+			pruning_timer: Delay::new(PRUNING_INTERVAL).fuse(),
 			metrics,
 			approval_voting_parallel_enabled,
 		}
@@ -233,61 +245,72 @@ impl Initialized {
 			gum::trace!(target: LOG_TARGET, "Waiting for message");
 			let mut overlay_db = OverlayedBackend::new(backend);
 			let default_confirm = Box::new(|| Ok(()));
-			let confirm_write =
-				match MuxedMessage::receive(ctx, &mut self.participation_receiver).await? {
-					MuxedMessage::Participation(msg) => {
-						gum::trace!(target: LOG_TARGET, "MuxedMessage::Participation");
-						let ParticipationStatement {
-							session,
+			let confirm_write = match MuxedMessage::receive(
+				ctx,
+				&mut self.participation_receiver,
+				&mut self.pruning_timer,
+			)
+			.await?
+			{
+				MuxedMessage::Participation(msg) => {
+					gum::trace!(target: LOG_TARGET, "MuxedMessage::Participation");
+					let ParticipationStatement {
+						session,
+						candidate_hash,
+						candidate_receipt,
+						outcome,
+					} = self.participation.get_participation_result(ctx, msg).await?;
+					if let Some(valid) = outcome.validity() {
+						gum::trace!(
+							target: LOG_TARGET,
+							?session,
+							?candidate_hash,
+							?valid,
+							"Issuing local statement based on participation outcome."
+						);
+						self.issue_local_statement(
+							ctx,
+							&mut overlay_db,
 							candidate_hash,
 							candidate_receipt,
-							outcome,
-						} = self.participation.get_participation_result(ctx, msg).await?;
-						if let Some(valid) = outcome.validity() {
-							gum::trace!(
-								target: LOG_TARGET,
-								?session,
-								?candidate_hash,
-								?valid,
-								"Issuing local statement based on participation outcome."
-							);
-							self.issue_local_statement(
-								ctx,
-								&mut overlay_db,
-								candidate_hash,
-								candidate_receipt,
-								session,
-								valid,
-								clock.now(),
-							)
-							.await?;
-						} else {
-							gum::warn!(target: LOG_TARGET, ?outcome, "Dispute participation failed");
-						}
+							session,
+							valid,
+							clock.now(),
+						)
+						.await?;
+					} else {
+						gum::warn!(target: LOG_TARGET, ?outcome, "Dispute participation failed");
+					}
+					default_confirm
+				},
+				MuxedMessage::Pruning => {
+					gum::trace!(target: LOG_TARGET, "MuxedMessage::Pruning");
+					self.pruning_timer = Delay::new(PRUNING_INTERVAL).fuse();
+					db::v1::resume_vote_cleanup(&mut overlay_db)?;
+					default_confirm
+				},
+				MuxedMessage::Subsystem(msg) => match msg {
+					FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(()),
+					FromOrchestra::Signal(OverseerSignal::ActiveLeaves(update)) => {
+						gum::trace!(target: LOG_TARGET, "OverseerSignal::ActiveLeaves");
+						self.process_active_leaves_update(
+							ctx,
+							&mut overlay_db,
+							update,
+							clock.now(),
+						)
+						.await?;
 						default_confirm
 					},
-					MuxedMessage::Subsystem(msg) => match msg {
-						FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(()),
-						FromOrchestra::Signal(OverseerSignal::ActiveLeaves(update)) => {
-							gum::trace!(target: LOG_TARGET, "OverseerSignal::ActiveLeaves");
-							self.process_active_leaves_update(
-								ctx,
-								&mut overlay_db,
-								update,
-								clock.now(),
-							)
-							.await?;
-							default_confirm
-						},
-						FromOrchestra::Signal(OverseerSignal::BlockFinalized(_, n)) => {
-							gum::trace!(target: LOG_TARGET, "OverseerSignal::BlockFinalized");
-							self.scraper.process_finalized_block(&n);
-							default_confirm
-						},
-						FromOrchestra::Communication { msg } =>
-							self.handle_incoming(ctx, &mut overlay_db, msg, clock.now()).await?,
+					FromOrchestra::Signal(OverseerSignal::BlockFinalized(_, n)) => {
+						gum::trace!(target: LOG_TARGET, "OverseerSignal::BlockFinalized");
+						self.scraper.process_finalized_block(&n);
+						default_confirm
 					},
-				};
+					FromOrchestra::Communication { msg } =>
+						self.handle_incoming(ctx, &mut overlay_db, msg, clock.now()).await?,
+				},
+			};
 
 			if !overlay_db.is_empty() {
 				let ops = overlay_db.into_write_ops();
@@ -336,6 +359,7 @@ impl Initialized {
 					// Fetch the last `DISPUTE_WINDOW` number of sessions unless there are no gaps
 					// in cache and we are not missing too many `SessionInfo`s
 					let prune_up_to = session_idx.saturating_sub(DISPUTE_WINDOW.get() - 1);
+					let earliest_session = self.retention.earliest_session(session_idx);
 					let fetch_lower_bound =
 						if !self.gaps_in_cache && self.highest_session_seen > prune_up_to {
 							self.highest_session_seen + 1
@@ -363,7 +387,7 @@ impl Initialized {
 
 					self.highest_session_seen = session_idx;
 
-					db::v1::note_earliest_session(overlay_db, prune_up_to)?;
+					db::v1::note_earliest_session(overlay_db, earliest_session)?;
 					self.spam_slots.prune_old(prune_up_to);
 					self.offchain_disabled_validators.prune_old(prune_up_to);
 				},
@@ -1576,6 +1600,8 @@ enum MuxedMessage {
 	Subsystem(FromOrchestra<DisputeCoordinatorMessage>),
 	/// Messages from participation workers.
 	Participation(participation::WorkerMessage),
+	/// The cleanup of votes of pruned sessions should be resumed.
+	Pruning,
 }
 
 #[overseer::contextbounds(DisputeCoordinator, prefix = self::overseer)]
@@ -1583,6 +1609,7 @@ impl MuxedMessage {
 	async fn receive<Context>(
 		ctx: &mut Context,
 		from_sender: &mut participation::WorkerMessageReceiver,
+		pruning_timer: &mut Fuse<Delay>,
 	) -> FatalResult<Self> {
 		// We are only fusing here to make `select` happy, in reality we will quit if the stream
 		// ends.
@@ -1591,6 +1618,7 @@ impl MuxedMessage {
 		futures::select!(
 			msg = from_overseer => Ok(Self::Subsystem(msg.map_err(FatalError::SubsystemReceive)?)),
 			msg = from_sender.next() => Ok(Self::Participation(msg.ok_or(FatalError::ParticipationWorkerReceiverExhausted)?)),
+			_ = pruning_timer => Ok(Self::Pruning),
 		)
 	}
 }
@@ -18,7 +18,7 @@
 //!
 //! This is the central subsystem of the node-side components which participate in disputes.
 //! This subsystem wraps a database which tracks all statements observed by all validators over some
-//! window of sessions. Votes older than this session window are pruned.
+//! window of sessions. Votes older than this session window are pruned, see [`DisputeRetention`].
 //!
 //! This subsystem will be the point which produce dispute votes, either positive or negative, based
 //! on locally-observed validation results as well as a sink for votes received by other subsystems.
@@ -130,6 +130,8 @@ pub struct DisputeCoordinatorSubsystem {
 pub struct Config {
 	/// The data column in the store to use for dispute data.
 	pub col_dispute_data: u32,
+	/// How long dispute data is kept in the store.
+	pub retention: DisputeRetention,
 }
 
 impl Config {
@@ -138,6 +140,37 @@ impl Config {
 	}
 }
 
+/// How many sessions of dispute data are kept in the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeRetention {
+	/// Keep the data of the given number of most recent sessions.
+	///
+	/// Windows shorter than [`DISPUTE_WINDOW`] are extended to it, as disputes can still be raised
+	/// for all sessions within it.
+	Sessions(SessionIndex),
+	/// Never prune any dispute data.
+	///
+	/// Only meant for forensic nodes, the store grows without bounds.
+	KeepAll,
+}
+
+impl Default for DisputeRetention {
+	fn default() -> Self {
+		Self::Sessions(DISPUTE_WINDOW.get())
+	}
+}
+
+impl DisputeRetention {
+	/// The earliest session to keep the data of, given the highest session seen.
+	pub(crate) fn earliest_session(&self, highest_session: SessionIndex) -> SessionIndex {
+		match *self {
+			Self::Sessions(sessions) =>
+				highest_session.saturating_sub(sessions.max(DISPUTE_WINDOW.get()) - 1),
+			Self::KeepAll => 0,
+		}
+	}
+}
+
 #[overseer::subsystem(DisputeCoordinator, error=SubsystemError, prefix=self::overseer)]
 impl<Context: Send> DisputeCoordinatorSubsystem {
 	fn start(self, ctx: Context) -> SpawnedSubsystem {
@@ -226,7 +259,8 @@ impl DisputeCoordinatorSubsystem {
 			let mut runtime_info = RuntimeInfo::new_with_config(RuntimeInfoConfig {
 				keystore: None,
 				session_cache_lru_size: DISPUTE_WINDOW.get(),
-			});
+			})
+			.with_metrics(self.metrics.runtime_info());
 			let mut overlay_db = OverlayedBackend::new(&mut backend);
 			let (
 				participations,
@@ -329,7 +363,7 @@ impl DisputeCoordinatorSubsystem {
 		// Prune obsolete disputes:
 		db::v1::note_earliest_session(
 			overlay_db,
-			highest_session.saturating_sub(DISPUTE_WINDOW.get() - 1),
+			self.config.retention.earliest_session(highest_session),
 		)?;
 
 		let mut participation_requests = Vec::new();
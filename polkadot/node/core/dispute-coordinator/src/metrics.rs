@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use polkadot_node_subsystem_util::metrics::{self, prometheus};
+use polkadot_node_subsystem_util::{
+	metrics::{self, prometheus},
+	runtime,
+};
 
 #[derive(Clone)]
 struct MetricsInner {
@@ -42,6 +45,8 @@ struct MetricsInner {
 	participation_priority_queue_size: prometheus::Gauge<prometheus::U64>,
 	/// Size of participation best effort queue
 	participation_best_effort_queue_size: prometheus::Gauge<prometheus::U64>,
+	/// Cache hits and misses of our `RuntimeInfo`.
+	runtime_info: runtime::Metrics,
 }
 
 /// Candidate validation metrics.
@@ -136,6 +141,11 @@ impl Metrics {
 			metrics.participation_best_effort_queue_size.set(size);
 		}
 	}
+
+	/// Metrics for the `RuntimeInfo` of the dispute coordinator.
+	pub(crate) fn runtime_info(&self) -> runtime::Metrics {
+		self.0.as_ref().map(|metrics| metrics.runtime_info.clone()).unwrap_or_default()
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -231,6 +241,7 @@ impl metrics::Metrics for Metrics {
 				"Number of disputes waiting for local participation in the best effort queue.")?,
 				registry,
 			)?,
+			runtime_info: runtime::Metrics::register(registry, "dispute-coordinator")?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -78,7 +78,7 @@ use crate::{
 	metrics::Metrics,
 	participation::{participation_full_happy_path, participation_missing_availability},
 	status::Clock,
-	Config, DisputeCoordinatorSubsystem,
+	Config, DisputeCoordinatorSubsystem, DisputeRetention,
 };
 
 use super::db::v1::DbBackend;
@@ -222,7 +222,7 @@ impl Default for TestState {
 		let db = kvdb_memorydb::create(1);
 		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[0]);
 		let db = Arc::new(db);
-		let config = Config { col_dispute_data: 0 };
+		let config = Config { col_dispute_data: 0, retention: DisputeRetention::default() };
 
 		let genesis_header = Header {
 			parent_hash: Hash::zero(),
@@ -4390,3 +4390,15 @@ async fn handle_disabled_validators_queries(
 		}
 	);
 }
+
+#[test]
+fn retention_keeps_at_least_the_dispute_window() {
+	let highest_session = 100;
+	let in_window = highest_session - (DISPUTE_WINDOW.get() - 1);
+
+	assert_eq!(DisputeRetention::default().earliest_session(highest_session), in_window);
+	assert_eq!(DisputeRetention::Sessions(1).earliest_session(highest_session), in_window);
+	assert_eq!(DisputeRetention::Sessions(50).earliest_session(highest_session), 51);
+	assert_eq!(DisputeRetention::Sessions(200).earliest_session(highest_session), 0);
+	assert_eq!(DisputeRetention::KeepAll.earliest_session(highest_session), 0);
+}
@@ -147,6 +147,11 @@ impl InterestView {
 		}
 	}
 
+	/// Returns whether the given `pvf` is in the view.
+	pub fn contains(&self, pvf: &ValidationCodeHash) -> bool {
+		self.pvfs.contains_key(pvf)
+	}
+
 	/// Returns all PVFs that previously received a judgement.
 	pub fn judgements(&self) -> impl Iterator<Item = (ValidationCodeHash, Judgement)> + '_ {
 		self.pvfs
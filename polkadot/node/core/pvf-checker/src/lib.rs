@@ -18,9 +18,20 @@
 //!
 //! This subsystem is responsible for scanning the chain for PVFs that are pending for the approval
 //! as well as submitting statements regarding them passing or not the PVF pre-checking.
-
-use futures::{channel::oneshot, future::BoxFuture, prelude::*, stream::FuturesUnordered};
-
+//!
+//! Only a random share of the PVFs is pre-checked locally right away. For the others, the
+//! statements of other validators are fetched, see [`statement_fetching`].
+
+use futures::{channel::oneshot, future::BoxFuture, pin_mut, prelude::*, stream::FuturesUnordered};
+
+use polkadot_node_network_protocol::{
+	request_response::{
+		incoming,
+		v1::{PvfCheckStatementFetchingRequest, PvfCheckStatementFetchingResponse},
+		IncomingRequest, IncomingRequestReceiver,
+	},
+	UnifiedReputationChange as Rep,
+};
 use polkadot_node_subsystem::{
 	messages::{CandidateValidationMessage, PreCheckOutcome, PvfCheckerMessage, RuntimeApiMessage},
 	overseer, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError,
@@ -28,16 +39,19 @@ use polkadot_node_subsystem::{
 };
 use polkadot_primitives::{
 	BlockNumber, Hash, PvfCheckStatement, SessionIndex, ValidationCodeHash, ValidatorId,
-	ValidatorIndex,
+	ValidatorIndex, ValidatorSignature,
 };
 use sp_keystore::KeystorePtr;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 const LOG_TARGET: &str = "parachain::pvf-checker";
 
+const COST_INVALID_REQUEST: Rep = Rep::CostMajor("Received message could not be decoded.");
+
 mod interest_view;
 mod metrics;
 mod runtime_api;
+mod statement_fetching;
 
 #[cfg(test)]
 mod tests;
@@ -47,22 +61,52 @@ use self::{
 	metrics::Metrics,
 };
 
+/// The default for [`Config::local_precheck_probability`].
+///
+/// Judgements are only adopted from more than a third of the validators, which have to pre-check
+/// the PVF locally for others to adopt their judgement.
+pub const DEFAULT_LOCAL_PRECHECK_PROBABILITY: f64 = 0.5;
+
+/// Configuration of the PVF pre-checking subsystem.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+	/// The probability with which a PVF is pre-checked locally right away.
+	///
+	/// Otherwise the judgement of other validators is adopted, if more than a third of the
+	/// session's validators signed agreeing statements. The PVF is pre-checked locally anyway if
+	/// they disagree or don't have a statement about it. `1.0` always pre-checks locally.
+	pub local_precheck_probability: f64,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self { local_precheck_probability: DEFAULT_LOCAL_PRECHECK_PROBABILITY }
+	}
+}
+
 /// PVF pre-checking subsystem.
 pub struct PvfCheckerSubsystem {
 	keystore: KeystorePtr,
+	statement_receiver: IncomingRequestReceiver<PvfCheckStatementFetchingRequest>,
+	config: Config,
 	metrics: Metrics,
 }
 
 impl PvfCheckerSubsystem {
-	pub fn new(keystore: KeystorePtr, metrics: Metrics) -> Self {
-		PvfCheckerSubsystem { keystore, metrics }
+	pub fn new(
+		keystore: KeystorePtr,
+		statement_receiver: IncomingRequestReceiver<PvfCheckStatementFetchingRequest>,
+		config: Config,
+		metrics: Metrics,
+	) -> Self {
+		PvfCheckerSubsystem { keystore, statement_receiver, config, metrics }
 	}
 }
 
 #[overseer::subsystem(PvfChecker, error=SubsystemError, prefix = self::overseer)]
 impl<Context> PvfCheckerSubsystem {
 	fn start(self, ctx: Context) -> SpawnedSubsystem {
-		let future = run(ctx, self.keystore, self.metrics)
+		let future = run(ctx, self.keystore, self.statement_receiver, self.config, self.metrics)
 			.map_err(|e| SubsystemError::with_origin("pvf-checker", e))
 			.boxed();
 
@@ -98,6 +142,18 @@ struct State {
 	/// The set of PVF hashes that we cast a vote for within the current session.
 	voted: HashSet<ValidationCodeHash>,
 
+	/// The PVFs in the view that we pre-checked locally, as opposed to adopting the judgement of
+	/// other validators.
+	checked_locally: HashSet<ValidationCodeHash>,
+
+	/// Our statements about the PVFs that we pre-checked locally, within the current session.
+	///
+	/// These are handed out to other validators on request.
+	statements: HashMap<ValidationCodeHash, (PvfCheckStatement, ValidatorSignature)>,
+
+	/// The probability with which a newly observed PVF is pre-checked locally right away.
+	local_precheck_probability: f64,
+
 	/// The collection of PVFs that are observed throughout the active heads.
 	view: InterestView,
 
@@ -113,12 +169,22 @@ struct State {
 	/// Resolving to `None` means that the request was dropped before replying.
 	currently_checking:
 		FuturesUnordered<BoxFuture<'static, Option<(PreCheckOutcome, ValidationCodeHash)>>>,
+
+	/// The futures fetching the statements of other validators about PVFs, see
+	/// [`statement_fetching::fetch_judgement`].
+	///
+	/// Each resolves to the PVF, the relay-parent it was observed in and the adopted judgement, if
+	/// any.
+	currently_fetching:
+		FuturesUnordered<BoxFuture<'static, (ValidationCodeHash, Hash, Option<Judgement>)>>,
 }
 
 #[overseer::contextbounds(PvfChecker, prefix = self::overseer)]
 async fn run<Context>(
 	mut ctx: Context,
 	keystore: KeystorePtr,
+	mut statement_receiver: IncomingRequestReceiver<PvfCheckStatementFetchingRequest>,
+	config: Config,
 	metrics: Metrics,
 ) -> SubsystemResult<()> {
 	let mut state = State {
@@ -126,12 +192,19 @@ async fn run<Context>(
 		recent_block: None,
 		latest_session: None,
 		voted: HashSet::with_capacity(16),
+		checked_locally: HashSet::new(),
+		statements: HashMap::new(),
+		local_precheck_probability: config.local_precheck_probability,
 		view: InterestView::new(),
 		currently_checking: FuturesUnordered::new(),
+		currently_fetching: FuturesUnordered::new(),
 	};
 
 	loop {
 		let mut sender = ctx.sender().clone();
+		let recv_statement_request = statement_receiver.recv(|| vec![COST_INVALID_REQUEST]).fuse();
+		pin_mut!(recv_statement_request);
+
 		futures::select! {
 			precheck_response = state.currently_checking.select_next_some() => {
 				if let Some((outcome, validation_code_hash)) = precheck_response {
@@ -148,6 +221,42 @@ async fn run<Context>(
 					// care here.
 				}
 			}
+			(validation_code_hash, relay_parent, judgement) =
+				state.currently_fetching.select_next_some() => {
+				metrics.on_judgement_fetched(judgement.is_some());
+				match judgement {
+					Some(judgement) => handle_judgement(
+						&mut state,
+						&mut sender,
+						&keystore,
+						&metrics,
+						judgement,
+						validation_code_hash,
+						false,
+					).await,
+					// The PVF is still pending, we have to find out ourselves.
+					None if state.view.contains(&validation_code_hash) => initiate_precheck(
+						&mut state,
+						&mut sender,
+						relay_parent,
+						validation_code_hash,
+						&metrics,
+					).await,
+					None => {},
+				}
+			}
+			req = recv_statement_request => match req {
+				Ok(req) => handle_statement_request(&state, req),
+				// Only happens on shutdown.
+				Err(incoming::Error::RequestChannelExhausted) => return Ok(()),
+				Err(err) => {
+					gum::debug!(
+						target: LOG_TARGET,
+						?err,
+						"failed to decode PVF check statement request",
+					);
+				},
+			},
 			from_overseer = ctx.recv().fuse() => {
 				let outcome = handle_from_overseer(
 					&mut state,
@@ -200,6 +309,22 @@ async fn handle_pvf_check(
 		},
 	};
 
+	handle_judgement(state, sender, keystore, metrics, judgement, validation_code_hash, true).await;
+}
+
+/// Handle a new judgement about a PVF and vote accordingly.
+///
+/// `checked_locally` tells whether we pre-checked the PVF ourselves. Only then is our statement
+/// about it handed out to other validators.
+async fn handle_judgement(
+	state: &mut State,
+	sender: &mut impl overseer::PvfCheckerSenderTrait,
+	keystore: &KeystorePtr,
+	metrics: &Metrics,
+	judgement: Judgement,
+	validation_code_hash: ValidationCodeHash,
+	checked_locally: bool,
+) {
 	match state.view.on_judgement(validation_code_hash, judgement) {
 		Ok(()) => (),
 		Err(()) => {
@@ -212,11 +337,15 @@ async fn handle_pvf_check(
 		},
 	}
 
+	if checked_locally {
+		state.checked_locally.insert(validation_code_hash);
+	}
+
 	match (state.credentials.as_ref(), state.recent_block, state.latest_session) {
 		// Note, the availability of credentials implies the availability of the recent block and
 		// the session index.
 		(Some(credentials), Some(recent_block), Some(session_index)) => {
-			sign_and_submit_pvf_check_statement(
+			let statement = sign_and_submit_pvf_check_statement(
 				sender,
 				keystore,
 				&mut state.voted,
@@ -228,11 +357,27 @@ async fn handle_pvf_check(
 				validation_code_hash,
 			)
 			.await;
+			if let Some(statement) = statement.filter(|_| checked_locally) {
+				state.statements.insert(validation_code_hash, statement);
+			}
 		},
 		_ => (),
 	}
 }
 
+/// Answer the request of another validator for our statement about a PVF.
+///
+/// There is no statement to hand out for PVFs whose judgement we adopted from other validators,
+/// it would not be backed by an actual pre-check.
+fn handle_statement_request(state: &State, req: IncomingRequest<PvfCheckStatementFetchingRequest>) {
+	let response = match state.statements.get(&req.payload.validation_code_hash) {
+		Some((statement, signature)) =>
+			PvfCheckStatementFetchingResponse::Statement(statement.clone(), signature.clone()),
+		None => PvfCheckStatementFetchingResponse::NotChecked,
+	};
+	let _ = req.send_response(response);
+}
+
 /// A marker for the outer loop that the subsystem should stop.
 struct Conclude;
 
@@ -292,7 +437,11 @@ async fn handle_leaves_update(
 		metrics.on_pvf_observed(outcome.newcomers.len());
 		metrics.on_pvf_left(outcome.left_num);
 		for newcomer in outcome.newcomers {
-			initiate_precheck(state, sender, activated.hash, newcomer, metrics).await;
+			if rand::random::<f64>() < state.local_precheck_probability {
+				initiate_precheck(state, sender, activated.hash, newcomer, metrics).await;
+			} else {
+				initiate_fetching(state, sender, keystore, activated.hash, newcomer);
+			}
 		}
 
 		if let Some((new_session_index, credentials)) = new_session_index {
@@ -302,13 +451,14 @@ async fn handle_leaves_update(
 			// - set (or reset) the credentials.
 			state.latest_session = Some(new_session_index);
 			state.voted.clear();
+			state.statements.clear();
 			state.credentials = credentials;
 
 			// If our node is a validator in the new session, we need to re-sign and submit all
 			// previously obtained judgements.
 			if let Some(ref credentials) = state.credentials {
 				for (code_hash, judgement) in state.view.judgements() {
-					sign_and_submit_pvf_check_statement(
+					let statement = sign_and_submit_pvf_check_statement(
 						sender,
 						keystore,
 						&mut state.voted,
@@ -320,12 +470,20 @@ async fn handle_leaves_update(
 						code_hash,
 					)
 					.await;
+					if let Some(statement) =
+						statement.filter(|_| state.checked_locally.contains(&code_hash))
+					{
+						state.statements.insert(code_hash, statement);
+					}
 				}
 			}
 		}
 	} else {
 		state.view.on_leaves_update(None, &update.deactivated);
 	}
+
+	state.checked_locally.retain(|code_hash| state.view.contains(code_hash));
+	state.statements.retain(|code_hash, _| state.view.contains(code_hash));
 }
 
 struct ActivationEffect {
@@ -429,7 +587,8 @@ async fn check_signing_credentials(
 
 /// Signs and submits a vote for or against a given validation code.
 ///
-/// If the validator already voted for the given code, this function does nothing.
+/// If the validator already voted for the given code, this function does nothing. Returns the
+/// signed statement, if any.
 async fn sign_and_submit_pvf_check_statement(
 	sender: &mut impl overseer::PvfCheckerSenderTrait,
 	keystore: &KeystorePtr,
@@ -440,7 +599,7 @@ async fn sign_and_submit_pvf_check_statement(
 	session_index: SessionIndex,
 	judgement: Judgement,
 	validation_code_hash: ValidationCodeHash,
-) {
+) -> Option<(PvfCheckStatement, ValidatorSignature)> {
 	gum::debug!(
 		target: LOG_TARGET,
 		?validation_code_hash,
@@ -459,7 +618,7 @@ async fn sign_and_submit_pvf_check_statement(
 			"already voted for this validation code",
 		);
 		metrics.on_vote_duplicate();
-		return
+		return None
 	}
 
 	voted.insert(validation_code_hash);
@@ -484,7 +643,7 @@ async fn sign_and_submit_pvf_check_statement(
 				?validation_code_hash,
 				"private key for signing is not available",
 			);
-			return
+			return None
 		},
 		Err(e) => {
 			gum::warn!(
@@ -495,11 +654,18 @@ async fn sign_and_submit_pvf_check_statement(
 				"error signing the statement: {:?}",
 				e,
 			);
-			return
+			return None
 		},
 	};
 
-	match runtime_api::submit_pvf_check_statement(sender, relay_parent, stmt, signature).await {
+	match runtime_api::submit_pvf_check_statement(
+		sender,
+		relay_parent,
+		stmt.clone(),
+		signature.clone(),
+	)
+	.await
+	{
 		Ok(()) => {
 			metrics.on_vote_submitted();
 		},
@@ -513,6 +679,8 @@ async fn sign_and_submit_pvf_check_statement(
 			);
 		},
 	}
+
+	Some((stmt, signature))
 }
 
 /// Sends a request to the candidate-validation subsystem to validate the given PVF.
@@ -557,3 +725,32 @@ async fn initiate_precheck(
 		}
 	}));
 }
+
+/// Starts fetching the statements of other validators about the given PVF, instead of
+/// pre-checking it locally.
+///
+/// The resulting future will be put into the `currently_fetching` set.
+fn initiate_fetching(
+	state: &mut State,
+	sender: &mut impl overseer::PvfCheckerSenderTrait,
+	keystore: &KeystorePtr,
+	relay_parent: Hash,
+	validation_code_hash: ValidationCodeHash,
+) {
+	gum::debug!(
+		target: LOG_TARGET,
+		?validation_code_hash,
+		?relay_parent,
+		"fetching the statements of other validators",
+	);
+
+	let judgement = statement_fetching::fetch_judgement(
+		sender.clone(),
+		keystore.clone(),
+		relay_parent,
+		validation_code_hash,
+	);
+	state
+		.currently_fetching
+		.push(Box::pin(async move { (validation_code_hash, relay_parent, judgement.await) }));
+}
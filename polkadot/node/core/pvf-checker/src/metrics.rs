@@ -26,6 +26,7 @@ struct MetricsInner {
 	votes_duplicate: prometheus::Counter<prometheus::U64>,
 	pvfs_observed: prometheus::Counter<prometheus::U64>,
 	pvfs_left: prometheus::Counter<prometheus::U64>,
+	judgements_fetched: prometheus::CounterVec<prometheus::U64>,
 }
 
 #[derive(Default, Clone)]
@@ -73,6 +74,17 @@ impl Metrics {
 			metrics.pvfs_left.inc_by(num as u64);
 		}
 	}
+
+	/// Called when fetching the statements of other validators about a PVF concluded.
+	///
+	/// `adopted` tells whether their judgement was adopted, as opposed to falling back to
+	/// pre-checking the PVF locally.
+	pub(crate) fn on_judgement_fetched(&self, adopted: bool) {
+		if let Some(metrics) = &self.0 {
+			let outcome = if adopted { "adopted" } else { "fallback" };
+			metrics.judgements_fetched.with_label_values(&[outcome]).inc();
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -124,6 +136,17 @@ the same session.",
 				)?,
 				registry,
 			)?,
+			judgements_fetched: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_pvf_precheck_judgements_fetched",
+						"The number of PVFs whose judgement was fetched from other validators, by \
+						whether it was adopted.",
+					),
+					&["outcome"],
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Self(Some(metrics)))
 	}
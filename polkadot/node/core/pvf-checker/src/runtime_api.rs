@@ -22,7 +22,8 @@ use polkadot_node_subsystem::{
 	SubsystemSender,
 };
 use polkadot_primitives::{
-	Hash, PvfCheckStatement, SessionIndex, ValidationCodeHash, ValidatorId, ValidatorSignature,
+	Hash, PvfCheckStatement, SessionIndex, SessionInfo, ValidationCodeHash, ValidatorId,
+	ValidatorSignature,
 };
 
 pub(crate) async fn session_index_for_child(
@@ -41,6 +42,16 @@ pub(crate) async fn validators(
 	runtime_api_request(sender, relay_parent, RuntimeApiRequest::Validators(tx), rx).await
 }
 
+pub(crate) async fn session_info(
+	sender: &mut impl SubsystemSender<RuntimeApiMessage>,
+	relay_parent: Hash,
+	session_index: SessionIndex,
+) -> Result<Option<SessionInfo>, RuntimeRequestError> {
+	let (tx, rx) = oneshot::channel();
+	runtime_api_request(sender, relay_parent, RuntimeApiRequest::SessionInfo(session_index, tx), rx)
+		.await
+}
+
 pub(crate) async fn submit_pvf_check_statement(
 	sender: &mut impl SubsystemSender<RuntimeApiMessage>,
 	relay_parent: Hash,
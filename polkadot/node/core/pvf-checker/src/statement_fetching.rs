@@ -0,0 +1,206 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Obtaining judgements from the PVF pre-checking statements of other validators.
+//!
+//! Pre-checking a PVF takes minutes of CPU time, which would otherwise be spent by every single
+//! validator once the PVF shows up on chain. Only a random subset of the validators pre-checks a
+//! PVF right away, the others adopt the judgement of the validators they ask, as long as more than
+//! a third of the session's validators signed agreeing statements. At least one of those is honest,
+//! which makes the adopted judgement as trustworthy as a local pre-check.
+
+use futures::future;
+use polkadot_node_network_protocol::request_response::{
+	v1::{PvfCheckStatementFetchingRequest, PvfCheckStatementFetchingResponse},
+	IfDisconnected, OutgoingRequest, Recipient, Requests,
+};
+use polkadot_node_subsystem::{messages::NetworkBridgeTxMessage, overseer};
+use polkadot_primitives::{
+	Hash, PvfCheckStatement, SessionIndex, SessionInfo, ValidationCodeHash, ValidatorIndex,
+	ValidatorSignature,
+};
+use rand::seq::SliceRandom;
+use sp_keystore::KeystorePtr;
+use sp_runtime::traits::AppVerify;
+use std::{collections::HashMap, time::Duration};
+
+use crate::{interest_view::Judgement, runtime_api, LOG_TARGET};
+
+/// The number of agreeing statements needed to adopt the judgement of other validators, in a
+/// session of `n_validators` validators.
+///
+/// Up to a third of the validators may be byzantine, so one more than that is needed for at least
+/// one statement to be backed by an honest pre-check.
+pub(crate) fn required_statements(n_validators: usize) -> usize {
+	n_validators / 3 + 1
+}
+
+/// How often validators are asked for their statements, before giving up.
+const MAX_ROUNDS: usize = 10;
+
+/// The time between two rounds of asking validators for their statements.
+///
+/// Validators asked right after the PVF showed up are most likely still pre-checking it, they are
+/// asked again in later rounds.
+const ROUND_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Obtain a judgement about the PVF from the statements of random validators of the session
+/// following `relay_parent`.
+///
+/// Resolves to `None` if the statements disagree or not enough validators made a statement in
+/// time, the PVF has to be pre-checked locally then.
+pub(crate) async fn fetch_judgement(
+	mut sender: impl overseer::PvfCheckerSenderTrait,
+	keystore: KeystorePtr,
+	relay_parent: Hash,
+	validation_code_hash: ValidationCodeHash,
+) -> Option<Judgement> {
+	let session_index =
+		runtime_api::session_index_for_child(&mut sender, relay_parent).await.ok()?;
+	let session_info =
+		match runtime_api::session_info(&mut sender, relay_parent, session_index).await {
+			Ok(Some(session_info)) => session_info,
+			_ => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?relay_parent,
+					?session_index,
+					"cannot fetch the session info to ask validators for their statements",
+				);
+				return None
+			},
+		};
+
+	let our_index = polkadot_node_subsystem_util::signing_key_and_index(
+		session_info.validators.iter(),
+		&keystore,
+	)
+	.map(|(_, index)| index);
+	let mut others: Vec<ValidatorIndex> = (0..session_info.validators.len() as u32)
+		.map(ValidatorIndex)
+		.filter(|index| Some(*index) != our_index)
+		.collect();
+	let required = required_statements(session_info.validators.len());
+	let mut statements = HashMap::new();
+
+	for round in 0..MAX_ROUNDS {
+		if round > 0 {
+			futures_timer::Delay::new(ROUND_INTERVAL).await;
+		}
+
+		let missing = required - statements.len();
+		let asked: Vec<ValidatorIndex> =
+			others.choose_multiple(&mut rand::thread_rng(), missing).copied().collect();
+		if asked.is_empty() {
+			break
+		}
+
+		let (requests, responses): (Vec<_>, Vec<_>) = asked
+			.into_iter()
+			.filter_map(|index| {
+				let authority = session_info.discovery_keys.get(index.0 as usize)?.clone();
+				let (request, response) = OutgoingRequest::new(
+					Recipient::Authority(authority),
+					PvfCheckStatementFetchingRequest { validation_code_hash },
+				);
+				Some((Requests::PvfCheckStatementFetchingV1(request), async move {
+					(index, response.await)
+				}))
+			})
+			.unzip();
+		sender
+			.send_message(NetworkBridgeTxMessage::SendRequests(
+				requests,
+				IfDisconnected::TryConnect,
+			))
+			.await;
+
+		for (index, response) in future::join_all(responses).await {
+			match response {
+				Ok(PvfCheckStatementFetchingResponse::Statement(statement, signature)) => {
+					// Validators only make a single statement per session.
+					others.retain(|other| *other != index);
+
+					if is_valid_statement(
+						&session_info,
+						session_index,
+						validation_code_hash,
+						index,
+						&statement,
+						&signature,
+					) {
+						statements.insert(index, statement.accept);
+					} else {
+						gum::debug!(
+							target: LOG_TARGET,
+							?validation_code_hash,
+							validator_index = ?index,
+							"received an invalid PVF check statement",
+						);
+					}
+				},
+				Ok(PvfCheckStatementFetchingResponse::NotChecked) => {},
+				Err(err) => gum::trace!(
+					target: LOG_TARGET,
+					?validation_code_hash,
+					validator_index = ?index,
+					?err,
+					"failed to fetch a PVF check statement",
+				),
+			}
+		}
+
+		let accepted = statements.values().filter(|accept| **accept).count();
+		if accepted != 0 && accepted != statements.len() {
+			gum::info!(
+				target: LOG_TARGET,
+				?validation_code_hash,
+				"validators disagree about the PVF, pre-checking it locally",
+			);
+			return None
+		}
+		if statements.len() >= required {
+			return Some(if accepted == 0 { Judgement::Invalid } else { Judgement::Valid })
+		}
+	}
+
+	gum::debug!(
+		target: LOG_TARGET,
+		?validation_code_hash,
+		received = statements.len(),
+		required,
+		"not enough validators made a statement about the PVF, pre-checking it locally",
+	);
+	None
+}
+
+/// Whether `statement` is a correctly signed statement of the validator at `validator_index`
+/// about the PVF.
+fn is_valid_statement(
+	session_info: &SessionInfo,
+	session_index: SessionIndex,
+	validation_code_hash: ValidationCodeHash,
+	validator_index: ValidatorIndex,
+	statement: &PvfCheckStatement,
+	signature: &ValidatorSignature,
+) -> bool {
+	let Some(validator) = session_info.validators.get(validator_index) else { return false };
+
+	statement.subject == validation_code_hash &&
+		statement.session_index == session_index &&
+		statement.validator_index == validator_index &&
+		signature.verify(&statement.signing_payload()[..], validator)
+}
@@ -14,11 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
+use codec::{Decode, Encode};
 use futures::{channel::oneshot, future::BoxFuture, prelude::*};
+use polkadot_node_network_protocol::request_response::{
+	v1::{PvfCheckStatementFetchingRequest, PvfCheckStatementFetchingResponse},
+	IfDisconnected, IncomingRequest, Recipient, ReqProtocolNames, Requests, ResponseSender,
+};
 use polkadot_node_subsystem::{
 	messages::{
-		AllMessages, CandidateValidationMessage, PreCheckOutcome, PvfCheckerMessage,
-		RuntimeApiMessage, RuntimeApiRequest,
+		AllMessages, CandidateValidationMessage, NetworkBridgeTxMessage, PreCheckOutcome,
+		PvfCheckerMessage, RuntimeApiMessage, RuntimeApiRequest,
 	},
 	ActiveLeavesUpdate, FromOrchestra, OverseerSignal, RuntimeApiError,
 };
@@ -26,12 +31,17 @@ use polkadot_node_subsystem_test_helpers::{
 	make_subsystem_context, mock::new_leaf, TestSubsystemContextHandle,
 };
 use polkadot_primitives::{
-	BlockNumber, Hash, Header, PvfCheckStatement, SessionIndex, ValidationCode, ValidationCodeHash,
-	ValidatorId,
+	AuthorityDiscoveryId, Block, BlockNumber, Hash, Header, IndexedVec, PvfCheckStatement,
+	SessionIndex, SessionInfo, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	ValidatorSignature,
 };
 use polkadot_primitives_test_helpers::{dummy_digest, dummy_hash, validator_pubkeys};
+use sc_network::{
+	config::{IncomingRequest as RawIncomingRequest, RequestResponseConfig},
+	PeerId, ProtocolName,
+};
 use sp_application_crypto::AppCrypto;
-use sp_core::testing::TaskExecutor;
+use sp_core::{testing::TaskExecutor, Pair};
 use sp_keyring::Sr25519Keyring;
 use sp_keystore::Keystore;
 use sp_runtime::traits::AppVerify;
@@ -84,6 +94,7 @@ struct LeafState {
 /// The state we model about a session.
 struct SessionState {
 	validators: Vec<ValidatorId>,
+	discovery_keys: Vec<AuthorityDiscoveryId>,
 }
 
 struct TestState {
@@ -99,7 +110,10 @@ impl TestState {
 		// Initialize the default session 1. No validators are present there.
 		let last_session_index = 1;
 		let mut sessions = HashMap::new();
-		sessions.insert(last_session_index, SessionState { validators: vec![] });
+		sessions.insert(
+			last_session_index,
+			SessionState { validators: vec![], discovery_keys: vec![] },
+		);
 
 		let mut leaves = HashMap::new();
 		leaves.insert(dummy_hash(), LeafState { session_index: last_session_index, pvfs: vec![] });
@@ -178,7 +192,14 @@ impl TestState {
 			self.last_session_index = new_session.session_index;
 			let prev = self.sessions.insert(
 				new_session.session_index,
-				SessionState { validators: validator_pubkeys(&new_session.validators) },
+				SessionState {
+					validators: validator_pubkeys(&new_session.validators),
+					discovery_keys: new_session
+						.validators
+						.iter()
+						.map(|validator| validator.public().into())
+						.collect(),
+				},
 			);
 			assert!(prev.is_none(), "Session {} already exists", new_session.session_index);
 		}
@@ -260,6 +281,82 @@ impl TestState {
 		}
 	}
 
+	/// Expects that the subsystem has sent a `SessionInfo` Runtime API request. Answers with the
+	/// mocked validators of the requested session.
+	async fn expect_session_info(&mut self, handle: &mut VirtualOverseer) {
+		match self.recv_timeout(handle).await.expect("timeout waiting for a message") {
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				_,
+				RuntimeApiRequest::SessionInfo(session_index, tx),
+			)) => {
+				let session = self.sessions.get(&session_index).unwrap();
+				tx.send(Ok(Some(SessionInfo {
+					active_validator_indices: vec![],
+					random_seed: [0; 32],
+					dispute_period: 6,
+					validators: IndexedVec::from(session.validators.clone()),
+					discovery_keys: session.discovery_keys.clone(),
+					assignment_keys: vec![],
+					validator_groups: IndexedVec::from(vec![]),
+					n_cores: 0,
+					zeroth_delay_tranche_width: 0,
+					relay_vrf_modulo_samples: 0,
+					n_delay_tranches: 0,
+					no_show_slots: 0,
+					needed_approvals: 0,
+				})))
+				.unwrap();
+			},
+			msg => panic!("Unexpected message was received: {:#?}", msg),
+		}
+	}
+
+	/// Expects that the subsystem has asked other validators of the last session for their
+	/// statements about a PVF. Returns mocked handles for the requests.
+	async fn expect_statement_requests(
+		&mut self,
+		handle: &mut VirtualOverseer,
+	) -> Vec<ExpectStatementRequest> {
+		match self.recv_timeout(handle).await.expect("timeout waiting for a message") {
+			AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendRequests(
+				requests,
+				IfDisconnected::TryConnect,
+			)) => {
+				let session = self.sessions.get(&self.last_session_index).unwrap();
+				requests
+					.into_iter()
+					.map(|request| match request {
+						Requests::PvfCheckStatementFetchingV1(request) => {
+							let authority = match request.peer {
+								Recipient::Authority(authority) => authority,
+								peer => panic!("Unexpected recipient: {:?}", peer),
+							};
+							let index = session
+								.discovery_keys
+								.iter()
+								.position(|key| *key == authority)
+								.expect("a validator of the session is asked");
+							let keyring = Sr25519Keyring::iter()
+								.find(|keyring| {
+									AuthorityDiscoveryId::from(keyring.public()) == authority
+								})
+								.unwrap();
+							ExpectStatementRequest {
+								session_index: self.last_session_index,
+								validator_index: ValidatorIndex(index as u32),
+								keyring,
+								validation_code_hash: request.payload.validation_code_hash,
+								tx: request.pending_response,
+							}
+						},
+						request => panic!("Unexpected request: {:?}", request),
+					})
+					.collect()
+			},
+			msg => panic!("Unexpected message was received: {:#?}", msg),
+		}
+	}
+
 	/// Expects that the subsystem has sent a pre-checking request to candidate-validation. Returns
 	/// a mocked handle for the request.
 	async fn expect_candidate_precheck(
@@ -337,6 +434,30 @@ impl ExpectCandidatePrecheck {
 	}
 }
 
+#[must_use]
+struct ExpectStatementRequest {
+	session_index: SessionIndex,
+	validator_index: ValidatorIndex,
+	keyring: Sr25519Keyring,
+	validation_code_hash: ValidationCodeHash,
+	tx: ResponseSender,
+}
+
+impl ExpectStatementRequest {
+	fn reply_statement(self, accept: bool) {
+		let statement = PvfCheckStatement {
+			accept,
+			subject: self.validation_code_hash,
+			session_index: self.session_index,
+			validator_index: self.validator_index,
+		};
+		let signature: ValidatorSignature =
+			self.keyring.pair().sign(&statement.signing_payload()).into();
+		let response = PvfCheckStatementFetchingResponse::Statement(statement, signature);
+		self.tx.send(Ok((response.encode(), ProtocolName::from("")))).unwrap();
+	}
+}
+
 #[must_use]
 struct ExpectSubmitVote {
 	relay_parent: Hash,
@@ -350,7 +471,25 @@ impl ExpectSubmitVote {
 	}
 }
 
+/// Run a test against a subsystem that pre-checks every PVF locally.
 fn test_harness(test: impl FnOnce(TestState, VirtualOverseer) -> BoxFuture<'static, ()>) {
+	test_harness_with_config(
+		crate::Config { local_precheck_probability: 1.0 },
+		|test_state, handle, req_cfg| {
+			async move {
+				// Keep the request channel open for the lifetime of the test.
+				let _req_cfg = req_cfg;
+				test(test_state, handle).await
+			}
+			.boxed()
+		},
+	)
+}
+
+fn test_harness_with_config(
+	config: crate::Config,
+	test: impl FnOnce(TestState, VirtualOverseer, RequestResponseConfig) -> BoxFuture<'static, ()>,
+) {
 	let pool = TaskExecutor::new();
 	let (ctx, handle) = make_subsystem_context::<PvfCheckerMessage, _>(pool.clone());
 	let keystore = Arc::new(sc_keystore::LocalKeystore::in_memory());
@@ -359,10 +498,18 @@ fn test_harness(test: impl FnOnce(TestState, VirtualOverseer) -> BoxFuture<'stat
 	Keystore::sr25519_generate_new(&*keystore, ValidatorId::ID, Some(&OUR_VALIDATOR.to_seed()))
 		.expect("Generating keys for our node failed");
 
-	let subsystem_task = crate::run(ctx, keystore, crate::Metrics::default()).map(|x| x.unwrap());
+	let req_protocol_names = ReqProtocolNames::new(&Hash::repeat_byte(0xff), None);
+	let (req_receiver, req_cfg) =
+		IncomingRequest::<PvfCheckStatementFetchingRequest>::get_config_receiver::<
+			Block,
+			sc_network::NetworkWorker<Block, Hash>,
+		>(&req_protocol_names);
+
+	let subsystem_task = crate::run(ctx, keystore, req_receiver, config, crate::Metrics::default())
+		.map(|x| x.unwrap());
 
 	let test_state = TestState::new();
-	let test_task = test(test_state, handle);
+	let test_task = test(test_state, handle, req_cfg);
 
 	futures::executor::block_on(future::join(subsystem_task, test_task));
 }
@@ -928,3 +1075,156 @@ fn dont_abstain_for_nondeterministic_pvfcheck_failure() {
 		.boxed()
 	});
 }
+
+/// Observes a new PVF in a session of four validators, while adopting the judgement of other
+/// validators.
+async fn observe_pvf_fetching_statements(test_state: &mut TestState, handle: &mut VirtualOverseer) {
+	let block = FakeLeaf::new(dummy_hash(), 1, vec![dummy_validation_code_hash(1)]);
+	test_state
+		.activate_leaf_with_session(
+			handle,
+			block,
+			StartsNewSession {
+				session_index: 2,
+				validators: vec![
+					OUR_VALIDATOR,
+					Sr25519Keyring::Bob,
+					Sr25519Keyring::Charlie,
+					Sr25519Keyring::Dave,
+				],
+			},
+		)
+		.await;
+
+	test_state.expect_pvfs_require_precheck(handle).await.reply_mock();
+	test_state.expect_session_for_child(handle).await;
+	test_state.expect_validators(handle).await;
+
+	// The session of the other validators is fetched for asking them.
+	test_state.expect_session_for_child(handle).await;
+	test_state.expect_session_info(handle).await;
+}
+
+#[test]
+fn adopts_agreeing_statements_of_other_validators() {
+	test_harness_with_config(
+		crate::Config { local_precheck_probability: 0.0 },
+		|mut test_state, mut handle, _req_cfg| {
+			async move {
+				observe_pvf_fetching_statements(&mut test_state, &mut handle).await;
+
+				let requests = test_state.expect_statement_requests(&mut handle).await;
+				assert_eq!(requests.len(), crate::statement_fetching::required_statements(4));
+				for request in requests {
+					assert_ne!(request.keyring, OUR_VALIDATOR);
+					assert_eq!(request.validation_code_hash, dummy_validation_code_hash(1));
+					request.reply_statement(false);
+				}
+
+				// No pre-check is done, the judgement is adopted.
+				let vote = test_state.expect_submit_vote(&mut handle).await;
+				assert_eq!(vote.stmt.accept, false);
+				assert_eq!(vote.stmt.validator_index, 0.into());
+				assert_eq!(vote.stmt.subject, dummy_validation_code_hash(1));
+				vote.reply_ok();
+
+				test_state.send_conclude(&mut handle).await;
+			}
+			.boxed()
+		},
+	);
+}
+
+#[test]
+fn prechecks_locally_if_other_validators_disagree() {
+	test_harness_with_config(
+		crate::Config { local_precheck_probability: 0.0 },
+		|mut test_state, mut handle, _req_cfg| {
+			async move {
+				observe_pvf_fetching_statements(&mut test_state, &mut handle).await;
+
+				let requests = test_state.expect_statement_requests(&mut handle).await;
+				for (i, request) in requests.into_iter().enumerate() {
+					request.reply_statement(i != 0);
+				}
+
+				let pre_check = test_state.expect_candidate_precheck(&mut handle).await;
+				assert_eq!(pre_check.validation_code_hash, dummy_validation_code_hash(1));
+				pre_check.reply(PreCheckOutcome::Valid);
+
+				let vote = test_state.expect_submit_vote(&mut handle).await;
+				assert_eq!(vote.stmt.accept, true);
+				vote.reply_ok();
+
+				test_state.send_conclude(&mut handle).await;
+			}
+			.boxed()
+		},
+	);
+}
+
+#[test]
+fn serves_statements_about_locally_checked_pvfs() {
+	test_harness_with_config(
+		crate::Config { local_precheck_probability: 1.0 },
+		|mut test_state, mut handle, req_cfg| {
+			async move {
+				let block = FakeLeaf::new(dummy_hash(), 1, vec![dummy_validation_code_hash(1)]);
+				test_state
+					.activate_leaf_with_session(
+						&mut handle,
+						block,
+						StartsNewSession { session_index: 2, validators: vec![OUR_VALIDATOR] },
+					)
+					.await;
+
+				test_state.expect_pvfs_require_precheck(&mut handle).await.reply_mock();
+				test_state.expect_session_for_child(&mut handle).await;
+				test_state.expect_validators(&mut handle).await;
+				test_state
+					.expect_candidate_precheck(&mut handle)
+					.await
+					.reply(PreCheckOutcome::Invalid);
+				test_state.expect_submit_vote(&mut handle).await.reply_ok();
+
+				let request_statement = |validation_code_hash| {
+					let inbound_queue = req_cfg.inbound_queue.clone().unwrap();
+					async move {
+						let (pending_response, response) = oneshot::channel();
+						inbound_queue
+							.send(RawIncomingRequest {
+								peer: PeerId::random(),
+								payload: PvfCheckStatementFetchingRequest { validation_code_hash }
+									.encode(),
+								pending_response,
+							})
+							.await
+							.unwrap();
+						let response = response.await.unwrap().result.unwrap();
+						PvfCheckStatementFetchingResponse::decode(&mut &response[..]).unwrap()
+					}
+				};
+
+				match request_statement(dummy_validation_code_hash(1)).await {
+					PvfCheckStatementFetchingResponse::Statement(statement, signature) => {
+						assert_eq!(statement.accept, false);
+						assert_eq!(statement.session_index, 2);
+						assert_eq!(statement.subject, dummy_validation_code_hash(1));
+						assert!(signature.verify(
+							&statement.signing_payload()[..],
+							&OUR_VALIDATOR.public().into()
+						));
+					},
+					response => panic!("Unexpected response: {:?}", response),
+				}
+				assert!(matches!(
+					request_statement(dummy_validation_code_hash(2)).await,
+					PvfCheckStatementFetchingResponse::NotChecked
+				));
+
+				test_state.send_conclude(&mut handle).await;
+			}
+			.boxed()
+		},
+	);
+}
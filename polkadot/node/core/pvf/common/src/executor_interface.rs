@@ -169,7 +169,9 @@ pub fn params_to_wasmtime_semantics(par: &ExecutorParams) -> (Semantics, Determi
 			ExecutorParam::WasmExtBulkMemory => sem.wasm_bulk_memory = true,
 			ExecutorParam::PrecheckingMaxMemory(_) |
 			ExecutorParam::PvfPrepTimeout(_, _) |
-			ExecutorParam::PvfExecTimeout(_, _) => (), /* Not used here */
+			ExecutorParam::PvfExecTimeout(_, _) |
+			ExecutorParam::ApprovalRelayVrfDelay(_, _) |
+			ExecutorParam::ApprovalNoShowSlack(_) => (), /* Not used here */
 		}
 	}
 	sem.deterministic_stack_limit = Some(stack_limit.clone());
@@ -1645,7 +1645,7 @@ impl State {
 		runtime_info: &mut RuntimeInfo,
 		runtime_api_sender: &mut RA,
 	) -> Result<CheckedIndirectAssignment, InvalidAssignmentError> {
-		let ExtendedSessionInfo { ref session_info, .. } = runtime_info
+		let ExtendedSessionInfo { ref session_info, ref executor_params, .. } = runtime_info
 			.get_session_info_by_index(runtime_api_sender, assignment.block_hash, entry.session)
 			.await
 			.map_err(|err| InvalidAssignmentError::SessionInfoNotFound(err))?;
@@ -1681,7 +1681,10 @@ impl State {
 			.check_assignment_cert(
 				claimed_cores,
 				assignment.validator,
-				&polkadot_node_primitives::approval::criteria::Config::from(session_info),
+				&polkadot_node_primitives::approval::criteria::Config::new(
+					session_info,
+					executor_params,
+				),
 				entry.vrf_story.clone(),
 				&assignment.cert,
 				backing_groups,
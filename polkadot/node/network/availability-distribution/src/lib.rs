@@ -43,10 +43,28 @@ mod pov_requester;
 mod responder;
 use responder::{run_chunk_receivers, run_pov_receiver};
 
+/// Limiting the rate at which chunks are served.
+mod serving_budget;
+pub use serving_budget::ServingBudget;
+
 mod metrics;
 /// Prometheus `Metrics` for availability distribution.
 pub use metrics::Metrics;
 
+/// Deterministic replay and simulation of the chunk fetch scheduling, for tests.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing {
+	pub use crate::requester::{
+		fetch_task::simulation::{
+			FetchSimulation, SimulatedFetch, SimulatedResponse, SimulatedValidator, SimulationEvent,
+		},
+		session_cache::{
+			BadValidators, Clock, RecordedEvent, ReplayError, RuntimeSessionInfo, SchedulingEvent,
+			SessionCache, SessionInfo, VirtualClock,
+		},
+	};
+}
+
 #[cfg(test)]
 mod tests;
 
@@ -60,6 +78,8 @@ pub struct AvailabilityDistributionSubsystem {
 	recvs: IncomingRequestReceivers,
 	/// Mapping of the req-response protocols to the full protocol names.
 	req_protocol_names: ReqProtocolNames,
+	/// The rate at which chunks are served.
+	serving_budget: ServingBudget,
 	/// Prometheus metrics.
 	metrics: Metrics,
 }
@@ -96,12 +116,18 @@ impl AvailabilityDistributionSubsystem {
 		metrics: Metrics,
 	) -> Self {
 		let runtime = RuntimeInfo::new(Some(keystore));
-		Self { runtime, recvs, req_protocol_names, metrics }
+		Self { runtime, recvs, req_protocol_names, serving_budget: Default::default(), metrics }
+	}
+
+	/// Serve chunks at the rate of `serving_budget`, instead of the default one.
+	pub fn with_serving_budget(mut self, serving_budget: ServingBudget) -> Self {
+		self.serving_budget = serving_budget;
+		self
 	}
 
 	/// Start processing work as passed on from the Overseer.
 	async fn run<Context>(self, mut ctx: Context) -> std::result::Result<(), FatalError> {
-		let Self { mut runtime, recvs, metrics, req_protocol_names } = self;
+		let Self { mut runtime, recvs, metrics, req_protocol_names, serving_budget } = self;
 
 		let IncomingRequestReceivers {
 			pov_req_receiver,
@@ -125,6 +151,7 @@ impl AvailabilityDistributionSubsystem {
 					sender,
 					chunk_req_v1_receiver,
 					chunk_req_v2_receiver,
+					serving_budget,
 					metrics.clone(),
 				)
 				.boxed(),
@@ -18,9 +18,12 @@ use polkadot_node_subsystem_util::{
 	metrics,
 	metrics::{
 		prometheus,
-		prometheus::{Counter, CounterVec, Opts, PrometheusError, Registry, U64},
+		prometheus::{
+			Counter, CounterVec, Histogram, HistogramOpts, Opts, PrometheusError, Registry, U64,
+		},
 	},
 };
+use std::time::Duration;
 
 /// Label for success counters.
 pub const SUCCEEDED: &'static str = "succeeded";
@@ -56,6 +59,9 @@ struct MetricsInner {
 	/// Number of times our first set of validators did not provide the needed chunk and we had to
 	/// query further validators.
 	retries: Counter<U64>,
+
+	/// Time chunk requests got deferred for, because the serving budget was exhausted.
+	chunk_serving_deferrals: Histogram,
 }
 
 impl Metrics {
@@ -98,6 +104,13 @@ impl Metrics {
 			metrics.retries.inc()
 		}
 	}
+
+	/// Record the time serving chunks got deferred for.
+	pub fn on_chunk_serving_deferred(&self, deferral: Duration) {
+		if let Some(metrics) = &self.0 {
+			metrics.chunk_serving_deferrals.observe(deferral.as_secs_f64())
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -150,6 +163,16 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			chunk_serving_deferrals: prometheus::register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"polkadot_parachain_chunk_serving_deferral_seconds",
+						"Time serving chunks got deferred for, because the serving budget was exhausted.",
+					)
+					.buckets(vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0]),
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -50,6 +50,10 @@ use crate::{
 #[cfg(test)]
 mod tests;
 
+/// Deterministic simulation of chunk fetching.
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) mod simulation;
+
 /// Configuration for a `FetchTask`
 ///
 /// This exists to separate preparation of a `FetchTask` from actual starting it, which is
@@ -96,6 +100,16 @@ pub enum FromFetchTask {
 
 	/// We were not able to fetch the desired chunk for the given `CandidateHash`.
 	Failed(CandidateHash),
+
+	/// None of the validators of the group provided our chunk, resolve the group again.
+	///
+	/// The response is `None` if the group would be the same as before, because no later session
+	/// is known yet.
+	ResolveGroup {
+		session_index: SessionIndex,
+		group_index: GroupIndex,
+		response: oneshot::Sender<Option<Vec<AuthorityDiscoveryId>>>,
+	},
 }
 
 /// Information a running task needs.
@@ -113,6 +127,9 @@ struct RunningTask {
 	/// This vector gets drained during execution of the task (it will be empty afterwards).
 	group: Vec<AuthorityDiscoveryId>,
 
+	/// Whether `group` was resolved again after a session change already.
+	group_re_resolved: bool,
+
 	/// The request to send. We can store it as either v1 or v2, they have the same payload.
 	request: v2::ChunkFetchingRequest,
 
@@ -166,6 +183,7 @@ impl FetchTaskConfig {
 			group: session_info.validator_groups.get(core.group_responsible.0 as usize)
 				.expect("The responsible group of a candidate should be available in the corresponding session. qed.")
 				.clone(),
+			group_re_resolved: false,
 			request: v2::ChunkFetchingRequest {
 				candidate_hash: core.candidate_hash,
 				index: session_info.our_index,
@@ -257,7 +275,8 @@ impl RunningTask {
 
 	/// Fetch and store chunk.
 	///
-	/// Try validators in backing group in order.
+	/// Try validators in backing group in order. If none of them provides the chunk, the group is
+	/// tried once more after a session change, as resolved by the requester then.
 	async fn run_inner(mut self) {
 		let mut bad_validators = Vec::new();
 		let mut succeeded = false;
@@ -265,7 +284,7 @@ impl RunningTask {
 		let mut network_error_freq = gum::Freq::new();
 		let mut canceled_freq = gum::Freq::new();
 		// Try validators in reverse order:
-		while let Some(validator) = self.group.pop() {
+		while let Some(validator) = self.next_validator().await {
 			// Report retries:
 			if count > 0 {
 				self.metrics.on_retry();
@@ -330,6 +349,43 @@ impl RunningTask {
 		}
 	}
 
+	/// The next validator to request the chunk from.
+	///
+	/// Once the group is exhausted, it gets resolved again if a session change happened since the
+	/// task was spawned. Requests to the validators of the old session are likely to have failed
+	/// while connections were moved over to the validators of the new one.
+	async fn next_validator(&mut self) -> Option<AuthorityDiscoveryId> {
+		if self.group.is_empty() && !self.group_re_resolved {
+			self.group_re_resolved = true;
+			self.group = self.resolve_group().await.unwrap_or_default();
+			if !self.group.is_empty() {
+				gum::debug!(
+					target: LOG_TARGET,
+					relay_parent = ?self.relay_parent,
+					group_index = ?self.group_index,
+					session_index = ?self.session_index,
+					candidate_hash = ?self.request.candidate_hash,
+					"Retrying chunk fetching from group resolved after session change",
+				);
+			}
+		}
+		self.group.pop()
+	}
+
+	/// Ask the requester to resolve our group again.
+	async fn resolve_group(&mut self) -> Option<Vec<AuthorityDiscoveryId>> {
+		let (tx, rx) = oneshot::channel();
+		self.sender
+			.send(FromFetchTask::ResolveGroup {
+				session_index: self.session_index,
+				group_index: self.group_index,
+				response: tx,
+			})
+			.await
+			.ok()?;
+		rx.await.ok().flatten()
+	}
+
 	/// Do request and return response, if successful.
 	async fn do_request(
 		&mut self,
@@ -0,0 +1,335 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deterministic simulation of chunk fetching.
+//!
+//! [`FetchSimulation`] runs fetch tasks against scripted validators on a [`VirtualClock`]. Given
+//! the same seed, sessions and fetches, the validators are tried in the same order and the
+//! [`SessionCache`] records the same events on every run.
+
+use std::{
+	collections::{BTreeMap, HashMap, VecDeque},
+	future::Future,
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll},
+	time::Duration,
+};
+
+use codec::Encode;
+use futures::{channel::mpsc, task::noop_waker, StreamExt};
+
+use polkadot_erasure_coding::{branches, obtain_chunks_v1};
+use polkadot_node_network_protocol::request_response::{
+	outgoing::{Recipient, Requests},
+	v2, Protocol, ReqProtocolNames,
+};
+use polkadot_node_primitives::{AvailableData, BlockData, ErasureChunk, PoV, Proof};
+use polkadot_node_subsystem::messages::{
+	AllMessages, AvailabilityStoreMessage, NetworkBridgeTxMessage,
+};
+use polkadot_primitives::{
+	AuthorityDiscoveryId, CandidateHash, ChunkIndex, GroupIndex, Hash, PersistedValidationData,
+	SessionIndex,
+};
+use sc_network::{ProtocolName, RequestFailure};
+
+use super::{FromFetchTask, RunningTask};
+use crate::{
+	metrics::Metrics,
+	requester::session_cache::{Clock, SessionCache, VirtualClock},
+};
+
+/// How a simulated validator answers chunk requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulatedResponse {
+	/// A valid chunk.
+	Chunk,
+	/// The validator doesn't have the chunk.
+	NoSuchChunk,
+	/// A chunk not matching the erasure root.
+	InvalidChunk,
+	/// The request fails on the network level.
+	NetworkError,
+}
+
+/// A scripted validator.
+#[derive(Debug, Clone, Copy)]
+pub struct SimulatedValidator {
+	/// How the validator answers.
+	pub response: SimulatedResponse,
+	/// How long the validator takes to answer.
+	pub latency: Duration,
+}
+
+/// A chunk to fetch in a [`FetchSimulation`].
+#[derive(Debug, Clone)]
+pub struct SimulatedFetch {
+	/// The candidate to fetch the chunk of.
+	pub candidate_hash: CandidateHash,
+	/// The session of the group to fetch from, it must be cached.
+	pub session_index: SessionIndex,
+	/// The group to fetch from.
+	pub group_index: GroupIndex,
+	/// When the fetch starts, on the virtual clock.
+	pub start: Duration,
+}
+
+/// Something that happened in a [`FetchSimulation`], at a time on the virtual clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationEvent {
+	/// A validator was asked for the chunk of the candidate.
+	Requested { at: Duration, candidate_hash: CandidateHash, validator: AuthorityDiscoveryId },
+	/// The chunk of the candidate was stored.
+	Stored { at: Duration, candidate_hash: CandidateHash },
+	/// None of the validators provided the chunk of the candidate.
+	Failed { at: Duration, candidate_hash: CandidateHash },
+}
+
+/// A response on its way to a fetch task.
+type PendingResponse = (
+	futures::channel::oneshot::Sender<std::result::Result<(Vec<u8>, ProtocolName), RequestFailure>>,
+	std::result::Result<(Vec<u8>, ProtocolName), RequestFailure>,
+);
+
+/// A fetch task driven by the simulation.
+struct SimulatedTask {
+	candidate_hash: CandidateHash,
+	task: Pin<Box<dyn Future<Output = ()>>>,
+	rx: mpsc::Receiver<FromFetchTask>,
+	finished: bool,
+}
+
+/// Runs fetch tasks against scripted validators on a virtual clock.
+pub struct FetchSimulation {
+	cache: SessionCache,
+	clock: VirtualClock,
+	validators: HashMap<AuthorityDiscoveryId, SimulatedValidator>,
+	req_protocol_names: ReqProtocolNames,
+	erasure_root: Hash,
+	chunk: ErasureChunk,
+}
+
+impl FetchSimulation {
+	/// Create a simulation whose [`SessionCache`] is seeded by `seed`.
+	///
+	/// Validators which are not in `validators` fail every request right away.
+	pub fn new(seed: u64, validators: HashMap<AuthorityDiscoveryId, SimulatedValidator>) -> Self {
+		let clock = VirtualClock::default();
+		let available_data = AvailableData {
+			pov: Arc::new(PoV { block_data: BlockData(vec![42; 64]) }),
+			validation_data: PersistedValidationData::default(),
+		};
+		let chunks =
+			obtain_chunks_v1(2, &available_data).expect("Two chunks can always be obtained. qed.");
+		let mut branches = branches(&chunks);
+		let erasure_root = branches.root();
+		let (proof, chunk) = branches.next().expect("There are two chunks. qed.");
+		let chunk = ErasureChunk {
+			chunk: chunk.to_vec(),
+			index: ChunkIndex(0),
+			proof: Proof::try_from(proof).expect("The proof of two chunks is small. qed."),
+		};
+
+		FetchSimulation {
+			cache: SessionCache::with_clock(seed, Clock::Virtual(clock.clone())),
+			clock,
+			validators,
+			req_protocol_names: ReqProtocolNames::new(&Hash::zero(), None),
+			erasure_root,
+			chunk,
+		}
+	}
+
+	/// The session cache the fetch tasks are scheduled with.
+	pub fn cache(&self) -> &SessionCache {
+		&self.cache
+	}
+
+	/// The session cache, e.g. to insert the sessions to fetch from.
+	pub fn cache_mut(&mut self) -> &mut SessionCache {
+		&mut self.cache
+	}
+
+	/// The virtual clock of the simulation.
+	pub fn clock(&self) -> &VirtualClock {
+		&self.clock
+	}
+
+	/// Run the given fetches until all of them are done.
+	///
+	/// Fetches of our own group are skipped, like the requester does. A fetch starting at the
+	/// same time as a response arrives starts first.
+	pub fn run(&mut self, mut fetches: Vec<SimulatedFetch>) -> Vec<SimulationEvent> {
+		fetches.sort_by_key(|fetch| fetch.start);
+		let mut fetches = VecDeque::from(fetches);
+		let mut tasks = Vec::new();
+		// Keyed by the arrival time and the order the requests were sent in.
+		let mut responses = BTreeMap::<(u64, u64), PendingResponse>::new();
+		let mut sent: u64 = 0;
+		let mut events = Vec::new();
+		let waker = noop_waker();
+		let mut cx = Context::from_waker(&waker);
+
+		loop {
+			// Let the tasks run until all of them wait for a response.
+			loop {
+				let mut progress = false;
+				for task in tasks.iter_mut().filter(|task: &&mut SimulatedTask| !task.finished) {
+					if task.task.as_mut().poll(&mut cx).is_ready() {
+						task.finished = true;
+						progress = true;
+					}
+					while let Poll::Ready(Some(msg)) = task.rx.poll_next_unpin(&mut cx) {
+						progress = true;
+						for (latency, response) in
+							self.handle_message(task.candidate_hash, msg, &mut events)
+						{
+							let at = self.clock.now().saturating_add(millis(latency));
+							responses.insert((at, sent), response);
+							sent += 1;
+						}
+					}
+				}
+				if !progress {
+					break
+				}
+			}
+			tasks.retain(|task| !task.finished);
+
+			let next_fetch = fetches.front().map(|fetch| millis(fetch.start));
+			let next_response = responses.keys().next().map(|(at, _)| *at);
+			match (next_fetch, next_response) {
+				(Some(start), response) if response.map_or(true, |at| start <= at) => {
+					self.clock.set(start.max(self.clock.now()));
+					let fetch = fetches.pop_front().expect("There is a next fetch. qed.");
+					tasks.extend(self.start_fetch(fetch));
+				},
+				(_, Some(at)) => {
+					self.clock.set(at.max(self.clock.now()));
+					let (_, (tx, response)) =
+						responses.pop_first().expect("There is a next response. qed.");
+					let _ = tx.send(response);
+				},
+				(None, None) => break,
+			}
+		}
+
+		events
+	}
+
+	/// Spawn the task fetching the chunk of `fetch`, unless it is skipped.
+	fn start_fetch(&self, fetch: SimulatedFetch) -> Option<SimulatedTask> {
+		let SimulatedFetch { candidate_hash, session_index, group_index, .. } = fetch;
+		let session = self.cache.cached_session(session_index)?;
+		if session.our_group == Some(group_index) {
+			return None
+		}
+
+		let (sender, rx) = mpsc::channel(0);
+		let task = RunningTask {
+			session_index,
+			group_index,
+			group: session.validator_groups.get(group_index.0 as usize)?.clone(),
+			group_re_resolved: false,
+			request: v2::ChunkFetchingRequest { candidate_hash, index: session.our_index },
+			erasure_root: self.erasure_root,
+			relay_parent: Hash::zero(),
+			sender,
+			metrics: Metrics::new_dummy(),
+			chunk_index: self.chunk.index,
+			req_v1_protocol_name: self.req_protocol_names.get_name(Protocol::ChunkFetchingV1),
+			req_v2_protocol_name: self.req_protocol_names.get_name(Protocol::ChunkFetchingV2),
+		};
+
+		Some(SimulatedTask {
+			candidate_hash,
+			task: Box::pin(task.run_inner()),
+			rx,
+			finished: false,
+		})
+	}
+
+	/// Handle a message of a fetch task, returning the responses to the requests it sent with
+	/// their latencies.
+	fn handle_message(
+		&mut self,
+		candidate_hash: CandidateHash,
+		msg: FromFetchTask,
+		events: &mut Vec<SimulationEvent>,
+	) -> Vec<(Duration, PendingResponse)> {
+		let at = Duration::from_millis(self.clock.now());
+		let mut responses = Vec::new();
+		match msg {
+			FromFetchTask::Message(msg) => match AllMessages::from(msg) {
+				AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendRequests(requests, _)) =>
+					for request in requests {
+						let Requests::ChunkFetching(request) = request else { continue };
+						let Recipient::Authority(validator) = request.peer.clone() else {
+							continue
+						};
+						let (latency, response) = match self.validators.get(&validator) {
+							Some(simulated) =>
+								(simulated.latency, self.response(simulated.response)),
+							None => (Duration::ZERO, Err(RequestFailure::Refused)),
+						};
+						events.push(SimulationEvent::Requested { at, candidate_hash, validator });
+						responses.push((latency, (request.pending_response, response)));
+					},
+				AllMessages::AvailabilityStore(AvailabilityStoreMessage::StoreChunk {
+					tx, ..
+				}) => {
+					events.push(SimulationEvent::Stored { at, candidate_hash });
+					let _ = tx.send(Ok(()));
+				},
+				_ => {},
+			},
+			FromFetchTask::Concluded(Some(bad_validators)) =>
+				self.cache.report_bad_log(bad_validators),
+			FromFetchTask::Concluded(None) => {},
+			FromFetchTask::Failed(candidate_hash) =>
+				events.push(SimulationEvent::Failed { at, candidate_hash }),
+			FromFetchTask::ResolveGroup { session_index, group_index, response } => {
+				let _ = response.send(
+					self.cache.resolve_group_after_session_change(session_index, group_index),
+				);
+			},
+		}
+		responses
+	}
+
+	/// The response of a validator answering with `response`.
+	fn response(
+		&self,
+		response: SimulatedResponse,
+	) -> std::result::Result<(Vec<u8>, ProtocolName), RequestFailure> {
+		let protocol_name = self.req_protocol_names.get_name(Protocol::ChunkFetchingV2);
+		let response = match response {
+			SimulatedResponse::Chunk => v2::ChunkFetchingResponse::Chunk(self.chunk.clone()),
+			SimulatedResponse::NoSuchChunk => v2::ChunkFetchingResponse::NoSuchChunk,
+			SimulatedResponse::InvalidChunk => v2::ChunkFetchingResponse::Chunk(ErasureChunk {
+				chunk: vec![1, 2, 3],
+				..self.chunk.clone()
+			}),
+			SimulatedResponse::NetworkError => return Err(RequestFailure::Refused),
+		};
+		Ok((response.encode(), protocol_name))
+	}
+}
+
+fn millis(duration: Duration) -> u64 {
+	u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
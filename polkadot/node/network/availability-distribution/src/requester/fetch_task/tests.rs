@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use codec::Encode;
 
@@ -84,6 +84,7 @@ fn task_does_not_accept_invalid_chunk(#[case] protocol: Protocol) {
 		},
 		valid_chunks: HashSet::new(),
 		req_protocol_names,
+		resolved_group: None,
 	};
 	test.run(task, rx);
 }
@@ -121,6 +122,7 @@ fn task_stores_valid_chunk(#[case] protocol: Protocol) {
 		},
 		valid_chunks: [(chunk.chunk)].into_iter().collect(),
 		req_protocol_names,
+		resolved_group: None,
 	};
 	test.run(task, rx);
 }
@@ -161,6 +163,7 @@ fn task_does_not_accept_wrongly_indexed_chunk(#[case] protocol: Protocol) {
 		},
 		valid_chunks: HashSet::new(),
 		req_protocol_names,
+		resolved_group: None,
 	};
 	test.run(task, rx);
 }
@@ -229,10 +232,131 @@ fn task_stores_valid_chunk_if_there_is_one(#[case] protocol: Protocol) {
 		},
 		valid_chunks: [(chunk.chunk)].into_iter().collect(),
 		req_protocol_names,
+		resolved_group: None,
 	};
 	test.run(task, rx);
 }
 
+/// Task tries the group again, once it got resolved after a session change.
+#[test]
+fn task_retries_group_resolved_after_session_change() {
+	let req_protocol_names = ReqProtocolNames::new(&Hash::repeat_byte(0xff), None);
+	let protocol = Protocol::ChunkFetchingV2;
+	let chunk_index = ChunkIndex(1);
+	let (mut task, rx) = get_test_running_task(&req_protocol_names, ValidatorIndex(2), chunk_index);
+	let pov = PoV { block_data: BlockData(vec![45, 46, 47]) };
+
+	let (root_hash, chunk) = get_valid_chunk_data(pov, 10, chunk_index);
+	task.erasure_root = root_hash;
+	// Bob doesn't have the chunk, only Alice of the resolved group does.
+	task.group = vec![Sr25519Keyring::Bob.public().into()];
+	let protocol_name = req_protocol_names.get_name(protocol);
+
+	let test = TestRun {
+		chunk_responses: [
+			(
+				Recipient::Authority(Sr25519Keyring::Alice.public().into()),
+				get_response(
+					protocol,
+					protocol_name.clone(),
+					Some((chunk.chunk.clone(), chunk.proof, chunk_index)),
+				),
+			),
+			(
+				Recipient::Authority(Sr25519Keyring::Bob.public().into()),
+				get_response(protocol, protocol_name.clone(), None),
+			),
+		]
+		.into_iter()
+		.collect(),
+		valid_chunks: [(chunk.chunk)].into_iter().collect(),
+		req_protocol_names,
+		resolved_group: Some(vec![
+			Sr25519Keyring::Alice.public().into(),
+			Sr25519Keyring::Bob.public().into(),
+		]),
+	};
+	test.run(task, rx);
+}
+
+/// Simulated fetches are reproducible, and later fetches try the validators which failed last.
+#[test]
+fn simulated_fetches_try_bad_validators_last() {
+	use crate::testing::*;
+
+	let alice: AuthorityDiscoveryId = Sr25519Keyring::Alice.public().into();
+	let validators = [
+		(Sr25519Keyring::Alice, SimulatedResponse::Chunk, 100),
+		(Sr25519Keyring::Bob, SimulatedResponse::NoSuchChunk, 50),
+		(Sr25519Keyring::Charlie, SimulatedResponse::InvalidChunk, 20),
+		(Sr25519Keyring::Dave, SimulatedResponse::NetworkError, 1000),
+	]
+	.into_iter()
+	.map(|(keyring, response, latency)| {
+		let latency = Duration::from_millis(latency);
+		(keyring.public().into(), SimulatedValidator { response, latency })
+	})
+	.collect::<HashMap<_, _>>();
+	let session = RuntimeSessionInfo {
+		validator_groups: vec![(0..4).map(ValidatorIndex).collect(), vec![ValidatorIndex(4)]],
+		discovery_keys: Sr25519Keyring::iter().take(5).map(|k| k.public().into()).collect(),
+		our_index: Some(ValidatorIndex(4)),
+		our_group: Some(GroupIndex(1)),
+		node_features: Default::default(),
+	};
+	let fetches = [(0, 0), (1, 10_000), (2, 10_000)]
+		.into_iter()
+		.map(|(candidate, start)| SimulatedFetch {
+			candidate_hash: CandidateHash([candidate; 32].into()),
+			session_index: 1,
+			group_index: if candidate == 2 { GroupIndex(1) } else { GroupIndex(0) },
+			start: Duration::from_millis(start),
+		})
+		.collect::<Vec<_>>();
+
+	let simulate = || {
+		let mut simulation = FetchSimulation::new(7, validators.clone());
+		assert!(simulation.cache_mut().insert_fetched_session(1, session.clone()));
+		let events = simulation.run(fetches.clone());
+		(events, simulation)
+	};
+	let (events, simulation) = simulate();
+	let (events_again, simulation_again) = simulate();
+	assert_eq!(events, events_again);
+	assert_eq!(simulation.cache().recorded_events(), simulation_again.cache().recorded_events());
+
+	let stored = |candidate: u8| {
+		events.iter().find_map(|event| match event {
+			SimulationEvent::Stored { at, candidate_hash }
+				if *candidate_hash == CandidateHash([candidate; 32].into()) =>
+				Some(*at),
+			_ => None,
+		})
+	};
+	assert!(stored(0).is_some());
+	// All validators which failed the first fetch are tried last by the second one.
+	assert_eq!(stored(1), Some(Duration::from_millis(10_100)));
+	let requested = events
+		.iter()
+		.filter_map(|event| match event {
+			SimulationEvent::Requested { candidate_hash, validator, .. }
+				if *candidate_hash == CandidateHash([1; 32].into()) =>
+				Some(validator.clone()),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(requested, vec![alice]);
+	// Our own group is not fetched from.
+	assert_eq!(stored(2), None);
+
+	// The captured events reproduce the order of the validators.
+	let replayed = SessionCache::replay(simulation.cache().recorded_events().to_vec()).unwrap();
+	assert_eq!(
+		replayed.cached_session(1).unwrap().validator_groups,
+		simulation.cache().cached_session(1).unwrap().validator_groups,
+	);
+}
+
 struct TestRun {
 	/// Response to deliver for a given validator index.
 	/// None means, answer with `NetworkError`.
@@ -241,10 +365,12 @@ struct TestRun {
 	valid_chunks: HashSet<Vec<u8>>,
 	/// Request protocol names
 	req_protocol_names: ReqProtocolNames,
+	/// The group to hand out, once the task asks for its group to be resolved again.
+	resolved_group: Option<Vec<AuthorityDiscoveryId>>,
 }
 
 impl TestRun {
-	fn run(self, task: RunningTask, rx: mpsc::Receiver<FromFetchTask>) {
+	fn run(mut self, task: RunningTask, rx: mpsc::Receiver<FromFetchTask>) {
 		sp_tracing::init_for_tests();
 		let mut rx = rx.fuse();
 		let task = task.run_inner().fuse();
@@ -266,6 +392,9 @@ impl TestRun {
 					FromFetchTask::Concluded(_) => break,
 					FromFetchTask::Failed(_) => break,
 					FromFetchTask::Message(msg) => end_ok = self.handle_message(msg).await,
+					FromFetchTask::ResolveGroup { response, .. } => {
+						let _ = response.send(self.resolved_group.take());
+					},
 				}
 			}
 			if !end_ok {
@@ -357,6 +486,7 @@ fn get_test_running_task(
 			session_index: 0,
 			group_index: GroupIndex(0),
 			group: Vec::new(),
+			group_re_resolved: false,
 			request: v2::ChunkFetchingRequest {
 				candidate_hash: CandidateHash([43u8; 32].into()),
 				index: validator_index,
@@ -46,11 +46,11 @@ use super::{FatalError, Metrics, Result, LOG_TARGET};
 mod tests;
 
 /// Cache for session information.
-mod session_cache;
+pub(crate) mod session_cache;
 use session_cache::SessionCache;
 
 /// A task fetching a particular chunk.
-mod fetch_task;
+pub(crate) mod fetch_task;
 use fetch_task::{FetchTask, FetchTaskConfig, FromFetchTask};
 
 /// Requester takes care of requesting erasure chunks from backing groups and stores them in the
@@ -162,6 +162,21 @@ impl Requester {
 			self.add_cores(ctx, runtime, leaf, leaf_session_index, cores).await?;
 		}
 
+		// Not being able to cache the session is not fatal, fetch tasks of the previous session
+		// just fail without resolving their group again.
+		if let Err(err) = self
+			.session_cache
+			.cache_leaf_session(ctx, runtime, leaf, leaf_session_index)
+			.await
+		{
+			gum::warn!(
+				target: LOG_TARGET,
+				error = ?err,
+				leaf_session_index,
+				"Failed to cache the session of the leaf"
+			);
+		}
+
 		Ok(())
 	}
 
@@ -268,6 +283,16 @@ impl Stream for Requester {
 					// Make sure we retry on next block still pending availability.
 					self.fetches.remove(&candidate_hash);
 				},
+				Poll::Ready(Some(FromFetchTask::ResolveGroup {
+					session_index,
+					group_index,
+					response,
+				})) => {
+					let group = self
+						.session_cache
+						.resolve_group_after_session_change(session_index, group_index);
+					let _ = response.send(group);
+				},
 				Poll::Ready(None) => return Poll::Ready(None),
 				Poll::Pending => return Poll::Pending,
 			}
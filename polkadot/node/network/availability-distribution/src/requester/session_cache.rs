@@ -14,13 +14,31 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashSet;
+//! Session information used to schedule chunk fetches.
+//!
+//! The order in which the validators of a group are tried is random, so are the effects of
+//! [`SessionCache::report_bad`] on it. To reproduce a reported fetch ordering, the cache uses a
+//! seeded RNG and logs every event affecting the order at `trace` level as a hex encoded
+//! [`RecordedEvent`]. With the `test-utils` feature, `SessionCache::replay` rebuilds the cache
+//! from such a log.
 
-use rand::{seq::SliceRandom, thread_rng};
+use std::{collections::HashSet, time::Instant};
+#[cfg(any(test, feature = "test-utils"))]
+use std::{
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use codec::{Decode, Encode};
+use rand::{seq::SliceRandom, thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use schnellru::{ByLength, LruMap};
 
 use polkadot_node_subsystem::overseer;
-use polkadot_node_subsystem_util::{request_node_features, runtime::RuntimeInfo};
+use polkadot_node_subsystem_util::runtime::RuntimeInfo;
 use polkadot_primitives::{
 	AuthorityDiscoveryId, GroupIndex, Hash, NodeFeatures, SessionIndex, ValidatorIndex,
 };
@@ -40,6 +58,133 @@ pub struct SessionCache {
 	/// to get any existing cache entry, before fetching new information, as we should not mess up
 	/// the order of validators in `SessionInfo::validator_groups`.
 	session_info_cache: LruMap<SessionIndex, SessionInfo>,
+
+	/// Used to shuffle the validator groups.
+	rng: ChaCha12Rng,
+
+	/// The number of events recorded so far.
+	tick: u64,
+
+	/// The clock the events are recorded on.
+	clock: Clock,
+
+	/// The events recorded so far.
+	#[cfg(any(test, feature = "test-utils"))]
+	recorded: Vec<RecordedEvent>,
+}
+
+/// The clock [`SchedulingEvent`]s are recorded on.
+#[derive(Debug, Clone)]
+pub enum Clock {
+	/// The wall clock, started at the given instant.
+	System(Instant),
+	/// A clock which only advances when told to, for simulations and replays.
+	#[cfg(any(test, feature = "test-utils"))]
+	Virtual(VirtualClock),
+}
+
+impl Clock {
+	/// Milliseconds since the clock was started.
+	fn now(&self) -> u64 {
+		match self {
+			Clock::System(start) => u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+			#[cfg(any(test, feature = "test-utils"))]
+			Clock::Virtual(clock) => clock.now(),
+		}
+	}
+}
+
+/// A shared clock which only advances when told to.
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Debug, Clone, Default)]
+pub struct VirtualClock(Arc<AtomicU64>);
+
+#[cfg(any(test, feature = "test-utils"))]
+impl VirtualClock {
+	/// Milliseconds since the clock was started.
+	pub fn now(&self) -> u64 {
+		self.0.load(Ordering::SeqCst)
+	}
+
+	/// Set the clock to `millis`.
+	pub fn set(&self, millis: u64) {
+		self.0.store(millis, Ordering::SeqCst)
+	}
+
+	/// Advance the clock by `by`.
+	pub fn advance(&self, by: Duration) {
+		let by = u64::try_from(by.as_millis()).unwrap_or(u64::MAX);
+		self.0.fetch_add(by, Ordering::SeqCst);
+	}
+}
+
+/// The session information queried from the runtime, before it is localized.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct RuntimeSessionInfo {
+	/// Validator groups of the session, in the order of the runtime.
+	pub validator_groups: Vec<Vec<ValidatorIndex>>,
+	/// The authority discovery keys of the session.
+	pub discovery_keys: Vec<AuthorityDiscoveryId>,
+	/// Our index, `None` if we are not a validator of the session.
+	pub our_index: Option<ValidatorIndex>,
+	/// Our group, `None` if we are not part of any group.
+	pub our_group: Option<GroupIndex>,
+	/// Node features.
+	pub node_features: NodeFeatures,
+}
+
+/// An event affecting the order in which validators are tried.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum SchedulingEvent {
+	/// The RNG shuffling the validator groups was seeded, always the first event.
+	Seeded {
+		/// The seed of the RNG.
+		seed: u64,
+	},
+	/// The info of a session was requested, `fetched` holds what the runtime returned if it was
+	/// not cached.
+	SessionRequested { session_index: SessionIndex, fetched: Option<RuntimeSessionInfo> },
+	/// Validators of a group were reported bad.
+	BadValidatorsReported {
+		session_index: SessionIndex,
+		group_index: GroupIndex,
+		bad_validators: Vec<AuthorityDiscoveryId>,
+	},
+	/// The validators of a group were resolved again after a session change.
+	GroupResolved { session_index: SessionIndex, group_index: GroupIndex },
+}
+
+/// A [`SchedulingEvent`] together with the time it happened at.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct RecordedEvent {
+	/// The number of events recorded before this one, so that a log with missing events is
+	/// detected when replaying it.
+	pub tick: u64,
+	/// Milliseconds since the clock of the cache was started.
+	pub at: u64,
+	/// The event.
+	pub event: SchedulingEvent,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl RecordedEvent {
+	/// Decode an event as it is logged by the [`SessionCache`].
+	pub fn from_hex(hex: &str) -> Option<Self> {
+		let encoded = sp_core::bytes::from_hex(hex).ok()?;
+		Self::decode(&mut &encoded[..]).ok()
+	}
+}
+
+/// Errors replaying a log of [`RecordedEvent`]s.
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ReplayError {
+	#[error("The log does not start with the seed of the RNG")]
+	MissingSeed,
+	#[error("Expected the event at tick {expected}, found tick {found}")]
+	MissingEvents { expected: u64, found: u64 },
+	#[error("Session {0} is not cached")]
+	NoSuchCachedSession(SessionIndex),
 }
 
 /// Localized session information, tailored for the needs of availability distribution.
@@ -84,12 +229,39 @@ pub struct BadValidators {
 
 #[overseer::contextbounds(AvailabilityDistribution, prefix = self::overseer)]
 impl SessionCache {
-	/// Create a new `SessionCache`.
+	/// Create a new `SessionCache`, shuffling the validator groups with a random seed.
 	pub fn new() -> Self {
-		SessionCache {
-			// We need to cache the current and the last session the most:
-			session_info_cache: LruMap::new(ByLength::new(2)),
-		}
+		Self::with_clock(thread_rng().gen(), Clock::System(Instant::now()))
+	}
+
+	/// Create a new `SessionCache`, shuffling the validator groups with the RNG seeded by `seed`
+	/// and recording the events on `clock`.
+	pub fn with_clock(seed: u64, clock: Clock) -> Self {
+		let mut cache = SessionCache {
+			// We need to cache the current and the last session the most, as well as the one
+			// before, for fetch tasks that outlive a session change:
+			session_info_cache: LruMap::new(ByLength::new(3)),
+			rng: ChaCha12Rng::seed_from_u64(seed),
+			tick: 0,
+			clock,
+			#[cfg(any(test, feature = "test-utils"))]
+			recorded: Vec::new(),
+		};
+		cache.record(SchedulingEvent::Seeded { seed });
+		cache
+	}
+
+	fn record(&mut self, event: SchedulingEvent) {
+		let event = RecordedEvent { tick: self.tick, at: self.clock.now(), event };
+		self.tick += 1;
+		gum::trace!(
+			target: LOG_TARGET,
+			tick = event.tick,
+			event = %sp_core::bytes::to_hex(&event.encode(), false),
+			"Scheduling event",
+		);
+		#[cfg(any(test, feature = "test-utils"))]
+		self.recorded.push(event);
 	}
 
 	/// Tries to retrieve `SessionInfo`.
@@ -104,19 +276,59 @@ impl SessionCache {
 		gum::trace!(target: LOG_TARGET, session_index, "Calling `get_session_info`");
 
 		if self.session_info_cache.get(&session_index).is_none() {
-			if let Some(info) =
-				Self::query_info_from_runtime(ctx, runtime, parent, session_index).await?
-			{
-				gum::trace!(target: LOG_TARGET, session_index, "Storing session info in lru!");
-				self.session_info_cache.insert(session_index, info);
-			} else {
+			let fetched =
+				Self::query_info_from_runtime(ctx, runtime, parent, session_index).await?;
+			if !self.insert_fetched_session(session_index, fetched) {
 				return Ok(None)
 			}
+		} else {
+			self.record(SchedulingEvent::SessionRequested { session_index, fetched: None });
 		}
 
 		Ok(self.session_info_cache.get(&session_index).map(|i| &*i))
 	}
 
+	/// Fetch the `SessionInfo` of the session of `leaf`, if it is not cached yet.
+	///
+	/// The runtime stores the `SessionInfo` of a session on its first block, so the session gets
+	/// cached on that block even if no chunks are fetched in it. Fetch tasks spawned in the
+	/// previous session can then have their group resolved again, see
+	/// [`resolve_group_after_session_change`](Self::resolve_group_after_session_change).
+	pub async fn cache_leaf_session<Context>(
+		&mut self,
+		ctx: &mut Context,
+		runtime: &mut RuntimeInfo,
+		leaf: Hash,
+		leaf_session_index: SessionIndex,
+	) -> Result<()> {
+		if self.session_info_cache.peek(&leaf_session_index).is_none() {
+			gum::trace!(target: LOG_TARGET, leaf_session_index, "Caching session of leaf");
+			self.get_session_info(ctx, runtime, leaf, leaf_session_index).await?;
+		}
+		Ok(())
+	}
+
+	/// Resolve the validators of a group of the given session again, if a later session is known
+	/// by now.
+	///
+	/// Returns `None` if the session is the latest one cached, the group would be the same as
+	/// resolved before then. The group reflects the bad validators reported in the meantime.
+	pub fn resolve_group_after_session_change(
+		&mut self,
+		session_index: SessionIndex,
+		group_index: GroupIndex,
+	) -> Option<Vec<AuthorityDiscoveryId>> {
+		self.record(SchedulingEvent::GroupResolved { session_index, group_index });
+		if !self.session_info_cache.iter().any(|(index, _)| *index > session_index) {
+			return None
+		}
+		self.session_info_cache
+			.get(&session_index)?
+			.validator_groups
+			.get(group_index.0 as usize)
+			.cloned()
+	}
+
 	/// Variant of `report_bad` that never fails, but just logs errors.
 	///
 	/// Not being able to report bad validators is not fatal, so we should not shutdown the
@@ -143,10 +355,17 @@ impl SessionCache {
 				missing_session: report.session_index,
 			},
 		)?;
+		// Only reports which can be applied are recorded, so they can also be applied when
+		// replaying.
 		let group = session.validator_groups.get_mut(report.group_index.0 as usize).expect(
 			"A bad validator report must contain a valid group for the reported session. qed.",
 		);
 		let bad_set = report.bad_validators.iter().collect::<HashSet<_>>();
+		let event = SchedulingEvent::BadValidatorsReported {
+			session_index: report.session_index,
+			group_index: report.group_index,
+			bad_validators: report.bad_validators.clone(),
+		};
 
 		// Get rid of bad boys:
 		group.retain(|v| !bad_set.contains(v));
@@ -155,67 +374,221 @@ impl SessionCache {
 		let mut new_group = report.bad_validators;
 		new_group.append(group);
 		*group = new_group;
+		self.record(event);
 		Ok(())
 	}
 
+	/// Insert the info of a session as fetched from the runtime into the cache.
+	///
+	/// Returns `false` if we are not a validator of the session.
+	pub fn insert_fetched_session(
+		&mut self,
+		session_index: SessionIndex,
+		fetched: RuntimeSessionInfo,
+	) -> bool {
+		let inserted = self.insert_session(session_index, &fetched);
+		self.record(SchedulingEvent::SessionRequested { session_index, fetched: Some(fetched) });
+		inserted
+	}
+
+	/// Shuffle the validator groups of `info` and insert it into the cache.
+	///
+	/// Returns `false` if we are not a validator of the session.
+	fn insert_session(&mut self, session_index: SessionIndex, info: &RuntimeSessionInfo) -> bool {
+		let RuntimeSessionInfo {
+			validator_groups,
+			discovery_keys,
+			our_index,
+			our_group,
+			node_features,
+		} = info;
+		let Some(our_index) = *our_index else { return false };
+
+		// Shuffle validators in groups:
+		let mut validator_groups = validator_groups.clone();
+		for g in validator_groups.iter_mut() {
+			g.shuffle(&mut self.rng)
+		}
+		// Look up `AuthorityDiscoveryId`s right away:
+		let validator_groups: Vec<Vec<_>> = validator_groups
+			.into_iter()
+			.map(|group| {
+				group
+					.into_iter()
+					.map(|index| {
+						discovery_keys.get(index.0 as usize)
+							.expect("There should be a discovery key for each validator of each validator group. qed.")
+							.clone()
+					})
+					.collect()
+			})
+			.collect();
+
+		gum::trace!(target: LOG_TARGET, session_index, "Storing session info in lru!");
+		self.session_info_cache.insert(
+			session_index,
+			SessionInfo {
+				validator_groups,
+				our_index,
+				session_index,
+				our_group: *our_group,
+				node_features: node_features.clone(),
+			},
+		);
+		true
+	}
+
 	/// Query needed information from runtime.
 	///
 	/// We need to pass in the relay parent for our call to `request_session_info`. We should
 	/// actually don't need that: I suppose it is used for internal caching based on relay parents,
 	/// which we don't use here. It should not do any harm though.
-	///
-	/// Returns: `None` if not a validator.
 	async fn query_info_from_runtime<Context>(
 		ctx: &mut Context,
 		runtime: &mut RuntimeInfo,
 		relay_parent: Hash,
 		session_index: SessionIndex,
-	) -> Result<Option<SessionInfo>> {
+	) -> Result<RuntimeSessionInfo> {
 		let info = runtime
 			.get_session_info_by_index(ctx.sender(), relay_parent, session_index)
 			.await?;
 
-		let node_features = request_node_features(relay_parent, session_index, ctx.sender())
-			.await
-			.await?
-			.map_err(Error::FailedNodeFeatures)?;
+		Ok(RuntimeSessionInfo {
+			validator_groups: info.session_info.validator_groups.to_vec(),
+			discovery_keys: info.session_info.discovery_keys.clone(),
+			our_index: info.validator_info.our_index,
+			our_group: info.validator_info.our_group,
+			node_features: info.node_features.clone(),
+		})
+	}
+}
 
-		let discovery_keys = info.session_info.discovery_keys.clone();
-		let mut validator_groups = info.session_info.validator_groups.clone();
+#[cfg(any(test, feature = "test-utils"))]
+impl SessionCache {
+	/// Rebuild the cache from the events logged by another cache.
+	///
+	/// The validator groups of the returned cache are in the order they were in the original
+	/// one after the last event. The returned cache records its events on a [`VirtualClock`],
+	/// following the times of the replayed events.
+	pub fn replay(
+		events: impl IntoIterator<Item = RecordedEvent>,
+	) -> std::result::Result<Self, ReplayError> {
+		let clock = VirtualClock::default();
+		let mut events = events.into_iter();
+		let mut cache = match events.next() {
+			Some(RecordedEvent { tick: 0, at, event: SchedulingEvent::Seeded { seed } }) => {
+				clock.set(at);
+				Self::with_clock(seed, Clock::Virtual(clock.clone()))
+			},
+			_ => return Err(ReplayError::MissingSeed),
+		};
 
-		if let Some(our_index) = info.validator_info.our_index {
-			// Get our group index:
-			let our_group = info.validator_info.our_group;
+		for RecordedEvent { tick, at, event } in events {
+			if tick != cache.tick {
+				return Err(ReplayError::MissingEvents { expected: cache.tick, found: tick })
+			}
+			clock.set(at);
 
-			// Shuffle validators in groups:
-			let mut rng = thread_rng();
-			for g in validator_groups.iter_mut() {
-				g.shuffle(&mut rng)
+			match event {
+				SchedulingEvent::Seeded { .. } => return Err(ReplayError::MissingSeed),
+				SchedulingEvent::SessionRequested { session_index, fetched: None } => {
+					cache
+						.session_info_cache
+						.get(&session_index)
+						.ok_or(ReplayError::NoSuchCachedSession(session_index))?;
+					cache
+						.record(SchedulingEvent::SessionRequested { session_index, fetched: None });
+				},
+				SchedulingEvent::SessionRequested { session_index, fetched: Some(fetched) } => {
+					cache.insert_fetched_session(session_index, fetched);
+				},
+				SchedulingEvent::BadValidatorsReported {
+					session_index,
+					group_index,
+					bad_validators,
+				} => cache
+					.report_bad(BadValidators { session_index, group_index, bad_validators })
+					.map_err(|_| ReplayError::NoSuchCachedSession(session_index))?,
+				SchedulingEvent::GroupResolved { session_index, group_index } => {
+					cache.resolve_group_after_session_change(session_index, group_index);
+				},
 			}
-			// Look up `AuthorityDiscoveryId`s right away:
-			let validator_groups: Vec<Vec<_>> = validator_groups
-				.into_iter()
-				.map(|group| {
-					group
-						.into_iter()
-						.map(|index| {
-							discovery_keys.get(index.0 as usize)
-								.expect("There should be a discovery key for each validator of each validator group. qed.")
-								.clone()
-						})
-						.collect()
-				})
-				.collect();
-
-			let info = SessionInfo {
-				validator_groups,
-				our_index,
-				session_index,
-				our_group,
-				node_features,
-			};
-			return Ok(Some(info))
 		}
-		return Ok(None)
+
+		Ok(cache)
+	}
+
+	/// A cached session, with its validator groups in the order they are tried in.
+	pub fn cached_session(&self, session_index: SessionIndex) -> Option<&SessionInfo> {
+		self.session_info_cache.peek(&session_index)
+	}
+
+	/// The events recorded so far, as they were logged.
+	pub fn recorded_events(&self) -> &[RecordedEvent] {
+		&self.recorded
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keyring::Sr25519Keyring;
+
+	fn runtime_session_info() -> RuntimeSessionInfo {
+		RuntimeSessionInfo {
+			validator_groups: vec![(0..5).map(ValidatorIndex).collect(), vec![ValidatorIndex(5)]],
+			discovery_keys: Sr25519Keyring::iter().take(6).map(|k| k.public().into()).collect(),
+			our_index: Some(ValidatorIndex(5)),
+			our_group: Some(GroupIndex(1)),
+			node_features: NodeFeatures::EMPTY,
+		}
+	}
+
+	#[test]
+	fn replay_reproduces_validator_order() {
+		let bad: AuthorityDiscoveryId = Sr25519Keyring::Charlie.public().into();
+		let clock = VirtualClock::default();
+
+		let mut cache = SessionCache::with_clock(42, Clock::Virtual(clock.clone()));
+		clock.advance(Duration::from_millis(1500));
+		assert!(cache.insert_fetched_session(1, runtime_session_info()));
+		clock.advance(Duration::from_millis(250));
+		cache
+			.report_bad(BadValidators {
+				session_index: 1,
+				group_index: GroupIndex(0),
+				bad_validators: vec![bad.clone()],
+			})
+			.unwrap();
+
+		// The log as it would be captured from the node.
+		let log = cache
+			.recorded_events()
+			.iter()
+			.map(|event| sp_core::bytes::to_hex(&event.encode(), false))
+			.collect::<Vec<_>>();
+		let events =
+			log.iter().map(|hex| RecordedEvent::from_hex(hex).unwrap()).collect::<Vec<_>>();
+		assert_eq!(events.iter().map(|event| event.at).collect::<Vec<_>>(), vec![0, 1500, 1750]);
+
+		let replayed = SessionCache::replay(events.clone()).unwrap();
+		let validator_groups =
+			|cache: &SessionCache| cache.cached_session(1).unwrap().validator_groups.clone();
+		assert_eq!(validator_groups(&replayed), validator_groups(&cache));
+		assert_eq!(validator_groups(&replayed)[0][0], bad);
+		// Replaying reproduces the log itself.
+		assert_eq!(replayed.recorded_events(), &events[..]);
+
+		// Gaps in the log are detected.
+		let mut incomplete = events.clone();
+		incomplete.remove(1);
+		assert_eq!(
+			SessionCache::replay(incomplete).err(),
+			Some(ReplayError::MissingEvents { expected: 1, found: 2 })
+		);
+		assert_eq!(
+			SessionCache::replay(events[1..].to_vec()).err(),
+			Some(ReplayError::MissingSeed)
+		);
 	}
 }
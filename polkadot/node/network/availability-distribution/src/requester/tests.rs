@@ -320,3 +320,72 @@ fn check_ancestry_lookup_in_different_sessions() {
 		assert_eq!(fetch_tasks.len(), 2.min(Requester::LEAF_ANCESTRY_LEN_WITHIN_SESSION + 1));
 	});
 }
+
+#[test]
+fn new_session_is_cached_on_its_first_block() {
+	let mut test_state = TestState::new();
+	let mut requester =
+		Requester::new(ReqProtocolNames::new(&Hash::repeat_byte(0xff), None), Default::default());
+	let keystore = make_ferdie_keystore();
+	let mut runtime = RuntimeInfo::new(Some(keystore));
+
+	test_state.session_index_for_block = |block_number| match block_number {
+		0..=3 => 1,
+		_ => 2,
+	};
+
+	test_harness(test_state.clone(), |mut ctx| async move {
+		let chain = &test_state.relay_chain;
+		let block_number = 2;
+		let update = ActiveLeavesUpdate {
+			activated: Some(new_leaf(chain[block_number], block_number as u32)),
+			deactivated: Vec::new().into(),
+		};
+		requester
+			.update_fetching_heads(&mut ctx, &mut runtime, update)
+			.await
+			.expect("Leaf processing failed");
+
+		// No later session is known, the group stays the same.
+		assert!(requester
+			.session_cache
+			.resolve_group_after_session_change(1, GroupIndex(1))
+			.is_none());
+
+		// The last block of the session, the `SessionInfo` of the next one is not stored yet.
+		let block_number = 3;
+		let update = ActiveLeavesUpdate {
+			activated: Some(new_leaf(chain[block_number], block_number as u32)),
+			deactivated: vec![chain[2]].into(),
+		};
+		requester
+			.update_fetching_heads(&mut ctx, &mut runtime, update)
+			.await
+			.expect("Leaf processing failed");
+		assert!(requester
+			.session_cache
+			.resolve_group_after_session_change(1, GroupIndex(1))
+			.is_none());
+
+		// The first block of the new session.
+		let block_number = 4;
+		let update = ActiveLeavesUpdate {
+			activated: Some(new_leaf(chain[block_number], block_number as u32)),
+			deactivated: vec![chain[3]].into(),
+		};
+		requester
+			.update_fetching_heads(&mut ctx, &mut runtime, update)
+			.await
+			.expect("Leaf processing failed");
+
+		// The new session is cached, the group gets resolved again.
+		let group = requester
+			.session_cache
+			.resolve_group_after_session_change(1, GroupIndex(1))
+			.expect("The new session is cached");
+		assert_eq!(
+			group.len(),
+			test_state.session_info.validator_groups.get(GroupIndex(1)).unwrap().len()
+		);
+	});
+}
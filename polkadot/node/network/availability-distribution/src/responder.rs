@@ -33,6 +33,7 @@ use polkadot_primitives::{CandidateHash, ValidatorIndex};
 use crate::{
 	error::{JfyiError, Result},
 	metrics::{Metrics, FAILED, NOT_FOUND, SUCCEEDED},
+	serving_budget::{Budgeter, ServingBudget},
 	LOG_TARGET,
 };
 
@@ -67,10 +68,13 @@ pub async fn run_pov_receiver<Sender>(
 }
 
 /// Receiver task to be forked as a separate task to handle chunk requests.
+///
+/// Requests are served at the rate of `serving_budget`, requests exceeding it are deferred.
 pub async fn run_chunk_receivers<Sender>(
 	mut sender: Sender,
 	mut receiver_v1: IncomingRequestReceiver<v1::ChunkFetchingRequest>,
 	mut receiver_v2: IncomingRequestReceiver<v2::ChunkFetchingRequest>,
+	serving_budget: ServingBudget,
 	metrics: Metrics,
 ) where
 	Sender: SubsystemSender<AvailabilityStoreMessage>,
@@ -85,11 +89,19 @@ pub async fn run_chunk_receivers<Sender>(
 		Some(chunk) => v2::ChunkFetchingResponse::Chunk(chunk.into()),
 	};
 
+	let mut budgeter = Budgeter::new(serving_budget);
+
 	loop {
+		if let Some(deferral) = budgeter.ready().await {
+			metrics.on_chunk_serving_deferred(deferral);
+		}
+
 		select! {
 			res = receiver_v1.recv(|| vec![COST_INVALID_REQUEST]).fuse() => match res.into_nested() {
 				Ok(Ok(msg)) => {
-					answer_chunk_request_log(&mut sender, msg, make_resp_v1, &metrics).await;
+					let served =
+						answer_chunk_request_log(&mut sender, msg, make_resp_v1, &metrics).await;
+					budgeter.spend(served);
 				},
 				Err(fatal) => {
 					gum::debug!(
@@ -109,7 +121,10 @@ pub async fn run_chunk_receivers<Sender>(
 			},
 			res = receiver_v2.recv(|| vec![COST_INVALID_REQUEST]).fuse() => match res.into_nested() {
 				Ok(Ok(msg)) => {
-					answer_chunk_request_log(&mut sender, msg.into(), make_resp_v2, &metrics).await;
+					let served =
+						answer_chunk_request_log(&mut sender, msg.into(), make_resp_v2, &metrics)
+							.await;
+					budgeter.spend(served);
 				},
 				Err(fatal) => {
 					gum::debug!(
@@ -157,13 +172,14 @@ pub async fn answer_pov_request_log<Sender>(
 
 /// Variant of `answer_chunk_request` that does Prometheus metric and logging on errors.
 ///
-/// Any errors of `answer_request` will simply be logged.
+/// Any errors of `answer_request` will simply be logged. Returns the number of bytes served.
 pub async fn answer_chunk_request_log<Sender, Req, MakeResp>(
 	sender: &mut Sender,
 	req: IncomingRequest<Req>,
 	make_response: MakeResp,
 	metrics: &Metrics,
-) where
+) -> usize
+where
 	Req: IsRequest + Decode + Encode + Into<v1::ChunkFetchingRequest>,
 	Req::Response: Encode,
 	Sender: SubsystemSender<AvailabilityStoreMessage>,
@@ -171,7 +187,14 @@ pub async fn answer_chunk_request_log<Sender, Req, MakeResp>(
 {
 	let res = answer_chunk_request(sender, req, make_response).await;
 	match res {
-		Ok(result) => metrics.on_served_chunk(if result { SUCCEEDED } else { NOT_FOUND }),
+		Ok(Some(served)) => {
+			metrics.on_served_chunk(SUCCEEDED);
+			served
+		},
+		Ok(None) => {
+			metrics.on_served_chunk(NOT_FOUND);
+			0
+		},
 		Err(err) => {
 			gum::warn!(
 				target: LOG_TARGET,
@@ -179,6 +202,7 @@ pub async fn answer_chunk_request_log<Sender, Req, MakeResp>(
 				"Serving chunk failed with error"
 			);
 			metrics.on_served_chunk(FAILED);
+			0
 		},
 	}
 }
@@ -211,12 +235,12 @@ where
 
 /// Answer an incoming chunk request by querying the av store.
 ///
-/// Returns: `Ok(true)` if chunk was found and served.
+/// Returns: `Ok(Some(size))` with the encoded size of the response, if chunk was found and served.
 pub async fn answer_chunk_request<Sender, Req, MakeResp>(
 	sender: &mut Sender,
 	req: IncomingRequest<Req>,
 	make_response: MakeResp,
-) -> Result<bool>
+) -> Result<Option<usize>>
 where
 	Sender: SubsystemSender<AvailabilityStoreMessage>,
 	Req: IsRequest + Decode + Encode + Into<v1::ChunkFetchingRequest>,
@@ -229,7 +253,7 @@ where
 
 	let chunk = query_chunk(sender, payload.candidate_hash, payload.index).await?;
 
-	let result = chunk.is_some();
+	let found = chunk.is_some();
 
 	gum::trace!(
 		target: LOG_TARGET,
@@ -241,12 +265,13 @@ where
 	);
 
 	let response = make_response(chunk);
+	let served = response.encoded_size();
 
 	req.pending_response
 		.send_response(response)
 		.map_err(|_| JfyiError::SendResponse)?;
 
-	Ok(result)
+	Ok(found.then_some(served))
 }
 
 /// Query chunk from the availability store.
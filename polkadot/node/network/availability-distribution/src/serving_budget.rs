@@ -0,0 +1,103 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metering of the bytes served by the chunk responder.
+//!
+//! Every chunk served means a database read in the availability store. On small machines, serving
+//! requests as fast as they arrive can starve block import, so requests exceeding the budget are
+//! deferred until it is replenished. They stay queued in the meantime, none are dropped.
+
+use std::time::{Duration, Instant};
+
+/// The rate at which erasure chunks are served to other validators.
+#[derive(Debug, Clone, Copy)]
+pub struct ServingBudget {
+	/// The sustained number of bytes served per second.
+	pub bytes_per_second: u64,
+	/// The number of bytes that can be served in a burst, after being idle for a while.
+	pub burst_bytes: u64,
+}
+
+impl Default for ServingBudget {
+	fn default() -> Self {
+		Self { bytes_per_second: 64 * 1024 * 1024, burst_bytes: 16 * 1024 * 1024 }
+	}
+}
+
+/// A token bucket of bytes, refilled at the rate of a [`ServingBudget`].
+pub(crate) struct Budgeter {
+	budget: ServingBudget,
+	/// The bytes that can still be served. Negative once a response exceeded the budget.
+	available: f64,
+	last_refill: Instant,
+}
+
+impl Budgeter {
+	pub(crate) fn new(budget: ServingBudget) -> Self {
+		Self { budget, available: budget.burst_bytes as f64, last_refill: Instant::now() }
+	}
+
+	/// Account for a response of `bytes` having been served.
+	pub(crate) fn spend(&mut self, bytes: usize) {
+		self.available -= bytes as f64;
+	}
+
+	/// Wait until the budget allows serving the next request.
+	///
+	/// Returns the time the request got deferred for, if it was.
+	pub(crate) async fn ready(&mut self) -> Option<Duration> {
+		let deferral = self.deferral(Instant::now())?;
+		futures_timer::Delay::new(deferral).await;
+		Some(deferral)
+	}
+
+	/// The time the next request has to wait at `now`, until the budget is replenished.
+	fn deferral(&mut self, now: Instant) -> Option<Duration> {
+		let rate = self.budget.bytes_per_second.max(1) as f64;
+		let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+		self.last_refill = now;
+		self.available = (self.available + elapsed * rate).min(self.budget.burst_bytes as f64);
+
+		(self.available < 0.0).then(|| Duration::from_secs_f64(-self.available / rate))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn requests_exceeding_the_budget_are_deferred() {
+		let mut budgeter =
+			Budgeter::new(ServingBudget { bytes_per_second: 1000, burst_bytes: 2000 });
+		let start = budgeter.last_refill;
+
+		// The burst is served right away.
+		assert_eq!(budgeter.deferral(start), None);
+		budgeter.spend(2000);
+		assert_eq!(budgeter.deferral(start), None);
+
+		// Going over the burst defers the next request until the budget is replenished.
+		budgeter.spend(500);
+		assert_eq!(budgeter.deferral(start), Some(Duration::from_millis(500)));
+		assert_eq!(budgeter.deferral(start + Duration::from_millis(500)), None);
+
+		// Idle time doesn't replenish the budget beyond the burst.
+		budgeter.spend(100);
+		assert_eq!(budgeter.deferral(start + Duration::from_secs(60)), None);
+		assert_eq!(budgeter.available, 2000.0);
+	}
+}
@@ -0,0 +1,269 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Batching of small validation protocol messages.
+//!
+//! Statement and approval distribution send many small notifications, each of them paying for
+//! the framing of the substream. When batching is enabled, small messages to a peer of
+//! [`ValidationVersion::V4`] are held back for a short window and sent as a single
+//! [`WireMessage::Batch`] notification. Peers of older versions get their messages right away.
+
+use super::*;
+
+use codec::Encode;
+use futures_timer::Delay;
+use polkadot_node_network_protocol::{peer_set::ValidationVersion, v3 as protocol_v3};
+use sc_network::MessageSink;
+use std::time::Duration;
+
+use crate::network::send_validation_message_v3;
+
+/// Configuration of the batching of validation protocol messages.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageBatching {
+	/// For how long messages to a peer are held back, waiting for more messages to batch them
+	/// with.
+	pub window: Duration,
+	/// Messages with an encoded size above this are never batched.
+	pub max_message_size: usize,
+	/// A batch is sent right away once the size of its messages reaches this.
+	///
+	/// Must be well below the maximum notification size of the validation peer set.
+	pub max_batch_size: usize,
+}
+
+impl Default for MessageBatching {
+	fn default() -> Self {
+		Self {
+			window: Duration::from_millis(10),
+			max_message_size: 1024,
+			max_batch_size: 32 * 1024,
+		}
+	}
+}
+
+/// Batches the validation protocol messages to the peers that negotiated
+/// [`ValidationVersion::V4`].
+///
+/// The same batcher must be passed to [`NetworkBridgeTx::with_message_batching`], which batches
+/// the messages of the subsystems, and [`NetworkBridgeRx::with_message_batching`], which tracks
+/// the versions of the peers and sends view updates through the batcher, so that views never
+/// overtake the messages sent before them.
+#[derive(Clone)]
+pub struct MessageBatcher(Arc<Mutex<BatcherState>>);
+
+struct BatcherState {
+	config: MessageBatching,
+	/// The connected peers which can decode batches.
+	batching_peers: HashSet<PeerId>,
+	pending: HashMap<PeerId, PendingBatch>,
+}
+
+#[derive(Default)]
+struct PendingBatch {
+	messages: Vec<protocol_v3::ValidationProtocol>,
+	size: usize,
+}
+
+type NotificationSinks = Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>;
+
+impl MessageBatcher {
+	/// Create a new batcher.
+	pub fn new(config: MessageBatching) -> Self {
+		Self(Arc::new(Mutex::new(BatcherState {
+			config,
+			batching_peers: HashSet::new(),
+			pending: HashMap::new(),
+		})))
+	}
+
+	/// For how long messages are held back.
+	pub(crate) fn window(&self) -> Duration {
+		self.0.lock().config.window
+	}
+
+	/// Note that `peer` connected on the validation peer set with the negotiated `version`.
+	pub(crate) fn note_peer_connected(&self, peer: PeerId, version: ProtocolVersion) {
+		if version == ValidationVersion::V4.into() {
+			self.0.lock().batching_peers.insert(peer);
+		}
+	}
+
+	/// Note that `peer` disconnected from the validation peer set, its held back messages are
+	/// dropped.
+	pub(crate) fn note_peer_disconnected(&self, peer: &PeerId) {
+		let mut inner = self.0.lock();
+		inner.batching_peers.remove(peer);
+		inner.pending.remove(peer);
+	}
+
+	/// Send `message` to `peers`, holding it back for the peers it can be batched for.
+	///
+	/// Returns `true` if messages are held back afterwards.
+	pub(crate) fn send(
+		&self,
+		peers: Vec<PeerId>,
+		message: protocol_v3::ValidationProtocol,
+		metrics: &Metrics,
+		notification_sinks: &NotificationSinks,
+	) -> bool {
+		let mut inner = self.0.lock();
+		let config = inner.config;
+		let size = message.encoded_size();
+
+		let (batched, direct): (Vec<_>, Vec<_>) = peers.into_iter().partition(|peer| {
+			size <= config.max_message_size && inner.batching_peers.contains(peer)
+		});
+
+		// A message that isn't batched must not overtake the ones held back for the same peer.
+		for peer in &direct {
+			inner.flush_peer(peer, metrics, notification_sinks);
+		}
+
+		for peer in batched {
+			let batch = inner.pending.entry(peer).or_default();
+			batch.messages.push(message.clone());
+			batch.size += size;
+
+			if batch.size >= config.max_batch_size {
+				inner.flush_peer(&peer, metrics, notification_sinks);
+			}
+		}
+
+		send_validation_message_v3(
+			direct,
+			WireMessage::ProtocolMessage(message),
+			metrics,
+			notification_sinks,
+		);
+
+		!inner.pending.is_empty()
+	}
+
+	/// Send our `view` to `peers`, after the messages held back for them.
+	pub(crate) fn send_view_update(
+		&self,
+		peers: Vec<PeerId>,
+		view: View,
+		metrics: &Metrics,
+		notification_sinks: &NotificationSinks,
+	) {
+		let mut inner = self.0.lock();
+		for peer in &peers {
+			inner.flush_peer(peer, metrics, notification_sinks);
+		}
+
+		send_validation_message_v3(
+			peers,
+			WireMessage::ViewUpdate(view),
+			metrics,
+			notification_sinks,
+		);
+	}
+
+	/// Send all held back messages.
+	pub(crate) fn flush(&self, metrics: &Metrics, notification_sinks: &NotificationSinks) {
+		let mut inner = self.0.lock();
+		for (peer, batch) in std::mem::take(&mut inner.pending) {
+			send_batch(peer, batch, metrics, notification_sinks);
+		}
+	}
+}
+
+impl BatcherState {
+	fn flush_peer(
+		&mut self,
+		peer: &PeerId,
+		metrics: &Metrics,
+		notification_sinks: &NotificationSinks,
+	) {
+		if let Some(batch) = self.pending.remove(peer) {
+			send_batch(*peer, batch, metrics, notification_sinks);
+		}
+	}
+}
+
+/// Sends the validation protocol messages of `NetworkBridgeTx`, through the [`MessageBatcher`] if
+/// batching is enabled.
+pub(crate) struct Batcher {
+	batcher: Option<MessageBatcher>,
+	/// The end of the batching window of the held back messages.
+	flush_timeout: Option<Delay>,
+}
+
+impl Batcher {
+	/// Create a new batcher, batching is disabled if `batcher` is `None`.
+	pub(crate) fn new(batcher: Option<MessageBatcher>) -> Self {
+		Self { batcher, flush_timeout: None }
+	}
+
+	/// Send `message` to `peers`, or hold it back to batch it with the next messages.
+	pub(crate) fn send(
+		&mut self,
+		peers: Vec<PeerId>,
+		message: protocol_v3::ValidationProtocol,
+		metrics: &Metrics,
+		notification_sinks: &NotificationSinks,
+	) {
+		let Some(batcher) = &self.batcher else {
+			return send_validation_message_v3(
+				peers,
+				WireMessage::ProtocolMessage(message),
+				metrics,
+				notification_sinks,
+			)
+		};
+
+		if batcher.send(peers, message, metrics, notification_sinks) && self.flush_timeout.is_none()
+		{
+			self.flush_timeout = Some(Delay::new(batcher.window()));
+		}
+	}
+
+	/// Wait for the batching window of the held back messages to end.
+	///
+	/// Never resolves if there are no held back messages.
+	pub(crate) fn flush_timeout(&mut self) -> future::Either<&mut Delay, future::Pending<()>> {
+		match self.flush_timeout.as_mut() {
+			Some(timeout) => future::Either::Left(timeout),
+			None => future::Either::Right(future::pending()),
+		}
+	}
+
+	/// Send all held back messages.
+	pub(crate) fn flush(&mut self, metrics: &Metrics, notification_sinks: &NotificationSinks) {
+		self.flush_timeout = None;
+		if let Some(batcher) = &self.batcher {
+			batcher.flush(metrics, notification_sinks);
+		}
+	}
+}
+
+fn send_batch(
+	peer: PeerId,
+	mut batch: PendingBatch,
+	metrics: &Metrics,
+	notification_sinks: &NotificationSinks,
+) {
+	metrics.on_messages_batched(batch.messages.len());
+
+	let message = if batch.messages.len() == 1 {
+		WireMessage::ProtocolMessage(batch.messages.remove(0))
+	} else {
+		WireMessage::Batch(batch.messages)
+	};
+	send_validation_message_v3(vec![peer], message, metrics, notification_sinks);
+}
@@ -0,0 +1,389 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Census of the protocol versions spoken by our peers.
+//!
+//! For the notification protocols the version negotiated with every connected peer is part of
+//! the [`Shared`] state already. For the request-response protocols there is no such thing as a
+//! connection, so we learn about the versions a peer supports from the responses to our requests:
+//! a response on the fallback protocol or an `UnsupportedProtocols` failure tells us the peer
+//! doesn't speak the primary protocol. The responses are routed through a [`CensusWorker`], a
+//! task of its own, so that they are forwarded to the requesters no matter what the network
+//! bridge is busy with.
+//!
+//! The census is reported via Prometheus and, if a telemetry handle is configured, periodically
+//! via telemetry.
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	time::{Duration, Instant},
+};
+
+use futures::{
+	channel::{mpsc, oneshot},
+	future::BoxFuture,
+	stream::FuturesUnordered,
+	StreamExt,
+};
+use schnellru::{ByLength, LruMap};
+
+use polkadot_node_network_protocol::request_response::{
+	IsRequest, OutgoingRequest, Protocol, Recipient, ReqProtocolNames, Requests, ResponseSender,
+};
+use sc_network::{types::ProtocolName, OutboundFailure, RequestFailure};
+use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
+
+use crate::{count_peers_by_version, Metrics, PeerSet, Shared};
+
+/// The minimum time between two census telemetry reports.
+const TELEMETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The maximum number of recipients we remember per request-response protocol.
+///
+/// Comfortably more than the number of validators in any network.
+const MAX_RECIPIENTS_PER_PROTOCOL: u32 = 2048;
+
+/// Rate limited reporting of the census via telemetry.
+pub(crate) struct CensusTelemetry {
+	handle: Option<TelemetryHandle>,
+	last_report: Option<Instant>,
+}
+
+impl CensusTelemetry {
+	pub(crate) fn new(handle: Option<TelemetryHandle>) -> Self {
+		Self { handle, last_report: None }
+	}
+
+	/// Whether a report is due now. If so, the next one is due `TELEMETRY_INTERVAL` from now.
+	fn report_due(&mut self) -> bool {
+		if self.handle.is_none() ||
+			self.last_report.is_some_and(|last| last.elapsed() < TELEMETRY_INTERVAL)
+		{
+			return false
+		}
+
+		self.last_report = Some(Instant::now());
+		true
+	}
+
+	/// Report the number of connected peers per notification protocol version.
+	pub(crate) fn report_peer_sets(&mut self, shared: &Shared) {
+		if !self.report_due() {
+			return
+		}
+
+		let peers = peer_set_census(shared);
+		telemetry!(
+			self.handle;
+			SUBSTRATE_INFO;
+			"parachain.network_bridge.peer_set_census";
+			"peers" => peers,
+		);
+	}
+
+	/// Report the number of recipients supporting each request-response protocol.
+	fn report_req_protocols(&mut self, recipients: &HashMap<Protocol, LruMap<Recipient, bool>>) {
+		if !self.report_due() {
+			return
+		}
+
+		let (supported, unsupported) = req_protocol_census(recipients);
+		telemetry!(
+			self.handle;
+			SUBSTRATE_INFO;
+			"parachain.network_bridge.req_protocol_census";
+			"supported" => supported,
+			"unsupported" => unsupported,
+		);
+	}
+}
+
+/// The number of connected peers per notification protocol version, keyed by the protocol label.
+fn peer_set_census(shared: &Shared) -> BTreeMap<&'static str, usize> {
+	let guard = shared.0.lock();
+	[(PeerSet::Validation, &guard.validation_peers), (PeerSet::Collation, &guard.collation_peers)]
+		.into_iter()
+		.flat_map(|(peer_set, peers)| {
+			count_peers_by_version(peers).into_iter().map(move |(version, count)| {
+				(peer_set.get_protocol_label(version).unwrap_or("unknown"), count)
+			})
+		})
+		.collect()
+}
+
+/// The number of recipients supporting and not supporting each request-response protocol, keyed
+/// by the protocol label.
+fn req_protocol_census(
+	recipients: &HashMap<Protocol, LruMap<Recipient, bool>>,
+) -> (BTreeMap<&'static str, usize>, BTreeMap<&'static str, usize>) {
+	let mut supported = BTreeMap::new();
+	let mut unsupported = BTreeMap::new();
+	for (protocol, recipients) in recipients {
+		let supporting = count_supporting(recipients);
+		supported.insert(req_protocol_label(*protocol), supporting);
+		unsupported.insert(req_protocol_label(*protocol), recipients.len() - supporting);
+	}
+	(supported, unsupported)
+}
+
+fn count_supporting(recipients: &LruMap<Recipient, bool>) -> usize {
+	recipients.iter().filter(|(_, supported)| **supported).count()
+}
+
+/// What a response told us about the protocols its sender supports.
+type Observations = Vec<(Protocol, Recipient, bool)>;
+
+/// A response forwarded to its requester, yielding what it told us.
+type TrackedResponse = BoxFuture<'static, Observations>;
+
+/// Census of the request-response protocols supported by the recipients of our requests.
+///
+/// Hands the intercepted responses over to its [`CensusWorker`].
+pub(crate) struct ReqProtocolCensus {
+	req_protocol_names: ReqProtocolNames,
+	tracked: mpsc::UnboundedSender<TrackedResponse>,
+}
+
+impl ReqProtocolCensus {
+	/// Create the census, together with the worker which must be spawned for it.
+	pub(crate) fn new(
+		req_protocol_names: ReqProtocolNames,
+		metrics: Metrics,
+		telemetry: Option<TelemetryHandle>,
+	) -> (Self, CensusWorker) {
+		let (tracked, tracked_rx) = mpsc::unbounded();
+		let worker = CensusWorker {
+			tracked: tracked_rx,
+			pending: FuturesUnordered::new(),
+			recipients: HashMap::new(),
+			metrics,
+			telemetry: CensusTelemetry::new(telemetry),
+		};
+		(Self { req_protocol_names, tracked }, worker)
+	}
+
+	/// Intercept the response to `req`, so the census learns from it.
+	///
+	/// The request is left alone if the worker is gone.
+	pub(crate) fn track(&mut self, req: &mut Requests) {
+		if self.tracked.is_closed() {
+			return
+		}
+
+		let Intercepted { recipient, protocol, fallback, response, pending_response } = match req {
+			Requests::ChunkFetching(req) => intercept(req),
+			Requests::CollationFetchingV1(req) => intercept(req),
+			Requests::CollationFetchingV2(req) => intercept(req),
+			Requests::PoVFetchingV1(req) => intercept(req),
+			Requests::AvailableDataFetchingV1(req) => intercept(req),
+			Requests::DisputeSendingV1(req) => intercept(req),
+			Requests::AttestedCandidateV2(req) => intercept(req),
+			Requests::CollationPrecheckV1(req) => intercept(req),
+			Requests::CollationPushV1(req) => intercept(req),
+			Requests::PvfCheckStatementFetchingV1(req) => intercept(req),
+		};
+		let protocol_name = self.req_protocol_names.get_name(protocol);
+		let fallback =
+			fallback.map(|fallback| (fallback, self.req_protocol_names.get_name(fallback)));
+
+		let tracked = Box::pin(async move {
+			let Ok(result) = response.await else { return Vec::new() };
+
+			let observations = match &result {
+				Ok((_, name)) if *name == protocol_name => vec![(protocol, recipient, true)],
+				Ok((_, name)) => match fallback {
+					Some((fallback, fallback_name)) if *name == fallback_name =>
+						vec![(protocol, recipient.clone(), false), (fallback, recipient, true)],
+					_ => Vec::new(),
+				},
+				Err(RequestFailure::Network(OutboundFailure::UnsupportedProtocols)) =>
+					std::iter::once(protocol)
+						.chain(fallback.map(|(fallback, _)| fallback))
+						.map(|protocol| (protocol, recipient.clone(), false))
+						.collect(),
+				Err(_) => Vec::new(),
+			};
+
+			// The requester might have lost interest already.
+			let _ = pending_response.send(result);
+			observations
+		});
+		// The worker only stops once the census is dropped, or the node shuts down.
+		let _ = self.tracked.unbounded_send(tracked);
+	}
+}
+
+/// Forwards the tracked responses to the requesters and learns from them.
+pub(crate) struct CensusWorker {
+	tracked: mpsc::UnboundedReceiver<TrackedResponse>,
+	/// The responses to the tracked requests still in flight.
+	pending: FuturesUnordered<TrackedResponse>,
+	/// Whether a recipient supports a protocol, as of the latest response it sent us.
+	recipients: HashMap<Protocol, LruMap<Recipient, bool>>,
+	metrics: Metrics,
+	telemetry: CensusTelemetry,
+}
+
+impl CensusWorker {
+	/// Run until the [`ReqProtocolCensus`] is dropped and all the tracked responses are
+	/// forwarded.
+	pub(crate) async fn run(mut self) {
+		self.drive().await
+	}
+
+	async fn drive(&mut self) {
+		loop {
+			futures::select! {
+				tracked = self.tracked.next() => match tracked {
+					Some(tracked) => self.pending.push(tracked),
+					None => break,
+				},
+				observations = self.pending.select_next_some() => self.note(observations),
+			}
+		}
+
+		while let Some(observations) = self.pending.next().await {
+			self.note(observations);
+		}
+	}
+
+	/// Record what a response told us about the protocols its sender supports.
+	fn note(&mut self, observations: Observations) {
+		for (protocol, recipient, supported) in observations {
+			let recipients = self
+				.recipients
+				.entry(protocol)
+				.or_insert_with(|| LruMap::new(ByLength::new(MAX_RECIPIENTS_PER_PROTOCOL)));
+			recipients.insert(recipient, supported);
+
+			let supporting = count_supporting(recipients);
+			self.metrics.note_req_protocol_recipients(
+				req_protocol_label(protocol),
+				supporting,
+				recipients.len() - supporting,
+			);
+		}
+
+		self.telemetry.report_req_protocols(&self.recipients);
+	}
+}
+
+/// A request whose response is routed through the census.
+struct Intercepted {
+	recipient: Recipient,
+	protocol: Protocol,
+	fallback: Option<Protocol>,
+	/// Receives the response from the network.
+	response: oneshot::Receiver<Result<(Vec<u8>, ProtocolName), RequestFailure>>,
+	/// Forwards the response to the requester.
+	pending_response: ResponseSender,
+}
+
+fn intercept<Req: IsRequest, FallbackReq>(
+	req: &mut OutgoingRequest<Req, FallbackReq>,
+) -> Intercepted {
+	let (tx, response) = oneshot::channel();
+	Intercepted {
+		recipient: req.peer.clone(),
+		protocol: Req::PROTOCOL,
+		fallback: req.fallback_request.as_ref().map(|(_, protocol)| *protocol),
+		response,
+		pending_response: std::mem::replace(&mut req.pending_response, tx),
+	}
+}
+
+/// The metric and telemetry label of a request-response protocol.
+fn req_protocol_label(protocol: Protocol) -> &'static str {
+	match protocol {
+		Protocol::ChunkFetchingV1 => "chunk_fetching_v1",
+		Protocol::ChunkFetchingV2 => "chunk_fetching_v2",
+		Protocol::CollationFetchingV1 => "collation_fetching_v1",
+		Protocol::CollationFetchingV2 => "collation_fetching_v2",
+		Protocol::PoVFetchingV1 => "pov_fetching_v1",
+		Protocol::AvailableDataFetchingV1 => "available_data_fetching_v1",
+		Protocol::DisputeSendingV1 => "dispute_sending_v1",
+		Protocol::AttestedCandidateV2 => "attested_candidate_v2",
+		Protocol::CollationPrecheckV1 => "collation_precheck_v1",
+		Protocol::CollationPushV1 => "collation_push_v1",
+		Protocol::PvfCheckStatementFetchingV1 => "pvf_check_statement_fetching_v1",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{executor::block_on, prelude::*};
+	use polkadot_node_network_protocol::{
+		request_response::{v1, v2, OutgoingResult},
+		PeerId,
+	};
+	use polkadot_primitives::{CandidateHash, ValidatorIndex};
+
+	fn chunk_request(
+		peer: PeerId,
+	) -> (Requests, impl Future<Output = OutgoingResult<(Vec<u8>, ProtocolName)>>) {
+		let candidate_hash = CandidateHash::default();
+		let index = ValidatorIndex(0);
+		let (req, response) = OutgoingRequest::new_with_fallback(
+			Recipient::Peer(peer),
+			v2::ChunkFetchingRequest { candidate_hash, index },
+			v1::ChunkFetchingRequest { candidate_hash, index },
+		);
+		(Requests::ChunkFetching(req), response)
+	}
+
+	fn respond(req: Requests, result: Result<(Vec<u8>, ProtocolName), RequestFailure>) {
+		let Requests::ChunkFetching(req) = req else { panic!("only chunk requests are sent") };
+		req.pending_response.send(result).unwrap();
+	}
+
+	#[test]
+	fn census_learns_from_responses() {
+		let names = ReqProtocolNames::new(&[0u8; 32], None);
+		let (mut census, mut worker) =
+			ReqProtocolCensus::new(names.clone(), Metrics::default(), None);
+		let v1_name = names.get_name(Protocol::ChunkFetchingV1);
+		let v2_name = names.get_name(Protocol::ChunkFetchingV2);
+
+		// A response on the fallback protocol.
+		let (mut req, fallback_response) = chunk_request(PeerId::random());
+		census.track(&mut req);
+		respond(req, Ok((vec![1], v1_name.clone())));
+
+		// A response on the primary protocol.
+		let (mut req, primary_response) = chunk_request(PeerId::random());
+		census.track(&mut req);
+		respond(req, Ok((vec![2], v2_name.clone())));
+
+		// Other failures than unsupported protocols don't tell us anything, but are forwarded.
+		let (mut req, failed_response) = chunk_request(PeerId::random());
+		census.track(&mut req);
+		respond(req, Err(RequestFailure::Network(OutboundFailure::DialFailure)));
+
+		// The worker alone forwards the responses, it stops once the census is dropped.
+		drop(census);
+		block_on(worker.drive());
+		assert_eq!(block_on(fallback_response).unwrap(), (vec![1], v1_name));
+		assert_eq!(block_on(primary_response).unwrap(), (vec![2], v2_name));
+		assert!(block_on(failed_response).is_err());
+
+		let (supported, unsupported) = req_protocol_census(&worker.recipients);
+		assert_eq!(supported.get("chunk_fetching_v2"), Some(&1));
+		assert_eq!(unsupported.get("chunk_fetching_v2"), Some(&1));
+		assert_eq!(supported.get("chunk_fetching_v1"), Some(&1));
+		assert_eq!(unsupported.get("chunk_fetching_v1"), Some(&0));
+	}
+}
@@ -37,7 +37,10 @@ use polkadot_node_network_protocol::{
 /// To be passed to [`FullNetworkConfiguration::add_notification_protocol`]().
 pub use polkadot_node_network_protocol::peer_set::{peer_sets_info, IsAuthority};
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
 
 mod validator_discovery;
 
@@ -50,6 +53,11 @@ use self::network::Network;
 mod metrics;
 pub use self::metrics::Metrics;
 
+mod census;
+
+mod batching;
+pub use self::batching::{MessageBatcher, MessageBatching};
+
 mod errors;
 pub(crate) use self::errors::Error;
 
@@ -81,6 +89,12 @@ pub(crate) enum WireMessage<M> {
 	/// A view update from a peer.
 	#[codec(index = 2)]
 	ViewUpdate(View),
+	/// Multiple messages from a peer on a specific protocol, sent as a single notification.
+	///
+	/// Only sent to peers of
+	/// [`ValidationVersion::V4`](polkadot_node_network_protocol::peer_set::ValidationVersion::V4).
+	#[codec(index = 3)]
+	Batch(Vec<M>),
 }
 
 #[derive(Debug)]
@@ -100,6 +114,8 @@ struct SharedInner {
 	local_view: Option<View>,
 	validation_peers: HashMap<PeerId, PeerData>,
 	collation_peers: HashMap<PeerId, PeerData>,
+	/// The validation peers authority discovery doesn't know as authorities.
+	validation_non_authorities: HashSet<PeerId>,
 }
 
 // Counts the number of peers that are connectioned using `version`
@@ -55,6 +55,24 @@ impl Metrics {
 		});
 	}
 
+	pub fn note_req_protocol_recipients(
+		&self,
+		protocol: &'static str,
+		supported: usize,
+		unsupported: usize,
+	) {
+		if let Some(metrics) = self.0.as_ref() {
+			metrics
+				.req_protocol_recipients
+				.with_label_values(&[protocol, "true"])
+				.set(supported as u64);
+			metrics
+				.req_protocol_recipients
+				.with_label_values(&[protocol, "false"])
+				.set(unsupported as u64);
+		}
+	}
+
 	pub fn on_notification_received(
 		&self,
 		peer_set: PeerSet,
@@ -121,6 +139,12 @@ impl Metrics {
 			metrics.rx_delayed_processing.observe(queue_size as f64);
 		}
 	}
+	pub fn on_messages_batched(&self, count: usize) {
+		if let Some(metrics) = self.0.as_ref() {
+			metrics.batched_messages.observe(count as f64);
+		}
+	}
+
 	pub fn time_delayed_rx_events(
 		&self,
 	) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
@@ -135,6 +159,7 @@ pub(crate) struct MetricsInner {
 	disconnected_events: prometheus::CounterVec<prometheus::U64>,
 	desired_peer_count: prometheus::GaugeVec<prometheus::U64>,
 	report_events: prometheus::Counter<prometheus::U64>,
+	req_protocol_recipients: prometheus::GaugeVec<prometheus::U64>,
 
 	notifications_received: prometheus::CounterVec<prometheus::U64>,
 	notifications_sent: prometheus::CounterVec<prometheus::U64>,
@@ -148,6 +173,7 @@ pub(crate) struct MetricsInner {
 	// we also need a dynamics for this queue size in case of messages delays.
 	rx_delayed_processing: prometheus::Histogram,
 	rx_delayed_processing_time: prometheus::Histogram,
+	batched_messages: prometheus::Histogram,
 }
 
 impl metrics::Metrics for Metrics {
@@ -202,6 +228,16 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			req_protocol_recipients: prometheus::register(
+				prometheus::GaugeVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_network_bridge_req_protocol_recipients",
+						"The number of recent request recipients supporting a request-response protocol or not",
+					),
+					&["protocol", "supported"]
+				)?,
+				registry,
+			)?,
 			notifications_received: prometheus::register(
 				prometheus::CounterVec::new(
 					prometheus::Opts::new(
@@ -270,6 +306,15 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			batched_messages: prometheus::register(
+				prometheus::Histogram::with_opts(
+					prometheus::HistogramOpts::new(
+						"polkadot_parachain_network_bridge_batched_messages",
+						"Number of validation protocol messages sent in a single notification",
+					).buckets(vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0]),
+				)?,
+				registry,
+			)?,
 		};
 
 		Ok(Metrics(Some(metrics)))
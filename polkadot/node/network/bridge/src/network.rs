@@ -253,10 +253,11 @@ impl Network for Arc<dyn NetworkService> {
 				);
 
 				let mut found_peer_id = None;
-				// Note: `get_addresses_by_authority_id` searched in a cache, and it thus expected
-				// to be very quick.
+				// Note: `get_prioritized_addresses_by_authority_id` searched in a cache, and it
+				// thus expected to be very quick. The addresses are added in the order the
+				// authority prefers them to be dialed.
 				for addr in authority_discovery
-					.get_addresses_by_authority_id(authority)
+					.get_prioritized_addresses_by_authority_id(authority)
 					.await
 					.into_iter()
 					.flat_map(|list| list.into_iter())
@@ -28,6 +28,7 @@ use sc_network::{
 	service::traits::{NotificationEvent, ValidationResult},
 	MessageSink, NotificationService,
 };
+use sc_telemetry::TelemetryHandle;
 use sp_consensus::SyncOracle;
 
 use polkadot_node_network_protocol::{
@@ -35,7 +36,7 @@ use polkadot_node_network_protocol::{
 	grid_topology::{SessionGridTopology, TopologyPeerInfo},
 	peer_set::{
 		CollationVersion, PeerSet, PeerSetProtocolNames, PerPeerSet, ProtocolVersion,
-		ValidationVersion,
+		ValidationVersion, VALIDATION_NON_RESERVED_SLOTS,
 	},
 	v1 as protocol_v1, v2 as protocol_v2, v3 as protocol_v3, ObservedRole, OurView, PeerId,
 	UnifiedReputationChange as Rep, View,
@@ -68,7 +69,7 @@ use super::validator_discovery;
 use crate::network::{
 	send_collation_message_v1, send_collation_message_v2, send_validation_message_v3, Network,
 };
-use crate::{network::get_peer_id_by_authority_id, WireMessage};
+use crate::{census::CensusTelemetry, network::get_peer_id_by_authority_id, WireMessage};
 
 use super::metrics::Metrics;
 
@@ -91,6 +92,9 @@ pub struct NetworkBridgeRx<N, AD> {
 	collation_service: Box<dyn NotificationService>,
 	notification_sinks: Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
 	approval_voting_parallel_enabled: bool,
+	reserved_authority_slots: usize,
+	telemetry: Option<TelemetryHandle>,
+	message_batcher: Option<MessageBatcher>,
 }
 
 impl<N, AD> NetworkBridgeRx<N, AD> {
@@ -129,8 +133,41 @@ impl<N, AD> NetworkBridgeRx<N, AD> {
 			collation_service,
 			notification_sinks,
 			approval_voting_parallel_enabled,
+			reserved_authority_slots: 0,
+			telemetry: None,
+			message_batcher: None,
 		}
 	}
+
+	/// Reserve `slots` of the non-reserved validation peer set slots for authorities.
+	///
+	/// Peers that authority discovery doesn't know as authorities are disconnected as soon as
+	/// they would take one of these slots, and so are connected peers that stop being authorities
+	/// while all the other slots are taken. Defaults to `0`, i.e. no slots are reserved.
+	pub fn with_reserved_authority_slots(mut self, slots: usize) -> Self {
+		self.reserved_authority_slots = slots;
+		self
+	}
+
+	/// Periodically report the census of the protocol versions of our peers via `telemetry`.
+	pub fn with_telemetry(mut self, telemetry: Option<TelemetryHandle>) -> Self {
+		self.telemetry = telemetry;
+		self
+	}
+
+	/// Track the peers which can decode batches in `message_batcher` and send our view updates
+	/// through it, see [`NetworkBridgeTx::with_message_batching`].
+	pub fn with_message_batching(mut self, message_batcher: MessageBatcher) -> Self {
+		self.message_batcher = Some(message_batcher);
+		self
+	}
+
+	/// The maximum number of validation peers that are not authorities, if limited.
+	fn max_non_authority_peers(&self) -> Option<usize> {
+		let non_reserved_slots = 2 * VALIDATION_NON_RESERVED_SLOTS as usize;
+		(self.reserved_authority_slots > 0)
+			.then(|| non_reserved_slots.saturating_sub(self.reserved_authority_slots))
+	}
 }
 
 #[overseer::subsystem(NetworkBridgeRx, error = SubsystemError, prefix = self::overseer)]
@@ -161,6 +198,8 @@ async fn handle_validation_message<AD>(
 	notification_service: &mut Box<dyn NotificationService>,
 	notification_sinks: &mut Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
 	approval_voting_parallel_enabled: bool,
+	max_non_authority_peers: Option<usize>,
+	message_batcher: Option<&MessageBatcher>,
 ) where
 	AD: validator_discovery::AuthorityDiscovery + Send,
 {
@@ -190,7 +229,30 @@ async fn handle_validation_message<AD>(
 				},
 			};
 
-			let (peer_set, version) = {
+			let maybe_authority =
+				authority_discovery_service.get_authority_ids_by_peer_id(peer).await;
+
+			if let (None, Some(max_non_authority_peers)) =
+				(&maybe_authority, max_non_authority_peers)
+			{
+				let non_authority_peers = shared.0.lock().validation_non_authorities.len();
+				if non_authority_peers >= max_non_authority_peers {
+					gum::debug!(
+						target: LOG_TARGET,
+						?peer,
+						?role,
+						non_authority_peers,
+						"Disconnecting non-authority peer, remaining validation slots are reserved for authorities",
+					);
+					network_service.disconnect_peer(
+						peer,
+						peerset_protocol_names.get_main_name(PeerSet::Validation),
+					);
+					return
+				}
+			}
+
+			let (peer_set, negotiated_version) = {
 				let (peer_set, version) =
 					(PeerSet::Validation, PeerSet::Validation.get_main_version());
 
@@ -227,6 +289,13 @@ async fn handle_validation_message<AD>(
 					(peer_set, version)
 				}
 			};
+			// Version 4 only adds batches to the wire format, the subsystems speak version 3
+			// with these peers.
+			let version = if negotiated_version == ValidationVersion::V4.into() {
+				ValidationVersion::V3.into()
+			} else {
+				negotiated_version
+			};
 			// store the notification sink to `notification_sinks` so both `NetworkBridgeRx`
 			// and `NetworkBridgeTx` can send messages to the peer.
 			match notification_service.message_sink(&peer) {
@@ -237,7 +306,7 @@ async fn handle_validation_message<AD>(
 					gum::warn!(
 						target: LOG_TARGET,
 						peerset = ?peer_set,
-						version = %version,
+						version = %negotiated_version,
 						?peer,
 						?role,
 						"Message sink not available for peer",
@@ -250,7 +319,7 @@ async fn handle_validation_message<AD>(
 				target: LOG_TARGET,
 				action = "PeerConnected",
 				peer_set = ?peer_set,
-				version = %version,
+				version = %negotiated_version,
 				peer = ?peer,
 				role = ?role
 			);
@@ -266,13 +335,18 @@ async fn handle_validation_message<AD>(
 					},
 				}
 
-				metrics.on_peer_connected(peer_set, version);
+				if maybe_authority.is_none() {
+					shared.validation_non_authorities.insert(peer);
+				}
+
+				metrics.on_peer_connected(peer_set, negotiated_version);
 
 				shared.local_view.clone().unwrap_or(View::default())
 			};
 
-			let maybe_authority =
-				authority_discovery_service.get_authority_ids_by_peer_id(peer).await;
+			if let Some(message_batcher) = message_batcher {
+				message_batcher.note_peer_connected(peer, negotiated_version);
+			}
 
 			dispatch_validation_events_to_all(
 				vec![
@@ -288,7 +362,7 @@ async fn handle_validation_message<AD>(
 			match ValidationVersion::try_from(version)
 				.expect("try_get_protocol has already checked version is known; qed")
 			{
-				ValidationVersion::V3 => send_validation_message_v3(
+				ValidationVersion::V3 | ValidationVersion::V4 => send_validation_message_v3(
 					vec![peer],
 					WireMessage::<protocol_v3::ValidationProtocol>::ViewUpdate(local_view),
 					metrics,
@@ -308,6 +382,7 @@ async fn handle_validation_message<AD>(
 
 			let was_connected = {
 				let mut shared = shared.0.lock();
+				shared.validation_non_authorities.remove(&peer);
 				let peer_map = &mut shared.validation_peers;
 
 				let w = peer_map.remove(&peer).is_some();
@@ -317,6 +392,9 @@ async fn handle_validation_message<AD>(
 			};
 
 			notification_sinks.lock().remove(&(peer_set, peer));
+			if let Some(message_batcher) = message_batcher {
+				message_batcher.note_peer_disconnected(&peer);
+			}
 
 			if was_connected && version == peer_set.get_main_version() {
 				dispatch_validation_event_to_all(
@@ -635,6 +713,8 @@ async fn handle_network_messages<AD>(
 	mut collation_service: Box<dyn NotificationService>,
 	mut notification_sinks: Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
 	approval_voting_parallel_enabled: bool,
+	max_non_authority_peers: Option<usize>,
+	message_batcher: Option<MessageBatcher>,
 ) -> Result<(), Error>
 where
 	AD: validator_discovery::AuthorityDiscovery + Send,
@@ -653,6 +733,8 @@ where
 					&mut validation_service,
 					&mut notification_sinks,
 					approval_voting_parallel_enabled,
+					max_non_authority_peers,
+					message_batcher.as_ref(),
 				).await,
 				None => return Err(Error::EventStreamConcluded),
 			},
@@ -704,18 +786,25 @@ where
 }
 
 #[overseer::contextbounds(NetworkBridgeRx, prefix = self::overseer)]
-async fn run_incoming_orchestra_signals<Context, AD>(
+async fn run_incoming_orchestra_signals<Context, N, AD>(
 	mut ctx: Context,
+	network_service: N,
 	mut authority_discovery_service: AD,
 	shared: Shared,
+	peerset_protocol_names: PeerSetProtocolNames,
+	max_non_authority_peers: Option<usize>,
 	sync_oracle: Box<dyn SyncOracle + Send>,
 	metrics: Metrics,
 	notification_sinks: Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
 	approval_voting_parallel_enabled: bool,
+	telemetry: Option<TelemetryHandle>,
+	message_batcher: Option<MessageBatcher>,
 ) -> Result<(), Error>
 where
+	N: Network,
 	AD: validator_discovery::AuthorityDiscovery + Clone,
 {
+	let mut census_telemetry = CensusTelemetry::new(telemetry);
 	// This is kept sorted, descending, by block number.
 	let mut live_heads: Vec<ActivatedLeaf> = Vec::with_capacity(MAX_VIEW_HEADS);
 	let mut finalized_number = 0;
@@ -778,6 +867,14 @@ where
 					?authority_ids,
 					"`AuthorityDiscoveryId`s have changed",
 				);
+				reclassify_validation_peer(
+					&shared,
+					&network_service,
+					&peerset_protocol_names,
+					peer_id,
+					!authority_ids.is_empty(),
+					max_non_authority_peers,
+				);
 				// using unbounded send to avoid cycles
 				// the messages are sent only once per session up to one per peer
 				dispatch_collation_event_to_all_unbounded(
@@ -828,8 +925,10 @@ where
 							&metrics,
 							&notification_sinks,
 							approval_voting_parallel_enabled,
+							message_batcher.as_ref(),
 						);
 						note_peers_count(&metrics, &shared);
+						census_telemetry.report_peer_sets(&shared);
 					}
 				}
 			},
@@ -867,6 +966,7 @@ where
 	N: Network,
 	AD: validator_discovery::AuthorityDiscovery + Clone,
 {
+	let max_non_authority_peers = bridge.max_non_authority_peers();
 	let NetworkBridgeRx {
 		network_service,
 		authority_discovery_service,
@@ -878,6 +978,9 @@ where
 		collation_service,
 		notification_sinks,
 		approval_voting_parallel_enabled,
+		reserved_authority_slots: _,
+		telemetry,
+		message_batcher,
 	} = bridge;
 
 	let (task, network_event_handler) = handle_network_messages(
@@ -891,6 +994,8 @@ where
 		collation_service,
 		notification_sinks.clone(),
 		approval_voting_parallel_enabled,
+		max_non_authority_peers,
+		message_batcher.clone(),
 	)
 	.remote_handle();
 
@@ -899,12 +1004,17 @@ where
 
 	let orchestra_signal_handler = run_incoming_orchestra_signals(
 		ctx,
+		network_service,
 		authority_discovery_service,
 		shared,
+		peerset_protocol_names,
+		max_non_authority_peers,
 		sync_oracle,
 		metrics,
 		notification_sinks,
 		approval_voting_parallel_enabled,
+		telemetry,
+		message_batcher,
 	);
 
 	futures::pin_mut!(orchestra_signal_handler);
@@ -916,6 +1026,44 @@ where
 	Ok(())
 }
 
+/// Record whether the connected validation peer `peer` is an authority after an authority
+/// discovery update.
+///
+/// A peer that stops being an authority is disconnected if the non-authority peers then exceed
+/// `max_non_authority_peers`, to give its slot back to the authorities.
+fn reclassify_validation_peer(
+	shared: &Shared,
+	network_service: &impl Network,
+	peerset_protocol_names: &PeerSetProtocolNames,
+	peer: PeerId,
+	is_authority: bool,
+	max_non_authority_peers: Option<usize>,
+) {
+	let non_authority_peers = {
+		let mut shared = shared.0.lock();
+		if !shared.validation_peers.contains_key(&peer) {
+			return
+		}
+		if is_authority {
+			shared.validation_non_authorities.remove(&peer);
+			return
+		}
+		shared.validation_non_authorities.insert(peer);
+		shared.validation_non_authorities.len()
+	};
+
+	if max_non_authority_peers.is_some_and(|max| non_authority_peers > max) {
+		gum::debug!(
+			target: LOG_TARGET,
+			?peer,
+			non_authority_peers,
+			"Disconnecting peer which is no longer an authority, remaining validation slots are reserved for authorities",
+		);
+		network_service
+			.disconnect_peer(peer, peerset_protocol_names.get_main_name(PeerSet::Validation));
+	}
+}
+
 fn construct_view(
 	live_heads: impl DoubleEndedIterator<Item = Hash>,
 	finalized_number: BlockNumber,
@@ -932,6 +1080,7 @@ fn update_our_view<Context>(
 	metrics: &Metrics,
 	notification_sinks: &Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
 	approval_voting_parallel_enabled: bool,
+	message_batcher: Option<&MessageBatcher>,
 ) {
 	let new_view = construct_view(live_heads.iter().map(|v| v.hash), finalized_number);
 
@@ -1005,12 +1154,21 @@ fn update_our_view<Context>(
 		notification_sinks,
 	);
 
-	send_validation_message_v3(
-		v3_validation_peers,
-		WireMessage::ViewUpdate(new_view.clone()),
-		metrics,
-		notification_sinks,
-	);
+	// The view must not overtake the messages held back for batching.
+	match message_batcher {
+		Some(message_batcher) => message_batcher.send_view_update(
+			v3_validation_peers,
+			new_view,
+			metrics,
+			notification_sinks,
+		),
+		None => send_validation_message_v3(
+			v3_validation_peers,
+			WireMessage::ViewUpdate(new_view),
+			metrics,
+			notification_sinks,
+		),
+	}
 }
 
 // Handle messages on a specific v1 peer-set. The peer is expected to be connected on that
@@ -1060,6 +1218,14 @@ fn handle_peer_messages<RawMessage: Decode, OutMessage: From<RawMessage>>(
 			},
 			WireMessage::ProtocolMessage(message) =>
 				NetworkBridgeEvent::PeerMessage(peer, message.into()),
+			WireMessage::Batch(messages) => {
+				outgoing_events.extend(
+					messages
+						.into_iter()
+						.map(|message| NetworkBridgeEvent::PeerMessage(peer, message.into())),
+				);
+				continue
+			},
 		})
 	}
 
@@ -223,14 +223,18 @@ impl TestNetworkHandle {
 		}
 
 		// because of how protocol negotiation works, if two peers support at least one common
-		// protocol, the protocol is negotiated over the main protocol (`ValidationVersion::V3`) but
+		// protocol, the protocol is negotiated over the main protocol (`ValidationVersion::V4`) but
 		// if either one of the peers used a fallback protocol for the negotiation (meaning they
 		// don't support the main protocol but some older version of it ), `negotiated_fallback` is
 		// set to that protocol.
 		let negotiated_fallback = match (protocol_version.into(), peer_set) {
 			(1, PeerSet::Collation) => Some(ProtocolName::from("/polkadot/collation/1")),
 			(2, PeerSet::Collation) => None,
-			(3, PeerSet::Validation) => None,
+			(3, PeerSet::Validation) => Some(
+				PeerSetProtocolNames::new(Hash::repeat_byte(0xff), None)
+					.get_name(PeerSet::Validation, ValidationVersion::V3.into()),
+			),
+			(4, PeerSet::Validation) => None,
 			_ => unreachable!(),
 		};
 
@@ -502,6 +506,14 @@ async fn await_peer_connections(
 fn test_harness<T: Future<Output = VirtualOverseer>>(
 	sync_oracle: Box<dyn SyncOracle + Send>,
 	test: impl FnOnce(TestHarness) -> T,
+) {
+	test_harness_with_reserved_authority_slots(0, sync_oracle, test)
+}
+
+fn test_harness_with_reserved_authority_slots<T: Future<Output = VirtualOverseer>>(
+	reserved_authority_slots: usize,
+	sync_oracle: Box<dyn SyncOracle + Send>,
+	test: impl FnOnce(TestHarness) -> T,
 ) {
 	let genesis_hash = Hash::repeat_byte(0xff);
 	let fork_id = None;
@@ -526,6 +538,9 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 		collation_service,
 		notification_sinks,
 		approval_voting_parallel_enabled: false,
+		reserved_authority_slots,
+		telemetry: None,
+		message_batcher: None,
 	};
 
 	let network_bridge = run_network_in(bridge, context)
@@ -647,6 +662,130 @@ fn send_our_view_upon_connection() {
 	});
 }
 
+#[test]
+fn disconnect_non_authorities_from_reserved_slots() {
+	let (oracle, handle) = make_sync_oracle(false);
+	// Leave a single validation slot to non-authorities.
+	let reserved_authority_slots = 2 * VALIDATION_NON_RESERVED_SLOTS as usize - 1;
+	test_harness_with_reserved_authority_slots(
+		reserved_authority_slots,
+		Box::new(oracle),
+		|test_harness| async move {
+			let TestHarness { mut network_handle, mut virtual_overseer, shared } = test_harness;
+
+			let peer_a = PeerId::random();
+			let peer_b = PeerId::random();
+
+			virtual_overseer
+				.send(FromOrchestra::Signal(OverseerSignal::ActiveLeaves(
+					ActiveLeavesUpdate::start_work(new_leaf(Hash::repeat_byte(1), 1)),
+				)))
+				.await;
+
+			handle.await_mode_switch().await;
+
+			for peer in [peer_a, peer_b] {
+				network_handle
+					.connect_peer(
+						peer,
+						ValidationVersion::V3.into(),
+						PeerSet::Validation,
+						ObservedRole::Full,
+					)
+					.await;
+			}
+
+			await_peer_connections(&shared, 1, 0).await;
+
+			let actions = network_handle.next_network_actions(2).await;
+			assert_network_actions_contains(
+				&actions,
+				&NetworkAction::DisconnectPeer(peer_b, PeerSet::Validation),
+			);
+			assert!(shared.0.lock().validation_peers.contains_key(&peer_a));
+
+			virtual_overseer
+		},
+	);
+}
+
+#[test]
+fn disconnect_peers_which_stop_being_authorities() {
+	let (oracle, handle) = make_sync_oracle(false);
+	// Leave a single validation slot to non-authorities.
+	let reserved_authority_slots = 2 * VALIDATION_NON_RESERVED_SLOTS as usize - 1;
+	test_harness_with_reserved_authority_slots(
+		reserved_authority_slots,
+		Box::new(oracle),
+		|test_harness| async move {
+			let TestHarness { mut network_handle, mut virtual_overseer, shared } = test_harness;
+
+			let peer_a = PeerId::random();
+			let peer_b = PeerId::random();
+
+			virtual_overseer
+				.send(FromOrchestra::Signal(OverseerSignal::ActiveLeaves(
+					ActiveLeavesUpdate::start_work(new_leaf(Hash::repeat_byte(1), 1)),
+				)))
+				.await;
+
+			handle.await_mode_switch().await;
+
+			network_handle
+				.connect_peer(
+					peer_a,
+					ValidationVersion::V3.into(),
+					PeerSet::Validation,
+					ObservedRole::Full,
+				)
+				.await;
+			await_peer_connections(&shared, 1, 0).await;
+
+			// `peer_a` turns out to be an authority, which frees the slot for `peer_b`.
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: NetworkBridgeRxMessage::UpdatedAuthorityIds {
+						peer_id: peer_a,
+						authority_ids: HashSet::from([Sr25519Keyring::Alice.public().into()]),
+					},
+				})
+				.await;
+			while shared.0.lock().validation_non_authorities.contains(&peer_a) {
+				futures_timer::Delay::new(std::time::Duration::from_millis(100)).await;
+			}
+
+			network_handle
+				.connect_peer(
+					peer_b,
+					ValidationVersion::V3.into(),
+					PeerSet::Validation,
+					ObservedRole::Full,
+				)
+				.await;
+			await_peer_connections(&shared, 2, 0).await;
+
+			// `peer_a` is no longer an authority and has to give its slot back.
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: NetworkBridgeRxMessage::UpdatedAuthorityIds {
+						peer_id: peer_a,
+						authority_ids: HashSet::new(),
+					},
+				})
+				.await;
+
+			let actions = network_handle.next_network_actions(3).await;
+			assert_network_actions_contains(
+				&actions,
+				&NetworkAction::DisconnectPeer(peer_a, PeerSet::Validation),
+			);
+			assert!(!actions.contains(&NetworkAction::DisconnectPeer(peer_b, PeerSet::Validation)));
+
+			virtual_overseer
+		},
+	);
+}
+
 #[test]
 fn sends_view_updates_to_peers() {
 	let (oracle, handle) = make_sync_oracle(false);
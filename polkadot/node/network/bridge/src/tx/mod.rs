@@ -30,18 +30,19 @@ use polkadot_node_subsystem::{
 
 use polkadot_node_network_protocol::request_response::Requests;
 use sc_network::{MessageSink, ReputationChange};
+use sc_telemetry::TelemetryHandle;
 
-use crate::validator_discovery;
+use crate::{census::ReqProtocolCensus, validator_discovery};
 
 /// Actual interfacing to the network based on the `Network` trait.
 ///
 /// Defines the `Network` trait with an implementation for an `Arc<NetworkService>`.
-use crate::network::{
-	send_collation_message_v1, send_collation_message_v2, send_validation_message_v3, Network,
-};
+use crate::network::{send_collation_message_v1, send_collation_message_v2, Network};
 
 use crate::metrics::Metrics;
 
+use crate::batching::Batcher;
+
 #[cfg(test)]
 mod tests;
 
@@ -57,6 +58,8 @@ pub struct NetworkBridgeTx<N, AD> {
 	req_protocol_names: ReqProtocolNames,
 	peerset_protocol_names: PeerSetProtocolNames,
 	notification_sinks: Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
+	telemetry: Option<TelemetryHandle>,
+	message_batcher: Option<MessageBatcher>,
 }
 
 impl<N, AD> NetworkBridgeTx<N, AD> {
@@ -80,8 +83,28 @@ impl<N, AD> NetworkBridgeTx<N, AD> {
 			req_protocol_names,
 			peerset_protocol_names,
 			notification_sinks,
+			telemetry: None,
+			message_batcher: None,
 		}
 	}
+
+	/// Periodically report the census of the request-response protocols supported by the
+	/// recipients of our requests via `telemetry`.
+	pub fn with_telemetry(mut self, telemetry: Option<TelemetryHandle>) -> Self {
+		self.telemetry = telemetry;
+		self
+	}
+
+	/// Coalesce small validation protocol messages to the same peer into single notifications.
+	///
+	/// Only the peers that negotiated [`ValidationVersion::V4`] get batches, and only once
+	/// `message_batcher` is also passed to [`NetworkBridgeRx::with_message_batching`].
+	///
+	/// [`ValidationVersion::V4`]: polkadot_node_network_protocol::peer_set::ValidationVersion::V4
+	pub fn with_message_batching(mut self, message_batcher: MessageBatcher) -> Self {
+		self.message_batcher = Some(message_batcher);
+		self
+	}
 }
 
 #[overseer::subsystem(NetworkBridgeTx, error = SubsystemError, prefix = self::overseer)]
@@ -107,6 +130,8 @@ async fn handle_subsystem_messages<Context, N, AD>(
 	req_protocol_names: ReqProtocolNames,
 	peerset_protocol_names: PeerSetProtocolNames,
 	notification_sinks: Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
+	telemetry: Option<TelemetryHandle>,
+	message_batcher: Option<MessageBatcher>,
 ) -> Result<(), Error>
 where
 	N: Network,
@@ -114,26 +139,35 @@ where
 {
 	let mut validator_discovery =
 		validator_discovery::Service::<N, AD>::new(peerset_protocol_names.clone());
+	let (mut census, census_worker) =
+		ReqProtocolCensus::new(req_protocol_names.clone(), metrics.clone(), telemetry);
+	ctx.spawn("network-bridge-census", Box::pin(census_worker.run()))?;
+	let mut batcher = Batcher::new(message_batcher);
 
 	loop {
-		match ctx.recv().fuse().await? {
-			FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(()),
-			FromOrchestra::Signal(_) => { /* handled by incoming */ },
-			FromOrchestra::Communication { msg } => {
-				(network_service, authority_discovery_service) =
-					handle_incoming_subsystem_communication(
-						&mut ctx,
-						network_service,
-						&mut validator_discovery,
-						authority_discovery_service.clone(),
-						msg,
-						&metrics,
-						&req_protocol_names,
-						&peerset_protocol_names,
-						&notification_sinks,
-					)
-					.await;
+		futures::select! {
+			msg = ctx.recv().fuse() => match msg? {
+				FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(()),
+				FromOrchestra::Signal(_) => { /* handled by incoming */ },
+				FromOrchestra::Communication { msg } => {
+					(network_service, authority_discovery_service) =
+						handle_incoming_subsystem_communication(
+							&mut ctx,
+							network_service,
+							&mut validator_discovery,
+							authority_discovery_service.clone(),
+							msg,
+							&metrics,
+							&req_protocol_names,
+							&peerset_protocol_names,
+							&notification_sinks,
+							&mut census,
+							&mut batcher,
+						)
+						.await;
+				},
 			},
+			_ = batcher.flush_timeout().fuse() => batcher.flush(&metrics, &notification_sinks),
 		}
 	}
 }
@@ -149,6 +183,8 @@ async fn handle_incoming_subsystem_communication<Context, N, AD>(
 	req_protocol_names: &ReqProtocolNames,
 	peerset_protocol_names: &PeerSetProtocolNames,
 	notification_sinks: &Arc<Mutex<HashMap<(PeerSet, PeerId), Box<dyn MessageSink>>>>,
+	census: &mut ReqProtocolCensus,
+	batcher: &mut Batcher,
 ) -> (N, AD)
 where
 	N: Network,
@@ -195,12 +231,8 @@ where
 			);
 
 			match msg {
-				ValidationProtocols::V3(msg) => send_validation_message_v3(
-					peers,
-					WireMessage::ProtocolMessage(msg),
-					&metrics,
-					notification_sinks,
-				),
+				ValidationProtocols::V3(msg) =>
+					batcher.send(peers, msg, &metrics, notification_sinks),
 			}
 		},
 		NetworkBridgeTxMessage::SendValidationMessages(msgs) => {
@@ -213,12 +245,8 @@ where
 
 			for (peers, msg) in msgs {
 				match msg {
-					ValidationProtocols::V3(msg) => send_validation_message_v3(
-						peers,
-						WireMessage::ProtocolMessage(msg),
-						&metrics,
-						notification_sinks,
-					),
+					ValidationProtocols::V3(msg) =>
+						batcher.send(peers, msg, &metrics, notification_sinks),
 				}
 			}
 		},
@@ -275,7 +303,7 @@ where
 				num_requests = %reqs.len(),
 			);
 
-			for req in reqs {
+			for mut req in reqs {
 				match req {
 					Requests::ChunkFetching(ref req) => {
 						// This is not the actual request that will succeed, as we don't know yet
@@ -293,8 +321,13 @@ where
 					Requests::PoVFetchingV1(_) => metrics.on_message("pov_fetching_v1"),
 					Requests::DisputeSendingV1(_) => metrics.on_message("dispute_sending_v1"),
 					Requests::AttestedCandidateV2(_) => metrics.on_message("attested_candidate_v2"),
+					Requests::CollationPrecheckV1(_) => metrics.on_message("collation_precheck_v1"),
+					Requests::CollationPushV1(_) => metrics.on_message("collation_push_v1"),
+					Requests::PvfCheckStatementFetchingV1(_) =>
+						metrics.on_message("pvf_check_statement_fetching_v1"),
 				}
 
+				census.track(&mut req);
 				network_service
 					.start_request(
 						&mut authority_discovery_service,
@@ -381,6 +414,8 @@ where
 		req_protocol_names,
 		peerset_protocol_names,
 		notification_sinks,
+		telemetry,
+		message_batcher,
 	} = bridge;
 
 	handle_subsystem_messages(
@@ -391,6 +426,8 @@ where
 		req_protocol_names,
 		peerset_protocol_names,
 		notification_sinks,
+		telemetry,
+		message_batcher,
 	)
 	.await?;
 
@@ -28,7 +28,7 @@ use sc_network::{
 
 use codec::DecodeAll;
 use polkadot_node_network_protocol::{
-	peer_set::PeerSetProtocolNames,
+	peer_set::{PeerSetProtocolNames, ValidationVersion},
 	request_response::{outgoing::Requests, ReqProtocolNames},
 	v1 as protocol_v1, v3 as protocol_v3, CollationProtocols, ObservedRole, ValidationProtocols,
 };
@@ -236,6 +236,13 @@ struct TestHarness {
 }
 
 fn test_harness<T: Future<Output = VirtualOverseer>>(test: impl FnOnce(TestHarness) -> T) {
+	test_harness_with_batching(None, test)
+}
+
+fn test_harness_with_batching<T: Future<Output = VirtualOverseer>>(
+	message_batcher: Option<MessageBatcher>,
+	test: impl FnOnce(TestHarness) -> T,
+) {
 	let genesis_hash = Hash::repeat_byte(0xff);
 	let fork_id = None;
 	let req_protocol_names = ReqProtocolNames::new(genesis_hash, fork_id);
@@ -248,7 +255,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(test: impl FnOnce(TestHarne
 	let (context, virtual_overseer) =
 		polkadot_node_subsystem_test_helpers::make_subsystem_context(pool);
 
-	let bridge_out = NetworkBridgeTx::new(
+	let mut bridge_out = NetworkBridgeTx::new(
 		network,
 		discovery,
 		Metrics(None),
@@ -256,6 +263,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(test: impl FnOnce(TestHarne
 		peerset_protocol_names,
 		network_notification_sinks,
 	);
+	bridge_out.message_batcher = message_batcher;
 
 	let network_bridge_out_fut = run_network_out(bridge_out, context)
 		.map_err(|e| panic!("bridge-out subsystem execution failed {:?}", e))
@@ -369,3 +377,151 @@ fn send_messages_to_peers() {
 		virtual_overseer
 	});
 }
+
+#[test]
+fn small_validation_messages_are_batched() {
+	let message_batching = MessageBatching { max_message_size: 16, ..Default::default() };
+	let message_batcher = MessageBatcher::new(message_batching);
+	test_harness_with_batching(Some(message_batcher.clone()), |test_harness| async move {
+		let TestHarness { mut network_handle, mut virtual_overseer } = test_harness;
+
+		let peer = PeerId::random();
+		network_handle.connect_peer(peer, PeerSet::Validation, ObservedRole::Full).await;
+		message_batcher.note_peer_connected(peer, ValidationVersion::V4.into());
+
+		let small_message = protocol_v3::ValidationProtocol::ApprovalDistribution(
+			protocol_v3::ApprovalDistributionMessage::Approvals(Vec::new()),
+		);
+		let large_message = protocol_v3::ValidationProtocol::StatementDistribution(
+			protocol_v3::StatementDistributionMessage::BackedCandidateKnown(
+				protocol_v3::BackedCandidateAcknowledgement {
+					candidate_hash: polkadot_primitives::CandidateHash(Hash::repeat_byte(1)),
+					statement_knowledge: protocol_v3::StatementFilter::blank(4),
+				},
+			),
+		);
+		assert!(large_message.encoded_size() > message_batching.max_message_size);
+
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: NetworkBridgeTxMessage::SendValidationMessages(vec![
+					(vec![peer], ValidationProtocols::V3(small_message.clone())),
+					(vec![peer], ValidationProtocols::V3(small_message.clone())),
+				]),
+			})
+			.await;
+
+		// Both messages are sent together once the batching window ended.
+		assert_eq!(
+			network_handle
+				.next_network_action()
+				.timeout(TIMEOUT)
+				.await
+				.expect("Batch is sent"),
+			NetworkAction::WriteNotification(
+				peer,
+				PeerSet::Validation,
+				WireMessage::Batch(vec![small_message.clone(), small_message.clone()]).encode(),
+			)
+		);
+
+		// A large message is not held back, but flushes the pending messages first.
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: NetworkBridgeTxMessage::SendValidationMessages(vec![
+					(vec![peer], ValidationProtocols::V3(small_message.clone())),
+					(vec![peer], ValidationProtocols::V3(large_message.clone())),
+				]),
+			})
+			.await;
+
+		assert_eq!(
+			network_handle.next_network_action().timeout(TIMEOUT).await.unwrap(),
+			NetworkAction::WriteNotification(
+				peer,
+				PeerSet::Validation,
+				WireMessage::ProtocolMessage(small_message).encode(),
+			)
+		);
+		assert_eq!(
+			network_handle.next_network_action().timeout(TIMEOUT).await.unwrap(),
+			NetworkAction::WriteNotification(
+				peer,
+				PeerSet::Validation,
+				WireMessage::ProtocolMessage(large_message).encode(),
+			)
+		);
+
+		virtual_overseer
+	});
+}
+
+#[test]
+fn view_updates_do_not_overtake_batched_messages() {
+	let message_batching =
+		MessageBatching { window: std::time::Duration::from_secs(60), ..Default::default() };
+	let message_batcher = MessageBatcher::new(message_batching);
+	test_harness_with_batching(Some(message_batcher.clone()), |test_harness| async move {
+		let TestHarness { mut network_handle, mut virtual_overseer } = test_harness;
+
+		let peer = PeerId::random();
+		let peer_v3 = PeerId::random();
+		network_handle.connect_peer(peer, PeerSet::Validation, ObservedRole::Full).await;
+		network_handle
+			.connect_peer(peer_v3, PeerSet::Validation, ObservedRole::Full)
+			.await;
+		message_batcher.note_peer_connected(peer, ValidationVersion::V4.into());
+		message_batcher.note_peer_connected(peer_v3, ValidationVersion::V3.into());
+
+		let message = protocol_v3::ValidationProtocol::ApprovalDistribution(
+			protocol_v3::ApprovalDistributionMessage::Approvals(Vec::new()),
+		);
+
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: NetworkBridgeTxMessage::SendValidationMessage(
+					vec![peer, peer_v3],
+					ValidationProtocols::V3(message.clone()),
+				),
+			})
+			.await;
+
+		// Peers of version 3 can't decode batches and get the message right away.
+		assert_eq!(
+			network_handle.next_network_action().timeout(TIMEOUT).await.unwrap(),
+			NetworkAction::WriteNotification(
+				peer_v3,
+				PeerSet::Validation,
+				WireMessage::ProtocolMessage(message.clone()).encode(),
+			)
+		);
+
+		// Our view is only sent after the message held back for `peer`.
+		let view = View::new(vec![Hash::repeat_byte(1)], 1);
+		message_batcher.send_view_update(
+			vec![peer],
+			view.clone(),
+			&Metrics(None),
+			&network_handle.notification_sinks,
+		);
+
+		assert_eq!(
+			network_handle.next_network_action().timeout(TIMEOUT).await.unwrap(),
+			NetworkAction::WriteNotification(
+				peer,
+				PeerSet::Validation,
+				WireMessage::ProtocolMessage(message).encode(),
+			)
+		);
+		assert_eq!(
+			network_handle.next_network_action().timeout(TIMEOUT).await.unwrap(),
+			NetworkAction::WriteNotification(
+				peer,
+				PeerSet::Validation,
+				WireMessage::<protocol_v3::ValidationProtocol>::ViewUpdate(view).encode(),
+			)
+		);
+
+		virtual_overseer
+	});
+}
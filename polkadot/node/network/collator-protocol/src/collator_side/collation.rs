@@ -18,10 +18,14 @@
 
 use std::collections::{HashSet, VecDeque};
 
+use codec::Encode;
 use futures::{future::BoxFuture, stream::FuturesUnordered};
 
 use polkadot_node_network_protocol::{
-	request_response::{incoming::OutgoingResponse, v2 as protocol_v2, IncomingRequest},
+	request_response::{
+		incoming::OutgoingResponse, v1 as protocol_v1, v2 as protocol_v2, IncomingRequest,
+		OutgoingResult,
+	},
 	PeerId,
 };
 use polkadot_node_primitives::PoV;
@@ -32,6 +36,12 @@ use polkadot_primitives::{
 
 /// The status of a collation as seen from the collator.
 pub enum CollationStatus {
+	/// The collation is checked by a validator of the backing group, it is not advertised until
+	/// the result is in.
+	Prechecking,
+	/// The collation was pushed to a validator of the backing group, it is not advertised unless
+	/// the validator refuses it.
+	Pushing,
 	/// The collation was created, but we did not advertise it to any validator.
 	Created,
 	/// The collation was advertised to at least one validator.
@@ -68,6 +78,26 @@ pub struct Collation {
 	pub status: CollationStatus,
 }
 
+impl Collation {
+	/// The encoded [`protocol_v2::CollationFetchingResponse`] to a request for this collation.
+	pub fn encoded_response(&self) -> Vec<u8> {
+		let response = match &self.parent_head_data {
+			ParentHeadData::WithData { head_data, .. } =>
+				protocol_v2::CollationFetchingResponse::CollationWithParentHeadData {
+					receipt: self.receipt.clone(),
+					pov: self.pov.clone(),
+					parent_head_data: head_data.clone(),
+				},
+			ParentHeadData::OnlyHash(_) => protocol_v2::CollationFetchingResponse::Collation(
+				self.receipt.clone(),
+				self.pov.clone(),
+			),
+		};
+
+		response.encode()
+	}
+}
+
 /// Stores the state for waiting collation fetches per relay parent.
 #[derive(Default)]
 pub struct WaitingCollationFetches {
@@ -126,13 +156,15 @@ impl VersionedCollationRequest {
 		}
 	}
 
-	/// Sends the response back to requester.
-	pub fn send_outgoing_response(
+	/// Sends the already encoded response back to requester.
+	///
+	/// See [`Collation::encoded_response`].
+	pub fn send_encoded_outgoing_response(
 		self,
-		response: OutgoingResponse<protocol_v2::CollationFetchingResponse>,
+		response: OutgoingResponse<Vec<u8>>,
 	) -> Result<(), ()> {
 		match self {
-			VersionedCollationRequest::V2(req) => req.send_outgoing_response(response),
+			VersionedCollationRequest::V2(req) => req.send_encoded_outgoing_response(response),
 		}
 	}
 }
@@ -154,3 +186,33 @@ pub struct CollationSendResult {
 }
 
 pub type ActiveCollationFetches = FuturesUnordered<BoxFuture<'static, CollationSendResult>>;
+
+/// The result of a collation precheck request.
+pub struct PrecheckResult {
+	/// Relay parent of the collation.
+	pub relay_parent: Hash,
+	/// Candidate hash of the collation.
+	pub candidate_hash: CandidateHash,
+	/// The validator that prechecked the collation.
+	pub peer_id: PeerId,
+	/// The response of the validator.
+	pub response: OutgoingResult<protocol_v1::CollationPrecheckResponse>,
+}
+
+/// Collation precheck requests which are still waiting for a response.
+pub type ActivePrechecks = FuturesUnordered<BoxFuture<'static, PrecheckResult>>;
+
+/// The result of pushing a collation to a validator.
+pub struct PushResult {
+	/// Relay parent of the collation.
+	pub relay_parent: Hash,
+	/// Candidate hash of the collation.
+	pub candidate_hash: CandidateHash,
+	/// The validator the collation was pushed to.
+	pub peer_id: PeerId,
+	/// The response of the validator.
+	pub response: OutgoingResult<protocol_v1::CollationPushResponse>,
+}
+
+/// Pushed collations which are still waiting for a response.
+pub type ActivePushes = FuturesUnordered<BoxFuture<'static, PushResult>>;
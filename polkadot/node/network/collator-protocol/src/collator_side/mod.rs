@@ -31,7 +31,8 @@ use polkadot_node_network_protocol::{
 	peer_set::{CollationVersion, PeerSet},
 	request_response::{
 		incoming::{self, OutgoingResponse},
-		v2 as request_v2, IncomingRequestReceiver,
+		v1 as request_v1, v2 as request_v2, IncomingRequestReceiver, OutgoingRequest, Recipient,
+		Requests,
 	},
 	v1 as protocol_v1, v2 as protocol_v2, CollationProtocols, OurView, PeerId,
 	UnifiedReputationChange as Rep, View,
@@ -39,7 +40,8 @@ use polkadot_node_network_protocol::{
 use polkadot_node_primitives::{CollationSecondedSignal, PoV, Statement};
 use polkadot_node_subsystem::{
 	messages::{
-		CollatorProtocolMessage, NetworkBridgeEvent, NetworkBridgeTxMessage, ParentHeadData,
+		CollatorProtocolMessage, IfDisconnected, NetworkBridgeEvent, NetworkBridgeTxMessage,
+		ParentHeadData,
 	},
 	overseer, FromOrchestra, OverseerSignal,
 };
@@ -50,8 +52,9 @@ use polkadot_node_subsystem_util::{
 	TimeoutExt,
 };
 use polkadot_primitives::{
-	vstaging::CandidateReceiptV2 as CandidateReceipt, AuthorityDiscoveryId, CandidateHash,
-	CollatorPair, CoreIndex, GroupIndex, Hash, HeadData, Id as ParaId, SessionIndex,
+	node_features::FeatureIndex, vstaging::CandidateReceiptV2 as CandidateReceipt,
+	AuthorityDiscoveryId, CandidateHash, CollatorPair, CoreIndex, GroupIndex, Hash, HeadData,
+	Id as ParaId, SessionIndex,
 };
 
 use crate::{modify_reputation, LOG_TARGET};
@@ -59,15 +62,18 @@ use crate::{modify_reputation, LOG_TARGET};
 mod collation;
 mod error;
 mod metrics;
+mod response_cache;
 #[cfg(test)]
 mod tests;
 mod validators_buffer;
 
 use collation::{
-	ActiveCollationFetches, Collation, CollationSendResult, CollationStatus,
-	VersionedCollationRequest, WaitingCollationFetches,
+	ActiveCollationFetches, ActivePrechecks, ActivePushes, Collation, CollationSendResult,
+	CollationStatus, PrecheckResult, PushResult, VersionedCollationRequest,
+	WaitingCollationFetches,
 };
 use error::{log_error, Error, FatalError, Result};
+use response_cache::{ResponseCache, DEFAULT_RESPONSE_CACHE_SIZE};
 use validators_buffer::{
 	ResetInterestTimeout, ValidatorGroupsBuffer, RESET_INTEREST_TIMEOUT, VALIDATORS_BUFFER_CAPACITY,
 };
@@ -304,8 +310,24 @@ struct State {
 	/// to unneeded peers.
 	advertisement_timeouts: FuturesUnordered<ResetInterestTimeout>,
 
+	/// Encoded responses of already requested collations.
+	///
+	/// Entries are removed together with the collation when its relay parent moves out of
+	/// our view.
+	response_cache: ResponseCache,
+
 	/// Aggregated reputation change
 	reputation: ReputationAggregator,
+
+	/// Whether collations are prechecked by a validator of the backing group before they are
+	/// advertised.
+	precheck_collations: bool,
+
+	/// Collation precheck requests in flight.
+	active_prechecks: ActivePrechecks,
+
+	/// Collations pushed to validators, waiting for their response.
+	active_pushes: ActivePushes,
 }
 
 impl State {
@@ -314,12 +336,14 @@ impl State {
 	fn new(
 		local_peer_id: PeerId,
 		collator_pair: CollatorPair,
+		precheck_collations: bool,
 		metrics: Metrics,
 		reputation: ReputationAggregator,
 	) -> State {
 		State {
 			local_peer_id,
 			collator_pair,
+			precheck_collations,
 			metrics,
 			collating_on: Default::default(),
 			peer_data: Default::default(),
@@ -332,8 +356,35 @@ impl State {
 			waiting_collation_fetches: Default::default(),
 			active_collation_fetches: Default::default(),
 			advertisement_timeouts: Default::default(),
+			response_cache: ResponseCache::new(DEFAULT_RESPONSE_CACHE_SIZE),
 			reputation,
+			active_prechecks: Default::default(),
+			active_pushes: Default::default(),
+		}
+	}
+
+	/// The encoded response to a request for a collation we distributed, if we still have it.
+	///
+	/// The response is encoded on the first request and served from the cache afterwards.
+	fn collation_response(
+		&mut self,
+		relay_parent: &Hash,
+		candidate_hash: &CandidateHash,
+	) -> Option<Vec<u8>> {
+		if let Some(response) = self.response_cache.get(candidate_hash) {
+			return Some(response.to_vec())
 		}
+
+		let response = self
+			.per_relay_parent
+			.get(relay_parent)?
+			.collations
+			.get(candidate_hash)?
+			.collation()
+			.encoded_response();
+		self.response_cache.insert(*candidate_hash, response.clone());
+
+		Some(response)
 	}
 }
 
@@ -428,7 +479,7 @@ async fn distribute_collation<Context>(
 	let our_core = core_index;
 
 	// Determine the group on that core.
-	let GroupValidators { validators, session_index, group_index } =
+	let GroupValidators { validators, session_index, group_index, collation_push } =
 		determine_our_validators(ctx, runtime, our_core, candidate_relay_parent).await?;
 
 	if validators.is_empty() {
@@ -478,6 +529,73 @@ async fn distribute_collation<Context>(
 		state.collation_result_senders.insert(candidate_hash, result_sender);
 	}
 
+	// Any connected validator of the group can have the collation pushed to it, or precheck it.
+	let group = &per_relay_parent.validator_group[&core_index];
+	let connected_validator = state
+		.peer_ids
+		.iter()
+		.find(|(_, ids)| ids.iter().any(|id| group.validators.contains(id)))
+		.map(|(peer_id, _)| *peer_id);
+
+	let status = match connected_validator {
+		Some(peer_id) if collation_push => {
+			let (request, response) = OutgoingRequest::new(
+				Recipient::Peer(peer_id),
+				request_v1::CollationPushRequest {
+					candidate_receipt: receipt.clone(),
+					pov: pov.clone(),
+					parent_head_data: parent_head_data.clone(),
+				},
+			);
+			ctx.send_message(NetworkBridgeTxMessage::SendRequests(
+				vec![Requests::CollationPushV1(request)],
+				IfDisconnected::ImmediateError,
+			))
+			.await;
+			state.active_pushes.push(
+				async move {
+					PushResult {
+						relay_parent: candidate_relay_parent,
+						candidate_hash,
+						peer_id,
+						response: response.await,
+					}
+				}
+				.boxed(),
+			);
+
+			CollationStatus::Pushing
+		},
+		Some(peer_id) if state.precheck_collations => {
+			let (request, response) = OutgoingRequest::new(
+				Recipient::Peer(peer_id),
+				request_v1::CollationPrecheckRequest {
+					candidate_receipt: receipt.clone(),
+					parent_head_data: parent_head_data.clone(),
+				},
+			);
+			ctx.send_message(NetworkBridgeTxMessage::SendRequests(
+				vec![Requests::CollationPrecheckV1(request)],
+				IfDisconnected::ImmediateError,
+			))
+			.await;
+			state.active_prechecks.push(
+				async move {
+					PrecheckResult {
+						relay_parent: candidate_relay_parent,
+						candidate_hash,
+						peer_id,
+						response: response.await,
+					}
+				}
+				.boxed(),
+			);
+
+			CollationStatus::Prechecking
+		},
+		_ => CollationStatus::Created,
+	};
+
 	let parent_head_data = if elastic_scaling {
 		ParentHeadData::WithData { hash: parent_head_data_hash, head_data: parent_head_data }
 	} else {
@@ -486,12 +604,24 @@ async fn distribute_collation<Context>(
 
 	per_relay_parent.collations.insert(
 		candidate_hash,
-		CollationWithCoreIndex(
-			Collation { receipt, pov, parent_head_data, status: CollationStatus::Created },
-			core_index,
-		),
+		CollationWithCoreIndex(Collation { receipt, pov, parent_head_data, status }, core_index),
 	);
 
+	advertise_to_interested_peers(ctx, state, candidate_relay_parent, id).await;
+
+	Ok(())
+}
+
+/// Advertise the collations at `relay_parent` to all connected peers interested in it.
+#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
+async fn advertise_to_interested_peers<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	relay_parent: Hash,
+	para_id: ParaId,
+) {
+	let Some(per_relay_parent) = state.per_relay_parent.get_mut(&relay_parent) else { return };
+
 	// The leaf should be present in the allowed ancestry of some leaf.
 	//
 	// It's collation-producer responsibility to verify that there exists
@@ -503,9 +633,9 @@ async fn distribute_collation<Context>(
 			v.iter().any(|block_hash| {
 				state.implicit_view.as_ref().map(|implicit_view| {
 					implicit_view
-						.known_allowed_relay_parents_under(block_hash, Some(id))
+						.known_allowed_relay_parents_under(block_hash, Some(para_id))
 						.unwrap_or_default()
-						.contains(&candidate_relay_parent)
+						.contains(&relay_parent)
 				}) == Some(true)
 			})
 		})
@@ -515,7 +645,7 @@ async fn distribute_collation<Context>(
 	for peer_id in interested {
 		advertise_collation(
 			ctx,
-			candidate_relay_parent,
+			relay_parent,
 			per_relay_parent,
 			peer_id,
 			&state.peer_ids,
@@ -524,8 +654,130 @@ async fn distribute_collation<Context>(
 		)
 		.await;
 	}
+}
 
-	Ok(())
+/// Act on the response of a validator to a collation precheck request.
+///
+/// A single validator can't veto a collation, it might be malicious or lag behind. A collation
+/// rejected for reasons that don't depend on how far the validator is in following the relay chain
+/// is not advertised to it, but still to the rest of the backing group. On any error, the collation
+/// is advertised as if it was not prechecked, validators without support for the protocol are not
+/// held against it.
+#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
+async fn handle_precheck_result<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	PrecheckResult { relay_parent, candidate_hash, peer_id, response }: PrecheckResult,
+) {
+	use request_v1::{CollationPrecheckRejection as Rejection, CollationPrecheckResponse};
+
+	let Some(para_id) = state.collating_on else { return };
+	let Some(per_relay_parent) = state.per_relay_parent.get_mut(&relay_parent) else {
+		// The relay parent went out of view, together with the collation.
+		return
+	};
+	let Some(collation_with_core) = per_relay_parent.collations.get_mut(&candidate_hash) else {
+		return
+	};
+	collation_with_core.collation_mut().status = CollationStatus::Created;
+	let core_index = *collation_with_core.core_index();
+
+	match response {
+		Ok(CollationPrecheckResponse::Rejected(
+			reason @ (Rejection::ParaNotAssigned |
+			Rejection::InvalidDescriptor |
+			Rejection::InvalidPersistedValidationData),
+		)) => {
+			gum::debug!(
+				target: LOG_TARGET,
+				?relay_parent,
+				?candidate_hash,
+				?peer_id,
+				?reason,
+				"Collation was rejected by a validator, advertising it to the others",
+			);
+
+			if let Some(validator_group) = per_relay_parent.validator_group.get_mut(&core_index) {
+				validator_group.advertised_to_peer(&candidate_hash, &state.peer_ids, &peer_id);
+			}
+		},
+		Ok(response) => gum::debug!(
+			target: LOG_TARGET,
+			?relay_parent,
+			?candidate_hash,
+			?peer_id,
+			?response,
+			"Collation was prechecked",
+		),
+		Err(err) => gum::debug!(
+			target: LOG_TARGET,
+			?relay_parent,
+			?candidate_hash,
+			?peer_id,
+			?err,
+			"Collation precheck failed, advertising collation anyway",
+		),
+	}
+
+	advertise_to_interested_peers(ctx, state, relay_parent, para_id).await;
+}
+
+/// Act on the response of a validator to a collation pushed to it.
+///
+/// A collation accepted by the validator counts as requested, the validator already started
+/// seconding it. Otherwise the collation is advertised to the backing group, as if it was never
+/// pushed.
+#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
+async fn handle_push_result<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	PushResult { relay_parent, candidate_hash, peer_id, response }: PushResult,
+) {
+	let Some(para_id) = state.collating_on else { return };
+	let Some(collation_with_core) = state
+		.per_relay_parent
+		.get_mut(&relay_parent)
+		.and_then(|per_relay_parent| per_relay_parent.collations.get_mut(&candidate_hash))
+	else {
+		// The relay parent went out of view, together with the collation.
+		return
+	};
+	let collation = collation_with_core.collation_mut();
+
+	match response {
+		Ok(request_v1::CollationPushResponse::Accepted) => {
+			gum::debug!(
+				target: LOG_TARGET,
+				?relay_parent,
+				?candidate_hash,
+				?peer_id,
+				"Pushed collation was accepted by a validator",
+			);
+
+			collation.status.advance_to_requested();
+			state.metrics.on_collation_sent();
+			return
+		},
+		Ok(request_v1::CollationPushResponse::Rejected) => gum::debug!(
+			target: LOG_TARGET,
+			?relay_parent,
+			?candidate_hash,
+			?peer_id,
+			"Pushed collation was rejected by a validator, advertising collation",
+		),
+		Err(err) => gum::debug!(
+			target: LOG_TARGET,
+			?relay_parent,
+			?candidate_hash,
+			?peer_id,
+			?err,
+			"Pushing collation failed, advertising collation",
+		),
+	}
+
+	collation.status = CollationStatus::Created;
+
+	advertise_to_interested_peers(ctx, state, relay_parent, para_id).await;
 }
 
 /// Validators of a particular group index.
@@ -536,6 +788,9 @@ struct GroupValidators {
 
 	session_index: SessionIndex,
 	group_index: GroupIndex,
+
+	/// Whether collations can be pushed to the validators, see [`FeatureIndex::CollationPush`].
+	collation_push: bool,
 }
 
 /// Figure out current group of validators assigned to the para being collated on.
@@ -549,10 +804,15 @@ async fn determine_our_validators<Context>(
 	relay_parent: Hash,
 ) -> Result<GroupValidators> {
 	let session_index = runtime.get_session_index_for_child(ctx.sender(), relay_parent).await?;
-	let info = &runtime
+	let extended_info = runtime
 		.get_session_info_by_index(ctx.sender(), relay_parent, session_index)
-		.await?
-		.session_info;
+		.await?;
+	let collation_push = extended_info
+		.node_features
+		.get(FeatureIndex::CollationPush as usize)
+		.map(|b| *b)
+		.unwrap_or(false);
+	let info = &extended_info.session_info;
 	gum::debug!(target: LOG_TARGET, ?session_index, "Received session info");
 	let groups = &info.validator_groups;
 	let num_cores = groups.len();
@@ -571,6 +831,7 @@ async fn determine_our_validators<Context>(
 		validators: current_validators,
 		session_index,
 		group_index: current_group_index,
+		collation_push,
 	};
 
 	Ok(current_validators)
@@ -640,6 +901,17 @@ async fn advertise_collation<Context>(
 		let core_index = *collation_and_core.core_index();
 		let collation = collation_and_core.collation_mut();
 
+		if matches!(collation.status, CollationStatus::Prechecking | CollationStatus::Pushing) {
+			gum::trace!(
+				target: LOG_TARGET,
+				?relay_parent,
+				?candidate_hash,
+				peer_id = %peer,
+				"Not advertising collation before the precheck or push is concluded",
+			);
+			continue
+		}
+
 		let Some(validator_group) = per_relay_parent.validator_group.get_mut(&core_index) else {
 			gum::debug!(
 				target: LOG_TARGET,
@@ -785,34 +1057,22 @@ async fn process_msg<Context>(
 }
 
 /// Issue a response to a previously requested collation.
-async fn send_collation(
-	state: &mut State,
-	request: VersionedCollationRequest,
-	receipt: CandidateReceipt,
-	pov: PoV,
-	parent_head_data: ParentHeadData,
-) {
+///
+/// `response` is the encoded response, see [`State::collation_response`].
+async fn send_collation(state: &mut State, request: VersionedCollationRequest, response: Vec<u8>) {
 	let (tx, rx) = oneshot::channel();
 
 	let relay_parent = request.relay_parent();
 	let peer_id = request.peer_id();
-	let candidate_hash = receipt.hash();
+	let candidate_hash = request.candidate_hash();
 
-	let result = match parent_head_data {
-		ParentHeadData::WithData { head_data, .. } =>
-			Ok(request_v2::CollationFetchingResponse::CollationWithParentHeadData {
-				receipt,
-				pov,
-				parent_head_data: head_data,
-			}),
-		ParentHeadData::OnlyHash(_) =>
-			Ok(request_v2::CollationFetchingResponse::Collation(receipt, pov)),
+	let response = OutgoingResponse {
+		result: Ok(response),
+		reputation_changes: Vec::new(),
+		sent_feedback: Some(tx),
 	};
 
-	let response =
-		OutgoingResponse { result, reputation_changes: Vec::new(), sent_feedback: Some(tx) };
-
-	if let Err(_) = request.send_outgoing_response(response) {
+	if let Err(_) = request.send_encoded_outgoing_response(response) {
 		gum::warn!(target: LOG_TARGET, "Sending collation response failed");
 	}
 
@@ -978,29 +1238,23 @@ async fn handle_incoming_request<Context>(
 				VersionedCollationRequest::V2(req) =>
 					per_relay_parent.collations.get_mut(&req.payload.candidate_hash),
 			};
-			let (receipt, pov, parent_head_data) =
-				if let Some(collation_with_core) = collation_with_core {
-					let collation = collation_with_core.collation_mut();
-					collation.status.advance_to_requested();
-					(
-						collation.receipt.clone(),
-						collation.pov.clone(),
-						collation.parent_head_data.clone(),
-					)
-				} else {
-					gum::warn!(
-						target: LOG_TARGET,
-						relay_parent = %relay_parent,
-						"received a `RequestCollation` for a relay parent we don't have collation stored.",
-					);
+			let candidate_hash = if let Some(collation_with_core) = collation_with_core {
+				let collation = collation_with_core.collation_mut();
+				collation.status.advance_to_requested();
+				collation.receipt.hash()
+			} else {
+				gum::warn!(
+					target: LOG_TARGET,
+					relay_parent = %relay_parent,
+					"received a `RequestCollation` for a relay parent we don't have collation stored.",
+				);
 
-					return Ok(())
-				};
+				return Ok(())
+			};
 
 			state.metrics.on_collation_sent_requested();
 
 			let waiting = state.waiting_collation_fetches.entry(relay_parent).or_default();
-			let candidate_hash = receipt.hash();
 
 			if !waiting.waiting_peers.insert((peer_id, candidate_hash)) {
 				gum::debug!(
@@ -1023,7 +1277,10 @@ async fn handle_incoming_request<Context>(
 				waiting.collation_fetch_active = true;
 				// Obtain a timer for sending collation
 				let _ = state.metrics.time_collation_distribution("send");
-				send_collation(state, req, receipt, pov, parent_head_data).await;
+				let response = state
+					.collation_response(&relay_parent, &candidate_hash)
+					.expect("the collation was found in our view above; qed");
+				send_collation(state, req, response).await;
 			}
 		},
 		Some(our_para_id) => {
@@ -1284,8 +1541,11 @@ async fn handle_our_view_change<Context>(
 				let candidate_hash = collation.receipt.hash();
 				state.collation_result_senders.remove(&candidate_hash);
 				state.validator_groups_buf.remove_candidate(&candidate_hash);
+				state.response_cache.remove(&candidate_hash);
 
 				match collation.status {
+					CollationStatus::Prechecking |
+					CollationStatus::Pushing |
 					CollationStatus::Created => gum::warn!(
 						target: LOG_TARGET,
 						candidate_hash = ?collation.receipt.hash(),
@@ -1319,6 +1579,7 @@ pub(crate) async fn run<Context>(
 	local_peer_id: PeerId,
 	collator_pair: CollatorPair,
 	req_v2_receiver: IncomingRequestReceiver<request_v2::CollationFetchingRequest>,
+	precheck_collations: bool,
 	metrics: Metrics,
 ) -> std::result::Result<(), FatalError> {
 	run_inner(
@@ -1326,6 +1587,7 @@ pub(crate) async fn run<Context>(
 		local_peer_id,
 		collator_pair,
 		req_v2_receiver,
+		precheck_collations,
 		metrics,
 		ReputationAggregator::default(),
 		REPUTATION_CHANGE_INTERVAL,
@@ -1339,6 +1601,7 @@ async fn run_inner<Context>(
 	local_peer_id: PeerId,
 	collator_pair: CollatorPair,
 	mut req_v2_receiver: IncomingRequestReceiver<request_v2::CollationFetchingRequest>,
+	precheck_collations: bool,
 	metrics: Metrics,
 	reputation: ReputationAggregator,
 	reputation_interval: Duration,
@@ -1348,7 +1611,8 @@ async fn run_inner<Context>(
 	let new_reputation_delay = || futures_timer::Delay::new(reputation_interval).fuse();
 	let mut reputation_delay = new_reputation_delay();
 
-	let mut state = State::new(local_peer_id, collator_pair, metrics, reputation);
+	let mut state =
+		State::new(local_peer_id, collator_pair, precheck_collations, metrics, reputation);
 	let mut runtime = RuntimeInfo::new(None);
 
 	loop {
@@ -1411,24 +1675,18 @@ async fn run_inner<Context>(
 					continue
 				};
 
-				let next_collation_with_core = {
-					let per_relay_parent = match state.per_relay_parent.get(&relay_parent) {
-						Some(per_relay_parent) => per_relay_parent,
-						None => continue,
-					};
-
-					per_relay_parent.collations.get(&next.candidate_hash())
-				};
-
-				if let Some(collation_with_core) = next_collation_with_core {
-					let collation = collation_with_core.collation();
-					let receipt = collation.receipt.clone();
-					let pov = collation.pov.clone();
-					let parent_head_data = collation.parent_head_data.clone();
-
-					send_collation(&mut state, next, receipt, pov, parent_head_data).await;
+				if let Some(response) =
+					state.collation_response(&relay_parent, &next.candidate_hash())
+				{
+					send_collation(&mut state, next, response).await;
 				}
 			},
+			precheck = state.active_prechecks.select_next_some() => {
+				handle_precheck_result(&mut ctx, &mut state, precheck).await;
+			},
+			push = state.active_pushes.select_next_some() => {
+				handle_push_result(&mut ctx, &mut state, push).await;
+			},
 			(candidate_hash, peer_id) = state.advertisement_timeouts.select_next_some() => {
 				// NOTE: it doesn't necessarily mean that a validator gets disconnected,
 				// it only will if there're no other advertisements we want to send.
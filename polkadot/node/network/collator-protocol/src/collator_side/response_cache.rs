@@ -0,0 +1,140 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Cache of encoded collation fetching responses.
+//!
+//! With elastic scaling and group rotations a single collation is usually fetched by several
+//! validators. Encoding the response means copying the whole PoV, so instead of doing it for
+//! every request we keep the encoded response around until the collation goes out of view.
+//!
+//! The cache is bounded by the total size of the stored responses. When it is full the oldest
+//! responses are evicted first; an evicted response is simply encoded again on the next request.
+
+use std::collections::{HashMap, VecDeque};
+
+use polkadot_primitives::CandidateHash;
+
+/// The default upper bound on the total size of the cached responses, in bytes.
+///
+/// Enough to keep a few maximum sized PoVs of all collations in view.
+pub const DEFAULT_RESPONSE_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Encoded collation fetching responses keyed by candidate hash.
+pub struct ResponseCache {
+	/// The encoded responses.
+	responses: HashMap<CandidateHash, Vec<u8>>,
+	/// Candidates in insertion order, oldest first.
+	order: VecDeque<CandidateHash>,
+	/// Total size of all responses in `responses`.
+	size: usize,
+	/// The upper bound on `size`.
+	max_size: usize,
+}
+
+impl ResponseCache {
+	/// Create an empty cache holding at most `max_size` bytes of responses.
+	pub fn new(max_size: usize) -> Self {
+		Self { responses: HashMap::new(), order: VecDeque::new(), size: 0, max_size }
+	}
+
+	/// Get the cached response for `candidate_hash`.
+	pub fn get(&self, candidate_hash: &CandidateHash) -> Option<&[u8]> {
+		self.responses.get(candidate_hash).map(|response| response.as_slice())
+	}
+
+	/// Cache the encoded `response` for `candidate_hash`, evicting the oldest responses if needed.
+	///
+	/// Responses larger than the whole cache are not stored.
+	pub fn insert(&mut self, candidate_hash: CandidateHash, response: Vec<u8>) {
+		if response.len() > self.max_size {
+			return
+		}
+
+		self.remove(&candidate_hash);
+		while self.size + response.len() > self.max_size {
+			let Some(oldest) = self.order.pop_front() else { break };
+			if let Some(evicted) = self.responses.remove(&oldest) {
+				self.size -= evicted.len();
+			}
+		}
+
+		self.size += response.len();
+		self.order.push_back(candidate_hash);
+		self.responses.insert(candidate_hash, response);
+	}
+
+	/// Remove the response for `candidate_hash`, e.g. because its relay parent went out of view.
+	pub fn remove(&mut self, candidate_hash: &CandidateHash) {
+		if let Some(response) = self.responses.remove(candidate_hash) {
+			self.size -= response.len();
+			self.order.retain(|hash| hash != candidate_hash);
+		}
+	}
+
+	/// Total size of the cached responses, in bytes.
+	#[cfg(test)]
+	pub fn size(&self) -> usize {
+		self.size
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_primitives::Hash;
+
+	fn candidate(n: u8) -> CandidateHash {
+		CandidateHash(Hash::repeat_byte(n))
+	}
+
+	#[test]
+	fn evicts_oldest_responses_when_full() {
+		let mut cache = ResponseCache::new(10);
+
+		cache.insert(candidate(1), vec![1; 4]);
+		cache.insert(candidate(2), vec![2; 4]);
+		assert_eq!(cache.size(), 8);
+
+		cache.insert(candidate(3), vec![3; 4]);
+		assert_eq!(cache.get(&candidate(1)), None);
+		assert_eq!(cache.get(&candidate(2)), Some(&[2; 4][..]));
+		assert_eq!(cache.get(&candidate(3)), Some(&[3; 4][..]));
+		assert_eq!(cache.size(), 8);
+
+		// Too large to be cached at all.
+		cache.insert(candidate(4), vec![4; 11]);
+		assert_eq!(cache.get(&candidate(4)), None);
+		assert_eq!(cache.size(), 8);
+	}
+
+	#[test]
+	fn remove_releases_memory() {
+		let mut cache = ResponseCache::new(10);
+
+		cache.insert(candidate(1), vec![1; 6]);
+		cache.insert(candidate(1), vec![1; 4]);
+		assert_eq!(cache.size(), 4);
+
+		cache.insert(candidate(2), vec![2; 6]);
+		cache.remove(&candidate(1));
+		assert_eq!(cache.get(&candidate(1)), None);
+		assert_eq!(cache.size(), 6);
+
+		// Removing a missing entry is a no-op.
+		cache.remove(&candidate(1));
+		assert_eq!(cache.size(), 6);
+	}
+}
@@ -27,7 +27,7 @@ use futures_timer::Delay;
 
 use codec::{Decode, Encode};
 
-use sc_network::config::IncomingRequest as RawIncomingRequest;
+use sc_network::{config::IncomingRequest as RawIncomingRequest, ProtocolName};
 use sp_core::crypto::Pair;
 use sp_keyring::Sr25519Keyring;
 use sp_runtime::traits::AppVerify;
@@ -35,6 +35,7 @@ use sp_runtime::traits::AppVerify;
 use polkadot_node_network_protocol::{
 	peer_set::CollationVersion,
 	request_response::{
+		v1::{CollationPrecheckRejection, CollationPrecheckResponse, CollationPushResponse},
 		v2::{CollationFetchingRequest, CollationFetchingResponse},
 		IncomingRequest, ReqProtocolNames,
 	},
@@ -47,10 +48,12 @@ use polkadot_node_subsystem::messages::{
 use polkadot_node_subsystem_test_helpers as test_helpers;
 use polkadot_node_subsystem_util::{reputation::add_reputation, TimeoutExt};
 use polkadot_primitives::{
-	AuthorityDiscoveryId, Block, CollatorPair, ExecutorParams, GroupIndex, GroupRotationInfo,
-	IndexedVec, NodeFeatures, SessionIndex, SessionInfo, ValidatorId, ValidatorIndex,
+	node_features, AuthorityDiscoveryId, Block, CollatorPair, ExecutorParams, GroupIndex,
+	GroupRotationInfo, IndexedVec, NodeFeatures, SessionIndex, SessionInfo, ValidatorId,
+	ValidatorIndex,
 };
 use polkadot_primitives_test_helpers::TestCandidateBuilder;
+use rstest::rstest;
 
 mod prospective_parachains;
 use prospective_parachains::{expect_declare_msg, update_view};
@@ -70,6 +73,7 @@ struct TestState {
 	local_peer_id: PeerId,
 	collator_pair: CollatorPair,
 	session_index: SessionIndex,
+	node_features: NodeFeatures,
 }
 
 fn validator_pubkeys(val_ids: &[Sr25519Keyring]) -> IndexedVec<ValidatorIndex, ValidatorId> {
@@ -139,6 +143,7 @@ impl Default for TestState {
 			local_peer_id,
 			collator_pair,
 			session_index: 1,
+			node_features: NodeFeatures::EMPTY,
 		}
 	}
 }
@@ -193,6 +198,16 @@ fn test_harness<T: Future<Output = TestHarness>>(
 	collator_pair: CollatorPair,
 	reputation: ReputationAggregator,
 	test: impl FnOnce(TestHarness) -> T,
+) {
+	test_harness_with_precheck(local_peer_id, collator_pair, reputation, false, test)
+}
+
+fn test_harness_with_precheck<T: Future<Output = TestHarness>>(
+	local_peer_id: PeerId,
+	collator_pair: CollatorPair,
+	reputation: ReputationAggregator,
+	precheck_collations: bool,
+	test: impl FnOnce(TestHarness) -> T,
 ) {
 	let _ = sp_tracing::init_for_tests();
 
@@ -214,6 +229,7 @@ fn test_harness<T: Future<Output = TestHarness>>(
 			local_peer_id,
 			collator_pair,
 			collation_req_v2_receiver,
+			precheck_collations,
 			Default::default(),
 			reputation,
 			REPUTATION_CHANGE_TEST_INTERVAL,
@@ -348,7 +364,7 @@ async fn distribute_collation_with_receipt(
 				_,
 				RuntimeApiRequest::NodeFeatures(_, si_tx),
 			)) => {
-				si_tx.send(Ok(NodeFeatures::EMPTY)).unwrap();
+				si_tx.send(Ok(test_state.node_features.clone())).unwrap();
 			},
 			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
 				_relay_parent,
@@ -1493,3 +1509,167 @@ fn connect_to_buffered_groups() {
 		},
 	);
 }
+
+#[rstest]
+#[case(CollationPrecheckResponse::Accepted, true)]
+#[case(CollationPrecheckResponse::Rejected(CollationPrecheckRejection::UnknownParent), true)]
+#[case(CollationPrecheckResponse::Rejected(CollationPrecheckRejection::InvalidDescriptor), false)]
+fn collations_are_prechecked_before_advertising(
+	#[case] response: CollationPrecheckResponse,
+	#[case] advertised: bool,
+) {
+	let test_state = TestState::default();
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness_with_precheck(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		true,
+		|mut test_harness| async move {
+			let virtual_overseer = &mut test_harness.virtual_overseer;
+
+			let peer = test_state.current_group_validator_peer_ids()[0];
+			let validator_id = test_state.current_group_validator_authority_ids()[0].clone();
+
+			overseer_send(virtual_overseer, CollatorProtocolMessage::CollateOn(test_state.para_id))
+				.await;
+
+			update_view(&test_state, virtual_overseer, vec![(test_state.relay_parent, 10)], 1)
+				.await;
+
+			connect_peer(virtual_overseer, peer, CollationVersion::V2, Some(validator_id)).await;
+			expect_declare_msg(virtual_overseer, &test_state, &peer).await;
+			send_peer_view_change(virtual_overseer, &peer, vec![test_state.relay_parent]).await;
+
+			let DistributeCollation { candidate, .. } =
+				distribute_collation(virtual_overseer, &test_state, test_state.relay_parent, true)
+					.await;
+
+			// The connected validator is asked to precheck the collation before it is advertised.
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendRequests(
+					mut reqs,
+					IfDisconnected::ImmediateError,
+				)) => {
+					assert_eq!(reqs.len(), 1);
+					assert_matches!(
+						reqs.pop(),
+						Some(Requests::CollationPrecheckV1(req)) => {
+							assert_eq!(req.peer, Recipient::Peer(peer));
+							assert_eq!(req.payload.candidate_receipt, candidate);
+							req.pending_response
+								.send(Ok((response.encode(), ProtocolName::from(""))))
+								.unwrap();
+						}
+					);
+				}
+			);
+
+			if advertised {
+				expect_advertise_collation_msg(
+					virtual_overseer,
+					&[peer],
+					test_state.relay_parent,
+					vec![candidate.hash()],
+				)
+				.await;
+			} else {
+				// The collation isn't advertised to the validator that rejected it, but still to
+				// the other validators of the group.
+				let peer2 = test_state.current_group_validator_peer_ids()[1];
+				let validator_id2 = test_state.current_group_validator_authority_ids()[1].clone();
+				connect_peer(virtual_overseer, peer2, CollationVersion::V2, Some(validator_id2))
+					.await;
+				expect_declare_msg(virtual_overseer, &test_state, &peer2).await;
+				send_peer_view_change(virtual_overseer, &peer2, vec![test_state.relay_parent])
+					.await;
+				expect_advertise_collation_msg(
+					virtual_overseer,
+					&[peer2],
+					test_state.relay_parent,
+					vec![candidate.hash()],
+				)
+				.await;
+			}
+
+			test_harness
+		},
+	)
+}
+
+#[rstest]
+#[case(CollationPushResponse::Accepted)]
+#[case(CollationPushResponse::Rejected)]
+fn collations_are_pushed_to_validators(#[case] response: CollationPushResponse) {
+	let mut test_state = TestState::default();
+	let feature = node_features::FeatureIndex::CollationPush as usize;
+	test_state.node_features.resize(feature + 1, false);
+	test_state.node_features.set(feature, true);
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		|mut test_harness| async move {
+			let virtual_overseer = &mut test_harness.virtual_overseer;
+
+			let peer = test_state.current_group_validator_peer_ids()[0];
+			let validator_id = test_state.current_group_validator_authority_ids()[0].clone();
+
+			overseer_send(virtual_overseer, CollatorProtocolMessage::CollateOn(test_state.para_id))
+				.await;
+
+			update_view(&test_state, virtual_overseer, vec![(test_state.relay_parent, 10)], 1)
+				.await;
+
+			connect_peer(virtual_overseer, peer, CollationVersion::V2, Some(validator_id)).await;
+			expect_declare_msg(virtual_overseer, &test_state, &peer).await;
+			send_peer_view_change(virtual_overseer, &peer, vec![test_state.relay_parent]).await;
+
+			let DistributeCollation { candidate, pov_block } =
+				distribute_collation(virtual_overseer, &test_state, test_state.relay_parent, true)
+					.await;
+
+			// The collation is pushed to the connected validator instead of being advertised.
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendRequests(
+					mut reqs,
+					IfDisconnected::ImmediateError,
+				)) => {
+					assert_eq!(reqs.len(), 1);
+					assert_matches!(
+						reqs.pop(),
+						Some(Requests::CollationPushV1(req)) => {
+							assert_eq!(req.peer, Recipient::Peer(peer));
+							assert_eq!(req.payload.candidate_receipt, candidate);
+							assert_eq!(req.payload.pov, pov_block);
+							req.pending_response
+								.send(Ok((response.encode(), ProtocolName::from(""))))
+								.unwrap();
+						}
+					);
+				}
+			);
+
+			if response == CollationPushResponse::Rejected {
+				expect_advertise_collation_msg(
+					virtual_overseer,
+					&[peer],
+					test_state.relay_parent,
+					vec![candidate.hash()],
+				)
+				.await;
+			} else {
+				assert!(overseer_recv_with_timeout(virtual_overseer, TIMEOUT).await.is_none());
+			}
+
+			test_harness
+		},
+	)
+}
@@ -21,7 +21,10 @@
 #![deny(unused_crate_dependencies)]
 #![recursion_limit = "256"]
 
-use std::time::{Duration, Instant};
+use std::{
+	collections::{HashMap, HashSet},
+	time::{Duration, Instant},
+};
 
 use futures::{
 	stream::{FusedStream, StreamExt},
@@ -32,10 +35,10 @@ use polkadot_node_subsystem_util::reputation::ReputationAggregator;
 use sp_keystore::KeystorePtr;
 
 use polkadot_node_network_protocol::{
-	request_response::{v2 as protocol_v2, IncomingRequestReceiver},
+	request_response::{v1 as protocol_v1, v2 as protocol_v2, IncomingRequestReceiver},
 	PeerId, UnifiedReputationChange as Rep,
 };
-use polkadot_primitives::CollatorPair;
+use polkadot_primitives::{CollatorId, CollatorPair, Id as ParaId};
 
 use polkadot_node_subsystem::{errors::SubsystemError, overseer, DummySubsystem, SpawnedSubsystem};
 
@@ -64,12 +67,25 @@ impl Default for CollatorEvictionPolicy {
 	}
 }
 
+/// The collators trusted to push their collations to validators, per para.
+///
+/// Pushed collations skip the spam protection of advertisements, see
+/// [`polkadot_primitives::node_features::FeatureIndex::CollationPush`].
+pub type PushCollators = HashMap<ParaId, HashSet<CollatorId>>;
+
 /// What side of the collator protocol is being engaged
 pub enum ProtocolSide {
 	/// Validators operate on the relay chain.
 	Validator {
 		/// The keystore holding validator keys.
 		keystore: KeystorePtr,
+		/// Receiver for collation precheck requests.
+		precheck_receiver: IncomingRequestReceiver<protocol_v1::CollationPrecheckRequest>,
+		/// Receiver for collations pushed by collators.
+		push_receiver: IncomingRequestReceiver<protocol_v1::CollationPushRequest>,
+		/// The collators whose pushed collations are accepted. Pushes of any other collator are
+		/// rejected.
+		push_collators: PushCollators,
 		/// An eviction policy for inactive peers or validators.
 		eviction_policy: CollatorEvictionPolicy,
 		/// Prometheus metrics for validators.
@@ -91,6 +107,9 @@ pub enum ProtocolSide {
 		collator_pair: CollatorPair,
 		/// Receiver for v2 collation fetching requests.
 		request_receiver_v2: IncomingRequestReceiver<protocol_v2::CollationFetchingRequest>,
+		/// Have collations prechecked by a validator of the backing group before advertising
+		/// them.
+		precheck_collations: bool,
 		/// Metrics.
 		metrics: collator_side::Metrics,
 	},
@@ -118,19 +137,45 @@ impl CollatorProtocolSubsystem {
 impl<Context> CollatorProtocolSubsystem {
 	fn start(self, ctx: Context) -> SpawnedSubsystem {
 		let future = match self.protocol_side {
-			ProtocolSide::Validator { keystore, eviction_policy, metrics } =>
-				validator_side::run(ctx, keystore, eviction_policy, metrics)
-					.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
-					.boxed(),
+			ProtocolSide::Validator {
+				keystore,
+				precheck_receiver,
+				push_receiver,
+				push_collators,
+				eviction_policy,
+				metrics,
+			} => validator_side::run(
+				ctx,
+				keystore,
+				precheck_receiver,
+				push_receiver,
+				push_collators,
+				eviction_policy,
+				metrics,
+			)
+			.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
+			.boxed(),
 			#[cfg(feature = "experimental-collator-protocol")]
 			ProtocolSide::ValidatorExperimental { keystore, metrics } =>
 				validator_side_experimental::run(ctx, keystore, metrics)
 					.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
 					.boxed(),
-			ProtocolSide::Collator { peer_id, collator_pair, request_receiver_v2, metrics } =>
-				collator_side::run(ctx, peer_id, collator_pair, request_receiver_v2, metrics)
-					.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
-					.boxed(),
+			ProtocolSide::Collator {
+				peer_id,
+				collator_pair,
+				request_receiver_v2,
+				precheck_collations,
+				metrics,
+			} => collator_side::run(
+				ctx,
+				peer_id,
+				collator_pair,
+				request_receiver_v2,
+				precheck_collations,
+				metrics,
+			)
+			.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
+			.boxed(),
 			ProtocolSide::None => return DummySubsystem.start(ctx),
 		};
 
@@ -0,0 +1,157 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `AdvertisementBudget` limits the advertisements accepted per para and relay parent and scores
+//! the collators exceeding their budgets. Refer to [`AdvertisementBudget`] for more details.
+
+use std::collections::HashMap;
+
+use polkadot_primitives::{CollatorId, Hash, Id as ParaId, SessionIndex};
+
+/// Advertisements accepted per claim of a para at a relay parent, from all of its collators.
+///
+/// Several collators of the same para may compete for a claim, so the budget is shared by all of
+/// them and has to leave room for some honest competition.
+pub const PARA_ADVERTISEMENTS_PER_CLAIM: usize = 4;
+
+/// Score from which a collator is considered to be systematically over-advertising.
+///
+/// Every rejected advertisement adds one to the score of its collator.
+pub const SPAM_SCORE_THRESHOLD: u32 = 8;
+
+/// Tracks the advertisement budgets of the paras and the spam scores of the collators.
+///
+/// The budget of a para at a relay parent is [`PARA_ADVERTISEMENTS_PER_CLAIM`] advertisements for
+/// every claim of the para in the claim queue, plus one claim for the collators building ahead.
+///
+/// Collators sending advertisements that are rejected for exceeding a budget or being duplicates
+/// get their score increased. Scores are halved on every new leaf, so a collator only crosses the
+/// [`SPAM_SCORE_THRESHOLD`] by over-advertising on several relay parents in a row. Scores are kept
+/// across relay parents and reconnections, and reset on a new session.
+#[derive(Debug, Default)]
+pub struct AdvertisementBudget {
+	/// The session of the last leaf.
+	session_index: Option<SessionIndex>,
+	/// Advertisements accepted per relay parent and para.
+	accepted: HashMap<(Hash, ParaId), usize>,
+	/// Spam scores of the collators.
+	scores: HashMap<CollatorId, u32>,
+}
+
+impl AdvertisementBudget {
+	/// Whether the para can still have an advertisement accepted at the relay parent, given the
+	/// number of its claims.
+	pub fn has_budget(&self, relay_parent: Hash, para_id: ParaId, claims: usize) -> bool {
+		let accepted = self.accepted.get(&(relay_parent, para_id)).copied().unwrap_or_default();
+		accepted < (claims + 1) * PARA_ADVERTISEMENTS_PER_CLAIM
+	}
+
+	/// Note an advertisement accepted for the para at the relay parent.
+	pub fn note_accepted(&mut self, relay_parent: Hash, para_id: ParaId) {
+		*self.accepted.entry((relay_parent, para_id)).or_default() += 1;
+	}
+
+	/// Note an advertisement of the collator that was rejected for over-advertising.
+	///
+	/// Returns whether the collator is now considered to be systematically over-advertising.
+	pub fn note_over_advertisement(&mut self, collator_id: &CollatorId) -> bool {
+		let score = self.scores.entry(collator_id.clone()).or_default();
+		*score = score.saturating_add(1);
+		*score >= SPAM_SCORE_THRESHOLD
+	}
+
+	/// The current spam score of the collator.
+	pub fn score(&self, collator_id: &CollatorId) -> u32 {
+		self.scores.get(collator_id).copied().unwrap_or_default()
+	}
+
+	/// Decay the scores on a new leaf, or reset them if the leaf starts a new session.
+	pub fn on_new_leaf(&mut self, session_index: SessionIndex) {
+		if self.session_index.replace(session_index) != Some(session_index) {
+			self.scores.clear();
+			return
+		}
+
+		self.scores.retain(|_, score| {
+			*score /= 2;
+			*score > 0
+		});
+	}
+
+	/// Remove the budgets of the relay parents not matching the predicate.
+	pub fn retain_relay_parents(&mut self, mut keep: impl FnMut(&Hash) -> bool) {
+		self.accepted.retain(|(relay_parent, _), _| keep(relay_parent));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::crypto::UncheckedFrom;
+
+	fn collator(n: u8) -> CollatorId {
+		CollatorId::unchecked_from([n; 32])
+	}
+
+	#[test]
+	fn para_budget_scales_with_claims() {
+		let mut budget = AdvertisementBudget::default();
+		let relay_parent = Hash::from_low_u64_be(1);
+		let para_id = ParaId::new(1);
+
+		for _ in 0..2 * PARA_ADVERTISEMENTS_PER_CLAIM {
+			assert!(budget.has_budget(relay_parent, para_id, 1));
+			budget.note_accepted(relay_parent, para_id);
+		}
+		assert!(!budget.has_budget(relay_parent, para_id, 1));
+		assert!(budget.has_budget(relay_parent, para_id, 2));
+
+		// Budgets are per relay parent and para.
+		assert!(budget.has_budget(Hash::from_low_u64_be(2), para_id, 1));
+		assert!(budget.has_budget(relay_parent, ParaId::new(2), 1));
+
+		budget.retain_relay_parents(|hash| *hash != relay_parent);
+		assert!(budget.has_budget(relay_parent, para_id, 1));
+	}
+
+	#[test]
+	fn systematic_over_advertisement_crosses_threshold() {
+		let mut budget = AdvertisementBudget::default();
+		budget.on_new_leaf(1);
+
+		// A burst on one relay parent decays before reaching the threshold.
+		for _ in 0..SPAM_SCORE_THRESHOLD - 1 {
+			assert!(!budget.note_over_advertisement(&collator(1)));
+		}
+		budget.on_new_leaf(1);
+		assert_eq!(budget.score(&collator(1)), (SPAM_SCORE_THRESHOLD - 1) / 2);
+
+		// Over-advertising on every relay parent is caught.
+		let mut spamming = false;
+		for _ in 0..3 {
+			for _ in 0..=SPAM_SCORE_THRESHOLD / 2 {
+				spamming = budget.note_over_advertisement(&collator(1));
+			}
+			budget.on_new_leaf(1);
+		}
+		assert!(spamming);
+		assert_eq!(budget.score(&collator(2)), 0);
+
+		// A new session resets the scores.
+		budget.on_new_leaf(2);
+		assert_eq!(budget.score(&collator(1)), 0);
+	}
+}
@@ -18,7 +18,7 @@ use fatality::thiserror::Error;
 use futures::channel::oneshot;
 
 use polkadot_node_subsystem::RuntimeApiError;
-use polkadot_node_subsystem_util::backing_implicit_view;
+use polkadot_node_subsystem_util::{backing_implicit_view, runtime};
 use polkadot_primitives::vstaging::CandidateDescriptorVersion;
 
 /// General result.
@@ -42,8 +42,8 @@ pub enum Error {
 	#[error("Response receiver for claim queue request cancelled")]
 	CancelledClaimQueue(oneshot::Canceled),
 
-	#[error("Response receiver for node features request cancelled")]
-	CancelledNodeFeatures(oneshot::Canceled),
+	#[error("Error while fetching node features")]
+	FetchNodeFeatures(runtime::Error),
 
 	#[error("No state for the relay parent")]
 	RelayParentStateNotFound,
@@ -15,7 +15,8 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use futures::{
-	channel::oneshot, future::BoxFuture, select, stream::FuturesUnordered, FutureExt, StreamExt,
+	channel::oneshot, future::BoxFuture, pin_mut, select, stream::FuturesUnordered, FutureExt,
+	StreamExt,
 };
 use futures_timer::Delay;
 use std::{
@@ -31,8 +32,10 @@ use polkadot_node_network_protocol::{
 	self as net_protocol,
 	peer_set::{CollationVersion, PeerSet},
 	request_response::{
+		incoming,
 		outgoing::{Recipient, RequestError},
-		v1 as request_v1, v2 as request_v2, OutgoingRequest, Requests,
+		v1 as request_v1, v2 as request_v2, IncomingRequest, IncomingRequestReceiver,
+		OutgoingRequest, Requests,
 	},
 	v1 as protocol_v1, v2 as protocol_v2, CollationProtocols, OurView, PeerId,
 	UnifiedReputationChange as Rep, View,
@@ -49,7 +52,8 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_util::{
 	backing_implicit_view::View as ImplicitView,
 	reputation::{ReputationAggregator, REPUTATION_CHANGE_INTERVAL},
-	request_claim_queue, request_node_features, request_session_index_for_child,
+	request_claim_queue, request_session_index_for_child,
+	runtime::RuntimeInfo,
 };
 use polkadot_primitives::{
 	node_features,
@@ -60,11 +64,13 @@ use polkadot_primitives::{
 
 use super::{modify_reputation, tick_stream, LOG_TARGET};
 
+mod advertisement_budget;
 mod claim_queue_state;
 mod collation;
 mod error;
 mod metrics;
 
+use advertisement_budget::AdvertisementBudget;
 use claim_queue_state::ClaimQueueState;
 use collation::{
 	fetched_collation_sanity_check, BlockedCollationId, CollationEvent, CollationFetchError,
@@ -89,6 +95,8 @@ const COST_WRONG_PARA: Rep = Rep::Malicious("A collator provided a collation for
 const COST_PROTOCOL_MISUSE: Rep =
 	Rep::Malicious("A collator advertising a collation for an async backing relay parent using V1");
 const COST_UNNEEDED_COLLATOR: Rep = Rep::CostMinor("An unneeded collator connected");
+const COST_ADVERTISEMENT_SPAM: Rep =
+	Rep::CostMajorRepeated("A collator is systematically sending too many advertisements");
 const BENEFIT_NOTIFY_GOOD: Rep =
 	Rep::BenefitMinor("A collator was noted good by another subsystem");
 
@@ -149,6 +157,17 @@ enum InsertAdvertisementError {
 	PeerLimitReached,
 }
 
+impl InsertAdvertisementError {
+	/// Whether the collator violated the protocol by over-advertising, which counts towards its
+	/// spam score.
+	fn is_over_advertisement(&self) -> bool {
+		match self {
+			Self::Duplicate | Self::PeerLimitReached => true,
+			Self::OutOfOurView | Self::UndeclaredCollator => false,
+		}
+	}
+}
+
 #[derive(Debug)]
 struct PeerData {
 	view: View,
@@ -344,6 +363,9 @@ struct PerRelayParent {
 	assignment: GroupAssignments,
 	collations: Collations,
 	v2_receipts: bool,
+	/// Whether collators may push collations to us, see
+	/// [`node_features::FeatureIndex::CollationPush`].
+	collation_push: bool,
 	current_core: CoreIndex,
 	session_index: SessionIndex,
 }
@@ -385,6 +407,9 @@ struct State {
 	/// Metrics.
 	metrics: Metrics,
 
+	/// The collators whose pushed collations are accepted.
+	push_collators: crate::PushCollators,
+
 	/// When a timer in this `FuturesUnordered` triggers, we should dequeue the next request
 	/// attempt in the corresponding `collations_per_relay_parent`.
 	///
@@ -405,6 +430,12 @@ struct State {
 
 	/// Aggregated reputation change
 	reputation: ReputationAggregator,
+
+	/// Advertisement budgets of the paras and spam scores of the collators.
+	advertisement_budget: AdvertisementBudget,
+
+	/// Caches the node features of the sessions.
+	runtime_info: RuntimeInfo,
 }
 
 impl State {
@@ -478,6 +509,7 @@ async fn construct_per_relay_parent<Sender>(
 	keystore: &KeystorePtr,
 	relay_parent: Hash,
 	v2_receipts: bool,
+	collation_push: bool,
 	session_index: SessionIndex,
 ) -> Result<Option<PerRelayParent>>
 where
@@ -531,6 +563,7 @@ where
 		assignment,
 		collations,
 		v2_receipts,
+		collation_push,
 		session_index,
 		current_core: core_now,
 	}))
@@ -936,12 +969,17 @@ enum AdvertisementError {
 	InvalidAssignment,
 	/// Para reached a limit of seconded candidates for this relay parent.
 	SecondedLimitReached,
+	/// Para exhausted its advertisement budget for this relay parent.
+	ParaLimitReached,
 	/// Collator trying to advertise a collation using V1 protocol for an async backing relay
 	/// parent.
 	ProtocolMisuse,
 	/// Advertisement is invalid.
 	#[allow(dead_code)]
 	Invalid(InsertAdvertisementError),
+	/// Advertisement exceeds a budget and the collator is systematically over-advertising.
+	#[allow(dead_code)]
+	Spam(InsertAdvertisementError),
 	/// Seconding not allowed by backing subsystem
 	BlockedByBacking,
 }
@@ -952,8 +990,9 @@ impl AdvertisementError {
 		match self {
 			InvalidAssignment => Some(COST_WRONG_PARA),
 			ProtocolMisuse => Some(COST_PROTOCOL_MISUSE),
+			Spam(_) => Some(COST_ADVERTISEMENT_SPAM),
 			RelayParentUnknown | UndeclaredCollator | Invalid(_) => Some(COST_UNEXPECTED_MESSAGE),
-			UnknownPeer | SecondedLimitReached | BlockedByBacking => None,
+			UnknownPeer | SecondedLimitReached | ParaLimitReached | BlockedByBacking => None,
 		}
 	}
 }
@@ -1133,15 +1172,41 @@ where
 
 	// Always insert advertisements that pass all the checks for spam protection.
 	let candidate_hash = prospective_candidate.map(|(hash, ..)| hash);
-	let (collator_id, para_id) = peer_data
-		.insert_advertisement(
-			relay_parent,
-			candidate_hash,
-			&state.implicit_view,
-			&state.active_leaves,
-			&per_relay_parent,
-		)
-		.map_err(AdvertisementError::Invalid)?;
+	let claims = assignment.current.iter().filter(|para| **para == collator_para_id).count();
+	if !state.advertisement_budget.has_budget(relay_parent, collator_para_id, claims) {
+		return Err(AdvertisementError::ParaLimitReached)
+	}
+	let inserted = peer_data.insert_advertisement(
+		relay_parent,
+		candidate_hash,
+		&state.implicit_view,
+		&state.active_leaves,
+		&per_relay_parent,
+	);
+	let (collator_id, para_id) = match inserted {
+		Ok(inserted) => inserted,
+		Err(err) if err.is_over_advertisement() => {
+			let collator_id =
+				peer_data.collator_id().ok_or(AdvertisementError::UndeclaredCollator)?;
+			let spamming = state.advertisement_budget.note_over_advertisement(collator_id);
+			gum::debug!(
+				target: LOG_TARGET,
+				?peer_id,
+				?relay_parent,
+				para_id = ?collator_para_id,
+				score = state.advertisement_budget.score(collator_id),
+				error = ?err,
+				"Advertisement exceeds budget",
+			);
+			return Err(if spamming {
+				AdvertisementError::Spam(err)
+			} else {
+				AdvertisementError::Invalid(err)
+			})
+		},
+		Err(err) => return Err(AdvertisementError::Invalid(err)),
+	};
+	state.advertisement_budget.note_accepted(relay_parent, para_id);
 
 	ensure_seconding_limit_is_respected(&relay_parent, para_id, state)?;
 
@@ -1276,13 +1341,16 @@ where
 			.await
 			.map_err(Error::CancelledSessionIndex)??;
 
-		let v2_receipts = request_node_features(*leaf, session_index, sender)
-			.await
+		let node_features = state
+			.runtime_info
+			.get_node_features(sender, *leaf, session_index)
 			.await
-			.map_err(Error::CancelledNodeFeatures)??
-			.get(node_features::FeatureIndex::CandidateReceiptV2 as usize)
-			.map(|b| *b)
-			.unwrap_or(false);
+			.map_err(Error::FetchNodeFeatures)?;
+		let has_feature = |feature: node_features::FeatureIndex| {
+			node_features.get(feature as usize).map(|b| *b).unwrap_or(false)
+		};
+		let v2_receipts = has_feature(node_features::FeatureIndex::CandidateReceiptV2);
+		let collation_push = has_feature(node_features::FeatureIndex::CollationPush);
 
 		let Some(per_relay_parent) = construct_per_relay_parent(
 			sender,
@@ -1290,6 +1358,7 @@ where
 			keystore,
 			*leaf,
 			v2_receipts,
+			collation_push,
 			session_index,
 		)
 		.await?
@@ -1299,6 +1368,7 @@ where
 
 		state.active_leaves.insert(*leaf);
 		state.per_relay_parent.insert(*leaf, per_relay_parent);
+		state.advertisement_budget.on_new_leaf(session_index);
 
 		state
 			.implicit_view
@@ -1313,14 +1383,15 @@ where
 			.unwrap_or_default();
 		for block_hash in allowed_ancestry {
 			if let Entry::Vacant(entry) = state.per_relay_parent.entry(*block_hash) {
-				// Safe to use the same v2 receipts config for the allowed relay parents as well
-				// as the same session index since they must be in the same session.
+				// Safe to use the same node features for the allowed relay parents as well as the
+				// same session index since they must be in the same session.
 				if let Some(per_relay_parent) = construct_per_relay_parent(
 					sender,
 					&mut state.current_assignments,
 					keystore,
 					*block_hash,
 					v2_receipts,
+					collation_push,
 					session_index,
 				)
 				.await?
@@ -1361,6 +1432,10 @@ where
 		}
 	}
 
+	state
+		.advertisement_budget
+		.retain_relay_parents(|relay_parent| state.per_relay_parent.contains_key(relay_parent));
+
 	// Remove blocked seconding requests that left the view.
 	state.blocked_from_seconding.retain(|_, collations| {
 		collations.retain(|collation| {
@@ -1598,12 +1673,18 @@ async fn process_msg<Context>(
 pub(crate) async fn run<Context>(
 	ctx: Context,
 	keystore: KeystorePtr,
+	precheck_receiver: IncomingRequestReceiver<request_v1::CollationPrecheckRequest>,
+	push_receiver: IncomingRequestReceiver<request_v1::CollationPushRequest>,
+	push_collators: crate::PushCollators,
 	eviction_policy: crate::CollatorEvictionPolicy,
 	metrics: Metrics,
 ) -> std::result::Result<(), std::convert::Infallible> {
 	run_inner(
 		ctx,
 		keystore,
+		precheck_receiver,
+		push_receiver,
+		push_collators,
 		eviction_policy,
 		metrics,
 		ReputationAggregator::default(),
@@ -1616,6 +1697,9 @@ pub(crate) async fn run<Context>(
 async fn run_inner<Context>(
 	mut ctx: Context,
 	keystore: KeystorePtr,
+	mut precheck_receiver: IncomingRequestReceiver<request_v1::CollationPrecheckRequest>,
+	mut push_receiver: IncomingRequestReceiver<request_v1::CollationPushRequest>,
+	push_collators: crate::PushCollators,
 	eviction_policy: crate::CollatorEvictionPolicy,
 	metrics: Metrics,
 	reputation: ReputationAggregator,
@@ -1624,7 +1708,7 @@ async fn run_inner<Context>(
 	let new_reputation_delay = || futures_timer::Delay::new(reputation_interval).fuse();
 	let mut reputation_delay = new_reputation_delay();
 
-	let mut state = State { metrics, reputation, ..Default::default() };
+	let mut state = State { metrics, reputation, push_collators, ..Default::default() };
 
 	let next_inactivity_stream = tick_stream(ACTIVITY_POLL);
 	futures::pin_mut!(next_inactivity_stream);
@@ -1633,6 +1717,11 @@ async fn run_inner<Context>(
 	let mut canceled_freq = gum::Freq::new();
 
 	loop {
+		let recv_precheck = precheck_receiver.recv(|| vec![COST_CORRUPTED_MESSAGE]).fuse();
+		pin_mut!(recv_precheck);
+		let recv_push = push_receiver.recv(|| vec![COST_CORRUPTED_MESSAGE]).fuse();
+		pin_mut!(recv_push);
+
 		select! {
 			_ = reputation_delay => {
 				state.reputation.send(ctx.sender()).await;
@@ -1656,6 +1745,30 @@ async fn run_inner<Context>(
 			_ = next_inactivity_stream.next() => {
 				disconnect_inactive_peers(ctx.sender(), &eviction_policy, &state.peer_data).await;
 			},
+			req = recv_precheck => match req {
+				Ok(req) => handle_precheck_request(&mut ctx, &mut state, req).await,
+				// Only happens on shutdown.
+				Err(incoming::Error::RequestChannelExhausted) => break,
+				Err(err) => {
+					gum::debug!(
+						target: LOG_TARGET,
+						?err,
+						"Failed to decode collation precheck request",
+					);
+				},
+			},
+			req = recv_push => match req {
+				Ok(req) => handle_push_request(&mut ctx, &mut state, req).await,
+				// Only happens on shutdown.
+				Err(incoming::Error::RequestChannelExhausted) => break,
+				Err(err) => {
+					gum::debug!(
+						target: LOG_TARGET,
+						?err,
+						"Failed to decode collation push request",
+					);
+				},
+			},
 			resp = state.collation_requests.select_next_some() => {
 				let relay_parent = resp.0.pending_collation.relay_parent;
 				let res = match handle_collation_fetch_response(
@@ -1826,6 +1939,220 @@ where
 	rx.await.map_err(SecondingError::CancelledProspectiveValidationData)
 }
 
+/// Respond to a collator asking to check a collation before advertising it.
+///
+/// Requests of peers that didn't declare themselves as collators of the para are dropped.
+#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
+async fn handle_precheck_request<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	req: IncomingRequest<request_v1::CollationPrecheckRequest>,
+) {
+	let descriptor = req.payload.candidate_receipt.descriptor();
+	let para_id = descriptor.para_id();
+	let relay_parent = descriptor.relay_parent();
+	let candidate_hash = req.payload.candidate_receipt.hash();
+
+	if state.peer_data.get(&req.peer).and_then(PeerData::collating_para) != Some(para_id) {
+		gum::debug!(
+			target: LOG_TARGET,
+			peer_id = ?req.peer,
+			?para_id,
+			?relay_parent,
+			"Collation precheck requested by a peer not collating on the para",
+		);
+		modify_reputation(&mut state.reputation, ctx.sender(), req.peer, COST_UNEXPECTED_MESSAGE)
+			.await;
+		return
+	}
+
+	let response = match precheck_collation(ctx.sender(), state, &req.payload).await {
+		Ok(response) => response,
+		Err(err) => {
+			// Not the fault of the collator, it will carry on without a verdict.
+			gum::debug!(
+				target: LOG_TARGET,
+				?relay_parent,
+				?candidate_hash,
+				err = ?err,
+				"Failed to precheck collation",
+			);
+			return
+		},
+	};
+
+	gum::debug!(
+		target: LOG_TARGET,
+		peer_id = ?req.peer,
+		?relay_parent,
+		?candidate_hash,
+		?response,
+		"Prechecked collation",
+	);
+	let _ = req.send_response(response);
+}
+
+/// Check what can be checked about a collation without its PoV.
+async fn precheck_collation<Sender>(
+	sender: &mut Sender,
+	state: &State,
+	request: &request_v1::CollationPrecheckRequest,
+) -> std::result::Result<request_v1::CollationPrecheckResponse, SecondingError>
+where
+	Sender: CollatorProtocolSenderTrait,
+{
+	use request_v1::{CollationPrecheckRejection as Rejection, CollationPrecheckResponse};
+
+	let descriptor = request.candidate_receipt.descriptor();
+	let para_id = descriptor.para_id();
+	let relay_parent = descriptor.relay_parent();
+
+	let Some(per_relay_parent) = state.per_relay_parent.get(&relay_parent) else {
+		return Ok(CollationPrecheckResponse::Rejected(Rejection::UnknownRelayParent))
+	};
+	if !per_relay_parent.assignment.current.contains(&para_id) {
+		return Ok(CollationPrecheckResponse::Rejected(Rejection::ParaNotAssigned))
+	}
+	if descriptor_version_sanity_check(descriptor, per_relay_parent).is_err() {
+		return Ok(CollationPrecheckResponse::Rejected(Rejection::InvalidDescriptor))
+	}
+
+	let parent_head_data = request.parent_head_data.clone();
+	let Some(pvd) = request_prospective_validation_data(
+		sender,
+		relay_parent,
+		parent_head_data.hash(),
+		para_id,
+		Some(parent_head_data),
+	)
+	.await?
+	else {
+		return Ok(CollationPrecheckResponse::Rejected(Rejection::UnknownParent))
+	};
+	if pvd.hash() != descriptor.persisted_validation_data_hash() {
+		return Ok(CollationPrecheckResponse::Rejected(Rejection::InvalidPersistedValidationData))
+	}
+
+	Ok(CollationPrecheckResponse::Accepted)
+}
+
+/// Second a collation pushed by a collator, without it being advertised and fetched first.
+///
+/// Requests of peers that didn't declare themselves as collators of the para are dropped, pushes of
+/// collators that are not trusted to push for the para are rejected. Pushed collations are also
+/// rejected if they can't be seconded right away, the collator then advertises them instead.
+#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
+async fn handle_push_request<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	req: IncomingRequest<request_v1::CollationPushRequest>,
+) {
+	let IncomingRequest { peer, payload, pending_response } = req;
+	let request_v1::CollationPushRequest { candidate_receipt, pov, parent_head_data } = payload;
+	let para_id = candidate_receipt.descriptor.para_id();
+	let relay_parent = candidate_receipt.descriptor.relay_parent();
+	let candidate_hash = candidate_receipt.hash();
+
+	let Some(collator_id) = state
+		.peer_data
+		.get(&peer)
+		.filter(|peer_data| peer_data.collating_para() == Some(para_id))
+		.and_then(PeerData::collator_id)
+		.cloned()
+	else {
+		gum::debug!(
+			target: LOG_TARGET,
+			peer_id = ?peer,
+			?para_id,
+			?relay_parent,
+			"Collation pushed by a peer not collating on the para",
+		);
+		modify_reputation(&mut state.reputation, ctx.sender(), peer, COST_UNEXPECTED_MESSAGE).await;
+		return
+	};
+
+	let trusted = state
+		.push_collators
+		.get(&para_id)
+		.map_or(false, |collators| collators.contains(&collator_id));
+	if !trusted {
+		gum::debug!(
+			target: LOG_TARGET,
+			peer_id = ?peer,
+			?collator_id,
+			?para_id,
+			?relay_parent,
+			"Collation pushed by a collator not trusted to push",
+		);
+		modify_reputation(&mut state.reputation, ctx.sender(), peer, COST_UNEXPECTED_MESSAGE).await;
+		let _ = pending_response.send_response(request_v1::CollationPushResponse::Rejected);
+		return
+	}
+
+	let push_allowed =
+		state.per_relay_parent.get(&relay_parent).map_or(false, |per_relay_parent| {
+			per_relay_parent.collation_push &&
+			per_relay_parent.assignment.current.contains(&para_id) &&
+			// A pushed collation is seconded in place of a fetched one.
+			matches!(per_relay_parent.collations.status, CollationStatus::Waiting)
+		}) && ensure_seconding_limit_is_respected(&relay_parent, para_id, state).is_ok();
+
+	let parent_head_data_hash = parent_head_data.hash();
+	let seconding = push_allowed &&
+		can_second(ctx.sender(), para_id, relay_parent, candidate_hash, parent_head_data_hash)
+			.await;
+
+	let response = if seconding {
+		let collation_event = CollationEvent {
+			collator_id,
+			collator_protocol_version: CollationVersion::V2,
+			pending_collation: PendingCollation::new(
+				relay_parent,
+				para_id,
+				&peer,
+				Some(ProspectiveCandidate { candidate_hash, parent_head_data_hash }),
+			),
+		};
+		let fetch = PendingCollationFetch {
+			collation_event,
+			candidate_receipt,
+			pov,
+			maybe_parent_head_data: Some(parent_head_data),
+		};
+
+		match kick_off_seconding(ctx, state, fetch).await {
+			Ok(true) => request_v1::CollationPushResponse::Accepted,
+			Ok(false) => request_v1::CollationPushResponse::Rejected,
+			Err(err) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?relay_parent,
+					?candidate_hash,
+					error = %err,
+					"Seconding a pushed collation failed",
+				);
+				if err.is_malicious() {
+					modify_reputation(&mut state.reputation, ctx.sender(), peer, COST_REPORT_BAD)
+						.await;
+				}
+				request_v1::CollationPushResponse::Rejected
+			},
+		}
+	} else {
+		request_v1::CollationPushResponse::Rejected
+	};
+
+	gum::debug!(
+		target: LOG_TARGET,
+		peer_id = ?peer,
+		?relay_parent,
+		?candidate_hash,
+		?response,
+		"Handled pushed collation",
+	);
+	let _ = pending_response.send_response(response);
+}
+
 /// Handle a fetched collation result.
 /// Returns whether or not seconding has begun.
 #[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
@@ -22,7 +22,7 @@ use sp_core::{crypto::Pair, Encode};
 use sp_keyring::Sr25519Keyring;
 use sp_keystore::Keystore;
 use std::{
-	collections::{BTreeMap, VecDeque},
+	collections::{BTreeMap, HashSet, VecDeque},
 	iter,
 	sync::Arc,
 	time::Duration,
@@ -31,7 +31,7 @@ use std::{
 use self::prospective_parachains::update_view;
 use polkadot_node_network_protocol::{
 	peer_set::CollationVersion,
-	request_response::{Requests, ResponseSender},
+	request_response::{IncomingRequest, ReqProtocolNames, Requests, ResponseSender},
 	ObservedRole,
 };
 use polkadot_node_primitives::{BlockData, PoV};
@@ -41,8 +41,8 @@ use polkadot_node_subsystem::messages::{
 use polkadot_node_subsystem_test_helpers as test_helpers;
 use polkadot_node_subsystem_util::{reputation::add_reputation, TimeoutExt};
 use polkadot_primitives::{
-	node_features, vstaging::CandidateReceiptV2 as CandidateReceipt, CollatorPair, CoreIndex,
-	GroupRotationInfo, HeadData, NodeFeatures, PersistedValidationData, ValidatorId,
+	node_features, vstaging::CandidateReceiptV2 as CandidateReceipt, Block, CollatorPair,
+	CoreIndex, GroupRotationInfo, HeadData, NodeFeatures, PersistedValidationData, ValidatorId,
 	ValidatorIndex,
 };
 use polkadot_primitives_test_helpers::{dummy_candidate_receipt_bad_sig, dummy_hash};
@@ -76,6 +76,8 @@ struct TestState {
 	session_index: SessionIndex,
 	// Used by `update_view` to keep track of latest requested ancestor
 	last_known_block: Option<u32>,
+	// Used by `update_view` to keep track of the sessions with cached node features
+	known_sessions: HashSet<SessionIndex>,
 }
 
 impl Default for TestState {
@@ -133,6 +135,7 @@ impl Default for TestState {
 			node_features,
 			session_index: 1,
 			last_known_block: None,
+			known_sessions: HashSet::new(),
 		}
 	}
 }
@@ -201,11 +204,21 @@ type VirtualOverseer =
 struct TestHarness {
 	virtual_overseer: VirtualOverseer,
 	keystore: KeystorePtr,
+	precheck_queue: async_channel::Sender<sc_network::config::IncomingRequest>,
+	push_queue: async_channel::Sender<sc_network::config::IncomingRequest>,
 }
 
 fn test_harness<T: Future<Output = VirtualOverseer>>(
 	reputation: ReputationAggregator,
 	test: impl FnOnce(TestHarness) -> T,
+) {
+	test_harness_with_push_collators(reputation, Default::default(), test)
+}
+
+fn test_harness_with_push_collators<T: Future<Output = VirtualOverseer>>(
+	reputation: ReputationAggregator,
+	push_collators: crate::PushCollators,
+	test: impl FnOnce(TestHarness) -> T,
 ) {
 	sp_tracing::init_for_tests();
 
@@ -222,9 +235,25 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 	)
 	.expect("Insert key into keystore");
 
+	let req_protocol_names = ReqProtocolNames::new(&Hash::repeat_byte(0xff), None);
+	let (precheck_receiver, precheck_cfg) = IncomingRequest::get_config_receiver::<
+		Block,
+		sc_network::NetworkWorker<Block, Hash>,
+	>(&req_protocol_names);
+	// The config outlives the test, so that the subsystem keeps receiving requests.
+	let precheck_queue = precheck_cfg.inbound_queue.clone().expect("Config of an inbound protocol");
+	let (push_receiver, push_cfg) = IncomingRequest::get_config_receiver::<
+		Block,
+		sc_network::NetworkWorker<Block, Hash>,
+	>(&req_protocol_names);
+	let push_queue = push_cfg.inbound_queue.clone().expect("Config of an inbound protocol");
+
 	let subsystem = run_inner(
 		context,
 		keystore.clone(),
+		precheck_receiver,
+		push_receiver,
+		push_collators,
 		crate::CollatorEvictionPolicy {
 			inactive_collator: ACTIVITY_TIMEOUT,
 			undeclared: DECLARE_TIMEOUT,
@@ -234,7 +263,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 		REPUTATION_CHANGE_TEST_INTERVAL,
 	);
 
-	let test_fut = test(TestHarness { virtual_overseer, keystore });
+	let test_fut = test(TestHarness { virtual_overseer, keystore, precheck_queue, push_queue });
 
 	futures::pin_mut!(test_fut);
 	futures::pin_mut!(subsystem);
@@ -108,15 +108,18 @@ pub(super) async fn update_view(
 			}
 		);
 
-		assert_matches!(
-			overseer_recv(virtual_overseer).await,
-			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
-				_,
-				RuntimeApiRequest::NodeFeatures(_, tx)
-			)) => {
-				tx.send(Ok(test_state.node_features.clone())).unwrap();
-			}
-		);
+		// Node features are only requested for the first leaf of a session.
+		if test_state.known_sessions.insert(test_state.session_index) {
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_,
+					RuntimeApiRequest::NodeFeatures(_, tx)
+				)) => {
+					tx.send(Ok(test_state.node_features.clone())).unwrap();
+				}
+			);
+		}
 
 		assert_construct_per_relay_parent(
 			virtual_overseer,
@@ -466,7 +469,7 @@ fn v1_advertisement_accepted_and_seconded() {
 	let mut test_state = TestState::default();
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let pair_a = CollatorPair::generate().0;
 
@@ -690,7 +693,7 @@ fn second_multiple_candidates_per_relay_parent() {
 	let mut test_state = TestState::with_one_scheduled_para();
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let pair = CollatorPair::generate().0;
 
@@ -1084,7 +1087,7 @@ fn child_blocked_from_seconding_by_parent(#[case] valid_parent: bool) {
 	let mut test_state = TestState::with_one_scheduled_para();
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let pair = CollatorPair::generate().0;
 
@@ -1392,7 +1395,7 @@ fn v2_descriptor(#[case] v2_feature_enabled: bool) {
 	}
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let pair_a = CollatorPair::generate().0;
 
@@ -1606,7 +1609,7 @@ fn fair_collation_fetches() {
 	let mut test_state = TestState::with_shared_core();
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let head_b = Hash::from_low_u64_be(128);
 		let head_b_num: u32 = 2;
@@ -1708,7 +1711,7 @@ fn collation_fetching_prefer_entries_earlier_in_claim_queue() {
 	let mut test_state = TestState::with_shared_core();
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let pair_a = CollatorPair::generate().0;
 		let collator_a = PeerId::random();
@@ -1883,7 +1886,7 @@ fn collation_fetching_considers_advertisements_from_the_whole_view() {
 	let mut test_state = TestState::with_shared_core();
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let pair_a = CollatorPair::generate().0;
 		let collator_a = PeerId::random();
@@ -2022,7 +2025,7 @@ fn collation_fetching_fairness_handles_old_claims() {
 	let mut test_state = TestState::with_shared_core();
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let pair_a = CollatorPair::generate().0;
 		let collator_a = PeerId::random();
@@ -2180,7 +2183,7 @@ fn claims_below_are_counted_correctly() {
 	test_state.scheduling_lookahead = 2;
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let hash_a = Hash::from_low_u64_be(test_state.relay_parent.to_low_u64_be() - 1);
 		let hash_b = Hash::from_low_u64_be(hash_a.to_low_u64_be() - 1);
@@ -2270,7 +2273,7 @@ fn claims_above_are_counted_correctly() {
 	test_state.scheduling_lookahead = 2;
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let hash_a = Hash::from_low_u64_be(test_state.relay_parent.to_low_u64_be() - 1); // block 0
 		let hash_b = Hash::from_low_u64_be(hash_a.to_low_u64_be() - 1); // block 1
@@ -2375,7 +2378,7 @@ fn claim_fills_last_free_slot() {
 	test_state.scheduling_lookahead = 2;
 
 	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
-		let TestHarness { mut virtual_overseer, keystore } = test_harness;
+		let TestHarness { mut virtual_overseer, keystore, .. } = test_harness;
 
 		let hash_a = Hash::from_low_u64_be(test_state.relay_parent.to_low_u64_be() - 1); // block 0
 		let hash_b = Hash::from_low_u64_be(hash_a.to_low_u64_be() - 1); // block 1
@@ -2462,3 +2465,242 @@ fn claim_fills_last_free_slot() {
 		virtual_overseer
 	});
 }
+
+#[test]
+fn collation_precheck() {
+	use codec::Decode;
+	use request_v1::{
+		CollationPrecheckRejection as Rejection, CollationPrecheckRequest,
+		CollationPrecheckResponse,
+	};
+	use sc_network::config::{IncomingRequest as RawIncomingRequest, OutgoingResponse};
+
+	let mut test_state = TestState::default();
+
+	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, precheck_queue, .. } = test_harness;
+
+		let head_b = Hash::from_low_u64_be(128);
+		let head_b_num: u32 = 0;
+
+		update_view(&mut virtual_overseer, &mut test_state, vec![(head_b, head_b_num)]).await;
+
+		let peer_a = PeerId::random();
+		connect_and_declare_collator(
+			&mut virtual_overseer,
+			peer_a,
+			CollatorPair::generate().0,
+			test_state.chain_ids[0],
+			CollationVersion::V2,
+		)
+		.await;
+
+		let precheck = |peer, relay_parent| {
+			let (candidate_receipt, _) = create_dummy_candidate_and_commitments(
+				test_state.chain_ids[0],
+				HeadData(vec![1]),
+				relay_parent,
+			);
+			let (pending_response, rx) = oneshot::channel();
+			precheck_queue
+				.try_send(RawIncomingRequest {
+					peer,
+					payload: CollationPrecheckRequest {
+						candidate_receipt,
+						parent_head_data: dummy_pvd().parent_head,
+					}
+					.encode(),
+					pending_response,
+				})
+				.unwrap();
+			rx
+		};
+		let decode = |response: OutgoingResponse| {
+			CollationPrecheckResponse::decode(&mut &response.result.unwrap()[..]).unwrap()
+		};
+
+		// The persisted validation data matches the descriptor.
+		let response = precheck(peer_a, head_b);
+		assert_persisted_validation_data(
+			&mut virtual_overseer,
+			CollationVersion::V2,
+			head_b,
+			test_state.chain_ids[0],
+			Some(dummy_pvd().parent_head.hash()),
+			Some(dummy_pvd()),
+		)
+		.await;
+		assert_eq!(decode(response.await.unwrap()), CollationPrecheckResponse::Accepted);
+
+		// The persisted validation data doesn't match.
+		let response = precheck(peer_a, head_b);
+		let mut pvd = dummy_pvd();
+		pvd.relay_parent_number += 1;
+		assert_persisted_validation_data(
+			&mut virtual_overseer,
+			CollationVersion::V2,
+			head_b,
+			test_state.chain_ids[0],
+			None,
+			Some(pvd),
+		)
+		.await;
+		assert_eq!(
+			decode(response.await.unwrap()),
+			CollationPrecheckResponse::Rejected(Rejection::InvalidPersistedValidationData)
+		);
+
+		// The parent is unknown to prospective parachains.
+		let response = precheck(peer_a, head_b);
+		assert_persisted_validation_data(
+			&mut virtual_overseer,
+			CollationVersion::V2,
+			head_b,
+			test_state.chain_ids[0],
+			None,
+			None,
+		)
+		.await;
+		assert_eq!(
+			decode(response.await.unwrap()),
+			CollationPrecheckResponse::Rejected(Rejection::UnknownParent)
+		);
+
+		// The relay parent is out of view.
+		let response = precheck(peer_a, Hash::repeat_byte(0xaa));
+		assert_eq!(
+			decode(response.await.unwrap()),
+			CollationPrecheckResponse::Rejected(Rejection::UnknownRelayParent)
+		);
+
+		// Peers which didn't declare themselves as collators of the para are punished.
+		let peer_b = PeerId::random();
+		let response = precheck(peer_b, head_b);
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::NetworkBridgeTx(
+				NetworkBridgeTxMessage::ReportPeer(ReportPeerMessage::Single(peer_id, rep)),
+			) => {
+				assert_eq!(peer_id, peer_b);
+				assert_eq!(rep.value, COST_UNEXPECTED_MESSAGE.cost_or_benefit());
+			}
+		);
+		assert!(response.await.is_err());
+
+		virtual_overseer
+	});
+}
+
+#[rstest]
+#[case(true, true)]
+#[case(true, false)]
+#[case(false, true)]
+fn collation_push(#[case] push_enabled: bool, #[case] trusted: bool) {
+	use codec::Decode;
+	use request_v1::{CollationPushRequest, CollationPushResponse};
+	use sc_network::config::{IncomingRequest as RawIncomingRequest, OutgoingResponse};
+
+	let mut test_state = TestState::default();
+	if push_enabled {
+		let feature = node_features::FeatureIndex::CollationPush as usize;
+		test_state.node_features.resize(feature + 1, false);
+		test_state.node_features.set(feature, true);
+	}
+
+	let collator = CollatorPair::generate().0;
+	let mut push_collators = crate::PushCollators::new();
+	if trusted {
+		push_collators
+			.entry(test_state.chain_ids[0])
+			.or_default()
+			.insert(collator.public());
+	}
+
+	let reputation = ReputationAggregator::new(|_| true);
+	test_harness_with_push_collators(reputation, push_collators, |test_harness| async move {
+		let TestHarness { mut virtual_overseer, push_queue, .. } = test_harness;
+
+		let head_b = Hash::from_low_u64_be(128);
+		let head_b_num: u32 = 0;
+
+		update_view(&mut virtual_overseer, &mut test_state, vec![(head_b, head_b_num)]).await;
+
+		let peer_a = PeerId::random();
+		connect_and_declare_collator(
+			&mut virtual_overseer,
+			peer_a,
+			collator,
+			test_state.chain_ids[0],
+			CollationVersion::V2,
+		)
+		.await;
+
+		let pov = PoV { block_data: BlockData(vec![1]) };
+		let (candidate_receipt, _) = create_dummy_candidate_and_commitments(
+			test_state.chain_ids[0],
+			HeadData(vec![1]),
+			head_b,
+		);
+		let push = || {
+			let (pending_response, rx) = oneshot::channel();
+			push_queue
+				.try_send(RawIncomingRequest {
+					peer: peer_a,
+					payload: CollationPushRequest {
+						candidate_receipt: candidate_receipt.clone(),
+						pov: pov.clone(),
+						parent_head_data: dummy_pvd().parent_head,
+					}
+					.encode(),
+					pending_response,
+				})
+				.unwrap();
+			rx
+		};
+		let decode = |response: OutgoingResponse| {
+			CollationPushResponse::decode(&mut &response.result.unwrap()[..]).unwrap()
+		};
+
+		let response = push();
+		if !trusted {
+			// Pushing collators that are not trusted is costly.
+			assert_matches!(
+				overseer_recv(&mut virtual_overseer).await,
+				AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::ReportPeer(
+					ReportPeerMessage::Single(peer, rep),
+				)) => {
+					assert_eq!(peer, peer_a);
+					assert_eq!(rep.value, COST_UNEXPECTED_MESSAGE.cost_or_benefit());
+				}
+			);
+		}
+		if !push_enabled || !trusted {
+			assert_eq!(decode(response.await.unwrap()), CollationPushResponse::Rejected);
+			return virtual_overseer
+		}
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::CandidateBacking(CandidateBackingMessage::CanSecond(request, tx)) => {
+				assert_eq!(request.candidate_hash, candidate_receipt.hash());
+				assert_eq!(request.parent_head_data_hash, dummy_pvd().parent_head.hash());
+				tx.send(true).unwrap();
+			}
+		);
+		assert_candidate_backing_second(
+			&mut virtual_overseer,
+			head_b,
+			test_state.chain_ids[0],
+			&pov,
+			CollationVersion::V2,
+		)
+		.await;
+		assert_eq!(decode(response.await.unwrap()), CollationPushResponse::Accepted);
+
+		// Nothing else is seconded at the relay parent while the candidate is being validated.
+		let response = push();
+		assert_eq!(decode(response.await.unwrap()), CollationPushResponse::Rejected);
+
+		virtual_overseer
+	});
+}
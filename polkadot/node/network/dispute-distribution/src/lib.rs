@@ -113,6 +113,27 @@ pub const RECEIVE_RATE_LIMIT: Duration = Duration::from_millis(100);
 /// We add 50ms extra, just to have some save margin to the `RECEIVE_RATE_LIMIT`.
 pub const SEND_RATE_LIMIT: Duration = RECEIVE_RATE_LIMIT.saturating_add(Duration::from_millis(50));
 
+/// Configuration of the rate limit on the `sender` side.
+///
+/// On top of this global limit, sending to peers which are slow to confirm our requests is slowed
+/// down further, up to 8 times `base_rate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendRateLimitConfig {
+	/// Minimum interval between starting to send for two disputes, once a burst is used up.
+	pub base_rate: Duration,
+	/// Number of disputes we may start sending for at once.
+	///
+	/// Receivers queue up to 10 requests per peer before they start dropping them, bursts should
+	/// stay well below that.
+	pub max_burst: u32,
+}
+
+impl Default for SendRateLimitConfig {
+	fn default() -> Self {
+		Self { base_rate: SEND_RATE_LIMIT, max_burst: 1 }
+	}
+}
+
 /// The dispute distribution subsystem.
 pub struct DisputeDistributionSubsystem<AD> {
 	/// Easy and efficient runtime access for this subsystem.
@@ -179,6 +200,12 @@ where
 		}
 	}
 
+	/// Use the given send rate limit instead of the default [`SendRateLimitConfig`].
+	pub fn with_send_rate_limit(mut self, config: SendRateLimitConfig) -> Self {
+		self.disputes_sender.set_rate_limit_config(config);
+		self
+	}
+
 	/// Start processing work as passed on from the Overseer.
 	async fn run<Context>(mut self, mut ctx: Context) -> std::result::Result<(), FatalError> {
 		let receiver = DisputesReceiver::new(
@@ -49,6 +49,9 @@ struct MetricsInner {
 
 	/// The duration of issued dispute request to response.
 	time_dispute_request: prometheus::Histogram,
+
+	/// Number of requests held back, because the peer was slow to confirm previous requests.
+	deferred_requests: Counter<U64>,
 }
 
 impl Metrics {
@@ -81,6 +84,13 @@ impl Metrics {
 		}
 	}
 
+	/// Requests to slow peers have been held back.
+	pub fn on_deferred_requests(&self, num_requests: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.deferred_requests.inc_by(num_requests as u64)
+		}
+	}
+
 	/// Get a timer to time request/response duration.
 	pub fn time_dispute_request(&self) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
 		self.0.as_ref().map(|metrics| metrics.time_dispute_request.start_timer())
@@ -124,6 +134,13 @@ impl metrics::Metrics for Metrics {
 				))?,
 				registry,
 			)?,
+			deferred_requests: prometheus::register(
+				Counter::new(
+					"polkadot_parachain_dispute_distribution_deferred_requests",
+					"Total number of requests to slow peers which have been held back.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -16,14 +16,11 @@
 
 use std::{
 	collections::{HashMap, HashSet},
-	pin::Pin,
-	task::Poll,
-	time::Duration,
+	time::Instant,
 };
 
-use futures::{channel::oneshot, future::poll_fn, Future};
+use futures::channel::oneshot;
 
-use futures_timer::Delay;
 use indexmap::{map::Entry, IndexMap};
 use polkadot_node_network_protocol::request_response::v1::DisputeRequest;
 use polkadot_node_primitives::{DisputeMessage, DisputeStatus};
@@ -41,15 +38,18 @@ use polkadot_primitives::{CandidateHash, Hash, SessionIndex};
 /// As we assume disputes have a priority, we start sending for disputes in the order
 /// `start_sender` got called.
 mod send_task;
-use send_task::SendTask;
 pub use send_task::TaskFinish;
+use send_task::{SendTask, TaskResult};
+
+mod rate_limit;
+use rate_limit::{PeerRateLimits, RateLimit};
 
 /// Error and [`Result`] type for sender.
 mod error;
 pub use error::{Error, FatalError, JfyiError, Result};
 
 use self::error::JfyiErrorResult;
-use crate::{Metrics, LOG_TARGET, SEND_RATE_LIMIT};
+use crate::{Metrics, SendRateLimitConfig, LOG_TARGET};
 
 /// Messages as sent by background tasks.
 #[derive(Debug)]
@@ -87,9 +87,12 @@ pub struct DisputeSender<M> {
 	/// `Some` if we are waiting for a response `DisputeCoordinatorMessage::ActiveDisputes`.
 	waiting_for_active_disputes: Option<WaitForActiveDisputesState>,
 
-	/// Future for delaying too frequent creation of dispute sending tasks.
+	/// Token bucket for delaying too frequent creation of dispute sending tasks.
 	rate_limit: RateLimit,
 
+	/// Rate limits for peers which are slow to confirm our requests.
+	peer_limits: PeerRateLimits,
+
 	/// Metrics for reporting stats about sent requests.
 	metrics: Metrics,
 }
@@ -113,15 +116,22 @@ impl<M: 'static + Send + Sync> DisputeSender<M> {
 			disputes: IndexMap::new(),
 			tx,
 			waiting_for_active_disputes: None,
-			rate_limit: RateLimit::new(),
+			rate_limit: RateLimit::new(SendRateLimitConfig::default()),
+			peer_limits: PeerRateLimits::new(SendRateLimitConfig::default()),
 			metrics,
 		}
 	}
 
+	/// Use the given rate limit configuration instead of the default one.
+	pub fn set_rate_limit_config(&mut self, config: SendRateLimitConfig) {
+		self.rate_limit = RateLimit::new(config);
+		self.peer_limits = PeerRateLimits::new(config);
+	}
+
 	/// Create a `SendTask` for a particular new dispute.
 	///
-	/// This function is rate-limited by `SEND_RATE_LIMIT`. It will block if called too frequently
-	/// in order to maintain the limit.
+	/// This function is rate-limited by the configured `SendRateLimitConfig`. It will block if
+	/// called too frequently in order to maintain the limit.
 	pub async fn start_sender<Context>(
 		&mut self,
 		ctx: &mut Context,
@@ -144,6 +154,7 @@ impl<M: 'static + Send + Sync> DisputeSender<M> {
 					&self.active_sessions,
 					NestingSender::new(self.tx.clone(), DisputeSenderMessage::TaskFinish),
 					req,
+					&mut self.peer_limits,
 					&self.metrics,
 				)
 				.await?;
@@ -178,7 +189,10 @@ impl<M: 'static + Send + Sync> DisputeSender<M> {
 					},
 					Some(task) => task,
 				};
-				task.on_finished_send(&receiver, result);
+				let succeeded = matches!(result, TaskResult::Succeeded);
+				if let Some(latency) = task.on_finished_send(&receiver, result) {
+					self.peer_limits.on_finished(&receiver, latency, succeeded);
+				}
 			},
 			DisputeSenderMessage::ActiveDisputesReady(result) => {
 				let state = self.waiting_for_active_disputes.take();
@@ -246,11 +260,11 @@ impl<M: 'static + Send + Sync> DisputeSender<M> {
 
 	/// Handle new active disputes response.
 	///
-	/// - Initiate a retry of failed sends which are still active.
+	/// - Initiate a retry of failed and deferred sends which are still active.
 	/// - Get new authorities to send messages to.
 	/// - Get rid of obsolete tasks and disputes.
 	///
-	/// This function ensures the `SEND_RATE_LIMIT`, therefore it might block.
+	/// This function ensures the send rate limit, therefore it might block.
 	async fn handle_new_active_disputes<Context>(
 		&mut self,
 		ctx: &mut Context,
@@ -263,21 +277,28 @@ impl<M: 'static + Send + Sync> DisputeSender<M> {
 		// Cleanup obsolete senders (retain keeps order of remaining elements):
 		self.disputes
 			.retain(|candidate_hash, _| active_disputes.contains(candidate_hash));
+		self.peer_limits.prune(Instant::now());
 
 		// Iterates in order of insertion:
 		let mut should_rate_limit = true;
 		for (candidate_hash, dispute) in self.disputes.iter_mut() {
-			if have_new_sessions || dispute.has_failed_sends() {
+			if have_new_sessions || dispute.has_failed_sends() || dispute.has_deferred_sends() {
 				if should_rate_limit {
 					self.rate_limit
 						.limit("while going through new sessions/failed sends", *candidate_hash)
 						.await;
 				}
 				let sends_happened = dispute
-					.refresh_sends(ctx, runtime, &self.active_sessions, &self.metrics)
+					.refresh_sends(
+						ctx,
+						runtime,
+						&self.active_sessions,
+						&mut self.peer_limits,
+						&self.metrics,
+					)
 					.await?;
 				// Only rate limit if we actually sent something out _and_ it was not just because
-				// of errors on previous sends.
+				// of errors or deferrals on previous sends.
 				//
 				// Reasoning: It would not be acceptable to slow down the whole subsystem, just
 				// because of a few bad peers having problems. It is actually better to risk
@@ -306,53 +327,6 @@ impl<M: 'static + Send + Sync> DisputeSender<M> {
 	}
 }
 
-/// Rate limiting logic.
-///
-/// Suitable for the sending side.
-struct RateLimit {
-	limit: Delay,
-}
-
-impl RateLimit {
-	/// Create new `RateLimit` that is immediately ready.
-	fn new() -> Self {
-		// Start with an empty duration, as there has not been any previous call.
-		Self { limit: Delay::new(Duration::new(0, 0)) }
-	}
-
-	/// Initialized with actual `SEND_RATE_LIMIT` duration.
-	fn new_limit() -> Self {
-		Self { limit: Delay::new(SEND_RATE_LIMIT) }
-	}
-
-	/// Wait until ready and prepare for next call.
-	///
-	/// String given as occasion and candidate hash are logged in case the rate limit hit.
-	async fn limit(&mut self, occasion: &'static str, candidate_hash: CandidateHash) {
-		// Wait for rate limit and add some logging:
-		let mut num_wakes: u32 = 0;
-		poll_fn(|cx| {
-			let old_limit = Pin::new(&mut self.limit);
-			match old_limit.poll(cx) {
-				Poll::Pending => {
-					gum::debug!(
-						target: LOG_TARGET,
-						?occasion,
-						?candidate_hash,
-						?num_wakes,
-						"Sending rate limit hit, slowing down requests"
-					);
-					num_wakes += 1;
-					Poll::Pending
-				},
-				Poll::Ready(()) => Poll::Ready(()),
-			}
-		})
-		.await;
-		*self = Self::new_limit();
-	}
-}
-
 /// Retrieve the currently active sessions.
 ///
 /// List is all indices of all active sessions together with the head that was used for the query.
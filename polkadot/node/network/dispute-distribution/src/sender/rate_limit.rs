@@ -0,0 +1,222 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rate limiting of the sending side.
+//!
+//! Two limits apply: [`RateLimit`] limits how often we start sending for a dispute, which is what
+//! keeps us below the `RECEIVE_RATE_LIMIT` of all peers at once, as each dispute results in a
+//! request to every peer. [`PeerRateLimits`] further slows down sending to peers which are slow to
+//! confirm our requests, so we don't overwhelm them while sending at full speed to everybody else.
+
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+use futures_timer::Delay;
+use polkadot_primitives::{AuthorityDiscoveryId, CandidateHash};
+
+use crate::{SendRateLimitConfig, LOG_TARGET};
+
+/// By how much, relative to the base rate, we slow down sending to a slow peer at most.
+pub const MAX_PEER_SLOWDOWN: u32 = 8;
+
+/// Peers we did not send anything to for that long are forgotten.
+const PEER_STATE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Token bucket limiting the rate at which we start sending for disputes.
+///
+/// Up to `max_burst` disputes can be started at once, afterwards one every `base_rate`.
+pub struct RateLimit {
+	config: SendRateLimitConfig,
+	tokens: u32,
+	last_refill: Instant,
+}
+
+impl RateLimit {
+	/// Create a new `RateLimit` that is immediately ready for a full burst.
+	pub fn new(config: SendRateLimitConfig) -> Self {
+		Self { config, tokens: config.max_burst.max(1), last_refill: Instant::now() }
+	}
+
+	/// Wait until ready and prepare for next call.
+	///
+	/// String given as occasion and candidate hash are logged in case the rate limit hit.
+	pub async fn limit(&mut self, occasion: &'static str, candidate_hash: CandidateHash) {
+		self.refill(Instant::now());
+		while self.tokens == 0 {
+			let wait = (self.last_refill + self.config.base_rate)
+				.saturating_duration_since(Instant::now());
+			gum::debug!(
+				target: LOG_TARGET,
+				?occasion,
+				?candidate_hash,
+				?wait,
+				"Sending rate limit hit, slowing down requests"
+			);
+			Delay::new(wait).await;
+			self.refill(Instant::now());
+		}
+		self.tokens -= 1;
+	}
+
+	fn refill(&mut self, now: Instant) {
+		let max_burst = self.config.max_burst.max(1);
+		let elapsed = now.saturating_duration_since(self.last_refill);
+		let periods = (elapsed.as_nanos() / self.config.base_rate.as_nanos().max(1))
+			.min(max_burst as u128) as u32;
+		self.tokens = (self.tokens + periods).min(max_burst);
+		if self.tokens == max_burst {
+			self.last_refill = now;
+		} else {
+			self.last_refill += self.config.base_rate * periods;
+		}
+	}
+}
+
+/// What we know about how fast a peer confirms our requests.
+struct PeerState {
+	/// Moving average of the time it took the peer to confirm our requests.
+	latency: Duration,
+	/// When we last sent a request to the peer.
+	last_sent: Option<Instant>,
+}
+
+/// Per peer rate limits, adapting to how fast each peer confirms our requests.
+///
+/// A peer confirming within `base_rate` is not limited any further than by [`RateLimit`]. For
+/// slower peers, the interval between two requests grows with their confirmation latency, up to
+/// `MAX_PEER_SLOWDOWN` times `base_rate`. Failed requests count as maximally slow.
+pub struct PeerRateLimits {
+	base_rate: Duration,
+	peers: HashMap<AuthorityDiscoveryId, PeerState>,
+}
+
+impl PeerRateLimits {
+	/// Create rate limits which don't limit any peer until we observed its confirmations.
+	pub fn new(config: SendRateLimitConfig) -> Self {
+		Self { base_rate: config.base_rate, peers: HashMap::new() }
+	}
+
+	/// The minimum interval between two requests to `peer`.
+	pub fn interval(&self, peer: &AuthorityDiscoveryId) -> Duration {
+		let latency = self.peers.get(peer).map_or(Duration::ZERO, |state| state.latency);
+		latency.clamp(self.base_rate, self.max_interval())
+	}
+
+	/// Whether we may send a request to `peer` at `now`.
+	pub fn may_send(&self, peer: &AuthorityDiscoveryId, now: Instant) -> bool {
+		match self.peers.get(peer) {
+			Some(PeerState { latency, last_sent: Some(last_sent) })
+				if *latency > self.base_rate =>
+				now.saturating_duration_since(*last_sent) >= self.interval(peer),
+			_ => true,
+		}
+	}
+
+	/// Note that we sent a request to `peer` at `now`.
+	pub fn on_sent(&mut self, peer: &AuthorityDiscoveryId, now: Instant) {
+		self.peers
+			.entry(peer.clone())
+			.or_insert(PeerState { latency: Duration::ZERO, last_sent: None })
+			.last_sent = Some(now);
+	}
+
+	/// Note that a request to `peer` finished after `latency`.
+	pub fn on_finished(&mut self, peer: &AuthorityDiscoveryId, latency: Duration, succeeded: bool) {
+		let sample = if succeeded { latency } else { self.max_interval() };
+		match self.peers.get_mut(peer) {
+			Some(state) if state.latency != Duration::ZERO =>
+				state.latency = (state.latency * 3 + sample) / 4,
+			Some(state) => state.latency = sample,
+			None => {
+				self.peers.insert(peer.clone(), PeerState { latency: sample, last_sent: None });
+			},
+		}
+	}
+
+	/// Forget about peers we haven't sent anything to in a long time.
+	pub fn prune(&mut self, now: Instant) {
+		self.peers.retain(|_, state| {
+			state.last_sent.is_some_and(|last_sent| {
+				now.saturating_duration_since(last_sent) < PEER_STATE_TIMEOUT
+			})
+		});
+	}
+
+	fn max_interval(&self) -> Duration {
+		self.base_rate * MAX_PEER_SLOWDOWN
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keyring::Sr25519Keyring;
+
+	fn config() -> SendRateLimitConfig {
+		SendRateLimitConfig { base_rate: Duration::from_millis(100), max_burst: 3 }
+	}
+
+	#[test]
+	fn rate_limit_allows_bursts() {
+		let start = Instant::now();
+		let mut limit = RateLimit::new(config());
+		limit.tokens = 0;
+		limit.last_refill = start;
+
+		limit.refill(start + Duration::from_millis(250));
+		assert_eq!(limit.tokens, 2);
+		assert_eq!(limit.last_refill, start + Duration::from_millis(200));
+
+		// never more than a burst.
+		limit.refill(start + Duration::from_secs(10));
+		assert_eq!(limit.tokens, 3);
+		assert_eq!(limit.last_refill, start + Duration::from_secs(10));
+	}
+
+	#[test]
+	fn slow_peers_are_limited() {
+		let fast: AuthorityDiscoveryId = Sr25519Keyring::Alice.public().into();
+		let slow: AuthorityDiscoveryId = Sr25519Keyring::Bob.public().into();
+		let failing: AuthorityDiscoveryId = Sr25519Keyring::Charlie.public().into();
+		let start = Instant::now();
+		let mut limits = PeerRateLimits::new(config());
+
+		for peer in [&fast, &slow, &failing] {
+			assert!(limits.may_send(peer, start));
+			limits.on_sent(peer, start);
+		}
+		limits.on_finished(&fast, Duration::from_millis(20), true);
+		limits.on_finished(&slow, Duration::from_millis(400), true);
+		limits.on_finished(&failing, Duration::from_millis(20), false);
+
+		let soon = start + Duration::from_millis(200);
+		assert!(limits.may_send(&fast, soon));
+		assert!(!limits.may_send(&slow, soon));
+		assert!(!limits.may_send(&failing, soon));
+		assert!(limits.may_send(&slow, start + Duration::from_millis(400)));
+		assert_eq!(limits.interval(&fast), Duration::from_millis(100));
+		assert_eq!(limits.interval(&failing), Duration::from_millis(800));
+
+		// the latency is averaged.
+		limits.on_finished(&slow, Duration::from_millis(0), true);
+		assert_eq!(limits.interval(&slow), Duration::from_millis(300));
+
+		limits.prune(start + PEER_STATE_TIMEOUT);
+		assert!(limits.peers.is_empty());
+	}
+}
@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::{HashMap, HashSet};
+use std::{
+	collections::{HashMap, HashSet},
+	time::{Duration, Instant},
+};
 
 use futures::{Future, FutureExt};
 
@@ -32,7 +35,10 @@ use polkadot_primitives::{
 	AuthorityDiscoveryId, CandidateHash, Hash, SessionIndex, ValidatorIndex,
 };
 
-use super::error::{FatalError, Result};
+use super::{
+	error::{FatalError, Result},
+	rate_limit::PeerRateLimits,
+};
 
 use crate::{
 	metrics::{FAILED, SUCCEEDED},
@@ -57,14 +63,17 @@ pub struct SendTask<M> {
 	/// Whether we have any tasks failed since the last refresh.
 	has_failed_sends: bool,
 
+	/// Whether we held back any requests to slow peers on the last refresh.
+	has_deferred_sends: bool,
+
 	/// Sender to be cloned for tasks.
 	tx: NestingSender<M, TaskFinish>,
 }
 
 /// Status of a particular vote/statement delivery to a particular validator.
 enum DeliveryStatus {
-	/// Request is still in flight.
-	Pending,
+	/// Request is still in flight, since the given instant.
+	Pending(Instant),
 	/// Succeeded - no need to send request to this peer anymore.
 	Succeeded,
 }
@@ -112,11 +121,19 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 		active_sessions: &HashMap<SessionIndex, Hash>,
 		tx: NestingSender<M, TaskFinish>,
 		request: DisputeRequest,
+		peer_limits: &mut PeerRateLimits,
 		metrics: &Metrics,
 	) -> Result<Self> {
-		let mut send_task =
-			Self { request, deliveries: HashMap::new(), has_failed_sends: false, tx };
-		send_task.refresh_sends(ctx, runtime, active_sessions, metrics).await?;
+		let mut send_task = Self {
+			request,
+			deliveries: HashMap::new(),
+			has_failed_sends: false,
+			has_deferred_sends: false,
+			tx,
+		};
+		send_task
+			.refresh_sends(ctx, runtime, active_sessions, peer_limits, metrics)
+			.await?;
 		Ok(send_task)
 	}
 
@@ -126,8 +143,9 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 	/// happens and on a regular basis to ensure we are retrying failed attempts.
 	///
 	/// This might resend to validators and is thus subject to any rate limiting we might want.
-	/// Calls to this function for different instances should be rate limited according to
-	/// `SEND_RATE_LIMIT`.
+	/// Calls to this function for different instances should be rate limited according to the
+	/// configured `SendRateLimitConfig`. Requests to peers which are still subject to their
+	/// `peer_limits` are deferred to a later refresh.
 	///
 	/// Returns: `True` if this call resulted in new requests.
 	pub async fn refresh_sends<Context>(
@@ -135,16 +153,18 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 		ctx: &mut Context,
 		runtime: &mut RuntimeInfo,
 		active_sessions: &HashMap<SessionIndex, Hash>,
+		peer_limits: &mut PeerRateLimits,
 		metrics: &Metrics,
 	) -> Result<bool> {
 		let new_authorities = self.get_relevant_validators(ctx, runtime, active_sessions).await?;
 
 		// Note this will also contain all authorities for which sending failed previously:
-		let add_authorities: Vec<_> = new_authorities
+		let now = Instant::now();
+		let (add_authorities, deferred_authorities): (Vec<_>, Vec<_>) = new_authorities
 			.iter()
 			.filter(|a| !self.deliveries.contains_key(a))
 			.map(Clone::clone)
-			.collect();
+			.partition(|a| peer_limits.may_send(a, now));
 
 		// Get rid of dead/irrelevant tasks/statuses:
 		gum::trace!(
@@ -160,11 +180,19 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 			new_and_failed_authorities = ?add_authorities.len(),
 			overall_authority_set_size = ?new_authorities.len(),
 			already_running_deliveries = ?self.deliveries.len(),
+			deferred_authorities = ?deferred_authorities.len(),
 			"Starting new send requests for authorities."
 		);
-		let new_statuses =
-			send_requests(ctx, self.tx.clone(), add_authorities, self.request.clone(), metrics)
-				.await?;
+		metrics.on_deferred_requests(deferred_authorities.len());
+		let new_statuses = send_requests(
+			ctx,
+			self.tx.clone(),
+			add_authorities,
+			self.request.clone(),
+			peer_limits,
+			metrics,
+		)
+		.await?;
 
 		let was_empty = new_statuses.is_empty();
 		gum::trace!(
@@ -174,6 +202,7 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 		);
 
 		self.has_failed_sends = false;
+		self.has_deferred_sends = !deferred_authorities.is_empty();
 		self.deliveries.extend(new_statuses.into_iter());
 		Ok(!was_empty)
 	}
@@ -183,11 +212,22 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 		self.has_failed_sends
 	}
 
+	/// Whether any requests to slow peers have been held back on the last refresh.
+	pub fn has_deferred_sends(&self) -> bool {
+		self.has_deferred_sends
+	}
+
 	/// Handle a finished response waiting task.
 	///
 	/// Called by `DisputeSender` upon reception of the corresponding message from our spawned
 	/// `wait_response_task`.
-	pub fn on_finished_send(&mut self, authority: &AuthorityDiscoveryId, result: TaskResult) {
+	///
+	/// Returns: How long it took the request to finish, if it was still pending.
+	pub fn on_finished_send(
+		&mut self,
+		authority: &AuthorityDiscoveryId,
+		result: TaskResult,
+	) -> Option<Duration> {
 		match result {
 			TaskResult::Failed(err) => {
 				gum::trace!(
@@ -200,7 +240,10 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 
 				self.has_failed_sends = true;
 				// Remove state, so we know what to try again:
-				self.deliveries.remove(authority);
+				match self.deliveries.remove(authority) {
+					Some(DeliveryStatus::Pending(sent_at)) => Some(sent_at.elapsed()),
+					_ => None,
+				}
 			},
 			TaskResult::Succeeded => {
 				let status = match self.deliveries.get_mut(&authority) {
@@ -214,12 +257,15 @@ impl<M: 'static + Send + Sync> SendTask<M> {
 							?result,
 							"Received `FromSendingTask::Finished` for non existing task."
 						);
-						return
+						return None
 					},
 					Some(status) => status,
 				};
 				// We are done here:
-				*status = DeliveryStatus::Succeeded;
+				match std::mem::replace(status, DeliveryStatus::Succeeded) {
+					DeliveryStatus::Pending(sent_at) => Some(sent_at.elapsed()),
+					DeliveryStatus::Succeeded => None,
+				}
 			},
 		}
 	}
@@ -278,8 +324,10 @@ async fn send_requests<Context, M: 'static + Send + Sync>(
 	tx: NestingSender<M, TaskFinish>,
 	receivers: Vec<AuthorityDiscoveryId>,
 	req: DisputeRequest,
+	peer_limits: &mut PeerRateLimits,
 	metrics: &Metrics,
 ) -> Result<HashMap<AuthorityDiscoveryId, DeliveryStatus>> {
+	let now = Instant::now();
 	let mut statuses = HashMap::with_capacity(receivers.len());
 	let mut reqs = Vec::with_capacity(receivers.len());
 
@@ -298,7 +346,8 @@ async fn send_requests<Context, M: 'static + Send + Sync>(
 		);
 
 		ctx.spawn("dispute-sender", fut.boxed()).map_err(FatalError::SpawnTask)?;
-		statuses.insert(receiver, DeliveryStatus::Pending);
+		peer_limits.on_sent(&receiver, now);
+		statuses.insert(receiver, DeliveryStatus::Pending(now));
 	}
 
 	let msg = NetworkBridgeTxMessage::SendRequests(reqs, IfDisconnected::ImmediateError);
@@ -44,6 +44,7 @@ use polkadot_node_network_protocol::{
 	authority_discovery::AuthorityDiscovery, peer_set::PeerSet, GossipSupportNetworkMessage,
 	PeerId, ValidationProtocols,
 };
+use polkadot_node_primitives::session_history::SessionHistoryHandle;
 use polkadot_node_subsystem::{
 	messages::{
 		ChainApiMessage, GossipSupportMessage, NetworkBridgeEvent, NetworkBridgeRxMessage,
@@ -136,6 +137,8 @@ pub struct GossipSupport<AD> {
 	/// The oldest session we need to build a topology for because
 	/// the finalized blocks are from a session we haven't built a topology for.
 	finalized_needed_session: Option<u32>,
+	/// The validator sets of the sessions we have seen.
+	session_history: SessionHistoryHandle,
 	/// Subsystem metrics.
 	metrics: Metrics,
 }
@@ -163,10 +166,17 @@ where
 			min_known_session: u32::MAX,
 			authority_discovery,
 			finalized_needed_session: None,
+			session_history: SessionHistoryHandle::default(),
 			metrics,
 		}
 	}
 
+	/// Record the validator sets of the sessions in `session_history`.
+	pub fn with_session_history(mut self, session_history: SessionHistoryHandle) -> Self {
+		self.session_history = session_history;
+		self
+	}
+
 	async fn run<Context>(mut self, mut ctx: Context) -> Self {
 		fn get_connectivity_check_delay() -> Delay {
 			Delay::new(LOW_CONNECTIVITY_WARN_DELAY)
@@ -282,6 +292,7 @@ where
 						"New session detected",
 					);
 					self.last_session_index = Some(session_index);
+					self.session_history.record(session_index, (&session_info).into());
 				}
 
 				// Connect to authorities from the past/present/future.
@@ -410,6 +421,8 @@ where
 					Some(session_info) => session_info,
 					_ => return Ok(()),
 				};
+				self.session_history
+					.record(finalized_session_index, (&finalized_session_info).into());
 
 				let our_index = self.get_key_index_and_update_metrics(&finalized_session_info)?;
 				update_gossip_topology(
@@ -37,6 +37,7 @@ use polkadot_node_network_protocol::{
 	peer_set::ValidationVersion,
 	ObservedRole,
 };
+use polkadot_node_primitives::session_history::SessionValidators;
 use polkadot_node_subsystem::messages::{AllMessages, RuntimeApiMessage, RuntimeApiRequest};
 use polkadot_node_subsystem_test_helpers as test_helpers;
 use polkadot_node_subsystem_util::TimeoutExt as _;
@@ -417,6 +418,11 @@ fn issues_a_connection_request_on_new_session() {
 
 	assert_eq!(state.last_session_index, Some(1));
 	assert!(state.last_failure.is_none());
+	assert_eq!(state.session_history.sessions(), vec![1]);
+	assert_eq!(
+		state.session_history.session(1).as_deref(),
+		Some(&SessionValidators::from(&make_session_info()))
+	);
 
 	// does not issue on the same session
 	let hash = Hash::repeat_byte(0xBB);
@@ -36,6 +36,19 @@ pub trait AuthorityDiscovery: Send + Debug + 'static {
 		&mut self,
 		authority: AuthorityDiscoveryId,
 	) -> Option<HashSet<Multiaddr>>;
+	/// Get the addresses for the given [`AuthorityDiscoveryId`] from the local address cache,
+	/// preferred addresses first.
+	///
+	/// Defaults to the addresses of [`Self::get_addresses_by_authority_id`] in no particular
+	/// order.
+	async fn get_prioritized_addresses_by_authority_id(
+		&mut self,
+		authority: AuthorityDiscoveryId,
+	) -> Option<Vec<Multiaddr>> {
+		self.get_addresses_by_authority_id(authority)
+			.await
+			.map(|addresses| addresses.into_iter().collect())
+	}
 	/// Get the [`AuthorityDiscoveryId`] for the given [`PeerId`] from the local address cache.
 	async fn get_authority_ids_by_peer_id(
 		&mut self,
@@ -52,6 +65,13 @@ impl AuthorityDiscovery for AuthorityDiscoveryService {
 		AuthorityDiscoveryService::get_addresses_by_authority_id(self, authority).await
 	}
 
+	async fn get_prioritized_addresses_by_authority_id(
+		&mut self,
+		authority: AuthorityDiscoveryId,
+	) -> Option<Vec<Multiaddr>> {
+		AuthorityDiscoveryService::get_prioritized_addresses_by_authority_id(self, authority).await
+	}
+
 	async fn get_authority_ids_by_peer_id(
 		&mut self,
 		peer_id: PeerId,
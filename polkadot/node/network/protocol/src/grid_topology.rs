@@ -730,4 +730,31 @@ mod tests {
 			assert_eq!(column_result, expected_column);
 		}
 	}
+
+	#[test]
+	fn grid_reaches_everyone_in_two_hops() {
+		for len in (1..=130).chain([300, 1000]) {
+			let neighbors: Vec<(HashSet<usize>, HashSet<usize>)> = (0..len)
+				.map(|i| {
+					let matrix = matrix_neighbors(i, len).unwrap();
+					(matrix.row_neighbors.collect(), matrix.column_neighbors.collect())
+				})
+				.collect();
+
+			for (origin, (x, y)) in neighbors.iter().enumerate() {
+				// Neighborhood is symmetric, so validators agree on whom to route to.
+				assert!(x.iter().all(|n| neighbors[*n].0.contains(&origin)));
+				assert!(y.iter().all(|n| neighbors[*n].1.contains(&origin)));
+
+				// The origin routes to its X and Y neighbors, which route to their Y and X
+				// neighbors respectively.
+				let mut reached: HashSet<usize> = x.union(y).copied().collect();
+				reached.extend(x.iter().flat_map(|n| neighbors[*n].1.iter().copied()));
+				reached.extend(y.iter().flat_map(|n| neighbors[*n].0.iter().copied()));
+				reached.insert(origin);
+
+				assert_eq!(reached.len(), len, "{} validators", len);
+			}
+		}
+	}
 }
@@ -19,14 +19,18 @@
 use derive_more::Display;
 use polkadot_primitives::Hash;
 use sc_network::{
-	config::SetConfig, peer_store::PeerStoreProvider, service::NotificationMetrics,
-	types::ProtocolName, NetworkBackend, NotificationService,
+	config::{InactivityEviction, SetConfig},
+	peer_store::PeerStoreProvider,
+	service::NotificationMetrics,
+	types::ProtocolName,
+	NetworkBackend, NotificationService,
 };
 use sp_runtime::traits::Block;
 use std::{
 	collections::{hash_map::Entry, HashMap},
 	ops::{Index, IndexMut},
 	sync::Arc,
+	time::Duration,
 };
 use strum::{EnumIter, IntoEnumIterator};
 
@@ -60,17 +64,38 @@ pub enum IsAuthority {
 	No,
 }
 
+/// The number of non-reserved incoming slots of the validation peer set, and the number of
+/// non-reserved outgoing slots.
+///
+/// We allow full nodes to connect to validators for gossip. To ensure any `MIN_GOSSIP_PEERS`
+/// always include reserved peers, we limit the amount of non-reserved slots to be less than
+/// `MIN_GOSSIP_PEERS` in total.
+pub const VALIDATION_NON_RESERVED_SLOTS: u32 = super::MIN_GOSSIP_PEERS as u32 / 2 - 1;
+
+/// When non-reserved peers of the validation peer set are disconnected for being inactive, if
+/// enabled.
+///
+/// Active peers send at least a view update for every new leaf, so they are well above these
+/// thresholds.
+pub const VALIDATION_INACTIVITY_EVICTION: InactivityEviction = InactivityEviction {
+	max_idle: Duration::from_secs(2 * 60),
+	min_notifications: 20,
+	rate_window: Duration::from_secs(10 * 60),
+};
+
 impl PeerSet {
 	/// Get `sc_network` peer set configurations for each peerset on the default version.
 	///
 	/// Those should be used in the network configuration to register the protocols with the
-	/// network service.
+	/// network service. Inactive non-reserved peers of the validation peer set are disconnected
+	/// according to `validation_inactivity_eviction`, if given.
 	pub fn get_info<B: Block, N: NetworkBackend<B, <B as Block>::Hash>>(
 		self,
 		is_authority: IsAuthority,
 		peerset_protocol_names: &PeerSetProtocolNames,
 		metrics: NotificationMetrics,
 		peer_store_handle: Arc<dyn PeerStoreProvider>,
+		validation_inactivity_eviction: Option<InactivityEviction>,
 	) -> (N::NotificationProtocolConfig, (PeerSet, Box<dyn NotificationService>)) {
 		// Networking layer relies on `get_main_name()` being the main name of the protocol
 		// for peersets and connection management.
@@ -90,14 +115,11 @@ impl PeerSet {
 					max_notification_size,
 					None,
 					SetConfig {
-						// we allow full nodes to connect to validators for gossip
-						// to ensure any `MIN_GOSSIP_PEERS` always include reserved peers
-						// we limit the amount of non-reserved slots to be less
-						// than `MIN_GOSSIP_PEERS` in total
-						in_peers: super::MIN_GOSSIP_PEERS as u32 / 2 - 1,
-						out_peers: super::MIN_GOSSIP_PEERS as u32 / 2 - 1,
+						in_peers: VALIDATION_NON_RESERVED_SLOTS,
+						out_peers: VALIDATION_NON_RESERVED_SLOTS,
 						reserved_nodes: Vec::new(),
 						non_reserved_mode: sc_network::config::NonReservedPeerMode::Accept,
+						inactivity_eviction: validation_inactivity_eviction,
 					},
 					metrics,
 					peer_store_handle,
@@ -122,6 +144,7 @@ impl PeerSet {
 						} else {
 							sc_network::config::NonReservedPeerMode::Deny
 						},
+						inactivity_eviction: None,
 					},
 					metrics,
 					peer_store_handle,
@@ -138,7 +161,7 @@ impl PeerSet {
 	/// of the main protocol name reported by [`PeerSetProtocolNames::get_main_name()`].
 	pub fn get_main_version(self) -> ProtocolVersion {
 		match self {
-			PeerSet::Validation => ValidationVersion::V3.into(),
+			PeerSet::Validation => ValidationVersion::V4.into(),
 			PeerSet::Collation => CollationVersion::V2.into(),
 		}
 	}
@@ -164,6 +187,8 @@ impl PeerSet {
 			PeerSet::Validation =>
 				if version == ValidationVersion::V3.into() {
 					Some("validation/3")
+				} else if version == ValidationVersion::V4.into() {
+					Some("validation/4")
 				} else {
 					None
 				},
@@ -214,6 +239,7 @@ pub fn peer_sets_info<B: Block, N: NetworkBackend<B, <B as Block>::Hash>>(
 	peerset_protocol_names: &PeerSetProtocolNames,
 	metrics: NotificationMetrics,
 	peer_store_handle: Arc<dyn PeerStoreProvider>,
+	validation_inactivity_eviction: Option<InactivityEviction>,
 ) -> Vec<(N::NotificationProtocolConfig, (PeerSet, Box<dyn NotificationService>))> {
 	PeerSet::iter()
 		.map(|s| {
@@ -222,6 +248,7 @@ pub fn peer_sets_info<B: Block, N: NetworkBackend<B, <B as Block>::Hash>>(
 				&peerset_protocol_names,
 				metrics.clone(),
 				Arc::clone(&peer_store_handle),
+				validation_inactivity_eviction,
 			)
 		})
 		.collect()
@@ -242,6 +269,11 @@ impl From<ProtocolVersion> for u32 {
 pub enum ValidationVersion {
 	/// The third version.
 	V3 = 3,
+	/// The fourth version, which can batch several messages into a single notification.
+	///
+	/// The messages are the ones of [`ValidationVersion::V3`]. The network bridge reports peers
+	/// of this version as [`ValidationVersion::V3`] peers to the subsystems.
+	V4 = 4,
 }
 
 /// Supported collation protocol versions. Only versions defined here must be used in the codebase.
@@ -433,18 +465,24 @@ impl PeerSetProtocolNames {
 		format!("{}/{}/{}", prefix, short_name, version).into()
 	}
 
-	/// Get the protocol fallback names. Currently, it only holds
-	/// the legacy name for the collation protocol version 1.
+	/// Get the protocol fallback names. Currently, it holds the name of the validation protocol
+	/// version 3 and the legacy name for the collation protocol version 1.
 	fn get_fallback_names(
 		protocol: PeerSet,
-		_genesis_hash: &Hash,
-		_fork_id: Option<&str>,
+		genesis_hash: &Hash,
+		fork_id: Option<&str>,
 	) -> Vec<ProtocolName> {
 		let mut fallbacks = vec![];
 		match protocol {
 			PeerSet::Validation => {
-				// The validation protocol no longer supports protocol versions 1 and 2,
-				// and only version 3 is used. Therefore, fallback protocols remain empty.
+				// The validation protocol no longer supports protocol versions 1 and 2. Version 3
+				// is the fallback of version 4, which only adds batching.
+				fallbacks.push(Self::generate_name(
+					genesis_hash,
+					fork_id,
+					protocol,
+					ValidationVersion::V3.into(),
+				));
 			},
 			PeerSet::Collation => {
 				fallbacks.push(LEGACY_COLLATION_PROTOCOL_V1.into());
@@ -526,9 +564,16 @@ mod tests {
 		let protocol_names = PeerSetProtocolNames::new(genesis_hash, None);
 
 		let validation_main =
-			"/7ac8741de8b7146d8a5617fd462914557fe63c265a7f1c10e7dae32858eebb80/validation/3";
+			"/7ac8741de8b7146d8a5617fd462914557fe63c265a7f1c10e7dae32858eebb80/validation/4";
 		assert_eq!(
 			protocol_names.try_get_protocol(&validation_main.into()),
+			Some((PeerSet::Validation, TestVersion(4).into())),
+		);
+
+		let validation_fallback =
+			"/7ac8741de8b7146d8a5617fd462914557fe63c265a7f1c10e7dae32858eebb80/validation/3";
+		assert_eq!(
+			protocol_names.try_get_protocol(&validation_fallback.into()),
 			Some((PeerSet::Validation, TestVersion(3).into())),
 		);
 
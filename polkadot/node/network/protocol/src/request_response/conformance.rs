@@ -0,0 +1,325 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conformance of the wire format of the requests and responses.
+//!
+//! Nodes running different versions exchange these payloads, so their SCALE encoding must not
+//! change as long as the protocol name stays the same. A sample of every request and response is
+//! compared against the encoding committed in `fixtures/wire_format.txt`.
+//!
+//! A failure means the layout of a payload changed. That requires a new protocol version, with
+//! its own types and fixtures, instead of updating the existing fixtures.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use bitvec::{bitvec, order::Lsb0};
+use codec::{Decode, DecodeAll, Encode};
+use rand::{Rng, SeedableRng};
+
+use polkadot_node_primitives::{
+	AvailableData, BlockData, ErasureChunk, InvalidDisputeVote, PoV, Proof,
+	UncheckedDisputeMessage, ValidDisputeVote,
+};
+use polkadot_primitives::{
+	vstaging::{CandidateDescriptorV2, CandidateReceiptV2, CommittedCandidateReceiptV2},
+	CandidateCommitments, CandidateHash, ChunkIndex, CompactStatement, CoreIndex, Hash, HeadData,
+	Id as ParaId, InvalidDisputeStatementKind, PersistedValidationData, PvfCheckStatement,
+	UncheckedSignedStatement, ValidDisputeStatementKind, ValidationCode, ValidationCodeHash,
+	ValidatorIndex, ValidatorSignature,
+};
+
+use super::{v1, v2};
+use crate::v3::StatementFilter;
+
+const FIXTURES: &str = include_str!("fixtures/wire_format.txt");
+
+/// The encoding of a sample payload.
+struct Sample {
+	name: &'static str,
+	encoded: Vec<u8>,
+	/// Decodes an encoded payload of the same type and encodes it again.
+	reencode: fn(&[u8]) -> Result<Vec<u8>, codec::Error>,
+}
+
+fn reencode<T: Encode + Decode>(mut encoded: &[u8]) -> Result<Vec<u8>, codec::Error> {
+	T::decode_all(&mut encoded).map(|payload| payload.encode())
+}
+
+fn sample<T: Encode + Decode>(name: &'static str, payload: T) -> Sample {
+	Sample { name, encoded: payload.encode(), reencode: reencode::<T> }
+}
+
+fn signature(byte: u8) -> ValidatorSignature {
+	ValidatorSignature::decode(&mut &[byte; 64][..]).unwrap()
+}
+
+fn descriptor() -> CandidateDescriptorV2 {
+	CandidateDescriptorV2::new(
+		ParaId::from(2000),
+		Hash::repeat_byte(1),
+		CoreIndex(3),
+		4,
+		Hash::repeat_byte(5),
+		Hash::repeat_byte(6),
+		Hash::repeat_byte(7),
+		Hash::repeat_byte(8),
+		ValidationCodeHash::from(Hash::repeat_byte(10)),
+	)
+}
+
+fn receipt() -> CandidateReceiptV2 {
+	CandidateReceiptV2 { descriptor: descriptor(), commitments_hash: Hash::repeat_byte(9) }
+}
+
+fn pov() -> PoV {
+	PoV { block_data: BlockData(vec![1, 2, 3]) }
+}
+
+fn head_data() -> HeadData {
+	HeadData(vec![4, 5])
+}
+
+fn persisted_validation_data() -> PersistedValidationData {
+	PersistedValidationData {
+		parent_head: head_data(),
+		relay_parent_number: 6,
+		relay_parent_storage_root: Hash::repeat_byte(7),
+		max_pov_size: 1024,
+	}
+}
+
+fn samples() -> Vec<Sample> {
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+
+	vec![
+		sample(
+			"v1::ChunkFetchingRequest",
+			v1::ChunkFetchingRequest { candidate_hash, index: ValidatorIndex(2) },
+		),
+		sample(
+			"v1::ChunkFetchingResponse::Chunk",
+			v1::ChunkFetchingResponse::Chunk(v1::ChunkResponse {
+				chunk: vec![1, 2, 3],
+				proof: Proof::dummy_proof(),
+			}),
+		),
+		sample("v1::ChunkFetchingResponse::NoSuchChunk", v1::ChunkFetchingResponse::NoSuchChunk),
+		sample(
+			"v1::CollationFetchingRequest",
+			v1::CollationFetchingRequest {
+				relay_parent: Hash::repeat_byte(1),
+				para_id: ParaId::from(2000),
+			},
+		),
+		sample(
+			"v1::CollationFetchingResponse::Collation",
+			v1::CollationFetchingResponse::Collation(receipt(), pov()),
+		),
+		sample(
+			"v1::CollationFetchingResponse::CollationWithParentHeadData",
+			v1::CollationFetchingResponse::CollationWithParentHeadData {
+				receipt: receipt(),
+				pov: pov(),
+				parent_head_data: head_data(),
+			},
+		),
+		sample(
+			"v1::CollationPrecheckRequest",
+			v1::CollationPrecheckRequest {
+				candidate_receipt: receipt(),
+				parent_head_data: head_data(),
+			},
+		),
+		sample("v1::CollationPrecheckResponse::Accepted", v1::CollationPrecheckResponse::Accepted),
+		sample(
+			"v1::CollationPrecheckResponse::Rejected",
+			v1::CollationPrecheckResponse::Rejected(
+				v1::CollationPrecheckRejection::InvalidPersistedValidationData,
+			),
+		),
+		sample(
+			"v1::CollationPushRequest",
+			v1::CollationPushRequest {
+				candidate_receipt: receipt(),
+				pov: pov(),
+				parent_head_data: head_data(),
+			},
+		),
+		sample("v1::CollationPushResponse::Accepted", v1::CollationPushResponse::Accepted),
+		sample("v1::CollationPushResponse::Rejected", v1::CollationPushResponse::Rejected),
+		sample(
+			"v1::PvfCheckStatementFetchingRequest",
+			v1::PvfCheckStatementFetchingRequest {
+				validation_code_hash: ValidationCodeHash::from(Hash::repeat_byte(10)),
+			},
+		),
+		sample(
+			"v1::PvfCheckStatementFetchingResponse::Statement",
+			v1::PvfCheckStatementFetchingResponse::Statement(
+				PvfCheckStatement {
+					accept: true,
+					subject: ValidationCodeHash::from(Hash::repeat_byte(10)),
+					session_index: 4,
+					validator_index: ValidatorIndex(2),
+				},
+				signature(11),
+			),
+		),
+		sample(
+			"v1::PvfCheckStatementFetchingResponse::NotChecked",
+			v1::PvfCheckStatementFetchingResponse::NotChecked,
+		),
+		sample("v1::PoVFetchingRequest", v1::PoVFetchingRequest { candidate_hash }),
+		sample("v1::PoVFetchingResponse::PoV", v1::PoVFetchingResponse::PoV(pov())),
+		sample("v1::PoVFetchingResponse::NoSuchPoV", v1::PoVFetchingResponse::NoSuchPoV),
+		sample(
+			"v1::AvailableDataFetchingRequest",
+			v1::AvailableDataFetchingRequest { candidate_hash },
+		),
+		sample(
+			"v1::AvailableDataFetchingResponse::AvailableData",
+			v1::AvailableDataFetchingResponse::AvailableData(AvailableData {
+				pov: Arc::new(pov()),
+				validation_data: persisted_validation_data(),
+			}),
+		),
+		sample(
+			"v1::AvailableDataFetchingResponse::NoSuchData",
+			v1::AvailableDataFetchingResponse::NoSuchData,
+		),
+		sample(
+			"v1::DisputeRequest",
+			v1::DisputeRequest(UncheckedDisputeMessage {
+				candidate_receipt: receipt(),
+				session_index: 4,
+				invalid_vote: InvalidDisputeVote {
+					validator_index: ValidatorIndex(1),
+					signature: signature(11),
+					kind: InvalidDisputeStatementKind::Explicit,
+				},
+				valid_vote: ValidDisputeVote {
+					validator_index: ValidatorIndex(2),
+					signature: signature(12),
+					kind: ValidDisputeStatementKind::BackingValid(Hash::repeat_byte(1)),
+				},
+			}),
+		),
+		sample("v1::DisputeResponse::Confirmed", v1::DisputeResponse::Confirmed),
+		sample(
+			"v2::AttestedCandidateRequest",
+			v2::AttestedCandidateRequest {
+				candidate_hash,
+				mask: StatementFilter {
+					seconded_in_group: bitvec![u8, Lsb0; 1, 0, 1],
+					validated_in_group: bitvec![u8, Lsb0; 0, 1, 0],
+				},
+			},
+		),
+		sample(
+			"v2::AttestedCandidateResponse",
+			v2::AttestedCandidateResponse {
+				candidate_receipt: CommittedCandidateReceiptV2 {
+					descriptor: descriptor(),
+					commitments: CandidateCommitments {
+						upward_messages: Default::default(),
+						horizontal_messages: Default::default(),
+						new_validation_code: Some(ValidationCode(vec![1])),
+						head_data: head_data(),
+						processed_downward_messages: 1,
+						hrmp_watermark: 6,
+					},
+				},
+				persisted_validation_data: persisted_validation_data(),
+				statements: vec![UncheckedSignedStatement::new(
+					CompactStatement::Seconded(candidate_hash),
+					ValidatorIndex(2),
+					signature(11),
+				)],
+			},
+		),
+		sample(
+			"v2::CollationFetchingRequest",
+			v2::CollationFetchingRequest {
+				relay_parent: Hash::repeat_byte(1),
+				para_id: ParaId::from(2000),
+				candidate_hash: CandidateHash(Hash::repeat_byte(2)),
+			},
+		),
+		sample(
+			"v2::ChunkFetchingRequest",
+			v2::ChunkFetchingRequest { candidate_hash, index: ValidatorIndex(2) },
+		),
+		sample(
+			"v2::ChunkFetchingResponse::Chunk",
+			v2::ChunkFetchingResponse::Chunk(ErasureChunk {
+				chunk: vec![1, 2, 3],
+				index: ChunkIndex(4),
+				proof: Proof::dummy_proof(),
+			}),
+		),
+		sample("v2::ChunkFetchingResponse::NoSuchChunk", v2::ChunkFetchingResponse::NoSuchChunk),
+	]
+}
+
+fn fixtures() -> BTreeMap<&'static str, Vec<u8>> {
+	FIXTURES
+		.lines()
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| {
+			let (name, encoded) = line.split_once(' ').expect("Fixtures are `<name> <hex>`");
+			(name, hex::decode(encoded).expect("Fixtures are valid hex"))
+		})
+		.collect()
+}
+
+#[test]
+fn encodings_match_fixtures() {
+	let mut fixtures = fixtures();
+
+	for Sample { name, encoded, reencode } in samples() {
+		let fixture = fixtures.remove(name).unwrap_or_else(|| panic!("No fixture for {name}"));
+		assert_eq!(hex::encode(&encoded), hex::encode(&fixture), "The encoding of {name} changed",);
+		assert_eq!(reencode(&fixture).unwrap(), fixture, "{name} doesn't decode its fixture");
+	}
+
+	assert!(fixtures.is_empty(), "Fixtures without samples: {:?}", fixtures.keys());
+}
+
+#[test]
+fn decoding_garbage_does_not_panic() {
+	let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+	let fixtures = fixtures();
+
+	for Sample { name, reencode, .. } in samples() {
+		let fixture = &fixtures[name];
+
+		for _ in 0..1000 {
+			let mut garbage = fixture.clone();
+			match rng.gen_range(0..4) {
+				0 => garbage.truncate(rng.gen_range(0..fixture.len())),
+				1 => {
+					let index = rng.gen_range(0..fixture.len());
+					garbage[index] = rng.gen();
+				},
+				2 => garbage.extend((0..rng.gen_range(1..64)).map(|_| rng.gen::<u8>())),
+				_ => garbage = (0..rng.gen_range(0..512)).map(|_| rng.gen()).collect(),
+			}
+
+			// Payloads decoding despite the garbage must still be encodable.
+			let _ = reencode(&garbage);
+		}
+	}
+}
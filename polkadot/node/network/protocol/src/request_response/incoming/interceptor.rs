@@ -0,0 +1,129 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Middleware for incoming requests.
+//!
+//! Interceptors are registered on an [`IncomingRequestReceiver`](super::IncomingRequestReceiver)
+//! by the node builder, before the receiver is handed to the subsystem. They see every decoded
+//! request before the subsystem does and can be used for concerns shared by all protocols, like
+//! authenticating requesters, logging or mirroring traffic.
+
+use super::IncomingRequest;
+
+/// What should happen to a request after it went through an [`Interceptor`].
+#[derive(Debug)]
+pub enum Interception<Req> {
+	/// Pass the request, possibly modified, on to the next interceptor and eventually the
+	/// subsystem.
+	Continue(IncomingRequest<Req>),
+	/// The interceptor took care of the request, it will not reach the subsystem.
+	///
+	/// The interceptor either answered the request itself or dropped it, in which case the
+	/// requester receives an error.
+	Handled,
+}
+
+/// Inspects, modifies or short-circuits incoming requests.
+///
+/// Interceptors run in the order they were registered, on the task of the subsystem receiving
+/// the requests, so they should not block.
+pub trait Interceptor<Req>: Send + Sync {
+	/// Handle a decoded incoming `request`.
+	fn intercept(&self, request: IncomingRequest<Req>) -> Interception<Req>;
+}
+
+impl<Req, F> Interceptor<Req> for F
+where
+	F: Fn(IncomingRequest<Req>) -> Interception<Req> + Send + Sync,
+{
+	fn intercept(&self, request: IncomingRequest<Req>) -> Interception<Req> {
+		self(request)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		marker::PhantomData,
+		sync::{
+			atomic::{AtomicUsize, Ordering},
+			Arc,
+		},
+	};
+
+	use codec::{Decode, Encode};
+	use futures::{channel::oneshot, executor::block_on};
+	use polkadot_primitives::{CandidateHash, Hash};
+	use sc_network::config as netconfig;
+	use sc_network_types::PeerId;
+
+	use super::{super::IncomingRequestReceiver, *};
+	use crate::request_response::v1::{PoVFetchingRequest, PoVFetchingResponse};
+
+	fn request(
+		candidate_hash: CandidateHash,
+	) -> (netconfig::IncomingRequest, oneshot::Receiver<netconfig::OutgoingResponse>) {
+		let (pending_response, response) = oneshot::channel();
+		let raw = netconfig::IncomingRequest {
+			peer: PeerId::random(),
+			payload: PoVFetchingRequest { candidate_hash }.encode(),
+			pending_response,
+		};
+		(raw, response)
+	}
+
+	#[test]
+	fn interceptors_can_short_circuit_requests() {
+		let (tx, raw) = async_channel::bounded(4);
+		let seen = Arc::new(AtomicUsize::new(0));
+		let blocked = CandidateHash(Hash::repeat_byte(1));
+		let allowed = CandidateHash(Hash::repeat_byte(2));
+
+		let counter = seen.clone();
+		let mut receiver = IncomingRequestReceiver::<PoVFetchingRequest> {
+			raw,
+			interceptors: Vec::new(),
+			phantom: PhantomData,
+		}
+		.with_interceptor(move |request: IncomingRequest<PoVFetchingRequest>| {
+			counter.fetch_add(1, Ordering::Relaxed);
+			Interception::Continue(request)
+		})
+		.with_interceptor(move |request: IncomingRequest<PoVFetchingRequest>| {
+			if request.payload.candidate_hash == blocked {
+				let _ = request.send_response(PoVFetchingResponse::NoSuchPoV);
+				Interception::Handled
+			} else {
+				Interception::Continue(request)
+			}
+		});
+
+		let (blocked_request, blocked_response) = request(blocked);
+		let (allowed_request, _allowed_response) = request(allowed);
+		block_on(tx.send(blocked_request)).unwrap();
+		block_on(tx.send(allowed_request)).unwrap();
+
+		let received = block_on(receiver.recv(Vec::new)).unwrap();
+		assert_eq!(received.payload.candidate_hash, allowed);
+		assert_eq!(seen.load(Ordering::Relaxed), 2);
+
+		let response = block_on(blocked_response).unwrap().result.unwrap();
+		assert!(matches!(
+			PoVFetchingResponse::decode(&mut &response[..]).unwrap(),
+			PoVFetchingResponse::NoSuchPoV
+		));
+	}
+}
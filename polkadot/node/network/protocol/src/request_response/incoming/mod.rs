@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use futures::{channel::oneshot, StreamExt};
 
@@ -30,6 +30,9 @@ use crate::UnifiedReputationChange;
 mod error;
 pub use error::{Error, FatalError, JfyiError, Result};
 
+mod interceptor;
+pub use interceptor::{Interception, Interceptor};
+
 /// A request coming in, including a sender for sending responses.
 ///
 /// Typed `IncomingRequest`s, see `IncomingRequest::get_config_receiver` and substrate
@@ -58,7 +61,7 @@ where
 		req_protocol_names: &ReqProtocolNames,
 	) -> (IncomingRequestReceiver<Req>, N::RequestResponseProtocolConfig) {
 		let (raw, cfg) = Req::PROTOCOL.get_config::<B, N>(req_protocol_names);
-		(IncomingRequestReceiver { raw, phantom: PhantomData {} }, cfg)
+		(IncomingRequestReceiver { raw, interceptors: Vec::new(), phantom: PhantomData {} }, cfg)
 	}
 
 	/// Create new `IncomingRequest`.
@@ -133,6 +136,16 @@ where
 	) -> std::result::Result<(), ()> {
 		self.pending_response.send_outgoing_response(resp)
 	}
+
+	/// Send an already encoded response with additional options.
+	///
+	/// Calls [`OutgoingResponseSender::send_encoded_outgoing_response`].
+	pub fn send_encoded_outgoing_response(
+		self,
+		resp: OutgoingResponse<Vec<u8>>,
+	) -> std::result::Result<(), ()> {
+		self.pending_response.send_encoded_outgoing_response(resp)
+	}
 }
 
 /// Sender for sending back responses on an `IncomingRequest`.
@@ -182,6 +195,26 @@ where
 
 		self.pending_response.send(response).map_err(|_| ())
 	}
+
+	/// Send an already encoded response with additional options.
+	///
+	/// Like [`Self::send_outgoing_response`], but `resp.result` must be the SCALE encoding of a
+	/// `Req::Response`. Useful to send the same large response to several peers without encoding
+	/// it every time.
+	pub fn send_encoded_outgoing_response(
+		self,
+		resp: OutgoingResponse<Vec<u8>>,
+	) -> std::result::Result<(), ()> {
+		let OutgoingResponse { result, reputation_changes, sent_feedback } = resp;
+
+		let response = netconfig::OutgoingResponse {
+			result,
+			reputation_changes: reputation_changes.into_iter().map(|c| c.into()).collect(),
+			sent_feedback,
+		};
+
+		self.pending_response.send(response).map_err(|_| ())
+	}
 }
 
 /// Typed variant of [`netconfig::OutgoingResponse`].
@@ -204,9 +237,11 @@ pub struct OutgoingResponse<Response> {
 
 /// Receiver for incoming requests.
 ///
-/// Takes care of decoding and handling of invalid encoded requests.
+/// Takes care of decoding and handling of invalid encoded requests, and of running the registered
+/// [`Interceptor`]s.
 pub struct IncomingRequestReceiver<Req> {
 	raw: async_channel::Receiver<netconfig::IncomingRequest>,
+	interceptors: Vec<Arc<dyn Interceptor<Req>>>,
 	phantom: PhantomData<Req>,
 }
 
@@ -215,18 +250,49 @@ where
 	Req: IsRequest + Decode + Encode,
 	Req::Response: Encode,
 {
+	/// Register an interceptor, run after all previously registered ones.
+	pub fn with_interceptor(mut self, interceptor: impl Interceptor<Req> + 'static) -> Self {
+		self.add_interceptor(Arc::new(interceptor));
+		self
+	}
+
+	/// Register a shared interceptor, run after all previously registered ones.
+	///
+	/// The same interceptor can be registered on receivers of different protocols.
+	pub fn add_interceptor(&mut self, interceptor: Arc<dyn Interceptor<Req>>) {
+		self.interceptors.push(interceptor);
+	}
+
 	/// Try to receive the next incoming request.
 	///
 	/// Any received request will be decoded, on decoding errors the provided reputation changes
-	/// will be applied and an error will be reported.
+	/// will be applied and an error will be reported. Requests handled by an interceptor are
+	/// skipped.
 	pub async fn recv<F>(&mut self, reputation_changes: F) -> Result<IncomingRequest<Req>>
 	where
 		F: FnOnce() -> Vec<UnifiedReputationChange>,
 	{
-		let req = match self.raw.next().await {
-			None => return Err(FatalError::RequestChannelExhausted.into()),
-			Some(raw) => IncomingRequest::<Req>::try_from_raw(raw, reputation_changes())?,
-		};
-		Ok(req)
+		let reputation_changes = reputation_changes();
+		loop {
+			let req = match self.raw.next().await {
+				None => return Err(FatalError::RequestChannelExhausted.into()),
+				Some(raw) => IncomingRequest::<Req>::try_from_raw(raw, reputation_changes.clone())?,
+			};
+
+			if let Some(req) = self.intercept(req) {
+				return Ok(req)
+			}
+		}
+	}
+
+	/// Run `req` through the interceptors, returns `None` if one of them handled it.
+	fn intercept(&self, mut req: IncomingRequest<Req>) -> Option<IncomingRequest<Req>> {
+		for interceptor in &self.interceptors {
+			match interceptor.intercept(req) {
+				Interception::Continue(next) => req = next,
+				Interception::Handled => return None,
+			}
+		}
+		Some(req)
 	}
 }
@@ -76,6 +76,10 @@ pub mod v1;
 /// Actual versioned requests and responses that are sent over the wire.
 pub mod v2;
 
+/// Golden vectors of the wire format of the requests and responses.
+#[cfg(test)]
+mod conformance;
+
 /// A protocol per subsystem seems to make the most sense, this way we don't need any dispatching
 /// within protocols.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EnumIter)]
@@ -100,6 +104,17 @@ pub enum Protocol {
 	/// Protocol for chunk fetching version 2, used by availability distribution and availability
 	/// recovery.
 	ChunkFetchingV2,
+
+	/// Protocol for collators to have a candidate receipt checked by a backing validator, before
+	/// advertising the collation.
+	CollationPrecheckV1,
+
+	/// Protocol for trusted collators to push collations to backing validators, instead of
+	/// advertising them.
+	CollationPushV1,
+
+	/// Protocol for validators to fetch the PVF pre-checking statements of other validators.
+	PvfCheckStatementFetchingV1,
 }
 
 /// Minimum bandwidth we expect for validators - 500Mbit/s is the recommendation, so approximately
@@ -146,6 +161,75 @@ const ATTESTED_CANDIDATE_TIMEOUT: Duration = Duration::from_millis(2500);
 /// to have 5 slow nodes connected, to delay transfer for others by `ATTESTED_CANDIDATE_TIMEOUT`.
 pub const MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS: u32 = 5;
 
+/// The network conditions the request-response protocols are tuned for.
+///
+/// The constants of this module assume a validator with the recommended bandwidth, which is the
+/// [`NetworkProfile::Datacenter`] profile. The other profiles assume less bandwidth and scale the
+/// protocols accordingly: requests are given more time to complete, while fewer of them are queued
+/// and served in parallel, so that the ones accepted can still be served before they time out.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NetworkProfile {
+	/// About 500Mbit/s, the recommendation for validators.
+	#[default]
+	Datacenter,
+	/// About 100Mbit/s, e.g. a residential fiber connection.
+	Home,
+	/// About 40Mbit/s, e.g. a shared or metered connection.
+	Constrained,
+}
+
+impl NetworkProfile {
+	/// Minimum bandwidth in bytes per second we expect for nodes with this profile.
+	pub const fn min_bandwidth_bytes(self) -> u64 {
+		match self {
+			NetworkProfile::Datacenter => MIN_BANDWIDTH_BYTES,
+			NetworkProfile::Home => 10 * 1024 * 1024,
+			NetworkProfile::Constrained => 4 * 1024 * 1024,
+		}
+	}
+
+	/// Scale a request timeout of the datacenter profile to this profile.
+	pub const fn scale_timeout(self, timeout: Duration) -> Duration {
+		match self {
+			NetworkProfile::Datacenter => timeout,
+			NetworkProfile::Home => timeout.saturating_mul(2),
+			NetworkProfile::Constrained => timeout.saturating_mul(4),
+		}
+	}
+
+	/// Scale the size of an incoming request channel of the datacenter profile to this profile.
+	///
+	/// Channels are never scaled below a size of one.
+	pub fn scale_channel_size(self, size: usize) -> usize {
+		let divisor = match self {
+			NetworkProfile::Datacenter => 1,
+			NetworkProfile::Home => 2,
+			NetworkProfile::Constrained => 4,
+		};
+		(size / divisor).max(1)
+	}
+
+	/// How many `AttestedCandidateV2` requests are served in parallel.
+	///
+	/// See [`MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS`].
+	pub const fn max_parallel_attested_candidate_requests(self) -> u32 {
+		match self {
+			NetworkProfile::Datacenter => MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS,
+			NetworkProfile::Home => 3,
+			NetworkProfile::Constrained => 2,
+		}
+	}
+
+	/// Name of the profile, as reported in telemetry.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			NetworkProfile::Datacenter => "datacenter",
+			NetworkProfile::Home => "home",
+			NetworkProfile::Constrained => "constrained",
+		}
+	}
+}
+
 /// Response size limit for responses of POV like data.
 ///
 /// Same as what we use in substrate networking.
@@ -161,6 +245,17 @@ const ATTESTED_CANDIDATE_RESPONSE_SIZE: u64 = MAX_CODE_SIZE as u64 + 100_000;
 /// timeout as we want to get statements through to each node in any case.
 pub const DISPUTE_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
 
+/// Maximum request size for `CollationPrecheckV1`.
+///
+/// The request is dominated by the parent head data, this leaves plenty of room for the head data
+/// size limits of deployed parachains.
+const COLLATION_PRECHECK_REQUEST_SIZE: u64 = 64 * 1024;
+
+/// Maximum request size for `CollationPushV1`.
+///
+/// The request carries the PoV in addition to what is sent for a precheck.
+const COLLATION_PUSH_REQUEST_SIZE: u64 = POV_RESPONSE_SIZE + COLLATION_PRECHECK_REQUEST_SIZE;
+
 impl Protocol {
 	/// Get a configuration for a given Request response protocol.
 	///
@@ -181,7 +276,8 @@ impl Protocol {
 		self,
 		req_protocol_names: &ReqProtocolNames,
 	) -> (async_channel::Receiver<network::IncomingRequest>, N::RequestResponseProtocolConfig) {
-		let (tx, rx) = async_channel::bounded(self.get_channel_size());
+		let (tx, rx) =
+			async_channel::bounded(self.get_channel_size(req_protocol_names.network_profile()));
 		let cfg = self.create_config::<B, N>(req_protocol_names, Some(tx));
 		(rx, cfg)
 	}
@@ -192,6 +288,7 @@ impl Protocol {
 		tx: Option<async_channel::Sender<network::IncomingRequest>>,
 	) -> N::RequestResponseProtocolConfig {
 		let name = req_protocol_names.get_name(self);
+		let profile = req_protocol_names.network_profile();
 		let legacy_names = self.get_legacy_name().into_iter().map(Into::into).collect();
 		match self {
 			Protocol::ChunkFetchingV1 | Protocol::ChunkFetchingV2 => N::request_response_config(
@@ -200,7 +297,7 @@ impl Protocol {
 				1_000,
 				POV_RESPONSE_SIZE,
 				// We are connected to all validators:
-				CHUNK_REQUEST_TIMEOUT,
+				profile.scale_timeout(CHUNK_REQUEST_TIMEOUT),
 				tx,
 			),
 			Protocol::CollationFetchingV1 | Protocol::CollationFetchingV2 =>
@@ -210,7 +307,7 @@ impl Protocol {
 					1_000,
 					POV_RESPONSE_SIZE,
 					// Taken from initial implementation in collator protocol:
-					POV_REQUEST_TIMEOUT_CONNECTED,
+					profile.scale_timeout(POV_REQUEST_TIMEOUT_CONNECTED),
 					tx,
 				),
 			Protocol::PoVFetchingV1 => N::request_response_config(
@@ -218,7 +315,7 @@ impl Protocol {
 				legacy_names,
 				1_000,
 				POV_RESPONSE_SIZE,
-				POV_REQUEST_TIMEOUT_CONNECTED,
+				profile.scale_timeout(POV_REQUEST_TIMEOUT_CONNECTED),
 				tx,
 			),
 			Protocol::AvailableDataFetchingV1 => N::request_response_config(
@@ -227,7 +324,7 @@ impl Protocol {
 				1_000,
 				// Available data size is dominated by the PoV size.
 				POV_RESPONSE_SIZE,
-				POV_REQUEST_TIMEOUT_CONNECTED,
+				profile.scale_timeout(POV_REQUEST_TIMEOUT_CONNECTED),
 				tx,
 			),
 			Protocol::DisputeSendingV1 => N::request_response_config(
@@ -237,7 +334,7 @@ impl Protocol {
 				// Responses are just confirmation, in essence not even a bit. So 100 seems
 				// plenty.
 				100,
-				DISPUTE_REQUEST_TIMEOUT,
+				profile.scale_timeout(DISPUTE_REQUEST_TIMEOUT),
 				tx,
 			),
 			Protocol::AttestedCandidateV2 => N::request_response_config(
@@ -245,15 +342,44 @@ impl Protocol {
 				legacy_names,
 				1_000,
 				ATTESTED_CANDIDATE_RESPONSE_SIZE,
-				ATTESTED_CANDIDATE_TIMEOUT,
+				profile.scale_timeout(ATTESTED_CANDIDATE_TIMEOUT),
+				tx,
+			),
+			Protocol::CollationPrecheckV1 => N::request_response_config(
+				name,
+				legacy_names,
+				COLLATION_PRECHECK_REQUEST_SIZE,
+				// The response is a verdict, 100 is plenty.
+				100,
+				// Collators are connected to the validators of their backing group:
+				profile.scale_timeout(DEFAULT_REQUEST_TIMEOUT_CONNECTED),
+				tx,
+			),
+			Protocol::CollationPushV1 => N::request_response_config(
+				name,
+				legacy_names,
+				COLLATION_PUSH_REQUEST_SIZE,
+				// The response is a verdict, 100 is plenty.
+				100,
+				// Same as fetching, just in the other direction.
+				profile.scale_timeout(POV_REQUEST_TIMEOUT_CONNECTED),
+				tx,
+			),
+			Protocol::PvfCheckStatementFetchingV1 => N::request_response_config(
+				name,
+				legacy_names,
+				100,
+				// A statement and its signature, 1_000 is plenty.
+				1_000,
+				profile.scale_timeout(DEFAULT_REQUEST_TIMEOUT),
 				tx,
 			),
 		}
 	}
 
 	// Channel sizes for the supported protocols.
-	fn get_channel_size(self) -> usize {
-		match self {
+	fn get_channel_size(self, profile: NetworkProfile) -> usize {
+		let size = match self {
 			// Hundreds of validators will start requesting their chunks once they see a candidate
 			// awaiting availability on chain. Given that they will see that block at different
 			// times (due to network delays), 100 seems big enough to accommodate for "bursts",
@@ -271,26 +397,38 @@ impl Protocol {
 			// average, so something in the ballpark of 100 should be fine. Nodes will retry on
 			// failure, so having a good value here is mostly about performance tuning.
 			Protocol::DisputeSendingV1 => 100,
+			// Same as for collation fetching, but in the opposite direction.
+			Protocol::CollationPrecheckV1 => 10,
+			// Requests carry a PoV each, as for collation fetching.
+			Protocol::CollationPushV1 => 10,
+			// Only a subset of the validators asks for statements, in a burst after a PVF needing
+			// pre-checking shows up in a block. Requests are cheap to answer.
+			Protocol::PvfCheckStatementFetchingV1 => 100,
+
+			Protocol::AttestedCandidateV2 => return Self::attested_candidate_channel_size(profile),
+		};
+		profile.scale_channel_size(size)
+	}
 
-			Protocol::AttestedCandidateV2 => {
-				// We assume we can utilize up to 70% of the available bandwidth for statements.
-				// This is just a guess/estimate, with the following considerations: If we are
-				// faster than that, queue size will stay low anyway, even if not - requesters will
-				// get an immediate error, but if we are slower, requesters will run in a timeout -
-				// wasting precious time.
-				let available_bandwidth = 7 * MIN_BANDWIDTH_BYTES / 10;
-				let size = u64::saturating_sub(
-					ATTESTED_CANDIDATE_TIMEOUT.as_millis() as u64 * available_bandwidth /
-						(1000 * MAX_CODE_SIZE as u64),
-					MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS as u64,
-				);
-				debug_assert!(
-					size > 0,
-					"We should have a channel size greater zero, otherwise we won't accept any requests."
-				);
-				size as usize
-			},
-		}
+	// The channel size for `AttestedCandidateV2`, derived from the bandwidth of the profile.
+	fn attested_candidate_channel_size(profile: NetworkProfile) -> usize {
+		// We assume we can utilize up to 70% of the available bandwidth for statements.
+		// This is just a guess/estimate, with the following considerations: If we are
+		// faster than that, queue size will stay low anyway, even if not - requesters will
+		// get an immediate error, but if we are slower, requesters will run in a timeout -
+		// wasting precious time.
+		let available_bandwidth = 7 * profile.min_bandwidth_bytes() / 10;
+		let size = u64::saturating_sub(
+			profile.scale_timeout(ATTESTED_CANDIDATE_TIMEOUT).as_millis() as u64 *
+				available_bandwidth /
+				(1000 * MAX_CODE_SIZE as u64),
+			profile.max_parallel_attested_candidate_requests() as u64,
+		);
+		debug_assert!(
+			size > 0,
+			"We should have a channel size greater zero, otherwise we won't accept any requests."
+		);
+		size as usize
 	}
 
 	/// Legacy protocol name associated with each peer set, if any.
@@ -308,6 +446,9 @@ impl Protocol {
 			Protocol::AttestedCandidateV2 => None,
 			Protocol::CollationFetchingV2 => None,
 			Protocol::ChunkFetchingV2 => None,
+			Protocol::CollationPrecheckV1 => None,
+			Protocol::CollationPushV1 => None,
+			Protocol::PvfCheckStatementFetchingV1 => None,
 		}
 	}
 }
@@ -322,9 +463,12 @@ pub trait IsRequest {
 }
 
 /// Type for getting on the wire [`Protocol`] names using genesis hash & fork id.
+///
+/// It also carries the [`NetworkProfile`] the protocols are configured for.
 #[derive(Clone)]
 pub struct ReqProtocolNames {
 	names: HashMap<Protocol, ProtocolName>,
+	network_profile: NetworkProfile,
 }
 
 impl ReqProtocolNames {
@@ -334,7 +478,18 @@ impl ReqProtocolNames {
 		for protocol in Protocol::iter() {
 			names.insert(protocol, Self::generate_name(protocol, &genesis_hash, fork_id));
 		}
-		Self { names }
+		Self { names, network_profile: NetworkProfile::default() }
+	}
+
+	/// Configure the protocols for `network_profile` instead of the default profile.
+	pub fn with_network_profile(mut self, network_profile: NetworkProfile) -> Self {
+		self.network_profile = network_profile;
+		self
+	}
+
+	/// The [`NetworkProfile`] the protocols are configured for.
+	pub fn network_profile(&self) -> NetworkProfile {
+		self.network_profile
 	}
 
 	/// Get on the wire [`Protocol`] name.
@@ -364,6 +519,9 @@ impl ReqProtocolNames {
 			Protocol::PoVFetchingV1 => "/req_pov/1",
 			Protocol::AvailableDataFetchingV1 => "/req_available_data/1",
 			Protocol::DisputeSendingV1 => "/send_dispute/1",
+			Protocol::CollationPrecheckV1 => "/req_collation_precheck/1",
+			Protocol::CollationPushV1 => "/req_collation_push/1",
+			Protocol::PvfCheckStatementFetchingV1 => "/req_pvf_check_statement/1",
 
 			// V2:
 			Protocol::CollationFetchingV2 => "/req_collation/2",
@@ -374,3 +532,47 @@ impl ReqProtocolNames {
 		format!("{}{}", prefix, short_name).into()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{NetworkProfile, Protocol, ATTESTED_CANDIDATE_TIMEOUT, CHUNK_REQUEST_TIMEOUT};
+	use strum::IntoEnumIterator;
+
+	const PROFILES: [NetworkProfile; 3] =
+		[NetworkProfile::Datacenter, NetworkProfile::Home, NetworkProfile::Constrained];
+
+	#[test]
+	fn every_profile_accepts_requests() {
+		for profile in PROFILES {
+			for protocol in Protocol::iter() {
+				assert!(protocol.get_channel_size(profile) > 0, "{:?} {:?}", profile, protocol);
+			}
+		}
+	}
+
+	#[test]
+	fn datacenter_profile_keeps_the_defaults() {
+		let profile = NetworkProfile::Datacenter;
+		assert_eq!(profile.scale_timeout(CHUNK_REQUEST_TIMEOUT), CHUNK_REQUEST_TIMEOUT);
+		assert_eq!(profile.scale_channel_size(100), 100);
+		assert_eq!(Protocol::AttestedCandidateV2.get_channel_size(profile), 24);
+	}
+
+	#[test]
+	fn slower_profiles_wait_longer_and_queue_less() {
+		for pair in PROFILES.windows(2) {
+			let (faster, slower) = (pair[0], pair[1]);
+			assert!(
+				slower.scale_timeout(ATTESTED_CANDIDATE_TIMEOUT) >
+					faster.scale_timeout(ATTESTED_CANDIDATE_TIMEOUT)
+			);
+			assert!(
+				slower.max_parallel_attested_candidate_requests() <
+					faster.max_parallel_attested_candidate_requests()
+			);
+			for protocol in Protocol::iter() {
+				assert!(protocol.get_channel_size(slower) < protocol.get_channel_size(faster));
+			}
+		}
+	}
+}
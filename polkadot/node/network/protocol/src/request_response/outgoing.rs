@@ -45,6 +45,12 @@ pub enum Requests {
 	/// Fetch a collation from a collator which previously announced it.
 	/// Compared to V1 it requires specifying which candidate is requested by its hash.
 	CollationFetchingV2(OutgoingRequest<v2::CollationFetchingRequest>),
+	/// Have a collation checked by a backing validator before advertising it.
+	CollationPrecheckV1(OutgoingRequest<v1::CollationPrecheckRequest>),
+	/// Push a collation to a backing validator.
+	CollationPushV1(OutgoingRequest<v1::CollationPushRequest>),
+	/// Fetch the PVF pre-checking statement of a validator.
+	PvfCheckStatementFetchingV1(OutgoingRequest<v1::PvfCheckStatementFetchingRequest>),
 }
 
 impl Requests {
@@ -64,6 +70,9 @@ impl Requests {
 			Self::AvailableDataFetchingV1(r) => r.encode_request(),
 			Self::DisputeSendingV1(r) => r.encode_request(),
 			Self::AttestedCandidateV2(r) => r.encode_request(),
+			Self::CollationPrecheckV1(r) => r.encode_request(),
+			Self::CollationPushV1(r) => r.encode_request(),
+			Self::PvfCheckStatementFetchingV1(r) => r.encode_request(),
 		}
 	}
 }
@@ -23,7 +23,7 @@ use polkadot_node_primitives::{
 };
 use polkadot_primitives::{
 	vstaging::CandidateReceiptV2 as CandidateReceipt, CandidateHash, Hash, HeadData, Id as ParaId,
-	ValidatorIndex,
+	PvfCheckStatement, ValidationCodeHash, ValidatorIndex, ValidatorSignature,
 };
 
 use super::{IsRequest, Protocol};
@@ -133,6 +133,106 @@ impl IsRequest for CollationFetchingRequest {
 	const PROTOCOL: Protocol = Protocol::CollationFetchingV1;
 }
 
+/// Request a backing validator to check a collation, before it is advertised.
+///
+/// Only what can be checked without the PoV is checked, the candidate is not executed.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct CollationPrecheckRequest {
+	/// The receipt of the candidate.
+	pub candidate_receipt: CandidateReceipt,
+	/// The head data of the candidate's parent.
+	pub parent_head_data: HeadData,
+}
+
+/// Responses to `CollationPrecheckRequest`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum CollationPrecheckResponse {
+	/// Nothing is wrong with the collation, as far as can be told without its PoV.
+	#[codec(index = 0)]
+	Accepted,
+	/// The collation would be rejected by the validator.
+	#[codec(index = 1)]
+	Rejected(CollationPrecheckRejection),
+}
+
+/// Why a collation was rejected by `CollationPrecheckRequest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum CollationPrecheckRejection {
+	/// The relay parent is not in the view of the validator.
+	#[codec(index = 0)]
+	UnknownRelayParent,
+	/// The para is not assigned to the core of the validator at the relay parent.
+	#[codec(index = 1)]
+	ParaNotAssigned,
+	/// The version, core index or session index of the descriptor is invalid.
+	#[codec(index = 2)]
+	InvalidDescriptor,
+	/// The parent head is not known to the validator.
+	#[codec(index = 3)]
+	UnknownParent,
+	/// The persisted validation data hash of the descriptor doesn't match the one of the
+	/// validator.
+	#[codec(index = 4)]
+	InvalidPersistedValidationData,
+}
+
+impl IsRequest for CollationPrecheckRequest {
+	type Response = CollationPrecheckResponse;
+	const PROTOCOL: Protocol = Protocol::CollationPrecheckV1;
+}
+
+/// Push a collation to a backing validator, instead of advertising it.
+///
+/// Only accepted if the collation push node feature is enabled.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct CollationPushRequest {
+	/// The receipt of the candidate.
+	pub candidate_receipt: CandidateReceipt,
+	/// Proof to verify the state transition of the parachain.
+	pub pov: PoV,
+	/// The head data of the candidate's parent.
+	pub parent_head_data: HeadData,
+}
+
+/// Responses to `CollationPushRequest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum CollationPushResponse {
+	/// The validator started seconding the collation.
+	#[codec(index = 0)]
+	Accepted,
+	/// The validator did not take the collation, it should be advertised instead.
+	#[codec(index = 1)]
+	Rejected,
+}
+
+impl IsRequest for CollationPushRequest {
+	type Response = CollationPushResponse;
+	const PROTOCOL: Protocol = Protocol::CollationPushV1;
+}
+
+/// Request the statement a validator made about a PVF pending pre-checking.
+#[derive(Debug, Copy, Clone, Encode, Decode)]
+pub struct PvfCheckStatementFetchingRequest {
+	/// Hash of the validation code.
+	pub validation_code_hash: ValidationCodeHash,
+}
+
+/// Responses to `PvfCheckStatementFetchingRequest`.
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum PvfCheckStatementFetchingResponse {
+	/// The statement of the validator, as submitted to the chain.
+	#[codec(index = 0)]
+	Statement(PvfCheckStatement, ValidatorSignature),
+	/// The validator did not pre-check the PVF itself.
+	#[codec(index = 1)]
+	NotChecked,
+}
+
+impl IsRequest for PvfCheckStatementFetchingRequest {
+	type Response = PvfCheckStatementFetchingResponse;
+	const PROTOCOL: Protocol = Protocol::PvfCheckStatementFetchingV1;
+}
+
 /// Request the advertised collation at that relay-parent.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct PoVFetchingRequest {
@@ -81,7 +81,7 @@ pub enum Error {
 	FetchMinimumBackingVotes(RuntimeApiError),
 
 	#[error("Fetching node features failed {0:?}")]
-	FetchNodeFeatures(RuntimeApiError),
+	FetchNodeFeatures(runtime::Error),
 
 	#[error("Attempted to share statement when not a validator or not assigned")]
 	InvalidShare,
@@ -25,7 +25,7 @@ use error::FatalResult;
 use std::time::Duration;
 
 use polkadot_node_network_protocol::request_response::{
-	v2::AttestedCandidateRequest, IncomingRequestReceiver,
+	v2::AttestedCandidateRequest, IncomingRequestReceiver, MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS,
 };
 use polkadot_node_subsystem::{
 	messages::StatementDistributionMessage, overseer, ActiveLeavesUpdate, FromOrchestra,
@@ -55,6 +55,8 @@ pub struct StatementDistributionSubsystem {
 	keystore: KeystorePtr,
 	/// Receiver for incoming candidate requests.
 	req_receiver: Option<IncomingRequestReceiver<AttestedCandidateRequest>>,
+	/// Maximum number of candidate requests served in parallel.
+	max_parallel_requests: u32,
 	/// Prometheus metrics
 	metrics: Metrics,
 	/// Aggregated reputation change
@@ -121,7 +123,20 @@ impl StatementDistributionSubsystem {
 		req_receiver: IncomingRequestReceiver<AttestedCandidateRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { keystore, req_receiver: Some(req_receiver), metrics, reputation: Default::default() }
+		Self {
+			keystore,
+			req_receiver: Some(req_receiver),
+			max_parallel_requests: MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS,
+			metrics,
+			reputation: Default::default(),
+		}
+	}
+
+	/// Serve at most `max_parallel_requests` candidate requests in parallel, instead of
+	/// [`MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS`].
+	pub fn with_max_parallel_requests(mut self, max_parallel_requests: u32) -> Self {
+		self.max_parallel_requests = max_parallel_requests;
+		self
 	}
 
 	async fn run<Context>(self, ctx: Context) -> std::result::Result<(), FatalError> {
@@ -146,6 +161,7 @@ impl StatementDistributionSubsystem {
 			v2::respond_task(
 				self.req_receiver.take().expect("Mandatory argument to new. qed"),
 				res_sender.clone(),
+				self.max_parallel_requests,
 				self.metrics.clone(),
 			)
 			.boxed(),
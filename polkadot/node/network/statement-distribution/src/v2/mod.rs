@@ -26,7 +26,6 @@ use polkadot_node_network_protocol::{
 		incoming::OutgoingResponse,
 		v2::{AttestedCandidateRequest, AttestedCandidateResponse},
 		IncomingRequest, IncomingRequestReceiver, Requests,
-		MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS,
 	},
 	v3::{self as protocol_v3, StatementFilter},
 	IfDisconnected, PeerId, UnifiedReputationChange as Rep, ValidationProtocols, View,
@@ -43,8 +42,10 @@ use polkadot_node_subsystem::{
 	overseer, ActivatedLeaf,
 };
 use polkadot_node_subsystem_util::{
-	backing_implicit_view::View as ImplicitView, reputation::ReputationAggregator,
-	request_min_backing_votes, request_node_features, runtime::ClaimQueueSnapshot,
+	backing_implicit_view::View as ImplicitView,
+	reputation::ReputationAggregator,
+	request_min_backing_votes,
+	runtime::{ClaimQueueSnapshot, RuntimeInfo},
 };
 use polkadot_primitives::{
 	node_features::FeatureIndex,
@@ -313,6 +314,8 @@ pub(crate) struct State {
 	authorities: HashMap<AuthorityDiscoveryId, PeerId>,
 	request_manager: RequestManager,
 	response_manager: ResponseManager,
+	/// Caches the node features of the sessions.
+	runtime_info: RuntimeInfo,
 }
 
 impl State {
@@ -329,6 +332,7 @@ impl State {
 			request_manager: RequestManager::new(),
 			response_manager: ResponseManager::new(),
 			unused_topologies: HashMap::new(),
+			runtime_info: RuntimeInfo::new(None),
 		}
 	}
 
@@ -617,12 +621,11 @@ pub(crate) async fn handle_active_leaves_update<Context>(
 					.await
 					.map_err(JfyiError::RuntimeApiUnavailable)?
 					.map_err(JfyiError::FetchMinimumBackingVotes)?;
-			let node_features =
-				request_node_features(new_relay_parent, session_index, ctx.sender())
-					.await
-					.await
-					.map_err(JfyiError::RuntimeApiUnavailable)?
-					.map_err(JfyiError::FetchNodeFeatures)?;
+			let node_features = state
+				.runtime_info
+				.get_node_features(ctx.sender(), new_relay_parent, session_index)
+				.await
+				.map_err(JfyiError::FetchNodeFeatures)?;
 			let mut per_session_state = PerSessionState::new(
 				session_info,
 				&state.keystore,
@@ -911,7 +914,7 @@ fn pending_statement_network_message(
 	compact: CompactStatement,
 ) -> Option<(Vec<PeerId>, net_protocol::VersionedValidationProtocol)> {
 	match peer.1 {
-		ValidationVersion::V3 => statement_store
+		ValidationVersion::V3 | ValidationVersion::V4 => statement_store
 			.validator_statement(originator, compact)
 			.map(|s| s.as_unchecked().clone())
 			.map(|signed| {
@@ -1036,7 +1039,7 @@ async fn send_pending_grid_messages<Context>(
 					local_knowledge.clone(),
 				);
 				match peer_id.1 {
-					ValidationVersion::V3 => messages.push((
+					ValidationVersion::V3 | ValidationVersion::V4 => messages.push((
 						vec![peer_id.0],
 						ValidationProtocols::V3(
 							protocol_v3::StatementDistributionMessage::BackedCandidateManifest(
@@ -2402,13 +2405,14 @@ fn post_acknowledgement_statement_messages(
 			false,
 		);
 		match peer.1.into() {
-			ValidationVersion::V3 => messages.push(ValidationProtocols::V3(
-				protocol_v3::StatementDistributionMessage::Statement(
-					relay_parent,
-					statement.as_unchecked().clone(),
-				)
-				.into(),
-			)),
+			ValidationVersion::V3 | ValidationVersion::V4 =>
+				messages.push(ValidationProtocols::V3(
+					protocol_v3::StatementDistributionMessage::Statement(
+						relay_parent,
+						statement.as_unchecked().clone(),
+					)
+					.into(),
+				)),
 		};
 	}
 
@@ -2540,7 +2544,7 @@ fn acknowledgement_and_statement_messages(
 	};
 
 	let mut messages = match peer.1 {
-		ValidationVersion::V3 => vec![(
+		ValidationVersion::V3 | ValidationVersion::V4 => vec![(
 			vec![peer.0],
 			ValidationProtocols::V3(
 				protocol_v3::StatementDistributionMessage::BackedCandidateKnown(acknowledgement),
@@ -3288,6 +3292,7 @@ pub(crate) struct ResponderMessage {
 pub(crate) async fn respond_task(
 	mut receiver: IncomingRequestReceiver<AttestedCandidateRequest>,
 	mut sender: mpsc::Sender<ResponderMessage>,
+	max_parallel_requests: u32,
 	metrics: Metrics,
 ) {
 	let mut pending_out = FuturesUnordered::new();
@@ -3317,7 +3322,7 @@ pub(crate) async fn respond_task(
 				}
 
 				// If we are over parallel limit wait for one to finish
-				if pending_out.len() >= MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS as usize {
+				if pending_out.len() >= max_parallel_requests as usize {
 					gum::trace!(target: LOG_TARGET, "Over max parallel requests, waiting for one to finish");
 					metrics.on_max_parallel_requests_reached();
 					let (_, peer) = pending_out.select_next_some().await;
@@ -1246,6 +1246,7 @@ fn delayed_reputation_changes() {
 		let subsystem = crate::StatementDistributionSubsystem {
 			keystore,
 			req_receiver: Some(candidate_req_receiver),
+			max_parallel_requests: MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS,
 			metrics: Default::default(),
 			reputation: ReputationAggregator::new(|_| false),
 		};
@@ -403,6 +403,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 		let subsystem = crate::StatementDistributionSubsystem {
 			keystore,
 			req_receiver: Some(candidate_req_receiver),
+			max_parallel_requests: MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS,
 			metrics: Default::default(),
 			reputation: ReputationAggregator::new(|_| true),
 		};
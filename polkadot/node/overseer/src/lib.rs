@@ -94,7 +94,11 @@ pub use polkadot_node_subsystem_types::{
 };
 
 pub mod metrics;
-pub use self::metrics::Metrics as OverseerMetrics;
+pub use self::metrics::{MessageProcessingMetrics, Metrics as OverseerMetrics};
+
+/// A subsystem wrapper timing the processing of its messages.
+pub mod timing;
+pub use self::timing::TimedSubsystem;
 
 /// A dummy subsystem, mostly useful for placeholders and tests.
 pub mod dummy;
@@ -477,6 +481,7 @@ pub struct Overseer<SupportsParachains> {
 	#[subsystem(sends: [
 		CandidateValidationMessage,
 		RuntimeApiMessage,
+		NetworkBridgeTxMessage,
 	])]
 	pvf_checker: PvfChecker,
 
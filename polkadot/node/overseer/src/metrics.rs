@@ -30,10 +30,12 @@ struct MetricsInner {
 	to_subsystem_bounded_sent: prometheus::GaugeVec<prometheus::U64>,
 	to_subsystem_bounded_received: prometheus::GaugeVec<prometheus::U64>,
 	to_subsystem_bounded_blocked: prometheus::GaugeVec<prometheus::U64>,
+	to_subsystem_bounded_queue_depth: prometheus::GaugeVec<prometheus::U64>,
 
 	to_subsystem_unbounded_tof: prometheus::HistogramVec,
 	to_subsystem_unbounded_sent: prometheus::GaugeVec<prometheus::U64>,
 	to_subsystem_unbounded_received: prometheus::GaugeVec<prometheus::U64>,
+	to_subsystem_unbounded_queue_depth: prometheus::GaugeVec<prometheus::U64>,
 
 	signals_sent: prometheus::GaugeVec<prometheus::U64>,
 	signals_received: prometheus::GaugeVec<prometheus::U64>,
@@ -101,6 +103,11 @@ impl Metrics {
 						.with_label_values(&[name])
 						.set(readouts.bounded.blocked as u64);
 
+					metrics
+						.to_subsystem_bounded_queue_depth
+						.with_label_values(&[name])
+						.set(readouts.bounded.sent.saturating_sub(readouts.bounded.received) as u64);
+
 					metrics
 						.to_subsystem_unbounded_sent
 						.with_label_values(&[name])
@@ -111,6 +118,12 @@ impl Metrics {
 						.with_label_values(&[name])
 						.set(readouts.unbounded.received as u64);
 
+					metrics
+						.to_subsystem_unbounded_queue_depth
+						.with_label_values(&[name])
+						.set(readouts.unbounded.sent.saturating_sub(readouts.unbounded.received)
+							as u64);
+
 					metrics
 						.signals_sent
 						.with_label_values(&[name])
@@ -204,6 +217,16 @@ impl MetricsTrait for Metrics {
 				)?,
 				registry,
 			)?,
+			to_subsystem_bounded_queue_depth: prometheus::register(
+				prometheus::GaugeVec::<prometheus::U64>::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_subsystem_bounded_queue_depth",
+						"Number of messages waiting in subsystems' bounded queues",
+					),
+					&["subsystem_name"],
+				)?,
+				registry,
+			)?,
 			to_subsystem_unbounded_tof: prometheus::register(
 				prometheus::HistogramVec::new(
 					prometheus::HistogramOpts::new(
@@ -238,6 +261,16 @@ impl MetricsTrait for Metrics {
 				)?,
 				registry,
 			)?,
+			to_subsystem_unbounded_queue_depth: prometheus::register(
+				prometheus::GaugeVec::<prometheus::U64>::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_subsystem_unbounded_queue_depth",
+						"Number of messages waiting in subsystems' unbounded queues",
+					),
+					&["subsystem_name"],
+				)?,
+				registry,
+			)?,
 			signals_sent: prometheus::register(
 				prometheus::GaugeVec::<prometheus::U64>::new(
 					prometheus::Opts::new(
@@ -284,3 +317,47 @@ impl fmt::Debug for Metrics {
 		f.write_str("Metrics {{...}}")
 	}
 }
+
+/// Prometheus metrics of the time subsystems take to process their messages, see
+/// [`TimedSubsystem`](crate::TimedSubsystem).
+#[derive(Default, Clone)]
+pub struct MessageProcessingMetrics(Option<prometheus::HistogramVec>);
+
+impl MessageProcessingMetrics {
+	/// Provide a timer for processing a message, which observes on drop.
+	pub(crate) fn time_processing(
+		&self,
+		message_type: &'static str,
+		variant: &'static str,
+	) -> Option<prometheus::prometheus::HistogramTimer> {
+		self.0
+			.as_ref()
+			.map(|metrics| metrics.with_label_values(&[message_type, variant]).start_timer())
+	}
+}
+
+impl MetricsTrait for MessageProcessingMetrics {
+	fn try_register(registry: &prometheus::Registry) -> Result<Self, prometheus::PrometheusError> {
+		let processing_duration = prometheus::register(
+			prometheus::HistogramVec::new(
+				prometheus::HistogramOpts::new(
+					"polkadot_parachain_subsystem_message_processing_duration",
+					"Time a subsystem took to process a message, by message type and variant",
+				)
+				.buckets(vec![
+					0.0001, 0.0004, 0.0016, 0.0064, 0.0256, 0.1024, 0.4096, 1.6384, 3.2768, 4.9152,
+					6.5536,
+				]),
+				&["message_type", "variant"],
+			)?,
+			registry,
+		)?;
+		Ok(Self(Some(processing_duration)))
+	}
+}
+
+impl fmt::Debug for MessageProcessingMetrics {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("MessageProcessingMetrics {{...}}")
+	}
+}
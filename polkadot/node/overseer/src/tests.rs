@@ -25,7 +25,7 @@ use polkadot_node_primitives::{
 };
 use polkadot_node_subsystem_test_helpers::mock::{dummy_unpin_handle, new_leaf};
 use polkadot_node_subsystem_types::messages::{
-	NetworkBridgeEvent, PvfExecKind, ReportPeerMessage, RuntimeApiRequest,
+	MessageVariant, NetworkBridgeEvent, PvfExecKind, ReportPeerMessage, RuntimeApiRequest,
 };
 use polkadot_primitives::{
 	vstaging::CandidateReceiptV2, CandidateHash, CollatorPair, Id as ParaId,
@@ -1238,3 +1238,75 @@ fn context_holds_onto_message_until_enough_signals_received() {
 
 	futures::executor::block_on(test_fut);
 }
+
+#[test]
+fn message_variants_have_static_names() {
+	let (tx, _rx) = oneshot::channel();
+	let msg = ChainApiMessage::FinalizedBlockNumber(tx);
+	assert_eq!(
+		(ChainApiMessage::MESSAGE_TYPE, msg.variant()),
+		("ChainApiMessage", "FinalizedBlockNumber")
+	);
+
+	// Runtime API messages are named by their request.
+	let msg = RuntimeApiMessage::Request(
+		Default::default(),
+		RuntimeApiRequest::Validators(oneshot::channel().0),
+	);
+	assert_eq!(msg.variant(), "Validators");
+}
+
+#[test]
+fn timed_subsystem_records_message_processing() {
+	let spawner = sp_core::testing::TaskExecutor::new();
+
+	executor::block_on(async move {
+		let registry = prometheus::Registry::new();
+		let processing_metrics = MessageProcessingMetrics::register(Some(&registry)).unwrap();
+		let (s1_tx, s1_rx) = metered::channel::<usize>(64);
+		let mut s1_rx = s1_rx.fuse();
+
+		let (overseer, handle) = dummy_overseer_builder(spawner, MockSupportsParachains, None)
+			.unwrap()
+			.replace_candidate_validation(move |_| {
+				TimedSubsystem::new(TestSubsystem1(s1_tx), processing_metrics)
+			})
+			.build()
+			.unwrap();
+		let mut handle = Handle::new(handle);
+		let overseer_fut = overseer.run().fuse();
+
+		pin_mut!(overseer_fut);
+
+		handle
+			.send_msg_anon(AllMessages::CandidateValidation(test_candidate_validation_msg()))
+			.await;
+
+		select! {
+			_ = overseer_fut => panic!("overseer concluded early"),
+			processed = s1_rx.next() => assert_eq!(processed, Some(0)),
+		}
+
+		// The message is processed once the subsystem asks for the next one, at the latest when
+		// it receives `Conclude`.
+		handle.stop().await;
+		assert!(overseer_fut.await.is_ok());
+
+		let processing_duration = registry
+			.gather()
+			.into_iter()
+			.find(|mf| mf.get_name() == "polkadot_parachain_subsystem_message_processing_duration")
+			.expect("Must contain the processing duration metric");
+		let validate = processing_duration
+			.get_metric()
+			.iter()
+			.find(|metric| {
+				metric
+					.get_label()
+					.iter()
+					.any(|label| label.get_value() == "ValidateFromExhaustive")
+			})
+			.expect("The message was timed");
+		assert_eq!(validate.get_histogram().get_sample_count(), 1);
+	});
+}
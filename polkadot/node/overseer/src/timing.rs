@@ -0,0 +1,147 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{metrics::MessageProcessingMetrics, prometheus, OverseerSignal};
+use futures::Future;
+use orchestra::{FromOrchestra, SpawnedSubsystem, Subsystem, SubsystemContext};
+use polkadot_node_subsystem_types::{
+	errors::{SubsystemError, SubsystemResult},
+	messages::MessageVariant,
+};
+use std::pin::Pin;
+
+/// A subsystem which records how long it takes to process each of its messages and signals.
+///
+/// A message is being processed from the moment the subsystem receives it until it asks for the
+/// next one.
+pub struct TimedSubsystem<Sub> {
+	subsystem: Sub,
+	metrics: MessageProcessingMetrics,
+}
+
+impl<Sub> TimedSubsystem<Sub> {
+	/// Time the processing of the messages of `subsystem`.
+	pub fn new(subsystem: Sub, metrics: MessageProcessingMetrics) -> Self {
+		Self { subsystem, metrics }
+	}
+}
+
+impl<Context, Sub> Subsystem<Context, SubsystemError> for TimedSubsystem<Sub>
+where
+	Context: SubsystemContext<Signal = OverseerSignal, Error = SubsystemError>,
+	<Context as SubsystemContext>::Message: MessageVariant,
+	Sub: Subsystem<TimedContext<Context>, SubsystemError>,
+{
+	fn start(self, ctx: Context) -> SpawnedSubsystem<SubsystemError> {
+		self.subsystem.start(TimedContext::new(ctx, self.metrics))
+	}
+}
+
+/// The context of a [`TimedSubsystem`].
+pub struct TimedContext<Context> {
+	inner: Context,
+	metrics: MessageProcessingMetrics,
+	/// The timer of the message being processed.
+	processing: Option<prometheus::prometheus::HistogramTimer>,
+}
+
+impl<Context> TimedContext<Context>
+where
+	Context: SubsystemContext<Signal = OverseerSignal, Error = SubsystemError>,
+	<Context as SubsystemContext>::Message: MessageVariant,
+{
+	fn new(inner: Context, metrics: MessageProcessingMetrics) -> Self {
+		Self { inner, metrics, processing: None }
+	}
+
+	/// Stop the timer of the message being processed, if any.
+	fn processed(&mut self) {
+		self.processing = None;
+	}
+
+	/// Start the timer of `msg`.
+	fn received(&mut self, msg: &FromOrchestra<Context::Message, OverseerSignal>) {
+		let (message_type, variant) = match msg {
+			FromOrchestra::Communication { msg } =>
+				(<Context::Message as MessageVariant>::MESSAGE_TYPE, msg.variant()),
+			FromOrchestra::Signal(signal) => ("OverseerSignal", signal_variant(signal)),
+		};
+		self.processing = self.metrics.time_processing(message_type, variant);
+	}
+}
+
+fn signal_variant(signal: &OverseerSignal) -> &'static str {
+	match signal {
+		OverseerSignal::ActiveLeaves(_) => "ActiveLeaves",
+		OverseerSignal::BlockFinalized(..) => "BlockFinalized",
+		OverseerSignal::Conclude => "Conclude",
+	}
+}
+
+#[async_trait::async_trait]
+impl<Context> SubsystemContext for TimedContext<Context>
+where
+	Context: SubsystemContext<Signal = OverseerSignal, Error = SubsystemError>,
+	<Context as SubsystemContext>::Message: MessageVariant,
+{
+	type Message = <Context as SubsystemContext>::Message;
+	type Sender = <Context as SubsystemContext>::Sender;
+	type Error = SubsystemError;
+	type OutgoingMessages = <Context as SubsystemContext>::OutgoingMessages;
+	type Signal = OverseerSignal;
+
+	async fn try_recv(&mut self) -> Result<Option<FromOrchestra<Self::Message, Self::Signal>>, ()> {
+		let msg = self.inner.try_recv().await?;
+		if let Some(msg) = &msg {
+			self.received(msg);
+		}
+		Ok(msg)
+	}
+
+	async fn recv(&mut self) -> SubsystemResult<FromOrchestra<Self::Message, Self::Signal>> {
+		self.processed();
+		let msg = self.inner.recv().await?;
+		self.received(&msg);
+		Ok(msg)
+	}
+
+	async fn recv_signal(&mut self) -> SubsystemResult<Self::Signal> {
+		self.processed();
+		let signal = self.inner.recv_signal().await?;
+		self.processing = self.metrics.time_processing("OverseerSignal", signal_variant(&signal));
+		Ok(signal)
+	}
+
+	fn spawn(
+		&mut self,
+		name: &'static str,
+		s: Pin<Box<dyn Future<Output = ()> + Send>>,
+	) -> SubsystemResult<()> {
+		self.inner.spawn(name, s)
+	}
+
+	fn spawn_blocking(
+		&mut self,
+		name: &'static str,
+		s: Pin<Box<dyn Future<Output = ()> + Send>>,
+	) -> SubsystemResult<()> {
+		self.inner.spawn_blocking(name, s)
+	}
+
+	fn sender(&mut self) -> &mut Self::Sender {
+		self.inner.sender()
+	}
+}
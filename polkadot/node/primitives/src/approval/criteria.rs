@@ -22,7 +22,8 @@ use crate::approval::{
 };
 use codec::{Decode, Encode};
 use polkadot_primitives::{
-	AssignmentId, CandidateHash, CoreIndex, GroupIndex, IndexedVec, SessionInfo, ValidatorIndex,
+	AssignmentId, CandidateHash, CoreIndex, ExecutorParams, GroupIndex, IndexedVec, SessionInfo,
+	ValidatorIndex,
 };
 use sc_keystore::LocalKeystore;
 
@@ -109,6 +110,22 @@ impl<'a> From<&'a SessionInfo> for Config {
 	}
 }
 
+impl Config {
+	/// Create the config of a session, taking the overrides of its executor parameters into
+	/// account.
+	pub fn new(session_info: &SessionInfo, executor_params: &ExecutorParams) -> Self {
+		let mut config = Config::from(session_info);
+		if let Some((n_delay_tranches, zeroth_delay_tranche_width)) =
+			executor_params.approval_relay_vrf_delay()
+		{
+			config.n_delay_tranches = n_delay_tranches;
+			config.zeroth_delay_tranche_width = zeroth_delay_tranche_width;
+		}
+
+		config
+	}
+}
+
 /// A trait for producing and checking assignments.
 ///
 /// Approval voting subsystem implements a a real implemention
@@ -0,0 +1,129 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! No-show statistics of the validators, as observed by approval voting.
+
+use parking_lot::RwLock;
+use polkadot_primitives::{SessionIndex, ValidatorIndex};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The number of sessions no-show statistics are kept for.
+pub const NO_SHOW_STATS_SESSIONS: usize = 6;
+
+/// The no-show statistics of a validator in a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorNoShowStats {
+	/// The number of candidates the validator was a no-show for when they got approved.
+	pub no_shows: u64,
+	/// The number of approvals of the validator that were received after their no-show
+	/// deadline.
+	pub late_approvals: u64,
+}
+
+/// The no-show statistics of the validators of a session, by validator index.
+pub type SessionNoShowStats = BTreeMap<ValidatorIndex, ValidatorNoShowStats>;
+
+/// A handle to the no-show statistics of the last [`NO_SHOW_STATS_SESSIONS`] sessions.
+///
+/// Written by approval voting and read by RPCs, cloning the handle is cheap. The statistics are
+/// not persisted, they start from scratch after a restart.
+#[derive(Debug, Clone, Default)]
+pub struct NoShowStatsHandle(Arc<RwLock<BTreeMap<SessionIndex, SessionNoShowStats>>>);
+
+impl NoShowStatsHandle {
+	/// Note that `validator` was a no-show in `session`.
+	pub fn record_no_show(&self, session: SessionIndex, validator: ValidatorIndex) {
+		self.update(session, validator, |stats| stats.no_shows += 1);
+	}
+
+	/// Note that an approval of `validator` was late in `session`.
+	pub fn record_late_approval(&self, session: SessionIndex, validator: ValidatorIndex) {
+		self.update(session, validator, |stats| stats.late_approvals += 1);
+	}
+
+	/// The statistics of `session`, if it is one of the tracked sessions.
+	pub fn session(&self, session: SessionIndex) -> Option<SessionNoShowStats> {
+		self.0.read().get(&session).cloned()
+	}
+
+	/// The statistics of `validator` in `session`, if any were recorded.
+	pub fn validator(
+		&self,
+		session: SessionIndex,
+		validator: ValidatorIndex,
+	) -> Option<ValidatorNoShowStats> {
+		self.0.read().get(&session)?.get(&validator).copied()
+	}
+
+	/// The most recent session statistics are known for.
+	pub fn latest_session(&self) -> Option<SessionIndex> {
+		self.0.read().keys().next_back().copied()
+	}
+
+	fn update(
+		&self,
+		session: SessionIndex,
+		validator: ValidatorIndex,
+		f: impl FnOnce(&mut ValidatorNoShowStats),
+	) {
+		let mut sessions = self.0.write();
+		if sessions.len() >= NO_SHOW_STATS_SESSIONS &&
+			!sessions.contains_key(&session) &&
+			sessions.keys().next().is_some_and(|oldest| session < *oldest)
+		{
+			return
+		}
+
+		f(sessions.entry(session).or_default().entry(validator).or_default());
+		while sessions.len() > NO_SHOW_STATS_SESSIONS {
+			sessions.pop_first();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn only_recent_sessions_are_kept() {
+		let handle = NoShowStatsHandle::default();
+		handle.record_no_show(1, ValidatorIndex(0));
+		handle.record_no_show(1, ValidatorIndex(0));
+		handle.record_late_approval(1, ValidatorIndex(0));
+		handle.record_late_approval(1, ValidatorIndex(3));
+
+		assert_eq!(
+			handle.session(1),
+			Some(BTreeMap::from([
+				(ValidatorIndex(0), ValidatorNoShowStats { no_shows: 2, late_approvals: 1 }),
+				(ValidatorIndex(3), ValidatorNoShowStats { no_shows: 0, late_approvals: 1 }),
+			]))
+		);
+
+		for session in 2..=NO_SHOW_STATS_SESSIONS as SessionIndex + 1 {
+			handle.record_no_show(session, ValidatorIndex(1));
+		}
+		assert_eq!(handle.session(1), None);
+		assert_eq!(handle.latest_session(), Some(NO_SHOW_STATS_SESSIONS as SessionIndex + 1));
+
+		// sessions older than all the tracked ones are ignored.
+		handle.record_no_show(1, ValidatorIndex(0));
+		assert_eq!(handle.session(1), None);
+	}
+}
@@ -49,6 +49,8 @@ pub use polkadot_parachain_primitives::primitives::{
 
 pub mod approval;
 
+pub mod session_history;
+
 /// Disputes related types.
 pub mod disputes;
 pub use disputes::{
@@ -413,8 +415,13 @@ impl MaybeCompressedPoV {
 	///
 	/// If `self == Raw` it is compressed using [`maybe_compress_pov`].
 	pub fn into_compressed(self) -> PoV {
+		self.into_compressed_with(&PoVCompressionConfig::default())
+	}
+
+	/// Convert into a compressed [`PoV`], compressing a `Raw` PoV as configured.
+	pub fn into_compressed_with(self, config: &PoVCompressionConfig) -> PoV {
 		match self {
-			Self::Raw(raw) => maybe_compress_pov(raw),
+			Self::Raw(raw) => config.compress(raw),
 			Self::Compressed(compressed) => compressed,
 		}
 	}
@@ -666,9 +673,116 @@ impl ErasureChunk {
 /// Compress a PoV, unless it exceeds the [`POV_BOMB_LIMIT`].
 #[cfg(not(target_os = "unknown"))]
 pub fn maybe_compress_pov(pov: PoV) -> PoV {
-	let PoV { block_data: BlockData(raw) } = pov;
-	let raw = sp_maybe_compressed_blob::compress(&raw, POV_BOMB_LIMIT).unwrap_or(raw);
+	PoVCompressionConfig::default().compress(pov)
+}
+
+/// The highest zstd level a PoV can be compressed with.
+pub const MAX_POV_COMPRESSION_LEVEL: i32 = 22;
+
+/// How a collator compresses the PoVs it builds.
+///
+/// Validators detect compressed PoVs and decompress them the same way, whatever the level, so
+/// this only trades the collator's CPU time against bandwidth and needs no coordination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoVCompression {
+	/// Send PoVs uncompressed.
+	None,
+	/// Compress PoVs with zstd, using the given level.
+	Zstd {
+		/// The compression level, from 1 to [`MAX_POV_COMPRESSION_LEVEL`].
+		level: i32,
+	},
+}
+
+impl Default for PoVCompression {
+	fn default() -> Self {
+		Self::Zstd { level: sp_maybe_compressed_blob::DEFAULT_COMPRESSION_LEVEL }
+	}
+}
+
+impl std::fmt::Display for PoVCompression {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::None => write!(f, "none"),
+			Self::Zstd { level } => write!(f, "zstd:{}", level),
+		}
+	}
+}
+
+impl std::str::FromStr for PoVCompression {
+	type Err = String;
+
+	/// Parse `none`, `zstd` or `zstd:<level>`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.split_once(':') {
+			None if s == "none" => Ok(Self::None),
+			None if s == "zstd" => Ok(Self::default()),
+			Some(("zstd", level)) => match level.parse() {
+				Ok(level) if (1..=MAX_POV_COMPRESSION_LEVEL).contains(&level) =>
+					Ok(Self::Zstd { level }),
+				_ => Err(format!(
+					"invalid zstd level `{}`, expected 1 to {}",
+					level, MAX_POV_COMPRESSION_LEVEL
+				)),
+			},
+			_ =>
+				Err(format!("invalid PoV compression `{}`, expected `none` or `zstd[:<level>]`", s)),
+		}
+	}
+}
+
+/// Node-side configuration of PoV (de)compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoVCompressionConfig {
+	/// How PoVs are compressed.
+	pub compression: PoVCompression,
+	/// The maximum size of a PoV after decompression.
+	///
+	/// PoVs bigger than that are sent uncompressed and compressed PoVs decompressing to more are
+	/// rejected. Validators always use [`POV_BOMB_LIMIT`], as they must agree on the validity of
+	/// a PoV, so the limit is capped at that.
+	pub bomb_limit: usize,
+}
 
-	let pov = PoV { block_data: BlockData(raw) };
-	pov
+impl Default for PoVCompressionConfig {
+	fn default() -> Self {
+		Self { compression: PoVCompression::default(), bomb_limit: POV_BOMB_LIMIT }
+	}
+}
+
+#[cfg(not(target_os = "unknown"))]
+impl PoVCompressionConfig {
+	/// The effective bomb limit, at most [`POV_BOMB_LIMIT`].
+	pub fn bomb_limit(&self) -> usize {
+		self.bomb_limit.min(POV_BOMB_LIMIT)
+	}
+
+	/// Compress a PoV as configured, unless it exceeds the bomb limit.
+	///
+	/// Uncompressed block data which happens to start like compressed data would be rejected by
+	/// validators, so it is compressed even with [`PoVCompression::None`].
+	pub fn compress(&self, pov: PoV) -> PoV {
+		let PoV { block_data: BlockData(raw) } = pov;
+		let compression = match self.compression {
+			PoVCompression::None if sp_maybe_compressed_blob::is_compressed(&raw) =>
+				PoVCompression::default(),
+			compression => compression,
+		};
+		let raw = match compression {
+			PoVCompression::None => raw,
+			PoVCompression::Zstd { level } =>
+				sp_maybe_compressed_blob::compress_with_level(&raw, self.bomb_limit(), level)
+					.unwrap_or(raw),
+		};
+
+		PoV { block_data: BlockData(raw) }
+	}
+
+	/// Decompress PoV block data, if compressed, up to the bomb limit.
+	pub fn decompress<'a>(
+		&self,
+		block_data: &'a [u8],
+	) -> Result<std::borrow::Cow<'a, [u8]>, sp_maybe_compressed_blob::Error> {
+		sp_maybe_compressed_blob::decompress(block_data, self.bomb_limit())
+	}
 }
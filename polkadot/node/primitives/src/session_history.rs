@@ -0,0 +1,175 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Validator sets of the recent sessions, as observed by gossip support.
+//!
+//! The runtime only keeps the `SessionInfo` of the sessions in the dispute window. The node keeps
+//! the validators of the sessions it saw for longer, so they can be served without querying the
+//! storage of an archive node.
+
+use parking_lot::RwLock;
+use polkadot_primitives::{
+	AuthorityDiscoveryId, SessionIndex, SessionInfo, ValidatorId, ValidatorIndex,
+};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The default number of sessions the validator sets are kept for.
+pub const DEFAULT_SESSION_HISTORY_DEPTH: usize = 32;
+
+/// The validators of a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionValidators {
+	/// The validators of the session, by validator index.
+	pub validators: Vec<ValidatorId>,
+	/// The authority discovery keys of all authorities of the session, including the ones which
+	/// are not parachain validators.
+	pub discovery_keys: Vec<AuthorityDiscoveryId>,
+	/// The validators of each backing group, by group index.
+	pub validator_groups: Vec<Vec<ValidatorIndex>>,
+}
+
+impl From<&SessionInfo> for SessionValidators {
+	fn from(session_info: &SessionInfo) -> Self {
+		Self {
+			validators: session_info.validators.to_vec(),
+			discovery_keys: session_info.discovery_keys.clone(),
+			validator_groups: session_info.validator_groups.to_vec(),
+		}
+	}
+}
+
+struct SessionHistory {
+	depth: usize,
+	sessions: BTreeMap<SessionIndex, Arc<SessionValidators>>,
+}
+
+impl SessionHistory {
+	fn prune(&mut self) {
+		while self.sessions.len() > self.depth {
+			self.sessions.pop_first();
+		}
+	}
+}
+
+/// A handle to the validator sets of the most recent sessions.
+///
+/// Written by gossip support and read by RPCs, cloning the handle is cheap. The RPCs also record
+/// the sessions still known to the runtime, so the history is filled on nodes without gossip
+/// support too. The validator sets are not persisted, after a restart only the sessions seen since
+/// then are known.
+#[derive(Clone)]
+pub struct SessionHistoryHandle(Arc<RwLock<SessionHistory>>);
+
+impl Default for SessionHistoryHandle {
+	fn default() -> Self {
+		Self::new(DEFAULT_SESSION_HISTORY_DEPTH)
+	}
+}
+
+impl std::fmt::Debug for SessionHistoryHandle {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let history = self.0.read();
+		f.debug_struct("SessionHistoryHandle")
+			.field("depth", &history.depth)
+			.field("sessions", &history.sessions.keys())
+			.finish()
+	}
+}
+
+impl SessionHistoryHandle {
+	/// Create a handle keeping the validator sets of the last `depth` sessions.
+	pub fn new(depth: usize) -> Self {
+		Self(Arc::new(RwLock::new(SessionHistory { depth, sessions: BTreeMap::new() })))
+	}
+
+	/// The number of sessions the validator sets are kept for.
+	pub fn depth(&self) -> usize {
+		self.0.read().depth
+	}
+
+	/// Change the number of sessions the validator sets are kept for.
+	pub fn set_depth(&self, depth: usize) {
+		let mut history = self.0.write();
+		history.depth = depth;
+		history.prune();
+	}
+
+	/// Note the validators of `session`.
+	///
+	/// Does nothing if the session is older than all the kept sessions and there is no room
+	/// left.
+	pub fn record(&self, session: SessionIndex, validators: SessionValidators) {
+		let mut history = self.0.write();
+		if history.sessions.len() >= history.depth &&
+			history.sessions.keys().next().map_or(true, |oldest| session < *oldest)
+		{
+			return
+		}
+
+		history.sessions.insert(session, Arc::new(validators));
+		history.prune();
+	}
+
+	/// Whether the validators of `session` are known.
+	pub fn contains(&self, session: SessionIndex) -> bool {
+		self.0.read().sessions.contains_key(&session)
+	}
+
+	/// The validators of `session`, if it is one of the kept sessions.
+	pub fn session(&self, session: SessionIndex) -> Option<Arc<SessionValidators>> {
+		self.0.read().sessions.get(&session).cloned()
+	}
+
+	/// The sessions the validators are known for, oldest first.
+	pub fn sessions(&self) -> Vec<SessionIndex> {
+		self.0.read().sessions.keys().copied().collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn validators(n: u32) -> SessionValidators {
+		SessionValidators {
+			validators: Vec::new(),
+			discovery_keys: Vec::new(),
+			validator_groups: vec![(0..n).map(ValidatorIndex).collect()],
+		}
+	}
+
+	#[test]
+	fn only_recent_sessions_are_kept() {
+		let handle = SessionHistoryHandle::new(3);
+		for session in 1..=4 {
+			handle.record(session, validators(session));
+		}
+		assert_eq!(handle.sessions(), vec![2, 3, 4]);
+		assert_eq!(handle.session(4).as_deref(), Some(&validators(4)));
+
+		// sessions older than all the kept ones are ignored.
+		handle.record(1, validators(1));
+		assert!(!handle.contains(1));
+
+		handle.set_depth(1);
+		assert_eq!(handle.sessions(), vec![4]);
+
+		// Nothing is kept with a depth of 0.
+		handle.set_depth(0);
+		handle.record(5, validators(5));
+		assert!(handle.sessions().is_empty());
+	}
+}
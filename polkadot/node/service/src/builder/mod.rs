@@ -24,7 +24,10 @@ pub(crate) use partial::{new_partial, new_partial_basics};
 
 use crate::{
 	grandpa_support, open_database,
-	overseer::{ExtendedOverseerGenArgs, OverseerGen, OverseerGenArgs},
+	overseer::{
+		BitfieldSigningConfig, ExtendedOverseerGenArgs, OverseerGen, OverseerGenArgs,
+		PushCollators, ServingBudget,
+	},
 	parachains_db,
 	relay_chain_selection::SelectRelayChain,
 	workers, Chain, Error, FullBackend, FullClient, IdentifyVariant, IsParachainNode,
@@ -40,10 +43,13 @@ use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig
 use polkadot_node_core_chain_selection::{
 	self as chain_selection_subsystem, Config as ChainSelectionConfig,
 };
-use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
+use polkadot_node_core_dispute_coordinator::{
+	Config as DisputeCoordinatorConfig, DisputeRetention,
+};
+use polkadot_node_core_pvf_checker::Config as PvfCheckerConfig;
 use polkadot_node_network_protocol::{
-	peer_set::{PeerSet, PeerSetProtocolNames},
-	request_response::{IncomingRequest, ReqProtocolNames},
+	peer_set::{PeerSet, PeerSetProtocolNames, VALIDATION_INACTIVITY_EVICTION},
+	request_response::{IncomingRequest, NetworkProfile, Protocol, ReqProtocolNames},
 };
 use polkadot_node_subsystem_types::DefaultSubsystemClient;
 use polkadot_overseer::{Handle, OverseerConnector};
@@ -53,7 +59,7 @@ use sc_network::config::FullNetworkConfiguration;
 use sc_network_sync::WarpSyncConfig;
 use sc_service::{Configuration, RpcHandlers, TaskManager};
 use sc_sysinfo::Metric;
-use sc_telemetry::TelemetryWorkerHandle;
+use sc_telemetry::{telemetry, TelemetryWorkerHandle, SUBSTRATE_INFO};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_consensus_beefy::ecdsa_crypto;
 use sp_runtime::traits::Block as BlockT;
@@ -83,8 +89,19 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub prepare_workers_soft_max_num: Option<usize>,
 	/// An optional absolute number of pvf workers that can be spawned in the pvf prepare pool.
 	pub prepare_workers_hard_max_num: Option<usize>,
+	/// An optional percentage of the approval executions to cross-check with a different
+	/// executor configuration.
+	pub pvf_cross_check_percent: Option<u8>,
+	/// An optional percentage of the PVFs to pre-check locally right away, instead of adopting the
+	/// judgement of other validators.
+	pub pvf_local_precheck_percent: Option<u8>,
 	/// How long finalized data should be kept in the availability store (in hours)
 	pub keep_finalized_for: Option<u32>,
+	/// For how many sessions dispute data should be kept, `Some(0)` keeps it forever. Defaults to
+	/// the dispute window.
+	pub dispute_window: Option<u32>,
+	/// For how many sessions the validator sets are kept for the session history RPCs.
+	pub session_history_depth: Option<u32>,
 	pub overseer_gen: OverseerGenerator,
 	pub overseer_message_channel_capacity_override: Option<usize>,
 	#[allow(dead_code)]
@@ -92,6 +109,25 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub hwbench: Option<sc_sysinfo::HwBench>,
 	/// Enable approval voting processing in parallel.
 	pub enable_approval_voting_parallel: bool,
+	/// The network conditions the request-response protocols are sized for.
+	pub network_profile: NetworkProfile,
+	/// The collators trusted to push collations to this validator, per para.
+	pub collation_push_collators: PushCollators,
+	/// Whether a collator has its collations prechecked by a validator of the backing group before
+	/// advertising them.
+	pub precheck_collations: bool,
+	/// Whether inactive non-reserved peers of the validation peer set are disconnected.
+	pub evict_inactive_validation_peers: bool,
+	/// The number of non-reserved validation peer set slots reserved for authorities.
+	pub reserved_authority_slots: usize,
+	/// The number of GRANDPA rounds whose observed votes are recorded, `0` disables recording.
+	pub grandpa_vote_history: usize,
+	/// The rate at which erasure chunks are served to other validators.
+	pub chunk_serving_budget: ServingBudget,
+	/// Configuration for the bitfield signing subsystem.
+	pub bitfield_signing_config: BitfieldSigningConfig,
+	/// Whether BABE claims secondary slots when the epoch configuration allows them.
+	pub babe_secondary_slot_claims: bool,
 }
 
 /// Completely built polkadot node service.
@@ -150,8 +186,12 @@ where
 			SelectRelayChain::new_longest_chain(basics.backend.clone())
 		};
 
-		let partial_components =
-			new_partial::<SelectRelayChain<_>>(&mut config, basics, select_chain)?;
+		let partial_components = new_partial::<SelectRelayChain<_>>(
+			&mut config,
+			basics,
+			select_chain,
+			params.grandpa_vote_history,
+		)?;
 
 		let net_config = sc_network::config::FullNetworkConfiguration::<_, _, Network>::new(
 			&config.network,
@@ -201,8 +241,21 @@ where
 					execute_workers_max_num,
 					prepare_workers_soft_max_num,
 					prepare_workers_hard_max_num,
+					pvf_cross_check_percent,
+					pvf_local_precheck_percent,
 					keep_finalized_for,
+					dispute_window,
+					session_history_depth,
 					enable_approval_voting_parallel,
+					network_profile,
+					collation_push_collators,
+					precheck_collations,
+					evict_inactive_validation_peers,
+					reserved_authority_slots,
+					grandpa_vote_history: _,
+					chunk_serving_budget,
+					bitfield_signing_config,
+					babe_secondary_slot_claims,
 				},
 			overseer_connector,
 			partial_components:
@@ -246,7 +299,10 @@ where
 
 			Some(backoff)
 		};
-		let shared_voter_state = rpc_setup;
+		let (shared_voter_state, vote_history, no_show_stats, session_history) = rpc_setup;
+		if let Some(depth) = session_history_depth {
+			session_history.set_depth(depth as usize);
+		}
 		let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 		let auth_disc_public_addresses = config.network.public_addresses.clone();
 
@@ -321,6 +377,7 @@ where
 				&peerset_protocol_names,
 				metrics.clone(),
 				Arc::clone(&peer_store_handle),
+				evict_inactive_validation_peers.then_some(VALIDATION_INACTIVITY_EVICTION),
 			)
 			.into_iter()
 			.map(|(config, (peerset, service))| {
@@ -332,7 +389,8 @@ where
 			std::collections::HashMap::new()
 		};
 
-		let req_protocol_names = ReqProtocolNames::new(&genesis_hash, config.chain_spec.fork_id());
+		let req_protocol_names = ReqProtocolNames::new(&genesis_hash, config.chain_spec.fork_id())
+			.with_network_profile(network_profile);
 
 		let (collation_req_v1_receiver, cfg) =
 			IncomingRequest::get_config_receiver::<_, Network>(&req_protocol_names);
@@ -366,6 +424,15 @@ where
 		));
 
 		let ext_overseer_args = if is_parachain_node.is_running_alongside_parachain_node() {
+			// Collators only send collation precheck and push requests, validators answer them.
+			net_config.add_request_response_protocol(
+				Protocol::CollationPrecheckV1
+					.get_outbound_only_config::<_, Network>(&req_protocol_names),
+			);
+			net_config.add_request_response_protocol(
+				Protocol::CollationPushV1
+					.get_outbound_only_config::<_, Network>(&req_protocol_names),
+			);
 			None
 		} else {
 			let parachains_db = open_database(&config.database)?;
@@ -393,6 +460,7 @@ where
 					pvf_execute_workers_max_num: execute_workers_max_num.unwrap_or(4),
 					pvf_prepare_workers_soft_max_num: prepare_workers_soft_max_num.unwrap_or(1),
 					pvf_prepare_workers_hard_max_num: prepare_workers_hard_max_num.unwrap_or(2),
+					pvf_cross_check_percent: pvf_cross_check_percent.unwrap_or(0),
 				})
 			} else {
 				None
@@ -400,15 +468,34 @@ where
 			let (candidate_req_v2_receiver, cfg) =
 				IncomingRequest::get_config_receiver::<_, Network>(&req_protocol_names);
 			net_config.add_request_response_protocol(cfg);
+			let (collation_precheck_receiver, cfg) =
+				IncomingRequest::get_config_receiver::<_, Network>(&req_protocol_names);
+			net_config.add_request_response_protocol(cfg);
+			let (collation_push_receiver, cfg) =
+				IncomingRequest::get_config_receiver::<_, Network>(&req_protocol_names);
+			net_config.add_request_response_protocol(cfg);
+			let (pvf_check_statement_receiver, cfg) =
+				IncomingRequest::get_config_receiver::<_, Network>(&req_protocol_names);
+			net_config.add_request_response_protocol(cfg);
 			let (dispute_req_receiver, cfg) =
 				IncomingRequest::get_config_receiver::<_, Network>(&req_protocol_names);
 			net_config.add_request_response_protocol(cfg);
+			let pvf_checker_config = match pvf_local_precheck_percent {
+				Some(percent) =>
+					PvfCheckerConfig { local_precheck_probability: f64::from(percent) / 100.0 },
+				None => PvfCheckerConfig::default(),
+			};
 			let approval_voting_config = ApprovalVotingConfig {
 				col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
 				slot_duration_millis: slot_duration.as_millis() as u64,
 			};
 			let dispute_coordinator_config = DisputeCoordinatorConfig {
 				col_dispute_data: parachains_db::REAL_COLUMNS.col_dispute_coordinator_data,
+				retention: match dispute_window {
+					None => DisputeRetention::default(),
+					Some(0) => DisputeRetention::KeepAll,
+					Some(sessions) => DisputeRetention::Sessions(sessions),
+				},
 			};
 			let chain_selection_config = ChainSelectionConfig {
 				col_data: parachains_db::REAL_COLUMNS.col_chain_selection_data,
@@ -435,12 +522,24 @@ where
 				parachains_db,
 				candidate_validation_config,
 				availability_config,
+				bitfield_signing_config,
 				pov_req_receiver,
 				chunk_req_v1_receiver,
 				chunk_req_v2_receiver,
+				chunk_serving_budget,
 				candidate_req_v2_receiver,
+				collation_precheck_receiver,
+				collation_push_receiver,
+				collation_push_collators,
+				pvf_check_statement_receiver,
+				pvf_checker_config,
 				approval_voting_config,
+				no_show_stats,
+				session_history,
 				dispute_req_receiver,
+				dispute_send_rate_limit: Default::default(),
+				reserved_authority_slots,
+				telemetry: telemetry.as_ref().map(|x| x.handle()),
 				dispute_coordinator_config,
 				chain_selection_config,
 				fetch_chunks_threshold,
@@ -478,6 +577,7 @@ where
 					network_provider: Arc::new(network.clone()),
 					is_validator: role.is_authority(),
 					enable_http_requests: false,
+					http_client_config: Default::default(),
 					custom_extensions: move |_| vec![],
 				})?
 				.run(client.clone(), task_manager.spawn_handle())
@@ -500,6 +600,13 @@ where
 			telemetry: telemetry.as_mut(),
 		})?;
 
+		telemetry!(
+			telemetry.as_ref().map(|x| x.handle());
+			SUBSTRATE_INFO;
+			"polkadot.network_profile";
+			"profile" => network_profile.as_str(),
+		);
+
 		if let Some(hwbench) = hwbench {
 			sc_sysinfo::print_hwbench(&hwbench);
 			match SUBSTRATE_REFERENCE_HARDWARE.check_hardware(&hwbench, role.is_authority()) {
@@ -617,6 +724,7 @@ where
 						req_protocol_names,
 						peerset_protocol_names,
 						notification_services,
+						precheck_collations,
 					},
 					ext_overseer_args,
 				)
@@ -709,6 +817,11 @@ where
 				block_proposal_slot_portion: sc_consensus_babe::SlotProportion::new(2f32 / 3f32),
 				max_block_proposal_slot_portion: None,
 				telemetry: telemetry.as_ref().map(|x| x.handle()),
+				secondary_slot_claims: if babe_secondary_slot_claims {
+					sc_consensus_babe::SecondarySlotClaims::Enabled
+				} else {
+					sc_consensus_babe::SecondarySlotClaims::Disabled
+				},
 			};
 
 			let babe = sc_consensus_babe::start_babe(babe_config)?;
@@ -819,6 +932,7 @@ where
 				voting_rule: voting_rules_builder.build(),
 				prometheus_registry: prometheus_registry.clone(),
 				shared_voter_state,
+				vote_history,
 				telemetry: telemetry.as_ref().map(|x| x.handle()),
 				notification_service: grandpa_notification_service,
 				offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
@@ -22,6 +22,9 @@ use crate::{
 	fake_runtime_api::RuntimeApi, grandpa_support, relay_chain_selection, Error, FullBackend,
 	FullClient, IdentifyVariant, GRANDPA_JUSTIFICATION_PERIOD,
 };
+use polkadot_node_primitives::{
+	approval::stats::NoShowStatsHandle, session_history::SessionHistoryHandle,
+};
 use polkadot_primitives::Block;
 use sc_consensus_grandpa::FinalityProofProvider as GrandpaFinalityProofProvider;
 use sc_executor::{HeapAllocStrategy, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY};
@@ -69,7 +72,12 @@ pub(crate) type PolkadotPartialComponents<ChainSelection> = sc_service::PartialC
 			sc_consensus_babe::BabeLink<Block>,
 			sc_consensus_beefy::BeefyVoterLinks<Block, ecdsa_crypto::AuthorityId>,
 		),
-		sc_consensus_grandpa::SharedVoterState,
+		(
+			sc_consensus_grandpa::SharedVoterState,
+			sc_consensus_grandpa::SharedVoteHistory<Block>,
+			NoShowStatsHandle,
+			SessionHistoryHandle,
+		),
 		sp_consensus_babe::SlotDuration,
 		Option<Telemetry>,
 	),
@@ -143,6 +151,7 @@ pub(crate) fn new_partial<ChainSelection>(
 	config: &mut Configuration,
 	Basics { task_manager, backend, client, keystore_container, telemetry }: Basics,
 	select_chain: ChainSelection,
+	grandpa_vote_history: usize,
 ) -> Result<PolkadotPartialComponents<ChainSelection>, Error>
 where
 	ChainSelection: 'static + SelectChain<Block>,
@@ -215,13 +224,21 @@ where
 	let justification_stream = grandpa_link.justification_stream();
 	let shared_authority_set = grandpa_link.shared_authority_set().clone();
 	let shared_voter_state = sc_consensus_grandpa::SharedVoterState::empty();
+	let vote_history = sc_consensus_grandpa::SharedVoteHistory::new(grandpa_vote_history);
 	let finality_proof_provider = GrandpaFinalityProofProvider::new_for_service(
 		backend.clone(),
 		Some(shared_authority_set.clone()),
 	);
 
 	let import_setup = (block_import, grandpa_link, babe_link, beefy_voter_links);
-	let rpc_setup = shared_voter_state.clone();
+	let no_show_stats = NoShowStatsHandle::default();
+	let session_history = SessionHistoryHandle::default();
+	let rpc_setup = (
+		shared_voter_state.clone(),
+		vote_history.clone(),
+		no_show_stats.clone(),
+		session_history.clone(),
+	);
 
 	let rpc_extensions_builder = {
 		let client = client.clone();
@@ -244,6 +261,7 @@ where
 				},
 				grandpa: polkadot_rpc::GrandpaDeps {
 					shared_voter_state: shared_voter_state.clone(),
+					vote_history: vote_history.clone(),
 					shared_authority_set: shared_authority_set.clone(),
 					justification_stream: justification_stream.clone(),
 					subscription_executor: subscription_executor.clone(),
@@ -255,6 +273,8 @@ where
 					subscription_executor,
 				},
 				backend: backend.clone(),
+				no_show_stats: no_show_stats.clone(),
+				session_history: session_history.clone(),
 			};
 
 			polkadot_rpc::create_full(deps).map_err(Into::into)
@@ -400,6 +400,12 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl runtime_api::OnDemandApi<Block, Balance> for Runtime {
+		fn spot_price(_: u32, _: Option<u32>) -> polkadot_primitives::vstaging::OnDemandSpotPrice<Balance> {
+			unimplemented!()
+		}
+	}
+
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce> for Runtime {
 		fn account_nonce(_: AccountId) -> Nonce {
 			unimplemented!()
@@ -25,17 +25,21 @@ use polkadot_node_core_av_store::Config as AvailabilityConfig;
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
 use polkadot_node_core_chain_selection::Config as ChainSelectionConfig;
 use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
+use polkadot_node_core_pvf_checker::Config as PvfCheckerConfig;
 use polkadot_node_network_protocol::{
 	peer_set::{PeerSet, PeerSetProtocolNames},
 	request_response::{
 		v1 as request_v1, v2 as request_v2, IncomingRequestReceiver, ReqProtocolNames,
 	},
 };
+use polkadot_node_primitives::{
+	approval::stats::NoShowStatsHandle, session_history::SessionHistoryHandle,
+};
 #[cfg(any(feature = "malus", test))]
 pub use polkadot_overseer::{dummy::dummy_overseer_builder, HeadSupportsParachains};
 use polkadot_overseer::{
-	metrics::Metrics as OverseerMetrics, MetricsTrait, Overseer, OverseerConnector, OverseerHandle,
-	SpawnGlue,
+	metrics::Metrics as OverseerMetrics, MessageProcessingMetrics, MetricsTrait, Overseer,
+	OverseerConnector, OverseerHandle, SpawnGlue, TimedSubsystem,
 };
 
 use parking_lot::Mutex;
@@ -43,14 +47,15 @@ use sc_authority_discovery::Service as AuthorityDiscoveryService;
 use sc_client_api::AuxStore;
 use sc_keystore::LocalKeystore;
 use sc_network::{NetworkStateInfo, NotificationService};
+use sc_telemetry::TelemetryHandle;
 use std::{collections::HashMap, sync::Arc};
 
 pub use polkadot_approval_distribution::ApprovalDistribution as ApprovalDistributionSubsystem;
 pub use polkadot_availability_bitfield_distribution::BitfieldDistribution as BitfieldDistributionSubsystem;
-pub use polkadot_availability_distribution::AvailabilityDistributionSubsystem;
+pub use polkadot_availability_distribution::{AvailabilityDistributionSubsystem, ServingBudget};
 pub use polkadot_availability_recovery::AvailabilityRecoverySubsystem;
-pub use polkadot_collator_protocol::{CollatorProtocolSubsystem, ProtocolSide};
-pub use polkadot_dispute_distribution::DisputeDistributionSubsystem;
+pub use polkadot_collator_protocol::{CollatorProtocolSubsystem, ProtocolSide, PushCollators};
+pub use polkadot_dispute_distribution::{DisputeDistributionSubsystem, SendRateLimitConfig};
 pub use polkadot_gossip_support::GossipSupport as GossipSupportSubsystem;
 pub use polkadot_network_bridge::{
 	Metrics as NetworkBridgeMetrics, NetworkBridgeRx as NetworkBridgeRxSubsystem,
@@ -62,8 +67,8 @@ pub use polkadot_node_core_approval_voting_parallel::{
 	ApprovalVotingParallelSubsystem, Metrics as ApprovalVotingParallelMetrics,
 };
 pub use polkadot_node_core_av_store::AvailabilityStoreSubsystem;
-pub use polkadot_node_core_backing::CandidateBackingSubsystem;
-pub use polkadot_node_core_bitfield_signing::BitfieldSigningSubsystem;
+pub use polkadot_node_core_backing::{CandidateBackingSubsystem, DEFAULT_MAX_PARALLEL_VALIDATIONS};
+pub use polkadot_node_core_bitfield_signing::{BitfieldSigningConfig, BitfieldSigningSubsystem};
 pub use polkadot_node_core_candidate_validation::CandidateValidationSubsystem;
 pub use polkadot_node_core_chain_api::ChainApiSubsystem;
 pub use polkadot_node_core_chain_selection::ChainSelectionSubsystem;
@@ -108,6 +113,9 @@ where
 	pub peerset_protocol_names: PeerSetProtocolNames,
 	/// Notification services for validation/collation protocols.
 	pub notification_services: HashMap<PeerSet, Box<dyn NotificationService>>,
+	/// Whether collators have their collations prechecked by a validator of the backing group
+	/// before advertising them.
+	pub precheck_collations: bool,
 }
 
 pub struct ExtendedOverseerGenArgs {
@@ -119,18 +127,43 @@ pub struct ExtendedOverseerGenArgs {
 	pub candidate_validation_config: Option<CandidateValidationConfig>,
 	/// Configuration for the availability store subsystem.
 	pub availability_config: AvailabilityConfig,
+	/// Configuration for the bitfield signing subsystem.
+	pub bitfield_signing_config: BitfieldSigningConfig,
 	/// POV request receiver.
 	pub pov_req_receiver: IncomingRequestReceiver<request_v1::PoVFetchingRequest>,
 	/// Erasure chunk request v1 receiver.
 	pub chunk_req_v1_receiver: IncomingRequestReceiver<request_v1::ChunkFetchingRequest>,
 	/// Erasure chunk request v2 receiver.
 	pub chunk_req_v2_receiver: IncomingRequestReceiver<request_v2::ChunkFetchingRequest>,
+	/// The rate at which erasure chunks are served to other validators.
+	pub chunk_serving_budget: ServingBudget,
 	/// Receiver for incoming candidate requests.
 	pub candidate_req_v2_receiver: IncomingRequestReceiver<request_v2::AttestedCandidateRequest>,
+	/// Receiver for incoming collation precheck requests.
+	pub collation_precheck_receiver: IncomingRequestReceiver<request_v1::CollationPrecheckRequest>,
+	/// Receiver for collations pushed by collators.
+	pub collation_push_receiver: IncomingRequestReceiver<request_v1::CollationPushRequest>,
+	/// The collators whose pushed collations are accepted, per para.
+	pub collation_push_collators: PushCollators,
+	/// Receiver for requests of other validators for our PVF pre-checking statements.
+	pub pvf_check_statement_receiver:
+		IncomingRequestReceiver<request_v1::PvfCheckStatementFetchingRequest>,
+	/// Configuration for the PVF pre-checking subsystem.
+	pub pvf_checker_config: PvfCheckerConfig,
 	/// Configuration for the approval voting subsystem.
 	pub approval_voting_config: ApprovalVotingConfig,
+	/// The no-show statistics recorded by the approval voting subsystem.
+	pub no_show_stats: NoShowStatsHandle,
+	/// The validator sets of the recent sessions, recorded by the gossip support subsystem.
+	pub session_history: SessionHistoryHandle,
 	/// Receiver for incoming disputes.
 	pub dispute_req_receiver: IncomingRequestReceiver<request_v1::DisputeRequest>,
+	/// Rate limit for sending out dispute requests.
+	pub dispute_send_rate_limit: SendRateLimitConfig,
+	/// Number of validation peer set slots reserved for authorities.
+	pub reserved_authority_slots: usize,
+	/// Telemetry the network bridge reports the protocol version census of our peers to.
+	pub telemetry: Option<TelemetryHandle>,
 	/// Configuration for the dispute coordinator subsystem.
 	pub dispute_coordinator_config: DisputeCoordinatorConfig,
 	/// Configuration for the chain selection subsystem.
@@ -144,6 +177,16 @@ pub struct ExtendedOverseerGenArgs {
 	pub enable_approval_voting_parallel: bool,
 }
 
+/// The number of candidates backing may validate in parallel.
+///
+/// Twice the number of PVF execute workers, so fetching PoVs and making them available overlaps
+/// with execution.
+fn max_parallel_validations(config: &Option<CandidateValidationConfig>) -> usize {
+	config
+		.as_ref()
+		.map_or(DEFAULT_MAX_PARALLEL_VALIDATIONS, |config| 2 * config.pvf_execute_workers_max_num)
+}
+
 /// Obtain a prepared validator `Overseer`, that is initialized with all default values.
 ///
 /// The difference between this function and `validator_with_parallel_overseer_builder` is that this
@@ -165,18 +208,31 @@ pub fn validator_overseer_builder<Spawner, RuntimeClient>(
 		req_protocol_names,
 		peerset_protocol_names,
 		notification_services,
+		precheck_collations: _,
 	}: OverseerGenArgs<Spawner, RuntimeClient>,
 	ExtendedOverseerGenArgs {
 		keystore,
 		parachains_db,
 		candidate_validation_config,
 		availability_config,
+		bitfield_signing_config,
 		pov_req_receiver,
 		chunk_req_v1_receiver,
 		chunk_req_v2_receiver,
+		chunk_serving_budget,
 		candidate_req_v2_receiver,
+		collation_precheck_receiver,
+		collation_push_receiver,
+		collation_push_collators,
+		pvf_check_statement_receiver,
+		pvf_checker_config,
 		approval_voting_config,
+		no_show_stats,
+		session_history,
 		dispute_req_receiver,
+		dispute_send_rate_limit,
+		reserved_authority_slots,
+		telemetry,
 		dispute_coordinator_config,
 		chain_selection_config,
 		fetch_chunks_threshold,
@@ -235,34 +291,44 @@ where
 		Metrics::register(registry)?;
 
 	let builder = Overseer::builder()
-		.network_bridge_tx(NetworkBridgeTxSubsystem::new(
-			network_service.clone(),
-			authority_discovery_service.clone(),
-			network_bridge_metrics.clone(),
-			req_protocol_names.clone(),
-			peerset_protocol_names.clone(),
-			notification_sinks.clone(),
-		))
-		.network_bridge_rx(NetworkBridgeRxSubsystem::new(
-			network_service.clone(),
-			authority_discovery_service.clone(),
-			Box::new(sync_service.clone()),
-			network_bridge_metrics,
-			peerset_protocol_names,
-			notification_services,
-			notification_sinks,
-			enable_approval_voting_parallel,
-		))
-		.availability_distribution(AvailabilityDistributionSubsystem::new(
-			keystore.clone(),
-			IncomingRequestReceivers {
-				pov_req_receiver,
-				chunk_req_v1_receiver,
-				chunk_req_v2_receiver,
-			},
-			req_protocol_names.clone(),
-			Metrics::register(registry)?,
-		))
+		.network_bridge_tx(
+			NetworkBridgeTxSubsystem::new(
+				network_service.clone(),
+				authority_discovery_service.clone(),
+				network_bridge_metrics.clone(),
+				req_protocol_names.clone(),
+				peerset_protocol_names.clone(),
+				notification_sinks.clone(),
+			)
+			.with_telemetry(telemetry.clone()),
+		)
+		.network_bridge_rx(
+			NetworkBridgeRxSubsystem::new(
+				network_service.clone(),
+				authority_discovery_service.clone(),
+				Box::new(sync_service.clone()),
+				network_bridge_metrics,
+				peerset_protocol_names,
+				notification_services,
+				notification_sinks,
+				enable_approval_voting_parallel,
+			)
+			.with_reserved_authority_slots(reserved_authority_slots)
+			.with_telemetry(telemetry),
+		)
+		.availability_distribution(
+			AvailabilityDistributionSubsystem::new(
+				keystore.clone(),
+				IncomingRequestReceivers {
+					pov_req_receiver,
+					chunk_req_v1_receiver,
+					chunk_req_v2_receiver,
+				},
+				req_protocol_names.clone(),
+				Metrics::register(registry)?,
+			)
+			.with_serving_budget(chunk_serving_budget),
+		)
 		.availability_recovery(AvailabilityRecoverySubsystem::for_validator(
 			fetch_chunks_threshold,
 			available_data_req_receiver,
@@ -276,21 +342,28 @@ where
 			Metrics::register(registry)?,
 		))
 		.bitfield_distribution(BitfieldDistributionSubsystem::new(Metrics::register(registry)?))
-		.bitfield_signing(BitfieldSigningSubsystem::new(
-			keystore.clone(),
-			Metrics::register(registry)?,
-		))
-		.candidate_backing(CandidateBackingSubsystem::new(
-			keystore.clone(),
-			Metrics::register(registry)?,
-		))
+		.bitfield_signing(
+			BitfieldSigningSubsystem::new(keystore.clone(), Metrics::register(registry)?)
+				.with_config(bitfield_signing_config),
+		)
+		.candidate_backing(
+			CandidateBackingSubsystem::new(keystore.clone(), Metrics::register(registry)?)
+				.with_max_parallel_validations(max_parallel_validations(
+					&candidate_validation_config,
+				)),
+		)
 		.candidate_validation(CandidateValidationSubsystem::with_config(
 			candidate_validation_config,
 			keystore.clone(),
 			Metrics::register(registry)?, // candidate-validation metrics
 			Metrics::register(registry)?, // validation host metrics
 		))
-		.pvf_checker(PvfCheckerSubsystem::new(keystore.clone(), Metrics::register(registry)?))
+		.pvf_checker(PvfCheckerSubsystem::new(
+			keystore.clone(),
+			pvf_check_statement_receiver,
+			pvf_checker_config,
+			Metrics::register(registry)?,
+		))
 		.chain_api(ChainApiSubsystem::new(runtime_client.clone(), Metrics::register(registry)?))
 		.collation_generation(DummySubsystem)
 		.collator_protocol({
@@ -301,6 +374,9 @@ where
 					))),
 				IsParachainNode::No => ProtocolSide::Validator {
 					keystore: keystore.clone(),
+					precheck_receiver: collation_precheck_receiver,
+					push_receiver: collation_push_receiver,
+					push_collators: collation_push_collators,
 					eviction_policy: Default::default(),
 					metrics: Metrics::register(registry)?,
 				},
@@ -313,30 +389,41 @@ where
 			Metrics::register(registry)?,
 			spawner.clone(),
 		))
-		.statement_distribution(StatementDistributionSubsystem::new(
-			keystore.clone(),
-			candidate_req_v2_receiver,
-			Metrics::register(registry)?,
-		))
+		.statement_distribution(
+			StatementDistributionSubsystem::new(
+				keystore.clone(),
+				candidate_req_v2_receiver,
+				Metrics::register(registry)?,
+			)
+			.with_max_parallel_requests(
+				req_protocol_names.network_profile().max_parallel_attested_candidate_requests(),
+			),
+		)
 		.approval_distribution(ApprovalDistributionSubsystem::new(
 			approval_voting_parallel_metrics.approval_distribution_metrics(),
 			approval_voting_config.slot_duration_millis,
 			Arc::new(RealAssignmentCriteria {}),
 		))
-		.approval_voting(ApprovalVotingSubsystem::with_config(
-			approval_voting_config.clone(),
-			parachains_db.clone(),
-			keystore.clone(),
-			Box::new(sync_service.clone()),
-			approval_voting_parallel_metrics.approval_voting_metrics(),
-			Arc::new(spawner.clone()),
-		))
+		.approval_voting(
+			ApprovalVotingSubsystem::with_config(
+				approval_voting_config.clone(),
+				parachains_db.clone(),
+				keystore.clone(),
+				Box::new(sync_service.clone()),
+				approval_voting_parallel_metrics.approval_voting_metrics(),
+				Arc::new(spawner.clone()),
+			)
+			.with_no_show_stats(no_show_stats),
+		)
 		.approval_voting_parallel(DummySubsystem)
-		.gossip_support(GossipSupportSubsystem::new(
-			keystore.clone(),
-			authority_discovery_service.clone(),
-			Metrics::register(registry)?,
-		))
+		.gossip_support(
+			GossipSupportSubsystem::new(
+				keystore.clone(),
+				authority_discovery_service.clone(),
+				Metrics::register(registry)?,
+			)
+			.with_session_history(session_history),
+		)
 		.dispute_coordinator(DisputeCoordinatorSubsystem::new(
 			parachains_db.clone(),
 			dispute_coordinator_config,
@@ -344,12 +431,15 @@ where
 			Metrics::register(registry)?,
 			enable_approval_voting_parallel,
 		))
-		.dispute_distribution(DisputeDistributionSubsystem::new(
-			keystore.clone(),
-			dispute_req_receiver,
-			authority_discovery_service.clone(),
-			Metrics::register(registry)?,
-		))
+		.dispute_distribution(
+			DisputeDistributionSubsystem::new(
+				keystore.clone(),
+				dispute_req_receiver,
+				authority_discovery_service.clone(),
+				Metrics::register(registry)?,
+			)
+			.with_send_rate_limit(dispute_send_rate_limit),
+		)
 		.chain_selection(ChainSelectionSubsystem::new(chain_selection_config, parachains_db))
 		.prospective_parachains(ProspectiveParachainsSubsystem::new(Metrics::register(registry)?))
 		.activation_external_listeners(Default::default())
@@ -387,18 +477,31 @@ pub fn validator_with_parallel_overseer_builder<Spawner, RuntimeClient>(
 		req_protocol_names,
 		peerset_protocol_names,
 		notification_services,
+		precheck_collations: _,
 	}: OverseerGenArgs<Spawner, RuntimeClient>,
 	ExtendedOverseerGenArgs {
 		keystore,
 		parachains_db,
 		candidate_validation_config,
 		availability_config,
+		bitfield_signing_config,
 		pov_req_receiver,
 		chunk_req_v1_receiver,
 		chunk_req_v2_receiver,
+		chunk_serving_budget,
 		candidate_req_v2_receiver,
+		collation_precheck_receiver,
+		collation_push_receiver,
+		collation_push_collators,
+		pvf_check_statement_receiver,
+		pvf_checker_config,
 		approval_voting_config,
+		no_show_stats,
+		session_history,
 		dispute_req_receiver,
+		dispute_send_rate_limit,
+		reserved_authority_slots,
+		telemetry,
 		dispute_coordinator_config,
 		chain_selection_config,
 		fetch_chunks_threshold,
@@ -456,34 +559,44 @@ where
 	let approval_voting_parallel_metrics: ApprovalVotingParallelMetrics =
 		Metrics::register(registry)?;
 	let builder = Overseer::builder()
-		.network_bridge_tx(NetworkBridgeTxSubsystem::new(
-			network_service.clone(),
-			authority_discovery_service.clone(),
-			network_bridge_metrics.clone(),
-			req_protocol_names.clone(),
-			peerset_protocol_names.clone(),
-			notification_sinks.clone(),
-		))
-		.network_bridge_rx(NetworkBridgeRxSubsystem::new(
-			network_service.clone(),
-			authority_discovery_service.clone(),
-			Box::new(sync_service.clone()),
-			network_bridge_metrics,
-			peerset_protocol_names,
-			notification_services,
-			notification_sinks,
-			enable_approval_voting_parallel,
-		))
-		.availability_distribution(AvailabilityDistributionSubsystem::new(
-			keystore.clone(),
-			IncomingRequestReceivers {
-				pov_req_receiver,
-				chunk_req_v1_receiver,
-				chunk_req_v2_receiver,
-			},
-			req_protocol_names.clone(),
-			Metrics::register(registry)?,
-		))
+		.network_bridge_tx(
+			NetworkBridgeTxSubsystem::new(
+				network_service.clone(),
+				authority_discovery_service.clone(),
+				network_bridge_metrics.clone(),
+				req_protocol_names.clone(),
+				peerset_protocol_names.clone(),
+				notification_sinks.clone(),
+			)
+			.with_telemetry(telemetry.clone()),
+		)
+		.network_bridge_rx(
+			NetworkBridgeRxSubsystem::new(
+				network_service.clone(),
+				authority_discovery_service.clone(),
+				Box::new(sync_service.clone()),
+				network_bridge_metrics,
+				peerset_protocol_names,
+				notification_services,
+				notification_sinks,
+				enable_approval_voting_parallel,
+			)
+			.with_reserved_authority_slots(reserved_authority_slots)
+			.with_telemetry(telemetry),
+		)
+		.availability_distribution(
+			AvailabilityDistributionSubsystem::new(
+				keystore.clone(),
+				IncomingRequestReceivers {
+					pov_req_receiver,
+					chunk_req_v1_receiver,
+					chunk_req_v2_receiver,
+				},
+				req_protocol_names.clone(),
+				Metrics::register(registry)?,
+			)
+			.with_serving_budget(chunk_serving_budget),
+		)
 		.availability_recovery(AvailabilityRecoverySubsystem::for_validator(
 			fetch_chunks_threshold,
 			available_data_req_receiver,
@@ -497,21 +610,28 @@ where
 			Metrics::register(registry)?,
 		))
 		.bitfield_distribution(BitfieldDistributionSubsystem::new(Metrics::register(registry)?))
-		.bitfield_signing(BitfieldSigningSubsystem::new(
-			keystore.clone(),
-			Metrics::register(registry)?,
-		))
-		.candidate_backing(CandidateBackingSubsystem::new(
-			keystore.clone(),
-			Metrics::register(registry)?,
-		))
+		.bitfield_signing(
+			BitfieldSigningSubsystem::new(keystore.clone(), Metrics::register(registry)?)
+				.with_config(bitfield_signing_config),
+		)
+		.candidate_backing(
+			CandidateBackingSubsystem::new(keystore.clone(), Metrics::register(registry)?)
+				.with_max_parallel_validations(max_parallel_validations(
+					&candidate_validation_config,
+				)),
+		)
 		.candidate_validation(CandidateValidationSubsystem::with_config(
 			candidate_validation_config,
 			keystore.clone(),
 			Metrics::register(registry)?, // candidate-validation metrics
 			Metrics::register(registry)?, // validation host metrics
 		))
-		.pvf_checker(PvfCheckerSubsystem::new(keystore.clone(), Metrics::register(registry)?))
+		.pvf_checker(PvfCheckerSubsystem::new(
+			keystore.clone(),
+			pvf_check_statement_receiver,
+			pvf_checker_config,
+			Metrics::register(registry)?,
+		))
 		.chain_api(ChainApiSubsystem::new(runtime_client.clone(), Metrics::register(registry)?))
 		.collation_generation(DummySubsystem)
 		.collator_protocol({
@@ -522,6 +642,9 @@ where
 					))),
 				IsParachainNode::No => ProtocolSide::Validator {
 					keystore: keystore.clone(),
+					precheck_receiver: collation_precheck_receiver,
+					push_receiver: collation_push_receiver,
+					push_collators: collation_push_collators,
 					eviction_policy: Default::default(),
 					metrics: Metrics::register(registry)?,
 				},
@@ -534,27 +657,38 @@ where
 			Metrics::register(registry)?,
 			spawner.clone(),
 		))
-		.statement_distribution(StatementDistributionSubsystem::new(
-			keystore.clone(),
-			candidate_req_v2_receiver,
-			Metrics::register(registry)?,
-		))
+		.statement_distribution(
+			StatementDistributionSubsystem::new(
+				keystore.clone(),
+				candidate_req_v2_receiver,
+				Metrics::register(registry)?,
+			)
+			.with_max_parallel_requests(
+				req_protocol_names.network_profile().max_parallel_attested_candidate_requests(),
+			),
+		)
 		.approval_distribution(DummySubsystem)
 		.approval_voting(DummySubsystem)
-		.approval_voting_parallel(ApprovalVotingParallelSubsystem::with_config(
-			approval_voting_config,
-			parachains_db.clone(),
-			keystore.clone(),
-			Box::new(sync_service.clone()),
-			approval_voting_parallel_metrics,
-			spawner.clone(),
-			overseer_message_channel_capacity_override,
-		))
-		.gossip_support(GossipSupportSubsystem::new(
-			keystore.clone(),
-			authority_discovery_service.clone(),
-			Metrics::register(registry)?,
-		))
+		.approval_voting_parallel(
+			ApprovalVotingParallelSubsystem::with_config(
+				approval_voting_config,
+				parachains_db.clone(),
+				keystore.clone(),
+				Box::new(sync_service.clone()),
+				approval_voting_parallel_metrics,
+				spawner.clone(),
+				overseer_message_channel_capacity_override,
+			)
+			.with_no_show_stats(no_show_stats),
+		)
+		.gossip_support(
+			GossipSupportSubsystem::new(
+				keystore.clone(),
+				authority_discovery_service.clone(),
+				Metrics::register(registry)?,
+			)
+			.with_session_history(session_history),
+		)
 		.dispute_coordinator(DisputeCoordinatorSubsystem::new(
 			parachains_db.clone(),
 			dispute_coordinator_config,
@@ -562,12 +696,15 @@ where
 			Metrics::register(registry)?,
 			enable_approval_voting_parallel,
 		))
-		.dispute_distribution(DisputeDistributionSubsystem::new(
-			keystore.clone(),
-			dispute_req_receiver,
-			authority_discovery_service.clone(),
-			Metrics::register(registry)?,
-		))
+		.dispute_distribution(
+			DisputeDistributionSubsystem::new(
+				keystore.clone(),
+				dispute_req_receiver,
+				authority_discovery_service.clone(),
+				Metrics::register(registry)?,
+			)
+			.with_send_rate_limit(dispute_send_rate_limit),
+		)
 		.chain_selection(ChainSelectionSubsystem::new(chain_selection_config, parachains_db))
 		.prospective_parachains(ProspectiveParachainsSubsystem::new(Metrics::register(registry)?))
 		.activation_external_listeners(Default::default())
@@ -601,6 +738,7 @@ pub fn collator_overseer_builder<Spawner, RuntimeClient>(
 		req_protocol_names,
 		peerset_protocol_names,
 		notification_services,
+		precheck_collations,
 	}: OverseerGenArgs<Spawner, RuntimeClient>,
 ) -> Result<
 	InitializedOverseerBuilder<
@@ -695,6 +833,7 @@ where
 					peer_id: network_service.local_peer_id(),
 					collator_pair,
 					request_receiver_v2: collation_req_v2_receiver,
+					precheck_collations,
 					metrics: Metrics::register(registry)?,
 				},
 				IsParachainNode::FullNode => ProtocolSide::None,
@@ -730,6 +869,45 @@ where
 	Ok(builder)
 }
 
+/// Wrap the subsystems of an overseer builder into [`TimedSubsystem`]s.
+///
+/// The pvf checker and bitfield signing subsystems don't receive messages and aren't wrapped.
+macro_rules! with_message_timing {
+	($builder:expr, $metrics:expr) => {{
+		let metrics: MessageProcessingMetrics = $metrics;
+		with_message_timing!(@replace $builder, metrics, [
+			replace_candidate_validation,
+			replace_candidate_backing,
+			replace_statement_distribution,
+			replace_availability_distribution,
+			replace_availability_recovery,
+			replace_bitfield_distribution,
+			replace_provisioner,
+			replace_runtime_api,
+			replace_availability_store,
+			replace_network_bridge_rx,
+			replace_network_bridge_tx,
+			replace_chain_api,
+			replace_collation_generation,
+			replace_collator_protocol,
+			replace_approval_distribution,
+			replace_approval_voting,
+			replace_approval_voting_parallel,
+			replace_gossip_support,
+			replace_dispute_coordinator,
+			replace_dispute_distribution,
+			replace_chain_selection,
+			replace_prospective_parachains,
+		])
+	}};
+	(@replace $builder:expr, $metrics:ident, [$($replace:ident),* $(,)?]) => {
+		$builder$(.$replace({
+			let metrics = $metrics.clone();
+			move |subsystem| TimedSubsystem::new(subsystem, metrics)
+		}))*
+	};
+}
+
 /// Trait for the `fn` generating the overseer.
 pub trait OverseerGen {
 	/// Overwrite the full generation of the overseer, including the subsystems.
@@ -766,12 +944,16 @@ impl OverseerGen for ValidatorOverseerGen {
 			"create validator overseer as mandatory extended arguments were not provided"
 				.to_owned(),
 		)))?;
+		let processing_metrics = MessageProcessingMetrics::register(args.registry)?;
 		if ext_args.enable_approval_voting_parallel {
-			validator_with_parallel_overseer_builder(args, ext_args)?
-				.build_with_connector(connector)
-				.map_err(|e| e.into())
+			with_message_timing!(
+				validator_with_parallel_overseer_builder(args, ext_args)?,
+				processing_metrics
+			)
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
 		} else {
-			validator_overseer_builder(args, ext_args)?
+			with_message_timing!(validator_overseer_builder(args, ext_args)?, processing_metrics)
 				.build_with_connector(connector)
 				.map_err(|e| e.into())
 		}
@@ -792,7 +974,8 @@ impl OverseerGen for CollatorOverseerGen {
 		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
 		Spawner: 'static + SpawnNamed + Clone + Unpin,
 	{
-		collator_overseer_builder(args)?
+		let processing_metrics = MessageProcessingMetrics::register(args.registry)?;
+		with_message_timing!(collator_overseer_builder(args)?, processing_metrics)
 			.build_with_connector(connector)
 			.map_err(|e| e.into())
 	}
@@ -1451,3 +1451,170 @@ pub enum ProspectiveParachainsMessage {
 		oneshot::Sender<Option<PersistedValidationData>>,
 	),
 }
+
+/// The static names of a subsystem message and of its variants, e.g. to label metrics.
+pub trait MessageVariant {
+	/// The name of the message type.
+	const MESSAGE_TYPE: &'static str;
+
+	/// The name of the variant of this message.
+	fn variant(&self) -> &'static str;
+}
+
+macro_rules! impl_message_variant {
+	($($message:ident { $($variant:ident),* $(,)? })*) => {
+		$(
+			impl MessageVariant for $message {
+				const MESSAGE_TYPE: &'static str = stringify!($message);
+
+				fn variant(&self) -> &'static str {
+					match *self {
+						$(Self::$variant { .. } => stringify!($variant),)*
+					}
+				}
+			}
+		)*
+	};
+}
+
+impl_message_variant! {
+	CandidateValidationMessage { ValidateFromExhaustive, PreCheck }
+	CandidateBackingMessage { GetBackableCandidates, CanSecond, Second, Statement }
+	StatementDistributionMessage { Share, Backed, NetworkBridgeUpdate }
+	AvailabilityDistributionMessage { FetchPoV }
+	AvailabilityRecoveryMessage { RecoverAvailableData }
+	BitfieldDistributionMessage { DistributeBitfield, NetworkBridgeUpdate }
+	ProvisionerMessage { RequestInherentData, ProvisionableData }
+	RuntimeApiRequest {
+		Version,
+		Authorities,
+		Validators,
+		ValidatorGroups,
+		AvailabilityCores,
+		PersistedValidationData,
+		AssumedValidationData,
+		CheckValidationOutputs,
+		SessionIndexForChild,
+		ValidationCode,
+		ValidationCodeByHash,
+		CandidatePendingAvailability,
+		CandidateEvents,
+		SessionExecutorParams,
+		SessionInfo,
+		DmqContents,
+		InboundHrmpChannelsContents,
+		CurrentBabeEpoch,
+		FetchOnChainVotes,
+		SubmitPvfCheckStatement,
+		PvfsRequirePrecheck,
+		ValidationCodeHash,
+		Disputes,
+		UnappliedSlashes,
+		KeyOwnershipProof,
+		SubmitReportDisputeLost,
+		MinimumBackingVotes,
+		DisabledValidators,
+		ParaBackingState,
+		AsyncBackingParams,
+		NodeFeatures,
+		ApprovalVotingParams,
+		ClaimQueue,
+		CandidatesPendingAvailability,
+		BackingConstraints,
+		SchedulingLookahead,
+		ValidationCodeBombLimit,
+	}
+	AvailabilityStoreMessage {
+		QueryAvailableData,
+		QueryDataAvailability,
+		QueryChunk,
+		QueryChunkSize,
+		QueryAllChunks,
+		QueryChunkAvailability,
+		StoreChunk,
+		StoreAvailableData,
+	}
+	NetworkBridgeRxMessage { NewGossipTopology, UpdatedAuthorityIds }
+	NetworkBridgeTxMessage {
+		ReportPeer,
+		DisconnectPeer,
+		SendValidationMessage,
+		SendCollationMessage,
+		SendValidationMessages,
+		SendCollationMessages,
+		SendRequests,
+		ConnectToValidators,
+		ConnectToResolvedValidators,
+		AddToResolvedValidators,
+	}
+	ChainApiMessage {
+		BlockNumber,
+		BlockHeader,
+		BlockWeight,
+		FinalizedBlockHash,
+		FinalizedBlockNumber,
+		Ancestors,
+	}
+	CollationGenerationMessage { Initialize, Reinitialize, SubmitCollation }
+	CollatorProtocolMessage {
+		CollateOn,
+		DistributeCollation,
+		NetworkBridgeUpdate,
+		Invalid,
+		Seconded,
+	}
+	ApprovalDistributionMessage {
+		NewBlocks,
+		DistributeAssignment,
+		DistributeApproval,
+		NetworkBridgeUpdate,
+		GetApprovalSignatures,
+		ApprovalCheckingLagUpdate,
+	}
+	ApprovalVotingMessage {
+		ImportAssignment,
+		ImportApproval,
+		ApprovedAncestor,
+		GetApprovalSignaturesForCandidate,
+	}
+	ApprovalVotingParallelMessage {
+		ApprovedAncestor,
+		GetApprovalSignaturesForCandidate,
+		NewBlocks,
+		DistributeAssignment,
+		DistributeApproval,
+		NetworkBridgeUpdate,
+		GetApprovalSignatures,
+		ApprovalCheckingLagUpdate,
+	}
+	GossipSupportMessage { NetworkBridgeUpdate }
+	DisputeCoordinatorMessage {
+		ImportStatements,
+		RecentDisputes,
+		ActiveDisputes,
+		QueryCandidateVotes,
+		IssueLocalStatement,
+		DetermineUndisputedChain,
+	}
+	DisputeDistributionMessage { SendDispute }
+	ChainSelectionMessage { Approved, Leaves, BestLeafContaining, RevertBlocks }
+	ProspectiveParachainsMessage {
+		IntroduceSecondedCandidate,
+		CandidateBacked,
+		GetBackableCandidates,
+		GetHypotheticalMembership,
+		GetMinimumRelayParents,
+		GetProspectiveValidationData,
+	}
+}
+
+impl MessageVariant for RuntimeApiMessage {
+	const MESSAGE_TYPE: &'static str = "RuntimeApiMessage";
+
+	// All runtime API messages are requests, they are named by the request.
+	fn variant(&self) -> &'static str {
+		match *self {
+			Self::Request(_, ref request) => request.variant(),
+		}
+	}
+}
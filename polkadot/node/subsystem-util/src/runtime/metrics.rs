@@ -0,0 +1,112 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics of the caches of a `RuntimeInfo`.
+
+use polkadot_node_metrics::metrics::prometheus::{
+	self, CounterVec, Opts, PrometheusError, Registry, U64,
+};
+
+/// The caches of a `RuntimeInfo`, used as the `cache` label of the metrics.
+#[derive(Clone, Copy)]
+pub(super) enum Cache {
+	SessionIndex,
+	SessionInfo,
+	DisabledValidators,
+	NodeFeatures,
+}
+
+impl Cache {
+	fn label(self) -> &'static str {
+		match self {
+			Cache::SessionIndex => "session_index",
+			Cache::SessionInfo => "session_info",
+			Cache::DisabledValidators => "disabled_validators",
+			Cache::NodeFeatures => "node_features",
+		}
+	}
+}
+
+#[derive(Clone)]
+struct MetricsInner {
+	hits: CounterVec<U64>,
+	misses: CounterVec<U64>,
+}
+
+/// Cache hit and miss counters of a `RuntimeInfo`.
+///
+/// Each subsystem owning a `RuntimeInfo` registers its own instance, distinguished by the
+/// `subsystem` label.
+#[derive(Clone, Default)]
+pub struct Metrics(Option<MetricsInner>);
+
+impl Metrics {
+	/// Register the metrics of the `RuntimeInfo` of `subsystem`.
+	pub fn register(registry: &Registry, subsystem: &'static str) -> Result<Self, PrometheusError> {
+		let counter = |name: &str, help: &str| {
+			prometheus::register(
+				CounterVec::new(
+					Opts::new(name, help).const_label("subsystem", subsystem),
+					&["cache"],
+				)?,
+				registry,
+			)
+		};
+
+		Ok(Metrics(Some(MetricsInner {
+			hits: counter(
+				"polkadot_parachain_runtime_info_cache_hits_total",
+				"Number of runtime information lookups served from the cache.",
+			)?,
+			misses: counter(
+				"polkadot_parachain_runtime_info_cache_misses_total",
+				"Number of runtime information lookups that required a runtime API request.",
+			)?,
+		})))
+	}
+
+	pub(super) fn on_lookup(&self, cache: Cache, hit: bool) {
+		if let Some(metrics) = &self.0 {
+			let counter = if hit { &metrics.hits } else { &metrics.misses };
+			counter.with_label_values(&[cache.label()]).inc();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn subsystems_share_registry() {
+		let registry = Registry::new();
+
+		let first = Metrics::register(&registry, "first").unwrap();
+		let second = Metrics::register(&registry, "second").unwrap();
+		assert!(Metrics::register(&registry, "first").is_err());
+
+		first.on_lookup(Cache::SessionIndex, true);
+		first.on_lookup(Cache::SessionIndex, false);
+		second.on_lookup(Cache::SessionInfo, true);
+
+		let hits = |metrics: &Metrics, cache: Cache| {
+			metrics.0.as_ref().unwrap().hits.with_label_values(&[cache.label()]).get()
+		};
+		assert_eq!(hits(&first, Cache::SessionIndex), 1);
+		assert_eq!(hits(&first, Cache::SessionInfo), 0);
+		assert_eq!(hits(&second, Cache::SessionInfo), 1);
+	}
+}
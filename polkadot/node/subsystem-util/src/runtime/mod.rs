@@ -55,6 +55,12 @@ mod error;
 use error::Result;
 pub use error::{recv_runtime, Error, FatalError, JfyiError};
 
+/// Cache hit and miss counters.
+mod metrics;
+
+use metrics::Cache;
+pub use metrics::Metrics;
+
 const LOG_TARGET: &'static str = "parachain::runtime-info";
 
 /// Configuration for construction a `RuntimeInfo`.
@@ -65,6 +71,9 @@ pub struct Config {
 	pub keystore: Option<KeystorePtr>,
 
 	/// How many sessions should we keep in the cache?
+	///
+	/// The session index cache keeps entries for at least 10 relay parents, see
+	/// [`RuntimeInfo::with_session_index_cache_size`] for keeping more.
 	pub session_cache_lru_size: u32,
 }
 
@@ -86,12 +95,18 @@ pub struct RuntimeInfo {
 	/// Look up cached sessions by `SessionIndex`.
 	session_info_cache: LruMap<SessionIndex, ExtendedSessionInfo>,
 
+	/// Node features of the sessions, for the subsystems which don't need the whole session info.
+	node_features_cache: LruMap<SessionIndex, NodeFeatures>,
+
 	/// Unpin handle of *some* block in the session.
 	/// Only blocks pinned explicitly by `pin_block` are stored here.
 	pinned_blocks: LruMap<SessionIndex, UnpinHandle>,
 
 	/// Key store for determining whether we are a validator and what `ValidatorIndex` we have.
 	keystore: Option<KeystorePtr>,
+
+	/// Cache hit and miss counters.
+	metrics: Metrics,
 }
 
 /// `SessionInfo` with additional useful data for validator nodes.
@@ -102,7 +117,10 @@ pub struct ExtendedSessionInfo {
 	pub validator_info: ValidatorInfo,
 	/// Session executor parameters
 	pub executor_params: ExecutorParams,
-	/// Node features
+	/// Node features of the session.
+	///
+	/// Fetched and cached together with the session info, so there is no need to request them
+	/// separately.
 	pub node_features: NodeFeatures,
 }
 
@@ -126,6 +144,12 @@ impl Default for Config {
 	}
 }
 
+impl Default for RuntimeInfo {
+	fn default() -> Self {
+		Self::new_with_config(Config::default())
+	}
+}
+
 impl RuntimeInfo {
 	/// Create a new `RuntimeInfo` for convenient runtime fetches.
 	pub fn new(keystore: Option<KeystorePtr>) -> Self {
@@ -137,12 +161,29 @@ impl RuntimeInfo {
 		Self {
 			session_index_cache: LruMap::new(ByLength::new(cfg.session_cache_lru_size.max(10))),
 			session_info_cache: LruMap::new(ByLength::new(cfg.session_cache_lru_size)),
+			node_features_cache: LruMap::new(ByLength::new(cfg.session_cache_lru_size)),
 			disabled_validators_cache: LruMap::new(ByLength::new(100)),
 			pinned_blocks: LruMap::new(ByLength::new(cfg.session_cache_lru_size)),
 			keystore: cfg.keystore,
+			metrics: Metrics::default(),
 		}
 	}
 
+	/// Keep the session index of up to `size` relay parents in the cache.
+	///
+	/// The session index is looked up for every relay parent a subsystem deals with, so during
+	/// long finality lags it might be worth caching more than a handful of them.
+	pub fn with_session_index_cache_size(mut self, size: u32) -> Self {
+		self.session_index_cache = LruMap::new(ByLength::new(size));
+		self
+	}
+
+	/// Count cache hits and misses with `metrics`.
+	pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+		self.metrics = metrics;
+		self
+	}
+
 	/// Returns the session index expected at any child of the `parent` block.
 	/// This does not return the session index for the `parent` block.
 	pub async fn get_session_index_for_child<Sender>(
@@ -153,8 +194,10 @@ impl RuntimeInfo {
 	where
 		Sender: SubsystemSender<RuntimeApiMessage>,
 	{
-		match self.session_index_cache.get(&parent) {
-			Some(index) => Ok(*index),
+		let cached = self.session_index_cache.get(&parent).copied();
+		self.metrics.on_lookup(Cache::SessionIndex, cached.is_some());
+		match cached {
+			Some(index) => Ok(index),
 			None => {
 				let index =
 					recv_runtime(request_session_index_for_child(parent, sender).await).await?;
@@ -198,7 +241,9 @@ impl RuntimeInfo {
 	where
 		Sender: SubsystemSender<RuntimeApiMessage>,
 	{
-		match self.disabled_validators_cache.get(&relay_parent).cloned() {
+		let cached = self.disabled_validators_cache.get(&relay_parent).cloned();
+		self.metrics.on_lookup(Cache::DisabledValidators, cached.is_some());
+		match cached {
 			Some(result) => Ok(result),
 			None => {
 				let disabled_validators =
@@ -222,7 +267,9 @@ impl RuntimeInfo {
 	where
 		Sender: SubsystemSender<RuntimeApiMessage>,
 	{
-		if self.session_info_cache.get(&session_index).is_none() {
+		let cached = self.session_info_cache.get(&session_index).is_some();
+		self.metrics.on_lookup(Cache::SessionInfo, cached);
+		if !cached {
 			let session_info =
 				recv_runtime(request_session_info(parent, session_index, sender).await)
 					.await?
@@ -235,12 +282,7 @@ impl RuntimeInfo {
 
 			let validator_info = self.get_validator_info(&session_info)?;
 
-			let node_features =
-				request_node_features(parent, session_index, sender).await.await??;
-			let last_set_index = node_features.iter_ones().last().unwrap_or_default();
-			if last_set_index >= FeatureIndex::FirstUnassigned as usize {
-				gum::warn!(target: LOG_TARGET, "Runtime requires feature bit {} that node doesn't support, please upgrade node version", last_set_index);
-			}
+			let node_features = self.get_node_features(sender, parent, session_index).await?;
 
 			let full_info = ExtendedSessionInfo {
 				session_info,
@@ -257,6 +299,41 @@ impl RuntimeInfo {
 			.expect("We just put the value there. qed."))
 	}
 
+	/// Get the node features of a session by session index.
+	///
+	/// The node features are cached with the session info, subsystems should get them from here
+	/// rather than requesting them from the runtime.
+	pub async fn get_node_features<Sender>(
+		&mut self,
+		sender: &mut Sender,
+		parent: Hash,
+		session_index: SessionIndex,
+	) -> Result<NodeFeatures>
+	where
+		Sender: SubsystemSender<RuntimeApiMessage>,
+	{
+		if let Some(info) = self.session_info_cache.peek(&session_index) {
+			return Ok(info.node_features.clone())
+		}
+
+		let cached = self.node_features_cache.get(&session_index).cloned();
+		self.metrics.on_lookup(Cache::NodeFeatures, cached.is_some());
+		match cached {
+			Some(node_features) => Ok(node_features),
+			None => {
+				let node_features =
+					request_node_features(parent, session_index, sender).await.await??;
+				let last_set_index = node_features.iter_ones().last().unwrap_or_default();
+				if last_set_index >= FeatureIndex::FirstUnassigned as usize {
+					gum::warn!(target: LOG_TARGET, "Runtime requires feature bit {} that node doesn't support, please upgrade node version", last_set_index);
+				}
+
+				self.node_features_cache.insert(session_index, node_features.clone());
+				Ok(node_features)
+			},
+		}
+	}
+
 	/// Convenience function for checking the signature of something signed.
 	pub async fn check_signature<Sender, Payload, RealPayload>(
 		&mut self,
@@ -105,8 +105,21 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					execute_workers_max_num: None,
 					prepare_workers_hard_max_num: None,
 					prepare_workers_soft_max_num: None,
+					pvf_cross_check_percent: None,
+					pvf_local_precheck_percent: None,
 					enable_approval_voting_parallel: false,
 					keep_finalized_for: None,
+					dispute_window: None,
+					session_history_depth: None,
+					network_profile: polkadot_service::NetworkProfile::Datacenter,
+					collation_push_collators: Default::default(),
+					precheck_collations: false,
+					evict_inactive_validation_peers: false,
+					reserved_authority_slots: 0,
+					grandpa_vote_history: 0,
+					bitfield_signing_config: Default::default(),
+					chunk_serving_budget: Default::default(),
+					babe_secondary_slot_claims: true,
 				},
 			),
 		sc_network::config::NetworkBackendType::Litep2p =>
@@ -128,8 +141,21 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					execute_workers_max_num: None,
 					prepare_workers_hard_max_num: None,
 					prepare_workers_soft_max_num: None,
+					pvf_cross_check_percent: None,
+					pvf_local_precheck_percent: None,
 					enable_approval_voting_parallel: false,
 					keep_finalized_for: None,
+					dispute_window: None,
+					session_history_depth: None,
+					network_profile: polkadot_service::NetworkProfile::Datacenter,
+					collation_push_collators: Default::default(),
+					precheck_collations: false,
+					evict_inactive_validation_peers: false,
+					reserved_authority_slots: 0,
+					grandpa_vote_history: 0,
+					bitfield_signing_config: Default::default(),
+					chunk_serving_budget: Default::default(),
+					babe_secondary_slot_claims: true,
 				},
 			),
 	}
@@ -129,6 +129,7 @@ use alloc::{
 	collections::{btree_map::BTreeMap, vec_deque::VecDeque},
 	vec::Vec,
 };
+use codec::Codec;
 use polkadot_core_primitives as pcp;
 use polkadot_parachain_primitives::primitives as ppp;
 
@@ -316,4 +317,17 @@ sp_api::decl_runtime_apis! {
 
 
 	}
+
+	/// The API for querying the price of on-demand coretime.
+	pub trait OnDemandApi<Balance>
+	where
+		Balance: Codec
+	{
+		/// The current spot price of on-demand coretime and its projection for the next
+		/// `blocks` blocks.
+		///
+		/// The projection assumes the order queue has `queue_size` orders, or its current size
+		/// if `None`. The number of projected blocks is capped by the runtime.
+		fn spot_price(blocks: u32, queue_size: Option<u32>) -> vstaging::OnDemandSpotPrice<Balance>;
+	}
 }
@@ -120,6 +120,18 @@ pub enum ExecutorParam {
 	/// Enables WASM bulk memory proposal
 	#[codec(index = 7)]
 	WasmExtBulkMemory,
+	/// Relay-VRF delay assignment parameters of approval voting, overriding the ones of the
+	/// session info. The values are the number of delay tranches and the width of the zeroth
+	/// delay tranche. The number of delay tranches must not be 0.
+	///
+	/// Must only be set once all validators are able to decode it, since validators using
+	/// different values reject each other's assignments.
+	#[codec(index = 8)]
+	ApprovalRelayVrfDelay(u32, u32),
+	/// Number of approval voting ticks (500 ms) an assigned validator is given, on top of the
+	/// `no_show_slots` of the session info, before being considered a no-show.
+	#[codec(index = 9)]
+	ApprovalNoShowSlack(u32),
 }
 
 /// Possible inconsistencies of executor params.
@@ -242,6 +254,8 @@ impl ExecutorParams {
 				PvfPrepTimeout(..) => Some(param),
 				PvfExecTimeout(..) => None,
 				WasmExtBulkMemory => Some(param),
+				ApprovalRelayVrfDelay(..) => None,
+				ApprovalNoShowSlack(..) => None,
 			})
 			.for_each(|p| enc.extend(p.encode()));
 
@@ -282,6 +296,27 @@ impl ExecutorParams {
 		None
 	}
 
+	/// Returns the relay-VRF delay assignment parameters of approval voting, if any, as the
+	/// number of delay tranches and the width of the zeroth delay tranche
+	pub fn approval_relay_vrf_delay(&self) -> Option<(u32, u32)> {
+		for param in &self.0 {
+			if let ExecutorParam::ApprovalRelayVrfDelay(n_delay_tranches, zeroth_width) = param {
+				return Some((*n_delay_tranches, *zeroth_width))
+			}
+		}
+		None
+	}
+
+	/// Returns the approval voting no-show slack in ticks, if any
+	pub fn approval_no_show_slack(&self) -> Option<u32> {
+		for param in &self.0 {
+			if let ExecutorParam::ApprovalNoShowSlack(slack) = param {
+				return Some(*slack)
+			}
+		}
+		None
+	}
+
 	/// Check params coherence.
 	pub fn check_consistency(&self) -> Result<(), ExecutorParamError> {
 		use ExecutorParam::*;
@@ -325,6 +360,8 @@ impl ExecutorParams {
 					PvfExecKind::Approval => "PvfExecKind::Approval",
 				},
 				WasmExtBulkMemory => "WasmExtBulkMemory",
+				ApprovalRelayVrfDelay(..) => "ApprovalRelayVrfDelay",
+				ApprovalNoShowSlack(_) => "ApprovalNoShowSlack",
 			};
 
 			match *param {
@@ -359,6 +396,19 @@ impl ExecutorParams {
 				WasmExtBulkMemory => {
 					check!(param_ident, 1);
 				},
+
+				ApprovalRelayVrfDelay(n_delay_tranches, zeroth_width) => {
+					check!(
+						param_ident,
+						n_delay_tranches,
+						n_delay_tranches == 0 ||
+							n_delay_tranches.checked_add(zeroth_width).is_none(),
+					);
+				},
+
+				ApprovalNoShowSlack(val) => {
+					check!(param_ident, val);
+				},
 			}
 		}
 
@@ -429,6 +479,8 @@ fn ensure_prep_hash_changes() {
 			PvfExecTimeout(PvfExecKind::Backing, 0),
 			PvfExecTimeout(PvfExecKind::Approval, 0),
 			WasmExtBulkMemory,
+			ApprovalRelayVrfDelay(0, 0),
+			ApprovalNoShowSlack(0),
 		][..],
 	);
 
@@ -452,6 +504,8 @@ fn ensure_prep_hash_changes() {
 			PvfExecTimeout(_, _) => continue,
 			WasmExtBulkMemory =>
 				(ExecutorParams::default(), ExecutorParams::from(&[WasmExtBulkMemory][..])),
+			ApprovalRelayVrfDelay(..) => continue,
+			ApprovalNoShowSlack(_) => continue,
 		};
 
 		assert_ne!(ep1.prep_hash(), ep2.prep_hash());
@@ -2100,10 +2100,16 @@ pub mod node_features {
 		/// See [RFC-103](https://github.com/polkadot-fellows/RFCs/pull/103) for details.
 		/// Only enable if at least 2/3 of nodes support the feature.
 		CandidateReceiptV2 = 3,
+		// Index 4 is reserved, it was assigned to a gossip topology layout that was dropped.
+		/// Collators push their collations to a validator of the backing group over
+		/// `/req_collation_push/1`, sparing the advertisement and the fetch of the collation.
+		/// Only meant for networks whose collators are permissioned, as pushed collations are not
+		/// subject to the spam protection of advertisements.
+		CollationPush = 5,
 		/// First unassigned feature bit.
 		/// Every time a new feature flag is assigned it should take this value.
 		/// and this should be incremented.
-		FirstUnassigned = 4,
+		FirstUnassigned = 6,
 	}
 }
 
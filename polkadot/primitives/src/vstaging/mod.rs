@@ -1035,6 +1035,20 @@ pub fn transpose_claim_queue(
 	per_para_claim_queue
 }
 
+/// The spot price of on-demand coretime, along with its projection for the next blocks.
+#[derive(Clone, Encode, Decode, TypeInfo, RuntimeDebug, PartialEq, Eq)]
+pub struct OnDemandSpotPrice<Balance> {
+	/// The price of an order placed in the current block.
+	pub spot_price: Balance,
+	/// The queue size the projection assumes.
+	pub queue_size: u32,
+	/// The maximum size of the order queue.
+	pub queue_capacity: u32,
+	/// The spot price of the next blocks, starting with the next one, assuming the size of the
+	/// order queue stays at `queue_size`.
+	pub projection: Vec<Balance>,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
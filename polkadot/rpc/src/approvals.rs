@@ -0,0 +1,98 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC methods exposing the statistics of the approval voting subsystem.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use polkadot_node_primitives::approval::stats::{NoShowStatsHandle, ValidatorNoShowStats};
+use polkadot_primitives::SessionIndex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The no-show statistics of the validators of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoShowStatsReport {
+	/// The session the statistics are for.
+	pub session: SessionIndex,
+	/// The statistics of the validators with at least one no-show or late approval, by
+	/// validator index.
+	pub validators: BTreeMap<u32, ValidatorNoShowStats>,
+}
+
+/// Provides RPC methods to query the statistics of the approval voting subsystem.
+#[rpc(client, server)]
+pub trait ApprovalsApi {
+	/// Returns the no-shows and late approvals of the validators in `session`, or in the latest
+	/// session if none is given, as observed by this node since it started.
+	///
+	/// Returns `null` if the session is not one of the recent sessions tracked by the node.
+	#[method(name = "approvals_noShowStats")]
+	fn no_show_stats(&self, session: Option<SessionIndex>) -> RpcResult<Option<NoShowStatsReport>>;
+}
+
+/// Implements [`ApprovalsApiServer`] on top of the statistics recorded by approval voting.
+pub struct Approvals {
+	no_show_stats: NoShowStatsHandle,
+}
+
+impl Approvals {
+	/// Create a new [`Approvals`] RPC handler.
+	pub fn new(no_show_stats: NoShowStatsHandle) -> Self {
+		Self { no_show_stats }
+	}
+}
+
+impl ApprovalsApiServer for Approvals {
+	fn no_show_stats(&self, session: Option<SessionIndex>) -> RpcResult<Option<NoShowStatsReport>> {
+		let Some(session) = session.or_else(|| self.no_show_stats.latest_session()) else {
+			return Ok(None)
+		};
+
+		Ok(self.no_show_stats.session(session).map(|stats| NoShowStatsReport {
+			session,
+			validators: stats.into_iter().map(|(index, stats)| (index.0, stats)).collect(),
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_primitives::ValidatorIndex;
+
+	#[test]
+	fn reports_latest_session_by_default() {
+		let handle = NoShowStatsHandle::default();
+		let rpc = Approvals::new(handle.clone());
+		assert_eq!(rpc.no_show_stats(None).unwrap(), None);
+
+		handle.record_no_show(3, ValidatorIndex(7));
+		handle.record_late_approval(4, ValidatorIndex(1));
+
+		let report = rpc.no_show_stats(None).unwrap().unwrap();
+		assert_eq!(report.session, 4);
+		assert_eq!(
+			report.validators,
+			BTreeMap::from([(1, ValidatorNoShowStats { no_shows: 0, late_approvals: 1 })])
+		);
+		assert_eq!(
+			rpc.no_show_stats(Some(3)).unwrap().unwrap().validators,
+			BTreeMap::from([(7, ValidatorNoShowStats { no_shows: 1, late_approvals: 0 })])
+		);
+		assert_eq!(rpc.no_show_stats(Some(2)).unwrap(), None);
+	}
+}
@@ -21,6 +21,9 @@
 use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
+use polkadot_node_primitives::{
+	approval::stats::NoShowStatsHandle, session_history::SessionHistoryHandle,
+};
 use polkadot_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Nonce};
 use sc_client_api::AuxStore;
 use sc_consensus_beefy::communication::notification::{
@@ -38,6 +41,10 @@ use sp_consensus_babe::BabeApi;
 use sp_consensus_beefy::AuthorityIdBound;
 use sp_keystore::KeystorePtr;
 
+pub mod approvals;
+pub mod on_demand;
+pub mod session_history;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = RpcModule<()>;
 
@@ -53,6 +60,8 @@ pub struct BabeDeps {
 pub struct GrandpaDeps<B> {
 	/// Voting round info.
 	pub shared_voter_state: sc_consensus_grandpa::SharedVoterState,
+	/// History of the votes observed by the voter.
+	pub vote_history: sc_consensus_grandpa::SharedVoteHistory<Block>,
 	/// Authority set info.
 	pub shared_authority_set: sc_consensus_grandpa::SharedAuthoritySet<Hash, BlockNumber>,
 	/// Receives notifications about justification events from Grandpa.
@@ -91,17 +100,26 @@ pub struct FullDeps<C, P, SC, B, AuthorityId: AuthorityIdBound> {
 	pub beefy: BeefyDeps<AuthorityId>,
 	/// Backend used by the node.
 	pub backend: Arc<B>,
+	/// The no-show statistics recorded by approval voting.
+	pub no_show_stats: NoShowStatsHandle,
+	/// The validator sets of the recent sessions, recorded by gossip support and the RPCs.
+	pub session_history: SessionHistoryHandle,
 }
 
 /// Instantiate all RPC extensions.
 pub fn create_full<C, P, SC, B, AuthorityId>(
-	FullDeps { client, pool, select_chain, chain_spec, babe, grandpa, beefy, backend }: FullDeps<
-		C,
-		P,
-		SC,
-		B,
-		AuthorityId,
-	>,
+	FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		babe,
+		grandpa,
+		beefy,
+		backend,
+		no_show_stats,
+		session_history,
+	}: FullDeps<C, P, SC, B, AuthorityId>,
 ) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
@@ -114,6 +132,8 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash, BlockNumber>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: polkadot_primitives::runtime_api::OnDemandApi<Block, Balance>,
+	C::Api: polkadot_primitives::runtime_api::ParachainHost<Block>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
@@ -123,12 +143,15 @@ where
 	AuthorityId: AuthorityIdBound,
 	<AuthorityId as RuntimeAppPublic>::Signature: Send + Sync,
 {
+	use approvals::{Approvals, ApprovalsApiServer};
 	use mmr_rpc::{Mmr, MmrApiServer};
+	use on_demand::{OnDemand, OnDemandApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_beefy_rpc::{Beefy, BeefyApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
 	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
+	use session_history::{SessionHistory, SessionHistoryApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 	use substrate_state_trie_migration_rpc::{StateMigration, StateMigrationApiServer};
 
@@ -136,6 +159,7 @@ where
 	let BabeDeps { babe_worker_handle, keystore } = babe;
 	let GrandpaDeps {
 		shared_voter_state,
+		vote_history,
 		shared_authority_set,
 		justification_stream,
 		subscription_executor,
@@ -145,6 +169,8 @@ where
 	io.merge(StateMigration::new(client.clone(), backend.clone()).into_rpc())?;
 	io.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(OnDemand::new(client.clone()).into_rpc())?;
+	io.merge(SessionHistory::new(client.clone(), session_history).into_rpc())?;
 	io.merge(
 		Mmr::new(
 			client.clone(),
@@ -164,6 +190,7 @@ where
 			shared_voter_state,
 			justification_stream,
 			finality_provider,
+			vote_history,
 		)
 		.into_rpc(),
 	)?;
@@ -179,6 +206,7 @@ where
 		)?
 		.into_rpc(),
 	)?;
+	io.merge(Approvals::new(no_show_stats).into_rpc())?;
 
 	Ok(io)
 }
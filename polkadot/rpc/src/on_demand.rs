@@ -0,0 +1,130 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC methods for buyers of on-demand coretime.
+
+use std::sync::Arc;
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject};
+use polkadot_primitives::{runtime_api::OnDemandApi, vstaging, Balance, Block, Hash};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+
+/// The spot price of on-demand coretime, see [`vstaging::OnDemandSpotPrice`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPrice {
+	/// The price of an order placed at the queried block.
+	pub spot_price: NumberOrHex,
+	/// The queue size the projection assumes.
+	pub queue_size: u32,
+	/// The maximum size of the order queue.
+	pub queue_capacity: u32,
+	/// The spot price of the blocks after the queried one.
+	pub projection: Vec<NumberOrHex>,
+}
+
+impl From<vstaging::OnDemandSpotPrice<Balance>> for SpotPrice {
+	fn from(price: vstaging::OnDemandSpotPrice<Balance>) -> Self {
+		Self {
+			spot_price: price.spot_price.into(),
+			queue_size: price.queue_size,
+			queue_capacity: price.queue_capacity,
+			projection: price.projection.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Provides RPC methods to query the price of on-demand coretime.
+#[rpc(client, server)]
+pub trait OnDemandApi {
+	/// Returns the spot price of on-demand coretime and its projection for the next `blocks`
+	/// blocks, assuming the order queue has `queue_size` orders, or its current size if none is
+	/// given.
+	#[method(name = "onDemand_spotPrice")]
+	fn spot_price(
+		&self,
+		blocks: u32,
+		queue_size: Option<u32>,
+		at: Option<Hash>,
+	) -> RpcResult<SpotPrice>;
+}
+
+/// Implements [`OnDemandApiServer`] on top of the [`OnDemandApi`] runtime API.
+pub struct OnDemand<C> {
+	client: Arc<C>,
+}
+
+impl<C> OnDemand<C> {
+	/// Create a new [`OnDemand`] RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> OnDemandApiServer for OnDemand<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: OnDemandApi<Block, Balance>,
+{
+	fn spot_price(
+		&self,
+		blocks: u32,
+		queue_size: Option<u32>,
+		at: Option<Hash>,
+	) -> RpcResult<SpotPrice> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.spot_price(at, blocks, queue_size)
+			.map(Into::into)
+			.map_err(|e| {
+				ErrorObject::owned(
+					1,
+					"Unable to query the on-demand spot price.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn spot_price_is_serialized_in_camel_case() {
+		let price = SpotPrice::from(vstaging::OnDemandSpotPrice {
+			spot_price: 10,
+			queue_size: 1,
+			queue_capacity: 4,
+			projection: vec![11, u128::MAX],
+		});
+
+		assert_eq!(
+			serde_json::to_value(price).unwrap(),
+			serde_json::json!({
+				"spotPrice": "0xa",
+				"queueSize": 1,
+				"queueCapacity": 4,
+				"projection": ["0xb", "0xffffffffffffffffffffffffffffffff"],
+			})
+		);
+	}
+}
@@ -0,0 +1,182 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC methods exposing the validator sets of the recent sessions.
+
+use std::sync::Arc;
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject};
+use polkadot_node_primitives::session_history::SessionHistoryHandle;
+use polkadot_primitives::{
+	runtime_api::ParachainHost, AuthorityDiscoveryId, Block, SessionIndex, ValidatorId,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+/// The validators of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionValidatorsReport {
+	/// The session the validators are for.
+	pub session: SessionIndex,
+	/// The validators of the session, by validator index.
+	pub validators: Vec<ValidatorId>,
+	/// The authority discovery keys of all authorities of the session.
+	pub discovery_keys: Vec<AuthorityDiscoveryId>,
+	/// The validator indices of each backing group, by group index.
+	pub validator_groups: Vec<Vec<u32>>,
+}
+
+/// Provides RPC methods to query the validator sets of the recent sessions.
+#[rpc(client, server)]
+pub trait SessionHistoryApi {
+	/// Returns the sessions the validators are known for, oldest first.
+	#[method(name = "sessionHistory_sessions")]
+	fn sessions(&self) -> RpcResult<Vec<SessionIndex>>;
+
+	/// Returns the validators, discovery keys and backing groups of `session`, or of the latest
+	/// known session if none is given.
+	///
+	/// Returns `null` if the session is not one of the sessions kept by the node.
+	#[method(name = "sessionHistory_validators")]
+	fn validators(
+		&self,
+		session: Option<SessionIndex>,
+	) -> RpcResult<Option<SessionValidatorsReport>>;
+}
+
+/// Implements [`SessionHistoryApiServer`] on top of the sessions recorded by gossip support.
+///
+/// Before answering, the sessions whose `SessionInfo` is still kept by the runtime at the best
+/// block are recorded too, so that any full node serves them.
+pub struct SessionHistory<C> {
+	client: Arc<C>,
+	session_history: SessionHistoryHandle,
+}
+
+impl<C> SessionHistory<C> {
+	/// Create a new [`SessionHistory`] RPC handler.
+	pub fn new(client: Arc<C>, session_history: SessionHistoryHandle) -> Self {
+		Self { client, session_history }
+	}
+}
+
+impl<C> SessionHistory<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ParachainHost<Block>,
+{
+	/// Record the validators of the recent sessions known to the runtime at the best block.
+	///
+	/// The runtime only keeps the sessions of the dispute window, the sessions known to the node
+	/// are not queried again.
+	fn record_runtime_sessions(&self) -> RpcResult<()> {
+		let runtime_error = |e: sp_api::ApiError| {
+			ErrorObject::owned(1, "Unable to query the session info.", Some(e.to_string()))
+		};
+
+		let best_hash = self.client.info().best_hash;
+		let api = self.client.runtime_api();
+		let current = api.session_index_for_child(best_hash).map_err(runtime_error)?;
+		let depth = self.session_history.depth() as SessionIndex;
+		if depth == 0 {
+			return Ok(())
+		}
+		let oldest = current.saturating_sub(depth - 1);
+
+		for session in (oldest..=current).rev() {
+			if self.session_history.contains(session) {
+				continue
+			}
+			match api.session_info(best_hash, session).map_err(runtime_error)? {
+				Some(session_info) => self.session_history.record(session, (&session_info).into()),
+				// The older sessions are pruned by the runtime as well.
+				None => break,
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<C> SessionHistoryApiServer for SessionHistory<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ParachainHost<Block>,
+{
+	fn sessions(&self) -> RpcResult<Vec<SessionIndex>> {
+		self.record_runtime_sessions()?;
+		Ok(self.session_history.sessions())
+	}
+
+	fn validators(
+		&self,
+		session: Option<SessionIndex>,
+	) -> RpcResult<Option<SessionValidatorsReport>> {
+		self.record_runtime_sessions()?;
+		Ok(report(&self.session_history, session))
+	}
+}
+
+/// The validators of `session` recorded in `session_history`, or of the latest recorded session.
+fn report(
+	session_history: &SessionHistoryHandle,
+	session: Option<SessionIndex>,
+) -> Option<SessionValidatorsReport> {
+	let session = session.or_else(|| session_history.sessions().pop())?;
+
+	session_history.session(session).map(|validators| SessionValidatorsReport {
+		session,
+		validators: validators.validators.clone(),
+		discovery_keys: validators.discovery_keys.clone(),
+		validator_groups: validators
+			.validator_groups
+			.iter()
+			.map(|group| group.iter().map(|index| index.0).collect())
+			.collect(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_primitives::session_history::SessionValidators;
+	use polkadot_primitives::ValidatorIndex;
+
+	#[test]
+	fn reports_latest_session_by_default() {
+		let handle = SessionHistoryHandle::new(2);
+		assert_eq!(report(&handle, None), None);
+
+		for session in 3..=5 {
+			handle.record(
+				session,
+				SessionValidators {
+					validators: Vec::new(),
+					discovery_keys: Vec::new(),
+					validator_groups: vec![vec![ValidatorIndex(session)]],
+				},
+			);
+		}
+
+		assert_eq!(handle.sessions(), vec![4, 5]);
+		let latest = report(&handle, None).unwrap();
+		assert_eq!(latest.session, 5);
+		assert_eq!(latest.validator_groups, vec![vec![5]]);
+		assert_eq!(report(&handle, Some(4)).unwrap().validator_groups, vec![vec![4]]);
+		assert_eq!(report(&handle, Some(3)), None);
+	}
+}
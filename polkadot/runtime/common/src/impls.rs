@@ -24,7 +24,10 @@ use frame_support::traits::{
 };
 use pallet_treasury::TreasuryAccountId;
 use polkadot_primitives::Balance;
-use sp_runtime::{traits::TryConvert, Perquintill, RuntimeDebug};
+use sp_runtime::{
+	traits::{Morph, TryConvert},
+	Perquintill, RuntimeDebug,
+};
 use xcm::VersionedLocation;
 
 /// Logic for the author to get a portion of fees.
@@ -205,6 +208,20 @@ impl TryConvert<&VersionedLocation, xcm::latest::Location> for VersionedLocation
 	}
 }
 
+/// Converts an account into the [`VersionedLocation`] of the same account, as the beneficiaries of
+/// treasury spends paid over XCM are expressed, e.g. for the `PartialPayoutOrigin` of the
+/// treasury.
+pub struct AccountIdToBeneficiary;
+impl<AccountId: Into<[u8; 32]>> Morph<AccountId> for AccountIdToBeneficiary {
+	type Outcome = VersionedLocation;
+	fn morph(who: AccountId) -> VersionedLocation {
+		VersionedLocation::from(xcm::latest::Location::new(
+			0,
+			[xcm::latest::Junction::AccountId32 { network: None, id: who.into() }],
+		))
+	}
+}
+
 /// Adapter for [`Contains`] trait to match [`VersionedLocatableAsset`] type converted to the latest
 /// version of itself where it's location matched by `L` and it's asset id by `A` parameter types.
 pub struct ContainsParts<C>(core::marker::PhantomData<C>);
@@ -392,6 +409,7 @@ mod tests {
 		type AssetKind = ();
 		type Beneficiary = Self::AccountId;
 		type BeneficiaryLookup = IdentityLookup<Self::AccountId>;
+		type PartialPayoutOrigin = frame_system::EnsureSigned<Self::AccountId>;
 		type Paymaster = PayFromAccount<Balances, TreasuryAccount>;
 		type BalanceConverter = UnityAssetBalanceConversion;
 		type PayoutPeriod = ConstU64<0>;
@@ -23,7 +23,7 @@ use alloc::{
 	vec,
 	vec::Vec,
 };
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeWithMemTracking, Encode};
 use core::{fmt, mem};
 use frame_support::{pallet_prelude::*, traits::ReservableCurrency, DefaultNoBound};
 use frame_system::pallet_prelude::*;
@@ -67,6 +67,8 @@ pub trait WeightInfo {
 	fn establish_system_channel() -> Weight;
 	fn poke_channel_deposits() -> Weight;
 	fn establish_channel_with_system() -> Weight;
+	fn hrmp_set_auto_accept_policy() -> Weight;
+	fn hrmp_remove_auto_accept_policy() -> Weight;
 }
 
 /// A weight info that is only suitable for testing.
@@ -109,8 +111,17 @@ impl WeightInfo for TestWeightInfo {
 	fn establish_channel_with_system() -> Weight {
 		Weight::MAX
 	}
+	fn hrmp_set_auto_accept_policy() -> Weight {
+		Weight::MAX
+	}
+	fn hrmp_remove_auto_accept_policy() -> Weight {
+		Weight::MAX
+	}
 }
 
+/// Maximum number of paras in the allow-list of an [`HrmpAutoAcceptPolicy`].
+pub const MAX_AUTO_ACCEPT_ALLOWED_SENDERS: u32 = 100;
+
 /// A description of a request to open an HRMP channel.
 #[derive(Encode, Decode, TypeInfo)]
 pub struct HrmpOpenChannelRequest {
@@ -129,6 +140,34 @@ pub struct HrmpOpenChannelRequest {
 	pub max_total_size: u32,
 }
 
+/// The criteria under which a para automatically accepts the requests to open an inbound HRMP
+/// channel, without having to call
+/// [`hrmp_accept_open_channel`](Pallet::hrmp_accept_open_channel).
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct HrmpAutoAcceptPolicy {
+	/// The minimum value of the maximum message size of the accepted channels.
+	pub min_message_size: u32,
+	/// The maximum value of the maximum message size of the accepted channels.
+	pub max_message_size: u32,
+	/// The maximum capacity of the accepted channels.
+	pub max_capacity: u32,
+	/// Whether only the requests whose sender paid a deposit are accepted, i.e. no requests from
+	/// system chains.
+	pub require_sender_deposit: bool,
+	/// The paras whose requests are accepted, or all of them if empty.
+	pub allowed_senders: BoundedVec<ParaId, ConstU32<MAX_AUTO_ACCEPT_ALLOWED_SENDERS>>,
+}
+
+impl HrmpAutoAcceptPolicy {
+	/// Whether the open channel request from `sender` is accepted by the policy.
+	fn accepts(&self, sender: ParaId, request: &HrmpOpenChannelRequest) -> bool {
+		(self.min_message_size..=self.max_message_size).contains(&request.max_message_size) &&
+			request.max_capacity <= self.max_capacity &&
+			(!self.require_sender_deposit || !request.sender_deposit.is_zero()) &&
+			(self.allowed_senders.is_empty() || self.allowed_senders.contains(&sender))
+	}
+}
+
 /// A metadata of an HRMP channel.
 #[derive(Encode, Decode, TypeInfo)]
 #[cfg_attr(test, derive(Debug))]
@@ -324,6 +363,12 @@ pub mod pallet {
 		},
 		/// An HRMP channel's deposits were updated.
 		OpenChannelDepositsUpdated { sender: ParaId, recipient: ParaId },
+		/// A para set the policy to automatically accept inbound channel requests.
+		AutoAcceptPolicySet { para_id: ParaId, policy: HrmpAutoAcceptPolicy },
+		/// A para removed its policy to automatically accept inbound channel requests.
+		AutoAcceptPolicyRemoved { para_id: ParaId },
+		/// Open HRMP channel accepted automatically, according to the policy of the recipient.
+		OpenChannelAutoAccepted { sender: ParaId, recipient: ParaId },
 	}
 
 	#[pallet::error]
@@ -368,6 +413,10 @@ pub mod pallet {
 		WrongWitness,
 		/// The channel between these two chains cannot be authorized.
 		ChannelCreationNotAuthorized,
+		/// The auto-accept policy accepts no channel.
+		InvalidAutoAcceptPolicy,
+		/// The para has no auto-accept policy.
+		NoAutoAcceptPolicy,
 	}
 
 	/// The set of pending HRMP open channel requests.
@@ -401,6 +450,11 @@ pub mod pallet {
 	pub type HrmpAcceptedChannelRequestCount<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, u32, ValueQuery>;
 
+	/// The policies of the paras to automatically accept inbound channel requests.
+	#[pallet::storage]
+	pub type HrmpAutoAcceptPolicies<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, HrmpAutoAcceptPolicy>;
+
 	/// A set of pending HRMP close channel requests that are going to be closed during the session
 	/// change. Used for checking if a given channel is registered for closure.
 	///
@@ -511,9 +565,11 @@ pub mod pallet {
 		/// These numbers are a subject to the relay-chain configuration limits.
 		///
 		/// The channel can be opened only after the recipient confirms it and only on a session
-		/// change.
+		/// change. The request is confirmed right away if it matches the auto-accept policy of
+		/// the recipient.
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as Config>::WeightInfo::hrmp_init_open_channel())]
+		#[pallet::weight(<T as Config>::WeightInfo::hrmp_init_open_channel()
+			.saturating_add(<T as Config>::WeightInfo::hrmp_accept_open_channel()))]
 		pub fn hrmp_init_open_channel(
 			origin: OriginFor<T>,
 			recipient: ParaId,
@@ -533,6 +589,7 @@ pub mod pallet {
 				proposed_max_capacity,
 				proposed_max_message_size,
 			});
+			Self::try_auto_accept_open_channel(origin, recipient);
 			Ok(())
 		}
 
@@ -898,6 +955,41 @@ pub mod pallet {
 
 			Ok(Pays::No.into())
 		}
+
+		/// Set the policy under which the origin automatically accepts the requests to open an
+		/// inbound channel, replacing the previous one.
+		///
+		/// The requests matching the policy are accepted when made, the pending ones are not.
+		///
+		/// The origin needs to be the parachain origin.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::hrmp_set_auto_accept_policy())]
+		pub fn hrmp_set_auto_accept_policy(
+			origin: OriginFor<T>,
+			policy: HrmpAutoAcceptPolicy,
+		) -> DispatchResult {
+			let para_id = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			ensure!(
+				policy.max_capacity > 0 && policy.min_message_size <= policy.max_message_size,
+				Error::<T>::InvalidAutoAcceptPolicy
+			);
+
+			HrmpAutoAcceptPolicies::<T>::insert(para_id, &policy);
+			Self::deposit_event(Event::AutoAcceptPolicySet { para_id, policy });
+			Ok(())
+		}
+
+		/// Revoke the policy of the origin to automatically accept inbound channel requests.
+		///
+		/// The origin needs to be the parachain origin.
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::hrmp_remove_auto_accept_policy())]
+		pub fn hrmp_remove_auto_accept_policy(origin: OriginFor<T>) -> DispatchResult {
+			let para_id = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			HrmpAutoAcceptPolicies::<T>::take(para_id).ok_or(Error::<T>::NoAutoAcceptPolicy)?;
+			Self::deposit_event(Event::AutoAcceptPolicyRemoved { para_id });
+			Ok(())
+		}
 	}
 }
 
@@ -1034,6 +1126,7 @@ impl<T: Config> Pallet<T> {
 	fn clean_hrmp_after_outgoing(outgoing_para: &ParaId) {
 		HrmpOpenChannelRequestCount::<T>::remove(outgoing_para);
 		HrmpAcceptedChannelRequestCount::<T>::remove(outgoing_para);
+		HrmpAutoAcceptPolicies::<T>::remove(outgoing_para);
 
 		let ingress = HrmpIngressChannelsIndex::<T>::take(outgoing_para)
 			.into_iter()
@@ -1561,6 +1654,30 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Accept the open channel request from `sender` if it matches the auto-accept policy of
+	/// `recipient`.
+	///
+	/// The request is left pending if it can't be accepted, e.g. because the recipient can't pay
+	/// the deposit.
+	fn try_auto_accept_open_channel(sender: ParaId, recipient: ParaId) {
+		let Some(policy) = HrmpAutoAcceptPolicies::<T>::get(recipient) else { return };
+		let channel_id = HrmpChannelId { sender, recipient };
+		let Some(request) = HrmpOpenChannelRequests::<T>::get(&channel_id) else { return };
+		if !policy.accepts(sender, &request) {
+			return
+		}
+
+		match Self::accept_open_channel(recipient, sender) {
+			Ok(()) => Self::deposit_event(Event::OpenChannelAutoAccepted { sender, recipient }),
+			Err(err) => log::debug!(
+				target: "runtime::hrmp",
+				"Auto-accepting the channel {:?} failed: {:?}",
+				channel_id,
+				err,
+			),
+		}
+	}
+
 	fn cancel_open_request(origin: ParaId, channel_id: HrmpChannelId) -> DispatchResult {
 		// check if the origin is allowed to close the channel.
 		ensure!(channel_id.is_participant(origin), Error::<T>::CancelHrmpOpenChannelUnauthorized);
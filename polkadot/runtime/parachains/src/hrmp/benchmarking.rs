@@ -561,6 +561,50 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn hrmp_set_auto_accept_policy() {
+		let para_id: ParaId = 1u32.into();
+		let origin: crate::Origin = 1u32.into();
+		let allowed_senders = (0..MAX_AUTO_ACCEPT_ALLOWED_SENDERS)
+			.map(|id| ParaId::from(id + 2))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		let policy = HrmpAutoAcceptPolicy {
+			min_message_size: 1,
+			max_message_size: u32::MAX,
+			max_capacity: u32::MAX,
+			require_sender_deposit: true,
+			allowed_senders,
+		};
+
+		#[extrinsic_call]
+		_(origin, policy.clone());
+
+		assert_last_event::<T>(Event::<T>::AutoAcceptPolicySet { para_id, policy }.into());
+	}
+
+	#[benchmark]
+	fn hrmp_remove_auto_accept_policy() {
+		let para_id: ParaId = 1u32.into();
+		let origin: crate::Origin = 1u32.into();
+		HrmpAutoAcceptPolicies::<T>::insert(
+			para_id,
+			HrmpAutoAcceptPolicy {
+				min_message_size: 1,
+				max_message_size: u32::MAX,
+				max_capacity: u32::MAX,
+				require_sender_deposit: true,
+				allowed_senders: Default::default(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(origin);
+
+		assert_last_event::<T>(Event::<T>::AutoAcceptPolicyRemoved { para_id }.into());
+	}
+
 	impl_benchmark_test_suite!(
 		Hrmp,
 		crate::mock::new_test_ext(crate::hrmp::tests::GenesisConfigBuilder::default().build()),
@@ -179,6 +179,79 @@ fn open_channel_works() {
 	});
 }
 
+#[test]
+fn auto_accept_policy_works() {
+	let para_a = 2001.into();
+	let para_a_origin: crate::Origin = 2001.into();
+	let para_b = 2003.into();
+	let para_b_origin: crate::Origin = 2003.into();
+	let para_c = 2004.into();
+	let para_c_origin: crate::Origin = 2004.into();
+	let is_confirmed = |sender, recipient| {
+		HrmpOpenChannelRequests::<Test>::get(&HrmpChannelId { sender, recipient })
+			.map(|request| request.confirmed)
+	};
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+		register_parachain(para_c);
+		run_to_block(5, Some(vec![4, 5]));
+
+		let policy = HrmpAutoAcceptPolicy {
+			min_message_size: 1,
+			max_message_size: 8,
+			max_capacity: 2,
+			require_sender_deposit: true,
+			allowed_senders: vec![para_a].try_into().unwrap(),
+		};
+		assert_noop!(
+			Hrmp::hrmp_set_auto_accept_policy(
+				para_b_origin.clone().into(),
+				HrmpAutoAcceptPolicy { min_message_size: 9, ..policy.clone() }
+			),
+			Error::<Test>::InvalidAutoAcceptPolicy
+		);
+		assert_ok!(Hrmp::hrmp_set_auto_accept_policy(para_b_origin.clone().into(), policy.clone()));
+		assert_ok!(Hrmp::hrmp_set_auto_accept_policy(
+			para_a_origin.clone().into(),
+			HrmpAutoAcceptPolicy {
+				max_message_size: 4,
+				allowed_senders: Default::default(),
+				..policy
+			}
+		));
+
+		// Only the requests matching the policy are accepted.
+		assert_ok!(Hrmp::hrmp_init_open_channel(para_a_origin.clone().into(), para_b, 2, 8));
+		assert_eq!(is_confirmed(para_a, para_b), Some(true));
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::OpenChannelAutoAccepted {
+				sender: para_a,
+				recipient: para_b
+			})));
+		assert_ok!(Hrmp::hrmp_init_open_channel(para_c_origin.clone().into(), para_b, 2, 8));
+		assert_eq!(is_confirmed(para_c, para_b), Some(false));
+		assert_ok!(Hrmp::hrmp_init_open_channel(para_b_origin.into(), para_a, 2, 8));
+		assert_eq!(is_confirmed(para_b, para_a), Some(false));
+		assert_ok!(Hrmp::hrmp_init_open_channel(para_c_origin.into(), para_a, 2, 4));
+		assert_eq!(is_confirmed(para_c, para_a), Some(true));
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		// The policy can be revoked.
+		assert_ok!(Hrmp::hrmp_remove_auto_accept_policy(para_a_origin.clone().into()));
+		assert_noop!(
+			Hrmp::hrmp_remove_auto_accept_policy(para_a_origin.into()),
+			Error::<Test>::NoAutoAcceptPolicy
+		);
+
+		run_to_block(8, Some(vec![8]));
+		assert!(channel_exists(para_a, para_b));
+		assert!(channel_exists(para_c, para_a));
+		assert!(!channel_exists(para_c, para_b));
+	});
+}
+
 #[test]
 fn force_open_channel_works() {
 	let para_a = 1.into();
@@ -401,6 +401,7 @@ impl pallet_message_queue::Config for Test {
 	type MaxStale = ConstU32<8>;
 	type ServiceWeight = MessageQueueServiceWeight;
 	type IdleMaxServiceWeight = ();
+	type QueueServicePolicy = ();
 }
 
 parameter_types! {
@@ -409,6 +410,9 @@ parameter_types! {
 	// defined Timeslice for Coretime.
 	pub const MaxHistoricalRevenue: BlockNumber = 2 * 5;
 	pub const OnDemandPalletId: PalletId = PalletId(*b"py/ondmd");
+	pub const MaxStandingOrders: u32 = 10;
+	pub const StandingOrderDeposit: Balance = 10;
+	pub const MaxStandingOrderLifetime: BlockNumber = 1_000;
 }
 
 impl on_demand::Config for Test {
@@ -418,6 +422,9 @@ impl on_demand::Config for Test {
 	type WeightInfo = crate::on_demand::TestWeightInfo;
 	type MaxHistoricalRevenue = MaxHistoricalRevenue;
 	type PalletId = OnDemandPalletId;
+	type MaxStandingOrders = MaxStandingOrders;
+	type StandingOrderDeposit = StandingOrderDeposit;
+	type MaxStandingOrderLifetime = MaxStandingOrderLifetime;
 }
 
 impl assigner_coretime::Config for Test {}
@@ -105,6 +105,49 @@ mod benchmarks {
 		_(RawOrigin::Signed(caller.into()), BalanceOf::<T>::max_value(), para_id)
 	}
 
+	#[benchmark]
+	fn place_standing_order() {
+		// Setup
+		let caller: T::AccountId = whitelisted_caller();
+		let para_id = ParaId::from(111u32);
+		init_parathread::<T>(para_id);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.into()),
+			para_id,
+			BalanceOf::<T>::max_value(),
+			u32::MAX,
+			expires_at,
+		)
+	}
+
+	#[benchmark]
+	fn cancel_standing_order() {
+		// Setup
+		let caller: T::AccountId = whitelisted_caller();
+		let para_id = ParaId::from(111u32);
+		init_parathread::<T>(para_id);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let deposit = T::StandingOrderDeposit::get();
+		T::Currency::reserve(&caller, deposit).expect("the caller is funded; qed");
+		StandingOrders::<T>::insert(
+			para_id,
+			StandingOrder {
+				funder: caller.clone(),
+				max_price: BalanceOf::<T>::max_value(),
+				remaining: u32::MAX,
+				expires_at: frame_system::Pallet::<T>::block_number() + 10u32.into(),
+				deposit,
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.into()), para_id)
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(
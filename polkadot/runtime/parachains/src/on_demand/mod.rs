@@ -25,6 +25,12 @@
 //! a specific `ParaId`, orders for blockspace for that `ParaId` will only be assigned to
 //! that `CoreIndex`.
 //!
+//! Besides single orders, an account can place a standing order for a para: an order is then
+//! placed on every block where the spot price is below the maximum price of the standing order,
+//! paid with the credits of the account, until the desired number of blocks is ordered or the
+//! standing order expires. The funder of a standing order reserves a deposit for it, which is
+//! refunded once the standing order is cancelled, completed or expired.
+//!
 //! NOTE: Once we have elastic scaling implemented we might want to extend this module to support
 //! ignoring core affinity up to a certain extend. This should be opt-in though as the parachain
 //! needs to support multiple cores in the same block. If we want to enable a single parachain
@@ -51,29 +57,34 @@ use frame_support::{
 		defensive_prelude::*,
 		Currency,
 		ExistenceRequirement::{self, AllowDeath, KeepAlive},
-		WithdrawReasons,
+		ReservableCurrency, WithdrawReasons,
 	},
 	PalletId,
 };
 use frame_system::{pallet_prelude::*, Pallet as System};
-use polkadot_primitives::{CoreIndex, Id as ParaId};
+use polkadot_primitives::{vstaging::OnDemandSpotPrice, CoreIndex, Id as ParaId};
 use sp_runtime::{
 	traits::{AccountIdConversion, One, SaturatedConversion},
 	FixedPointNumber, FixedPointOperand, FixedU128, Perbill, Saturating,
 };
 use types::{
 	BalanceOf, CoreAffinityCount, EnqueuedOrder, QueuePushDirection, QueueStatusType,
-	SpotTrafficCalculationErr,
+	SpotTrafficCalculationErr, StandingOrder,
 };
 
 const LOG_TARGET: &str = "runtime::parachains::on-demand";
 
+/// The maximum number of blocks [`Pallet::spot_price`] projects the spot price for.
+pub const MAX_SPOT_PRICE_PROJECTION: u32 = 256;
+
 pub use pallet::*;
 
 pub trait WeightInfo {
 	fn place_order_allow_death(s: u32) -> Weight;
 	fn place_order_keep_alive(s: u32) -> Weight;
 	fn place_order_with_credits(s: u32) -> Weight;
+	fn place_standing_order() -> Weight;
+	fn cancel_standing_order() -> Weight;
 }
 
 /// A weight info that is only suitable for testing.
@@ -91,6 +102,14 @@ impl WeightInfo for TestWeightInfo {
 	fn place_order_with_credits(_: u32) -> Weight {
 		Weight::MAX
 	}
+
+	fn place_standing_order() -> Weight {
+		Weight::MAX
+	}
+
+	fn cancel_standing_order() -> Weight {
+		Weight::MAX
+	}
 }
 
 /// Defines how the account wants to pay for on-demand.
@@ -121,7 +140,7 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// The runtime's definition of a Currency.
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// Something that provides the weight of this pallet.
 		type WeightInfo: WeightInfo;
@@ -138,6 +157,18 @@ pub mod pallet {
 		/// Identifier for the internal revenue balance.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// The maximum number of standing orders, processed on every block.
+		#[pallet::constant]
+		type MaxStandingOrders: Get<u32>;
+
+		/// The deposit reserved from the funder of a standing order.
+		#[pallet::constant]
+		type StandingOrderDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of blocks a standing order can be placed for.
+		#[pallet::constant]
+		type MaxStandingOrderLifetime: Get<BlockNumberFor<Self>>;
 	}
 
 	/// Creates an empty queue status for an empty queue with initial traffic value.
@@ -189,6 +220,16 @@ pub mod pallet {
 	pub type Credits<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
+	/// Standing orders of on-demand coretime, by para.
+	#[pallet::storage]
+	pub type StandingOrders<T: Config> = CountedStorageMap<
+		_,
+		Twox64Concat,
+		ParaId,
+		StandingOrder<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -198,6 +239,20 @@ pub mod pallet {
 		SpotPriceSet { spot_price: BalanceOf<T> },
 		/// An account was given credits.
 		AccountCredited { who: T::AccountId, amount: BalanceOf<T> },
+		/// A standing order was placed for a para.
+		StandingOrderPlaced {
+			para_id: ParaId,
+			funder: T::AccountId,
+			max_price: BalanceOf<T>,
+			count: u32,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// All the orders of a standing order were placed.
+		StandingOrderCompleted { para_id: ParaId },
+		/// A standing order expired before all of its orders were placed.
+		StandingOrderExpired { para_id: ParaId, remaining: u32 },
+		/// A standing order was cancelled by its funder.
+		StandingOrderCancelled { para_id: ParaId, remaining: u32 },
 	}
 
 	#[pallet::error]
@@ -209,11 +264,22 @@ pub mod pallet {
 		SpotPriceHigherThanMaxAmount,
 		/// The account doesn't have enough credits to purchase on-demand coretime.
 		InsufficientCredits,
+		/// The para already has a standing order.
+		StandingOrderExists,
+		/// The maximum number of standing orders is reached.
+		TooManyStandingOrders,
+		/// The standing order has no orders to place, is already expired or expires after the
+		/// maximum lifetime of a standing order.
+		InvalidStandingOrder,
+		/// The para has no standing order.
+		NoStandingOrder,
+		/// The origin is not the funder of the standing order.
+		NotStandingOrderFunder,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
 			// Update revenue information storage.
 			Revenue::<T>::mutate(|revenue| {
 				if let Some(overdue) =
@@ -236,7 +302,9 @@ pub mod pallet {
 
 			// Reads: `Revenue`, `ActiveConfig`, `QueueStatus`
 			// Writes: `Revenue`, `QueueStatus`
-			T::DbWeight::get().reads_writes(3, 2)
+			T::DbWeight::get()
+				.reads_writes(3, 2)
+				.saturating_add(Self::process_standing_orders(now))
 		}
 	}
 
@@ -343,6 +411,102 @@ pub mod pallet {
 				PaymentType::Credits,
 			)
 		}
+
+		/// Place a standing order of on-demand coretime for a para.
+		///
+		/// On every block where the spot price is at most `max_price`, an order is placed for the
+		/// para and paid with the on-demand credits of the origin, until `count` orders are placed
+		/// or the block `expires_at` is passed.
+		///
+		/// The `StandingOrderDeposit` is reserved from the origin until the standing order is
+		/// cancelled, completed or expired.
+		///
+		/// Parameters:
+		/// - `origin`: The funder of the standing order, its credits pay for the orders.
+		/// - `para_id`: A `ParaId` the origin wants to provide blockspace for.
+		/// - `max_price`: The maximum number of credits to spend on a single order.
+		/// - `count`: The number of orders to place.
+		/// - `expires_at`: The last block where an order can be placed, at most
+		///   `MaxStandingOrderLifetime` blocks from now.
+		///
+		/// Errors:
+		/// - `StandingOrderExists`
+		/// - `TooManyStandingOrders`
+		/// - `InvalidStandingOrder`
+		///
+		/// Events:
+		/// - `StandingOrderPlaced`
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::place_standing_order())]
+		pub fn place_standing_order(
+			origin: OriginFor<T>,
+			para_id: ParaId,
+			max_price: BalanceOf<T>,
+			count: u32,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let funder = ensure_signed(origin)?;
+			let now = System::<T>::block_number();
+			ensure!(
+				count > 0 &&
+					expires_at > now &&
+					expires_at <= now.saturating_add(T::MaxStandingOrderLifetime::get()),
+				Error::<T>::InvalidStandingOrder
+			);
+			ensure!(!StandingOrders::<T>::contains_key(para_id), Error::<T>::StandingOrderExists);
+			ensure!(
+				StandingOrders::<T>::count() < T::MaxStandingOrders::get(),
+				Error::<T>::TooManyStandingOrders
+			);
+
+			let deposit = T::StandingOrderDeposit::get();
+			T::Currency::reserve(&funder, deposit)?;
+			StandingOrders::<T>::insert(
+				para_id,
+				StandingOrder {
+					funder: funder.clone(),
+					max_price,
+					remaining: count,
+					expires_at,
+					deposit,
+				},
+			);
+			Self::deposit_event(Event::<T>::StandingOrderPlaced {
+				para_id,
+				funder,
+				max_price,
+				count,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Cancel the standing order of a para, refunding its deposit.
+		///
+		/// Parameters:
+		/// - `origin`: The funder of the standing order.
+		/// - `para_id`: The `ParaId` of the standing order.
+		///
+		/// Errors:
+		/// - `NoStandingOrder`
+		/// - `NotStandingOrderFunder`
+		///
+		/// Events:
+		/// - `StandingOrderCancelled`
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_standing_order())]
+		pub fn cancel_standing_order(origin: OriginFor<T>, para_id: ParaId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = StandingOrders::<T>::get(para_id).ok_or(Error::<T>::NoStandingOrder)?;
+			ensure!(order.funder == who, Error::<T>::NotStandingOrderFunder);
+
+			Self::remove_standing_order(para_id, &order);
+			Self::deposit_event(Event::<T>::StandingOrderCancelled {
+				para_id,
+				remaining: order.remaining,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -536,6 +700,78 @@ where
 		})
 	}
 
+	/// Remove a standing order, refunding its deposit to its funder.
+	fn remove_standing_order(
+		para_id: ParaId,
+		order: &StandingOrder<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+	) {
+		StandingOrders::<T>::remove(para_id);
+		let remaining = T::Currency::unreserve(&order.funder, order.deposit);
+		if !remaining.is_zero() {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to refund {:?} of the standing order deposit of {:?}",
+				remaining,
+				para_id,
+			);
+		}
+	}
+
+	/// Place the orders of the standing orders whose maximum price is reached by the spot price,
+	/// and remove the completed and expired standing orders.
+	///
+	/// Returns the weight consumed.
+	fn process_standing_orders(now: BlockNumberFor<T>) -> Weight {
+		let mut weight = T::DbWeight::get().reads(1);
+		for (para_id, mut order) in StandingOrders::<T>::iter() {
+			if now > order.expires_at {
+				Self::remove_standing_order(para_id, &order);
+				// Reads: `StandingOrders`, `System::Account`
+				// Writes: `StandingOrders`, `CounterForStandingOrders`, `System::Account`
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 3));
+				Self::deposit_event(Event::<T>::StandingOrderExpired {
+					para_id,
+					remaining: order.remaining,
+				});
+				continue
+			}
+
+			let queue_size = QueueStatus::<T>::get().size();
+			weight.saturating_accrue(
+				<T as Config>::WeightInfo::place_order_with_credits(queue_size)
+					.saturating_add(T::DbWeight::get().reads_writes(3, 3)),
+			);
+			// Orders which can't be placed in this block, e.g. because the spot price is above
+			// the maximum price or the funder is out of credits, are retried in the next block.
+			let placed = Self::do_place_order(
+				order.funder.clone(),
+				order.max_price,
+				para_id,
+				KeepAlive,
+				PaymentType::Credits,
+			);
+			if let Err(error) = placed {
+				log::trace!(
+					target: LOG_TARGET,
+					"Standing order of {:?} not placed at {:?}: {:?}",
+					para_id,
+					now,
+					error,
+				);
+				continue
+			}
+
+			order.remaining.saturating_dec();
+			if order.remaining == 0 {
+				Self::remove_standing_order(para_id, &order);
+				Self::deposit_event(Event::<T>::StandingOrderCompleted { para_id });
+			} else {
+				StandingOrders::<T>::insert(para_id, order);
+			}
+		}
+		weight
+	}
+
 	/// Calculate and update spot traffic.
 	fn update_spot_traffic(
 		config: &configuration::HostConfiguration<BlockNumberFor<T>>,
@@ -759,6 +995,44 @@ where
 		amount
 	}
 
+	/// The current spot price and its projection for the next `blocks` blocks.
+	///
+	/// The spot traffic is updated once per block, the projection assumes the order queue has
+	/// `queue_size` orders in all of them, or its current size if `None`. At most
+	/// [`MAX_SPOT_PRICE_PROJECTION`] blocks are projected.
+	pub fn spot_price(blocks: u32, queue_size: Option<u32>) -> OnDemandSpotPrice<BalanceOf<T>> {
+		let config = configuration::ActiveConfig::<T>::get();
+		let params = &config.scheduler_params;
+		let queue_status = QueueStatus::<T>::get();
+		let queue_capacity = params.on_demand_queue_max_size;
+		let queue_size = queue_size.unwrap_or_else(|| queue_status.size()).min(queue_capacity);
+		let base_fee = params.on_demand_base_fee.saturated_into::<BalanceOf<T>>();
+
+		let mut traffic = queue_status.traffic;
+		let projection = (0..blocks.min(MAX_SPOT_PRICE_PROJECTION))
+			.map(|_| {
+				// Like `update_spot_traffic`, keep the traffic if it can't be calculated.
+				if let Ok(new_traffic) = Self::calculate_spot_traffic(
+					traffic,
+					queue_capacity,
+					queue_size,
+					params.on_demand_target_queue_utilization,
+					params.on_demand_fee_variability,
+				) {
+					traffic = new_traffic;
+				}
+				traffic.saturating_mul_int(base_fee)
+			})
+			.collect();
+
+		OnDemandSpotPrice {
+			spot_price: queue_status.traffic.saturating_mul_int(base_fee),
+			queue_size,
+			queue_capacity,
+			projection,
+		}
+	}
+
 	/// Account of the pallet pot, where the funds from instantaneous coretime sale are accumulated.
 	pub fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account_truncating()
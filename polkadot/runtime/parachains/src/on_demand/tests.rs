@@ -266,6 +266,39 @@ fn spot_traffic_decreases_between_idle_blocks() {
 	})
 }
 
+#[test]
+fn spot_price_projection_follows_traffic_updates() {
+	let para_id = ParaId::from(111);
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		schedule_blank_para(para_id, ParaKind::Parathread);
+		run_to_block(100, |n| if n == 100 { Some(Default::default()) } else { None });
+
+		OnDemand::set_queue_status(QueueStatusType {
+			traffic: FixedU128::from_u32(10),
+			..Default::default()
+		});
+
+		let price = OnDemand::spot_price(2, None);
+		assert_eq!(price.queue_size, 0);
+		assert_eq!(price.projection.len(), 2);
+		assert!(price.projection[0] < price.spot_price);
+
+		// The projection of an idle queue is what the next idle blocks end up with.
+		run_to_block(101, |n| if n == 100 { Some(Default::default()) } else { None });
+		assert_eq!(OnDemand::spot_price(0, None).spot_price, price.projection[0]);
+		run_to_block(102, |n| if n == 100 { Some(Default::default()) } else { None });
+		assert_eq!(OnDemand::spot_price(0, None).spot_price, price.projection[1]);
+
+		// A full queue makes the price go up, and the queue size is capped by its capacity.
+		let price = OnDemand::spot_price(u32::MAX, Some(u32::MAX));
+		assert_eq!(price.queue_size, price.queue_capacity);
+		assert_eq!(price.projection.len(), MAX_SPOT_PRICE_PROJECTION as usize);
+		assert!(price.projection[0] > price.spot_price);
+		assert!(price.projection.windows(2).all(|prices| prices[0] <= prices[1]));
+	})
+}
+
 #[test]
 #[allow(deprecated)]
 fn place_order_works() {
@@ -400,6 +433,160 @@ fn place_order_with_credits() {
 	});
 }
 
+fn orders_placed_for(para_id: ParaId) -> usize {
+	System::events()
+		.into_iter()
+		.filter(|record| {
+			matches!(
+				record.event,
+				crate::mock::RuntimeEvent::OnDemand(Event::OnDemandOrderPlaced { para_id: id, .. })
+					if id == para_id
+			)
+		})
+		.count()
+}
+
+#[test]
+fn standing_order_places_orders_until_completed() {
+	let alice = 1u64;
+	let initial_credit = 10_000_000u128;
+	let para_id = ParaId::from(111);
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		schedule_blank_para(para_id, ParaKind::Parathread);
+		OnDemand::credit_account(alice, initial_credit);
+		Balances::make_free_balance_be(&alice, 1_000);
+		run_to_block(100, |n| if n == 100 { Some(Default::default()) } else { None });
+
+		assert_ok!(OnDemand::place_standing_order(
+			RuntimeOrigin::signed(alice),
+			para_id,
+			initial_credit,
+			2,
+			200
+		));
+		assert_eq!(StandingOrders::<Test>::get(para_id).map(|order| order.remaining), Some(2));
+		assert_eq!(Balances::reserved_balance(alice), 10);
+
+		// One order is placed on every block.
+		run_to_block(101, |_| None);
+		assert_eq!(orders_placed_for(para_id), 1);
+		assert_eq!(StandingOrders::<Test>::get(para_id).map(|order| order.remaining), Some(1));
+		assert!(Credits::<Test>::get(alice) < initial_credit);
+
+		run_to_block(103, |_| None);
+		assert_eq!(orders_placed_for(para_id), 2);
+		assert!(!StandingOrders::<Test>::contains_key(para_id));
+		System::assert_has_event(Event::StandingOrderCompleted { para_id }.into());
+		// The deposit is refunded once the standing order is completed.
+		assert_eq!(Balances::reserved_balance(alice), 0);
+	});
+}
+
+#[test]
+fn standing_order_waits_for_price_and_expires() {
+	let alice = 1u64;
+	let para_id = ParaId::from(111);
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		schedule_blank_para(para_id, ParaKind::Parathread);
+		OnDemand::credit_account(alice, 10_000_000u128);
+		Balances::make_free_balance_be(&alice, 1_000);
+		run_to_block(100, |n| if n == 100 { Some(Default::default()) } else { None });
+
+		// The spot price is always above a maximum price of zero.
+		assert_ok!(OnDemand::place_standing_order(
+			RuntimeOrigin::signed(alice),
+			para_id,
+			0,
+			2,
+			102
+		));
+		run_to_block(102, |_| None);
+		assert_eq!(orders_placed_for(para_id), 0);
+		assert!(StandingOrders::<Test>::contains_key(para_id));
+
+		run_to_block(103, |_| None);
+		assert!(!StandingOrders::<Test>::contains_key(para_id));
+		System::assert_has_event(Event::StandingOrderExpired { para_id, remaining: 2 }.into());
+		assert_eq!(Balances::reserved_balance(alice), 0);
+	});
+}
+
+#[test]
+fn standing_order_placement_and_cancellation_checks() {
+	let alice = 1u64;
+	let bob = 2u64;
+	let para_id = ParaId::from(111);
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		run_to_block(10, |_| None);
+		Balances::make_free_balance_be(&alice, 1_000);
+		Balances::make_free_balance_be(&bob, 1_000);
+		assert_noop!(
+			OnDemand::place_standing_order(RuntimeOrigin::signed(alice), para_id, 1, 0, 20),
+			Error::<Test>::InvalidStandingOrder
+		);
+		assert_noop!(
+			OnDemand::place_standing_order(RuntimeOrigin::signed(alice), para_id, 1, 1, 10),
+			Error::<Test>::InvalidStandingOrder
+		);
+		// The lifetime of a standing order is bounded.
+		let max_lifetime = <Test as on_demand::Config>::MaxStandingOrderLifetime::get();
+		assert_noop!(
+			OnDemand::place_standing_order(
+				RuntimeOrigin::signed(alice),
+				para_id,
+				1,
+				1,
+				10 + max_lifetime + 1
+			),
+			Error::<Test>::InvalidStandingOrder
+		);
+		// The funder must be able to reserve the deposit.
+		assert_noop!(
+			OnDemand::place_standing_order(RuntimeOrigin::signed(3), para_id, 1, 1, 20),
+			BalancesError::<Test, _>::InsufficientBalance
+		);
+
+		assert_ok!(OnDemand::place_standing_order(RuntimeOrigin::signed(alice), para_id, 1, 1, 20));
+		assert_eq!(Balances::reserved_balance(alice), 10);
+		assert_noop!(
+			OnDemand::place_standing_order(RuntimeOrigin::signed(bob), para_id, 1, 1, 20),
+			Error::<Test>::StandingOrderExists
+		);
+
+		// Only the funder can cancel the standing order.
+		assert_noop!(
+			OnDemand::cancel_standing_order(RuntimeOrigin::signed(bob), para_id),
+			Error::<Test>::NotStandingOrderFunder
+		);
+		assert_ok!(OnDemand::cancel_standing_order(RuntimeOrigin::signed(alice), para_id));
+		System::assert_last_event(Event::StandingOrderCancelled { para_id, remaining: 1 }.into());
+		assert_eq!(Balances::reserved_balance(alice), 0);
+		assert_noop!(
+			OnDemand::cancel_standing_order(RuntimeOrigin::signed(alice), para_id),
+			Error::<Test>::NoStandingOrder
+		);
+
+		// The number of standing orders is bounded.
+		let max = <Test as on_demand::Config>::MaxStandingOrders::get();
+		for id in 0..max {
+			assert_ok!(OnDemand::place_standing_order(
+				RuntimeOrigin::signed(alice),
+				ParaId::from(id),
+				1,
+				1,
+				20
+			));
+		}
+		assert_noop!(
+			OnDemand::place_standing_order(RuntimeOrigin::signed(alice), para_id, 1, 1, 20),
+			Error::<Test>::TooManyStandingOrders
+		);
+	});
+}
+
 #[test]
 fn pop_assignment_for_core_works() {
 	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
@@ -30,6 +30,24 @@ use sp_runtime::FixedU128;
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// A standing order of on-demand coretime for a para.
+///
+/// An order is placed on every block where the spot price is at most `max_price`, paid with the
+/// credits of `funder`, until `remaining` orders are placed or the standing order expires.
+#[derive(Encode, Decode, TypeInfo, RuntimeDebug, PartialEq, Eq, Clone)]
+pub struct StandingOrder<AccountId, Balance, BlockNumber> {
+	/// The account whose credits pay for the orders.
+	pub funder: AccountId,
+	/// The maximum spot price paid for an order.
+	pub max_price: Balance,
+	/// The number of orders still to be placed.
+	pub remaining: u32,
+	/// The last block where an order is placed.
+	pub expires_at: BlockNumber,
+	/// The deposit reserved from `funder`, refunded once the standing order is removed.
+	pub deposit: Balance,
+}
+
 /// Meta data for full queue.
 ///
 /// This includes elements with affinity and free entries.
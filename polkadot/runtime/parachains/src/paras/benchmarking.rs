@@ -121,6 +121,17 @@ mod benchmarks {
 		_(RawOrigin::Root, para_id, context);
 	}
 
+	#[benchmark]
+	fn force_set_upgrade_cooldown_override() {
+		let para_id = ParaId::from(1000);
+		let cooldown = BlockNumberFor::<T>::from(10u32);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, para_id, Some(cooldown));
+
+		assert_eq!(UpgradeCooldownOverrides::<T>::get(para_id), Some(cooldown));
+	}
+
 	#[benchmark]
 	fn force_schedule_code_upgrade(c: Linear<MIN_CODE_SIZE, MAX_CODE_SIZE>) {
 		let new_code = ValidationCode(vec![0; c as usize]);
@@ -551,6 +551,7 @@ pub trait WeightInfo {
 	fn force_set_current_code(c: u32) -> Weight;
 	fn force_set_current_head(s: u32) -> Weight;
 	fn force_set_most_recent_context() -> Weight;
+	fn force_set_upgrade_cooldown_override() -> Weight;
 	fn force_schedule_code_upgrade(c: u32) -> Weight;
 	fn force_note_new_head(s: u32) -> Weight;
 	fn force_queue_action() -> Weight;
@@ -575,6 +576,9 @@ impl WeightInfo for TestWeightInfo {
 	fn force_set_most_recent_context() -> Weight {
 		Weight::MAX
 	}
+	fn force_set_upgrade_cooldown_override() -> Weight {
+		Weight::MAX
+	}
 	fn force_schedule_code_upgrade(_c: u32) -> Weight {
 		Weight::MAX
 	}
@@ -678,6 +682,8 @@ pub mod pallet {
 		/// The given validation code was rejected by the PVF pre-checking vote.
 		/// `code_hash` `para_id`
 		PvfCheckRejected(ValidationCodeHash, ParaId),
+		/// The validation upgrade cooldown of a Para was overridden or reset. `para_id`
+		UpgradeCooldownOverridden(ParaId),
 	}
 
 	#[pallet::error]
@@ -837,6 +843,12 @@ pub mod pallet {
 	pub(super) type UpgradeCooldowns<T: Config> =
 		StorageValue<_, Vec<(ParaId, BlockNumberFor<T>)>, ValueQuery>;
 
+	/// The validation upgrade cooldowns of the paras which don't use the
+	/// `validation_upgrade_cooldown` of the host configuration.
+	#[pallet::storage]
+	pub type UpgradeCooldownOverrides<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, BlockNumberFor<T>>;
+
 	/// The list of upcoming code upgrades.
 	///
 	/// Each item is a pair of which para performs a code upgrade and at which relay-chain block it
@@ -1161,6 +1173,24 @@ pub mod pallet {
 			MostRecentContext::<T>::insert(&para, context);
 			Ok(())
 		}
+
+		/// Override the validation upgrade cooldown of a para, or reset it to the
+		/// `validation_upgrade_cooldown` of the host configuration if `cooldown` is `None`.
+		///
+		/// The cooldown applies from the next code upgrade of the para, the cooldown of an upgrade
+		/// already scheduled is left as is.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::force_set_upgrade_cooldown_override())]
+		pub fn force_set_upgrade_cooldown_override(
+			origin: OriginFor<T>,
+			para: ParaId,
+			cooldown: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			UpgradeCooldownOverrides::<T>::set(&para, cooldown);
+			Self::deposit_event(Event::UpgradeCooldownOverridden(para));
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -1363,6 +1393,7 @@ impl<T: Config> Pallet<T> {
 
 					Heads::<T>::remove(&para);
 					MostRecentContext::<T>::remove(&para);
+					UpgradeCooldownOverrides::<T>::remove(&para);
 					FutureCodeUpgrades::<T>::remove(&para);
 					UpgradeGoAheadSignal::<T>::remove(&para);
 					UpgradeRestrictionSignal::<T>::remove(&para);
@@ -2010,7 +2041,9 @@ impl<T: Config> Pallet<T> {
 		FutureCodeHash::<T>::insert(&id, &code_hash);
 		UpgradeRestrictionSignal::<T>::insert(&id, UpgradeRestriction::Present);
 
-		let next_possible_upgrade_at = inclusion_block_number + cfg.validation_upgrade_cooldown;
+		let cooldown =
+			UpgradeCooldownOverrides::<T>::get(&id).unwrap_or(cfg.validation_upgrade_cooldown);
+		let next_possible_upgrade_at = inclusion_block_number + cooldown;
 		UpgradeCooldowns::<T>::mutate(|upgrade_cooldowns| {
 			let insert_idx = upgrade_cooldowns
 				.binary_search_by_key(&next_possible_upgrade_at, |&(_, b)| b)
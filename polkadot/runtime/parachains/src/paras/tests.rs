@@ -778,6 +778,61 @@ fn submit_code_change_when_not_allowed_is_err() {
 	});
 }
 
+#[test]
+fn upgrade_cooldown_override_works() {
+	let validation_upgrade_cooldown = 100;
+
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			para_kind: ParaKind::Parachain,
+			genesis_head: dummy_head_data(),
+			validation_code: vec![1, 2, 3].into(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration { validation_upgrade_cooldown, ..Default::default() },
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+		run_to_block(1, Some(vec![1]));
+
+		assert_err!(
+			Paras::force_set_upgrade_cooldown_override(RuntimeOrigin::signed(1), para_id, Some(5)),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(Paras::force_set_upgrade_cooldown_override(
+			RuntimeOrigin::root(),
+			para_id,
+			Some(5)
+		));
+		assert_eq!(UpgradeCooldownOverrides::<Test>::get(para_id), Some(5));
+
+		// The override is used instead of the cooldown of the host configuration.
+		Paras::schedule_code_upgrade(
+			para_id,
+			test_validation_code_1(),
+			1,
+			&configuration::ActiveConfig::<Test>::get(),
+			UpgradeStrategy::SetGoAheadSignal,
+		);
+		assert_eq!(UpgradeCooldowns::<Test>::get(), vec![(para_id, 1 + 5)]);
+
+		assert_ok!(Paras::force_set_upgrade_cooldown_override(
+			RuntimeOrigin::root(),
+			para_id,
+			None
+		));
+		assert!(!UpgradeCooldownOverrides::<Test>::contains_key(para_id));
+	});
+}
+
 #[test]
 fn upgrade_restriction_elapsed_doesnt_mean_can_upgrade() {
 	// Situation: parachain scheduled upgrade but it doesn't produce any candidate after
@@ -61,8 +61,8 @@ use polkadot_primitives::{
 use polkadot_runtime_common::{
 	assigned_slots, auctions, claims, crowdloan, identity_migrator, impl_runtime_weights,
 	impls::{
-		ContainsParts, LocatableAssetConverter, ToAuthor, VersionedLocatableAsset,
-		VersionedLocationConverter,
+		AccountIdToBeneficiary, ContainsParts, LocatableAssetConverter, ToAuthor,
+		VersionedLocatableAsset, VersionedLocationConverter,
 	},
 	paras_registrar, paras_sudo_wrapper, prod_or_fast, slots,
 	traits::{Leaser, OnSwap},
@@ -100,8 +100,8 @@ use frame_support::{
 	traits::{
 		fungible::HoldConsideration, tokens::UnityOrOuterConversion, Contains, EitherOf,
 		EitherOfDiverse, EnsureOrigin, EnsureOriginWithArg, EverythingBut, InstanceFilter,
-		KeyOwnerProofSystem, LinearStoragePrice, PrivilegeCmp, ProcessMessage, ProcessMessageError,
-		StorageMapShim, WithdrawReasons,
+		KeyOwnerProofSystem, LinearStoragePrice, MapSuccess, PrivilegeCmp, ProcessMessage,
+		ProcessMessageError, StorageMapShim, WithdrawReasons,
 	},
 	weights::{ConstantMultiplier, WeightMeter, WeightToFee as _},
 	PalletId,
@@ -539,6 +539,7 @@ impl pallet_treasury::Config for Runtime {
 	type AssetKind = VersionedLocatableAsset;
 	type Beneficiary = VersionedLocation;
 	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type PartialPayoutOrigin = MapSuccess<EnsureSigned<AccountId>, AccountIdToBeneficiary>;
 	type Paymaster = PayOverXcm<
 		TreasuryInteriorLocation,
 		crate::xcm_config::XcmRouter,
@@ -783,6 +784,9 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type MaxCallLen = ConstU32<0>;
+	type CallDepositPerByte = ();
+	type CallExpiry = ConstU32<0>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -990,6 +994,7 @@ impl pallet_proxy::Config for Runtime {
 	type WeightInfo = weights::pallet_proxy::WeightInfo<Runtime>;
 	type MaxPending = MaxPending;
 	type CallHasher = BlakeTwo256;
+	type AnnouncementFilter = ();
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
@@ -1078,6 +1083,7 @@ impl pallet_message_queue::Config for Runtime {
 	type MaxStale = MessageQueueMaxStale;
 	type ServiceWeight = MessageQueueServiceWeight;
 	type IdleMaxServiceWeight = MessageQueueServiceWeight;
+	type QueueServicePolicy = ();
 	#[cfg(not(feature = "runtime-benchmarks"))]
 	type MessageProcessor = MessageProcessor;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1151,6 +1157,9 @@ parameter_types! {
 	// Keep 2 timeslices worth of revenue information.
 	pub const MaxHistoricalRevenue: BlockNumber = 2 * TIMESLICE_PERIOD;
 	pub const OnDemandPalletId: PalletId = PalletId(*b"py/ondmd");
+	pub const MaxStandingOrders: u32 = 100;
+	pub const StandingOrderDeposit: Balance = deposit(1, 88);
+	pub const MaxStandingOrderLifetime: BlockNumber = 28 * DAYS;
 }
 
 impl parachains_on_demand::Config for Runtime {
@@ -1160,6 +1169,9 @@ impl parachains_on_demand::Config for Runtime {
 	type WeightInfo = weights::polkadot_runtime_parachains_on_demand::WeightInfo<Runtime>;
 	type MaxHistoricalRevenue = MaxHistoricalRevenue;
 	type PalletId = OnDemandPalletId;
+	type MaxStandingOrders = MaxStandingOrders;
+	type StandingOrderDeposit = StandingOrderDeposit;
+	type MaxStandingOrderLifetime = MaxStandingOrderLifetime;
 }
 
 impl parachains_assigner_coretime::Config for Runtime {}
@@ -1763,6 +1775,7 @@ pub mod migrations {
 
 		// migrates session storage item
 		pallet_session::migrations::v1::MigrateV0ToV1<Runtime, pallet_session::migrations::v1::InitOffenceSeverity<Runtime>>,
+		pallet_scheduler::migration::v5::MigrateV4ToV5<Runtime>,
 
         // permanent
         pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
@@ -2397,6 +2410,15 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl polkadot_primitives::runtime_api::OnDemandApi<Block, Balance> for Runtime {
+		fn spot_price(
+			blocks: u32,
+			queue_size: Option<u32>,
+		) -> polkadot_primitives::vstaging::OnDemandSpotPrice<Balance> {
+			OnDemandAssignmentProvider::spot_price(blocks, queue_size)
+		}
+	}
+
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce> for Runtime {
 		fn account_nonce(account: AccountId) -> Nonce {
 			System::account_nonce(account)
@@ -381,4 +381,27 @@ impl<T: frame_system::Config> polkadot_runtime_parachains::hrmp::WeightInfo for
 			.saturating_add(T::DbWeight::get().reads(23))
 			.saturating_add(T::DbWeight::get().writes(11))
 	}
+	/// Storage: `Hrmp::HrmpAutoAcceptPolicies` (r:0 w:1)
+	/// Proof: `Hrmp::HrmpAutoAcceptPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn hrmp_set_auto_accept_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_217_000 picoseconds.
+		Weight::from_parts(14_902_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Hrmp::HrmpAutoAcceptPolicies` (r:1 w:1)
+	/// Proof: `Hrmp::HrmpAutoAcceptPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn hrmp_remove_auto_accept_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `141`
+		//  Estimated: `3606`
+		// Minimum execution time: 17_534_000 picoseconds.
+		Weight::from_parts(18_113_000, 0)
+			.saturating_add(Weight::from_parts(0, 3606))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
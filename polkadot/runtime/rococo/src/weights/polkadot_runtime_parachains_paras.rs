@@ -101,6 +101,17 @@ impl<T: frame_system::Config> polkadot_runtime_parachains::paras::WeightInfo for
 			.saturating_add(Weight::from_parts(0, 0))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Paras::UpgradeCooldownOverrides` (r:0 w:1)
+	/// Proof: `Paras::UpgradeCooldownOverrides` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn force_set_upgrade_cooldown_override() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_548_000 picoseconds.
+		Weight::from_parts(3_776_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 	/// Storage: `Paras::FutureCodeHash` (r:1 w:1)
 	/// Proof: `Paras::FutureCodeHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Paras::CurrentCodeHash` (r:1 w:0)
@@ -399,6 +399,7 @@ impl pallet_staking::Config for Runtime {
 	type NominationsQuota = pallet_staking::FixedNominationsQuota<MAX_QUOTA_NOMINATIONS>;
 	type MaxUnlockingChunks = frame_support::traits::ConstU32<32>;
 	type MaxControllersInDeprecationBatch = ConstU32<5900>;
+	type MaxAutoPayoutWeight = ();
 	type HistoryDepth = frame_support::traits::ConstU32<84>;
 	type BenchmarkingConfig = polkadot_runtime_common::StakingBenchmarkingConfig;
 	type EventListeners = ();
@@ -609,6 +610,9 @@ parameter_types! {
 	// Keep 2 timeslices worth of revenue information.
 	pub const MaxHistoricalRevenue: BlockNumber = 2 * 5;
 	pub const OnDemandPalletId: PalletId = PalletId(*b"py/ondmd");
+	pub const MaxStandingOrders: u32 = 100;
+	pub const StandingOrderDeposit: Balance = CENTS;
+	pub const MaxStandingOrderLifetime: BlockNumber = 28 * DAYS;
 }
 
 impl parachains_dmp::Config for Runtime {}
@@ -637,6 +641,9 @@ impl parachains_on_demand::Config for Runtime {
 	type WeightInfo = parachains_on_demand::TestWeightInfo;
 	type MaxHistoricalRevenue = MaxHistoricalRevenue;
 	type PalletId = OnDemandPalletId;
+	type MaxStandingOrders = MaxStandingOrders;
+	type StandingOrderDeposit = StandingOrderDeposit;
+	type MaxStandingOrderLifetime = MaxStandingOrderLifetime;
 }
 
 impl parachains_assigner_coretime::Config for Runtime {}
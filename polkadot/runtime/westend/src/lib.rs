@@ -38,8 +38,8 @@ use frame_support::{
 	traits::{
 		fungible::HoldConsideration, tokens::UnityOrOuterConversion, ConstU32, Contains, EitherOf,
 		EitherOfDiverse, EnsureOriginWithArg, EverythingBut, FromContains, InstanceFilter,
-		KeyOwnerProofSystem, LinearStoragePrice, Nothing, ProcessMessage, ProcessMessageError,
-		VariantCountOf, WithdrawReasons,
+		KeyOwnerProofSystem, LinearStoragePrice, MapSuccess, Nothing, ProcessMessage,
+		ProcessMessageError, VariantCountOf, WithdrawReasons,
 	},
 	weights::{ConstantMultiplier, WeightMeter, WeightToFee as _},
 	PalletId,
@@ -70,8 +70,8 @@ use polkadot_runtime_common::{
 	elections::OnChainAccuracy,
 	identity_migrator, impl_runtime_weights,
 	impls::{
-		ContainsParts, LocatableAssetConverter, ToAuthor, VersionedLocatableAsset,
-		VersionedLocationConverter,
+		AccountIdToBeneficiary, ContainsParts, LocatableAssetConverter, ToAuthor,
+		VersionedLocatableAsset, VersionedLocationConverter,
 	},
 	paras_registrar, paras_sudo_wrapper, prod_or_fast, slots,
 	traits::OnSwap,
@@ -122,7 +122,9 @@ use xcm::{
 use xcm_builder::PayOverXcm;
 
 use xcm_runtime_apis::{
-	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
+	dry_run::{
+		CallDryRunEffects, CallMultiHopDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects,
+	},
 	fees::Error as XcmPaymentApiError,
 };
 
@@ -772,6 +774,7 @@ impl pallet_staking::Config for Runtime {
 	type MaxUnlockingChunks = frame_support::traits::ConstU32<32>;
 	type HistoryDepth = frame_support::traits::ConstU32<84>;
 	type MaxControllersInDeprecationBatch = MaxControllersInDeprecationBatch;
+	type MaxAutoPayoutWeight = ();
 	type BenchmarkingConfig = polkadot_runtime_common::StakingBenchmarkingConfig;
 	type EventListeners = (NominationPools, DelegatedStaking);
 	type WeightInfo = weights::pallet_staking::WeightInfo<Runtime>;
@@ -929,6 +932,7 @@ impl pallet_treasury::Config for Runtime {
 	type AssetKind = VersionedLocatableAsset;
 	type Beneficiary = VersionedLocation;
 	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type PartialPayoutOrigin = MapSuccess<EnsureSigned<AccountId>, AccountIdToBeneficiary>;
 	type Paymaster = PayOverXcm<
 		TreasuryInteriorLocation,
 		crate::xcm_config::XcmRouter,
@@ -1129,6 +1133,9 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type MaxCallLen = ConstU32<0>;
+	type CallDepositPerByte = ();
+	type CallExpiry = ConstU32<0>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -1341,6 +1348,7 @@ impl pallet_proxy::Config for Runtime {
 	type WeightInfo = weights::pallet_proxy::WeightInfo<Runtime>;
 	type MaxPending = MaxPending;
 	type CallHasher = BlakeTwo256;
+	type AnnouncementFilter = ();
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
@@ -1423,6 +1431,7 @@ impl pallet_message_queue::Config for Runtime {
 	type MaxStale = MessageQueueMaxStale;
 	type ServiceWeight = MessageQueueServiceWeight;
 	type IdleMaxServiceWeight = MessageQueueServiceWeight;
+	type QueueServicePolicy = ();
 	#[cfg(not(feature = "runtime-benchmarks"))]
 	type MessageProcessor = MessageProcessor;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1496,6 +1505,9 @@ parameter_types! {
 	// Keep 2 timeslices worth of revenue information.
 	pub const MaxHistoricalRevenue: BlockNumber = 2 * TIMESLICE_PERIOD;
 	pub const OnDemandPalletId: PalletId = PalletId(*b"py/ondmd");
+	pub const MaxStandingOrders: u32 = 100;
+	pub const StandingOrderDeposit: Balance = deposit(1, 88);
+	pub const MaxStandingOrderLifetime: BlockNumber = 28 * DAYS;
 }
 
 impl parachains_on_demand::Config for Runtime {
@@ -1505,6 +1517,9 @@ impl parachains_on_demand::Config for Runtime {
 	type WeightInfo = weights::polkadot_runtime_parachains_on_demand::WeightInfo<Runtime>;
 	type MaxHistoricalRevenue = MaxHistoricalRevenue;
 	type PalletId = OnDemandPalletId;
+	type MaxStandingOrders = MaxStandingOrders;
+	type StandingOrderDeposit = StandingOrderDeposit;
+	type MaxStandingOrderLifetime = MaxStandingOrderLifetime;
 }
 
 impl parachains_assigner_coretime::Config for Runtime {}
@@ -2025,6 +2040,8 @@ pub mod migrations {
 			Runtime,
 			pallet_staking::migrations::v17::MigrateDisabledToSession<Runtime>,
 		>,
+		pallet_staking::migrations::v17::MigrateV16ToV17<Runtime>,
+		pallet_scheduler::migration::v5::MigrateV4ToV5<Runtime>,
 		// permanent
 		pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 	);
@@ -2600,6 +2617,15 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl polkadot_primitives::runtime_api::OnDemandApi<Block, Balance> for Runtime {
+		fn spot_price(
+			blocks: u32,
+			queue_size: Option<u32>,
+		) -> polkadot_primitives::vstaging::OnDemandSpotPrice<Balance> {
+			OnDemandAssignmentProvider::spot_price(blocks, queue_size)
+		}
+	}
+
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce> for Runtime {
 		fn account_nonce(account: AccountId) -> Nonce {
 			System::account_nonce(account)
@@ -2674,6 +2700,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl xcm_runtime_apis::dry_run::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
 		fn dry_run_call(origin: OriginCaller, call: RuntimeCall, result_xcms_version: XcmVersion) -> Result<CallDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
 			XcmPallet::dry_run_call::<Runtime, xcm_config::XcmRouter, OriginCaller, RuntimeCall>(origin, call, result_xcms_version)
@@ -2682,6 +2709,10 @@ sp_api::impl_runtime_apis! {
 		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
 			XcmPallet::dry_run_xcm::<Runtime, xcm_config::XcmRouter, RuntimeCall, xcm_config::XcmConfig>(origin_location, xcm)
 		}
+
+		fn dry_run_call_multi_hop(origin: OriginCaller, call: RuntimeCall, result_xcms_version: XcmVersion) -> Result<CallMultiHopDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
+			XcmPallet::dry_run_call_multi_hop::<Runtime, xcm_config::XcmRouter, OriginCaller, RuntimeCall, xcm_config::SystemParachainsFeeEstimator>(origin, call, result_xcms_version)
+		}
 	}
 
 	impl xcm_runtime_apis::conversions::LocationToAccountApi<Block, AccountId> for Runtime {
@@ -2757,6 +2788,7 @@ sp_api::impl_runtime_apis! {
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			log::info!("try-runtime::on_runtime_upgrade westend.");
@@ -2764,6 +2796,14 @@ sp_api::impl_runtime_apis! {
 			(weight, BlockWeights::get().max_block)
 		}
 
+		fn on_runtime_upgrade_with_report(
+			checks: frame_try_runtime::UpgradeCheckSelect,
+			idempotency_checks: bool,
+		) -> frame_try_runtime::UpgradeReport {
+			log::info!("try-runtime::on_runtime_upgrade_with_report westend.");
+			Executive::try_runtime_upgrade_with_report(checks, idempotency_checks).unwrap()
+		}
+
 		fn execute_block(
 			block: Block,
 			state_root_check: bool,
@@ -3051,4 +3091,13 @@ sp_api::impl_runtime_apis! {
 			XcmPallet::is_trusted_teleporter(asset, location)
 		}
 	}
+
+	impl xcm_runtime_apis::trapped_assets::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(origin: VersionedLocation) -> Result<
+			Vec<xcm_runtime_apis::trapped_assets::TrappedAssets>,
+			xcm_runtime_apis::trapped_assets::Error
+		> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
 }
@@ -890,4 +890,27 @@ impl<T: frame_system::Config> pallet_staking::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(10_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: `Staking::SlashDeferDurations` (r:0 w:1)
+	/// Proof: `Staking::SlashDeferDurations` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn set_slash_defer_duration() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_791_000 picoseconds.
+		Weight::from_parts(2_985_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Staking::UnappliedSlashes` (r:1 w:1)
+	/// Proof: `Staking::UnappliedSlashes` (`max_values`: None, `max_size`: Some(3231), added: 5706, mode: `MaxEncodedLen`)
+	fn reduce_deferred_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `122707`
+		//  Estimated: `6696`
+		// Minimum execution time: 29_830_000 picoseconds.
+		Weight::from_parts(30_661_000, 0)
+			.saturating_add(Weight::from_parts(0, 6696))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
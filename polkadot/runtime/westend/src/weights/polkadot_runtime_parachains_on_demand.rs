@@ -123,4 +123,36 @@ impl<T: frame_system::Config> polkadot_runtime_parachains::on_demand::WeightInfo
 			.saturating_add(T::DbWeight::get().writes(4))
 			.saturating_add(Weight::from_parts(0, 8).saturating_mul(s.into()))
 	}
+	/// Storage: `OnDemandAssignmentProvider::StandingOrders` (r:1 w:1)
+	/// Proof: `OnDemandAssignmentProvider::StandingOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `OnDemandAssignmentProvider::CounterForStandingOrders` (r:1 w:1)
+	/// Proof: `OnDemandAssignmentProvider::CounterForStandingOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn place_standing_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `109`
+		//  Estimated: `3593`
+		// Minimum execution time: 12_208_000 picoseconds.
+		Weight::from_parts(12_655_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `OnDemandAssignmentProvider::StandingOrders` (r:1 w:1)
+	/// Proof: `OnDemandAssignmentProvider::StandingOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `OnDemandAssignmentProvider::CounterForStandingOrders` (r:1 w:1)
+	/// Proof: `OnDemandAssignmentProvider::CounterForStandingOrders` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn cancel_standing_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `172`
+		//  Estimated: `3637`
+		// Minimum execution time: 14_873_000 picoseconds.
+		Weight::from_parts(15_391_000, 0)
+			.saturating_add(Weight::from_parts(0, 3637))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 }
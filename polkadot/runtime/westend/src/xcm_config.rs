@@ -22,9 +22,11 @@ use super::{
 	TransactionByteFee, Treasury, WeightToFee, XcmPallet,
 };
 use crate::governance::pallet_custom_origins::Treasurer;
+use codec::Encode;
 use frame_support::{
 	parameter_types,
 	traits::{Contains, Disabled, Equals, Everything, Nothing},
+	weights::WeightToFee as _,
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
@@ -47,7 +49,8 @@ use xcm_builder::{
 	TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
 	XcmFeeManagerFromComponents,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{traits::WeightBounds, XcmExecutor};
+use xcm_runtime_apis::fees::RemoteFeeEstimator;
 
 parameter_types! {
 	pub const TokenLocation: Location = Here.into_location();
@@ -318,3 +321,37 @@ impl pallet_xcm::Config for Runtime {
 	// Aliasing is disabled: xcm_executor::Config::Aliasers only allows `AliasChildLocation`.
 	type AuthorizedAliasConsideration = Disabled;
 }
+
+/// Estimates the fees of the system parachains, for the multi-hop dry runs.
+///
+/// The system parachains are assumed to weigh and price their execution like the relay chain, in
+/// the relay token, and to charge its base delivery fees for the messages they send.
+pub struct SystemParachainsFeeEstimator;
+impl SystemParachainsFeeEstimator {
+	fn is_system_parachain(location: &Location) -> bool {
+		IsChildSystemParachain::<ParaId>::contains(location)
+	}
+}
+
+impl RemoteFeeEstimator for SystemParachainsFeeEstimator {
+	fn weigh(location: &Location, message: &Xcm<()>) -> Option<Weight> {
+		if !Self::is_system_parachain(location) {
+			return None
+		}
+		<XcmConfig as xcm_executor::Config>::Weigher::weight(&mut message.clone().into()).ok()
+	}
+
+	fn weight_to_fee(location: &Location, weight: Weight, asset: &AssetId) -> Option<u128> {
+		(Self::is_system_parachain(location) && asset.0 == Location::parent())
+			.then(|| WeightToFee::weight_to_fee(&weight))
+	}
+
+	fn delivery_fees(location: &Location, _: &Location, message: &Xcm<()>) -> Option<Assets> {
+		Self::is_system_parachain(location).then(|| {
+			let fee = BaseDeliveryFee::get().saturating_add(
+				TransactionByteFee::get().saturating_mul(message.encoded_size() as u128),
+			);
+			(Parent, fee).into()
+		})
+	}
+}
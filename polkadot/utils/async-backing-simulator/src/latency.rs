@@ -0,0 +1,142 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Latency distributions used to model the network and the validators.
+
+use rand::Rng;
+use rand_distr::{Distribution, Exp, Normal};
+use std::{fmt, str::FromStr};
+
+/// A distribution of latencies, in milliseconds.
+///
+/// Parsed from `fixed:<ms>` (or just `<ms>`), `uniform:<min>-<max>`,
+/// `normal:<mean>,<std_dev>` and `exp:<mean>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LatencyDistribution {
+	/// Always the same latency.
+	Fixed(u64),
+	/// Uniformly distributed between `min` and `max`, inclusive.
+	Uniform { min: u64, max: u64 },
+	/// Normally distributed. Negative samples are clamped to zero.
+	Normal { mean: f64, std_dev: f64 },
+	/// Exponentially distributed with the given mean, for a long tail of slow samples.
+	Exponential { mean: f64 },
+}
+
+impl LatencyDistribution {
+	/// Sample a latency, in milliseconds.
+	pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+		let latency = match *self {
+			Self::Fixed(latency) => return latency,
+			Self::Uniform { min, max } => return rng.gen_range(min.min(max)..=max.max(min)),
+			Self::Normal { mean, std_dev } =>
+				Normal::new(mean, std_dev).map_or(mean, |normal| normal.sample(rng)),
+			Self::Exponential { mean } => Exp::new(1.0 / mean).map_or(mean, |exp| exp.sample(rng)),
+		};
+
+		latency.max(0.0).round() as u64
+	}
+}
+
+impl FromStr for LatencyDistribution {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (kind, params) = s.split_once(':').unwrap_or(("fixed", s));
+		let invalid = || format!("invalid {kind} latency distribution: {params}");
+		let number = |s: &str| s.trim().parse::<f64>().ok().filter(|n| n.is_finite() && *n >= 0.0);
+
+		match kind {
+			"fixed" => params.trim().parse().map(Self::Fixed).map_err(|_| invalid()),
+			"uniform" => {
+				let (min, max) = params.split_once('-').ok_or_else(invalid)?;
+				let min = min.trim().parse().map_err(|_| invalid())?;
+				let max = max.trim().parse().map_err(|_| invalid())?;
+				if min > max {
+					return Err(invalid())
+				}
+				Ok(Self::Uniform { min, max })
+			},
+			"normal" => {
+				let (mean, std_dev) = params.split_once(',').ok_or_else(invalid)?;
+				let mean = number(mean).ok_or_else(invalid)?;
+				let std_dev = number(std_dev).ok_or_else(invalid)?;
+				Ok(Self::Normal { mean, std_dev })
+			},
+			"exp" => {
+				let mean = number(params).filter(|mean| *mean > 0.0).ok_or_else(invalid)?;
+				Ok(Self::Exponential { mean })
+			},
+			_ => Err(format!("unknown latency distribution: {kind}")),
+		}
+	}
+}
+
+impl fmt::Display for LatencyDistribution {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Fixed(latency) => write!(f, "fixed:{latency}"),
+			Self::Uniform { min, max } => write!(f, "uniform:{min}-{max}"),
+			Self::Normal { mean, std_dev } => write!(f, "normal:{mean},{std_dev}"),
+			Self::Exponential { mean } => write!(f, "exp:{mean}"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	#[test]
+	fn parses_distributions() {
+		assert_eq!("250".parse(), Ok(LatencyDistribution::Fixed(250)));
+		assert_eq!("fixed:250".parse(), Ok(LatencyDistribution::Fixed(250)));
+		assert_eq!(
+			"uniform:100-500".parse(),
+			Ok(LatencyDistribution::Uniform { min: 100, max: 500 })
+		);
+		assert_eq!(
+			"normal:300,50".parse(),
+			Ok(LatencyDistribution::Normal { mean: 300.0, std_dev: 50.0 })
+		);
+		assert_eq!("exp:200".parse(), Ok(LatencyDistribution::Exponential { mean: 200.0 }));
+
+		for invalid in ["uniform:500-100", "normal:300", "exp:0", "normal:-1,2", "pareto:1"] {
+			assert!(invalid.parse::<LatencyDistribution>().is_err(), "{invalid}");
+		}
+	}
+
+	#[test]
+	fn display_round_trips() {
+		for s in ["fixed:250", "uniform:100-500", "normal:300,50", "exp:200"] {
+			assert_eq!(s.parse::<LatencyDistribution>().unwrap().to_string(), s);
+		}
+	}
+
+	#[test]
+	fn samples_stay_in_range() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+		let uniform = LatencyDistribution::Uniform { min: 100, max: 200 };
+		assert!((0..1000).map(|_| uniform.sample(&mut rng)).all(|l| (100..=200).contains(&l)));
+
+		// Samples are all negative, they must be clamped.
+		let normal = LatencyDistribution::Normal { mean: -1000.0, std_dev: 10.0 };
+		assert!((0..1000).map(|_| normal.sample(&mut rng)).all(|l| l == 0));
+	}
+}
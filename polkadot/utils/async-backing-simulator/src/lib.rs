@@ -0,0 +1,28 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Simulator of the async backing pipeline of a parachain.
+//!
+//! Models the candidate throughput and inclusion latency of a parachain as a function of the
+//! [`AsyncBackingParams`](polkadot_primitives::AsyncBackingParams) and of the latencies of the
+//! network, so that the effect of a change of `max_candidate_depth` or `allowed_ancestry_len`
+//! can be estimated before it is enacted on a production network.
+
+mod latency;
+mod simulation;
+
+pub use latency::LatencyDistribution;
+pub use simulation::{simulate, NetworkConfig, SimulationConfig, SimulationReport};
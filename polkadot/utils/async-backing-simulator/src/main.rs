@@ -0,0 +1,149 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Estimate the throughput and inclusion latency of a parachain for a range of async backing
+//! parameters.
+//!
+//! Every combination of the given `max_candidate_depth` and `allowed_ancestry_len` values is
+//! simulated with the same seed, e.g.
+//!
+//! ```text
+//! async-backing-simulator --max-candidate-depth 0,1,3 --allowed-ancestry-len 0,2 \
+//!     --backing exp:2000
+//! ```
+
+use clap::Parser;
+use polkadot_async_backing_simulator::{
+	simulate, LatencyDistribution, NetworkConfig, SimulationConfig,
+};
+use polkadot_primitives::AsyncBackingParams;
+
+#[derive(Debug, Parser)]
+struct Opt {
+	/// Values of `max_candidate_depth` to simulate.
+	#[arg(long, value_delimiter = ',', default_value = "3")]
+	max_candidate_depth: Vec<u32>,
+
+	/// Values of `allowed_ancestry_len` to simulate.
+	#[arg(long, value_delimiter = ',', default_value = "2")]
+	allowed_ancestry_len: Vec<u32>,
+
+	/// Time between two relay chain blocks, in milliseconds.
+	#[arg(long, default_value_t = 6000)]
+	relay_block_time: u64,
+
+	/// Time between two collation attempts, in milliseconds.
+	#[arg(long, default_value_t = 6000)]
+	para_block_time: u64,
+
+	/// Time of the first collation attempt, in milliseconds.
+	#[arg(long, default_value_t = 1000)]
+	collation_offset: u64,
+
+	/// The number of relay chain blocks to simulate.
+	#[arg(long, default_value_t = 10_000)]
+	relay_blocks: u32,
+
+	/// Seed of the latency samples.
+	#[arg(long, default_value_t = 0)]
+	seed: u64,
+
+	/// Latency of the relay chain blocks reaching the collator.
+	///
+	/// Latencies are given as `fixed:<ms>`, `uniform:<min>-<max>`, `normal:<mean>,<std_dev>`
+	/// or `exp:<mean>`.
+	#[arg(long, default_value = "normal:300,100")]
+	relay_propagation: LatencyDistribution,
+
+	/// Latency of the collations reaching the backing group.
+	#[arg(long, default_value = "normal:300,100")]
+	collation_transfer: LatencyDistribution,
+
+	/// Time for the backing group to validate a candidate.
+	#[arg(long, default_value = "normal:1500,500")]
+	backing: LatencyDistribution,
+
+	/// Time for a backed candidate to become available.
+	#[arg(long, default_value = "normal:1500,500")]
+	availability: LatencyDistribution,
+
+	/// Print the reports as JSON.
+	#[arg(long)]
+	json: bool,
+}
+
+fn main() -> Result<(), serde_json::Error> {
+	let opt = Opt::parse();
+	let network = NetworkConfig {
+		relay_propagation: opt.relay_propagation,
+		collation_transfer: opt.collation_transfer,
+		backing: opt.backing,
+		availability: opt.availability,
+	};
+
+	let mut reports = Vec::new();
+	for &max_candidate_depth in &opt.max_candidate_depth {
+		for &allowed_ancestry_len in &opt.allowed_ancestry_len {
+			reports.push(simulate(&SimulationConfig {
+				params: AsyncBackingParams { max_candidate_depth, allowed_ancestry_len },
+				relay_block_time: opt.relay_block_time,
+				para_block_time: opt.para_block_time,
+				collation_offset: opt.collation_offset,
+				relay_blocks: opt.relay_blocks,
+				seed: opt.seed,
+				network,
+			}));
+		}
+	}
+
+	if opt.json {
+		println!("{}", serde_json::to_string_pretty(&reports)?);
+		return Ok(())
+	}
+
+	println!(
+		"{:>5} {:>8} {:>10} {:>8} {:>8} {:>8} {:>8} {:>9} {:>8} {:>8} {:>8}",
+		"depth",
+		"ancestry",
+		"throughput",
+		"built",
+		"included",
+		"dropped",
+		"skipped",
+		"mean (ms)",
+		"p50 (ms)",
+		"p95 (ms)",
+		"max (ms)",
+	);
+	for report in reports {
+		println!(
+			"{:>5} {:>8} {:>10.3} {:>8} {:>8} {:>8} {:>8} {:>9.0} {:>8} {:>8} {:>8}",
+			report.max_candidate_depth,
+			report.allowed_ancestry_len,
+			report.throughput,
+			report.built,
+			report.included,
+			report.dropped,
+			report.skipped,
+			report.mean_latency,
+			report.p50_latency,
+			report.p95_latency,
+			report.max_latency,
+		);
+	}
+
+	Ok(())
+}
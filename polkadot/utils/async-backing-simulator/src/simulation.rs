@@ -0,0 +1,273 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Discrete event simulation of a single parachain core.
+//!
+//! The relay chain authors a block every `relay_block_time`. Each block can back one candidate
+//! of the parachain and include the candidate backed in a previous block, once it is available.
+//! A collator builds a candidate every `para_block_time`, on top of the last relay block it has
+//! heard of and of the last candidate it built. Async backing restricts this pipeline in two
+//! ways:
+//!
+//! - the collator can't build when the unincluded segment (candidates built but not included yet)
+//!   already holds `max_candidate_depth + 1` candidates,
+//! - a candidate can only be backed while its relay parent is at most `allowed_ancestry_len` blocks
+//!   older than the parent of the backing block. Candidates exceeding that are dropped, together
+//!   with their descendants.
+
+use crate::LatencyDistribution;
+use polkadot_primitives::AsyncBackingParams;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Latencies of the steps a candidate goes through.
+#[derive(Debug, Clone, Copy)]
+pub struct NetworkConfig {
+	/// Time for a new relay chain block to reach the collator.
+	pub relay_propagation: LatencyDistribution,
+	/// Time for a collation to reach the backing group.
+	pub collation_transfer: LatencyDistribution,
+	/// Time for the backing group to validate the candidate and gossip its statements.
+	pub backing: LatencyDistribution,
+	/// Time for the candidate to become available after it has been backed on chain.
+	pub availability: LatencyDistribution,
+}
+
+/// Configuration of a simulation.
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationConfig {
+	/// The async backing parameters to simulate.
+	pub params: AsyncBackingParams,
+	/// Time between two relay chain blocks, in milliseconds.
+	pub relay_block_time: u64,
+	/// Time between two collation attempts of the collator, in milliseconds.
+	pub para_block_time: u64,
+	/// Time of the first collation attempt, in milliseconds. Collators build shortly after the
+	/// start of a relay chain slot, once they have imported the new relay chain block.
+	pub collation_offset: u64,
+	/// The number of relay chain blocks to simulate.
+	pub relay_blocks: u32,
+	/// The seed of the latency samples, the same seed always gives the same report.
+	pub seed: u64,
+	/// The latencies of the network and the validators.
+	pub network: NetworkConfig,
+}
+
+/// The outcome of a simulation.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReport {
+	/// The simulated `max_candidate_depth`.
+	pub max_candidate_depth: u32,
+	/// The simulated `allowed_ancestry_len`.
+	pub allowed_ancestry_len: u32,
+	/// The number of simulated relay chain blocks.
+	pub relay_blocks: u32,
+	/// The number of candidates built by the collator.
+	pub built: u32,
+	/// The number of candidates included in the relay chain.
+	pub included: u32,
+	/// The number of candidates dropped because their relay parent got too old.
+	pub dropped: u32,
+	/// The number of collation attempts skipped because the unincluded segment was full.
+	pub skipped: u32,
+	/// Included candidates per relay chain block.
+	pub throughput: f64,
+	/// Mean time between building a candidate and its inclusion, in milliseconds.
+	pub mean_latency: f64,
+	/// Median inclusion latency, in milliseconds.
+	pub p50_latency: u64,
+	/// 95th percentile of the inclusion latency, in milliseconds.
+	pub p95_latency: u64,
+	/// Maximum inclusion latency, in milliseconds.
+	pub max_latency: u64,
+}
+
+/// A candidate in the unincluded segment.
+struct Candidate {
+	built_at: u64,
+	relay_parent: u32,
+	/// When the backing group is done with the candidate.
+	backable_at: u64,
+	/// When the candidate becomes available, once backed on chain.
+	available_at: Option<u64>,
+}
+
+/// Run a simulation.
+pub fn simulate(config: &SimulationConfig) -> SimulationReport {
+	let SimulationConfig {
+		params,
+		relay_block_time,
+		para_block_time,
+		collation_offset,
+		relay_blocks,
+		seed,
+		network,
+	} = *config;
+	let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+	// When the collator learns about every relay chain block, the genesis being known from
+	// the start. Kept non-decreasing, a block can't be imported before its parent.
+	let mut known_at = vec![0u64];
+	for number in 1..=relay_blocks as u64 {
+		let seen = number * relay_block_time + network.relay_propagation.sample(&mut rng);
+		known_at.push(seen.max(*known_at.last().expect("genesis is pushed first; qed")));
+	}
+
+	let mut segment = VecDeque::<Candidate>::new();
+	let mut latencies = Vec::new();
+	let (mut built, mut dropped, mut skipped) = (0, 0, 0);
+	let mut next_slot = collation_offset;
+
+	for number in 1..=relay_blocks {
+		let now = number as u64 * relay_block_time;
+
+		// The collator builds until the relay chain block is authored.
+		while next_slot < now {
+			if segment.len() > params.max_candidate_depth as usize {
+				skipped += 1;
+			} else {
+				let known = known_at.partition_point(|seen| *seen <= next_slot) as u32 - 1;
+				let relay_parent =
+					segment.back().map_or(known, |parent| parent.relay_parent.max(known));
+				let backable_at = next_slot +
+					network.collation_transfer.sample(&mut rng) +
+					network.backing.sample(&mut rng);
+
+				segment.push_back(Candidate {
+					built_at: next_slot,
+					relay_parent,
+					backable_at,
+					available_at: None,
+				});
+				built += 1;
+			}
+			next_slot += para_block_time;
+		}
+
+		// Inclusion of the candidate pending availability.
+		if segment.front().and_then(|c| c.available_at).map_or(false, |at| at <= now) {
+			let candidate = segment.pop_front().expect("checked above; qed");
+			latencies.push(now - candidate.built_at);
+		}
+
+		// Candidates whose relay parent left the allowed ancestry are dropped, with their
+		// descendants built on top of them.
+		let parent = number - 1;
+		if let Some(expired) = segment.iter().position(|c| {
+			c.available_at.is_none() &&
+				parent.saturating_sub(c.relay_parent) > params.allowed_ancestry_len
+		}) {
+			dropped += (segment.len() - expired) as u32;
+			segment.truncate(expired);
+		}
+
+		// Backing of the next candidate if the core is free.
+		if let Some(candidate) = segment.front_mut() {
+			if candidate.available_at.is_none() &&
+				candidate.backable_at <= now &&
+				candidate.relay_parent <= parent
+			{
+				candidate.available_at = Some(now + network.availability.sample(&mut rng));
+			}
+		}
+	}
+
+	latencies.sort_unstable();
+	let percentile = |p: usize| {
+		latencies.get((latencies.len() * p / 100).min(latencies.len().saturating_sub(1)))
+	};
+	let included = latencies.len() as u32;
+
+	SimulationReport {
+		max_candidate_depth: params.max_candidate_depth,
+		allowed_ancestry_len: params.allowed_ancestry_len,
+		relay_blocks,
+		built,
+		included,
+		dropped,
+		skipped,
+		throughput: included as f64 / relay_blocks.max(1) as f64,
+		mean_latency: latencies.iter().sum::<u64>() as f64 / included.max(1) as f64,
+		p50_latency: percentile(50).copied().unwrap_or_default(),
+		p95_latency: percentile(95).copied().unwrap_or_default(),
+		max_latency: latencies.last().copied().unwrap_or_default(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn config(max_candidate_depth: u32, allowed_ancestry_len: u32) -> SimulationConfig {
+		SimulationConfig {
+			params: AsyncBackingParams { max_candidate_depth, allowed_ancestry_len },
+			relay_block_time: 6000,
+			para_block_time: 6000,
+			collation_offset: 1000,
+			relay_blocks: 100,
+			seed: 42,
+			network: NetworkConfig {
+				relay_propagation: LatencyDistribution::Fixed(200),
+				collation_transfer: LatencyDistribution::Fixed(300),
+				backing: LatencyDistribution::Fixed(1000),
+				availability: LatencyDistribution::Fixed(1000),
+			},
+		}
+	}
+
+	#[test]
+	fn synchronous_backing_includes_every_other_block() {
+		let report = simulate(&config(0, 0));
+
+		assert_eq!(report.dropped, 0);
+		assert!((report.throughput - 0.5).abs() < 0.05, "{report:?}");
+		assert_eq!(report.p50_latency, 11000);
+	}
+
+	#[test]
+	fn async_backing_includes_every_block() {
+		let report = simulate(&config(3, 2));
+
+		assert_eq!(report.dropped, 0);
+		assert!(report.throughput > 0.95, "{report:?}");
+		assert!(report.included > simulate(&config(0, 0)).included);
+		assert_eq!(report.p50_latency, 11000);
+	}
+
+	#[test]
+	fn slow_backing_requires_ancestry() {
+		let mut slow = config(3, 0);
+		slow.network.backing = LatencyDistribution::Fixed(7000);
+
+		let report = simulate(&slow);
+		assert_eq!(report.included, 0);
+		assert!(report.dropped > 0);
+
+		slow.params.allowed_ancestry_len = 2;
+		assert!(simulate(&slow).throughput > 0.9);
+	}
+
+	#[test]
+	fn same_seed_gives_same_report() {
+		let mut random = config(3, 2);
+		random.network.backing = LatencyDistribution::Exponential { mean: 2000.0 };
+
+		assert_eq!(simulate(&random), simulate(&random));
+	}
+}
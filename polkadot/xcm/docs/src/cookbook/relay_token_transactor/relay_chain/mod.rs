@@ -92,6 +92,7 @@ impl pallet_message_queue::Config for Runtime {
 	type QueuePausedQuery = ();
 	type WeightInfo = ();
 	type IdleMaxServiceWeight = MessageQueueServiceWeight;
+	type QueueServicePolicy = ();
 }
 
 construct_runtime! {
@@ -26,6 +26,7 @@ mod mock;
 mod tests;
 
 pub mod migration;
+mod multi_hop;
 
 extern crate alloc;
 
@@ -56,8 +57,9 @@ use sp_runtime::{
 };
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::{
-	ExecuteController, ExecuteControllerWeightInfo, InspectMessageQueues, QueryController,
-	QueryControllerWeightInfo, SendController, SendControllerWeightInfo,
+	BatchFallback, ExecuteController, ExecuteControllerWeightInfo, InspectMessageQueues,
+	QueryController, QueryControllerWeightInfo, SendController, SendControllerWeightInfo,
+	TransactBatch,
 };
 use xcm_executor::{
 	traits::{
@@ -70,8 +72,11 @@ use xcm_executor::{
 };
 use xcm_runtime_apis::{
 	authorized_aliases::{Error as AuthorizedAliasersApiError, OriginAliaser},
-	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
-	fees::Error as XcmPaymentApiError,
+	dry_run::{
+		CallDryRunEffects, CallMultiHopDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects,
+	},
+	fees::{Error as XcmPaymentApiError, HopFees, MultiHopFees, RemoteFeeEstimator},
+	trapped_assets::{Error as TrappedAssetsApiError, TrappedAssets as TrappedAssetsEntry},
 	trusted_query::Error as TrustedQueryApiError,
 };
 
@@ -580,6 +585,14 @@ pub mod pallet {
 		AliasAuthorizationRemoved { aliaser: Location, target: Location },
 		/// `target` removed all alias authorizations.
 		AliasesAuthorizationsRemoved { target: Location },
+		/// A batch of calls was sent to `destination` for dispatch, its dispatch result will be
+		/// the response of the query `query_id`.
+		TransactBatchSent {
+			origin: Location,
+			destination: Location,
+			query_id: QueryId,
+			message_id: XcmHash,
+		},
 	}
 
 	#[pallet::origin]
@@ -679,6 +692,15 @@ pub mod pallet {
 		/// The alias to remove authorization for was not found.
 		#[codec(index = 27)]
 		AliasNotFound,
+		/// There are no assets trapped for the origin.
+		#[codec(index = 28)]
+		NoTrappedAssets,
+		/// The trapped assets of another origin can only be claimed to that origin.
+		#[codec(index = 29)]
+		BeneficiaryNotAllowed,
+		/// Too many calls have been attempted for a batch.
+		#[codec(index = 30)]
+		TooManyCalls,
 	}
 
 	impl<T: Config> From<SendError> for Error<T> {
@@ -761,6 +783,23 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// The assets trapped per origin, for the existing [`AssetTraps`].
+	///
+	/// Keys are the origin and the hash of the assets, both in the latest version, so the same
+	/// assets trapped under different XCM versions share an entry. Their [`AssetTraps`] entries
+	/// are looked up for every supported version. Only the assets trapped since this map was
+	/// introduced are listed.
+	#[pallet::storage]
+	pub(super) type TrappedAssets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VersionedLocation,
+		Identity,
+		H256,
+		VersionedAssets,
+		OptionQuery,
+	>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -1451,6 +1490,76 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Claims the assets trapped with `origin_location` as origin, without having to know
+		/// them.
+		///
+		/// Up to `MAX_TRAPS_PER_CLAIM` asset traps are claimed, call again to claim more.
+		///
+		/// - `origin`: Anyone can call this extrinsic. Unless the origin is `origin_location`, the
+		///   assets can only be claimed to `origin_location` itself.
+		/// - `origin_location`: The origin of the failed XCM executions that trapped the assets.
+		/// - `beneficiary`: The location/account where the claimed assets will be deposited.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::claim_assets().saturating_mul(MAX_TRAPS_PER_CLAIM as u64))]
+		pub fn claim_trapped_assets(
+			origin: OriginFor<T>,
+			origin_location: Box<VersionedLocation>,
+			beneficiary: Box<VersionedLocation>,
+		) -> DispatchResultWithPostInfo {
+			let caller = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			let origin_location: Location =
+				(*origin_location).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: Location =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			tracing::debug!(target: "xcm::pallet_xcm::claim_trapped_assets", ?caller, ?origin_location, ?beneficiary);
+			ensure!(
+				caller == origin_location || beneficiary == origin_location,
+				Error::<T>::BeneficiaryNotAllowed
+			);
+
+			let mut claims = Vec::new();
+			let mut number_of_assets = 0u32;
+			let versioned_origin = VersionedLocation::from(origin_location.clone());
+			'traps: for versioned_assets in
+				TrappedAssets::<T>::iter_prefix_values(&versioned_origin)
+			{
+				let Ok(assets) = Assets::try_from(versioned_assets.clone()) else { continue };
+				for (_, version, count) in Self::asset_traps_of(&origin_location, &versioned_assets)
+				{
+					let ticket: Location = GeneralIndex(version as u128).into();
+					for _ in 0..count {
+						if claims.len() == MAX_TRAPS_PER_CLAIM {
+							break 'traps
+						}
+						number_of_assets.saturating_accrue(assets.len() as u32);
+						claims.push(ClaimAsset { assets: assets.clone(), ticket: ticket.clone() });
+					}
+				}
+			}
+			ensure!(!claims.is_empty(), Error::<T>::NoTrappedAssets);
+
+			let claimed = claims.len() as u64;
+			let mut message = Xcm(claims);
+			message
+				.0
+				.push(DepositAsset { assets: AllCounted(number_of_assets).into(), beneficiary });
+			let weight =
+				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			let mut hash = message.using_encoded(sp_io::hashing::blake2_256);
+			let outcome = T::XcmExecutor::prepare_and_execute(
+				origin_location,
+				message,
+				&mut hash,
+				weight,
+				weight,
+			);
+			outcome.ensure_complete().map_err(|error| {
+				tracing::error!(target: "xcm::pallet_xcm::claim_trapped_assets", ?error, "XCM execution failed with error");
+				Error::<T>::LocalExecutionIncomplete
+			})?;
+			Ok(Some(T::WeightInfo::claim_assets().saturating_mul(claimed)).into())
+		}
+
 		/// Transfer assets from the local chain to the destination chain using explicit transfer
 		/// types for assets and fees.
 		///
@@ -1698,12 +1807,101 @@ pub mod pallet {
 				Err(Error::<T>::AliasNotFound.into())
 			}
 		}
+
+		/// Dispatch several calls on the `dest` chain with a single `Transact`, as a batch of its
+		/// `pallet-utility`, and get the dispatch result back.
+		///
+		/// A query is registered for the dispatch result of the batch, `ReportTransactStatus` is
+		/// used to report it back even if the batch fails. The `query_id` is in the
+		/// `TransactBatchSent` event, the result can be read with the `take_response` of the
+		/// query handler once the response arrived.
+		///
+		/// - `origin`: Must be capable of sending XCM, the calls are dispatched with its origin on
+		///   `dest` according to `origin_kind`.
+		/// - `dest`: The chain dispatching the calls.
+		/// - `utility_pallet_index`: The index of `pallet-utility` in the runtime of `dest`.
+		/// - `fallback`: What happens to the other calls when one of them fails. Only with
+		///   `BatchFallback::RevertAll` is a failing call reported as a failure of the batch.
+		/// - `calls`: The calls to dispatch, encoded for the runtime of `dest`.
+		/// - `origin_kind`: The kind of origin the calls are dispatched with on `dest`.
+		/// - `fallback_max_weight`: The weight of the batch, for the chains still on XCM v4.
+		/// - `remote_fees`: The asset withdrawn on `dest` to pay for the execution, if it isn't
+		///   free. What is left of it is deposited back to the origin.
+		/// - `timeout`: The block number after which the response is not expected anymore.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::send().saturating_add(T::WeightInfo::new_query()))]
+		pub fn send_transact_batch(
+			origin: OriginFor<T>,
+			dest: Box<VersionedLocation>,
+			utility_pallet_index: u8,
+			fallback: BatchFallback,
+			calls: Vec<Vec<u8>>,
+			origin_kind: OriginKind,
+			fallback_max_weight: Option<Weight>,
+			remote_fees: Option<Box<VersionedAsset>>,
+			timeout: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let origin_location = T::SendXcmOrigin::ensure_origin(origin)?;
+			let interior: Junctions =
+				origin_location.clone().try_into().map_err(|_| Error::<T>::InvalidOrigin)?;
+			let dest = Location::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+			let remote_fees = remote_fees
+				.map(|fees| Asset::try_from(*fees))
+				.transpose()
+				.map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(!calls.is_empty(), Error::<T>::Empty);
+			ensure!(calls.len() <= MAX_CALLS_PER_TRANSACT_BATCH, Error::<T>::TooManyCalls);
+
+			let context = T::UniversalLocation::get();
+			let response_destination = context
+				.invert_target(&dest)
+				.map_err(|()| Error::<T>::DestinationNotInvertible)?;
+			let query_id = Self::do_new_query(dest.clone(), None, timeout, origin_location.clone());
+			let mut appendix = vec![ReportTransactStatus(QueryResponseInfo {
+				destination: response_destination,
+				query_id,
+				max_weight: Weight::zero(),
+			})];
+
+			let mut message = Xcm(Vec::new());
+			if let Some(fees) = remote_fees {
+				let beneficiary = origin_location
+					.clone()
+					.reanchored(&dest, &context)
+					.map_err(|_| Error::<T>::CannotReanchor)?;
+				message.0.push(WithdrawAsset(fees.clone().into()));
+				message.0.push(BuyExecution { fees, weight_limit: Unlimited });
+				appendix.push(RefundSurplus);
+				appendix.push(DepositAsset { assets: AllCounted(1).into(), beneficiary });
+			}
+			message.0.push(SetAppendix(Xcm(appendix)));
+			let batch = TransactBatch { utility_pallet_index, fallback, calls };
+			message.0.push(batch.into_transact(origin_kind, fallback_max_weight));
+
+			let message_id = Self::send_xcm(interior, dest.clone(), message.clone()).map_err(|error| {
+				tracing::error!(target: "xcm::pallet_xcm::send_transact_batch", ?error, ?dest, ?message, "XCM send failed with error");
+				Error::<T>::from(error)
+			})?;
+			Self::deposit_event(Event::TransactBatchSent {
+				origin: origin_location,
+				destination: dest,
+				query_id,
+				message_id,
+			});
+			Ok(())
+		}
 	}
 }
 
 /// The maximum number of distinct assets allowed to be transferred in a single helper extrinsic.
 const MAX_ASSETS_FOR_TRANSFER: usize = 2;
 
+/// The maximum number of asset traps claimed by a single `claim_trapped_assets` call.
+const MAX_TRAPS_PER_CLAIM: usize = 16;
+
+/// The maximum number of calls dispatched by a single `send_transact_batch` call.
+const MAX_CALLS_PER_TRANSACT_BATCH: usize = 16;
+
 /// Specify how assets used for fees are handled during asset transfers.
 #[derive(Clone, PartialEq)]
 enum FeesHandling<T: Config> {
@@ -1800,6 +1998,53 @@ impl<T: Config> Pallet<T> {
 		AssetTraps::<T>::get(trap_id)
 	}
 
+	/// Returns the assets trapped with `origin` as origin, for the
+	/// `xcm_runtime_apis::trapped_assets::TrappedAssetsApi` runtime API.
+	pub fn trapped_assets(
+		origin: VersionedLocation,
+	) -> Result<Vec<TrappedAssetsEntry>, TrappedAssetsApiError> {
+		// storage entries are always latest version
+		let origin = origin.into_version(XCM_VERSION).map_err(|e| {
+			tracing::debug!(
+				target: "xcm::pallet_xcm::trapped_assets",
+				?e, "Failed to convert versioned location",
+			);
+			TrappedAssetsApiError::LocationVersionConversionFailed
+		})?;
+		let location = Location::try_from(origin.clone())
+			.map_err(|()| TrappedAssetsApiError::LocationVersionConversionFailed)?;
+		Ok(TrappedAssets::<T>::iter_prefix_values(&origin)
+			.map(|assets| {
+				let count = Self::asset_traps_of(&location, &assets)
+					.into_iter()
+					.fold(0u32, |total, (_, _, count)| total.saturating_add(count));
+				TrappedAssetsEntry { assets, count }
+			})
+			.collect())
+	}
+
+	/// The key of `assets` in [`TrappedAssets`], the hash of the assets in the latest version.
+	fn trapped_assets_key(assets: &VersionedAssets) -> Option<H256> {
+		let latest = assets.clone().into_version(XCM_VERSION).ok()?;
+		Some(BlakeTwo256::hash_of(&latest))
+	}
+
+	/// The non-empty [`AssetTraps`] entries of `assets` trapped with `origin`, as their hash, the
+	/// XCM version the assets were trapped in and the number of traps.
+	///
+	/// The hash of an asset trap depends on the version of the assets, so there is one candidate
+	/// entry per supported version.
+	fn asset_traps_of(origin: &Location, assets: &VersionedAssets) -> Vec<(H256, XcmVersion, u32)> {
+		(MIN_XCM_VERSION..=XCM_VERSION)
+			.filter_map(|version| {
+				let versioned = assets.clone().into_version(version).ok()?;
+				let hash = BlakeTwo256::hash_of(&(origin, &versioned));
+				let count = AssetTraps::<T>::get(hash);
+				(count > 0).then_some((hash, version, count))
+			})
+			.collect()
+	}
+
 	/// Find `TransferType`s for `assets` and fee identified through `fee_asset_item`, when
 	/// transferring to `dest`.
 	///
@@ -2846,6 +3091,131 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Dry-runs `call` with the given `origin`, then simulates the forwarded XCMs on every hop
+	/// up to their final destination.
+	///
+	/// The remote hops are simulated with the fees estimated by `Estimator`, see
+	/// the `multi_hop` module for the instructions taken into account.
+	/// Meant to be used in the `xcm_runtime_apis::dry_run::DryRunApi` runtime API.
+	pub fn dry_run_call_multi_hop<Runtime, Router, OriginCaller, RuntimeCall, Estimator>(
+		origin: OriginCaller,
+		call: RuntimeCall,
+		result_xcms_version: XcmVersion,
+	) -> Result<
+		CallMultiHopDryRunEffects<<Runtime as frame_system::Config>::RuntimeEvent>,
+		XcmDryRunApiError,
+	>
+	where
+		Runtime: crate::Config,
+		Router: InspectMessageQueues,
+		RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+		<RuntimeCall as Dispatchable>::RuntimeOrigin: From<OriginCaller>,
+		Estimator: RemoteFeeEstimator,
+	{
+		let info = call.get_dispatch_info();
+		let effects = Self::dry_run_call::<Runtime, Router, OriginCaller, RuntimeCall>(
+			origin,
+			call,
+			XCM_VERSION,
+		)?;
+		let execution_weight = match &effects.execution_result {
+			Ok(post_info) => post_info.calc_actual_weight(&info),
+			Err(error) => error.post_info.calc_actual_weight(&info),
+		};
+
+		let versioned_conversion_failed = |error| {
+			tracing::error!(
+				target: "xcm::DryRunApi::dry_run_call_multi_hop",
+				?error, "Forwarded xcms version conversion failed"
+			);
+			XcmDryRunApiError::VersionedConversionFailed
+		};
+		let mut pending = Vec::new();
+		for (destination, messages) in &effects.forwarded_xcms {
+			let destination = Location::try_from(destination.clone())
+				.map_err(|()| versioned_conversion_failed("destination"))?;
+			for message in messages {
+				let message = Xcm::<()>::try_from(message.clone())
+					.map_err(|()| versioned_conversion_failed("message"))?;
+				pending.push((destination.clone(), message));
+			}
+		}
+
+		let mut delivery_fees = AssetsInHolding::new();
+		for (destination, message) in &pending {
+			let (_, fees) =
+				validate_send::<Runtime::XcmRouter>(destination.clone(), message.clone()).map_err(
+					|error| {
+						tracing::error!(
+							target: "xcm::DryRunApi::dry_run_call_multi_hop",
+							?error, ?destination, "Failed to validate send to destination"
+						);
+						XcmDryRunApiError::FeesNotEstimable
+					},
+				)?;
+			delivery_fees.subsume_assets(fees.into());
+		}
+		let mut hops = vec![(
+			Location::here(),
+			execution_weight,
+			Assets::new(),
+			delivery_fees.into_assets_iter().collect::<Vec<_>>().into(),
+		)];
+
+		let universal_location = Runtime::UniversalLocation::get();
+		let mut delivered_assets = Assets::new();
+		let mut pending = pending.into_iter().collect::<alloc::collections::VecDeque<_>>();
+		while let Some((location, message)) = pending.pop_front() {
+			if hops.len() > multi_hop::MAX_REMOTE_HOPS {
+				tracing::debug!(
+					target: "xcm::DryRunApi::dry_run_call_multi_hop",
+					?location, "Too many hops to simulate"
+				);
+				return Err(XcmDryRunApiError::FeesNotEstimable)
+			}
+			let hop = multi_hop::simulate_remote_hop::<Estimator>(
+				&universal_location,
+				&location,
+				message,
+			)?;
+			hops.push((location, hop.execution_weight, hop.execution_fees, hop.delivery_fees));
+			delivered_assets = hop.deposited;
+			pending.extend(hop.forwarded);
+		}
+
+		let hops = hops
+			.into_iter()
+			.map(|(location, execution_weight, execution_fees, delivery_fees)| {
+				Ok(HopFees {
+					location: VersionedLocation::from(location)
+						.into_version(result_xcms_version)?,
+					execution_weight,
+					execution_fees: VersionedAssets::from(execution_fees)
+						.into_version(result_xcms_version)?,
+					delivery_fees: VersionedAssets::from(delivery_fees)
+						.into_version(result_xcms_version)?,
+				})
+			})
+			.collect::<Result<Vec<_>, ()>>()
+			.map_err(|()| versioned_conversion_failed("fees"))?;
+		let delivered_assets = VersionedAssets::from(delivered_assets)
+			.into_version(result_xcms_version)
+			.map_err(|()| versioned_conversion_failed("delivered assets"))?;
+
+		let local_xcm = effects
+			.local_xcm
+			.map(|xcm| xcm.into_version(result_xcms_version))
+			.transpose()
+			.map_err(|()| versioned_conversion_failed("local xcm"))?;
+		let forwarded_xcms =
+			Self::convert_forwarded_xcms(result_xcms_version, effects.forwarded_xcms)?;
+
+		Ok(CallMultiHopDryRunEffects {
+			effects: CallDryRunEffects { local_xcm, forwarded_xcms, ..effects },
+			fees: MultiHopFees { hops, delivered_assets },
+		})
+	}
+
 	/// Dry-runs `xcm` with the given `origin_location`.
 	///
 	/// Returns execution result, events, and any forwarded XCMs to other locations.
@@ -3252,6 +3622,17 @@ impl<T: Config> Pallet<T> {
 			)
 		);
 
+		// check `TrappedAssets`
+		ensure!(
+			TrappedAssets::<T>::iter().all(|(origin, key, assets)| {
+				Self::trapped_assets_key(&assets) == Some(key) &&
+					Location::try_from(origin).map_or(false, |origin| {
+						!Self::asset_traps_of(&origin, &assets).is_empty()
+					})
+			}),
+			TryRuntimeError::Other("`TrappedAssets` should only list existing `AssetTraps`!")
+		);
+
 		// if migration has been already scheduled, everything is ok and data will be eventually
 		// migrated
 		if CurrentMigration::<T>::exists() {
@@ -3556,6 +3937,9 @@ impl<T: Config> DropAssets for Pallet<T> {
 		let versioned = VersionedAssets::from(Assets::from(assets));
 		let hash = BlakeTwo256::hash_of(&(&origin, &versioned));
 		AssetTraps::<T>::mutate(hash, |n| *n += 1);
+		if let Some(key) = Self::trapped_assets_key(&versioned) {
+			TrappedAssets::<T>::insert(VersionedLocation::from(origin.clone()), key, &versioned);
+		}
 		Self::deposit_event(Event::AssetsTrapped {
 			hash,
 			origin: origin.clone(),
@@ -3586,7 +3970,16 @@ impl<T: Config> ClaimAssets for Pallet<T> {
 		let hash = BlakeTwo256::hash_of(&(origin.clone(), versioned.clone()));
 		match AssetTraps::<T>::get(hash) {
 			0 => return false,
-			1 => AssetTraps::<T>::remove(hash),
+			1 => {
+				AssetTraps::<T>::remove(hash);
+				// The same assets may still be trapped in another version.
+				let latest = VersionedAssets::from(assets.clone());
+				if Self::asset_traps_of(origin, &latest).is_empty() {
+					if let Some(key) = Self::trapped_assets_key(&latest) {
+						TrappedAssets::<T>::remove(VersionedLocation::from(origin.clone()), key);
+					}
+				}
+			},
 			n => AssetTraps::<T>::insert(hash, n - 1),
 		}
 		Self::deposit_event(Event::AssetsClaimed {
@@ -0,0 +1,224 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Simulation of the XCMs forwarded to remote chains, to estimate their fees on every hop.
+//!
+//! The remote chains can't be executed locally, so their execution is approximated: the holding
+//! register is tracked through the asset instructions, the execution fees estimated by the
+//! [`RemoteFeeEstimator`] are charged on the first `BuyExecution` or `PayFees`, and the delivery
+//! fees are deducted from the assets transferred further. Other instructions are ignored.
+
+use alloc::{vec, vec::Vec};
+use xcm::latest::{prelude::*, AssetTransferFilter};
+use xcm_executor::AssetsInHolding;
+use xcm_runtime_apis::{dry_run::Error as XcmDryRunApiError, fees::RemoteFeeEstimator};
+
+const LOG_TARGET: &str = "xcm::pallet_xcm::multi_hop";
+
+/// Maximum number of remote hops simulated, in case messages bounce between chains.
+pub(crate) const MAX_REMOTE_HOPS: usize = 8;
+
+/// The simulated execution of an XCM on a remote chain.
+#[derive(Debug, Default)]
+pub(crate) struct RemoteHop {
+	pub execution_weight: Weight,
+	pub execution_fees: Assets,
+	pub delivery_fees: Assets,
+	/// The assets deposited on the remote chain.
+	pub deposited: Assets,
+	/// The XCMs sent to the next hops, with destinations relative to the local chain.
+	pub forwarded: Vec<(Location, Xcm<()>)>,
+}
+
+/// The instruction introducing the transferred assets on the next hop, see
+/// [`xcm_executor::traits::TransferType`].
+type Preamble = fn(Assets) -> Instruction<()>;
+
+fn preamble_of(filter: &AssetTransferFilter) -> Preamble {
+	match filter {
+		AssetTransferFilter::Teleport(_) => ReceiveTeleportedAsset,
+		AssetTransferFilter::ReserveDeposit(_) => ReserveAssetDeposited,
+		AssetTransferFilter::ReserveWithdraw(_) => WithdrawAsset,
+	}
+}
+
+/// An XCM sent to the next hop by one of the transfer instructions.
+struct Transfer {
+	/// The assets paying for the execution on the next hop, with `InitiateTransfer`.
+	fees: Option<(Preamble, AssetsInHolding)>,
+	assets: Vec<(Preamble, AssetsInHolding)>,
+	/// Whether the origin is cleared, or `UnpaidExecution` is requested, before `xcm`.
+	clear_origin: bool,
+	unpaid: bool,
+	xcm: Xcm<()>,
+}
+
+impl Transfer {
+	/// A transfer by one of the instructions prior to `InitiateTransfer`.
+	fn legacy(preamble: Preamble, assets: AssetsInHolding, xcm: Xcm<()>) -> Self {
+		Self {
+			fees: None,
+			assets: vec![(preamble, assets)],
+			clear_origin: true,
+			unpaid: false,
+			xcm,
+		}
+	}
+
+	/// The message received by `destination`, from the chain at `context`.
+	fn message(&self, destination: &Location, context: &InteriorLocation) -> Xcm<()> {
+		let reanchored = |assets: &AssetsInHolding| -> Assets {
+			let mut assets = assets.clone();
+			assets.reanchor(destination, context, None);
+			assets.into_assets_iter().collect::<Vec<_>>().into()
+		};
+
+		let mut message = vec![];
+		if let Some((preamble, fees)) = &self.fees {
+			let fees = reanchored(fees);
+			if let Some(asset) = fees.get(0).cloned() {
+				message.push(preamble(fees));
+				message.push(PayFees { asset });
+			}
+		}
+		for (preamble, assets) in &self.assets {
+			let assets = reanchored(assets);
+			if !assets.is_none() {
+				message.push(preamble(assets));
+			}
+		}
+		if self.clear_origin {
+			message.push(ClearOrigin);
+		}
+		if self.unpaid {
+			message.push(UnpaidExecution { weight_limit: Unlimited, check_origin: None });
+		}
+		message.extend(self.xcm.0.iter().cloned());
+		Xcm(message)
+	}
+
+	/// Deducts `fee` from the transferred assets, starting with the ones paying for the fees.
+	fn deduct(&mut self, fee: &Asset) {
+		let Fungible(mut remaining) = fee.fun else { return };
+		for (_, assets) in self.fees.iter_mut().chain(self.assets.iter_mut()) {
+			if remaining == 0 {
+				break
+			}
+			let taken = assets.saturating_take(Asset::from((fee.id.clone(), remaining)).into());
+			remaining -= taken.fungible.get(&fee.id).copied().unwrap_or_default();
+		}
+	}
+}
+
+/// Simulates the execution of `message` on `location`, relative to the local chain at
+/// `universal_location`.
+pub(crate) fn simulate_remote_hop<Estimator: RemoteFeeEstimator>(
+	universal_location: &InteriorLocation,
+	location: &Location,
+	message: Xcm<()>,
+) -> Result<RemoteHop, XcmDryRunApiError> {
+	let not_estimable = || {
+		tracing::debug!(target: LOG_TARGET, ?location, "Fees of the remote hop are not estimable");
+		XcmDryRunApiError::FeesNotEstimable
+	};
+	let context = Location::from(universal_location.clone())
+		.appended_with(location.clone())
+		.map_err(|_| not_estimable())?
+		.interior;
+
+	let mut hop = RemoteHop {
+		execution_weight: Estimator::weigh(location, &message).ok_or_else(not_estimable)?,
+		..Default::default()
+	};
+	let mut holding = AssetsInHolding::new();
+	let mut fees_paid = false;
+
+	for instruction in message.0 {
+		let (destination, mut transfer) = match instruction {
+			WithdrawAsset(assets) |
+			ReserveAssetDeposited(assets) |
+			ReceiveTeleportedAsset(assets) => {
+				holding.subsume_assets(assets.into());
+				continue
+			},
+			BuyExecution { fees, .. } | PayFees { asset: fees } if !fees_paid => {
+				fees_paid = true;
+				let amount = Estimator::weight_to_fee(location, hop.execution_weight, &fees.id)
+					.ok_or_else(not_estimable)?;
+				let fee: Asset = (fees.id, amount).into();
+				let _ = holding.saturating_take(fee.clone().into());
+				hop.execution_fees.push(fee);
+				continue
+			},
+			DepositAsset { assets, .. } => {
+				holding
+					.saturating_take(assets)
+					.into_assets_iter()
+					.for_each(|asset| hop.deposited.push(asset));
+				continue
+			},
+			DepositReserveAsset { assets, dest, xcm } => (
+				dest,
+				Transfer::legacy(ReserveAssetDeposited, holding.saturating_take(assets), xcm),
+			),
+			InitiateReserveWithdraw { assets, reserve, xcm } =>
+				(reserve, Transfer::legacy(WithdrawAsset, holding.saturating_take(assets), xcm)),
+			InitiateTeleport { assets, dest, xcm } => (
+				dest,
+				Transfer::legacy(ReceiveTeleportedAsset, holding.saturating_take(assets), xcm),
+			),
+			InitiateTransfer { destination, remote_fees, preserve_origin, assets, remote_xcm } => {
+				let fees = remote_fees.map(|filter| {
+					(preamble_of(&filter), holding.saturating_take(filter.inner().clone()))
+				});
+				let assets = assets
+					.into_iter()
+					.map(|filter| {
+						(preamble_of(&filter), holding.saturating_take(filter.inner().clone()))
+					})
+					.collect();
+				let unpaid = fees.is_none();
+				let transfer = Transfer {
+					fees,
+					assets,
+					clear_origin: !preserve_origin,
+					unpaid,
+					xcm: remote_xcm,
+				};
+				(destination, transfer)
+			},
+			_ => continue,
+		};
+
+		let delivery_fees = Estimator::delivery_fees(
+			location,
+			&destination,
+			&transfer.message(&destination, &context),
+		)
+		.ok_or_else(not_estimable)?;
+		for fee in delivery_fees.into_inner() {
+			transfer.deduct(&fee);
+			hop.delivery_fees.push(fee);
+		}
+
+		let message = transfer.message(&destination, &context);
+		let destination =
+			location.clone().appended_with(destination).map_err(|_| not_estimable())?;
+		hop.forwarded.push((destination, message));
+	}
+
+	Ok(hop)
+}
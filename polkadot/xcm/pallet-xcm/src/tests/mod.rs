@@ -40,7 +40,7 @@ use sp_runtime::{
 	SaturatedConversion, TokenError,
 };
 use xcm::{latest::QueryResponseInfo, prelude::*};
-use xcm_builder::AllowKnownQueryResponses;
+use xcm_builder::{AllowKnownQueryResponses, BatchFallback};
 use xcm_executor::{
 	traits::{Properties, QueryHandler, QueryResponseStatus, ShouldExecute},
 	XcmExecutor,
@@ -337,6 +337,80 @@ fn send_works() {
 	});
 }
 
+/// Test that `send_transact_batch` sends a batch of calls and registers a query for its
+/// dispatch result.
+#[test]
+fn send_transact_batch_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let sender: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest = RelayLocation::get();
+		let calls = vec![vec![0u8, 1], vec![2u8, 3]];
+		let batch = |calls| {
+			XcmPallet::send_transact_batch(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(dest.clone().into()),
+				40,
+				BatchFallback::RevertAll,
+				calls,
+				OriginKind::SovereignAccount,
+				None,
+				Some(Box::new((Here, SEND_AMOUNT).into())),
+				100,
+			)
+		};
+		assert_noop!(batch(vec![]), Error::<Test>::Empty);
+		assert_noop!(batch(vec![vec![0u8]; 17]), Error::<Test>::TooManyCalls);
+		assert_ok!(batch(calls.clone()));
+
+		let query_id = 0;
+		let message = Xcm(vec![
+			DescendOrigin(sender.clone().try_into().unwrap()),
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			BuyExecution { fees: (Here, SEND_AMOUNT).into(), weight_limit: Unlimited },
+			SetAppendix(Xcm(vec![
+				ReportTransactStatus(QueryResponseInfo {
+					destination: UniversalLocation::get().invert_target(&dest).unwrap(),
+					query_id,
+					max_weight: Weight::zero(),
+				}),
+				RefundSurplus,
+				DepositAsset {
+					assets: AllCounted(1).into(),
+					beneficiary: sender
+						.clone()
+						.reanchored(&dest, &UniversalLocation::get())
+						.unwrap(),
+				},
+			])),
+			Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				fallback_max_weight: None,
+				call: vec![40u8, 2, 8, 0, 1, 2, 3].into(),
+			},
+		]);
+		assert_eq!(sent_xcm(), vec![(dest.clone(), message.clone())]);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::TransactBatchSent {
+				origin: sender.clone(),
+				destination: dest.clone(),
+				query_id,
+				message_id: fake_message_hash(&message),
+			})
+		);
+		assert_eq!(
+			Queries::<Test>::get(query_id),
+			Some(QueryStatus::Pending {
+				responder: dest.into(),
+				maybe_match_querier: Some(sender.into()),
+				maybe_notify: None,
+				timeout: 100,
+			})
+		);
+	});
+}
+
 /// Test that sending an `XCM` message fails when the `XcmRouter` blocks the
 /// matching message format
 ///
@@ -713,6 +787,110 @@ fn claim_assets_works() {
 	});
 }
 
+// Like `claim_assets_works` but listing and claiming the trapped assets without knowing them.
+#[test]
+fn claim_trapped_assets_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let trapping_program =
+			Xcm::<RuntimeCall>::builder_unsafe().withdraw_asset((Here, SEND_AMOUNT)).build();
+		for _ in 0..2 {
+			assert_ok!(XcmPallet::execute(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(VersionedXcm::from(trapping_program.clone())),
+				BaseXcmWeight::get() * 2,
+			));
+		}
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - 2 * SEND_AMOUNT);
+
+		let alice = VersionedLocation::from(Location::from(AccountId32 {
+			network: None,
+			id: ALICE.clone().into(),
+		}));
+		let bob = VersionedLocation::from(Location::from(AccountId32 {
+			network: None,
+			id: BOB.clone().into(),
+		}));
+		assert_eq!(
+			XcmPallet::trapped_assets(alice.clone()),
+			Ok(vec![xcm_runtime_apis::trapped_assets::TrappedAssets {
+				assets: VersionedAssets::from(Assets::from((Here, SEND_AMOUNT))),
+				count: 2,
+			}])
+		);
+		assert_eq!(XcmPallet::trapped_assets(bob.clone()), Ok(vec![]));
+
+		// Anyone can claim them, but only to their origin.
+		assert_noop!(
+			XcmPallet::claim_trapped_assets(
+				RuntimeOrigin::signed(BOB),
+				Box::new(alice.clone()),
+				Box::new(bob.clone()),
+			),
+			Error::<Test>::BeneficiaryNotAllowed
+		);
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::signed(BOB),
+			Box::new(alice.clone()),
+			Box::new(alice.clone()),
+		));
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE);
+		assert_eq!(AssetTraps::<Test>::iter().collect::<Vec<_>>(), vec![]);
+		assert_eq!(XcmPallet::trapped_assets(alice.clone()), Ok(vec![]));
+
+		assert_noop!(
+			XcmPallet::claim_trapped_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(alice),
+				Box::new(bob),
+			),
+			Error::<Test>::NoTrappedAssets
+		);
+	});
+}
+
+// Assets trapped under an older XCM version are listed and claimed along with the same assets
+// trapped under the latest one.
+#[test]
+fn claim_trapped_assets_across_versions_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let trapping_program =
+			Xcm::<RuntimeCall>::builder_unsafe().withdraw_asset((Here, SEND_AMOUNT)).build();
+		for _ in 0..2 {
+			assert_ok!(XcmPallet::execute(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(VersionedXcm::from(trapping_program.clone())),
+				BaseXcmWeight::get() * 2,
+			));
+		}
+
+		// Move one of the traps to the hash of the assets in the previous version, as if it was
+		// trapped before the runtime upgraded to the latest XCM version.
+		let source: Location = AccountId32 { network: None, id: ALICE.clone().into() }.into();
+		let latest = VersionedAssets::from(Assets::from((Here, SEND_AMOUNT)));
+		let previous = latest.clone().into_version(XCM_VERSION - 1).unwrap();
+		AssetTraps::<Test>::insert(BlakeTwo256::hash_of(&(source.clone(), latest.clone())), 1);
+		AssetTraps::<Test>::insert(BlakeTwo256::hash_of(&(source.clone(), previous)), 1);
+
+		let alice = VersionedLocation::from(source);
+		assert_eq!(
+			XcmPallet::trapped_assets(alice.clone()),
+			Ok(vec![xcm_runtime_apis::trapped_assets::TrappedAssets { assets: latest, count: 2 }])
+		);
+
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(alice.clone()),
+			Box::new(alice.clone()),
+		));
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert_eq!(AssetTraps::<Test>::iter().collect::<Vec<_>>(), vec![]);
+		assert_eq!(XcmPallet::trapped_assets(alice), Ok(vec![]));
+	});
+}
+
 /// Test failure to complete execution reverts intermediate side-effects.
 ///
 /// XCM program will withdraw and deposit some assets, then fail execution of a further withdraw.
@@ -131,6 +131,9 @@ pub use routing::{
 	EnsureDecodableXcm, EnsureDelivery, InspectMessageQueues, WithTopicSource, WithUniqueTopic,
 };
 
+mod transact_batch;
+pub use transact_batch::{BatchFallback, TransactBatch};
+
 mod transactional;
 pub use transactional::FrameTransactionalProcessor;
 
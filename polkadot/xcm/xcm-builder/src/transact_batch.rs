@@ -0,0 +1,122 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `TransactBatch` for dispatching several calls on a remote chain with a single `Transact`.
+
+use alloc::{vec, vec::Vec};
+use codec::{Compact, Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use xcm::prelude::*;
+
+/// What happens to the calls of a [`TransactBatch`] when one of them fails.
+///
+/// Each variant is dispatched by one of the calls of `pallet-utility` on the remote chain. Only
+/// with [`BatchFallback::RevertAll`] does the failure of a call fail the `Transact`, and hence
+/// the reported dispatch result.
+#[derive(
+	Clone,
+	Copy,
+	Debug,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum BatchFallback {
+	/// All the calls are reverted, with `batch_all`.
+	RevertAll,
+	/// The calls before the failing one are kept and the following ones are not dispatched, with
+	/// `batch`.
+	StopAtFailure,
+	/// The failing calls are reverted and all the others are dispatched, with `force_batch`.
+	SkipFailed,
+}
+
+impl BatchFallback {
+	/// The index of the call dispatching the batch in `pallet-utility`.
+	fn utility_call_index(&self) -> u8 {
+		match self {
+			BatchFallback::StopAtFailure => 0,
+			BatchFallback::RevertAll => 2,
+			BatchFallback::SkipFailed => 4,
+		}
+	}
+}
+
+/// Calls to dispatch as a batch on a remote chain, by its `pallet-utility`.
+///
+/// The calls are encoded for the remote chain, which is why the index of its `pallet-utility` has
+/// to be provided.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactBatch {
+	/// The index of `pallet-utility` in the remote runtime.
+	pub utility_pallet_index: u8,
+	pub fallback: BatchFallback,
+	/// The encoded calls of the remote runtime.
+	pub calls: Vec<Vec<u8>>,
+}
+
+impl TransactBatch {
+	/// The encoded call of the remote `pallet-utility` dispatching the batch.
+	pub fn encoded_call(&self) -> Vec<u8> {
+		let mut call = vec![self.utility_pallet_index, self.fallback.utility_call_index()];
+		Compact(self.calls.len() as u32).encode_to(&mut call);
+		self.calls.iter().for_each(|encoded| call.extend_from_slice(encoded));
+		call
+	}
+
+	/// The `Transact` dispatching the batch with `origin_kind`.
+	///
+	/// Its dispatch result can be reported back with a `ReportTransactStatus` in the appendix of
+	/// the message, so that it is reported even if the batch fails.
+	pub fn into_transact<Call>(
+		self,
+		origin_kind: OriginKind,
+		fallback_max_weight: Option<Weight>,
+	) -> Instruction<Call> {
+		Transact { origin_kind, fallback_max_weight, call: self.encoded_call().into() }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encoded_call_is_a_utility_batch() {
+		let batch = TransactBatch {
+			utility_pallet_index: 40,
+			fallback: BatchFallback::RevertAll,
+			calls: vec![vec![0, 1], vec![2, 3, 4]],
+		};
+		assert_eq!(batch.encoded_call(), vec![40, 2, 8, 0, 1, 2, 3, 4]);
+
+		let skipping = TransactBatch { fallback: BatchFallback::SkipFailed, ..batch.clone() };
+		assert_eq!(skipping.encoded_call()[..2], [40, 4]);
+
+		assert_eq!(
+			batch.clone().into_transact::<()>(OriginKind::SovereignAccount, None),
+			Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				fallback_max_weight: None,
+				call: batch.encoded_call().into(),
+			}
+		);
+	}
+}
@@ -18,6 +18,7 @@
 //! This API can be used to simulate XCMs and, for example, find the fees
 //! that need to be paid.
 
+use crate::fees::MultiHopFees;
 use alloc::vec::Vec;
 use codec::{Decode, Encode};
 use frame_support::pallet_prelude::{DispatchResultWithPostInfo, TypeInfo};
@@ -47,6 +48,15 @@ pub struct XcmDryRunEffects<Event> {
 	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
 }
 
+/// Effects of dry-running an extrinsic, with the fees of the XCMs it sent on every hop.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct CallMultiHopDryRunEffects<Event> {
+	/// The effects of the extrinsic on the local chain.
+	pub effects: CallDryRunEffects<Event>,
+	/// The fees of the forwarded XCMs up to their final destination.
+	pub fees: MultiHopFees,
+}
+
 sp_api::decl_runtime_apis! {
 	/// API for dry-running extrinsics and XCM programs to get the programs that need to be passed to the fees API.
 	///
@@ -73,6 +83,15 @@ sp_api::decl_runtime_apis! {
 
 		/// Dry run XCM program
 		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<Call>) -> Result<XcmDryRunEffects<Event>, Error>;
+
+		/// Dry run call, then simulate the forwarded XCMs on all the hops up to their final
+		/// destination.
+		///
+		/// The remote hops are simulated with the weigher and prices configured by the runtime for
+		/// the remote chains, they are estimates. The execution fees of the local hop are the
+		/// transaction fees of the extrinsic and are not included.
+		#[api_version(3)]
+		fn dry_run_call_multi_hop(origin: OriginCaller, call: Call, result_xcms_version: XcmVersion) -> Result<CallMultiHopDryRunEffects<Event>, Error>;
 	}
 }
 
@@ -85,4 +104,8 @@ pub enum Error {
 	/// Converting a versioned data structure from one version to another failed.
 	#[codec(index = 1)]
 	VersionedConversionFailed,
+
+	/// The fees of a hop could not be estimated, e.g. the remote chain is unknown to the runtime.
+	#[codec(index = 2)]
+	FeesNotEstimable,
 }
@@ -20,7 +20,10 @@ use alloc::vec::Vec;
 use codec::{Decode, Encode};
 use frame_support::pallet_prelude::TypeInfo;
 use sp_weights::Weight;
-use xcm::{Version, VersionedAssetId, VersionedAssets, VersionedLocation, VersionedXcm};
+use xcm::{
+	latest::{AssetId, Assets, Location, Xcm},
+	Version, VersionedAssetId, VersionedAssets, VersionedLocation, VersionedXcm,
+};
 
 sp_api::decl_runtime_apis! {
 	/// A trait of XCM payment API.
@@ -69,6 +72,62 @@ sp_api::decl_runtime_apis! {
 	}
 }
 
+/// The fees of the execution of an XCM on one of the hops of its route.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct HopFees {
+	/// The location executing the XCM, relative to the local chain.
+	pub location: VersionedLocation,
+	/// The weight of the execution.
+	pub execution_weight: Weight,
+	/// The fees charged for the execution, relative to `location`.
+	pub execution_fees: VersionedAssets,
+	/// The fees charged for delivering the XCMs sent to the next hops, relative to `location`.
+	pub delivery_fees: VersionedAssets,
+}
+
+/// The fees of the XCMs forwarded over several hops, and what is left of the transferred assets.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct MultiHopFees {
+	/// The fees of every hop, starting with the local chain.
+	pub hops: Vec<HopFees>,
+	/// The assets deposited on the final destination, relative to it.
+	pub delivered_assets: VersionedAssets,
+}
+
+/// Estimates the fees charged by remote chains, to simulate the hops of the XCMs sent to them.
+///
+/// Locations are relative to the local chain, assets are relative to the remote chain. `None` is
+/// returned for the chains whose fees are not known.
+pub trait RemoteFeeEstimator {
+	/// The weight of executing `message` on `location`.
+	fn weigh(location: &Location, message: &Xcm<()>) -> Option<Weight>;
+
+	/// The fee charged by `location` for `weight`, in `asset`.
+	fn weight_to_fee(location: &Location, weight: Weight, asset: &AssetId) -> Option<u128>;
+
+	/// The fees charged by `location` for delivering `message` to `destination`, which is
+	/// relative to `location`.
+	fn delivery_fees(
+		location: &Location,
+		destination: &Location,
+		message: &Xcm<()>,
+	) -> Option<Assets>;
+}
+
+impl RemoteFeeEstimator for () {
+	fn weigh(_: &Location, _: &Xcm<()>) -> Option<Weight> {
+		None
+	}
+
+	fn weight_to_fee(_: &Location, _: Weight, _: &AssetId) -> Option<u128> {
+		None
+	}
+
+	fn delivery_fees(_: &Location, _: &Location, _: &Xcm<()>) -> Option<Assets> {
+		None
+	}
+}
+
 #[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
 pub enum Error {
 	/// An API part is unsupported.
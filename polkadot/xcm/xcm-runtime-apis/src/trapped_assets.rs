@@ -0,0 +1,49 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains runtime APIs for listing the assets trapped by failed XCM executions.
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::TypeInfo;
+use xcm::{VersionedAssets, VersionedLocation};
+
+/// Assets trapped for an origin. The same `assets` may have been trapped `count` times.
+///
+/// `assets` are in the XCM version that was the latest when they were trapped, which is the
+/// version to use when claiming them.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+pub struct TrappedAssets {
+	pub assets: VersionedAssets,
+	pub count: u32,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for listing the assets trapped by failed XCM executions.
+	pub trait TrappedAssetsApi {
+		/// Returns the assets trapped with `origin` as the origin of the XCM execution.
+		fn trapped_assets(origin: VersionedLocation) -> Result<Vec<TrappedAssets>, Error>;
+	}
+}
+
+/// `TrappedAssetsApi` Runtime APIs errors.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// Converting a location from one version to another failed.
+	#[codec(index = 0)]
+	LocationVersionConversionFailed,
+}
@@ -20,16 +20,17 @@ use frame_support::sp_runtime::testing::H256;
 use frame_system::RawOrigin;
 use sp_api::ProvideRuntimeApi;
 use xcm::prelude::*;
+use xcm_executor::traits::TransferType;
 use xcm_runtime_apis::{
 	dry_run::{CallDryRunEffects, DryRunApi},
-	fees::XcmPaymentApi,
+	fees::{HopFees, XcmPaymentApi},
 };
 
 mod mock;
 use mock::{
 	fake_message_hash, new_test_ext_with_balances, new_test_ext_with_balances_and_assets,
-	DeliveryFees, ExistentialDeposit, HereLocation, OriginCaller, RuntimeCall, RuntimeEvent,
-	TestClient,
+	DeliveryFees, ExistentialDeposit, HereLocation, OriginCaller, RemoteDeliveryFees, RuntimeCall,
+	RuntimeEvent, TestClient,
 };
 
 // Scenario: User `1` in the local chain (id 2000) wants to transfer assets to account `[0u8; 32]`
@@ -337,6 +338,79 @@ fn dry_run_before_api_v2_reserve_asset_transfer() {
 	}
 }
 
+// Scenario: User `1` in the local chain (id 2000) wants to transfer the relay token to account
+// `[0u8; 32]` on Parachain(2001), through "AssetHub" which is the reserve of the relay token.
+// The multi-hop dry run reports the fees of every hop and what is left on Parachain(2001).
+//
+//                 Reserve withdraw                 Reserve deposit
+// Parachain(2000) ----------------> Parachain(1000) ----------------> Parachain(2001)
+#[test]
+fn dry_run_multi_hop_reserve_asset_transfer() {
+	sp_tracing::init_for_tests();
+	let who = 1; // AccountId = u64.
+	let balances = vec![(who, DeliveryFees::get() + ExistentialDeposit::get())];
+	let assets = vec![(1, who, 100)]; // id, account_id, balance.
+	new_test_ext_with_balances_and_assets(balances, assets).execute_with(|| {
+		let xcm_on_asset_hub = Xcm::<()>::builder_unsafe()
+			.deposit_reserve_asset(
+				AllCounted(1),
+				(Parent, Parachain(2001)),
+				Xcm::builder_unsafe()
+					.buy_execution((Parent, 100u128), Unlimited)
+					.deposit_asset(AllCounted(1), [0u8; 32])
+					.build(),
+			)
+			.build();
+		let call = RuntimeCall::XcmPallet(pallet_xcm::Call::transfer_assets_using_type_and_then {
+			dest: Box::new(VersionedLocation::from((Parent, Parachain(1000)))),
+			assets: Box::new(VersionedAssets::from((Parent, 100u128))),
+			assets_transfer_type: Box::new(TransferType::DestinationReserve),
+			remote_fees_id: Box::new(VersionedAssetId::from(AssetId(Location::parent()))),
+			fees_transfer_type: Box::new(TransferType::DestinationReserve),
+			custom_xcm_on_dest: Box::new(VersionedXcm::from(xcm_on_asset_hub)),
+			weight_limit: Unlimited,
+		});
+		let origin = OriginCaller::system(RawOrigin::Signed(who));
+		let dry_run_effects = TestClient
+			.runtime_api()
+			.dry_run_call_multi_hop(H256::zero(), origin, call, XCM_VERSION)
+			.unwrap()
+			.unwrap();
+		assert!(dry_run_effects.effects.execution_result.is_ok());
+
+		let fees = dry_run_effects.fees;
+		assert_eq!(fees.hops.len(), 3);
+		assert_eq!(fees.hops[0].location, VersionedLocation::from(Location::here()));
+		assert_eq!(fees.hops[0].execution_fees, VersionedAssets::from(Assets::new()));
+		assert_eq!(fees.hops[0].delivery_fees, VersionedAssets::from((Here, DeliveryFees::get())));
+
+		// Both remote chains charge 40 for executing their 4 instructions.
+		let remote_weight = Weight::from_parts(400, 40);
+		assert_eq!(
+			fees.hops[1],
+			HopFees {
+				location: VersionedLocation::from((Parent, Parachain(1000))),
+				execution_weight: remote_weight,
+				execution_fees: VersionedAssets::from((Parent, 40u128)),
+				delivery_fees: VersionedAssets::from((Parent, RemoteDeliveryFees::get())),
+			}
+		);
+		assert_eq!(
+			fees.hops[2],
+			HopFees {
+				location: VersionedLocation::from((Parent, Parachain(2001))),
+				execution_weight: remote_weight,
+				execution_fees: VersionedAssets::from((Parent, 40u128)),
+				delivery_fees: VersionedAssets::from(Assets::new()),
+			}
+		);
+		assert_eq!(
+			fees.delivered_assets,
+			VersionedAssets::from((Parent, 100 - 40 - RemoteDeliveryFees::get() - 40))
+		);
+	});
+}
+
 fn dry_run_xcm_common(xcm_version: XcmVersion) {
 	sp_tracing::init_for_tests();
 	let who = 1; // AccountId = u64.
@@ -46,8 +46,11 @@ use xcm_executor::{
 
 use xcm_runtime_apis::{
 	conversions::{Error as LocationToAccountApiError, LocationToAccountApi},
-	dry_run::{CallDryRunEffects, DryRunApi, Error as XcmDryRunApiError, XcmDryRunEffects},
-	fees::{Error as XcmPaymentApiError, XcmPaymentApi},
+	dry_run::{
+		CallDryRunEffects, CallMultiHopDryRunEffects, DryRunApi, Error as XcmDryRunApiError,
+		XcmDryRunEffects,
+	},
+	fees::{Error as XcmPaymentApiError, RemoteFeeEstimator, XcmPaymentApi},
 	trusted_query::{Error as TrustedQueryApiError, TrustedQueryApi},
 };
 
@@ -158,6 +161,7 @@ pub type XcmRouter = TestXcmSender;
 
 parameter_types! {
 	pub const DeliveryFees: u128 = 20; // Random value.
+	pub const RemoteDeliveryFees: u128 = 5; // Random value.
 	pub const ExistentialDeposit: u128 = 1; // Random value.
 	pub const BaseXcmWeight: Weight = Weight::from_parts(100, 10); // Random value.
 	pub const MaxInstructions: u32 = 100;
@@ -183,6 +187,26 @@ impl WeightToFeeT for WeightToFee {
 
 type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
 
+/// Estimates the fees of the sibling parachains.
+/// They charge `BaseXcmWeight` per instruction, a fee of a tenth of the ref_time in any asset,
+/// and `RemoteDeliveryFees` of the relay token for every message they send.
+pub struct SiblingFeeEstimator;
+impl RemoteFeeEstimator for SiblingFeeEstimator {
+	fn weigh(location: &Location, message: &Xcm<()>) -> Option<Weight> {
+		matches!(location.unpack(), (1, [Parachain(_)]))
+			.then(|| BaseXcmWeight::get().saturating_mul(message.len() as u64))
+	}
+
+	fn weight_to_fee(location: &Location, weight: Weight, _: &AssetId) -> Option<u128> {
+		matches!(location.unpack(), (1, [Parachain(_)])).then(|| weight.ref_time() as u128 / 10)
+	}
+
+	fn delivery_fees(location: &Location, _: &Location, _: &Xcm<()>) -> Option<Assets> {
+		matches!(location.unpack(), (1, [Parachain(_)]))
+			.then(|| (Parent, RemoteDeliveryFees::get()).into())
+	}
+}
+
 /// Matches the pair (NativeToken, AssetHub).
 /// This is used in the `IsTeleporter` configuration item, meaning we accept our native token
 /// coming from AssetHub as a teleport.
@@ -521,5 +545,13 @@ sp_api::mock_impl_runtime_apis! {
 		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
 			pallet_xcm::Pallet::<TestRuntime>::dry_run_xcm::<TestRuntime, XcmRouter, RuntimeCall, XcmConfig>(origin_location, xcm)
 		}
+
+		fn dry_run_call_multi_hop(
+			origin: OriginCaller,
+			call: RuntimeCall,
+			result_xcms_version: XcmVersion,
+		) -> Result<CallMultiHopDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
+			pallet_xcm::Pallet::<TestRuntime>::dry_run_call_multi_hop::<TestRuntime, XcmRouter, OriginCaller, RuntimeCall, SiblingFeeEstimator>(origin, call, result_xcms_version)
+		}
 	}
 }
@@ -7,4 +7,6 @@ mod elastic_scaling;
 #[cfg(feature = "zombie-ci")]
 mod functional;
 #[cfg(feature = "zombie-ci")]
+mod request_response;
+#[cfg(feature = "zombie-ci")]
 mod smoke;
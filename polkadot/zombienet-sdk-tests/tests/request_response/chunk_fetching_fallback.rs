@@ -0,0 +1,163 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Test that validators keep fetching chunks from peers of an older version that don't speak the
+// `/req_chunk/2` protocol. New validators first try `/req_chunk/2` and, when it is not supported,
+// fall back to `/req_chunk/1`. Availability and finality must not be affected.
+
+use anyhow::anyhow;
+
+use super::{metric_or_zero, protocol_name, requests_out_failure, requests_out_success};
+use cumulus_zombienet_sdk_helpers::{assert_finality_lag, assert_finalized_para_throughput};
+use polkadot_primitives::Id as ParaId;
+use serde_json::json;
+use subxt::{OnlineClient, PolkadotConfig};
+use zombienet_sdk::NetworkConfigBuilder;
+
+// Image of a polkadot version that does not speak the `/req_chunk/2` protocol.
+const DEFAULT_OLD_POLKADOT_IMAGE: &str = "docker.io/paritypr/polkadot-debug:master-bde0bbe5";
+// Collators of the same version, so that their candidates are accepted by the old validators.
+const DEFAULT_OLD_COL_IMAGE: &str = "docker.io/paritypr/colander:master-bde0bbe5";
+
+// Reasons for which a request over a protocol the remote doesn't support fails, when it is not
+// retried over a fallback protocol, for both network backends.
+const UNSUPPORTED_REASONS: [&str; 3] =
+	["unsupported", "unsupported-protocol", "invalid-fallback-protocol"];
+
+#[tokio::test(flavor = "multi_thread")]
+async fn chunk_fetching_fallback_test() -> Result<(), anyhow::Error> {
+	let _ = env_logger::try_init_from_env(
+		env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "info"),
+	);
+
+	let images = zombienet_sdk::environment::get_images_from_env();
+	let old_polkadot_image =
+		std::env::var("OLD_POLKADOT_IMAGE").unwrap_or(DEFAULT_OLD_POLKADOT_IMAGE.to_string());
+	let old_col_image = std::env::var("OLD_COL_IMAGE").unwrap_or(DEFAULT_OLD_COL_IMAGE.to_string());
+
+	let config = NetworkConfigBuilder::new()
+		.with_relaychain(|r| {
+			let r = r
+				.with_chain("rococo-local")
+				.with_default_command("polkadot")
+				.with_default_image(images.polkadot.as_str())
+				.with_default_args(vec![("-lparachain=debug,\
+					parachain::availability-recovery=trace,\
+					parachain::availability-distribution=trace")
+					.into()])
+				.with_genesis_overrides(json!({
+					"configuration": {
+						"config": {
+							"needed_approvals": 4,
+							"scheduler_params": {
+								"max_validators_per_core": 2
+							}
+						}
+					}
+				}))
+				.with_node(|node| node.with_name("validator-0"))
+				.with_node(|node| node.with_name("validator-1"));
+
+			(0..2).fold(r, |acc, i| {
+				acc.with_node(|node| {
+					node.with_name(&format!("old-validator-{i}"))
+						.with_image(old_polkadot_image.as_str())
+				})
+			})
+		})
+		.with_parachain(|p| {
+			p.with_id(2000)
+				.with_default_command("undying-collator")
+				.with_default_image(old_col_image.as_str())
+				.cumulus_based(false)
+				.with_default_args(vec![("-lparachain=debug").into()])
+				.with_collator(|n| n.with_name("collator-2000"))
+		})
+		.with_parachain(|p| {
+			p.with_id(2001)
+				.with_default_command("undying-collator")
+				.with_default_image(old_col_image.as_str())
+				.cumulus_based(false)
+				.with_default_args(vec![("-lparachain=debug").into()])
+				.with_collator(|n| n.with_name("collator-2001"))
+		})
+		.build()
+		.map_err(|e| {
+			let errs = e.into_iter().map(|e| e.to_string()).collect::<Vec<_>>().join(" ");
+			anyhow!("config errs: {errs}")
+		})?;
+
+	let spawn_fn = zombienet_sdk::environment::get_spawn_fn();
+	let network = spawn_fn(config).await?;
+
+	let relay_node = network.get_node("validator-0")?;
+	let relay_client: OnlineClient<PolkadotConfig> = relay_node.wait_client().await?;
+
+	assert_finalized_para_throughput(
+		&relay_client,
+		15,
+		[(ParaId::from(2000), 10..16), (ParaId::from(2001), 10..16)]
+			.into_iter()
+			.collect(),
+	)
+	.await?;
+
+	assert_finality_lag(&relay_client, 5).await?;
+
+	let genesis_hash = relay_client.genesis_hash();
+	let chunk_v1 = protocol_name(genesis_hash, "/req_chunk/1");
+	let chunk_v2 = protocol_name(genesis_hash, "/req_chunk/2");
+
+	for name in ["validator-0", "validator-1"] {
+		let node = network.get_node(name)?;
+
+		// New validators fetch chunks from each other over the new protocol, and from the old
+		// validators over the fallback protocol.
+		node.wait_metric_with_timeout(requests_out_success(&chunk_v2), |v| v > 0.0, 60u64)
+			.await?;
+		node.wait_metric_with_timeout(requests_out_success(&chunk_v1), |v| v > 0.0, 60u64)
+			.await?;
+
+		// Every request for a chunk the remote couldn't answer over the new protocol was retried
+		// over the fallback protocol instead of failing.
+		for reason in UNSUPPORTED_REASONS {
+			let failures = metric_or_zero(node, &requests_out_failure(&chunk_v2, reason)).await;
+			assert_eq!(failures, 0.0, "{name}: chunk requests failed with reason {reason}");
+		}
+
+		// Old validators don't refuse the fallback requests.
+		let refused = metric_or_zero(node, &requests_out_failure(&chunk_v1, "refused")).await;
+		let fetched = metric_or_zero(node, &requests_out_success(&chunk_v1)).await;
+		assert!(
+			refused <= fetched / 10.0,
+			"{name}: {refused} refused vs {fetched} successful fallback chunk requests"
+		);
+	}
+
+	for name in ["validator-0", "validator-1", "old-validator-0", "old-validator-1"] {
+		let node = network.get_node(name)?;
+
+		// Availability distribution and recovery worked for all versions.
+		node.wait_metric_with_timeout(
+			"polkadot_parachain_fetched_chunks_total{success=\"succeeded\"}",
+			|v| v >= 10.0,
+			400u64,
+		)
+		.await?;
+		node.assert("polkadot_parachain_fetched_chunks_total{success=\"failed\"}", 0.0)
+			.await?;
+		node.assert(
+			"polkadot_parachain_availability_recovery_recoveries_finished{result=\"failure\"}",
+			0.0,
+		)
+		.await?;
+	}
+
+	relay_node
+		.assert("polkadot_parachain_approval_checking_finality_lag", 0.0)
+		.await?;
+
+	log::info!("Test finished successfully");
+
+	Ok(())
+}
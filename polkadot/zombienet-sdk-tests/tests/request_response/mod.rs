@@ -0,0 +1,35 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests of the request-response protocols across mixed-version validator fleets.
+
+use subxt::utils::H256;
+use zombienet_sdk::NetworkNode;
+
+mod chunk_fetching_fallback;
+
+/// The on-the-wire name of the request-response protocol `short_name` (e.g. `/req_chunk/1`) of
+/// the chain with the given genesis hash.
+pub fn protocol_name(genesis_hash: H256, short_name: &str) -> String {
+	format!("/{:x}{}", genesis_hash, short_name)
+}
+
+/// The metric counting the outgoing requests over `protocol` that received a response.
+pub fn requests_out_success(protocol: &str) -> String {
+	format!("substrate_sub_libp2p_requests_out_success_total_count{{protocol=\"{protocol}\"}}")
+}
+
+/// The metric counting the outgoing requests over `protocol` that failed for `reason`.
+pub fn requests_out_failure(protocol: &str, reason: &str) -> String {
+	format!(
+		"substrate_sub_libp2p_requests_out_failure_total{{protocol=\"{protocol}\",reason=\"{reason}\"}}"
+	)
+}
+
+/// The current value of `metric` on `node`.
+///
+/// Labelled counters are only exported once they have been incremented, so a metric that is not
+/// reported yet counts as zero.
+pub async fn metric_or_zero(node: &NetworkNode, metric: &str) -> f64 {
+	node.reports(metric).await.unwrap_or(0.0)
+}
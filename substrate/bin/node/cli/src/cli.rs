@@ -33,6 +33,10 @@ pub struct Cli {
 	#[clap(flatten)]
 	pub mixnet_params: sc_cli::MixnetParams,
 
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub signing_lease_params: sc_cli::SigningLeaseParams,
+
 	/// Disable automatic hardware benchmarks.
 	///
 	/// By default these benchmarks are automatically ran at startup and measure
@@ -43,9 +47,20 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// The number of GRANDPA rounds whose observed votes are kept in memory.
+	///
+	/// The recorded votes are served by the `grandpa_roundVotes` RPC. Defaults to `0`, i.e. no
+	/// votes are recorded.
+	#[arg(long, value_name = "ROUNDS", default_value_t = 0)]
+	pub grandpa_vote_history: usize,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub invariant_checker: sc_invariant_checker::InvariantCheckerParams,
 }
 
 /// Possible subcommands of the main binary.
@@ -168,10 +168,65 @@ pub fn create_extrinsic(
 	.into()
 }
 
+/// The chain spec property configuring the BABE recovery mode.
+const BABE_RECOVERY_PROPERTY: &str = "babeRecovery";
+
+/// The BABE recovery mode configuration in the chain spec properties.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BabeRecoveryProperty {
+	after_empty_slots: u64,
+	authorities: Vec<String>,
+}
+
+/// Read the BABE recovery mode configuration from the chain spec properties.
+///
+/// Recovery is only honoured for development and local chains, it is ignored on live chains.
+fn babe_recovery(
+	config: &Configuration,
+) -> Result<Option<sc_consensus_babe::RecoveryConfig>, ServiceError> {
+	use sp_core::crypto::Ss58Codec;
+
+	let Some(property) = config.chain_spec.properties().remove(BABE_RECOVERY_PROPERTY) else {
+		return Ok(None)
+	};
+
+	if config.chain_spec.chain_type() == sc_service::ChainType::Live {
+		log::warn!(
+			"⚠️  Ignoring the `{}` chain spec property of live chain {}",
+			BABE_RECOVERY_PROPERTY,
+			config.chain_spec.id(),
+		);
+		return Ok(None)
+	}
+
+	let property: BabeRecoveryProperty = serde_json::from_value(property).map_err(|e| {
+		ServiceError::Other(format!(
+			"Invalid `{}` chain spec property: {}",
+			BABE_RECOVERY_PROPERTY, e
+		))
+	})?;
+	let authorities: Vec<sc_consensus_babe::AuthorityId> = property
+		.authorities
+		.iter()
+		.map(|address| {
+			sp_core::sr25519::Public::from_ss58check(address).map(Into::into).map_err(|e| {
+				ServiceError::Other(format!("Invalid BABE recovery authority {}: {:?}", address, e))
+			})
+		})
+		.collect::<Result<_, _>>()?;
+
+	Ok(Some(sc_consensus_babe::RecoveryConfig {
+		after_empty_slots: property.after_empty_slots,
+		authorities,
+	}))
+}
+
 /// Creates a new partial node.
 pub fn new_partial(
 	config: &Configuration,
 	mixnet_config: Option<&sc_mixnet::Config>,
+	grandpa_vote_history: usize,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -193,7 +248,7 @@ pub fn new_partial(
 				sc_consensus_babe::BabeLink<Block>,
 				beefy::BeefyVoterLinks<Block, beefy_primitives::ecdsa_crypto::AuthorityId>,
 			),
-			grandpa::SharedVoterState,
+			(grandpa::SharedVoterState, grandpa::SharedVoteHistory<Block>),
 			Option<Telemetry>,
 			Arc<StatementStore>,
 			Option<sc_mixnet::ApiBackend>,
@@ -213,6 +268,10 @@ pub fn new_partial(
 		.transpose()?;
 
 	let executor = sc_service::new_wasm_executor(&config.executor);
+	let memory_watermarks = executor.memory_watermarks().clone();
+	if let Some(registry) = config.prometheus_registry() {
+		memory_watermarks.register(registry)?;
+	}
 
 	let (client, backend, keystore_container, task_manager) =
 		sc_service::new_full_parts::<Block, RuntimeApi, _>(
@@ -262,6 +321,16 @@ pub fn new_partial(
 		beefy_block_import,
 		client.clone(),
 	)?;
+	let babe_link = match babe_recovery(config)? {
+		Some(recovery) => {
+			log::warn!(
+				"🚑 BABE recovery mode enabled after {} empty slots",
+				recovery.after_empty_slots
+			);
+			babe_link.with_recovery(recovery)
+		},
+		None => babe_link,
+	};
 
 	let slot_duration = babe_link.config().slot_duration();
 	let (import_queue, babe_worker_handle) =
@@ -309,6 +378,8 @@ pub fn new_partial(
 		let shared_authority_set = grandpa_link.shared_authority_set().clone();
 		let shared_voter_state = grandpa::SharedVoterState::empty();
 		let shared_voter_state2 = shared_voter_state.clone();
+		let vote_history = grandpa::SharedVoteHistory::new(grandpa_vote_history);
+		let vote_history2 = vote_history.clone();
 
 		let finality_proof_provider = grandpa::FinalityProofProvider::new_for_service(
 			backend.clone(),
@@ -336,6 +407,7 @@ pub fn new_partial(
 					},
 					grandpa: node_rpc::GrandpaDeps {
 						shared_voter_state: shared_voter_state.clone(),
+						vote_history: vote_history.clone(),
 						shared_authority_set: shared_authority_set.clone(),
 						justification_stream: justification_stream.clone(),
 						subscription_executor: subscription_executor.clone(),
@@ -353,12 +425,13 @@ pub fn new_partial(
 					statement_store: rpc_statement_store.clone(),
 					backend: rpc_backend.clone(),
 					mixnet_api: mixnet_api.as_ref().cloned(),
+					memory_watermarks: memory_watermarks.clone(),
 				};
 
 				node_rpc::create_full(deps).map_err(Into::into)
 			};
 
-		(rpc_extensions_builder, shared_voter_state2)
+		(rpc_extensions_builder, (shared_voter_state2, vote_history2))
 	};
 
 	Ok(sc_service::PartialComponents {
@@ -400,7 +473,9 @@ pub struct NewFullBase {
 pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	mixnet_config: Option<sc_mixnet::Config>,
+	signing_lease: Option<sc_keystore::SigningLeaseConfig>,
 	disable_hardware_benchmarks: bool,
+	grandpa_vote_history: usize,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
 			Block,
@@ -419,6 +494,11 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let enable_offchain_worker = config.offchain_worker.enabled;
+	let offchain_http_client_config = sc_offchain::HttpClientConfig {
+		proxy: config.offchain_worker.http_proxy.clone(),
+		ca_certificates: config.offchain_worker.http_ca_certificates.clone(),
+		max_request_timeout: config.offchain_worker.http_request_timeout,
+	};
 
 	let hwbench = (!disable_hardware_benchmarks)
 		.then(|| {
@@ -439,12 +519,23 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		transaction_pool,
 		other:
 			(rpc_builder, import_setup, rpc_setup, mut telemetry, statement_store, mixnet_api_backend),
-	} = new_partial(&config, mixnet_config.as_ref())?;
+	} = new_partial(&config, mixnet_config.as_ref(), grandpa_vote_history)?;
+	let keystore_container =
+		match signing_lease {
+			Some(signing_lease) => {
+				let slot_duration = sc_consensus_babe::configuration(&*client)?.slot_duration();
+				keystore_container.with_signing_lease(signing_lease.with_slot_key_type(
+					sp_core::crypto::key_types::BABE,
+					slot_duration.as_duration(),
+				))
+			},
+			None => keystore_container,
+		};
 
 	let metrics = N::register_notification_metrics(
 		config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
 	);
-	let shared_voter_state = rpc_setup;
+	let (shared_voter_state, vote_history) = rpc_setup;
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 	let auth_disc_public_addresses = config.network.public_addresses.clone();
 
@@ -631,6 +722,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			block_proposal_slot_portion: SlotProportion::new(0.5),
 			max_block_proposal_slot_portion: None,
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
+			secondary_slot_claims: Default::default(),
 		};
 
 		let babe = sc_consensus_babe::start_babe(babe_config)?;
@@ -748,6 +840,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			voting_rule: grandpa::VotingRulesBuilder::default().build(),
 			prometheus_registry: prometheus_registry.clone(),
 			shared_voter_state,
+			vote_history,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool.clone()),
 		};
 
@@ -792,6 +885,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 				network_provider: Arc::new(network.clone()),
 				is_validator: role.is_authority(),
 				enable_http_requests: true,
+				http_client_config: offchain_http_client_config,
 				custom_extensions: move |_| {
 					vec![Box::new(statement_store.clone().as_statement_store_ext()) as Box<_>]
 				},
@@ -816,28 +910,37 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let mixnet_config = cli.mixnet_params.config(config.role.is_authority());
+	let signing_lease = cli
+		.signing_lease_params
+		.config()
+		.map_err(|e| ServiceError::Other(e.to_string()))?;
 	let database_path = config.database.path().map(Path::to_path_buf);
+	let prometheus_registry = config.prometheus_registry().cloned();
 
-	let task_manager = match config.network.network_backend.unwrap_or_default() {
+	let (task_manager, client) = match config.network.network_backend.unwrap_or_default() {
 		sc_network::config::NetworkBackendType::Libp2p => {
-			let task_manager = new_full_base::<sc_network::NetworkWorker<_, _>>(
+			let node = new_full_base::<sc_network::NetworkWorker<_, _>>(
 				config,
 				mixnet_config,
+				signing_lease,
 				cli.no_hardware_benchmarks,
+				cli.grandpa_vote_history,
 				|_, _| (),
 			)
-			.map(|NewFullBase { task_manager, .. }| task_manager)?;
-			task_manager
+			.map(|NewFullBase { task_manager, client, .. }| (task_manager, client))?;
+			node
 		},
 		sc_network::config::NetworkBackendType::Litep2p => {
-			let task_manager = new_full_base::<sc_network::Litep2pNetworkBackend>(
+			let node = new_full_base::<sc_network::Litep2pNetworkBackend>(
 				config,
 				mixnet_config,
+				signing_lease,
 				cli.no_hardware_benchmarks,
+				cli.grandpa_vote_history,
 				|_, _| (),
 			)
-			.map(|NewFullBase { task_manager, .. }| task_manager)?;
-			task_manager
+			.map(|NewFullBase { task_manager, client, .. }| (task_manager, client))?;
+			node
 		},
 	};
 
@@ -850,6 +953,13 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 		.map_err(|e| ServiceError::Application(e.into()))?;
 	}
 
+	sc_invariant_checker::InvariantChecker::try_spawn(
+		cli.invariant_checker,
+		client,
+		prometheus_registry.as_ref(),
+		&task_manager.spawn_handle(),
+	)?;
+
 	Ok(task_manager)
 }
 
@@ -920,7 +1030,9 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
 						false,
+						0,
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
 							setup_handles = Some((block_import.clone(), babe_link.clone()));
@@ -1121,7 +1233,9 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
 						false,
+						0,
 						|_, _| (),
 					)?;
 				Ok(sc_service_test::TestNetComponents::new(
@@ -28,6 +28,7 @@ use sp_core::{storage::well_known_keys, traits::Externalities};
 use sp_runtime::{
 	traits::Hash as HashT, transaction_validity::InvalidTransaction, ApplyExtrinsicResult,
 };
+use substrate_fee_calc::FeeCalculator;
 
 use kitchensink_runtime::{
 	constants::{currency::*, time::SLOT_DURATION},
@@ -63,7 +64,12 @@ pub fn bloaty_code_unwrap() -> &'static [u8] {
 fn transfer_fee(extrinsic: &UncheckedExtrinsic) -> Balance {
 	let mut info = default_transfer_call().get_dispatch_info();
 	info.extension_weight = extrinsic.0.extension_weight();
-	TransactionPayment::compute_fee(extrinsic.encode().len() as u32, &info, 0)
+	FeeCalculator::of_runtime::<Runtime>().compute_fee(
+		&info,
+		extrinsic.encode().len() as u32,
+		0,
+		TransactionPayment::next_fee_multiplier(),
+	)
 }
 
 /// Default transfer fee, same as `transfer_fee`, but with a weight refund factored in.
@@ -71,7 +77,13 @@ fn transfer_fee_with_refund(extrinsic: &UncheckedExtrinsic, weight_refund: Weigh
 	let mut info = default_transfer_call().get_dispatch_info();
 	info.extension_weight = extrinsic.0.extension_weight();
 	let post_info = (Some(info.total_weight().saturating_sub(weight_refund)), info.pays_fee).into();
-	TransactionPayment::compute_actual_fee(extrinsic.encode().len() as u32, &info, &post_info, 0)
+	FeeCalculator::of_runtime::<Runtime>().compute_actual_fee(
+		&info,
+		&post_info,
+		extrinsic.encode().len() as u32,
+		0,
+		TransactionPayment::next_fee_multiplier(),
+	)
 }
 
 fn xt() -> UncheckedExtrinsic {
@@ -16,34 +16,21 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use codec::{Decode, Encode};
-use frame_support::Hashable;
 use frame_system::offchain::AppCrypto;
 use polkadot_sdk::*;
 use sc_executor::error::Result;
-use sp_consensus_babe::{
-	digests::{PreDigest, SecondaryPlainPreDigest},
-	Slot, BABE_ENGINE_ID,
-};
-use sp_core::{
-	crypto::KeyTypeId,
-	sr25519::Signature,
-	traits::{CallContext, CodeExecutor, RuntimeCode},
-};
+use sp_consensus_babe::Slot;
+use sp_core::{crypto::KeyTypeId, sr25519::Signature};
 use sp_runtime::{
 	traits::{BlakeTwo256, Header as HeaderT},
-	ApplyExtrinsicResult, Digest, DigestItem, MultiSignature, MultiSigner,
+	MultiSignature, MultiSigner,
 };
-use sp_state_machine::TestExternalities as CoreTestExternalities;
 
 use kitchensink_runtime::{
-	constants::currency::*, Block, BuildStorage, CheckedExtrinsic, Header, Runtime,
-	UncheckedExtrinsic,
+	constants::currency::*, BuildStorage, CheckedExtrinsic, Header, Runtime, UncheckedExtrinsic,
 };
 use node_primitives::{BlockNumber, Hash};
-use node_testing::keyring::*;
-use sp_externalities::Externalities;
-use staging_node_cli::service::RuntimeExecutor;
+use node_testing::{block_builder::TestBlockBuilder, keyring::*};
 
 pub const TEST_KEY_TYPE_ID: KeyTypeId = KeyTypeId(*b"test");
 
@@ -76,14 +63,12 @@ pub fn compact_code_unwrap() -> &'static [u8] {
 	)
 }
 
-pub const GENESIS_HASH: [u8; 32] = [69u8; 32];
+pub use node_testing::block_builder::{executor, TestExternalities, GENESIS_HASH};
 
 pub const SPEC_VERSION: u32 = kitchensink_runtime::VERSION.spec_version;
 
 pub const TRANSACTION_VERSION: u32 = kitchensink_runtime::VERSION.transaction_version;
 
-pub type TestExternalities<H> = CoreTestExternalities<H>;
-
 pub fn sign(xt: CheckedExtrinsic) -> UncheckedExtrinsic {
 	node_testing::keyring::sign(xt, SPEC_VERSION, TRANSACTION_VERSION, GENESIS_HASH, None)
 }
@@ -99,26 +84,13 @@ pub fn from_block_number(n: u32) -> Header {
 	Header::new(n, Default::default(), Default::default(), [69; 32].into(), Default::default())
 }
 
-pub fn executor() -> RuntimeExecutor {
-	RuntimeExecutor::builder().build()
-}
-
 pub fn executor_call(
 	t: &mut TestExternalities<BlakeTwo256>,
 	method: &str,
 	data: &[u8],
 ) -> (Result<Vec<u8>>, bool) {
-	let mut t = t.ext();
-
-	let code = t.storage(sp_core::storage::well_known_keys::CODE).unwrap();
-	let heap_pages = t.storage(sp_core::storage::well_known_keys::HEAP_PAGES);
-	let runtime_code = RuntimeCode {
-		code_fetcher: &sp_core::traits::WrappedRuntimeCode(code.as_slice().into()),
-		hash: sp_crypto_hashing::blake2_256(&code).to_vec(),
-		heap_pages: heap_pages.and_then(|hp| Decode::decode(&mut &hp[..]).ok()),
-	};
 	sp_tracing::try_init_simple();
-	executor().call(&mut t, &runtime_code, method, data, CallContext::Onchain)
+	node_testing::block_builder::executor_call(t, method, data)
 }
 
 pub fn new_test_ext(code: &[u8]) -> TestExternalities<BlakeTwo256> {
@@ -129,10 +101,11 @@ pub fn new_test_ext(code: &[u8]) -> TestExternalities<BlakeTwo256> {
 	ext
 }
 
-/// Construct a fake block.
+/// Construct a fake block with a BABE pre-digest for `babe_slot`.
 ///
 /// `extrinsics` must be a list of valid extrinsics, i.e. none of the extrinsics for example
-/// can report `ExhaustResources`. Otherwise, this function panics.
+/// can report `ExhaustResources`. Otherwise, this function panics. Use [`TestBlockBuilder`]
+/// directly for control over the digest and the inherents of the block.
 pub fn construct_block(
 	env: &mut TestExternalities<BlakeTwo256>,
 	number: BlockNumber,
@@ -140,57 +113,8 @@ pub fn construct_block(
 	extrinsics: Vec<CheckedExtrinsic>,
 	babe_slot: Slot,
 ) -> (Vec<u8>, Hash) {
-	use sp_trie::{LayoutV1 as Layout, TrieConfiguration};
-
-	// sign extrinsics.
-	let extrinsics = extrinsics.into_iter().map(sign).collect::<Vec<_>>();
-
-	// calculate the header fields that we can.
-	let extrinsics_root =
-		Layout::<BlakeTwo256>::ordered_trie_root(extrinsics.iter().map(Encode::encode))
-			.to_fixed_bytes()
-			.into();
-
-	let header = Header {
-		parent_hash,
-		number,
-		extrinsics_root,
-		state_root: Default::default(),
-		digest: Digest {
-			logs: vec![DigestItem::PreRuntime(
-				BABE_ENGINE_ID,
-				PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
-					slot: babe_slot,
-					authority_index: 42,
-				})
-				.encode(),
-			)],
-		},
-	};
-
-	// execute the block to get the real header.
-	executor_call(env, "Core_initialize_block", &header.encode()).0.unwrap();
-
-	for extrinsic in extrinsics.iter() {
-		// Try to apply the `extrinsic`. It should be valid, in the sense that it passes
-		// all pre-inclusion checks.
-		let r = executor_call(env, "BlockBuilder_apply_extrinsic", &extrinsic.encode())
-			.0
-			.expect("application of an extrinsic failed");
-
-		match ApplyExtrinsicResult::decode(&mut &r[..])
-			.expect("apply result deserialization failed")
-		{
-			Ok(_) => {},
-			Err(e) => panic!("Applying extrinsic failed: {:?}", e),
-		}
-	}
-
-	let header = Header::decode(
-		&mut &executor_call(env, "BlockBuilder_finalize_block", &[0u8; 0]).0.unwrap()[..],
-	)
-	.unwrap();
-
-	let hash = header.blake2_256();
-	(Block { header, extrinsics }.encode(), hash.into())
+	TestBlockBuilder::new(env, number, parent_hash)
+		.babe_slot(babe_slot)
+		.extrinsics(extrinsics)
+		.build()
 }
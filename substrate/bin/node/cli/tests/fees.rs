@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use codec::{Encode, Joiner};
+use codec::{Decode, Encode, Joiner};
 use frame_support::{
 	dispatch::GetDispatchInfo,
 	traits::Currency,
@@ -24,13 +24,15 @@ use frame_support::{
 };
 use kitchensink_runtime::{
 	constants::{currency::*, time::SLOT_DURATION},
-	Balances, CheckedExtrinsic, Multiplier, Runtime, RuntimeCall, TransactionByteFee,
+	Balances, Block, CheckedExtrinsic, Multiplier, Runtime, RuntimeCall, TransactionByteFee,
 	TransactionPayment,
 };
 use node_primitives::Balance;
-use node_testing::keyring::*;
+use node_testing::{block_builder::TestBlockBuilder, fee_replay, keyring::*};
+use pallet_transaction_payment::{FeeDetails, NextFeeMultiplier};
 use polkadot_sdk::*;
-use sp_runtime::{traits::One, Perbill};
+use sp_runtime::{traits::One, DigestItem, FixedPointNumber, Perbill};
+use substrate_fee_calc::FeeCalculator;
 
 pub mod common;
 use self::common::{sign, *};
@@ -118,6 +120,119 @@ fn fee_multiplier_increases_and_decreases_on_big_weight() {
 	});
 }
 
+#[test]
+fn fee_multiplier_decreases_per_block_across_missed_slots() {
+	let initial_multiplier = Multiplier::saturating_from_integer(2);
+	let set_multiplier = || NextFeeMultiplier::<Runtime>::put(initial_multiplier);
+
+	let mut t = new_test_ext(compact_code_unwrap());
+	t.execute_with(set_multiplier);
+
+	let mut tt = new_test_ext(compact_code_unwrap());
+	let custom_item = DigestItem::Other(b"custom".to_vec());
+
+	let time1 = 42 * 1000;
+	let block1 = TestBlockBuilder::new(&mut tt, 1, GENESIS_HASH.into())
+		.with_state(set_multiplier)
+		.babe_slot((time1 / SLOT_DURATION).into())
+		.digest_item(custom_item.clone())
+		.inherent(RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time1 }))
+		.extrinsic(CheckedExtrinsic {
+			format: sp_runtime::generic::ExtrinsicFormat::Signed(charlie(), tx_ext(0, 0)),
+			function: RuntimeCall::System(frame_system::Call::remark { remark: vec![0; 1] }),
+		})
+		.build();
+
+	// ten slots later, nine of them missed.
+	let time2 = time1 + 10 * SLOT_DURATION;
+	let block2 = TestBlockBuilder::new(&mut tt, 2, block1.1)
+		.babe_slot((time2 / SLOT_DURATION).into())
+		.inherent(RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time2 }))
+		.build();
+
+	let header = Block::decode(&mut &block1.0[..]).unwrap().header;
+	assert!(header.digest.logs.contains(&custom_item));
+
+	executor_call(&mut t, "Core_execute_block", &block1.0).0.unwrap();
+	let after_block1 = t.execute_with(|| TransactionPayment::next_fee_multiplier());
+	assert!(after_block1 < initial_multiplier);
+
+	// the multiplier is updated once per block, however many slots were missed.
+	executor_call(&mut t, "Core_execute_block", &block2.0).0.unwrap();
+	t.execute_with(|| {
+		let fm = TransactionPayment::next_fee_multiplier();
+		assert!(fm < after_block1);
+		assert!(fm > Multiplier::one());
+	});
+}
+
+#[test]
+fn replayed_block_reports_fee_multiplier_and_fullness() {
+	let mut t = new_test_ext(compact_code_unwrap());
+	let mut tt = new_test_ext(compact_code_unwrap());
+
+	let time = 42 * 1000;
+	let block = TestBlockBuilder::new(&mut tt, 1, GENESIS_HASH.into())
+		.babe_slot((time / SLOT_DURATION).into())
+		.inherent(RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time }))
+		.extrinsic(CheckedExtrinsic {
+			format: sp_runtime::generic::ExtrinsicFormat::Signed(charlie(), tx_ext(0, 0)),
+			function: RuntimeCall::Sudo(pallet_sudo::Call::sudo {
+				call: Box::new(RuntimeCall::RootTesting(pallet_root_testing::Call::fill_block {
+					ratio: Perbill::from_percent(60),
+				})),
+			}),
+		})
+		.build();
+	let expected_multiplier = tt.execute_with(|| TransactionPayment::next_fee_multiplier());
+
+	let sample =
+		fee_replay::replay_block(&mut t, Block::decode(&mut &block.0[..]).unwrap()).unwrap();
+	assert_eq!(sample.number, 1);
+	assert_eq!(sample.failed_extrinsics, 0);
+	assert_eq!(sample.next_multiplier, expected_multiplier);
+	assert!(sample.next_multiplier > Multiplier::one());
+	// 60% of the block is 80% of the weight available to normal extrinsics.
+	assert!(sample.fullness > Perbill::from_percent(75));
+	assert!(sample.transfer_fee > 0);
+}
+
+#[test]
+fn generated_accounts_pay_fees_in_a_single_block() {
+	const ACCOUNTS: u32 = 50;
+	let funds = 10 * DOLLARS;
+
+	let mut t = new_test_ext(compact_code_unwrap());
+	fund_nth(&mut t, 0..ACCOUNTS, funds);
+	let mut tt = new_test_ext(compact_code_unwrap());
+	fund_nth(&mut tt, 0..ACCOUNTS, funds);
+
+	let xts = signed_by_nth(
+		0..ACCOUNTS,
+		0,
+		|_| RuntimeCall::System(frame_system::Call::remark { remark: vec![0; 64] }),
+		SPEC_VERSION,
+		TRANSACTION_VERSION,
+		GENESIS_HASH,
+	);
+	let time = 42 * 1000;
+	let block = TestBlockBuilder::new(&mut tt, 1, GENESIS_HASH.into())
+		.babe_slot((time / SLOT_DURATION).into())
+		.inherent(RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time }))
+		.signed_extrinsics(xts)
+		.build();
+
+	executor_call(&mut t, "Core_execute_block", &block.0).0.unwrap();
+
+	t.execute_with(|| {
+		for i in 0..ACCOUNTS {
+			let free = Balances::free_balance(nth(i));
+			assert!(free < funds, "account {} did not pay a fee", i);
+			assert_eq!(frame_system::Pallet::<Runtime>::account_nonce(nth(i)), 1);
+		}
+	});
+}
+
 fn new_account_info(free_dollars: u128) -> Vec<u8> {
 	frame_system::AccountInfo {
 		nonce: 0u32,
@@ -156,6 +271,11 @@ fn transaction_fee_is_correct() {
 	let r = executor_call(&mut t, "Core_initialize_block", &vec![].and(&from_block_number(1u32))).0;
 
 	assert!(r.is_ok());
+	let r =
+		executor_call(&mut t, "TransactionPaymentApi_query_fee_breakdown", &xt.encode().encode())
+			.0
+			.unwrap();
+	let fee_breakdown = Option::<FeeDetails<Balance>>::decode(&mut &r[..]).unwrap().unwrap();
 	let r = executor_call(&mut t, "BlockBuilder_apply_extrinsic", &vec![].and(&xt.clone())).0;
 	assert!(r.is_ok());
 
@@ -167,27 +287,25 @@ fn transaction_fee_is_correct() {
 		// - Length fee
 		// - Tip
 		// - Creation-fee of bob's account.
-		let mut balance_alice = (100 - 69) * DOLLARS;
-
-		let base_weight = ExtrinsicBaseWeight::get();
-		let base_fee = IdentityFee::<Balance>::weight_to_fee(&base_weight);
-
-		let length_fee = TransactionByteFee::get() * (xt.clone().encode().len() as Balance);
-		balance_alice -= length_fee;
-
 		let mut info = default_transfer_call().get_dispatch_info();
 		info.extension_weight = xt.0.extension_weight();
-		let weight = info.total_weight();
-		let weight_fee = IdentityFee::<Balance>::weight_to_fee(&weight);
+		let len = xt.clone().encode().len() as u32;
 
 		// we know that weight to fee multiplier is effect-less in block 1.
+		let fee =
+			FeeCalculator::of_runtime::<Runtime>().fee_details(&info, len, tip, Multiplier::one());
+		let inclusion_fee = fee.inclusion_fee.clone().unwrap();
+
 		// current weight of transfer = 200_000_000
 		// Linear weight to fee is 1:1 right now (1 weight = 1 unit of balance)
-		assert_eq!(weight_fee, weight.ref_time() as Balance);
-		balance_alice -= base_fee;
-		balance_alice -= weight_fee;
-		balance_alice -= tip;
+		let base_weight = ExtrinsicBaseWeight::get();
+		assert_eq!(inclusion_fee.base_fee, IdentityFee::<Balance>::weight_to_fee(&base_weight));
+		assert_eq!(inclusion_fee.adjusted_weight_fee, info.total_weight().ref_time() as Balance);
+		assert_eq!(inclusion_fee.len_fee, TransactionByteFee::get() * len as Balance);
+
+		assert_eq!(Balances::total_balance(&alice()), (100 - 69) * DOLLARS - fee.final_fee());
 
-		assert_eq!(Balances::total_balance(&alice()), balance_alice);
+		// The runtime API gives the same breakdown, extension weight and tip included.
+		assert_eq!(fee_breakdown, fee);
 	});
 }
@@ -41,8 +41,10 @@ use sc_consensus_beefy::communication::notification::{
 	BeefyBestBlockStream, BeefyVersionedFinalityProofStream,
 };
 use sc_consensus_grandpa::{
-	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
+	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoteHistory,
+	SharedVoterState,
 };
+use sc_executor::MemoryWatermarks;
 pub use sc_rpc::SubscriptionTaskExecutor;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -66,6 +68,8 @@ pub struct BabeDeps {
 pub struct GrandpaDeps<B> {
 	/// Voting round info.
 	pub shared_voter_state: SharedVoterState,
+	/// History of the votes observed by the voter.
+	pub vote_history: SharedVoteHistory<Block>,
 	/// Authority set info.
 	pub shared_authority_set: SharedAuthoritySet<Hash, BlockNumber>,
 	/// Receives notifications about justification events from Grandpa.
@@ -108,6 +112,8 @@ pub struct FullDeps<C, P, SC, B, AuthorityId: AuthorityIdBound> {
 	pub backend: Arc<B>,
 	/// Mixnet API.
 	pub mixnet_api: Option<sc_mixnet::Api>,
+	/// Peak memory used by the runtime calls of the executor.
+	pub memory_watermarks: MemoryWatermarks,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -123,11 +129,13 @@ pub fn create_full<C, P, SC, B, AuthorityId>(
 		statement_store,
 		backend,
 		mixnet_api,
+		memory_watermarks,
 	}: FullDeps<C, P, SC, B, AuthorityId>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
 		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::StorageProvider<Block, B>
 		+ HeaderBackend<Block>
 		+ AuxStore
 		+ HeaderMetadata<Block, Error = BlockChainError>
@@ -137,9 +145,11 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash, BlockNumber>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: sp_api::Metadata<Block>,
+	C::Api: pallet_balances_rpc::BalancesRuntimeApi<Block, AccountId, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + 'static,
+	P: TransactionPool<Block = Block> + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashingFor<Block>>,
@@ -147,7 +157,11 @@ where
 	<AuthorityId as RuntimeAppPublic>::Signature: Send + Sync,
 {
 	use mmr_rpc::{Mmr, MmrApiServer};
-	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use pallet_balances_rpc::{Balances, BalancesApiServer};
+	use pallet_transaction_payment_rpc::{
+		ExtrinsicDecode, ExtrinsicDecodeApiServer, ReadySet, ReadySetApiServer, StorageDecode,
+		StorageDecodeApiServer, TransactionPayment, TransactionPaymentApiServer,
+	};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_beefy_rpc::{Beefy, BeefyApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
@@ -157,7 +171,7 @@ where
 		statement::StatementApiServer,
 	};
 	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
-	use substrate_frame_rpc_system::{System, SystemApiServer};
+	use substrate_frame_rpc_system::{DryRunBlock, DryRunBlockApiServer, System, SystemApiServer};
 	use substrate_state_trie_migration_rpc::{StateMigration, StateMigrationApiServer};
 
 	let mut io = RpcModule::new(());
@@ -165,13 +179,15 @@ where
 	let BabeDeps { keystore, babe_worker_handle } = babe;
 	let GrandpaDeps {
 		shared_voter_state,
+		vote_history,
 		shared_authority_set,
 		justification_stream,
 		subscription_executor,
 		finality_provider,
 	} = grandpa;
 
-	io.merge(System::new(client.clone(), pool).into_rpc())?;
+	io.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
+	io.merge(DryRunBlock::new(client.clone(), backend.clone()).into_rpc())?;
 	// Making synchronous calls in light client freezes the browser currently,
 	// more context: https://github.com/paritytech/substrate/pull/3480
 	// These RPCs should use an asynchronous caller instead.
@@ -185,6 +201,10 @@ where
 		.into_rpc(),
 	)?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(ExtrinsicDecode::<_, Block, B, Balance>::new(client.clone()).into_rpc())?;
+	io.merge(ReadySet::<_, _, Balance>::new(client.clone(), pool).into_rpc())?;
+	io.merge(StorageDecode::<_, Block, B>::new(client.clone()).into_rpc())?;
+	io.merge(Balances::<_, Block, Balance>::new(client.clone()).into_rpc())?;
 	io.merge(
 		Babe::new(client.clone(), babe_worker_handle.clone(), keystore, select_chain).into_rpc(),
 	)?;
@@ -195,6 +215,7 @@ where
 			shared_voter_state,
 			justification_stream,
 			finality_provider,
+			vote_history,
 		)
 		.into_rpc(),
 	)?;
@@ -205,7 +226,7 @@ where
 	)?;
 
 	io.merge(StateMigration::new(client.clone(), backend).into_rpc())?;
-	io.merge(Dev::new(client).into_rpc())?;
+	io.merge(Dev::new(client).with_memory_watermarks(memory_watermarks).into_rpc())?;
 	let statement_store = sc_rpc::statement::StatementStore::new(statement_store).into_rpc();
 	io.merge(statement_store)?;
 
@@ -364,6 +364,7 @@ impl frame_system::Config for Runtime {
 	type SystemWeightInfo = frame_system::weights::SubstrateWeight<Runtime>;
 	type SS58Prefix = ConstU16<42>;
 	type MaxConsumers = ConstU32<16>;
+	type BlockWeightHistoryLength = ConstU32<256>;
 	type MultiBlockMigrator = MultiBlockMigrations;
 }
 
@@ -388,6 +389,8 @@ parameter_types! {
 	pub const DepositBase: Balance = deposit(1, 88);
 	// Additional storage item size of 32 bytes.
 	pub const DepositFactor: Balance = deposit(0, 32);
+	// Additional storage item size of 1 byte per byte of a stored call.
+	pub const CallDepositPerByte: Balance = deposit(0, 1);
 }
 
 impl pallet_multisig::Config for Runtime {
@@ -397,6 +400,9 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
+	type CallDepositPerByte = CallDepositPerByte;
+	type CallExpiry = ConstU32<{ 7 * DAYS }>;
 	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -485,6 +491,7 @@ impl pallet_proxy::Config for Runtime {
 	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
 	type MaxPending = ConstU32<32>;
 	type CallHasher = BlakeTwo256;
+	type AnnouncementFilter = ();
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
@@ -716,6 +723,7 @@ parameter_types! {
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MaxNominators: u32 = 64;
 	pub const MaxControllersInDeprecationBatch: u32 = 5900;
+	pub MaxAutoPayoutWeight: Weight = Perbill::from_percent(10) * MAXIMUM_BLOCK_WEIGHT;
 	pub OffchainRepeat: BlockNumber = 5;
 	pub HistoryDepth: u32 = 84;
 }
@@ -760,6 +768,7 @@ impl pallet_staking::Config for Runtime {
 	type TargetList = pallet_staking::UseValidatorsMap<Self>;
 	type MaxUnlockingChunks = ConstU32<32>;
 	type MaxControllersInDeprecationBatch = MaxControllersInDeprecationBatch;
+	type MaxAutoPayoutWeight = MaxAutoPayoutWeight;
 	type HistoryDepth = HistoryDepth;
 	type EventListeners = (NominationPools, DelegatedStaking);
 	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
@@ -1104,9 +1113,26 @@ impl pallet_ranked_collective::Config for Runtime {
 	type BenchmarkSetup = (CoreFellowship, Salary);
 }
 
+parameter_types! {
+	pub const MaxTopicRemarkLen: u32 = 256;
+	pub const MaxRemarksPerTopic: u32 = 64;
+	pub const TopicRetentionPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxTopics: u32 = 4096;
+	// Held for the sender, the block number and the deposit stored along every remark.
+	pub const TopicRemarkDepositBase: Balance = deposit(1, 52);
+	pub const TopicRemarkDepositPerByte: Balance = deposit(0, 1);
+}
+
 impl pallet_remark::Config for Runtime {
 	type WeightInfo = pallet_remark::weights::SubstrateWeight<Self>;
 	type RuntimeEvent = RuntimeEvent;
+	type MaxTopicRemarkLen = MaxTopicRemarkLen;
+	type MaxRemarksPerTopic = MaxRemarksPerTopic;
+	type TopicRetentionPeriod = TopicRetentionPeriod;
+	type MaxTopics = MaxTopics;
+	type Currency = Balances;
+	type TopicRemarkDepositBase = TopicRemarkDepositBase;
+	type TopicRemarkDepositPerByte = TopicRemarkDepositPerByte;
 }
 
 impl pallet_root_testing::Config for Runtime {
@@ -1323,6 +1349,7 @@ impl pallet_treasury::Config for Runtime {
 	type AssetKind = NativeOrWithId<u32>;
 	type Beneficiary = AccountId;
 	type BeneficiaryLookup = Indices;
+	type PartialPayoutOrigin = EnsureSigned<AccountId>;
 	type Paymaster = PayAssetFromAccount<NativeAndAssets, TreasuryAccount>;
 	type BalanceConverter = AssetRate;
 	type PayoutPeriod = SpendPayoutPeriod;
@@ -1389,6 +1416,7 @@ impl pallet_message_queue::Config for Runtime {
 	type MaxStale = ConstU32<128>;
 	type ServiceWeight = MessageQueueServiceWeight;
 	type IdleMaxServiceWeight = ();
+	type QueueServicePolicy = ();
 }
 
 parameter_types! {
@@ -1476,6 +1504,7 @@ impl pallet_revive::Config for Runtime {
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_revive::weights::SubstrateWeight<Self>;
 	type ChainExtension = ();
+	type Precompiles = ();
 	type AddressMapper = pallet_revive::AccountId32Mapper<Self>;
 	type RuntimeMemory = ConstU32<{ 128 * 1024 * 1024 }>;
 	type PVFMemory = ConstU32<{ 512 * 1024 * 1024 }>;
@@ -1801,6 +1830,8 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type Freezer = ();
 	type Extra = ();
 	type CallbackHandle = ();
+	type OnTransfer = ();
+	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1831,6 +1862,8 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	type CallbackHandle = ();
+	type OnTransfer = ();
+	type RuntimeFreezeReason = RuntimeFreezeReason;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -2868,6 +2901,7 @@ type Migrations = (
 	pallet_alliance::migration::Migration<Runtime>,
 	pallet_contracts::Migration<Runtime>,
 	pallet_identity::migration::versioned::V0ToV1<Runtime, IDENTITY_MIGRATION_KEY_LIMIT>,
+	pallet_scheduler::migration::v5::MigrateV4ToV5<Runtime>,
 );
 
 type EventRecord = frame_system::EventRecord<
@@ -3109,6 +3143,23 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_remark::runtime_api::RemarkApi<Block, AccountId, BlockNumber> for Runtime {
+		fn topic_remarks(topic: pallet_remark::Topic) -> Vec<(AccountId, BlockNumber, Vec<u8>)> {
+			Remark::topic_remarks(topic)
+				.into_iter()
+				.map(|remark| (remark.sender, remark.block, remark.data.into_inner()))
+				.collect()
+		}
+	}
+
+	impl pallet_multisig::runtime_api::MultisigApi<Block, AccountId, BlockNumber> for Runtime {
+		fn pending_operations(
+			multisig: AccountId,
+		) -> Vec<pallet_multisig::PendingOperation<BlockNumber, AccountId>> {
+			Multisig::pending_operations(&multisig)
+		}
+	}
+
 	impl sp_offchain::OffchainWorkerApi<Block> for Runtime {
 		fn offchain_worker(header: &<Block as BlockT>::Header) {
 			Executive::offchain_worker(header)
@@ -3151,6 +3202,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_balances::runtime_api::BalancesApi<Block, AccountId, Balance> for Runtime {
+		fn balance_breakdown(who: AccountId) -> pallet_balances::BalanceBreakdown<Balance> {
+			Balances::balance_breakdown(&who)
+		}
+	}
+
 	impl pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance> for Runtime {
 		fn pending_rewards(who: AccountId) -> Balance {
 			NominationPools::api_pending_rewards(who).unwrap_or_default()
@@ -3268,6 +3325,16 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame_system_rpc_runtime_api::BlockWeightHistoryApi<
+		Block,
+		BlockNumber,
+		frame_system::ConsumedWeight,
+	> for Runtime {
+		fn block_weight_history() -> Vec<(BlockNumber, frame_system::ConsumedWeight)> {
+			System::block_weight_history()
+		}
+	}
+
 	impl assets_api::AssetsApi<
 		Block,
 		AccountId,
@@ -3519,6 +3586,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(5)]
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -3535,6 +3603,16 @@ impl_runtime_apis! {
 		fn query_length_to_fee(length: u32) -> Balance {
 			TransactionPayment::length_to_fee(length)
 		}
+		fn query_fee_breakdown(encoded_xt: Vec<u8>) -> Option<FeeDetails<Balance>> {
+			let len = encoded_xt.len() as u32;
+			let uxt = UncheckedExtrinsic::decode(&mut &encoded_xt[..]).ok()?;
+			let tip = match &uxt.0.preamble {
+				generic::Preamble::Signed(_, _, tx_ext) | generic::Preamble::General(_, tx_ext) =>
+					tx_ext.7.0.tip(),
+				generic::Preamble::Bare(_) => 0,
+			};
+			Some(TransactionPayment::query_fee_breakdown(uxt, len, tip))
+		}
 	}
 
 	impl pallet_asset_conversion::AssetConversionApi<
@@ -3776,6 +3854,7 @@ impl_runtime_apis! {
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
@@ -3785,6 +3864,14 @@ impl_runtime_apis! {
 			(weight, RuntimeBlockWeights::get().max_block)
 		}
 
+		fn on_runtime_upgrade_with_report(
+			checks: frame_try_runtime::UpgradeCheckSelect,
+			idempotency_checks: bool,
+		) -> frame_try_runtime::UpgradeReport {
+			// NOTE: intentional unwrap, same as above.
+			Executive::try_runtime_upgrade_with_report(checks, idempotency_checks).unwrap()
+		}
+
 		fn execute_block(
 			block: Block,
 			state_root_check: bool,
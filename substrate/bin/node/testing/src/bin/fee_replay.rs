@@ -0,0 +1,69 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Replay recent blocks of a kitchensink chain with another runtime and print the fees as CSV.
+//!
+//! See [`node_testing::fee_replay`].
+
+use clap::Parser;
+use node_primitives::Hash;
+use sp_runtime::{PerThing, Perbill};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+struct Opt {
+	/// The node to fetch the blocks and their state from.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	uri: String,
+
+	/// The wasm runtime to replay the blocks with.
+	#[arg(long)]
+	runtime: PathBuf,
+
+	/// The last block to replay, the finalized head if not given.
+	#[arg(long)]
+	at: Option<Hash>,
+
+	/// The number of blocks to replay.
+	#[arg(long, default_value_t = 100)]
+	blocks: u32,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+	sp_tracing::try_init_simple();
+	let Opt { uri, runtime, at, blocks } = Opt::parse();
+
+	let code = std::fs::read(&runtime)
+		.map_err(|e| format!("Reading the runtime from {} failed: {e}", runtime.display()))?;
+	let samples = node_testing::fee_replay::replay(uri, code, at, blocks).await?;
+
+	println!("block,next_multiplier,fullness,transfer_fee,failed_extrinsics");
+	for sample in samples {
+		println!(
+			"{},{},{},{},{}",
+			sample.number,
+			sample.next_multiplier,
+			sample.fullness.deconstruct() as f64 / Perbill::ACCURACY as f64,
+			sample.transfer_fee,
+			sample.failed_extrinsics,
+		);
+	}
+
+	Ok(())
+}
@@ -0,0 +1,256 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Construction of blocks by executing the runtime directly against test externalities.
+//!
+//! Unlike the client based [`crate::bench`] utilities, no database or import pipeline is
+//! involved: the runtime APIs are called one by one on the given state, which makes it possible
+//! to control the digest and the parent state of a block precisely.
+
+use codec::{Decode, Encode};
+use kitchensink_runtime::{Block, CheckedExtrinsic, Header, RuntimeCall, UncheckedExtrinsic};
+use node_cli::service::RuntimeExecutor;
+use node_primitives::{BlockNumber, Hash};
+use sc_executor::error::Result;
+use sp_consensus_babe::{
+	digests::{PreDigest, SecondaryPlainPreDigest},
+	Slot, BABE_ENGINE_ID,
+};
+use sp_core::traits::{CallContext, CodeExecutor, RuntimeCode};
+use sp_externalities::Externalities;
+use sp_runtime::{
+	generic::ExtrinsicFormat,
+	traits::{BlakeTwo256, Hash as HashT, Header as HeaderT},
+	ApplyExtrinsicResult, Digest, DigestItem, StateVersion,
+};
+use sp_state_machine::TestExternalities as CoreTestExternalities;
+
+/// The genesis hash that extrinsics are signed against by default.
+pub const GENESIS_HASH: [u8; 32] = [69u8; 32];
+
+/// Test externalities blocks are constructed on.
+pub type TestExternalities<H> = CoreTestExternalities<H>;
+
+/// The executor used to call into the runtime.
+pub fn executor() -> RuntimeExecutor {
+	RuntimeExecutor::builder().build()
+}
+
+/// Call `method` of the runtime code stored in `t` with the given encoded `data`.
+pub fn executor_call(
+	t: &mut TestExternalities<BlakeTwo256>,
+	method: &str,
+	data: &[u8],
+) -> (Result<Vec<u8>>, bool) {
+	let mut t = t.ext();
+
+	let code = t.storage(sp_core::storage::well_known_keys::CODE).unwrap();
+	let heap_pages = t.storage(sp_core::storage::well_known_keys::HEAP_PAGES);
+	let runtime_code = RuntimeCode {
+		code_fetcher: &sp_core::traits::WrappedRuntimeCode(code.as_slice().into()),
+		hash: sp_crypto_hashing::blake2_256(&code).to_vec(),
+		heap_pages: heap_pages.and_then(|hp| Decode::decode(&mut &hp[..]).ok()),
+	};
+	executor().call(&mut t, &runtime_code, method, data, CallContext::Onchain)
+}
+
+/// The BABE pre-runtime digest item of a secondary plain claim of `slot`.
+pub fn babe_pre_digest(slot: Slot, authority_index: u32) -> DigestItem {
+	DigestItem::PreRuntime(
+		BABE_ENGINE_ID,
+		PreDigest::SecondaryPlain(SecondaryPlainPreDigest { slot, authority_index }).encode(),
+	)
+}
+
+/// Builder of a fake block, executed on top of the state of some [`TestExternalities`].
+///
+/// The extrinsics must all be valid, i.e. none of them can for example report
+/// `ExhaustResources`. Otherwise, [`TestBlockBuilder::build`] panics.
+///
+/// ```ignore
+/// let (block, hash) = TestBlockBuilder::new(&mut ext, 1, GENESIS_HASH.into())
+/// 	.babe_slot(slot)
+/// 	.digest_item(DigestItem::Other(vec![42]))
+/// 	.inherent(RuntimeCall::Timestamp(pallet_timestamp::Call::set { now }))
+/// 	.extrinsic(xt)
+/// 	.build();
+/// ```
+pub struct TestBlockBuilder<'a> {
+	env: &'a mut TestExternalities<BlakeTwo256>,
+	number: BlockNumber,
+	parent_hash: Hash,
+	digest: Vec<DigestItem>,
+	inherents: Vec<CheckedExtrinsic>,
+	extrinsics: Vec<CheckedExtrinsic>,
+	presigned: Vec<UncheckedExtrinsic>,
+	spec_version: u32,
+	transaction_version: u32,
+	genesis_hash: [u8; 32],
+}
+
+impl<'a> TestBlockBuilder<'a> {
+	/// Start building block `number` with parent `parent_hash` on top of the state in `env`.
+	///
+	/// The digest is empty and extrinsics are signed for the kitchensink runtime version and
+	/// [`GENESIS_HASH`] until configured otherwise.
+	pub fn new(
+		env: &'a mut TestExternalities<BlakeTwo256>,
+		number: BlockNumber,
+		parent_hash: Hash,
+	) -> Self {
+		Self {
+			env,
+			number,
+			parent_hash,
+			digest: Vec::new(),
+			inherents: Vec::new(),
+			extrinsics: Vec::new(),
+			presigned: Vec::new(),
+			spec_version: kitchensink_runtime::VERSION.spec_version,
+			transaction_version: kitchensink_runtime::VERSION.transaction_version,
+			genesis_hash: GENESIS_HASH,
+		}
+	}
+
+	/// Modify the parent state before the block is initialized.
+	pub fn with_state(self, f: impl FnOnce()) -> Self {
+		self.env.execute_with(f);
+		self
+	}
+
+	/// Append an arbitrary item to the digest of the block.
+	pub fn digest_item(mut self, item: DigestItem) -> Self {
+		self.digest.push(item);
+		self
+	}
+
+	/// Append a BABE pre-runtime digest claiming `slot` to the digest of the block.
+	///
+	/// Leaving a gap to the slot of the parent block simulates missed slots.
+	pub fn babe_slot(self, slot: Slot) -> Self {
+		self.digest_item(babe_pre_digest(slot, 42))
+	}
+
+	/// Inject an inherent. Inherents are applied, in order, before all other extrinsics.
+	pub fn inherent(mut self, call: RuntimeCall) -> Self {
+		self.inherents
+			.push(CheckedExtrinsic { format: ExtrinsicFormat::Bare, function: call });
+		self
+	}
+
+	/// Append an extrinsic. Signed extrinsics are signed when the block is built.
+	pub fn extrinsic(mut self, xt: CheckedExtrinsic) -> Self {
+		self.extrinsics.push(xt);
+		self
+	}
+
+	/// Append several extrinsics.
+	pub fn extrinsics(mut self, xts: impl IntoIterator<Item = CheckedExtrinsic>) -> Self {
+		self.extrinsics.extend(xts);
+		self
+	}
+
+	/// Append already signed extrinsics, such as those of [`crate::keyring::signed_by_nth`].
+	///
+	/// They are applied after all extrinsics appended with [`Self::extrinsic`].
+	pub fn signed_extrinsics(mut self, xts: impl IntoIterator<Item = UncheckedExtrinsic>) -> Self {
+		self.presigned.extend(xts);
+		self
+	}
+
+	/// Set the runtime versions and genesis hash the extrinsics are signed against.
+	pub fn signing_params(
+		mut self,
+		spec_version: u32,
+		transaction_version: u32,
+		genesis_hash: [u8; 32],
+	) -> Self {
+		self.spec_version = spec_version;
+		self.transaction_version = transaction_version;
+		self.genesis_hash = genesis_hash;
+		self
+	}
+
+	/// Execute the block on the state and return it encoded, together with its hash.
+	///
+	/// The state is left as it is after the block was finalized.
+	pub fn build(self) -> (Vec<u8>, Hash) {
+		let Self {
+			env,
+			number,
+			parent_hash,
+			digest,
+			inherents,
+			extrinsics,
+			presigned,
+			spec_version,
+			transaction_version,
+			genesis_hash,
+		} = self;
+
+		// sign extrinsics.
+		let extrinsics = inherents
+			.into_iter()
+			.chain(extrinsics)
+			.map(|xt| {
+				crate::keyring::sign(xt, spec_version, transaction_version, genesis_hash, None)
+			})
+			.chain(presigned)
+			.collect::<Vec<UncheckedExtrinsic>>();
+
+		// calculate the header fields that we can.
+		let extrinsics_root = BlakeTwo256::ordered_trie_root(
+			extrinsics.iter().map(Encode::encode).collect(),
+			StateVersion::V1,
+		);
+
+		let header = Header {
+			parent_hash,
+			number,
+			extrinsics_root,
+			state_root: Default::default(),
+			digest: Digest { logs: digest },
+		};
+
+		// execute the block to get the real header.
+		executor_call(env, "Core_initialize_block", &header.encode()).0.unwrap();
+
+		for extrinsic in extrinsics.iter() {
+			// Try to apply the `extrinsic`. It should be valid, in the sense that it passes
+			// all pre-inclusion checks.
+			let r = executor_call(env, "BlockBuilder_apply_extrinsic", &extrinsic.encode())
+				.0
+				.expect("application of an extrinsic failed");
+
+			match ApplyExtrinsicResult::decode(&mut &r[..])
+				.expect("apply result deserialization failed")
+			{
+				Ok(_) => {},
+				Err(e) => panic!("Applying extrinsic failed: {:?}", e),
+			}
+		}
+
+		let header = Header::decode(
+			&mut &executor_call(env, "BlockBuilder_finalize_block", &[0u8; 0]).0.unwrap()[..],
+		)
+		.unwrap();
+
+		let hash = header.hash();
+		(Block { header, extrinsics }.encode(), hash)
+	}
+}
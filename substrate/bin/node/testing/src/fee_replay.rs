@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Replay of historical blocks with an overridden runtime, recording how the fees evolve.
+//!
+//! The state of the parent of the first block is fetched with [`remote_externalities`], then the
+//! runtime code is replaced and the extrinsics of the real blocks are applied one by one, like
+//! [`crate::block_builder::TestBlockBuilder`] does. This shows how a change of e.g. the
+//! `FeeMultiplierUpdate` of the runtime would have played out on a live chain.
+//!
+//! Extrinsics are applied as they were signed, so the overriding runtime has to keep the
+//! `spec_version` and `transaction_version` of the replayed blocks for them to be valid. Extrinsics
+//! which fail to apply are skipped and counted.
+//!
+//! Fetching the blocks from a live chain requires the `fee-replay` feature.
+
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchClass, traits::Get};
+use kitchensink_runtime::{
+	constants::currency::DOLLARS, Block, CheckedExtrinsic, Header, Multiplier, Runtime, RuntimeCall,
+};
+use node_primitives::{Balance, BlockNumber};
+use pallet_transaction_payment::RuntimeDispatchInfo;
+use sp_runtime::{generic::ExtrinsicFormat, traits::BlakeTwo256, ApplyExtrinsicResult, Perbill};
+#[cfg(feature = "fee-replay")]
+use {
+	node_primitives::Hash,
+	remote_externalities::{Builder, Mode, OnlineConfig},
+	sp_runtime::generic::SignedBlock,
+	substrate_rpc_client::{ws_client, ChainApi, WsClient},
+};
+
+use crate::{
+	block_builder::{executor_call, TestExternalities, GENESIS_HASH},
+	keyring::{alice, bob, sign, tx_ext},
+};
+
+/// The fees after a replayed block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeSample {
+	/// The number of the replayed block.
+	pub number: BlockNumber,
+	/// The fee multiplier of the next block.
+	pub next_multiplier: Multiplier,
+	/// The share of the weight available to normal extrinsics that is used by the block, in
+	/// whichever of `ref_time` and `proof_size` the block is fuller.
+	pub fullness: Perbill,
+	/// The fee of a transfer in the next block, tip excluded.
+	pub transfer_fee: Balance,
+	/// The number of extrinsics of the block which failed to apply.
+	pub failed_extrinsics: u32,
+}
+
+/// Fetch up to `count` blocks ending with `at`, or the finalized head, oldest first.
+///
+/// Fetches less blocks if the genesis block is reached.
+#[cfg(feature = "fee-replay")]
+pub async fn fetch_blocks(
+	client: &WsClient,
+	at: Option<Hash>,
+	count: u32,
+) -> Result<Vec<Block>, String> {
+	let mut hash = match at {
+		Some(at) => at,
+		None => ChainApi::<(), Hash, Header, ()>::finalized_head(client)
+			.await
+			.map_err(|e| e.to_string())?,
+	};
+
+	let mut blocks = Vec::new();
+	while blocks.len() < count as usize {
+		let block = ChainApi::<(), Hash, Header, SignedBlock<Block>>::block(client, Some(hash))
+			.await
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Block {hash:?} not found"))?
+			.block;
+		if block.header.number == 0 {
+			break
+		}
+
+		hash = block.header.parent_hash;
+		blocks.push(block);
+	}
+	blocks.reverse();
+
+	Ok(blocks)
+}
+
+/// Replay up to `count` blocks ending with `at`, or the finalized head, of the chain at `uri` with
+/// the runtime `code`.
+#[cfg(feature = "fee-replay")]
+pub async fn replay(
+	uri: String,
+	code: Vec<u8>,
+	at: Option<Hash>,
+	count: u32,
+) -> Result<Vec<FeeSample>, String> {
+	let client = ws_client(&uri).await?;
+	let blocks = fetch_blocks(&client, at, count).await?;
+	let Some(first) = blocks.first() else { return Ok(Vec::new()) };
+
+	let mut ext = Builder::<Block>::new()
+		.mode(Mode::Online(OnlineConfig {
+			at: Some(first.header.parent_hash),
+			transport: uri.into(),
+			..Default::default()
+		}))
+		.build()
+		.await?;
+	ext.insert(sp_core::storage::well_known_keys::CODE.to_vec(), code);
+
+	blocks.into_iter().map(|block| replay_block(&mut ext, block)).collect()
+}
+
+/// Apply the extrinsics of `block` on top of the state of its parent in `ext`.
+pub fn replay_block(
+	ext: &mut TestExternalities<BlakeTwo256>,
+	block: Block,
+) -> Result<FeeSample, String> {
+	let Block { header, extrinsics } = block;
+	let number = header.number;
+	let call = |ext: &mut TestExternalities<BlakeTwo256>, method: &str, data: &[u8]| {
+		executor_call(ext, method, data)
+			.0
+			.map_err(|e| format!("Calling {method} at block {number} failed: {e}"))
+	};
+
+	call(
+		ext,
+		"Core_initialize_block",
+		&Header { state_root: Default::default(), ..header }.encode(),
+	)?;
+
+	let mut failed_extrinsics = 0;
+	for extrinsic in extrinsics {
+		let result = call(ext, "BlockBuilder_apply_extrinsic", &extrinsic.encode())?;
+		if !matches!(ApplyExtrinsicResult::decode(&mut &result[..]), Ok(Ok(_))) {
+			failed_extrinsics += 1;
+		}
+	}
+
+	call(ext, "BlockBuilder_finalize_block", &[])?;
+
+	let (next_multiplier, fullness) = ext.execute_with(|| {
+		let weights = <Runtime as frame_system::Config>::BlockWeights::get();
+		let max_normal = weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block);
+		let used = *frame_system::BlockWeight::<Runtime>::get().get(DispatchClass::Normal);
+
+		(
+			pallet_transaction_payment::NextFeeMultiplier::<Runtime>::get(),
+			Perbill::from_rational(used.ref_time(), max_normal.ref_time())
+				.max(Perbill::from_rational(used.proof_size(), max_normal.proof_size())),
+		)
+	});
+
+	let transfer = sign(
+		CheckedExtrinsic {
+			format: ExtrinsicFormat::Signed(alice(), tx_ext(0, 0)),
+			function: RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+				dest: bob().into(),
+				value: DOLLARS,
+			}),
+		},
+		kitchensink_runtime::VERSION.spec_version,
+		kitchensink_runtime::VERSION.transaction_version,
+		GENESIS_HASH,
+		None,
+	);
+	let len = transfer.encoded_size() as u32;
+	let info = call(ext, "TransactionPaymentApi_query_info", &(transfer, len).encode())?;
+	let info = RuntimeDispatchInfo::<Balance>::decode(&mut &info[..])
+		.map_err(|e| format!("Decoding the fee of a transfer at block {number} failed: {e}"))?;
+
+	Ok(FeeSample {
+		number,
+		next_multiplier,
+		fullness,
+		transfer_fee: info.partial_fee,
+		failed_extrinsics,
+	})
+}
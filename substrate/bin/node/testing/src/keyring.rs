@@ -18,13 +18,21 @@
 
 //! Test accounts.
 
+use crate::block_builder::TestExternalities;
 use codec::Encode;
-use kitchensink_runtime::{CheckedExtrinsic, SessionKeys, TxExtension, UncheckedExtrinsic};
+use core::ops::Range;
+use kitchensink_runtime::{
+	Balances, CheckedExtrinsic, RuntimeCall, RuntimeOrigin, SessionKeys, TxExtension,
+	UncheckedExtrinsic,
+};
 use node_primitives::{AccountId, Balance, Nonce};
-use sp_core::{crypto::get_public_from_string_or_panic, ecdsa, ed25519, sr25519};
+use sp_core::{crypto::get_public_from_string_or_panic, ecdsa, ed25519, sr25519, Pair};
 use sp_crypto_hashing::blake2_256;
 use sp_keyring::Sr25519Keyring;
-use sp_runtime::generic::{self, Era, ExtrinsicFormat, EXTRINSIC_FORMAT_VERSION};
+use sp_runtime::{
+	generic::{self, Era, ExtrinsicFormat, EXTRINSIC_FORMAT_VERSION},
+	traits::BlakeTwo256,
+};
 
 /// Alice's account id.
 pub fn alice() -> AccountId {
@@ -56,6 +64,52 @@ pub fn ferdie() -> AccountId {
 	Sr25519Keyring::Ferdie.into()
 }
 
+/// The key pair of the `i`-th generated test account.
+///
+/// Accounts are derived deterministically from the development phrase, and never collide with
+/// the well-known accounts above.
+pub fn nth_pair(i: u32) -> sr25519::Pair {
+	sr25519::Pair::from_string(&format!("//node-testing//{}", i), None)
+		.expect("static derivation path is valid; qed")
+}
+
+/// The account id of the `i`-th generated test account.
+pub fn nth(i: u32) -> AccountId {
+	nth_pair(i).public().into()
+}
+
+/// Set the free balance of the generated test accounts in `accounts` to `amount`.
+pub fn fund_nth(ext: &mut TestExternalities<BlakeTwo256>, accounts: Range<u32>, amount: Balance) {
+	ext.execute_with(|| {
+		for i in accounts {
+			Balances::force_set_balance(RuntimeOrigin::root(), nth(i).into(), amount)
+				.expect("root can set any balance; qed");
+		}
+	});
+}
+
+/// Sign one extrinsic for each generated test account in `accounts`.
+///
+/// The extrinsic of account `i` dispatches `call(i)`, and is signed with the given `nonce`.
+pub fn signed_by_nth(
+	accounts: Range<u32>,
+	nonce: Nonce,
+	call: impl Fn(u32) -> RuntimeCall,
+	spec_version: u32,
+	tx_version: u32,
+	genesis_hash: [u8; 32],
+) -> Vec<UncheckedExtrinsic> {
+	accounts
+		.map(|i| {
+			let xt = CheckedExtrinsic {
+				format: ExtrinsicFormat::Signed(nth(i), tx_ext(nonce, 0)),
+				function: call(i),
+			};
+			sign_with(|_| nth_pair(i), xt, spec_version, tx_version, genesis_hash, None)
+		})
+		.collect()
+}
+
 /// Convert keyrings into `SessionKeys`.
 ///
 /// # Panics
@@ -91,12 +145,35 @@ pub fn tx_ext(nonce: Nonce, extra_fee: Balance) -> TxExtension {
 }
 
 /// Sign given `CheckedExtrinsic`.
+///
+/// # Panics
+///
+/// Panics if the signer is not one of the well-known accounts, see [`sign_with`] otherwise.
 pub fn sign(
 	xt: CheckedExtrinsic,
 	spec_version: u32,
 	tx_version: u32,
 	genesis_hash: [u8; 32],
 	metadata_hash: Option<[u8; 32]>,
+) -> UncheckedExtrinsic {
+	sign_with(
+		|signed| Sr25519Keyring::from_account_id(signed).unwrap().pair(),
+		xt,
+		spec_version,
+		tx_version,
+		genesis_hash,
+		metadata_hash,
+	)
+}
+
+/// Sign given `CheckedExtrinsic` with the key pair that `key` returns for its signer.
+pub fn sign_with(
+	key: impl FnOnce(&AccountId) -> sr25519::Pair,
+	xt: CheckedExtrinsic,
+	spec_version: u32,
+	tx_version: u32,
+	genesis_hash: [u8; 32],
+	metadata_hash: Option<[u8; 32]>,
 ) -> UncheckedExtrinsic {
 	match xt.format {
 		ExtrinsicFormat::Signed(signed, tx_ext) => {
@@ -109,7 +186,7 @@ pub fn sign(
 				genesis_hash,
 				metadata_hash,
 			);
-			let key = Sr25519Keyring::from_account_id(&signed).unwrap();
+			let key = key(&signed);
 			let signature =
 				payload
 					.using_encoded(|b| {
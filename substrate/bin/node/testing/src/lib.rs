@@ -21,6 +21,8 @@
 #![warn(missing_docs)]
 
 pub mod bench;
+pub mod block_builder;
 pub mod client;
+pub mod fee_replay;
 pub mod genesis;
 pub mod keyring;
@@ -342,6 +342,12 @@ pub struct AllocationStats {
 	/// Currently the bumper's only ever incremented, so this is simultaneously
 	/// the current value as well as the peak value.
 	pub address_space_used: u32,
+
+	/// The peak size (in bytes) of the linear memory the allocator operated on.
+	///
+	/// This includes the memory below the heap base and the memory reserved when
+	/// the allocator had to grow the linear memory.
+	pub memory_size_peak: u64,
 }
 
 /// Convert the given `size` in bytes into the number of pages.
@@ -449,6 +455,7 @@ impl FreeingBumpHeapAllocator {
 		self.stats.bytes_allocated_peak =
 			max(self.stats.bytes_allocated_peak, self.stats.bytes_allocated);
 		self.stats.address_space_used = self.bumper - self.original_heap_base;
+		self.stats.memory_size_peak = max(self.stats.memory_size_peak, mem.size());
 
 		log::trace!(target: LOG_TARGET, "after allocation: {:?}", self.stats);
 
@@ -1107,6 +1114,19 @@ mod tests {
 		assert_eq!(3, mem.pages());
 	}
 
+	#[test]
+	fn should_track_peak_memory_size() {
+		let mut mem = MemoryInstance::with_pages(1);
+		let mut heap = FreeingBumpHeapAllocator::new(0);
+
+		let ptr = heap.allocate(&mut mem, 8).unwrap();
+		assert_eq!(heap.stats().memory_size_peak, PAGE_SIZE as u64);
+
+		heap.deallocate(&mut mem, ptr).unwrap();
+		heap.allocate(&mut mem, PAGE_SIZE * 2).unwrap();
+		assert_eq!(heap.stats().memory_size_peak, 3 * PAGE_SIZE as u64);
+	}
+
 	#[test]
 	fn modifying_the_header_leads_to_an_error() {
 		let mut mem = MemoryInstance::with_pages(1);
@@ -74,6 +74,24 @@ impl Service {
 		rx.await.ok().flatten()
 	}
 
+	/// Like [`Self::get_addresses_by_authority_id`], but returns the addresses in the order of
+	/// the priorities the authority published them with, preferred addresses first.
+	///
+	/// Addresses should be dialed in this order.
+	pub async fn get_prioritized_addresses_by_authority_id(
+		&mut self,
+		authority: AuthorityId,
+	) -> Option<Vec<Multiaddr>> {
+		let (tx, rx) = oneshot::channel();
+
+		self.to_worker
+			.send(ServicetoWorkerMsg::GetPrioritizedAddressesByAuthorityId(authority, tx))
+			.await
+			.ok()?;
+
+		rx.await.ok().flatten()
+	}
+
 	/// Get the [`AuthorityId`] for the given [`PeerId`] from the local address
 	/// cache.
 	///
@@ -363,6 +363,13 @@ where
 					self.addr_cache.get_addresses_by_authority_id(&authority).map(Clone::clone),
 				);
 			},
+			ServicetoWorkerMsg::GetPrioritizedAddressesByAuthorityId(authority, sender) => {
+				let _ = sender.send(
+					self.addr_cache
+						.get_prioritized_addresses_by_authority_id(&authority)
+						.map(|addresses| addresses.to_vec()),
+				);
+			},
 			ServicetoWorkerMsg::GetAuthorityIdsByPeerId(peer_id, sender) => {
 				let _ = sender
 					.send(self.addr_cache.get_authority_ids_by_peer_id(&peer_id).map(Clone::clone));
@@ -370,7 +377,11 @@ where
 		}
 	}
 
-	fn addresses_to_publish(&mut self) -> impl Iterator<Item = Multiaddr> {
+	/// The addresses to publish together with their priority, preferred addresses first.
+	///
+	/// Public addresses are preferred in the order they were configured, all other addresses
+	/// share the lowest priority.
+	fn addresses_to_publish(&mut self) -> impl Iterator<Item = (Multiaddr, u32)> {
 		let local_peer_id = self.network.local_peer_id();
 		let publish_non_global_ips = self.publish_non_global_ips;
 
@@ -424,15 +435,17 @@ where
 		);
 
 		let mut seen_addresses = HashSet::new();
+		let fallback_priority = self.public_addresses.len() as u32;
 
 		let addresses = self
 			.public_addresses
 			.clone()
 			.into_iter()
-			.chain(global_listen_addresses)
-			.chain(external_addresses)
+			.zip(0..)
+			.chain(global_listen_addresses.map(|address| (address, fallback_priority)))
+			.chain(external_addresses.map(|address| (address, fallback_priority)))
 			// Deduplicate addresses.
-			.filter(|address| seen_addresses.insert(address.clone()))
+			.filter(|(address, _)| seen_addresses.insert(address.clone()))
 			.take(MAX_ADDRESSES_TO_PUBLISH)
 			.collect::<Vec<_>>();
 
@@ -459,9 +472,9 @@ where
 		}
 
 		// The address must include the local peer id.
-		addresses
-			.into_iter()
-			.map(move |a| a.with(multiaddr::Protocol::P2p(*local_peer_id.as_ref())))
+		addresses.into_iter().map(move |(address, priority)| {
+			(address.with(multiaddr::Protocol::P2p(*local_peer_id.as_ref())), priority)
+		})
 	}
 
 	/// Publish own public addresses.
@@ -475,7 +488,8 @@ where
 		}
 		.clone();
 
-		let addresses = serialize_addresses(self.addresses_to_publish());
+		let (addresses, priorities): (Vec<_>, Vec<_>) = self.addresses_to_publish().unzip();
+		let addresses = serialize_addresses(addresses.into_iter());
 		if addresses.is_empty() {
 			trace!(
 				target: LOG_TARGET,
@@ -515,7 +529,8 @@ where
 				.set(addresses.len().try_into().unwrap_or(std::u64::MAX));
 		}
 
-		let serialized_record = serialize_authority_record(addresses, Some(build_creation_time()))?;
+		let serialized_record =
+			serialize_authority_record(addresses, priorities, Some(build_creation_time()))?;
 		let peer_signature = sign_record_with_peer_id(&serialized_record, &self.network)?;
 
 		let keys_vec = keys.iter().cloned().collect::<Vec<_>>();
@@ -834,13 +849,19 @@ where
 			})
 			.unwrap_or_default(); // 0 is a sane default for records that do not have creation time present.
 
-		let addresses: Vec<Multiaddr> = authority_record
+		let mut addresses: Vec<(Multiaddr, u32)> = authority_record
 			.addresses
 			.into_iter()
-			.map(|a| a.try_into())
+			.zip(authority_record.priorities.into_iter().chain(std::iter::repeat(u32::MAX)))
+			.map(|(a, priority)| a.try_into().map(|a| (a, priority)))
 			.collect::<std::result::Result<_, _>>()
 			.map_err(Error::ParsingMultiaddress)?;
 
+		// Preferred addresses first. The sort is stable, so addresses of the same priority stay in
+		// the order they were published in.
+		addresses.sort_by_key(|(_, priority)| *priority);
+		let addresses = addresses.into_iter().map(|(a, _)| a);
+
 		let get_peer_id = |a: &Multiaddr| match a.iter().last() {
 			Some(multiaddr::Protocol::P2p(key)) => PeerId::from_multihash(key).ok(),
 			_ => None,
@@ -848,7 +869,6 @@ where
 
 		// Ignore [`Multiaddr`]s without [`PeerId`] or with own addresses.
 		let addresses: Vec<Multiaddr> = addresses
-			.into_iter()
 			.filter(|a| get_peer_id(&a).filter(|p| *p != local_peer_id).is_some())
 			.collect();
 
@@ -1069,11 +1089,12 @@ fn build_creation_time() -> schema::TimestampInfo {
 
 fn serialize_authority_record(
 	addresses: Vec<Vec<u8>>,
+	priorities: Vec<u32>,
 	creation_time: Option<schema::TimestampInfo>,
 ) -> Result<Vec<u8>> {
 	let mut serialized_record = vec![];
 
-	schema::AuthorityRecord { addresses, creation_time }
+	schema::AuthorityRecord { addresses, creation_time, priorities }
 		.encode(&mut serialized_record)
 		.map_err(Error::EncodingProto)?;
 	Ok(serialized_record)
@@ -33,6 +33,9 @@ pub(super) struct AddrCache {
 	/// it's not expected that a single `AuthorityId` can have multiple `PeerId`s.
 	authority_id_to_addresses: HashMap<AuthorityId, HashSet<Multiaddr>>,
 	peer_id_to_authority_ids: HashMap<PeerId, HashSet<AuthorityId>>,
+	/// The addresses of `authority_id_to_addresses` in the order of their priority, preferred
+	/// addresses first.
+	authority_id_to_prioritized_addresses: HashMap<AuthorityId, Vec<Multiaddr>>,
 }
 
 impl AddrCache {
@@ -40,13 +43,19 @@ impl AddrCache {
 		AddrCache {
 			authority_id_to_addresses: HashMap::new(),
 			peer_id_to_authority_ids: HashMap::new(),
+			authority_id_to_prioritized_addresses: HashMap::new(),
 		}
 	}
 
 	/// Inserts the given [`AuthorityId`] and [`Vec<Multiaddr>`] pair for future lookups by
 	/// [`AuthorityId`] or [`PeerId`].
+	///
+	/// The addresses are expected in the order of their priority, preferred addresses first.
 	pub fn insert(&mut self, authority_id: AuthorityId, addresses: Vec<Multiaddr>) {
-		let addresses = addresses.into_iter().collect::<HashSet<_>>();
+		let mut prioritized_addresses = addresses;
+		let mut seen_addresses = HashSet::new();
+		prioritized_addresses.retain(|address| seen_addresses.insert(address.clone()));
+		let addresses = seen_addresses;
 		let peer_ids = addresses_to_peer_ids(&addresses);
 
 		if peer_ids.is_empty() {
@@ -72,6 +81,8 @@ impl AddrCache {
 			"Found addresses for authority {authority_id:?}: {addresses:?}",
 		);
 
+		self.authority_id_to_prioritized_addresses
+			.insert(authority_id.clone(), prioritized_addresses);
 		let old_addresses = self.authority_id_to_addresses.insert(authority_id.clone(), addresses);
 		let old_peer_ids = addresses_to_peer_ids(&old_addresses.unwrap_or_default());
 
@@ -120,6 +131,17 @@ impl AddrCache {
 		self.authority_id_to_addresses.get(authority_id)
 	}
 
+	/// Returns the addresses for the given [`AuthorityId`] in the order of their priority,
+	/// preferred addresses first.
+	pub fn get_prioritized_addresses_by_authority_id(
+		&self,
+		authority_id: &AuthorityId,
+	) -> Option<&[Multiaddr]> {
+		self.authority_id_to_prioritized_addresses
+			.get(authority_id)
+			.map(|a| a.as_slice())
+	}
+
 	/// Returns the [`AuthorityId`]s for the given [`PeerId`].
 	///
 	/// As the authority id can change between sessions, one [`PeerId`] can be mapped to
@@ -142,6 +164,7 @@ impl AddrCache {
 
 		for authority_id_to_remove in authority_ids_to_remove {
 			// Remove other entries from `self.authority_id_to_addresses`.
+			self.authority_id_to_prioritized_addresses.remove(&authority_id_to_remove);
 			let addresses = if let Some(addresses) =
 				self.authority_id_to_addresses.remove(&authority_id_to_remove)
 			{
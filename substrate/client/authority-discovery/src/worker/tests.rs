@@ -289,9 +289,30 @@ fn build_dht_event<Signer: NetworkSigner>(
 	network: Option<&Signer>,
 	creation_time: Option<schema::TimestampInfo>,
 ) -> Vec<(KademliaKey, Vec<u8>)> {
-	let serialized_record =
-		serialize_authority_record(serialize_addresses(addresses.into_iter()), creation_time)
-			.unwrap();
+	build_dht_event_with_priorities(
+		addresses,
+		Vec::new(),
+		public_key,
+		key_store,
+		network,
+		creation_time,
+	)
+}
+
+fn build_dht_event_with_priorities<Signer: NetworkSigner>(
+	addresses: Vec<Multiaddr>,
+	priorities: Vec<u32>,
+	public_key: AuthorityId,
+	key_store: &MemoryKeystore,
+	network: Option<&Signer>,
+	creation_time: Option<schema::TimestampInfo>,
+) -> Vec<(KademliaKey, Vec<u8>)> {
+	let serialized_record = serialize_authority_record(
+		serialize_addresses(addresses.into_iter()),
+		priorities,
+		creation_time,
+	)
+	.unwrap();
 
 	let peer_signature = network.map(|n| sign_record_with_peer_id(&serialized_record, n).unwrap());
 	let kv_pairs = sign_record_with_authority_ids(
@@ -677,6 +698,70 @@ impl DhtValueFoundTester {
 	}
 }
 
+#[test]
+fn addresses_to_publish_prefers_public_addresses() {
+	let (_dht_event_tx, dht_event_rx) = channel(1000);
+	let network: Arc<TestNetwork> = Arc::new(Default::default());
+	let public_addresses: Vec<Multiaddr> = vec![
+		"/ip6/2001:db8::1/tcp/30333".parse().unwrap(),
+		"/ip6/2001:db8::2/tcp/30333".parse().unwrap(),
+	];
+
+	let (_to_worker, from_service) = mpsc::channel(0);
+	let mut worker = Worker::new(
+		from_service,
+		Arc::new(TestApi { authorities: vec![] }),
+		network.clone(),
+		Box::pin(dht_event_rx),
+		Role::PublishAndDiscover(MemoryKeystore::new().into()),
+		None,
+		WorkerConfig { public_addresses: public_addresses.clone(), ..Default::default() },
+	);
+
+	let addresses = worker.addresses_to_publish().collect::<Vec<_>>();
+	assert_eq!(addresses.len(), public_addresses.len() + network.external_addresses.len());
+	for (i, public_address) in public_addresses.into_iter().enumerate() {
+		assert!(addresses[i].0.to_string().starts_with(&public_address.to_string()));
+		assert_eq!(addresses[i].1, i as u32);
+	}
+	assert!(addresses[2..].iter().all(|(_, priority)| *priority == 2));
+}
+
+#[test]
+fn cache_addresses_in_order_of_priority() {
+	let mut tester = DhtValueFoundTester::new();
+	let addresses: Vec<_> = (1..5).map(|i| tester.multiaddr_with_peer_id(i)).collect();
+	// The last address has no priority and is tried last.
+	let kv_pairs = build_dht_event_with_priorities::<TestNetwork>(
+		addresses.clone(),
+		vec![2, 0, 1],
+		tester.remote_authority_public.into(),
+		&tester.remote_key_store,
+		None,
+		Some(build_creation_time()),
+	);
+
+	let cached_remote_addresses = tester.process_value_found(false, kv_pairs).0;
+	assert_eq!(Some(addresses.iter().cloned().collect()), cached_remote_addresses);
+
+	let prioritized_addresses = tester
+		.local_worker
+		.as_ref()
+		.unwrap()
+		.addr_cache
+		.get_prioritized_addresses_by_authority_id(&tester.remote_authority_public.into())
+		.map(|addresses| addresses.to_vec());
+	assert_eq!(
+		Some(vec![
+			addresses[1].clone(),
+			addresses[2].clone(),
+			addresses[0].clone(),
+			addresses[3].clone()
+		]),
+		prioritized_addresses,
+	);
+}
+
 #[test]
 fn limit_number_of_addresses_added_to_cache_per_authority() {
 	let mut tester = DhtValueFoundTester::new();
@@ -1044,7 +1129,7 @@ fn addresses_to_publish_adds_p2p() {
 
 	assert!(
 		matches!(
-			worker.addresses_to_publish().next().unwrap().pop().unwrap(),
+			worker.addresses_to_publish().next().unwrap().0.pop().unwrap(),
 			multiaddr::Protocol::P2p(_)
 		),
 		"Expect `addresses_to_publish` to append `p2p` protocol component.",
@@ -1082,7 +1167,7 @@ fn addresses_to_publish_respects_existing_p2p_protocol() {
 
 	assert_eq!(
 		network.external_addresses,
-		worker.addresses_to_publish().collect::<Vec<_>>(),
+		worker.addresses_to_publish().map(|(address, _)| address).collect::<Vec<_>>(),
 		"Expected Multiaddr from `TestNetwork` to not be altered.",
 	);
 }
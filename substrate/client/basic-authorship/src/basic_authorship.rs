@@ -37,6 +37,7 @@ use sp_core::traits::SpawnNamed;
 use sp_inherents::InherentData;
 use sp_runtime::{
 	traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
 	Digest, ExtrinsicInclusionMode, Percent, SaturatedConversion,
 };
 use std::{marker::PhantomData, pin::Pin, sync::Arc, time};
@@ -55,10 +56,90 @@ pub const DEFAULT_BLOCK_SIZE_LIMIT: usize = 4 * 1024 * 1024 + 512;
 
 const DEFAULT_SOFT_DEADLINE_PERCENT: Percent = Percent::from_percent(50);
 
+const DEFAULT_INCLUSION_RESERVE_PERCENT: Percent = Percent::from_percent(0);
+
 const LOG_TARGET: &'static str = "basic-authorship";
 
+/// Hook invoked by the [`Proposer`] right before the block is built.
+///
+/// It is called once the proposer stopped pulling transactions from the pool, i.e. within the
+/// time reserved with [`ProposerFactory::set_inclusion_reserve`], and lets the block author push
+/// final extrinsics of their own, e.g. priority transactions.
+pub trait InclusionCallback<Block: BlockT>: Send + Sync {
+	/// Push the final extrinsics of the block through `inclusion`.
+	fn on_final_inclusion(&self, inclusion: &mut FinalInclusion<'_, Block>);
+}
+
+impl<Block: BlockT> InclusionCallback<Block> for () {
+	fn on_final_inclusion(&self, _: &mut FinalInclusion<'_, Block>) {}
+}
+
+/// The block under construction, as seen by an [`InclusionCallback`].
+///
+/// The node has no knowledge of the weight of extrinsics. When the block runs out of weight, the
+/// runtime rejects further extrinsics and [`FinalInclusion::push`] reports an error for which
+/// `exhausted_resources` holds, the same as when the block size limit would be exceeded.
+pub struct FinalInclusion<'a, Block: BlockT> {
+	block_builder: &'a mut dyn BuildBlock<Block>,
+	now: &'a (dyn Fn() -> time::Instant + Send + Sync),
+	deadline: time::Instant,
+	block_size_limit: usize,
+	include_proof_in_block_size_estimation: bool,
+}
+
+impl<'a, Block: BlockT> FinalInclusion<'a, Block> {
+	/// The time left until the block must be built.
+	pub fn remaining_time(&self) -> time::Duration {
+		self.deadline.saturating_duration_since((self.now)())
+	}
+
+	/// The number of bytes that can still be added to the block.
+	pub fn remaining_block_size(&self) -> usize {
+		self.block_size_limit.saturating_sub(
+			self.block_builder
+				.estimate_block_size(self.include_proof_in_block_size_estimation),
+		)
+	}
+
+	/// Push `extrinsic` to the block.
+	///
+	/// Fails without touching the block if the extrinsic doesn't fit into the remaining block
+	/// size.
+	pub fn push(&mut self, extrinsic: Block::Extrinsic) -> Result<(), sp_blockchain::Error> {
+		if extrinsic.encoded_size() > self.remaining_block_size() {
+			return Err(ApplyExtrinsicFailed(Validity(TransactionValidityError::Invalid(
+				InvalidTransaction::ExhaustsResources,
+			))))
+		}
+
+		self.block_builder.push(extrinsic)
+	}
+}
+
+/// The parts of a block builder used by [`FinalInclusion`].
+trait BuildBlock<Block: BlockT> {
+	fn push(&mut self, extrinsic: Block::Extrinsic) -> Result<(), sp_blockchain::Error>;
+
+	fn estimate_block_size(&self, include_proof: bool) -> usize;
+}
+
+impl<'a, Block, C> BuildBlock<Block> for sc_block_builder::BlockBuilder<'a, Block, C>
+where
+	Block: BlockT,
+	C: CallApiAt<Block> + ProvideRuntimeApi<Block> + 'a,
+	C::Api: ApiExt<Block> + BlockBuilderApi<Block>,
+{
+	fn push(&mut self, extrinsic: Block::Extrinsic) -> Result<(), sp_blockchain::Error> {
+		sc_block_builder::BlockBuilder::push(self, extrinsic)
+	}
+
+	fn estimate_block_size(&self, include_proof: bool) -> usize {
+		sc_block_builder::BlockBuilder::estimate_block_size(self, include_proof)
+	}
+}
+
 /// [`Proposer`] factory.
-pub struct ProposerFactory<A, C, PR> {
+pub struct ProposerFactory<A, C, PR, IC = ()> {
 	spawn_handle: Box<dyn SpawnNamed>,
 	/// The client instance.
 	client: Arc<C>,
@@ -79,6 +160,10 @@ pub struct ProposerFactory<A, C, PR> {
 	/// we switch to a fixed-amount mode, in which after we see `MAX_SKIPPED_TRANSACTIONS`
 	/// transactions which exhaust resources, we will conclude that the block is full.
 	soft_deadline_percent: Percent,
+	/// Percentage of the proposing time reserved for the [`InclusionCallback`].
+	inclusion_reserve_percent: Percent,
+	/// Invoked right before the block is built.
+	inclusion_callback: Option<Arc<IC>>,
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
@@ -86,7 +171,7 @@ pub struct ProposerFactory<A, C, PR> {
 	_phantom: PhantomData<PR>,
 }
 
-impl<A, C, PR> Clone for ProposerFactory<A, C, PR> {
+impl<A, C, PR, IC> Clone for ProposerFactory<A, C, PR, IC> {
 	fn clone(&self) -> Self {
 		Self {
 			spawn_handle: self.spawn_handle.clone(),
@@ -95,6 +180,8 @@ impl<A, C, PR> Clone for ProposerFactory<A, C, PR> {
 			metrics: self.metrics.clone(),
 			default_block_size_limit: self.default_block_size_limit,
 			soft_deadline_percent: self.soft_deadline_percent,
+			inclusion_reserve_percent: self.inclusion_reserve_percent,
+			inclusion_callback: self.inclusion_callback.clone(),
 			telemetry: self.telemetry.clone(),
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
 			_phantom: self._phantom,
@@ -120,6 +207,8 @@ impl<A, C> ProposerFactory<A, C, DisableProofRecording> {
 			metrics: PrometheusMetrics::new(prometheus),
 			default_block_size_limit: DEFAULT_BLOCK_SIZE_LIMIT,
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
+			inclusion_reserve_percent: DEFAULT_INCLUSION_RESERVE_PERCENT,
+			inclusion_callback: None,
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
@@ -149,6 +238,8 @@ impl<A, C> ProposerFactory<A, C, EnableProofRecording> {
 			metrics: PrometheusMetrics::new(prometheus),
 			default_block_size_limit: DEFAULT_BLOCK_SIZE_LIMIT,
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
+			inclusion_reserve_percent: DEFAULT_INCLUSION_RESERVE_PERCENT,
+			inclusion_callback: None,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
 			_phantom: PhantomData,
@@ -161,7 +252,7 @@ impl<A, C> ProposerFactory<A, C, EnableProofRecording> {
 	}
 }
 
-impl<A, C, PR> ProposerFactory<A, C, PR> {
+impl<A, C, PR, IC> ProposerFactory<A, C, PR, IC> {
 	/// Set the default block size limit in bytes.
 	///
 	/// The default value for the block size limit is:
@@ -188,14 +279,40 @@ impl<A, C, PR> ProposerFactory<A, C, PR> {
 	pub fn set_soft_deadline(&mut self, percent: Percent) {
 		self.soft_deadline_percent = percent;
 	}
+
+	/// Set the percentage of the proposing time reserved for the [`InclusionCallback`].
+	///
+	/// Transactions from the pool are only pushed until the reserved time at the end of block
+	/// building starts. The default is to reserve no time.
+	pub fn set_inclusion_reserve(&mut self, percent: Percent) {
+		self.inclusion_reserve_percent = percent;
+	}
+
+	/// Invoke `callback` right before each block is built.
+	pub fn with_inclusion_callback<T>(self, callback: T) -> ProposerFactory<A, C, PR, T> {
+		ProposerFactory {
+			spawn_handle: self.spawn_handle,
+			client: self.client,
+			transaction_pool: self.transaction_pool,
+			metrics: self.metrics,
+			default_block_size_limit: self.default_block_size_limit,
+			soft_deadline_percent: self.soft_deadline_percent,
+			inclusion_reserve_percent: self.inclusion_reserve_percent,
+			inclusion_callback: Some(Arc::new(callback)),
+			telemetry: self.telemetry,
+			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			_phantom: PhantomData,
+		}
+	}
 }
 
-impl<Block, C, A, PR> ProposerFactory<A, C, PR>
+impl<Block, C, A, PR, IC> ProposerFactory<A, C, PR, IC>
 where
 	A: TransactionPool<Block = Block> + 'static,
 	Block: BlockT,
 	C: HeaderBackend<Block> + ProvideRuntimeApi<Block> + Send + Sync + 'static,
 	C::Api: ApiExt<Block> + BlockBuilderApi<Block>,
+	IC: InclusionCallback<Block> + 'static,
 {
 	fn init_with_now(
 		&mut self,
@@ -220,6 +337,11 @@ where
 			metrics: self.metrics.clone(),
 			default_block_size_limit: self.default_block_size_limit,
 			soft_deadline_percent: self.soft_deadline_percent,
+			inclusion_reserve_percent: self.inclusion_reserve_percent,
+			inclusion_callback: self
+				.inclusion_callback
+				.clone()
+				.map(|callback| callback as Arc<dyn InclusionCallback<Block>>),
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
@@ -229,13 +351,14 @@ where
 	}
 }
 
-impl<A, Block, C, PR> sp_consensus::Environment<Block> for ProposerFactory<A, C, PR>
+impl<A, Block, C, PR, IC> sp_consensus::Environment<Block> for ProposerFactory<A, C, PR, IC>
 where
 	A: TransactionPool<Block = Block> + 'static,
 	Block: BlockT,
 	C: HeaderBackend<Block> + ProvideRuntimeApi<Block> + CallApiAt<Block> + Send + Sync + 'static,
 	C::Api: ApiExt<Block> + BlockBuilderApi<Block>,
 	PR: ProofRecording,
+	IC: InclusionCallback<Block> + 'static,
 {
 	type CreateProposer = future::Ready<Result<Self::Proposer, Self::Error>>;
 	type Proposer = Proposer<Block, C, A, PR>;
@@ -258,6 +381,8 @@ pub struct Proposer<Block: BlockT, C, A: TransactionPool, PR> {
 	default_block_size_limit: usize,
 	include_proof_in_block_size_estimation: bool,
 	soft_deadline_percent: Percent,
+	inclusion_reserve_percent: Percent,
+	inclusion_callback: Option<Arc<dyn InclusionCallback<Block>>>,
 	telemetry: Option<TelemetryHandle>,
 	_phantom: PhantomData<PR>,
 }
@@ -340,8 +465,12 @@ where
 
 		let mode = block_builder.extrinsic_inclusion_mode();
 		let end_reason = match mode {
-			ExtrinsicInclusionMode::AllExtrinsics =>
-				self.apply_extrinsics(&mut block_builder, deadline, block_size_limit).await?,
+			ExtrinsicInclusionMode::AllExtrinsics => {
+				let end_reason =
+					self.apply_extrinsics(&mut block_builder, deadline, block_size_limit).await?;
+				self.apply_final_inclusion(&mut block_builder, deadline, block_size_limit);
+				end_reason
+			},
 			ExtrinsicInclusionMode::OnlyInherents => EndProposingReason::TransactionForbidden,
 		};
 		let (block, storage_changes, proof) = block_builder.build()?.into_inner();
@@ -398,6 +527,25 @@ where
 		Ok(())
 	}
 
+	/// Let the [`InclusionCallback`], if any, push the final extrinsics of the block.
+	fn apply_final_inclusion(
+		&self,
+		block_builder: &mut sc_block_builder::BlockBuilder<'_, Block, C>,
+		deadline: time::Instant,
+		block_size_limit: Option<usize>,
+	) {
+		let Some(inclusion_callback) = &self.inclusion_callback else { return };
+
+		let mut inclusion = FinalInclusion {
+			block_builder,
+			now: &*self.now,
+			deadline,
+			block_size_limit: block_size_limit.unwrap_or(self.default_block_size_limit),
+			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+		};
+		inclusion_callback.on_final_inclusion(&mut inclusion);
+	}
+
 	/// Apply as many extrinsics as possible to the block.
 	async fn apply_extrinsics(
 		&self,
@@ -406,8 +554,13 @@ where
 		block_size_limit: Option<usize>,
 	) -> Result<EndProposingReason, sp_blockchain::Error> {
 		// proceed with transactions
-		// We calculate soft deadline used only in case we start skipping transactions.
+		// The end of the proposing time is reserved for the inclusion callback.
 		let now = (self.now)();
+		let reserved = self
+			.inclusion_reserve_percent
+			.mul_floor(deadline.saturating_duration_since(now).as_micros().saturated_into::<u64>());
+		let deadline = deadline - time::Duration::from_micros(reserved);
+		// We calculate soft deadline used only in case we start skipping transactions.
 		let left = deadline.saturating_duration_since(now);
 		let left_micros: u64 = left.as_micros().saturated_into();
 		let soft_deadline =
@@ -734,6 +887,59 @@ mod tests {
 			.unwrap();
 	}
 
+	#[test]
+	fn inclusion_callback_pushes_final_extrinsics() {
+		struct PushExtrinsic(Arc<Mutex<Option<time::Duration>>>);
+
+		impl InclusionCallback<TestBlock> for PushExtrinsic {
+			fn on_final_inclusion(&self, inclusion: &mut FinalInclusion<'_, TestBlock>) {
+				*self.0.lock() = Some(inclusion.remaining_time());
+				inclusion.push(extrinsic(0)).unwrap();
+
+				let too_large = inclusion.remaining_block_size() + 1;
+				let result = inclusion
+					.push(ExtrinsicBuilder::new_include_data(vec![42; too_large]).nonce(1).build());
+				assert!(matches!(
+					result,
+					Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources()
+				));
+			}
+		}
+
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = Arc::from(BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		));
+
+		let remaining_time = Arc::new(Mutex::new(None));
+		let mut proposer_factory =
+			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None)
+				.with_inclusion_callback(PushExtrinsic(remaining_time.clone()));
+		proposer_factory.set_inclusion_reserve(Percent::from_percent(20));
+
+		let now = time::Instant::now();
+		let proposer = proposer_factory.init_with_now(
+			&client.expect_header(client.info().genesis_hash).unwrap(),
+			Box::new(move || now),
+		);
+
+		let deadline = time::Duration::from_secs(10);
+		let block =
+			block_on(proposer.propose(Default::default(), Default::default(), deadline, None))
+				.map(|r| r.block)
+				.unwrap();
+
+		// the callback runs within the whole proposing time, minus the 10% kept for sealing.
+		assert_eq!(*remaining_time.lock(), Some(time::Duration::from_secs(9)));
+		assert_eq!(block.extrinsics().len(), 1);
+		assert_eq!(block.extrinsics()[0], extrinsic(0));
+	}
+
 	#[test]
 	fn proposed_storage_changes_should_match_execute_block_storage_changes() {
 		let (client, backend) = TestClientBuilder::new().build_with_backend();
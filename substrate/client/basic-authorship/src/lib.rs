@@ -72,4 +72,6 @@
 
 mod basic_authorship;
 
-pub use crate::basic_authorship::{Proposer, ProposerFactory, DEFAULT_BLOCK_SIZE_LIMIT};
+pub use crate::basic_authorship::{
+	FinalInclusion, InclusionCallback, Proposer, ProposerFactory, DEFAULT_BLOCK_SIZE_LIMIT,
+};
@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	commands::export_state_cmd::{parse_prefix, storage_prefixes},
 	error,
 	params::{NodeKeyParams, SharedParams},
 	CliConfiguration,
@@ -26,9 +27,10 @@ use log::info;
 use sc_network::config::build_multiaddr;
 use sc_service::{
 	config::{MultiaddrWithPeerId, NetworkConfiguration},
-	ChainSpec,
+	ChainSpec, GenericChainSpec, NoExtension,
 };
-use std::io::Write;
+use sp_runtime::BuildStorage;
+use std::{io::Write, path::PathBuf};
 
 /// The `build-spec` command used to build a specification.
 #[derive(Debug, Clone, Parser)]
@@ -43,6 +45,30 @@ pub struct BuildSpecCmd {
 	#[arg(long)]
 	pub disable_default_bootnode: bool,
 
+	/// Merge the genesis state of the given chain spec, e.g. a filtered `export-state`, into the
+	/// genesis of the built specification.
+	///
+	/// The keys of the given chain spec override those of the built specification and its child
+	/// tries replace those of the built specification. This implies `--raw`.
+	#[arg(long, value_name = "PATH")]
+	pub merge_state: Option<PathBuf>,
+
+	/// A hex encoded prefix the state given to `--merge-state` was exported for.
+	///
+	/// The keys of the built specification starting with this prefix are removed before merging,
+	/// so that keys removed from the exported chain are not kept. Can be passed multiple times,
+	/// together with `--merge-pallet`, and should match the `--prefix` given to `export-state`.
+	#[arg(long, value_name = "HEX", value_parser = parse_prefix, requires = "merge_state")]
+	pub merge_prefix: Vec<Vec<u8>>,
+
+	/// The name of a pallet the state given to `--merge-state` was exported for.
+	///
+	/// The storage of this pallet is removed from the built specification before merging. Can be
+	/// passed multiple times, together with `--merge-prefix`, and should match the `--pallet`
+	/// given to `export-state`.
+	#[arg(long, value_name = "NAME", requires = "merge_state")]
+	pub merge_pallet: Vec<String>,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -60,7 +86,7 @@ impl BuildSpecCmd {
 		network_config: NetworkConfiguration,
 	) -> error::Result<()> {
 		info!("Building chain spec");
-		let raw_output = self.raw;
+		let raw_output = self.raw || self.merge_state.is_some();
 
 		if spec.boot_nodes().is_empty() && !self.disable_default_bootnode {
 			let keys = network_config.node_key.into_keypair()?;
@@ -72,6 +98,17 @@ impl BuildSpecCmd {
 			spec.add_boot_node(addr)
 		}
 
+		if let Some(path) = &self.merge_state {
+			info!("Merging the genesis state of {}", path.display());
+			let exported = GenericChainSpec::<NoExtension>::from_json_file(path.clone())?
+				.as_storage_builder()
+				.build_storage()?;
+			let mut storage = spec.as_storage_builder().build_storage()?;
+			let prefixes = storage_prefixes(&self.merge_pallet, &self.merge_prefix);
+			sc_service::chain_ops::merge_raw_state(&mut storage, exported, &prefixes);
+			spec.set_storage(storage);
+		}
+
 		let json = sc_service::chain_ops::build_spec(&*spec, raw_output)?;
 		if std::io::stdout().write_all(json.as_bytes()).is_err() {
 			let _ = std::io::stderr().write_all(b"Error writing to stdout\n");
@@ -35,6 +35,20 @@ pub struct ExportStateCmd {
 	#[arg(value_name = "HASH or NUMBER")]
 	pub input: Option<BlockNumberOrHash>,
 
+	/// Only export the storage keys starting with this hex encoded prefix.
+	///
+	/// Can be passed multiple times, together with `--pallet`. The state is exported entirely if
+	/// neither is given.
+	#[arg(long, value_name = "HEX", value_parser = parse_prefix)]
+	pub prefix: Vec<Vec<u8>>,
+
+	/// Only export the storage of the pallet with this name, e.g. `Balances`.
+	///
+	/// Can be passed multiple times, together with `--prefix`. This only covers the storage
+	/// items of the pallet, child tries owned by the pallet must be selected with `--prefix`.
+	#[arg(long, value_name = "NAME")]
+	pub pallet: Vec<String>,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -68,7 +82,12 @@ impl ExportStateCmd {
 			Some(id) => client.expect_block_hash_from_id(&id)?,
 			None => client.usage_info().chain.best_hash,
 		};
-		let raw_state = sc_service::chain_ops::export_raw_state(client, hash)?;
+		let prefixes = self.prefixes();
+		let raw_state = if prefixes.is_empty() {
+			sc_service::chain_ops::export_raw_state(client, hash)?
+		} else {
+			sc_service::chain_ops::export_raw_state_with_prefixes(client, hash, &prefixes)?
+		};
 		input_spec.set_storage(raw_state);
 
 		info!("Generating new chain spec...");
@@ -78,6 +97,24 @@ impl ExportStateCmd {
 		}
 		Ok(())
 	}
+
+	/// The storage prefixes to export, empty if the whole state is exported.
+	pub fn prefixes(&self) -> Vec<Vec<u8>> {
+		storage_prefixes(&self.pallet, &self.prefix)
+	}
+}
+
+/// The storage prefixes of the given `pallets`, followed by the raw `prefixes`.
+pub(crate) fn storage_prefixes(pallets: &[String], prefixes: &[Vec<u8>]) -> Vec<Vec<u8>> {
+	pallets
+		.iter()
+		.map(|pallet| sp_crypto_hashing::twox_128(pallet.as_bytes()).to_vec())
+		.chain(prefixes.iter().cloned())
+		.collect()
+}
+
+pub(crate) fn parse_prefix(prefix: &str) -> Result<Vec<u8>, String> {
+	array_bytes::hex2bytes(prefix).map_err(|e| format!("Invalid hex prefix: {:?}", e))
 }
 
 impl CliConfiguration for ExportStateCmd {
@@ -93,3 +130,33 @@ impl CliConfiguration for ExportStateCmd {
 		Some(&self.database_params)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn prefixes_include_pallets_and_raw_prefixes() {
+		let cmd = ExportStateCmd::try_parse_from([
+			"export-state",
+			"--pallet",
+			"System",
+			"--prefix",
+			"0x3a636f6465",
+			"--prefix",
+			"26aa",
+		])
+		.unwrap();
+
+		assert_eq!(
+			cmd.prefixes(),
+			vec![
+				array_bytes::hex2bytes_unchecked("26aa394eea5630e07c48ae0c9558cef7"),
+				b":code".to_vec(),
+				vec![0x26, 0xaa],
+			]
+		);
+		assert!(ExportStateCmd::try_parse_from(["export-state"]).unwrap().prefixes().is_empty());
+		assert!(ExportStateCmd::try_parse_from(["export-state", "--prefix", "0xzz"]).is_err());
+	}
+}
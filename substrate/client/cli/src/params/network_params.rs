@@ -257,6 +257,7 @@ impl NetworkParams {
 				} else {
 					NonReservedPeerMode::Accept
 				},
+				inactivity_eviction: None,
 			},
 			default_peers_set_num_full: self.in_peers + self.out_peers,
 			listen_addresses,
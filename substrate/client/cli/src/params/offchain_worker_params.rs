@@ -26,6 +26,7 @@
 use clap::{ArgAction, Args};
 use sc_network::config::Role;
 use sc_service::config::OffchainWorkerConfig;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{error, OffchainWorkerEnabled};
 
@@ -47,6 +48,26 @@ pub struct OffchainWorkerParams {
 	/// Allows the runtime to write directly to offchain workers DB during block import.
 	#[arg(long = "enable-offchain-indexing", value_name = "ENABLE_OFFCHAIN_INDEXING", default_value_t = false, action = ArgAction::Set)]
 	pub indexing_enabled: bool,
+
+	/// HTTP proxy to send the HTTP requests of the offchain workers through.
+	///
+	/// Given as `http://<host>:<port>`. HTTPS requests are tunnelled through the proxy with
+	/// `CONNECT`.
+	#[arg(long = "offchain-http-proxy", value_name = "URL")]
+	pub http_proxy: Option<String>,
+
+	/// PEM file of a certificate authority to trust for the HTTPS requests of the offchain
+	/// workers, in addition to the native ones.
+	///
+	/// Can be passed multiple times.
+	#[arg(long = "offchain-http-ca-certificate", value_name = "PATH")]
+	pub http_ca_certificates: Vec<PathBuf>,
+
+	/// Maximum time, in seconds, an HTTP host function called by an offchain worker may block.
+	///
+	/// Deadlines set by the runtime beyond it, or no deadline at all, are capped to this value.
+	#[arg(long = "offchain-http-request-timeout", value_name = "SECONDS")]
+	pub http_request_timeout: Option<u64>,
 }
 
 impl OffchainWorkerParams {
@@ -60,6 +81,12 @@ impl OffchainWorkerParams {
 		};
 
 		let indexing_enabled = self.indexing_enabled;
-		Ok(OffchainWorkerConfig { enabled, indexing_enabled })
+		Ok(OffchainWorkerConfig {
+			enabled,
+			indexing_enabled,
+			http_proxy: self.http_proxy.clone(),
+			http_ca_certificates: self.http_ca_certificates.clone(),
+			http_request_timeout: self.http_request_timeout.map(Duration::from_secs),
+		})
 	}
 }
@@ -0,0 +1,87 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error;
+use clap::Args;
+use sc_keystore::{CommandLease, FileSigningLog, SigningLeaseConfig};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+/// Parameters used to guard the authority keys behind a signing lease.
+///
+/// For high availability setups, where a standby node shares the authority keys of the active
+/// one and only signs while it holds the lease.
+#[derive(Debug, Clone, Args)]
+pub struct SigningLeaseParams {
+	/// Shell command acquiring or renewing the lease required to sign with the authority keys.
+	///
+	/// The command is run every `--signing-lease-interval` seconds. The lease is held for three
+	/// intervals after every successful run, i.e. one exiting with a zero status. The lock behind
+	/// the command, e.g. in etcd or Consul, must therefore last longer than that.
+	#[arg(long, value_name = "COMMAND", requires = "signing_lease_log")]
+	pub signing_lease_command: Option<String>,
+
+	/// File recording the last round or slot signed in by each lease holder.
+	///
+	/// Must be shared by all the nodes sharing the authority keys, e.g. on a network file system.
+	/// A node never signs in a round or a slot another holder already signed in, even after the
+	/// takeover delay.
+	#[arg(long, value_name = "PATH")]
+	pub signing_lease_log: Option<PathBuf>,
+
+	/// Time between two runs of the signing lease command, in seconds.
+	#[arg(long, value_name = "SECONDS", default_value_t = 2)]
+	pub signing_lease_interval: u64,
+
+	/// Time to wait after acquiring the signing lease before signing, in seconds.
+	///
+	/// Leaves time to the previous holder of the lease to stop signing. Should exceed the validity
+	/// of the lease, i.e. three intervals.
+	#[arg(long, value_name = "SECONDS", default_value_t = 12)]
+	pub signing_lease_takeover_delay: u64,
+}
+
+impl SigningLeaseParams {
+	/// Returns the signing lease configuration, or `None` if no lease is required.
+	///
+	/// The key types signed per slot are left to the node, see
+	/// [`SigningLeaseConfig::with_slot_key_type`].
+	pub fn config(&self) -> error::Result<Option<SigningLeaseConfig>> {
+		let Some(command) = self.signing_lease_command.clone() else { return Ok(None) };
+		let Some(log) = self.signing_lease_log.clone() else {
+			return Err(error::Error::Input(
+				"The signing lease requires a signing log, see `--signing-lease-log`".into(),
+			))
+		};
+
+		let interval = Duration::from_secs(self.signing_lease_interval);
+		let takeover_delay = Duration::from_secs(self.signing_lease_takeover_delay);
+		if interval.is_zero() || takeover_delay < 3 * interval {
+			return Err(error::Error::Input(
+				"The signing lease takeover delay must be at least three lease intervals".into(),
+			));
+		}
+
+		let lease = CommandLease::spawn(command, interval, 3 * interval)?;
+		Ok(Some(SigningLeaseConfig {
+			lease: Arc::new(lease),
+			takeover_delay,
+			signing_log: Arc::new(FileSigningLog::new(log)),
+			slot_key_types: Vec::new(),
+		}))
+	}
+}
@@ -39,6 +39,28 @@ impl Into<sc_transaction_pool::TransactionPoolType> for TransactionPoolType {
 	}
 }
 
+/// Order of the ready transactions in the transaction pool.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum TransactionPoolOrdering {
+	/// Includes the transactions with the highest priority first.
+	Priority,
+	/// Includes the transactions with the highest priority per byte first.
+	///
+	/// Fits parachains, whose blocks are usually bound by the size of the proof of validity.
+	PriorityPerByte,
+}
+
+impl Into<sc_transaction_pool::ReadyOrdering> for TransactionPoolOrdering {
+	fn into(self) -> sc_transaction_pool::ReadyOrdering {
+		match self {
+			TransactionPoolOrdering::Priority => sc_transaction_pool::ReadyOrdering::Priority,
+			TransactionPoolOrdering::PriorityPerByte =>
+				sc_transaction_pool::ReadyOrdering::PriorityPerByte,
+		}
+	}
+}
+
 /// Parameters used to create the pool configuration.
 #[derive(Debug, Clone, Args)]
 pub struct TransactionPoolParams {
@@ -59,6 +81,10 @@ pub struct TransactionPoolParams {
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::SingleState)]
 	pub pool_type: TransactionPoolType,
+
+	/// The order in which the ready transactions are included into blocks.
+	#[arg(long, value_enum, default_value_t = TransactionPoolOrdering::Priority)]
+	pub pool_ordering: TransactionPoolOrdering,
 }
 
 impl TransactionPoolParams {
@@ -71,5 +97,6 @@ impl TransactionPoolParams {
 			self.pool_type.into(),
 			is_dev,
 		)
+		.with_ready_ordering(self.pool_ordering.into())
 	}
 }
@@ -32,6 +32,56 @@ use sp_core::{
 };
 use sp_keystore::KeystorePtr;
 
+/// Whether the authoring worker claims secondary slots.
+///
+/// Secondary slots are only ever claimed if the epoch configuration allows them, this merely
+/// lets a node opt out of authoring them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SecondarySlotClaims {
+	/// Claim secondary slots whenever the epoch configuration allows them.
+	#[default]
+	Enabled,
+	/// Only claim primary slots.
+	Disabled,
+}
+
+/// Configuration of the recovery mode for chains with very few authorities.
+///
+/// When no block has been produced for `after_empty_slots` consecutive slots on top of some
+/// block, the slot is assigned to the recovery `authorities` in a round-robin fashion, regardless
+/// of the primary and secondary slot assignments. Recovery blocks are authored with a secondary
+/// plain pre-digest, so every node importing them must use the same configuration.
+///
+/// This lets private networks with offline primaries make progress and must never be enabled on
+/// production chains.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryConfig {
+	/// The number of consecutive empty slots after which recovery authorities may author.
+	pub after_empty_slots: u64,
+	/// The authorities allowed to author in recovery mode. Only those that are part of the
+	/// authority set of the epoch can actually author.
+	pub authorities: Vec<AuthorityId>,
+}
+
+impl RecoveryConfig {
+	/// The recovery authority expected to author `slot` on top of a block authored in
+	/// `parent_slot`, or `None` if the slot is not a recovery slot.
+	///
+	/// `parent_slot` is `None` when building on top of genesis, recovery never applies there.
+	pub fn author(&self, slot: Slot, parent_slot: Option<Slot>) -> Option<&AuthorityId> {
+		if self.authorities.is_empty() {
+			return None
+		}
+
+		let parent_slot = parent_slot?;
+		if *slot <= parent_slot.saturating_add(self.after_empty_slots) {
+			return None
+		}
+
+		self.authorities.get((*slot % self.authorities.len() as u64) as usize)
+	}
+}
+
 /// Calculates the primary selection threshold for a given authority, taking
 /// into account `c` (`1 - c` represents the probability of a slot being empty).
 pub(super) fn calculate_primary_threshold(
@@ -177,6 +227,33 @@ fn claim_secondary_slot(
 	None
 }
 
+/// Claim a recovery slot if the recovery authority expected to author `slot` is one of the
+/// epoch authorities and its key is in the keystore.
+pub(super) fn claim_recovery_slot(
+	slot: Slot,
+	parent_slot: Option<Slot>,
+	epoch: &Epoch,
+	keystore: &KeystorePtr,
+	recovery: &RecoveryConfig,
+) -> Option<(PreDigest, AuthorityId)> {
+	let expected_author = recovery.author(slot, parent_slot)?;
+	let authority_index = epoch
+		.authorities
+		.iter()
+		.position(|(authority_id, _)| authority_id == expected_author)?;
+
+	if !keystore.has_keys(&[(expected_author.to_raw_vec(), AuthorityId::ID)]) {
+		return None
+	}
+
+	let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+		slot,
+		authority_index: authority_index as u32,
+	});
+
+	Some((pre_digest, expected_author.clone()))
+}
+
 /// Tries to claim the given slot number. This method starts by trying to claim
 /// a primary VRF based slot. If we are not able to claim it, then if we have
 /// secondary slots enabled for the given epoch, we will fallback to trying to
@@ -185,6 +262,16 @@ pub fn claim_slot(
 	slot: Slot,
 	epoch: &Epoch,
 	keystore: &KeystorePtr,
+) -> Option<(PreDigest, AuthorityId)> {
+	claim_slot_with(slot, epoch, keystore, SecondarySlotClaims::Enabled)
+}
+
+/// Like `claim_slot`, but only falls back to secondary slots if `secondary_slot_claims` allows it.
+pub fn claim_slot_with(
+	slot: Slot,
+	epoch: &Epoch,
+	keystore: &KeystorePtr,
+	secondary_slot_claims: SecondarySlotClaims,
 ) -> Option<(PreDigest, AuthorityId)> {
 	let authorities = epoch
 		.authorities
@@ -192,7 +279,7 @@ pub fn claim_slot(
 		.enumerate()
 		.map(|(index, a)| (a.0.clone(), index))
 		.collect::<Vec<_>>();
-	claim_slot_using_keys(slot, epoch, keystore, &authorities)
+	claim_slot_using_keys_with(slot, epoch, keystore, &authorities, secondary_slot_claims)
 }
 
 /// Like `claim_slot`, but allows passing an explicit set of key pairs. Useful if we intend
@@ -202,10 +289,23 @@ pub fn claim_slot_using_keys(
 	epoch: &Epoch,
 	keystore: &KeystorePtr,
 	keys: &[(AuthorityId, usize)],
+) -> Option<(PreDigest, AuthorityId)> {
+	claim_slot_using_keys_with(slot, epoch, keystore, keys, SecondarySlotClaims::Enabled)
+}
+
+/// Like `claim_slot_using_keys`, but only falls back to secondary slots if
+/// `secondary_slot_claims` allows it.
+pub fn claim_slot_using_keys_with(
+	slot: Slot,
+	epoch: &Epoch,
+	keystore: &KeystorePtr,
+	keys: &[(AuthorityId, usize)],
+	secondary_slot_claims: SecondarySlotClaims,
 ) -> Option<(PreDigest, AuthorityId)> {
 	claim_primary_slot(slot, epoch, epoch.config.c, keystore, keys).or_else(|| {
-		if epoch.config.allowed_slots.is_secondary_plain_slots_allowed() ||
-			epoch.config.allowed_slots.is_secondary_vrf_slots_allowed()
+		if secondary_slot_claims == SecondarySlotClaims::Enabled &&
+			(epoch.config.allowed_slots.is_secondary_plain_slots_allowed() ||
+				epoch.config.allowed_slots.is_secondary_vrf_slots_allowed())
 		{
 			claim_secondary_slot(
 				slot,
@@ -309,6 +409,74 @@ mod tests {
 		assert_eq!(claim_slot(10.into(), &epoch, &keystore).unwrap().1, valid_public_key.into());
 	}
 
+	#[test]
+	fn secondary_slot_claims_can_be_disabled() {
+		let keystore: KeystorePtr = MemoryKeystore::new().into();
+		let public_key = keystore
+			.sr25519_generate_new(AuthorityId::ID, Some(sp_core::crypto::DEV_PHRASE))
+			.unwrap();
+
+		let epoch = Epoch {
+			epoch_index: 10,
+			start_slot: 0.into(),
+			duration: 20,
+			authorities: vec![(public_key.into(), 10)],
+			randomness: Default::default(),
+			config: BabeEpochConfiguration {
+				c: (0, 10),
+				allowed_slots: AllowedSlots::PrimaryAndSecondaryPlainSlots,
+			},
+		}
+		.into();
+
+		assert!(
+			claim_slot_with(10.into(), &epoch, &keystore, SecondarySlotClaims::Enabled).is_some()
+		);
+		assert!(
+			claim_slot_with(10.into(), &epoch, &keystore, SecondarySlotClaims::Disabled).is_none()
+		);
+	}
+
+	#[test]
+	fn recovery_slots_are_claimed_after_empty_slots() {
+		let keystore: KeystorePtr = MemoryKeystore::new().into();
+		let public_key: AuthorityId = keystore
+			.sr25519_generate_new(AuthorityId::ID, Some(sp_core::crypto::DEV_PHRASE))
+			.unwrap()
+			.into();
+		let other = AuthorityId::from(Pair::generate().0.public());
+
+		let epoch = Epoch {
+			epoch_index: 10,
+			start_slot: 0.into(),
+			duration: 20,
+			authorities: vec![(other.clone(), 1), (public_key.clone(), 1)],
+			randomness: Default::default(),
+			config: BabeEpochConfiguration {
+				c: (0, 10),
+				allowed_slots: AllowedSlots::PrimarySlots,
+			},
+		}
+		.into();
+
+		let recovery =
+			RecoveryConfig { after_empty_slots: 3, authorities: vec![public_key.clone(), other] };
+
+		// not enough empty slots, or building on top of genesis.
+		assert!(recovery.author(13.into(), Some(10.into())).is_none());
+		assert!(recovery.author(20.into(), None).is_none());
+
+		// slot 14 belongs to the first recovery authority, slot 15 to the second one.
+		let (pre_digest, author) =
+			claim_recovery_slot(14.into(), Some(10.into()), &epoch, &keystore, &recovery).unwrap();
+		assert!(matches!(pre_digest, PreDigest::SecondaryPlain(_)));
+		assert_eq!((pre_digest.slot(), pre_digest.authority_index()), (14.into(), 1));
+		assert_eq!(author, public_key);
+		assert!(
+			claim_recovery_slot(15.into(), Some(10.into()), &epoch, &keystore, &recovery).is_none()
+		);
+	}
+
 	#[test]
 	fn secondary_slot_author_selection_works() {
 		let authorities = (0..1000)
@@ -138,6 +138,7 @@ pub use sp_consensus_babe::{
 	BabeConfiguration, BabeEpochConfiguration, ConsensusLog, Randomness, BABE_ENGINE_ID,
 };
 
+pub use authorship::{RecoveryConfig, SecondarySlotClaims};
 pub use aux_schema::load_block_weight as block_weight;
 
 mod migration;
@@ -457,6 +458,9 @@ pub struct BabeParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS> {
 
 	/// Handle use to report telemetries.
 	pub telemetry: Option<TelemetryHandle>,
+
+	/// Whether secondary slots are claimed when the epoch configuration allows them.
+	pub secondary_slot_claims: SecondarySlotClaims,
 }
 
 /// Start the babe worker.
@@ -476,6 +480,7 @@ pub fn start_babe<B, C, SC, E, I, SO, CIDP, BS, L, Error>(
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		telemetry,
+		secondary_slot_claims,
 	}: BabeParams<B, C, SC, E, I, SO, L, CIDP, BS>,
 ) -> Result<BabeWorker<B>, ConsensusError>
 where
@@ -515,6 +520,8 @@ where
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		telemetry,
+		secondary_slot_claims,
+		recovery: babe_link.recovery.clone(),
 	};
 
 	info!(target: LOG_TARGET, "👶 Starting BABE Authorship worker");
@@ -712,6 +719,8 @@ struct BabeSlotWorker<B: BlockT, C, E, I, SO, L, BS> {
 	block_proposal_slot_portion: SlotProportion,
 	max_block_proposal_slot_portion: Option<SlotProportion>,
 	telemetry: Option<TelemetryHandle>,
+	secondary_slot_claims: SecondarySlotClaims,
+	recovery: Option<RecoveryConfig>,
 }
 
 #[async_trait::async_trait]
@@ -768,19 +777,35 @@ where
 
 	async fn claim_slot(
 		&mut self,
-		_parent_header: &B::Header,
+		parent_header: &B::Header,
 		slot: Slot,
 		epoch_descriptor: &ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>,
 	) -> Option<Self::Claim> {
 		debug!(target: LOG_TARGET, "Attempting to claim slot {}", slot);
-		let s = authorship::claim_slot(
+		let epoch_changes = self.epoch_changes.shared_data();
+		let epoch = epoch_changes
+			.viable_epoch(epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))?;
+		let s = authorship::claim_slot_with(
 			slot,
-			self.epoch_changes
-				.shared_data()
-				.viable_epoch(epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))?
-				.as_ref(),
+			epoch.as_ref(),
 			&self.keystore,
-		);
+			self.secondary_slot_claims,
+		)
+		.or_else(|| {
+			let recovery = self.recovery.as_ref()?;
+			let claim = authorship::claim_recovery_slot(
+				slot,
+				parent_slot::<B>(parent_header),
+				epoch.as_ref(),
+				&self.keystore,
+				recovery,
+			);
+			if claim.is_some() {
+				warn!(target: LOG_TARGET, "🚑 Claiming slot {} in recovery mode", slot);
+			}
+			claim
+		});
+		drop(epoch_changes);
 
 		if s.is_some() {
 			debug!(target: LOG_TARGET, "Claimed slot {}", slot);
@@ -920,6 +945,15 @@ pub fn find_pre_digest<B: BlockT>(header: &B::Header) -> Result<PreDigest, Error
 	pre_digest.ok_or_else(|| babe_err(Error::NoPreRuntimeDigest))
 }
 
+/// The slot of `header`, or `None` for genesis and headers without a valid pre-digest.
+fn parent_slot<B: BlockT>(header: &B::Header) -> Option<Slot> {
+	if header.number().is_zero() {
+		return None
+	}
+
+	find_pre_digest::<B>(header).ok().map(|pre_digest| pre_digest.slot())
+}
+
 /// Extract the BABE epoch change digest from the given header, if it exists.
 fn find_next_epoch_digest<B: BlockT>(
 	header: &B::Header,
@@ -963,6 +997,7 @@ fn find_next_config_digest<B: BlockT>(
 pub struct BabeLink<Block: BlockT> {
 	epoch_changes: SharedEpochChanges<Block, Epoch>,
 	config: BabeConfiguration,
+	recovery: Option<RecoveryConfig>,
 }
 
 impl<Block: BlockT> BabeLink<Block> {
@@ -975,6 +1010,19 @@ impl<Block: BlockT> BabeLink<Block> {
 	pub fn config(&self) -> &BabeConfiguration {
 		&self.config
 	}
+
+	/// Enable the recovery mode for both authoring and import.
+	///
+	/// Must be set before the link is passed to [`import_queue`] and [`start_babe`].
+	pub fn with_recovery(mut self, recovery: RecoveryConfig) -> Self {
+		self.recovery = Some(recovery);
+		self
+	}
+
+	/// Get the recovery configuration of this link, if the recovery mode is enabled.
+	pub fn recovery(&self) -> Option<&RecoveryConfig> {
+		self.recovery.as_ref()
+	}
 }
 
 /// A verifier for Babe blocks.
@@ -986,6 +1034,7 @@ pub struct BabeVerifier<Block: BlockT, Client, SelectChain, CIDP> {
 	epoch_changes: SharedEpochChanges<Block, Epoch>,
 	telemetry: Option<TelemetryHandle>,
 	offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+	recovery: Option<RecoveryConfig>,
 }
 
 impl<Block, Client, SelectChain, CIDP> BabeVerifier<Block, Client, SelectChain, CIDP>
@@ -1179,6 +1228,19 @@ where
 			.map_err(Error::<Block>::FetchParentHeader)?;
 
 		let pre_digest = find_pre_digest::<Block>(&block.header)?;
+		let recovery_author = match &self.recovery {
+			Some(recovery) => {
+				let parent_header = self
+					.client
+					.header(parent_hash)
+					.map_err(Error::<Block>::FetchParentHeader)?
+					.ok_or(Error::<Block>::ParentUnavailable(parent_hash, hash))?;
+				recovery
+					.author(pre_digest.slot(), parent_slot::<Block>(&parent_header))
+					.cloned()
+			},
+			None => None,
+		};
 		let (check_header, epoch_descriptor) = {
 			let epoch_changes = self.epoch_changes.shared_data();
 			let epoch_descriptor = epoch_changes
@@ -1201,6 +1263,7 @@ where
 				pre_digest: Some(pre_digest),
 				slot_now: slot_now + 1,
 				epoch: viable_epoch.as_ref(),
+				recovery_author,
 			};
 
 			(verification::check_header::<Block>(v_params)?, epoch_descriptor)
@@ -1744,7 +1807,8 @@ where
 		+ 'static,
 {
 	let epoch_changes = aux_schema::load_epoch_changes::<Block, _>(&*client, &config)?;
-	let link = BabeLink { epoch_changes: epoch_changes.clone(), config: config.clone() };
+	let link =
+		BabeLink { epoch_changes: epoch_changes.clone(), config: config.clone(), recovery: None };
 
 	// NOTE: this isn't entirely necessary, but since we didn't use to prune the
 	// epoch tree it is useful as a migration, so that nodes prune long trees on
@@ -1846,6 +1910,7 @@ where
 		telemetry,
 		client: client.clone(),
 		offchain_tx_pool_factory,
+		recovery: babe_link.recovery.clone(),
 	};
 
 	let (worker_tx, worker_rx) = channel(HANDLE_BUFFER_SIZE);
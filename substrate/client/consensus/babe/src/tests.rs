@@ -272,6 +272,7 @@ impl TestNetFactory for BabeTestNet {
 				offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 					RejectAllTxPool::default(),
 				),
+				recovery: data.link.recovery.clone(),
 			},
 			mutator: MUTATOR.with(|m| m.borrow().clone()),
 		}
@@ -402,6 +403,7 @@ async fn run_one_test(mutator: impl Fn(&mut TestHeader, Stage) + Send + Sync + '
 				block_proposal_slot_portion: SlotProportion::new(0.5),
 				max_block_proposal_slot_portion: None,
 				telemetry: None,
+				secondary_slot_claims: Default::default(),
 			})
 			.expect("Starts babe"),
 		);
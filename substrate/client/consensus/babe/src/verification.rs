@@ -51,6 +51,8 @@ pub(super) struct VerificationParams<'a, B: 'a + BlockT> {
 	pub(super) slot_now: Slot,
 	/// Epoch descriptor of the epoch this block _should_ be under, if it's valid.
 	pub(super) epoch: &'a Epoch,
+	/// The authority allowed to author this block in recovery mode, if any.
+	pub(super) recovery_author: Option<AuthorityId>,
 }
 
 /// Check a header has been signed by the right key. If the slot is too far in
@@ -67,7 +69,7 @@ pub(super) struct VerificationParams<'a, B: 'a + BlockT> {
 pub(super) fn check_header<B: BlockT + Sized>(
 	params: VerificationParams<B>,
 ) -> Result<CheckedHeader<B::Header, VerifiedHeaderInfo>, Error<B>> {
-	let VerificationParams { mut header, pre_digest, slot_now, epoch } = params;
+	let VerificationParams { mut header, pre_digest, slot_now, epoch, recovery_author } = params;
 
 	let authorities = &epoch.authorities;
 	let pre_digest = pre_digest.map(Ok).unwrap_or_else(|| find_pre_digest::<B>(&header))?;
@@ -107,6 +109,18 @@ pub(super) fn check_header<B: BlockT + Sized>(
 
 			check_primary_header::<B>(pre_hash, primary, sig, epoch, epoch.config.c)?;
 		},
+		PreDigest::SecondaryPlain(secondary) if recovery_author.as_ref() == Some(&author) => {
+			debug!(
+				target: LOG_TARGET,
+				"Verifying recovery block #{} at slot: {}",
+				header.number(),
+				secondary.slot,
+			);
+
+			if !AuthorityPair::verify(&sig, pre_hash.as_ref(), &author) {
+				return Err(babe_err(Error::BadSignature(pre_hash)))
+			}
+		},
 		PreDigest::SecondaryPlain(secondary)
 			if epoch.config.allowed_slots.is_secondary_plain_slots_allowed() =>
 		{
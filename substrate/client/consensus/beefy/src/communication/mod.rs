@@ -83,6 +83,7 @@ pub fn beefy_peers_set_config<
 			out_peers: 25,
 			reserved_nodes: Vec::new(),
 			non_reserved_mode: sc_network::config::NonReservedPeerMode::Accept,
+			inactivity_eviction: None,
 		},
 		metrics,
 		peer_store_handle,
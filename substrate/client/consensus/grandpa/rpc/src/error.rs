@@ -33,6 +33,9 @@ pub enum Error {
 	/// GRANDPA prove finality failed.
 	#[error("GRANDPA prove finality rpc failed: {0}")]
 	ProveFinalityFailed(#[from] sc_consensus_grandpa::FinalityProofError),
+	/// The node is not recording the GRANDPA vote history.
+	#[error("GRANDPA vote history is not being recorded")]
+	VoteHistoryDisabled,
 }
 
 /// The error codes returned by jsonrpc.
@@ -45,6 +48,8 @@ pub enum ErrorCode {
 	VoterStateTooLarge,
 	/// Failed to prove finality.
 	ProveFinality,
+	/// The vote history is not being recorded.
+	VoteHistoryDisabled,
 }
 
 impl From<Error> for ErrorCode {
@@ -54,6 +59,7 @@ impl From<Error> for ErrorCode {
 			Error::AuthoritySetIdReportedAsUnreasonablyLarge => ErrorCode::AuthoritySetTooLarge,
 			Error::VoterStateReportsUnreasonablyLargeNumbers => ErrorCode::VoterStateTooLarge,
 			Error::ProveFinalityFailed(_) => ErrorCode::ProveFinality,
+			Error::VoteHistoryDisabled => ErrorCode::VoteHistoryDisabled,
 		}
 	}
 }
@@ -32,17 +32,19 @@ mod error;
 mod finality;
 mod notification;
 mod report;
+mod votes;
 
 use error::Error;
 use finality::{EncodedFinalityProof, RpcFinalityProofProvider};
 use notification::JustificationNotification;
 use report::{ReportAuthoritySet, ReportVoterState, ReportedRoundStates};
-use sc_consensus_grandpa::GrandpaJustificationStream;
+use sc_consensus_grandpa::{GrandpaJustificationStream, SharedVoteHistory};
 use sc_rpc::{
 	utils::{BoundedVecDeque, PendingSubscription},
 	SubscriptionTaskExecutor,
 };
 use sp_runtime::traits::{Block as BlockT, NumberFor};
+use votes::ReportedRoundVotes;
 
 /// Provides RPC methods for interacting with GRANDPA.
 #[rpc(client, server)]
@@ -65,6 +67,19 @@ pub trait GrandpaApi<Notification, Hash, Number> {
 	/// in the set and all the intermediary headers to link them together.
 	#[method(name = "grandpa_proveFinality")]
 	async fn prove_finality(&self, block: Number) -> Result<Option<EncodedFinalityProof>, Error>;
+
+	/// Returns all the prevotes and precommits observed by the local voter in the
+	/// rounds `from_round..=to_round` of the given authority set, alongside the
+	/// equivocation proofs that can be built from them.
+	///
+	/// Only the rounds still kept in the vote history of the node are returned.
+	#[method(name = "grandpa_roundVotes")]
+	async fn round_votes(
+		&self,
+		set_id: u64,
+		from_round: u64,
+		to_round: u64,
+	) -> Result<Vec<ReportedRoundVotes<Hash, Number>>, Error>;
 }
 
 /// Provides RPC methods for interacting with GRANDPA.
@@ -74,6 +89,7 @@ pub struct Grandpa<AuthoritySet, VoterState, Block: BlockT, ProofProvider> {
 	voter_state: VoterState,
 	justification_stream: GrandpaJustificationStream<Block>,
 	finality_proof_provider: Arc<ProofProvider>,
+	vote_history: SharedVoteHistory<Block>,
 }
 impl<AuthoritySet, VoterState, Block: BlockT, ProofProvider>
 	Grandpa<AuthoritySet, VoterState, Block, ProofProvider>
@@ -85,8 +101,16 @@ impl<AuthoritySet, VoterState, Block: BlockT, ProofProvider>
 		voter_state: VoterState,
 		justification_stream: GrandpaJustificationStream<Block>,
 		finality_proof_provider: Arc<ProofProvider>,
+		vote_history: SharedVoteHistory<Block>,
 	) -> Self {
-		Self { executor, authority_set, voter_state, justification_stream, finality_proof_provider }
+		Self {
+			executor,
+			authority_set,
+			voter_state,
+			justification_stream,
+			finality_proof_provider,
+			vote_history,
+		}
 	}
 }
 
@@ -126,6 +150,24 @@ where
 			error::Error::ProveFinalityFailed(e)
 		})
 	}
+
+	async fn round_votes(
+		&self,
+		set_id: u64,
+		from_round: u64,
+		to_round: u64,
+	) -> Result<Vec<ReportedRoundVotes<Block::Hash, NumberFor<Block>>>, Error> {
+		if !self.vote_history.is_enabled() {
+			return Err(Error::VoteHistoryDisabled)
+		}
+
+		Ok(self
+			.vote_history
+			.rounds(set_id, from_round..=to_round)
+			.into_iter()
+			.map(Into::into)
+			.collect())
+	}
 }
 
 #[cfg(test)]
@@ -266,6 +308,7 @@ mod tests {
 			voter_state,
 			justification_stream,
 			finality_proof_provider,
+			SharedVoteHistory::disabled(),
 		)
 		.into_rpc();
 
@@ -412,4 +455,14 @@ mod tests {
 		let finality_proof_rpc: FinalityProof<Header> = Decode::decode(&mut &bytes[..]).unwrap();
 		assert_eq!(finality_proof_rpc, finality_proof);
 	}
+
+	#[tokio::test]
+	async fn round_votes_require_vote_history() {
+		let (rpc, _) = setup_io_handler(TestVoterState);
+		let expected_response = r#"{"jsonrpc":"2.0","id":0,"error":{"code":5,"message":"GRANDPA vote history is not being recorded"}}"#.to_string();
+		let request = r#"{"jsonrpc":"2.0","method":"grandpa_roundVotes","params":[0,1,2],"id":0}"#;
+		let (response, _) = rpc.raw_json_request(&request, 1).await.unwrap();
+
+		assert_eq!(expected_response, response);
+	}
 }
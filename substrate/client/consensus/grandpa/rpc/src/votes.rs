@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use codec::Encode;
+use serde::{Deserialize, Serialize};
+
+use sc_consensus_grandpa::{AuthorityId, RecordedRound};
+use sp_consensus_grandpa::{AuthoritySignature, EquivocationProof};
+use sp_core::Bytes;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+
+/// A single prevote or precommit observed by the local voter.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedVote<Hash, Number> {
+	voter: AuthorityId,
+	target_hash: Hash,
+	target_number: Number,
+	/// SCALE-encoded signature of the vote.
+	signature: Bytes,
+}
+
+impl<Hash, Number> ReportedVote<Hash, Number> {
+	fn new(
+		voter: &AuthorityId,
+		target_hash: Hash,
+		target_number: Number,
+		signature: &AuthoritySignature,
+	) -> Self {
+		Self {
+			voter: voter.clone(),
+			target_hash,
+			target_number,
+			signature: signature.encode().into(),
+		}
+	}
+}
+
+/// All the votes observed by the local voter in a round, in a form suitable
+/// for serialization.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedRoundVotes<Hash, Number> {
+	set_id: u64,
+	round: u64,
+	base_hash: Hash,
+	base_number: Number,
+	prevotes: Vec<ReportedVote<Hash, Number>>,
+	precommits: Vec<ReportedVote<Hash, Number>>,
+	/// SCALE-encoded [`EquivocationProof`]s that can be built from the votes above.
+	equivocations: Vec<Bytes>,
+}
+
+impl<Block: BlockT> From<RecordedRound<Block>>
+	for ReportedRoundVotes<Block::Hash, NumberFor<Block>>
+{
+	fn from(round: RecordedRound<Block>) -> Self {
+		let prevotes = round
+			.prevotes()
+			.map(|(id, prevote, signature)| {
+				ReportedVote::new(id, prevote.target_hash, prevote.target_number, signature)
+			})
+			.collect();
+
+		let precommits = round
+			.precommits()
+			.map(|(id, precommit, signature)| {
+				ReportedVote::new(id, precommit.target_hash, precommit.target_number, signature)
+			})
+			.collect();
+
+		let equivocations = round
+			.equivocations()
+			.into_iter()
+			.map(|equivocation| EquivocationProof::new(round.set_id, equivocation).encode().into())
+			.collect();
+
+		Self {
+			set_id: round.set_id,
+			round: round.number,
+			base_hash: round.base.0,
+			base_number: round.base.1,
+			prevotes,
+			precommits,
+			equivocations,
+		}
+	}
+}
@@ -56,6 +56,7 @@ use crate::{
 	local_authority_id,
 	notification::GrandpaJustificationSender,
 	until_imported::UntilVoteTargetImported,
+	vote_history::SharedVoteHistory,
 	voting_rule::VotingRule as VotingRuleT,
 	ClientForGrandpa, CommandOrError, Commit, Config, Error, NewAuthoritySet, Precommit, Prevote,
 	PrimaryPropose, SignedMessage, VoterCommand, LOG_TARGET,
@@ -442,6 +443,7 @@ pub(crate) struct Environment<
 	pub(crate) voting_rule: VR,
 	pub(crate) metrics: Option<Metrics>,
 	pub(crate) justification_sender: Option<GrandpaJustificationSender<Block>>,
+	pub(crate) vote_history: SharedVoteHistory<Block>,
 	pub(crate) telemetry: Option<TelemetryHandle>,
 	pub(crate) offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
 	pub(crate) _phantom: PhantomData<Backend>,
@@ -1026,6 +1028,8 @@ where
 			Ok(Some(set_state))
 		})?;
 
+		self.vote_history.record(self.set_id, round, base, historical_votes.seen());
+
 		// clear any cached local authority id associated with this round
 		self.voter_set_state.finished_voting_on(round);
 
@@ -1036,7 +1040,7 @@ where
 		&self,
 		round: RoundNumber,
 		state: RoundState<Block::Hash, NumberFor<Block>>,
-		_base: (Block::Hash, NumberFor<Block>),
+		base: (Block::Hash, NumberFor<Block>),
 		historical_votes: &HistoricalVotes<Block>,
 	) -> Result<(), Self::Error> {
 		debug!(
@@ -1086,6 +1090,8 @@ where
 			Ok(Some(set_state))
 		})?;
 
+		self.vote_history.record(self.set_id, round, base, historical_votes.seen());
+
 		Ok(())
 	}
 
@@ -123,6 +123,7 @@ mod justification;
 mod notification;
 mod observer;
 mod until_imported;
+mod vote_history;
 mod voting_rule;
 pub mod warp_proof;
 
@@ -135,6 +136,7 @@ pub use import::{find_forced_change, find_scheduled_change, GrandpaBlockImport};
 pub use justification::GrandpaJustification;
 pub use notification::{GrandpaJustificationSender, GrandpaJustificationStream};
 pub use observer::run_grandpa_observer;
+pub use vote_history::{RecordedRound, SharedVoteHistory};
 pub use voting_rule::{
 	BeforeBestBlockBy, ThreeQuartersOfTheUnfinalizedChain, VotingRule, VotingRuleResult,
 	VotingRulesBuilder,
@@ -695,6 +697,10 @@ pub struct GrandpaParams<Block: BlockT, C, N, S, SC, VR> {
 	pub prometheus_registry: Option<prometheus_endpoint::Registry>,
 	/// The voter state is exposed at an RPC endpoint.
 	pub shared_voter_state: SharedVoterState,
+	/// History of the votes observed by the voter, exposed at an RPC endpoint.
+	///
+	/// Use [`SharedVoteHistory::disabled`] to not record any votes.
+	pub vote_history: SharedVoteHistory<Block>,
 	/// TelemetryHandle instance.
 	pub telemetry: Option<TelemetryHandle>,
 	/// Offchain transaction pool factory.
@@ -724,6 +730,7 @@ pub fn grandpa_peers_set_config<B: BlockT, N: NetworkBackend<B, <B as BlockT>::H
 			out_peers: 0,
 			reserved_nodes: Vec::new(),
 			non_reserved_mode: sc_network::config::NonReservedPeerMode::Deny,
+			inactivity_eviction: None,
 		},
 		metrics,
 		peer_store_handle,
@@ -754,6 +761,7 @@ where
 		voting_rule,
 		prometheus_registry,
 		shared_voter_state,
+		vote_history,
 		telemetry,
 		offchain_tx_pool_factory,
 	} = grandpa_params;
@@ -829,6 +837,7 @@ where
 		voter_commands_rx,
 		prometheus_registry,
 		shared_voter_state,
+		vote_history,
 		justification_sender,
 		telemetry,
 		offchain_tx_pool_factory,
@@ -899,6 +908,7 @@ where
 		voter_commands_rx: TracingUnboundedReceiver<VoterCommand<Block::Hash, NumberFor<Block>>>,
 		prometheus_registry: Option<prometheus_endpoint::Registry>,
 		shared_voter_state: SharedVoterState,
+		vote_history: SharedVoteHistory<Block>,
 		justification_sender: GrandpaJustificationSender<Block>,
 		telemetry: Option<TelemetryHandle>,
 		offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
@@ -925,6 +935,7 @@ where
 			voter_set_state: persistent_data.set_state,
 			metrics: metrics.as_ref().map(|m| m.environment.clone()),
 			justification_sender: Some(justification_sender),
+			vote_history,
 			telemetry: telemetry.clone(),
 			offchain_tx_pool_factory,
 			_phantom: PhantomData,
@@ -1077,6 +1088,7 @@ where
 					voting_rule: self.env.voting_rule.clone(),
 					metrics: self.env.metrics.clone(),
 					justification_sender: self.env.justification_sender.clone(),
+					vote_history: self.env.vote_history.clone(),
 					telemetry: self.telemetry.clone(),
 					offchain_tx_pool_factory: self.env.offchain_tx_pool_factory.clone(),
 					_phantom: PhantomData,
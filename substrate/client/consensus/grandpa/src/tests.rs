@@ -339,6 +339,7 @@ fn initialize_grandpa(
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			vote_history: SharedVoteHistory::disabled(),
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
@@ -498,6 +499,7 @@ async fn finalize_3_voters_1_full_observer() {
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			vote_history: SharedVoteHistory::disabled(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -597,6 +599,7 @@ async fn transition_3_voters_twice_1_full_observer() {
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			vote_history: SharedVoteHistory::disabled(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1080,6 +1083,7 @@ async fn voter_persists_its_votes() {
 			voting_rule: VotingRulesBuilder::default().build(),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			vote_history: SharedVoteHistory::disabled(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1130,6 +1134,7 @@ async fn voter_persists_its_votes() {
 			voting_rule: VotingRulesBuilder::default().build(),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			vote_history: SharedVoteHistory::disabled(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1351,6 +1356,7 @@ async fn voter_catches_up_to_latest_round_when_behind() {
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			vote_history: SharedVoteHistory::disabled(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1484,6 +1490,7 @@ where
 		voting_rule,
 		metrics: None,
 		justification_sender: None,
+		vote_history: SharedVoteHistory::disabled(),
 		telemetry: None,
 		_phantom: PhantomData,
 		offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(RejectAllTxPool::default()),
@@ -2002,6 +2009,72 @@ async fn grandpa_environment_never_overwrites_round_voter_state() {
 	assert_matches!(get_current_round(2).unwrap(), HasVoted::Yes(_, _));
 }
 
+#[tokio::test]
+async fn grandpa_environment_records_vote_history() {
+	use finality_grandpa::voter::Environment as _;
+
+	let peers = &[Ed25519Keyring::Alice];
+	let voters = make_ids(peers);
+
+	let mut net = GrandpaTestNet::new(TestApi::new(voters), 1, 0);
+	let peer = net.peer(0);
+	let network_service = peer.network_service().clone();
+	let sync_service = peer.sync_service().clone();
+	let notification_service =
+		peer.take_notification_service(&grandpa_protocol_name::NAME.into()).unwrap();
+	let link = peer.data.lock().take().unwrap();
+
+	let vote_history = SharedVoteHistory::new(4);
+	let environment = Environment {
+		vote_history: vote_history.clone(),
+		..test_environment(
+			&link,
+			None,
+			network_service.clone(),
+			sync_service,
+			notification_service,
+			(),
+		)
+	};
+
+	let round_state = || finality_grandpa::round::State::genesis(Default::default());
+	let base = || Default::default();
+
+	let info = peer.client().info();
+	let prevote = |n| {
+		let message = finality_grandpa::Message::Prevote(finality_grandpa::Prevote {
+			target_hash: info.best_hash,
+			target_number: n,
+		});
+		let payload = sp_consensus_grandpa::localized_payload(1, environment.set_id, &message);
+		finality_grandpa::SignedMessage {
+			message,
+			signature: Ed25519Keyring::Alice.sign(&payload[..]).into(),
+			id: Ed25519Keyring::Alice.public().into(),
+		}
+	};
+
+	let votes = finality_grandpa::HistoricalVotes::new_with(vec![prevote(0)], None, None);
+	environment.completed(1, round_state(), base(), &votes).unwrap();
+
+	let rounds = vote_history.rounds(environment.set_id, 1..=1);
+	assert_eq!(rounds.len(), 1);
+	assert_eq!(rounds[0].prevotes().count(), 1);
+	assert!(vote_history.equivocations(environment.set_id).is_empty());
+
+	// concluding the round extends the recorded votes, which now include an equivocation.
+	let votes =
+		finality_grandpa::HistoricalVotes::new_with(vec![prevote(0), prevote(1)], None, None);
+	environment.concluded(1, round_state(), base(), &votes).unwrap();
+
+	let rounds = vote_history.rounds(environment.set_id, 1..=1);
+	assert_eq!(rounds[0].prevotes().count(), 2);
+	assert_matches!(
+		&vote_history.equivocations(environment.set_id)[..],
+		[(1, sp_consensus_grandpa::Equivocation::Prevote(_))]
+	);
+}
+
 #[tokio::test]
 async fn justification_with_equivocation() {
 	use sp_application_crypto::Pair;
@@ -0,0 +1,381 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Recording of the votes observed by the local voter.
+//!
+//! The voter set state only keeps the votes of the last couple of completed
+//! rounds, which is enough to restart the voter but not enough to reconstruct
+//! what happened after a finality incident. The [`SharedVoteHistory`] keeps
+//! every prevote and precommit observed in a configurable number of recent
+//! rounds (across authority set changes) so that the evidence can be exported
+//! for accountable-safety analysis.
+
+use std::{
+	collections::{HashSet, VecDeque},
+	ops::RangeInclusive,
+	sync::Arc,
+};
+
+use parking_lot::RwLock;
+use sp_consensus_grandpa::{AuthorityId, AuthoritySignature, Equivocation, RoundNumber, SetId};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+
+use crate::{Message, Precommit, Prevote, SignedMessage};
+
+/// All the votes observed by the local voter in a single round.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRound<Block: BlockT> {
+	/// The authority set id the round belongs to.
+	pub set_id: SetId,
+	/// The round number.
+	pub number: RoundNumber,
+	/// The target block base used for voting in the round.
+	pub base: (Block::Hash, NumberFor<Block>),
+	/// All the votes observed in the round, in the order they were received.
+	pub votes: Vec<SignedMessage<Block::Header>>,
+}
+
+impl<Block: BlockT> RecordedRound<Block> {
+	/// Iterate over all the prevotes observed in the round.
+	pub fn prevotes(
+		&self,
+	) -> impl Iterator<Item = (&AuthorityId, &Prevote<Block::Header>, &AuthoritySignature)> {
+		self.votes.iter().filter_map(|vote| match vote.message {
+			Message::Prevote(ref prevote) => Some((&vote.id, prevote, &vote.signature)),
+			_ => None,
+		})
+	}
+
+	/// Iterate over all the precommits observed in the round.
+	pub fn precommits(
+		&self,
+	) -> impl Iterator<Item = (&AuthorityId, &Precommit<Block::Header>, &AuthoritySignature)> {
+		self.votes.iter().filter_map(|vote| match vote.message {
+			Message::Precommit(ref precommit) => Some((&vote.id, precommit, &vote.signature)),
+			_ => None,
+		})
+	}
+
+	/// Returns all the equivocations that can be proven with the votes
+	/// observed in the round, i.e. pairs of prevotes or precommits by the
+	/// same voter on different targets.
+	///
+	/// At most one equivocation is returned per voter, as a single one is enough to
+	/// report the voter for the round. Prevote equivocations take precedence.
+	pub fn equivocations(&self) -> Vec<Equivocation<Block::Hash, NumberFor<Block>>> {
+		let mut equivocations = Vec::new();
+		let mut equivocators = HashSet::new();
+
+		let prevotes = self.prevotes().collect::<Vec<_>>();
+		for (i, (id, first, first_signature)) in prevotes.iter().enumerate() {
+			if equivocators.contains(*id) {
+				continue
+			}
+			if let Some((_, second, second_signature)) =
+				prevotes[i + 1..].iter().find(|(other, vote, _)| other == id && vote != first)
+			{
+				equivocators.insert((*id).clone());
+				equivocations.push(Equivocation::Prevote(finality_grandpa::Equivocation {
+					round_number: self.number,
+					identity: (*id).clone(),
+					first: ((*first).clone(), (*first_signature).clone()),
+					second: ((*second).clone(), (*second_signature).clone()),
+				}));
+			}
+		}
+
+		let precommits = self.precommits().collect::<Vec<_>>();
+		for (i, (id, first, first_signature)) in precommits.iter().enumerate() {
+			if equivocators.contains(*id) {
+				continue
+			}
+			if let Some((_, second, second_signature)) =
+				precommits[i + 1..].iter().find(|(other, vote, _)| other == id && vote != first)
+			{
+				equivocators.insert((*id).clone());
+				equivocations.push(Equivocation::Precommit(finality_grandpa::Equivocation {
+					round_number: self.number,
+					identity: (*id).clone(),
+					first: ((*first).clone(), (*first_signature).clone()),
+					second: ((*second).clone(), (*second_signature).clone()),
+				}));
+			}
+		}
+
+		equivocations
+	}
+}
+
+/// Bounded in-memory history of the votes observed by the local voter.
+///
+/// Rounds are kept ordered by `(set_id, round)` and the oldest round is evicted
+/// once more than `max_rounds` rounds have been recorded. A history created with
+/// [`SharedVoteHistory::disabled`] doesn't record anything.
+pub struct SharedVoteHistory<Block: BlockT> {
+	inner: Arc<RwLock<VecDeque<RecordedRound<Block>>>>,
+	max_rounds: usize,
+}
+
+impl<Block: BlockT> Clone for SharedVoteHistory<Block> {
+	fn clone(&self) -> Self {
+		SharedVoteHistory { inner: self.inner.clone(), max_rounds: self.max_rounds }
+	}
+}
+
+impl<Block: BlockT> SharedVoteHistory<Block> {
+	/// Create a new vote history recording all the votes of the last `max_rounds` rounds.
+	pub fn new(max_rounds: usize) -> Self {
+		SharedVoteHistory {
+			inner: Arc::new(RwLock::new(VecDeque::with_capacity(max_rounds))),
+			max_rounds,
+		}
+	}
+
+	/// Create a vote history that doesn't record any votes.
+	pub fn disabled() -> Self {
+		Self::new(0)
+	}
+
+	/// Whether votes are being recorded.
+	pub fn is_enabled(&self) -> bool {
+		self.max_rounds > 0
+	}
+
+	/// The maximum number of rounds kept in the history.
+	pub fn max_rounds(&self) -> usize {
+		self.max_rounds
+	}
+
+	/// Record the votes observed in the given round. If the round has already
+	/// been recorded its votes are replaced, the GRANDPA voter always reports a
+	/// superset of the previously reported votes for a round.
+	pub(crate) fn record(
+		&self,
+		set_id: SetId,
+		number: RoundNumber,
+		base: (Block::Hash, NumberFor<Block>),
+		votes: &[SignedMessage<Block::Header>],
+	) {
+		if !self.is_enabled() {
+			return
+		}
+
+		let mut rounds = self.inner.write();
+		let key = (set_id, number);
+
+		match rounds.binary_search_by_key(&key, |round| (round.set_id, round.number)) {
+			Ok(idx) => rounds[idx].votes = votes.to_vec(),
+			Err(idx) => {
+				// rounds older than everything we keep would be evicted right away.
+				if idx == 0 && rounds.len() >= self.max_rounds {
+					return
+				}
+
+				rounds.insert(idx, RecordedRound { set_id, number, base, votes: votes.to_vec() });
+				while rounds.len() > self.max_rounds {
+					rounds.pop_front();
+				}
+			},
+		}
+	}
+
+	/// Returns the recorded rounds of the given set whose number is within `range`,
+	/// ordered by round number.
+	pub fn rounds(
+		&self,
+		set_id: SetId,
+		range: RangeInclusive<RoundNumber>,
+	) -> Vec<RecordedRound<Block>> {
+		self.inner
+			.read()
+			.iter()
+			.filter(|round| round.set_id == set_id && range.contains(&round.number))
+			.cloned()
+			.collect()
+	}
+
+	/// Returns all the equivocations that can be proven with the votes recorded for
+	/// the given set, alongside the round they happened in.
+	pub fn equivocations(
+		&self,
+		set_id: SetId,
+	) -> Vec<(RoundNumber, Equivocation<Block::Hash, NumberFor<Block>>)> {
+		self.inner
+			.read()
+			.iter()
+			.filter(|round| round.set_id == set_id)
+			.flat_map(|round| {
+				round
+					.equivocations()
+					.into_iter()
+					.map(move |equivocation| (round.number, equivocation))
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use sp_core::H256;
+	use sp_keyring::Ed25519Keyring;
+	use substrate_test_runtime_client::runtime::Block;
+
+	const VOTERS: [Ed25519Keyring; 2] = [Ed25519Keyring::Alice, Ed25519Keyring::Bob];
+
+	fn voter(n: usize) -> AuthorityId {
+		VOTERS[n].public().into()
+	}
+
+	fn signed(
+		n: usize,
+		message: Message<<Block as BlockT>::Header>,
+	) -> SignedMessage<<Block as BlockT>::Header> {
+		let payload = sp_consensus_grandpa::localized_payload(1, 0, &message);
+		SignedMessage { message, signature: VOTERS[n].sign(&payload[..]).into(), id: voter(n) }
+	}
+
+	fn prevote(n: usize, target: u64) -> SignedMessage<<Block as BlockT>::Header> {
+		signed(
+			n,
+			Message::Prevote(finality_grandpa::Prevote::new(H256::from_low_u64_be(target), target)),
+		)
+	}
+
+	fn precommit(n: usize, target: u64) -> SignedMessage<<Block as BlockT>::Header> {
+		signed(
+			n,
+			Message::Precommit(finality_grandpa::Precommit::new(
+				H256::from_low_u64_be(target),
+				target,
+			)),
+		)
+	}
+
+	#[test]
+	fn disabled_history_does_not_record() {
+		let history = SharedVoteHistory::<Block>::disabled();
+		history.record(0, 1, (H256::zero(), 0), &[prevote(0, 1)]);
+
+		assert!(history.rounds(0, 0..=u64::MAX).is_empty());
+	}
+
+	#[test]
+	fn evicts_oldest_rounds() {
+		let history = SharedVoteHistory::<Block>::new(2);
+
+		history.record(0, 1, (H256::zero(), 0), &[prevote(0, 1)]);
+		history.record(0, 2, (H256::zero(), 0), &[prevote(0, 2)]);
+		// a new set starts, rounds are ordered by set id first.
+		history.record(1, 1, (H256::zero(), 0), &[prevote(0, 3)]);
+
+		assert!(history.rounds(0, 1..=1).is_empty());
+		assert_eq!(history.rounds(0, 1..=2).len(), 1);
+		assert_eq!(history.rounds(1, 1..=1).len(), 1);
+
+		// rounds older than the retained window are dropped straight away.
+		history.record(0, 1, (H256::zero(), 0), &[prevote(0, 1)]);
+		assert!(history.rounds(0, 1..=1).is_empty());
+	}
+
+	#[test]
+	fn re_recording_a_round_replaces_its_votes() {
+		let history = SharedVoteHistory::<Block>::new(4);
+
+		history.record(0, 1, (H256::zero(), 0), &[prevote(0, 1)]);
+		history.record(0, 1, (H256::zero(), 0), &[prevote(0, 1), precommit(0, 1)]);
+
+		let rounds = history.rounds(0, 1..=1);
+		assert_eq!(rounds.len(), 1);
+		assert_eq!(rounds[0].prevotes().count(), 1);
+		assert_eq!(rounds[0].precommits().count(), 1);
+	}
+
+	#[test]
+	fn detects_equivocations() {
+		let history = SharedVoteHistory::<Block>::new(4);
+
+		history.record(
+			0,
+			1,
+			(H256::zero(), 0),
+			&[
+				prevote(0, 1),
+				prevote(1, 1),
+				prevote(0, 2),
+				precommit(0, 1),
+				precommit(1, 1),
+				precommit(1, 3),
+			],
+		);
+
+		let equivocations = history.equivocations(0);
+		assert_eq!(equivocations.len(), 2);
+
+		assert_matches!(
+			&equivocations[0],
+			(1, Equivocation::Prevote(e)) if e.identity == voter(0)
+		);
+		assert_matches!(
+			&equivocations[1],
+			(1, Equivocation::Precommit(e)) if e.identity == voter(1)
+		);
+
+		assert!(history.equivocations(1).is_empty());
+	}
+
+	#[test]
+	fn reports_each_equivocator_once_per_round() {
+		let history = SharedVoteHistory::<Block>::new(4);
+
+		history.record(
+			0,
+			1,
+			(H256::zero(), 0),
+			&[
+				prevote(0, 1),
+				prevote(0, 2),
+				prevote(0, 1),
+				prevote(0, 3),
+				precommit(0, 1),
+				precommit(0, 2),
+				precommit(1, 1),
+				precommit(1, 2),
+				precommit(1, 3),
+			],
+		);
+		history.record(0, 2, (H256::zero(), 0), &[prevote(0, 1), prevote(0, 2)]);
+
+		let equivocations = history.equivocations(0);
+		assert_eq!(equivocations.len(), 3);
+
+		assert_matches!(
+			&equivocations[0],
+			(1, Equivocation::Prevote(e)) if e.identity == voter(0)
+		);
+		assert_matches!(
+			&equivocations[1],
+			(1, Equivocation::Precommit(e)) if e.identity == voter(1)
+		);
+		assert_matches!(
+			&equivocations[2],
+			(2, Equivocation::Prevote(e)) if e.identity == voter(0)
+		);
+	}
+}
@@ -0,0 +1,154 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Driving slot based consensus engines with an [`InherentDataProviderRegistry`].
+
+use crate::InherentDataProviderExt;
+use sp_consensus_slots::Slot;
+use sp_inherents::{
+	CreateInherentDataProviders, Error, InherentData, InherentDataProvider,
+	InherentDataProviderRegistry, InherentIdentifier, RegisteredInherentDataProviders,
+};
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// An [`InherentDataProviderRegistry`] that provides the slot of a slot based consensus engine.
+///
+/// The slot is read from the inherent data of the registered providers, under the inherent
+/// identifier of the consensus engine, e.g. `sp_consensus_aura::inherents::INHERENT_IDENTIFIER`.
+pub struct SlotInherentDataProviderRegistry<Block: BlockT, ExtraArgs> {
+	registry: Arc<InherentDataProviderRegistry<Block, ExtraArgs>>,
+	slot_identifier: InherentIdentifier,
+}
+
+impl<Block: BlockT, ExtraArgs> Clone for SlotInherentDataProviderRegistry<Block, ExtraArgs> {
+	fn clone(&self) -> Self {
+		Self { registry: self.registry.clone(), slot_identifier: self.slot_identifier }
+	}
+}
+
+impl<Block: BlockT, ExtraArgs> SlotInherentDataProviderRegistry<Block, ExtraArgs> {
+	/// Read the slot provided by the providers of `registry` under `slot_identifier`.
+	pub fn new(
+		registry: InherentDataProviderRegistry<Block, ExtraArgs>,
+		slot_identifier: InherentIdentifier,
+	) -> Self {
+		Self { registry: Arc::new(registry), slot_identifier }
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, ExtraArgs> CreateInherentDataProviders<Block, ExtraArgs>
+	for SlotInherentDataProviderRegistry<Block, ExtraArgs>
+where
+	Block: BlockT,
+	ExtraArgs: Clone + Send + 'static,
+{
+	type InherentDataProviders = SlotInherentDataProviders;
+
+	async fn create_inherent_data_providers(
+		&self,
+		parent: Block::Hash,
+		extra_args: ExtraArgs,
+	) -> Result<Self::InherentDataProviders, Box<dyn std::error::Error + Send + Sync>> {
+		let providers = self.registry.create_inherent_data_providers(parent, extra_args).await?;
+		let slot = providers
+			.inherent_data()
+			.get_data::<Slot>(&self.slot_identifier)?
+			.ok_or("No registered inherent data provider provides the slot")?;
+
+		Ok(SlotInherentDataProviders { slot, providers })
+	}
+}
+
+/// The inherent data providers created by a [`SlotInherentDataProviderRegistry`].
+pub struct SlotInherentDataProviders {
+	slot: Slot,
+	providers: RegisteredInherentDataProviders,
+}
+
+impl InherentDataProviderExt for SlotInherentDataProviders {
+	fn slot(&self) -> Slot {
+		self.slot
+	}
+}
+
+#[async_trait::async_trait]
+impl InherentDataProvider for SlotInherentDataProviders {
+	async fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), Error> {
+		self.providers.provide_inherent_data(inherent_data).await
+	}
+
+	async fn try_handle_error(
+		&self,
+		identifier: &InherentIdentifier,
+		error: &[u8],
+	) -> Option<Result<(), Error>> {
+		self.providers.try_handle_error(identifier, error).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use substrate_test_runtime_client::runtime::Block;
+
+	const SLOT: InherentIdentifier = *b"testslot";
+
+	struct SlotProvider(Slot);
+
+	#[async_trait::async_trait]
+	impl InherentDataProvider for SlotProvider {
+		async fn provide_inherent_data(&self, data: &mut InherentData) -> Result<(), Error> {
+			data.put_data(SLOT, &self.0)
+		}
+
+		async fn try_handle_error(
+			&self,
+			_: &InherentIdentifier,
+			_: &[u8],
+		) -> Option<Result<(), Error>> {
+			None
+		}
+	}
+
+	fn registry(slot: u64) -> InherentDataProviderRegistry<Block, ()> {
+		InherentDataProviderRegistry::builder()
+			.register("slot", &[], move |_, (), _| async move {
+				Ok::<_, Box<dyn std::error::Error + Send + Sync>>(SlotProvider(slot.into()))
+			})
+			.build()
+			.unwrap()
+	}
+
+	#[test]
+	fn slot_is_read_from_the_registered_providers() {
+		let providers = futures::executor::block_on(
+			SlotInherentDataProviderRegistry::new(registry(42), SLOT)
+				.create_inherent_data_providers(Default::default(), ()),
+		)
+		.unwrap();
+		assert_eq!(providers.slot(), Slot::from(42));
+
+		let missing = futures::executor::block_on(
+			SlotInherentDataProviderRegistry::new(registry(42), *b"missslot")
+				.create_inherent_data_providers(Default::default(), ()),
+		);
+		assert!(missing.is_err());
+	}
+}
@@ -26,9 +26,11 @@
 #![warn(missing_docs)]
 
 mod aux_schema;
+mod inherents;
 mod slots;
 
 pub use aux_schema::{check_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND};
+pub use inherents::{SlotInherentDataProviderRegistry, SlotInherentDataProviders};
 use slots::Slots;
 pub use slots::{time_until_next_slot, SlotInfo};
 
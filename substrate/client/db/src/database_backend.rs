@@ -0,0 +1,200 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Pluggable key-value stores for the client database.
+//!
+//! RocksDB and ParityDB are built in. Any other store is plugged in by implementing
+//! [`DatabaseBackend`] and configuring the client with
+//! [`DatabaseSource::Backend`](crate::DatabaseSource::Backend). The backend is asked to open the
+//! store at the path of the database, given the layout of the columns. The client then only
+//! accesses the store through the object-safe column API of [`Database`].
+
+use crate::{columns, utils::NUM_COLUMNS, DbHash};
+use sp_database::{ColumnId, Database};
+use std::{fmt, path::Path, sync::Arc};
+
+/// Description of a column of the client database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnInfo {
+	/// The identifier of the column.
+	pub id: ColumnId,
+	/// Whether the values of the column are reference counted.
+	///
+	/// The client uses [`Transaction::store`](sp_database::Transaction::store),
+	/// [`reference`](sp_database::Transaction::reference) and
+	/// [`release`](sp_database::Transaction::release) on these columns, keyed by the hash of the
+	/// value. Backends reporting [`Database::supports_ref_counting`] must count them.
+	pub ref_counted: bool,
+	/// Whether the column holds values that are worth compressing.
+	pub compressed: bool,
+}
+
+/// The layout of the columns of the client database.
+pub(crate) fn column_layout() -> Vec<ColumnInfo> {
+	let ref_counted = [columns::STATE, columns::TRANSACTION];
+	let compressed = [
+		columns::STATE,
+		columns::HEADER,
+		columns::BODY,
+		columns::BODY_INDEX,
+		columns::TRANSACTION,
+		columns::JUSTIFICATIONS,
+	];
+
+	(0..NUM_COLUMNS)
+		.map(|id| ColumnInfo {
+			id,
+			ref_counted: ref_counted.contains(&id),
+			compressed: compressed.contains(&id),
+		})
+		.collect()
+}
+
+/// Configuration given to a [`DatabaseBackend`] to open a store.
+#[derive(Debug)]
+pub struct DatabaseBackendConfig<'a> {
+	/// Path to the store.
+	pub path: &'a Path,
+	/// Whether the store should be created if it doesn't exist.
+	pub create: bool,
+	/// Cache size in MiB, the backend is free to ignore it.
+	pub cache_size: usize,
+	/// The columns of the store, indexed by their identifier.
+	pub columns: &'a [ColumnInfo],
+}
+
+/// Error opening a store with a [`DatabaseBackend`].
+#[derive(Debug)]
+pub enum DatabaseBackendError {
+	/// There is no store at the given path and it wasn't asked to create one.
+	DoesNotExist,
+	/// Any other error.
+	Other(String),
+}
+
+impl fmt::Display for DatabaseBackendError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::DoesNotExist => write!(f, "Database does not exist at given location"),
+			Self::Other(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+/// A key-value store the client database can be kept in.
+pub trait DatabaseBackend: Send + Sync {
+	/// Name of the backend, used in the logs.
+	fn name(&self) -> &str;
+
+	/// Open the store described by `config`.
+	fn open(
+		&self,
+		config: &DatabaseBackendConfig,
+	) -> Result<Arc<dyn Database<DbHash>>, DatabaseBackendError>;
+}
+
+impl fmt::Debug for dyn DatabaseBackend {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DatabaseBackend({})", self.name())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		utils::{open_database, DatabaseType, OpenDbError},
+		DatabaseSource,
+	};
+	use parking_lot::Mutex;
+	use sp_database::MemDb;
+	use std::path::PathBuf;
+	use substrate_test_runtime_client::runtime::Block;
+
+	#[derive(Default)]
+	struct MemBackend {
+		db: Mutex<Option<Arc<MemDb>>>,
+		opened: Mutex<Vec<(PathBuf, bool, usize)>>,
+	}
+
+	impl DatabaseBackend for MemBackend {
+		fn name(&self) -> &str {
+			"mem"
+		}
+
+		fn open(
+			&self,
+			config: &DatabaseBackendConfig,
+		) -> Result<Arc<dyn Database<DbHash>>, DatabaseBackendError> {
+			assert_eq!(config.columns.len(), NUM_COLUMNS as usize);
+			self.opened.lock().push((config.path.into(), config.create, config.cache_size));
+
+			let mut db = self.db.lock();
+			match &*db {
+				Some(db) => Ok(db.clone()),
+				None if config.create => Ok(db.insert(Arc::new(MemDb::new())).clone()),
+				None => Err(DatabaseBackendError::DoesNotExist),
+			}
+		}
+	}
+
+	#[test]
+	fn custom_backend_is_opened() {
+		let backend = Arc::new(MemBackend::default());
+		let source = DatabaseSource::Backend {
+			backend: backend.clone(),
+			path: "/db/full".into(),
+			cache_size: 128,
+		};
+
+		assert!(matches!(
+			open_database::<Block>(&source, DatabaseType::Full, false),
+			Err(OpenDbError::DoesNotExist)
+		));
+
+		let db = open_database::<Block>(&source, DatabaseType::Full, true).unwrap();
+		assert_eq!(
+			db.get(crate::utils::COLUMN_META, crate::utils::meta_keys::TYPE),
+			Some(b"full".to_vec())
+		);
+		// Opening again gives the same store.
+		open_database::<Block>(&source, DatabaseType::Full, false).unwrap();
+
+		assert_eq!(
+			*backend.opened.lock(),
+			vec![
+				("/db/full".into(), false, 128),
+				("/db/full".into(), true, 128),
+				("/db/full".into(), false, 128),
+			]
+		);
+		assert_eq!(source.path(), Some(Path::new("/db/full")));
+		assert_eq!(source.to_string(), "mem");
+	}
+
+	#[test]
+	fn column_layout_matches_columns() {
+		let layout = column_layout();
+
+		assert!(layout.iter().enumerate().all(|(i, column)| column.id == i as ColumnId));
+		assert_eq!(
+			layout.iter().filter(|c| c.ref_counted).map(|c| c.id).collect::<Vec<_>>(),
+			vec![columns::STATE, columns::TRANSACTION]
+		);
+	}
+}
@@ -33,6 +33,7 @@ pub mod offchain;
 pub mod bench;
 
 mod children;
+mod database_backend;
 mod parity_db;
 mod pinned_blocks_cache;
 mod record_stats_state;
@@ -95,6 +96,9 @@ use sp_trie::{cache::SharedTrieCache, prefixed_key, MemoryDB, MerkleValue, Prefi
 use utils::BLOCK_GAP_CURRENT_VERSION;
 
 // Re-export the Database trait so that one can pass an implementation of it.
+pub use database_backend::{
+	ColumnInfo, DatabaseBackend, DatabaseBackendConfig, DatabaseBackendError,
+};
 pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
 
@@ -371,6 +375,16 @@ pub enum DatabaseSource {
 		/// if set, the `create` flag will be required to open such datasource
 		require_create_flag: bool,
 	},
+
+	/// Open the database at a given path with a custom [`DatabaseBackend`].
+	Backend {
+		/// The backend opening the database.
+		backend: Arc<dyn DatabaseBackend>,
+		/// Path to the database.
+		path: PathBuf,
+		/// Cache size in MiB, passed to the backend.
+		cache_size: usize,
+	},
 }
 
 impl DatabaseSource {
@@ -386,6 +400,7 @@ impl DatabaseSource {
 			DatabaseSource::RocksDb { path, .. } => Some(path),
 			DatabaseSource::ParityDb { path } => Some(path),
 			DatabaseSource::Custom { .. } => None,
+			DatabaseSource::Backend { path, .. } => Some(path),
 		}
 	}
 
@@ -406,6 +421,10 @@ impl DatabaseSource {
 				true
 			},
 			DatabaseSource::Custom { .. } => false,
+			DatabaseSource::Backend { ref mut path, .. } => {
+				*path = p.into();
+				true
+			},
 		}
 	}
 }
@@ -418,6 +437,7 @@ impl std::fmt::Display for DatabaseSource {
 			DatabaseSource::RocksDb { .. } => "RocksDb",
 			DatabaseSource::ParityDb { .. } => "ParityDb",
 			DatabaseSource::Custom { .. } => "Custom",
+			DatabaseSource::Backend { backend, .. } => backend.name(),
 		};
 		write!(f, "{}", name)
 	}
@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 use crate::{
 	columns,
+	database_backend::column_layout,
 	utils::{DatabaseType, NUM_COLUMNS},
 };
 /// A `Database` adapter for parity-db.
@@ -43,31 +44,19 @@ pub fn open<H: Clone + AsRef<[u8]>>(
 	let mut config = parity_db::Options::with_columns(path, NUM_COLUMNS as u8);
 
 	match db_type {
-		DatabaseType::Full => {
-			let compressed = [
-				columns::STATE,
-				columns::HEADER,
-				columns::BODY,
-				columns::BODY_INDEX,
-				columns::TRANSACTION,
-				columns::JUSTIFICATIONS,
-			];
-
-			for i in compressed {
-				let column = &mut config.columns[i as usize];
-				column.compression = parity_db::CompressionType::Lz4;
-			}
-
-			let state_col = &mut config.columns[columns::STATE as usize];
-			state_col.ref_counted = true;
-			state_col.preimage = true;
-			state_col.uniform = true;
-
-			let tx_col = &mut config.columns[columns::TRANSACTION as usize];
-			tx_col.ref_counted = true;
-			tx_col.preimage = true;
-			tx_col.uniform = true;
-		},
+		DatabaseType::Full =>
+			for info in column_layout() {
+				let column = &mut config.columns[info.id as usize];
+				if info.compressed {
+					column.compression = parity_db::CompressionType::Lz4;
+				}
+				// Ref counted values are stored by the hash of their preimage.
+				if info.ref_counted {
+					column.ref_counted = true;
+					column.preimage = true;
+					column.uniform = true;
+				}
+			},
 	}
 
 	if upgrade {
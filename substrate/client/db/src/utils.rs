@@ -210,6 +210,15 @@ fn open_database_at<Block: BlockT>(
 			}
 			db.clone()
 		},
+		DatabaseSource::Backend { backend, path, cache_size } => {
+			let columns = crate::database_backend::column_layout();
+			backend.open(&crate::DatabaseBackendConfig {
+				path,
+				create,
+				cache_size: *cache_size,
+				columns: &columns,
+			})?
+		},
 		DatabaseSource::Auto { paritydb_path, rocksdb_path, cache_size } => {
 			// check if rocksdb exists first, if not, open paritydb
 			match open_kvdb_rocksdb::<Block>(rocksdb_path, db_type, false, *cache_size) {
@@ -270,6 +279,15 @@ impl From<OpenDbError> for sp_blockchain::Error {
 	}
 }
 
+impl From<crate::DatabaseBackendError> for OpenDbError {
+	fn from(err: crate::DatabaseBackendError) -> Self {
+		match err {
+			crate::DatabaseBackendError::DoesNotExist => OpenDbError::DoesNotExist,
+			crate::DatabaseBackendError::Other(e) => OpenDbError::Internal(e),
+		}
+	}
+}
+
 impl From<parity_db::Error> for OpenDbError {
 	fn from(err: parity_db::Error) -> Self {
 		if matches!(err, parity_db::Error::DatabaseNotFound) {
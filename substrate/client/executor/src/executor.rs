@@ -18,11 +18,13 @@
 
 use crate::{
 	error::{Error, Result},
+	memory_watermark::{MemoryWatermarks, RuntimeCallCategory},
 	wasm_runtime::{RuntimeCache, WasmExecutionMethod},
 	RuntimeVersionOf,
 };
 
 use std::{
+	collections::HashMap,
 	marker::PhantomData,
 	panic::{AssertUnwindSafe, UnwindSafe},
 	path::PathBuf,
@@ -82,17 +84,35 @@ fn unwrap_heap_pages(pages: Option<HeapAllocStrategy>) -> HeapAllocStrategy {
 	pages.unwrap_or_else(|| DEFAULT_HEAP_ALLOC_STRATEGY)
 }
 
+/// Heap allocation strategies of the calls into one runtime, overriding the ones of the
+/// [`WasmExecutor`].
+///
+/// Registered with [`WasmExecutorBuilder::with_runtime_heap_alloc_strategy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeHeapAllocStrategy {
+	/// The strategy of the onchain calls.
+	///
+	/// Takes precedence over the heap pages set on chain. Nodes executing blocks with a bigger
+	/// heap than the rest of the network may import blocks that the others reject, so this
+	/// should only be used for runtimes that are known to need it.
+	pub onchain: Option<HeapAllocStrategy>,
+	/// The strategy of the offchain calls.
+	pub offchain: Option<HeapAllocStrategy>,
+}
+
 /// Builder for creating a [`WasmExecutor`] instance.
 pub struct WasmExecutorBuilder<H = sp_io::SubstrateHostFunctions> {
 	_phantom: PhantomData<H>,
 	method: WasmExecutionMethod,
 	onchain_heap_alloc_strategy: Option<HeapAllocStrategy>,
 	offchain_heap_alloc_strategy: Option<HeapAllocStrategy>,
+	runtime_heap_alloc_strategies: HashMap<Vec<u8>, RuntimeHeapAllocStrategy>,
 	ignore_onchain_heap_pages: bool,
 	max_runtime_instances: usize,
 	cache_path: Option<PathBuf>,
 	allow_missing_host_functions: bool,
 	runtime_cache_size: u8,
+	memory_watermarks: MemoryWatermarks,
 }
 
 impl<H> WasmExecutorBuilder<H> {
@@ -105,11 +125,13 @@ impl<H> WasmExecutorBuilder<H> {
 			method: WasmExecutionMethod::default(),
 			onchain_heap_alloc_strategy: None,
 			offchain_heap_alloc_strategy: None,
+			runtime_heap_alloc_strategies: HashMap::new(),
 			ignore_onchain_heap_pages: false,
 			max_runtime_instances: 2,
 			runtime_cache_size: 4,
 			allow_missing_host_functions: false,
 			cache_path: None,
+			memory_watermarks: MemoryWatermarks::new(),
 		}
 	}
 
@@ -139,6 +161,23 @@ impl<H> WasmExecutorBuilder<H> {
 		self
 	}
 
+	/// Create the wasm executor with the given heap allocation `strategy` for the runtime with
+	/// the given `code_hash`.
+	///
+	/// The `code_hash` is the [`RuntimeCode::hash`] of the runtime. The strategies set in
+	/// `strategy` are used for the calls into this runtime, instead of the ones of the executor.
+	/// Registering a strategy again for the same runtime replaces the previous one.
+	///
+	/// By default all the runtimes use the strategies of the executor.
+	pub fn with_runtime_heap_alloc_strategy(
+		mut self,
+		code_hash: impl Into<Vec<u8>>,
+		strategy: RuntimeHeapAllocStrategy,
+	) -> Self {
+		self.runtime_heap_alloc_strategies.insert(code_hash.into(), strategy);
+		self
+	}
+
 	/// Create the wasm executor and follow/ignore onchain heap pages value.
 	///
 	/// By default this the onchain heap pages value is followed.
@@ -193,6 +232,17 @@ impl<H> WasmExecutorBuilder<H> {
 		self
 	}
 
+	/// Create the wasm executor with the given `memory_watermarks`.
+	///
+	/// The peak memory used by every runtime call is recorded in `memory_watermarks`. This allows
+	/// sharing the record with e.g. the RPC layer before the executor is moved into the client.
+	///
+	/// By default a new empty record is used.
+	pub fn with_memory_watermarks(mut self, memory_watermarks: MemoryWatermarks) -> Self {
+		self.memory_watermarks = memory_watermarks;
+		self
+	}
+
 	/// Build the configured [`WasmExecutor`].
 	pub fn build(self) -> WasmExecutor<H> {
 		WasmExecutor {
@@ -203,6 +253,7 @@ impl<H> WasmExecutorBuilder<H> {
 			default_onchain_heap_alloc_strategy: unwrap_heap_pages(
 				self.onchain_heap_alloc_strategy,
 			),
+			runtime_heap_alloc_strategies: Arc::new(self.runtime_heap_alloc_strategies),
 			ignore_onchain_heap_pages: self.ignore_onchain_heap_pages,
 			cache: Arc::new(RuntimeCache::new(
 				self.max_runtime_instances,
@@ -211,6 +262,7 @@ impl<H> WasmExecutorBuilder<H> {
 			)),
 			cache_path: self.cache_path,
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			memory_watermarks: self.memory_watermarks,
 			phantom: PhantomData,
 		}
 	}
@@ -225,6 +277,8 @@ pub struct WasmExecutor<H = sp_io::SubstrateHostFunctions> {
 	default_onchain_heap_alloc_strategy: HeapAllocStrategy,
 	/// The heap allocation strategy for offchain Wasm calls.
 	default_offchain_heap_alloc_strategy: HeapAllocStrategy,
+	/// The heap allocation strategies overridden per runtime, by code hash.
+	runtime_heap_alloc_strategies: Arc<HashMap<Vec<u8>, RuntimeHeapAllocStrategy>>,
 	/// Ignore onchain heap pages value.
 	ignore_onchain_heap_pages: bool,
 	/// WASM runtime cache.
@@ -234,6 +288,8 @@ pub struct WasmExecutor<H = sp_io::SubstrateHostFunctions> {
 	cache_path: Option<PathBuf>,
 	/// Ignore missing function imports.
 	allow_missing_host_functions: bool,
+	/// Peak memory used by the runtime calls.
+	memory_watermarks: MemoryWatermarks,
 	phantom: PhantomData<H>,
 }
 
@@ -243,10 +299,12 @@ impl<H> Clone for WasmExecutor<H> {
 			method: self.method,
 			default_onchain_heap_alloc_strategy: self.default_onchain_heap_alloc_strategy,
 			default_offchain_heap_alloc_strategy: self.default_offchain_heap_alloc_strategy,
+			runtime_heap_alloc_strategies: self.runtime_heap_alloc_strategies.clone(),
 			ignore_onchain_heap_pages: self.ignore_onchain_heap_pages,
 			cache: self.cache.clone(),
 			cache_path: self.cache_path.clone(),
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			memory_watermarks: self.memory_watermarks.clone(),
 			phantom: self.phantom,
 		}
 	}
@@ -293,6 +351,7 @@ impl<H> WasmExecutor<H> {
 			default_offchain_heap_alloc_strategy: unwrap_heap_pages(
 				default_heap_pages.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ }),
 			),
+			runtime_heap_alloc_strategies: Default::default(),
 			ignore_onchain_heap_pages: false,
 			cache: Arc::new(RuntimeCache::new(
 				max_runtime_instances,
@@ -301,6 +360,7 @@ impl<H> WasmExecutor<H> {
 			)),
 			cache_path,
 			allow_missing_host_functions: false,
+			memory_watermarks: MemoryWatermarks::new(),
 			phantom: PhantomData,
 		}
 	}
@@ -310,6 +370,40 @@ impl<H> WasmExecutor<H> {
 		WasmExecutorBuilder::new()
 	}
 
+	/// Returns the peak memory used by the runtime calls executed so far.
+	pub fn memory_watermarks(&self) -> &MemoryWatermarks {
+		&self.memory_watermarks
+	}
+
+	/// Returns the heap allocation strategy of a call into `runtime_code` in the given `context`.
+	fn heap_alloc_strategy(
+		&self,
+		runtime_code: &RuntimeCode,
+		context: CallContext,
+	) -> HeapAllocStrategy {
+		let overridden = self.runtime_heap_alloc_strategies.get(&runtime_code.hash);
+
+		match context {
+			CallContext::Offchain => overridden
+				.and_then(|strategy| strategy.offchain)
+				.unwrap_or(self.default_offchain_heap_alloc_strategy),
+			CallContext::Onchain => {
+				if let Some(strategy) = overridden.and_then(|strategy| strategy.onchain) {
+					return strategy
+				}
+
+				if self.ignore_onchain_heap_pages {
+					self.default_onchain_heap_alloc_strategy
+				} else {
+					runtime_code
+						.heap_pages
+						.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
+						.unwrap_or_else(|| self.default_onchain_heap_alloc_strategy)
+				}
+			},
+		}
+	}
+
 	/// Ignore missing function imports if set true.
 	#[deprecated(note = "use `Self::builder` method instead of it")]
 	pub fn allow_missing_host_functions(&mut self, allow_missing_host_functions: bool) {
@@ -367,6 +461,33 @@ where
 		}
 	}
 
+	/// Call `method` on the given `instance` and record its memory usage.
+	fn call_instance(
+		&self,
+		instance: &mut dyn WasmInstance,
+		ext: &mut dyn Externalities,
+		method: &str,
+		data: &[u8],
+		context: CallContext,
+	) -> Result<Result<Vec<u8>>> {
+		let mut allocation_stats = None;
+
+		let mut instance = AssertUnwindSafe(instance);
+		let mut allocation_stats_out = AssertUnwindSafe(&mut allocation_stats);
+		let result = with_externalities_safe(ext, move || {
+			let (result, allocation_stats) = instance.call_with_allocation_stats(method, data);
+			**allocation_stats_out = allocation_stats;
+			result
+		});
+
+		if let Some(allocation_stats) = allocation_stats {
+			self.memory_watermarks
+				.record(RuntimeCallCategory::classify(method, context), &allocation_stats);
+		}
+
+		result
+	}
+
 	/// Perform a call into the given runtime.
 	///
 	/// The runtime is passed as a [`RuntimeBlob`]. The runtime will be instantiated with the
@@ -508,26 +629,12 @@ where
 			"Executing function",
 		);
 
-		let on_chain_heap_alloc_strategy = if self.ignore_onchain_heap_pages {
-			self.default_onchain_heap_alloc_strategy
-		} else {
-			runtime_code
-				.heap_pages
-				.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
-				.unwrap_or_else(|| self.default_onchain_heap_alloc_strategy)
-		};
-
-		let heap_alloc_strategy = match context {
-			CallContext::Offchain => self.default_offchain_heap_alloc_strategy,
-			CallContext::Onchain => on_chain_heap_alloc_strategy,
-		};
-
 		let result = self.with_instance(
 			runtime_code,
 			ext,
-			heap_alloc_strategy,
+			self.heap_alloc_strategy(runtime_code, context),
 			|_, mut instance, _on_chain_version, mut ext| {
-				with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+				self.call_instance(&mut **instance, &mut **ext, method, data, context)
 			},
 		);
 
@@ -544,19 +651,10 @@ where
 		ext: &mut dyn Externalities,
 		runtime_code: &RuntimeCode,
 	) -> Result<RuntimeVersion> {
-		let on_chain_heap_pages = if self.ignore_onchain_heap_pages {
-			self.default_onchain_heap_alloc_strategy
-		} else {
-			runtime_code
-				.heap_pages
-				.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
-				.unwrap_or_else(|| self.default_onchain_heap_alloc_strategy)
-		};
-
 		self.with_instance(
 			runtime_code,
 			ext,
-			on_chain_heap_pages,
+			self.heap_alloc_strategy(runtime_code, CallContext::Onchain),
 			|_module, _instance, version, _ext| {
 				Ok(version.cloned().ok_or_else(|| Error::ApiError("Unknown version".into())))
 			},
@@ -678,25 +776,11 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 			"Executing function",
 		);
 
-		let on_chain_heap_alloc_strategy = if self.wasm.ignore_onchain_heap_pages {
-			self.wasm.default_onchain_heap_alloc_strategy
-		} else {
-			runtime_code
-				.heap_pages
-				.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
-				.unwrap_or_else(|| self.wasm.default_onchain_heap_alloc_strategy)
-		};
-
-		let heap_alloc_strategy = match context {
-			CallContext::Offchain => self.wasm.default_offchain_heap_alloc_strategy,
-			CallContext::Onchain => on_chain_heap_alloc_strategy,
-		};
-
 		let mut used_native = false;
 		let result = self.wasm.with_instance(
 			runtime_code,
 			ext,
-			heap_alloc_strategy,
+			self.wasm.heap_alloc_strategy(runtime_code, context),
 			|_, mut instance, on_chain_version, mut ext| {
 				let on_chain_version =
 					on_chain_version.ok_or_else(|| Error::ApiError("Unknown version".into()))?;
@@ -725,7 +809,7 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 						);
 					}
 
-					with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+					self.wasm.call_instance(&mut **instance, &mut **ext, method, data, context)
 				}
 			},
 		);
@@ -806,4 +890,82 @@ mod tests {
 
 		my_interface::say_hello_world("hey");
 	}
+
+	#[test]
+	fn runtime_calls_update_memory_watermarks() {
+		let memory_watermarks = MemoryWatermarks::new();
+		let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+			.with_memory_watermarks(memory_watermarks.clone())
+			.build();
+
+		let code_fetcher = sp_core::traits::WrappedRuntimeCode(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+		);
+		let runtime_code =
+			RuntimeCode { code_fetcher: &code_fetcher, heap_pages: None, hash: vec![1, 2, 3] };
+
+		let mut ext = sp_io::TestExternalities::default();
+		let (result, _) = executor.call(
+			&mut ext.ext(),
+			&runtime_code,
+			"Core_version",
+			&[],
+			CallContext::Offchain,
+		);
+		result.unwrap();
+
+		let watermark = memory_watermarks.get(RuntimeCallCategory::Offchain);
+		assert_eq!(watermark.calls, 1);
+		assert!(watermark.linear_memory_peak > 0);
+		assert!(watermark.heap_allocated_peak > 0);
+		assert_eq!(memory_watermarks.get(RuntimeCallCategory::RuntimeApi).calls, 0);
+	}
+
+	#[test]
+	fn runtime_heap_alloc_strategy_overrides_defaults() {
+		let memory_watermarks = MemoryWatermarks::new();
+		let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+			.with_runtime_heap_alloc_strategy(
+				vec![1, 2, 3],
+				RuntimeHeapAllocStrategy {
+					onchain: Some(HeapAllocStrategy::Dynamic { maximum_pages: None }),
+					offchain: None,
+				},
+			)
+			.with_memory_watermarks(memory_watermarks.clone())
+			.build();
+
+		let code_fetcher = sp_core::traits::WrappedRuntimeCode(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+		);
+		let runtime_code = |hash| RuntimeCode {
+			code_fetcher: &code_fetcher,
+			// Ignored by the overridden runtime.
+			heap_pages: Some(4096),
+			hash,
+		};
+		let linear_memory_peak = |hash| {
+			memory_watermarks.reset();
+			let mut ext = sp_io::TestExternalities::default();
+			let (result, _) = executor.call(
+				&mut ext.ext(),
+				&runtime_code(hash),
+				"Core_version",
+				&[],
+				CallContext::Onchain,
+			);
+			result.unwrap();
+			memory_watermarks.get(RuntimeCallCategory::RuntimeApi).linear_memory_peak
+		};
+
+		// A dynamic heap only grows as much as needed.
+		assert!(linear_memory_peak(vec![1, 2, 3]) < 4096 * 64 * 1024);
+		// Other runtimes follow the onchain heap pages.
+		assert!(linear_memory_peak(vec![4, 5, 6]) > 4096 * 64 * 1024);
+
+		assert_eq!(
+			executor.heap_alloc_strategy(&runtime_code(vec![1, 2, 3]), CallContext::Offchain),
+			DEFAULT_HEAP_ALLOC_STRATEGY,
+		);
+	}
 }
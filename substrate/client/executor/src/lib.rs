@@ -34,12 +34,16 @@
 mod executor;
 #[cfg(test)]
 mod integration_tests;
+mod memory_watermark;
 mod wasm_runtime;
 
 pub use codec::Codec;
 #[allow(deprecated)]
 pub use executor::NativeElseWasmExecutor;
-pub use executor::{with_externalities_safe, NativeExecutionDispatch, WasmExecutor};
+pub use executor::{
+	with_externalities_safe, NativeExecutionDispatch, RuntimeHeapAllocStrategy, WasmExecutor,
+};
+pub use memory_watermark::{MemoryWatermark, MemoryWatermarks, RuntimeCallCategory};
 #[doc(hidden)]
 pub use sp_core::traits::Externalities;
 pub use sp_version::{NativeVersion, RuntimeVersion};
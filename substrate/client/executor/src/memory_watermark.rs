@@ -0,0 +1,326 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracking of the peak memory used by runtime calls.
+//!
+//! Every runtime call executed by the [`WasmExecutor`](crate::WasmExecutor) reports the
+//! [`AllocationStats`] of its instance. [`MemoryWatermarks`] keeps the highest values observed so
+//! far per [`RuntimeCallCategory`], which tells how close a runtime gets to its heap limit long
+//! before the allocator runs out of space. The distribution of the heap usage of the calls is
+//! exported as well, to tell the occasional outlier from a runtime that is always close to its
+//! limit.
+
+use std::{fmt, sync::Arc};
+
+use parking_lot::Mutex;
+use prometheus_endpoint::{
+	exponential_buckets, register, GaugeVec, HistogramOpts, HistogramVec, Opts, PrometheusError,
+	Registry, U64,
+};
+use sc_executor_common::wasm_runtime::AllocationStats;
+use sp_core::traits::CallContext;
+
+/// The kind of work a runtime call is performed for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuntimeCallCategory {
+	/// Execution of a block being imported.
+	BlockImport,
+	/// Initialization, extrinsic application and finalization of a block being authored.
+	BlockAuthoring,
+	/// Any other on-chain runtime API call, e.g. issued through the `state_call` RPC.
+	RuntimeApi,
+	/// Off-chain calls, e.g. offchain workers and transaction validation.
+	Offchain,
+}
+
+impl RuntimeCallCategory {
+	/// All the categories.
+	pub const ALL: [Self; 4] =
+		[Self::BlockImport, Self::BlockAuthoring, Self::RuntimeApi, Self::Offchain];
+
+	/// Returns the category of a call to `method` executed in `context`.
+	pub fn classify(method: &str, context: CallContext) -> Self {
+		match context {
+			CallContext::Offchain => Self::Offchain,
+			CallContext::Onchain if method == "Core_execute_block" => Self::BlockImport,
+			CallContext::Onchain
+				if method == "Core_initialize_block" || method.starts_with("BlockBuilder_") =>
+				Self::BlockAuthoring,
+			CallContext::Onchain => Self::RuntimeApi,
+		}
+	}
+
+	/// Returns the name of the category, as used in the metric labels.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::BlockImport => "block_import",
+			Self::BlockAuthoring => "block_authoring",
+			Self::RuntimeApi => "runtime_api",
+			Self::Offchain => "offchain",
+		}
+	}
+
+	fn index(&self) -> usize {
+		*self as usize
+	}
+}
+
+impl fmt::Display for RuntimeCallCategory {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// The peak memory usage observed for the runtime calls of a category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryWatermark {
+	/// The number of calls recorded.
+	pub calls: u64,
+	/// The peak size of the linear memory, in bytes.
+	pub linear_memory_peak: u64,
+	/// The peak number of bytes allocated by the heap allocator at the same time.
+	pub heap_allocated_peak: u32,
+	/// The peak address space used by the heap allocator, in bytes.
+	pub heap_address_space_peak: u32,
+}
+
+impl MemoryWatermark {
+	fn record(&mut self, stats: &AllocationStats) {
+		self.calls += 1;
+		self.linear_memory_peak = self.linear_memory_peak.max(stats.memory_size_peak);
+		self.heap_allocated_peak = self.heap_allocated_peak.max(stats.bytes_allocated_peak);
+		self.heap_address_space_peak = self.heap_address_space_peak.max(stats.address_space_used);
+	}
+}
+
+struct Metrics {
+	linear_memory_peak: GaugeVec<U64>,
+	heap_allocated_peak: GaugeVec<U64>,
+	heap_address_space_peak: GaugeVec<U64>,
+	heap_allocated: HistogramVec,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let gauge = |name: &str, help: &str| {
+			register(GaugeVec::new(Opts::new(name, help), &["category"])?, registry)
+		};
+
+		Ok(Self {
+			linear_memory_peak: gauge(
+				"substrate_runtime_linear_memory_peak_bytes",
+				"Peak size of the runtime linear memory per runtime call category",
+			)?,
+			heap_allocated_peak: gauge(
+				"substrate_runtime_heap_allocated_peak_bytes",
+				"Peak number of bytes allocated on the runtime heap per runtime call category",
+			)?,
+			heap_address_space_peak: gauge(
+				"substrate_runtime_heap_address_space_peak_bytes",
+				"Peak address space used by the runtime heap allocator per runtime call category",
+			)?,
+			heap_allocated: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"substrate_runtime_heap_allocated_bytes",
+						"Peak number of bytes allocated on the runtime heap by every runtime call",
+					)
+					// From 64KiB to 128MiB.
+					.buckets(exponential_buckets(64.0 * 1024.0, 2.0, 12)?),
+					&["category"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	fn report(&self, category: RuntimeCallCategory, watermark: &MemoryWatermark) {
+		let label = [category.as_str()];
+		self.linear_memory_peak
+			.with_label_values(&label)
+			.set(watermark.linear_memory_peak);
+		self.heap_allocated_peak
+			.with_label_values(&label)
+			.set(watermark.heap_allocated_peak.into());
+		self.heap_address_space_peak
+			.with_label_values(&label)
+			.set(watermark.heap_address_space_peak.into());
+	}
+
+	fn observe(&self, category: RuntimeCallCategory, stats: &AllocationStats) {
+		self.heap_allocated
+			.with_label_values(&[category.as_str()])
+			.observe(stats.bytes_allocated_peak.into());
+	}
+}
+
+#[derive(Default)]
+struct Inner {
+	watermarks: [MemoryWatermark; RuntimeCallCategory::ALL.len()],
+	metrics: Option<Metrics>,
+}
+
+/// Shared record of the peak memory used by runtime calls, per [`RuntimeCallCategory`].
+///
+/// Cloning is cheap, all the clones refer to the same record.
+#[derive(Clone, Default)]
+pub struct MemoryWatermarks {
+	inner: Arc<Mutex<Inner>>,
+}
+
+impl MemoryWatermarks {
+	/// Create a new empty record.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Export the watermarks as Prometheus metrics in the given `registry`.
+	pub fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		let metrics = Metrics::register(registry)?;
+
+		let mut inner = self.inner.lock();
+		for category in RuntimeCallCategory::ALL {
+			metrics.report(category, &inner.watermarks[category.index()]);
+		}
+		inner.metrics = Some(metrics);
+
+		Ok(())
+	}
+
+	/// Record the memory used by a runtime call of the given `category`.
+	pub fn record(&self, category: RuntimeCallCategory, stats: &AllocationStats) {
+		let mut inner = self.inner.lock();
+		let Inner { watermarks, metrics } = &mut *inner;
+
+		let watermark = &mut watermarks[category.index()];
+		watermark.record(stats);
+		if let Some(metrics) = metrics {
+			metrics.report(category, watermark);
+			metrics.observe(category, stats);
+		}
+	}
+
+	/// Returns the watermark of the given `category`.
+	pub fn get(&self, category: RuntimeCallCategory) -> MemoryWatermark {
+		self.inner.lock().watermarks[category.index()]
+	}
+
+	/// Returns the watermarks of all the categories.
+	pub fn all(&self) -> Vec<(RuntimeCallCategory, MemoryWatermark)> {
+		let inner = self.inner.lock();
+		RuntimeCallCategory::ALL
+			.into_iter()
+			.map(|category| (category, inner.watermarks[category.index()]))
+			.collect()
+	}
+
+	/// Forget all the recorded watermarks.
+	pub fn reset(&self) {
+		let mut inner = self.inner.lock();
+		let Inner { watermarks, metrics } = &mut *inner;
+
+		*watermarks = Default::default();
+		if let Some(metrics) = metrics {
+			for category in RuntimeCallCategory::ALL {
+				metrics.report(category, &watermarks[category.index()]);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn stats(memory_size: u64, allocated: u32, address_space: u32) -> AllocationStats {
+		let mut stats = AllocationStats::default();
+		stats.memory_size_peak = memory_size;
+		stats.bytes_allocated_peak = allocated;
+		stats.address_space_used = address_space;
+		stats
+	}
+
+	#[test]
+	fn calls_are_classified() {
+		use RuntimeCallCategory::*;
+
+		let onchain = |method| RuntimeCallCategory::classify(method, CallContext::Onchain);
+		assert_eq!(onchain("Core_execute_block"), BlockImport);
+		assert_eq!(onchain("Core_initialize_block"), BlockAuthoring);
+		assert_eq!(onchain("BlockBuilder_apply_extrinsic"), BlockAuthoring);
+		assert_eq!(onchain("Metadata_metadata"), RuntimeApi);
+		assert_eq!(
+			RuntimeCallCategory::classify("Core_execute_block", CallContext::Offchain),
+			Offchain
+		);
+	}
+
+	#[test]
+	fn keeps_highest_values_per_category() {
+		let watermarks = MemoryWatermarks::new();
+
+		watermarks.record(RuntimeCallCategory::BlockImport, &stats(3, 1, 2));
+		watermarks.record(RuntimeCallCategory::BlockImport, &stats(2, 4, 1));
+		watermarks.record(RuntimeCallCategory::Offchain, &stats(10, 10, 10));
+
+		assert_eq!(
+			watermarks.get(RuntimeCallCategory::BlockImport),
+			MemoryWatermark {
+				calls: 2,
+				linear_memory_peak: 3,
+				heap_allocated_peak: 4,
+				heap_address_space_peak: 2,
+			},
+		);
+		assert_eq!(watermarks.get(RuntimeCallCategory::RuntimeApi), MemoryWatermark::default());
+
+		watermarks.reset();
+		assert!(watermarks.all().iter().all(|(_, w)| *w == MemoryWatermark::default()));
+	}
+
+	#[test]
+	fn watermarks_are_exported_as_metrics() {
+		let registry = Registry::new();
+		let watermarks = MemoryWatermarks::new();
+		watermarks.record(RuntimeCallCategory::BlockAuthoring, &stats(65536, 1024, 2048));
+		watermarks.register(&registry).unwrap();
+
+		let metrics = registry.gather();
+		let linear_memory = metrics
+			.iter()
+			.find(|m| m.get_name() == "substrate_runtime_linear_memory_peak_bytes")
+			.unwrap();
+		let authoring = linear_memory
+			.get_metric()
+			.iter()
+			.find(|m| m.get_label()[0].get_value() == "block_authoring")
+			.unwrap();
+		assert_eq!(authoring.get_gauge().get_value(), 65536.0);
+
+		// Only the calls made after the registration are part of the distribution.
+		watermarks.record(RuntimeCallCategory::BlockAuthoring, &stats(65536, 512, 2048));
+		let heap_allocated = registry
+			.gather()
+			.into_iter()
+			.find(|m| m.get_name() == "substrate_runtime_heap_allocated_bytes")
+			.unwrap();
+		let histogram = heap_allocated.get_metric()[0].get_histogram();
+		assert_eq!(histogram.get_sample_count(), 1);
+		assert_eq!(histogram.get_sample_sum(), 512.0);
+	}
+}
@@ -0,0 +1,305 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Continuous invariant checks of the finalized chain.
+//!
+//! Every given number of finalized blocks, the [`InvariantChecker`] executes the finalized block
+//! again on top of the state of its parent through the `TryRuntime_execute_block` runtime API,
+//! running the `try_state` hooks of the pallets afterwards. Violated invariants are reported in
+//! the logs and the metrics of the node, which brings the checks of `try-runtime` to the nodes
+//! following the chain in production instead of only the CI.
+//!
+//! The runtime of the chain must be built with the `try-runtime` feature, the checks are skipped
+//! otherwise.
+
+use clap::Args;
+use codec::Encode;
+use futures::StreamExt;
+use prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
+use sc_client_api::{BlockBackend, BlockchainEvents, CallExecutor, ExecutorProvider};
+use sp_core::traits::{CallContext, SpawnNamed};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	SaturatedConversion,
+};
+use std::{iter, marker::PhantomData, sync::Arc};
+
+const LOG_TARGET: &str = "invariant-checker";
+
+/// Mirror of `frame_support::traits::TryStateSelect`, which only exists in runtimes built with
+/// the `try-runtime` feature. Encoded the same way.
+#[derive(Debug, Clone, PartialEq, Encode)]
+enum TryStateSelect {
+	#[codec(index = 1)]
+	All,
+	#[codec(index = 3)]
+	Only(Vec<Vec<u8>>),
+}
+
+/// Parameters used to create the invariant checker.
+#[derive(Default, Debug, Clone, Args)]
+pub struct InvariantCheckerParams {
+	/// Run the `try-state` checks of the runtime every given number of finalized blocks.
+	///
+	/// The finalized block is executed again and the checks are run on the resulting state.
+	/// The runtime must be built with the `try-runtime` feature.
+	///
+	/// If `0` is given the checks are disabled.
+	#[arg(long = "try-state-interval", value_name = "BLOCKS", default_value_t = 0)]
+	pub interval: u32,
+
+	/// Only run the `try-state` checks of the given pallet.
+	///
+	/// May be given multiple times. The checks of all the pallets are run by default.
+	#[arg(long = "try-state-pallet", value_name = "PALLET")]
+	pub pallets: Vec<String>,
+}
+
+impl InvariantCheckerParams {
+	fn select(&self) -> TryStateSelect {
+		if self.pallets.is_empty() {
+			TryStateSelect::All
+		} else {
+			TryStateSelect::Only(self.pallets.iter().map(|p| p.as_bytes().to_vec()).collect())
+		}
+	}
+}
+
+struct Metrics {
+	checks: Counter<U64>,
+	violations: Counter<U64>,
+	last_checked_block: Gauge<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			checks: register(
+				Counter::new(
+					"substrate_invariant_checker_checks_total",
+					"Number of finalized blocks whose try-state checks were run",
+				)?,
+				registry,
+			)?,
+			violations: register(
+				Counter::new(
+					"substrate_invariant_checker_violations_total",
+					"Number of finalized blocks whose try-state checks failed",
+				)?,
+				registry,
+			)?,
+			last_checked_block: register(
+				Gauge::new(
+					"substrate_invariant_checker_last_checked_block",
+					"Number of the last finalized block whose try-state checks were run",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Why a block could not be checked.
+enum CheckError {
+	/// The runtime is not built with the `try-runtime` feature.
+	Unsupported,
+	/// The block or the state of its parent is not available, e.g. pruned.
+	Unavailable(String),
+	/// The execution of the block or the `try_state` hooks failed.
+	Violation(String),
+}
+
+/// Invariant checker service: runs the `try-state` checks of the runtime on finalized blocks.
+pub struct InvariantChecker<Block, Client> {
+	client: Arc<Client>,
+	/// Blocks whose number is a multiple of `interval` are checked.
+	interval: u64,
+	select: TryStateSelect,
+	metrics: Option<Metrics>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, Client> InvariantChecker<Block, Client>
+where
+	Block: BlockT,
+	Client: BlockchainEvents<Block>
+		+ BlockBackend<Block>
+		+ ExecutorProvider<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+{
+	/// Spawns the invariant checker of the given `client`, unless disabled by the `parameters`.
+	pub fn try_spawn(
+		parameters: InvariantCheckerParams,
+		client: Arc<Client>,
+		registry: Option<&Registry>,
+		spawner: &impl SpawnNamed,
+	) -> Result<(), PrometheusError> {
+		if parameters.interval == 0 {
+			log::debug!(target: LOG_TARGET, "Interval `0` given, invariant checks disabled");
+			return Ok(())
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Running the try-state checks every {} finalized blocks",
+			parameters.interval,
+		);
+
+		let checker = Self {
+			client,
+			interval: parameters.interval.into(),
+			select: parameters.select(),
+			metrics: registry.map(Metrics::register).transpose()?,
+			_phantom: PhantomData,
+		};
+
+		// Blocks are executed on the thread of the task.
+		spawner.spawn_blocking("invariant-checker", None, Box::pin(checker.run()));
+
+		Ok(())
+	}
+
+	async fn run(self) {
+		let mut finality_notifications = self.client.finality_notification_stream();
+		let mut unsupported_reported = false;
+
+		while let Some(notification) = finality_notifications.next().await {
+			let due = due_blocks(
+				&notification.tree_route,
+				notification.hash,
+				(*notification.header.number()).saturated_into(),
+				self.interval,
+			);
+
+			for (number, hash) in due {
+				match self.check(hash) {
+					Ok(()) => {
+						log::debug!(target: LOG_TARGET, "Invariants hold at #{number} ({hash:?})");
+						unsupported_reported = false;
+					},
+					Err(CheckError::Unsupported) if unsupported_reported => continue,
+					Err(CheckError::Unsupported) => {
+						log::warn!(
+							target: LOG_TARGET,
+							"The runtime at #{number} ({hash:?}) is not built with the `try-runtime` \
+							feature, skipping the invariant checks",
+						);
+						unsupported_reported = true;
+						continue
+					},
+					Err(CheckError::Unavailable(error)) => {
+						log::warn!(
+							target: LOG_TARGET,
+							"Could not check the invariants at #{number} ({hash:?}): {error}",
+						);
+						continue
+					},
+					Err(CheckError::Violation(error)) => {
+						log::error!(
+							target: LOG_TARGET,
+							"Invariants violated at #{number} ({hash:?}): {error}",
+						);
+						if let Some(metrics) = &self.metrics {
+							metrics.violations.inc();
+						}
+					},
+				}
+
+				if let Some(metrics) = &self.metrics {
+					metrics.checks.inc();
+					metrics.last_checked_block.set(number);
+				}
+			}
+		}
+	}
+
+	/// Executes the block `hash` again and runs the `try_state` hooks.
+	fn check(&self, hash: Block::Hash) -> Result<(), CheckError> {
+		let block = self
+			.client
+			.block(hash)
+			.map_err(|e| CheckError::Unavailable(e.to_string()))?
+			.ok_or_else(|| CheckError::Unavailable("block body not found".into()))?
+			.block;
+		let parent_hash = *block.header().parent_hash();
+		let executor = self.client.executor();
+
+		let version = executor
+			.runtime_version(parent_hash)
+			.map_err(|e| CheckError::Unavailable(e.to_string()))?;
+		let try_runtime_api = sp_core::hashing::blake2_64(b"TryRuntime");
+		if !version.has_api_with(&try_runtime_api, |_| true) {
+			return Err(CheckError::Unsupported)
+		}
+
+		// Signatures and state root were already checked on import.
+		let call_data = (block, false, false, &self.select).encode();
+		executor
+			.call(parent_hash, "TryRuntime_execute_block", &call_data, CallContext::Offchain)
+			.map(drop)
+			.map_err(|e| match e {
+				sp_blockchain::Error::Execution(e) => CheckError::Violation(e.to_string()),
+				e => CheckError::Unavailable(e.to_string()),
+			})
+	}
+}
+
+/// Returns the numbers and hashes of the blocks finalized by a notification that are due for a
+/// check.
+///
+/// `route` holds the blocks implicitly finalized before `last`, whose number is `last_number`.
+fn due_blocks<H: Copy>(route: &[H], last: H, last_number: u64, interval: u64) -> Vec<(u64, H)> {
+	let first_number = last_number.saturating_sub(route.len() as u64);
+
+	route
+		.iter()
+		.copied()
+		.chain(iter::once(last))
+		.enumerate()
+		.map(|(i, hash)| (first_number + i as u64, hash))
+		.filter(|(number, _)| number % interval == 0)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn due_blocks_are_multiples_of_the_interval() {
+		assert_eq!(due_blocks(&[], 'a', 10, 5), vec![(10, 'a')]);
+		assert_eq!(due_blocks(&[], 'a', 11, 5), vec![]);
+		assert_eq!(
+			due_blocks(&['a', 'b', 'c', 'd', 'e', 'f'], 'g', 16, 5),
+			vec![(10, 'a'), (15, 'f')]
+		);
+	}
+
+	#[test]
+	fn select_is_encoded_as_in_frame_support() {
+		let params = |pallets: &[&str]| InvariantCheckerParams {
+			interval: 1,
+			pallets: pallets.iter().map(|p| p.to_string()).collect(),
+		};
+
+		assert_eq!(params(&[]).select().encode(), vec![1]);
+		assert_eq!(params(&["System"]).select().encode(), [&[3, 4, 24][..], b"System"].concat());
+	}
+}
@@ -0,0 +1,792 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Keystore only signing while holding a lease, for high availability validator setups.
+//!
+//! An active and a standby node may share the same authority keys, so that the standby takes
+//! over when the active one fails. Both must never sign at the same time, or they equivocate and
+//! get slashed. The [`LeasedKeystore`] guards the keys of a node behind a [`SigningLease`]: while
+//! the lease isn't held, the keys are hidden from the consensus engines and every signing request
+//! is refused. Once the lease is acquired, signing only resumes after a takeover delay, leaving
+//! time to the previous holder to notice it has lost the lease and to stop signing.
+//!
+//! The delay alone doesn't prevent the new holder from signing a vote or a block conflicting
+//! with one the previous holder signed right before losing the lease, in the same GRANDPA round or
+//! slot. Before every signature, the position it falls at is therefore recorded in a
+//! [`SigningLog`] shared by the holders, and a holder only signs at positions past the last one
+//! recorded by another holder.
+
+use parking_lot::Mutex;
+use sp_core::{
+	crypto::{key_types, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore};
+use std::{
+	collections::hash_map::RandomState,
+	fs,
+	hash::{BuildHasher, Hasher},
+	io::{self, Write},
+	path::PathBuf,
+	process::{Command, Stdio},
+	sync::{Arc, Weak},
+	thread,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls381, ecdsa_bls381};
+}
+
+const LOG_TARGET: &str = "keystore";
+
+/// Decides whether this node may sign with its authority keys.
+///
+/// The lease is typically backed by a lock shared by all the nodes using the same keys, only one
+/// of them holding it at any time.
+pub trait SigningLease: Send + Sync {
+	/// Returns whether this node currently holds the lease.
+	fn is_held(&self) -> bool;
+}
+
+/// Where a signature falls in the progress of a consensus protocol.
+///
+/// Positions are ordered by `era` first, later positions being greater.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SigningPosition {
+	/// The GRANDPA authority set id, always zero for slots.
+	pub era: u64,
+	/// The GRANDPA round or the slot.
+	pub index: u64,
+}
+
+/// A signature recorded in a [`SigningLog`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SigningRecord {
+	/// The keystore that signed, random for every [`LeasedKeystore`].
+	pub holder: u64,
+	/// Where the signature falls.
+	pub position: SigningPosition,
+}
+
+/// Keeps the last signature made with the keys of every key type.
+///
+/// The log must be shared by all the nodes using the same keys, e.g. stored on a file system all
+/// of them access or next to the lock behind the [`SigningLease`].
+pub trait SigningLog: Send + Sync {
+	/// Returns the last signature recorded for `key_type`.
+	fn last(&self, key_type: KeyTypeId) -> io::Result<Option<SigningRecord>>;
+
+	/// Records a signature for `key_type`, before it is handed out.
+	fn record(&self, key_type: KeyTypeId, record: SigningRecord) -> io::Result<()>;
+}
+
+/// A [`SigningLog`] stored in a JSON file.
+///
+/// The file is read again before every signature, so that the records of other nodes sharing it
+/// are seen, and replaced atomically on every record.
+pub struct FileSigningLog {
+	path: PathBuf,
+	lock: Mutex<()>,
+}
+
+impl FileSigningLog {
+	/// Stores the log at `path`, which is created on the first record.
+	pub fn new(path: PathBuf) -> Self {
+		Self { path, lock: Mutex::new(()) }
+	}
+
+	fn read(&self) -> io::Result<serde_json::Map<String, serde_json::Value>> {
+		match fs::read(&self.path) {
+			Ok(content) => serde_json::from_slice(&content).map_err(Into::into),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Default::default()),
+			Err(error) => Err(error),
+		}
+	}
+}
+
+impl SigningLog for FileSigningLog {
+	fn last(&self, key_type: KeyTypeId) -> io::Result<Option<SigningRecord>> {
+		let _lock = self.lock.lock();
+		let Some(record) = self.read()?.remove(&array_bytes::bytes2hex("", key_type.0)) else {
+			return Ok(None)
+		};
+
+		let field = |name| {
+			record.get(name).and_then(serde_json::Value::as_u64).ok_or_else(|| {
+				io::Error::new(
+					io::ErrorKind::InvalidData,
+					format!("Invalid signing record {record}"),
+				)
+			})
+		};
+		Ok(Some(SigningRecord {
+			holder: field("holder")?,
+			position: SigningPosition { era: field("era")?, index: field("index")? },
+		}))
+	}
+
+	fn record(&self, key_type: KeyTypeId, record: SigningRecord) -> io::Result<()> {
+		let _lock = self.lock.lock();
+		let mut log = self.read()?;
+		log.insert(
+			array_bytes::bytes2hex("", key_type.0),
+			serde_json::json!({
+				"holder": record.holder,
+				"era": record.position.era,
+				"index": record.position.index,
+			}),
+		);
+
+		let tmp = self.path.with_extension("tmp");
+		let mut file = fs::File::create(&tmp)?;
+		file.write_all(&serde_json::to_vec(&log)?)?;
+		file.sync_all()?;
+		fs::rename(&tmp, &self.path)
+	}
+}
+
+/// Configuration of a [`LeasedKeystore`].
+#[derive(Clone)]
+pub struct SigningLeaseConfig {
+	/// The lease to hold to sign.
+	pub lease: Arc<dyn SigningLease>,
+	/// How long to wait after acquiring the lease before signing.
+	///
+	/// Should exceed the time the previous holder may keep signing after losing the lease, i.e.
+	/// how long its lease stays valid plus the duration of a signing operation.
+	pub takeover_delay: Duration,
+	/// Where the positions of the signatures are recorded.
+	pub signing_log: Arc<dyn SigningLog>,
+	/// The key types signing once per slot, e.g. BABE or Aura, with the duration of a slot.
+	///
+	/// GRANDPA signatures are always guarded by their round.
+	pub slot_key_types: Vec<(KeyTypeId, Duration)>,
+}
+
+impl SigningLeaseConfig {
+	/// Guards the signatures with keys of `key_type` by the slot they are made in.
+	pub fn with_slot_key_type(mut self, key_type: KeyTypeId, slot_duration: Duration) -> Self {
+		self.slot_key_types.push((key_type, slot_duration));
+		self
+	}
+}
+
+/// A [`Keystore`] that only exposes and signs with the keys of `K` while holding a lease.
+///
+/// Keys can still be inserted and generated while the lease isn't held.
+pub struct LeasedKeystore<K> {
+	inner: K,
+	config: SigningLeaseConfig,
+	/// When the lease has been acquired, `None` while it isn't held.
+	acquired_at: Mutex<Option<Instant>>,
+	/// Identifies the signatures of this keystore in the signing log.
+	holder: u64,
+}
+
+impl<K: Keystore> LeasedKeystore<K> {
+	/// Guards the keys of `inner` behind the lease of `config`.
+	pub fn new(inner: K, config: SigningLeaseConfig) -> Self {
+		let mut hasher = RandomState::new().build_hasher();
+		hasher.write_u32(std::process::id());
+		Self { inner, config, acquired_at: Mutex::new(None), holder: hasher.finish() }
+	}
+
+	/// Returns whether the keys can be used right now.
+	fn can_sign(&self) -> bool {
+		let mut acquired_at = self.acquired_at.lock();
+
+		if !self.config.lease.is_held() {
+			if acquired_at.take().is_some() {
+				log::warn!(target: LOG_TARGET, "Signing lease lost, not signing anymore");
+			}
+			return false;
+		}
+
+		let acquired_at = *acquired_at.get_or_insert_with(|| {
+			log::info!(
+				target: LOG_TARGET,
+				"Signing lease acquired, signing in {:?}",
+				self.config.takeover_delay,
+			);
+			Instant::now()
+		});
+		acquired_at.elapsed() >= self.config.takeover_delay
+	}
+
+	/// The current slot of `key_type`, if it signs once per slot.
+	fn current_slot(&self, key_type: KeyTypeId) -> Option<SigningPosition> {
+		let (_, slot_duration) = self.config.slot_key_types.iter().find(|(k, _)| *k == key_type)?;
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+		let index = (now.as_millis() / slot_duration.as_millis().max(1)) as u64;
+		Some(SigningPosition { era: 0, index })
+	}
+
+	/// The position of signing `msg` with keys of `key_type`, `None` if it isn't guarded.
+	fn position(
+		&self,
+		key_type: KeyTypeId,
+		msg: &[u8],
+	) -> std::result::Result<Option<SigningPosition>, TraitError> {
+		if key_type != key_types::GRANDPA {
+			return Ok(self.current_slot(key_type))
+		}
+
+		// GRANDPA signs the encoding of `(message, round, set_id)`.
+		let Some(tail) = msg.len().checked_sub(16).map(|start| &msg[start..]) else {
+			return Err(TraitError::Other("Unexpected GRANDPA signing payload".into()))
+		};
+		let (round, set_id) = tail.split_at(8);
+		Ok(Some(SigningPosition {
+			era: u64::from_le_bytes(set_id.try_into().expect("8 bytes long; qed")),
+			index: u64::from_le_bytes(round.try_into().expect("8 bytes long; qed")),
+		}))
+	}
+
+	/// Returns the last signature recorded for `key_type`.
+	fn last_signed(
+		&self,
+		key_type: KeyTypeId,
+	) -> std::result::Result<Option<SigningRecord>, TraitError> {
+		self.config
+			.signing_log
+			.last(key_type)
+			.map_err(|error| TraitError::Other(error.to_string()))
+	}
+
+	/// Returns whether signing at `position` may conflict with the `last` signature.
+	fn conflicts(&self, last: &SigningRecord, position: SigningPosition) -> bool {
+		last.holder != self.holder && position <= last.position
+	}
+
+	/// Returns whether the keys of `key_type` can be used right now, to claim a slot.
+	fn can_use(&self, key_type: KeyTypeId) -> bool {
+		let Some(slot) = self.current_slot(key_type) else { return true };
+		match self.last_signed(key_type) {
+			Ok(last) => !last.is_some_and(|last| self.conflicts(&last, slot)),
+			Err(error) => {
+				log::warn!(target: LOG_TARGET, "Failed to read the signing log: {error}");
+				false
+			},
+		}
+	}
+
+	/// Refuses to sign `msg` with keys of `key_type` if the keys can't be used, or if another
+	/// holder of the lease may have signed at the same position. The position is recorded before
+	/// signing.
+	fn check_signing(
+		&self,
+		key_type: KeyTypeId,
+		msg: &[u8],
+	) -> std::result::Result<(), TraitError> {
+		if !self.can_sign() {
+			return Err(TraitError::Unavailable)
+		}
+		let Some(position) = self.position(key_type, msg)? else { return Ok(()) };
+
+		let last = self.last_signed(key_type)?;
+		if let Some(last) = last.filter(|last| self.conflicts(last, position)) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Not signing at {position:?} with {key_type:?} keys, another holder of the signing \
+				lease signed at {:?}",
+				last.position,
+			);
+			return Err(TraitError::Unavailable)
+		}
+
+		// The record never goes backwards, it must cover everything signed so far.
+		if last.is_some_and(|last| last.position >= position) {
+			return Ok(())
+		}
+		self.config
+			.signing_log
+			.record(key_type, SigningRecord { holder: self.holder, position })
+			.map_err(|error| TraitError::Other(error.to_string()))
+	}
+
+	/// Returns the result of `f` if the keys of `key_type` can be used, or the default value of
+	/// `T`, e.g. no keys at all.
+	fn if_leased<T: Default>(&self, key_type: KeyTypeId, f: impl FnOnce() -> T) -> T {
+		if self.can_sign() && self.can_use(key_type) {
+			f()
+		} else {
+			T::default()
+		}
+	}
+}
+
+impl<K: Keystore> Keystore for LeasedKeystore<K> {
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.if_leased(key_type, || self.inner.sr25519_public_keys(key_type))
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.inner.sr25519_generate_new(key_type, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.check_signing(key_type, msg)?;
+		self.inner.sr25519_sign(key_type, public, msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		self.check_signing(key_type, &[])?;
+		self.inner.sr25519_vrf_sign(key_type, public, data)
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		input: &sr25519::vrf::VrfInput,
+	) -> std::result::Result<Option<sr25519::vrf::VrfPreOutput>, TraitError> {
+		self.check_signing(key_type, &[])?;
+		self.inner.sr25519_vrf_pre_output(key_type, public, input)
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.if_leased(key_type, || self.inner.ed25519_public_keys(key_type))
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.inner.ed25519_generate_new(key_type, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ed25519::Signature>, TraitError> {
+		self.check_signing(key_type, msg)?;
+		self.inner.ed25519_sign(key_type, public, msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.if_leased(key_type, || self.inner.ecdsa_public_keys(key_type))
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.inner.ecdsa_generate_new(key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.check_signing(key_type, msg)?;
+		self.inner.ecdsa_sign(key_type, public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.check_signing(key_type, msg)?;
+		self.inner.ecdsa_sign_prehashed(key_type, public, msg)
+	}
+
+	fn insert(
+		&self,
+		key_type: KeyTypeId,
+		suri: &str,
+		public: &[u8],
+	) -> std::result::Result<(), ()> {
+		self.inner.insert(key_type, suri, public)
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
+		if !self.can_sign() || !self.can_use(key_type) {
+			return Ok(Vec::new());
+		}
+		self.inner.keys(key_type)
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		self.can_sign() &&
+			public_keys.iter().all(|(_, key_type)| self.can_use(*key_type)) &&
+			self.inner.has_keys(public_keys)
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+			self.if_leased(key_type, || self.inner.bandersnatch_public_keys(key_type))
+		}
+
+		fn bandersnatch_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bandersnatch::Public, TraitError> {
+			self.inner.bandersnatch_generate_new(key_type, seed)
+		}
+
+		fn bandersnatch_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bandersnatch::Signature>, TraitError> {
+			self.check_signing(key_type, msg)?;
+			self.inner.bandersnatch_sign(key_type, public, msg)
+		}
+
+		fn bandersnatch_vrf_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			input: &bandersnatch::vrf::VrfSignData,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+			self.check_signing(key_type, &[])?;
+			self.inner.bandersnatch_vrf_sign(key_type, public, input)
+		}
+
+		fn bandersnatch_vrf_pre_output(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			input: &bandersnatch::vrf::VrfInput,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfPreOutput>, TraitError> {
+			self.check_signing(key_type, &[])?;
+			self.inner.bandersnatch_vrf_pre_output(key_type, public, input)
+		}
+
+		fn bandersnatch_ring_vrf_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			input: &bandersnatch::vrf::VrfSignData,
+			prover: &bandersnatch::ring_vrf::RingProver,
+		) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+			self.check_signing(key_type, &[])?;
+			self.inner.bandersnatch_ring_vrf_sign(key_type, public, input, prover)
+		}
+	}
+
+	sp_keystore::bls_experimental_enabled! {
+		fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+			self.if_leased(id, || self.inner.bls381_public_keys(id))
+		}
+
+		fn ecdsa_bls381_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa_bls381::Public> {
+			self.if_leased(id, || self.inner.ecdsa_bls381_public_keys(id))
+		}
+
+		fn bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls381::Public, TraitError> {
+			self.inner.bls381_generate_new(key_type, seed)
+		}
+
+		fn ecdsa_bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<ecdsa_bls381::Public, TraitError> {
+			self.inner.ecdsa_bls381_generate_new(key_type, seed)
+		}
+
+		fn bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+			self.check_signing(key_type, msg)?;
+			self.inner.bls381_sign(key_type, public, msg)
+		}
+
+		fn ecdsa_bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.check_signing(key_type, msg)?;
+			self.inner.ecdsa_bls381_sign(key_type, public, msg)
+		}
+
+		fn ecdsa_bls381_sign_with_keccak256(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.check_signing(key_type, msg)?;
+			self.inner.ecdsa_bls381_sign_with_keccak256(key_type, public, msg)
+		}
+	}
+}
+
+/// A [`SigningLease`] backed by an external lock, e.g. in etcd or Consul.
+///
+/// A shell command acquiring or renewing the lock is run periodically in a background thread. The
+/// lease is held for `validity` after the start of every run of the command that exits
+/// successfully, so the lock itself must outlive `validity`.
+pub struct CommandLease {
+	held_until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl CommandLease {
+	/// Runs `command` with `sh -c` every `interval`.
+	///
+	/// The thread running the command stops once the returned lease is dropped.
+	pub fn spawn(command: String, interval: Duration, validity: Duration) -> io::Result<Self> {
+		let held_until = Arc::new(Mutex::new(None));
+		let weak = Arc::downgrade(&held_until);
+
+		thread::Builder::new()
+			.name("signing-lease".into())
+			.spawn(move || renew_with_command(&command, interval, validity, weak))?;
+
+		Ok(Self { held_until })
+	}
+}
+
+impl SigningLease for CommandLease {
+	fn is_held(&self) -> bool {
+		self.held_until.lock().map_or(false, |held_until| Instant::now() < held_until)
+	}
+}
+
+fn renew_with_command(
+	command: &str,
+	interval: Duration,
+	validity: Duration,
+	held_until: Weak<Mutex<Option<Instant>>>,
+) {
+	loop {
+		// The lease is counted from the start of the command, whatever the time it takes.
+		let started = Instant::now();
+		let renewed = match Command::new("sh").arg("-c").arg(command).stdin(Stdio::null()).status()
+		{
+			Ok(status) => {
+				if !status.success() {
+					log::debug!(target: LOG_TARGET, "Signing lease command exited with {status}");
+				}
+				status.success()
+			},
+			Err(error) => {
+				log::warn!(target: LOG_TARGET, "Failed to run the signing lease command: {error}");
+				false
+			},
+		};
+
+		let Some(held_until) = held_until.upgrade() else { return };
+		*held_until.lock() = renewed.then(|| started + validity);
+		drop(held_until);
+
+		thread::sleep(interval.saturating_sub(started.elapsed()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use sp_core::testing::SR25519;
+	use std::sync::atomic::{AtomicBool, Ordering};
+	use tempfile::TempDir;
+
+	struct TestLease(AtomicBool);
+
+	impl SigningLease for TestLease {
+		fn is_held(&self) -> bool {
+			self.0.load(Ordering::SeqCst)
+		}
+	}
+
+	fn leased_keystore_with_log(
+		takeover_delay: Duration,
+		signing_log: Arc<dyn SigningLog>,
+	) -> (Arc<TestLease>, LeasedKeystore<LocalKeystore>) {
+		let lease = Arc::new(TestLease(AtomicBool::new(false)));
+		let config = SigningLeaseConfig {
+			lease: lease.clone(),
+			takeover_delay,
+			signing_log,
+			slot_key_types: Vec::new(),
+		}
+		// A single slot for the whole test.
+		.with_slot_key_type(key_types::BABE, Duration::from_secs(1 << 40));
+		(lease, LeasedKeystore::new(LocalKeystore::in_memory(), config))
+	}
+
+	fn leased_keystore(
+		takeover_delay: Duration,
+	) -> (TempDir, Arc<TestLease>, LeasedKeystore<LocalKeystore>) {
+		let dir = TempDir::new().unwrap();
+		let log = Arc::new(FileSigningLog::new(dir.path().join("signing-log")));
+		let (lease, keystore) = leased_keystore_with_log(takeover_delay, log);
+		(dir, lease, keystore)
+	}
+
+	fn grandpa_payload(vote: &[u8], round: u64, set_id: u64) -> Vec<u8> {
+		[vote, &round.to_le_bytes(), &set_id.to_le_bytes()].concat()
+	}
+
+	#[test]
+	fn keys_are_only_usable_with_the_lease() {
+		let (_dir, lease, keystore) = leased_keystore(Duration::ZERO);
+
+		// Keys can be managed without the lease.
+		let public = keystore.sr25519_generate_new(SR25519, None).unwrap();
+		assert!(keystore.sr25519_public_keys(SR25519).is_empty());
+		assert!(!keystore.has_keys(&[(public.to_vec(), SR25519)]));
+		assert!(matches!(
+			keystore.sr25519_sign(SR25519, &public, b"msg"),
+			Err(TraitError::Unavailable)
+		));
+
+		lease.0.store(true, Ordering::SeqCst);
+		assert_eq!(keystore.sr25519_public_keys(SR25519), vec![public]);
+		assert!(keystore.has_keys(&[(public.to_vec(), SR25519)]));
+		assert!(keystore.sr25519_sign(SR25519, &public, b"msg").unwrap().is_some());
+
+		lease.0.store(false, Ordering::SeqCst);
+		assert!(keystore.sr25519_public_keys(SR25519).is_empty());
+		assert!(keystore.sr25519_sign(SR25519, &public, b"msg").is_err());
+	}
+
+	#[test]
+	fn signing_waits_for_the_takeover_delay() {
+		let (_dir, lease, keystore) = leased_keystore(Duration::from_millis(200));
+		let public = keystore.sr25519_generate_new(SR25519, None).unwrap();
+
+		lease.0.store(true, Ordering::SeqCst);
+		assert!(keystore.sr25519_sign(SR25519, &public, b"msg").is_err());
+		thread::sleep(Duration::from_millis(250));
+		assert!(keystore.sr25519_sign(SR25519, &public, b"msg").unwrap().is_some());
+
+		// Losing the lease, even briefly, restarts the delay.
+		lease.0.store(false, Ordering::SeqCst);
+		assert!(keystore.sr25519_sign(SR25519, &public, b"msg").is_err());
+		lease.0.store(true, Ordering::SeqCst);
+		assert!(keystore.sr25519_sign(SR25519, &public, b"msg").is_err());
+	}
+
+	#[test]
+	fn takeover_in_the_same_round_is_refused() {
+		let dir = TempDir::new().unwrap();
+		let log = Arc::new(FileSigningLog::new(dir.path().join("signing-log")));
+		let (active_lease, active) = leased_keystore_with_log(Duration::ZERO, log.clone());
+		let (standby_lease, standby) = leased_keystore_with_log(Duration::ZERO, log);
+
+		let grandpa = key_types::GRANDPA;
+		let public = active.ed25519_generate_new(grandpa, Some("//Alice")).unwrap();
+		assert_eq!(standby.ed25519_generate_new(grandpa, Some("//Alice")).unwrap(), public);
+
+		active_lease.0.store(true, Ordering::SeqCst);
+		assert!(active.ed25519_sign(grandpa, &public, &grandpa_payload(b"a", 5, 1)).is_ok());
+
+		// The standby takes over in the middle of the round.
+		active_lease.0.store(false, Ordering::SeqCst);
+		standby_lease.0.store(true, Ordering::SeqCst);
+		assert!(matches!(
+			standby.ed25519_sign(grandpa, &public, &grandpa_payload(b"b", 5, 1)),
+			Err(TraitError::Unavailable)
+		));
+		assert!(standby.ed25519_sign(grandpa, &public, &grandpa_payload(b"b", 4, 1)).is_err());
+		assert!(standby.ed25519_sign(grandpa, &public, &grandpa_payload(b"b", 6, 1)).is_ok());
+		assert!(standby.ed25519_sign(grandpa, &public, &grandpa_payload(b"b", 0, 2)).is_ok());
+
+		// The record is kept on disk, for the next holder.
+		let record = FileSigningLog::new(dir.path().join("signing-log"))
+			.last(grandpa)
+			.unwrap()
+			.unwrap();
+		assert_eq!(record.holder, standby.holder);
+		assert_eq!(record.position, SigningPosition { era: 2, index: 0 });
+
+		// Leaving the active one unable to sign in its old rounds.
+		standby_lease.0.store(false, Ordering::SeqCst);
+		active_lease.0.store(true, Ordering::SeqCst);
+		assert!(active.ed25519_sign(grandpa, &public, &grandpa_payload(b"a", 6, 1)).is_err());
+	}
+
+	#[test]
+	fn takeover_in_the_same_slot_is_refused() {
+		let (_dir, active_lease, active) = leased_keystore(Duration::ZERO);
+		let log = active.config.signing_log.clone();
+		let (standby_lease, standby) = leased_keystore_with_log(Duration::ZERO, log);
+
+		let babe = key_types::BABE;
+		let public = active.sr25519_generate_new(babe, Some("//Alice")).unwrap();
+		standby.sr25519_generate_new(babe, Some("//Alice")).unwrap();
+
+		active_lease.0.store(true, Ordering::SeqCst);
+		assert!(active.sr25519_sign(babe, &public, b"block").unwrap().is_some());
+		// Signing again in the same slot is up to the consensus engine of the holder.
+		assert!(active.sr25519_sign(babe, &public, b"seal").unwrap().is_some());
+
+		active_lease.0.store(false, Ordering::SeqCst);
+		standby_lease.0.store(true, Ordering::SeqCst);
+		assert!(standby.sr25519_public_keys(babe).is_empty());
+		assert!(!standby.has_keys(&[(public.to_vec(), babe)]));
+		assert!(matches!(
+			standby.sr25519_sign(babe, &public, b"other block"),
+			Err(TraitError::Unavailable)
+		));
+	}
+
+	#[test]
+	fn command_lease_follows_the_command_status() {
+		let interval = Duration::from_millis(50);
+		let validity = Duration::from_secs(60);
+
+		let held = CommandLease::spawn("true".into(), interval, validity).unwrap();
+		let not_held = CommandLease::spawn("false".into(), interval, validity).unwrap();
+		thread::sleep(interval * 4);
+
+		assert!(held.is_held());
+		assert!(!not_held.is_held());
+	}
+}
@@ -23,8 +23,14 @@ use sp_core::crypto::KeyTypeId;
 use sp_keystore::Error as TraitError;
 use std::io;
 
+/// Keystore only signing while holding a lease
+mod lease;
 /// Local keystore implementation
 mod local;
+pub use lease::{
+	CommandLease, FileSigningLog, LeasedKeystore, SigningLease, SigningLeaseConfig, SigningLog,
+	SigningPosition, SigningRecord,
+};
 pub use local::LocalKeystore;
 pub use sp_keystore::Keystore;
 
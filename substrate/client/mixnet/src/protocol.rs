@@ -51,6 +51,7 @@ pub fn peers_set_config<Block: BlockT, Network: NetworkBackend<Block, <Block as
 			out_peers: 0,
 			reserved_nodes: Vec::new(),
 			non_reserved_mode: NonReservedPeerMode::Accept,
+			inactivity_eviction: None,
 		}
 	} else {
 		SetConfig {
@@ -58,6 +59,7 @@ pub fn peers_set_config<Block: BlockT, Network: NetworkBackend<Block, <Block as
 			out_peers: 0,
 			reserved_nodes: Vec::new(),
 			non_reserved_mode: NonReservedPeerMode::Deny,
+			inactivity_eviction: None,
 		}
 	};
 
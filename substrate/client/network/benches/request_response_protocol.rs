@@ -89,6 +89,7 @@ where
 			out_peers: 1,
 			reserved_nodes: vec![],
 			non_reserved_mode: NonReservedPeerMode::Accept,
+			inactivity_eviction: None,
 		},
 		NotificationMetrics::new(None),
 		network_config.peer_store_handle(),
@@ -25,6 +25,7 @@ pub use crate::{
 	discovery::DEFAULT_KADEMLIA_REPLICATION_FACTOR,
 	peer_store::PeerStoreProvider,
 	protocol::{notification_service, NotificationsSink, ProtocolHandlePair},
+	protocol_controller::InactivityEviction,
 	request_responses::{
 		IncomingRequest, OutgoingResponse, ProtocolConfig as RequestResponseConfig,
 	},
@@ -441,6 +442,11 @@ pub struct SetConfig {
 	/// Whether nodes that aren't in [`SetConfig::reserved_nodes`] are accepted or automatically
 	/// refused.
 	pub non_reserved_mode: NonReservedPeerMode,
+
+	/// Disconnect non-reserved nodes which are inactive, to free their slots for other nodes.
+	///
+	/// Only supported by the libp2p backend.
+	pub inactivity_eviction: Option<InactivityEviction>,
 }
 
 impl Default for SetConfig {
@@ -450,6 +456,7 @@ impl Default for SetConfig {
 			out_peers: 75,
 			reserved_nodes: Vec::new(),
 			non_reserved_mode: NonReservedPeerMode::Accept,
+			inactivity_eviction: None,
 		}
 	}
 }
@@ -290,6 +290,24 @@ impl PeerStoreProvider for PeerstoreHandle {
 	fn add_known_peer(&self, peer: PeerId) {
 		self.0.lock().peers.entry(peer).or_default().last_updated = Instant::now();
 	}
+
+	/// Get the reputations of all peers with a non-zero reputation.
+	fn reputations(&self) -> Vec<(PeerId, i32)> {
+		self.0
+			.lock()
+			.peers
+			.iter()
+			.filter_map(|(peer, info)| (info.reputation != 0).then_some((*peer, info.reputation)))
+			.collect()
+	}
+
+	/// Override the reputations of the given peers.
+	fn set_reputations(&self, reputations: Vec<(PeerId, i32)>) {
+		for (peer, reputation) in reputations {
+			let change = reputation.saturating_sub(self.peer_reputation(&peer));
+			self.report_peer(peer, ReputationChange::new(change, "Reputation set"));
+		}
+	}
 }
 
 /// `Peerstore` handle for testing.
@@ -70,4 +70,12 @@ impl PeerStoreProvider for MockPeerStore {
 	fn add_known_peer(&self, _peer_id: PeerId) {
 		unimplemented!()
 	}
+
+	fn reputations(&self) -> Vec<(PeerId, i32)> {
+		Vec::new()
+	}
+
+	fn set_reputations(&self, _reputations: Vec<(PeerId, i32)>) {
+		unimplemented!()
+	}
 }
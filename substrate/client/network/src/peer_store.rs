@@ -98,6 +98,14 @@ pub trait PeerStoreProvider: Debug + Send + Sync {
 
 	/// Add known peer.
 	fn add_known_peer(&self, peer_id: sc_network_types::PeerId);
+
+	/// Get the reputations of all peers with a non-zero reputation.
+	fn reputations(&self) -> Vec<(sc_network_types::PeerId, i32)>;
+
+	/// Override the reputations of the given peers, e.g. when restoring them after a restart.
+	///
+	/// Peers whose new reputation is below [`BANNED_THRESHOLD`] are disconnected.
+	fn set_reputations(&self, reputations: Vec<(sc_network_types::PeerId, i32)>);
 }
 
 /// Actual implementation of peer reputations and connection candidates provider.
@@ -154,6 +162,42 @@ impl PeerStoreProvider for PeerStoreHandle {
 	fn add_known_peer(&self, peer_id: sc_network_types::PeerId) {
 		self.inner.lock().add_known_peer(peer_id.into());
 	}
+
+	fn reputations(&self) -> Vec<(sc_network_types::PeerId, i32)> {
+		self.inner
+			.lock()
+			.reputations()
+			.into_iter()
+			.map(|(peer_id, reputation)| (peer_id.into(), reputation))
+			.collect()
+	}
+
+	fn set_reputations(&self, reputations: Vec<(sc_network_types::PeerId, i32)>) {
+		let mut inner = self.inner.lock();
+		for (peer_id, reputation) in reputations {
+			inner.set_reputation(peer_id.into(), reputation);
+		}
+	}
+}
+
+/// Decay `reputation` towards 0 as the peer store does over `seconds_passed`.
+pub(crate) fn decay_reputation(mut reputation: i32, seconds_passed: u64) -> i32 {
+	for _ in 0..seconds_passed {
+		let mut diff = reputation / INVERSE_DECREMENT;
+		if diff == 0 && reputation < 0 {
+			diff = -1;
+		} else if diff == 0 && reputation > 0 {
+			diff = 1;
+		}
+
+		reputation = reputation.saturating_sub(diff);
+
+		if reputation == 0 {
+			break
+		}
+	}
+
+	reputation
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -208,20 +252,7 @@ impl PeerInfo {
 	fn decay_reputation(&mut self, seconds_passed: u64) {
 		// Note that decaying the reputation value happens "on its own",
 		// so we don't do `bump_last_updated()`.
-		for _ in 0..seconds_passed {
-			let mut diff = self.reputation / INVERSE_DECREMENT;
-			if diff == 0 && self.reputation < 0 {
-				diff = -1;
-			} else if diff == 0 && self.reputation > 0 {
-				diff = 1;
-			}
-
-			self.reputation = self.reputation.saturating_sub(diff);
-
-			if self.reputation == 0 {
-				break
-			}
-		}
+		self.reputation = decay_reputation(self.reputation, seconds_passed);
 	}
 
 	fn bump_last_updated(&mut self) {
@@ -379,6 +410,20 @@ impl PeerStoreInner {
 		}
 	}
 
+	fn reputations(&self) -> Vec<(PeerId, i32)> {
+		self.peers
+			.iter()
+			.filter_map(|(peer_id, info)| {
+				(info.reputation != 0).then_some((*peer_id, info.reputation))
+			})
+			.collect()
+	}
+
+	fn set_reputation(&mut self, peer_id: PeerId, reputation: i32) {
+		let change = reputation.saturating_sub(self.peer_reputation(&peer_id));
+		self.report_peer(peer_id, ReputationChange::new(change, "Reputation set"));
+	}
+
 	fn add_known_peer(&mut self, peer_id: PeerId) {
 		match self.peers.entry(peer_id) {
 			Entry::Occupied(mut e) => {
@@ -570,4 +615,29 @@ mod tests {
 		assert_eq!(metrics.num_discovered.get(), 3);
 		assert_eq!(metrics.num_banned_peers.get(), 2);
 	}
+
+	#[test]
+	fn set_reputations_overrides_reputations() {
+		let peer_a = sc_network_types::PeerId::random();
+		let peer_b = sc_network_types::PeerId::random();
+		let peer_c = sc_network_types::PeerId::random();
+
+		let peerstore = PeerStore::new(vec![peer_c.into()], None);
+		let handle = peerstore.handle();
+		handle.report_peer(
+			peer_a,
+			sc_network_common::types::ReputationChange { value: 100, reason: "test".into() },
+		);
+
+		handle.set_reputations(vec![(peer_a, -50), (peer_b, i32::MIN)]);
+		assert_eq!(handle.peer_reputation(&peer_a), -50);
+		assert!(handle.is_banned(&peer_b));
+
+		// Peers without a reputation are not exported.
+		let mut reputations = handle.reputations();
+		reputations.sort();
+		let mut expected = vec![(peer_a, -50), (peer_b, i32::MIN)];
+		expected.sort();
+		assert_eq!(reputations, expected);
+	}
 }
@@ -2219,6 +2219,8 @@ impl NetworkBehaviour for Notifications {
 			NotifsHandlerOut::Notification { protocol_index, message } => {
 				let set_id = SetId::from(protocol_index);
 				if self.is_open(&peer_id, set_id) {
+					self.protocol_controller_handles[usize::from(set_id)]
+						.notification_received(peer_id);
 					trace!(
 						target: LOG_TARGET,
 						"Handler({:?}) => Notification({}, {:?}, {} bytes)",
@@ -2438,6 +2440,7 @@ mod tests {
 				out_peers: 25,
 				reserved_nodes: HashSet::new(),
 				reserved_only: false,
+				inactivity_eviction: None,
 			},
 			to_notifications,
 			Arc::new(MockPeerStore {}),
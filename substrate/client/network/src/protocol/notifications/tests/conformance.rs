@@ -67,6 +67,7 @@ fn setup_libp2p(
 			out_peers,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		},
 		to_notifications.clone(),
 		Arc::new(peer_store.handle()),
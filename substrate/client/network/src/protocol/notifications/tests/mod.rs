@@ -87,6 +87,7 @@ fn build_nodes() -> (Swarm<CustomProtoWithAddr>, Swarm<CustomProtoWithAddr>) {
 				out_peers: 25,
 				reserved_nodes: Default::default(),
 				reserved_only: false,
+				inactivity_eviction: None,
 			},
 			to_notifications,
 			Arc::new(peer_store.handle()),
@@ -41,11 +41,15 @@
 //! Even though this does not guarantee that `ProtocolController` and `Notifications` have the same
 //! view of the peers' states at any given moment, the eventual consistency is maintained.
 
-use crate::peer_store::{PeerStoreProvider, ProtocolHandle as ProtocolHandleT};
+use crate::{
+	peer_store::{PeerStoreProvider, ProtocolHandle as ProtocolHandleT},
+	ReputationChange,
+};
 
 use futures::{channel::oneshot, future::Either, FutureExt, StreamExt};
 use libp2p::PeerId;
 use log::{debug, error, trace, warn};
+use parking_lot::Mutex;
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_arithmetic::traits::SaturatedConversion;
 use std::{
@@ -58,6 +62,12 @@ use wasm_timer::Delay;
 /// Log target for this file.
 pub const LOG_TARGET: &str = "peerset";
 
+/// Reputation change for a peer disconnected because of its inactivity.
+///
+/// Just enough to make other candidates preferred when the freed slot is allocated.
+const INACTIVE_PEER_REPUTATION_CHANGE: ReputationChange =
+	ReputationChange::new(-(1 << 10), "Inactive peer");
+
 /// `Notifications` protocol index. For historical reasons it's called `SetId`, because it
 /// used to refer to a set of peers in a peerset for this protocol.
 ///
@@ -102,6 +112,41 @@ pub struct ProtoSetConfig {
 
 	/// If true, we only accept nodes in [`ProtoSetConfig::reserved_nodes`].
 	pub reserved_only: bool,
+
+	/// Disconnect regular nodes which are inactive, see [`InactivityEviction`].
+	pub inactivity_eviction: Option<InactivityEviction>,
+}
+
+/// Policy for disconnecting regular peers which don't send notifications, freeing their slots for
+/// other peers.
+///
+/// Reserved peers are never disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InactivityEviction {
+	/// Peers from which no notification was received for this long are disconnected.
+	pub max_idle: Duration,
+	/// Peers from which fewer than this many notifications were received during the last
+	/// [`InactivityEviction::rate_window`] are disconnected. `0` disables the check.
+	pub min_notifications: u32,
+	/// The window over which [`InactivityEviction::min_notifications`] is checked.
+	pub rate_window: Duration,
+}
+
+/// Notification activity of a regular peer.
+#[derive(Debug, Clone, Copy)]
+struct PeerActivity {
+	/// When the last notification was received, or the peer was connected.
+	last_notification: Instant,
+	/// Start of the current [`InactivityEviction::rate_window`].
+	window_start: Instant,
+	/// Notifications received since `window_start`.
+	window_notifications: u32,
+}
+
+impl PeerActivity {
+	fn new(now: Instant) -> Self {
+		Self { last_notification: now, window_start: now, window_notifications: 0 }
+	}
 }
 
 /// Message that is sent by [`ProtocolController`] to `Notifications`.
@@ -167,6 +212,12 @@ enum Event {
 	Dropped(PeerId),
 }
 
+/// Number of notifications received from each peer since the last check for inactive peers.
+///
+/// Written by `Notifications` and drained by [`ProtocolController`] on its periodic tick, so
+/// noting a notification doesn't cost a message per notification.
+type ReceivedNotifications = Arc<Mutex<HashMap<PeerId, u32>>>;
+
 /// Shared handle to [`ProtocolController`]. Distributed around the code outside of the
 /// protocol implementation.
 #[derive(Debug, Clone)]
@@ -175,6 +226,8 @@ pub struct ProtocolHandle {
 	actions_tx: TracingUnboundedSender<Action>,
 	/// Connection events from `Notifications`. We prioritize them over actions.
 	events_tx: TracingUnboundedSender<Event>,
+	/// Notifications received from peers, if [`ProtocolController`] tracks their activity.
+	received_notifications: Option<ReceivedNotifications>,
 }
 
 impl ProtocolHandle {
@@ -229,6 +282,17 @@ impl ProtocolHandle {
 	pub fn dropped(&self, peer_id: PeerId) {
 		let _ = self.events_tx.unbounded_send(Event::Dropped(peer_id));
 	}
+
+	/// Note that a notification was received from the peer.
+	///
+	/// Does nothing if [`ProtocolController`] doesn't evict inactive peers.
+	pub fn notification_received(&self, peer_id: PeerId) {
+		if let Some(received_notifications) = &self.received_notifications {
+			let mut received_notifications = received_notifications.lock();
+			let count = received_notifications.entry(peer_id).or_default();
+			*count = count.saturating_add(1);
+		}
+	}
 }
 
 impl ProtocolHandleT for ProtocolHandle {
@@ -290,6 +354,14 @@ pub struct ProtocolController {
 	reserved_nodes: HashMap<PeerId, PeerState>,
 	/// Connect only to reserved nodes.
 	reserved_only: bool,
+	/// Policy for disconnecting inactive regular nodes.
+	inactivity_eviction: Option<InactivityEviction>,
+	/// Notification activity of the connected regular nodes.
+	///
+	/// Entries are created lazily when checking for inactive nodes.
+	activity: HashMap<PeerId, PeerActivity>,
+	/// Notifications received since the last check for inactive nodes.
+	received_notifications: ReceivedNotifications,
 	/// Next time to allocate slots. This is done once per second.
 	next_periodic_alloc_slots: Instant,
 	/// Outgoing channel for messages to `Notifications`.
@@ -309,7 +381,15 @@ impl ProtocolController {
 	) -> (ProtocolHandle, ProtocolController) {
 		let (actions_tx, actions_rx) = tracing_unbounded("mpsc_api_protocol", 10_000);
 		let (events_tx, events_rx) = tracing_unbounded("mpsc_notifications_protocol", 10_000);
-		let handle = ProtocolHandle { actions_tx, events_tx };
+		let received_notifications = ReceivedNotifications::default();
+		let handle = ProtocolHandle {
+			actions_tx,
+			events_tx,
+			received_notifications: config
+				.inactivity_eviction
+				.is_some()
+				.then(|| received_notifications.clone()),
+		};
 		peer_store.register_protocol(Arc::new(handle.clone()));
 		let reserved_nodes =
 			config.reserved_nodes.iter().map(|p| (*p, PeerState::NotConnected)).collect();
@@ -324,6 +404,9 @@ impl ProtocolController {
 			nodes: HashMap::new(),
 			reserved_nodes,
 			reserved_only: config.reserved_only,
+			inactivity_eviction: config.inactivity_eviction,
+			activity: HashMap::new(),
+			received_notifications,
 			next_periodic_alloc_slots: Instant::now(),
 			to_notifications,
 			peer_store,
@@ -355,6 +438,7 @@ impl ProtocolController {
 					None => return false,
 				},
 				_ = next_alloc_slots => {
+					self.evict_inactive_peers(Instant::now());
 					self.alloc_slots();
 					self.next_periodic_alloc_slots = Instant::now() + Duration::new(1, 0);
 				},
@@ -778,6 +862,72 @@ impl ProtocolController {
 		true
 	}
 
+	/// Disconnect the regular peers which are inactive according to the
+	/// [`InactivityEviction`] policy.
+	///
+	/// Notifications received since the previous call are accounted to `now`. Nobody is
+	/// disconnected if most of the regular peers look inactive at once, as that rather points to
+	/// a stall of the protocol, or of the local node, than to misbehaving peers.
+	fn evict_inactive_peers(&mut self, now: Instant) {
+		let Some(policy) = self.inactivity_eviction else { return };
+
+		// Forget the peers which are no longer connected.
+		self.activity.retain(|peer_id, _| self.nodes.contains_key(peer_id));
+
+		let received_notifications = std::mem::take(&mut *self.received_notifications.lock());
+		for (peer_id, count) in received_notifications {
+			if !self.nodes.contains_key(&peer_id) {
+				continue
+			}
+
+			let activity = self.activity.entry(peer_id).or_insert_with(|| PeerActivity::new(now));
+			activity.last_notification = now;
+			activity.window_notifications = activity.window_notifications.saturating_add(count);
+		}
+
+		let mut inactive = Vec::new();
+		for peer_id in self.nodes.keys() {
+			let activity = self.activity.entry(*peer_id).or_insert_with(|| PeerActivity::new(now));
+
+			if now.saturating_duration_since(activity.last_notification) >= policy.max_idle {
+				inactive.push(*peer_id);
+				continue
+			}
+
+			if now.saturating_duration_since(activity.window_start) >= policy.rate_window {
+				if activity.window_notifications < policy.min_notifications {
+					inactive.push(*peer_id);
+					continue
+				}
+
+				activity.window_start = now;
+				activity.window_notifications = 0;
+			}
+		}
+
+		if inactive.len() * 2 > self.nodes.len() {
+			debug!(
+				target: LOG_TARGET,
+				"{} of {} peers look inactive in {:?}, not disconnecting anyone.",
+				inactive.len(),
+				self.nodes.len(),
+				self.set_id,
+			);
+			return
+		}
+
+		for peer_id in inactive {
+			debug!(
+				target: LOG_TARGET,
+				"Disconnecting inactive peer {peer_id} from {:?}.",
+				self.set_id,
+			);
+			self.activity.remove(&peer_id);
+			self.peer_store.report_peer(peer_id.into(), INACTIVE_PEER_REPUTATION_CHANGE);
+			self.on_disconnect_peer(peer_id);
+		}
+	}
+
 	/// Initiate outgoing connections trying to connect all reserved nodes and fill in all outgoing
 	/// slots.
 	fn alloc_slots(&mut self) {
@@ -879,6 +1029,8 @@ mod tests {
 			fn peer_role(&self, peer_id: &sc_network_types::PeerId) -> Option<ObservedRole>;
 			fn outgoing_candidates(&self, count: usize, ignored: HashSet<sc_network_types::PeerId>) -> Vec<sc_network_types::PeerId>;
 			fn add_known_peer(&self, peer_id: sc_network_types::PeerId);
+			fn reputations(&self) -> Vec<(sc_network_types::PeerId, i32)>;
+			fn set_reputations(&self, reputations: Vec<(sc_network_types::PeerId, i32)>);
 		}
 	}
 
@@ -893,6 +1045,7 @@ mod tests {
 			out_peers: 0,
 			reserved_nodes: std::iter::once(reserved1).collect(),
 			reserved_only: true,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -955,6 +1108,7 @@ mod tests {
 			out_peers: 0,
 			reserved_nodes: std::iter::once(reserved1).collect(),
 			reserved_only: true,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1006,6 +1160,7 @@ mod tests {
 			out_peers: 0,
 			reserved_nodes: std::iter::once(reserved1).collect(),
 			reserved_only: true,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1065,6 +1220,7 @@ mod tests {
 			out_peers: 2,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1110,8 +1266,13 @@ mod tests {
 		let outgoing_candidates = vec![regular1.into(), regular2.into()];
 		let reserved_nodes = [reserved1, reserved2].iter().cloned().collect();
 
-		let config =
-			ProtoSetConfig { in_peers: 10, out_peers: 10, reserved_nodes, reserved_only: false };
+		let config = ProtoSetConfig {
+			in_peers: 10,
+			out_peers: 10,
+			reserved_nodes,
+			reserved_only: false,
+			inactivity_eviction: None,
+		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
 		let mut peer_store = MockPeerStoreHandle::new();
@@ -1152,6 +1313,7 @@ mod tests {
 			out_peers: 2,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1222,6 +1384,7 @@ mod tests {
 			out_peers: 2,
 			reserved_nodes: HashSet::new(),
 			reserved_only: true,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1248,6 +1411,7 @@ mod tests {
 			out_peers: 0,
 			reserved_nodes: HashSet::new(),
 			reserved_only: true,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1285,6 +1449,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: true,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1331,6 +1496,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: [reserved1, reserved2].iter().cloned().collect(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1391,6 +1557,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: [reserved1, reserved2].iter().cloned().collect(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1423,6 +1590,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: [reserved1, reserved2].iter().cloned().collect(),
 			reserved_only: true,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1469,6 +1637,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: [peer1, peer2].iter().cloned().collect(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1518,6 +1687,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1560,6 +1730,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1609,6 +1780,114 @@ mod tests {
 		assert_eq!(controller.num_out, 0);
 	}
 
+	#[test]
+	fn inactive_regular_peers_are_evicted() {
+		let active = PeerId::random();
+		let idle = PeerId::random();
+		let quiet = PeerId::random();
+		let reserved = PeerId::random();
+		let policy = InactivityEviction {
+			max_idle: Duration::from_secs(60),
+			min_notifications: 2,
+			rate_window: Duration::from_secs(100),
+		};
+
+		let config = ProtoSetConfig {
+			in_peers: 10,
+			out_peers: 0,
+			reserved_nodes: std::iter::once(reserved).collect(),
+			reserved_only: false,
+			inactivity_eviction: Some(policy),
+		};
+		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
+
+		let mut peer_store = MockPeerStoreHandle::new();
+		peer_store.expect_register_protocol().once().return_const(());
+		peer_store.expect_is_banned().times(4).return_const(false);
+		peer_store.expect_report_peer().times(2).return_const(());
+
+		let (handle, mut controller) =
+			ProtocolController::new(SetId::from(0), config, tx, Arc::new(peer_store));
+
+		for (index, peer_id) in [active, idle, quiet, reserved].into_iter().enumerate() {
+			controller.on_incoming_connection(peer_id, IncomingIndex(index as u64));
+			assert_eq!(rx.try_recv().unwrap(), Message::Accept(IncomingIndex(index as u64)));
+		}
+
+		let start = Instant::now();
+		controller.evict_inactive_peers(start);
+		assert_eq!(controller.nodes.len(), 3);
+
+		// `idle` doesn't send anything, `quiet` only a single notification.
+		handle.notification_received(active);
+		controller.evict_inactive_peers(start + Duration::from_secs(30));
+		handle.notification_received(quiet);
+		handle.notification_received(reserved);
+		controller.evict_inactive_peers(start + Duration::from_secs(50));
+		assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+
+		controller.evict_inactive_peers(start + Duration::from_secs(60));
+		assert_eq!(rx.try_recv().unwrap(), Message::Drop { set_id: SetId::from(0), peer_id: idle });
+		assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+
+		handle.notification_received(active);
+		controller.evict_inactive_peers(start + Duration::from_secs(80));
+		controller.evict_inactive_peers(start + Duration::from_secs(100));
+		assert_eq!(
+			rx.try_recv().unwrap(),
+			Message::Drop { set_id: SetId::from(0), peer_id: quiet }
+		);
+		assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+
+		// Reserved peers are never evicted.
+		assert_eq!(controller.nodes.keys().collect::<Vec<_>>(), vec![&active]);
+		assert!(controller.reserved_nodes.get(&reserved).unwrap().is_connected());
+		assert_eq!(controller.num_in, 1);
+	}
+
+	#[test]
+	fn peers_are_not_evicted_when_most_are_inactive() {
+		let active = PeerId::random();
+		let idle1 = PeerId::random();
+		let idle2 = PeerId::random();
+		let policy = InactivityEviction {
+			max_idle: Duration::from_secs(60),
+			min_notifications: 0,
+			rate_window: Duration::from_secs(100),
+		};
+
+		let config = ProtoSetConfig {
+			in_peers: 10,
+			out_peers: 0,
+			reserved_nodes: HashSet::new(),
+			reserved_only: false,
+			inactivity_eviction: Some(policy),
+		};
+		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
+
+		let mut peer_store = MockPeerStoreHandle::new();
+		peer_store.expect_register_protocol().once().return_const(());
+		peer_store.expect_is_banned().times(3).return_const(false);
+		peer_store.expect_report_peer().never();
+
+		let (handle, mut controller) =
+			ProtocolController::new(SetId::from(0), config, tx, Arc::new(peer_store));
+
+		for (index, peer_id) in [active, idle1, idle2].into_iter().enumerate() {
+			controller.on_incoming_connection(peer_id, IncomingIndex(index as u64));
+			assert_eq!(rx.try_recv().unwrap(), Message::Accept(IncomingIndex(index as u64)));
+		}
+
+		let start = Instant::now();
+		controller.evict_inactive_peers(start);
+
+		// Two of three peers went silent at once, which looks like a stall.
+		handle.notification_received(active);
+		controller.evict_inactive_peers(start + Duration::from_secs(60));
+		assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+		assert_eq!(controller.nodes.len(), 3);
+	}
+
 	#[test]
 	fn disconnecting_reserved_peers_is_a_noop() {
 		let reserved1 = PeerId::random();
@@ -1619,6 +1898,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: [reserved1, reserved2].iter().cloned().collect(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1675,6 +1955,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1727,6 +2008,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: [reserved1, reserved2].iter().cloned().collect(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1787,6 +2069,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1839,6 +2122,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1892,6 +2176,7 @@ mod tests {
 			out_peers: 1,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1945,6 +2230,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -1975,6 +2261,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: HashSet::new(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -2000,6 +2287,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: std::iter::once(reserved1).collect(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
@@ -2026,6 +2314,7 @@ mod tests {
 			out_peers: 10,
 			reserved_nodes: std::iter::once(reserved1).collect(),
 			reserved_only: false,
+			inactivity_eviction: None,
 		};
 		let (tx, mut rx) = tracing_unbounded("mpsc_test_to_notifications", 100);
 
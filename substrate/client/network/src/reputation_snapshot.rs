@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistence of the peer reputations across restarts.
+//!
+//! The reputations of a [`PeerStoreProvider`] are periodically written to a JSON file, and
+//! restored from it on startup. The reputations decay while the node is down just like they do
+//! while it is running, so misbehaving peers stay banned for the same time regardless of restarts.
+
+use crate::peer_store::{decay_reputation, PeerStoreProvider, LOG_TARGET};
+
+use sc_network_types::PeerId;
+use serde::{Deserialize, Serialize};
+use wasm_timer::Delay;
+
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the reputations file in the network configuration directory.
+pub const FILE_NAME: &str = "peer_reputations.json";
+
+/// Interval at which the reputations are written to disk.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Reputations as they are stored on disk.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+	/// Seconds since the UNIX epoch at which the snapshot was taken.
+	saved_at: u64,
+	/// The reputations of the peers.
+	reputations: Vec<PeerReputation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerReputation {
+	/// Base58-encoded `PeerId`.
+	peer_id: String,
+	reputation: i32,
+}
+
+fn unix_time() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs())
+}
+
+impl Snapshot {
+	fn new(reputations: &[(PeerId, i32)], saved_at: u64) -> Self {
+		let reputations = reputations
+			.iter()
+			.map(|(peer_id, reputation)| PeerReputation {
+				peer_id: peer_id.to_base58(),
+				reputation: *reputation,
+			})
+			.collect();
+
+		Self { saved_at, reputations }
+	}
+
+	/// Get the reputations decayed up to `now`.
+	///
+	/// Entries with an invalid `PeerId` and reputations which decayed to 0 are skipped.
+	fn into_reputations(self, now: u64) -> Vec<(PeerId, i32)> {
+		let downtime = now.saturating_sub(self.saved_at);
+
+		self.reputations
+			.into_iter()
+			.filter_map(|PeerReputation { peer_id, reputation }| {
+				let Ok(peer_id) = peer_id.parse::<PeerId>() else {
+					log::debug!(target: LOG_TARGET, "Skipping reputation of invalid peer {peer_id}");
+					return None
+				};
+
+				let reputation = decay_reputation(reputation, downtime);
+				(reputation != 0).then_some((peer_id, reputation))
+			})
+			.collect()
+	}
+}
+
+/// Load the reputations stored at `path`, decayed for the time passed since they were saved.
+///
+/// Returns no reputations if the file does not exist.
+pub fn load(path: &Path) -> io::Result<Vec<(PeerId, i32)>> {
+	let file = match fs::File::open(path) {
+		Ok(file) => file,
+		Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(err) => return Err(err),
+	};
+	let snapshot: Snapshot = serde_json::from_reader(io::BufReader::new(file))?;
+
+	Ok(snapshot.into_reputations(unix_time()))
+}
+
+/// Store `reputations` at `path`.
+///
+/// The file is replaced atomically, so that a crash while saving does not lose the reputations.
+pub fn save(path: &Path, reputations: &[(PeerId, i32)]) -> io::Result<()> {
+	let tmp_path = path.with_extension("tmp");
+	fs::write(&tmp_path, serde_json::to_vec(&Snapshot::new(reputations, unix_time()))?)?;
+
+	fs::rename(tmp_path, path)
+}
+
+/// Restore the reputations of `peer_store` from `path` and keep saving them there.
+pub async fn run(peer_store: Arc<dyn PeerStoreProvider>, path: PathBuf) {
+	match load(&path) {
+		Ok(reputations) => {
+			log::debug!(
+				target: LOG_TARGET,
+				"Restored {} peer reputations from {}",
+				reputations.len(),
+				path.display(),
+			);
+			peer_store.set_reputations(reputations);
+		},
+		Err(err) => log::warn!(
+			target: LOG_TARGET,
+			"Failed to restore peer reputations from {}: {err}",
+			path.display(),
+		),
+	}
+
+	loop {
+		let _ = Delay::new(SAVE_INTERVAL).await;
+
+		if let Err(err) = save(&path, &peer_store.reputations()) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to save peer reputations to {}: {err}",
+				path.display(),
+			);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reputations_survive_a_restart() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(FILE_NAME);
+		assert!(load(&path).unwrap().is_empty());
+
+		let peer_a = PeerId::random();
+		let peer_b = PeerId::random();
+		save(&path, &[(peer_a, i32::MIN), (peer_b, 1000)]).unwrap();
+
+		let mut reputations = load(&path).unwrap();
+		reputations.sort();
+		let mut expected = vec![(peer_a, i32::MIN), (peer_b, 1000)];
+		expected.sort();
+		assert_eq!(reputations.len(), expected.len());
+		for ((peer, reputation), (expected_peer, expected_reputation)) in
+			reputations.into_iter().zip(expected)
+		{
+			assert_eq!(peer, expected_peer);
+			// The clock may have ticked while saving and loading.
+			assert!((0..=2).any(|secs| decay_reputation(expected_reputation, secs) == reputation));
+		}
+	}
+
+	#[test]
+	fn reputations_decay_during_downtime() {
+		let peer_a = PeerId::random();
+		let peer_b = PeerId::random();
+		let snapshot = Snapshot::new(&[(peer_a, i32::MIN), (peer_b, 100)], 1000);
+
+		// `peer_b` decayed to 0 and is dropped.
+		assert_eq!(
+			snapshot.into_reputations(1000 + 600),
+			vec![(peer_a, decay_reputation(i32::MIN, 600))],
+		);
+	}
+}
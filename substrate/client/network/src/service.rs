@@ -366,6 +366,7 @@ where
 						.map(|node| node.peer_id.into())
 						.collect(),
 					reserved_only: set_config.non_reserved_mode.is_reserved_only(),
+					inactivity_eviction: set_config.inactivity_eviction,
 				};
 
 				ProtocolController::new(
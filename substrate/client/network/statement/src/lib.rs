@@ -138,6 +138,7 @@ impl StatementHandlerPrototype {
 				out_peers: 0,
 				reserved_nodes: Vec::new(),
 				non_reserved_mode: NonReservedPeerMode::Deny,
+				inactivity_eviction: None,
 			},
 			metrics,
 			peer_store_handle,
@@ -27,6 +27,7 @@ mod disconnected_peers;
 pub mod polkadot;
 pub mod state;
 pub mod state_sync;
+mod state_sync_journal;
 pub mod warp;
 
 use crate::{
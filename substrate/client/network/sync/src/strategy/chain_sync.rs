@@ -37,6 +37,7 @@ use crate::{
 	strategy::{
 		disconnected_peers::DisconnectedPeers,
 		state_sync::{ImportResult, StateSync, StateSyncProvider},
+		state_sync_journal::{StateSyncJournal, MAX_RESUMABLE_TARGET_AGE},
 		warp::{WarpSyncPhase, WarpSyncProgress},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -69,6 +70,7 @@ use std::{
 	any::Any,
 	collections::{HashMap, HashSet},
 	ops::Range,
+	path::PathBuf,
 	sync::Arc,
 };
 
@@ -332,6 +334,8 @@ pub struct ChainSync<B: BlockT, Client> {
 	downloaded_blocks: usize,
 	/// State sync in progress, if any.
 	state_sync: Option<StateSync<B, Client>>,
+	/// Path of the journal used to resume an interrupted state sync, if any.
+	state_sync_journal: Option<PathBuf>,
 	/// Enable importing existing blocks. This is used after the state download to
 	/// catch up to the latest state while re-importing blocks.
 	import_existing: bool,
@@ -968,6 +972,7 @@ where
 			state_request_protocol_name,
 			downloaded_blocks: 0,
 			state_sync: None,
+			state_sync_journal: None,
 			import_existing: false,
 			block_downloader,
 			gap_sync: None,
@@ -992,6 +997,13 @@ where
 		Ok(sync)
 	}
 
+	/// Persist the progress of state sync to the journal at `path`, so that an interrupted state
+	/// sync resumes where it stopped after a restart.
+	pub fn with_state_sync_journal(mut self, path: Option<PathBuf>) -> Self {
+		self.state_sync_journal = path;
+		self
+	}
+
 	#[must_use]
 	fn add_peer_inner(
 		&mut self,
@@ -2033,14 +2045,20 @@ where
 		heads.sort();
 		let median = heads[heads.len() / 2];
 		if finalized_number + STATE_SYNC_FINALITY_THRESHOLD.saturated_into() >= median {
-			if let Ok(Some(header)) = self.client.header(finalized_hash) {
+			if let Some(header) = self.resumable_state_sync_target(finalized_number) {
+				log::debug!(
+					target: LOG_TARGET,
+					"Resuming state sync for #{} ({})",
+					header.number(),
+					header.hash(),
+				);
+				self.start_state_sync(header, skip_proofs);
+			} else if let Ok(Some(header)) = self.client.header(finalized_hash) {
 				log::debug!(
 					target: LOG_TARGET,
 					"Starting state sync for #{finalized_number} ({finalized_hash})",
 				);
-				self.state_sync =
-					Some(StateSync::new(self.client.clone(), header, None, None, skip_proofs));
-				self.allowed_requests.set_all();
+				self.start_state_sync(header, skip_proofs);
 			} else {
 				log::error!(
 					target: LOG_TARGET,
@@ -2052,6 +2070,29 @@ where
 		}
 	}
 
+	/// Returns the header of the journaled state sync target if the state sync can be resumed
+	/// instead of being started from scratch at `finalized_number`.
+	///
+	/// The journaled target must still be part of the canonical chain and recent enough for the
+	/// peers to have its state.
+	fn resumable_state_sync_target(&self, finalized_number: NumberFor<B>) -> Option<B::Header> {
+		let target = StateSyncJournal::read_target::<B>(self.state_sync_journal.as_deref()?)?;
+		let age = finalized_number.checked_sub(&target.number())?;
+
+		(age <= MAX_RESUMABLE_TARGET_AGE.into() &&
+			self.client.hash(target.number()).ok().flatten() == Some(target.hash()))
+		.then_some(target.header)
+	}
+
+	fn start_state_sync(&mut self, header: B::Header, skip_proofs: bool) {
+		let state_sync = StateSync::new(self.client.clone(), header, None, None, skip_proofs);
+		self.state_sync = Some(match &self.state_sync_journal {
+			Some(path) => state_sync.with_journal(path.clone()),
+			None => state_sync,
+		});
+		self.allowed_requests.set_all();
+	}
+
 	/// A version of `actions()` that doesn't schedule extra requests. For testing only.
 	#[cfg(test)]
 	#[must_use]
@@ -26,6 +26,8 @@ use crate::{
 	strategy::{
 		chain_sync::{ChainSync, ChainSyncMode},
 		state::StateStrategy,
+		state_sync::StateSync,
+		state_sync_journal::{JournalTarget, StateSyncJournal, MAX_RESUMABLE_TARGET_AGE},
 		warp::{WarpSync, WarpSyncConfig},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -41,7 +43,7 @@ use sc_network_common::sync::{message::BlockAnnounce, SyncMode};
 use sc_network_types::PeerId;
 use sp_blockchain::{Error as ClientError, HeaderBackend, HeaderMetadata};
 use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{any::Any, collections::HashMap, path::PathBuf, sync::Arc};
 
 /// Corresponding `ChainSync` mode.
 fn chain_sync_mode(sync_mode: SyncMode) -> ChainSyncMode {
@@ -73,6 +75,10 @@ where
 	pub state_request_protocol_name: ProtocolName,
 	/// Block downloader
 	pub block_downloader: Arc<dyn BlockDownloader<Block>>,
+	/// Path of the journal used to resume an interrupted state sync after a restart.
+	///
+	/// State sync progress is not persisted if `None`.
+	pub state_sync_journal_path: Option<PathBuf>,
 }
 
 /// Proxy to specific syncing strategies used in Polkadot.
@@ -382,7 +388,8 @@ where
 				config.block_downloader.clone(),
 				config.metrics_registry.as_ref(),
 				std::iter::empty(),
-			)?;
+			)?
+			.with_state_sync_journal(config.state_sync_journal_path.clone());
 			Ok(Self {
 				config,
 				client,
@@ -404,17 +411,48 @@ where
 						target: LOG_TARGET,
 						"Warp sync is complete, continuing with state sync."
 					);
-					let state_sync = StateStrategy::new(
-						self.client.clone(),
-						res.target_header,
-						res.target_body,
-						res.target_justifications,
-						false,
-						self.peer_best_blocks
-							.iter()
-							.map(|(peer_id, (_, best_number))| (*peer_id, *best_number)),
-						self.config.state_request_protocol_name.clone(),
-					);
+					let (target_header, target_body, target_justifications) =
+						match self.resumable_state_sync_target(*res.target_header.number()) {
+							Some(target) => {
+								info!(
+									target: LOG_TARGET,
+									"Resuming interrupted state sync of #{} ({}).",
+									target.number(),
+									target.hash(),
+								);
+								(target.header, target.body, target.justifications)
+							},
+							None => (res.target_header, res.target_body, res.target_justifications),
+						};
+					let peers = self
+						.peer_best_blocks
+						.iter()
+						.map(|(peer_id, (_, best_number))| (*peer_id, *best_number));
+					let state_sync = match &self.config.state_sync_journal_path {
+						Some(path) => StateStrategy::new_with_provider(
+							Box::new(
+								StateSync::new(
+									self.client.clone(),
+									target_header,
+									target_body,
+									target_justifications,
+									false,
+								)
+								.with_journal(path.clone()),
+							),
+							peers,
+							self.config.state_request_protocol_name.clone(),
+						),
+						None => StateStrategy::new(
+							self.client.clone(),
+							target_header,
+							target_body,
+							target_justifications,
+							false,
+							peers,
+							self.config.state_request_protocol_name.clone(),
+						),
+					};
 
 					self.warp = None;
 					self.state = Some(state_sync);
@@ -437,7 +475,8 @@ where
 							(*peer_id, *best_hash, *best_number)
 						}),
 					) {
-						Ok(chain_sync) => chain_sync,
+						Ok(chain_sync) => chain_sync
+							.with_state_sync_journal(self.config.state_sync_journal_path.clone()),
 						Err(e) => {
 							error!(target: LOG_TARGET, "Failed to start `ChainSync`.");
 							return Err(e)
@@ -467,7 +506,8 @@ where
 					(*peer_id, *best_hash, *best_number)
 				}),
 			) {
-				Ok(chain_sync) => chain_sync,
+				Ok(chain_sync) =>
+					chain_sync.with_state_sync_journal(self.config.state_sync_journal_path.clone()),
 				Err(e) => {
 					error!(target: LOG_TARGET, "Failed to start `ChainSync`.");
 					return Err(e);
@@ -481,4 +521,16 @@ where
 			unreachable!("Only warp & state strategies can finish; qed")
 		}
 	}
+
+	/// Returns the journaled state sync target if the state sync interrupted by a restart can be
+	/// resumed instead of starting a new one at `target_number`.
+	fn resumable_state_sync_target(&self, target_number: NumberFor<B>) -> Option<JournalTarget<B>> {
+		let target =
+			StateSyncJournal::read_target::<B>(self.config.state_sync_journal_path.as_deref()?)?;
+
+		(target.number() <= target_number &&
+			target_number - target.number() <= MAX_RESUMABLE_TARGET_AGE.into() &&
+			!target.skip_proof)
+			.then_some(target)
+	}
 }
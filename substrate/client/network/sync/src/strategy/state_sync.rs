@@ -20,10 +20,11 @@
 
 use crate::{
 	schema::v1::{KeyValueStateEntry, StateEntry, StateRequest, StateResponse},
+	strategy::state_sync_journal::{JournalChunk, JournalTarget, StateSyncJournal},
 	LOG_TARGET,
 };
 use codec::{Decode, Encode};
-use log::debug;
+use log::{debug, info, warn};
 use sc_client_api::{CompactProof, KeyValueStates, ProofProvider};
 use sc_consensus::ImportedState;
 use smallvec::SmallVec;
@@ -32,7 +33,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications,
 };
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{collections::HashMap, fmt, path::PathBuf, sync::Arc};
 
 /// Generic state sync provider. Used for mocking in tests.
 pub trait StateSyncProvider<B: BlockT>: Send + Sync {
@@ -143,6 +144,8 @@ pub struct StateSync<B: BlockT, Client> {
 	metadata: StateSyncMetadata<B>,
 	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
 	client: Arc<Client>,
+	journal: Option<StateSyncJournal>,
+	journal_chunk: JournalChunk,
 }
 
 impl<B, Client> StateSync<B, Client>
@@ -170,6 +173,85 @@ where
 				skip_proof,
 			},
 			state: HashMap::default(),
+			journal: None,
+			journal_chunk: JournalChunk::default(),
+		}
+	}
+
+	/// Persist the downloaded state to the journal at `path`, so that the download can be resumed
+	/// after a restart.
+	///
+	/// If the journal already contains state downloaded for the same target, it is replayed and
+	/// the download continues from where it stopped. Journaling is disabled if the journal cannot
+	/// be opened.
+	pub fn with_journal(mut self, path: PathBuf) -> Self {
+		let target = JournalTarget::<B> {
+			header: self.metadata.target_header.clone(),
+			body: self.metadata.target_body.clone(),
+			justifications: self.metadata.target_justifications.clone(),
+			skip_proof: self.metadata.skip_proof,
+		};
+
+		let (journal, chunks) = match StateSyncJournal::open(&path, &target) {
+			Ok(journal) => journal,
+			Err(e) => {
+				warn!(
+					target: LOG_TARGET,
+					"Failed to open state sync journal at {}: {e}. State sync progress will \
+					 not be persisted.",
+					path.display(),
+				);
+				return self
+			},
+		};
+
+		if let Some(last) = chunks.last() {
+			info!(
+				target: LOG_TARGET,
+				"Resuming state sync of #{} ({}) with {} bytes already downloaded.",
+				self.metadata.target_number(),
+				self.metadata.target_hash(),
+				last.imported_bytes,
+			);
+		}
+
+		for chunk in chunks {
+			for (state_root, key_values) in chunk.entries {
+				self.process_state_key_values(state_root, key_values);
+			}
+			self.metadata.last_key = chunk.last_key.into();
+			self.metadata.imported_bytes = chunk.imported_bytes;
+		}
+
+		self.journal = Some(journal);
+		self
+	}
+
+	/// Append the key-values imported from the last response to the journal, if any.
+	fn write_journal(&mut self) {
+		let Some(journal) = self.journal.as_mut() else { return };
+
+		let chunk = JournalChunk {
+			last_key: self.metadata.last_key.clone().into_vec(),
+			imported_bytes: self.metadata.imported_bytes,
+			entries: std::mem::take(&mut self.journal_chunk.entries),
+		};
+
+		if let Err(e) = journal.append(&chunk) {
+			warn!(
+				target: LOG_TARGET,
+				"Failed to write state sync journal: {e}. State sync progress will not be \
+				 persisted anymore.",
+			);
+			self.journal = None;
+		}
+	}
+
+	/// Remove the journal, the state is complete and doesn't have to be resumed anymore.
+	fn remove_journal(&mut self) {
+		self.journal_chunk = JournalChunk::default();
+		if let Some(Err(e)) = self.journal.take().map(StateSyncJournal::remove) {
+			debug!(target: LOG_TARGET, "Failed to remove state sync journal: {e}");
 		}
 	}
 
@@ -180,7 +262,7 @@ where
 	) {
 		let is_top = state_root.is_empty();
 
-		let entry = self.state.entry(state_root).or_default();
+		let entry = self.state.entry(state_root.clone()).or_default();
 
 		if entry.0.len() > 0 && entry.1.len() > 1 {
 			// Already imported child_trie with same root.
@@ -190,6 +272,15 @@ where
 
 		let mut child_storage_roots = Vec::new();
 
+		let key_values = if self.journal.is_some() {
+			let key_values = key_values.into_iter().collect::<Vec<_>>();
+			self.journal_chunk.entries.push((state_root.clone(), key_values.clone()));
+			key_values
+		} else {
+			key_values.into_iter().collect()
+		};
+
+		let entry = self.state.entry(state_root).or_default();
 		for (key, value) in key_values {
 			// Skip all child key root (will be recalculated on import)
 			if is_top && well_known_keys::is_child_storage_key(key.as_slice()) {
@@ -303,6 +394,7 @@ where
 		};
 		if complete {
 			self.metadata.complete = true;
+			self.remove_journal();
 			let target_hash = self.metadata.target_hash();
 			ImportResult::Import(
 				target_hash,
@@ -312,6 +404,7 @@ where
 				self.metadata.target_justifications.clone(),
 			)
 		} else {
+			self.write_journal();
 			ImportResult::Continue
 		}
 	}
@@ -0,0 +1,297 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk journal of the state downloaded by [`super::state_sync::StateSync`].
+//!
+//! The journal is an append-only file of length-prefixed SCALE-encoded records. The first record
+//! describes the state sync target, every following record contains the key-values imported from
+//! a single state response together with the key cursor after that response. A partially written
+//! trailing record (e.g. because the node was killed mid-write) is discarded when the journal is
+//! opened again.
+
+use codec::{Decode, Encode};
+use sp_runtime::{
+	traits::{Block as BlockT, Header, NumberFor},
+	Justifications,
+};
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{self, Read, Seek, SeekFrom, Write},
+	path::{Path, PathBuf},
+};
+
+/// Maximum age (in blocks) of a journaled state sync target, relative to a freshly selected
+/// target, for it to be resumed. Beyond that the peers are likely to have pruned the state
+/// of the journaled target. This is half of the default state pruning window.
+pub(crate) const MAX_RESUMABLE_TARGET_AGE: u32 = 128;
+
+/// Maximum size of a record. Records are far smaller, as a chunk holds the key-values of a single
+/// state response, so a larger length can only be read from a corrupt journal.
+const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
+/// The block a journaled state sync is downloading the state of.
+#[derive(Encode, Decode)]
+pub(crate) struct JournalTarget<B: BlockT> {
+	pub(crate) header: B::Header,
+	pub(crate) body: Option<Vec<B::Extrinsic>>,
+	pub(crate) justifications: Option<Justifications>,
+	pub(crate) skip_proof: bool,
+}
+
+impl<B: BlockT> JournalTarget<B> {
+	/// Returns the hash of the target block.
+	pub(crate) fn hash(&self) -> B::Hash {
+		self.header.hash()
+	}
+
+	/// Returns the number of the target block.
+	pub(crate) fn number(&self) -> NumberFor<B> {
+		*self.header.number()
+	}
+}
+
+/// The key-values imported from a single state response.
+#[derive(Encode, Decode, Default)]
+pub(crate) struct JournalChunk {
+	/// Key cursor after the response was imported.
+	pub(crate) last_key: Vec<Vec<u8>>,
+	/// Total state size imported so far.
+	pub(crate) imported_bytes: u64,
+	/// Imported key-values, grouped by (child) trie root.
+	pub(crate) entries: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>,
+}
+
+/// Append-only journal of an ongoing state sync.
+pub(crate) struct StateSyncJournal {
+	path: PathBuf,
+	file: File,
+}
+
+impl StateSyncJournal {
+	/// Read the target of the journal stored at `path`, if any.
+	pub(crate) fn read_target<B: BlockT>(path: &Path) -> Option<JournalTarget<B>> {
+		let mut file = File::open(path).ok()?;
+		let (record, _) = read_record(&mut file).ok()??;
+		JournalTarget::decode(&mut &record[..]).ok()
+	}
+
+	/// Open the journal stored at `path` for the given `target`.
+	///
+	/// If the journal was written for the same target, the chunks it contains are returned so
+	/// that they can be replayed. Otherwise the journal is reset.
+	pub(crate) fn open<B: BlockT>(
+		path: &Path,
+		target: &JournalTarget<B>,
+	) -> io::Result<(Self, Vec<JournalChunk>)> {
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		let mut file = OpenOptions::new().read(true).write(true).create(true).open(path)?;
+		let mut chunks = Vec::new();
+		let mut valid_len = 0;
+
+		let same_target = match read_record(&mut file)? {
+			Some((record, len)) => match JournalTarget::<B>::decode(&mut &record[..]) {
+				Ok(journaled)
+					if journaled.hash() == target.hash() &&
+						journaled.skip_proof == target.skip_proof =>
+				{
+					valid_len = len;
+					true
+				},
+				_ => false,
+			},
+			None => false,
+		};
+
+		if same_target {
+			while let Some((record, len)) = read_record(&mut file)? {
+				match JournalChunk::decode(&mut &record[..]) {
+					Ok(chunk) => {
+						chunks.push(chunk);
+						valid_len += len;
+					},
+					Err(_) => break,
+				}
+			}
+		}
+
+		// Drop anything after the last valid record, e.g. a partially written chunk.
+		file.set_len(valid_len)?;
+		file.seek(SeekFrom::End(0))?;
+
+		let mut journal = Self { path: path.to_path_buf(), file };
+		if !same_target {
+			journal.write_record(&target.encode())?;
+		}
+
+		Ok((journal, chunks))
+	}
+
+	/// Append a chunk to the journal.
+	pub(crate) fn append(&mut self, chunk: &JournalChunk) -> io::Result<()> {
+		self.write_record(&chunk.encode())
+	}
+
+	/// Remove the journal from disk.
+	pub(crate) fn remove(self) -> io::Result<()> {
+		drop(self.file);
+		fs::remove_file(&self.path)
+	}
+
+	fn write_record(&mut self, record: &[u8]) -> io::Result<()> {
+		let len = u32::try_from(record.len())
+			.ok()
+			.filter(|len| *len as usize <= MAX_RECORD_SIZE)
+			.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "record too large"))?;
+		self.file.write_all(&len.to_le_bytes())?;
+		self.file.write_all(record)?;
+		self.file.sync_data()
+	}
+}
+
+/// Read the next length-prefixed record, returning it alongside the number of bytes consumed.
+///
+/// Returns `None` if the end of the file is reached, including when the last record is truncated
+/// or its length exceeds [`MAX_RECORD_SIZE`].
+fn read_record(file: &mut File) -> io::Result<Option<(Vec<u8>, u64)>> {
+	let mut len = [0u8; 4];
+	match file.read_exact(&mut len) {
+		Ok(()) => {},
+		Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+		Err(e) => return Err(e),
+	}
+
+	let len = u32::from_le_bytes(len) as usize;
+	if len > MAX_RECORD_SIZE {
+		return Ok(None)
+	}
+
+	let mut record = vec![0u8; len];
+	match file.read_exact(&mut record) {
+		Ok(()) => Ok(Some((record, 4 + len as u64))),
+		Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+		Err(e) => Err(e),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::Header as _;
+	use substrate_test_runtime_client::runtime::{Block, Header};
+
+	fn target(number: u64) -> JournalTarget<Block> {
+		JournalTarget {
+			header: Header::new(
+				number,
+				Default::default(),
+				Default::default(),
+				Default::default(),
+				Default::default(),
+			),
+			body: None,
+			justifications: None,
+			skip_proof: false,
+		}
+	}
+
+	fn chunk(key: u8) -> JournalChunk {
+		JournalChunk {
+			last_key: vec![vec![key]],
+			imported_bytes: key as u64,
+			entries: vec![(Vec::new(), vec![(vec![key], vec![key])])],
+		}
+	}
+
+	#[test]
+	fn journal_is_replayed_for_the_same_target() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("state-sync");
+
+		let (mut journal, chunks) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		assert!(chunks.is_empty());
+		journal.append(&chunk(1)).unwrap();
+		journal.append(&chunk(2)).unwrap();
+		drop(journal);
+
+		assert_eq!(StateSyncJournal::read_target::<Block>(&path).unwrap().number(), 1);
+
+		let (_, chunks) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		assert_eq!(chunks.iter().map(|c| c.imported_bytes).collect::<Vec<_>>(), vec![1, 2]);
+	}
+
+	#[test]
+	fn journal_is_reset_for_a_different_target() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("state-sync");
+
+		let (mut journal, _) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		journal.append(&chunk(1)).unwrap();
+		drop(journal);
+
+		let (_, chunks) = StateSyncJournal::open(&path, &target(2)).unwrap();
+		assert!(chunks.is_empty());
+		assert_eq!(StateSyncJournal::read_target::<Block>(&path).unwrap().number(), 2);
+	}
+
+	#[test]
+	fn truncated_chunk_is_discarded() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("state-sync");
+
+		let (mut journal, _) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		journal.append(&chunk(1)).unwrap();
+		journal.append(&chunk(2)).unwrap();
+		drop(journal);
+
+		let len = fs::metadata(&path).unwrap().len();
+		OpenOptions::new().write(true).open(&path).unwrap().set_len(len - 1).unwrap();
+
+		let (mut journal, chunks) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		assert_eq!(chunks.len(), 1);
+
+		// appending after a truncated record keeps the journal readable.
+		journal.append(&chunk(3)).unwrap();
+		drop(journal);
+		let (journal, chunks) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		assert_eq!(chunks.iter().map(|c| c.imported_bytes).collect::<Vec<_>>(), vec![1, 3]);
+
+		journal.remove().unwrap();
+		assert!(!path.exists());
+	}
+
+	#[test]
+	fn oversized_record_is_discarded() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("state-sync");
+
+		let (mut journal, _) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		journal.append(&chunk(1)).unwrap();
+		drop(journal);
+
+		let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+		file.write_all(&u32::MAX.to_le_bytes()).unwrap();
+		file.write_all(&[0u8; 16]).unwrap();
+		drop(file);
+
+		let (_, chunks) = StateSyncJournal::open(&path, &target(1)).unwrap();
+		assert_eq!(chunks.iter().map(|c| c.imported_bytes).collect::<Vec<_>>(), vec![1]);
+	}
+}
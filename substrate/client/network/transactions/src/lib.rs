@@ -159,6 +159,7 @@ impl TransactionsHandlerPrototype {
 				out_peers: 0,
 				reserved_nodes: Vec::new(),
 				non_reserved_mode: NonReservedPeerMode::Deny,
+				inactivity_eviction: None,
 			},
 			metrics,
 			peer_store_handle,
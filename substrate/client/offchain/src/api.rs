@@ -21,7 +21,7 @@ use std::{collections::HashSet, str::FromStr, sync::Arc, thread::sleep};
 use crate::NetworkProvider;
 use codec::{Decode, Encode};
 use futures::Future;
-pub use http::SharedClient;
+pub use http::{HttpClientConfig, SharedClient};
 use sc_network::Multiaddr;
 use sc_network_types::PeerId;
 use sp_core::{
@@ -27,6 +27,7 @@
 //! (i.e.: the socket should continue being processed) in the background even if the runtime isn't
 //! actively calling any function.
 
+use self::proxy::ProxyConnector;
 use crate::api::timestamp;
 use bytes::buf::{Buf, Reader};
 use fnv::FnvHashMap;
@@ -36,16 +37,21 @@ use hyper::body::Body as _;
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
 use hyper_util::{client::legacy as client, rt::TokioExecutor};
 use once_cell::sync::Lazy;
+use rustls::pki_types::{pem::PemObject as _, CertificateDer};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_core::offchain::{HttpError, HttpRequestId, HttpRequestStatus, Timestamp};
 use std::{
-	fmt,
+	fmt, io,
 	io::Read as _,
+	path::PathBuf,
 	pin::Pin,
 	sync::Arc,
 	task::{Context, Poll},
+	time::Duration,
 };
 
+mod proxy;
+
 const LOG_TARGET: &str = "offchain-worker::http";
 
 pub type Body = BoxBody<hyper::body::Bytes, hyper::Error>;
@@ -53,22 +59,101 @@ pub type Body = BoxBody<hyper::body::Bytes, hyper::Error>;
 type Sender = mpsc::Sender<Result<hyper::body::Frame<hyper::body::Bytes>, hyper::Error>>;
 type Receiver = mpsc::Receiver<Result<hyper::body::Frame<hyper::body::Bytes>, hyper::Error>>;
 
-type HyperClient = client::Client<HttpsConnector<client::connect::HttpConnector>, Body>;
+type HyperClient = client::Client<HttpsConnector<ProxyConnector>, Body>;
 type LazyClient = Lazy<HyperClient, Box<dyn FnOnce() -> HyperClient + Send>>;
 
+/// Configuration of the HTTP client used by the offchain workers.
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientConfig {
+	/// URL of an HTTP proxy to send the requests through, e.g. `http://127.0.0.1:3128`.
+	///
+	/// HTTPS requests are tunnelled through the proxy with `CONNECT`.
+	pub proxy: Option<String>,
+	/// PEM files of certificate authorities to trust, in addition to the native ones.
+	pub ca_certificates: Vec<PathBuf>,
+	/// The longest a single HTTP host function may block, whatever the deadline given by the
+	/// runtime. Without a ceiling, a runtime passing no deadline waits for as long as it takes.
+	pub max_request_timeout: Option<Duration>,
+}
+
 /// Wrapper struct used for keeping the hyper_rustls client running.
 #[derive(Clone)]
-pub struct SharedClient(Arc<LazyClient>);
+pub struct SharedClient {
+	client: Arc<LazyClient>,
+	max_request_timeout: Option<Duration>,
+}
 
 impl SharedClient {
-	pub fn new() -> std::io::Result<Self> {
-		let builder = HttpsConnectorBuilder::new()
-			.with_provider_and_native_roots(rustls::crypto::ring::default_provider())?;
-		Ok(Self(Arc::new(Lazy::new(Box::new(|| {
-			let connector = builder.https_or_http().enable_http1().enable_http2().build();
-			client::Client::builder(TokioExecutor::new()).build(connector)
-		})))))
+	pub fn new() -> io::Result<Self> {
+		Self::with_config(&HttpClientConfig::default())
+	}
+
+	/// Creates a client with the given configuration.
+	///
+	/// The proxy URL and the certificates are checked right away, while the client itself is only
+	/// built when the first request is made.
+	pub fn with_config(config: &HttpClientConfig) -> io::Result<Self> {
+		let provider = rustls::crypto::ring::default_provider();
+		let builder = if config.ca_certificates.is_empty() {
+			HttpsConnectorBuilder::new().with_provider_and_native_roots(provider)?
+		} else {
+			HttpsConnectorBuilder::new()
+				.with_tls_config(tls_config(provider, &config.ca_certificates)?)
+		};
+		let proxy = config.proxy.as_deref().map(proxy::parse_proxy).transpose()?;
+
+		Ok(Self {
+			client: Arc::new(Lazy::new(Box::new(move || {
+				let connector = builder
+					.https_or_http()
+					.enable_http1()
+					.enable_http2()
+					.wrap_connector(ProxyConnector::new(proxy));
+				client::Client::builder(TokioExecutor::new()).build(connector)
+			}))),
+			max_request_timeout: config.max_request_timeout,
+		})
+	}
+}
+
+/// Builds a TLS configuration trusting the native certificate authorities and the ones found in
+/// `ca_certificates`.
+fn tls_config(
+	provider: rustls::crypto::CryptoProvider,
+	ca_certificates: &[PathBuf],
+) -> io::Result<rustls::ClientConfig> {
+	let mut roots = rustls::RootCertStore::empty();
+
+	let native = rustls_native_certs::load_native_certs();
+	for error in native.errors {
+		tracing::warn!(target: LOG_TARGET, %error, "Failed to load a native certificate");
+	}
+	roots.add_parsable_certificates(native.certs);
+
+	for path in ca_certificates {
+		let invalid = |reason: String| {
+			io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("Invalid CA certificate file {}: {reason}", path.display()),
+			)
+		};
+
+		let certificates = CertificateDer::pem_file_iter(path)
+			.and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+			.map_err(|error| invalid(error.to_string()))?;
+		if certificates.is_empty() {
+			return Err(invalid("no certificate found".into()));
+		}
+		for certificate in certificates {
+			roots.add(certificate).map_err(|error| invalid(error.to_string()))?;
+		}
 	}
+
+	Ok(rustls::ClientConfig::builder_with_provider(Arc::new(provider))
+		.with_safe_default_protocol_versions()
+		.map_err(io::Error::other)?
+		.with_root_certificates(roots)
+		.with_no_client_auth())
 }
 
 /// Creates a pair of [`HttpApi`] and [`HttpWorker`].
@@ -83,10 +168,11 @@ pub fn http(shared_client: SharedClient) -> (HttpApi, HttpWorker) {
 		// writing runtime code with hardcoded IDs.
 		next_id: HttpRequestId(rand::random::<u16>() % 2000),
 		requests: FnvHashMap::default(),
+		max_request_timeout: shared_client.max_request_timeout,
 	};
 
 	let engine =
-		HttpWorker { to_api, from_api, http_client: shared_client.0, requests: Vec::new() };
+		HttpWorker { to_api, from_api, http_client: shared_client.client, requests: Vec::new() };
 
 	(api, engine)
 }
@@ -105,6 +191,8 @@ pub struct HttpApi {
 	next_id: HttpRequestId,
 	/// List of HTTP requests in preparation or in progress.
 	requests: FnvHashMap<HttpRequestId, HttpApiRequest>,
+	/// Ceiling applied to the deadlines passed by the runtime.
+	max_request_timeout: Option<Duration>,
 }
 
 /// One active request within `HttpApi`.
@@ -144,6 +232,15 @@ struct HttpApiRequestRp {
 }
 
 impl HttpApi {
+	/// Caps `deadline` to the configured ceiling, if any.
+	fn clamp_deadline(&self, deadline: Option<Timestamp>) -> Option<Timestamp> {
+		let Some(max_request_timeout) = self.max_request_timeout else { return deadline };
+		let ceiling = timestamp::now().add(sp_core::offchain::Duration::from_millis(
+			max_request_timeout.as_millis().try_into().unwrap_or(u64::MAX),
+		));
+		Some(deadline.map_or(ceiling, |deadline| deadline.min(ceiling)))
+	}
+
 	/// Mimics the corresponding method in the offchain API.
 	pub fn request_start(&mut self, method: &str, uri: &str) -> Result<HttpRequestId, ()> {
 		// Start by building the prototype of the request.
@@ -216,7 +313,7 @@ impl HttpApi {
 		// Don't forget to add it back if necessary when returning.
 		let mut request = self.requests.remove(&request_id).ok_or(HttpError::Invalid)?;
 
-		let mut deadline = timestamp::deadline_to_future(deadline);
+		let mut deadline = timestamp::deadline_to_future(self.clamp_deadline(deadline));
 		// Closure that writes data to a sender, taking the deadline into account. Can return `Ok`
 		// (if the body has been written), or `DeadlineReached`, or `IoError`.
 		// If `IoError` is returned, don't forget to remove the request from the list.
@@ -366,7 +463,7 @@ impl HttpApi {
 			self.requests.insert(*id, HttpApiRequest::Dispatched(None));
 		}
 
-		let mut deadline = timestamp::deadline_to_future(deadline);
+		let mut deadline = timestamp::deadline_to_future(self.clamp_deadline(deadline));
 
 		loop {
 			// Within that loop, first try to see if we have all the elements for a response.
@@ -497,7 +594,9 @@ impl HttpApi {
 		buffer: &mut [u8],
 		deadline: Option<Timestamp>,
 	) -> Result<usize, HttpError> {
-		// Do an implicit wait on the request.
+		// Do an implicit wait on the request. The ceiling is computed once so that it applies to
+		// the whole call.
+		let deadline = self.clamp_deadline(deadline);
 		let _ = self.response_wait(&[request_id], deadline);
 
 		// Remove the request from the list and handle situations where the request is invalid or
@@ -794,9 +893,8 @@ mod tests {
 				hyper::body::Bytes::from("Hello World!")
 			)))
 		};
-		( $response:expr ) => {{
-			let hyper_client = SHARED_CLIENT.clone();
-			let (api, worker) = http(hyper_client.clone());
+		( $client:expr, $response:expr ) => {{
+			let (api, worker) = http($client);
 
 			let (addr_tx, addr_rx) = std::sync::mpsc::channel();
 			std::thread::spawn(move || {
@@ -834,6 +932,9 @@ mod tests {
 			});
 			(api, addr_rx.recv().unwrap())
 		}};
+		( $response:expr ) => {
+			build_api_server!(SHARED_CLIENT.clone(), $response)
+		};
 	}
 
 	#[test]
@@ -1124,6 +1225,87 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn requests_go_through_proxy() {
+		use std::io::{BufRead, Write};
+
+		let deadline = timestamp::now().add(Duration::from_millis(10_000));
+
+		// The proxy answers with the target of the request it received.
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let proxy = listener.local_addr().unwrap();
+		std::thread::spawn(move || {
+			let (mut stream, _) = listener.accept().unwrap();
+			let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+			let mut request_line = String::new();
+			reader.read_line(&mut request_line).unwrap();
+			let target = request_line.split(' ').nth(1).unwrap().to_owned();
+			write!(stream, "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}", target.len(), target)
+				.unwrap();
+			let _ = std::io::copy(&mut reader, &mut std::io::sink());
+		});
+
+		let client = SharedClient::with_config(&HttpClientConfig {
+			proxy: Some(format!("http://{}", proxy)),
+			..Default::default()
+		})
+		.unwrap();
+		let (mut api, _) = build_api_server!(
+			client,
+			hyper::Response::new(http_body_util::Empty::<hyper::body::Bytes>::new())
+		);
+
+		let id = api.request_start("GET", "http://offchain.invalid/path").unwrap();
+		match api.response_wait(&[id], Some(deadline))[0] {
+			HttpRequestStatus::Finished(200) => {},
+			v => panic!("Connecting through the proxy failed: {:?}", v),
+		}
+
+		let mut buf = vec![0; 2048];
+		let n = api.response_read_body(id, &mut buf, Some(deadline)).unwrap();
+		assert_eq!(&buf[..n], b"http://offchain.invalid/path");
+	}
+
+	#[test]
+	fn max_request_timeout_caps_deadlines() {
+		let client = SharedClient::with_config(&HttpClientConfig {
+			max_request_timeout: Some(std::time::Duration::from_millis(100)),
+			..Default::default()
+		})
+		.unwrap();
+		// The server never answers.
+		let (mut api, addr) = build_api_server!(client, {
+			future::pending::<()>().await;
+			hyper::Response::new(http_body_util::Empty::<hyper::body::Bytes>::new())
+		});
+
+		let id = api.request_start("POST", &format!("http://{}", addr)).unwrap();
+		api.request_write_body(id, &[], None).unwrap();
+
+		match api.response_wait(&[id], None)[0] {
+			HttpRequestStatus::DeadlineReached => {},
+			v => panic!("The deadline should have been capped: {:?}", v),
+		}
+		match api.response_read_body(id, &mut [0; 64], None) {
+			Err(HttpError::DeadlineReached) => {},
+			v => panic!("The deadline should have been capped: {:?}", v),
+		}
+	}
+
+	#[test]
+	fn invalid_client_config_is_rejected() {
+		for proxy in ["127.0.0.1:3128", "socks5://127.0.0.1:1080", "https://127.0.0.1:3128"] {
+			let config = HttpClientConfig { proxy: Some(proxy.into()), ..Default::default() };
+			assert!(SharedClient::with_config(&config).is_err(), "{proxy}");
+		}
+
+		let empty = tempfile::NamedTempFile::new().unwrap();
+		for path in [empty.path().into(), "/nonexistent/ca.pem".into()] {
+			let config = HttpClientConfig { ca_certificates: vec![path], ..Default::default() };
+			assert!(SharedClient::with_config(&config).is_err());
+		}
+	}
+
 	#[test]
 	fn shared_http_client_is_only_initialized_on_access() {
 		let shared_client = SharedClient::new().unwrap();
@@ -1139,7 +1321,7 @@ mod tests {
 		}
 
 		// Check that the http client wasn't initialized, because it wasn't used.
-		assert!(Lazy::into_value(Arc::try_unwrap(shared_client.0).unwrap()).is_err());
+		assert!(Lazy::into_value(Arc::try_unwrap(shared_client.client).unwrap()).is_err());
 
 		let shared_client = SharedClient::new().unwrap();
 
@@ -1154,6 +1336,6 @@ mod tests {
 		}
 
 		// Check that the http client initialized, because it was used.
-		assert!(Lazy::into_value(Arc::try_unwrap(shared_client.0).unwrap()).is_ok());
+		assert!(Lazy::into_value(Arc::try_unwrap(shared_client.client).unwrap()).is_ok());
 	}
 }
@@ -0,0 +1,200 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Connector sending the HTTP requests of the offchain workers through an HTTP proxy.
+//!
+//! Plain HTTP requests are forwarded by the proxy, the connection being flagged as proxied so
+//! that hyper writes them in absolute form. HTTPS requests go through a tunnel opened with a
+//! `CONNECT` request, the TLS session with the target being established through that tunnel by
+//! the [`HttpsConnector`](hyper_rustls::HttpsConnector) wrapping this connector.
+
+use futures::prelude::*;
+use hyper::{
+	http::uri::Scheme,
+	rt::{Read, ReadBufCursor, Write},
+	Uri,
+};
+use hyper_util::{
+	client::legacy::connect::{Connected, Connection, HttpConnector},
+	rt::TokioIo,
+};
+use std::{
+	io,
+	pin::Pin,
+	task::{Context, Poll},
+};
+use tokio::{
+	io::{AsyncReadExt as _, AsyncWriteExt as _},
+	net::TcpStream,
+};
+use tower::Service;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Upper bound on the size of the response of the proxy to a `CONNECT` request.
+const MAX_CONNECT_RESPONSE_LEN: usize = 8 * 1024;
+
+/// Parses the URL of an HTTP proxy, e.g. `http://127.0.0.1:3128`.
+pub fn parse_proxy(proxy: &str) -> io::Result<Uri> {
+	let invalid = |reason: String| {
+		io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid HTTP proxy {proxy}: {reason}"))
+	};
+
+	let uri = proxy.parse::<Uri>().map_err(|error| invalid(error.to_string()))?;
+	if uri.scheme() != Some(&Scheme::HTTP) || uri.host().is_none() {
+		return Err(invalid("expected an `http://<host>:<port>` URL".into()));
+	}
+
+	Ok(uri)
+}
+
+/// Connects either directly to the target of a request, or to the proxy when one is configured.
+#[derive(Clone)]
+pub struct ProxyConnector {
+	http: HttpConnector,
+	proxy: Option<Uri>,
+}
+
+impl ProxyConnector {
+	pub fn new(proxy: Option<Uri>) -> Self {
+		let mut http = HttpConnector::new();
+		// HTTPS targets are handled by the wrapping `HttpsConnector`.
+		http.enforce_http(false);
+		Self { http, proxy }
+	}
+}
+
+impl Service<Uri> for ProxyConnector {
+	type Response = ProxyStream;
+	type Error = BoxError;
+	type Future = Pin<Box<dyn Future<Output = Result<ProxyStream, BoxError>> + Send>>;
+
+	fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+		self.http.poll_ready(cx).map_err(Into::into)
+	}
+
+	fn call(&mut self, target: Uri) -> Self::Future {
+		let mut http = self.http.clone();
+		let proxy = self.proxy.clone();
+
+		Box::pin(async move {
+			let Some(proxy) = proxy else {
+				return Ok(ProxyStream { io: http.call(target).await?, proxied: false });
+			};
+
+			let io = http.call(proxy).await?;
+			if target.scheme() == Some(&Scheme::HTTPS) {
+				Ok(ProxyStream { io: tunnel(io, &target).await?, proxied: false })
+			} else {
+				Ok(ProxyStream { io, proxied: true })
+			}
+		})
+	}
+}
+
+/// Asks the proxy to open a tunnel to `target`.
+async fn tunnel(io: TokioIo<TcpStream>, target: &Uri) -> Result<TokioIo<TcpStream>, BoxError> {
+	let host = target.host().ok_or("Request URI without a host")?;
+	let port = target.port_u16().unwrap_or(443);
+
+	let mut stream = io.into_inner();
+	stream
+		.write_all(
+			format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n").as_bytes(),
+		)
+		.await?;
+
+	// The target doesn't send anything before our TLS handshake, so nothing after the end of the
+	// proxy response can be read here.
+	let mut response = Vec::new();
+	let mut buf = [0; 1024];
+	while !response.windows(4).any(|window| window == b"\r\n\r\n") {
+		if response.len() > MAX_CONNECT_RESPONSE_LEN {
+			return Err("HTTP proxy response to CONNECT is too large".into());
+		}
+		let read = stream.read(&mut buf).await?;
+		if read == 0 {
+			return Err("HTTP proxy closed the connection during CONNECT".into());
+		}
+		response.extend_from_slice(&buf[..read]);
+	}
+
+	let status = response.split(|b| *b == b' ').nth(1).unwrap_or_default();
+	if status != b"200" {
+		return Err(format!(
+			"HTTP proxy refused to CONNECT to {host}:{port}: {}",
+			String::from_utf8_lossy(response.split(|b| *b == b'\r').next().unwrap_or_default())
+		)
+		.into());
+	}
+
+	Ok(TokioIo::new(stream))
+}
+
+/// Connection opened by the [`ProxyConnector`].
+pub struct ProxyStream {
+	io: TokioIo<TcpStream>,
+	/// Whether requests are sent to the proxy rather than to their target.
+	proxied: bool,
+}
+
+impl Connection for ProxyStream {
+	fn connected(&self) -> Connected {
+		self.io.connected().proxy(self.proxied)
+	}
+}
+
+impl Read for ProxyStream {
+	fn poll_read(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context,
+		buf: ReadBufCursor<'_>,
+	) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.io).poll_read(cx, buf)
+	}
+}
+
+impl Write for ProxyStream {
+	fn poll_write(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context,
+		buf: &[u8],
+	) -> Poll<io::Result<usize>> {
+		Pin::new(&mut self.io).poll_write(cx, buf)
+	}
+
+	fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.io).poll_flush(cx)
+	}
+
+	fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.io).poll_shutdown(cx)
+	}
+
+	fn is_write_vectored(&self) -> bool {
+		self.io.is_write_vectored()
+	}
+
+	fn poll_write_vectored(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context,
+		bufs: &[io::IoSlice<'_>],
+	) -> Poll<io::Result<usize>> {
+		Pin::new(&mut self.io).poll_write_vectored(cx, bufs)
+	}
+}
@@ -38,6 +38,9 @@ pub enum Error {
 	/// The witness compaction failed.
 	#[error("Failed to create to compact the witness")]
 	WitnessCompactionFailed,
+	/// The node doesn't track the memory used by the runtime.
+	#[error("Runtime memory watermarks are not tracked by this node")]
+	MemoryWatermarksUnavailable,
 	/// The method is marked as unsafe but unsafe flag wasn't supplied on the CLI.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
@@ -55,6 +58,8 @@ impl From<Error> for ErrorObjectOwned {
 			Error::BlockExecutionFailed => ErrorObject::owned(BASE_ERROR + 3, msg, None::<()>),
 			Error::WitnessCompactionFailed => ErrorObject::owned(BASE_ERROR + 4, msg, None::<()>),
 			Error::ProofExtractionFailed => ErrorObject::owned(BASE_ERROR + 5, msg, None::<()>),
+			Error::MemoryWatermarksUnavailable =>
+				ErrorObject::owned(BASE_ERROR + 6, msg, None::<()>),
 			Error::UnsafeRpcCalled(e) => e.into(),
 		}
 	}
@@ -48,6 +48,26 @@ pub struct BlockStats {
 	pub num_extrinsics: u64,
 }
 
+/// Peak memory used by the runtime calls of a category, returned by the
+/// `dev_runtimeMemoryWatermarks` RPC.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeMemoryWatermark {
+	/// The runtime call category, one of `block_import`, `block_authoring`, `runtime_api` and
+	/// `offchain`.
+	pub category: String,
+	/// The number of runtime calls recorded in this category.
+	pub calls: u64,
+	/// The peak size of the runtime linear memory, in bytes.
+	pub linear_memory_peak: u64,
+	/// The peak number of bytes allocated on the runtime heap at the same time.
+	pub heap_allocated_peak: u32,
+	/// The peak address space used by the runtime heap allocator, in bytes.
+	///
+	/// Once this reaches the heap size, the runtime runs out of memory.
+	pub heap_address_space_peak: u32,
+}
+
 /// Substrate dev API.
 ///
 /// This API contains unstable and unsafe methods only meant for development nodes. They
@@ -61,4 +81,12 @@ pub trait DevApi<Hash> {
 	/// this function will return `None`.
 	#[method(name = "dev_getBlockStats", with_extensions)]
 	fn block_stats(&self, block_hash: Hash) -> Result<Option<BlockStats>, Error>;
+
+	/// Returns the peak memory used by the runtime calls executed since the node started, per
+	/// runtime call category.
+	///
+	/// This helps sizing the runtime heap and catching memory regressions before the runtime
+	/// runs out of memory.
+	#[method(name = "dev_runtimeMemoryWatermarks", with_extensions)]
+	fn runtime_memory_watermarks(&self) -> Result<Vec<RuntimeMemoryWatermark>, Error>;
 }
@@ -71,6 +71,16 @@ pub struct PeerInfo<Hash, Number> {
 	pub best_number: Number,
 }
 
+/// Reputation of a network peer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerReputation {
+	/// Peer ID
+	pub peer_id: String,
+	/// Reputation of the peer, peers below the ban threshold are disconnected
+	pub reputation: i32,
+}
+
 /// The role the node is running as
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum NodeRole {
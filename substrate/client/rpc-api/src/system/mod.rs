@@ -23,7 +23,7 @@ pub mod helpers;
 
 use jsonrpsee::{core::JsonValue, proc_macros::rpc};
 
-pub use self::helpers::{Health, NodeRole, PeerInfo, SyncState, SystemInfo};
+pub use self::helpers::{Health, NodeRole, PeerInfo, PeerReputation, SyncState, SystemInfo};
 pub use error::Error;
 
 /// Substrate system RPC API
@@ -98,6 +98,22 @@ pub trait SystemApi<Hash, Number> {
 	#[method(name = "system_reservedPeers")]
 	async fn system_reserved_peers(&self) -> Result<Vec<String>, Error>;
 
+	/// Returns the reputations of the peers known to the node, including the banned ones.
+	///
+	/// The result can be passed to `importPeerReputations` of another node, for example to share
+	/// bans between the nodes of an operator.
+	#[method(name = "system_peerReputations", with_extensions)]
+	async fn system_peer_reputations(&self) -> Result<Vec<PeerReputation>, Error>;
+
+	/// Overrides the reputations of the given peers. Returns the empty string or an error.
+	///
+	/// Peers whose reputation drops below the ban threshold are disconnected.
+	#[method(name = "system_importPeerReputations", with_extensions)]
+	async fn system_import_peer_reputations(
+		&self,
+		reputations: Vec<PeerReputation>,
+	) -> Result<(), Error>;
+
 	/// Returns the roles the node is running as.
 	#[method(name = "system_nodeRoles")]
 	async fn system_node_roles(&self) -> Result<Vec<NodeRole>, Error>;
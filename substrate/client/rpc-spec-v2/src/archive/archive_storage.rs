@@ -211,74 +211,82 @@ where
 		let maybe_child_trie_str =
 			items.first().and_then(|item| item.child_trie_key_string.clone());
 
-		// Iterator over the current block and previous block
-		// at the same time to compare the keys. This approach effectively
-		// leverages backpressure to avoid memory consumption.
-		let keys_iter = self.client.raw_keys_iter(hash, maybe_child_trie.clone())?;
-		let previous_keys_iter =
-			self.client.raw_keys_iter(previous_hash, maybe_child_trie.clone())?;
-
-		let mut diff_iter = lexicographic_diff(keys_iter, previous_keys_iter);
-
-		while let Some(item) = diff_iter.next() {
-			let (operation_type, key) = match item {
-				Diff::Added(key) => (ArchiveStorageDiffOperationType::Added, key),
-				Diff::Deleted(key) => (ArchiveStorageDiffOperationType::Deleted, key),
-				Diff::Equal(key) => (ArchiveStorageDiffOperationType::Modified, key),
-			};
-
-			let Some(fetch_type) = Self::belongs_to_query(&key, &items) else {
-				// The key does not belong the the query items.
-				continue;
-			};
+		// Only the keys under the queried prefixes are visited. The prefixes don't overlap and are
+		// sorted, so the keys are still reported in lexicographic order.
+		for prefix in query_prefixes(&items) {
+			// Iterator over the current block and previous block
+			// at the same time to compare the keys. This approach effectively
+			// leverages backpressure to avoid memory consumption.
+			let keys_iter =
+				self.client.raw_keys_iter(hash, maybe_child_trie.clone(), prefix.as_ref())?;
+			let previous_keys_iter = self.client.raw_keys_iter(
+				previous_hash,
+				maybe_child_trie.clone(),
+				prefix.as_ref(),
+			)?;
+
+			let mut diff_iter = lexicographic_diff(keys_iter, previous_keys_iter);
+
+			while let Some(item) = diff_iter.next() {
+				let (operation_type, key) = match item {
+					Diff::Added(key) => (ArchiveStorageDiffOperationType::Added, key),
+					Diff::Deleted(key) => (ArchiveStorageDiffOperationType::Deleted, key),
+					Diff::Equal(key) => (ArchiveStorageDiffOperationType::Modified, key),
+				};
 
-			let maybe_result = match operation_type {
-				ArchiveStorageDiffOperationType::Added =>
-					self.fetch_storage(hash, key.clone(), maybe_child_trie.clone(), fetch_type)?,
-				ArchiveStorageDiffOperationType::Deleted => self.fetch_storage(
-					previous_hash,
-					key.clone(),
-					maybe_child_trie.clone(),
-					fetch_type,
-				)?,
-				ArchiveStorageDiffOperationType::Modified => {
-					let Some(storage_result) = self.fetch_storage(
-						hash,
-						key.clone(),
-						maybe_child_trie.clone(),
-						fetch_type,
-					)?
-					else {
-						continue
-					};
+				let Some(fetch_type) = Self::belongs_to_query(&key, &items) else {
+					// The key does not belong the the query items.
+					continue;
+				};
 
-					let Some(previous_storage_result) = self.fetch_storage(
+				let maybe_result = match operation_type {
+					ArchiveStorageDiffOperationType::Added =>
+						self.fetch_storage(hash, key.clone(), maybe_child_trie.clone(), fetch_type)?,
+					ArchiveStorageDiffOperationType::Deleted => self.fetch_storage(
 						previous_hash,
 						key.clone(),
 						maybe_child_trie.clone(),
 						fetch_type,
-					)?
-					else {
-						continue
-					};
+					)?,
+					ArchiveStorageDiffOperationType::Modified => {
+						let Some(storage_result) = self.fetch_storage(
+							hash,
+							key.clone(),
+							maybe_child_trie.clone(),
+							fetch_type,
+						)?
+						else {
+							continue
+						};
+
+						let Some(previous_storage_result) = self.fetch_storage(
+							previous_hash,
+							key.clone(),
+							maybe_child_trie.clone(),
+							fetch_type,
+						)?
+						else {
+							continue
+						};
+
+						// For modified records we need to check the actual storage values.
+						if storage_result == previous_storage_result {
+							continue
+						}
+
+						Some(storage_result)
+					},
+				};
 
-					// For modified records we need to check the actual storage values.
-					if storage_result == previous_storage_result {
-						continue
+				if let Some(storage_result) = maybe_result {
+					if !Self::send_result(
+						&tx,
+						storage_result,
+						operation_type,
+						maybe_child_trie_str.clone(),
+					) {
+						return Ok(())
 					}
-
-					Some(storage_result)
-				},
-			};
-
-			if let Some(storage_result) = maybe_result {
-				if !Self::send_result(
-					&tx,
-					storage_result,
-					operation_type,
-					maybe_child_trie_str.clone(),
-				) {
-					return Ok(())
 				}
 			}
 		}
@@ -350,6 +358,31 @@ where
 	}
 }
 
+/// Returns the prefixes of the keys to visit for the given query items, sorted.
+///
+/// Prefixes covered by a shorter one are dropped, regardless of the requested return type, so
+/// that every key is visited once. `None` stands for all the keys of the trie.
+fn query_prefixes(items: &[DiffDetails]) -> Vec<Option<StorageKey>> {
+	let mut prefixes = items.iter().map(|item| item.key.clone()).collect::<Vec<_>>();
+	if prefixes.is_empty() {
+		return vec![None]
+	}
+
+	prefixes.sort();
+	prefixes.dedup();
+
+	let mut result: Vec<StorageKey> = Vec::with_capacity(prefixes.len());
+	for prefix in prefixes {
+		// Sorting puts a prefix right before the keys it covers.
+		if result.last().map_or(false, |last| prefix.as_ref().starts_with(last.as_ref())) {
+			continue
+		}
+		result.push(prefix);
+	}
+
+	result.into_iter().map(Some).collect()
+}
+
 /// The result of the `lexicographic_diff` method.
 #[derive(Debug, PartialEq)]
 enum Diff<T> {
@@ -846,4 +879,32 @@ mod tests {
 		];
 		assert_eq!(diff, expected);
 	}
+
+	#[test]
+	fn query_prefixes_do_not_overlap() {
+		let details = |key: Vec<u8>, return_type| DiffDetails {
+			key: StorageKey(key),
+			return_type,
+			child_trie_key: None,
+			child_trie_key_string: None,
+		};
+
+		assert_eq!(query_prefixes(&[]), vec![None]);
+
+		let items = vec![
+			details(vec![2, 1], ArchiveStorageDiffType::Value),
+			details(vec![1, 2, 3], ArchiveStorageDiffType::Hash),
+			details(vec![2], ArchiveStorageDiffType::Hash),
+			details(vec![1, 2], ArchiveStorageDiffType::Value),
+			details(vec![1, 3], ArchiveStorageDiffType::Value),
+		];
+		assert_eq!(
+			query_prefixes(&items),
+			vec![
+				Some(StorageKey(vec![1, 2])),
+				Some(StorageKey(vec![1, 3])),
+				Some(StorageKey(vec![2]))
+			]
+		);
+	}
 }
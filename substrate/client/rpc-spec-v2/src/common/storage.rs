@@ -199,16 +199,17 @@ where
 		}
 	}
 
-	/// Raw iterator over the keys.
+	/// Raw iterator over the keys, optionally restricted to the keys starting with `prefix`.
 	pub fn raw_keys_iter(
 		&self,
 		hash: Block::Hash,
 		child_key: Option<ChildInfo>,
+		prefix: Option<&StorageKey>,
 	) -> Result<impl Iterator<Item = StorageKey>, String> {
 		let keys_iter = if let Some(child_key) = child_key {
-			self.client.child_storage_keys(hash, child_key, None, None)
+			self.client.child_storage_keys(hash, child_key, prefix, None)
 		} else {
-			self.client.storage_keys(hash, None, None)
+			self.client.storage_keys(hash, prefix, None)
 		};
 
 		keys_iter.map_err(|err| err.to_string())
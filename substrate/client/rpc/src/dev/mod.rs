@@ -24,6 +24,7 @@ mod tests;
 
 use jsonrpsee::Extensions;
 use sc_client_api::{BlockBackend, HeaderBackend};
+use sc_executor::MemoryWatermarks;
 use sc_rpc_api::{check_if_safe, dev::error::Error};
 use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_core::Encode;
@@ -36,20 +37,28 @@ use std::{
 	sync::Arc,
 };
 
-pub use sc_rpc_api::dev::{BlockStats, DevApiServer};
+pub use sc_rpc_api::dev::{BlockStats, DevApiServer, RuntimeMemoryWatermark};
 
 type HasherOf<Block> = <<Block as BlockT>::Header as Header>::Hashing;
 
 /// The Dev API. All methods are unsafe.
 pub struct Dev<Block: BlockT, Client> {
 	client: Arc<Client>,
+	memory_watermarks: Option<MemoryWatermarks>,
 	_phantom: PhantomData<Block>,
 }
 
 impl<Block: BlockT, Client> Dev<Block, Client> {
 	/// Create a new Dev API.
 	pub fn new(client: Arc<Client>) -> Self {
-		Self { client, _phantom: PhantomData::default() }
+		Self { client, memory_watermarks: None, _phantom: PhantomData::default() }
+	}
+
+	/// Serve `dev_runtimeMemoryWatermarks` from the given `memory_watermarks`, usually the ones of
+	/// the executor used by the client.
+	pub fn with_memory_watermarks(mut self, memory_watermarks: MemoryWatermarks) -> Self {
+		self.memory_watermarks = Some(memory_watermarks);
+		self
 	}
 }
 
@@ -113,4 +122,26 @@ where
 			.encoded_size() as u64;
 		Ok(Some(BlockStats { witness_len, witness_compact_len, block_len, num_extrinsics }))
 	}
+
+	fn runtime_memory_watermarks(
+		&self,
+		ext: &Extensions,
+	) -> Result<Vec<RuntimeMemoryWatermark>, Error> {
+		check_if_safe(ext)?;
+
+		let memory_watermarks =
+			self.memory_watermarks.as_ref().ok_or(Error::MemoryWatermarksUnavailable)?;
+
+		Ok(memory_watermarks
+			.all()
+			.into_iter()
+			.map(|(category, watermark)| RuntimeMemoryWatermark {
+				category: category.to_string(),
+				calls: watermark.calls,
+				linear_memory_peak: watermark.linear_memory_peak,
+				heap_allocated_peak: watermark.heap_allocated_peak,
+				heap_address_space_peak: watermark.heap_address_space_peak,
+			})
+			.collect())
+	}
 }
@@ -18,6 +18,7 @@
 
 use super::*;
 use crate::DenyUnsafe;
+use jsonrpsee::core::EmptyServerParams as EmptyParams;
 use sc_block_builder::BlockBuilderBuilder;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
@@ -107,3 +108,30 @@ async fn deny_unsafe_works() {
 		r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"RPC call is unsafe to be called externally"}}"#
 	);
 }
+
+#[tokio::test]
+async fn runtime_memory_watermarks_work() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+
+	let mut api = <Dev<Block, _>>::new(client.clone()).into_rpc();
+	api.extensions_mut().insert(DenyUnsafe::No);
+	assert!(api
+		.call::<_, Vec<RuntimeMemoryWatermark>>("dev_runtimeMemoryWatermarks", EmptyParams::new())
+		.await
+		.is_err());
+
+	let mut api = <Dev<Block, _>>::new(client)
+		.with_memory_watermarks(MemoryWatermarks::new())
+		.into_rpc();
+	api.extensions_mut().insert(DenyUnsafe::No);
+	let watermarks = api
+		.call::<_, Vec<RuntimeMemoryWatermark>>("dev_runtimeMemoryWatermarks", EmptyParams::new())
+		.await
+		.unwrap();
+
+	assert_eq!(
+		watermarks.iter().map(|w| w.category.as_str()).collect::<Vec<_>>(),
+		vec!["block_import", "block_authoring", "runtime_api", "offchain"],
+	);
+	assert!(watermarks.iter().all(|w| w.calls == 0));
+}
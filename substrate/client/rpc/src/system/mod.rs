@@ -31,7 +31,7 @@ use sc_tracing::logging;
 use sc_utils::mpsc::TracingUnboundedSender;
 use sp_runtime::traits::{self, Header as HeaderT};
 
-pub use self::helpers::{Health, NodeRole, PeerInfo, SyncState, SystemInfo};
+pub use self::helpers::{Health, NodeRole, PeerInfo, PeerReputation, SyncState, SystemInfo};
 pub use sc_rpc_api::system::*;
 
 /// System API implementation
@@ -59,6 +59,10 @@ pub enum Request<B: traits::Block> {
 	NetworkRemoveReservedPeer(String, oneshot::Sender<error::Result<()>>),
 	/// Must return the list of reserved peers
 	NetworkReservedPeers(oneshot::Sender<Vec<String>>),
+	/// Must return the reputations of the known peers.
+	NetworkPeerReputations(oneshot::Sender<Vec<PeerReputation>>),
+	/// Must return any potential parse error.
+	NetworkImportPeerReputations(Vec<PeerReputation>, oneshot::Sender<error::Result<()>>),
 	/// Must return the node role.
 	NodeRoles(oneshot::Sender<Vec<NodeRole>>),
 	/// Must return the state of the node syncing.
@@ -164,6 +168,33 @@ impl<B: traits::Block> SystemApiServer<B::Hash, <B::Header as HeaderT>::Number>
 		rx.await.map_err(|e| Error::Internal(e.to_string()))
 	}
 
+	async fn system_peer_reputations(
+		&self,
+		ext: &Extensions,
+	) -> Result<Vec<PeerReputation>, Error> {
+		check_if_safe(ext)?;
+		let (tx, rx) = oneshot::channel();
+		let _ = self.send_back.unbounded_send(Request::NetworkPeerReputations(tx));
+		rx.await.map_err(|e| Error::Internal(e.to_string()))
+	}
+
+	async fn system_import_peer_reputations(
+		&self,
+		ext: &Extensions,
+		reputations: Vec<PeerReputation>,
+	) -> Result<(), Error> {
+		check_if_safe(ext)?;
+		let (tx, rx) = oneshot::channel();
+		let _ = self
+			.send_back
+			.unbounded_send(Request::NetworkImportPeerReputations(reputations, tx));
+		match rx.await {
+			Ok(Ok(())) => Ok(()),
+			Ok(Err(e)) => Err(e),
+			Err(e) => Err(Error::Internal(e.to_string())),
+		}
+	}
+
 	async fn system_node_roles(&self) -> Result<Vec<NodeRole>, Error> {
 		let (tx, rx) = oneshot::channel();
 		let _ = self.send_back.unbounded_send(Request::NodeRoles(tx));
@@ -113,6 +113,22 @@ fn api<T: Into<Option<Status>>>(sync: T) -> RpcModule<System<Block>> {
 					let _ = sender
 						.send(vec!["QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string()]);
 				},
+				Request::NetworkPeerReputations(sender) => {
+					let _ = sender.send(vec![PeerReputation {
+						peer_id: "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string(),
+						reputation: i32::MIN,
+					}]);
+				},
+				Request::NetworkImportPeerReputations(reputations, sender) => {
+					let _ = match reputations
+						.iter()
+						.try_for_each(|reputation| reputation.peer_id.parse::<PeerId>().map(|_| ()))
+					{
+						Ok(()) => sender.send(Ok(())),
+						Err(s) =>
+							sender.send(Err(error::Error::MalformattedPeerArg(s.to_string()))),
+					};
+				},
 				Request::NodeRoles(sender) => {
 					let _ = sender.send(vec![NodeRole::Authority]);
 				},
@@ -337,6 +353,38 @@ async fn system_network_reserved_peers() {
 	assert_eq!(reserved_peers, vec!["QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string()],);
 }
 
+#[tokio::test]
+async fn system_network_peer_reputations() {
+	let reputations: Vec<PeerReputation> =
+		api(None).call("system_peerReputations", EmptyParams::new()).await.unwrap();
+	assert_eq!(
+		reputations,
+		vec![PeerReputation {
+			peer_id: "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string(),
+			reputation: i32::MIN,
+		}],
+	);
+}
+
+#[tokio::test]
+async fn system_network_import_peer_reputations() {
+	let good_reputations = [vec![PeerReputation {
+		peer_id: "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string(),
+		reputation: -100,
+	}]];
+	let _good: () = api(None)
+		.call("system_importPeerReputations", good_reputations)
+		.await
+		.expect("call with good peer ids works");
+
+	let bad_reputations =
+		[vec![PeerReputation { peer_id: "/ip4/198.51.100.19".to_string(), reputation: -100 }]];
+	assert_matches!(
+		api(None).call::<_, ()>("system_importPeerReputations", bad_reputations).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("base-58 decode error")
+	);
+}
+
 #[test]
 fn test_add_reset_log_filter() {
 	const EXPECTED_BEFORE_ADD: &'static str = "EXPECTED_BEFORE_ADD";
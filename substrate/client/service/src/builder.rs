@@ -40,7 +40,7 @@ use sc_executor::{
 	sp_wasm_interface::HostFunctions, HeapAllocStrategy, NativeExecutionDispatch, RuntimeVersionOf,
 	WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY,
 };
-use sc_keystore::LocalKeystore;
+use sc_keystore::{LeasedKeystore, LocalKeystore, SigningLeaseConfig};
 use sc_network::{
 	config::{FullNetworkConfiguration, ProtocolId, SyncMode},
 	multiaddr::Protocol,
@@ -110,28 +110,41 @@ type TFullParts<TBl, TRtApi, TExec> =
 	(TFullClient<TBl, TRtApi, TExec>, Arc<TFullBackend<TBl>>, KeystoreContainer, TaskManager);
 
 /// Construct a local keystore shareable container
-pub struct KeystoreContainer(Arc<LocalKeystore>);
+pub struct KeystoreContainer {
+	local: Arc<LocalKeystore>,
+	keystore: KeystorePtr,
+}
 
 impl KeystoreContainer {
 	/// Construct KeystoreContainer
 	pub fn new(config: &KeystoreConfig) -> Result<Self, Error> {
-		let keystore = Arc::new(match config {
+		let local = Arc::new(match config {
 			KeystoreConfig::Path { path, password } =>
 				LocalKeystore::open(path.clone(), password.clone())?,
 			KeystoreConfig::InMemory => LocalKeystore::in_memory(),
 		});
 
-		Ok(Self(keystore))
+		Ok(Self { keystore: local.clone(), local })
+	}
+
+	/// Only sign with the keys of the keystore while holding the lease of `config`.
+	///
+	/// Must be called before handing out the [`keystore`](Self::keystore), the keystores returned
+	/// before are not guarded.
+	pub fn with_signing_lease(self, config: SigningLeaseConfig) -> Self {
+		Self { keystore: Arc::new(LeasedKeystore::new(self.local.clone(), config)), ..self }
 	}
 
 	/// Returns a shared reference to a dynamic `Keystore` trait implementation.
 	pub fn keystore(&self) -> KeystorePtr {
-		self.0.clone()
+		self.keystore.clone()
 	}
 
 	/// Returns a shared reference to the local keystore .
+	///
+	/// The local keystore isn't guarded by the signing lease, if any.
 	pub fn local_keystore(&self) -> Arc<LocalKeystore> {
-		self.0.clone()
+		self.local.clone()
 	}
 }
 
@@ -1075,9 +1088,22 @@ where
 	net_config.add_notification_protocol(transactions_config);
 
 	// Start task for `PeerStore`
+	let peer_store_handle = net_config.peer_store_handle();
 	let peer_store = net_config.take_peer_store();
 	spawn_handle.spawn("peer-store", Some("networking"), peer_store.run());
 
+	// Keep the peer reputations across restarts.
+	if let Some(net_config_path) = &net_config.network_config.net_config_path {
+		spawn_handle.spawn(
+			"peer-reputations",
+			Some("networking"),
+			sc_network::reputation_snapshot::run(
+				peer_store_handle.clone(),
+				net_config_path.join(sc_network::reputation_snapshot::FILE_NAME),
+			),
+		);
+	}
+
 	let sync_service = Arc::new(sync_service);
 
 	let network_params = sc_network::config::Params::<Block, <Block as BlockT>::Hash, Net> {
@@ -1132,6 +1158,7 @@ where
 		build_system_rpc_future::<_, _, <Block as BlockT>::Hash>(
 			role,
 			network_mut.network_service(),
+			peer_store_handle,
 			sync_service.clone(),
 			client.clone(),
 			system_rpc_rx,
@@ -1377,6 +1404,11 @@ where
 		metrics_registry: metrics_registry.cloned(),
 		state_request_protocol_name,
 		block_downloader,
+		state_sync_journal_path: net_config
+			.network_config
+			.net_config_path
+			.as_ref()
+			.map(|path| path.join("state-sync-journal")),
 	};
 	Ok(Box::new(PolkadotSyncingStrategy::new(
 		syncing_config,
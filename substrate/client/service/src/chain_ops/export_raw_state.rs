@@ -34,10 +34,66 @@ where
 	B: BlockT,
 	BA: sc_client_api::backend::Backend<B>,
 {
-	let mut top = BTreeMap::new();
-	let mut children_default = HashMap::new();
+	let mut storage = Storage::default();
+	export_raw_state_into(&*client, hash, None, &mut storage)?;
+	Ok(storage)
+}
+
+/// Export the raw state at the given `block`, restricted to the keys starting with one of
+/// `prefixes`.
+///
+/// Default child tries are only exported if their root key, which starts with
+/// [`well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX`], matches one of the prefixes.
+pub fn export_raw_state_with_prefixes<B, BA, C>(
+	client: Arc<C>,
+	hash: B::Hash,
+	prefixes: &[Vec<u8>],
+) -> Result<Storage, Error>
+where
+	C: UsageProvider<B> + StorageProvider<B, BA>,
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+{
+	let mut storage = Storage::default();
+	for prefix in prefixes {
+		export_raw_state_into(&*client, hash, Some(&StorageKey(prefix.clone())), &mut storage)?;
+	}
+	Ok(storage)
+}
+
+/// Merge the raw state `from`, exported for the given `prefixes`, into `into`.
+///
+/// The keys of `into` starting with one of the prefixes are removed first, as are its default
+/// child tries whose root key, prefixed with
+/// [`well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX`], does. This drops the entries of `into`
+/// that were removed from the state `from` was exported from. The keys of `from` then override
+/// the keys of `into` and its child tries replace those of `into`.
+pub fn merge_raw_state(into: &mut Storage, from: Storage, prefixes: &[Vec<u8>]) {
+	let is_overridden = |key: &[u8]| prefixes.iter().any(|prefix| key.starts_with(prefix));
+
+	into.top.retain(|key, _| !is_overridden(key));
+	into.children_default.retain(|root_key, _| {
+		!is_overridden(&[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, &root_key[..]].concat())
+	});
+
+	into.top.extend(from.top);
+	into.children_default.extend(from.children_default);
+}
 
-	for (key, value) in client.storage_pairs(hash, None, None)? {
+fn export_raw_state_into<B, BA, C>(
+	client: &C,
+	hash: B::Hash,
+	prefix: Option<&StorageKey>,
+	storage: &mut Storage,
+) -> Result<(), Error>
+where
+	C: UsageProvider<B> + StorageProvider<B, BA>,
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+{
+	let Storage { top, children_default } = storage;
+
+	for (key, value) in client.storage_pairs(hash, prefix, None)? {
 		// Remove all default child storage roots from the top storage and collect the child storage
 		// pairs.
 		if key.0.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
@@ -59,5 +115,71 @@ where
 		top.insert(key.0, value.0);
 	}
 
-	Ok(Storage { top, children_default })
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn child(root_key: &[u8], pairs: &[(&[u8], &[u8])]) -> (Vec<u8>, StorageChild) {
+		let data = pairs.iter().map(|(k, v)| (k.to_vec(), v.to_vec())).collect();
+		(root_key.to_vec(), StorageChild { child_info: ChildInfo::new_default(root_key), data })
+	}
+
+	#[test]
+	fn merge_raw_state_overrides_keys() {
+		let mut into = Storage {
+			top: BTreeMap::from([(b"a".to_vec(), b"1".to_vec()), (b"b".to_vec(), b"1".to_vec())]),
+			children_default: HashMap::from([child(b"c", &[(b"x", b"1"), (b"y", b"1")])]),
+		};
+		let from = Storage {
+			top: BTreeMap::from([(b"b".to_vec(), b"2".to_vec()), (b"c".to_vec(), b"2".to_vec())]),
+			children_default: HashMap::from([
+				child(b"c", &[(b"y", b"2")]),
+				child(b"d", &[(b"z", b"2")]),
+			]),
+		};
+
+		merge_raw_state(&mut into, from, &[]);
+
+		assert_eq!(
+			into.top,
+			BTreeMap::from([
+				(b"a".to_vec(), b"1".to_vec()),
+				(b"b".to_vec(), b"2".to_vec()),
+				(b"c".to_vec(), b"2".to_vec()),
+			])
+		);
+		assert_eq!(into.children_default[&b"c".to_vec()], child(b"c", &[(b"y", b"2")]).1);
+		assert_eq!(into.children_default[&b"d".to_vec()], child(b"d", &[(b"z", b"2")]).1);
+	}
+
+	#[test]
+	fn merge_raw_state_clears_overridden_prefixes() {
+		let mut into = Storage {
+			top: BTreeMap::from([
+				(b"a".to_vec(), b"1".to_vec()),
+				(b"b1".to_vec(), b"1".to_vec()),
+				(b"b2".to_vec(), b"1".to_vec()),
+			]),
+			children_default: HashMap::from([
+				child(b"b", &[(b"x", b"1")]),
+				child(b"c", &[(b"x", b"1")]),
+			]),
+		};
+		let from = Storage {
+			top: BTreeMap::from([(b"b1".to_vec(), b"2".to_vec())]),
+			children_default: HashMap::new(),
+		};
+		let child_prefix = [well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, &b"b"[..]].concat();
+
+		merge_raw_state(&mut into, from, &[b"b".to_vec(), child_prefix]);
+
+		assert_eq!(
+			into.top,
+			BTreeMap::from([(b"a".to_vec(), b"1".to_vec()), (b"b1".to_vec(), b"2".to_vec())])
+		);
+		assert_eq!(into.children_default, HashMap::from([child(b"c", &[(b"x", b"1")])]));
+	}
 }
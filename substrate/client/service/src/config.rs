@@ -44,6 +44,7 @@ use std::{
 	net::SocketAddr,
 	num::NonZeroU32,
 	path::{Path, PathBuf},
+	time::Duration,
 };
 use tempfile::TempDir;
 
@@ -154,6 +155,13 @@ pub struct OffchainWorkerConfig {
 	pub enabled: bool,
 	/// allow writes from the runtime to the offchain worker database.
 	pub indexing_enabled: bool,
+	/// HTTP proxy the offchain workers send their HTTP requests through.
+	pub http_proxy: Option<String>,
+	/// PEM files of certificate authorities trusted by the offchain workers, in addition to the
+	/// native ones.
+	pub http_ca_certificates: Vec<PathBuf>,
+	/// The longest an HTTP host function called by an offchain worker may block.
+	pub http_request_timeout: Option<Duration>,
 }
 
 /// Configuration of the Prometheus endpoint.
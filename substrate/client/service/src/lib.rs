@@ -43,8 +43,8 @@ use jsonrpsee::RpcModule;
 use log::{debug, error, trace, warn};
 use sc_client_api::{blockchain::HeaderBackend, BlockBackend, BlockchainEvents, ProofProvider};
 use sc_network::{
-	config::MultiaddrWithPeerId, service::traits::NetworkService, NetworkBackend, NetworkBlock,
-	NetworkPeers, NetworkStateInfo,
+	config::MultiaddrWithPeerId, peer_store::PeerStoreProvider, service::traits::NetworkService,
+	NetworkBackend, NetworkBlock, NetworkPeers, NetworkStateInfo,
 };
 use sc_network_sync::SyncingService;
 use sc_network_types::PeerId;
@@ -257,6 +257,7 @@ pub async fn build_system_rpc_future<
 >(
 	role: Role,
 	network_service: Arc<dyn NetworkService>,
+	peer_store: Arc<dyn PeerStoreProvider>,
 	sync_service: Arc<SyncingService<B>>,
 	client: Arc<C>,
 	mut rpc_rx: TracingUnboundedReceiver<sc_rpc::system::Request<B>>,
@@ -348,6 +349,36 @@ pub async fn build_system_rpc_future<
 				let _ =
 					sender.send(reserved_peers.iter().map(|peer_id| peer_id.to_base58()).collect());
 			},
+			sc_rpc::system::Request::NetworkPeerReputations(sender) => {
+				let _ = sender.send(
+					peer_store
+						.reputations()
+						.into_iter()
+						.map(|(peer_id, reputation)| sc_rpc::system::PeerReputation {
+							peer_id: peer_id.to_base58(),
+							reputation,
+						})
+						.collect(),
+				);
+			},
+			sc_rpc::system::Request::NetworkImportPeerReputations(reputations, sender) => {
+				let reputations = reputations
+					.into_iter()
+					.map(|sc_rpc::system::PeerReputation { peer_id, reputation }| {
+						peer_id.parse::<PeerId>().map(|peer_id| (peer_id, reputation))
+					})
+					.collect::<Result<Vec<_>, _>>();
+
+				let _ = match reputations {
+					Ok(reputations) => {
+						peer_store.set_reputations(reputations);
+						sender.send(Ok(()))
+					},
+					Err(e) => sender.send(Err(sc_rpc::system::error::Error::MalformattedPeerArg(
+						e.to_string(),
+					))),
+				};
+			},
 			sc_rpc::system::Request::NodeRoles(sender) => {
 				use sc_rpc::system::NodeRole;
 
@@ -21,7 +21,10 @@
 use crate::{
 	common::api::FullChainApi,
 	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
-	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
+	graph::{
+		base_pool::{ReadyOrdering, Transaction},
+		ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options,
+	},
 	single_state_txpool::BasicPool as SingleStateFullPool,
 	TransactionPoolWrapper, LOG_TARGET,
 };
@@ -84,6 +87,12 @@ impl TransactionPoolOptions {
 		TransactionPoolOptions { options, txpool_type }
 	}
 
+	/// Sets the order in which the ready transactions are handed out for block building.
+	pub fn with_ready_ordering(mut self, ready_ordering: ReadyOrdering) -> Self {
+		self.options.ready_ordering = ready_ordering;
+		self
+	}
+
 	/// Creates predefined options for benchmarking
 	pub fn new_for_benchmarks() -> TransactionPoolOptions {
 		TransactionPoolOptions {
@@ -98,6 +107,7 @@ impl TransactionPoolOptions {
 				},
 				reject_future_transactions: false,
 				ban_time: Duration::from_secs(30 * 60),
+				ready_ordering: Default::default(),
 			},
 			txpool_type: TransactionPoolType::SingleState,
 		}
@@ -22,7 +22,7 @@ use super::{
 	dropped_watcher::{MultiViewDroppedWatcherController, StreamOfDropped},
 	import_notification_sink::MultiViewImportNotificationSink,
 	metrics::{EventsMetricsCollector, MetricsLink as PrometheusMetrics},
-	multi_view_listener::MultiViewListener,
+	multi_view_listener::{MultiViewListener, TransactionEventStream},
 	tx_mem_pool::{InsertionInfo, TxMemPool, TXMEMPOOL_TRANSACTION_LIMIT_MULTIPLIER},
 	view::View,
 	view_store::ViewStore,
@@ -459,6 +459,20 @@ where
 		self.mempool.unwatched_and_watched_count()
 	}
 
+	/// Subscribes to the lifecycle events of all the transactions in the pool.
+	///
+	/// Every status reported by the views is delivered together with the block of the view, so
+	/// that the subscriber can follow a transaction on every fork, e.g. from being ready at the
+	/// best block to being included on one fork, which then gets retracted. Transactions included
+	/// in the retracted blocks are reported as [`Retracted`] on the update of the new best view.
+	///
+	/// [`Retracted`]: sc_transaction_pool_api::TransactionStatus::Retracted
+	pub fn transaction_events(
+		&self,
+	) -> TransactionEventStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>> {
+		self.view_store.listener.transaction_events()
+	}
+
 	/// Returns a set of future transactions for given block hash.
 	///
 	/// Intended for logging / tests.
@@ -1453,14 +1467,20 @@ where
 						None
 					})
 					.unwrap_or_default()
-					.into_iter();
+					.into_iter()
+					.map(|tx| (self.hash_of(&tx), tx))
+					.collect::<Vec<_>>();
+
+				self.view_store.listener.transactions_retracted(
+					hash,
+					block_transactions.iter().map(|(tx_hash, _)| *tx_hash).collect(),
+				);
 
 				let mut resubmitted_to_report = 0;
 
 				resubmit_transactions.extend(
 					block_transactions
 						.into_iter()
-						.map(|tx| (self.hash_of(&tx), tx))
 						.filter(|(tx_hash, _)| {
 							let contains = pruned_log.contains(&tx_hash);
 
@@ -374,6 +374,7 @@ mod view;
 mod view_store;
 
 pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use multi_view_listener::{TransactionEvent, TransactionEventStream};
 
 mod stream_map_util {
 	use futures::Stream;
@@ -67,6 +67,33 @@ pub type TxStatusStream<T> = Pin<Box<TransactionStatusStream<ExtrinsicHash<T>, B
 pub type ViewStatusStream<T> =
 	Pin<Box<dyn Stream<Item = TransactionStatusEvent<ExtrinsicHash<T>, BlockHash<T>>> + Send>>;
 
+/// A lifecycle event of a transaction, together with the fork it was observed on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionEvent<Hash, BlockHash> {
+	/// The hash of the transaction.
+	pub tx_hash: Hash,
+	/// The block the event relates to.
+	///
+	/// For the statuses reported by a view, this is the block the view is built at, i.e. the tip
+	/// of the fork. For [`TransactionStatus::Retracted`], [`TransactionStatus::Finalized`] and
+	/// [`TransactionStatus::FinalityTimeout`] this is the block carried by the status. `None` for
+	/// the events concerning the whole pool, e.g. the transaction being dropped or broadcasted.
+	pub block_hash: Option<BlockHash>,
+	/// The status of the transaction.
+	pub status: TransactionStatus<Hash, BlockHash>,
+}
+
+/// The stream of the lifecycle events of all the transactions of the pool.
+///
+/// Unlike the external watcher of a single transaction, the events of every view are delivered
+/// as they are, without deduplication.
+pub type TransactionEventStream<Hash, BlockHash> =
+	mpsc::TracingUnboundedReceiver<TransactionEvent<Hash, BlockHash>>;
+
+/// A sink of [`TransactionEvent`]s, one per subscriber.
+type TransactionEventSink<ChainApi> =
+	Controller<TransactionEvent<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>>;
+
 /// Commands to control / drive the task of the multi view listener.
 enum ControllerCommand<ChainApi: graph::ChainApi> {
 	/// Requests transaction status updated. Sent by transaction pool implementation.
@@ -76,6 +103,10 @@ enum ControllerCommand<ChainApi: graph::ChainApi> {
 	AddViewStream(BlockHash<ChainApi>, ViewStatusStream<ChainApi>),
 	/// Removes an existing view's stream associated with a specific block hash.
 	RemoveViewStream(BlockHash<ChainApi>),
+	/// Notifies that the given transactions were included in a block that got retracted.
+	///
+	/// Only reported to the subscribers of the transaction events.
+	TransactionsRetracted(BlockHash<ChainApi>, Vec<ExtrinsicHash<ChainApi>>),
 }
 
 /// Represents the transaction status update performed by transaction pool state machine. The
@@ -146,6 +177,20 @@ where
 	}
 }
 
+impl<ChainApi> TransactionStatusUpdate<ChainApi>
+where
+	ChainApi: graph::ChainApi,
+{
+	/// The block the update relates to, if any.
+	fn block_hash(&self) -> Option<BlockHash<ChainApi>> {
+		match self {
+			Self::Finalized(_, block_hash, _) | Self::FinalityTimeout(_, block_hash) =>
+				Some(*block_hash),
+			Self::Invalidated(_) | Self::Broadcasted(..) | Self::Dropped(..) => None,
+		}
+	}
+}
+
 impl<ChainApi> std::fmt::Debug for TransactionStatusUpdate<ChainApi>
 where
 	ChainApi: graph::ChainApi,
@@ -179,6 +224,8 @@ where
 		match self {
 			ControllerCommand::AddViewStream(h, _) => write!(f, "AddView({h})"),
 			ControllerCommand::RemoveViewStream(h) => write!(f, "RemoveView({h})"),
+			ControllerCommand::TransactionsRetracted(h, t) =>
+				write!(f, "TransactionsRetracted({h},{})", t.len()),
 			ControllerCommand::TransactionStatusRequest(c) => {
 				write!(f, "TransactionStatusRequest({c:?})")
 			},
@@ -255,6 +302,10 @@ pub struct MultiViewListener<ChainApi: graph::ChainApi> {
 	/// shared with listener's task.
 	external_controllers:
 		Arc<RwLock<HashMap<ExtrinsicHash<ChainApi>, Controller<ExternalWatcherCommand<ChainApi>>>>>,
+
+	/// The sinks of the [`TransactionEventStream`]s handed out to the subscribers. Shared with
+	/// listener's task.
+	event_subscribers: Arc<RwLock<Vec<TransactionEventSink<ChainApi>>>>,
 }
 
 /// A type representing a `MultiViewListener` task. For more details refer to
@@ -444,7 +495,8 @@ where
 	/// - transaction commands,
 	/// to multiple individual per-transaction external watcher contexts.
 	///
-	/// It also reports transactions statuses updates to the provided `events_metrics_collector`.
+	/// It also reports transactions statuses updates to the provided `events_metrics_collector`
+	/// and to the `event_subscribers`.
 	///
 	/// The returned future shall be polled by instantiator of `MultiViewListener`.
 	async fn task(
@@ -453,6 +505,7 @@ where
 		>,
 		mut command_receiver: CommandReceiver<ControllerCommand<ChainApi>>,
 		events_metrics_collector: EventsMetricsCollector<ChainApi>,
+		event_subscribers: Arc<RwLock<Vec<TransactionEventSink<ChainApi>>>>,
 	) {
 		let mut aggregated_streams_map: StreamMap<BlockHash<ChainApi>, ViewStatusStream<ChainApi>> =
 			Default::default();
@@ -462,6 +515,7 @@ where
 				biased;
 				Some((view_hash, (tx_hash, status))) =  next_event(&mut aggregated_streams_map) => {
 					events_metrics_collector.report_status(tx_hash, status.clone());
+					Self::notify_subscribers(&event_subscribers, tx_hash, Some(view_hash), &status);
 					if let Entry::Occupied(mut ctrl) = external_watchers_tx_hash_map.write().entry(tx_hash) {
 						trace!(
 							target: LOG_TARGET,
@@ -506,7 +560,9 @@ where
 
 						Some(ControllerCommand::TransactionStatusRequest(request)) => {
 							let tx_hash = request.hash();
-							events_metrics_collector.report_status(tx_hash, (&request).into());
+							let status: TransactionStatus<_, _> = (&request).into();
+							Self::notify_subscribers(&event_subscribers, tx_hash, request.block_hash(), &status);
+							events_metrics_collector.report_status(tx_hash, status);
 							if let Entry::Occupied(mut ctrl) = external_watchers_tx_hash_map.write().entry(tx_hash) {
 								if let Err(error) = ctrl
 									.get_mut()
@@ -517,6 +573,16 @@ where
 								}
 							}
 						},
+						Some(ControllerCommand::TransactionsRetracted(block_hash, tx_hashes)) => {
+							for tx_hash in tx_hashes {
+								Self::notify_subscribers(
+									&event_subscribers,
+									tx_hash,
+									Some(block_hash),
+									&TransactionStatus::Retracted(block_hash),
+								);
+							}
+						},
 						None =>  {}
 					}
 				},
@@ -524,6 +590,27 @@ where
 		}
 	}
 
+	/// Sends the event to every subscriber, dropping the ones that are gone.
+	fn notify_subscribers(
+		event_subscribers: &RwLock<Vec<TransactionEventSink<ChainApi>>>,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		block_hash: Option<BlockHash<ChainApi>>,
+		status: &TransactionStatus<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
+	) {
+		if event_subscribers.read().is_empty() {
+			return
+		}
+
+		let event = TransactionEvent { tx_hash, block_hash, status: status.clone() };
+		event_subscribers.write().retain(|sink| {
+			sink.unbounded_send(event.clone())
+				.inspect_err(|error| {
+					trace!(target: LOG_TARGET, ?tx_hash, ?error, "event subscriber gone");
+				})
+				.is_ok()
+		});
+	}
+
 	/// Creates a new [`MultiViewListener`] instance along with its associated worker task.
 	///
 	/// This function instantiates the new `MultiViewListener` and provides the worker task that
@@ -549,9 +636,15 @@ where
 			"txpool-multi-view-listener-task-controller",
 			CONTROLLER_QUEUE_WARN_SIZE,
 		);
-		let task = Self::task(external_controllers.clone(), rx, events_metrics_collector);
+		let event_subscribers = Arc::<RwLock<Vec<_>>>::default();
+		let task = Self::task(
+			external_controllers.clone(),
+			rx,
+			events_metrics_collector,
+			event_subscribers.clone(),
+		);
 
-		(Self { external_controllers, controller: tx }, task.boxed())
+		(Self { external_controllers, controller: tx, event_subscribers }, task.boxed())
 	}
 
 	/// Creates an external tstream of events for given transaction.
@@ -789,6 +882,45 @@ where
 		}
 	}
 
+	/// Send `Retracted` event for given transactions included in the retracted block.
+	///
+	/// This is only reported to the subscribers of the transaction events, the external watchers
+	/// of the transactions are not affected.
+	pub(crate) fn transactions_retracted(
+		&self,
+		block: BlockHash<ChainApi>,
+		tx_hashes: Vec<ExtrinsicHash<ChainApi>>,
+	) {
+		if tx_hashes.is_empty() {
+			return
+		}
+		trace!(target: LOG_TARGET, ?block, count = tx_hashes.len(), "transactions_retracted");
+		if let Err(error) = self
+			.controller
+			.unbounded_send(ControllerCommand::TransactionsRetracted(block, tx_hashes))
+		{
+			trace!(
+				target: LOG_TARGET,
+				?block,
+				%error,
+				"transactions_retracted: send message failed"
+			);
+		};
+	}
+
+	/// Subscribes to the lifecycle events of all the transactions.
+	///
+	/// See [`TransactionEventStream`].
+	pub(crate) fn transaction_events(
+		&self,
+	) -> TransactionEventStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>> {
+		const EVENTS_QUEUE_WARN_SIZE: usize = 100_000;
+		let (sink, stream) =
+			mpsc::tracing_unbounded("txpool-transaction-events", EVENTS_QUEUE_WARN_SIZE);
+		self.event_subscribers.write().push(sink);
+		stream
+	}
+
 	/// Removes stale controllers.
 	pub(crate) fn remove_stale_controllers(&self) {
 		self.external_controllers.write().retain(|_, c| !c.is_closed());
@@ -1101,4 +1233,47 @@ mod tests {
 		let _ = terminate_listener.send(());
 		let _ = listener_task.await.unwrap();
 	}
+
+	#[tokio::test]
+	async fn transaction_events_carry_the_fork() {
+		sp_tracing::try_init_simple();
+		let (listener, terminate_listener, listener_task) = create_multi_view_listener();
+
+		let block_hash0 = H256::repeat_byte(0x01);
+		let block_hash1 = H256::repeat_byte(0x02);
+		let tx_hash = H256::repeat_byte(0x0a);
+
+		let events = listener.transaction_events();
+		let handle = tokio::spawn(async move { events.take(5).collect::<Vec<_>>().await });
+
+		let view_stream0 = futures::stream::iter(
+			std::iter::repeat(tx_hash)
+				.zip(vec![TransactionStatus::Ready, TransactionStatus::InBlock((block_hash0, 0))]),
+		);
+		listener.add_view_aggregated_stream(block_hash0, view_stream0.boxed());
+		listener.transactions_retracted(block_hash0, vec![tx_hash]);
+
+		let view_stream1 =
+			futures::stream::iter(vec![(tx_hash, TransactionStatus::InBlock((block_hash1, 0)))]);
+		listener.add_view_aggregated_stream(block_hash1, view_stream1.boxed());
+		listener.transaction_finalized(tx_hash, block_hash1, 0);
+
+		let out = handle.await.unwrap();
+		debug!("out: {:#?}", out);
+
+		let event = |block_hash, status| TransactionEvent { tx_hash, block_hash, status };
+		assert_eq!(
+			out,
+			vec![
+				event(Some(block_hash0), TransactionStatus::Ready),
+				event(Some(block_hash0), TransactionStatus::InBlock((block_hash0, 0))),
+				event(Some(block_hash0), TransactionStatus::Retracted(block_hash0)),
+				event(Some(block_hash1), TransactionStatus::InBlock((block_hash1, 0))),
+				event(Some(block_hash1), TransactionStatus::Finalized((block_hash1, 0))),
+			]
+		);
+
+		let _ = terminate_listener.send(());
+		let _ = listener_task.await.unwrap();
+	}
 }
@@ -260,17 +260,18 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
 	fn default() -> Self {
-		Self::new(false)
+		Self::new(false, Default::default())
 	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
-	/// Create new pool given reject_future_transactions flag.
-	pub fn new(reject_future_transactions: bool) -> Self {
+	/// Create new pool given reject_future_transactions flag and the order of the ready
+	/// transactions.
+	pub fn new(reject_future_transactions: bool, ready_ordering: ReadyOrdering) -> Self {
 		Self {
 			reject_future_transactions,
 			future: Default::default(),
-			ready: Default::default(),
+			ready: ReadyTransactions::new(ready_ordering),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
 		}
@@ -467,6 +468,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = vec![];
 
+		let ordering = self.ready.ordering();
 		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
 			// find the worst transaction
 			let worst =
@@ -478,8 +480,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 							// while it prefers priority like need here, it also prefers older
 							// transactions for inclusion purposes and limit enforcement needs to
 							// prefer newer transactions instead and drop the older ones.
-							match worst.transaction.priority.cmp(&transaction.transaction.priority)
-							{
+							match ordering.compare(&worst.transaction, &transaction.transaction) {
 								Ordering::Less => worst,
 								Ordering::Equal =>
 									if worst.insertion_id > transaction.insertion_id {
@@ -630,6 +631,39 @@ impl Limit {
 	}
 }
 
+/// Order of the ready transactions, in which they are handed out for block building.
+///
+/// The transactions with the lowest value are also the first to be dropped when the ready queue
+/// is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadyOrdering {
+	/// The transactions with the highest priority go first.
+	#[default]
+	Priority,
+	/// The transactions with the highest priority per byte of their encoding go first.
+	///
+	/// Fits chains whose blocks are bound by their size rather than by their execution time,
+	/// e.g. parachains bound by the size of the proof of validity, which includes every
+	/// transaction of the block.
+	PriorityPerByte,
+}
+
+impl ReadyOrdering {
+	/// Compares the value of two transactions, for this ordering.
+	pub(crate) fn compare<Hash, Ex>(
+		self,
+		a: &Transaction<Hash, Ex>,
+		b: &Transaction<Hash, Ex>,
+	) -> Ordering {
+		match self {
+			Self::Priority => a.priority.cmp(&b.priority),
+			// `a.priority / a.bytes` against `b.priority / b.bytes`, without rounding.
+			Self::PriorityPerByte => (u128::from(a.priority) * b.bytes.max(1) as u128)
+				.cmp(&(u128::from(b.priority) * a.bytes.max(1) as u128)),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
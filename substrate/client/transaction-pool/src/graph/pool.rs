@@ -145,6 +145,8 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// Order of the ready transactions.
+	pub ready_ordering: base::ReadyOrdering,
 }
 
 impl Default for Options {
@@ -154,6 +156,7 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			ready_ordering: Default::default(),
 		}
 	}
 }
@@ -30,7 +30,7 @@ use sp_runtime::{traits::Member, transaction_validity::TransactionTag as Tag};
 use tracing::trace;
 
 use super::{
-	base_pool::Transaction,
+	base_pool::{ReadyOrdering, Transaction},
 	future::WaitingTransaction,
 	tracked_map::{self, TrackedMap},
 };
@@ -44,19 +44,24 @@ pub struct TransactionRef<Hash, Ex> {
 	pub transaction: Arc<Transaction<Hash, Ex>>,
 	/// Unique id when transaction was inserted into the pool.
 	pub insertion_id: u64,
+	/// Order of the ready queue the transaction is in.
+	pub ordering: ReadyOrdering,
 }
 
 impl<Hash, Ex> Clone for TransactionRef<Hash, Ex> {
 	fn clone(&self) -> Self {
-		Self { transaction: self.transaction.clone(), insertion_id: self.insertion_id }
+		Self {
+			transaction: self.transaction.clone(),
+			insertion_id: self.insertion_id,
+			ordering: self.ordering,
+		}
 	}
 }
 
 impl<Hash, Ex> Ord for TransactionRef<Hash, Ex> {
 	fn cmp(&self, other: &Self) -> cmp::Ordering {
-		self.transaction
-			.priority
-			.cmp(&other.transaction.priority)
+		self.ordering
+			.compare(&self.transaction, &other.transaction)
 			.then_with(|| other.transaction.valid_till.cmp(&self.transaction.valid_till))
 			.then_with(|| other.insertion_id.cmp(&self.insertion_id))
 	}
@@ -118,6 +123,8 @@ pub struct ReadyTransactions<Hash: hash::Hash + Eq, Ex> {
 	/// Best transactions that are ready to be included to the block without any other previous
 	/// transaction.
 	best: BTreeSet<TransactionRef<Hash, Ex>>,
+	/// Order of the best transactions.
+	ordering: ReadyOrdering,
 }
 
 impl<Hash, Ex> tracked_map::Size for ReadyTx<Hash, Ex> {
@@ -128,11 +135,19 @@ impl<Hash, Ex> tracked_map::Size for ReadyTx<Hash, Ex> {
 
 impl<Hash: hash::Hash + Eq, Ex> Default for ReadyTransactions<Hash, Ex> {
 	fn default() -> Self {
+		Self::new(Default::default())
+	}
+}
+
+impl<Hash: hash::Hash + Eq, Ex> ReadyTransactions<Hash, Ex> {
+	/// Creates an empty queue, returning the transactions in the given order.
+	pub fn new(ordering: ReadyOrdering) -> Self {
 		Self {
 			insertion_id: Default::default(),
 			provided_tags: Default::default(),
 			ready: Default::default(),
 			best: Default::default(),
+			ordering,
 		}
 	}
 }
@@ -152,6 +167,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 	/// returned transactions
 	/// 2. Then by priority:
 	/// - If there are two transactions with all requirements satisfied the one with higher priority
+	///   goes first. With [`ReadyOrdering::PriorityPerByte`], the one with higher priority per byte
 	///   goes first.
 	/// 3. Then by the ttl that's left
 	/// - transactions that are valid for a shorter time go first
@@ -219,7 +235,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 			self.provided_tags.insert(tag.clone(), hash.clone());
 		}
 
-		let transaction = TransactionRef { insertion_id, transaction };
+		let transaction = TransactionRef { insertion_id, transaction, ordering: self.ordering };
 
 		// insert to best if it doesn't require any other transaction to be included before it
 		if goes_to_best {
@@ -232,6 +248,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		Ok(replaced)
 	}
 
+	/// Returns the order of the ready transactions.
+	pub fn ordering(&self) -> ReadyOrdering {
+		self.ordering
+	}
+
 	/// Fold a list of ready transactions to compute a single value using initial value of
 	/// accumulator.
 	pub fn fold<R, F: FnMut(R, &ReadyTx<Hash, Ex>) -> R>(&self, init: R, f: F) -> R {
@@ -740,6 +761,30 @@ mod tests {
 		assert_eq!(it.next(), None);
 	}
 
+	#[test]
+	fn should_return_best_transactions_by_priority_per_byte() {
+		// given
+		let mut ready = ReadyTransactions::new(ReadyOrdering::PriorityPerByte);
+		let mut with_priority = |id, priority, bytes| {
+			let mut tx = tx(id);
+			tx.requires.clear();
+			tx.provides = vec![vec![id]];
+			tx.priority = priority;
+			tx.bytes = bytes;
+			import(&mut ready, tx).unwrap();
+		};
+		with_priority(1, 100, 200);
+		with_priority(2, 60, 100);
+		with_priority(3, 10, 10);
+		with_priority(4, 40, 40);
+
+		// when
+		let it = ready.get().map(|tx| tx.data[0]);
+
+		// then
+		assert_eq!(it.collect::<Vec<_>>(), vec![3, 4, 2, 1]);
+	}
+
 	#[test]
 	fn should_order_refs() {
 		let mut id = 1;
@@ -750,21 +795,17 @@ mod tests {
 			tx.valid_till = longevity;
 			tx
 		};
+		let tx_ref = |transaction, insertion_id| TransactionRef {
+			transaction: Arc::new(transaction),
+			insertion_id,
+			ordering: ReadyOrdering::Priority,
+		};
 		// higher priority = better
-		assert!(
-			TransactionRef { transaction: Arc::new(with_priority(3, 3)), insertion_id: 1 } >
-				TransactionRef { transaction: Arc::new(with_priority(2, 3)), insertion_id: 2 }
-		);
+		assert!(tx_ref(with_priority(3, 3), 1) > tx_ref(with_priority(2, 3), 2));
 		// lower validity = better
-		assert!(
-			TransactionRef { transaction: Arc::new(with_priority(3, 2)), insertion_id: 1 } >
-				TransactionRef { transaction: Arc::new(with_priority(3, 3)), insertion_id: 2 }
-		);
+		assert!(tx_ref(with_priority(3, 2), 1) > tx_ref(with_priority(3, 3), 2));
 		// lower insertion_id = better
-		assert!(
-			TransactionRef { transaction: Arc::new(with_priority(3, 3)), insertion_id: 1 } >
-				TransactionRef { transaction: Arc::new(with_priority(3, 3)), insertion_id: 2 }
-		);
+		assert!(tx_ref(with_priority(3, 3), 1) > tx_ref(with_priority(3, 3), 2));
 	}
 
 	#[test]
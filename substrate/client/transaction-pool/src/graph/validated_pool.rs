@@ -239,7 +239,8 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		rotator: PoolRotator<ExtrinsicHash<B>>,
 		event_handler: Option<L>,
 	) -> Self {
-		let base_pool = base::BasePool::new(options.reject_future_transactions);
+		let base_pool =
+			base::BasePool::new(options.reject_future_transactions, options.ready_ordering);
 		Self {
 			is_validator,
 			options,
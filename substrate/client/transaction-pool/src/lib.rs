@@ -35,9 +35,11 @@ use std::sync::Arc;
 pub use api::FullChainApi;
 pub use builder::{Builder, TransactionPoolHandle, TransactionPoolOptions, TransactionPoolType};
 pub use common::notification_future;
-pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use fork_aware_txpool::{
+	ForkAwareTxPool, ForkAwareTxPoolTask, TransactionEvent, TransactionEventStream,
+};
 pub use graph::{
-	base_pool::{Limit as PoolLimit, TimedTransactionSource},
+	base_pool::{Limit as PoolLimit, ReadyOrdering, TimedTransactionSource},
 	ChainApi, Options, Pool,
 };
 use single_state_txpool::prune_known_txs_for_block;
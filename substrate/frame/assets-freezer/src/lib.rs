@@ -113,8 +113,8 @@ pub mod pallet {
 		Blake2_128Concat,
 		T::AccountId,
 		BoundedVec<
-			IdAmount<T::RuntimeFreezeReason, T::Balance>,
-			VariantCountOf<T::RuntimeFreezeReason>,
+			IdAmount<<T as Config<I>>::RuntimeFreezeReason, T::Balance>,
+			VariantCountOf<<T as Config<I>>::RuntimeFreezeReason>,
 		>,
 		ValueQuery,
 	>;
@@ -144,8 +144,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		asset: T::AssetId,
 		who: &T::AccountId,
 		freezes: BoundedSlice<
-			IdAmount<T::RuntimeFreezeReason, T::Balance>,
-			VariantCountOf<T::RuntimeFreezeReason>,
+			IdAmount<<T as Config<I>>::RuntimeFreezeReason, T::Balance>,
+			VariantCountOf<<T as Config<I>>::RuntimeFreezeReason>,
 		>,
 	) -> DispatchResult {
 		let prev_frozen = FrozenBalances::<T, I>::get(asset.clone(), who).unwrap_or_default();
@@ -19,7 +19,12 @@
 
 use super::*;
 use alloc::vec;
-use frame_support::{defensive, traits::Get, BoundedVec};
+use frame_support::{
+	defensive,
+	storage::StorageDoubleMap as _,
+	traits::{tokens::IdAmount, Get, VariantCountOf},
+	BoundedSlice, BoundedVec,
+};
 
 #[must_use]
 pub(super) enum DeadConsequence {
@@ -100,10 +105,32 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			T::Holder::balance_on_hold(id.clone(), who).is_none(),
 			Error::<T, I>::ContainsHolds
 		);
-		ensure!(T::Freezer::frozen_balance(id, who).is_none(), Error::<T, I>::ContainsFreezes);
+		ensure!(Self::frozen_balance(id, who).is_none(), Error::<T, I>::ContainsFreezes);
 		Ok(())
 	}
 
+	/// The balance of the account frozen by the freezes of this pallet and by the `Freezer`.
+	pub(super) fn frozen_balance(id: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
+		let frozen = Freezes::<T, I>::get(id.clone(), who).into_iter().map(|f| f.amount).max();
+		frozen.max(T::Freezer::frozen_balance(id, who))
+	}
+
+	/// Updates the freezes of the account, removing them if they are empty.
+	pub(super) fn update_freezes(
+		id: T::AssetId,
+		who: &T::AccountId,
+		freezes: BoundedSlice<
+			IdAmount<T::RuntimeFreezeReason, T::Balance>,
+			VariantCountOf<T::RuntimeFreezeReason>,
+		>,
+	) {
+		if freezes.is_empty() {
+			Freezes::<T, I>::remove(id, who);
+		} else {
+			Freezes::<T, I>::insert(id, who, freezes);
+		}
+	}
+
 	pub(super) fn dead_account(
 		who: &T::AccountId,
 		d: &mut AssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T, I>>,
@@ -205,7 +232,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		if let Some(rest) = account.balance.checked_sub(&amount) {
 			match (
 				T::Holder::balance_on_hold(id.clone(), who),
-				T::Freezer::frozen_balance(id.clone(), who),
+				Self::frozen_balance(id.clone(), who),
 			) {
 				(None, None) =>
 					if rest < details.min_balance {
@@ -255,7 +282,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		let untouchable = match (
 			T::Holder::balance_on_hold(id.clone(), who),
-			T::Freezer::frozen_balance(id.clone(), who),
+			Self::frozen_balance(id.clone(), who),
 			keep_alive,
 		) {
 			(None, None, true) => details.min_balance,
@@ -655,6 +682,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 		let details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
 		ensure!(details.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+		T::OnTransfer::pre_transfer(&id, source, dest, amount)?;
 
 		// Figure out the debit and credit, together with side-effects.
 		let debit = Self::prep_debit(id.clone(), source, amount, f.into())?;
@@ -732,6 +760,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Ok(())
 		})?;
 
+		T::OnTransfer::post_transfer(&id, source, dest, credit);
 		Self::deposit_event(Event::Transferred {
 			asset_id: id,
 			from: source.clone(),
@@ -796,7 +825,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			}
 
 			ensure!(!T::Holder::contains_holds(id.clone()), Error::<T, I>::ContainsHolds);
-			ensure!(!T::Freezer::contains_freezes(id.clone()), Error::<T, I>::ContainsFreezes);
+			ensure!(
+				!T::Freezer::contains_freezes(id.clone()) && !Freezes::<T, I>::contains_prefix(&id),
+				Error::<T, I>::ContainsFreezes
+			);
 
 			details.status = AssetStatus::Destroying;
 
@@ -21,10 +21,11 @@ use alloc::vec::Vec;
 use frame_support::{
 	defensive,
 	traits::tokens::{
-		Fortitude,
+		Fortitude::{self, Polite},
+		IdAmount,
 		Precision::{self, BestEffort},
 		Preservation::{self, Expendable},
-		Provenance::{self, Minted},
+		Provenance::{self, Extant, Minted},
 	},
 };
 
@@ -100,6 +101,44 @@ impl<T: Config<I>, I: 'static> fungibles::Mutate<<T as SystemConfig>::AccountId>
 		Self::deposit_event(Event::Burned { asset_id, owner: target.clone(), balance });
 	}
 
+	fn transfer(
+		asset: Self::AssetId,
+		source: &<T as SystemConfig>::AccountId,
+		dest: &<T as SystemConfig>::AccountId,
+		amount: Self::Balance,
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		// Same as the default implementation, with the transfer hooks.
+		let _extra = <Self as fungibles::Inspect<_>>::can_withdraw(asset.clone(), source, amount)
+			.into_result(preservation != Expendable)?;
+		<Self as fungibles::Inspect<_>>::can_deposit(asset.clone(), dest, amount, Extant)
+			.into_result()?;
+		T::OnTransfer::pre_transfer(&asset, source, dest, amount)?;
+		if source == dest {
+			return Ok(amount)
+		}
+
+		<Self as fungibles::Unbalanced<_>>::decrease_balance(
+			asset.clone(),
+			source,
+			amount,
+			BestEffort,
+			preservation,
+			Polite,
+		)?;
+		// This should never fail as we checked `can_deposit` earlier. But we do a best-effort
+		// anyway.
+		let _ = <Self as fungibles::Unbalanced<_>>::increase_balance(
+			asset.clone(),
+			dest,
+			amount,
+			BestEffort,
+		);
+		T::OnTransfer::post_transfer(&asset, source, dest, amount);
+		Self::done_transfer(asset, source, dest, amount);
+		Ok(amount)
+	}
+
 	fn done_transfer(
 		asset_id: Self::AssetId,
 		source: &<T as SystemConfig>::AccountId,
@@ -358,3 +397,73 @@ impl<T: Config<I>, I: 'static> fungibles::Refund<T::AccountId> for Pallet<T, I>
 		}
 	}
 }
+
+impl<T: Config<I>, I: 'static> fungibles::InspectFreeze<T::AccountId> for Pallet<T, I> {
+	type Id = T::RuntimeFreezeReason;
+
+	fn balance_frozen(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> Self::Balance {
+		let freezes = Freezes::<T, I>::get(asset, who);
+		freezes.into_iter().find(|f| &f.id == id).map_or(Zero::zero(), |f| f.amount)
+	}
+
+	fn can_freeze(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Freezes::<T, I>::get(asset, who);
+		!freezes.is_full() || freezes.into_iter().any(|f| f.id == *id)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::MutateFreeze<T::AccountId> for Pallet<T, I> {
+	fn set_freeze(
+		asset: Self::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return <Self as fungibles::MutateFreeze<_>>::thaw(asset, id, who)
+		}
+		let mut freezes = Freezes::<T, I>::get(asset.clone(), who);
+		if let Some(f) = freezes.iter_mut().find(|f| &f.id == id) {
+			f.amount = amount;
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Self::update_freezes(asset, who, freezes.as_bounded_slice());
+		Ok(())
+	}
+
+	fn extend_freeze(
+		asset: Self::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(())
+		}
+		let mut freezes = Freezes::<T, I>::get(asset.clone(), who);
+		if let Some(f) = freezes.iter_mut().find(|f| &f.id == id) {
+			f.amount = f.amount.max(amount);
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Self::update_freezes(asset, who, freezes.as_bounded_slice());
+		Ok(())
+	}
+
+	fn thaw(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Freezes::<T, I>::get(asset.clone(), who);
+		freezes.retain(|f| &f.id != id);
+		MigratedFreezes::<T, I>::mutate_exists(asset.clone(), who, |maybe_reason| {
+			if maybe_reason.as_ref() == Some(id) {
+				*maybe_reason = None;
+			}
+		});
+		Self::update_freezes(asset, who, freezes.as_bounded_slice());
+		Ok(())
+	}
+}
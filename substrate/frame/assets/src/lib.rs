@@ -137,6 +137,17 @@
 //! Using `CallbackHandle` associated type, user can configure custom callback functions which are
 //! executed when new asset is created or an existing asset is destroyed.
 //!
+//! Using `OnTransfer` associated type, user can configure checks which are executed before every
+//! transfer of an asset, and are able to reject it, together with a callback executed after the
+//! transfer. This allows asset issuers to enforce compliance rules on all the transfers.
+//!
+//! ### Freezes
+//!
+//! Besides the account-wide freezes of the asset class's Freezer, the pallet implements
+//! [`fungibles::InspectFreeze`] and [`fungibles::MutateFreeze`], allowing other pallets to freeze
+//! an amount of an account balance for a given `RuntimeFreezeReason`. The frozen balance of an
+//! account is the largest of its freezes, and of the one reported by the `Freezer` hook.
+//!
 //! ## Related Modules
 //!
 //! * [`System`](../frame_system/index.html)
@@ -226,6 +237,41 @@ impl<AssetId, AccountId> AssetsCallback<AssetId, AccountId> for Tuple {
 	}
 }
 
+/// Trait with hooks that are executed around every transfer of an asset.
+pub trait AssetsTransferHook<AssetId, AccountId, Balance> {
+	/// Checks whether `amount` of the asset `id` can be transferred from `from` to `to`.
+	///
+	/// The transfer is rejected with the returned error.
+	fn pre_transfer(
+		_id: &AssetId,
+		_from: &AccountId,
+		_to: &AccountId,
+		_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	/// Indicates that `amount` of the asset `id` was transferred from `from` to `to`.
+	fn post_transfer(_id: &AssetId, _from: &AccountId, _to: &AccountId, _amount: Balance) {}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl<AssetId, AccountId, Balance: Copy> AssetsTransferHook<AssetId, AccountId, Balance> for Tuple {
+	fn pre_transfer(
+		id: &AssetId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		for_tuples!( #( Tuple::pre_transfer(id, from, to, amount)?; )* );
+		Ok(())
+	}
+
+	fn post_transfer(id: &AssetId, from: &AccountId, to: &AccountId, amount: Balance) {
+		for_tuples!( #( Tuple::post_transfer(id, from, to, amount); )* );
+	}
+}
+
 /// Auto-increment the [`NextAssetId`] when an asset is created.
 ///
 /// This has not effect if the [`NextAssetId`] value is not present.
@@ -251,12 +297,12 @@ pub mod pallet {
 	use codec::HasCompact;
 	use frame_support::{
 		pallet_prelude::*,
-		traits::{AccountTouch, ContainsPair},
+		traits::{tokens::IdAmount, AccountTouch, ContainsPair, VariantCount, VariantCountOf},
 	};
 	use frame_system::pallet_prelude::*;
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -300,6 +346,9 @@ pub mod pallet {
 			type Holder = ();
 			type Extra = ();
 			type CallbackHandle = ();
+			type OnTransfer = ();
+			#[inject_runtime_type]
+			type RuntimeFreezeReason = ();
 			type WeightInfo = ();
 			#[cfg(feature = "runtime-benchmarks")]
 			type BenchmarkHelper = ();
@@ -412,6 +461,16 @@ pub mod pallet {
 		/// used to set up auto-incrementing asset IDs for this collection.
 		type CallbackHandle: AssetsCallback<Self::AssetId, Self::AccountId>;
 
+		/// Hooks executed before and after every transfer of an asset.
+		///
+		/// Types implementing the [`AssetsTransferHook`] can be chained when listed together as a
+		/// tuple. The transfer is rejected if any of the pre-transfer checks fails.
+		type OnTransfer: AssetsTransferHook<Self::AssetId, Self::AccountId, Self::Balance>;
+
+		/// The overarching freeze reason, identifying the freezes of [`Freezes`].
+		#[pallet::no_default_bounds]
+		type RuntimeFreezeReason: Parameter + Member + MaxEncodedLen + Copy + VariantCount;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -464,6 +523,33 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The freezes placed on the balance of an account, through [`fungibles::MutateFreeze`].
+	#[pallet::storage]
+	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<
+			IdAmount<T::RuntimeFreezeReason, T::Balance>,
+			VariantCountOf<T::RuntimeFreezeReason>,
+		>,
+		ValueQuery,
+	>;
+
+	/// The reasons of the freezes placed by [`migration::freezes::MigrateFrozenAccounts`] on the
+	/// accounts frozen by their status, lifted by [`Pallet::thaw`] as the status was.
+	#[pallet::storage]
+	pub type MigratedFreezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::RuntimeFreezeReason,
+	>;
+
 	/// The asset ID enforced for the next asset creation, if any present. Otherwise, this storage
 	/// item has no effect.
 	///
@@ -700,6 +786,9 @@ pub mod pallet {
 		ContainsFreezes,
 		/// The asset cannot be destroyed because some accounts for this asset contain holds.
 		ContainsHolds,
+		/// Number of freezes on an account would exceed the variant count of the
+		/// `RuntimeFreezeReason`.
+		TooManyFreezes,
 	}
 
 	#[pallet::call(weight(<T as Config<I>>::WeightInfo))]
@@ -1121,6 +1210,9 @@ pub mod pallet {
 					AccountStatus::Liquid;
 				Ok(())
 			})?;
+			if let Some(reason) = MigratedFreezes::<T, I>::get(&id, &who) {
+				<Self as fungibles::MutateFreeze<T::AccountId>>::thaw(id.clone(), &reason, &who)?;
+			}
 
 			Self::deposit_event(Event::<T, I>::Thawed { asset_id: id, who });
 			Ok(())
@@ -46,6 +46,120 @@ pub mod next_asset_id {
 	}
 }
 
+pub mod freezes {
+	use super::*;
+	use frame_support::{
+		migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+		traits::{fungibles::MutateFreeze, Get, GetStorageVersion, StorageVersion},
+		weights::{Weight, WeightMeter},
+	};
+	use sp_runtime::traits::Bounded;
+
+	/// The pallet identifier of the multi-block migrations of this pallet.
+	pub const PALLET_MIGRATIONS_ID: &[u8; 13] = b"pallet-assets";
+
+	/// Moves the accounts frozen by the Freezer of their asset class to a freeze of their whole
+	/// balance, for the reason `Reason`.
+	///
+	/// The reason of each freeze is recorded in [`MigratedFreezes`], so that the Admin of the asset
+	/// class can still lift it with [`Pallet::thaw`]. Blocked accounts are left unchanged.
+	///
+	/// This is a multi-block migration from storage version 1 to 2. It migrates as many accounts
+	/// as the weight of each step allows and does nothing once the pallet is at another version.
+	pub struct MigrateFrozenAccounts<T, Reason, I: 'static = ()>(
+		core::marker::PhantomData<(T, Reason, I)>,
+	);
+
+	impl<T: Config<I>, Reason: Get<T::RuntimeFreezeReason>, I: 'static>
+		MigrateFrozenAccounts<T, Reason, I>
+	{
+		/// The worst case weight of visiting a single account.
+		///
+		/// Reads the account and its freezes, then writes both and its [`MigratedFreezes`] entry.
+		fn account_weight() -> Weight {
+			T::DbWeight::get().reads_writes(2, 3)
+		}
+
+		fn migrate_account(id: &T::AssetId, who: &T::AccountId) {
+			let reason = Reason::get();
+			if let Err(error) =
+				Pallet::<T, I>::set_freeze(id.clone(), &reason, who, T::Balance::max_value())
+			{
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to freeze a frozen account of asset {:?}: {:?}",
+					id,
+					error
+				);
+				return
+			}
+			MigratedFreezes::<T, I>::insert(id, who, reason);
+			Account::<T, I>::mutate(id, who, |maybe_account| {
+				if let Some(account) = maybe_account {
+					account.status = AccountStatus::Liquid;
+				}
+			});
+		}
+	}
+
+	impl<T: Config<I>, Reason: Get<T::RuntimeFreezeReason>, I: 'static> SteppedMigration
+		for MigrateFrozenAccounts<T, Reason, I>
+	{
+		type Cursor = (T::AssetId, T::AccountId);
+		type Identifier = MigrationId<13>;
+
+		fn id() -> Self::Identifier {
+			MigrationId { pallet_id: *PALLET_MIGRATIONS_ID, version_from: 1, version_to: 2 }
+		}
+
+		fn step(
+			mut cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			if Pallet::<T, I>::on_chain_storage_version() != Self::id().version_from as u16 {
+				return Ok(None)
+			}
+
+			let required = Self::account_weight();
+			if meter.remaining().any_lt(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required })
+			}
+
+			while meter.try_consume(required).is_ok() {
+				let mut iter = match &cursor {
+					Some((id, who)) =>
+						Account::<T, I>::iter_from(Account::<T, I>::hashed_key_for(id, who)),
+					None => Account::<T, I>::iter(),
+				};
+				let Some((id, who, account)) = iter.next() else {
+					StorageVersion::new(Self::id().version_to as u16).put::<Pallet<T, I>>();
+					log::info!(target: LOG_TARGET, "Migrated all frozen accounts to freezes");
+					return Ok(None)
+				};
+				if account.status == AccountStatus::Frozen {
+					Self::migrate_account(&id, &who);
+				}
+				cursor = Some((id, who));
+			}
+			Ok(cursor)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == Self::id().version_to as u16,
+				"the storage version should be bumped after the migration"
+			);
+			ensure!(
+				Account::<T, I>::iter()
+					.all(|(_, _, account)| account.status != AccountStatus::Frozen),
+				"no account should be frozen by its status after the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
 pub mod v1 {
 	use frame_support::{pallet_prelude::*, weights::Weight};
 
@@ -91,9 +205,8 @@ pub mod v1 {
 	pub struct MigrateToV1<T>(core::marker::PhantomData<T>);
 	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
 		fn on_runtime_upgrade() -> Weight {
-			let in_code_version = Pallet::<T>::in_code_storage_version();
 			let on_chain_version = Pallet::<T>::on_chain_storage_version();
-			if on_chain_version == 0 && in_code_version == 1 {
+			if on_chain_version == 0 {
 				let mut translated = 0u64;
 				Asset::<T>::translate::<
 					OldAssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T>>,
@@ -102,13 +215,8 @@ pub mod v1 {
 					translated.saturating_inc();
 					Some(old_value.migrate_to_v1())
 				});
-				in_code_version.put::<Pallet<T>>();
-				log::info!(
-					target: LOG_TARGET,
-					"Upgraded {} pools, storage to version {:?}",
-					translated,
-					in_code_version
-				);
+				StorageVersion::new(1).put::<Pallet<T>>();
+				log::info!(target: LOG_TARGET, "Upgraded {} pools, storage to version 1", translated);
 				T::DbWeight::get().reads_writes(translated + 1, translated + 1)
 			} else {
 				log::info!(
@@ -140,13 +248,9 @@ pub mod v1 {
 				"the asset count before and after the migration should be the same"
 			);
 
-			let in_code_version = Pallet::<T>::in_code_storage_version();
-			let on_chain_version = Pallet::<T>::on_chain_storage_version();
-
-			frame_support::ensure!(in_code_version == 1, "must_upgrade");
 			ensure!(
-				in_code_version == on_chain_version,
-				"after migration, the in_code_version and on_chain_version should be the same"
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"after migration, the on_chain_version should be 1"
 			);
 
 			Asset::<T>::iter().try_for_each(|(_id, asset)| -> Result<(), TryRuntimeError> {
@@ -20,10 +20,10 @@
 use super::*;
 use crate as pallet_assets;
 
-use codec::Encode;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::{
 	assert_ok, construct_runtime, derive_impl, parameter_types,
-	traits::{AsEnsureOriginWithArg, ConstU32},
+	traits::{AsEnsureOriginWithArg, ConstU32, VariantCount},
 };
 use sp_io::storage;
 use sp_runtime::BuildStorage;
@@ -47,6 +47,7 @@ impl frame_system::Config for Test {
 	type Block = Block;
 	type AccountData = pallet_balances::AccountData<u64>;
 	type MaxConsumers = ConstU32<3>;
+	type DbWeight = frame_support::weights::constants::RocksDbWeight;
 }
 
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
@@ -105,6 +106,29 @@ impl Config for Test {
 	type Freezer = TestFreezer;
 	type Holder = TestHolder;
 	type CallbackHandle = (AssetsCallbackHandle, AutoIncAssetId<Test>);
+	type OnTransfer = TestTransferHook;
+	type RuntimeFreezeReason = TestFreezeReason;
+}
+
+#[derive(
+	Decode,
+	DecodeWithMemTracking,
+	Encode,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	TypeInfo,
+	Debug,
+	Clone,
+	Copy,
+)]
+pub enum TestFreezeReason {
+	Governance,
+	Staking,
+}
+
+impl VariantCount for TestFreezeReason {
+	const VARIANT_COUNT: u32 = 2;
 }
 
 use std::collections::HashMap;
@@ -117,6 +141,31 @@ parameter_types! {
 	static Frozen: HashMap<(u32, u64), u64> = Default::default();
 	static OnHold: HashMap<(u32, u64), u64> = Default::default();
 	static Hooks: Vec<Hook> = Default::default();
+	static Blacklisted: Vec<u64> = Default::default();
+	static Transfers: Vec<(u32, u64, u64, u64)> = Default::default();
+}
+
+/// Rejects the transfers from and to the accounts of [`set_blacklisted`], and records the others.
+pub struct TestTransferHook;
+impl AssetsTransferHook<u32, u64, u64> for TestTransferHook {
+	fn pre_transfer(_id: &u32, from: &u64, to: &u64, _amount: u64) -> DispatchResult {
+		if Blacklisted::get().iter().any(|who| who == from || who == to) {
+			return Err(DispatchError::Other("blacklisted"))
+		}
+		Ok(())
+	}
+
+	fn post_transfer(id: &u32, from: &u64, to: &u64, amount: u64) {
+		Transfers::mutate(|v| v.push((*id, *from, *to, amount)));
+	}
+}
+
+pub(crate) fn set_blacklisted(who: Vec<u64>) {
+	Blacklisted::set(who);
+}
+
+pub(crate) fn take_transfers() -> Vec<(u32, u64, u64, u64)> {
+	Transfers::take()
 }
 
 pub struct TestHolder;
@@ -221,7 +270,11 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 
 	let mut ext: sp_io::TestExternalities = storage.into();
 	// Clear thread local vars for https://github.com/paritytech/substrate/issues/10479.
-	ext.execute_with(|| take_hooks());
+	ext.execute_with(|| {
+		take_hooks();
+		take_transfers();
+		set_blacklisted(vec![]);
+	});
 	ext.execute_with(|| System::set_block_number(1));
 	ext
 }
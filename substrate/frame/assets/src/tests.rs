@@ -2180,3 +2180,144 @@ fn asset_id_cannot_be_reused() {
 		assert!(Asset::<Test>::contains_key(7));
 	});
 }
+
+#[test]
+fn transfer_hooks_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 50));
+		assert_eq!(take_transfers(), vec![(0, 1, 2, 50)]);
+
+		// the pre-transfer check rejects transfers from and to the blacklisted accounts.
+		set_blacklisted(vec![3]);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 3, 10),
+			DispatchError::Other("blacklisted")
+		);
+		assert_noop!(
+			Assets::force_transfer(RuntimeOrigin::signed(1), 0, 2, 3, 10),
+			DispatchError::Other("blacklisted")
+		);
+		assert_noop!(
+			<Assets as fungibles::Mutate<_>>::transfer(0, &1, &3, 10, Protect),
+			DispatchError::Other("blacklisted")
+		);
+
+		// the hooks also apply to the transfers of the fungibles traits and of approvals.
+		assert_ok!(<Assets as fungibles::Mutate<_>>::transfer(0, &1, &2, 10, Protect));
+		Balances::make_free_balance_be(&1, 10);
+		assert_ok!(Assets::approve_transfer(RuntimeOrigin::signed(1), 0, 4, 10));
+		assert_ok!(Assets::transfer_approved(RuntimeOrigin::signed(4), 0, 1, 2, 5));
+		assert_eq!(take_transfers(), vec![(0, 1, 2, 10), (0, 1, 2, 5)]);
+	});
+}
+
+#[test]
+fn freezes_with_reasons_work() {
+	use frame_support::traits::fungibles::{InspectFreeze, MutateFreeze};
+	use TestFreezeReason::*;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		assert_ok!(Assets::set_freeze(0, &Governance, &1, 50));
+		assert_ok!(Assets::extend_freeze(0, &Staking, &1, 70));
+		assert_ok!(Assets::extend_freeze(0, &Staking, &1, 60));
+		assert_eq!(Assets::balance_frozen(0, &Staking, &1), 70);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Protect, Fortitude::Polite),
+			30
+		);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 31),
+			Error::<Test>::BalanceLow
+		);
+
+		// the account cannot die nor the asset be destroyed with freezes.
+		assert_noop!(
+			Assets::start_destroy(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::ContainsFreezes
+		);
+
+		// the largest freeze of the account applies, together with the `Freezer`.
+		assert_ok!(<Assets as MutateFreeze<_>>::thaw(0, &Staking, &1));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Protect, Fortitude::Polite),
+			50
+		);
+		set_frozen_balance(0, 1, 80);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Protect, Fortitude::Polite),
+			20
+		);
+		clear_frozen_balance(0, 1);
+
+		assert!(Assets::can_freeze(0, &Staking, &1));
+		assert_ok!(Assets::set_freeze(0, &Governance, &1, 0));
+		assert!(!Freezes::<Test>::contains_key(0, 1));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 100));
+	});
+}
+
+#[test]
+fn migrate_frozen_accounts_works() {
+	use crate::migration::freezes::MigrateFrozenAccounts;
+	use frame_support::{
+		migrations::SteppedMigration,
+		parameter_types,
+		traits::{fungibles::InspectFreeze, GetStorageVersion, StorageVersion},
+		weights::WeightMeter,
+	};
+
+	parameter_types! {
+		const Reason: TestFreezeReason = TestFreezeReason::Governance;
+	}
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 100));
+		assert_ok!(Assets::freeze(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Assets::block(RuntimeOrigin::signed(1), 0, 2));
+		StorageVersion::new(1).put::<Assets>();
+
+		// a single account is visited per step: the two accounts of asset 0 and the genesis
+		// account of asset 999, then the migration completes.
+		let account_weight = <Test as frame_system::Config>::DbWeight::get().reads_writes(2, 3);
+		let mut steps = 0;
+		let mut cursor = None;
+		loop {
+			let mut meter = WeightMeter::with_limit(account_weight);
+			cursor = MigrateFrozenAccounts::<Test, Reason>::step(cursor, &mut meter).unwrap();
+			steps += 1;
+			if cursor.is_none() {
+				break
+			}
+		}
+		assert_eq!(steps, 4);
+		assert_eq!(Assets::on_chain_storage_version(), 2);
+		// the migration does nothing once the storage version is bumped.
+		assert!(matches!(
+			MigrateFrozenAccounts::<Test, Reason>::step(None, &mut WeightMeter::new()),
+			Ok(None)
+		));
+
+		assert_eq!(Account::<Test>::get(0, 1).unwrap().status, AccountStatus::Liquid);
+		assert_eq!(Assets::balance_frozen(0, &TestFreezeReason::Governance, &1), u64::MAX);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 3, 10),
+			Error::<Test>::BalanceLow
+		);
+		// blocked accounts are left unchanged.
+		assert_eq!(Account::<Test>::get(0, 2).unwrap().status, AccountStatus::Blocked);
+		assert!(!Freezes::<Test>::contains_key(0, 2));
+
+		// the admin lifts the migrated freeze by thawing the account.
+		assert_ok!(Assets::thaw(RuntimeOrigin::signed(1), 0, 1));
+		assert!(!Freezes::<Test>::contains_key(0, 1));
+		assert!(!MigratedFreezes::<Test>::contains_key(0, 1));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 3, 10));
+	});
+}
@@ -0,0 +1,164 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the balances pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{
+		error::{ErrorCode, ErrorObject},
+		ErrorObjectOwned,
+	},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+
+pub use pallet_balances::runtime_api::BalancesApi as BalancesRuntimeApi;
+
+/// An amount of the balance of an account, along with the reason it can't be spent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReasonAmount {
+	/// The reason, as rendered by the runtime from the metadata of the reason type.
+	pub reason: String,
+	/// The amount.
+	pub amount: NumberOrHex,
+}
+
+/// Breakdown of the balance of an account, see [`pallet_balances::BalanceBreakdown`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceBreakdown {
+	pub free: NumberOrHex,
+	pub reserved: NumberOrHex,
+	pub frozen: NumberOrHex,
+	pub holds: Vec<ReasonAmount>,
+	pub reserves: Vec<ReasonAmount>,
+	pub freezes: Vec<ReasonAmount>,
+	pub locks: Vec<ReasonAmount>,
+	pub existential_deposit: NumberOrHex,
+	pub spendable: NumberOrHex,
+	pub spendable_keep_alive: NumberOrHex,
+}
+
+#[rpc(client, server)]
+pub trait BalancesApi<BlockHash, AccountId> {
+	/// Returns the holds, reserves, freezes and locks on the balance of `who`, and the part of
+	/// the balance that can be spent.
+	#[method(name = "balances_breakdown")]
+	fn balance_breakdown(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<BalanceBreakdown>;
+}
+
+/// Provides RPC methods to explain the balance of an account.
+pub struct Balances<C, Block, Balance> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(Block, Balance)>,
+}
+
+impl<C, Block, Balance> Balances<C, Block, Balance> {
+	/// Creates a new instance of the Balances Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, Balance> BalancesApiServer<<Block as BlockT>::Hash, AccountId>
+	for Balances<C, Block, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BalancesRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn balance_breakdown(
+		&self,
+		who: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<BalanceBreakdown> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let breakdown = api.balance_breakdown(at_hash, who).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the balance breakdown.",
+				Some(e.to_string()),
+			)
+		})?;
+
+		let try_into_rpc_balance = |value: Balance| -> Result<NumberOrHex, ErrorObjectOwned> {
+			value.try_into().map_err(|_| {
+				ErrorObject::owned(
+					ErrorCode::InvalidParams.code(),
+					format!("{} doesn't fit in NumberOrHex representation", value),
+					None::<()>,
+				)
+			})
+		};
+		let try_into_rpc_amounts = |amounts: Vec<pallet_balances::ReasonAmount<Balance>>| {
+			amounts
+				.into_iter()
+				.map(|amount| {
+					Ok(ReasonAmount {
+						reason: amount.reason,
+						amount: try_into_rpc_balance(amount.amount)?,
+					})
+				})
+				.collect::<Result<Vec<_>, ErrorObjectOwned>>()
+		};
+
+		Ok(BalanceBreakdown {
+			free: try_into_rpc_balance(breakdown.free)?,
+			reserved: try_into_rpc_balance(breakdown.reserved)?,
+			frozen: try_into_rpc_balance(breakdown.frozen)?,
+			holds: try_into_rpc_amounts(breakdown.holds)?,
+			reserves: try_into_rpc_amounts(breakdown.reserves)?,
+			freezes: try_into_rpc_amounts(breakdown.freezes)?,
+			locks: try_into_rpc_amounts(breakdown.locks)?,
+			existential_deposit: try_into_rpc_balance(breakdown.existential_deposit)?,
+			spendable: try_into_rpc_balance(breakdown.spendable)?,
+			spendable_keep_alive: try_into_rpc_balance(breakdown.spendable_keep_alive)?,
+		})
+	}
+}
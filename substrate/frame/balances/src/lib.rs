@@ -146,6 +146,7 @@ mod benchmarking;
 mod impl_currency;
 mod impl_fungible;
 pub mod migration;
+pub mod runtime_api;
 mod tests;
 mod types;
 pub mod weights;
@@ -187,7 +188,8 @@ use sp_runtime::{
 };
 
 pub use types::{
-	AccountData, AdjustmentDirection, BalanceLock, DustCleaner, ExtraFlags, Reasons, ReserveData,
+	AccountData, AdjustmentDirection, BalanceBreakdown, BalanceLock, DustCleaner, ExtraFlags,
+	ReasonAmount, Reasons, ReserveData,
 };
 pub use weights::WeightInfo;
 
@@ -932,6 +934,38 @@ pub mod pallet {
 			<Self as fungible::Inspect<_>>::reducible_balance(who.borrow(), Protect, Polite)
 		}
 
+		/// Get the breakdown of the balance of an account into its holds, reserves, freezes and
+		/// locks, along with the balance that can be spent.
+		pub fn balance_breakdown(who: &T::AccountId) -> BalanceBreakdown<T::Balance> {
+			let account = Self::account(who);
+			BalanceBreakdown {
+				free: account.free,
+				reserved: account.reserved,
+				frozen: account.frozen,
+				holds: Holds::<T, I>::get(who)
+					.iter()
+					.map(|hold| ReasonAmount::new(&hold.id, hold.amount))
+					.collect(),
+				reserves: Reserves::<T, I>::get(who)
+					.iter()
+					.map(|reserve| ReasonAmount::new(&reserve.id, reserve.amount))
+					.collect(),
+				freezes: Freezes::<T, I>::get(who)
+					.iter()
+					.map(|freeze| ReasonAmount::new(&freeze.id, freeze.amount))
+					.collect(),
+				locks: Locks::<T, I>::get(who)
+					.iter()
+					.map(|lock| ReasonAmount::new(&lock.id, lock.amount))
+					.collect(),
+				existential_deposit: Self::ed(),
+				spendable: Self::usable_balance(who),
+				spendable_keep_alive: <Self as fungible::Inspect<_>>::reducible_balance(
+					who, Preserve, Polite,
+				),
+			}
+		}
+
 		/// Get the reserved balance of an account.
 		pub fn reserved_balance(who: impl core::borrow::Borrow<T::AccountId>) -> T::Balance {
 			Self::account(who.borrow()).reserved
@@ -668,3 +668,34 @@ fn lone_hold_consideration_works() {
 			assert_eq!(Balances::balance_on_hold(&TestId::Foo, &who), 0);
 		});
 }
+
+#[test]
+fn balance_breakdown_works() {
+	use crate::{BalanceBreakdown, ReasonAmount};
+	use frame_support::traits::{LockableCurrency, NamedReservableCurrency, WithdrawReasons};
+
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&1, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 20));
+		assert_ok!(Balances::reserve_named(&TestId::Bar, &1, 5));
+		assert_ok!(Balances::set_freeze(&TestId::Baz, &1, 30));
+		Balances::set_lock(*b"staking ", &1, 40, WithdrawReasons::all());
+
+		let reason = |reason: &str, amount| ReasonAmount { reason: reason.into(), amount };
+		assert_eq!(
+			Balances::balance_breakdown(&1),
+			BalanceBreakdown {
+				free: 75,
+				reserved: 25,
+				frozen: 40,
+				holds: vec![reason("Foo", 20)],
+				reserves: vec![reason("Bar", 5)],
+				freezes: vec![reason("Baz", 30)],
+				locks: vec![reason("staking", 40)],
+				existential_deposit: 10,
+				spendable: Balances::reducible_balance(&1, Expendable, Polite),
+				spendable_keep_alive: Balances::reducible_balance(&1, Preserve, Polite),
+			}
+		);
+	});
+}
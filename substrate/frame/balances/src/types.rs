@@ -18,10 +18,12 @@
 //! Types used in the pallet.
 
 use crate::{Config, CreditOf, Event, Pallet};
+use alloc::{format, string::String, vec::Vec};
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use core::ops::BitOr;
 use frame_support::traits::{Imbalance, LockIdentifier, OnUnbalanced, WithdrawReasons};
-use scale_info::TypeInfo;
+use scale_info::{TypeDef, TypeInfo};
+use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::{RuntimeDebug, Saturating};
 
 /// Simplified reasons for withdrawing balance.
@@ -99,6 +101,89 @@ pub struct AccountData<Balance> {
 	pub flags: ExtraFlags,
 }
 
+/// An amount of the balance of an account that is held, frozen, locked or reserved, along with
+/// the reason of it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReasonAmount<Balance> {
+	/// The reason, rendered from its type information, e.g. `Preimage::Preimage` for the
+	/// `Preimage` variant of the hold reasons of the `Preimage` pallet.
+	///
+	/// Identifiers without type information, such as the ones of the locks, are rendered as text
+	/// if they are printable and as hex otherwise.
+	pub reason: String,
+	/// The amount.
+	pub amount: Balance,
+}
+
+impl<Balance> ReasonAmount<Balance> {
+	pub(crate) fn new<Reason: Encode + TypeInfo + 'static>(
+		reason: &Reason,
+		amount: Balance,
+	) -> Self {
+		Self { reason: render_reason(reason), amount }
+	}
+}
+
+/// Renders the variant names of a reason, joined by `::`, by following its encoding through its
+/// type information.
+fn render_reason<Reason: Encode + TypeInfo + 'static>(reason: &Reason) -> String {
+	let encoded = reason.encode();
+	let mut bytes = &encoded[..];
+	let mut ty = Reason::type_info();
+	let mut names = Vec::new();
+
+	loop {
+		let TypeDef::Variant(def) = &ty.type_def else { break };
+		let Some((index, rest)) = bytes.split_first() else { break };
+		let Some(variant) = def.variants.iter().find(|variant| variant.index == *index) else {
+			break
+		};
+		names.push(variant.name);
+		bytes = rest;
+		let inner = match &variant.fields[..] {
+			[field] => field.ty.type_info(),
+			_ => break,
+		};
+		ty = inner;
+	}
+
+	if !names.is_empty() {
+		return names.join("::")
+	}
+
+	match core::str::from_utf8(&encoded) {
+		Ok(text) if !text.is_empty() && text.bytes().all(|b| b.is_ascii_graphic() || b == b' ') =>
+			text.trim_end().into(),
+		_ => format!("0x{}", HexDisplay::from(&encoded)),
+	}
+}
+
+/// Breakdown of the balance of an account, explaining which part of it can be spent.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct BalanceBreakdown<Balance> {
+	/// The free balance of the account, see [`AccountData::free`].
+	pub free: Balance,
+	/// The reserved balance of the account, see [`AccountData::reserved`].
+	pub reserved: Balance,
+	/// The frozen balance of the account, see [`AccountData::frozen`].
+	pub frozen: Balance,
+	/// The holds on the balance, which add up to `reserved` together with the `reserves`.
+	pub holds: Vec<ReasonAmount<Balance>>,
+	/// The named reserves on the balance.
+	pub reserves: Vec<ReasonAmount<Balance>>,
+	/// The freezes on the balance. Freezes overlap, `frozen` is the largest of them and of the
+	/// `locks`.
+	pub freezes: Vec<ReasonAmount<Balance>>,
+	/// The locks on the balance, which overlap like the freezes.
+	pub locks: Vec<ReasonAmount<Balance>>,
+	/// The existential deposit.
+	pub existential_deposit: Balance,
+	/// The balance that can be spent if the account is allowed to be reaped.
+	pub spendable: Balance,
+	/// The balance that can be spent while keeping the account alive.
+	pub spendable_keep_alive: Balance,
+}
+
 const IS_NEW_LOGIC: u128 = 0x80000000_00000000_00000000_00000000u128;
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -101,6 +101,7 @@ impl pallet_treasury::Config for Test {
 	type AssetKind = ();
 	type Beneficiary = Self::AccountId;
 	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type PartialPayoutOrigin = frame_system::EnsureSigned<Self::AccountId>;
 	type Paymaster = PayFromAccount<Balances, TreasuryAccount>;
 	type BalanceConverter = UnityAssetBalanceConversion;
 	type PayoutPeriod = ConstU64<10>;
@@ -124,6 +125,7 @@ impl pallet_treasury::Config<Instance1> for Test {
 	type AssetKind = ();
 	type Beneficiary = Self::AccountId;
 	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type PartialPayoutOrigin = frame_system::EnsureSigned<Self::AccountId>;
 	type Paymaster = PayFromAccount<Balances, TreasuryInstance1Account>;
 	type BalanceConverter = UnityAssetBalanceConversion;
 	type PayoutPeriod = ConstU64<10>;
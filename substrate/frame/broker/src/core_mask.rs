@@ -69,6 +69,9 @@ impl CoreMask {
 	pub fn count_ones(&self) -> u32 {
 		self.0.iter().map(|i| i.count_ones()).sum()
 	}
+	pub fn is_set(&self, i: u32) -> bool {
+		i < 80 && self.0[(i / 8) as usize] & (128 >> (i % 8)) != 0
+	}
 	pub fn from_chunk(from: u32, to: u32) -> Self {
 		let mut v = [0u8; 10];
 		for i in (from.min(80) as usize)..(to.min(80) as usize) {
@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use super::*;
+use alloc::vec::Vec;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{fungible::Mutate, tokens::Preservation::Expendable, DefensiveResult},
@@ -297,6 +298,41 @@ impl<T: Config> Pallet<T> {
 		Ok(new_region_ids)
 	}
 
+	pub(crate) fn do_split_at(
+		region_id: RegionId,
+		maybe_check_owner: Option<T::AccountId>,
+		timeslice: Timeslice,
+	) -> Result<(RegionId, RegionId), Error<T>> {
+		ensure!(timeslice > region_id.begin, Error::<T>::PivotTooEarly);
+		Self::do_partition(region_id, maybe_check_owner, timeslice - region_id.begin)
+	}
+
+	pub(crate) fn do_interlace_evenly(
+		region_id: RegionId,
+		maybe_check_owner: Option<T::AccountId>,
+		parts: u32,
+	) -> Result<Vec<RegionId>, Error<T>> {
+		let bits = region_id.mask.count_ones();
+		ensure!(parts > 1 && parts <= bits && bits % parts == 0, Error::<T>::UnevenInterlace);
+		let bits_per_part = (bits / parts) as usize;
+
+		let mut region_ids = Vec::with_capacity(parts as usize);
+		let mut rest = region_id;
+		for _ in 1..parts {
+			// The part takes the lowest bits of what remains of the mask.
+			let pivot = (0..CORE_MASK_BITS as u32)
+				.filter(|&i| rest.mask.is_set(i))
+				.take(bits_per_part)
+				.fold(CoreMask::void(), |mut mask, i| mask.set(i));
+			let (part, other) = Self::do_interlace(rest, maybe_check_owner.clone(), pivot)?;
+			region_ids.push(part);
+			rest = other;
+		}
+		region_ids.push(rest);
+
+		Ok(region_ids)
+	}
+
 	pub(crate) fn do_assign(
 		region_id: RegionId,
 		maybe_check_owner: Option<T::AccountId>,
@@ -569,6 +569,9 @@ pub mod pallet {
 		/// Needed to prevent spam attacks.The amount of credits the user attempted to purchase is
 		/// below `T::MinimumCreditPurchase`.
 		CreditPurchaseTooSmall,
+		/// The interlace mask of the region can't be split evenly into the requested number of
+		/// parts.
+		UnevenInterlace,
 	}
 
 	#[derive(frame_support::DefaultNoBound)]
@@ -758,6 +761,47 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Split a Bulk Coretime Region into two non-overlapping Regions at a particular
+		/// timeslice.
+		///
+		/// Same as [`Pallet::partition`], with the pivot given as an absolute timeslice instead of
+		/// an offset into the Region.
+		///
+		/// - `origin`: Must be a Signed origin of the account which owns the Region `region_id`.
+		/// - `region_id`: The Region which should be partitioned into two non-overlapping Regions.
+		/// - `timeslice`: The timeslice at which the second Region begins.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::partition())]
+		pub fn split_at(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			timeslice: Timeslice,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_split_at(region_id, Some(who), timeslice)?;
+			Ok(())
+		}
+
+		/// Split a Bulk Coretime Region into `parts` wholly-overlapping Regions, whose interlace
+		/// masks have the same number of regularity parts and together make up the original
+		/// Region's interlace mask.
+		///
+		/// - `origin`: Must be a Signed origin of the account which owns the Region `region_id`.
+		/// - `region_id`: The Region which should become `parts` interlaced Regions.
+		/// - `parts`: The number of Regions to create. The number of regularity parts of the
+		///   Region's interlace mask must be a multiple of it.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::interlace().saturating_mul(parts.saturating_sub(1).into()))]
+		pub fn interlace_evenly(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			parts: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_interlace_evenly(region_id, Some(who), parts)?;
+			Ok(())
+		}
+
 		/// Assign a Bulk Coretime Region to a task.
 		///
 		/// - `origin`: Must be a Signed origin of the account which owns the Region `region_id`.
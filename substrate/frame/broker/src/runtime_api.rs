@@ -17,6 +17,8 @@
 
 //! Runtime API definition for the FRAME Broker pallet.
 
+use crate::RegionInfo;
+use alloc::vec::Vec;
 use codec::Codec;
 use sp_runtime::DispatchError;
 
@@ -28,4 +30,13 @@ sp_api::decl_runtime_apis! {
 		/// If there is an ongoing sale returns the current price of a core.
 		fn sale_price() -> Result<Balance, DispatchError>;
 	}
+
+	pub trait BrokerRegionsApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec
+	{
+		/// The Regions owned by `who`, with their timeslices and interlace masks.
+		fn regions(who: AccountId) -> Vec<RegionInfo<Balance>>;
+	}
 }
@@ -791,6 +791,91 @@ fn interlace_works() {
 	});
 }
 
+#[test]
+fn split_at_works() {
+	TestExt::new().endow(1, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_noop!(
+			Broker::split_at(RuntimeOrigin::signed(1), region, region.begin),
+			Error::<Test>::PivotTooEarly
+		);
+		assert_noop!(
+			Broker::split_at(RuntimeOrigin::signed(2), region, region.begin + 1),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Broker::split_at(RuntimeOrigin::signed(1), region, region.begin + 1));
+		let second = RegionId { begin: region.begin + 1, ..region };
+		assert_eq!(Regions::<Test>::get(region).map(|r| r.end), Some(region.begin + 1));
+		assert!(Regions::<Test>::contains_key(second));
+	});
+}
+
+#[test]
+fn interlace_evenly_works() {
+	TestExt::new().endow(1, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_noop!(
+			Broker::interlace_evenly(RuntimeOrigin::signed(1), region, 3),
+			Error::<Test>::UnevenInterlace
+		);
+		assert_noop!(
+			Broker::interlace_evenly(RuntimeOrigin::signed(1), region, 1),
+			Error::<Test>::UnevenInterlace
+		);
+
+		let parts = Broker::do_interlace_evenly(region, Some(1), 4).unwrap();
+		assert_eq!(
+			parts.iter().map(|id| id.mask).collect::<Vec<_>>(),
+			vec![
+				CoreMask::from_chunk(0, 20),
+				CoreMask::from_chunk(20, 40),
+				CoreMask::from_chunk(40, 60),
+				CoreMask::from_chunk(60, 80),
+			]
+		);
+		assert!(!Regions::<Test>::contains_key(region));
+		assert!(parts.iter().all(|id| Regions::<Test>::contains_key(id)));
+
+		// Interlaced masks are split along their set bits.
+		let parts = Broker::do_interlace_evenly(parts[1], Some(1), 2).unwrap();
+		assert_eq!(
+			parts.iter().map(|id| id.mask).collect::<Vec<_>>(),
+			vec![CoreMask::from_chunk(20, 30), CoreMask::from_chunk(30, 40)]
+		);
+	});
+}
+
+#[test]
+fn regions_of_works() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 2));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		let other = Broker::do_purchase(2, u64::max_value()).unwrap();
+		let (one, two) = Broker::do_interlace(region, None, CoreMask::from_chunk(0, 20)).unwrap();
+
+		let mut regions = Broker::regions_of(&1);
+		regions.sort_by_key(|info| info.parts);
+		let RegionRecord { end, paid, .. } = Regions::<Test>::get(one).unwrap();
+		assert_eq!(
+			regions,
+			vec![
+				RegionInfo { id: one, end, parts: 20, paid },
+				RegionInfo { id: two, end, parts: 60, paid },
+			]
+		);
+		assert_eq!(
+			Broker::regions_of(&2).into_iter().map(|info| info.id).collect::<Vec<_>>(),
+			vec![other]
+		);
+		assert!(Broker::regions_of(&3).is_empty());
+	});
+}
+
 #[test]
 fn cant_assign_unowned_region() {
 	TestExt::new().endow(1, 1000).execute_with(|| {
@@ -112,6 +112,20 @@ pub struct RegionRecord<AccountId, Balance> {
 }
 pub type RegionRecordOf<T> = RegionRecord<<T as SConfig>::AccountId, BalanceOf<T>>;
 
+/// A Region of an account, as reported by the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RegionInfo<Balance> {
+	/// The identity of the Region, including its first timeslice, core and interlace mask.
+	pub id: RegionId,
+	/// The timeslice at which the Region ends, exclusive.
+	pub end: Timeslice,
+	/// The number of regularity parts of the core scheduled by the Region, out of
+	/// [`CORE_MASK_BITS`].
+	pub parts: CoreMaskBitCount,
+	/// The amount paid to Polkadot for the Region, or `None` if renewal is not allowed.
+	pub paid: Option<Balance>,
+}
+
 /// An distinct item which can be scheduled on a Polkadot Core.
 #[derive(
 	Encode,
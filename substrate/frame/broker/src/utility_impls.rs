@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use super::*;
+use alloc::vec::Vec;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
@@ -55,6 +56,19 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The Regions owned by `who`.
+	pub fn regions_of(who: &T::AccountId) -> Vec<RegionInfo<BalanceOf<T>>> {
+		Regions::<T>::iter()
+			.filter(|(_, record)| record.owner.as_ref() == Some(who))
+			.map(|(id, record)| RegionInfo {
+				id,
+				end: record.end,
+				parts: id.mask.count_ones(),
+				paid: record.paid,
+			})
+			.collect()
+	}
+
 	pub fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account_truncating()
 	}
@@ -180,8 +180,13 @@ use sp_runtime::{
 
 #[cfg(feature = "try-runtime")]
 use ::{
+	alloc::{string::String, vec::Vec},
 	frame_support::{
-		traits::{TryDecodeEntireStorage, TryDecodeEntireStorageError, TryState},
+		traits::{
+			PalletsInfoAccess, StorageVersion, StorageVersionTransition, TryDecodeEntireStorage,
+			TryDecodeEntireStorageError, TryState, UpgradeReport,
+			STORAGE_VERSION_STORAGE_KEY_POSTFIX,
+		},
 		StorageNoopGuard,
 	},
 	frame_try_runtime::{TryStateSelect, UpgradeCheckSelect},
@@ -446,6 +451,13 @@ where
 			),
 		);
 
+		Self::try_runtime_upgrade_checks(checks)?;
+
+		Ok(before_all_weight.saturating_add(try_on_runtime_upgrade_weight))
+	}
+
+	/// The checks of the state executed after the migrations.
+	fn try_runtime_upgrade_checks(checks: UpgradeCheckSelect) -> Result<(), TryRuntimeError> {
 		// Nothing should modify the state after the migrations ran:
 		let _guard = StorageNoopGuard::default();
 
@@ -463,7 +475,7 @@ where
 			)?;
 		}
 
-		Ok(before_all_weight.saturating_add(try_on_runtime_upgrade_weight))
+		Ok(())
 	}
 
 	/// Logs the result of trying to decode the entire state.
@@ -499,6 +511,125 @@ where
 	}
 }
 
+#[cfg(feature = "try-runtime")]
+impl<
+		System: frame_system::Config + EnsureInherentsAreFirst<Block>,
+		Block: traits::Block<
+			Header = frame_system::pallet_prelude::HeaderFor<System>,
+			Hash = System::Hash,
+		>,
+		Context: Default,
+		UnsignedValidator,
+		AllPalletsWithSystem: OnRuntimeUpgrade
+			+ BeforeAllRuntimeMigrations
+			+ OnInitialize<BlockNumberFor<System>>
+			+ OnIdle<BlockNumberFor<System>>
+			+ OnFinalize<BlockNumberFor<System>>
+			+ OffchainWorker<BlockNumberFor<System>>
+			+ OnPoll<BlockNumberFor<System>>
+			+ TryState<BlockNumberFor<System>>
+			+ TryDecodeEntireStorage
+			+ PalletsInfoAccess,
+		COnRuntimeUpgrade: OnRuntimeUpgrade,
+	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+where
+	Block::Extrinsic: Checkable<Context> + Codec,
+	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
+	CallOf<Block::Extrinsic, Context>:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
+	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
+{
+	/// Execute all migrations of this runtime, and report each of them.
+	///
+	/// Same as [`Self::try_runtime_upgrade`], except that the `try_state` hooks, if selected by
+	/// `checks`, are also executed before the migrations.
+	///
+	/// With `idempotency_checks`, every migration is executed a second time once all of them ran
+	/// and were checked, and reported as idempotent if it left the state root unchanged. The state
+	/// is not reverted afterwards.
+	pub fn try_runtime_upgrade_with_report(
+		checks: UpgradeCheckSelect,
+		idempotency_checks: bool,
+	) -> Result<UpgradeReport, TryRuntimeError> {
+		let storage_versions = Self::on_chain_storage_versions();
+
+		if checks.try_state() {
+			let _guard = StorageNoopGuard::default();
+			AllPalletsWithSystem::try_state(
+				frame_system::Pallet::<System>::block_number(),
+				TryStateSelect::All,
+			)?;
+		}
+
+		let before_all_weight =
+			<AllPalletsWithSystem as BeforeAllRuntimeMigrations>::before_all_runtime_migrations();
+		let mut migrations = Vec::new();
+		let try_on_runtime_upgrade_weight = <(COnRuntimeUpgrade, AllPalletsWithSystem) as OnRuntimeUpgrade>::try_on_runtime_upgrade_with_report(
+			checks.pre_and_post(),
+			&mut migrations,
+		)?;
+
+		let version = <System::Version as frame_support::traits::Get<_>>::get();
+		frame_system::LastRuntimeUpgrade::<System>::put(
+			frame_system::LastRuntimeUpgradeInfo::from(version.clone()),
+		);
+
+		Self::try_runtime_upgrade_checks(checks)?;
+
+		let storage_versions = storage_versions
+			.into_iter()
+			.zip(Self::on_chain_storage_versions())
+			.filter(|((_, before), (_, after))| before != after)
+			.map(|((pallet, before), (_, after))| StorageVersionTransition {
+				pallet,
+				before,
+				after,
+			})
+			.collect();
+
+		if idempotency_checks {
+			let mut idempotent = Vec::new();
+			<(COnRuntimeUpgrade, AllPalletsWithSystem) as OnRuntimeUpgrade>::try_idempotency_check(
+				version.state_version(),
+				&mut idempotent,
+			);
+			for (migration, idempotent) in migrations.iter_mut().zip(idempotent) {
+				if !idempotent {
+					log::error!(
+						target: LOG_TARGET,
+						"Migration {} is not idempotent",
+						String::from_utf8_lossy(&migration.name),
+					);
+				}
+				migration.idempotent = Some(idempotent);
+			}
+		}
+
+		Ok(UpgradeReport {
+			weight: before_all_weight.saturating_add(try_on_runtime_upgrade_weight),
+			max_block_weight: <System::BlockWeights as frame_support::traits::Get<_>>::get()
+				.max_block,
+			migrations,
+			storage_versions,
+		})
+	}
+
+	/// The on-chain storage versions of all pallets, by name.
+	fn on_chain_storage_versions() -> Vec<(Vec<u8>, Option<StorageVersion>)> {
+		AllPalletsWithSystem::infos()
+			.into_iter()
+			.map(|info| {
+				let key = frame_support::storage::storage_prefix(
+					info.name.as_bytes(),
+					STORAGE_VERSION_STORAGE_KEY_POSTFIX,
+				);
+				(info.name.as_bytes().to_vec(), frame_support::storage::unhashed::get(&key))
+			})
+			.collect()
+	}
+}
+
 impl<
 		System: frame_system::Config + EnsureInherentsAreFirst<Block>,
 		Block: traits::Block<
@@ -1293,6 +1293,34 @@ fn try_execute_block_works() {
 	});
 }
 
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_upgrade_with_report_works() {
+	let weight = new_test_ext(1).execute_with(|| {
+		Executive::try_runtime_upgrade(frame_try_runtime::UpgradeCheckSelect::All).unwrap()
+	});
+
+	new_test_ext(1).execute_with(|| {
+		let report = Executive::try_runtime_upgrade_with_report(
+			frame_try_runtime::UpgradeCheckSelect::All,
+			false,
+		)
+		.unwrap();
+
+		assert_eq!(report.weight, weight);
+		assert_eq!(
+			report.max_block_weight,
+			<Runtime as frame_system::Config>::BlockWeights::get().max_block
+		);
+		// The custom migration is reported first, then every pallet.
+		assert_eq!(report.migrations.len(), 1 + AllPalletsWithSystem::infos().len());
+		assert!(report.migrations[0].name.ends_with(b"CustomOnRuntimeUpgrade"));
+		assert_eq!(report.migrations[0].weight, Weight::from_parts(100, 0));
+		assert!(report.migrations.iter().all(|migration| migration.idempotent.is_none()));
+		assert!(report.storage_versions.is_empty());
+	});
+}
+
 /// Same as `extrinsic_while_exts_forbidden_errors` but using the try-runtime function.
 #[test]
 #[cfg(feature = "try-runtime")]
@@ -74,6 +74,7 @@ impl Config for Test {
 	type MaxStale = MaxStale;
 	type ServiceWeight = ServiceWeight;
 	type IdleMaxServiceWeight = ();
+	type QueueServicePolicy = ();
 }
 
 /// The object that does the AHM message prioritization for us.
@@ -124,6 +124,12 @@
 //! to advance to the next *ready* queue and service it. This continues until there are no more
 //! queues on which it can make progress or not enough weight to check that.
 //!
+//! The [`Config::QueueServicePolicy`] can change this order: its prioritized queues are serviced
+//! first, before rotating the `ReadyRing`, and it can limit the share of the weight of a single
+//! call which a queue may use, so that a single flooded queue can not delay all the other ones.
+//! A message of a limited queue which needs more than the share of the queue is permanently
+//! overweight.
+//!
 //! # Scenario: Overweight execution
 //!
 //! A permanently over-weight message which was skipped by the message processing will never be
@@ -225,7 +231,7 @@ use sp_arithmetic::traits::{BaseArithmetic, Unsigned};
 use sp_core::{defer, H256};
 use sp_runtime::{
 	traits::{One, Zero},
-	SaturatedConversion, Saturating, TransactionOutcome,
+	Perbill, SaturatedConversion, Saturating, TransactionOutcome,
 };
 use sp_weights::WeightMeter;
 pub use weights::WeightInfo;
@@ -473,6 +479,44 @@ pub trait ForceSetHead<O> {
 	fn force_set_head(weight: &mut WeightMeter, origin: &O) -> Result<bool, ()>;
 }
 
+/// Decides how the weight available for servicing the queues is distributed between them.
+pub trait QueueServicePolicy<O> {
+	/// The queues to service before all the other ones, in order of priority.
+	fn prioritized() -> Vec<O>;
+
+	/// The maximal share of the weight of a single
+	/// [`frame_support::traits::ServiceQueues::service_queues`] call which the queue of `origin`
+	/// may use, or `None` if it is not limited.
+	fn max_share(origin: &O) -> Option<Perbill>;
+}
+
+impl<O> QueueServicePolicy<O> for () {
+	fn prioritized() -> Vec<O> {
+		Vec::new()
+	}
+
+	fn max_share(_: &O) -> Option<Perbill> {
+		None
+	}
+}
+
+/// Services the `Prioritized` queues first and without limit, and limits every other queue to a
+/// `MaxShare` of the service weight, which is then distributed fairly between them.
+pub struct PrioritizedFairShare<Prioritized, MaxShare>(
+	core::marker::PhantomData<(Prioritized, MaxShare)>,
+);
+impl<O: PartialEq, Prioritized: Get<Vec<O>>, MaxShare: Get<Perbill>> QueueServicePolicy<O>
+	for PrioritizedFairShare<Prioritized, MaxShare>
+{
+	fn prioritized() -> Vec<O> {
+		Prioritized::get()
+	}
+
+	fn max_share(origin: &O) -> Option<Perbill> {
+		(!Prioritized::get().contains(origin)).then(MaxShare::get)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -555,6 +599,11 @@ pub mod pallet {
 		/// If `None`, it will not call `ServiceQueues::service_queues` in `on_idle`.
 		#[pallet::constant]
 		type IdleMaxServiceWeight: Get<Option<Weight>>;
+
+		/// The priorities and weight budgets of the queues when servicing them.
+		///
+		/// Use `()` to service the queues in turn, without limiting their weight.
+		type QueueServicePolicy: QueueServicePolicy<MessageOriginOf<Self>>;
 	}
 
 	#[pallet::event]
@@ -1245,6 +1294,46 @@ impl<T: Config> Pallet<T> {
 		(total_processed > 0, next_ready)
 	}
 
+	/// Like [`Self::service_queue`], but within the budget of the queue given by
+	/// [`Config::QueueServicePolicy`], out of the `weight_limit` of the whole servicing.
+	///
+	/// `used` records the weight used by the limited queues so far.
+	fn service_queue_within_budget(
+		origin: MessageOriginOf<T>,
+		weight: &mut WeightMeter,
+		weight_limit: Weight,
+		overweight_limit: Weight,
+		used: &mut Vec<(MessageOriginOf<T>, Weight)>,
+	) -> (bool, Option<MessageOriginOf<T>>) {
+		let Some(share) = T::QueueServicePolicy::max_share(&origin) else {
+			return Self::service_queue(origin, weight, overweight_limit)
+		};
+		let budget = share * weight_limit;
+		let index = match used.iter().position(|(o, _)| o == &origin) {
+			Some(index) => index,
+			None => {
+				used.push((origin.clone(), Weight::zero()));
+				used.len() - 1
+			},
+		};
+		let remaining_budget = budget.saturating_sub(used[index].1);
+		let limited_by_budget = !weight.remaining().all_lte(remaining_budget);
+
+		let mut queue_weight = WeightMeter::with_limit(remaining_budget.min(weight.remaining()));
+		let (progressed, next) =
+			Self::service_queue(origin.clone(), &mut queue_weight, overweight_limit.min(budget));
+		weight.consume(queue_weight.consumed());
+		used[index].1.saturating_accrue(queue_weight.consumed());
+
+		// A queue out of budget must not stop the servicing of the other ones.
+		let next = next.or_else(|| {
+			limited_by_budget
+				.then(|| BookStateFor::<T>::get(&origin).ready_neighbours.map(|n| n.next))
+				.flatten()
+		});
+		(progressed, next)
+	}
+
 	/// Service as many messages of a page as possible.
 	///
 	/// Returns how many messages were processed and the page's status.
@@ -1620,6 +1709,24 @@ impl<T: Config> Pallet<T> {
 		});
 
 		match with_service_mutex(|| {
+			let mut used = Vec::new();
+			for origin in T::QueueServicePolicy::prioritized() {
+				// Reading the book state is covered by the base weight of servicing a queue.
+				if weight.try_consume(T::WeightInfo::service_queue_base()).is_err() {
+					break
+				}
+				if BookStateFor::<T>::get(&origin).ready_neighbours.is_none() {
+					continue
+				}
+				Self::service_queue_within_budget(
+					origin,
+					&mut weight,
+					weight_limit,
+					overweight_limit,
+					&mut used,
+				);
+			}
+
 			let mut next = match Self::bump_service_head(&mut weight) {
 				Some(h) => h,
 				None => return weight.consumed(),
@@ -1630,8 +1737,13 @@ impl<T: Config> Pallet<T> {
 			let mut last_no_progress = None;
 
 			loop {
-				let (progressed, n) =
-					Self::service_queue(next.clone(), &mut weight, overweight_limit);
+				let (progressed, n) = Self::service_queue_within_budget(
+					next.clone(),
+					&mut weight,
+					weight_limit,
+					overweight_limit,
+					&mut used,
+				);
 				next = match n {
 					Some(n) =>
 						if !progressed {
@@ -58,6 +58,7 @@ impl Config for Test {
 	type MaxStale = MaxStale;
 	type ServiceWeight = ServiceWeight;
 	type IdleMaxServiceWeight = ServiceWeight;
+	type QueueServicePolicy = MockedQueueServicePolicy;
 }
 
 /// Mocked `WeightInfo` impl with allows to set the weight per call.
@@ -303,6 +304,27 @@ impl QueuePausedQuery<MessageOrigin> for MockedQueuePauser {
 	}
 }
 
+parameter_types! {
+	pub static PrioritizedQueues: Vec<MessageOrigin> = vec![];
+	pub static MaxQueueShare: Option<Perbill> = None;
+}
+
+/// Prioritizes the [`PrioritizedQueues`] and limits the other queues to the [`MaxQueueShare`].
+pub struct MockedQueueServicePolicy;
+impl QueueServicePolicy<MessageOrigin> for MockedQueueServicePolicy {
+	fn prioritized() -> Vec<MessageOrigin> {
+		PrioritizedQueues::get()
+	}
+
+	fn max_share(origin: &MessageOrigin) -> Option<Perbill> {
+		if PrioritizedQueues::get().contains(origin) {
+			None
+		} else {
+			MaxQueueShare::get()
+		}
+	}
+}
+
 /// Create new test externalities.
 ///
 /// Is generic since it is used by the unit test, integration tests and benchmarks.
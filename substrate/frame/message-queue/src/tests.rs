@@ -166,6 +166,37 @@ fn service_queues_basic_works() {
 	});
 }
 
+#[test]
+fn service_queues_with_policy_works() {
+	use MessageOrigin::*;
+	build_and_execute::<Test>(|| {
+		MaxQueueShare::set(Some(Perbill::from_percent(50)));
+		MessageQueue::enqueue_messages(vec![msg("a"), msg("b"), msg("c")].into_iter(), Here);
+		MessageQueue::enqueue_messages(vec![msg("x"), msg("y"), msg("z")].into_iter(), There);
+		MessageQueue::enqueue_messages(vec![msg("1"), msg("2")].into_iter(), Everywhere(0));
+
+		// Every queue gets at most half of the weight.
+		assert_eq!(MessageQueue::service_queues(4.into_weight()), 4.into_weight());
+		assert_eq!(
+			MessagesProcessed::take(),
+			vec![(vmsg("a"), Here), (vmsg("b"), Here), (vmsg("x"), There), (vmsg("y"), There)]
+		);
+
+		// The prioritized queue is serviced first and without limit.
+		PrioritizedQueues::set(vec![Everywhere(0)]);
+		assert_eq!(MessageQueue::service_queues(3.into_weight()), 3.into_weight());
+		let processed = MessagesProcessed::take();
+		assert_eq!(processed.len(), 3);
+		assert_eq!(processed[..2], [(vmsg("1"), Everywhere(0)), (vmsg("2"), Everywhere(0))]);
+
+		// A message needing more than the share of its queue is permanently overweight.
+		MessageQueue::enqueue_message(msg("weight=3"), There);
+		MessageQueue::service_queues(4.into_weight());
+		assert_eq!(num_overweight_enqueued_events(), 1);
+		MessageQueue::do_try_state().unwrap();
+	});
+}
+
 #[test]
 fn service_queues_failing_messages_works() {
 	use MessageOrigin::*;
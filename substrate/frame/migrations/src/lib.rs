@@ -99,6 +99,11 @@
 //! migration via [`SteppedMigration::max_steps`]. The pallet **ONLY** guarantees progress if this
 //! is set to sensible limits (Goal 7).
 //!
+//! Migrations of all the entries of a storage map don't need to deal with cursors and weight
+//! metering themselves, they can implement [`SteppedMapMigration`] and be wrapped in a
+//! [`SteppedMapMigrationAdapter`]. Their number of migrated entries is reported by the
+//! `MigrationProgressed` event after every step.
+//!
 //! ### Scenario: Governance cleanup
 //!
 //! Every now and then, governance can make use of the [`clear_historic`][Pallet::clear_historic]
@@ -485,6 +490,16 @@ pub mod pallet {
 			/// Should be passed to `clear_historic` in a successive call.
 			next_cursor: Option<Vec<u8>>,
 		},
+		/// A migration reported its progress, after a `MigrationAdvanced`.
+		///
+		/// Only emitted by the migrations that keep track of their progress, see
+		/// [`SteppedMigration::progress`].
+		MigrationProgressed {
+			/// The index of the migration within the [`Config::Migrations`] list.
+			index: u32,
+			/// The number of items migrated so far.
+			migrated: u32,
+		},
 	}
 
 	#[pallet::error]
@@ -776,13 +791,22 @@ impl<T: Config> Pallet<T> {
 		let took = System::<T>::block_number().saturating_sub(cursor.started_at);
 		match next_cursor {
 			Ok(Some(next_cursor)) => {
-				let Ok(bound_next_cursor) = next_cursor.try_into() else {
+				let Ok(bound_next_cursor): Result<RawCursorOf<T>, _> = next_cursor.try_into()
+				else {
 					defensive!("The integrity check ensures that all cursors' MEL bound fits into CursorMaxLen; qed");
 					Self::upgrade_failed(Some(cursor.index));
 					return None
 				};
 
 				Self::deposit_event(Event::MigrationAdvanced { index: cursor.index, took });
+				if let Some(migrated) =
+					T::Migrations::nth_progress(cursor.index, bound_next_cursor.as_slice())
+				{
+					Self::deposit_event(Event::MigrationProgressed {
+						index: cursor.index,
+						migrated,
+					});
+				}
 				cursor.inner_cursor = Some(bound_next_cursor);
 
 				if max_steps.is_some_and(|max| took > max.into()) {
@@ -359,5 +359,73 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// `s`: Signatories, need at least 2 people
+	/// `z`: Call Length
+	#[benchmark]
+	fn store_call(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, { T::MaxCallLen::get() }>,
+	) -> Result<(), BenchmarkError> {
+		if T::MaxCallLen::get() == 0 {
+			return Err(BenchmarkError::Weightless)
+		}
+		// Leave room for the encoding of the `remark` call around its payload.
+		let (mut signatories, call) = setup_multi::<T>(s, z.saturating_sub(8))?;
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s.try_into().unwrap());
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let call_hash = call.using_encoded(blake2_256);
+		// Create the multi
+		Multisig::<T>::approve_as_multi(
+			RawOrigin::Signed(caller.clone()).into(),
+			s as u16,
+			signatories.clone(),
+			None,
+			call_hash,
+			Weight::zero(),
+		)?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), s as u16, signatories, call);
+
+		assert!(StoredCalls::<T>::contains_key(multi_account_id, call_hash));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_stored_call() -> Result<(), BenchmarkError> {
+		let max_call_len = T::MaxCallLen::get();
+		if max_call_len == 0 {
+			return Err(BenchmarkError::Weightless)
+		}
+		let s = T::MaxSignatories::get();
+		let (mut signatories, call) = setup_multi::<T>(s, max_call_len.saturating_sub(8))?;
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s.try_into().unwrap());
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let call_hash = call.using_encoded(blake2_256);
+		let o: <T as frame_system::Config>::RuntimeOrigin =
+			RawOrigin::Signed(caller.clone()).into();
+		Multisig::<T>::approve_as_multi(
+			o.clone(),
+			s as u16,
+			signatories.clone(),
+			None,
+			call_hash,
+			Weight::zero(),
+		)?;
+		Multisig::<T>::store_call(o, s as u16, signatories, call)?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), multi_account_id.clone(), call_hash);
+
+		assert!(!StoredCalls::<T>::contains_key(multi_account_id, call_hash));
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Multisig, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -45,6 +45,7 @@
 
 mod benchmarking;
 pub mod migrations;
+pub mod runtime_api;
 mod tests;
 pub mod weights;
 
@@ -120,6 +121,39 @@ where
 	pub approvals: BoundedVec<AccountId, MaxApprovals>,
 }
 
+/// The call data of an open multisig operation, stored on chain so that the signatories do not
+/// have to pass it around off-chain.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxCallLen))]
+pub struct StoredCall<BlockNumber, Balance, AccountId, MaxCallLen>
+where
+	MaxCallLen: Get<u32>,
+{
+	/// The encoded call.
+	pub data: BoundedVec<u8, MaxCallLen>,
+	/// The account who stored the call.
+	pub depositor: AccountId,
+	/// The amount held in reserve of the `depositor`, to be returned once the call is removed.
+	pub deposit: Balance,
+	/// The block from which on the call is no longer used and may be removed by anyone.
+	pub expires_at: BlockNumber,
+}
+
+/// An open multisig operation, as returned by the [`runtime_api::MultisigApi`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct PendingOperation<BlockNumber, AccountId> {
+	/// The hash of the call to be executed.
+	pub call_hash: [u8; 32],
+	/// The extrinsic when the multisig operation was opened.
+	pub when: Timepoint<BlockNumber>,
+	/// The account who opened it.
+	pub depositor: AccountId,
+	/// The approvals achieved so far, including the depositor.
+	pub approvals: Vec<AccountId>,
+	/// The encoded call, if it is stored on chain and has not expired.
+	pub call: Option<Vec<u8>>,
+}
+
 type CallHash = [u8; 32];
 
 enum CallOrHash<T: Config> {
@@ -165,6 +199,24 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSignatories: Get<u32>;
 
+		/// The maximum length of an encoded call that may be stored on chain with `store_call`.
+		///
+		/// Set to zero to disable call storage.
+		#[pallet::constant]
+		type MaxCallLen: Get<u32>;
+
+		/// The amount of currency needed per byte of a call stored on chain, on top of
+		/// `DepositBase`.
+		#[pallet::constant]
+		type CallDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The number of blocks after which a stored call expires.
+		///
+		/// Expired calls are no longer used to execute the operation and may be removed by
+		/// anyone.
+		#[pallet::constant]
+		type CallExpiry: Get<BlockNumberFor<Self>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: weights::WeightInfo;
 
@@ -211,6 +263,17 @@ pub mod pallet {
 		Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
 	>;
 
+	/// The calls stored on chain for open multisig operations.
+	#[pallet::storage]
+	pub type StoredCalls<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Identity,
+		[u8; 32],
+		StoredCall<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxCallLen>,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Threshold must be 2 or greater.
@@ -242,6 +305,14 @@ pub mod pallet {
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// Calls cannot be stored on chain.
+		CallStorageDisabled,
+		/// The encoded call is longer than `MaxCallLen`.
+		CallTooLarge,
+		/// No call is stored for the multisig operation.
+		NoStoredCall,
+		/// The stored call can only be removed by its depositor until it expires.
+		StoredCallNotExpired,
 	}
 
 	#[pallet::event]
@@ -278,6 +349,15 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// The call of a multisig operation has been stored on chain.
+		CallStored {
+			depositor: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// A stored call has been removed.
+		StoredCallRemoved { multisig: T::AccountId, call_hash: CallHash },
 	}
 
 	#[pallet::hooks]
@@ -431,7 +511,8 @@ pub mod pallet {
 		/// transaction index) of the first approval transaction.
 		/// - `call_hash`: The hash of the call to be executed.
 		///
-		/// NOTE: If this is the final approval, you will want to use `as_multi` instead.
+		/// NOTE: If this is the final approval, you will want to use `as_multi` instead, unless
+		/// the call was stored on chain with `store_call`, in which case it is executed here.
 		///
 		/// ## Complexity
 		/// - `O(S)`.
@@ -450,6 +531,7 @@ pub mod pallet {
 
 			T::WeightInfo::approve_as_multi_create(s)
 				.max(T::WeightInfo::approve_as_multi_approve(s))
+				.max(T::WeightInfo::as_multi_complete(s, T::MaxCallLen::get()))
 				.saturating_add(*max_weight)
 		})]
 		pub fn approve_as_multi(
@@ -493,7 +575,10 @@ pub mod pallet {
 		/// - I/O: 1 read `O(S)`, one remove.
 		/// - Storage: removes one item.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::cancel_as_multi(other_signatories.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::cancel_as_multi(other_signatories.len() as u32)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
 		pub fn cancel_as_multi(
 			origin: OriginFor<T>,
 			threshold: u16,
@@ -517,6 +602,7 @@ pub mod pallet {
 			let err_amount = T::Currency::unreserve(&m.depositor, m.deposit);
 			debug_assert!(err_amount.is_zero());
 			<Multisigs<T>>::remove(&id, &call_hash);
+			Self::clear_stored_call(&id, call_hash);
 
 			Self::deposit_event(Event::MultisigCancelled {
 				cancelling: who,
@@ -604,6 +690,100 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Store the call of an open multisig operation on chain, so that the remaining
+		/// signatories can approve and execute it with `approve_as_multi`, knowing only its hash.
+		///
+		/// The dispatch origin for this call must be _Signed_ and one of the signatories.
+		///
+		/// Payment: `DepositBase` plus `CallDepositPerByte` for each byte of the encoded call is
+		/// reserved. It is returned once the operation ends or the stored call is removed.
+		///
+		/// The call expires after `CallExpiry` blocks. An expired call may be replaced by storing
+		/// it again.
+		///
+		/// - `threshold`: The total number of approvals needed for this multisig.
+		/// - `other_signatories`: The accounts (other than the sender) who are part of the
+		///   multisig.
+		/// - `call`: The call of the operation, which must already be open.
+		///
+		/// Emits `CallStored` if successful.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::store_call(
+			other_signatories.len() as u32,
+			call.using_encoded(|d| d.len()) as u32,
+		))]
+		pub fn store_call(
+			origin: OriginFor<T>,
+			threshold: u16,
+			other_signatories: Vec<T::AccountId>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::MaxCallLen::get() > 0, Error::<T>::CallStorageDisabled);
+			ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
+			let max_sigs = T::MaxSignatories::get() as usize;
+			ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
+			ensure!(other_signatories.len() < max_sigs, Error::<T>::TooManySignatories);
+			let signatories = Self::ensure_sorted_and_insert(other_signatories, who.clone())?;
+			let id = Self::multi_account_id(&signatories, threshold);
+
+			let (call_hash, data) = call.using_encoded(|d| (blake2_256(d), d.to_vec()));
+			ensure!(<Multisigs<T>>::contains_key(&id, call_hash), Error::<T>::NotFound);
+			if let Some(stored) = <StoredCalls<T>>::get(&id, call_hash) {
+				ensure!(Self::is_expired(&stored.expires_at), Error::<T>::AlreadyStored);
+				T::Currency::unreserve(&stored.depositor, stored.deposit);
+			}
+
+			let deposit = Self::call_deposit(data.len() as u32);
+			let data: BoundedVec<_, _> = data.try_into().map_err(|_| Error::<T>::CallTooLarge)?;
+			T::Currency::reserve(&who, deposit)?;
+
+			let expires_at =
+				T::BlockNumberProvider::current_block_number().saturating_add(T::CallExpiry::get());
+			<StoredCalls<T>>::insert(
+				&id,
+				call_hash,
+				StoredCall { data, depositor: who.clone(), deposit, expires_at },
+			);
+			Self::deposit_event(Event::CallStored {
+				depositor: who,
+				multisig: id,
+				call_hash,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Remove a call stored with `store_call` and return its deposit to the depositor.
+		///
+		/// The dispatch origin for this call must be _Signed_. The depositor may remove the call
+		/// at any time, anyone else only once it has expired.
+		///
+		/// The transaction fee is waived if successful.
+		///
+		/// - `multisig`: The multisig account the call was stored for.
+		/// - `call_hash`: The hash of the stored call.
+		///
+		/// Emits `StoredCallRemoved` if successful.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::remove_stored_call())]
+		pub fn remove_stored_call(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stored =
+				<StoredCalls<T>>::get(&multisig, call_hash).ok_or(Error::<T>::NoStoredCall)?;
+			ensure!(
+				stored.depositor == who || Self::is_expired(&stored.expires_at),
+				Error::<T>::StoredCallNotExpired
+			);
+
+			Self::clear_stored_call(&multisig, call_hash);
+			Ok(Pays::No.into())
+		}
 	}
 }
 
@@ -641,7 +821,13 @@ impl<T: Config> Pallet<T> {
 				let (call_hash, call_len) = call.using_encoded(|d| (blake2_256(d), d.len()));
 				(call_hash, call_len, Some(call))
 			},
-			CallOrHash::Hash(h) => (h, 0, None),
+			CallOrHash::Hash(h) => match Self::stored_call(&id, &h)
+				.filter(|call| call.get_dispatch_info().call_weight.all_lte(max_weight))
+			{
+				// The call is known on chain; use it in case this is the final approval.
+				Some(call) => (h, call.encoded_size(), Some(call)),
+				None => (h, 0, None),
+			},
 		};
 
 		// Branch on whether the operation has already started or not.
@@ -671,6 +857,7 @@ impl<T: Config> Pallet<T> {
 				// attack.
 				<Multisigs<T>>::remove(&id, call_hash);
 				T::Currency::unreserve(&m.depositor, m.deposit);
+				Self::clear_stored_call(&id, call_hash);
 
 				let result = call.dispatch(RawOrigin::Signed(id.clone()).into());
 				Self::deposit_event(Event::MultisigExecuted {
@@ -747,6 +934,49 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The open multisig operations of the `multisig` account.
+	pub fn pending_operations(
+		multisig: &T::AccountId,
+	) -> Vec<PendingOperation<BlockNumberFor<T>, T::AccountId>> {
+		<Multisigs<T>>::iter_prefix(multisig)
+			.map(|(call_hash, m)| PendingOperation {
+				call_hash,
+				when: m.when,
+				depositor: m.depositor,
+				approvals: m.approvals.into_inner(),
+				call: <StoredCalls<T>>::get(multisig, call_hash)
+					.filter(|stored| !Self::is_expired(&stored.expires_at))
+					.map(|stored| stored.data.into_inner()),
+			})
+			.collect()
+	}
+
+	/// The decoded call stored for an operation of `multisig`, unless it has expired.
+	fn stored_call(
+		multisig: &T::AccountId,
+		call_hash: &CallHash,
+	) -> Option<<T as Config>::RuntimeCall> {
+		let stored = <StoredCalls<T>>::get(multisig, call_hash)?;
+		if Self::is_expired(&stored.expires_at) {
+			return None
+		}
+		Decode::decode(&mut &stored.data[..]).ok()
+	}
+
+	/// Remove the call stored for an operation of `multisig`, if any, and return its deposit.
+	fn clear_stored_call(multisig: &T::AccountId, call_hash: CallHash) {
+		if let Some(stored) = <StoredCalls<T>>::take(multisig, call_hash) {
+			let err_amount = T::Currency::unreserve(&stored.depositor, stored.deposit);
+			debug_assert!(err_amount.is_zero());
+			Self::deposit_event(Event::StoredCallRemoved { multisig: multisig.clone(), call_hash });
+		}
+	}
+
+	/// Whether a stored call expiring at `expires_at` has expired.
+	fn is_expired(expires_at: &BlockNumberFor<T>) -> bool {
+		T::BlockNumberProvider::current_block_number() >= *expires_at
+	}
+
 	/// The current `Timepoint`.
 	pub fn timepoint() -> Timepoint<BlockNumberFor<T>> {
 		Timepoint {
@@ -783,6 +1013,14 @@ impl<T: Config> Pallet<T> {
 	pub fn deposit(threshold: u16) -> BalanceOf<T> {
 		T::DepositBase::get() + T::DepositFactor::get() * threshold.into()
 	}
+
+	/// Calculate the deposit for storing a call of `len` bytes.
+	///
+	/// The deposit is calculated as `DepositBase + CallDepositPerByte * len`.
+	pub fn call_deposit(len: u32) -> BalanceOf<T> {
+		T::DepositBase::get()
+			.saturating_add(T::CallDepositPerByte::get().saturating_mul(len.into()))
+	}
 }
 
 /// Return the weight of a dispatch call result as an `Option`.
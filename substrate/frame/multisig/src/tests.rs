@@ -62,6 +62,8 @@ impl Contains<RuntimeCall> for TestBaseCallFilter {
 parameter_types! {
 	pub static MultisigDepositBase: u64 = 1;
 	pub static MultisigDepositFactor: u64 = 1;
+	pub static MultisigMaxCallLen: u32 = 128;
+	pub static MultisigCallDepositPerByte: u64 = 0;
 }
 
 impl Config for Test {
@@ -71,6 +73,9 @@ impl Config for Test {
 	type DepositBase = MultisigDepositBase;
 	type DepositFactor = MultisigDepositFactor;
 	type MaxSignatories = ConstU32<3>;
+	type MaxCallLen = MultisigMaxCallLen;
+	type CallDepositPerByte = MultisigCallDepositPerByte;
+	type CallExpiry = ConstU32<10>;
 	type WeightInfo = ();
 	type BlockNumberProvider = frame_system::Pallet<Test>;
 }
@@ -1005,3 +1010,178 @@ fn poke_deposit_handles_insufficient_balance() {
 		);
 	});
 }
+
+#[test]
+fn stored_call_can_be_executed_by_hash() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().call_weight;
+		let hash = blake2_256(&call.encode());
+
+		// There is no operation to store the call for yet.
+		assert_noop!(
+			Multisig::store_call(RuntimeOrigin::signed(2), 2, vec![1, 3], call.clone()),
+			Error::<Test>::NotFound,
+		);
+
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero()
+		));
+		let timepoint = now();
+
+		assert_ok!(Multisig::store_call(RuntimeOrigin::signed(2), 2, vec![1, 3], call.clone()));
+		System::assert_last_event(
+			Event::CallStored { depositor: 2, multisig: multi, call_hash: hash, expires_at: 11 }
+				.into(),
+		);
+		assert_eq!(Balances::reserved_balance(2), 1);
+		assert_noop!(
+			Multisig::store_call(RuntimeOrigin::signed(3), 2, vec![1, 2], call.clone()),
+			Error::<Test>::AlreadyStored,
+		);
+
+		assert_eq!(
+			Multisig::pending_operations(&multi),
+			vec![PendingOperation {
+				call_hash: hash,
+				when: timepoint,
+				depositor: 1,
+				approvals: vec![1],
+				call: Some(call.encode()),
+			}]
+		);
+
+		// The final approval only needs the hash.
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(3),
+			2,
+			vec![1, 2],
+			Some(timepoint),
+			hash,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 15);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(StoredCalls::<Test>::get(multi, hash).is_none());
+		assert!(Multisig::pending_operations(&multi).is_empty());
+	});
+}
+
+#[test]
+fn stored_call_expires_and_can_be_removed() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().call_weight;
+		let hash = blake2_256(&call.encode());
+
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero()
+		));
+		let timepoint = now();
+		assert_ok!(Multisig::store_call(RuntimeOrigin::signed(1), 2, vec![2, 3], call.clone()));
+		assert_eq!(Balances::reserved_balance(1), 4);
+
+		// Only the depositor may remove the call before it expires.
+		assert_noop!(
+			Multisig::remove_stored_call(RuntimeOrigin::signed(2), multi, hash),
+			Error::<Test>::StoredCallNotExpired,
+		);
+
+		System::set_block_number(11);
+		assert_eq!(Multisig::pending_operations(&multi)[0].call, None);
+
+		// An expired call is not executed; the approval is merely recorded.
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(timepoint),
+			hash,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 0);
+
+		// Anyone may clean it up, free of charge.
+		let result = Multisig::remove_stored_call(RuntimeOrigin::signed(3), multi, hash);
+		assert_eq!(result.unwrap().pays_fee, Pays::No);
+		System::assert_last_event(
+			Event::StoredCallRemoved { multisig: multi, call_hash: hash }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_noop!(
+			Multisig::remove_stored_call(RuntimeOrigin::signed(3), multi, hash),
+			Error::<Test>::NoStoredCall,
+		);
+	});
+}
+
+#[test]
+fn store_call_checks_and_cancel_cleans_up() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let call = call_transfer(6, 15);
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero()
+		));
+		let timepoint = now();
+
+		let large_call =
+			Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![0; 200] }));
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			blake2_256(&large_call.encode()),
+			Weight::zero()
+		));
+		assert_noop!(
+			Multisig::store_call(RuntimeOrigin::signed(1), 2, vec![2, 3], large_call),
+			Error::<Test>::CallTooLarge,
+		);
+
+		MultisigMaxCallLen::set(0);
+		assert_noop!(
+			Multisig::store_call(RuntimeOrigin::signed(2), 2, vec![1, 3], call.clone()),
+			Error::<Test>::CallStorageDisabled,
+		);
+		MultisigMaxCallLen::set(128);
+
+		assert_ok!(Multisig::store_call(RuntimeOrigin::signed(2), 2, vec![1, 3], call));
+		assert_eq!(Balances::reserved_balance(2), 1);
+
+		// Cancelling the operation also returns the deposit of the stored call.
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			timepoint,
+			hash
+		));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(StoredCalls::<Test>::get(multi, hash).is_none());
+	});
+}
@@ -79,6 +79,8 @@ pub trait WeightInfo {
 	fn approve_as_multi_approve(s: u32, ) -> Weight;
 	fn cancel_as_multi(s: u32, ) -> Weight;
 	fn poke_deposit(s: u32, ) -> Weight;
+	fn store_call(s: u32, z: u32, ) -> Weight;
+	fn remove_stored_call() -> Weight;
 }
 
 /// Weights for `pallet_multisig` using the Substrate node and recommended hardware.
@@ -212,6 +214,24 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Not benchmarked yet. Bounded by `as_multi_create`, which checks the signatories and
+	/// hashes the call the same way, plus the access to the stored call.
+	/// Storage: `Multisig::StoredCalls` (r:1 w:1)
+	/// Proof: `Multisig::StoredCalls` (`max_values`: None, `max_size`: Some(16512), added: 18987, mode: `MaxEncodedLen`)
+	fn store_call(s: u32, z: u32, ) -> Weight {
+		Self::as_multi_create(s, z)
+			.saturating_add(Weight::from_parts(0, 18987))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Not benchmarked yet. Bounded by `cancel_as_multi`, which removes an entry and returns
+	/// its deposit the same way, plus the proof of the stored call.
+	/// Storage: `Multisig::StoredCalls` (r:1 w:1)
+	/// Proof: `Multisig::StoredCalls` (`max_values`: None, `max_size`: Some(16512), added: 18987, mode: `MaxEncodedLen`)
+	fn remove_stored_call() -> Weight {
+		Self::cancel_as_multi(2)
+			.saturating_add(Weight::from_parts(0, 18987))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -344,4 +364,22 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Not benchmarked yet. Bounded by `as_multi_create`, which checks the signatories and
+	/// hashes the call the same way, plus the access to the stored call.
+	/// Storage: `Multisig::StoredCalls` (r:1 w:1)
+	/// Proof: `Multisig::StoredCalls` (`max_values`: None, `max_size`: Some(16512), added: 18987, mode: `MaxEncodedLen`)
+	fn store_call(s: u32, z: u32, ) -> Weight {
+		Self::as_multi_create(s, z)
+			.saturating_add(Weight::from_parts(0, 18987))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Not benchmarked yet. Bounded by `cancel_as_multi`, which removes an entry and returns
+	/// its deposit the same way, plus the proof of the stored call.
+	/// Storage: `Multisig::StoredCalls` (r:1 w:1)
+	/// Proof: `Multisig::StoredCalls` (`max_values`: None, `max_size`: Some(16512), added: 18987, mode: `MaxEncodedLen`)
+	fn remove_stored_call() -> Weight {
+		Self::cancel_as_multi(2)
+			.saturating_add(Weight::from_parts(0, 18987))
+	}
 }
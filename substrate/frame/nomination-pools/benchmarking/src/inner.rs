@@ -34,7 +34,8 @@ use pallet_nomination_pools::{
 	BalanceOf, BondExtra, BondedPoolInner, BondedPools, ClaimPermission, ClaimPermissions,
 	Commission, CommissionChangeRate, CommissionClaimPermission, ConfigOp, GlobalMaxCommission,
 	MaxPoolMembers, MaxPoolMembersPerPool, MaxPools, Metadata, MinCreateBond, MinJoinBond,
-	Pallet as Pools, PoolId, PoolMembers, PoolRoles, PoolState, RewardPools, SubPoolsStorage,
+	Pallet as Pools, PayoutPreference, PayoutPreferences, PoolId, PoolMembers, PoolRoles,
+	PoolState, RewardPools, SubPoolsStorage,
 };
 use pallet_staking::MaxNominationsOf;
 use sp_runtime::{
@@ -1172,5 +1173,47 @@ mod benchmarks {
 		assert_eq!(PoolMembers::<T>::get(&depositor).unwrap().total_balance(), deposit_amount);
 	}
 
+	#[benchmark]
+	fn set_payout_preference() {
+		// Create a pool
+		let min_create_bond = Pools::<T>::depositor_min_bond();
+		let (depositor, _pool_account) = create_pool_account::<T>(0, min_create_bond, None);
+
+		#[extrinsic_call]
+		_(RuntimeOrigin::Signed(depositor.clone()), Some(PayoutPreference::Compound));
+
+		assert_eq!(PayoutPreferences::<T>::get(depositor), Some(PayoutPreference::Compound));
+	}
+
+	#[benchmark]
+	fn process_payout_preference() {
+		let claimer: T::AccountId = account("claimer", USER_SEED + 4, 0);
+
+		// compounding is the heaviest preference, as like `bond_extra_other` it moves the pool in
+		// the voter list.
+		let origin_weight = Pools::<T>::depositor_min_bond() * 2u32.into();
+		let scenario = ListScenario::<T>::new(origin_weight, true).unwrap();
+		let extra = (scenario.dest_weight - origin_weight).max(CurrencyOf::<T>::minimum_balance());
+
+		assert_ok!(Pools::<T>::set_payout_preference(
+			RuntimeOrigin::Signed(scenario.creator1.clone()).into(),
+			Some(PayoutPreference::Compound),
+		));
+
+		// transfer exactly `extra` to the depositor of the src pool (1),
+		let reward_account1 = Pools::<T>::generate_reward_account(1);
+		assert!(extra >= CurrencyOf::<T>::minimum_balance());
+		let _ = CurrencyOf::<T>::mint_into(&reward_account1, extra);
+
+		#[extrinsic_call]
+		_(RuntimeOrigin::Signed(claimer), T::Lookup::unlookup(scenario.creator1.clone()));
+
+		// commission of 50% deducted here.
+		assert!(
+			T::StakeAdapter::active_stake(Pool::from(scenario.origin1)) >=
+				scenario.dest_weight / 2u32.into()
+		);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
 }
@@ -70,6 +70,12 @@
 //! other members to permissionlessly bond or withdraw their rewards by calling
 //! [`Call::bond_extra_other`] or [`Call::claim_payout_other`] respectively.
 //!
+//! Instead of claiming rewards manually, a pool member can set a [`PayoutPreference`] with
+//! [`Call::set_payout_preference`], to have their pending rewards either bonded back into the pool
+//! or paid to an account of their choice automatically. The preferences are applied by the pallet
+//! in [`Hooks::on_idle`], going through a bounded number of members per block, and can also be
+//! applied by any account with [`Call::process_payout_preference`].
+//!
 //! For design docs see the [reward pool](#reward-pool) section.
 //!
 //! ### Leave
@@ -509,6 +515,20 @@ impl ClaimPermission {
 	}
 }
 
+/// What to do with the pending rewards of a pool member automatically.
+///
+/// Members without a preference only get their rewards when claiming them, see
+/// [`ClaimPermission`].
+#[derive(
+	Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo,
+)]
+pub enum PayoutPreference<AccountId> {
+	/// Bond the pending rewards back into the pool.
+	Compound,
+	/// Pay the pending rewards out to the given account.
+	ClaimTo(AccountId),
+}
+
 /// A member in a pool.
 #[derive(
 	Encode,
@@ -1823,6 +1843,18 @@ pub mod pallet {
 	pub type ClaimPermissions<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, ClaimPermission, ValueQuery>;
 
+	/// Map from a pool member account to their opted payout preference.
+	#[pallet::storage]
+	pub type PayoutPreferences<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, PayoutPreference<T::AccountId>, OptionQuery>;
+
+	/// The last member whose payout preference was applied in `on_idle`.
+	///
+	/// The next block resumes from the member after it, `None` means starting from the first
+	/// member of [`PayoutPreferences`].
+	#[pallet::storage]
+	pub type AutoPayoutCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub min_join_bond: BalanceOf<T>,
@@ -1950,6 +1982,11 @@ pub mod pallet {
 		MinBalanceExcessAdjusted { pool_id: PoolId, amount: BalanceOf<T> },
 		/// A pool member's claim permission has been updated.
 		MemberClaimPermissionUpdated { member: T::AccountId, permission: ClaimPermission },
+		/// A pool member's payout preference has been updated.
+		MemberPayoutPreferenceUpdated {
+			member: T::AccountId,
+			preference: Option<PayoutPreference<T::AccountId>>,
+		},
 		/// A pool's metadata was updated.
 		MetadataUpdated { pool_id: PoolId, caller: T::AccountId },
 		/// A pool's nominating account (or the pool's root account) has nominated a validator set
@@ -2056,6 +2093,8 @@ pub mod pallet {
 		/// Account is restricted from participation in pools. This may happen if the account is
 		/// staking in another way already.
 		Restricted,
+		/// The pool member has not set a payout preference.
+		NoPayoutPreference,
 	}
 
 	#[derive(
@@ -2508,8 +2547,9 @@ pub mod pallet {
 			});
 
 			let post_info_weight = if member.total_points().is_zero() {
-				// remove any `ClaimPermission` associated with the member.
+				// remove any `ClaimPermission` and `PayoutPreference` associated with the member.
 				ClaimPermissions::<T>::remove(&member_account);
+				PayoutPreferences::<T>::remove(&member_account);
 
 				// member being reaped.
 				PoolMembers::<T>::remove(&member_account);
@@ -3236,10 +3276,65 @@ pub mod pallet {
 			Self::migrate_to_delegate_stake(pool_id)?;
 			Ok(Pays::No.into())
 		}
+
+		/// Allows a pool member to set a preference for their pending rewards to be bonded back
+		/// into the pool or paid out to an account automatically.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - Member of a pool.
+		/// * `preference` - The preference to be applied, `None` to only claim rewards manually.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::set_payout_preference())]
+		pub fn set_payout_preference(
+			origin: OriginFor<T>,
+			preference: Option<PayoutPreference<T::AccountId>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(PoolMembers::<T>::contains_key(&who), Error::<T>::PoolMemberNotFound);
+
+			// ensure member is not in an un-migrated state.
+			ensure!(
+				!Self::api_member_needs_delegate_migration(who.clone()),
+				Error::<T>::NotMigrated
+			);
+
+			PayoutPreferences::<T>::set(&who, preference.clone());
+
+			Self::deposit_event(Event::<T>::MemberPayoutPreferenceUpdated {
+				member: who,
+				preference,
+			});
+
+			Ok(())
+		}
+
+		/// Apply the payout preference of `member` to their pending rewards.
+		///
+		/// This call can be dispatched permissionlessly, regardless of the [`ClaimPermission`] of
+		/// the member, who opted in to the automatic payouts by setting a preference. Payout
+		/// preferences are otherwise applied in `on_idle`, when blocks have weight left.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::process_payout_preference())]
+		pub fn process_payout_preference(
+			origin: OriginFor<T>,
+			member: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let _caller = ensure_signed(origin)?;
+			let member_account = T::Lookup::lookup(member)?;
+			let preference = PayoutPreferences::<T>::get(&member_account)
+				.ok_or(Error::<T>::NoPayoutPreference)?;
+
+			Self::do_apply_payout_preference(&member_account, preference)
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<SystemBlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: SystemBlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::do_process_payout_preferences(remaining_weight)
+		}
+
 		#[cfg(feature = "try-runtime")]
 		fn try_state(_n: SystemBlockNumberFor<T>) -> Result<(), TryRuntimeError> {
 			Self::do_try_state(u8::MAX)
@@ -3448,6 +3543,17 @@ impl<T: Config> Pallet<T> {
 		member: &mut PoolMember<T>,
 		bonded_pool: &mut BondedPool<T>,
 		reward_pool: &mut RewardPool<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		Self::do_reward_payout_to(member_account, member_account, member, bonded_pool, reward_pool)
+	}
+
+	/// Same as [`Self::do_reward_payout`], but transfers the payout to `payee`.
+	fn do_reward_payout_to(
+		member_account: &T::AccountId,
+		payee: &T::AccountId,
+		member: &mut PoolMember<T>,
+		bonded_pool: &mut BondedPool<T>,
+		reward_pool: &mut RewardPool<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
 		debug_assert_eq!(member.pool_id, bonded_pool.id);
 		debug_assert_eq!(&mut PoolMembers::<T>::get(member_account).unwrap(), member);
@@ -3474,7 +3580,7 @@ impl<T: Config> Pallet<T> {
 
 		T::Currency::transfer(
 			&bonded_pool.reward_account(),
-			member_account,
+			payee,
 			pending_rewards,
 			// defensive: the depositor has put existential deposit into the pool and it stays
 			// untouched, reward account shall not die.
@@ -3686,6 +3792,90 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Bond back or pay out the pending rewards of the member, according to their `preference`.
+	fn do_apply_payout_preference(
+		member_account: &T::AccountId,
+		preference: PayoutPreference<T::AccountId>,
+	) -> DispatchResult {
+		// ensure member is not in an un-migrated state.
+		ensure!(
+			!Self::api_member_needs_delegate_migration(member_account.clone()),
+			Error::<T>::NotMigrated
+		);
+
+		// nothing to compound or pay out, spare the writes.
+		if Self::api_pending_rewards(member_account.clone()).unwrap_or_default().is_zero() {
+			return Ok(())
+		}
+
+		match preference {
+			PayoutPreference::Compound => Self::do_bond_extra(
+				member_account.clone(),
+				member_account.clone(),
+				BondExtra::Rewards,
+			),
+			PayoutPreference::ClaimTo(payee) => {
+				let (mut member, mut bonded_pool, mut reward_pool) =
+					Self::get_member_with_pools(member_account)?;
+
+				Self::do_reward_payout_to(
+					member_account,
+					&payee,
+					&mut member,
+					&mut bonded_pool,
+					&mut reward_pool,
+				)?;
+
+				Self::put_member_with_pools(member_account, member, bonded_pool, reward_pool);
+				Ok(())
+			},
+		}
+	}
+
+	/// Apply the payout preferences of as many members as fit in `limit`, resuming after the
+	/// member processed last.
+	///
+	/// Returns the weight consumed.
+	pub(crate) fn do_process_payout_preferences(limit: Weight) -> Weight {
+		let per_member = T::WeightInfo::process_payout_preference();
+		// read and update the cursor.
+		let mut consumed = T::DbWeight::get().reads_writes(1, 1);
+		if !consumed.saturating_add(per_member).all_lte(limit) {
+			return Weight::zero()
+		}
+
+		let last = AutoPayoutCursor::<T>::get();
+		let mut preferences = match &last {
+			Some(member) =>
+				PayoutPreferences::<T>::iter_from(PayoutPreferences::<T>::hashed_key_for(member)),
+			None => PayoutPreferences::<T>::iter(),
+		};
+
+		let mut cursor = None;
+		while consumed.saturating_add(per_member).all_lte(limit) {
+			let Some((member, preference)) = preferences.next() else {
+				// all members processed, start over from the first one next time.
+				cursor = None;
+				break
+			};
+
+			// revert the partial changes of a failed payout, on_idle is not transactional.
+			let applied = frame_support::storage::with_storage_layer(|| {
+				Self::do_apply_payout_preference(&member, preference)
+			});
+			if let Err(e) = applied {
+				log!(debug, "failed to apply the payout preference of {:?}: {:?}", member, e);
+			}
+			consumed.saturating_accrue(per_member);
+			cursor = Some(member);
+		}
+
+		if cursor != last {
+			AutoPayoutCursor::<T>::set(cursor);
+		}
+		consumed
+	}
+
 	fn do_adjust_pool_deposit(who: T::AccountId, pool: PoolId) -> DispatchResult {
 		let bonded_pool = BondedPool::<T>::get(pool).ok_or(Error::<T>::PoolNotFound)?;
 
@@ -3904,6 +4094,11 @@ impl<T: Config> Pallet<T> {
 			Ok(())
 		})?;
 
+		ensure!(
+			PayoutPreferences::<T>::iter_keys().all(|k| PoolMembers::<T>::contains_key(k)),
+			"`PayoutPreferences` keys must be a subset of the `PoolMembers` keys."
+		);
+
 		RewardPools::<T>::iter_keys().try_for_each(|id| -> Result<(), TryRuntimeError> {
 			// the sum of the pending rewards must be less than the leftover balance. Since the
 			// reward math rounds down, we might accumulate some dust here.
@@ -7638,3 +7638,146 @@ mod filter {
 		});
 	}
 }
+
+mod payout_preference {
+	use super::*;
+
+	#[test]
+	fn set_payout_preference_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// only members can set a preference.
+			assert_noop!(
+				Pools::set_payout_preference(
+					RuntimeOrigin::signed(20),
+					Some(PayoutPreference::Compound)
+				),
+				Error::<Runtime>::PoolMemberNotFound
+			);
+
+			// when
+			assert_ok!(Pools::set_payout_preference(
+				RuntimeOrigin::signed(10),
+				Some(PayoutPreference::ClaimTo(11))
+			));
+
+			// then
+			assert_eq!(PayoutPreferences::<Runtime>::get(10), Some(PayoutPreference::ClaimTo(11)));
+
+			// when
+			assert_ok!(Pools::set_payout_preference(RuntimeOrigin::signed(10), None));
+
+			// then
+			assert_eq!(PayoutPreferences::<Runtime>::get(10), None);
+			assert_noop!(
+				Pools::process_payout_preference(RuntimeOrigin::signed(20), 10),
+				Error::<Runtime>::NoPayoutPreference
+			);
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::MetadataUpdated { pool_id: 1, caller: 900 },
+					Event::MemberPayoutPreferenceUpdated {
+						member: 10,
+						preference: Some(PayoutPreference::ClaimTo(11))
+					},
+					Event::MemberPayoutPreferenceUpdated { member: 10, preference: None },
+				]
+			);
+		})
+	}
+
+	#[test]
+	fn payout_preferences_are_applied_on_idle() {
+		ExtBuilder::default()
+			.add_members(vec![(20, 20), (30, 10)])
+			.build_and_execute(|| {
+				assert_ok!(Pools::set_payout_preference(
+					RuntimeOrigin::signed(20),
+					Some(PayoutPreference::Compound)
+				));
+				assert_ok!(Pools::set_payout_preference(
+					RuntimeOrigin::signed(30),
+					Some(PayoutPreference::ClaimTo(31))
+				));
+				let _ = pool_events_since_last_call();
+
+				// 10, 20 and 30 have 10, 20 and 10 points.
+				deposit_rewards(40);
+
+				// when
+				Pools::on_idle(1, Weight::MAX);
+
+				// then 20 compounded their rewards, 30 got theirs paid to 31 and 10 has to claim.
+				assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 40);
+				assert_eq!(Currency::free_balance(&31), 10);
+				assert_eq!(Pools::api_pending_rewards(10), Some(10));
+				assert_eq!(Pools::api_pending_rewards(20), Some(0));
+				assert_eq!(Pools::api_pending_rewards(30), Some(0));
+
+				let mut events = pool_events_since_last_call();
+				events.sort_by_key(|e| format!("{:?}", e));
+				assert_eq!(
+					events,
+					vec![
+						Event::Bonded { member: 20, pool_id: 1, bonded: 20, joined: false },
+						Event::PaidOut { member: 20, pool_id: 1, payout: 20 },
+						Event::PaidOut { member: 30, pool_id: 1, payout: 10 },
+					]
+				);
+
+				// nothing left to pay out.
+				Pools::on_idle(2, Weight::MAX);
+				assert!(pool_events_since_last_call().is_empty());
+
+				// the preference of a member leaving the pool is removed.
+				assert_ok!(Pools::fully_unbond(RuntimeOrigin::signed(30), 30));
+				CurrentEra::set(3);
+				assert_ok!(Pools::withdraw_unbonded(RuntimeOrigin::signed(30), 30, 0));
+				assert_eq!(PayoutPreferences::<Runtime>::get(30), None);
+			})
+	}
+
+	#[test]
+	fn payout_preferences_are_applied_within_the_idle_weight() {
+		ExtBuilder::default()
+			.add_members(vec![(20, 10), (30, 10)])
+			.build_and_execute(|| {
+				for member in [20, 30] {
+					assert_ok!(Pools::set_payout_preference(
+						RuntimeOrigin::signed(member),
+						Some(PayoutPreference::Compound)
+					));
+				}
+				deposit_rewards(30);
+				let compounded = |member| PoolMembers::<Runtime>::get(member).unwrap().points > 10;
+				let per_member =
+					<<Runtime as Config>::WeightInfo as WeightInfo>::process_payout_preference();
+
+				// not enough weight for a single member.
+				assert_eq!(Pools::on_idle(1, per_member / 2), Weight::zero());
+				assert_eq!(AutoPayoutCursor::<Runtime>::get(), None);
+
+				// when
+				assert_eq!(Pools::on_idle(1, per_member), per_member);
+
+				// then only one member is processed.
+				let first = AutoPayoutCursor::<Runtime>::get().unwrap();
+				let second = if first == 20 { 30 } else { 20 };
+				assert!(compounded(first));
+				assert!(!compounded(second));
+
+				// when
+				assert_eq!(Pools::on_idle(2, per_member), per_member);
+
+				// then the next block resumes with the other member.
+				assert!(compounded(second));
+				assert_eq!(AutoPayoutCursor::<Runtime>::get(), Some(second));
+
+				// and the one after starts over.
+				Pools::on_idle(3, per_member);
+				assert_eq!(AutoPayoutCursor::<Runtime>::get(), None);
+			})
+	}
+}
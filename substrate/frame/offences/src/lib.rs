@@ -129,7 +129,8 @@ where
 
 		let slash_perbill: Vec<_> = (0..concurrent_offenders.len()).map(|_| new_fraction).collect();
 
-		T::OnOffenceHandler::on_offence(
+		T::OnOffenceHandler::on_offence_of_kind(
+			&O::ID,
 			&concurrent_offenders,
 			&slash_perbill,
 			offence.session_index(),
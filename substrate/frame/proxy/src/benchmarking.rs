@@ -231,6 +231,32 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn announce_call(
+		a: Linear<0, { T::MaxPending::get() - 1 }>,
+		p: Linear<1, { T::MaxProxies::get() - 1 }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		add_proxies::<T>(p, None)?;
+		// In this case the caller is the "target" proxy
+		let caller: T::AccountId = account("target", p - 1, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		// ... and "real" is the traditional caller. This is not a typo.
+		let real: T::AccountId = whitelisted_caller();
+		let real_lookup = T::Lookup::unlookup(real.clone());
+		add_announcements::<T>(a, Some(caller.clone()), None)?;
+		let call: <T as Config>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: vec![0; z as usize] }.into();
+		let call_hash = T::CallHasher::hash_of(&call);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), real_lookup, Box::new(call));
+
+		assert_last_event::<T>(Event::Announced { real, proxy: caller, call_hash }.into());
+
+		Ok(())
+	}
+
 	#[benchmark]
 	fn add_proxy(p: Linear<1, { T::MaxProxies::get() - 1 }>) -> Result<(), BenchmarkError> {
 		add_proxies::<T>(p, None)?;
@@ -108,6 +108,50 @@ pub enum DepositKind {
 	Announcements,
 }
 
+/// A hook deciding which announcements delayed proxies may make.
+///
+/// Announcements normally only carry the hash of the call, so the proxied account cannot tell
+/// what is being announced. Runtimes may require announcements to be made with
+/// [`Pallet::announce_call`] instead, and reject calls the proxy would not be allowed to make.
+pub trait AnnouncementFilter<AccountId, ProxyType, Call> {
+	/// Whether announcements by a proxy of `proxy_type` on behalf of `real` must include the
+	/// call, rather than only its hash.
+	fn requires_call(real: &AccountId, proxy_type: &ProxyType) -> bool;
+
+	/// Whether a proxy of `proxy_type` may announce `call` on behalf of `real`.
+	fn allows(real: &AccountId, proxy_type: &ProxyType, call: &Call) -> bool;
+}
+
+/// Announcements may be made with or without the call, and any call may be announced.
+impl<AccountId, ProxyType, Call> AnnouncementFilter<AccountId, ProxyType, Call> for () {
+	fn requires_call(_: &AccountId, _: &ProxyType) -> bool {
+		false
+	}
+
+	fn allows(_: &AccountId, _: &ProxyType, _: &Call) -> bool {
+		true
+	}
+}
+
+/// Requires every announcement to include the call, which may be at most `MaxCallLen` bytes long
+/// and must pass the filter of the proxy type.
+pub struct RequireAnnouncedCall<MaxCallLen>(core::marker::PhantomData<MaxCallLen>);
+impl<AccountId, ProxyType, Call, MaxCallLen> AnnouncementFilter<AccountId, ProxyType, Call>
+	for RequireAnnouncedCall<MaxCallLen>
+where
+	ProxyType: InstanceFilter<Call>,
+	Call: Encode,
+	MaxCallLen: Get<u32>,
+{
+	fn requires_call(_: &AccountId, _: &ProxyType) -> bool {
+		true
+	}
+
+	fn allows(_: &AccountId, proxy_type: &ProxyType, call: &Call) -> bool {
+		call.encoded_size() <= MaxCallLen::get() as usize && proxy_type.filter(call)
+	}
+}
+
 #[frame::pallet]
 pub mod pallet {
 	use super::*;
@@ -174,6 +218,15 @@ pub mod pallet {
 		/// The type of hash used for hashing the call.
 		type CallHasher: Hash;
 
+		/// Decides whether announcements must include the call and which calls may be announced.
+		///
+		/// Use `()` to allow announcing any call by its hash.
+		type AnnouncementFilter: AnnouncementFilter<
+			Self::AccountId,
+			Self::ProxyType,
+			<Self as Config>::RuntimeCall,
+		>;
+
 		/// The base amount of currency needed to reserve for creating an announcement.
 		///
 		/// This is held when a new storage item holding a `Balance` is created (typically 16
@@ -418,35 +471,15 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let real = T::Lookup::lookup(real)?;
-			Proxies::<T>::get(&real)
-				.0
-				.into_iter()
-				.find(|x| x.delegate == who)
-				.ok_or(Error::<T>::NotProxy)?;
-
-			let announcement = Announcement {
-				real: real.clone(),
-				call_hash,
-				height: T::BlockNumberProvider::current_block_number(),
-			};
-
-			Announcements::<T>::try_mutate(&who, |(ref mut pending, ref mut deposit)| {
-				pending.try_push(announcement).map_err(|_| Error::<T>::TooMany)?;
-				Self::rejig_deposit(
-					&who,
-					*deposit,
-					T::AnnouncementDepositBase::get(),
-					T::AnnouncementDepositFactor::get(),
-					pending.len(),
-				)
-				.map(|d| {
-					d.expect("Just pushed; pending.len() > 0; rejig_deposit returns Some; qed")
-				})
-				.map(|d| *deposit = d)
-			})?;
-			Self::deposit_event(Event::Announced { real, proxy: who, call_hash });
-
-			Ok(())
+			let defs = Proxies::<T>::get(&real).0;
+			let mut defs = defs.iter().filter(|x| x.delegate == who).peekable();
+			ensure!(defs.peek().is_some(), Error::<T>::NotProxy);
+			ensure!(
+				!defs.any(|x| T::AnnouncementFilter::requires_call(&real, &x.proxy_type)),
+				Error::<T>::CallRequired
+			);
+
+			Self::do_announce(who, real, call_hash)
 		}
 
 		/// Remove a given announcement.
@@ -551,6 +584,40 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Publish a proxy-call that will be made in the future.
+		///
+		/// Like `announce`, but takes the call itself rather than its hash, so that it can be
+		/// checked by the runtime's `AnnouncementFilter`. Only the hash of the call is stored.
+		///
+		/// The dispatch origin for this call must be _Signed_ and a proxy of `real`.
+		///
+		/// Parameters:
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `call`: The call to be made by the `real` account.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::announce_call(
+			T::MaxPending::get(),
+			T::MaxProxies::get(),
+			call.using_encoded(|c| c.len() as u32),
+		))]
+		pub fn announce_call(
+			origin: OriginFor<T>,
+			real: AccountIdLookupOf<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let real = T::Lookup::lookup(real)?;
+			let defs = Proxies::<T>::get(&real).0;
+			let mut defs = defs.iter().filter(|x| x.delegate == who).peekable();
+			ensure!(defs.peek().is_some(), Error::<T>::NotProxy);
+			ensure!(
+				defs.any(|x| T::AnnouncementFilter::allows(&real, &x.proxy_type, &call)),
+				Error::<T>::AnnouncementFiltered
+			);
+
+			Self::do_announce(who, real, T::CallHasher::hash_of(&call))
+		}
+
 		/// Poke / Adjust deposits made for proxies and announcements based on current values.
 		/// This can be used by accounts to possibly lower their locked amount.
 		///
@@ -707,6 +774,10 @@ pub mod pallet {
 		Unannounced,
 		/// Cannot add self as proxy.
 		NoSelfProxy,
+		/// Announcements on behalf of this account must include the call.
+		CallRequired,
+		/// The announced call is not allowed by the announcement filter.
+		AnnouncementFiltered,
 	}
 
 	/// The set of account proxies. Maps the account which has delegated to the accounts
@@ -943,6 +1014,35 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Record an announcement of `call_hash` by the proxy `who` on behalf of `real`.
+	fn do_announce(
+		who: T::AccountId,
+		real: T::AccountId,
+		call_hash: CallHashOf<T>,
+	) -> DispatchResult {
+		let announcement = Announcement {
+			real: real.clone(),
+			call_hash,
+			height: T::BlockNumberProvider::current_block_number(),
+		};
+
+		Announcements::<T>::try_mutate(&who, |(ref mut pending, ref mut deposit)| {
+			pending.try_push(announcement).map_err(|_| Error::<T>::TooMany)?;
+			Self::rejig_deposit(
+				&who,
+				*deposit,
+				T::AnnouncementDepositBase::get(),
+				T::AnnouncementDepositFactor::get(),
+				pending.len(),
+			)
+			.map(|d| d.expect("Just pushed; pending.len() > 0; rejig_deposit returns Some; qed"))
+			.map(|d| *deposit = d)
+		})?;
+		Self::deposit_event(Event::Announced { real, proxy: who, call_hash });
+
+		Ok(())
+	}
+
 	pub fn find_proxy(
 		real: &T::AccountId,
 		delegate: &T::AccountId,
@@ -113,6 +113,21 @@ parameter_types! {
 	pub static ProxyDepositFactor: u64 = 1;
 	pub static AnnouncementDepositBase: u64 = 1;
 	pub static AnnouncementDepositFactor: u64 = 1;
+	pub static RequireAnnouncedCalls: bool = false;
+}
+
+/// Requires announced calls of at most 64 bytes when `RequireAnnouncedCalls` is set.
+pub struct MockAnnouncementFilter;
+impl AnnouncementFilter<u64, ProxyType, RuntimeCall> for MockAnnouncementFilter {
+	fn requires_call(real: &u64, proxy_type: &ProxyType) -> bool {
+		RequireAnnouncedCalls::get() &&
+			RequireAnnouncedCall::<ConstU32<64>>::requires_call(real, proxy_type)
+	}
+
+	fn allows(real: &u64, proxy_type: &ProxyType, call: &RuntimeCall) -> bool {
+		!RequireAnnouncedCalls::get() ||
+			RequireAnnouncedCall::<ConstU32<64>>::allows(real, proxy_type, call)
+	}
 }
 
 impl Config for Test {
@@ -125,6 +140,7 @@ impl Config for Test {
 	type MaxProxies = ConstU32<4>;
 	type WeightInfo = ();
 	type CallHasher = BlakeTwo256;
+	type AnnouncementFilter = MockAnnouncementFilter;
 	type MaxPending = ConstU32<2>;
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
@@ -251,6 +267,53 @@ fn reject_announcement_works() {
 	});
 }
 
+#[test]
+fn announcement_filter_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(1), 3, ProxyType::JustTransfer, 1));
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(2), 3, ProxyType::Any, 1));
+		let call = Box::new(call_transfer(6, 1));
+		let call_hash = BlakeTwo256::hash_of(&call);
+
+		// Without a filter, calls can be announced either way.
+		assert_ok!(Proxy::announce(RuntimeOrigin::signed(3), 1, call_hash));
+		assert_ok!(Proxy::announce_call(RuntimeOrigin::signed(3), 1, call.clone()));
+		assert_ok!(Proxy::remove_announcement(RuntimeOrigin::signed(3), 1, call_hash));
+
+		RequireAnnouncedCalls::set(true);
+		assert_noop!(
+			Proxy::announce(RuntimeOrigin::signed(3), 1, call_hash),
+			Error::<Test>::CallRequired
+		);
+		// The proxy type would not be allowed to make the call.
+		let remark = Box::new(RuntimeCall::System(SystemCall::remark { remark: vec![] }));
+		assert_noop!(
+			Proxy::announce_call(RuntimeOrigin::signed(3), 1, remark),
+			Error::<Test>::AnnouncementFiltered
+		);
+		// The call is too long, even for a proxy that could make it.
+		let long_remark = Box::new(RuntimeCall::System(SystemCall::remark { remark: vec![0; 64] }));
+		assert_noop!(
+			Proxy::announce_call(RuntimeOrigin::signed(3), 2, long_remark),
+			Error::<Test>::AnnouncementFiltered
+		);
+		assert_noop!(
+			Proxy::announce_call(RuntimeOrigin::signed(4), 1, call.clone()),
+			Error::<Test>::NotProxy
+		);
+		assert_ok!(Proxy::announce_call(RuntimeOrigin::signed(3), 1, call.clone()));
+
+		// Only the hash is stored, and the announced call can be executed as usual.
+		assert_eq!(
+			Announcements::<Test>::get(3).0,
+			vec![Announcement { real: 1, call_hash, height: 1 }]
+		);
+		System::set_block_number(2);
+		assert_ok!(Proxy::proxy_announced(RuntimeOrigin::signed(0), 3, 1, None, call));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+	});
+}
+
 #[test]
 fn announcer_must_be_proxy() {
 	new_test_ext().execute_with(|| {
@@ -23,10 +23,34 @@ use super::*;
 use alloc::vec;
 use frame_benchmarking::v2::*;
 use frame_system::{EventRecord, Pallet as System, RawOrigin};
+use sp_runtime::traits::{Bounded, Zero};
 
 #[cfg(test)]
 use crate::Pallet as Remark;
 
+/// Stores `count` remarks of maximum length from distinct senders under `topic`, the first
+/// `expired` of which are past the retention period.
+fn fill_topic<T: Config>(topic: Topic, count: u32, expired: u32) {
+	let now = T::TopicRetentionPeriod::get();
+	System::<T>::set_block_number(now);
+	let deposit = T::TopicRemarkDepositBase::get();
+	let remarks = (0..count)
+		.map(|i| {
+			let sender: T::AccountId = account("sender", i, 0);
+			T::Currency::make_free_balance_be(&sender, BalanceOf::<T>::max_value() / 2u32.into());
+			T::Currency::reserve(&sender, deposit).unwrap();
+			TopicRemark {
+				sender,
+				block: if i < expired { Zero::zero() } else { now },
+				deposit,
+				data: vec![0u8; T::MaxTopicRemarkLen::get() as usize].try_into().unwrap(),
+			}
+		})
+		.collect::<Vec<_>>();
+	TopicRemarks::<T>::insert(topic, BoundedVec::try_from(remarks).unwrap());
+	TopicCount::<T>::put(1);
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = System::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -54,5 +78,46 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn store_with_topic(
+		l: Linear<1, { T::MaxTopicRemarkLen::get() }>,
+		r: Linear<0, { T::MaxRemarksPerTopic::get() }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let topic = [1u8; 32];
+
+		// Worst case: the ring buffer of the topic is full, `r` of its remarks expired and have
+		// their deposits released. If none expired, the oldest one has to be evicted instead.
+		fill_topic::<T>(topic, T::MaxRemarksPerTopic::get(), r);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), topic, vec![0u8; l as usize]);
+
+		assert_last_event::<T>(
+			Event::TopicStored {
+				sender: caller,
+				topic,
+				content_hash: sp_io::hashing::blake2_256(&vec![0u8; l as usize]).into(),
+			}
+			.into(),
+		);
+	}
+
+	#[benchmark]
+	fn prune_topic(r: Linear<1, { T::MaxRemarksPerTopic::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let topic = [1u8; 32];
+
+		// Worst case: all remarks of the topic expired, so it is removed as well.
+		fill_topic::<T>(topic, r, r);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), topic);
+
+		assert!(!TopicRemarks::<T>::contains_key(topic));
+		assert_last_event::<T>(Event::TopicPruned { topic, pruned: r }.into());
+	}
+
 	impl_benchmark_test_suite!(Remark, crate::mock::new_test_ext(), crate::mock::Test);
 }
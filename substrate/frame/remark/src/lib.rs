@@ -16,11 +16,22 @@
 // limitations under the License.
 
 //! Remark storage pallet. Indexes remarks and stores them off chain.
+//!
+//! Remarks can also be tagged with a [`Topic`] through [`Pallet::store_with_topic`]. The last
+//! [`Config::MaxRemarksPerTopic`] remarks of every topic are kept on chain for
+//! [`Config::TopicRetentionPeriod`] blocks and can be queried through the
+//! [`runtime_api::RemarkApi`], giving a lightweight signaling channel that doesn't require
+//! scraping the extrinsics of every block.
+//!
+//! A deposit is reserved from the sender of every topic remark for as long as it is kept on
+//! chain, and at most [`Config::MaxTopics`] topics can exist at once. Expired remarks can be
+//! removed by anyone through [`Pallet::prune_topic`], releasing their deposits.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+pub mod runtime_api;
 pub mod weights;
 
 #[cfg(test)]
@@ -31,16 +42,55 @@ mod tests;
 extern crate alloc;
 
 use alloc::vec::Vec;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Currency, Defensive, Get, ReservableCurrency},
+	BoundedVec,
+};
+use scale_info::TypeInfo;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+/// Identifier of the topic of a remark, e.g. the hash of a human readable name.
+pub type Topic = [u8; 32];
+
+/// A remark stored on chain under a [`Topic`].
+#[derive(
+	Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxLen))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen, Balance: MaxEncodedLen))]
+pub struct TopicRemark<AccountId, BlockNumber, Balance, MaxLen: Get<u32>> {
+	/// The account that stored the remark.
+	pub sender: AccountId,
+	/// The block the remark was stored in.
+	pub block: BlockNumber,
+	/// The amount reserved from `sender` while the remark is kept.
+	pub deposit: Balance,
+	/// The content of the remark.
+	pub data: BoundedVec<u8, MaxLen>,
+}
+
+/// The balance type of the currency deposits are reserved in.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// [`TopicRemark`] of the given runtime.
+pub type TopicRemarkOf<T> = TopicRemark<
+	<T as frame_system::Config>::AccountId,
+	frame_system::pallet_prelude::BlockNumberFor<T>,
+	BalanceOf<T>,
+	<T as Config>::MaxTopicRemarkLen,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, Zero};
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -49,6 +99,30 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
+		/// The maximum length of a remark stored under a topic.
+		#[pallet::constant]
+		type MaxTopicRemarkLen: Get<u32>;
+		/// The maximum number of remarks kept per topic. Once reached, storing a new remark
+		/// under the topic evicts the oldest one.
+		#[pallet::constant]
+		type MaxRemarksPerTopic: Get<u32>;
+		/// The number of blocks a remark stored under a topic is retained for.
+		#[pallet::constant]
+		type TopicRetentionPeriod: Get<BlockNumberFor<Self>>;
+		/// The maximum number of topics with remarks kept on chain at once.
+		#[pallet::constant]
+		type MaxTopics: Get<u32>;
+		/// The currency the deposits of topic remarks are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The base amount reserved for every remark stored under a topic.
+		///
+		/// This is held for the sender, the block number and the deposit stored alongside the
+		/// remark.
+		#[pallet::constant]
+		type TopicRemarkDepositBase: Get<BalanceOf<Self>>;
+		/// The amount reserved per byte of a remark stored under a topic.
+		#[pallet::constant]
+		type TopicRemarkDepositPerByte: Get<BalanceOf<Self>>;
 	}
 
 	#[pallet::error]
@@ -57,11 +131,42 @@ pub mod pallet {
 		Empty,
 		/// Attempted to call `store` outside of block execution.
 		BadContext,
+		/// The remark is longer than [`Config::MaxTopicRemarkLen`].
+		TooLong,
+		/// [`Config::MaxTopics`] topics already have remarks kept on chain.
+		TooManyTopics,
+		/// No remark of the topic is past the retention period.
+		NothingToPrune,
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
+	/// The most recent remarks of every topic, oldest first.
+	///
+	/// Remarks older than [`Config::TopicRetentionPeriod`] are only pruned when a new remark is
+	/// stored under the same topic or through [`Pallet::prune_topic`], use
+	/// [`Pallet::topic_remarks`] to read the retained ones. Topics without remarks are removed.
+	#[pallet::storage]
+	pub type TopicRemarks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		Topic,
+		BoundedVec<TopicRemarkOf<T>, T::MaxRemarksPerTopic>,
+		ValueQuery,
+	>;
+
+	/// The number of entries in [`TopicRemarks`].
+	#[pallet::storage]
+	pub type TopicCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(T::MaxRemarksPerTopic::get() > 0, "`MaxRemarksPerTopic` must not be zero");
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Index and store data off chain.
@@ -77,6 +182,85 @@ pub mod pallet {
 			Self::deposit_event(Event::Stored { sender, content_hash: content_hash.into() });
 			Ok(().into())
 		}
+
+		/// Store data on chain under the given `topic`.
+		///
+		/// The remark is retained for [`Config::TopicRetentionPeriod`] blocks, unless
+		/// [`Config::MaxRemarksPerTopic`] more recent remarks are stored under the same topic
+		/// in the meantime. `TopicRemarkDepositBase` plus `TopicRemarkDepositPerByte` per byte of
+		/// the remark is reserved from the sender until the remark is removed.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::store_with_topic(
+			remark.len() as u32,
+			T::MaxRemarksPerTopic::get(),
+		))]
+		pub fn store_with_topic(
+			origin: OriginFor<T>,
+			topic: Topic,
+			remark: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			ensure!(!remark.is_empty(), Error::<T>::Empty);
+			let sender = ensure_signed(origin)?;
+			let data: BoundedVec<u8, T::MaxTopicRemarkLen> =
+				remark.try_into().map_err(|_| Error::<T>::TooLong)?;
+			let len = data.len() as u32;
+			let content_hash = sp_io::hashing::blake2_256(&data);
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			if !TopicRemarks::<T>::contains_key(topic) {
+				let count = TopicCount::<T>::get();
+				ensure!(count < T::MaxTopics::get(), Error::<T>::TooManyTopics);
+				TopicCount::<T>::put(count.saturating_add(1));
+			}
+
+			let deposit = T::TopicRemarkDepositBase::get()
+				.saturating_add(T::TopicRemarkDepositPerByte::get().saturating_mul(len.into()));
+			T::Currency::reserve(&sender, deposit)?;
+
+			let mut remarks = TopicRemarks::<T>::get(topic);
+			let mut removed = Self::prune_expired(&mut remarks, now);
+			if remarks.is_full() {
+				Self::release_deposit(&remarks.remove(0));
+				removed.saturating_inc();
+			}
+			remarks
+				.try_push(TopicRemark { sender: sender.clone(), block: now, deposit, data })
+				.map_err(|_| DispatchError::Corruption)
+				.defensive_proof("`MaxRemarksPerTopic` is not zero and a slot was freed; qed")?;
+			TopicRemarks::<T>::insert(topic, remarks);
+
+			Self::deposit_event(Event::TopicStored {
+				sender,
+				topic,
+				content_hash: content_hash.into(),
+			});
+			Ok(Some(T::WeightInfo::store_with_topic(len, removed)).into())
+		}
+
+		/// Remove the remarks of `topic` that are past the retention period, releasing their
+		/// deposits.
+		///
+		/// The topic itself is removed once it has no remarks left. The dispatch origin for this
+		/// call must be _Signed_, but it doesn't have to be the sender of any of the remarks.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::prune_topic(T::MaxRemarksPerTopic::get()))]
+		pub fn prune_topic(origin: OriginFor<T>, topic: Topic) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let mut remarks = TopicRemarks::<T>::get(topic);
+			let pruned = Self::prune_expired(&mut remarks, now);
+			ensure!(!pruned.is_zero(), Error::<T>::NothingToPrune);
+			if remarks.is_empty() {
+				TopicRemarks::<T>::remove(topic);
+				TopicCount::<T>::mutate(|count| count.saturating_dec());
+			} else {
+				TopicRemarks::<T>::insert(topic, remarks);
+			}
+
+			Self::deposit_event(Event::TopicPruned { topic, pruned });
+			Ok(Some(T::WeightInfo::prune_topic(pruned)).into())
+		}
 	}
 
 	#[pallet::event]
@@ -84,5 +268,47 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// Stored data off chain.
 		Stored { sender: T::AccountId, content_hash: sp_core::H256 },
+		/// Stored data on chain under a topic.
+		TopicStored { sender: T::AccountId, topic: Topic, content_hash: sp_core::H256 },
+		/// Removed the expired remarks of a topic.
+		TopicPruned { topic: Topic, pruned: u32 },
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the remarks of the given `topic` that are still within the retention period,
+		/// oldest first.
+		pub fn topic_remarks(topic: Topic) -> Vec<TopicRemarkOf<T>> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			TopicRemarks::<T>::get(topic)
+				.into_iter()
+				.filter(|remark| Self::is_retained(remark, now))
+				.collect()
+		}
+
+		fn is_retained(remark: &TopicRemarkOf<T>, now: BlockNumberFor<T>) -> bool {
+			now.saturating_sub(remark.block) < T::TopicRetentionPeriod::get()
+		}
+
+		/// Removes the remarks past the retention period and releases their deposits, returning
+		/// the number of removed remarks.
+		fn prune_expired(
+			remarks: &mut BoundedVec<TopicRemarkOf<T>, T::MaxRemarksPerTopic>,
+			now: BlockNumberFor<T>,
+		) -> u32 {
+			let before = remarks.len();
+			remarks.retain(|remark| {
+				let retained = Self::is_retained(remark, now);
+				if !retained {
+					Self::release_deposit(remark);
+				}
+				retained
+			});
+			(before - remarks.len()) as u32
+		}
+
+		fn release_deposit(remark: &TopicRemarkOf<T>) {
+			let err_amount = T::Currency::unreserve(&remark.sender, remark.deposit);
+			debug_assert!(err_amount.is_zero());
+		}
 	}
 }
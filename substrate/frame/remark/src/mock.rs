@@ -18,7 +18,10 @@
 //! Test environment for remarks pallet.
 
 use crate as pallet_remark;
-use frame_support::derive_impl;
+use frame_support::{
+	derive_impl,
+	traits::{ConstU32, ConstU64},
+};
 use sp_runtime::BuildStorage;
 
 pub type Block = frame_system::mocking::MockBlock<Test>;
@@ -28,6 +31,7 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		Remark: pallet_remark,
 	}
 );
@@ -35,14 +39,35 @@ frame_support::construct_runtime!(
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
 	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
 }
 
 impl pallet_remark::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type MaxTopicRemarkLen = ConstU32<16>;
+	type MaxRemarksPerTopic = ConstU32<2>;
+	type TopicRetentionPeriod = ConstU64<10>;
+	type MaxTopics = ConstU32<2>;
+	type Currency = Balances;
+	type TopicRemarkDepositBase = ConstU64<2>;
+	type TopicRemarkDepositPerByte = ConstU64<1>;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = RuntimeGenesisConfig { system: Default::default() }.build_storage().unwrap();
+	let t = RuntimeGenesisConfig {
+		system: Default::default(),
+		balances: pallet_balances::GenesisConfig {
+			balances: vec![(1, 100), (2, 100), (3, 100)],
+			..Default::default()
+		},
+	}
+	.build_storage()
+	.unwrap();
 	t.into()
 }
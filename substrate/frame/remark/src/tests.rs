@@ -17,9 +17,9 @@
 
 //! Tests for remarks pallet.
 
-use super::{Error, Event, Pallet as Remark};
+use super::{Error, Event, Pallet as Remark, TopicCount, TopicRemarks};
 use crate::mock::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
 use frame_system::RawOrigin;
 
 #[test]
@@ -56,3 +56,172 @@ fn does_not_store_empty() {
 		assert!(System::events().is_empty());
 	});
 }
+
+#[test]
+fn stores_remark_under_topic() {
+	new_test_ext().execute_with(|| {
+		let topic = [1u8; 32];
+		let data = vec![1u8; 16];
+		System::set_block_number(1);
+		assert_ok!(Remark::<Test>::store_with_topic(
+			RawOrigin::Signed(1).into(),
+			topic,
+			data.clone()
+		));
+
+		System::assert_last_event(
+			Event::TopicStored {
+				sender: 1,
+				topic,
+				content_hash: sp_io::hashing::blake2_256(&data).into(),
+			}
+			.into(),
+		);
+
+		let remarks = Remark::<Test>::topic_remarks(topic);
+		assert_eq!(remarks.len(), 1);
+		assert_eq!((remarks[0].sender, remarks[0].block), (1, 1));
+		assert_eq!(remarks[0].data.to_vec(), data);
+		assert!(Remark::<Test>::topic_remarks([2u8; 32]).is_empty());
+
+		// the base deposit plus one per byte is reserved.
+		assert_eq!(remarks[0].deposit, 18);
+		assert_eq!(Balances::reserved_balance(1), 18);
+		assert_eq!(TopicCount::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn does_not_store_invalid_topic_remark() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Remark::<Test>::store_with_topic(RawOrigin::Signed(1).into(), [1u8; 32], vec![]),
+			Error::<Test>::Empty
+		);
+		assert_noop!(
+			Remark::<Test>::store_with_topic(RawOrigin::Signed(1).into(), [1u8; 32], vec![0u8; 17]),
+			Error::<Test>::TooLong
+		);
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn oldest_topic_remark_is_evicted() {
+	new_test_ext().execute_with(|| {
+		let topic = [1u8; 32];
+		for sender in 1..=3 {
+			System::set_block_number(sender);
+			assert_ok!(Remark::<Test>::store_with_topic(
+				RawOrigin::Signed(sender).into(),
+				topic,
+				vec![sender as u8]
+			));
+		}
+
+		let senders = Remark::<Test>::topic_remarks(topic)
+			.iter()
+			.map(|r| r.sender)
+			.collect::<Vec<_>>();
+		assert_eq!(senders, vec![2, 3]);
+
+		// the deposit of the evicted remark is released.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_eq!(Balances::reserved_balance(3), 3);
+	});
+}
+
+#[test]
+fn topic_remarks_expire_after_retention_period() {
+	new_test_ext().execute_with(|| {
+		let topic = [1u8; 32];
+		System::set_block_number(1);
+		assert_ok!(Remark::<Test>::store_with_topic(RawOrigin::Signed(1).into(), topic, vec![1]));
+
+		System::set_block_number(10);
+		assert_eq!(Remark::<Test>::topic_remarks(topic).len(), 1);
+
+		// expired remarks are not returned, but stay in storage until the topic is written to.
+		System::set_block_number(11);
+		assert!(Remark::<Test>::topic_remarks(topic).is_empty());
+		assert_eq!(TopicRemarks::<Test>::get(topic).len(), 1);
+
+		assert_ok!(Remark::<Test>::store_with_topic(RawOrigin::Signed(2).into(), topic, vec![2]));
+		let stored = TopicRemarks::<Test>::get(topic);
+		assert_eq!(stored.len(), 1);
+		assert_eq!(stored[0].sender, 2);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn topic_remark_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Remark::<Test>::store_with_topic(RawOrigin::Signed(4).into(), [1u8; 32], vec![1]),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert!(!TopicRemarks::<Test>::contains_key([1u8; 32]));
+		assert_eq!(TopicCount::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn number_of_topics_is_bounded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for topic in [[1u8; 32], [2u8; 32]] {
+			assert_ok!(Remark::<Test>::store_with_topic(
+				RawOrigin::Signed(1).into(),
+				topic,
+				vec![1]
+			));
+		}
+		assert_noop!(
+			Remark::<Test>::store_with_topic(RawOrigin::Signed(1).into(), [3u8; 32], vec![1]),
+			Error::<Test>::TooManyTopics
+		);
+
+		// existing topics can still be written to.
+		assert_ok!(Remark::<Test>::store_with_topic(
+			RawOrigin::Signed(2).into(),
+			[1u8; 32],
+			vec![2]
+		));
+		assert_eq!(TopicCount::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn prune_topic_releases_expired_remarks() {
+	new_test_ext().execute_with(|| {
+		let topic = [1u8; 32];
+		System::set_block_number(1);
+		assert_ok!(Remark::<Test>::store_with_topic(RawOrigin::Signed(1).into(), topic, vec![1]));
+		System::set_block_number(5);
+		assert_ok!(Remark::<Test>::store_with_topic(RawOrigin::Signed(2).into(), topic, vec![2]));
+
+		assert_noop!(
+			Remark::<Test>::prune_topic(RawOrigin::Signed(3).into(), topic),
+			Error::<Test>::NothingToPrune
+		);
+
+		// anyone can prune the expired remarks.
+		System::set_block_number(11);
+		assert_ok!(Remark::<Test>::prune_topic(RawOrigin::Signed(3).into(), topic));
+		System::assert_last_event(Event::TopicPruned { topic, pruned: 1 }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_eq!(TopicCount::<Test>::get(), 1);
+
+		// the topic is removed along with its last remark.
+		System::set_block_number(15);
+		assert_ok!(Remark::<Test>::prune_topic(RawOrigin::Signed(3).into(), topic));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!TopicRemarks::<Test>::contains_key(topic));
+		assert_eq!(TopicCount::<Test>::get(), 0);
+	});
+}
@@ -52,6 +52,8 @@ use core::marker::PhantomData;
 /// Weight functions needed for `pallet_remark`.
 pub trait WeightInfo {
 	fn store(l: u32, ) -> Weight;
+	fn store_with_topic(l: u32, r: u32, ) -> Weight;
+	fn prune_topic(r: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_remark` using the Substrate node and recommended hardware.
@@ -67,6 +69,51 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 0
 			.saturating_add(Weight::from_parts(1_643, 0).saturating_mul(l.into()))
 	}
+	/// Storage: `Remark::TopicRemarks` (r:1 w:1)
+	/// Proof: `Remark::TopicRemarks` (`max_values`: None, `max_size`: Some(19890), added: 22365, mode: `MaxEncodedLen`)
+	/// Storage: `Remark::TopicCount` (r:1 w:1)
+	/// Proof: `Remark::TopicCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:65 w:65)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[1, 256]`.
+	/// The range of component `r` is `[0, 64]`.
+	fn store_with_topic(l: u32, r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `19_940 + r * (66 ±0)`
+		//  Estimated: `23_355 + r * (2_603 ±0)`
+		// Minimum execution time: 64_302_000 picoseconds.
+		Weight::from_parts(66_419_318, 23355)
+			// Standard Error: 181
+			.saturating_add(Weight::from_parts(1_715, 0).saturating_mul(l.into()))
+			// Standard Error: 1_942
+			.saturating_add(Weight::from_parts(17_638_512, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
+	/// Storage: `Remark::TopicRemarks` (r:1 w:1)
+	/// Proof: `Remark::TopicRemarks` (`max_values`: None, `max_size`: Some(19890), added: 22365, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:64 w:64)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Remark::TopicCount` (r:1 w:1)
+	/// Proof: `Remark::TopicCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[1, 64]`.
+	fn prune_topic(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `95 + r * (376 ±0)`
+		//  Estimated: `23_355 + r * (2_603 ±0)`
+		// Minimum execution time: 38_571_000 picoseconds.
+		Weight::from_parts(21_084_905, 23355)
+			// Standard Error: 2_117
+			.saturating_add(Weight::from_parts(17_702_364, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -81,4 +128,49 @@ impl WeightInfo for () {
 			// Standard Error: 0
 			.saturating_add(Weight::from_parts(1_643, 0).saturating_mul(l.into()))
 	}
+	/// Storage: `Remark::TopicRemarks` (r:1 w:1)
+	/// Proof: `Remark::TopicRemarks` (`max_values`: None, `max_size`: Some(19890), added: 22365, mode: `MaxEncodedLen`)
+	/// Storage: `Remark::TopicCount` (r:1 w:1)
+	/// Proof: `Remark::TopicCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:65 w:65)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[1, 256]`.
+	/// The range of component `r` is `[0, 64]`.
+	fn store_with_topic(l: u32, r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `19_940 + r * (66 ±0)`
+		//  Estimated: `23_355 + r * (2_603 ±0)`
+		// Minimum execution time: 64_302_000 picoseconds.
+		Weight::from_parts(66_419_318, 23355)
+			// Standard Error: 181
+			.saturating_add(Weight::from_parts(1_715, 0).saturating_mul(l.into()))
+			// Standard Error: 1_942
+			.saturating_add(Weight::from_parts(17_638_512, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
+	/// Storage: `Remark::TopicRemarks` (r:1 w:1)
+	/// Proof: `Remark::TopicRemarks` (`max_values`: None, `max_size`: Some(19890), added: 22365, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:64 w:64)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Remark::TopicCount` (r:1 w:1)
+	/// Proof: `Remark::TopicCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[1, 64]`.
+	fn prune_topic(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `95 + r * (376 ±0)`
+		//  Estimated: `23_355 + r * (2_603 ±0)`
+		// Minimum execution time: 38_571_000 picoseconds.
+		Weight::from_parts(21_084_905, 23355)
+			// Standard Error: 2_117
+			.saturating_add(Weight::from_parts(17_702_364, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
 }
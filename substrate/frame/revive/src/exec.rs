@@ -1482,7 +1482,7 @@ where
 			// Enable read-only access if requested; cannot disable it if already set.
 			let is_read_only = read_only || self.is_read_only();
 
-			if is_precompile(dest_addr) {
+			if is_precompile::<T>(dest_addr) {
 				return self.run_precompile(*dest_addr, false, is_read_only, value, &input_data);
 			}
 
@@ -1561,7 +1561,7 @@ where
 		address: H160,
 		input_data: Vec<u8>,
 	) -> Result<(), ExecError> {
-		if is_precompile(&address) {
+		if is_precompile::<T>(&address) {
 			return self.run_precompile(
 				address,
 				true,
@@ -84,6 +84,7 @@ pub use crate::{
 	address::{create1, create2, is_eth_derived, AccountId32Mapper, AddressMapper},
 	exec::{MomentOf, Origin},
 	pallet::*,
+	pure_precompiles::{RuntimePrecompile, RuntimePrecompiles},
 };
 pub use primitives::*;
 pub use weights::WeightInfo;
@@ -191,6 +192,12 @@ pub mod pallet {
 		#[pallet::no_default_bounds]
 		type ChainExtension: chain_extension::ChainExtension<Self> + Default;
 
+		/// Precompiles registered by the runtime, in addition to the ones of the pallet.
+		///
+		/// Types implementing [`RuntimePrecompile`] can be listed together as a tuple, each of them
+		/// at its own address and with its own weight charged before its execution.
+		type Precompiles: RuntimePrecompiles;
+
 		/// Find the author of the current block.
 		type FindAuthor: FindAuthor<Self::AccountId>;
 
@@ -351,6 +358,7 @@ pub mod pallet {
 			type RuntimeCall = ();
 			type CallFilter = ();
 			type ChainExtension = ();
+			type Precompiles = ();
 			type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 			type DepositPerByte = DepositPerByte;
 			type DepositPerItem = DepositPerItem;
@@ -694,6 +702,22 @@ pub mod pallet {
 				max_events_size,
 				storage_size_limit
 			);
+
+			// The precompiles registered by the runtime must have distinct addresses, outside of
+			// the range reserved for the precompiles of the pallet.
+			let mut precompile_addresses = alloc::collections::BTreeSet::new();
+			for address in T::Precompiles::addresses() {
+				assert!(
+					!pure_precompiles::is_reserved(&H160(address)),
+					"The address {:?} of a runtime precompile is reserved for the pallet precompiles",
+					H160(address),
+				);
+				assert!(
+					precompile_addresses.insert(address),
+					"Multiple runtime precompiles are registered at the address {:?}",
+					H160(address),
+				);
+			}
 		}
 	}
 
@@ -15,7 +15,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{exec::ExecResult, Config, Error, ExecReturnValue, GasMeter, H160, LOG_TARGET};
+use crate::{
+	exec::ExecResult, gas::Token, Config, Error, ExecReturnValue, GasMeter, H160, LOG_TARGET,
+};
+use alloc::vec::Vec;
+use frame_support::weights::Weight;
+use pallet_revive_uapi::ReturnFlags;
 
 mod ecrecover;
 pub use ecrecover::*;
@@ -39,12 +44,87 @@ mod blake2f;
 pub use blake2f::*;
 
 /// Determine if the given address is a precompile.
-/// For now, we consider that all addresses between 0x1 and 0xff are reserved for precompiles.
-pub fn is_precompile(address: &H160) -> bool {
+/// All addresses between 0x1 and 0xff are reserved for the precompiles of the pallet, the other
+/// precompiles are registered by the runtime through [`Config::Precompiles`].
+pub fn is_precompile<T: Config>(address: &H160) -> bool {
+	is_reserved(address) || T::Precompiles::is_registered(address)
+}
+
+/// Determine if the given address is reserved for the precompiles of the pallet.
+pub(crate) fn is_reserved(address: &H160) -> bool {
 	let bytes = address.as_bytes();
 	bytes.starts_with(&[0u8; 19]) && bytes[19] != 0
 }
 
+/// A precompile registered by the runtime, see [`Config::Precompiles`].
+pub trait RuntimePrecompile {
+	/// The address of the precompile.
+	///
+	/// It must not be between 0x1 and 0xff, these addresses are reserved for the precompiles of
+	/// the pallet.
+	const ADDRESS: [u8; 20];
+
+	/// The weight charged before executing the precompile with the provided input data.
+	fn weight(input: &[u8]) -> Weight;
+
+	/// Executes the precompile with the provided input data, returning its output data.
+	fn execute(input: &[u8]) -> Result<Vec<u8>, &'static str>;
+}
+
+/// The precompiles registered by the runtime.
+///
+/// Implemented for tuples of [`RuntimePrecompile`].
+pub trait RuntimePrecompiles {
+	/// The addresses of the registered precompiles.
+	fn addresses() -> Vec<[u8; 20]>;
+
+	/// Determine if a precompile is registered at the given address.
+	fn is_registered(address: &H160) -> bool;
+
+	/// The weight of executing the precompile registered at the given address.
+	fn weight(address: &H160, input: &[u8]) -> Option<Weight>;
+
+	/// Executes the precompile registered at the given address.
+	fn execute(address: &H160, input: &[u8]) -> Option<Result<Vec<u8>, &'static str>>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(20)]
+#[tuple_types_custom_trait_bound(RuntimePrecompile)]
+impl RuntimePrecompiles for Tuple {
+	fn addresses() -> Vec<[u8; 20]> {
+		#[allow(unused_mut)]
+		let mut addresses = Vec::new();
+		for_tuples!( #( addresses.push(Tuple::ADDRESS); )* );
+		addresses
+	}
+
+	fn is_registered(address: &H160) -> bool {
+		for_tuples!( #( if address.0 == Tuple::ADDRESS { return true } )* );
+		false
+	}
+
+	fn weight(address: &H160, input: &[u8]) -> Option<Weight> {
+		for_tuples!( #( if address.0 == Tuple::ADDRESS { return Some(Tuple::weight(input)) } )* );
+		None
+	}
+
+	fn execute(address: &H160, input: &[u8]) -> Option<Result<Vec<u8>, &'static str>> {
+		for_tuples!( #( if address.0 == Tuple::ADDRESS { return Some(Tuple::execute(input)) } )* );
+		None
+	}
+}
+
+/// The weight of a precompile registered by the runtime.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Clone, Copy)]
+struct RuntimePrecompileToken(Weight);
+
+impl<T: Config> Token<T> for RuntimePrecompileToken {
+	fn weight(&self) -> Weight {
+		self.0
+	}
+}
+
 /// The `Precompile` trait defines the functionality for executing a precompiled contract.
 pub trait Precompile<T: Config> {
 	/// Executes the precompile with the provided input data.
@@ -57,23 +137,42 @@ pub struct Precompiles<T: Config> {
 
 impl<T: Config> Precompiles<T> {
 	pub fn execute(addr: H160, gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
-		match addr.as_bytes()[19] {
-			1u8 => ECRecover::execute(gas_meter, input),
-			2u8 => Sha256::execute(gas_meter, input),
-			3u8 => Ripemd160::execute(gas_meter, input),
-			4u8 => Identity::execute(gas_meter, input),
-			5u8 => Modexp::execute(gas_meter, input),
-			6u8 => Bn128Add::execute(gas_meter, input),
-			7u8 => Bn128Mul::execute(gas_meter, input),
-			8u8 => Bn128Pairing::execute(gas_meter, input),
-			9u8 => Blake2F::execute(gas_meter, input),
-			_ => return Err(Error::<T>::UnsupportedPrecompileAddress.into()),
-		}
-		.map_err(|reason| {
+		let result = if is_reserved(&addr) {
+			match addr.as_bytes()[19] {
+				1u8 => ECRecover::execute(gas_meter, input),
+				2u8 => Sha256::execute(gas_meter, input),
+				3u8 => Ripemd160::execute(gas_meter, input),
+				4u8 => Identity::execute(gas_meter, input),
+				5u8 => Modexp::execute(gas_meter, input),
+				6u8 => Bn128Add::execute(gas_meter, input),
+				7u8 => Bn128Mul::execute(gas_meter, input),
+				8u8 => Bn128Pairing::execute(gas_meter, input),
+				9u8 => Blake2F::execute(gas_meter, input),
+				_ => return Err(Error::<T>::UnsupportedPrecompileAddress.into()),
+			}
+		} else if let Some(weight) = T::Precompiles::weight(&addr, input) {
+			Self::execute_registered(&addr, weight, gas_meter, input)
+		} else {
+			return Err(Error::<T>::UnsupportedPrecompileAddress.into())
+		};
+
+		result.map_err(|reason| {
 			log::debug!(target: LOG_TARGET, "Precompile failed: {reason:?}");
 			Error::<T>::PrecompileFailure.into()
 		})
 	}
+
+	/// Executes a precompile registered by the runtime, charging its weight beforehand.
+	fn execute_registered(
+		addr: &H160,
+		weight: Weight,
+		gas_meter: &mut GasMeter<T>,
+		input: &[u8],
+	) -> Result<ExecReturnValue, &'static str> {
+		gas_meter.charge(RuntimePrecompileToken(weight))?;
+		let data = T::Precompiles::execute(addr, input).ok_or("precompile not registered")??;
+		Ok(ExecReturnValue { data, flags: ReturnFlags::empty() })
+	}
 }
 
 #[cfg(test)]
@@ -81,7 +180,6 @@ mod test {
 	use super::*;
 	use crate::{tests::Test, ExecReturnValue, Weight};
 	use alloy_core::hex;
-	use pallet_revive_uapi::ReturnFlags;
 
 	#[derive(Debug, serde::Deserialize)]
 	#[serde(rename_all = "PascalCase")]
@@ -36,7 +36,7 @@ use crate::{
 	weights::WeightInfo,
 	AccountId32Mapper, BalanceOf, Code, CodeInfoOf, Config, ContractInfo, ContractInfoOf,
 	DeletionQueueCounter, DepositLimit, Error, EthTransactError, HoldReason, Origin, Pallet,
-	PristineCode, H160,
+	PristineCode, RuntimePrecompile, H160,
 };
 
 use crate::test_utils::builder::Contract;
@@ -418,6 +418,7 @@ impl pallet_proxy::Config for Test {
 	type WeightInfo = ();
 	type MaxPending = ConstU32<32>;
 	type CallHasher = BlakeTwo256;
+	type AnnouncementFilter = ();
 	type AnnouncementDepositBase = ConstU64<1>;
 	type AnnouncementDepositFactor = ConstU64<1>;
 	type BlockNumberProvider = frame_system::Pallet<Test>;
@@ -520,6 +521,28 @@ impl FindAuthor<<Test as frame_system::Config>::AccountId> for Test {
 	}
 }
 
+/// A precompile registered by the runtime, returning its input data reversed.
+pub struct ReversePrecompile;
+
+impl ReversePrecompile {
+	pub const WEIGHT_PER_BYTE: Weight = Weight::from_parts(1_000_000, 0);
+}
+
+impl RuntimePrecompile for ReversePrecompile {
+	const ADDRESS: [u8; 20] = hex_literal::hex!("000000000000000000000000000000000000a000");
+
+	fn weight(input: &[u8]) -> Weight {
+		Self::WEIGHT_PER_BYTE.saturating_mul(input.len() as u64)
+	}
+
+	fn execute(input: &[u8]) -> Result<Vec<u8>, &'static str> {
+		if input.is_empty() {
+			return Err("empty input")
+		}
+		Ok(input.iter().rev().copied().collect())
+	}
+}
+
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
 impl Config for Test {
 	type Time = Timestamp;
@@ -528,6 +551,7 @@ impl Config for Test {
 	type CallFilter = TestFilter;
 	type ChainExtension =
 		(TestExtension, DisabledExtension, RevertingExtension, TempStorageExtension);
+	type Precompiles = (ReversePrecompile,);
 	type DepositPerByte = DepositPerByte;
 	type DepositPerItem = DepositPerItem;
 	type UnsafeUnstableInterface = UnstableInterface;
@@ -4578,6 +4602,43 @@ fn tracing_works() {
 	});
 }
 
+#[test]
+fn runtime_precompile_works() {
+	let (code, _code_hash) = compile_module("call_and_return").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let precompile_addr = H160(ReversePrecompile::ADDRESS);
+		let id = <Test as Config>::AddressMapper::to_account_id(&precompile_addr);
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
+			.value(1000)
+			.build_and_unwrap_contract();
+		let call_data = |input: &[u8]| {
+			(&precompile_addr, 100u64).encode().into_iter().chain(input.to_vec()).collect()
+		};
+
+		let short = builder::bare_call(addr).data(call_data(&[1, 2, 3])).build();
+		let result = short.result.unwrap();
+		assert_eq!(result.data, vec![3, 2, 1]);
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(test_utils::get_balance(&id), 101u64);
+
+		// the weight of the precompile is charged.
+		let long = builder::bare_call(addr).data(call_data(&[1; 103])).build();
+		assert_ok!(long.result);
+		assert!(
+			long.gas_consumed.ref_time() - short.gas_consumed.ref_time() >=
+				ReversePrecompile::WEIGHT_PER_BYTE.ref_time() * 100
+		);
+
+		// a failing precompile traps the caller.
+		assert_err!(
+			builder::bare_call(addr).data(call_data(&[])).build().result,
+			<Error<Test>>::ContractTrapped
+		);
+	});
+}
+
 #[test]
 fn unknown_precompiles_revert() {
 	let (code, _code_hash) = compile_module("read_only_call").unwrap();
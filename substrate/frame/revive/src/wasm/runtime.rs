@@ -1048,7 +1048,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		output_len_ptr: u32,
 	) -> Result<ReturnErrorCode, TrapReason> {
 		let callee = match memory.read_h160(callee_ptr) {
-			Ok(callee) if is_precompile(&callee) => callee,
+			Ok(callee) if is_precompile::<E::T>(&callee) => callee,
 			Ok(callee) => {
 				self.charge_gas(call_type.cost())?;
 				callee
@@ -387,7 +387,8 @@ mod benchmarks {
 		let name = u32_to_name(s - 1);
 		let address = Lookup::<T>::get(name).unwrap();
 		let period: BlockNumberFor<T> = 1_u32.into();
-		let retry_config = RetryConfig { total_retries: 10, remaining: 10, period };
+		let retry_config =
+			RetryConfig { total_retries: 10, remaining: 10, period, max_period: None };
 		Retries::<T>::insert(address, retry_config);
 		let (mut when, index) = address;
 		let task = Agenda::<T>::get(when)[index as usize].clone().unwrap();
@@ -408,7 +409,7 @@ mod benchmarks {
 		when = when + BlockNumberFor::<T>::one();
 		assert_eq!(
 			Retries::<T>::get((when, 0)),
-			Some(RetryConfig { total_retries: 10, remaining: 9, period })
+			Some(RetryConfig { total_retries: 10, remaining: 9, period, max_period: None })
 		);
 
 		Ok(())
@@ -430,10 +431,11 @@ mod benchmarks {
 
 		assert_eq!(
 			Retries::<T>::get((when, index)),
-			Some(RetryConfig { total_retries: 10, remaining: 10, period })
+			Some(RetryConfig { total_retries: 10, remaining: 10, period, max_period: None })
 		);
 		assert_last_event::<T>(
-			Event::RetrySet { task: address, id: None, period, retries: 10 }.into(),
+			Event::RetrySet { task: address, id: None, period, retries: 10, max_period: None }
+				.into(),
 		);
 
 		Ok(())
@@ -455,10 +457,17 @@ mod benchmarks {
 
 		assert_eq!(
 			Retries::<T>::get((when, index)),
-			Some(RetryConfig { total_retries: 10, remaining: 10, period })
+			Some(RetryConfig { total_retries: 10, remaining: 10, period, max_period: None })
 		);
 		assert_last_event::<T>(
-			Event::RetrySet { task: address, id: Some(name), period, retries: 10 }.into(),
+			Event::RetrySet {
+				task: address,
+				id: Some(name),
+				period,
+				retries: 10,
+				max_period: None,
+			}
+			.into(),
 		);
 
 		Ok(())
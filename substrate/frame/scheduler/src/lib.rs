@@ -134,6 +134,23 @@ pub struct RetryConfig<Period> {
 	remaining: u8,
 	/// Period of time between retry attempts.
 	period: Period,
+	/// If set, the period doubles after every failed attempt, up to this maximum.
+	max_period: Option<Period>,
+}
+
+impl<Period: Saturating + Ord + Copy> RetryConfig<Period> {
+	/// The period of time until the next retry attempt.
+	fn next_period(&self) -> Period {
+		let Some(max_period) = self.max_period else { return self.period };
+		let mut period = self.period;
+		for _ in 0..self.total_retries.saturating_sub(self.remaining) {
+			if period >= max_period {
+				break
+			}
+			period = period.saturating_add(period);
+		}
+		period.min(max_period)
+	}
 }
 
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
@@ -233,7 +250,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::{BlockNumberFor as SystemBlockNumberFor, OriginFor};
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -376,6 +393,7 @@ pub mod pallet {
 			id: Option<TaskName>,
 			period: BlockNumberFor<T>,
 			retries: u8,
+			max_period: Option<BlockNumberFor<T>>,
 		},
 		/// Cancel a retry configuration for some task.
 		RetryCancelled { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
@@ -404,6 +422,8 @@ pub mod pallet {
 		RescheduleNoChange,
 		/// Attempt to use a non-named function on a named task.
 		Named,
+		/// The maximum period of a retry backoff is lower than its initial period.
+		InvalidBackoff,
 	}
 
 	#[pallet::hooks]
@@ -553,19 +573,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::RuntimeOrigin::from(origin);
-			let (when, index) = task;
-			let agenda = Agenda::<T>::get(when);
-			let scheduled = agenda
-				.get(index as usize)
-				.and_then(Option::as_ref)
-				.ok_or(Error::<T>::NotFound)?;
-			Self::ensure_privilege(origin.caller(), &scheduled.origin)?;
-			Retries::<T>::insert(
-				(when, index),
-				RetryConfig { total_retries: retries, remaining: retries, period },
-			);
-			Self::deposit_event(Event::RetrySet { task, id: None, period, retries });
-			Ok(())
+			Self::do_set_retry(origin.caller(), task, None, retries, period, None)
 		}
 
 		/// Set a retry configuration for a named task so that, in case its scheduled run fails, it
@@ -590,24 +598,8 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::RuntimeOrigin::from(origin);
-			let (when, agenda_index) = Lookup::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
-			let agenda = Agenda::<T>::get(when);
-			let scheduled = agenda
-				.get(agenda_index as usize)
-				.and_then(Option::as_ref)
-				.ok_or(Error::<T>::NotFound)?;
-			Self::ensure_privilege(origin.caller(), &scheduled.origin)?;
-			Retries::<T>::insert(
-				(when, agenda_index),
-				RetryConfig { total_retries: retries, remaining: retries, period },
-			);
-			Self::deposit_event(Event::RetrySet {
-				task: (when, agenda_index),
-				id: Some(id),
-				period,
-				retries,
-			});
-			Ok(())
+			let task = Lookup::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+			Self::do_set_retry(origin.caller(), task, Some(id), retries, period, None)
 		}
 
 		/// Removes the retry configuration of a task.
@@ -635,6 +627,62 @@ pub mod pallet {
 			Self::deposit_event(Event::RetryCancelled { task, id: Some(id) });
 			Ok(())
 		}
+
+		/// Set a retry configuration with exponential backoff for a task.
+		///
+		/// Same as [`Pallet::set_retry`], except that the period between two retry attempts
+		/// starts at `period` and doubles after every failed attempt, up to `max_period`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_retry())]
+		pub fn set_retry_with_backoff(
+			origin: OriginFor<T>,
+			task: TaskAddress<BlockNumberFor<T>>,
+			retries: u8,
+			period: BlockNumberFor<T>,
+			max_period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_set_retry(origin.caller(), task, None, retries, period, Some(max_period))
+		}
+
+		/// Set a retry configuration with exponential backoff for a named task.
+		///
+		/// Same as [`Pallet::set_retry_named`], except that the period between two retry attempts
+		/// starts at `period` and doubles after every failed attempt, up to `max_period`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_retry_named())]
+		pub fn set_retry_named_with_backoff(
+			origin: OriginFor<T>,
+			id: TaskName,
+			retries: u8,
+			period: BlockNumberFor<T>,
+			max_period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			let task = Lookup::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+			Self::do_set_retry(origin.caller(), task, Some(id), retries, period, Some(max_period))
+		}
+
+		/// Cancel a named scheduled task, by the origin it was scheduled with.
+		///
+		/// Unlike [`Pallet::cancel_named`], this does not require the `ScheduleOrigin`: the origin
+		/// of the call must be the one the task is dispatched with.
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_named(T::MaxScheduledPerBlock::get()))]
+		pub fn cancel_named_by_origin(origin: OriginFor<T>, id: TaskName) -> DispatchResult {
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			let (when, index) = Lookup::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+			let scheduled_origin = Agenda::<T>::get(when)
+				.get(index as usize)
+				.and_then(Option::as_ref)
+				.map(|scheduled| scheduled.origin.clone())
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(*origin.caller() == scheduled_origin, BadOrigin);
+			Self::do_cancel_named(Some(scheduled_origin), id)?;
+			Ok(())
+		}
 	}
 }
 
@@ -1442,17 +1490,16 @@ impl<T: Config> Pallet<T> {
 			return;
 		}
 
-		let RetryConfig { total_retries, mut remaining, period } = retry_config;
-		remaining = match remaining.checked_sub(1) {
+		let wake = now.saturating_add(retry_config.next_period());
+		let remaining = match retry_config.remaining.checked_sub(1) {
 			Some(n) => n,
 			None => return,
 		};
-		let wake = now.saturating_add(period);
 		match Self::place_task(wake, task.as_retry()) {
 			Ok(address) => {
 				// Reinsert the retry config to the new address of the task after it was
 				// placed.
-				Retries::<T>::insert(address, RetryConfig { total_retries, remaining, period });
+				Retries::<T>::insert(address, RetryConfig { remaining, ..retry_config });
 			},
 			Err((_, task)) => {
 				// TODO: Leave task in storage somewhere for it to be
@@ -1466,6 +1513,37 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Set the retry configuration of the task at `(when, index)`, named `id` if it is a named
+	/// task, given the origin of the caller.
+	fn do_set_retry(
+		origin: &T::PalletsOrigin,
+		(when, index): TaskAddress<BlockNumberFor<T>>,
+		id: Option<TaskName>,
+		retries: u8,
+		period: BlockNumberFor<T>,
+		max_period: Option<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		ensure!(max_period.map_or(true, |max| max >= period), Error::<T>::InvalidBackoff);
+		let agenda = Agenda::<T>::get(when);
+		let scheduled = agenda
+			.get(index as usize)
+			.and_then(Option::as_ref)
+			.ok_or(Error::<T>::NotFound)?;
+		Self::ensure_privilege(origin, &scheduled.origin)?;
+		Retries::<T>::insert(
+			(when, index),
+			RetryConfig { total_retries: retries, remaining: retries, period, max_period },
+		);
+		Self::deposit_event(Event::RetrySet {
+			task: (when, index),
+			id,
+			period,
+			retries,
+			max_period,
+		});
+		Ok(())
+	}
+
 	/// Ensure that `left` has at least the same level of privilege or higher than `right`.
 	///
 	/// Returns an error if `left` has a lower level of privilege or the two cannot be compared.
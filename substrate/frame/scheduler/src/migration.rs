@@ -299,6 +299,67 @@ pub mod v4 {
 	}
 }
 
+pub mod v5 {
+	use super::*;
+	use frame_support::{
+		migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+	};
+
+	/// The retry configuration of the V4 scheduler, without backoff.
+	#[derive(Encode, Decode)]
+	pub(crate) struct RetryConfigV4<Period> {
+		pub(crate) total_retries: u8,
+		pub(crate) remaining: u8,
+		pub(crate) period: Period,
+	}
+
+	/// Adds the backoff to the retry configurations, disabled for all the existing ones.
+	pub struct UncheckedMigrateV4ToV5<T>(core::marker::PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateV4ToV5<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((Retries::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			Retries::<T>::translate::<RetryConfigV4<BlockNumberFor<T>>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(RetryConfig {
+					total_retries: old.total_retries,
+					remaining: old.remaining,
+					period: old.period,
+					max_period: None,
+				})
+			});
+			log::info!(target: TARGET, "Migrated {} retry configurations", translated);
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let retries: u32 =
+				Decode::decode(&mut state.as_ref()).expect("Must decode pre_upgrade state");
+			ensure!(
+				Retries::<T>::iter_values().count() as u32 == retries,
+				"All the retry configurations must be migrated"
+			);
+			Ok(())
+		}
+	}
+
+	/// Migrate the scheduler pallet from V4 to V5.
+	pub type MigrateV4ToV5<T> = VersionedMigration<
+		4,
+		5,
+		UncheckedMigrateV4ToV5<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
 #[cfg(test)]
 #[cfg(feature = "try-runtime")]
 mod test {
@@ -556,6 +617,29 @@ mod test {
 		});
 	}
 
+	#[test]
+	fn migration_v4_to_v5_works() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(4).put::<Scheduler>();
+
+			let old = v5::RetryConfigV4 { total_retries: 10, remaining: 3, period: 2u64 };
+			frame_support::storage::unhashed::put_raw(
+				&Retries::<Test>::hashed_key_for((4, 0)),
+				&old.encode(),
+			);
+
+			let data = v5::MigrateV4ToV5::<Test>::pre_upgrade().unwrap();
+			let _w = v5::MigrateV4ToV5::<Test>::on_runtime_upgrade();
+			v5::MigrateV4ToV5::<Test>::post_upgrade(data).unwrap();
+
+			assert_eq!(
+				Retries::<Test>::get((4, 0)),
+				Some(RetryConfig { total_retries: 10, remaining: 3, period: 2, max_period: None })
+			);
+			assert_eq!(StorageVersion::get::<Scheduler>(), 5);
+		});
+	}
+
 	fn signed(i: u64) -> OriginCaller {
 		system::RawOrigin::Signed(i).into()
 	}
@@ -236,6 +236,84 @@ fn retry_scheduling_works() {
 	});
 }
 
+#[test]
+fn retry_scheduling_with_backoff_works() {
+	new_test_ext().execute_with(|| {
+		// task fails until block 16 is reached
+		Threshold::<Test>::put((16, 100));
+		// task 42 at #4
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		// retry 10 times, first after 2 blocks, doubling the period up to 6 blocks
+		assert_ok!(Scheduler::set_retry_with_backoff(root().into(), (4, 0), 10, 2, 6));
+		assert_eq!(
+			Retries::<Test>::get((4, 0)),
+			Some(RetryConfig { total_retries: 10, remaining: 10, period: 2, max_period: Some(6) })
+		);
+		// task should be retried in block 6
+		System::run_to_block::<AllPalletsWithSystem>(4);
+		assert!(Agenda::<Test>::get(4).is_empty());
+		assert!(Agenda::<Test>::get(6)[0].is_some());
+		// then in block 10
+		System::run_to_block::<AllPalletsWithSystem>(6);
+		assert!(Agenda::<Test>::get(6).is_empty());
+		assert!(Agenda::<Test>::get(10)[0].is_some());
+		// the period is capped, next retry in block 16
+		System::run_to_block::<AllPalletsWithSystem>(10);
+		assert!(Agenda::<Test>::get(10).is_empty());
+		assert!(Agenda::<Test>::get(16)[0].is_some());
+		assert!(logger::log().is_empty());
+		// finally it should succeed
+		System::run_to_block::<AllPalletsWithSystem>(16);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn set_retry_with_invalid_backoff_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Scheduler::do_schedule_named(
+			[42u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		// the maximum period must not be lower than the initial one
+		assert_noop!(
+			Scheduler::set_retry_named_with_backoff(root().into(), [42u8; 32], 10, 3, 2),
+			Error::<Test>::InvalidBackoff
+		);
+		assert_ok!(Scheduler::set_retry_named_with_backoff(root().into(), [42u8; 32], 10, 3, 3));
+		System::assert_last_event(
+			crate::Event::RetrySet {
+				task: (4, 0),
+				id: Some([42u8; 32]),
+				period: 3,
+				retries: 10,
+				max_period: Some(3),
+			}
+			.into(),
+		);
+	});
+}
+
 #[test]
 fn named_retry_scheduling_works() {
 	new_test_ext().execute_with(|| {
@@ -893,7 +971,7 @@ fn set_retry_works() {
 		assert_ok!(Scheduler::set_retry(root().into(), (4, 0), 10, 2));
 		assert_eq!(
 			Retries::<Test>::get((4, 0)),
-			Some(RetryConfig { total_retries: 10, remaining: 10, period: 2 })
+			Some(RetryConfig { total_retries: 10, remaining: 10, period: 2, max_period: None })
 		);
 	});
 }
@@ -921,7 +999,7 @@ fn set_named_retry_works() {
 		let address = Lookup::<Test>::get([42u8; 32]).unwrap();
 		assert_eq!(
 			Retries::<Test>::get(address),
-			Some(RetryConfig { total_retries: 10, remaining: 10, period: 2 })
+			Some(RetryConfig { total_retries: 10, remaining: 10, period: 2, max_period: None })
 		);
 	});
 }
@@ -1855,6 +1933,40 @@ fn should_check_origin_for_cancel() {
 	});
 }
 
+#[test]
+fn cancel_named_by_origin_works() {
+	new_test_ext().execute_with(|| {
+		// task 42 at #4 with account 101 as origin, which is not a `ScheduleOrigin`
+		assert_ok!(Scheduler::do_schedule_named(
+			[42u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			101.into(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		assert_noop!(Scheduler::cancel_named(RuntimeOrigin::signed(101), [42u8; 32]), BadOrigin);
+		// only the origin of the task can cancel it
+		assert_noop!(
+			Scheduler::cancel_named_by_origin(RuntimeOrigin::signed(102), [42u8; 32]),
+			BadOrigin
+		);
+		assert_noop!(Scheduler::cancel_named_by_origin(root().into(), [42u8; 32]), BadOrigin);
+		assert_ok!(Scheduler::cancel_named_by_origin(RuntimeOrigin::signed(101), [42u8; 32]));
+		assert!(Lookup::<Test>::get([42u8; 32]).is_none());
+		assert_noop!(
+			Scheduler::cancel_named_by_origin(RuntimeOrigin::signed(101), [42u8; 32]),
+			Error::<Test>::NotFound
+		);
+		System::run_to_block::<AllPalletsWithSystem>(4);
+		assert!(logger::log().is_empty());
+	});
+}
+
 #[test]
 fn cancel_removes_retry_entry() {
 	new_test_ext().execute_with(|| {
@@ -23,13 +23,14 @@ use frame_support::{
 	parameter_types,
 	traits::{
 		fungible::HoldConsideration, tokens::UnityOrOuterConversion, EitherOf, EitherOfDiverse,
-		FromContains, LinearStoragePrice,
+		FromContains, LinearStoragePrice, MapSuccess,
 	},
 };
-use frame_system::EnsureRootWithSuccess;
+use frame_system::{EnsureRootWithSuccess, EnsureSigned};
 use pallet_xcm::{EnsureXcm, IsVoiceOfBody};
 use polkadot_runtime_common::impls::{
-	ContainsParts, LocatableAssetConverter, VersionedLocatableAsset, VersionedLocationConverter,
+	AccountIdToBeneficiary, ContainsParts, LocatableAssetConverter, VersionedLocatableAsset,
+	VersionedLocationConverter,
 };
 use sp_runtime::{traits::IdentityLookup, Percent};
 use xcm::latest::{
@@ -146,6 +147,7 @@ impl pallet_treasury::Config for Runtime {
 	type AssetKind = VersionedLocatableAsset;
 	type Beneficiary = VersionedLocation;
 	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type PartialPayoutOrigin = MapSuccess<EnsureSigned<AccountId>, AccountIdToBeneficiary>;
 	type Paymaster = PayOverXcm<
 		TreasuryInteriorLocation,
 		crate::xcm_config::XcmRouter,
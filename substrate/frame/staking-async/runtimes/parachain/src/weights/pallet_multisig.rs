@@ -169,4 +169,22 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Not benchmarked yet. Bounded by `as_multi_create`, which checks the signatories and
+	/// hashes the call the same way, plus the access to the stored call.
+	/// Storage: `Multisig::StoredCalls` (r:1 w:1)
+	/// Proof: `Multisig::StoredCalls` (`max_values`: None, `max_size`: Some(125), added: 2600, mode: `MaxEncodedLen`)
+	fn store_call(s: u32, z: u32, ) -> Weight {
+		<Self as pallet_multisig::WeightInfo>::as_multi_create(s, z)
+			.saturating_add(Weight::from_parts(0, 2600))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Not benchmarked yet. Bounded by `cancel_as_multi`, which removes an entry and returns
+	/// its deposit the same way, plus the proof of the stored call.
+	/// Storage: `Multisig::StoredCalls` (r:1 w:1)
+	/// Proof: `Multisig::StoredCalls` (`max_values`: None, `max_size`: Some(125), added: 2600, mode: `MaxEncodedLen`)
+	fn remove_stored_call() -> Weight {
+		<Self as pallet_multisig::WeightInfo>::cancel_as_multi(2)
+			.saturating_add(Weight::from_parts(0, 2600))
+	}
 }
@@ -695,6 +695,33 @@ mod benchmarks {
 		assert_eq!(UnappliedSlashes::<T>::get(&era).len(), (MAX_SLASHES - s) as usize);
 	}
 
+	#[benchmark]
+	fn set_slash_defer_duration() {
+		let kind = *b"babe:equivocatio";
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, kind, Some(0));
+
+		assert_eq!(SlashDeferDurations::<T>::get(kind), Some(0));
+	}
+
+	#[benchmark]
+	fn reduce_deferred_slash() {
+		let mut unapplied_slashes = Vec::new();
+		let era = EraIndex::one();
+		let dummy = || T::AccountId::decode(&mut TrailingZeroInput::zeroes()).unwrap();
+		for _ in 0..MAX_SLASHES {
+			unapplied_slashes
+				.push(UnappliedSlash::<T::AccountId, BalanceOf<T>>::default_from(dummy()));
+		}
+		UnappliedSlashes::<T>::insert(era, &unapplied_slashes);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, era, MAX_SLASHES - 1, Perbill::from_percent(50));
+
+		assert_eq!(UnappliedSlashes::<T>::get(&era).len(), MAX_SLASHES as usize);
+	}
+
 	#[benchmark]
 	fn payout_stakers_alive_staked(
 		n: Linear<0, { T::MaxExposurePageSize::get() as u32 }>,
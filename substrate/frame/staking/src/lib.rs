@@ -385,6 +385,18 @@ pub struct ActiveEraInfo {
 	pub start: Option<u64>,
 }
 
+/// Position of the automatic payouts in the rewards of past eras.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AutoPayoutCursor<AccountId> {
+	/// The era whose rewards are being paid out.
+	pub era: EraIndex,
+	/// The validator of `era` paid out last in the current round.
+	pub last_validator: Option<AccountId>,
+	/// Whether any page was paid out in the current round. The era is complete once a whole round
+	/// pays out nothing.
+	pub paid_in_round: bool,
+}
+
 /// Reward points of an era. Used to split era total payout between validators.
 ///
 /// This points will be used to reward validators and their respective nominators.
@@ -878,6 +890,8 @@ pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
 	reporters: Vec<AccountId>,
 	/// The amount of payout.
 	payout: Balance,
+	/// The era in which the offence was committed.
+	slash_era: EraIndex,
 }
 
 impl<AccountId, Balance: HasCompact + Zero> UnappliedSlash<AccountId, Balance> {
@@ -889,10 +903,24 @@ impl<AccountId, Balance: HasCompact + Zero> UnappliedSlash<AccountId, Balance> {
 			others: vec![],
 			reporters: vec![],
 			payout: Zero::zero(),
+			slash_era: Zero::zero(),
 		}
 	}
 }
 
+impl<AccountId, Balance: HasCompact + AtLeast32BitUnsigned + Copy>
+	UnappliedSlash<AccountId, Balance>
+{
+	/// Reduces all the amounts of the slash, and the payout of the reporters, by `fraction`.
+	pub(crate) fn reduce(&mut self, fraction: Perbill) {
+		let reduce = |amount: &mut Balance| *amount = amount.saturating_sub(fraction * *amount);
+
+		reduce(&mut self.own);
+		self.others.iter_mut().for_each(|(_, amount)| reduce(amount));
+		reduce(&mut self.payout);
+	}
+}
+
 /// Something that defines the maximum number of nominations per nominator based on a curve.
 ///
 /// The method `curve` implements the nomination quota curve and should not be used directly.
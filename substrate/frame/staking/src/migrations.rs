@@ -53,10 +53,90 @@ impl Default for ObsoleteReleases {
 #[storage_alias]
 type StorageVersion<T: Config> = StorageValue<Pallet<T>, ObsoleteReleases, ValueQuery>;
 
-/// Supports the migration of Validator Disabling from pallet-staking to pallet-session
+/// Supports the migration of Validator Disabling from pallet-staking to pallet-session, and
+/// records the era of the offence in the `UnappliedSlashes`, which can no longer be derived from
+/// the era they are applied in since slashes are deferred per offence kind.
 pub mod v17 {
 	use super::*;
 
+	/// [`UnappliedSlash`] before the era of the offence was added.
+	#[derive(Encode, Decode)]
+	struct OldUnappliedSlash<AccountId, Balance> {
+		validator: AccountId,
+		own: Balance,
+		others: Vec<(AccountId, Balance)>,
+		reporters: Vec<AccountId>,
+		payout: Balance,
+	}
+
+	pub struct VersionUncheckedMigrateV16ToV17<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV16ToV17<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let slashes = UnappliedSlashes::<T>::iter_keys()
+				.map(|era| (era, UnappliedSlashes::<T>::decode_len(era).unwrap_or_default() as u32))
+				.collect::<Vec<_>>();
+			Ok(slashes.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			// The slashes were all deferred by `SlashDeferDuration`, the era of the offence is the
+			// one the slashes used to be applied with.
+			let slash_defer_duration = T::SlashDeferDuration::get();
+			let mut translated = 0u64;
+
+			UnappliedSlashes::<T>::translate::<Vec<OldUnappliedSlash<T::AccountId, BalanceOf<T>>>, _>(
+				|era, old_slashes| {
+					translated.saturating_inc();
+					let slash_era = era.saturating_sub(slash_defer_duration);
+					Some(
+						old_slashes
+							.into_iter()
+							.map(|old| UnappliedSlash {
+								validator: old.validator,
+								own: old.own,
+								others: old.others,
+								reporters: old.reporters,
+								payout: old.payout,
+								slash_era,
+							})
+							.collect(),
+					)
+				},
+			);
+
+			log!(
+				info,
+				"v17 applied successfully, {} eras of unapplied slashes migrated.",
+				translated
+			);
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let old_slashes = Vec::<(EraIndex, u32)>::decode(&mut state.as_slice())
+				.expect("Failed to decode state");
+
+			for (era, count) in old_slashes {
+				frame_support::ensure!(
+					UnappliedSlashes::<T>::get(era).len() == count as usize,
+					"UnappliedSlashes length mismatch"
+				);
+			}
+
+			Ok(())
+		}
+	}
+
+	pub type MigrateV16ToV17<T> = VersionedMigration<
+		16,
+		17,
+		VersionUncheckedMigrateV16ToV17<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+
 	#[frame_support::storage_alias]
 	pub type DisabledValidators<T: Config> =
 		StorageValue<Pallet<T>, BoundedVec<(u32, OffenceSeverity), ConstU32<333>>, ValueQuery>;
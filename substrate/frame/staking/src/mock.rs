@@ -35,7 +35,7 @@ use sp_core::ConstBool;
 use sp_io;
 use sp_runtime::{curve::PiecewiseLinear, testing::UintAuthorityId, traits::Zero, BuildStorage};
 use sp_staking::{
-	offence::{OffenceDetails, OnOffenceHandler},
+	offence::{Kind, OffenceDetails, OnOffenceHandler},
 	OnStakingUpdate, StakingAccount,
 };
 
@@ -118,6 +118,7 @@ parameter_types! {
 	pub static Period: BlockNumber = 5;
 	pub static Offset: BlockNumber = 0;
 	pub static MaxControllersInDeprecationBatch: u32 = 5900;
+	pub static MaxAutoPayoutWeight: Weight = Weight::zero();
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -305,6 +306,7 @@ impl crate::pallet::pallet::Config for Test {
 	type MaxUnlockingChunks = MaxUnlockingChunks;
 	type HistoryDepth = HistoryDepth;
 	type MaxControllersInDeprecationBatch = MaxControllersInDeprecationBatch;
+	type MaxAutoPayoutWeight = MaxAutoPayoutWeight;
 	type EventListeners = EventListenerMock;
 	type Filter = MockedRestrictList;
 }
@@ -777,6 +779,23 @@ pub(crate) fn on_offence_now(
 	on_offence_in_era(offenders, slash_fraction, now)
 }
 
+pub(crate) fn on_offence_of_kind_now(
+	kind: &Kind,
+	offenders: &[OffenceDetails<
+		AccountId,
+		pallet_session::historical::IdentificationTuple<Test>,
+	>],
+	slash_fraction: &[Perbill],
+) {
+	let now = pallet_staking::ActiveEra::<Test>::get().unwrap().index;
+	let _ = <Staking as OnOffenceHandler<_, _, _>>::on_offence_of_kind(
+		kind,
+		offenders,
+		slash_fraction,
+		pallet_staking::ErasStartSessionIndex::<Test>::get(now).unwrap(),
+	);
+}
+
 pub(crate) fn offence_from(
 	offender: AccountId,
 	reporter: Option<Vec<AccountId>>,
@@ -43,7 +43,7 @@ use sp_runtime::{
 };
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
-	offence::{OffenceDetails, OnOffenceHandler},
+	offence::{Kind, OffenceDetails, OnOffenceHandler},
 	EraIndex, OnStakingUpdate, Page, SessionIndex, Stake,
 	StakingAccount::{self, Controller, Stash},
 	StakingInterface,
@@ -51,9 +51,10 @@ use sp_staking::{
 
 use crate::{
 	asset, election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
-	BalanceOf, EraInfo, EraPayout, Exposure, Forcing, IndividualExposure, LedgerIntegrityState,
-	MaxNominationsOf, MaxWinnersOf, Nominations, NominationsQuota, PositiveImbalanceOf,
-	RewardDestination, SessionInterface, StakingLedger, UnlockChunk, ValidatorPrefs, STAKING_ID,
+	AutoPayoutCursor, BalanceOf, EraInfo, EraPayout, Exposure, Forcing, IndividualExposure,
+	LedgerIntegrityState, MaxNominationsOf, MaxWinnersOf, Nominations, NominationsQuota,
+	PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger, UnlockChunk,
+	ValidatorPrefs, STAKING_ID,
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 
@@ -396,6 +397,91 @@ impl<T: Config> Pallet<T> {
 		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count)).into())
 	}
 
+	/// Pay out the unclaimed rewards of past eras, using at most `budget`.
+	///
+	/// The oldest era still in the history is paid out first. Its validators take turns, one page
+	/// each, so that validators with many pages don't delay the others.
+	pub(crate) fn do_auto_payouts(budget: Weight) -> Weight {
+		let db = T::DbWeight::get();
+		let payout_weight =
+			T::WeightInfo::payout_stakers_alive_staked(T::MaxExposurePageSize::get());
+		// Finding the next validator, reading its ledger and claimed pages, then paying it out.
+		let step_weight = payout_weight.saturating_add(db.reads(5));
+		// Reading the active era and the progress, writing the progress.
+		let mut used = db.reads_writes(2, 1);
+		if used.saturating_add(step_weight).any_gt(budget) {
+			return Weight::zero()
+		}
+
+		let Some(active_era) = ActiveEra::<T>::get().map(|era| era.index) else { return used };
+		let oldest_era = active_era.saturating_sub(T::HistoryDepth::get());
+		let mut cursor = AutoPayoutProgress::<T>::get()
+			.filter(|cursor| cursor.era >= oldest_era)
+			.unwrap_or(AutoPayoutCursor {
+				era: oldest_era,
+				last_validator: None,
+				paid_in_round: false,
+			});
+		let mut pages = 0;
+
+		while cursor.era < active_era && used.saturating_add(step_weight).all_lte(budget) {
+			used.saturating_accrue(db.reads(2));
+			let next_validator = match &cursor.last_validator {
+				Some(last) => ErasStakersOverview::<T>::iter_key_prefix_from(
+					cursor.era,
+					ErasStakersOverview::<T>::hashed_key_for(cursor.era, last),
+				)
+				.next(),
+				None if ErasValidatorReward::<T>::contains_key(cursor.era) =>
+					ErasStakersOverview::<T>::iter_key_prefix(cursor.era).next(),
+				// Nothing to pay out in this era.
+				None => None,
+			};
+
+			let Some(validator) = next_validator else {
+				if cursor.paid_in_round {
+					cursor.last_validator = None;
+					cursor.paid_in_round = false;
+				} else {
+					// Eras without rewards have no validators to pay out.
+					if cursor.last_validator.is_some() {
+						Self::deposit_event(Event::<T>::AutoPayoutsCompleted { era: cursor.era });
+					}
+					cursor = AutoPayoutCursor {
+						era: cursor.era + 1,
+						last_validator: None,
+						paid_in_round: false,
+					};
+				}
+				continue
+			};
+
+			used.saturating_accrue(db.reads(3));
+			let next_page =
+				Self::ledger(StakingAccount::Stash(validator.clone())).ok().and_then(|ledger| {
+					EraInfo::<T>::get_next_claimable_page(cursor.era, &validator, &ledger)
+				});
+			if let Some(page) = next_page {
+				let actual_weight =
+					match Self::do_payout_stakers_by_page(validator.clone(), cursor.era, page) {
+						Ok(info) => info.actual_weight,
+						Err(err) => err.post_info.actual_weight,
+					};
+				used.saturating_accrue(actual_weight.unwrap_or(payout_weight));
+				pages += 1;
+				cursor.paid_in_round = true;
+			}
+			cursor.last_validator = Some(validator);
+		}
+
+		if pages > 0 {
+			Self::deposit_event(Event::<T>::AutoPayoutsProcessed { pages, era: cursor.era });
+		}
+		AutoPayoutProgress::<T>::put(cursor);
+
+		used
+	}
+
 	/// Chill a stash account.
 	pub(crate) fn chill_stash(stash: &T::AccountId) {
 		let chilled_as_validator = Self::do_remove_validator(stash);
@@ -829,7 +915,7 @@ impl<T: Config> Pallet<T> {
 			active_era,
 		);
 		for slash in era_slashes {
-			let slash_era = active_era.saturating_sub(T::SlashDeferDuration::get());
+			let slash_era = slash.slash_era;
 			slashing::apply_slash::<T>(slash, slash_era);
 		}
 	}
@@ -1249,6 +1335,17 @@ impl<T: Config> Pallet<T> {
 		offenders: impl Iterator<Item = OffenceDetails<T::AccountId, T::AccountId>>,
 		slash_fractions: &[Perbill],
 		slash_session: SessionIndex,
+	) -> Weight {
+		Self::on_offence_of_kind(None, offenders, slash_fractions, slash_session)
+	}
+
+	/// Same as [`Self::on_offence`], deferring the slashes by the duration configured for the
+	/// offence `kind`, if any.
+	pub fn on_offence_of_kind(
+		kind: Option<&Kind>,
+		offenders: impl Iterator<Item = OffenceDetails<T::AccountId, T::AccountId>>,
+		slash_fractions: &[Perbill],
+		slash_session: SessionIndex,
 	) -> Weight {
 		let reward_proportion = SlashRewardFraction::<T>::get();
 		let mut consumed_weight = Weight::from_parts(0, 0);
@@ -1296,7 +1393,18 @@ impl<T: Config> Pallet<T> {
 
 		add_db_reads_writes(1, 1);
 
-		let slash_defer_duration = T::SlashDeferDuration::get();
+		let slash_defer_duration = Self::slash_defer_duration(kind);
+		if kind.is_some() {
+			add_db_reads_writes(1, 0);
+		}
+		// Deferred slashes are applied at the start of an era, which must be still to come for a
+		// late report. They must also be applied within the bonding duration of the slash era,
+		// before the slashed stake can be withdrawn.
+		let defer_to = slash_era
+			.saturating_add(slash_defer_duration)
+			.saturating_add(One::one())
+			.max(active_era.saturating_add(One::one()))
+			.min(slash_era.saturating_add(T::BondingDuration::get()));
 
 		let invulnerables = Invulnerables::<T>::get();
 		add_db_reads_writes(1, 0);
@@ -1346,7 +1454,7 @@ impl<T: Config> Pallet<T> {
 					add_db_reads_writes(rw, rw);
 				}
 				unapplied.reporters = details.reporters.clone();
-				if slash_defer_duration == 0 {
+				if slash_defer_duration == 0 || defer_to <= active_era {
 					// Apply right away.
 					slashing::apply_slash::<T>(unapplied, slash_era);
 					{
@@ -1365,12 +1473,11 @@ impl<T: Config> Pallet<T> {
 						slash_fraction,
 						slash_era,
 						active_era,
-						slash_era + slash_defer_duration + 1,
-					);
-					UnappliedSlashes::<T>::mutate(
-						slash_era.saturating_add(slash_defer_duration).saturating_add(One::one()),
-						move |for_later| for_later.push(unapplied),
+						defer_to,
 					);
+					UnappliedSlashes::<T>::mutate(defer_to, move |for_later| {
+						for_later.push(unapplied)
+					});
 					add_db_reads_writes(1, 1);
 				}
 			} else {
@@ -1381,6 +1488,14 @@ impl<T: Config> Pallet<T> {
 		consumed_weight
 	}
 
+	/// The number of eras the slashes for offences of the given `kind` are deferred by.
+	///
+	/// Falls back to [`Config::SlashDeferDuration`] for the kinds without a configured duration.
+	pub fn slash_defer_duration(kind: Option<&Kind>) -> EraIndex {
+		kind.and_then(|kind| SlashDeferDurations::<T>::get(kind))
+			.unwrap_or_else(T::SlashDeferDuration::get)
+	}
+
 	/// Unbonds a controller.
 	pub(crate) fn do_unbond(
 		controller: T::AccountId,
@@ -1773,6 +1888,32 @@ where
 
 		Self::on_offence(offenders, slash_fractions, slash_session)
 	}
+
+	fn on_offence_of_kind(
+		kind: &Kind,
+		offenders: &[OffenceDetails<
+			T::AccountId,
+			pallet_session::historical::IdentificationTuple<T>,
+		>],
+		slash_fractions: &[Perbill],
+		slash_session: SessionIndex,
+	) -> Weight {
+		log!(
+			debug,
+			"🦹 on_offence_of_kind: kind={:?}, offenders={:?}, slash_fractions={:?}, slash_session={}",
+			kind,
+			offenders,
+			slash_fractions,
+			slash_session,
+		);
+
+		let offenders = offenders.iter().map(|details| {
+			let (ref offender, _) = details.offender;
+			OffenceDetails { offender: offender.clone(), reporters: details.reporters.clone() }
+		});
+
+		Self::on_offence_of_kind(Some(kind), offenders, slash_fractions, slash_session)
+	}
 }
 
 impl<T: Config> ScoreProvider<T::AccountId> for Pallet<T> {
@@ -40,6 +40,7 @@ use sp_runtime::{
 };
 
 use sp_staking::{
+	offence::Kind,
 	EraIndex, Page, SessionIndex,
 	StakingAccount::{self, Controller, Stash},
 	StakingInterface,
@@ -50,10 +51,11 @@ mod impls;
 pub use impls::*;
 
 use crate::{
-	asset, slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, EraPayout,
-	EraRewardPoints, Exposure, ExposurePage, Forcing, LedgerIntegrityState, MaxNominationsOf,
-	NegativeImbalanceOf, Nominations, NominationsQuota, PositiveImbalanceOf, RewardDestination,
-	SessionInterface, StakingLedger, UnappliedSlash, UnlockChunk, ValidatorPrefs,
+	asset, slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, AutoPayoutCursor,
+	BalanceOf, EraPayout, EraRewardPoints, Exposure, ExposurePage, Forcing, LedgerIntegrityState,
+	MaxNominationsOf, NegativeImbalanceOf, Nominations, NominationsQuota, PositiveImbalanceOf,
+	RewardDestination, SessionInterface, StakingLedger, UnappliedSlash, UnlockChunk,
+	ValidatorPrefs,
 };
 
 // The speculative number of spans are used as an input of the weight annotation of
@@ -70,7 +72,7 @@ pub mod pallet {
 	use crate::{BenchmarkingConfig, PagedExposureMetadata};
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(16);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(17);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -216,12 +218,15 @@ pub mod pallet {
 		///
 		/// This should be less than the bonding duration. Set to 0 if slashes
 		/// should be applied immediately, without opportunity for intervention.
+		///
+		/// Can be overridden per offence kind, see [`SlashDeferDurations`].
 		#[pallet::constant]
 		type SlashDeferDuration: Get<EraIndex>;
 
 		/// The origin which can manage less critical staking parameters that does not require root.
 		///
-		/// Supported actions: (1) cancel deferred slash, (2) set minimum commission.
+		/// Supported actions: (1) cancel or reduce deferred slash, (2) set minimum commission,
+		/// (3) set the slash deferral of an offence kind.
 		#[pallet::no_default]
 		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
@@ -309,6 +314,14 @@ pub mod pallet {
 		/// The maximum amount of controller accounts that can be deprecated in one call.
 		type MaxControllersInDeprecationBatch: Get<u32>;
 
+		/// The maximum weight spent in `on_idle` on paying out the rewards of past eras which no
+		/// one claimed yet.
+		///
+		/// The oldest era is paid out first, its validators taking turns one page at a time. Zero
+		/// disables the automatic payouts.
+		#[pallet::constant]
+		type MaxAutoPayoutWeight: Get<Weight>;
+
 		/// Something that listens to staking updates and performs actions based on the data it
 		/// receives.
 		///
@@ -379,6 +392,7 @@ pub mod pallet {
 			type MaxUnlockingChunks = ConstU32<32>;
 			type MaxValidatorSet = ConstU32<100>;
 			type MaxControllersInDeprecationBatch = ConstU32<100>;
+			type MaxAutoPayoutWeight = ();
 			type EventListeners = ();
 			type Filter = Nothing;
 			#[cfg(feature = "std")]
@@ -688,6 +702,11 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Number of eras the slashes for offences of a given kind are deferred by, overriding
+	/// [`Config::SlashDeferDuration`].
+	#[pallet::storage]
+	pub type SlashDeferDurations<T> = StorageMap<_, Twox64Concat, Kind, EraIndex, OptionQuery>;
+
 	/// A mapping from still-bonded eras to the first session index of that era.
 	///
 	/// Must contains information for eras for the range:
@@ -742,6 +761,11 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ChillThreshold<T: Config> = StorageValue<_, Percent, OptionQuery>;
 
+	/// How far the automatic payouts of [`Config::MaxAutoPayoutWeight`] got.
+	#[pallet::storage]
+	pub type AutoPayoutProgress<T: Config> =
+		StorageValue<_, AutoPayoutCursor<T::AccountId>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -879,6 +903,15 @@ pub mod pallet {
 		/// Staking balance migrated from locks to holds, with any balance that could not be held
 		/// is force withdrawn.
 		CurrencyMigrated { stash: T::AccountId, force_withdraw: BalanceOf<T> },
+		/// The slashes for offences of `kind` are now deferred by `duration` eras, or by
+		/// [`Config::SlashDeferDuration`] eras if `None`.
+		SlashDeferDurationSet { kind: Kind, duration: Option<EraIndex> },
+		/// A deferred slash of `validator`, to be applied in `era`, was reduced by `fraction`.
+		SlashReduced { era: EraIndex, validator: T::AccountId, fraction: Perbill },
+		/// `pages` pages of rewards were paid out automatically, the payouts continue with `era`.
+		AutoPayoutsProcessed { pages: u32, era: EraIndex },
+		/// All the rewards of `era` are paid out.
+		AutoPayoutsCompleted { era: EraIndex },
 	}
 
 	#[pallet::error]
@@ -957,6 +990,8 @@ pub mod pallet {
 		/// Account is restricted from participation in staking. This may happen if the account is
 		/// staking in another way already, such as via pool.
 		Restricted,
+		/// Slashes can't be deferred by as many eras as the bonding duration or more.
+		InvalidSlashDeferDuration,
 	}
 
 	#[pallet::hooks]
@@ -966,6 +1001,15 @@ pub mod pallet {
 			T::DbWeight::get().reads(1)
 		}
 
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let budget = remaining_weight.min(T::MaxAutoPayoutWeight::get());
+			if budget.is_zero() {
+				return Weight::zero()
+			}
+
+			Self::do_auto_payouts(budget)
+		}
+
 		fn on_finalize(_n: BlockNumberFor<T>) {
 			// Set the start of the first era.
 			if let Some(mut active_era) = ActiveEra::<T>::get() {
@@ -2329,6 +2373,65 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Set the number of eras the slashes for offences of `kind` are deferred by, or reset it
+		/// to `T::SlashDeferDuration` if `None`.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		///
+		/// Only the offences reported afterwards are affected, the slashes already deferred are
+		/// applied as scheduled.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::set_slash_defer_duration())]
+		pub fn set_slash_defer_duration(
+			origin: OriginFor<T>,
+			kind: Kind,
+			duration: Option<EraIndex>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if let Some(duration) = duration {
+				ensure!(
+					duration < T::BondingDuration::get() || T::BondingDuration::get() == 0,
+					Error::<T>::InvalidSlashDeferDuration
+				);
+			}
+
+			SlashDeferDurations::<T>::set(kind, duration);
+			Self::deposit_event(Event::<T>::SlashDeferDurationSet { kind, duration });
+			Ok(())
+		}
+
+		/// Reduce a deferred slash by `fraction`, the rest of it is still applied.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		///
+		/// Parameters: era and index of the slash for that era to reduce, as in
+		/// [`Call::cancel_deferred_slash`]. The payout of the reporters is reduced by the same
+		/// fraction.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::reduce_deferred_slash())]
+		pub fn reduce_deferred_slash(
+			origin: OriginFor<T>,
+			era: EraIndex,
+			slash_index: u32,
+			fraction: Perbill,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			UnappliedSlashes::<T>::try_mutate(&era, |unapplied| -> DispatchResult {
+				let slash =
+					unapplied.get_mut(slash_index as usize).ok_or(Error::<T>::InvalidSlashIndex)?;
+				slash.reduce(fraction);
+
+				Self::deposit_event(Event::<T>::SlashReduced {
+					era,
+					validator: slash.validator.clone(),
+					fraction,
+				});
+				Ok(())
+			})
+		}
 	}
 }
 
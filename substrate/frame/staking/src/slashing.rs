@@ -292,6 +292,7 @@ pub(crate) fn compute_slash<T: Config>(
 		others: nominators_slashed,
 		reporters: Vec::new(),
 		payout: reward_payout,
+		slash_era: params.slash_era,
 	})
 }
 
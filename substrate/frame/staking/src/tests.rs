@@ -3159,6 +3159,125 @@ fn remove_multi_deferred() {
 		})
 }
 
+#[test]
+fn slash_defer_duration_per_offence_kind() {
+	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
+		let kind = *b"babe:equivocatio";
+		mock::start_active_era(1);
+
+		assert_noop!(
+			Staking::set_slash_defer_duration(RuntimeOrigin::signed(2), kind, Some(1)),
+			BadOrigin
+		);
+		// must be less than the bonding duration.
+		assert_noop!(
+			Staking::set_slash_defer_duration(RuntimeOrigin::root(), kind, Some(3)),
+			Error::<Test>::InvalidSlashDeferDuration
+		);
+		assert_ok!(Staking::set_slash_defer_duration(RuntimeOrigin::root(), kind, Some(1)));
+		assert_eq!(
+			staking_events_since_last_call().last(),
+			Some(&Event::SlashDeferDurationSet { kind, duration: Some(1) })
+		);
+		assert_eq!(Staking::slash_defer_duration(Some(&kind)), 1);
+		assert_eq!(Staking::slash_defer_duration(Some(b"im-online:offlin")), 2);
+		assert_eq!(Staking::slash_defer_duration(None), 2);
+
+		let balance_21 = asset::stakeable_balance::<Test>(&21);
+		on_offence_of_kind_now(&kind, &[offence_from(11, None)], &[Perbill::from_percent(10)]);
+		on_offence_now(&[offence_from(21, None)], &[Perbill::from_percent(10)]);
+
+		// the offence of the configured kind is deferred by one era less.
+		assert_eq!(UnappliedSlashes::<Test>::get(&3).len(), 1);
+		assert_eq!(UnappliedSlashes::<Test>::get(&4).len(), 1);
+
+		mock::start_active_era(3);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), balance_21);
+
+		mock::start_active_era(4);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), balance_21 - 100);
+
+		assert_ok!(Staking::set_slash_defer_duration(RuntimeOrigin::root(), kind, None));
+		assert_eq!(Staking::slash_defer_duration(Some(&kind)), 2);
+	})
+}
+
+#[test]
+fn late_reports_are_not_deferred_to_a_past_era() {
+	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
+		let kind = *b"babe:equivocatio";
+		assert_ok!(Staking::set_slash_defer_duration(RuntimeOrigin::root(), kind, Some(1)));
+		mock::start_active_era(3);
+
+		// reported for era 1, the slash would be deferred to era 3, which already started.
+		let slash_session = ErasStartSessionIndex::<Test>::get(1).unwrap();
+		let _ = <Staking as OnOffenceHandler<_, _, _>>::on_offence_of_kind(
+			&kind,
+			&[offence_from(11, None)],
+			&[Perbill::from_percent(10)],
+			slash_session,
+		);
+		assert!(UnappliedSlashes::<Test>::get(&3).is_empty());
+		assert_eq!(UnappliedSlashes::<Test>::get(&4).len(), 1);
+
+		mock::start_active_era(4);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 900);
+	})
+}
+
+#[test]
+fn reduce_deferred_slash_works() {
+	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
+		mock::start_active_era(1);
+
+		let exposure = Staking::eras_stakers(active_era(), &11);
+		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
+		assert_eq!(asset::stakeable_balance::<Test>(&101), 2000);
+
+		// deferred to start of era 4.
+		on_offence_now(&[offence_from(11, None)], &[Perbill::from_percent(10)]);
+
+		assert_noop!(
+			Staking::reduce_deferred_slash(
+				RuntimeOrigin::signed(2),
+				4,
+				0,
+				Perbill::from_percent(50)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Staking::reduce_deferred_slash(RuntimeOrigin::root(), 4, 1, Perbill::from_percent(50)),
+			Error::<Test>::InvalidSlashIndex
+		);
+		assert_ok!(Staking::reduce_deferred_slash(
+			RuntimeOrigin::root(),
+			4,
+			0,
+			Perbill::from_percent(50)
+		));
+		assert_eq!(
+			staking_events_since_last_call().last(),
+			Some(&Event::SlashReduced {
+				era: 4,
+				validator: 11,
+				fraction: Perbill::from_percent(50)
+			})
+		);
+
+		mock::start_active_era(4);
+
+		// only half of the 10% slash is applied.
+		let nominator_slash = Perbill::from_percent(10) * nominated_value;
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 950);
+		assert_eq!(
+			asset::stakeable_balance::<Test>(&101),
+			2000 - (nominator_slash - Perbill::from_percent(50) * nominator_slash)
+		);
+	})
+}
+
 #[test]
 fn claim_reward_at_the_last_era_and_no_double_claim_and_invalid_claim() {
 	// should check that:
@@ -4161,6 +4280,75 @@ fn test_commission_paid_across_pages() {
 	});
 }
 
+#[test]
+fn unclaimed_rewards_are_paid_out_on_idle() {
+	ExtBuilder::default().nominate(true).build_and_execute(|| {
+		reward_all_elected();
+		start_active_era(1);
+		reward_all_elected();
+		start_active_era(2);
+
+		let on_idle = |weight| <Staking as Hooks<u64>>::on_idle(System::block_number(), weight);
+		let auto_payout_events = || {
+			staking_events_since_last_call()
+				.into_iter()
+				.filter(|event| {
+					matches!(
+						event,
+						Event::AutoPayoutsProcessed { .. } | Event::AutoPayoutsCompleted { .. }
+					)
+				})
+				.collect::<Vec<_>>()
+		};
+		let payouts = || {
+			[0, 1]
+				.into_iter()
+				.flat_map(|era| {
+					[11, 21]
+						.into_iter()
+						.filter(move |v| !ClaimedRewards::<Test>::get(era, v).is_empty())
+				})
+				.count()
+		};
+
+		// Disabled by default.
+		assert_eq!(on_idle(Weight::MAX), Weight::zero());
+		assert_eq!(payouts(), 0);
+
+		// Only a single page fits into the budget.
+		MaxAutoPayoutWeight::set(Weight::MAX);
+		let db = <Test as frame_system::Config>::DbWeight::get();
+		let single_page = db.reads_writes(2, 1) +
+			db.reads(5) +
+			<Test as Config>::WeightInfo::payout_stakers_alive_staked(MaxExposurePageSize::get());
+		let _ = staking_events_since_last_call();
+		assert!(on_idle(single_page).all_lte(single_page));
+		assert_eq!(payouts(), 1);
+		assert_eq!(auto_payout_events(), vec![Event::AutoPayoutsProcessed { pages: 1, era: 0 }]);
+
+		// Too small budgets are ignored.
+		assert_eq!(on_idle(single_page - Weight::from_parts(1, 0)), Weight::zero());
+
+		// The remaining validators of era 0 are paid out before era 1.
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+		on_idle(Weight::MAX);
+		assert_eq!(payouts(), 4);
+		assert_eq!(
+			auto_payout_events(),
+			vec![
+				Event::AutoPayoutsCompleted { era: 0 },
+				Event::AutoPayoutsCompleted { era: 1 },
+				Event::AutoPayoutsProcessed { pages: 2, era: 2 },
+			]
+		);
+		assert_eq!(AutoPayoutProgress::<Test>::get().unwrap().era, 2);
+
+		// Nothing left to pay out.
+		on_idle(Weight::MAX);
+		assert!(auto_payout_events().is_empty());
+	})
+}
+
 #[test]
 fn payout_stakers_handles_weight_refund() {
 	// Note: this test relies on the assumption that `payout_stakers_alive_staked` is solely used by
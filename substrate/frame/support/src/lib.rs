@@ -88,6 +88,8 @@ pub mod storage;
 #[cfg(test)]
 mod tests;
 pub mod traits;
+#[cfg(feature = "std")]
+pub mod tx_extension_conformance;
 pub mod view_functions;
 pub mod weights;
 #[doc(hidden)]
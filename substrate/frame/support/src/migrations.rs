@@ -504,6 +504,13 @@ pub trait SteppedMigration {
 		None
 	}
 
+	/// The number of items migrated so far, as of `cursor`, if the migration keeps track of it.
+	///
+	/// Used by the callers to report the progress of the migration.
+	fn progress(_cursor: &Self::Cursor) -> Option<u32> {
+		None
+	}
+
 	/// Try to migrate as much as possible with the given weight.
 	///
 	/// **ANY STORAGE CHANGES MUST BE ROLLED-BACK BY THE CALLER UPON ERROR.** This is necessary
@@ -550,6 +557,128 @@ pub trait SteppedMigration {
 	}
 }
 
+/// A migration of all the entries of a storage map, one entry at a time.
+///
+/// Turned into a [`SteppedMigration`] by [`SteppedMapMigrationAdapter`], which takes care of
+/// iterating over the map across the steps, of metering the weight of every entry and of keeping
+/// track of the number of migrated entries.
+pub trait SteppedMapMigration {
+	/// The key of the migrated map.
+	type Key: codec::FullCodec + codec::MaxEncodedLen + Clone;
+
+	/// The value of the migrated map, as it is stored before the migration.
+	type Value: codec::FullCodec;
+
+	/// The migrated map.
+	///
+	/// Usually a [`storage_alias`](crate::storage_alias) of the map before the migration.
+	type Map: crate::storage::IterableStorageMap<Self::Key, Self::Value>;
+
+	/// The unique identifier type of this migration.
+	type Identifier: codec::FullCodec + codec::MaxEncodedLen;
+
+	/// The unique identifier of this migration, see [`SteppedMigration::id`].
+	fn id() -> Self::Identifier;
+
+	/// The maximum number of steps that this migration can take, see
+	/// [`SteppedMigration::max_steps`].
+	fn max_steps() -> Option<u32> {
+		None
+	}
+
+	/// The maximum weight of migrating one entry with [`Self::migrate`].
+	fn entry_weight() -> Weight;
+
+	/// Migrate the entry of the map at `key`.
+	///
+	/// The entry can be modified or removed, but no other entry of the map must be inserted.
+	/// Entries of another map sharing the same key space can be freely written.
+	fn migrate(key: Self::Key, value: Self::Value) -> Result<(), SteppedMigrationError>;
+
+	/// See [`SteppedMigration::pre_upgrade`].
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	/// See [`SteppedMigration::post_upgrade`].
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		Ok(())
+	}
+}
+
+/// The cursor of a [`SteppedMapMigrationAdapter`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
+pub struct SteppedMapCursor<Key> {
+	/// The key of the last migrated entry.
+	pub last_key: Key,
+	/// The number of entries migrated so far.
+	pub migrated: u32,
+}
+
+/// Implements [`SteppedMigration`] for a [`SteppedMapMigration`].
+///
+/// Every step migrates as many entries as the weight meter allows, and fails with
+/// [`SteppedMigrationError::InsufficientWeight`] if not even a single entry can be migrated. The
+/// number of migrated entries is reported as the [`SteppedMigration::progress`].
+pub struct SteppedMapMigrationAdapter<M>(PhantomData<M>);
+
+impl<M: SteppedMapMigration> SteppedMigration for SteppedMapMigrationAdapter<M> {
+	type Cursor = SteppedMapCursor<M::Key>;
+	type Identifier = M::Identifier;
+
+	fn id() -> Self::Identifier {
+		M::id()
+	}
+
+	fn max_steps() -> Option<u32> {
+		M::max_steps()
+	}
+
+	fn progress(cursor: &Self::Cursor) -> Option<u32> {
+		Some(cursor.migrated)
+	}
+
+	fn step(
+		mut cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		use crate::storage::{IterableStorageMap, StorageMap};
+
+		let required = M::entry_weight();
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required })
+		}
+
+		while meter.try_consume(required).is_ok() {
+			let mut iter = match &cursor {
+				// The iteration starts after the raw key of the last migrated entry, which is
+				// fine even if that entry was removed.
+				Some(cursor) => M::Map::iter_from(M::Map::hashed_key_for(&cursor.last_key)),
+				None => M::Map::iter(),
+			};
+			let Some((key, value)) = iter.next() else { return Ok(None) };
+
+			M::migrate(key.clone(), value)?;
+			let migrated = cursor.as_ref().map_or(0, |cursor| cursor.migrated).saturating_add(1);
+			cursor = Some(SteppedMapCursor { last_key: key, migrated });
+		}
+
+		Ok(cursor)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		M::pre_upgrade()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		M::post_upgrade(state)
+	}
+}
+
 /// Error that can occur during a [`SteppedMigration`].
 #[derive(Debug, Encode, Decode, MaxEncodedLen, scale_info::TypeInfo)]
 pub enum SteppedMigrationError {
@@ -701,6 +830,14 @@ pub trait SteppedMigrations {
 	/// Is guaranteed to return `Some` if `n < Self::len()`.
 	fn nth_max_steps(n: u32) -> Option<Option<u32>>;
 
+	/// The [`SteppedMigration::progress`] of the `n`th migration, as of the encoded `cursor`.
+	///
+	/// Returns `None` if the index is out of bounds, the cursor cannot be decoded or the migration
+	/// does not keep track of its progress.
+	fn nth_progress(_n: u32, _cursor: &[u8]) -> Option<u32> {
+		None
+	}
+
 	/// Do a [`SteppedMigration::step`] on the `n`th migration.
 	///
 	/// Is guaranteed to return `Some` if `n < Self::len()`.
@@ -836,6 +973,15 @@ impl<T: SteppedMigration> SteppedMigrations for T {
 			.defensive_proof("nth_max_steps should only be called with n==0")
 	}
 
+	fn nth_progress(n: u32, cursor: &[u8]) -> Option<u32> {
+		if !n.is_zero() {
+			defensive!("nth_progress should only be called with n==0");
+			return None
+		}
+
+		T::progress(&T::Cursor::decode(&mut &cursor[..]).ok()?)
+	}
+
 	fn nth_step(
 		n: u32,
 		cursor: Option<Vec<u8>>,
@@ -926,6 +1072,20 @@ impl SteppedMigrations for Tuple {
 		None
 	}
 
+	fn nth_progress(n: u32, cursor: &[u8]) -> Option<u32> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_progress(n - i, cursor)
+			}
+
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
 	fn nth_step(
 		n: u32,
 		cursor: Option<Vec<u8>>,
@@ -1122,6 +1282,38 @@ mod tests {
 		}
 	}
 
+	#[crate::storage_alias]
+	type OldMap = StorageMap<Prefix, crate::Twox64Concat, u32, u32>;
+
+	#[crate::storage_alias]
+	type NewMap = StorageMap<Prefix, crate::Twox64Concat, u32, u64>;
+
+	/// Moves the entries of `OldMap` to `NewMap`.
+	struct MoveMap;
+	impl SteppedMapMigration for MoveMap {
+		type Key = u32;
+		type Value = u32;
+		type Map = OldMap;
+		type Identifier = u8;
+
+		fn id() -> Self::Identifier {
+			4
+		}
+
+		fn entry_weight() -> Weight {
+			Weight::from_parts(1, 0)
+		}
+
+		fn migrate(key: u32, value: u32) -> Result<(), SteppedMigrationError> {
+			if value == u32::MAX {
+				return Err(SteppedMigrationError::Failed)
+			}
+			OldMap::remove(key);
+			NewMap::insert(key, value as u64 * 2);
+			Ok(())
+		}
+	}
+
 	// Three migrations combined to execute in order:
 	type Triple = (M0, (M1, M2));
 	// Six migrations, just concatenating the ones from before:
@@ -1176,6 +1368,47 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn stepped_map_migration_works() {
+		type M = SteppedMapMigrationAdapter<MoveMap>;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			(0..7).for_each(|i| OldMap::insert(i, i));
+
+			// Not enough weight for a single entry.
+			assert!(matches!(
+				M::step(None, &mut WeightMeter::with_limit(Weight::zero())),
+				Err(SteppedMigrationError::InsufficientWeight { required }) if required == Weight::from_parts(1, 0)
+			));
+
+			// Three entries are migrated per step.
+			let mut cursor = None;
+			let mut steps = 0;
+			loop {
+				let mut meter = WeightMeter::with_limit(Weight::from_parts(3, 0));
+				cursor = M::step(cursor, &mut meter).unwrap();
+				steps += 1;
+				let Some(cursor) = &cursor else { break };
+				assert_eq!(M::progress(cursor), Some((steps * 3).min(7)));
+				assert_eq!(
+					<M as SteppedMigrations>::nth_progress(0, &cursor.encode()),
+					Some((steps * 3).min(7))
+				);
+			}
+			assert_eq!(steps, 3);
+			assert_eq!(OldMap::iter().count(), 0);
+			assert_eq!(NewMap::iter().collect::<Vec<_>>().len(), 7);
+			(0..7).for_each(|i| assert_eq!(NewMap::get(i), Some(i as u64 * 2)));
+
+			// Errors of the migrated entries are forwarded.
+			OldMap::insert(0, u32::MAX);
+			assert!(matches!(
+				M::step(None, &mut WeightMeter::with_limit(Weight::from_parts(3, 0))),
+				Err(SteppedMigrationError::Failed)
+			));
+		});
+	}
+
 	#[test]
 	fn transactional_rollback_works() {
 		sp_io::TestExternalities::default().execute_with(|| {
@@ -142,6 +142,6 @@ pub use proving::*;
 mod try_runtime;
 #[cfg(feature = "try-runtime")]
 pub use try_runtime::{
-	Select as TryStateSelect, TryDecodeEntireStorage, TryDecodeEntireStorageError, TryState,
-	UpgradeCheckSelect,
+	MigrationReport, Select as TryStateSelect, StorageVersionTransition, TryDecodeEntireStorage,
+	TryDecodeEntireStorageError, TryState, UpgradeCheckSelect, UpgradeReport,
 };
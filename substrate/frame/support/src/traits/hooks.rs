@@ -26,10 +26,12 @@ use impl_trait_for_tuples::impl_for_tuples;
 use sp_runtime::traits::AtLeast32BitUnsigned;
 use sp_weights::WeightMeter;
 
+#[cfg(feature = "try-runtime")]
+use super::MigrationReport;
 #[cfg(feature = "try-runtime")]
 use alloc::vec::Vec;
 #[cfg(feature = "try-runtime")]
-use sp_runtime::TryRuntimeError;
+use sp_runtime::{StateVersion, TryRuntimeError};
 
 /// Provides a callback to execute logic before the all inherents.
 pub trait PreInherents {
@@ -215,6 +217,33 @@ pub trait OnRuntimeUpgrade {
 		Ok(weight)
 	}
 
+	/// Same as [`Self::try_on_runtime_upgrade`], but also pushes a [`MigrationReport`] to
+	/// `reports` for every migration executed.
+	///
+	/// Tuples report each of their members, so that the migrations of a runtime are reported
+	/// individually.
+	#[cfg(feature = "try-runtime")]
+	fn try_on_runtime_upgrade_with_report(
+		checks: bool,
+		reports: &mut Vec<MigrationReport>,
+	) -> Result<Weight, TryRuntimeError> {
+		let weight = Self::try_on_runtime_upgrade(checks)?;
+		reports.push(MigrationReport::new::<Self>(weight));
+		Ok(weight)
+	}
+
+	/// Execute the migration once more and push to `idempotent` whether the state root is
+	/// unchanged.
+	///
+	/// Expected to be called after the migration already ran, with the migrations reported in the
+	/// same order as [`Self::try_on_runtime_upgrade_with_report`].
+	#[cfg(feature = "try-runtime")]
+	fn try_idempotency_check(state_version: StateVersion, idempotent: &mut Vec<bool>) {
+		let root = sp_io::storage::root(state_version);
+		Self::on_runtime_upgrade();
+		idempotent.push(sp_io::storage::root(state_version) == root);
+	}
+
 	/// See [`Hooks::pre_upgrade`].
 	#[cfg(feature = "try-runtime")]
 	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
@@ -228,6 +257,34 @@ pub trait OnRuntimeUpgrade {
 	}
 }
 
+/// Merges the errors of the members of a tuple of migrations into a single one, logging them.
+#[cfg(feature = "try-runtime")]
+fn merge_migration_errors(errors: Vec<TryRuntimeError>) -> Result<(), TryRuntimeError> {
+	if errors.len() == 1 {
+		return Err(errors[0])
+	} else if !errors.is_empty() {
+		log::error!(
+			target: "try-runtime",
+			"Detected multiple errors while executing `try_on_runtime_upgrade`:",
+		);
+
+		errors.iter().for_each(|err| {
+			log::error!(
+				target: "try-runtime",
+				"{:?}",
+				err
+			);
+		});
+
+		return Err(
+			"Detected multiple errors while executing `try_on_runtime_upgrade`, check the logs!"
+				.into(),
+		)
+	}
+
+	Ok(())
+}
+
 /// This trait is intended for use within `VersionedMigration` to execute storage migrations without
 /// automatic version checks. Implementations should ensure migration logic is safe and idempotent.
 pub trait UncheckedOnRuntimeUpgrade {
@@ -291,28 +348,39 @@ impl OnRuntimeUpgrade for Tuple {
 			}
 		)*);
 
-		if errors.len() == 1 {
-			return Err(errors[0])
-		} else if !errors.is_empty() {
-			log::error!(
-				target: "try-runtime",
-				"Detected multiple errors while executing `try_on_runtime_upgrade`:",
-			);
+		merge_migration_errors(errors)?;
+		Ok(cumulative_weight)
+	}
 
-			errors.iter().for_each(|err| {
-				log::error!(
-					target: "try-runtime",
-					"{:?}",
-					err
-				);
-			});
+	/// Implements the default behavior of `try_on_runtime_upgrade_with_report` for tuples,
+	/// reporting each member.
+	#[cfg(feature = "try-runtime")]
+	fn try_on_runtime_upgrade_with_report(
+		checks: bool,
+		reports: &mut Vec<MigrationReport>,
+	) -> Result<Weight, TryRuntimeError> {
+		let mut cumulative_weight = Weight::zero();
 
-			return Err("Detected multiple errors while executing `try_on_runtime_upgrade`, check the logs!".into())
-		}
+		let mut errors = Vec::new();
+
+		for_tuples!(#(
+			match Tuple::try_on_runtime_upgrade_with_report(checks, reports) {
+				Ok(weight) => { cumulative_weight.saturating_accrue(weight); },
+				Err(err) => { errors.push(err); },
+			}
+		)*);
 
+		merge_migration_errors(errors)?;
 		Ok(cumulative_weight)
 	}
 
+	/// Implements the default behavior of `try_idempotency_check` for tuples, checking each
+	/// member.
+	#[cfg(feature = "try-runtime")]
+	fn try_idempotency_check(state_version: StateVersion, idempotent: &mut Vec<bool>) {
+		for_tuples!( #( Tuple::try_idempotency_check(state_version, idempotent); )* );
+	}
+
 	/// [`OnRuntimeUpgrade::pre_upgrade`] should not be used on a tuple.
 	///
 	/// Instead, implementors should use [`OnRuntimeUpgrade::try_on_runtime_upgrade`] which
@@ -691,6 +759,57 @@ mod tests {
 		});
 	}
 
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_on_runtime_upgrade_with_report_reports_each_migration() {
+		// Sets a value the first time only.
+		struct Idempotent;
+		impl OnRuntimeUpgrade for Idempotent {
+			fn on_runtime_upgrade() -> Weight {
+				if !sp_io::storage::exists(b"idempotent") {
+					sp_io::storage::set(b"idempotent", &[1]);
+				}
+				Weight::from_parts(10, 0)
+			}
+		}
+
+		// Increments a counter every time.
+		struct Counter;
+		impl OnRuntimeUpgrade for Counter {
+			fn on_runtime_upgrade() -> Weight {
+				let count = sp_io::storage::get(b"counter").map_or(0, |count| count[0]);
+				sp_io::storage::set(b"counter", &[count + 1]);
+				Weight::from_parts(20, 0)
+			}
+		}
+
+		TestExternalities::default().execute_with(|| {
+			let mut reports = Vec::new();
+			let weight = <(Idempotent, (Counter, Idempotent))>::try_on_runtime_upgrade_with_report(
+				true,
+				&mut reports,
+			)
+			.unwrap();
+			assert_eq!(weight, Weight::from_parts(40, 0));
+			assert_eq!(
+				reports,
+				vec![
+					MigrationReport::new::<Idempotent>(Weight::from_parts(10, 0)),
+					MigrationReport::new::<Counter>(Weight::from_parts(20, 0)),
+					MigrationReport::new::<Idempotent>(Weight::from_parts(10, 0)),
+				]
+			);
+			assert!(reports[1].name.ends_with(b"Counter"));
+
+			let mut idempotent = Vec::new();
+			<(Idempotent, (Counter, Idempotent))>::try_idempotency_check(
+				StateVersion::V1,
+				&mut idempotent,
+			);
+			assert_eq!(idempotent, vec![true, false, true]);
+		});
+	}
+
 	#[test]
 	fn on_initialize_and_on_runtime_upgrade_weight_merge_works() {
 		struct Test;
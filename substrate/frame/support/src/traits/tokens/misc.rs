@@ -312,8 +312,8 @@ pub trait ConversionFromAssetBalance<AssetBalance, AssetId, OutBalance> {
 	fn ensure_successful(asset_id: AssetId);
 }
 
-/// Implements [`ConversionFromAssetBalance`], enabling a 1:1 conversion of the asset balance
-/// value to the balance.
+/// Implements [`ConversionFromAssetBalance`] and [`ConversionToAssetBalance`], enabling a 1:1
+/// conversion between the asset balance value and the balance.
 pub struct UnityAssetBalanceConversion;
 impl<AssetBalance, AssetId, OutBalance>
 	ConversionFromAssetBalance<AssetBalance, AssetId, OutBalance> for UnityAssetBalanceConversion
@@ -327,10 +327,20 @@ where
 	#[cfg(feature = "runtime-benchmarks")]
 	fn ensure_successful(_: AssetId) {}
 }
+impl<InBalance, AssetId, AssetBalance> ConversionToAssetBalance<InBalance, AssetId, AssetBalance>
+	for UnityAssetBalanceConversion
+where
+	InBalance: Into<AssetBalance>,
+{
+	type Error = ();
+	fn to_asset_balance(balance: InBalance, _: AssetId) -> Result<AssetBalance, Self::Error> {
+		Ok(balance.into())
+	}
+}
 
-/// Implements [`ConversionFromAssetBalance`], allowing for a 1:1 balance conversion of the asset
-/// when it meets the conditions specified by `C`. If the conditions are not met, the conversion is
-/// delegated to `O`.
+/// Implements [`ConversionFromAssetBalance`] and [`ConversionToAssetBalance`], allowing for a 1:1
+/// balance conversion of the asset when it meets the conditions specified by `C`. If the conditions
+/// are not met, the conversion is delegated to `O`.
 pub struct UnityOrOuterConversion<C, O>(core::marker::PhantomData<(C, O)>);
 impl<AssetBalance, AssetId, OutBalance, C, O>
 	ConversionFromAssetBalance<AssetBalance, AssetId, OutBalance> for UnityOrOuterConversion<C, O>
@@ -354,6 +364,24 @@ where
 		O::ensure_successful(asset_id)
 	}
 }
+impl<InBalance, AssetId, AssetBalance, C, O>
+	ConversionToAssetBalance<InBalance, AssetId, AssetBalance> for UnityOrOuterConversion<C, O>
+where
+	C: Contains<AssetId>,
+	O: ConversionToAssetBalance<InBalance, AssetId, AssetBalance>,
+	InBalance: Into<AssetBalance>,
+{
+	type Error = O::Error;
+	fn to_asset_balance(
+		balance: InBalance,
+		asset_id: AssetId,
+	) -> Result<AssetBalance, Self::Error> {
+		if C::contains(&asset_id) {
+			return Ok(balance.into());
+		}
+		O::to_asset_balance(balance, asset_id)
+	}
+}
 
 /// Trait to handle NFT locking mechanism to ensure interactions with the asset can be implemented
 /// downstream to extend logic of Uniques/Nfts current functionality.
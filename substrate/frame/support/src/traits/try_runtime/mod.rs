@@ -20,7 +20,8 @@
 pub mod decode_entire_state;
 pub use decode_entire_state::{TryDecodeEntireStorage, TryDecodeEntireStorageError};
 
-use super::StorageInstance;
+use super::{StorageInstance, StorageVersion};
+use crate::weights::Weight;
 
 use alloc::vec::Vec;
 use impl_trait_for_tuples::impl_for_tuples;
@@ -139,6 +140,49 @@ impl core::str::FromStr for UpgradeCheckSelect {
 	}
 }
 
+/// The outcome of a single migration, see
+/// [`OnRuntimeUpgrade::try_on_runtime_upgrade_with_report`](super::OnRuntimeUpgrade::try_on_runtime_upgrade_with_report).
+#[derive(codec::Encode, codec::Decode, Clone, Debug, scale_info::TypeInfo, PartialEq, Eq)]
+pub struct MigrationReport {
+	/// The type name of the migration.
+	pub name: Vec<u8>,
+	/// The weight consumed by the migration.
+	pub weight: Weight,
+	/// Whether executing the migration a second time left the state untouched, if checked.
+	pub idempotent: Option<bool>,
+}
+
+impl MigrationReport {
+	/// The report of the migration `M`, which consumed `weight`.
+	pub fn new<M: ?Sized>(weight: Weight) -> Self {
+		Self { name: core::any::type_name::<M>().as_bytes().to_vec(), weight, idempotent: None }
+	}
+}
+
+/// The change of the on-chain storage version of a pallet during a runtime upgrade.
+#[derive(codec::Encode, codec::Decode, Clone, Debug, scale_info::TypeInfo, PartialEq, Eq)]
+pub struct StorageVersionTransition {
+	/// The name of the pallet, as configured in the runtime.
+	pub pallet: Vec<u8>,
+	/// The storage version before the upgrade, `None` if not set.
+	pub before: Option<StorageVersion>,
+	/// The storage version after the upgrade, `None` if not set.
+	pub after: Option<StorageVersion>,
+}
+
+/// The report of a runtime upgrade executed with try-runtime.
+#[derive(codec::Encode, codec::Decode, Clone, Debug, scale_info::TypeInfo, PartialEq, Eq)]
+pub struct UpgradeReport {
+	/// The total weight consumed by the upgrade.
+	pub weight: Weight,
+	/// The maximum weight of a block.
+	pub max_block_weight: Weight,
+	/// The migrations executed, in order.
+	pub migrations: Vec<MigrationReport>,
+	/// The pallets whose storage version changed.
+	pub storage_versions: Vec<StorageVersionTransition>,
+}
+
 /// Execute some checks to ensure the internal state of a pallet is consistent.
 ///
 /// Usually, these checks should check all of the invariants that are expected to be held on all of
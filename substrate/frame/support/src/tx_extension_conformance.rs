@@ -0,0 +1,411 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conformance test kit for the transaction extension pipeline of a runtime.
+//!
+//! Runtimes customizing their `TxExtension` tuple can easily break the pipeline in subtle ways:
+//! an extension placed before the one authorizing the origin, a fee charged twice, a weight that
+//! doesn't account for a new extension, etc. A [`ConformanceKit`] runs a set of golden
+//! [`Vector`]s through the whole pipeline (validation, preparation, dispatch and post dispatch)
+//! and reports every deviation from the expected outcome:
+//!
+//! - the order of the extensions, through their identifiers,
+//! - the validity of the transaction: priority, longevity and tags, or the validity error,
+//! - the weight of the extensions and the actual weight of the transaction,
+//! - any change to the state the runtime wants to check, e.g. the balance of the fee payer or the
+//!   nonce of the sender.
+//!
+//! ```ignore
+//! ConformanceKit::<RuntimeCall, TxExtension>::new()
+//! 	.expect_identifiers(&["CheckNonce", "CheckMortality", "ChargeTransactionPayment"])
+//! 	.vector(
+//! 		Vector::new("transfer", RuntimeOrigin::signed(ALICE), call, tx_ext(0, 0))
+//! 			.expect_valid(ExpectedValidity { priority: Some(0), ..Default::default() })
+//! 			.expect_change("fee", || Balances::free_balance(ALICE) as i128, -(FEE as i128))
+//! 			.expect_change("nonce", || System::account_nonce(ALICE) as i128, 1),
+//! 	)
+//! 	.assert();
+//! ```
+//!
+//! The kit must be run within externalities. Every vector is executed in its own storage
+//! transaction which is rolled back afterwards, so the vectors don't depend on each other.
+
+use crate::{
+	dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	storage::with_transaction_unchecked,
+};
+use codec::Encode;
+use sp_runtime::{
+	generic::ExtensionVersion,
+	traits::{
+		AsTransactionAuthorizedOrigin, DispatchTransaction, Dispatchable, TransactionExtension,
+	},
+	transaction_validity::{
+		TransactionLongevity, TransactionPriority, TransactionSource, TransactionTag,
+		TransactionValidityError, ValidTransaction,
+	},
+	TransactionOutcome,
+};
+use sp_weights::Weight;
+use std::fmt;
+
+/// The validity a [`Vector`] expects the extension pipeline to give to its transaction.
+///
+/// Fields set to `None` are not checked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedValidity {
+	/// The expected priority.
+	pub priority: Option<TransactionPriority>,
+	/// The expected longevity.
+	pub longevity: Option<TransactionLongevity>,
+	/// The expected tags provided by the transaction.
+	pub provides: Option<Vec<TransactionTag>>,
+	/// The expected tags required by the transaction.
+	pub requires: Option<Vec<TransactionTag>>,
+}
+
+/// A value read from the state before and after the dispatch of a [`Vector`].
+struct Observation {
+	name: &'static str,
+	read: Box<dyn Fn() -> i128>,
+	expected_change: i128,
+}
+
+/// A golden vector: a transaction and the outcome it is expected to have.
+pub struct Vector<Call: Dispatchable, Extension> {
+	name: &'static str,
+	origin: Call::RuntimeOrigin,
+	call: Call,
+	extension: Extension,
+	len: usize,
+	source: TransactionSource,
+	validity: Result<ExpectedValidity, TransactionValidityError>,
+	extension_weight: Option<Weight>,
+	actual_weight: Option<Weight>,
+	observations: Vec<Observation>,
+	dispatch: bool,
+}
+
+impl<Call: Dispatchable + Encode, Extension> Vector<Call, Extension> {
+	/// Create a new vector dispatching `call` with the given `origin` and `extension`.
+	///
+	/// By default the transaction is expected to be valid, its length is the length of the
+	/// encoded call and it is validated as coming from [`TransactionSource::External`].
+	pub fn new(
+		name: &'static str,
+		origin: Call::RuntimeOrigin,
+		call: Call,
+		extension: Extension,
+	) -> Self {
+		Self {
+			name,
+			origin,
+			len: call.encoded_size(),
+			call,
+			extension,
+			source: TransactionSource::External,
+			validity: Ok(Default::default()),
+			extension_weight: None,
+			actual_weight: None,
+			observations: Vec::new(),
+			dispatch: true,
+		}
+	}
+
+	/// Set the length of the transaction, as passed to the extensions.
+	pub fn with_len(mut self, len: usize) -> Self {
+		self.len = len;
+		self
+	}
+
+	/// Set the source the transaction is validated from.
+	pub fn with_source(mut self, source: TransactionSource) -> Self {
+		self.source = source;
+		self
+	}
+
+	/// Expect the transaction to be valid, with the given properties.
+	pub fn expect_valid(mut self, validity: ExpectedValidity) -> Self {
+		self.validity = Ok(validity);
+		self
+	}
+
+	/// Expect the transaction to be rejected with the given error.
+	///
+	/// Nothing is dispatched for such a vector, so only the weight of the extensions is checked
+	/// on top of the validity.
+	pub fn expect_invalid(mut self, error: impl Into<TransactionValidityError>) -> Self {
+		self.validity = Err(error.into());
+		self
+	}
+
+	/// Only validate the transaction, without dispatching it.
+	///
+	/// Useful for transactions that are valid for the transaction pool but can't be included
+	/// yet, e.g. the ones with a future nonce.
+	pub fn skip_dispatch(mut self) -> Self {
+		self.dispatch = false;
+		self
+	}
+
+	/// Expect the extensions to declare the given weight for the call.
+	pub fn expect_extension_weight(mut self, weight: Weight) -> Self {
+		self.extension_weight = Some(weight);
+		self
+	}
+
+	/// Expect the given actual weight after post dispatch, including the extensions' weight.
+	pub fn expect_actual_weight(mut self, weight: Weight) -> Self {
+		self.actual_weight = Some(weight);
+		self
+	}
+
+	/// Expect the value returned by `read` to change by `change` because of the dispatch.
+	pub fn expect_change(
+		mut self,
+		name: &'static str,
+		read: impl Fn() -> i128 + 'static,
+		change: i128,
+	) -> Self {
+		self.observations
+			.push(Observation { name, read: Box::new(read), expected_change: change });
+		self
+	}
+}
+
+/// A deviation from the outcome expected by a [`Vector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+	/// The name of the vector, or `"identifiers"` for the order of the extensions.
+	pub vector: &'static str,
+	/// What was checked.
+	pub check: String,
+	/// The expected outcome.
+	pub expected: String,
+	/// The actual outcome.
+	pub actual: String,
+}
+
+impl Mismatch {
+	fn new(
+		vector: &'static str,
+		check: impl Into<String>,
+		expected: impl fmt::Debug,
+		actual: impl fmt::Debug,
+	) -> Self {
+		Self {
+			vector,
+			check: check.into(),
+			expected: format!("{:?}", expected),
+			actual: format!("{:?}", actual),
+		}
+	}
+}
+
+impl fmt::Display for Mismatch {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{}: {} mismatch, expected {}, got {}",
+			self.vector, self.check, self.expected, self.actual
+		)
+	}
+}
+
+/// Runs golden [`Vector`]s through the transaction extension pipeline of a runtime.
+pub struct ConformanceKit<Call: Dispatchable, Extension> {
+	identifiers: Option<Vec<&'static str>>,
+	extension_version: ExtensionVersion,
+	vectors: Vec<Vector<Call, Extension>>,
+}
+
+impl<Call, Extension> Default for ConformanceKit<Call, Extension>
+where
+	Call: Dispatchable,
+{
+	fn default() -> Self {
+		Self { identifiers: None, extension_version: 0, vectors: Vec::new() }
+	}
+}
+
+impl<Call, Extension> ConformanceKit<Call, Extension>
+where
+	Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetDispatchInfo + Encode,
+	Call::RuntimeOrigin: AsTransactionAuthorizedOrigin + Clone,
+	Extension: TransactionExtension<Call>,
+{
+	/// Create a new kit without any vector.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Expect the extensions to be the given ones, in the given order.
+	pub fn expect_identifiers(mut self, identifiers: &[&'static str]) -> Self {
+		self.identifiers = Some(identifiers.to_vec());
+		self
+	}
+
+	/// Set the extension version the vectors are dispatched with, `0` by default.
+	pub fn with_extension_version(mut self, version: ExtensionVersion) -> Self {
+		self.extension_version = version;
+		self
+	}
+
+	/// Add a vector to the kit.
+	pub fn vector(mut self, vector: Vector<Call, Extension>) -> Self {
+		self.vectors.push(vector);
+		self
+	}
+
+	/// Run all the vectors and return the mismatches found.
+	pub fn run(self) -> Vec<Mismatch> {
+		let mut mismatches = Vec::new();
+
+		if let Some(expected) = self.identifiers {
+			let actual = Extension::metadata()
+				.into_iter()
+				.map(|metadata| metadata.identifier)
+				.collect::<Vec<_>>();
+			if actual != expected {
+				mismatches.push(Mismatch::new("identifiers", "extensions order", expected, actual));
+			}
+		}
+
+		for vector in self.vectors {
+			with_transaction_unchecked(|| {
+				run_vector(vector, self.extension_version, &mut mismatches);
+				TransactionOutcome::Rollback(())
+			});
+		}
+
+		mismatches
+	}
+
+	/// Run all the vectors and panic if any mismatch is found.
+	pub fn assert(self) {
+		let mismatches = self.run();
+		if !mismatches.is_empty() {
+			let report = mismatches.iter().map(|m| format!("  - {m}")).collect::<Vec<_>>();
+			panic!("transaction extension conformance failed:\n{}", report.join("\n"));
+		}
+	}
+}
+
+fn run_vector<Call, Extension>(
+	vector: Vector<Call, Extension>,
+	extension_version: ExtensionVersion,
+	mismatches: &mut Vec<Mismatch>,
+) where
+	Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetDispatchInfo + Encode,
+	Call::RuntimeOrigin: AsTransactionAuthorizedOrigin + Clone,
+	Extension: TransactionExtension<Call>,
+{
+	let Vector {
+		name,
+		origin,
+		call,
+		extension,
+		len,
+		source,
+		validity,
+		extension_weight,
+		actual_weight,
+		observations,
+		dispatch,
+	} = vector;
+
+	// Same as the info of an extrinsic carrying the extensions.
+	let mut info = call.get_dispatch_info();
+	info.extension_weight = extension.weight(&call);
+
+	if let Some(expected) = extension_weight.filter(|w| *w != info.extension_weight) {
+		mismatches.push(Mismatch::new(name, "extension weight", expected, info.extension_weight));
+	}
+
+	let result = extension
+		.validate_only(origin.clone(), &call, &info, len, source, extension_version)
+		.map(|(valid, _, _)| valid);
+	match (validity, result) {
+		(Ok(expected), Ok(valid)) => check_validity(name, &expected, &valid, mismatches),
+		(Err(expected), Err(actual)) => {
+			if actual != expected {
+				mismatches.push(Mismatch::new(name, "validity", Err::<(), _>(expected), actual));
+			}
+			return
+		},
+		(expected, actual) => {
+			mismatches.push(Mismatch::new(name, "validity", expected.map(|_| ()), actual));
+			return
+		},
+	}
+
+	if !dispatch {
+		return
+	}
+
+	let before = observations.iter().map(|o| (o.read)()).collect::<Vec<_>>();
+
+	let post_info =
+		match extension.dispatch_transaction(origin, call, &info, len, extension_version) {
+			Ok(Ok(post_info)) => post_info,
+			// The call failing is fine, the extensions still have to handle the transaction.
+			Ok(Err(error)) => error.post_info,
+			Err(error) => {
+				mismatches.push(Mismatch::new(name, "apply", "applied", error));
+				return
+			},
+		};
+
+	if let Some(expected) = actual_weight {
+		let actual = post_info.calc_actual_weight(&info);
+		if actual != expected {
+			mismatches.push(Mismatch::new(name, "actual weight", expected, actual));
+		}
+	}
+
+	for (observation, before) in observations.iter().zip(before) {
+		let change = (observation.read)() - before;
+		if change != observation.expected_change {
+			mismatches.push(Mismatch::new(
+				name,
+				format!("change of {}", observation.name),
+				observation.expected_change,
+				change,
+			));
+		}
+	}
+}
+
+fn check_validity(
+	name: &'static str,
+	expected: &ExpectedValidity,
+	actual: &ValidTransaction,
+	mismatches: &mut Vec<Mismatch>,
+) {
+	if let Some(priority) = expected.priority.filter(|p| *p != actual.priority) {
+		mismatches.push(Mismatch::new(name, "priority", priority, actual.priority));
+	}
+	if let Some(longevity) = expected.longevity.filter(|l| *l != actual.longevity) {
+		mismatches.push(Mismatch::new(name, "longevity", longevity, actual.longevity));
+	}
+	if let Some(provides) = expected.provides.as_ref().filter(|p| **p != actual.provides) {
+		mismatches.push(Mismatch::new(name, "provides", provides, &actual.provides));
+	}
+	if let Some(requires) = expected.requires.as_ref().filter(|r| **r != actual.requires) {
+		mismatches.push(Mismatch::new(name, "requires", requires, &actual.requires));
+	}
+}
@@ -883,6 +883,17 @@ fn test_metadata() {
 						" an identifier of the chain.",
 					]),
 				},
+				PalletConstantMetadata {
+					name: "BlockWeightHistoryLength",
+					ty: meta_type::<u32>(),
+					value: 0u32.encode(),
+					docs: maybe_docs(vec![
+						" The number of most recent blocks whose consumed weight is kept in",
+						" [`BlockWeightHistory`].",
+						"",
+						" Recording costs one storage write per block. Set to zero to disable it.",
+					]),
+				},
 			],
 			error: Some(meta_type::<frame_system::Error<Runtime>>().into()),
 			index: 30,
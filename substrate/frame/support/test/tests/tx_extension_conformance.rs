@@ -0,0 +1,259 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the transaction extension conformance test kit.
+
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use frame_support::{
+	derive_impl,
+	dispatch::GetDispatchInfo,
+	tx_extension_conformance::{ConformanceKit, ExpectedValidity, Vector},
+	weights::Weight,
+};
+use frame_system::{CheckMortality, CheckNonce, ExtensionsWeightInfo};
+use scale_info::TypeInfo;
+use sp_io::TestExternalities;
+use sp_runtime::{
+	generic::Era,
+	traits::{AsSystemOriginSigner, DispatchInfoOf, TransactionExtension, ValidateResult},
+	transaction_validity::{
+		InvalidTransaction, TransactionSource, TransactionValidityError, ValidTransaction,
+	},
+	BuildStorage,
+};
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::storage]
+	pub type Balances<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		FeePallet: pallet,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+}
+
+impl pallet::Config for Runtime {}
+
+const BASE_FEE: u64 = 10;
+const CHARGE_FEE_WEIGHT: Weight = Weight::from_parts(1_000, 0);
+
+/// Charges a fee of `BASE_FEE` plus the tip, the tip being the priority of the transaction.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, Debug, TypeInfo)]
+struct ChargeFee(u64);
+
+impl TransactionExtension<RuntimeCall> for ChargeFee {
+	const IDENTIFIER: &'static str = "ChargeFee";
+	type Implicit = ();
+	type Val = Option<(u64, u64)>;
+	type Pre = ();
+
+	fn weight(&self, _: &RuntimeCall) -> Weight {
+		CHARGE_FEE_WEIGHT
+	}
+
+	fn validate(
+		&self,
+		origin: RuntimeOrigin,
+		_call: &RuntimeCall,
+		_info: &DispatchInfoOf<RuntimeCall>,
+		_len: usize,
+		_self_implicit: Self::Implicit,
+		_inherited_implication: &impl Encode,
+		_source: TransactionSource,
+	) -> ValidateResult<Self::Val, RuntimeCall> {
+		let Some(who) = origin.as_system_origin_signer().copied() else {
+			return Ok((Default::default(), None, origin))
+		};
+
+		let fee = BASE_FEE + self.0;
+		if pallet::Balances::<Runtime>::get(who) < fee {
+			return Err(InvalidTransaction::Payment.into())
+		}
+
+		Ok((ValidTransaction { priority: self.0, ..Default::default() }, Some((who, fee)), origin))
+	}
+
+	fn prepare(
+		self,
+		val: Self::Val,
+		_origin: &RuntimeOrigin,
+		_call: &RuntimeCall,
+		_info: &DispatchInfoOf<RuntimeCall>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		if let Some((who, fee)) = val {
+			pallet::Balances::<Runtime>::mutate(who, |balance| *balance -= fee);
+		}
+		Ok(())
+	}
+}
+
+type TxExtension = (CheckNonce<Runtime>, CheckMortality<Runtime>, ChargeFee);
+
+const ALICE: u64 = 1;
+
+fn tx_ext(nonce: u32, era: Era, tip: u64) -> TxExtension {
+	(CheckNonce::from(nonce), CheckMortality::from(era), ChargeFee(tip))
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![1, 2, 3] })
+}
+
+fn alice_vector(name: &'static str, ext: TxExtension) -> Vector<RuntimeCall, TxExtension> {
+	Vector::new(name, RuntimeOrigin::signed(ALICE), remark(), ext)
+}
+
+fn new_test_ext() -> TestExternalities {
+	let mut ext: TestExternalities = frame_system::GenesisConfig::<Runtime>::default()
+		.build_storage()
+		.unwrap()
+		.into();
+	ext.execute_with(|| {
+		frame_system::Pallet::<Runtime>::inc_providers(&ALICE);
+		pallet::Balances::<Runtime>::insert(ALICE, 100);
+	});
+	ext
+}
+
+fn kit() -> ConformanceKit<RuntimeCall, TxExtension> {
+	ConformanceKit::new().expect_identifiers(&["CheckNonce", "CheckMortality", "ChargeFee"])
+}
+
+#[test]
+fn pipeline_matches_golden_vectors() {
+	new_test_ext().execute_with(|| {
+		let extension_weight = <() as ExtensionsWeightInfo>::check_nonce() +
+			<() as ExtensionsWeightInfo>::check_mortality_mortal_transaction() +
+			CHARGE_FEE_WEIGHT;
+
+		kit()
+			.vector(
+				alice_vector("mortal with tip", tx_ext(0, Era::mortal(4, 0), 5))
+					.expect_valid(ExpectedValidity {
+						priority: Some(5),
+						longevity: Some(4),
+						provides: Some(vec![(ALICE, 0u32).encode()]),
+						requires: Some(vec![]),
+					})
+					.expect_extension_weight(extension_weight)
+					.expect_actual_weight(
+						remark().get_dispatch_info().call_weight + extension_weight,
+					)
+					.expect_change(
+						"fee",
+						|| pallet::Balances::<Runtime>::get(ALICE).into(),
+						-(BASE_FEE as i128 + 5),
+					)
+					.expect_change(
+						"nonce",
+						|| frame_system::Pallet::<Runtime>::account_nonce(ALICE).into(),
+						1,
+					),
+			)
+			.vector(
+				// Vectors are isolated, the nonce of the previous one has been rolled back.
+				alice_vector("future nonce", tx_ext(1, Era::Immortal, 0))
+					.expect_valid(ExpectedValidity {
+						priority: Some(0),
+						longevity: Some(u64::MAX),
+						provides: Some(vec![(ALICE, 1u32).encode()]),
+						requires: Some(vec![(ALICE, 0u32).encode()]),
+					})
+					.skip_dispatch(),
+			)
+			.vector(
+				alice_vector("unknown birth block", tx_ext(0, Era::mortal(4, 1), 0))
+					.expect_invalid(InvalidTransaction::AncientBirthBlock),
+			)
+			.vector(
+				alice_vector("tip above balance", tx_ext(0, Era::Immortal, 95))
+					.expect_invalid(InvalidTransaction::Payment),
+			)
+			.assert();
+
+		// Nothing was kept from the vectors.
+		assert_eq!(pallet::Balances::<Runtime>::get(ALICE), 100);
+		assert_eq!(frame_system::Pallet::<Runtime>::account_nonce(ALICE), 0);
+	});
+}
+
+#[test]
+fn deviations_are_reported() {
+	new_test_ext().execute_with(|| {
+		let mismatches = ConformanceKit::<RuntimeCall, TxExtension>::new()
+			.expect_identifiers(&["CheckMortality", "CheckNonce", "ChargeFee"])
+			.vector(alice_vector("wrong fee", tx_ext(0, Era::Immortal, 0)).expect_change(
+				"fee",
+				|| pallet::Balances::<Runtime>::get(ALICE).into(),
+				-1,
+			))
+			.vector(
+				alice_vector("wrong priority", tx_ext(0, Era::Immortal, 1))
+					.expect_valid(ExpectedValidity { priority: Some(2), ..Default::default() }),
+			)
+			.vector(
+				alice_vector("unexpectedly valid", tx_ext(0, Era::Immortal, 0))
+					.expect_invalid(InvalidTransaction::Stale),
+			)
+			.run();
+
+		let checks = mismatches.iter().map(|m| (m.vector, m.check.as_str())).collect::<Vec<_>>();
+		assert_eq!(
+			checks,
+			vec![
+				("identifiers", "extensions order"),
+				("wrong fee", "change of fee"),
+				("wrong priority", "priority"),
+				("unexpectedly valid", "validity"),
+			]
+		);
+		assert_eq!(mismatches[1].expected, "-1");
+		assert_eq!(mismatches[1].actual, "-10");
+	});
+}
+
+#[test]
+#[should_panic(expected = "transaction extension conformance failed")]
+fn assert_panics_on_deviation() {
+	new_test_ext().execute_with(|| {
+		kit()
+			.vector(
+				alice_vector("stale nonce", tx_ext(0, Era::Immortal, 0))
+					.expect_invalid(InvalidTransaction::Stale),
+			)
+			.assert();
+	});
+}
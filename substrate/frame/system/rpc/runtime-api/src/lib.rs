@@ -23,6 +23,10 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
+
 #[docify::export(AccountNonceApi)]
 sp_api::decl_runtime_apis! {
 	/// The API to query account nonce.
@@ -33,4 +37,28 @@ sp_api::decl_runtime_apis! {
 		/// Get current account nonce of given `AccountId`.
 		fn account_nonce(account: AccountId) -> Nonce;
 	}
+
+	/// The API to query the events deposited by a single extrinsic.
+	///
+	/// Only meaningful for runtimes that enable the `event-index` feature of `frame-system`.
+	pub trait ExtrinsicEventsApi<EventRecord> where
+		EventRecord: codec::Codec,
+	{
+		/// Get the half-open range `[start, end)` of indexes into the block's events that were
+		/// deposited by the extrinsic at `extrinsic_index`.
+		fn extrinsic_event_range(extrinsic_index: u32) -> Option<(u32, u32)>;
+
+		/// Get the events deposited by the extrinsic at `extrinsic_index`.
+		fn extrinsic_events(extrinsic_index: u32) -> Vec<EventRecord>;
+	}
+
+	/// The API to query the weight consumed by recent blocks.
+	pub trait BlockWeightHistoryApi<BlockNumber, ConsumedWeight> where
+		BlockNumber: codec::Codec,
+		ConsumedWeight: codec::Codec,
+	{
+		/// Get the normal, operational and mandatory weight consumed by each of the blocks kept
+		/// in the history, oldest first.
+		fn block_weight_history() -> Vec<(BlockNumber, ConsumedWeight)>;
+	}
 }
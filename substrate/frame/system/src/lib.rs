@@ -118,7 +118,7 @@ use sp_runtime::{
 		InvalidTransaction, TransactionLongevity, TransactionSource, TransactionValidity,
 		ValidTransaction,
 	},
-	DispatchError, RuntimeDebug,
+	DispatchError, RuntimeDebug, SaturatedConversion,
 };
 use sp_version::RuntimeVersion;
 
@@ -328,6 +328,7 @@ pub mod pallet {
 			type AccountId = u64;
 			type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
 			type MaxConsumers = frame_support::traits::ConstU32<16>;
+			type BlockWeightHistoryLength = frame_support::traits::ConstU32<0>;
 			type AccountData = ();
 			type OnNewAccount = ();
 			type OnKilledAccount = ();
@@ -393,6 +394,9 @@ pub mod pallet {
 			/// The maximum number of consumers allowed on a single account. Using 128 as default.
 			type MaxConsumers = frame_support::traits::ConstU32<128>;
 
+			/// Recording the weight history of recent blocks is disabled by default.
+			type BlockWeightHistoryLength = frame_support::traits::ConstU32<0>;
+
 			/// The default data to be stored in an account.
 			type AccountData = ();
 
@@ -644,6 +648,13 @@ pub mod pallet {
 		/// The maximum number of consumers allowed on a single account.
 		type MaxConsumers: ConsumerLimits;
 
+		/// The number of most recent blocks whose consumed weight is kept in
+		/// [`BlockWeightHistory`].
+		///
+		/// Recording costs one storage write per block. Set to zero to disable it.
+		#[pallet::constant]
+		type BlockWeightHistoryLength: Get<u32>;
+
 		/// All migrations that should run in the next runtime upgrade.
 		///
 		/// These used to be formerly configured in `Executive`. Parachains need to ensure that
@@ -985,6 +996,12 @@ pub mod pallet {
 	#[pallet::getter(fn block_weight)]
 	pub type BlockWeight<T: Config> = StorageValue<_, ConsumedWeight, ValueQuery>;
 
+	/// The weight consumed by each of the last `BlockWeightHistoryLength` blocks, keyed by the
+	/// block number modulo `BlockWeightHistoryLength`.
+	#[pallet::storage]
+	pub type BlockWeightHistory<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (BlockNumberFor<T>, ConsumedWeight), OptionQuery>;
+
 	/// Total length (in bytes) for all extrinsics put together, for the current block.
 	#[pallet::storage]
 	#[pallet::whitelist_storage]
@@ -1057,6 +1074,16 @@ pub mod pallet {
 	pub(super) type EventTopics<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::Hash, Vec<(BlockNumberFor<T>, EventIndex)>, ValueQuery>;
 
+	/// Mapping between the index of an extrinsic in the current block and the half-open range
+	/// `[start, end)` of the events it deposited into the `<Events<T>>` list.
+	///
+	/// Only maintained when the `event-index` feature is enabled. Like the events themselves,
+	/// it is cleared by `Self::initialize` and can be inspected by the client after the block.
+	#[cfg(feature = "event-index")]
+	#[pallet::storage]
+	pub type ExtrinsicEventRanges<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (EventIndex, EventIndex), OptionQuery>;
+
 	/// Stores the `spec_version` and `spec_name` of when the last runtime upgrade happened.
 	#[pallet::storage]
 	#[pallet::unbounded]
@@ -1816,6 +1843,11 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let phase = ExecutionPhase::<T>::get().unwrap_or_default();
+		#[cfg(feature = "event-index")]
+		let extrinsic_index = match phase {
+			Phase::ApplyExtrinsic(extrinsic_index) => Some(extrinsic_index),
+			_ => None,
+		};
 		let event = EventRecord { phase, event, topics: topics.to_vec() };
 
 		// Index of the event to be added.
@@ -1833,6 +1865,15 @@ impl<T: Config> Pallet<T> {
 
 		Events::<T>::append(event);
 
+		// Events of an extrinsic are contiguous, since extrinsics are applied one after another.
+		#[cfg(feature = "event-index")]
+		if let Some(extrinsic_index) = extrinsic_index {
+			ExtrinsicEventRanges::<T>::mutate(extrinsic_index, |range| {
+				let start = range.map_or(event_idx, |(start, _)| start);
+				*range = Some((start, event_idx.saturating_add(1)));
+			});
+		}
+
 		for topic in topics {
 			<EventTopics<T>>::append(topic, &(block_number, event_idx));
 		}
@@ -1944,6 +1985,30 @@ impl<T: Config> Pallet<T> {
 		);
 	}
 
+	/// Record the weight consumed by the current block in the [`BlockWeightHistory`].
+	fn note_block_weight() {
+		let history_len = T::BlockWeightHistoryLength::get();
+		if history_len.is_zero() {
+			return
+		}
+		let number = Self::block_number();
+		let slot = (number % history_len.into()).saturated_into::<u32>();
+		BlockWeightHistory::<T>::insert(slot, (number, BlockWeight::<T>::get()));
+	}
+
+	/// The weight consumed by each of the last `BlockWeightHistoryLength` finalized blocks,
+	/// oldest first.
+	pub fn block_weight_history() -> Vec<(BlockNumberFor<T>, ConsumedWeight)> {
+		let history_len = T::BlockWeightHistoryLength::get();
+		// Entries of blocks that are older than the window are left over from a longer window.
+		let oldest = Self::block_number().saturating_sub(history_len.into());
+		let mut history: Vec<_> = BlockWeightHistory::<T>::iter_values()
+			.filter(|(number, _)| *number >= oldest)
+			.collect();
+		history.sort_by_key(|(number, _)| *number);
+		history
+	}
+
 	/// Remove temporary "environment" entries in storage, compute the storage root and return the
 	/// resulting header for this block.
 	pub fn finalize() -> HeaderFor<T> {
@@ -1952,12 +2017,14 @@ impl<T: Config> Pallet<T> {
 		AllExtrinsicsLen::<T>::kill();
 		storage::unhashed::kill(well_known_keys::INTRABLOCK_ENTROPY);
 		InherentsApplied::<T>::kill();
+		Self::note_block_weight();
 
 		// The following fields
 		//
 		// - <Events<T>>
 		// - <EventCount<T>>
 		// - <EventTopics<T>>
+		// - <ExtrinsicEventRanges<T>> (with the `event-index` feature)
 		// - <Number<T>>
 		// - <ParentHash<T>>
 		// - <Digest<T>>
@@ -2040,6 +2107,31 @@ impl<T: Config> Pallet<T> {
 		Events::<T>::stream_iter()
 	}
 
+	/// Get the half-open range of indexes into the current events that were deposited by the
+	/// extrinsic at `extrinsic_index`, if it deposited any.
+	#[cfg(feature = "event-index")]
+	pub fn extrinsic_event_range(extrinsic_index: u32) -> Option<(EventIndex, EventIndex)> {
+		ExtrinsicEventRanges::<T>::get(extrinsic_index)
+	}
+
+	/// Get the events deposited by the extrinsic at `extrinsic_index`.
+	///
+	/// Should only be called if you know what you are doing and outside of the runtime block
+	/// execution else it can have a large impact on the PoV size of a block.
+	#[cfg(feature = "event-index")]
+	pub fn extrinsic_events_no_consensus(
+		extrinsic_index: u32,
+	) -> Vec<EventRecord<T::RuntimeEvent, T::Hash>> {
+		let Some((start, end)) = Self::extrinsic_event_range(extrinsic_index) else {
+			return Vec::new()
+		};
+		Self::read_events_no_consensus()
+			.skip(start as usize)
+			.take(end.saturating_sub(start) as usize)
+			.map(|e| *e)
+			.collect()
+	}
+
 	/// Read and return the events of a specific pallet, as denoted by `E`.
 	///
 	/// This is useful for a pallet that wishes to read only the events it has deposited into
@@ -2137,6 +2229,8 @@ impl<T: Config> Pallet<T> {
 		<Events<T>>::kill();
 		EventCount::<T>::kill();
 		let _ = <EventTopics<T>>::clear(u32::max_value(), None);
+		#[cfg(feature = "event-index")]
+		let _ = <ExtrinsicEventRanges<T>>::clear(u32::max_value(), None);
 	}
 
 	/// Assert the given `event` exists.
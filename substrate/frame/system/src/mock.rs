@@ -16,7 +16,7 @@
 // limitations under the License.
 
 use crate::{self as frame_system, *};
-use frame_support::{derive_impl, parameter_types};
+use frame_support::{derive_impl, parameter_types, traits::ConstU32};
 use sp_runtime::{type_with_default::TypeWithDefault, BuildStorage, Perbill};
 
 type Block = mocking::MockBlock<Test>;
@@ -96,6 +96,7 @@ impl Config for Test {
 	type OnKilledAccount = RecordKilled;
 	type MultiBlockMigrator = MockedMigrator;
 	type Nonce = TypeWithDefault<u64, DefaultNonceProvider>;
+	type BlockWeightHistoryLength = ConstU32<3>;
 }
 
 parameter_types! {
@@ -578,6 +578,42 @@ fn deposit_event_topics() {
 	});
 }
 
+#[cfg(feature = "event-index")]
+#[test]
+fn extrinsic_event_ranges_work() {
+	new_test_ext().execute_with(|| {
+		System::reset_events();
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		System::deposit_event(SysEvent::CodeUpdated);
+		System::note_finished_initialize();
+
+		// First extrinsic deposits two events, the second one none, the third one.
+		System::deposit_event(SysEvent::NewAccount { account: 1 });
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+		System::deposit_event(SysEvent::NewAccount { account: 2 });
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+		System::note_finished_extrinsics();
+		System::deposit_event(SysEvent::KilledAccount { account: 2 });
+		System::finalize();
+
+		assert_eq!(System::extrinsic_event_range(0), Some((1, 3)));
+		assert_eq!(System::extrinsic_event_range(1), Some((3, 4)));
+		assert_eq!(System::extrinsic_event_range(2), Some((4, 6)));
+		assert_eq!(System::extrinsic_event_range(3), None);
+
+		let events = System::extrinsic_events_no_consensus(2);
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[0].event, SysEvent::NewAccount { account: 2 }.into());
+		assert!(events.iter().all(|e| e.phase == Phase::ApplyExtrinsic(2)));
+
+		// The ranges are cleared together with the events.
+		System::reset_events();
+		assert_eq!(System::extrinsic_event_range(0), None);
+		assert!(System::extrinsic_events_no_consensus(0).is_empty());
+	});
+}
+
 #[test]
 fn event_util_functions_should_work() {
 	new_test_ext().execute_with(|| {
@@ -589,6 +625,34 @@ fn event_util_functions_should_work() {
 	});
 }
 
+#[test]
+fn block_weight_history_keeps_the_most_recent_blocks() {
+	new_test_ext().execute_with(|| {
+		for n in 1..=5 {
+			System::reset_events();
+			System::initialize(&n, &[n as u8 - 1; 32].into(), &Default::default());
+			System::register_extra_weight_unchecked(
+				Weight::from_parts(n * 10, 0),
+				DispatchClass::Normal,
+			);
+			System::register_extra_weight_unchecked(
+				Weight::from_parts(n, 0),
+				DispatchClass::Mandatory,
+			);
+			System::finalize();
+		}
+
+		// The history length of the mock is 3.
+		let history = System::block_weight_history();
+		assert_eq!(history.iter().map(|(n, _)| *n).collect::<Vec<_>>(), vec![3, 4, 5]);
+		let (_, weight) = &history[2];
+		assert_eq!(*weight.get(DispatchClass::Normal), Weight::from_parts(50, 0));
+		assert_eq!(*weight.get(DispatchClass::Operational), Weight::zero());
+		assert_eq!(*weight.get(DispatchClass::Mandatory), Weight::from_parts(5, 0));
+		assert_eq!(BlockWeightHistory::<Test>::iter().count(), 3);
+	});
+}
+
 #[test]
 fn prunes_block_hash_mappings() {
 	new_test_ext().execute_with(|| {
@@ -150,12 +150,14 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A transaction fee `actual_fee`, of which `tip` was added to the minimum inclusion fee,
-		/// has been paid by `who` in an asset `asset_id`.
+		/// has been paid by `who` in an asset `asset_id`. The fee was swapped for `native_fee` in
+		/// the native currency, tip included.
 		AssetTxFeePaid {
 			who: T::AccountId,
 			actual_fee: BalanceOf<T>,
 			tip: BalanceOf<T>,
 			asset_id: T::AssetId,
+			native_fee: BalanceOf<T>,
 		},
 		/// A swap of the refund in native currency back to asset failed.
 		AssetRefundFailed { native_amount_kept: BalanceOf<T> },
@@ -190,6 +192,11 @@ where
 		Self { tip, asset_id }
 	}
 
+	/// Returns the tip as being chosen by the transaction sender.
+	pub fn tip(&self) -> BalanceOf<T> {
+		self.tip
+	}
+
 	/// Fee withdrawal logic that dispatches to either [`Config::OnChargeAssetTransaction`] or
 	/// [`pallet_transaction_payment::Config::OnChargeTransaction`].
 	fn withdraw_fee(
@@ -413,6 +420,7 @@ where
 					actual_fee: converted_fee,
 					tip,
 					asset_id,
+					native_fee: actual_fee,
 				});
 
 				Ok(unspent_weight)
@@ -270,6 +270,9 @@ where
 
 			if debt.peek().is_zero() {
 				// No refund given.
+				Pallet::<T>::deposit_event(Event::<T>::AssetRefundFailed {
+					native_amount_kept: refund_amount,
+				});
 				(initial_asset_consumed, fee_paid)
 			} else {
 				let (refund, adjusted_paid) = fee_paid.split(refund_amount);
@@ -296,6 +299,9 @@ where
 					// The error should not occur since swap was quoted before.
 					Err((refund, _)) => {
 						defensive!("Refund swap should pass for the quoted amount");
+						Pallet::<T>::deposit_event(Event::<T>::AssetRefundFailed {
+							native_amount_kept: refund_amount,
+						});
 						match F::settle(who, debt, Preservation::Expendable) {
 							Ok(dust) => ensure!(dust.peek().is_zero(), InvalidTransaction::Payment),
 							// The error should not occur as the `debt` was just withdrawn above.
@@ -510,6 +510,13 @@ fn asset_transaction_payment_with_tip_and_refund() {
 				who: caller,
 				amount: expected_token_refund,
 			}));
+			System::assert_last_event(RuntimeEvent::AssetTxPayment(Event::AssetTxFeePaid {
+				who: caller,
+				actual_fee: fee_in_asset - expected_token_refund,
+				tip,
+				asset_id: asset_id.into(),
+				native_fee: expected_fee + tip,
+			}));
 		});
 }
 
@@ -19,6 +19,9 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
 use codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
 
@@ -33,6 +36,14 @@ sp_api::decl_runtime_apis! {
 		fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> FeeDetails<Balance>;
 		fn query_weight_to_fee(weight: sp_weights::Weight) -> Balance;
 		fn query_length_to_fee(length: u32) -> Balance;
+
+		/// Query the fee details of the given encoded extrinsic.
+		///
+		/// Unlike `query_fee_details`, the tip of the extrinsic is accounted for. The adjusted
+		/// weight fee includes the weight of the transaction extensions. Returns `None` if the
+		/// extrinsic can't be decoded.
+		#[api_version(5)]
+		fn query_fee_breakdown(encoded_xt: Vec<u8>) -> Option<FeeDetails<Balance>>;
 	}
 
 	#[api_version(3)]
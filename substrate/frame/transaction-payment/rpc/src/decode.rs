@@ -0,0 +1,543 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of extrinsics and events into JSON, driven by the runtime metadata.
+//!
+//! See [`crate::DecodedExtrinsic`] for how values are represented. Unsigned integers that don't fit
+//! into a `u64` become hex strings so that amounts can be read as [`sp_rpc::number::NumberOrHex`].
+
+use codec::{Compact, Decode, Error};
+use frame_metadata::{
+	v15::{RuntimeMetadataV15, StorageEntryType, StorageHasher},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive, Variant};
+use serde_json::{Map, Value};
+use sp_core::U256;
+
+/// The version of the metadata the decoding relies on.
+pub(crate) const METADATA_VERSION: u32 = 15;
+
+/// Bit of the extrinsic version byte set for signed extrinsics.
+const SIGNED_EXTRINSIC: u8 = 0b1000_0000;
+
+/// Bit of the extrinsic version byte set for general extrinsics.
+const GENERAL_EXTRINSIC: u8 = 0b0100_0000;
+
+/// The maximum nesting depth of the decoded values.
+///
+/// Recursive types like nested batch calls could otherwise exhaust the stack of the RPC server.
+const MAX_DEPTH: u32 = 256;
+
+/// Decode the versioned metadata returned by `Metadata_metadata_at_version`.
+pub(crate) fn decode_metadata(encoded: &[u8]) -> Result<RuntimeMetadataV15, Error> {
+	match RuntimeMetadataPrefixed::decode(&mut &encoded[..])?.1 {
+		RuntimeMetadata::V15(metadata) => Ok(metadata),
+		_ => Err("unsupported metadata version".into()),
+	}
+}
+
+/// Decode an encoded extrinsic, including its length prefix.
+///
+/// Returns an object with the `version` of the extrinsic, the `signature` or the `extensions`
+/// if any and the `call`.
+pub(crate) fn decode_extrinsic(
+	metadata: &RuntimeMetadataV15,
+	encoded: &[u8],
+) -> Result<Value, Error> {
+	let registry = &metadata.types;
+	let extrinsic = &metadata.extrinsic;
+	let input = &mut &encoded[..];
+
+	let len = Compact::<u32>::decode(input)?.0 as usize;
+	if input.len() != len {
+		return Err("extrinsic length prefix doesn't match its length".into())
+	}
+
+	let version = u8::decode(input)?;
+	let mut decoded = Map::new();
+	decoded.insert("version".into(), (version & !(SIGNED_EXTRINSIC | GENERAL_EXTRINSIC)).into());
+
+	if version & SIGNED_EXTRINSIC != 0 {
+		let mut signature = Map::new();
+		signature.insert("address".into(), decode_value(registry, extrinsic.address_ty.id, input)?);
+		signature
+			.insert("signature".into(), decode_value(registry, extrinsic.signature_ty.id, input)?);
+		signature
+			.insert("extensions".into(), decode_value(registry, extrinsic.extra_ty.id, input)?);
+		decoded.insert("signature".into(), signature.into());
+	} else if version & GENERAL_EXTRINSIC != 0 {
+		decoded.insert("extensionVersion".into(), u8::decode(input)?.into());
+		decoded.insert("extensions".into(), decode_value(registry, extrinsic.extra_ty.id, input)?);
+	}
+
+	decoded.insert("call".into(), decode_value(registry, extrinsic.call_ty.id, input)?);
+
+	if !input.is_empty() {
+		return Err("extrinsic has trailing bytes".into())
+	}
+
+	Ok(decoded.into())
+}
+
+/// Decode the encoded `System::Events` of a block into the list of event records.
+pub(crate) fn decode_events(
+	metadata: &RuntimeMetadataV15,
+	encoded: &[u8],
+) -> Result<Vec<Value>, Error> {
+	let events_ty = metadata
+		.pallets
+		.iter()
+		.find(|pallet| pallet.name == "System")
+		.and_then(|pallet| pallet.storage.as_ref())
+		.and_then(|storage| storage.entries.iter().find(|entry| entry.name == "Events"))
+		.and_then(|entry| match &entry.ty {
+			StorageEntryType::Plain(ty) => Some(ty.id),
+			_ => None,
+		})
+		.ok_or("`System::Events` not found in the metadata")?;
+
+	let input = &mut &encoded[..];
+	match decode_value(&metadata.types, events_ty, input)? {
+		Value::Array(events) => Ok(events),
+		_ => Err("`System::Events` is not a sequence".into()),
+	}
+}
+
+/// A storage map, as described by the metadata.
+pub(crate) struct StorageMap<'a> {
+	/// The prefix of the keys of the map.
+	pub prefix: Vec<u8>,
+	/// The hashers of the keys, one per key of the map.
+	pub hashers: &'a [StorageHasher],
+	/// The type of the key, a tuple of the keys if the map has several.
+	pub key_ty: u32,
+	/// The type of the values.
+	pub value_ty: u32,
+}
+
+/// Find the storage map `entry` of `pallet` in the metadata.
+pub(crate) fn storage_map<'a>(
+	metadata: &'a RuntimeMetadataV15,
+	pallet: &str,
+	entry: &str,
+) -> Result<StorageMap<'a>, Error> {
+	let storage = metadata
+		.pallets
+		.iter()
+		.find(|p| p.name == pallet)
+		.and_then(|pallet| pallet.storage.as_ref())
+		.ok_or("pallet storage not found in the metadata")?;
+	let entry = storage
+		.entries
+		.iter()
+		.find(|e| e.name == entry)
+		.ok_or("storage entry not found in the metadata")?;
+
+	match &entry.ty {
+		StorageEntryType::Map { hashers, key, value } => Ok(StorageMap {
+			prefix: [
+				sp_core::twox_128(storage.prefix.as_bytes()),
+				sp_core::twox_128(entry.name.as_bytes()),
+			]
+			.concat(),
+			hashers,
+			key_ty: key.id,
+			value_ty: value.id,
+		}),
+		StorageEntryType::Plain(_) => Err("storage entry is not a map".into()),
+	}
+}
+
+/// Decode the keys of an entry of `map` from the storage key `key`, without its prefix.
+///
+/// Keys hashed without their value being appended can't be recovered, their hash is returned
+/// instead.
+pub(crate) fn decode_storage_keys(
+	registry: &PortableRegistry,
+	map: &StorageMap,
+	key: &[u8],
+) -> Result<Vec<Value>, Error> {
+	let key_tys = match map.hashers {
+		[_] => vec![map.key_ty],
+		hashers =>
+			match &registry.resolve(map.key_ty).ok_or("type not found in the metadata")?.type_def {
+				TypeDef::Tuple(tuple) if tuple.fields.len() == hashers.len() =>
+					tuple.fields.iter().map(|field| field.id).collect(),
+				_ => return Err("storage key doesn't match its hashers".into()),
+			},
+	};
+
+	let input = &mut &key[..];
+	let keys = map
+		.hashers
+		.iter()
+		.zip(key_tys)
+		.map(|(hasher, ty)| {
+			let (hash_len, concat) = match hasher {
+				StorageHasher::Blake2_128 | StorageHasher::Twox128 => (16, false),
+				StorageHasher::Blake2_256 | StorageHasher::Twox256 => (32, false),
+				StorageHasher::Blake2_128Concat => (16, true),
+				StorageHasher::Twox64Concat => (8, true),
+				StorageHasher::Identity => (0, true),
+			};
+			let hash = take(input, hash_len)?;
+			if concat {
+				decode_value(registry, ty, input)
+			} else {
+				Ok(hex(hash))
+			}
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	if !input.is_empty() {
+		return Err("storage key has trailing bytes".into())
+	}
+
+	Ok(keys)
+}
+
+/// Decode a value of type `ty` from `input`.
+///
+/// Fails for values nested deeper than [`MAX_DEPTH`].
+pub(crate) fn decode_value(
+	registry: &PortableRegistry,
+	ty: u32,
+	input: &mut &[u8],
+) -> Result<Value, Error> {
+	decode_nested(registry, ty, input, 0)
+}
+
+/// Decode a value of type `ty` nested `depth` levels deep from `input`.
+fn decode_nested(
+	registry: &PortableRegistry,
+	ty: u32,
+	input: &mut &[u8],
+	depth: u32,
+) -> Result<Value, Error> {
+	if depth > MAX_DEPTH {
+		return Err("value is nested too deeply".into())
+	}
+	let depth = depth + 1;
+	let ty = registry.resolve(ty).ok_or("type not found in the metadata")?;
+
+	match &ty.type_def {
+		TypeDef::Composite(composite) => decode_fields(registry, &composite.fields, input, depth),
+		TypeDef::Variant(variant) => {
+			let index = u8::decode(input)?;
+			let variant = variant
+				.variants
+				.iter()
+				.find(|variant| variant.index == index)
+				.ok_or("unknown variant index")?;
+
+			if ty.path.segments == ["Option"] {
+				return match variant.fields.first() {
+					Some(field) => decode_nested(registry, field.ty.id, input, depth),
+					None => Ok(Value::Null),
+				}
+			}
+
+			decode_variant(registry, variant, input, depth)
+		},
+		TypeDef::Sequence(sequence) => {
+			let len = Compact::<u32>::decode(input)?.0 as usize;
+			decode_elements(registry, sequence.type_param.id, len, input, depth)
+		},
+		TypeDef::Array(array) =>
+			decode_elements(registry, array.type_param.id, array.len as usize, input, depth),
+		TypeDef::Tuple(tuple) if tuple.fields.is_empty() => Ok(Value::Null),
+		TypeDef::Tuple(tuple) => tuple
+			.fields
+			.iter()
+			.map(|field| decode_nested(registry, field.id, input, depth))
+			.collect::<Result<Vec<_>, _>>()
+			.map(Value::Array),
+		TypeDef::Primitive(primitive) => decode_primitive(primitive, input),
+		TypeDef::Compact(compact) => decode_compact(registry, compact.type_param.id, input, depth),
+		TypeDef::BitSequence(bits) => {
+			let store = registry
+				.resolve(bits.bit_store_type.id)
+				.ok_or("type not found in the metadata")?;
+			let store_size = match &store.type_def {
+				TypeDef::Primitive(TypeDefPrimitive::U8) => 1,
+				TypeDef::Primitive(TypeDefPrimitive::U16) => 2,
+				TypeDef::Primitive(TypeDefPrimitive::U32) => 4,
+				TypeDef::Primitive(TypeDefPrimitive::U64) => 8,
+				_ => return Err("unsupported bit store type".into()),
+			};
+			let bits = Compact::<u32>::decode(input)?.0 as usize;
+			let len = bits.div_ceil(store_size * 8) * store_size;
+			Ok(hex(take(input, len)?))
+		},
+	}
+}
+
+fn decode_fields(
+	registry: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	input: &mut &[u8],
+	depth: u32,
+) -> Result<Value, Error> {
+	match fields {
+		[] => Ok(Value::Null),
+		[field] if field.name.is_none() => decode_nested(registry, field.ty.id, input, depth),
+		fields if fields.iter().all(|field| field.name.is_some()) => fields
+			.iter()
+			.map(|field| {
+				let name = field.name.clone().unwrap_or_default();
+				Ok((name, decode_nested(registry, field.ty.id, input, depth)?))
+			})
+			.collect::<Result<Map<_, _>, Error>>()
+			.map(Value::Object),
+		fields => fields
+			.iter()
+			.map(|field| decode_nested(registry, field.ty.id, input, depth))
+			.collect::<Result<Vec<_>, _>>()
+			.map(Value::Array),
+	}
+}
+
+fn decode_variant(
+	registry: &PortableRegistry,
+	variant: &Variant<PortableForm>,
+	input: &mut &[u8],
+	depth: u32,
+) -> Result<Value, Error> {
+	if variant.fields.is_empty() {
+		return Ok(Value::String(variant.name.clone()))
+	}
+
+	let mut decoded = Map::new();
+	decoded.insert(variant.name.clone(), decode_fields(registry, &variant.fields, input, depth)?);
+	Ok(decoded.into())
+}
+
+fn decode_elements(
+	registry: &PortableRegistry,
+	ty: u32,
+	len: usize,
+	input: &mut &[u8],
+	depth: u32,
+) -> Result<Value, Error> {
+	let is_byte = registry
+		.resolve(ty)
+		.is_some_and(|ty| matches!(ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)));
+	if is_byte {
+		return Ok(hex(take(input, len)?))
+	}
+
+	(0..len)
+		.map(|_| decode_nested(registry, ty, input, depth))
+		.collect::<Result<Vec<_>, _>>()
+		.map(Value::Array)
+}
+
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, Error> {
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => bool::decode(input)?.into(),
+		TypeDefPrimitive::Char =>
+			char::from_u32(u32::decode(input)?).ok_or("invalid char")?.to_string().into(),
+		TypeDefPrimitive::Str => String::decode(input)?.into(),
+		TypeDefPrimitive::U8 => u8::decode(input)?.into(),
+		TypeDefPrimitive::U16 => u16::decode(input)?.into(),
+		TypeDefPrimitive::U32 => u32::decode(input)?.into(),
+		TypeDefPrimitive::U64 => u64::decode(input)?.into(),
+		TypeDefPrimitive::U128 => unsigned(u128::decode(input)?),
+		TypeDefPrimitive::U256 => {
+			let value = U256::from_little_endian(take(input, 32)?);
+			match u128::try_from(value) {
+				Ok(value) => unsigned(value),
+				Err(_) => Value::String(format!("{:#x}", value)),
+			}
+		},
+		TypeDefPrimitive::I8 => i8::decode(input)?.into(),
+		TypeDefPrimitive::I16 => i16::decode(input)?.into(),
+		TypeDefPrimitive::I32 => i32::decode(input)?.into(),
+		TypeDefPrimitive::I64 => i64::decode(input)?.into(),
+		TypeDefPrimitive::I128 => {
+			let value = i128::decode(input)?;
+			i64::try_from(value)
+				.map(Value::from)
+				.unwrap_or_else(|_| value.to_string().into())
+		},
+		TypeDefPrimitive::I256 => hex(take(input, 32)?),
+	})
+}
+
+fn decode_compact(
+	registry: &PortableRegistry,
+	ty: u32,
+	input: &mut &[u8],
+	depth: u32,
+) -> Result<Value, Error> {
+	if depth > MAX_DEPTH {
+		return Err("value is nested too deeply".into())
+	}
+	let ty = registry.resolve(ty).ok_or("type not found in the metadata")?;
+
+	match &ty.type_def {
+		TypeDef::Primitive(
+			TypeDefPrimitive::U8 |
+			TypeDefPrimitive::U16 |
+			TypeDefPrimitive::U32 |
+			TypeDefPrimitive::U64 |
+			TypeDefPrimitive::U128,
+		) => Ok(unsigned(Compact::<u128>::decode(input)?.0)),
+		TypeDef::Composite(composite) => match &composite.fields[..] {
+			[] => Ok(Value::Null),
+			[field] => {
+				let value = decode_compact(registry, field.ty.id, input, depth + 1)?;
+				Ok(match &field.name {
+					Some(name) => Value::Object(Map::from_iter([(name.clone(), value)])),
+					None => value,
+				})
+			},
+			_ => Err("unsupported compact type".into()),
+		},
+		TypeDef::Tuple(tuple) if tuple.fields.is_empty() => Ok(Value::Null),
+		_ => Err("unsupported compact type".into()),
+	}
+}
+
+fn unsigned(value: u128) -> Value {
+	match u64::try_from(value) {
+		Ok(value) => value.into(),
+		Err(_) => Value::String(format!("{:#x}", value)),
+	}
+}
+
+fn hex(bytes: &[u8]) -> Value {
+	Value::String(format!("0x{}", sp_core::hexdisplay::HexDisplay::from(&bytes)))
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+	if input.len() < len {
+		return Err("not enough data".into())
+	}
+
+	let (taken, rest) = input.split_at(len);
+	*input = rest;
+	Ok(taken)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use scale_info::{MetaType, Registry, TypeInfo};
+	use serde_json::json;
+
+	#[derive(Encode, TypeInfo)]
+	enum Call {
+		Remark { remark: Vec<u8> },
+		Transfer(u32, #[codec(compact)] u128),
+		Noop,
+	}
+
+	#[derive(Encode, TypeInfo)]
+	struct Record {
+		call: Call,
+		tip: Option<u128>,
+		bits: (bool, [u16; 2]),
+	}
+
+	#[derive(Encode, TypeInfo)]
+	enum Nested {
+		Leaf,
+		Node(Box<Nested>),
+	}
+
+	fn decode<T: TypeInfo + Encode + 'static>(value: T) -> Value {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&MetaType::new::<T>());
+		let registry: PortableRegistry = registry.into();
+
+		let encoded = value.encode();
+		let input = &mut &encoded[..];
+		let decoded = decode_value(&registry, ty.id, input).unwrap();
+		assert!(input.is_empty());
+		decoded
+	}
+
+	#[test]
+	fn values_are_decoded_into_json() {
+		assert_eq!(
+			decode(Record {
+				call: Call::Remark { remark: vec![1, 2, 255] },
+				tip: None,
+				bits: (true, [1, 2])
+			}),
+			json!({
+				"call": { "Remark": { "remark": "0x0102ff" } },
+				"tip": null,
+				"bits": [true, [1, 2]],
+			})
+		);
+		assert_eq!(
+			decode(Record {
+				call: Call::Transfer(7, u128::MAX),
+				tip: Some(3),
+				bits: (false, [0, 0])
+			}),
+			json!({
+				"call": { "Transfer": [7, "0xffffffffffffffffffffffffffffffff"] },
+				"tip": 3,
+				"bits": [false, [0, 0]],
+			})
+		);
+		assert_eq!(decode(Call::Noop), json!("Noop"));
+	}
+
+	#[test]
+	fn deeply_nested_values_are_rejected() {
+		let nested =
+			|depth| (0..depth).fold(Nested::Leaf, |inner, _| Nested::Node(Box::new(inner)));
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&MetaType::new::<Nested>());
+		let registry: PortableRegistry = registry.into();
+
+		let encoded = nested(MAX_DEPTH / 2).encode();
+		assert!(decode_value(&registry, ty.id, &mut &encoded[..]).is_ok());
+		let encoded = nested(MAX_DEPTH * 2).encode();
+		assert!(decode_value(&registry, ty.id, &mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn storage_keys_are_decoded() {
+		let mut registry = Registry::new();
+		let key_ty = registry.register_type(&MetaType::new::<(u32, u64, u8)>());
+		let registry: PortableRegistry = registry.into();
+		let hashers =
+			[StorageHasher::Twox64Concat, StorageHasher::Blake2_128, StorageHasher::Identity];
+		let map =
+			StorageMap { prefix: Vec::new(), hashers: &hashers, key_ty: key_ty.id, value_ty: 0 };
+
+		let key = [
+			sp_core::twox_64(&7u32.encode()).as_slice(),
+			&7u32.encode(),
+			&sp_core::blake2_128(&8u64.encode()),
+			&9u8.encode(),
+		]
+		.concat();
+		assert_eq!(
+			decode_storage_keys(&registry, &map, &key).unwrap(),
+			vec![json!(7), hex(&sp_core::blake2_128(&8u64.encode())), json!(9)],
+		);
+		assert!(decode_storage_keys(&registry, &map, &key[..key.len() - 1]).is_err());
+	}
+}
@@ -19,7 +19,7 @@
 
 use std::sync::Arc;
 
-use codec::{Codec, Decode};
+use codec::{Codec, Decode, Encode};
 use jsonrpsee::{
 	core::RpcResult,
 	proc_macros::rpc,
@@ -29,14 +29,24 @@ use jsonrpsee::{
 	},
 };
 use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
-use sp_api::ProvideRuntimeApi;
+use sc_client_api::{BlockBackend, StorageProvider};
+use sc_transaction_pool_api::{
+	BlockHash, InPoolTransaction, TransactionPool, TransactionPriority, TxHash,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::{Metadata, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::Bytes;
+use sp_core::{storage::StorageKey, Bytes};
 use sp_rpc::number::NumberOrHex;
-use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, MaybeDisplay};
 
 pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
 
+mod decode;
+pub mod storage;
+
+pub use storage::{StorageDecode, StorageDecodeApiClient, StorageDecodeApiServer};
+
 #[rpc(client, server)]
 pub trait TransactionPaymentApi<BlockHash, ResponseType> {
 	#[method(name = "payment_queryInfo")]
@@ -50,6 +60,83 @@ pub trait TransactionPaymentApi<BlockHash, ResponseType> {
 	) -> RpcResult<FeeDetails<NumberOrHex>>;
 }
 
+/// The fee of a decoded extrinsic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "kind")]
+pub enum ExtrinsicFee {
+	/// The fee paid by an extrinsic included in a block, as reported by its
+	/// `TransactionPayment::TransactionFeePaid` event.
+	Paid { actual_fee: NumberOrHex, tip: NumberOrHex },
+	/// The fee a pending extrinsic would pay, tip excluded.
+	Estimated(FeeDetails<NumberOrHex>),
+	/// The extrinsic included in a block paid no fee, e.g. because it is an inherent.
+	None,
+}
+
+/// An extrinsic decoded against the metadata of the runtime.
+///
+/// Composites with named fields are converted to objects, other composites, tuples and
+/// sequences to arrays. Variants without fields are converted to their name and variants with
+/// fields to an object with their name as the only key. `Option`s are converted to `null` or
+/// their value. Bytes and unsigned integers that don't fit into a `u64` are converted to hex
+/// strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedExtrinsic {
+	/// The `version` of the extrinsic, its `signature` or `extensions` if any and its `call`.
+	pub extrinsic: serde_json::Value,
+	/// The event records of the extrinsic. Empty for pending extrinsics.
+	pub events: Vec<serde_json::Value>,
+	/// The fee of the extrinsic.
+	pub fee: ExtrinsicFee,
+}
+
+/// Decodes extrinsics against the metadata of the runtime, for clients that don't have it.
+#[rpc(client, server)]
+pub trait ExtrinsicDecodeApi<BlockHash> {
+	/// Decode the pending extrinsic `encoded_xt` and estimate its fee at block `at`.
+	#[method(name = "payment_decodeExtrinsic")]
+	fn decode_extrinsic(
+		&self,
+		encoded_xt: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<DecodedExtrinsic>;
+
+	/// Decode the extrinsic at `index` in block `at`, along with its events and the fee it paid.
+	#[method(name = "payment_decodeBlockExtrinsic")]
+	fn decode_block_extrinsic(&self, at: BlockHash, index: u32) -> RpcResult<DecodedExtrinsic>;
+}
+
+/// The maximum number of transactions returned by `payment_readyTransactions`.
+pub const MAX_READY_TRANSACTIONS: u32 = 1024;
+
+/// A transaction of the ready set of the transaction pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyTransaction<Hash> {
+	/// The hash of the transaction.
+	pub hash: Hash,
+	/// The priority the transaction was validated with.
+	pub priority: TransactionPriority,
+	/// For how many blocks the transaction stays valid.
+	pub longevity: u64,
+	/// The length of the encoded transaction.
+	pub encoded_len: u32,
+	/// The fee the transaction would pay at the best block, tip excluded.
+	pub estimated_fee: NumberOrHex,
+}
+
+/// Inspection of the ready set of the transaction pool, for block builders.
+#[rpc(client, server)]
+pub trait ReadySetApi<Hash> {
+	/// Returns up to `limit` transactions of the ready set, in the order the block builder
+	/// would include them, along with their fee at the current fee multiplier.
+	///
+	/// At most [`MAX_READY_TRANSACTIONS`] are returned, also if no `limit` is given.
+	#[method(name = "payment_readyTransactions", with_extensions)]
+	fn ready_transactions(&self, limit: Option<u32>) -> RpcResult<Vec<ReadyTransaction<Hash>>>;
+}
+
 /// Provides RPC methods to query a dispatchable's class, weight and fee.
 pub struct TransactionPayment<C, P> {
 	/// Shared reference to the client.
@@ -64,6 +151,36 @@ impl<C, P> TransactionPayment<C, P> {
 	}
 }
 
+/// Provides RPC methods to decode extrinsics along with their fee.
+pub struct ExtrinsicDecode<C, Block, Backend, Balance> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(Block, Backend, Balance)>,
+}
+
+impl<C, Block, Backend, Balance> ExtrinsicDecode<C, Block, Backend, Balance> {
+	/// Creates a new instance of the ExtrinsicDecode Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Provides RPC methods to inspect the ready set of the transaction pool.
+pub struct ReadySet<C, P, Balance> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	/// Shared reference to the transaction pool.
+	pool: Arc<P>,
+	_marker: std::marker::PhantomData<Balance>,
+}
+
+impl<C, P, Balance> ReadySet<C, P, Balance> {
+	/// Creates a new instance of the ReadySet Rpc helper.
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Self { client, pool, _marker: Default::default() }
+	}
+}
+
 /// Error type of this RPC api.
 pub enum Error {
 	/// The transaction was not decodable.
@@ -150,27 +267,247 @@ where
 			)
 		})?;
 
-		let try_into_rpc_balance = |value: Balance| {
-			value.try_into().map_err(|_| {
-				ErrorObject::owned(
-					ErrorCode::InvalidParams.code(),
-					format!("{} doesn't fit in NumberOrHex representation", value),
-					None::<()>,
-				)
+		into_rpc_fee_details(fee_details)
+	}
+}
+
+/// Convert the balances of `fee_details` to their RPC representation, leaving out the tip.
+fn into_rpc_fee_details<Balance>(
+	fee_details: FeeDetails<Balance>,
+) -> RpcResult<FeeDetails<NumberOrHex>>
+where
+	Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	let try_into_rpc_balance = |value: Balance| {
+		value.try_into().map_err(|_| {
+			ErrorObject::owned(
+				ErrorCode::InvalidParams.code(),
+				format!("{} doesn't fit in NumberOrHex representation", value),
+				None::<()>,
+			)
+		})
+	};
+
+	Ok(FeeDetails {
+		inclusion_fee: if let Some(inclusion_fee) = fee_details.inclusion_fee {
+			Some(InclusionFee {
+				base_fee: try_into_rpc_balance(inclusion_fee.base_fee)?,
+				len_fee: try_into_rpc_balance(inclusion_fee.len_fee)?,
+				adjusted_weight_fee: try_into_rpc_balance(inclusion_fee.adjusted_weight_fee)?,
 			})
+		} else {
+			None
+		},
+		tip: Default::default(),
+	})
+}
+
+/// The metadata of the runtime at `at`.
+pub(crate) fn metadata<C, Block>(
+	client: &C,
+	at: Block::Hash,
+) -> RpcResult<frame_metadata::v15::RuntimeMetadataV15>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: Metadata<Block>,
+{
+	let metadata = client
+		.runtime_api()
+		.metadata_at_version(at, decode::METADATA_VERSION)
+		.map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the metadata.",
+				Some(e.to_string()),
+			)
+		})?
+		.ok_or_else(|| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the metadata.",
+				Some(format!("Metadata V{} is not supported", decode::METADATA_VERSION)),
+			)
+		})?;
+
+	decode::decode_metadata(&metadata).map_err(|e| {
+		ErrorObject::owned(
+			Error::RuntimeError.into(),
+			"Unable to decode the metadata.",
+			Some(e.to_string()),
+		)
+	})
+}
+
+impl<C, Block, Backend, Balance> ExtrinsicDecodeApiServer<<Block as BlockT>::Hash>
+	for ExtrinsicDecode<C, Block, Backend, Balance>
+where
+	Block: BlockT,
+	Backend: sc_client_api::Backend<Block> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ StorageProvider<Block, Backend>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: TransactionPaymentRuntimeApi<Block, Balance> + Metadata<Block>,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn decode_extrinsic(
+		&self,
+		encoded_xt: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<DecodedExtrinsic> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let decode_error = |e: codec::Error| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode the extrinsic.",
+				Some(e.to_string()),
+			)
 		};
 
-		Ok(FeeDetails {
-			inclusion_fee: if let Some(inclusion_fee) = fee_details.inclusion_fee {
-				Some(InclusionFee {
-					base_fee: try_into_rpc_balance(inclusion_fee.base_fee)?,
-					len_fee: try_into_rpc_balance(inclusion_fee.len_fee)?,
-					adjusted_weight_fee: try_into_rpc_balance(inclusion_fee.adjusted_weight_fee)?,
-				})
-			} else {
-				None
-			},
-			tip: Default::default(),
+		let metadata = metadata(&*self.client, at_hash)?;
+		let extrinsic = decode::decode_extrinsic(&metadata, &encoded_xt).map_err(decode_error)?;
+
+		let encoded_len = encoded_xt.len() as u32;
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(decode_error)?;
+		let fee_details =
+			self.client.runtime_api().query_fee_details(at_hash, uxt, encoded_len).map_err(
+				|e| {
+					ErrorObject::owned(
+						Error::RuntimeError.into(),
+						"Unable to query fee details.",
+						Some(e.to_string()),
+					)
+				},
+			)?;
+
+		Ok(DecodedExtrinsic {
+			extrinsic,
+			events: Vec::new(),
+			fee: ExtrinsicFee::Estimated(into_rpc_fee_details(fee_details)?),
 		})
 	}
+
+	fn decode_block_extrinsic(&self, at: Block::Hash, index: u32) -> RpcResult<DecodedExtrinsic> {
+		fn map_err(error: impl ToString, code: Error, desc: &'static str) -> ErrorObjectOwned {
+			ErrorObject::owned(code.into(), desc, Some(error.to_string()))
+		}
+		let invalid_params = |desc: &str| {
+			ErrorObject::owned(ErrorCode::InvalidParams.code(), desc.to_string(), None::<()>)
+		};
+
+		let header = self
+			.client
+			.header(at)
+			.map_err(|e| map_err(e, Error::RuntimeError, "Unable to query the block."))?
+			.ok_or_else(|| invalid_params("Block not found."))?;
+		let encoded_xt = self
+			.client
+			.block_body(at)
+			.map_err(|e| map_err(e, Error::RuntimeError, "Unable to query the block."))?
+			.and_then(|body| body.get(index as usize).map(Encode::encode))
+			.ok_or_else(|| invalid_params("Extrinsic not found."))?;
+
+		// The block was executed by the runtime of its parent.
+		let metadata = metadata(&*self.client, *header.parent_hash())?;
+		let extrinsic = decode::decode_extrinsic(&metadata, &encoded_xt)
+			.map_err(|e| map_err(e, Error::DecodeError, "Unable to decode the extrinsic."))?;
+
+		let events_key =
+			[sp_core::twox_128(b"System").as_slice(), sp_core::twox_128(b"Events").as_slice()]
+				.concat();
+		let events = match self
+			.client
+			.storage(at, &StorageKey(events_key))
+			.map_err(|e| map_err(e, Error::RuntimeError, "Unable to query the events."))?
+		{
+			Some(events) => decode::decode_events(&metadata, &events.0)
+				.map_err(|e| map_err(e, Error::DecodeError, "Unable to decode the events."))?,
+			None => Vec::new(),
+		};
+		let phase = serde_json::json!({ "ApplyExtrinsic": index });
+		let events = events
+			.into_iter()
+			.filter(|record| record.get("phase") == Some(&phase))
+			.collect::<Vec<_>>();
+
+		let fee = match events
+			.iter()
+			.find_map(|record| record.pointer("/event/TransactionPayment/TransactionFeePaid"))
+		{
+			Some(fee_paid) => {
+				let amount = |name: &str| {
+					fee_paid
+						.get(name)
+						.cloned()
+						.and_then(|amount| serde_json::from_value::<NumberOrHex>(amount).ok())
+						.ok_or_else(|| {
+							map_err(
+								format!("Invalid `{}` of `TransactionFeePaid`", name),
+								Error::DecodeError,
+								"Unable to decode the events.",
+							)
+						})
+				};
+				ExtrinsicFee::Paid { actual_fee: amount("actual_fee")?, tip: amount("tip")? }
+			},
+			None => ExtrinsicFee::None,
+		};
+
+		Ok(DecodedExtrinsic { extrinsic, events, fee })
+	}
+}
+
+impl<C, P, Balance> ReadySetApiServer<TxHash<P>> for ReadySet<C, P, Balance>
+where
+	P: TransactionPool + 'static,
+	C: ProvideRuntimeApi<P::Block> + HeaderBackend<P::Block> + Send + Sync + 'static,
+	C::Api: TransactionPaymentRuntimeApi<P::Block, Balance>,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn ready_transactions(
+		&self,
+		ext: &jsonrpsee::Extensions,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<ReadyTransaction<TxHash<P>>>> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		let limit = limit.unwrap_or(MAX_READY_TRANSACTIONS).min(MAX_READY_TRANSACTIONS);
+		let best_hash: BlockHash<P> = self.client.info().best_hash;
+		let api = self.client.runtime_api();
+
+		self.pool
+			.ready()
+			.take(limit as usize)
+			.map(|tx| {
+				let uxt = (**tx.data()).clone();
+				let encoded_len = uxt.encoded_size() as u32;
+				let info = api.query_info(best_hash, uxt, encoded_len).map_err(|e| {
+					ErrorObject::owned(
+						Error::RuntimeError.into(),
+						"Unable to query dispatch info.",
+						Some(e.to_string()),
+					)
+				})?;
+				let estimated_fee = info.partial_fee.try_into().map_err(|_| {
+					ErrorObject::owned(
+						ErrorCode::InvalidParams.code(),
+						format!("{} doesn't fit in NumberOrHex representation", info.partial_fee),
+						None::<()>,
+					)
+				})?;
+
+				Ok(ReadyTransaction {
+					hash: tx.hash().clone(),
+					priority: *tx.priority(),
+					longevity: *tx.longevity(),
+					encoded_len,
+					estimated_fee,
+				})
+			})
+			.collect()
+	}
 }
@@ -0,0 +1,170 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC methods to page through the entries of storage maps, decoded against the metadata of the
+//! runtime.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{
+		error::{ErrorCode, ErrorObject},
+		ErrorObjectOwned,
+	},
+};
+use sc_client_api::StorageProvider;
+use serde::{Deserialize, Serialize};
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, Bytes};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{decode, metadata, Error};
+
+/// The maximum number of entries returned by `storageMap_decodedEntries`.
+pub const MAX_STORAGE_PAGE_ENTRIES: u32 = 1000;
+
+/// The maximum size of the encoded values returned by `storageMap_decodedEntries`.
+pub const MAX_STORAGE_PAGE_BYTES: u32 = 4 * 1024 * 1024;
+
+/// An entry of a storage map decoded against the metadata of the runtime.
+///
+/// Values are represented like in [`DecodedExtrinsic`](crate::DecodedExtrinsic).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedStorageEntry {
+	/// The raw storage key of the entry.
+	pub key: Bytes,
+	/// The keys of the entry, one per hasher of the map. Keys which can't be recovered from
+	/// their hash are represented by their hash.
+	pub keys: Vec<serde_json::Value>,
+	/// The value of the entry.
+	pub value: serde_json::Value,
+}
+
+/// A page of the entries of a storage map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedStoragePage {
+	/// The entries of the page, ordered by their raw storage key.
+	pub entries: Vec<DecodedStorageEntry>,
+	/// Pass as `start_key` to get the next page. `None` if there are no more entries.
+	pub next_key: Option<Bytes>,
+}
+
+/// Iterates storage maps, decoding their entries against the metadata of the runtime.
+#[rpc(client, server)]
+pub trait StorageDecodeApi<BlockHash> {
+	/// Returns up to `limit` entries of the storage map `entry` of `pallet` at block `at`,
+	/// starting after the raw key `start_key`.
+	///
+	/// The page ends early once the encoded values reach `max_bytes`, but contains at least one
+	/// entry. Both limits are capped by [`MAX_STORAGE_PAGE_ENTRIES`] and
+	/// [`MAX_STORAGE_PAGE_BYTES`].
+	#[method(name = "storageMap_decodedEntries")]
+	fn decode_storage_map(
+		&self,
+		pallet: String,
+		entry: String,
+		limit: u32,
+		max_bytes: Option<u32>,
+		start_key: Option<Bytes>,
+		at: Option<BlockHash>,
+	) -> RpcResult<DecodedStoragePage>;
+}
+
+/// Provides RPC methods to iterate storage maps.
+pub struct StorageDecode<C, Block, Backend> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(Block, Backend)>,
+}
+
+impl<C, Block, Backend> StorageDecode<C, Block, Backend> {
+	/// Creates a new instance of the StorageDecode Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, Backend> StorageDecodeApiServer<<Block as BlockT>::Hash>
+	for StorageDecode<C, Block, Backend>
+where
+	Block: BlockT,
+	Backend: sc_client_api::Backend<Block> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ StorageProvider<Block, Backend>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: Metadata<Block>,
+{
+	fn decode_storage_map(
+		&self,
+		pallet: String,
+		entry: String,
+		limit: u32,
+		max_bytes: Option<u32>,
+		start_key: Option<Bytes>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<DecodedStoragePage> {
+		fn map_err(error: impl ToString, code: Error, desc: &'static str) -> ErrorObjectOwned {
+			ErrorObject::owned(code.into(), desc, Some(error.to_string()))
+		}
+
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let metadata = metadata(&*self.client, at_hash)?;
+		let map = decode::storage_map(&metadata, &pallet, &entry).map_err(|e| {
+			ErrorObject::owned(ErrorCode::InvalidParams.code(), e.to_string(), None::<()>)
+		})?;
+
+		let limit = limit.min(MAX_STORAGE_PAGE_ENTRIES) as usize;
+		let max_bytes = max_bytes.unwrap_or(MAX_STORAGE_PAGE_BYTES).min(MAX_STORAGE_PAGE_BYTES);
+		let prefix = StorageKey(map.prefix.clone());
+		let start_key = start_key.map(|key| StorageKey(key.0));
+		let mut pairs = self
+			.client
+			.storage_pairs(at_hash, Some(&prefix), start_key.as_ref())
+			.map_err(|e| map_err(e, Error::RuntimeError, "Unable to query the storage."))?
+			.peekable();
+		let mut entries = Vec::new();
+		let mut size = 0usize;
+		while let Some((_, value)) = pairs.peek() {
+			if entries.len() >= limit ||
+				(!entries.is_empty() && size + value.0.len() > max_bytes as usize)
+			{
+				break
+			}
+
+			let Some((key, value)) = pairs.next() else { break };
+			size += value.0.len();
+			let keys =
+				decode::decode_storage_keys(&metadata.types, &map, &key.0[map.prefix.len()..])
+					.map_err(|e| map_err(e, Error::DecodeError, "Unable to decode the entry."))?;
+			let value = decode::decode_value(&metadata.types, map.value_ty, &mut &value.0[..])
+				.map_err(|e| map_err(e, Error::DecodeError, "Unable to decode the entry."))?;
+			entries.push(DecodedStorageEntry { key: Bytes(key.0), keys, value });
+		}
+
+		let next_key = pairs.peek().and(entries.last()).map(|entry| entry.key.clone());
+
+		Ok(DecodedStoragePage { entries, next_key })
+	}
+}
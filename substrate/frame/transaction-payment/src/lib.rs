@@ -43,7 +43,9 @@
 //!   - A means of updating the fee for the next block, via defining a multiplier, based on the
 //!     final state of the chain at the end of the previous block. This can be configured via
 //!     [`Config::FeeMultiplierUpdate`]
-//!   - How the fees are paid via [`Config::OnChargeTransaction`].
+//!   - How the fees are paid via [`Config::OnChargeTransaction`]. [`SplitFungibleAdapter`] routes
+//!     the inclusion fee and the tip independently, e.g. to burn the former and pay the latter to
+//!     the block author.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -66,8 +68,10 @@ use sp_runtime::{
 		Convert, DispatchInfoOf, Dispatchable, One, PostDispatchInfoOf, SaturatedConversion,
 		Saturating, TransactionExtension, Zero,
 	},
-	transaction_validity::{TransactionPriority, TransactionValidityError, ValidTransaction},
-	FixedPointNumber, FixedU128, Perbill, Perquintill, RuntimeDebug,
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionValidityError, ValidTransaction,
+	},
+	FixedI128, FixedPointNumber, FixedU128, Perbill, Perquintill, RuntimeDebug,
 };
 pub use types::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
 pub use weights::WeightInfo;
@@ -153,6 +157,10 @@ pub trait MultiplierUpdate: Convert<Multiplier, Multiplier> {
 	fn target() -> Perquintill;
 	/// Variability factor
 	fn variability() -> Multiplier;
+	/// Maximum relative change of the multiplier from one block to the next.
+	fn max_step() -> Multiplier {
+		<Multiplier as sp_runtime::traits::Bounded>::max_value()
+	}
 }
 
 impl MultiplierUpdate for () {
@@ -208,32 +216,7 @@ where
 		let max_multiplier = X::get();
 		let previous = previous.max(min_multiplier);
 
-		let weights = T::BlockWeights::get();
-		// the computed ratio is only among the normal class.
-		let normal_max_weight =
-			weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block);
-		let current_block_weight = frame_system::Pallet::<T>::block_weight();
-		let normal_block_weight =
-			current_block_weight.get(DispatchClass::Normal).min(normal_max_weight);
-
-		// Normalize dimensions so they can be compared. Ensure (defensive) max weight is non-zero.
-		let normalized_ref_time = Perbill::from_rational(
-			normal_block_weight.ref_time(),
-			normal_max_weight.ref_time().max(1),
-		);
-		let normalized_proof_size = Perbill::from_rational(
-			normal_block_weight.proof_size(),
-			normal_max_weight.proof_size().max(1),
-		);
-
-		// Pick the limiting dimension. If the proof size is the limiting dimension, then the
-		// multiplier is adjusted by the proof size. Otherwise, it is adjusted by the ref time.
-		let (normal_limiting_dimension, max_limiting_dimension) =
-			if normalized_ref_time < normalized_proof_size {
-				(normal_block_weight.proof_size(), normal_max_weight.proof_size())
-			} else {
-				(normal_block_weight.ref_time(), normal_max_weight.ref_time())
-			};
+		let (normal_limiting_dimension, max_limiting_dimension) = normal_limiting_dimension::<T>();
 
 		let target_block_fullness = S::get();
 		let adjustment_variable = V::get();
@@ -267,6 +250,180 @@ where
 	}
 }
 
+/// Returns the consumption of the limiting dimension of the normal class in the current block,
+/// along with the maximum of that dimension.
+///
+/// See [`TargetedFeeAdjustment`] for how the limiting dimension is picked.
+fn normal_limiting_dimension<T: frame_system::Config>() -> (u64, u64) {
+	let weights = T::BlockWeights::get();
+	// the computed ratio is only among the normal class.
+	let normal_max_weight =
+		weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block);
+	let current_block_weight = frame_system::Pallet::<T>::block_weight();
+	let normal_block_weight =
+		current_block_weight.get(DispatchClass::Normal).min(normal_max_weight);
+
+	// Normalize dimensions so they can be compared. Ensure (defensive) max weight is non-zero.
+	let normalized_ref_time =
+		Perbill::from_rational(normal_block_weight.ref_time(), normal_max_weight.ref_time().max(1));
+	let normalized_proof_size = Perbill::from_rational(
+		normal_block_weight.proof_size(),
+		normal_max_weight.proof_size().max(1),
+	);
+
+	// Pick the limiting dimension. If the proof size is the limiting dimension, then the
+	// multiplier is adjusted by the proof size. Otherwise, it is adjusted by the ref time.
+	if normalized_ref_time < normalized_proof_size {
+		(normal_block_weight.proof_size(), normal_max_weight.proof_size())
+	} else {
+		(normal_block_weight.ref_time(), normal_max_weight.ref_time())
+	}
+}
+
+/// Gains of the [`PidFeeAdjustment`] controller.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct PidGains {
+	/// Gain applied to the current deviation from the target block fullness.
+	pub proportional: Multiplier,
+	/// Gain applied to the deviations accumulated over the previous blocks.
+	pub integral: Multiplier,
+	/// Gain applied to the change of the deviation since the previous block.
+	pub derivative: Multiplier,
+}
+
+/// State of the [`PidFeeAdjustment`] controller, kept across blocks.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	Default,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct PidState {
+	/// Sum of the deviations from the target block fullness, bounded to prevent windup.
+	pub integral: FixedI128,
+	/// Deviation from the target block fullness of the previous block.
+	pub previous_error: FixedI128,
+}
+
+/// A PID controller updating the fee multiplier per block, an alternative to
+/// [`TargetedFeeAdjustment`] reacting faster to sustained congestion.
+///
+/// given:
+/// 	s = previous block weight
+/// 	s'= ideal block weight
+/// 	m = maximum block weight
+/// 		e = (s - s')/m
+/// 		i = sum of e over the previous blocks, including this one
+/// 		d = e - previous e
+/// 		u = kp * e + ki * i + kd * d, bounded by the maximum step
+/// 	then:
+/// 	next_multiplier = prev_multiplier * (1 + u)
+///
+/// Where `s'` is given by `S`, the gains `(kp, ki, kd)` by `G` and the maximum step, relative to
+/// the previous multiplier, by `Step`. `M` and `X` provide the floor and the ceiling of the
+/// multiplier. The block weight is measured like in [`TargetedFeeAdjustment`], in the limiting
+/// dimension of the _normal transaction_ capacity of the block.
+///
+/// Under sustained congestion the integral term keeps growing, so the multiplier accelerates
+/// until the congestion is absorbed or the maximum step is reached. The integral is bounded such
+/// that `ki * i` never exceeds the maximum step, which keeps the multiplier from overshooting for
+/// long once the congestion ends.
+///
+/// The state of the controller is kept in [`FeeMultiplierPidState`], and updated by every call to
+/// `convert`. It is meant to be called once per block, in `on_finalize`.
+pub struct PidFeeAdjustment<T, S, G, Step, M, X>(core::marker::PhantomData<(T, S, G, Step, M, X)>);
+
+impl<T, S, G, Step, M, X> MultiplierUpdate for PidFeeAdjustment<T, S, G, Step, M, X>
+where
+	T: Config,
+	S: Get<Perquintill>,
+	G: Get<PidGains>,
+	Step: Get<Multiplier>,
+	M: Get<Multiplier>,
+	X: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn max() -> Multiplier {
+		X::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	fn variability() -> Multiplier {
+		G::get().proportional
+	}
+	fn max_step() -> Multiplier {
+		Step::get()
+	}
+}
+
+impl<T, S, G, Step, M, X> Convert<Multiplier, Multiplier> for PidFeeAdjustment<T, S, G, Step, M, X>
+where
+	T: Config,
+	S: Get<Perquintill>,
+	G: Get<PidGains>,
+	Step: Get<Multiplier>,
+	M: Get<Multiplier>,
+	X: Get<Multiplier>,
+{
+	fn convert(previous: Multiplier) -> Multiplier {
+		let min_multiplier = M::get();
+		let max_multiplier = X::get();
+		// Defensive only, see `TargetedFeeAdjustment`.
+		let previous = previous.max(min_multiplier);
+
+		let (normal_limiting_dimension, max_limiting_dimension) = normal_limiting_dimension::<T>();
+		let target_weight = (S::get() * max_limiting_dimension) as u128;
+		let block_weight = normal_limiting_dimension as u128;
+
+		let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
+		let diff = FixedI128::saturating_from_rational(diff_abs, max_limiting_dimension.max(1));
+		let error = if block_weight >= target_weight { diff } else { -diff };
+
+		let gains = G::get();
+		let max_step = to_signed(Step::get());
+		let (proportional, integral, derivative) =
+			(to_signed(gains.proportional), to_signed(gains.integral), to_signed(gains.derivative));
+
+		let step = FeeMultiplierPidState::<T>::mutate(|state| {
+			let integral_bound =
+				if integral.is_zero() { Zero::zero() } else { max_step / integral };
+			state.integral =
+				state.integral.saturating_add(error).clamp(-integral_bound, integral_bound);
+			let change = error.saturating_sub(state.previous_error);
+			state.previous_error = error;
+
+			proportional
+				.saturating_mul(error)
+				.saturating_add(integral.saturating_mul(state.integral))
+				.saturating_add(derivative.saturating_mul(change))
+				.clamp(-max_step, max_step)
+		});
+
+		let change =
+			Multiplier::from_inner(step.into_inner().unsigned_abs()).saturating_mul(previous);
+		if step.is_negative() {
+			previous.saturating_sub(change).clamp(min_multiplier, max_multiplier)
+		} else {
+			previous.saturating_add(change).clamp(min_multiplier, max_multiplier)
+		}
+	}
+}
+
+/// Converts a multiplier to a signed fixed point number, saturating.
+fn to_signed(multiplier: Multiplier) -> FixedI128 {
+	FixedI128::from_inner(multiplier.into_inner().min(i128::MAX as u128) as i128)
+}
+
 /// A struct to make the fee multiplier a constant
 pub struct ConstFeeMultiplier<M: Get<Multiplier>>(core::marker::PhantomData<M>);
 
@@ -411,6 +568,10 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
+	/// State of the [`PidFeeAdjustment`] controller. Unused by the other multiplier updates.
+	#[pallet::storage]
+	pub type FeeMultiplierPidState<T: Config> = StorageValue<_, PidState, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub multiplier: Multiplier,
@@ -438,6 +599,41 @@ pub mod pallet {
 		/// A transaction fee `actual_fee`, of which `tip` was added to the minimum inclusion fee,
 		/// has been paid by `who`.
 		TransactionFeePaid { who: T::AccountId, actual_fee: BalanceOf<T>, tip: BalanceOf<T> },
+		/// The fee paid by `who` has been routed by the `SplitFungibleAdapter`: `to_author` was
+		/// paid to the block author, `to_treasury` to the treasury and `burned` was burned.
+		TransactionFeeRouted {
+			who: T::AccountId,
+			to_author: BalanceOf<T>,
+			to_treasury: BalanceOf<T>,
+			burned: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// The target block fullness of the fee multiplier update.
+		#[pallet::constant_name(TargetBlockFullness)]
+		fn target_block_fullness() -> Perquintill {
+			T::FeeMultiplierUpdate::target()
+		}
+
+		/// The maximum relative change of the fee multiplier from one block to the next.
+		#[pallet::constant_name(MaxMultiplierStep)]
+		fn max_multiplier_step() -> Multiplier {
+			T::FeeMultiplierUpdate::max_step()
+		}
+
+		/// The floor of the fee multiplier.
+		#[pallet::constant_name(MinimumMultiplier)]
+		fn minimum_multiplier() -> Multiplier {
+			T::FeeMultiplierUpdate::min()
+		}
+
+		/// The ceiling of the fee multiplier.
+		#[pallet::constant_name(MaximumMultiplier)]
+		fn maximum_multiplier() -> Multiplier {
+			T::FeeMultiplierUpdate::max()
+		}
 	}
 
 	#[pallet::hooks]
@@ -553,6 +749,29 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Query the detailed fee of a given extrinsic, paying the given `tip`.
+	///
+	/// Unlike [`Self::query_fee_details`], the tip is accounted for. The caller is in charge of
+	/// extracting it from the transaction extensions of the extrinsic. The adjusted weight fee
+	/// includes the weight of the transaction extensions.
+	pub fn query_fee_breakdown<Extrinsic: sp_runtime::traits::ExtrinsicLike + GetDispatchInfo>(
+		unchecked_extrinsic: Extrinsic,
+		len: u32,
+		tip: BalanceOf<T>,
+	) -> FeeDetails<BalanceOf<T>>
+	where
+		T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+	{
+		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
+
+		if unchecked_extrinsic.is_bare() {
+			// Bare extrinsics have no inclusion fee, nor tip.
+			FeeDetails { inclusion_fee: None, tip: 0u32.into() }
+		} else {
+			Self::compute_fee_details(len, &dispatch_info, tip)
+		}
+	}
+
 	/// Query information of a dispatch class, weight, and fee of a given encoded `Call`.
 	pub fn query_call_info(call: T::RuntimeCall, len: u32) -> RuntimeDispatchInfo<BalanceOf<T>>
 	where
@@ -778,6 +997,11 @@ where
 	/// `tip * (max_block_{weight|length} / bounded_{weight|length})`, since given current
 	/// state of-the-art blockchains, number of per-block transactions is expected to be in a
 	/// range reasonable enough to not saturate the `Balance` type while multiplying by the tip.
+	///
+	/// Both weight dimensions are considered. As in `CheckWeight`, the encoded length of the
+	/// transaction is part of its proof size, since the transaction itself ends up in the proof
+	/// of validity of the block. On parachains, whose blocks are usually bound by the proof size,
+	/// the priority is then the tip per byte of proof of validity.
 	pub fn get_priority(
 		info: &DispatchInfoOf<T::RuntimeCall>,
 		len: usize,
@@ -790,8 +1014,11 @@ where
 		let max_block_length = *T::BlockLength::get().max.get(info.class) as u64;
 
 		// bounded_weight is used as a divisor later so we keep it non-zero.
-		let bounded_weight =
-			info.total_weight().max(Weight::from_parts(1, 1)).min(max_block_weight);
+		let bounded_weight = info
+			.total_weight()
+			.saturating_add(Weight::from_parts(0, len as u64))
+			.max(Weight::from_parts(1, 1))
+			.min(max_block_weight);
 		let bounded_length = (len as u64).clamp(1, max_block_length);
 
 		// returns the scarce resource, i.e. the one that is limiting the number of transactions.
@@ -980,6 +1207,164 @@ where
 	}
 }
 
+/// Require the transactor pay for themselves, with the fee bounded by a cap chosen by the sender.
+///
+/// Instead of a plain tip, the sender signs a `max_fee` they are willing to pay in total and a
+/// `max_priority_fee` they are willing to pay on top of the inclusion fee, in the spirit of
+/// EIP-1559. The inclusion fee is computed against the current [`NextFeeMultiplier`]; the
+/// effective priority fee is whatever fits below `max_fee`, capped at `max_priority_fee`, and is
+/// then handled exactly like a tip.
+///
+/// # Transaction Validity
+///
+/// The transaction is rejected with [`InvalidTransaction::Payment`] if the inclusion fee alone
+/// exceeds `max_fee`. Otherwise the `priority` is derived from the effective priority fee in the
+/// same way [`ChargeTransactionPayment`] derives it from the tip.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargePriorityFee<T: Config> {
+	/// The maximum total fee the sender is willing to pay.
+	#[codec(compact)]
+	max_fee: BalanceOf<T>,
+	/// The maximum fee the sender is willing to pay on top of the inclusion fee.
+	#[codec(compact)]
+	max_priority_fee: BalanceOf<T>,
+}
+
+impl<T: Config> ChargePriorityFee<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync,
+{
+	/// Create the extension from the caps chosen by the transaction sender.
+	pub fn new(max_fee: BalanceOf<T>, max_priority_fee: BalanceOf<T>) -> Self {
+		Self { max_fee, max_priority_fee }
+	}
+
+	/// Returns the maximum total fee chosen by the transaction sender.
+	pub fn max_fee(&self) -> BalanceOf<T> {
+		self.max_fee
+	}
+
+	/// Returns the maximum priority fee chosen by the transaction sender.
+	pub fn max_priority_fee(&self) -> BalanceOf<T> {
+		self.max_priority_fee
+	}
+
+	/// Compute the final fee and the effective priority fee of a transaction.
+	///
+	/// Fails if the inclusion fee under the current multiplier already exceeds `max_fee`.
+	pub fn effective_fee(
+		&self,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), TransactionValidityError> {
+		let inclusion_fee = Pallet::<T>::compute_fee(len as u32, info, Zero::zero());
+		if inclusion_fee > self.max_fee {
+			return Err(InvalidTransaction::Payment.into())
+		}
+		let priority_fee = self.max_priority_fee.min(self.max_fee.saturating_sub(inclusion_fee));
+		Ok((inclusion_fee.saturating_add(priority_fee), priority_fee))
+	}
+}
+
+impl<T: Config> core::fmt::Debug for ChargePriorityFee<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "ChargePriorityFee<{:?}, {:?}>", self.max_fee, self.max_priority_fee)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut core::fmt::Formatter) -> core::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config> TransactionExtension<T::RuntimeCall> for ChargePriorityFee<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargePriorityFee";
+	type Implicit = ();
+	type Val = Val<T>;
+	type Pre = Pre<T>;
+
+	fn weight(&self, _: &T::RuntimeCall) -> Weight {
+		T::WeightInfo::charge_transaction_payment()
+	}
+
+	fn validate(
+		&self,
+		origin: <T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		_: (),
+		_implication: &impl Encode,
+		_source: TransactionSource,
+	) -> Result<
+		(ValidTransaction, Self::Val, <T::RuntimeCall as Dispatchable>::RuntimeOrigin),
+		TransactionValidityError,
+	> {
+		let Ok(who) = frame_system::ensure_signed(origin.clone()) else {
+			return Ok((ValidTransaction::default(), Val::NoCharge, origin));
+		};
+		let (final_fee, priority_fee) = self.effective_fee(info, len)?;
+		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::can_withdraw_fee(
+			&who,
+			call,
+			info,
+			final_fee,
+			priority_fee,
+		)?;
+		Ok((
+			ValidTransaction {
+				priority: ChargeTransactionPayment::<T>::get_priority(
+					info,
+					len,
+					priority_fee,
+					final_fee,
+				),
+				..Default::default()
+			},
+			Val::Charge { tip: priority_fee, who, fee: final_fee },
+			origin,
+		))
+	}
+
+	fn prepare(
+		self,
+		val: Self::Val,
+		_origin: &<T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match val {
+			Val::Charge { tip, who, fee } => {
+				let imbalance =
+					<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(
+						&who, call, info, fee, tip,
+					)?;
+				Ok(Pre::Charge { tip, who, imbalance })
+			},
+			Val::NoCharge => Ok(Pre::NoCharge { refund: self.weight(call) }),
+		}
+	}
+
+	fn post_dispatch_details(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<Weight, TransactionValidityError> {
+		// Refunds and fee events are handled exactly as for a tipped transaction.
+		<ChargeTransactionPayment<T> as TransactionExtension<T::RuntimeCall>>::post_dispatch_details(
+			pre, info, post_info, len, result,
+		)
+	}
+}
+
 impl<T: Config, AnyCall: GetDispatchInfo + Encode> EstimateCallFee<AnyCall, BalanceOf<T>>
 	for Pallet<T>
 where
@@ -43,6 +43,7 @@ pub(crate) const CALL: &<Runtime as frame_system::Config>::RuntimeCall =
 
 parameter_types! {
 	pub(crate) static ExtrinsicBaseWeight: Weight = Weight::zero();
+	pub(crate) static MaxBlockProofSize: u64 = u64::MAX;
 }
 
 pub struct BlockWeights;
@@ -54,7 +55,7 @@ impl Get<frame_system::limits::BlockWeights> for BlockWeights {
 				weights.base_extrinsic = ExtrinsicBaseWeight::get().into();
 			})
 			.for_class(DispatchClass::non_mandatory(), |weights| {
-				weights.max_total = Weight::from_parts(1024, u64::MAX).into();
+				weights.max_total = Weight::from_parts(1024, MaxBlockProofSize::get()).into();
 			})
 			.build_or_panic()
 	}
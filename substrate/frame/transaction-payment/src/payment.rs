@@ -16,19 +16,22 @@
 // limitations under the License.
 
 /// ! Traits and default implementation for paying transaction fees.
-use crate::Config;
+use crate::{Config, Event, Pallet};
 
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use core::marker::PhantomData;
+use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{CheckedSub, DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
 	transaction_validity::InvalidTransaction,
+	Percent, RuntimeDebug,
 };
 
 use frame_support::{
 	traits::{
 		fungible::{Balanced, Credit, Debt, Inspect},
 		tokens::{Precision, WithdrawConsequence},
-		Currency, ExistenceRequirement, Imbalance, OnUnbalanced, WithdrawReasons,
+		Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, WithdrawReasons,
 	},
 	unsigned::TransactionValidityError,
 };
@@ -188,6 +191,155 @@ where
 	}
 }
 
+/// The shares of an amount that are paid to the block author and to the treasury by the
+/// [`SplitFungibleAdapter`]. Whatever is left is burned.
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Default,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+)]
+pub struct FeeShares {
+	/// The share paid to the block author.
+	pub author: Percent,
+	/// The share paid to the treasury. Capped to what is left after the author's share.
+	pub treasury: Percent,
+}
+
+/// Implements [`OnChargeTransaction`] like [`FungibleAdapter`], but routes the inclusion fee and
+/// the tip independently.
+///
+/// `FeeShares` and `TipShares` determine how the inclusion fee and the tip are split between
+/// `ToAuthor` and `ToTreasury`; the remainder is burned. They can be backed by
+/// `pallet-parameters` to be adjusted without a runtime upgrade. A
+/// [`Event::TransactionFeeRouted`] event reports the split of every transaction.
+pub struct SplitFungibleAdapter<F, ToAuthor, ToTreasury, FeeShares, TipShares>(
+	PhantomData<(F, ToAuthor, ToTreasury, FeeShares, TipShares)>,
+);
+
+impl<T, F, ToAuthor, ToTreasury, FeeSharesT, TipSharesT> OnChargeTransaction<T>
+	for SplitFungibleAdapter<F, ToAuthor, ToTreasury, FeeSharesT, TipSharesT>
+where
+	T: Config,
+	T::OnChargeTransaction: OnChargeTransaction<T, Balance = F::Balance>,
+	F: Balanced<T::AccountId>,
+	ToAuthor: OnUnbalanced<Credit<T::AccountId, F>>,
+	ToTreasury: OnUnbalanced<Credit<T::AccountId, F>>,
+	FeeSharesT: Get<FeeShares>,
+	TipSharesT: Get<FeeShares>,
+{
+	type LiquidityInfo = Option<Credit<T::AccountId, F>>;
+	type Balance = <F as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		<FungibleAdapter<F, ()> as OnChargeTransaction<T>>::withdraw_fee(
+			who,
+			call,
+			dispatch_info,
+			fee,
+			tip,
+		)
+	}
+
+	fn can_withdraw_fee(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<(), TransactionValidityError> {
+		<FungibleAdapter<F, ()> as OnChargeTransaction<T>>::can_withdraw_fee(
+			who,
+			call,
+			dispatch_info,
+			fee,
+			tip,
+		)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+		_post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		let Some(paid) = already_withdrawn else { return Ok(()) };
+		// Refund exactly as the `FungibleAdapter` does.
+		let refund_amount = paid.peek().saturating_sub(corrected_fee);
+		let refund_imbalance =
+			if refund_amount > Zero::zero() && F::total_balance(who) > F::Balance::zero() {
+				F::deposit(who, refund_amount, Precision::BestEffort)
+					.unwrap_or_else(|_| Debt::<T::AccountId, F>::zero())
+			} else {
+				Debt::<T::AccountId, F>::zero()
+			};
+		let adjusted_paid: Credit<T::AccountId, F> = paid
+			.offset(refund_imbalance)
+			.same()
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+
+		let (tip, fee) = adjusted_paid.split(tip);
+		let (fee_to_author, fee_to_treasury, fee_burned) =
+			split_by_shares::<T::AccountId, F>(fee, FeeSharesT::get());
+		let (tip_to_author, tip_to_treasury, tip_burned) =
+			split_by_shares::<T::AccountId, F>(tip, TipSharesT::get());
+		let to_author = fee_to_author.merge(tip_to_author);
+		let to_treasury = fee_to_treasury.merge(tip_to_treasury);
+		let burned = fee_burned.merge(tip_burned);
+
+		let event = Event::<T>::TransactionFeeRouted {
+			who: who.clone(),
+			to_author: to_author.peek(),
+			to_treasury: to_treasury.peek(),
+			burned: burned.peek(),
+		};
+		ToAuthor::on_unbalanced(to_author);
+		ToTreasury::on_unbalanced(to_treasury);
+		// Dropping the credit reduces the total issuance.
+		drop(burned);
+		Pallet::<T>::deposit_event(event);
+
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn endow_account(who: &T::AccountId, amount: Self::Balance) {
+		let _ = F::deposit(who, amount, Precision::BestEffort);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn minimum_balance() -> Self::Balance {
+		F::minimum_balance()
+	}
+}
+
+/// Split `credit` into the parts for the author, for the treasury and the remainder.
+fn split_by_shares<A, F: Balanced<A>>(
+	credit: Credit<A, F>,
+	shares: FeeShares,
+) -> (Credit<A, F>, Credit<A, F>, Credit<A, F>) {
+	let amount = credit.peek();
+	let (to_author, rest) = credit.split(shares.author.mul_floor(amount));
+	let to_treasury = shares.treasury.mul_floor(amount).min(rest.peek());
+	let (to_treasury, burned) = rest.split(to_treasury);
+	(to_author, to_treasury, burned)
+}
+
 /// Implements the transaction payment for a pallet implementing the [`Currency`]
 /// trait (eg. the pallet_balances) using an unbalance handler (implementing
 /// [`OnUnbalanced`]).
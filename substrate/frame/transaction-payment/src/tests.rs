@@ -24,7 +24,7 @@ use sp_runtime::{
 	generic::UncheckedExtrinsic,
 	traits::{DispatchTransaction, One},
 	transaction_validity::{InvalidTransaction, TransactionSource::External},
-	BuildStorage,
+	BuildStorage, Percent,
 };
 
 use frame_support::{
@@ -42,6 +42,7 @@ pub struct ExtBuilder {
 	base_weight: Weight,
 	byte_fee: u64,
 	weight_to_fee: u64,
+	max_block_proof_size: u64,
 	initial_multiplier: Option<Multiplier>,
 }
 
@@ -52,6 +53,7 @@ impl Default for ExtBuilder {
 			base_weight: Weight::zero(),
 			byte_fee: 1,
 			weight_to_fee: 1,
+			max_block_proof_size: u64::MAX,
 			initial_multiplier: None,
 		}
 	}
@@ -70,6 +72,10 @@ impl ExtBuilder {
 		self.weight_to_fee = weight_to_fee;
 		self
 	}
+	pub fn max_block_proof_size(mut self, max_block_proof_size: u64) -> Self {
+		self.max_block_proof_size = max_block_proof_size;
+		self
+	}
 	pub fn balance_factor(mut self, factor: u64) -> Self {
 		self.balance_factor = factor;
 		self
@@ -82,6 +88,7 @@ impl ExtBuilder {
 		ExtrinsicBaseWeight::mutate(|v| *v = self.base_weight);
 		TRANSACTION_BYTE_FEE.with(|v| *v.borrow_mut() = self.byte_fee);
 		WEIGHT_TO_FEE.with(|v| *v.borrow_mut() = self.weight_to_fee);
+		MaxBlockProofSize::mutate(|v| *v = self.max_block_proof_size);
 	}
 	pub fn build(self) -> sp_io::TestExternalities {
 		self.set_constants();
@@ -202,6 +209,72 @@ fn transaction_extension_transaction_payment_multiplied_refund_works() {
 		});
 }
 
+#[test]
+fn charge_priority_fee_caps_the_fee() {
+	ExtBuilder::default()
+		.balance_factor(10)
+		.base_weight(Weight::from_parts(5, 0))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(10);
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(3, 2));
+
+			let len = 10;
+			let mut info = info_from_weight(Weight::from_parts(100, 0));
+			info.extension_weight = ChargePriorityFee::<Runtime>::new(0, 0).weight(CALL);
+			// 5 base fee, 10 byte fee, 3/2 * (100 call weight fee + 10 ext weight fee)
+			let inclusion_fee = 5 + 10 + 165;
+
+			// The inclusion fee alone exceeds the cap.
+			assert_eq!(
+				ChargePriorityFee::<Runtime>::new(inclusion_fee - 1, 50)
+					.validate_only(Some(2).into(), CALL, &info, len, External, 0)
+					.unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Payment),
+			);
+
+			// Only 10 of the 50 priority fee fit below the cap.
+			let ext = ChargePriorityFee::<Runtime>::new(inclusion_fee + 10, 50);
+			assert_eq!(ext.effective_fee(&info, len).unwrap(), (inclusion_fee + 10, 10));
+			let priority = ext
+				.validate_only(Some(2).into(), CALL, &info, len, External, 0)
+				.unwrap()
+				.0
+				.priority;
+			assert_eq!(
+				priority,
+				ChargeTransactionPayment::<Runtime>::get_priority(
+					&info,
+					len,
+					10,
+					inclusion_fee + 10
+				),
+			);
+			assert!(priority > 0);
+
+			ext.test_run(Some(2).into(), CALL, &info, len, 0, |_| {
+				assert_eq!(Balances::free_balance(2), 200 - inclusion_fee - 10);
+				Ok(post_info_from_weight(Weight::from_parts(50, 0)))
+			})
+			.unwrap()
+			.unwrap();
+			// 3/2 of the returned 50 units of call weight is refunded, the priority fee is not.
+			assert_eq!(Balances::free_balance(2), 200 - (inclusion_fee - 75) - 10);
+			assert_eq!(TipUnbalancedAmount::get(), 10);
+			System::assert_has_event(RuntimeEvent::TransactionPayment(
+				pallet_transaction_payment::Event::<Runtime>::TransactionFeePaid {
+					who: 2,
+					actual_fee: inclusion_fee - 75 + 10,
+					tip: 10,
+				},
+			));
+
+			// A generous cap only pays the requested priority fee.
+			let ext = ChargePriorityFee::<Runtime>::new(1_000, 5);
+			assert_eq!(ext.effective_fee(&info, len).unwrap(), (inclusion_fee + 5, 5));
+		});
+}
+
 #[test]
 fn transaction_extension_transaction_payment_is_bounded() {
 	ExtBuilder::default().balance_factor(1000).byte_fee(0).build().execute_with(|| {
@@ -792,6 +865,24 @@ fn higher_tip_have_higher_priority() {
 	}
 }
 
+#[test]
+fn length_counts_towards_proof_size_in_priority() {
+	let info = info_from_weight(Weight::from_parts(1, 10));
+	let priority = |len| ChargeTransactionPayment::<Runtime>::get_priority(&info, len, 0, 0);
+
+	ExtBuilder::default().build().execute_with(|| {
+		// Bound by ref time, the length doesn't matter.
+		assert_eq!(priority(10), 1024);
+		assert_eq!(priority(90), 1024);
+	});
+
+	ExtBuilder::default().max_block_proof_size(1000).build().execute_with(|| {
+		// Bound by proof size, including the length: `1000 / (10 + len)`.
+		assert_eq!(priority(10), 50);
+		assert_eq!(priority(90), 10);
+	});
+}
+
 #[test]
 fn post_info_can_change_pays_fee() {
 	ExtBuilder::default()
@@ -915,3 +1006,175 @@ fn fungible_adapter_no_zero_refund_action() {
 		);
 	});
 }
+
+frame_support::parameter_types! {
+	pub const AuthorAccount: u64 = 5;
+	pub const TreasuryAccount: u64 = 6;
+	pub SplitFeeShares: FeeShares =
+		FeeShares { author: Percent::from_percent(50), treasury: Percent::from_percent(20) };
+	pub SplitTipShares: FeeShares =
+		FeeShares { author: Percent::from_percent(100), treasury: Percent::from_percent(100) };
+}
+
+#[test]
+fn split_fungible_adapter_routes_fee_and_tip() {
+	use frame_support::traits::tokens::imbalance::ResolveTo;
+	type SplitAdapterT = payment::SplitFungibleAdapter<
+		Balances,
+		ResolveTo<AuthorAccount, Balances>,
+		ResolveTo<TreasuryAccount, Balances>,
+		SplitFeeShares,
+		SplitTipShares,
+	>;
+
+	ExtBuilder::default().balance_factor(10).build().execute_with(|| {
+		System::set_block_number(10);
+		let issuance = Balances::total_issuance();
+
+		// 40 are withdrawn, of which 10 are refunded; 10 of the actual fee of 30 is the tip.
+		let (fee, actual_fee, tip) = (40, 30, 10);
+		let already_paid = <SplitAdapterT as OnChargeTransaction<Runtime>>::withdraw_fee(
+			&1,
+			CALL,
+			&CALL.get_dispatch_info(),
+			fee,
+			tip,
+		)
+		.expect("Account must have enough funds.");
+		assert_ok!(<SplitAdapterT as OnChargeTransaction<Runtime>>::correct_and_deposit_fee(
+			&1,
+			&CALL.get_dispatch_info(),
+			&default_post_info(),
+			actual_fee,
+			tip,
+			already_paid,
+		));
+
+		// Of the inclusion fee of 20, 10 go to the author, 4 to the treasury and 6 are burned.
+		// The tip goes entirely to the author, leaving nothing for the treasury.
+		assert_eq!(Balances::free_balance(1), 100 - actual_fee);
+		assert_eq!(Balances::free_balance(5), 500 + 20);
+		assert_eq!(Balances::free_balance(6), 600 + 4);
+		assert_eq!(Balances::total_issuance(), issuance - 6);
+		System::assert_last_event(
+			Event::<Runtime>::TransactionFeeRouted {
+				who: 1,
+				to_author: 20,
+				to_treasury: 4,
+				burned: 6,
+			}
+			.into(),
+		);
+	});
+}
+
+frame_support::parameter_types! {
+	pub PidTarget: Perquintill = Perquintill::from_percent(25);
+	pub PidMinMultiplier: Multiplier = Multiplier::saturating_from_rational(9, 10);
+	pub PidMaxMultiplier: Multiplier = Multiplier::saturating_from_rational(115, 100);
+	pub PidMaxStep: Multiplier = Multiplier::saturating_from_rational(1, 10);
+	pub PidAggressiveGains: PidGains = PidGains { proportional: Multiplier::one(), ..Default::default() };
+	pub SlowVariability: Multiplier = Multiplier::saturating_from_rational(4, 100_000);
+	pub PidGentleGains: PidGains = PidGains {
+		proportional: SlowVariability::get(),
+		integral: Multiplier::saturating_from_rational(1, 100_000),
+		derivative: Zero::zero(),
+	};
+	pub PidGentleMaxStep: Multiplier = Multiplier::saturating_from_rational(1, 100);
+	pub NoMinMultiplier: Multiplier = Multiplier::one();
+	pub NoMaxMultiplier: Multiplier = <Multiplier as sp_runtime::traits::Bounded>::max_value();
+}
+
+fn set_block_fullness(fullness: Perbill) {
+	let max = BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+	System::set_block_consumed_resources(Weight::from_parts(fullness * max.ref_time(), 0), 0);
+}
+
+#[test]
+fn pid_fee_adjustment_respects_max_step_and_bounds() {
+	type Update = PidFeeAdjustment<
+		Runtime,
+		PidTarget,
+		PidAggressiveGains,
+		PidMaxStep,
+		PidMinMultiplier,
+		PidMaxMultiplier,
+	>;
+
+	ExtBuilder::default().build().execute_with(|| {
+		let fractional = |n, d| Multiplier::saturating_from_rational(n, d);
+		let mut multiplier = Multiplier::one();
+
+		// A full block is far off target, yet the multiplier only changes by the maximum step.
+		set_block_fullness(Perbill::one());
+		multiplier = Update::convert(multiplier);
+		assert_eq!(multiplier, fractional(11, 10));
+
+		// Capped by the ceiling.
+		multiplier = Update::convert(multiplier);
+		assert_eq!(multiplier, PidMaxMultiplier::get());
+
+		// Empty blocks bring it down by the maximum step, until the floor.
+		set_block_fullness(Perbill::zero());
+		multiplier = Update::convert(multiplier);
+		assert_eq!(multiplier, fractional(1035, 1000));
+		multiplier = Update::convert(multiplier);
+		assert_eq!(multiplier, fractional(9315, 10000));
+		multiplier = Update::convert(multiplier);
+		assert_eq!(multiplier, PidMinMultiplier::get());
+
+		// Without an integral gain, no error is accumulated.
+		assert_eq!(FeeMultiplierPidState::<Runtime>::get().integral, Zero::zero());
+
+		assert_eq!(Update::max_step(), PidMaxStep::get());
+		assert_eq!(Update::variability(), Multiplier::one());
+	});
+}
+
+#[test]
+fn pid_fee_adjustment_reacts_faster_to_sustained_congestion() {
+	type Pid = PidFeeAdjustment<
+		Runtime,
+		PidTarget,
+		PidGentleGains,
+		PidGentleMaxStep,
+		NoMinMultiplier,
+		NoMaxMultiplier,
+	>;
+	type Slow = TargetedFeeAdjustment<
+		Runtime,
+		PidTarget,
+		SlowVariability,
+		NoMinMultiplier,
+		NoMaxMultiplier,
+	>;
+
+	ExtBuilder::default().build().execute_with(|| {
+		let (mut pid, mut slow) = (Multiplier::one(), Multiplier::one());
+		let (mut pid_step, mut slow_step) = (Multiplier::zero(), Multiplier::zero());
+
+		set_block_fullness(Perbill::one());
+		for _ in 0..20 {
+			let (next_pid, next_slow) = (Pid::convert(pid), Slow::convert(slow));
+			// The integral term makes the PID controller accelerate.
+			assert!(next_pid - pid > pid_step);
+			(pid_step, slow_step) = (next_pid - pid, next_slow - slow);
+			(pid, slow) = (next_pid, next_slow);
+			assert!(pid > slow);
+		}
+		assert!(pid_step > slow_step.saturating_mul(Multiplier::saturating_from_integer(5)));
+		assert_eq!(
+			FeeMultiplierPidState::<Runtime>::get(),
+			PidState {
+				integral: FixedI128::saturating_from_rational(15, 1),
+				previous_error: FixedI128::saturating_from_rational(3, 4),
+			}
+		);
+
+		// A block on target only leaves the integral term.
+		set_block_fullness(Perbill::from_percent(25));
+		let next_pid = Pid::convert(pid);
+		assert!(next_pid > pid);
+		assert_eq!(FeeMultiplierPidState::<Runtime>::get().previous_error, Zero::zero());
+	});
+}
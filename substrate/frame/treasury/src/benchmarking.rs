@@ -28,7 +28,7 @@ use frame_benchmarking::{
 use frame_support::{
 	assert_err, assert_ok, ensure,
 	traits::{
-		tokens::{ConversionFromAssetBalance, PaymentStatus},
+		tokens::{ConversionFromAssetBalance, ConversionToAssetBalance, PaymentStatus},
 		EnsureOrigin, OnInitialize,
 	},
 };
@@ -263,6 +263,95 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// This benchmark is short-circuited if `SpendOrigin` cannot provide
+	/// a successful origin, in which case `spend_valued` is un-callable and can use weight=0.
+	#[benchmark]
+	fn spend_valued() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset_kind, _, beneficiary, beneficiary_lookup) = create_spend_arguments::<T, _>(SEED);
+		let value: BalanceOf<T, I> = 100u32.into();
+		T::BalanceConverter::ensure_successful(asset_kind.clone());
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(asset_kind.clone()),
+			value,
+			Box::new(beneficiary_lookup),
+			None,
+		);
+
+		let valid_from = T::BlockNumberProvider::current_block_number();
+		let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
+		assert_last_event::<T, I>(
+			Event::ValuedSpendApproved {
+				index: 0,
+				asset_kind,
+				value,
+				beneficiary,
+				valid_from,
+				expire_at,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	/// This benchmark is short-circuited if `PartialPayoutOrigin` cannot provide
+	/// a successful origin, in which case `payout_partial` is un-callable and can use weight=0.
+	#[benchmark]
+	fn payout_partial() -> Result<(), BenchmarkError> {
+		let caller = T::PartialPayoutOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let beneficiary = T::PartialPayoutOrigin::ensure_origin(caller.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let beneficiary_lookup = T::BeneficiaryLookup::unlookup(beneficiary.clone());
+		let asset_kind = T::BenchmarkHelper::create_asset_kind(SEED);
+		let value: BalanceOf<T, I> = 100u32.into();
+		T::BalanceConverter::ensure_successful(asset_kind.clone());
+
+		let spend_exists = if let Ok(origin) = T::SpendOrigin::try_successful_origin() {
+			Treasury::<T, _>::spend_valued(
+				origin,
+				Box::new(asset_kind.clone()),
+				value,
+				Box::new(beneficiary_lookup),
+				None,
+			)?;
+
+			true
+		} else {
+			false
+		};
+
+		let half = value / 2u32.into();
+		let amount = T::BalanceConverter::to_asset_balance(half, asset_kind.clone())
+			.map_err(|_| BenchmarkError::Stop("Failed to convert the value"))?;
+		T::Paymaster::ensure_successful(&beneficiary, asset_kind, amount);
+
+		#[block]
+		{
+			let res = Treasury::<T, _>::payout_partial(caller, 0u32, half);
+
+			if spend_exists {
+				assert_ok!(res);
+			} else {
+				assert_err!(res, crate::Error::<T, _>::InvalidIndex);
+			}
+		}
+
+		if spend_exists {
+			let id = match Spends::<T, I>::get(0).unwrap().status {
+				PaymentState::Attempted { id, .. } => id,
+				_ => panic!("No payout attempt made"),
+			};
+			assert_last_event::<T, I>(Event::Paid { index: 0, payment_id: id }.into());
+		}
+
+		Ok(())
+	}
+
 	#[benchmark]
 	fn check_status() -> Result<(), BenchmarkError> {
 		let (asset_kind, amount, beneficiary, beneficiary_lookup) =
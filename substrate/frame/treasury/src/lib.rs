@@ -193,6 +193,21 @@ pub struct SpendStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, Paymen
 	status: PaymentState<PaymentId>,
 }
 
+/// The value in the native asset of an approved treasury spend, which can be paid out of its
+/// asset kind in several payouts.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct SpendValuation<Balance, BlockNumber> {
+	/// The value of the spend in the native asset.
+	value: Balance,
+	/// The value paid out so far.
+	paid: Balance,
+	/// The value of the payout being attempted.
+	attempted: Balance,
+	/// The block number by which the remaining value has to be claimed.
+	claim_until: BlockNumber,
+}
+
 /// Index of an approved treasury spend.
 pub type SpendIndex = u32;
 
@@ -202,7 +217,7 @@ pub mod pallet {
 	use frame_support::{
 		dispatch_context::with_context,
 		pallet_prelude::*,
-		traits::tokens::{ConversionFromAssetBalance, PaymentStatus},
+		traits::tokens::{ConversionFromAssetBalance, ConversionToAssetBalance, PaymentStatus},
 	};
 	use frame_system::pallet_prelude::{ensure_signed, OriginFor};
 
@@ -266,17 +281,27 @@ pub mod pallet {
 		/// Converting trait to take a source type and convert to [`Self::Beneficiary`].
 		type BeneficiaryLookup: StaticLookup<Target = Self::Beneficiary>;
 
+		/// The origin required for claiming a spend valued in the native asset in several parts.
+		/// The `Success` value is the beneficiary this origin acts for, only its own spends can
+		/// be paid out partially.
+		type PartialPayoutOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::Beneficiary>;
+
 		/// Type for processing spends of [Self::AssetKind] in favor of [`Self::Beneficiary`].
 		type Paymaster: Pay<Beneficiary = Self::Beneficiary, AssetKind = Self::AssetKind>;
 
 		/// Type for converting the balance of an [Self::AssetKind] to the balance of the native
-		/// asset, solely for the purpose of asserting the result against the maximum allowed spend
-		/// amount of the [`Self::SpendOrigin`].
+		/// asset, for the purpose of asserting the result against the maximum allowed spend
+		/// amount of the [`Self::SpendOrigin`], and back, for paying out the spends valued in the
+		/// native asset.
 		type BalanceConverter: ConversionFromAssetBalance<
-			<Self::Paymaster as Pay>::Balance,
-			Self::AssetKind,
-			BalanceOf<Self, I>,
-		>;
+				<Self::Paymaster as Pay>::Balance,
+				Self::AssetKind,
+				BalanceOf<Self, I>,
+			> + ConversionToAssetBalance<
+				BalanceOf<Self, I>,
+				Self::AssetKind,
+				<Self::Paymaster as Pay>::Balance,
+			>;
 
 		/// The period during which an approved treasury spend has to be claimed.
 		#[pallet::constant]
@@ -352,6 +377,16 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The values in the native asset of the spends approved with `spend_valued`.
+	#[pallet::storage]
+	pub type SpendValuations<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		SpendIndex,
+		SpendValuation<BalanceOf<T, I>, BlockNumberFor<T, I>>,
+		OptionQuery,
+	>;
+
 	/// The blocknumber for the last triggered spend period.
 	#[pallet::storage]
 	pub type LastSpendPeriod<T, I = ()> = StorageValue<_, BlockNumberFor<T, I>, OptionQuery>;
@@ -414,6 +449,18 @@ pub mod pallet {
 		/// A spend was processed and removed from the storage. It might have been successfully
 		/// paid or it may have expired.
 		SpendProcessed { index: SpendIndex },
+		/// A new spend valued in the native asset has been approved.
+		ValuedSpendApproved {
+			index: SpendIndex,
+			asset_kind: T::AssetKind,
+			value: BalanceOf<T, I>,
+			beneficiary: T::Beneficiary,
+			valid_from: BlockNumberFor<T, I>,
+			expire_at: BlockNumberFor<T, I>,
+		},
+		/// A payout of a spend valued in the native asset succeeded, the remaining value can be
+		/// claimed until the spend expires.
+		SpendPartiallyPaid { index: SpendIndex, paid: BalanceOf<T, I>, remaining: BalanceOf<T, I> },
 	}
 
 	/// Error for the treasury pallet.
@@ -442,6 +489,10 @@ pub mod pallet {
 		NotAttempted,
 		/// The payment has neither failed nor succeeded yet.
 		Inconclusive,
+		/// The spend is not valued in the native asset, it can only be paid out at once.
+		NotValued,
+		/// The origin is not the beneficiary of the spend.
+		NotBeneficiary,
 	}
 
 	#[pallet::hooks]
@@ -663,25 +714,7 @@ pub mod pallet {
 				T::BalanceConverter::from_asset_balance(amount, *asset_kind.clone())
 					.map_err(|_| Error::<T, I>::FailedToConvertBalance)?;
 
-			ensure!(native_amount <= max_amount, Error::<T, I>::InsufficientPermission);
-
-			with_context::<SpendContext<BalanceOf<T, I>>, _>(|v| {
-				let context = v.or_default();
-				// We group based on `max_amount`, to distinguish between different kind of
-				// origins. (assumes that all origins have different `max_amount`)
-				//
-				// Worst case is that we reject some "valid" request.
-				let spend = context.spend_in_context.entry(max_amount).or_default();
-
-				// Ensure that we don't overflow nor use more than `max_amount`
-				if spend.checked_add(&native_amount).map(|s| s > max_amount).unwrap_or(true) {
-					Err(Error::<T, I>::InsufficientPermission)
-				} else {
-					*spend = spend.saturating_add(native_amount);
-					Ok(())
-				}
-			})
-			.unwrap_or(Ok(()))?;
+			Self::ensure_spend_allowed(max_amount, native_amount)?;
 
 			let index = SpendCount::<T, I>::get();
 			Spends::<T, I>::insert(
@@ -721,6 +754,10 @@ pub mod pallet {
 		/// In case of a payout failure, the spend status must be updated with the `check_status`
 		/// dispatchable before retrying with the current function.
 		///
+		/// The remaining value of a spend approved with `spend_valued` is paid out in its asset
+		/// kind, at the conversion rate of the [`Config::BalanceConverter`] at the time of the
+		/// payout.
+		///
 		/// ### Parameters
 		/// - `index`: The spend index.
 		///
@@ -731,25 +768,7 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::payout())]
 		pub fn payout(origin: OriginFor<T>, index: SpendIndex) -> DispatchResult {
 			ensure_signed(origin)?;
-			let mut spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
-			let now = T::BlockNumberProvider::current_block_number();
-			ensure!(now >= spend.valid_from, Error::<T, I>::EarlyPayout);
-			ensure!(spend.expire_at > now, Error::<T, I>::SpendExpired);
-			ensure!(
-				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
-				Error::<T, I>::AlreadyAttempted
-			);
-
-			let id = T::Paymaster::pay(&spend.beneficiary, spend.asset_kind.clone(), spend.amount)
-				.map_err(|_| Error::<T, I>::PayoutError)?;
-
-			spend.status = PaymentState::Attempted { id };
-			spend.expire_at = now.saturating_add(T::PayoutPeriod::get());
-			Spends::<T, I>::insert(index, spend);
-
-			Self::deposit_event(Event::<T, I>::Paid { index, payment_id: id });
-
-			Ok(())
+			Self::do_payout(index, None)
 		}
 
 		/// Check the status of the spend and remove it from the storage if processed.
@@ -784,6 +803,7 @@ pub mod pallet {
 			if now > spend.expire_at && !matches!(spend.status, State::Attempted { .. }) {
 				// spend has expired and no further status update is expected.
 				Spends::<T, I>::remove(index);
+				SpendValuations::<T, I>::remove(index);
 				Self::deposit_event(Event::<T, I>::SpendProcessed { index });
 				return Ok(Pays::No.into())
 			}
@@ -793,15 +813,41 @@ pub mod pallet {
 				_ => return Err(Error::<T, I>::NotAttempted.into()),
 			};
 
+			let mut valuation = SpendValuations::<T, I>::get(index);
 			match T::Paymaster::check_payment(payment_id) {
 				Status::Failure => {
 					spend.status = PaymentState::Failed;
 					Spends::<T, I>::insert(index, spend);
+					if let Some(mut valuation) = valuation {
+						valuation.attempted = Zero::zero();
+						SpendValuations::<T, I>::insert(index, valuation);
+					}
 					Self::deposit_event(Event::<T, I>::PaymentFailed { index, payment_id });
 				},
 				Status::Success | Status::Unknown => {
-					Spends::<T, I>::remove(index);
-					Self::deposit_event(Event::<T, I>::SpendProcessed { index });
+					if let Some(valuation) = valuation.as_mut() {
+						valuation.paid = valuation.paid.saturating_add(valuation.attempted);
+						valuation.attempted = Zero::zero();
+					}
+					match valuation {
+						Some(valuation) if valuation.paid < valuation.value => {
+							// The rest of the spend can be claimed until it expires.
+							spend.status = PaymentState::Pending;
+							spend.expire_at = valuation.claim_until;
+							Spends::<T, I>::insert(index, spend);
+							Self::deposit_event(Event::<T, I>::SpendPartiallyPaid {
+								index,
+								paid: valuation.paid,
+								remaining: valuation.value.saturating_sub(valuation.paid),
+							});
+							SpendValuations::<T, I>::insert(index, valuation);
+						},
+						_ => {
+							Spends::<T, I>::remove(index);
+							SpendValuations::<T, I>::remove(index);
+							Self::deposit_event(Event::<T, I>::SpendProcessed { index });
+						},
+					}
 					return Ok(Pays::No.into())
 				},
 				Status::InProgress => return Err(Error::<T, I>::Inconclusive.into()),
@@ -836,9 +882,196 @@ pub mod pallet {
 			);
 
 			Spends::<T, I>::remove(index);
+			SpendValuations::<T, I>::remove(index);
 			Self::deposit_event(Event::<T, I>::AssetSpendVoided { index });
 			Ok(())
 		}
+
+		/// Propose and approve a spend of treasury funds, valued in the native asset.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be [`Config::SpendOrigin`] with the `Success` value being at least `value`.
+		///
+		/// ## Details
+		///
+		/// Create an approved spend for transferring `value`, denominated in the native asset, in
+		/// `asset_kind` to a designated beneficiary. The amount of `asset_kind` is converted from
+		/// the value using the [`Config::BalanceConverter`] at the time of every payout, so that
+		/// the beneficiary receives the approved value whatever the rate of the asset.
+		///
+		/// The spend can be claimed at once with the `payout` dispatchable, or in several parts
+		/// with the `payout_partial` dispatchable, within the [`Config::PayoutPeriod`]. A payout
+		/// that is not concluded by then can still be checked with `check_status`, the remaining
+		/// value expires.
+		///
+		/// ### Parameters
+		/// - `asset_kind`: An indicator of the specific asset class to be spent.
+		/// - `value`: The value to be transferred to the `beneficiary`, in the native asset.
+		/// - `beneficiary`: The beneficiary of the spend.
+		/// - `valid_from`: The block number from which the spend can be claimed, see `spend`.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::ValuedSpendApproved`] if successful.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::spend_valued())]
+		pub fn spend_valued(
+			origin: OriginFor<T>,
+			asset_kind: Box<T::AssetKind>,
+			#[pallet::compact] value: BalanceOf<T, I>,
+			beneficiary: Box<BeneficiaryLookupOf<T, I>>,
+			valid_from: Option<BlockNumberFor<T, I>>,
+		) -> DispatchResult {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(*beneficiary)?;
+
+			let now = T::BlockNumberProvider::current_block_number();
+			let valid_from = valid_from.unwrap_or(now);
+			let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
+			ensure!(expire_at > now, Error::<T, I>::SpendExpired);
+
+			// The asset amount is only indicative until the payout.
+			let amount = T::BalanceConverter::to_asset_balance(value, *asset_kind.clone())
+				.map_err(|_| Error::<T, I>::FailedToConvertBalance)?;
+
+			Self::ensure_spend_allowed(max_amount, value)?;
+
+			let index = SpendCount::<T, I>::get();
+			Spends::<T, I>::insert(
+				index,
+				SpendStatus {
+					asset_kind: *asset_kind.clone(),
+					amount,
+					beneficiary: beneficiary.clone(),
+					valid_from,
+					expire_at,
+					status: PaymentState::Pending,
+				},
+			);
+			SpendValuations::<T, I>::insert(
+				index,
+				SpendValuation {
+					value,
+					paid: Zero::zero(),
+					attempted: Zero::zero(),
+					claim_until: expire_at,
+				},
+			);
+			SpendCount::<T, I>::put(index + 1);
+
+			Self::deposit_event(Event::ValuedSpendApproved {
+				index,
+				asset_kind: *asset_kind,
+				value,
+				beneficiary,
+				valid_from,
+				expire_at,
+			});
+			Ok(())
+		}
+
+		/// Claim a part of a spend valued in the native asset.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be [`Config::PartialPayoutOrigin`] with the `Success` value being the beneficiary
+		/// of the spend.
+		///
+		/// ## Details
+		///
+		/// Pays out `value` of the spend, or its remaining value if lower, like `payout`. Once the
+		/// payout succeeded, the `check_status` dispatchable makes the remaining value claimable
+		/// again. Only the beneficiary can split the payouts of its spend, anyone else can only
+		/// pay out the remaining value at once with `payout`.
+		///
+		/// ### Parameters
+		/// - `index`: The spend index.
+		/// - `value`: The value to be paid out, in the native asset.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::Paid`] if successful.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::payout_partial())]
+		pub fn payout_partial(
+			origin: OriginFor<T>,
+			index: SpendIndex,
+			#[pallet::compact] value: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let beneficiary = T::PartialPayoutOrigin::ensure_origin(origin)?;
+			let spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			ensure!(spend.beneficiary == beneficiary, Error::<T, I>::NotBeneficiary);
+			Self::do_payout(index, Some(value))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Ensure that the spend origin with the maximum native amount `max_amount` is allowed to
+		/// spend `native_amount`, including the spends in the same dispatch context.
+		fn ensure_spend_allowed(
+			max_amount: BalanceOf<T, I>,
+			native_amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			ensure!(native_amount <= max_amount, Error::<T, I>::InsufficientPermission);
+
+			with_context::<SpendContext<BalanceOf<T, I>>, _>(|v| {
+				let context = v.or_default();
+				// We group based on `max_amount`, to distinguish between different kind of
+				// origins. (assumes that all origins have different `max_amount`)
+				//
+				// Worst case is that we reject some "valid" request.
+				let spend = context.spend_in_context.entry(max_amount).or_default();
+
+				// Ensure that we don't overflow nor use more than `max_amount`
+				if spend.checked_add(&native_amount).map(|s| s > max_amount).unwrap_or(true) {
+					Err(Error::<T, I>::InsufficientPermission)
+				} else {
+					*spend = spend.saturating_add(native_amount);
+					Ok(())
+				}
+			})
+			.unwrap_or(Ok(()))?;
+
+			Ok(())
+		}
+
+		/// Pay out the spend at `index`, or `value` of it if the spend is valued in the native
+		/// asset.
+		fn do_payout(index: SpendIndex, value: Option<BalanceOf<T, I>>) -> DispatchResult {
+			let mut spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = T::BlockNumberProvider::current_block_number();
+			ensure!(now >= spend.valid_from, Error::<T, I>::EarlyPayout);
+			ensure!(spend.expire_at > now, Error::<T, I>::SpendExpired);
+			ensure!(
+				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T, I>::AlreadyAttempted
+			);
+
+			if let Some(mut valuation) = SpendValuations::<T, I>::get(index) {
+				let remaining = valuation.value.saturating_sub(valuation.paid);
+				valuation.attempted = value.map_or(remaining, |value| value.min(remaining));
+				spend.amount = T::BalanceConverter::to_asset_balance(
+					valuation.attempted,
+					spend.asset_kind.clone(),
+				)
+				.map_err(|_| Error::<T, I>::FailedToConvertBalance)?;
+				SpendValuations::<T, I>::insert(index, valuation);
+			} else {
+				ensure!(value.is_none(), Error::<T, I>::NotValued);
+			}
+
+			let id = T::Paymaster::pay(&spend.beneficiary, spend.asset_kind.clone(), spend.amount)
+				.map_err(|_| Error::<T, I>::PayoutError)?;
+
+			spend.status = PaymentState::Attempted { id };
+			spend.expire_at = now.saturating_add(T::PayoutPeriod::get());
+			Spends::<T, I>::insert(index, spend);
+
+			Self::deposit_event(Event::<T, I>::Paid { index, payment_id: id });
+
+			Ok(())
+		}
 	}
 }
 
@@ -1050,6 +1283,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// [`SpendCount`].
 	/// 3. For each spend entry contained in [`Spends`] we should have spend.expire_at
 	/// > spend.valid_from.
+	/// 4. Each entry in [`SpendValuations`] should be saved under the key of a spend in [`Spends`],
+	/// and its paid value should not exceed its value.
 	#[cfg(any(feature = "try-runtime", test))]
 	fn try_state_spends() -> Result<(), sp_runtime::TryRuntimeError> {
 		let current_spend_count = SpendCount::<T, I>::get();
@@ -1074,6 +1309,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Ok(())
 		})?;
 
+		SpendValuations::<T, I>::iter().try_for_each(|(index, valuation)| -> DispatchResult {
+			ensure!(Spends::<T, I>::contains_key(index), "Valuation of a missing spend.");
+			ensure!(valuation.paid <= valuation.value, "Valued spend is overpaid.");
+			Ok(())
+		})?;
+
 		Ok(())
 	}
 }
@@ -30,7 +30,7 @@ use frame_support::{
 	pallet_prelude::Pays,
 	parameter_types,
 	traits::{
-		tokens::{ConversionFromAssetBalance, PaymentStatus},
+		tokens::{ConversionFromAssetBalance, ConversionToAssetBalance, PaymentStatus},
 		ConstU32, ConstU64, OnInitialize,
 	},
 	PalletId,
@@ -176,6 +176,12 @@ impl<N: Get<u64>> ConversionFromAssetBalance<u64, u32, u64> for MulBy<N> {
 	#[cfg(feature = "runtime-benchmarks")]
 	fn ensure_successful(_: u32) {}
 }
+impl<N: Get<u64>> ConversionToAssetBalance<u64, u32, u64> for MulBy<N> {
+	type Error = ();
+	fn to_asset_balance(balance: u64, _asset_id: u32) -> Result<u64, Self::Error> {
+		return balance.checked_div(N::get()).ok_or(())
+	}
+}
 
 impl Config for Test {
 	type PalletId = TreasuryPalletId;
@@ -192,6 +198,7 @@ impl Config for Test {
 	type AssetKind = u32;
 	type Beneficiary = u128;
 	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type PartialPayoutOrigin = frame_system::EnsureSigned<u128>;
 	type Paymaster = TestPay;
 	type BalanceConverter = MulBy<ConstU64<2>>;
 	type PayoutPeriod = SpendPayoutPeriod;
@@ -788,6 +795,103 @@ fn void_spend_works() {
 	});
 }
 
+#[test]
+fn spend_valued_partial_payouts_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// the value is checked against the maximum of the origin, in the native asset.
+		assert_noop!(
+			Treasury::spend_valued(RuntimeOrigin::signed(10), Box::new(1), 6, Box::new(6), None),
+			Error::<Test, _>::InsufficientPermission
+		);
+		// approve a spend valued `100` native coins, paid in asset `1`.
+		assert_ok!(Treasury::spend_valued(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None
+		));
+		System::assert_last_event(
+			Event::<Test, _>::ValuedSpendApproved {
+				index: 0,
+				asset_kind: 1,
+				value: 100,
+				beneficiary: 6,
+				valid_from: 1,
+				expire_at: 6,
+			}
+			.into(),
+		);
+
+		// only the beneficiary can split the payouts.
+		assert_noop!(
+			Treasury::payout_partial(RuntimeOrigin::signed(1), 0, 40),
+			Error::<Test, _>::NotBeneficiary
+		);
+
+		// claim `40` of the value, converted to `20` coins of asset `1`.
+		assert_ok!(Treasury::payout_partial(RuntimeOrigin::signed(6), 0, 40));
+		assert_eq!(paid(6, 1), 20);
+		let payment_id = get_payment_id(0).expect("no payment attempt");
+		assert_noop!(
+			Treasury::payout_partial(RuntimeOrigin::signed(6), 0, 10),
+			Error::<Test, _>::AlreadyAttempted
+		);
+		set_status(payment_id, PaymentStatus::Success);
+		assert_ok!(Treasury::check_status(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(
+			Event::<Test, _>::SpendPartiallyPaid { index: 0, paid: 40, remaining: 60 }.into(),
+		);
+		assert_eq!(Spends::<Test, _>::get(0).unwrap().expire_at, 6);
+
+		// claiming more than the remaining value only pays the remaining value.
+		assert_ok!(Treasury::payout_partial(RuntimeOrigin::signed(6), 0, 1000));
+		assert_eq!(paid(6, 1), 50);
+		let payment_id = get_payment_id(0).expect("no payment attempt");
+		set_status(payment_id, PaymentStatus::Success);
+		assert_ok!(Treasury::check_status(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(Event::<Test, _>::SpendProcessed { index: 0 }.into());
+		assert_eq!(SpendValuations::<Test, _>::get(0), None);
+
+		// spends in an asset amount cannot be paid out partially.
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 2, Box::new(6), None));
+		assert_noop!(
+			Treasury::payout_partial(RuntimeOrigin::signed(6), 1, 1),
+			Error::<Test, _>::NotValued
+		);
+	});
+}
+
+#[test]
+fn spend_valued_expires() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend_valued(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None
+		));
+		assert_ok!(Treasury::payout_partial(RuntimeOrigin::signed(6), 0, 40));
+		let payment_id = get_payment_id(0).expect("no payment attempt");
+		set_status(payment_id, PaymentStatus::Success);
+
+		// the remaining value expires with the spend.
+		System::set_block_number(7);
+		assert_ok!(Treasury::check_status(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(
+			Event::<Test, _>::SpendPartiallyPaid { index: 0, paid: 40, remaining: 60 }.into(),
+		);
+		assert_noop!(Treasury::payout(RuntimeOrigin::signed(1), 0), Error::<Test, _>::SpendExpired);
+		assert_ok!(Treasury::check_status(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(Event::<Test, _>::SpendProcessed { index: 0 }.into());
+		assert_eq!(Spends::<Test, _>::get(0), None);
+		assert_eq!(SpendValuations::<Test, _>::get(0), None);
+	});
+}
+
 #[test]
 fn check_status_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -17,7 +17,9 @@
 
 //! Supporting types for try-runtime, testing and dry-running commands.
 
-pub use frame_support::traits::{TryStateSelect, UpgradeCheckSelect};
+pub use frame_support::traits::{
+	MigrationReport, StorageVersionTransition, TryStateSelect, UpgradeCheckSelect, UpgradeReport,
+};
 use frame_support::weights::Weight;
 
 sp_api::decl_runtime_apis! {
@@ -36,6 +38,20 @@ sp_api::decl_runtime_apis! {
 		/// tracking is likely inaccurate.
 		fn on_runtime_upgrade(checks: UpgradeCheckSelect) -> (Weight, Weight);
 
+		/// dry-run runtime upgrades, like [`Self::on_runtime_upgrade`], and report each migration.
+		///
+		/// If `checks` selects them, the `try_state` hooks of all pallets are also executed before
+		/// the migrations. If `idempotency_checks` is `true`, every migration is executed a second
+		/// time after all of them ran, to check that it leaves the state untouched.
+		///
+		/// Returns the weight consumed by every migration, and the storage versions changed by the
+		/// upgrade.
+		#[api_version(2)]
+		fn on_runtime_upgrade_with_report(
+			checks: UpgradeCheckSelect,
+			idempotency_checks: bool,
+		) -> UpgradeReport;
+
 		/// Execute the given block, but optionally disable state-root and signature checks.
 		///
 		/// Optionally, a number of `try_state` hooks can also be executed after the block
@@ -21,6 +21,7 @@
 
 use alloc::vec;
 use frame_benchmarking::{benchmarking::add_to_whitelist, v2::*};
+use frame_support::weights::Weight;
 use frame_system::RawOrigin;
 
 use crate::*;
@@ -92,6 +93,17 @@ mod benchmark {
 		assert_last_event::<T>(Event::BatchCompleted.into());
 	}
 
+	#[benchmark]
+	fn batch_with_budget(c: Linear<0, 1000>) {
+		let calls = vec![frame_system::Call::remark { remark: vec![] }.into(); c as usize];
+		let caller = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), calls, Weight::MAX);
+
+		assert_last_event::<T>(Event::BudgetedBatchCompleted { executed: c }.into());
+	}
+
 	#[benchmark]
 	fn dispatch_as_fallible() {
 		let caller = account("caller", SEED, SEED);
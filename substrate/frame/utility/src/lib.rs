@@ -45,6 +45,8 @@
 //!
 //! #### For batch dispatch
 //! * `batch` - Dispatch multiple calls from the sender's origin.
+//! * `batch_with_budget` - Dispatch multiple calls from the sender's origin, until a weight budget
+//!   is exhausted.
 //!
 //! #### For pseudonymal dispatch
 //! * `as_derivative` - Dispatch a call from a derivative signed origin.
@@ -129,6 +131,9 @@ pub mod pallet {
 		IfElseMainSuccess,
 		/// The fallback call was dispatched.
 		IfElseFallbackCalled { main_error: DispatchError },
+		/// Batch of dispatches with a weight budget completed, the first `executed` calls were
+		/// dispatched.
+		BudgetedBatchCompleted { executed: u32 },
 	}
 
 	// Align the call size to 1KB. As we are currently compiling the runtime for native/wasm
@@ -583,6 +588,77 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Send a batch of dispatch calls, within a weight budget.
+		///
+		/// The calls are dispatched in order until the next one would exceed `max_weight`, the
+		/// remaining calls are skipped and their weight is refunded. Like `force_batch`, errors
+		/// don't interrupt the batch.
+		///
+		/// May be called from any origin except `None`.
+		///
+		/// - `calls`: The calls to be dispatched from the same origin. The number of call must not
+		///   exceed the constant: `batched_calls_limit` (available in constant metadata).
+		/// - `max_weight`: The maximum weight of the dispatched calls, excluding the weight of this
+		///   call itself.
+		///
+		/// If origin is root then the calls are dispatched without checking origin filter. (This
+		/// includes bypassing `frame_system::Config::BaseCallFilter`).
+		///
+		/// ## Complexity
+		/// - O(C) where C is the number of calls to be batched.
+		///
+		/// The number of dispatched calls is given by the `BudgetedBatchCompleted` event.
+		#[pallet::call_index(8)]
+		#[pallet::weight({
+			let (dispatch_weight, dispatch_class) = Pallet::<T>::weight_and_dispatch_class(&calls);
+			let dispatch_weight = dispatch_weight
+				.min(*max_weight)
+				.saturating_add(T::WeightInfo::batch_with_budget(calls.len() as u32));
+			(dispatch_weight, dispatch_class)
+		})]
+		pub fn batch_with_budget(
+			origin: OriginFor<T>,
+			calls: Vec<<T as Config>::RuntimeCall>,
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			// Do not allow the `None` origin.
+			if ensure_none(origin.clone()).is_ok() {
+				return Err(BadOrigin.into())
+			}
+
+			let is_root = ensure_root(origin.clone()).is_ok();
+			let calls_len = calls.len();
+			ensure!(calls_len <= Self::batched_calls_limit() as usize, Error::<T>::TooManyCalls);
+
+			// Track the actual weight of each of the batch calls.
+			let mut weight = Weight::zero();
+			let mut executed: u32 = 0;
+			for call in calls.into_iter() {
+				let info = call.get_dispatch_info();
+				// Stop before the budget is exceeded by the worst case weight of the call.
+				if weight.saturating_add(info.call_weight).any_gt(max_weight) {
+					break
+				}
+				// If origin is root, don't apply any dispatch filters; root can call anything.
+				let result = if is_root {
+					call.dispatch_bypass_filter(origin.clone())
+				} else {
+					call.dispatch(origin.clone())
+				};
+				executed += 1;
+				// Add the weight of this call.
+				weight = weight.saturating_add(extract_actual_weight(&result, &info));
+				if let Err(e) = result {
+					Self::deposit_event(Event::ItemFailed { error: e.error });
+				} else {
+					Self::deposit_event(Event::ItemCompleted);
+				}
+			}
+			Self::deposit_event(Event::BudgetedBatchCompleted { executed });
+			let base_weight = T::WeightInfo::batch_with_budget(executed);
+			Ok(Some(base_weight.saturating_add(weight)).into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -749,6 +749,56 @@ fn force_batch_works() {
 	});
 }
 
+#[test]
+fn batch_with_budget_works() {
+	new_test_ext().execute_with(|| {
+		let calls = vec![
+			call_foobar(false, Weight::from_parts(100, 0), None),
+			call_foobar(true, Weight::from_parts(200, 0), None),
+			call_foobar(false, Weight::from_parts(300, 0), None),
+			call_foobar(false, Weight::from_parts(400, 0), None),
+		];
+		let batch_call = RuntimeCall::Utility(UtilityCall::batch_with_budget {
+			calls,
+			max_weight: Weight::from_parts(700, 0),
+		});
+		// The declared weight of the calls is capped by the budget.
+		let info = batch_call.get_dispatch_info();
+		assert_eq!(
+			info.call_weight,
+			<Test as Config>::WeightInfo::batch_with_budget(4) + Weight::from_parts(700, 0)
+		);
+
+		// The fourth call doesn't fit in the remaining budget, errors don't interrupt the batch.
+		let result = batch_call.dispatch(RuntimeOrigin::signed(1));
+		assert_eq!(
+			extract_actual_weight(&result, &info),
+			<Test as Config>::WeightInfo::batch_with_budget(3) + Weight::from_parts(600, 0)
+		);
+		System::assert_has_event(
+			utility::Event::ItemFailed { error: DispatchError::Other("") }.into(),
+		);
+		System::assert_last_event(utility::Event::BudgetedBatchCompleted { executed: 3 }.into());
+
+		// The actual weight of the dispatched calls is taken into account.
+		let calls = vec![
+			call_foobar(false, Weight::from_parts(300, 0), Some(Weight::from_parts(100, 0))),
+			call_foobar(false, Weight::from_parts(300, 0), None),
+		];
+		assert_ok!(Utility::batch_with_budget(
+			RuntimeOrigin::signed(1),
+			calls,
+			Weight::from_parts(400, 0)
+		));
+		System::assert_last_event(utility::Event::BudgetedBatchCompleted { executed: 2 }.into());
+
+		assert_noop!(
+			Utility::batch_with_budget(RuntimeOrigin::none(), vec![], Weight::MAX),
+			BadOrigin
+		);
+	});
+}
+
 #[test]
 fn none_origin_does_not_work() {
 	new_test_ext().execute_with(|| {
@@ -152,6 +152,12 @@
 //! }
 //! ```
 //!
+//! When multiple inherent data providers depend on each other, e.g. the slot is derived from the
+//! timestamp, they can be registered in an [`InherentDataProviderRegistry`] with their
+//! dependencies declared explicitly. The registry creates the providers in dependency order, makes
+//! the inherent data of the already created providers available to the next ones and rejects
+//! unknown, duplicate or cyclic dependencies when it is built.
+//!
 //! # Creating the inherent
 //!
 //! As the inherents are created by the runtime, it depends on the runtime implementation on how
@@ -173,9 +179,16 @@ use alloc::{
 
 #[cfg(feature = "std")]
 mod client_side;
+#[cfg(feature = "std")]
+mod registry;
 
 #[cfg(feature = "std")]
 pub use client_side::*;
+#[cfg(feature = "std")]
+pub use registry::{
+	InherentDataProviderRegistry, InherentDataProviderRegistryBuilder, ProviderName,
+	RegisteredInherentDataProviders, RegistryError,
+};
 
 /// Errors that occur in context of inherents.
 #[derive(Debug)]
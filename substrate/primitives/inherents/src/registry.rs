@@ -0,0 +1,381 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A registry of inherent data providers with declared dependencies.
+//!
+//! Inherent data providers are usually wired together by hand in a single closure, e.g. the slot
+//! provider is created from the timestamp provider and the parachain inherent is created after the
+//! relay chain interface has been queried. Getting this order wrong only surfaces when authoring a
+//! block. The [`InherentDataProviderRegistry`] makes the ordering explicit: every provider is
+//! registered under a name together with the names of the providers it depends on, and
+//! [`InherentDataProviderRegistryBuilder::build`] validates the resulting graph, so that
+//! misconfiguration is detected when the service is built.
+
+use crate::{CreateInherentDataProviders, Error, InherentData, InherentDataProvider};
+use sp_runtime::traits::Block as BlockT;
+use std::{collections::HashMap, future::Future, pin::Pin};
+
+/// The name an inherent data provider is registered under.
+pub type ProviderName = &'static str;
+
+type CreateResult = Result<Box<dyn InherentDataProvider>, Box<dyn std::error::Error + Send + Sync>>;
+
+type CreateFn<Block, ExtraArgs> = Box<
+	dyn Fn(
+			<Block as BlockT>::Hash,
+			ExtraArgs,
+			InherentData,
+		) -> Pin<Box<dyn Future<Output = CreateResult> + Send>>
+		+ Send
+		+ Sync,
+>;
+
+/// Errors detected while building an [`InherentDataProviderRegistry`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RegistryError {
+	/// Two inherent data providers were registered under the same name.
+	#[error("Inherent data provider `{0}` is registered more than once")]
+	DuplicateProvider(ProviderName),
+	/// An inherent data provider depends on a provider that isn't registered.
+	#[error("Inherent data provider `{provider}` depends on unknown provider `{dependency}`")]
+	UnknownDependency {
+		/// The provider declaring the dependency.
+		provider: ProviderName,
+		/// The missing dependency.
+		dependency: ProviderName,
+	},
+	/// The dependencies between the given providers form a cycle.
+	#[error("Inherent data providers {0:?} have cyclic dependencies")]
+	DependencyCycle(Vec<ProviderName>),
+}
+
+struct RegisteredProvider<Block: BlockT, ExtraArgs> {
+	name: ProviderName,
+	dependencies: Vec<ProviderName>,
+	create: CreateFn<Block, ExtraArgs>,
+}
+
+/// Builder for an [`InherentDataProviderRegistry`].
+pub struct InherentDataProviderRegistryBuilder<Block: BlockT, ExtraArgs> {
+	providers: Vec<RegisteredProvider<Block, ExtraArgs>>,
+}
+
+impl<Block: BlockT, ExtraArgs> Default for InherentDataProviderRegistryBuilder<Block, ExtraArgs> {
+	fn default() -> Self {
+		Self { providers: Vec::new() }
+	}
+}
+
+impl<Block, ExtraArgs> InherentDataProviderRegistryBuilder<Block, ExtraArgs>
+where
+	Block: BlockT,
+	ExtraArgs: Clone + Send + 'static,
+{
+	/// Create a new empty builder.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register an inherent data provider under the given `name`.
+	///
+	/// The provider is only created once all its `dependencies` have been created. `create` is
+	/// called with the parent hash, the extra arguments passed to the registry and the
+	/// [`InherentData`] provided by all the providers created before it, which always includes
+	/// the data of its dependencies.
+	pub fn register<F, Fut, IDP>(
+		mut self,
+		name: ProviderName,
+		dependencies: &[ProviderName],
+		create: F,
+	) -> Self
+	where
+		F: Fn(Block::Hash, ExtraArgs, InherentData) -> Fut + Send + Sync + 'static,
+		Fut:
+			Future<Output = Result<IDP, Box<dyn std::error::Error + Send + Sync>>> + Send + 'static,
+		IDP: InherentDataProvider + 'static,
+	{
+		let create: CreateFn<Block, ExtraArgs> = Box::new(move |parent, extra_args, data| {
+			let future = create(parent, extra_args, data);
+			Box::pin(async move {
+				future.await.map(|idp| Box::new(idp) as Box<dyn InherentDataProvider>)
+			})
+		});
+
+		self.providers.push(RegisteredProvider {
+			name,
+			dependencies: dependencies.to_vec(),
+			create,
+		});
+		self
+	}
+
+	/// Validate the registered providers and compute the order in which they are created.
+	///
+	/// Providers without any ordering constraint between them keep their registration order.
+	pub fn build(self) -> Result<InherentDataProviderRegistry<Block, ExtraArgs>, RegistryError> {
+		let mut indices = HashMap::with_capacity(self.providers.len());
+		for (index, provider) in self.providers.iter().enumerate() {
+			if indices.insert(provider.name, index).is_some() {
+				return Err(RegistryError::DuplicateProvider(provider.name))
+			}
+		}
+
+		let mut pending_dependencies = Vec::with_capacity(self.providers.len());
+		let mut dependents = vec![Vec::new(); self.providers.len()];
+		for (index, provider) in self.providers.iter().enumerate() {
+			for dependency in &provider.dependencies {
+				let dependency_index =
+					*indices.get(dependency).ok_or(RegistryError::UnknownDependency {
+						provider: provider.name,
+						dependency: *dependency,
+					})?;
+				dependents[dependency_index].push(index);
+			}
+			pending_dependencies.push(provider.dependencies.len());
+		}
+
+		let mut order = Vec::with_capacity(self.providers.len());
+		let mut created = vec![false; self.providers.len()];
+		while order.len() < self.providers.len() {
+			let Some(next) =
+				(0..self.providers.len()).find(|i| !created[*i] && pending_dependencies[*i] == 0)
+			else {
+				let cycle = (0..self.providers.len())
+					.filter(|i| !created[*i])
+					.map(|i| self.providers[i].name)
+					.collect();
+				return Err(RegistryError::DependencyCycle(cycle))
+			};
+
+			created[next] = true;
+			order.push(next);
+			for dependent in &dependents[next] {
+				pending_dependencies[*dependent] -= 1;
+			}
+		}
+
+		let mut providers = self.providers.into_iter().map(Some).collect::<Vec<_>>();
+		let providers = order
+			.into_iter()
+			.map(|index| providers[index].take().expect("every index is visited once; qed"))
+			.collect();
+
+		Ok(InherentDataProviderRegistry { providers })
+	}
+}
+
+/// A validated set of inherent data providers, created in dependency order.
+///
+/// Implements [`CreateInherentDataProviders`], so it can be passed to the consensus engines
+/// instead of a closure.
+pub struct InherentDataProviderRegistry<Block: BlockT, ExtraArgs> {
+	providers: Vec<RegisteredProvider<Block, ExtraArgs>>,
+}
+
+impl<Block, ExtraArgs> InherentDataProviderRegistry<Block, ExtraArgs>
+where
+	Block: BlockT,
+	ExtraArgs: Clone + Send + 'static,
+{
+	/// Start building a new registry.
+	pub fn builder() -> InherentDataProviderRegistryBuilder<Block, ExtraArgs> {
+		InherentDataProviderRegistryBuilder::new()
+	}
+
+	/// The names of the registered providers in the order they are created.
+	pub fn creation_order(&self) -> Vec<ProviderName> {
+		self.providers.iter().map(|provider| provider.name).collect()
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, ExtraArgs> CreateInherentDataProviders<Block, ExtraArgs>
+	for InherentDataProviderRegistry<Block, ExtraArgs>
+where
+	Block: BlockT,
+	ExtraArgs: Clone + Send + 'static,
+{
+	type InherentDataProviders = RegisteredInherentDataProviders;
+
+	async fn create_inherent_data_providers(
+		&self,
+		parent: Block::Hash,
+		extra_args: ExtraArgs,
+	) -> Result<Self::InherentDataProviders, Box<dyn std::error::Error + Send + Sync>> {
+		let mut inherent_data = InherentData::new();
+		let mut providers = Vec::with_capacity(self.providers.len());
+
+		for provider in &self.providers {
+			let idp = (provider.create)(parent, extra_args.clone(), inherent_data.clone())
+				.await
+				.map_err(|e| {
+					format!("Failed to create inherent data provider `{}`: {}", provider.name, e)
+				})?;
+
+			idp.provide_inherent_data(&mut inherent_data).await.map_err(|e| {
+				format!("Inherent data provider `{}` failed to provide data: {}", provider.name, e)
+			})?;
+
+			providers.push((provider.name, idp));
+		}
+
+		Ok(RegisteredInherentDataProviders { providers, inherent_data })
+	}
+}
+
+/// The inherent data providers created by an [`InherentDataProviderRegistry`].
+pub struct RegisteredInherentDataProviders {
+	providers: Vec<(ProviderName, Box<dyn InherentDataProvider>)>,
+	inherent_data: InherentData,
+}
+
+impl RegisteredInherentDataProviders {
+	/// The inherent data provided by all the providers while they were being created.
+	pub fn inherent_data(&self) -> &InherentData {
+		&self.inherent_data
+	}
+
+	/// Returns the provider registered under the given `name`.
+	pub fn provider(&self, name: ProviderName) -> Option<&dyn InherentDataProvider> {
+		self.providers.iter().find(|(n, _)| *n == name).map(|(_, idp)| &**idp)
+	}
+}
+
+#[async_trait::async_trait]
+impl InherentDataProvider for RegisteredInherentDataProviders {
+	async fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), Error> {
+		for (_, idp) in &self.providers {
+			idp.provide_inherent_data(inherent_data).await?;
+		}
+
+		Ok(())
+	}
+
+	async fn try_handle_error(
+		&self,
+		identifier: &crate::InherentIdentifier,
+		error: &[u8],
+	) -> Option<Result<(), Error>> {
+		for (_, idp) in &self.providers {
+			if let Some(r) = idp.try_handle_error(identifier, error).await {
+				return Some(r)
+			}
+		}
+
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::InherentIdentifier;
+	use sp_runtime::testing::{MockCallU64, TestXt};
+
+	type Block = sp_runtime::testing::Block<TestXt<MockCallU64, ()>>;
+
+	const TIMESTAMP: InherentIdentifier = *b"testtime";
+	const SLOT: InherentIdentifier = *b"testslot";
+
+	struct Provider(InherentIdentifier, u64);
+
+	#[async_trait::async_trait]
+	impl InherentDataProvider for Provider {
+		async fn provide_inherent_data(&self, data: &mut InherentData) -> Result<(), Error> {
+			data.put_data(self.0, &self.1)
+		}
+
+		async fn try_handle_error(
+			&self,
+			_: &InherentIdentifier,
+			_: &[u8],
+		) -> Option<Result<(), Error>> {
+			None
+		}
+	}
+
+	fn create(
+		identifier: InherentIdentifier,
+		value: u64,
+	) -> impl Fn(
+		<Block as BlockT>::Hash,
+		(),
+		InherentData,
+	) -> futures::future::Ready<Result<Provider, Box<dyn std::error::Error + Send + Sync>>> {
+		move |_, (), _| futures::future::ready(Ok(Provider(identifier, value)))
+	}
+
+	#[test]
+	fn providers_are_created_in_dependency_order() {
+		let registry = InherentDataProviderRegistry::<Block, ()>::builder()
+			.register("slot", &["timestamp"], |_, (), data: InherentData| async move {
+				let timestamp = data.get_data::<u64>(&TIMESTAMP)?.ok_or("timestamp missing")?;
+				Ok::<_, Box<dyn std::error::Error + Send + Sync>>(Provider(SLOT, timestamp / 6))
+			})
+			.register("timestamp", &[], create(TIMESTAMP, 60))
+			.build()
+			.unwrap();
+
+		assert_eq!(registry.creation_order(), vec!["timestamp", "slot"]);
+
+		let providers = futures::executor::block_on(
+			registry.create_inherent_data_providers(Default::default(), ()),
+		)
+		.unwrap();
+		let data = futures::executor::block_on(providers.create_inherent_data()).unwrap();
+
+		assert_eq!(data.get_data::<u64>(&SLOT).unwrap(), Some(10));
+		assert_eq!(providers.inherent_data().get_data::<u64>(&TIMESTAMP).unwrap(), Some(60));
+		assert!(providers.provider("slot").is_some());
+	}
+
+	#[test]
+	fn unconstrained_providers_keep_registration_order() {
+		let registry = InherentDataProviderRegistry::<Block, ()>::builder()
+			.register("b", &[], create(*b"testinhb", 0))
+			.register("a", &[], create(*b"testinha", 0))
+			.register("c", &["a"], create(*b"testinhc", 0))
+			.build()
+			.unwrap();
+
+		assert_eq!(registry.creation_order(), vec!["b", "a", "c"]);
+	}
+
+	#[test]
+	fn misconfiguration_is_detected() {
+		let duplicate = InherentDataProviderRegistry::<Block, ()>::builder()
+			.register("timestamp", &[], create(TIMESTAMP, 0))
+			.register("timestamp", &[], create(TIMESTAMP, 0))
+			.build();
+		assert_eq!(duplicate.err(), Some(RegistryError::DuplicateProvider("timestamp")));
+
+		let unknown = InherentDataProviderRegistry::<Block, ()>::builder()
+			.register("slot", &["timestamp"], create(SLOT, 0))
+			.build();
+		assert_eq!(
+			unknown.err(),
+			Some(RegistryError::UnknownDependency { provider: "slot", dependency: "timestamp" })
+		);
+
+		let cycle = InherentDataProviderRegistry::<Block, ()>::builder()
+			.register("timestamp", &[], create(TIMESTAMP, 0))
+			.register("a", &["b"], create(*b"testinha", 0))
+			.register("b", &["a", "timestamp"], create(*b"testinhb", 0))
+			.build();
+		assert_eq!(cycle.err(), Some(RegistryError::DependencyCycle(vec!["a", "b"])));
+	}
+}
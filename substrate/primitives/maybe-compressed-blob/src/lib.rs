@@ -29,6 +29,9 @@ use std::{
 // This differs from the WASM magic bytes, so real WASM blobs will not have this prefix.
 const ZSTD_PREFIX: [u8; 8] = [82, 188, 83, 118, 70, 219, 142, 5];
 
+/// The zstd compression level used by [`compress`].
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
 /// A recommendation for the bomb limit for code blobs.
 ///
 /// This may be adjusted upwards in the future, but is set much higher than the
@@ -70,10 +73,15 @@ fn decompress_zstd(blob: &[u8], bomb_limit: usize) -> Result<Vec<u8>, Error> {
 	read_from_decoder(decoder, blob.len(), bomb_limit)
 }
 
+/// Whether the blob indicates that it is compressed, i.e. [`decompress`] would decompress it.
+pub fn is_compressed(blob: &[u8]) -> bool {
+	blob.starts_with(&ZSTD_PREFIX)
+}
+
 /// Decode a blob, if it indicates that it is compressed. Provide a `bomb_limit`, which
 /// is the limit of bytes which should be decompressed from the blob.
 pub fn decompress(blob: &[u8], bomb_limit: usize) -> Result<Cow<[u8]>, Error> {
-	if blob.starts_with(&ZSTD_PREFIX) {
+	if is_compressed(blob) {
 		decompress_zstd(&blob[ZSTD_PREFIX.len()..], bomb_limit).map(Into::into)
 	} else {
 		Ok(blob.into())
@@ -84,6 +92,14 @@ pub fn decompress(blob: &[u8], bomb_limit: usize) -> Result<Cow<[u8]>, Error> {
 /// this will not compress the blob, as the decoder will not be able to be
 /// able to differentiate it from a compression bomb.
 pub fn compress(blob: &[u8], bomb_limit: usize) -> Option<Vec<u8>> {
+	compress_with_level(blob, bomb_limit, DEFAULT_COMPRESSION_LEVEL)
+}
+
+/// Like [`compress`], but using the given zstd compression `level`.
+///
+/// Higher levels produce smaller blobs at the cost of more CPU time for compressing. The level
+/// makes no difference for decompressing.
+pub fn compress_with_level(blob: &[u8], bomb_limit: usize, level: i32) -> Option<Vec<u8>> {
 	if blob.len() > bomb_limit {
 		return None
 	}
@@ -91,7 +107,7 @@ pub fn compress(blob: &[u8], bomb_limit: usize) -> Option<Vec<u8>> {
 	let mut buf = ZSTD_PREFIX.to_vec();
 
 	{
-		let mut v = zstd::Encoder::new(&mut buf, 3).ok()?.auto_finish();
+		let mut v = zstd::Encoder::new(&mut buf, level).ok()?.auto_finish();
 		v.write_all(blob).ok()?;
 	}
 
@@ -123,6 +139,16 @@ mod tests {
 		assert_eq!(&decompress(&compressed, BOMB_LIMIT).unwrap()[..], &v[..])
 	}
 
+	#[test]
+	fn compression_level_does_not_matter_for_decompression() {
+		let v = (0..BOMB_LIMIT as u8).collect::<Vec<_>>();
+
+		for level in [1, DEFAULT_COMPRESSION_LEVEL, 19] {
+			let compressed = compress_with_level(&v, BOMB_LIMIT, level).unwrap();
+			assert_eq!(&decompress(&compressed, BOMB_LIMIT).unwrap()[..], &v[..]);
+		}
+	}
+
 	#[test]
 	fn decompresses_only_when_magic() {
 		let v = vec![0; BOMB_LIMIT + 1];
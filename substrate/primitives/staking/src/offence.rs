@@ -156,6 +156,19 @@ pub trait OnOffenceHandler<Reporter, Offender, Res> {
 		slash_fraction: &[Perbill],
 		session: SessionIndex,
 	) -> Res;
+
+	/// Same as [`Self::on_offence`], for an offence of the given `kind`.
+	///
+	/// Handlers treating offences differently depending on their kind should implement this.
+	/// Defaults to [`Self::on_offence`].
+	fn on_offence_of_kind(
+		_kind: &Kind,
+		offenders: &[OffenceDetails<Reporter, Offender>],
+		slash_fraction: &[Perbill],
+		session: SessionIndex,
+	) -> Res {
+		Self::on_offence(offenders, slash_fraction, session)
+	}
 }
 
 impl<Reporter, Offender, Res: Default> OnOffenceHandler<Reporter, Offender, Res> for () {
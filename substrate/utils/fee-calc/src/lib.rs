@@ -0,0 +1,269 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Off-chain computation of transaction fees.
+//!
+//! [`FeeCalculator`] reproduces the arithmetic of `pallet-transaction-payment` without access to
+//! the runtime storage: the fee multiplier is an explicit input instead of being read from
+//! `NextFeeMultiplier`. Given the same block weights, weight-to-fee and length-to-fee conversions,
+//! it computes the exact fee the pallet charges, so tests and tooling can predict fees without
+//! duplicating the formula:
+//!
+//! ```text
+//! inclusion_fee = base_fee + len_fee + multiplier * weight_fee
+//! final_fee = inclusion_fee + tip
+//! ```
+//!
+//! The weight of a transaction is the weight of its call plus the weight of its transaction
+//! extensions, i.e. `DispatchInfo::total_weight`.
+
+#![warn(missing_docs)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::{DispatchInfo, Pays, PostDispatchInfo},
+	weights::{Weight, WeightToFee},
+};
+use frame_system::limits::BlockWeights;
+use pallet_transaction_payment::{FeeDetails, InclusionFee, Multiplier};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Get},
+	FixedPointNumber, FixedPointOperand,
+};
+
+/// The balance type of the fees computed by a [`FeeCalculator`] using `W` as weight-to-fee.
+pub type BalanceOf<W> = <W as WeightToFee>::Balance;
+
+/// Computes the fees of transactions for a given fee configuration.
+///
+/// `W` converts weight to fee and `L` converts length to fee, like the `WeightToFee` and
+/// `LengthToFee` of `pallet_transaction_payment::Config`.
+pub struct FeeCalculator<W, L> {
+	block_weights: BlockWeights,
+	_phantom: PhantomData<(W, L)>,
+}
+
+impl<W, L> FeeCalculator<W, L>
+where
+	W: WeightToFee,
+	L: WeightToFee<Balance = BalanceOf<W>>,
+	BalanceOf<W>: FixedPointOperand + AtLeast32BitUnsigned,
+{
+	/// Create a calculator from the block weights of a runtime.
+	///
+	/// The block weights define the base weight of each dispatch class and the maximum weight
+	/// the weight fee is computed for.
+	pub fn new(block_weights: BlockWeights) -> Self {
+		Self { block_weights, _phantom: PhantomData }
+	}
+
+	/// The fee configuration of runtime `T`.
+	pub fn of_runtime<T>() -> Self
+	where
+		T: pallet_transaction_payment::Config<WeightToFee = W, LengthToFee = L>,
+	{
+		Self::new(T::BlockWeights::get())
+	}
+
+	/// The block weights the fees are computed with.
+	pub fn block_weights(&self) -> &BlockWeights {
+		&self.block_weights
+	}
+
+	/// The length portion of a fee.
+	pub fn length_to_fee(&self, len: u32) -> BalanceOf<W> {
+		L::weight_to_fee(&Weight::from_parts(len as u64, 0))
+	}
+
+	/// The unadjusted weight portion of a fee, with `weight` capped by the maximum block weight.
+	pub fn weight_to_fee(&self, weight: Weight) -> BalanceOf<W> {
+		W::weight_to_fee(&weight.min(self.block_weights.max_block))
+	}
+
+	/// The breakdown of the fee of a transaction of encoded length `len`, before dispatch.
+	pub fn fee_details(
+		&self,
+		info: &DispatchInfo,
+		len: u32,
+		tip: BalanceOf<W>,
+		multiplier: Multiplier,
+	) -> FeeDetails<BalanceOf<W>> {
+		self.fee_details_raw(info, info.total_weight(), info.pays_fee, len, tip, multiplier)
+	}
+
+	/// The fee of a transaction of encoded length `len`, tip included, before dispatch.
+	pub fn compute_fee(
+		&self,
+		info: &DispatchInfo,
+		len: u32,
+		tip: BalanceOf<W>,
+		multiplier: Multiplier,
+	) -> BalanceOf<W> {
+		self.fee_details(info, len, tip, multiplier).final_fee()
+	}
+
+	/// The breakdown of the fee of a transaction of encoded length `len`, after dispatch.
+	///
+	/// The weight and `Pays` reported in `post_info` are taken into account, like the refund of
+	/// `pallet-transaction-payment` does.
+	pub fn actual_fee_details(
+		&self,
+		info: &DispatchInfo,
+		post_info: &PostDispatchInfo,
+		len: u32,
+		tip: BalanceOf<W>,
+		multiplier: Multiplier,
+	) -> FeeDetails<BalanceOf<W>> {
+		self.fee_details_raw(
+			info,
+			post_info.calc_actual_weight(info),
+			post_info.pays_fee(info),
+			len,
+			tip,
+			multiplier,
+		)
+	}
+
+	/// The fee of a transaction of encoded length `len`, tip included, after dispatch.
+	pub fn compute_actual_fee(
+		&self,
+		info: &DispatchInfo,
+		post_info: &PostDispatchInfo,
+		len: u32,
+		tip: BalanceOf<W>,
+		multiplier: Multiplier,
+	) -> BalanceOf<W> {
+		self.actual_fee_details(info, post_info, len, tip, multiplier).final_fee()
+	}
+
+	fn fee_details_raw(
+		&self,
+		info: &DispatchInfo,
+		weight: Weight,
+		pays_fee: Pays,
+		len: u32,
+		tip: BalanceOf<W>,
+		multiplier: Multiplier,
+	) -> FeeDetails<BalanceOf<W>> {
+		if pays_fee == Pays::Yes {
+			let adjusted_weight_fee = multiplier.saturating_mul_int(self.weight_to_fee(weight));
+			let len_fee = self.length_to_fee(len);
+			let base_fee = self.weight_to_fee(self.block_weights.get(info.class).base_extrinsic);
+			FeeDetails {
+				inclusion_fee: Some(InclusionFee { base_fee, len_fee, adjusted_weight_fee }),
+				tip,
+			}
+		} else {
+			FeeDetails { inclusion_fee: None, tip }
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{
+		dispatch::{DispatchClass, PerDispatchClass},
+		traits::ConstU64,
+		weights::{ConstantMultiplier, IdentityFee},
+	};
+	use frame_system::limits::WeightsPerClass;
+
+	type Calculator = FeeCalculator<IdentityFee<u64>, ConstantMultiplier<u64, ConstU64<2>>>;
+
+	fn calculator() -> Calculator {
+		Calculator::new(BlockWeights {
+			base_block: Weight::zero(),
+			max_block: Weight::from_parts(1_000, u64::MAX),
+			per_class: PerDispatchClass::new(|class| WeightsPerClass {
+				base_extrinsic: Weight::from_parts(
+					if class == DispatchClass::Operational { 7 } else { 5 },
+					0,
+				),
+				max_extrinsic: None,
+				max_total: None,
+				reserved: None,
+			}),
+		})
+	}
+
+	fn info(call_weight: u64, extension_weight: u64) -> DispatchInfo {
+		DispatchInfo {
+			call_weight: Weight::from_parts(call_weight, 0),
+			extension_weight: Weight::from_parts(extension_weight, 0),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn fee_includes_all_components() {
+		let calculator = calculator();
+		let multiplier = Multiplier::saturating_from_rational(3, 2);
+
+		assert_eq!(
+			calculator.fee_details(&info(100, 10), 10, 3, multiplier),
+			FeeDetails {
+				inclusion_fee: Some(InclusionFee {
+					base_fee: 5,
+					len_fee: 20,
+					adjusted_weight_fee: 165,
+				}),
+				tip: 3,
+			}
+		);
+		assert_eq!(calculator.compute_fee(&info(100, 10), 10, 3, multiplier), 5 + 20 + 165 + 3);
+
+		// the base fee depends on the class.
+		let operational = DispatchInfo { class: DispatchClass::Operational, ..info(100, 10) };
+		assert_eq!(calculator.compute_fee(&operational, 10, 3, multiplier), 7 + 20 + 165 + 3);
+
+		// the weight fee is computed for at most a full block.
+		assert_eq!(
+			calculator.compute_fee(
+				&info(u64::MAX, 0),
+				0,
+				0,
+				Multiplier::saturating_from_integer(1)
+			),
+			5 + 1_000
+		);
+
+		// only the tip is paid by free transactions.
+		let free = DispatchInfo { pays_fee: Pays::No, ..info(100, 10) };
+		assert_eq!(calculator.compute_fee(&free, 10, 3, multiplier), 3);
+	}
+
+	#[test]
+	fn actual_fee_accounts_for_refunds() {
+		let calculator = calculator();
+		let multiplier = Multiplier::saturating_from_rational(3, 2);
+
+		// the actual weight includes the extension weight: 50 units of call weight and 10 units of
+		// extension weight.
+		let post_info = PostDispatchInfo {
+			actual_weight: Some(Weight::from_parts(60, 0)),
+			..Default::default()
+		};
+		assert_eq!(
+			calculator.compute_actual_fee(&info(100, 10), &post_info, 10, 3, multiplier),
+			5 + 20 + 90 + 3
+		);
+
+		let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::No };
+		assert_eq!(calculator.compute_actual_fee(&info(100, 10), &post_info, 10, 3, multiplier), 3);
+	}
+}
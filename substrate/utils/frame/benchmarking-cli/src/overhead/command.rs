@@ -25,9 +25,9 @@ use crate::{
 	},
 	overhead::{
 		command::ChainType::{Parachain, Relaychain, Unknown},
-		fake_runtime_api,
+		extensions, fake_runtime_api,
 		remark_builder::SubstrateRemarkBuilder,
-		template::TemplateData,
+		template::{ExtensionTemplateData, TemplateData},
 	},
 	shared::{
 		genesis_state,
@@ -146,6 +146,23 @@ pub struct OverheadParams {
 	/// a para-id and patch the state accordingly.
 	#[arg(long)]
 	pub para_id: Option<u32>,
+
+	/// Also measure the weight of each transaction extension.
+	///
+	/// Runs the benchmarks of the `--extension-pallets` through the `Benchmark` runtime API and
+	/// writes one weight constant per benchmark to `extension_weights.rs`. Requires a runtime
+	/// built with the `runtime-benchmarks` feature.
+	#[arg(long)]
+	pub extension_weights: bool,
+
+	/// The pallets whose benchmarks measure transaction extensions.
+	#[arg(
+		long,
+		value_delimiter = ',',
+		default_values = ["frame_system_extensions", "pallet_transaction_payment"],
+		requires = "extension_weights"
+	)]
+	pub extension_pallets: Vec<String>,
 }
 
 /// How the genesis state for benchmarking should be built.
@@ -529,7 +546,8 @@ impl OverheadCmd {
 	/// Measure the per-block and per-extrinsic execution overhead.
 	///
 	/// Writes the results to console and into two instances of the
-	/// `weights.hbs` template, one for each benchmark.
+	/// `weights.hbs` template, one for each benchmark. With `--extension-weights`,
+	/// the weights of the transaction extensions are written to `extension_weights.rs`.
 	pub fn run<Block, C>(
 		&self,
 		chain_name: String,
@@ -552,7 +570,7 @@ impl OverheadCmd {
 		}
 
 		let bench = Benchmark::new(
-			client,
+			client.clone(),
 			self.params.bench.clone(),
 			inherent_data,
 			digest_items,
@@ -585,6 +603,13 @@ impl OverheadCmd {
 			)?;
 			template.write(&self.params.weight.weight_path)?;
 		}
+		// per-extension weights
+		if self.params.extension_weights {
+			let at = client.usage_info().chain.best_hash;
+			let weights = extensions::bench_extensions(&*client, at, &self.params)?;
+			let template = ExtensionTemplateData::new(&chain_name, &self.params, weights)?;
+			template.write(&self.params.weight.weight_path)?;
+		}
 
 		Ok(())
 	}
@@ -779,4 +804,37 @@ mod tests {
 		]);
 		Ok(())
 	}
+
+	#[test]
+	fn test_cli_extension_pallets() -> Result<(), clap::Error> {
+		let cmd = OverheadCmd::try_parse_from(&["test", "--chain", "spec", "--extension-weights"])?;
+		assert!(cmd.params.extension_weights);
+		assert_eq!(
+			cmd.params.extension_pallets,
+			vec!["frame_system_extensions", "pallet_transaction_payment"]
+		);
+
+		let cmd = OverheadCmd::try_parse_from(&[
+			"test",
+			"--chain",
+			"spec",
+			"--extension-weights",
+			"--extension-pallets",
+			"frame_system_extensions,pallet_asset_tx_payment",
+		])?;
+		assert_eq!(
+			cmd.params.extension_pallets,
+			vec!["frame_system_extensions", "pallet_asset_tx_payment"]
+		);
+
+		assert!(OverheadCmd::try_parse_from(&[
+			"test",
+			"--chain",
+			"spec",
+			"--extension-pallets",
+			"frame_system_extensions"
+		])
+		.is_err());
+		Ok(())
+	}
 }
@@ -0,0 +1,224 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Measures the weight of transaction extensions.
+//!
+//! The per-extrinsic overhead measured by [`super::OverheadCmd`] covers a NO-OP extrinsic, but the
+//! weight of its transaction extensions is part of the `extension_weight` of every transaction and
+//! charged on top of it. Extensions are measured with the benchmarks that runtimes already declare
+//! for them, like the ones of `frame_system_extensions` and `pallet_transaction_payment`, executed
+//! through the `Benchmark` runtime API.
+
+use crate::{
+	overhead::command::OverheadParams,
+	shared::{StatSelect, Stats},
+};
+use codec::{Decode, Encode};
+use frame_benchmarking::{
+	Benchmark as BenchmarkApi, BenchmarkBatch, BenchmarkConfig, BenchmarkList,
+};
+use frame_support::traits::StorageInfo;
+use log::info;
+use sc_cli::{Error, Result};
+use serde::Serialize;
+use sp_api::{CallApiAt, CallApiAtParams, ProofRecorder, RuntimeApiInfo};
+use sp_blockchain::Error::RuntimeApiError;
+use sp_core::traits::CallContext;
+use sp_externalities::Extensions;
+use sp_runtime::traits::Block as BlockT;
+use sp_trie::proof_size_extension::ProofSizeExt;
+use std::cell::RefCell;
+
+/// The measured weight of a single transaction extension.
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct ExtensionWeight {
+	/// Name of the generated constant, without the `Weight` suffix.
+	pub(crate) name: String,
+	/// Pallet declaring the benchmark of the extension.
+	pub(crate) pallet: String,
+	/// Name of the benchmark of the extension.
+	pub(crate) benchmark: String,
+	/// Stats about the execution time in nanoseconds.
+	pub(crate) stats: Stats,
+	/// The resulting ref time weight.
+	pub(crate) ref_time: u64,
+	/// The size of the proof weight, as recorded while executing the benchmark.
+	pub(crate) proof_size: u64,
+}
+
+/// Measure the weight of all transaction extensions benchmarked by `params.extension_pallets`.
+///
+/// Every benchmark of these pallets is considered to measure one extension. Benchmarks with
+/// components are measured at the highest value of each component.
+pub(crate) fn bench_extensions<Block, C>(
+	client: &C,
+	at: Block::Hash,
+	params: &OverheadParams,
+) -> Result<Vec<ExtensionWeight>>
+where
+	Block: BlockT,
+	C: CallApiAt<Block>,
+{
+	let api_version = client
+		.runtime_version_at(at)
+		.map_err(|e| Error::Client(RuntimeApiError(e)))?
+		.api_version(&<dyn BenchmarkApi<Block> as RuntimeApiInfo>::ID)
+		.ok_or(
+			"The runtime does not implement the `Benchmark` runtime API, was it built with the \
+			`runtime-benchmarks` feature?",
+		)?;
+	if api_version < 2 {
+		return Err(
+			format!("Version {api_version} of the `Benchmark` runtime API is not supported").into()
+		)
+	}
+
+	let (list, _): (Vec<BenchmarkList>, Vec<StorageInfo>) =
+		call(client, at, "Benchmark_benchmark_metadata", false.encode())?;
+
+	let mut weights = Vec::new();
+	for pallet in &params.extension_pallets {
+		let Some(entry) = list.iter().find(|entry| entry.pallet == pallet.as_bytes()) else {
+			return Err(format!("The runtime has no benchmarks for pallet `{pallet}`").into())
+		};
+
+		for benchmark in &entry.benchmarks {
+			let benchmark_name = String::from_utf8_lossy(&benchmark.name).into_owned();
+			info!("Measuring transaction extension {pallet}::{benchmark_name}");
+
+			let config = |verify: bool, internal_repeats: u32| BenchmarkConfig {
+				pallet: entry.pallet.clone(),
+				instance: entry.instance.clone(),
+				benchmark: benchmark.name.clone(),
+				selected_components: benchmark
+					.components
+					.iter()
+					.map(|(param, _, high)| (*param, *high))
+					.collect(),
+				verify,
+				internal_repeats,
+			};
+			let dispatch = |config: BenchmarkConfig| -> Result<Vec<BenchmarkBatch>> {
+				call::<_, _, std::result::Result<Vec<BenchmarkBatch>, String>>(
+					client,
+					at,
+					"Benchmark_dispatch_benchmark",
+					config.encode(),
+				)?
+				.map_err(|e| format!("Benchmark {pallet}::{benchmark_name} failed: {e}").into())
+			};
+
+			// Verify the benchmark once, its timing is distorted by the verification.
+			dispatch(config(true, 1))?;
+			if params.bench.warmup > 0 {
+				dispatch(config(false, params.bench.warmup))?;
+			}
+			let results = dispatch(config(false, params.bench.repeat))?
+				.into_iter()
+				.flat_map(|batch| batch.results)
+				.collect::<Vec<_>>();
+
+			let record = results.iter().map(|r| r.extrinsic_time as u64).collect::<Vec<_>>();
+			let stats = Stats::new(&record)?;
+			let proof_size = results.iter().map(|r| r.proof_size as u64).max().unwrap_or_default();
+			info!("Transaction extension {pallet}::{benchmark_name} [ns]:\n{:?}", stats);
+
+			weights.push(ExtensionWeight {
+				name: constant_name(&benchmark_name),
+				pallet: pallet.clone(),
+				ref_time: params.weight.calc_weight(&stats)?,
+				benchmark: benchmark_name,
+				stats,
+				proof_size,
+			});
+		}
+	}
+
+	if let Some(duplicate) = weights
+		.iter()
+		.enumerate()
+		.find_map(|(i, w)| weights[..i].iter().find(|other| other.name == w.name).map(|_| &w.name))
+	{
+		return Err(format!("Several extension benchmarks are named like `{duplicate}`").into())
+	}
+
+	// Report the most expensive extensions first.
+	weights.sort_by_key(|w| std::cmp::Reverse(w.stats.select(StatSelect::Average)));
+	Ok(weights)
+}
+
+/// Call `function` of the runtime at block `at` and decode its result.
+///
+/// The storage proof of the call is recorded, the benchmarks measure their proof size through the
+/// [`ProofSizeExt`] on top of it. Every call starts with an empty proof.
+fn call<Block: BlockT, C: CallApiAt<Block>, R: Decode>(
+	client: &C,
+	at: Block::Hash,
+	function: &'static str,
+	arguments: Vec<u8>,
+) -> Result<R> {
+	let recorder = ProofRecorder::<Block>::default();
+	let mut extensions = Extensions::default();
+	extensions.register(ProofSizeExt::new(recorder.clone()));
+
+	let result = client
+		.call_api_at(CallApiAtParams {
+			at,
+			function,
+			arguments,
+			overlayed_changes: &Default::default(),
+			call_context: CallContext::Offchain,
+			recorder: &Some(recorder),
+			extensions: &RefCell::new(extensions),
+		})
+		.map_err(|e| Error::Client(RuntimeApiError(e)))?;
+
+	R::decode(&mut &result[..])
+		.map_err(|e| format!("Could not decode the result of `{function}`: {e}").into())
+}
+
+/// The name of the weight constant of the extension measured by `benchmark`.
+///
+/// Converts the snake case benchmark name to upper camel case, e.g. `check_nonce` to `CheckNonce`.
+fn constant_name(benchmark: &str) -> String {
+	benchmark
+		.split('_')
+		.filter(|part| !part.is_empty())
+		.map(|part| {
+			let mut chars = part.chars();
+			chars
+				.next()
+				.map(|first| first.to_uppercase().chain(chars).collect::<String>())
+				.unwrap_or_default()
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::constant_name;
+
+	#[test]
+	fn constant_names_are_upper_camel_case() {
+		assert_eq!(constant_name("check_nonce"), "CheckNonce");
+		assert_eq!(
+			constant_name("check_mortality_mortal_transaction"),
+			"CheckMortalityMortalTransaction"
+		);
+		assert_eq!(constant_name("charge_transaction_payment"), "ChargeTransactionPayment");
+		assert_eq!(constant_name("_weight__reclaim"), "WeightReclaim");
+	}
+}
@@ -18,6 +18,7 @@
 pub mod command;
 pub mod template;
 
+mod extensions;
 mod fake_runtime_api;
 pub mod remark_builder;
 
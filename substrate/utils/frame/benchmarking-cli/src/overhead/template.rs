@@ -17,6 +17,9 @@
 
 //! Converts a benchmark result into [`TemplateData`] and writes
 //! it into the `weights.hbs` template.
+//!
+//! The weights of the transaction extensions are converted into [`ExtensionTemplateData`] and
+//! written into the `extension_weights.hbs` template.
 
 use sc_cli::Result;
 
@@ -26,12 +29,16 @@ use serde::Serialize;
 use std::{env, fs, path::PathBuf};
 
 use crate::{
-	overhead::command::{BenchmarkType, OverheadParams},
+	overhead::{
+		command::{BenchmarkType, OverheadParams},
+		extensions::ExtensionWeight,
+	},
 	shared::{Stats, UnderscoreHelper},
 };
 
 static VERSION: &str = env!("CARGO_PKG_VERSION");
 static TEMPLATE: &str = include_str!("./weights.hbs");
+static EXTENSION_TEMPLATE: &str = include_str!("./extension_weights.hbs");
 
 /// Data consumed by Handlebar to fill out the `weights.hbs` template.
 #[derive(Serialize, Debug, Clone)]
@@ -74,12 +81,7 @@ impl TemplateData {
 		proof_size: u64,
 	) -> Result<Self> {
 		let ref_time = params.weight.calc_weight(stats)?;
-		let header = params
-			.header
-			.as_ref()
-			.map(|p| std::fs::read_to_string(p))
-			.transpose()?
-			.unwrap_or_default();
+		let header = read_header(params)?;
 
 		Ok(TemplateData {
 			short_name: t.short_name().into(),
@@ -101,28 +103,92 @@ impl TemplateData {
 	/// Fill out the `weights.hbs` HBS template with its own data.
 	/// Writes the result to `path` which can be a directory or a file.
 	pub fn write(&self, path: &Option<PathBuf>) -> Result<()> {
-		let mut handlebars = Handlebars::new();
-		// Format large integers with underscores.
-		handlebars.register_helper("underscore", Box::new(UnderscoreHelper));
-		// Don't HTML escape any characters.
-		handlebars.register_escape_fn(|s| -> String { s.to_string() });
-
-		let out_path = self.build_path(path)?;
-		let mut fd = fs::File::create(&out_path)?;
-		info!("Writing weights to {:?}", fs::canonicalize(&out_path)?);
-		handlebars
-			.render_template_to_write(TEMPLATE, &self, &mut fd)
-			.map_err(|e| format!("HBS template write: {:?}", e).into())
+		render(TEMPLATE, &self, build_path(path, &self.short_name)?)
 	}
+}
+
+/// Data consumed by Handlebar to fill out the `extension_weights.hbs` template.
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct ExtensionTemplateData {
+	/// Name of the runtime. Taken from the chain spec.
+	runtime_name: String,
+	/// Version of the benchmarking CLI used.
+	version: String,
+	/// Date that the template was filled out.
+	date: String,
+	/// Hostname of the machine that executed the benchmarks.
+	hostname: String,
+	/// CPU name of the machine that executed the benchmarks.
+	cpuname: String,
+	/// Header for the generated file.
+	header: String,
+	/// Command line arguments that were passed to the CLI.
+	args: Vec<String>,
+	/// Params of the executed command.
+	params: OverheadParams,
+	/// The measured weight of each extension.
+	extensions: Vec<ExtensionWeight>,
+}
+
+impl ExtensionTemplateData {
+	/// Returns a new [`Self`] from the given params.
+	pub(crate) fn new(
+		chain_name: &String,
+		params: &OverheadParams,
+		extensions: Vec<ExtensionWeight>,
+	) -> Result<Self> {
+		Ok(ExtensionTemplateData {
+			runtime_name: chain_name.to_owned(),
+			version: VERSION.into(),
+			date: chrono::Utc::now().format("%Y-%m-%d (Y/M/D)").to_string(),
+			hostname: params.hostinfo.hostname(),
+			cpuname: params.hostinfo.cpuname(),
+			header: read_header(params)?,
+			args: env::args().collect::<Vec<String>>(),
+			params: params.clone(),
+			extensions,
+		})
+	}
+
+	/// Fill out the `extension_weights.hbs` HBS template with its own data.
+	/// Writes the result to `path` which must be a directory.
+	pub fn write(&self, path: &Option<PathBuf>) -> Result<()> {
+		render(EXTENSION_TEMPLATE, &self, build_path(path, "extension")?)
+	}
+}
+
+/// Read the header for the generated file, if any.
+fn read_header(params: &OverheadParams) -> Result<String> {
+	Ok(params
+		.header
+		.as_ref()
+		.map(|p| std::fs::read_to_string(p))
+		.transpose()?
+		.unwrap_or_default())
+}
+
+/// Render `template` with `data` into the file at `out_path`.
+fn render(template: &str, data: &impl Serialize, out_path: PathBuf) -> Result<()> {
+	let mut handlebars = Handlebars::new();
+	// Format large integers with underscores.
+	handlebars.register_helper("underscore", Box::new(UnderscoreHelper));
+	// Don't HTML escape any characters.
+	handlebars.register_escape_fn(|s| -> String { s.to_string() });
+
+	let mut fd = fs::File::create(&out_path)?;
+	info!("Writing weights to {:?}", fs::canonicalize(&out_path)?);
+	handlebars
+		.render_template_to_write(template, data, &mut fd)
+		.map_err(|e| format!("HBS template write: {:?}", e).into())
+}
 
-	/// Build a path for the weight file.
-	fn build_path(&self, weight_out: &Option<PathBuf>) -> Result<PathBuf> {
-		let mut path = weight_out.clone().unwrap_or_else(|| PathBuf::from("."));
+/// Build a path for the weight file of the benchmark with `short_name`.
+fn build_path(weight_out: &Option<PathBuf>, short_name: &str) -> Result<PathBuf> {
+	let mut path = weight_out.clone().unwrap_or_else(|| PathBuf::from("."));
 
-		if !path.is_dir() {
-			return Err("Need directory as --weight-path".into())
-		}
-		path.push(format!("{}_weights.rs", self.short_name));
-		Ok(path)
+	if !path.is_dir() {
+		return Err("Need directory as --weight-path".into())
 	}
+	path.push(format!("{}_weights.rs", short_name));
+	Ok(path)
 }
@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use super::{
+	snapshot,
 	types::{ComponentRange, ComponentRangeMap},
 	writer, ListOutput, PalletCmd,
 };
@@ -42,13 +43,14 @@ use sp_core::{
 		testing::{TestOffchainExt, TestTransactionPoolExt},
 		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
 	},
-	traits::{CallContext, CodeExecutor, ReadRuntimeVersionExt, WrappedRuntimeCode},
+	traits::{CallContext, CodeExecutor, ReadRuntimeVersionExt, RuntimeCode, WrappedRuntimeCode},
 	Hasher,
 };
 use sp_externalities::Extensions;
 use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::traits::Hash;
 use sp_state_machine::StateMachine;
+use sp_storage::Storage;
 use sp_trie::{proof_size_extension::ProofSizeExt, recorder::Recorder};
 use sp_wasm_interface::HostFunctions;
 use std::{
@@ -273,24 +275,8 @@ impl PalletCmd {
 		let genesis_storage =
 			state_handler.build_storage::<SubstrateAndExtraHF<ExtraHostFunctions>>(None)?;
 
-		let cache_size = Some(self.database_cache_size as usize);
-		let state_with_tracking = BenchmarkingState::<Hasher>::new(
-			genesis_storage.clone(),
-			cache_size,
-			// Record proof size
-			true,
-			// Enable storage tracking
-			true,
-		)?;
-
-		let state_without_tracking = BenchmarkingState::<Hasher>::new(
-			genesis_storage,
-			cache_size,
-			// Proof recording depends on CLI settings
-			!self.disable_proof_recording,
-			// Do not enable storage tracking
-			false,
-		)?;
+		let (state_with_tracking, state_without_tracking) =
+			self.benchmarking_states::<Hasher>(genesis_storage)?;
 
 		let method =
 			execution_method_from_cli(self.wasm_method, self.wasmtime_instantiation_strategy);
@@ -359,17 +345,104 @@ impl PalletCmd {
 			return Ok(())
 		}
 
+		let pov_modes = Self::parse_pov_modes(&benchmarks_to_run)?;
+		let (batches, component_ranges) = self.run_benchmarks::<Hasher, ExtraHostFunctions>(
+			&benchmarks_to_run,
+			&state_with_tracking,
+			&state_without_tracking,
+			&executor,
+			&runtime_code,
+			benchmark_api_version,
+		)?;
+
+		let Some(snapshot_path) = &self.snapshot else {
+			return self.output(&batches, &storage_info, &component_ranges, pov_modes)
+		};
+
+		log::info!(target: LOG_TARGET, "Running the benchmarks against the snapshot {snapshot_path:?}");
+		let (snapshot_with_tracking, snapshot_without_tracking) =
+			self.benchmarking_states::<Hasher>(snapshot::load_snapshot(snapshot_path)?)?;
+		let (snapshot_batches, _) = self.run_benchmarks::<Hasher, ExtraHostFunctions>(
+			&benchmarks_to_run,
+			&snapshot_with_tracking,
+			&snapshot_without_tracking,
+			&executor,
+			&runtime_code,
+			benchmark_api_version,
+		)?;
+
+		self.output(&snapshot_batches, &storage_info, &component_ranges, pov_modes.clone())?;
+		if !self.quiet && !self.json_output {
+			self.print_snapshot_delta(
+				&batches,
+				&snapshot_batches,
+				&storage_info,
+				&component_ranges,
+				pov_modes,
+			)?;
+		}
+
+		Ok(())
+	}
+
+	/// Build the states to benchmark against from `storage`, with and without storage tracking.
+	fn benchmarking_states<Hasher>(
+		&self,
+		storage: Storage,
+	) -> Result<(BenchmarkingState<Hasher>, BenchmarkingState<Hasher>)>
+	where
+		Hasher: Hash,
+		<Hasher as Hash>::Output: DecodeWithMemTracking,
+	{
+		let cache_size = Some(self.database_cache_size as usize);
+		let state_with_tracking = BenchmarkingState::<Hasher>::new(
+			storage.clone(),
+			cache_size,
+			// Record proof size
+			true,
+			// Enable storage tracking
+			true,
+		)?;
+
+		let state_without_tracking = BenchmarkingState::<Hasher>::new(
+			storage,
+			cache_size,
+			// Proof recording depends on CLI settings
+			!self.disable_proof_recording,
+			// Do not enable storage tracking
+			false,
+		)?;
+
+		Ok((state_with_tracking, state_without_tracking))
+	}
+
+	/// Run the selected benchmarks against the given states.
+	///
+	/// Returns the combined results along with the component ranges of the benchmarks.
+	fn run_benchmarks<Hasher, ExtraHostFunctions>(
+		&self,
+		benchmarks_to_run: &[SelectedBenchmark],
+		state_with_tracking: &BenchmarkingState<Hasher>,
+		state_without_tracking: &BenchmarkingState<Hasher>,
+		executor: &WasmExecutor<SubstrateAndExtraHF<ExtraHostFunctions>>,
+		runtime_code: &RuntimeCode,
+		benchmark_api_version: u32,
+	) -> Result<(Vec<BenchmarkBatchSplitResults>, ComponentRangeMap)>
+	where
+		Hasher: Hash,
+		<Hasher as Hash>::Output: DecodeWithMemTracking,
+		ExtraHostFunctions: HostFunctions,
+	{
 		// Run the benchmarks
 		let mut batches = Vec::new();
 		let mut batches_db = Vec::new();
 		let mut timer = time::SystemTime::now();
 		// Maps (pallet, extrinsic) to its component ranges.
 		let mut component_ranges = HashMap::<(String, String), Vec<ComponentRange>>::new();
-		let pov_modes = Self::parse_pov_modes(&benchmarks_to_run)?;
 		let mut failed = Vec::<(String, String)>::new();
 
 		'outer: for (i, SelectedBenchmark { pallet, instance, extrinsic, components, .. }) in
-			benchmarks_to_run.clone().into_iter().enumerate()
+			benchmarks_to_run.iter().cloned().enumerate()
 		{
 			log::info!(
 				target: LOG_TARGET,
@@ -449,7 +522,7 @@ impl PalletCmd {
 
 				// First we run a verification
 				if !self.no_verify {
-					let state = &state_without_tracking;
+					let state = state_without_tracking;
 					// Don't use these results since verification code will add overhead.
 					let _batch: Vec<BenchmarkBatch> = match Self::exec_state_machine::<
 						std::result::Result<Vec<BenchmarkBatch>, String>,
@@ -459,11 +532,11 @@ impl PalletCmd {
 						StateMachine::new(
 							state,
 							&mut Default::default(),
-							&executor,
+							executor,
 							"Benchmark_dispatch_benchmark",
 							&params(true, 1),
 							&mut Self::build_extensions(executor.clone(), state.recorder()),
-							&runtime_code,
+							runtime_code,
 							CallContext::Offchain,
 						),
 						"dispatch a benchmark",
@@ -483,7 +556,7 @@ impl PalletCmd {
 				}
 				// Do one loop of DB tracking.
 				{
-					let state = &state_with_tracking;
+					let state = state_with_tracking;
 					let batch: Vec<BenchmarkBatch> = match Self::exec_state_machine::<
 						std::result::Result<Vec<BenchmarkBatch>, String>,
 						_,
@@ -492,11 +565,11 @@ impl PalletCmd {
 						StateMachine::new(
 							state, // todo remove tracking
 							&mut Default::default(),
-							&executor,
+							executor,
 							"Benchmark_dispatch_benchmark",
 							&params(false, self.repeat),
 							&mut Self::build_extensions(executor.clone(), state.recorder()),
-							&runtime_code,
+							runtime_code,
 							CallContext::Offchain,
 						),
 						"dispatch a benchmark",
@@ -518,7 +591,7 @@ impl PalletCmd {
 				}
 				// Finally run a bunch of loops to get extrinsic timing information.
 				for r in 0..self.external_repeat {
-					let state = &state_without_tracking;
+					let state = state_without_tracking;
 					let batch = match Self::exec_state_machine::<
 						std::result::Result<Vec<BenchmarkBatch>, String>,
 						_,
@@ -527,11 +600,11 @@ impl PalletCmd {
 						StateMachine::new(
 							state, // todo remove tracking
 							&mut Default::default(),
-							&executor,
+							executor,
 							"Benchmark_dispatch_benchmark",
 							&params(false, self.repeat),
 							&mut Self::build_extensions(executor.clone(), state.recorder()),
-							&runtime_code,
+							runtime_code,
 							CallContext::Offchain,
 						),
 						"dispatch a benchmark",
@@ -584,8 +657,7 @@ impl PalletCmd {
 
 		// Combine all of the benchmark results, so that benchmarks of the same pallet/function
 		// are together.
-		let batches = combine_batches(batches, batches_db);
-		self.output(&batches, &storage_info, &component_ranges, pov_modes)
+		Ok((combine_batches(batches, batches_db), component_ranges))
 	}
 
 	fn select_benchmarks_to_run(&self, list: Vec<BenchmarkList>) -> Result<Vec<SelectedBenchmark>> {
@@ -911,6 +983,60 @@ impl PalletCmd {
 		}
 	}
 
+	/// Prints how the base weights of the benchmarks changed between the genesis and the snapshot
+	/// state.
+	fn print_snapshot_delta(
+		&self,
+		genesis_batches: &[BenchmarkBatchSplitResults],
+		snapshot_batches: &[BenchmarkBatchSplitResults],
+		storage_info: &[StorageInfo],
+		component_ranges: &ComponentRangeMap,
+		pov_modes: PovModesMap,
+	) -> Result<()> {
+		let genesis = writer::base_weights(
+			genesis_batches,
+			storage_info,
+			component_ranges,
+			pov_modes.clone(),
+			self,
+		)?;
+		let snapshot = writer::base_weights(
+			snapshot_batches,
+			storage_info,
+			component_ranges,
+			pov_modes,
+			self,
+		)?;
+
+		let delta = |genesis: u128, snapshot: u128| {
+			format!("{genesis} -> {snapshot} ({:+})", snapshot as i128 - genesis as i128)
+		};
+
+		println!("Snapshot Delta\n========");
+		let mut keys = snapshot.keys().collect::<Vec<_>>();
+		keys.sort();
+		for key @ (pallet, instance, benchmark) in keys {
+			let Some(genesis) = genesis.get(key) else { continue };
+			let snapshot = &snapshot[key];
+
+			println!("Pallet: {pallet:?}, Instance: {instance:?}, Extrinsic: {benchmark:?}");
+			println!("Base Weight = {}", delta(genesis.weight, snapshot.weight));
+			println!("Base Reads = {}", delta(genesis.reads, snapshot.reads));
+			println!("Base Writes = {}", delta(genesis.writes, snapshot.writes));
+			println!(
+				"Base Recorded Proof Size = {}",
+				delta(genesis.recorded_proof_size, snapshot.recorded_proof_size)
+			);
+			println!(
+				"Base Calculated Proof Size = {}",
+				delta(genesis.calculated_proof_size, snapshot.calculated_proof_size)
+			);
+			println!();
+		}
+
+		Ok(())
+	}
+
 	/// Parses the PoV modes per benchmark that were specified by the `#[pov_mode]` attribute.
 	fn parse_pov_modes(benchmarks: &Vec<SelectedBenchmark>) -> Result<PovModesMap> {
 		use std::collections::hash_map::Entry;
@@ -1219,6 +1345,28 @@ mod tests {
 			"--genesis-builder-preset",
 			"preset",
 		]);
+
+		// Snapshot tests
+		cli_succeed(&[
+			"test",
+			"--extrinsic",
+			"",
+			"--pallet",
+			"",
+			"--runtime",
+			"path/to/runtime",
+			"--snapshot",
+			"path/to/snapshot",
+		])?;
+		cli_fail(&["test", "--json-input", "path/to/json", "--snapshot", "path/to/snapshot"]);
+		cli_fail(&[
+			"test",
+			"--runtime",
+			"path/to/runtime",
+			"--list",
+			"--snapshot",
+			"path/to/snapshot",
+		]);
 		Ok(())
 	}
 }
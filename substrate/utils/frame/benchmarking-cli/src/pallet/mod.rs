@@ -16,6 +16,7 @@
 // limitations under the License.
 
 mod command;
+mod snapshot;
 mod types;
 mod writer;
 
@@ -237,12 +238,30 @@ pub struct PalletCmd {
 	#[clap(long, default_value = "2")]
 	pub additional_trie_layers: u8,
 
+	/// Attribute the estimated proof size of every benchmark to the storage items it touches.
+	///
+	/// The estimated proof size of a benchmark is the one of its largest storage item. This adds
+	/// the estimate of every storage item to the comments of the output file, so that the items
+	/// dominating the proof size weight can be identified. Custom templates can use the
+	/// `storage_proof_size` of the benchmarks for the same purpose.
+	#[arg(long)]
+	pub pov_breakdown: bool,
+
 	/// A path to a `.json` file with existing benchmark results generated with `--json` or
 	/// `--json-file`. When specified the benchmarks are not actually executed, and the data for
 	/// the analysis is read from this file.
 	#[arg(long)]
 	pub json_input: Option<PathBuf>,
 
+	/// A path to a `remote-externalities` snapshot of a live chain to run the benchmarks against.
+	///
+	/// Benchmarking against the genesis state understates the proof size of accessing large
+	/// storage maps. With a snapshot the benchmarks are run against both states, the difference of
+	/// their base weights is printed, and the results of the snapshot state are the ones that are
+	/// written out. The runtime is the one of the genesis state, not the one of the snapshot.
+	#[arg(long, conflicts_with_all = ["json_input", "list"])]
+	pub snapshot: Option<PathBuf>,
+
 	/// Allow overwriting a single file with multiple results.
 	///
 	/// This exists only to restore legacy behaviour. It should never actually be needed.
@@ -0,0 +1,101 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loading the state of a live chain from a `remote-externalities` snapshot.
+
+use crate::overhead::OpaqueBlock;
+use remote_externalities::{Builder, Mode, OfflineConfig, SnapshotConfig};
+use sc_cli::Result;
+use sp_core::Hasher;
+use sp_state_machine::{Backend, IterArgs};
+use sp_storage::{
+	well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, ChildInfo, Storage, StorageChild,
+};
+use std::path::Path;
+
+/// Load the state of the snapshot at `path`.
+///
+/// The snapshot is created by `try-runtime create-snapshot` or any other user of
+/// `remote-externalities`.
+pub(crate) fn load_snapshot(path: &Path) -> Result<Storage> {
+	let mut ext = futures::executor::block_on(
+		Builder::<OpaqueBlock>::new()
+			.mode(Mode::Offline(OfflineConfig { state_snapshot: SnapshotConfig::new(path) }))
+			.build(),
+	)?;
+
+	storage_from_backend(&ext.as_backend())
+}
+
+/// Collect the top and child storage of `backend`.
+fn storage_from_backend<H: Hasher, B: Backend<H>>(backend: &B) -> Result<Storage> {
+	let pairs = |child_info: Option<ChildInfo>| -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+		backend
+			.pairs(IterArgs { child_info, ..Default::default() })
+			.map_err(|e| format!("Failed to iterate the snapshot: {e}"))?
+			.map(|pair| pair.map_err(|e| format!("Failed to read the snapshot: {e}").into()))
+			.collect()
+	};
+
+	let mut storage = Storage::default();
+	for (key, value) in pairs(None)? {
+		let Some(child_key) = key.strip_prefix(DEFAULT_CHILD_STORAGE_KEY_PREFIX) else {
+			storage.top.insert(key, value);
+			continue
+		};
+
+		let child_info = ChildInfo::new_default(child_key);
+		let data = pairs(Some(child_info.clone()))?.into_iter().collect();
+		storage
+			.children_default
+			.insert(child_key.to_vec(), StorageChild { data, child_info });
+	}
+
+	Ok(storage)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::BlakeTwo256;
+	use sp_state_machine::TestExternalities;
+
+	#[test]
+	fn storage_is_collected_from_backend() {
+		let child_info = ChildInfo::new_default(b"child");
+		let mut storage = Storage::default();
+		storage.top.insert(b"key".to_vec(), b"value".to_vec());
+		storage.children_default.insert(
+			b"child".to_vec(),
+			StorageChild {
+				data: [(b"child_key".to_vec(), b"child_value".to_vec())].into(),
+				child_info: child_info.clone(),
+			},
+		);
+
+		let mut ext = TestExternalities::<BlakeTwo256>::new(storage.clone());
+		let collected = storage_from_backend(&ext.as_backend()).unwrap();
+
+		assert_eq!(collected.top, storage.top);
+		assert_eq!(collected.children_default.len(), 1);
+		assert_eq!(
+			collected.children_default[&b"child"[..]].data,
+			storage.children_default[&b"child"[..]].data
+		);
+		assert_eq!(collected.children_default[&b"child"[..]].child_info, child_info);
+	}
+}
@@ -84,6 +84,7 @@ struct BenchmarkData {
 	comments: Vec<String>,
 	#[serde(serialize_with = "string_serialize")]
 	min_execution_time: u128,
+	storage_proof_size: Vec<StorageProofSize>,
 }
 
 // This forwards some specific metadata from the `PalletCmd`
@@ -99,6 +100,7 @@ struct CmdData {
 	analysis_choice: String,
 	worst_case_map_values: u32,
 	additional_trie_layers: u8,
+	pov_breakdown: bool,
 }
 
 // This encodes the component name and whether that component is used.
@@ -118,6 +120,15 @@ struct ComponentSlope {
 	error: u128,
 }
 
+// This encodes the estimated proof size of a benchmark attributed to a single storage item.
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+struct StorageProofSize {
+	name: String,
+	#[serde(serialize_with = "string_serialize")]
+	base: u128,
+	slopes: Vec<ComponentSlope>,
+}
+
 // Small helper to create an `io::Error` from a string.
 fn io_error(s: &str) -> std::io::Error {
 	use std::io::{Error, ErrorKind};
@@ -308,6 +319,7 @@ fn get_benchmark_data(
 				.into_iter()
 				.zip(proof_size.names.iter())
 				.zip(extract_errors(&proof_size.errors))
+				.filter(|((slope, _), _)| !slope.is_zero())
 				.map(|((slope, name), error)| ComponentSlope { name: name.clone(), slope, error })
 				.collect::<Vec<_>>();
 			(prefix.clone(), slope, proof_size.base)
@@ -341,6 +353,17 @@ fn get_benchmark_data(
 	}
 	used_calculated_proof_size.sort_by(|a, b| a.name.cmp(&b.name));
 
+	// The estimated proof size is the maximum over the storage items, so the breakdown shows which
+	// items dominate it. Largest items first.
+	let mut storage_proof_size = proof_size_per_components
+		.into_iter()
+		.map(|(prefix, mut slopes, base)| {
+			slopes.sort_by(|a, b| a.name.cmp(&b.name));
+			StorageProofSize { name: storage_item_name(&prefix, storage_info), base, slopes }
+		})
+		.collect::<Vec<_>>();
+	storage_proof_size.sort_by(|a, b| b.base.cmp(&a.base).then_with(|| a.name.cmp(&b.name)));
+
 	// This puts a marker on any component which is entirely unused in the weight formula.
 	let components = batch.time_results[0]
 		.components
@@ -373,6 +396,19 @@ fn get_benchmark_data(
 		component_ranges,
 		comments,
 		min_execution_time: extrinsic_time.minimum,
+		storage_proof_size,
+	}
+}
+
+// The name of the storage item with the given prefix, as displayed in the weight files.
+fn storage_item_name(prefix: &[u8], storage_info: &[StorageInfo]) -> String {
+	match storage_info.iter().find(|info| info.prefix == prefix) {
+		Some(info) => format!(
+			"`{}::{}`",
+			String::from_utf8_lossy(&info.pallet_name),
+			String::from_utf8_lossy(&info.storage_name)
+		),
+		None => format!("UNKNOWN KEY `0x{}`", HexDisplay::from(&prefix)),
 	}
 }
 
@@ -432,6 +468,7 @@ pub(crate) fn write_results(
 		analysis_choice: format!("{:?}", analysis_choice),
 		worst_case_map_values: cmd.worst_case_map_values,
 		additional_trie_layers: cmd.additional_trie_layers,
+		pov_breakdown: cmd.pov_breakdown,
 	};
 
 	// New Handlebars instance with helpers.
@@ -513,6 +550,61 @@ pub(crate) fn write_results(
 	Ok(())
 }
 
+/// The base weights of a benchmark, as they would be written to the output file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct BaseWeights {
+	pub(crate) weight: u128,
+	pub(crate) reads: u128,
+	pub(crate) writes: u128,
+	pub(crate) recorded_proof_size: u128,
+	pub(crate) calculated_proof_size: u128,
+}
+
+/// Analyze the base weights of all benchmarks in `batches`, keyed by pallet, instance and
+/// benchmark name.
+pub(crate) fn base_weights(
+	batches: &[BenchmarkBatchSplitResults],
+	storage_info: &[StorageInfo],
+	component_ranges: &ComponentRangeMap,
+	pov_modes: PovModesMap,
+	cmd: &PalletCmd,
+) -> Result<HashMap<(String, String, String), BaseWeights>, std::io::Error> {
+	let analysis_choice: AnalysisChoice =
+		cmd.output_analysis.clone().try_into().map_err(io_error)?;
+	let pov_analysis_choice: AnalysisChoice =
+		cmd.output_pov_analysis.clone().try_into().map_err(io_error)?;
+
+	let all_results = map_results(
+		batches,
+		storage_info,
+		component_ranges,
+		pov_modes,
+		cmd.default_pov_mode,
+		&analysis_choice,
+		&pov_analysis_choice,
+		cmd.worst_case_map_values,
+		cmd.additional_trie_layers,
+	)?;
+
+	Ok(all_results
+		.into_iter()
+		.flat_map(|((pallet, instance), benchmarks)| {
+			benchmarks.into_iter().map(move |benchmark| {
+				(
+					(pallet.clone(), instance.clone(), benchmark.name),
+					BaseWeights {
+						weight: benchmark.base_weight,
+						reads: benchmark.base_reads,
+						writes: benchmark.base_writes,
+						recorded_proof_size: benchmark.base_recorded_proof_size,
+						calculated_proof_size: benchmark.base_calculated_proof_size,
+					},
+				)
+			})
+		})
+		.collect())
+}
+
 /// This function looks at the keys touched during the benchmark, and the storage info we collected
 /// from the pallets, and creates comments with information about the storage keys touched during
 /// each benchmark.
@@ -1292,6 +1384,100 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn storage_proof_size_is_attributed_per_item() {
+		let mut results = Vec::new();
+		for i in 0..5 {
+			results.push(BenchmarkResult {
+				components: vec![(BenchmarkParameter::n, i)],
+				extrinsic_time: 0,
+				storage_root_time: 0,
+				reads: 1 + i,
+				repeat_reads: 0,
+				writes: 0,
+				repeat_writes: 0,
+				proof_size: 0,
+				keys: vec![
+					(b"value".to_vec(), 1, 0, false),
+					(b"map".to_vec(), i, 0, false),
+					(b"unknown".to_vec(), 1, 0, false),
+				],
+			})
+		}
+		let data = BenchmarkBatchSplitResults {
+			pallet: b"scheduler".to_vec(),
+			instance: b"instance".to_vec(),
+			benchmark: b"first_benchmark".to_vec(),
+			time_results: results.clone(),
+			db_results: results,
+		};
+		let storage_info = vec![
+			StorageInfo {
+				pallet_name: b"scheduler".to_vec(),
+				storage_name: b"Value".to_vec(),
+				prefix: b"value".to_vec(),
+				max_values: Some(1),
+				max_size: Some(1024),
+			},
+			StorageInfo {
+				pallet_name: b"scheduler".to_vec(),
+				storage_name: b"Map".to_vec(),
+				prefix: b"map".to_vec(),
+				max_values: Some(16),
+				max_size: Some(100),
+			},
+		];
+
+		let mapped_results = map_results(
+			&[data],
+			&storage_info,
+			&Default::default(),
+			Default::default(),
+			PovEstimationMode::MaxEncodedLen,
+			&AnalysisChoice::default(),
+			&AnalysisChoice::MedianSlopes,
+			1_000_000,
+			0,
+		)
+		.unwrap();
+		let result =
+			&mapped_results.get(&("scheduler".to_string(), "instance".to_string())).unwrap()[0];
+
+		assert_eq!(
+			result.storage_proof_size,
+			vec![
+				// The unknown key falls back to the measured proof size, which is empty, and to
+				// the overhead of a worst case map.
+				StorageProofSize {
+					name: "UNKNOWN KEY `0x756e6b6e6f776e`".into(),
+					base: 15 * 33 * 5,
+					slopes: vec![]
+				},
+				// A single layer of overhead for the value.
+				StorageProofSize {
+					name: "`scheduler::Value`".into(),
+					base: 1024 + 15 * 33,
+					slopes: vec![]
+				},
+				StorageProofSize {
+					name: "`scheduler::Map`".into(),
+					base: 0,
+					slopes: vec![ComponentSlope {
+						name: "n".into(),
+						slope: 100 + 15 * 33,
+						error: 0
+					}],
+				},
+			]
+		);
+		// The estimate is the maximum over the storage items.
+		assert_eq!(result.base_calculated_proof_size, 15 * 33 * 5);
+		assert_eq!(
+			result.component_calculated_proof_size,
+			vec![ComponentSlope { name: "n".into(), slope: 100 + 15 * 33, error: 0 }]
+		);
+	}
+
 	#[test]
 	fn template_works() {
 		let all_results = map_results(
@@ -1324,6 +1510,16 @@ mod test {
 			let output = handlebars.render_template(&TEMPLATE, &hbs_data);
 			assert!(output.is_ok());
 			println!("{:?}", output);
+			assert!(!output.unwrap().contains("per storage item"));
+
+			let hbs_data = TemplateData {
+				benchmarks: results.clone(),
+				cmd: CmdData { pov_breakdown: true, ..Default::default() },
+				..Default::default()
+			};
+			let output = handlebars.render_template(&TEMPLATE, &hbs_data).unwrap();
+			assert!(output.contains("// Estimated Proof Size per storage item in bytes:"));
+			assert!(output.contains("//  `bounded::bounded`: `"));
 		}
 	}
 
@@ -76,6 +76,22 @@ use sp_runtime::traits::BlakeTwo256;
 ///
 /// For the exact arguments of the `pallet` command, please refer to the `pallet` sub-module.
 ///
+/// ## Live-chain State
+///
+/// Benchmarks usually run against an almost empty genesis state, which understates the proof size
+/// of accessing deep storage maps. A `remote-externalities` snapshot of a live chain, for example
+/// created with `try-runtime create-snapshot`, can be passed to run the benchmarks against its
+/// state as well:
+///
+/// ```sh
+/// frame-omni-bencher v1 benchmark pallet \
+///     --runtime target/release/wbuild/westend-runtime/westend-runtime.compact.compressed.wasm \
+///     --pallet "pallet_balances" --extrinsic "" --snapshot westend.snap
+/// ```
+///
+/// The difference of the base weights per benchmark is printed at the end, and the results of the
+/// snapshot state are the ones written to `--output`.
+///
 /// ## Backwards Compatibility
 ///
 /// The exposed pallet sub-command is identical as the node-integrated CLI. The only difference is
@@ -0,0 +1,284 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Execution of temporary blocks, without importing them.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+	Extensions,
+};
+use sc_client_api::Backend;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::{twox_128, Bytes};
+use sp_runtime::{
+	legacy,
+	traits::{self, One},
+	Digest, TransactionOutcome,
+};
+use sp_weights::Weight;
+
+use crate::Error;
+
+/// The weight consumed by a block, per dispatch class.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsumedWeight {
+	pub normal: Weight,
+	pub operational: Weight,
+	pub mandatory: Weight,
+}
+
+/// The outcome of a block executed by [`DryRunBlockApiServer::dry_run_block`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunBlockResult {
+	/// The SCALE encoded `ApplyExtrinsicResult` of each extrinsic, in order.
+	pub results: Vec<Bytes>,
+	/// The SCALE encoded event records of the block.
+	///
+	/// The phase of an event record tells which extrinsic emitted it. The `ExtrinsicSuccess` and
+	/// `ExtrinsicFailed` events carry the weight consumed by each extrinsic.
+	pub events: Option<Bytes>,
+	/// The weight consumed by the block, including its initialization.
+	pub consumed_weight: Option<ConsumedWeight>,
+}
+
+/// Block dry run RPC methods.
+#[rpc(client, server)]
+pub trait DryRunBlockApi<BlockHash> {
+	/// Execute a temporary block with `extrinsics` on top of the block `at`, the best block if
+	/// `None`.
+	///
+	/// The block is initialized with the SCALE encoded `digest`, if any, and the extrinsics are
+	/// applied in order. Extrinsics that are invalid are left out of the block, like the block
+	/// builder does. The block is not finalized, so no inherents are required, and nothing is
+	/// imported.
+	#[method(name = "system_dryRunBlock", with_extensions)]
+	async fn dry_run_block(
+		&self,
+		extrinsics: Vec<Bytes>,
+		at: Option<BlockHash>,
+		digest: Option<Bytes>,
+	) -> RpcResult<DryRunBlockResult>;
+}
+
+/// An implementation of the block dry run RPC methods on full client.
+pub struct DryRunBlock<C, Block, BE> {
+	client: Arc<C>,
+	backend: Arc<BE>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block, BE> DryRunBlock<C, Block, BE> {
+	/// Create new `DryRunBlock` given client and backend.
+	pub fn new(client: Arc<C>, backend: Arc<BE>) -> Self {
+		Self { client, backend, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, BE> DryRunBlockApiServer<<Block as traits::Block>::Hash>
+	for DryRunBlock<C, Block, BE>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BlockBuilder<Block>,
+	BE: Backend<Block> + Send + Sync + 'static,
+	Block: traits::Block,
+{
+	async fn dry_run_block(
+		&self,
+		ext: &Extensions,
+		extrinsics: Vec<Bytes>,
+		at: Option<<Block as traits::Block>::Hash>,
+		digest: Option<Bytes>,
+	) -> RpcResult<DryRunBlockResult> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		fn runtime_error(error: impl ToString) -> ErrorObject<'static> {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to dry run block.",
+				Some(error.to_string()),
+			)
+		}
+		fn decode_error(error: impl ToString) -> ErrorObject<'static> {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to dry run block.",
+				Some(error.to_string()),
+			)
+		}
+
+		let parent_hash = at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash);
+		let parent_number = self
+			.client
+			.number(parent_hash)
+			.map_err(runtime_error)?
+			.ok_or_else(|| runtime_error(format!("Block `{:?}` not found.", parent_hash)))?;
+
+		let extrinsics = extrinsics
+			.iter()
+			.map(|extrinsic| {
+				<Block as traits::Block>::Extrinsic::decode(&mut &**extrinsic).map_err(decode_error)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let digest = digest
+			.map(|digest| Digest::decode(&mut &*digest).map_err(decode_error))
+			.transpose()?
+			.unwrap_or_default();
+
+		let header = <<Block as traits::Block>::Header as traits::Header>::new(
+			parent_number + One::one(),
+			Default::default(),
+			Default::default(),
+			parent_hash,
+			digest,
+		);
+
+		let mut api = self.client.runtime_api();
+		api.set_call_context(sp_core::traits::CallContext::Onchain);
+
+		let core_version = api
+			.api_version::<dyn Core<Block>>(parent_hash)
+			.map_err(runtime_error)?
+			.ok_or_else(|| runtime_error("Could not find `Core` api."))?;
+		if core_version >= 5 {
+			api.initialize_block(parent_hash, &header).map_err(runtime_error)?;
+		} else {
+			#[allow(deprecated)]
+			api.initialize_block_before_version_5(parent_hash, &header)
+				.map_err(runtime_error)?;
+		}
+
+		let bb_version = api
+			.api_version::<dyn BlockBuilder<Block>>(parent_hash)
+			.map_err(runtime_error)?
+			.ok_or_else(|| runtime_error("Could not find `BlockBuilder` api."))?;
+
+		let mut results = Vec::with_capacity(extrinsics.len());
+		for extrinsic in extrinsics {
+			let result = api.execute_in_transaction(|api| {
+				let result = if bb_version < 6 {
+					#[allow(deprecated)]
+					api.apply_extrinsic_before_version_6(parent_hash, extrinsic)
+						.map(legacy::byte_sized_error::convert_to_latest)
+				} else {
+					api.apply_extrinsic(parent_hash, extrinsic)
+				};
+
+				match result {
+					Ok(Ok(_)) => TransactionOutcome::Commit(result),
+					_ => TransactionOutcome::Rollback(result),
+				}
+			});
+			results.push(result.map_err(runtime_error)?.encode().into());
+		}
+
+		let state = self.backend.state_at(parent_hash).map_err(runtime_error)?;
+		let changes = api.into_storage_changes(&state, parent_hash).map_err(runtime_error)?;
+
+		let system_key = |name: &[u8]| [twox_128(b"System"), twox_128(name)].concat();
+		let (events_key, block_weight_key) = (system_key(b"Events"), system_key(b"BlockWeight"));
+
+		let mut result = DryRunBlockResult { results, events: None, consumed_weight: None };
+		for (key, value) in changes.main_storage_changes {
+			let Some(value) = value else { continue };
+
+			if key == events_key {
+				result.events = Some(value.into());
+			} else if key == block_weight_key {
+				let (normal, operational, mandatory) =
+					<(Weight, Weight, Weight)>::decode(&mut &value[..]).map_err(decode_error)?;
+				result.consumed_weight = Some(ConsumedWeight { normal, operational, mandatory });
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use sc_rpc_api::DenyUnsafe;
+	use sp_runtime::ApplyExtrinsicResult;
+	use substrate_test_runtime_client::{
+		runtime::Transfer, DefaultTestClientBuilderExt, Sr25519Keyring, TestClientBuilder,
+		TestClientBuilderExt,
+	};
+
+	#[tokio::test]
+	async fn dry_run_block_should_work() {
+		sp_tracing::try_init_simple();
+
+		let (client, backend) = TestClientBuilder::new().build_with_backend();
+		let client = Arc::new(client);
+		let dry_run = DryRunBlock::new(client.clone(), backend);
+
+		let transfer = |nonce| {
+			Transfer {
+				from: Sr25519Keyring::Alice.into(),
+				to: Sr25519Keyring::Bob.into(),
+				amount: 5,
+				nonce,
+			}
+			.into_unchecked_extrinsic()
+			.encode()
+			.into()
+		};
+
+		let mut deny_unsafe = Extensions::new();
+		deny_unsafe.insert(DenyUnsafe::Yes);
+		assert!(dry_run
+			.dry_run_block(&deny_unsafe, vec![transfer(0)], None, None)
+			.await
+			.is_err());
+
+		let mut allow_unsafe = Extensions::new();
+		allow_unsafe.insert(DenyUnsafe::No);
+		let result = dry_run
+			.dry_run_block(&allow_unsafe, vec![transfer(0), transfer(0), transfer(1)], None, None)
+			.await
+			.expect("Call is successful");
+
+		// the second transfer reuses the nonce of the first one, which was applied.
+		let results = result
+			.results
+			.iter()
+			.map(|result| ApplyExtrinsicResult::decode(&mut &result[..]).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(results[0], Ok(Ok(())));
+		assert!(results[1].is_err());
+		assert_eq!(results[2], Ok(Ok(())));
+		assert!(result.events.is_some());
+		assert!(result.consumed_weight.is_some());
+
+		// nothing was imported.
+		assert_eq!(client.info().best_number, 0);
+	}
+}
@@ -34,8 +34,13 @@ use sp_blockchain::HeaderBackend;
 use sp_core::{hexdisplay::HexDisplay, Bytes};
 use sp_runtime::{legacy, traits};
 
+pub use dry_run_block::{
+	ConsumedWeight, DryRunBlock, DryRunBlockApiClient, DryRunBlockApiServer, DryRunBlockResult,
+};
 pub use frame_system_rpc_runtime_api::AccountNonceApi;
 
+mod dry_run_block;
+
 /// System RPC methods.
 #[docify::export]
 #[rpc(client, server)]
@@ -160,6 +160,11 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				)),
 				network_provider: Arc::new(network.clone()),
 				enable_http_requests: true,
+				http_client_config: sc_offchain::HttpClientConfig {
+					proxy: config.offchain_worker.http_proxy.clone(),
+					ca_certificates: config.offchain_worker.http_ca_certificates.clone(),
+					max_request_timeout: config.offchain_worker.http_request_timeout,
+				},
 				custom_extensions: |_| vec![],
 			})?;
 		task_manager.spawn_handle().spawn(
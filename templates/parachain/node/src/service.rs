@@ -282,6 +282,7 @@ pub async fn start_parachain_node(
 			relay_chain_interface: relay_chain_interface.clone(),
 			import_queue: params.import_queue,
 			sybil_resistance_level: CollatorSybilResistance::Resistant, // because of Aura
+			block_relay: None,
 		})
 		.await?;
 
@@ -299,6 +300,7 @@ pub async fn start_parachain_node(
 				network_provider: Arc::new(network.clone()),
 				is_validator: parachain_config.role.is_authority(),
 				enable_http_requests: false,
+				http_client_config: Default::default(),
 				custom_extensions: move |_| vec![],
 			})?;
 		task_manager.spawn_handle().spawn(
@@ -7,6 +7,15 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// The number of GRANDPA rounds whose observed votes are kept in memory.
+	///
+	/// Defaults to `0`, i.e. no votes are recorded.
+	#[arg(long, value_name = "ROUNDS", default_value_t = 0)]
+	pub grandpa_vote_history: usize,
+
+	#[clap(flatten)]
+	pub signing_lease_params: sc_cli::SigningLeaseParams,
 }
 
 #[derive(Debug, clap::Subcommand)]
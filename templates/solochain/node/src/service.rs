@@ -3,12 +3,15 @@
 use futures::FutureExt;
 use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
-use sc_consensus_grandpa::SharedVoterState;
+use sc_consensus_grandpa::{SharedVoteHistory, SharedVoterState};
+use sc_consensus_slots::SlotInherentDataProviderRegistry;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncConfig};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use solochain_template_runtime::{self, apis::RuntimeApi, opaque::Block};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_inherents::{InherentData, InherentDataProviderRegistry};
+use sp_timestamp::TimestampInherentData;
 use std::{sync::Arc, time::Duration};
 
 pub(crate) type FullClient = sc_service::TFullClient<
@@ -36,6 +39,43 @@ pub type Service = sc_service::PartialComponents<
 	),
 >;
 
+/// The inherent data providers of the block import queue and of block authoring.
+///
+/// The slot is derived from the timestamp and the slot duration at the parent block.
+fn inherent_data_providers(
+	client: Arc<FullClient>,
+) -> Result<SlotInherentDataProviderRegistry<Block, ()>, ServiceError> {
+	let registry = InherentDataProviderRegistry::<Block, ()>::builder()
+		.register("timestamp", &[], |_, (), _| async {
+			Ok::<_, Box<dyn std::error::Error + Send + Sync>>(
+				sp_timestamp::InherentDataProvider::from_system_time(),
+			)
+		})
+		.register("slot", &["timestamp"], move |parent_hash, (), data: InherentData| {
+			let client = client.clone();
+			async move {
+				let slot_duration =
+					sc_consensus_aura::standalone::slot_duration_at(&*client, parent_hash)?;
+				let timestamp =
+					data.timestamp_inherent_data()?.ok_or("Timestamp inherent data is missing")?;
+
+				Ok::<_, Box<dyn std::error::Error + Send + Sync>>(
+					sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+						timestamp,
+						slot_duration,
+					),
+				)
+			}
+		})
+		.build()
+		.map_err(|e| ServiceError::Other(e.to_string()))?;
+
+	Ok(SlotInherentDataProviderRegistry::new(
+		registry,
+		sp_consensus_aura::inherents::INHERENT_IDENTIFIER,
+	))
+}
+
 pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 	let telemetry = config
 		.telemetry_endpoints
@@ -83,30 +123,12 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 
-	let cidp_client = client.clone();
 	let import_queue =
 		sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _>(ImportQueueParams {
 			block_import: grandpa_block_import.clone(),
 			justification_import: Some(Box::new(grandpa_block_import.clone())),
 			client: client.clone(),
-			create_inherent_data_providers: move |parent_hash, _| {
-				let cidp_client = cidp_client.clone();
-				async move {
-					let slot_duration = sc_consensus_aura::standalone::slot_duration_at(
-						&*cidp_client,
-						parent_hash,
-					)?;
-					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-
-					let slot =
-						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
-							*timestamp,
-							slot_duration,
-						);
-
-					Ok((slot, timestamp))
-				}
-			},
+			create_inherent_data_providers: inherent_data_providers(client.clone())?,
 			spawner: &task_manager.spawn_essential_handle(),
 			registry: config.prometheus_registry(),
 			check_for_equivocation: Default::default(),
@@ -131,6 +153,8 @@ pub fn new_full<
 	N: sc_network::NetworkBackend<Block, <Block as sp_runtime::traits::Block>::Hash>,
 >(
 	config: Configuration,
+	grandpa_vote_history: usize,
+	signing_lease: Option<sc_keystore::SigningLeaseConfig>,
 ) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
@@ -142,6 +166,17 @@ pub fn new_full<
 		transaction_pool,
 		other: (block_import, grandpa_link, mut telemetry),
 	} = new_partial(&config)?;
+	let keystore_container =
+		match signing_lease {
+			Some(signing_lease) => {
+				let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+				keystore_container.with_signing_lease(signing_lease.with_slot_key_type(
+					sp_core::crypto::key_types::AURA,
+					slot_duration.as_duration(),
+				))
+			},
+			None => keystore_container,
+		};
 
 	let mut net_config = sc_network::config::FullNetworkConfiguration::<
 		Block,
@@ -195,6 +230,11 @@ pub fn new_full<
 				)),
 				network_provider: Arc::new(network.clone()),
 				enable_http_requests: true,
+				http_client_config: sc_offchain::HttpClientConfig {
+					proxy: config.offchain_worker.http_proxy.clone(),
+					ca_certificates: config.offchain_worker.http_ca_certificates.clone(),
+					max_request_timeout: config.offchain_worker.http_request_timeout,
+				},
 				custom_extensions: |_| vec![],
 			})?;
 		task_manager.spawn_handle().spawn(
@@ -246,6 +286,7 @@ pub fn new_full<
 		);
 
 		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+		let create_inherent_data_providers = inherent_data_providers(client.clone())?;
 
 		let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
 			StartAuraParams {
@@ -254,17 +295,7 @@ pub fn new_full<
 				select_chain,
 				block_import,
 				proposer_factory,
-				create_inherent_data_providers: move |_, ()| async move {
-					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-
-					let slot =
-						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
-							*timestamp,
-							slot_duration,
-						);
-
-					Ok((slot, timestamp))
-				},
+				create_inherent_data_providers,
 				force_authoring,
 				backoff_authoring_blocks,
 				keystore: keystore_container.keystore(),
@@ -316,6 +347,7 @@ pub fn new_full<
 			voting_rule: sc_consensus_grandpa::VotingRulesBuilder::default().build(),
 			prometheus_registry,
 			shared_voter_state: SharedVoterState::empty(),
+			vote_history: SharedVoteHistory::new(grandpa_vote_history),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool),
 		};
@@ -357,6 +357,10 @@ pub use pallet_bags_list;
 #[cfg(feature = "pallet-balances")]
 pub use pallet_balances;
 
+/// RPC interface for the balances pallet.
+#[cfg(feature = "pallet-balances-rpc")]
+pub use pallet_balances_rpc;
+
 /// BEEFY FRAME pallet.
 #[cfg(feature = "pallet-beefy")]
 pub use pallet_beefy;
@@ -1093,6 +1097,10 @@ pub use sc_executor_wasmtime;
 #[cfg(feature = "sc-informant")]
 pub use sc_informant;
 
+/// Continuous try-state invariant checks of the finalized chain for substrate.
+#[cfg(feature = "sc-invariant-checker")]
+pub use sc_invariant_checker;
+
 /// Keystore (and session key management) for ed25519 based chains like Polkadot.
 #[cfg(feature = "sc-keystore")]
 pub use sc_keystore;